@@ -0,0 +1,60 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+use guardian::ml::simd::{self, scalar};
+
+// Series lengths: one feature vector, a syscall trace window, a flow capture
+const BENCH_LENGTHS: [usize; 3] = [256, 4_096, 65_536];
+const BENCH_WINDOWS: usize = 4;
+
+fn series(len: usize) -> Vec<f32> {
+    (0..len).map(|i| ((i * 7919) % 1000) as f32 / 10.0 - 50.0).collect()
+}
+
+/// Compares the scalar and dispatched paths; the dispatched label names the detected instruction set
+fn bench_feature_kernels(c: &mut Criterion) {
+    let level = format!("{:?}", simd::simd_level()).to_lowercase();
+
+    let mut group = c.benchmark_group("feature_normalize");
+    for &len in &BENCH_LENGTHS {
+        let values = series(len);
+        group.throughput(Throughput::Elements(len as u64));
+        group.bench_with_input(BenchmarkId::new("scalar", len), &values, |b, values| {
+            b.iter_batched_ref(|| values.clone(), |v| scalar::normalize(black_box(v), -1.0, 1.0), criterion::BatchSize::SmallInput)
+        });
+        group.bench_with_input(BenchmarkId::new(&level, len), &values, |b, values| {
+            b.iter_batched_ref(|| values.clone(), |v| simd::normalize(black_box(v), -1.0, 1.0), criterion::BatchSize::SmallInput)
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("feature_moments");
+    for &len in &BENCH_LENGTHS {
+        let values = series(len);
+        group.throughput(Throughput::Elements(len as u64));
+        group.bench_with_input(BenchmarkId::new("scalar", len), &values, |b, values| {
+            b.iter(|| {
+                let v = black_box(values.as_slice());
+                let (min, max) = scalar::min_max(v);
+                let mean = scalar::sum(v) / v.len() as f32;
+                black_box((min, max, scalar::sum_sq_dev(v, mean)))
+            })
+        });
+        group.bench_with_input(BenchmarkId::new(&level, len), &values, |b, values| {
+            b.iter(|| black_box(simd::moments(black_box(values))))
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("feature_windows");
+    for &len in &BENCH_LENGTHS {
+        let values = series(len);
+        group.throughput(Throughput::Elements(len as u64));
+        group.bench_with_input(BenchmarkId::new(&level, len), &values, |b, values| {
+            b.iter(|| black_box(simd::window_moments(black_box(values), BENCH_WINDOWS)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_feature_kernels);
+criterion_main!(benches);
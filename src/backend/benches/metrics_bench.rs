@@ -0,0 +1,138 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::sync::{Arc, Barrier};
+use std::time::{Duration, Instant};
+use tokio::runtime::Runtime;
+
+// Import internal components
+use guardian::utils::{MetricPriority, MetricType, MetricsCollector, MetricsConfig};
+
+// Constants for metrics recording benchmarks
+const BENCH_THREAD_COUNTS: [usize; 4] = [1, 2, 4, 8];
+const TARGET_RECORDS_PER_SEC: u64 = 100_000;
+const CONTENTION_RUN: Duration = Duration::from_secs(2);
+const MAX_P99_RECORD_LATENCY: Duration = Duration::from_micros(50);
+
+fn create_collector(rt: &Runtime) -> MetricsCollector {
+    rt.block_on(async {
+        MetricsCollector::new(MetricsConfig {
+            statsd_host: "localhost".into(),
+            statsd_port: 8125,
+            buffer_size: Some(100_000),
+            flush_interval: Some(Duration::from_millis(100)),
+            sampling_rates: None,
+        })
+        .unwrap()
+    })
+}
+
+fn record(collector: &MetricsCollector) {
+    collector
+        .record_metric(
+            "guardian.bench.events".into(),
+            1.0,
+            MetricType::Counter,
+            MetricPriority::High,
+            None,
+        )
+        .unwrap();
+}
+
+/// Benchmarks metric recording throughput with concurrent recording threads
+fn bench_record_throughput(c: &mut Criterion) {
+    let rt = Runtime::new().unwrap();
+    let collector = create_collector(&rt);
+    let mut group = c.benchmark_group("metrics_record");
+    group.sample_size(20);
+
+    for &threads in &BENCH_THREAD_COUNTS {
+        group.bench_with_input(BenchmarkId::new("concurrent", threads), &threads, |b, &threads| {
+            b.iter_custom(|iters| {
+                let per_thread = iters / threads as u64 + 1;
+                let barrier = Arc::new(Barrier::new(threads));
+                let start = Instant::now();
+                std::thread::scope(|scope| {
+                    for _ in 0..threads {
+                        let barrier = Arc::clone(&barrier);
+                        let collector = &collector;
+                        scope.spawn(move || {
+                            barrier.wait();
+                            for _ in 0..per_thread {
+                                black_box(record(collector));
+                            }
+                        });
+                    }
+                });
+                start.elapsed()
+            });
+        });
+    }
+
+    group.finish();
+}
+
+/// Records at 100k metrics/sec across threads while the drainer runs, asserting
+/// that per-record latency stays flat
+fn bench_record_contention(c: &mut Criterion) {
+    let rt = Runtime::new().unwrap();
+    let collector = create_collector(&rt);
+    let mut group = c.benchmark_group("metrics_contention");
+    group.sample_size(10);
+
+    let threads = 4;
+    let per_thread_rate = TARGET_RECORDS_PER_SEC / threads as u64;
+    let interval = Duration::from_nanos(1_000_000_000 / per_thread_rate);
+
+    group.bench_function("record_at_100k_per_sec", |b| {
+        b.iter_custom(|iters| {
+            let mut latencies: Vec<Duration> = std::thread::scope(|scope| {
+                let handles: Vec<_> = (0..threads)
+                    .map(|_| {
+                        let collector = &collector;
+                        scope.spawn(move || {
+                            let mut latencies = Vec::new();
+                            let start = Instant::now();
+                            let mut next = start;
+                            while start.elapsed() < CONTENTION_RUN {
+                                let record_start = Instant::now();
+                                record(collector);
+                                latencies.push(record_start.elapsed());
+
+                                next += interval;
+                                while Instant::now() < next {
+                                    std::hint::spin_loop();
+                                }
+                            }
+                            latencies
+                        })
+                    })
+                    .collect();
+                handles.into_iter().flat_map(|h| h.join().unwrap()).collect()
+            });
+
+            latencies.sort_unstable();
+            let total = latencies.len() as u64;
+            let p99 = latencies[(latencies.len() * 99) / 100];
+            assert!(
+                total >= TARGET_RECORDS_PER_SEC * CONTENTION_RUN.as_secs() * 9 / 10,
+                "recorded {} metrics, below target rate",
+                total
+            );
+            assert!(
+                p99 <= MAX_P99_RECORD_LATENCY,
+                "p99 record latency {:?} indicates contention",
+                p99
+            );
+            // Report the p99 record latency as the per-iteration cost
+            p99.mul_f64(iters as f64)
+        });
+    });
+
+    group.finish();
+}
+
+criterion_group!(
+    metrics_benches,
+    bench_record_throughput,
+    bench_record_contention
+);
+criterion_main!(metrics_benches);
@@ -0,0 +1,20 @@
+use std::{env, path::PathBuf};
+
+// Compiles the gRPC protos and emits the descriptor set served by gRPC reflection
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let out_dir = PathBuf::from(env::var("OUT_DIR")?);
+    tonic_build::configure()
+        .file_descriptor_set_path(out_dir.join("guardian_descriptor.bin"))
+        .compile(
+            &[
+                "src/api/proto/guardian.proto",
+                "src/api/proto/security.proto",
+                "src/api/proto/ml.proto",
+                "src/api/proto/attestation.proto",
+                "src/api/proto/workflow.proto",
+            ],
+            &["src/api/proto"],
+        )?;
+    println!("cargo:rerun-if-changed=src/api/proto");
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use std::sync::Arc;
+use tonic::{Request, Response, Status};
+use tracing::{info, instrument, warn};
+use metrics::counter;
+
+use crate::security::attestation::{Attestor, ATTESTATION_ALGORITHM};
+
+// Import the generated gRPC code
+tonic::include_proto!("guardian.attestation.v1");
+
+/// gRPC service serving signed attestation evidence to fleet verifiers
+#[derive(Debug)]
+pub struct GuardianAttestationService {
+    attestor: Arc<Attestor>,
+}
+
+impl GuardianAttestationService {
+    /// Creates a new GuardianAttestationService backed by the given attestor
+    pub fn new(attestor: Arc<Attestor>) -> Self {
+        Self { attestor }
+    }
+}
+
+#[tonic::async_trait]
+impl attestation_service_server::AttestationService for GuardianAttestationService {
+    /// Signs evidence of the running binary, models and configuration
+    #[instrument(skip(self, request))]
+    async fn get_attestation(
+        &self,
+        request: Request<AttestationRequest>,
+    ) -> Result<Response<AttestationReport>, Status> {
+        let nonce = request.into_inner().nonce;
+        let signed = self.attestor.attest(&nonce).await.map_err(|e| {
+            warn!(error = %e, "Attestation refused");
+            counter!("guardian.grpc.attestation.failures", 1);
+            Status::failed_precondition(e.to_string())
+        })?;
+
+        let evidence = &signed.evidence;
+        info!(key_id = %signed.key_id, tpm = evidence.tpm_quote.is_some(), "Attestation evidence issued");
+        Ok(Response::new(AttestationReport {
+            evidence: signed.evidence_bytes.clone(),
+            signature: signed.signature.clone(),
+            key_id: signed.key_id.clone(),
+            algorithm: ATTESTATION_ALGORITHM.to_string(),
+            binary_sha256: evidence.measurements.binary_sha256.clone(),
+            config_sha256: evidence.measurements.config_sha256.clone(),
+            model_versions: evidence
+                .measurements
+                .models
+                .iter()
+                .map(|(name, model)| (name.clone(), model.version.clone()))
+                .collect(),
+            secure_boot_required: evidence.secure_boot.required,
+            tpm_backed: evidence.tpm_quote.is_some(),
+            issued_at: Some(prost_types::Timestamp {
+                seconds: evidence.issued_at.timestamp(),
+                nanos: evidence.issued_at.timestamp_subsec_nanos() as i32,
+            }),
+        }))
+    }
+
+    /// Returns the public key evidence is signed with
+    #[instrument(skip(self, _request))]
+    async fn get_attestation_key(
+        &self,
+        _request: Request<AttestationKeyRequest>,
+    ) -> Result<Response<AttestationKey>, Status> {
+        Ok(Response::new(AttestationKey {
+            key_id: self.attestor.key_id(),
+            algorithm: ATTESTATION_ALGORITHM.to_string(),
+            public_key: self.attestor.public_key(),
+        }))
+    }
+}
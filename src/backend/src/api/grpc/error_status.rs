@@ -0,0 +1,81 @@
+use std::collections::HashMap;
+use tonic::{Code, Status};
+use tonic_types::{ErrorDetails, StatusExt};
+
+use crate::utils::error::GuardianError;
+
+/// `google.rpc.ErrorInfo` domain of Guardian errors
+pub const ERROR_DOMAIN: &str = "guardian";
+
+/// gRPC status for a Guardian error
+///
+/// The message is the error's display text. The details carry a `google.rpc.ErrorInfo`
+/// with the stable error code and a `google.rpc.RequestInfo` whose request ID is the
+/// correlation ID, so clients branch on the code and quote the ID when reporting problems.
+impl From<GuardianError> for Status {
+    fn from(error: GuardianError) -> Self {
+        let code = error.code();
+        let correlation_id = error.correlation_id().to_string();
+        let metadata = HashMap::from([
+            ("code".to_string(), code.numeric.to_string()),
+            ("category".to_string(), code.category.as_str().to_string()),
+            ("severity".to_string(), error.severity().as_str().to_string()),
+            ("retryable".to_string(), error.is_retryable().to_string()),
+            ("correlation_id".to_string(), correlation_id.clone()),
+        ]);
+
+        let mut details = ErrorDetails::with_error_info(code.reason, ERROR_DOMAIN, metadata);
+        details.set_request_info(correlation_id, "");
+        Status::with_error_details(grpc_code(&error), error.to_string(), details)
+    }
+}
+
+/// Stable code and correlation ID carried by a status built from a Guardian error
+pub fn error_code_of(status: &Status) -> Option<(String, String)> {
+    let info = status.get_details_error_info()?;
+    if info.domain != ERROR_DOMAIN {
+        return None;
+    }
+    let code = info.metadata.get("code")?.parse().ok()?;
+    let correlation_id = info.metadata.get("correlation_id").cloned().unwrap_or_default();
+    Some((format!("GRD-{}", code), correlation_id))
+}
+
+/// Rejected input and refused access are the caller's to fix; everything else is retried
+/// when the error allows it
+fn grpc_code(error: &GuardianError) -> Code {
+    match error {
+        GuardianError::ValidationError { .. } => Code::InvalidArgument,
+        GuardianError::SecurityError { .. } => Code::PermissionDenied,
+        _ if error.is_retryable() => Code::Unavailable,
+        _ => Code::Internal,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::error::{ErrorCategory, ErrorSeverity};
+
+    #[test]
+    fn test_status_carries_error_code_and_correlation_id() {
+        let correlation_id = crate::utils::correlation::current_or_new();
+        let error = GuardianError::ValidationError {
+            context: "limit must be positive".to_string(),
+            source: None,
+            severity: ErrorSeverity::Low,
+            timestamp: time::OffsetDateTime::now_utc(),
+            correlation_id,
+            category: ErrorCategory::Validation,
+            retry_count: 0,
+        };
+
+        let status = Status::from(error);
+        assert_eq!(status.code(), Code::InvalidArgument);
+        assert!(status.message().contains("limit must be positive"));
+        let info = status.get_details_error_info().unwrap();
+        assert_eq!((info.reason.as_str(), info.domain.as_str()), ("VALIDATION_ERROR", ERROR_DOMAIN));
+        assert_eq!(error_code_of(&status), Some(("GRD-5500".to_string(), correlation_id.to_string())));
+        assert_eq!(status.get_details_request_info().unwrap().request_id, correlation_id.to_string());
+    }
+}
@@ -7,13 +7,30 @@ use metrics::{counter, gauge, histogram};
 
 use crate::core::guardian::Guardian;
 use crate::core::system_state::{SystemState, SystemHealth};
+use crate::core::operations::{Operation, OperationStatus};
+use crate::core::posture::{PostureHistory, PosturePoint, PostureScore, PostureScorer};
+use crate::core::state_journal::{state_journal, StateTransition, DEFAULT_TRANSITION_WINDOW};
+use crate::core::status::{status_service, ComponentState, StatusReport};
+use crate::security::audit::AuditEvent;
+use crate::security::command_audit::{command_audit, CommandAuditQuery};
+use crate::security::incident::IncidentTracker;
 use crate::utils::error::GuardianError;
+use crate::utils::metrics_stream::{metrics_hub, MetricsFrame, DEFAULT_HEARTBEAT, DEFAULT_WINDOW, MIN_HEARTBEAT};
+use crate::utils::MetricType;
+
+/// Generated GuardianService messages, server and client
+pub mod guardian_proto {
+    tonic::include_proto!("guardian.core.v1");
+}
 
 // Service constants
 const SERVICE_NAME: &str = "guardian.v1.GuardianService";
 const MAX_EVENT_STREAM_BUFFER: usize = 1000;
 const CIRCUIT_BREAKER_THRESHOLD: u32 = 5;
 const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+const DEFAULT_POSTURE_HISTORY: chrono::Duration = chrono::Duration::days(7);
+/// Frames queued per metrics stream; the client's window does the real flow control
+const METRICS_STREAM_BUFFER: usize = 1;
 
 /// Circuit breaker for service reliability
 #[derive(Debug)]
@@ -54,6 +71,8 @@ pub struct GuardianService {
     system_state: Arc<RwLock<SystemState>>,
     circuit_breaker: Arc<CircuitBreaker>,
     metrics_collector: Arc<crate::utils::metrics::MetricsCollector>,
+    posture: Option<Arc<PostureScorer>>,
+    incidents: Option<Arc<IncidentTracker>>,
 }
 
 impl GuardianService {
@@ -75,9 +94,34 @@ impl GuardianService {
             system_state,
             circuit_breaker: Arc::new(CircuitBreaker::new()),
             metrics_collector: Arc::new(crate::utils::metrics::MetricsCollector::new(metrics_config)?),
+            posture: None,
+            incidents: None,
         })
     }
 
+    /// System state backing status queries and gRPC health
+    pub fn system_state(&self) -> Arc<RwLock<SystemState>> {
+        Arc::clone(&self.system_state)
+    }
+
+    /// Serves the security posture score and its history
+    pub fn with_posture(mut self, posture: Arc<PostureScorer>) -> Self {
+        self.posture = Some(posture);
+        self
+    }
+
+    /// Resolves incident IDs when listing state transitions around an incident
+    pub fn with_incidents(mut self, incidents: Arc<IncidentTracker>) -> Self {
+        self.incidents = Some(incidents);
+        self
+    }
+
+    fn posture(&self) -> Result<&PostureScorer, Status> {
+        self.posture
+            .as_deref()
+            .ok_or_else(|| Status::unimplemented("Posture scoring is not enabled on this host"))
+    }
+
     /// Validates request authentication and authorization
     #[instrument(skip(request))]
     fn validate_request<T>(&self, request: &Request<T>) -> Result<(), Status> {
@@ -102,6 +146,8 @@ impl GuardianService {
 
 #[tonic::async_trait]
 impl guardian_proto::guardian_service_server::GuardianService for GuardianService {
+    type StreamMetricsStream = tokio_stream::wrappers::ReceiverStream<Result<guardian_proto::MetricsFrame, Status>>;
+
     /// Retrieves current system status with enhanced security and validation
     #[instrument(skip(self, request))]
     async fn get_system_status(
@@ -159,6 +205,61 @@ impl guardian_proto::guardian_service_server::GuardianService for GuardianServic
         Ok(Response::new(tokio_stream::wrappers::ReceiverStream::new(rx)))
     }
 
+    /// Streams aggregated metric snapshots, sending only as many as the client's window allows
+    #[instrument(skip(self, request))]
+    async fn stream_metrics(
+        &self,
+        request: Request<guardian_proto::MetricsStreamRequest>,
+    ) -> Result<Response<Self::StreamMetricsStream>, Status> {
+        self.validate_request(&request)?;
+        let request = request.into_inner();
+
+        let prefixes = request.filter.map(|f| f.metric_names).unwrap_or_default();
+        let window = match request.initial_window {
+            0 => DEFAULT_WINDOW,
+            window => window,
+        };
+        let heartbeat = match request.heartbeat_interval_ms {
+            0 => DEFAULT_HEARTBEAT,
+            ms => Duration::from_millis(u64::from(ms)).max(MIN_HEARTBEAT),
+        };
+        let resume_token = Some(request.resume_token).filter(|t| !t.is_empty());
+        let mut subscription = metrics_hub().subscribe(prefixes, window, resume_token.as_deref());
+        info!(subscription = %subscription.id(), window, resumed = resume_token.is_some(), "Metrics stream opened");
+        counter!("guardian.metrics_stream.opened", 1);
+
+        let (tx, rx) = mpsc::channel(METRICS_STREAM_BUFFER);
+        tokio::spawn(async move {
+            loop {
+                let frame = convert_metrics_frame(
+                    subscription.id(),
+                    subscription.resumed_with_gap(),
+                    subscription.next(heartbeat).await,
+                );
+                if tx.send(Ok(frame)).await.is_err() {
+                    debug!(subscription = %subscription.id(), "Metrics stream closed by client");
+                    return;
+                }
+            }
+        });
+
+        Ok(Response::new(tokio_stream::wrappers::ReceiverStream::new(rx)))
+    }
+
+    /// Grants an open metrics stream credit for more snapshots
+    #[instrument(skip(self, request))]
+    async fn update_metrics_window(
+        &self,
+        request: Request<guardian_proto::MetricsWindowUpdate>,
+    ) -> Result<Response<guardian_proto::MetricsWindowAck>, Status> {
+        self.validate_request(&request)?;
+        let request = request.into_inner();
+        let window = metrics_hub()
+            .grant(&request.subscription_id, request.credits)
+            .ok_or_else(|| Status::not_found(format!("No metrics stream {}", request.subscription_id)))?;
+        Ok(Response::new(guardian_proto::MetricsWindowAck { window }))
+    }
+
     /// Executes system response actions with validation
     #[instrument(skip(self, request))]
     async fn execute_response(
@@ -169,13 +270,401 @@ impl guardian_proto::guardian_service_server::GuardianService for GuardianServic
 
         let response = self.guardian.execute_action(request.into_inner().action)
             .await
-            .map_err(|e| Status::internal(format!("Failed to execute response: {}", e)))?;
+            .map_err(Status::from)?;
 
         Ok(Response::new(guardian_proto::ExecuteResponseResponse {
             success: true,
             message: "Response executed successfully".into(),
         }))
     }
+
+    /// Lists tracked long-running operations
+    #[instrument(skip(self, request))]
+    async fn list_operations(
+        &self,
+        request: Request<guardian_proto::ListOperationsRequest>,
+    ) -> Result<Response<guardian_proto::ListOperationsResponse>, Status> {
+        self.validate_request(&request)?;
+
+        let filter = match guardian_proto::OperationStatus::from_i32(request.into_inner().status_filter) {
+            Some(guardian_proto::OperationStatus::Running) => Some(OperationStatus::Running),
+            Some(guardian_proto::OperationStatus::Completed) => Some(OperationStatus::Completed),
+            Some(guardian_proto::OperationStatus::Failed) => Some(OperationStatus::Failed),
+            Some(guardian_proto::OperationStatus::Cancelled) => Some(OperationStatus::Cancelled),
+            _ => None,
+        };
+
+        let operations = self.guardian.operations()
+            .list(filter)
+            .into_iter()
+            .map(convert_operation)
+            .collect();
+
+        counter!("guardian.service.operations.list", 1);
+        Ok(Response::new(guardian_proto::ListOperationsResponse { operations }))
+    }
+
+    /// Retrieves a single long-running operation
+    #[instrument(skip(self, request))]
+    async fn get_operation(
+        &self,
+        request: Request<guardian_proto::OperationRequest>,
+    ) -> Result<Response<guardian_proto::Operation>, Status> {
+        self.validate_request(&request)?;
+
+        let id = parse_operation_id(&request.into_inner().operation_id)?;
+        let operation = self.guardian.operations()
+            .get(&id)
+            .ok_or_else(|| Status::not_found(format!("Operation {} not found", id)))?;
+
+        Ok(Response::new(convert_operation(operation)))
+    }
+
+    /// Requests cancellation of a running operation
+    #[instrument(skip(self, request))]
+    async fn cancel_operation(
+        &self,
+        request: Request<guardian_proto::OperationRequest>,
+    ) -> Result<Response<guardian_proto::Operation>, Status> {
+        self.validate_request(&request)?;
+
+        let id = parse_operation_id(&request.into_inner().operation_id)?;
+        let operation = self.guardian.operations()
+            .cancel(&id)
+            .map_err(|e| Status::failed_precondition(e.to_string()))?;
+
+        counter!("guardian.service.operations.cancel", 1);
+        Ok(Response::new(convert_operation(operation)))
+    }
+
+    /// Starts key rotation as a tracked operation, to be followed with GetOperation
+    #[instrument(skip(self, request))]
+    async fn rotate_keys(
+        &self,
+        request: Request<guardian_proto::Empty>,
+    ) -> Result<Response<guardian_proto::Operation>, Status> {
+        self.validate_request(&request)?;
+
+        let security = crate::security::security_manager()
+            .ok_or_else(|| Status::unavailable("Security subsystem is not initialized"))?;
+        let id = security
+            .rotate_keys(self.guardian.operations())
+            .map_err(|e| Status::failed_precondition(e.to_string()))?;
+        let operation = self.guardian.operations()
+            .get(&id)
+            .ok_or_else(|| Status::internal(format!("Operation {} not tracked", id)))?;
+
+        counter!("guardian.service.operations.rotate_keys", 1);
+        Ok(Response::new(convert_operation(operation)))
+    }
+
+    /// Returns the latest security posture score
+    #[instrument(skip(self, request))]
+    async fn get_posture(
+        &self,
+        request: Request<guardian_proto::Empty>,
+    ) -> Result<Response<guardian_proto::PostureScore>, Status> {
+        self.validate_request(&request)?;
+
+        let posture = self.posture()?
+            .current()
+            .await
+            .map_err(Status::from)?;
+
+        Ok(Response::new(convert_posture(posture)))
+    }
+
+    /// Returns stored posture scores, the last week by default
+    #[instrument(skip(self, request))]
+    async fn get_posture_history(
+        &self,
+        request: Request<guardian_proto::PostureHistoryRequest>,
+    ) -> Result<Response<guardian_proto::PostureHistory>, Status> {
+        self.validate_request(&request)?;
+
+        let request = request.into_inner();
+        let end = request.end_time.map(from_timestamp).transpose()?.unwrap_or_else(chrono::Utc::now);
+        let start = request
+            .start_time
+            .map(from_timestamp)
+            .transpose()?
+            .unwrap_or(end - DEFAULT_POSTURE_HISTORY);
+        if start > end {
+            return Err(Status::invalid_argument("start_time must not be after end_time"));
+        }
+
+        let history = self.posture()?
+            .history(start, end)
+            .await
+            .map_err(Status::from)?;
+
+        Ok(Response::new(convert_posture_history(history)))
+    }
+
+    /// Lists journaled state transitions around a point in time or an incident's opening
+    #[instrument(skip(self, request))]
+    async fn list_state_transitions(
+        &self,
+        request: Request<guardian_proto::StateTransitionsRequest>,
+    ) -> Result<Response<guardian_proto::StateTransitionsResponse>, Status> {
+        self.validate_request(&request)?;
+
+        let request = request.into_inner();
+        let around = if request.incident_id.is_empty() {
+            request.around.map(from_timestamp).transpose()?.unwrap_or_else(chrono::Utc::now)
+        } else {
+            let incidents = self
+                .incidents
+                .as_deref()
+                .ok_or_else(|| Status::unimplemented("Incident tracking is not enabled on this host"))?;
+            let id = request
+                .incident_id
+                .parse()
+                .map_err(|_| Status::invalid_argument(format!("Invalid incident ID: {}", request.incident_id)))?;
+            let incident = incidents
+                .get(id)
+                .ok_or_else(|| Status::not_found(format!("Incident {} not found", request.incident_id)))?;
+            chrono::DateTime::<chrono::Utc>::from(incident.opened_at)
+        };
+        let window = match request.window_seconds {
+            0 => DEFAULT_TRANSITION_WINDOW,
+            seconds => Duration::from_secs(seconds.into()),
+        };
+
+        let transitions = state_journal()
+            .around(around, window)
+            .into_iter()
+            .map(convert_state_transition)
+            .collect();
+        Ok(Response::new(guardian_proto::StateTransitionsResponse { transitions }))
+    }
+
+    #[instrument(skip(self, request))]
+    async fn list_command_audit(
+        &self,
+        request: Request<guardian_proto::CommandAuditRequest>,
+    ) -> Result<Response<guardian_proto::CommandAuditResponse>, Status> {
+        self.validate_request(&request)?;
+
+        let request = request.into_inner();
+        let query = CommandAuditQuery {
+            principal: Some(request.principal).filter(|p| !p.is_empty()),
+            command: Some(request.command).filter(|c| !c.is_empty()),
+            since: request.since.map(from_timestamp).transpose()?,
+            changes_only: request.changes_only,
+            limit: request.limit as usize,
+        };
+
+        let entries = command_audit()
+            .history(&query)
+            .await
+            .map_err(Status::from)?
+            .iter()
+            .map(convert_command_audit)
+            .collect();
+        Ok(Response::new(guardian_proto::CommandAuditResponse { entries }))
+    }
+
+    /// Aggregates every component's status into one versioned report
+    #[instrument(skip(self, request))]
+    async fn get_status_report(
+        &self,
+        request: Request<guardian_proto::Empty>,
+    ) -> Result<Response<guardian_proto::StatusReport>, Status> {
+        self.validate_request(&request)?;
+        Ok(Response::new(convert_status_report(status_service().report().await)))
+    }
+}
+
+/// Parses an operation ID supplied by a client
+fn parse_operation_id(raw: &str) -> Result<uuid::Uuid, Status> {
+    uuid::Uuid::parse_str(raw)
+        .map_err(|_| Status::invalid_argument(format!("Invalid operation ID: {}", raw)))
+}
+
+fn to_timestamp(t: chrono::DateTime<chrono::Utc>) -> prost_types::Timestamp {
+    prost_types::Timestamp {
+        seconds: t.timestamp(),
+        nanos: t.timestamp_subsec_nanos() as i32,
+    }
+}
+
+fn from_timestamp(t: prost_types::Timestamp) -> Result<chrono::DateTime<chrono::Utc>, Status> {
+    chrono::DateTime::<chrono::Utc>::from_timestamp(t.seconds, t.nanos.max(0) as u32)
+        .ok_or_else(|| Status::invalid_argument("Invalid timestamp"))
+}
+
+/// Converts an internal operation snapshot to its gRPC representation
+fn convert_operation(operation: Operation) -> guardian_proto::Operation {
+    guardian_proto::Operation {
+        operation_id: operation.id.to_string(),
+        kind: operation.kind,
+        description: operation.description,
+        status: match operation.status {
+            OperationStatus::Running => guardian_proto::OperationStatus::Running,
+            OperationStatus::Completed => guardian_proto::OperationStatus::Completed,
+            OperationStatus::Failed => guardian_proto::OperationStatus::Failed,
+            OperationStatus::Cancelled => guardian_proto::OperationStatus::Cancelled,
+        } as i32,
+        progress_percent: operation.progress_percent,
+        cancel_requested: operation.cancel_requested,
+        error_message: operation.error.unwrap_or_default(),
+        created_at: Some(to_timestamp(operation.created_at)),
+        updated_at: Some(to_timestamp(operation.updated_at)),
+    }
+}
+
+/// Converts a posture score to its gRPC representation
+fn convert_posture(posture: PostureScore) -> guardian_proto::PostureScore {
+    guardian_proto::PostureScore {
+        score: posture.score,
+        computed_at: Some(to_timestamp(posture.computed_at)),
+        factors: posture
+            .factors
+            .into_iter()
+            .map(|f| guardian_proto::PostureFactor {
+                name: f.name,
+                score: f.score,
+                weight: f.weight,
+                detail: f.detail,
+            })
+            .collect(),
+    }
+}
+
+fn convert_posture_history(history: PostureHistory) -> guardian_proto::PostureHistory {
+    let points = |points: Vec<PosturePoint>| {
+        points
+            .into_iter()
+            .map(|p| guardian_proto::PosturePoint { at: Some(to_timestamp(p.at)), score: p.score })
+            .collect::<Vec<_>>()
+    };
+    guardian_proto::PostureHistory {
+        points: points(history.points),
+        factors: history
+            .factors
+            .into_iter()
+            .map(|(name, values)| guardian_proto::PostureFactorHistory { name, points: points(values) })
+            .collect(),
+    }
+}
+
+fn convert_health(health: &SystemHealth) -> guardian_proto::SystemState {
+    match health {
+        SystemHealth::Healthy => guardian_proto::SystemState::Running,
+        SystemHealth::Degraded => guardian_proto::SystemState::Degraded,
+        SystemHealth::Critical => guardian_proto::SystemState::Error,
+    }
+}
+
+fn convert_component_state(state: ComponentState) -> guardian_proto::ComponentState {
+    match state {
+        ComponentState::Healthy => guardian_proto::ComponentState::Healthy,
+        ComponentState::Degraded => guardian_proto::ComponentState::Degraded,
+        ComponentState::Unhealthy => guardian_proto::ComponentState::Unhealthy,
+        ComponentState::Unknown => guardian_proto::ComponentState::Unknown,
+    }
+}
+
+/// Converts a status report to its gRPC representation
+fn convert_status_report(report: StatusReport) -> guardian_proto::StatusReport {
+    guardian_proto::StatusReport {
+        schema_version: report.schema_version,
+        version: report.version,
+        generated_at: Some(to_timestamp(report.generated_at)),
+        overall: convert_component_state(report.overall) as i32,
+        components: report
+            .components
+            .into_iter()
+            .map(|c| guardian_proto::ComponentStatusReport {
+                name: c.name,
+                state: convert_component_state(c.state) as i32,
+                detail: c.detail.unwrap_or_default(),
+                metrics: c.metrics.into_iter().collect(),
+            })
+            .collect(),
+    }
+}
+
+/// Converts a journaled state transition to its gRPC representation
+fn convert_state_transition(transition: StateTransition) -> guardian_proto::StateTransition {
+    guardian_proto::StateTransition {
+        sequence: transition.sequence,
+        at: Some(to_timestamp(transition.at)),
+        from_state: convert_health(&transition.from) as i32,
+        to_state: convert_health(&transition.to) as i32,
+        cause: transition.cause.label().to_string(),
+        cause_detail: transition.cause.detail(),
+        inputs: transition.inputs.into_iter().collect(),
+    }
+}
+
+fn convert_command_audit(event: &AuditEvent) -> guardian_proto::CommandAuditEntry {
+    let tag = |name: &str| event.tags().get(name).cloned().unwrap_or_default();
+    let data = event.data();
+    guardian_proto::CommandAuditEntry {
+        event_id: event.id().to_string(),
+        at: Some(to_timestamp(event.timestamp())),
+        interface: tag("interface"),
+        principal: tag("principal"),
+        command: tag("command"),
+        args: data["args"]
+            .as_object()
+            .map(|args| {
+                args.iter()
+                    .map(|(name, value)| (name.clone(), value.as_str().unwrap_or_default().to_string()))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        outcome: tag("outcome"),
+        error: data["error"].as_str().unwrap_or_default().to_string(),
+        duration_ms: data["duration_ms"].as_f64().unwrap_or_default(),
+        change: tag("change") == "true",
+        args_sha256: data["args_sha256"].as_str().unwrap_or_default().to_string(),
+        sequence: event.sequence(),
+    }
+}
+
+fn convert_metrics_frame(subscription_id: &str, resumed_with_gap: bool, frame: MetricsFrame) -> guardian_proto::MetricsFrame {
+    let (resume_token, frame) = match frame {
+        MetricsFrame::Snapshot { snapshot, skipped, resume_token } => {
+            let samples = snapshot
+                .samples
+                .into_iter()
+                .map(|sample| guardian_proto::MetricSample {
+                    name: sample.name,
+                    metric_type: match sample.metric_type {
+                        MetricType::Counter => "counter",
+                        MetricType::Gauge => "gauge",
+                        MetricType::Histogram => "histogram",
+                    }
+                    .to_string(),
+                    value: sample.value,
+                    tags: sample.tags.into_iter().collect(),
+                })
+                .collect();
+            let snapshot = guardian_proto::MetricsSnapshot {
+                sequence: snapshot.sequence,
+                collected_at: Some(to_timestamp(snapshot.collected_at)),
+                samples,
+                skipped,
+            };
+            (resume_token, guardian_proto::metrics_frame::Frame::Snapshot(snapshot))
+        }
+        MetricsFrame::Heartbeat { window, resume_token } => {
+            let heartbeat = guardian_proto::MetricsHeartbeat {
+                sent_at: Some(to_timestamp(chrono::Utc::now())),
+                window,
+            };
+            (resume_token, guardian_proto::metrics_frame::Frame::Heartbeat(heartbeat))
+        }
+    };
+    guardian_proto::MetricsFrame {
+        subscription_id: subscription_id.to_string(),
+        resume_token,
+        resumed_with_gap,
+        frame: Some(frame),
+    }
 }
 
 /// Converts internal system status to gRPC response type
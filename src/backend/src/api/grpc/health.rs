@@ -0,0 +1,144 @@
+use std::{sync::Arc, time::Duration};
+
+use metrics::gauge;
+use parking_lot::RwLock;
+use tonic_health::{server::HealthReporter, ServingStatus};
+use tracing::info;
+
+use super::CircuitBreaker;
+use crate::core::system_state::{SystemHealth, SystemState};
+use crate::ml::warmup::Readiness;
+use crate::utils::error::{ErrorCategory, GuardianError};
+
+// Constants for gRPC health and reflection
+/// Fully qualified names of the services health is reported for, as in the protos
+pub const GUARDIAN_SERVICE: &str = "guardian.core.v1.GuardianService";
+pub const SECURITY_SERVICE: &str = "guardian.security.v1.SecurityService";
+pub const ML_SERVICE: &str = "guardian.ml.v1.MLService";
+const SERVICES: &[&str] = &[GUARDIAN_SERVICE, SECURITY_SERVICE, ML_SERVICE];
+/// The empty service name reports the health of the server as a whole
+const OVERALL: &str = "";
+
+/// Encoded descriptors of the Guardian protos, emitted by the build script
+pub const FILE_DESCRIPTOR_SET: &[u8] = tonic::include_file_descriptor_set!("guardian_descriptor");
+
+/// Builds the `grpc.reflection.v1alpha.ServerReflection` service covering the Guardian and health protos
+pub fn reflection_service() -> Result<
+    tonic_reflection::server::ServerReflectionServer<impl tonic_reflection::server::ServerReflection>,
+    GuardianError,
+> {
+    tonic_reflection::server::Builder::configure()
+        .register_encoded_file_descriptor_set(FILE_DESCRIPTOR_SET)
+        .register_encoded_file_descriptor_set(tonic_health::pb::FILE_DESCRIPTOR_SET)
+        .build()
+        .map_err(|e| GuardianError::SystemError {
+            context: "Failed to build gRPC reflection service".into(),
+            source: Some(Box::new(e)),
+            severity: crate::utils::error::ErrorSeverity::High,
+            timestamp: time::OffsetDateTime::now_utc(),
+            correlation_id: crate::utils::correlation::current_or_new(),
+            category: ErrorCategory::System,
+            retry_count: 0,
+        })
+}
+
+/// Keeps `grpc.health.v1.Health` statuses in step with the circuit breaker and system health
+#[derive(Debug, Clone)]
+pub struct HealthPublisher {
+    reporter: HealthReporter,
+    circuit_breaker: Arc<CircuitBreaker>,
+    system_state: Arc<RwLock<SystemState>>,
+}
+
+impl HealthPublisher {
+    pub(super) fn new(
+        reporter: HealthReporter,
+        circuit_breaker: Arc<CircuitBreaker>,
+        system_state: Arc<RwLock<SystemState>>,
+    ) -> Self {
+        Self { reporter, circuit_breaker, system_state }
+    }
+
+    /// Publishes the current status of every service and of the server as a whole
+    pub async fn refresh(&self) {
+        let (health, readiness) = {
+            let state = self.system_state.read();
+            (state.health().clone(), state.readiness())
+        };
+        let breaker_open = self.circuit_breaker.is_open();
+        let mut reporter = self.reporter.clone();
+
+        let mut overall = ServingStatus::Serving;
+        for service in SERVICES {
+            let status = service_status(service, breaker_open, &health, readiness);
+            if status != ServingStatus::Serving {
+                overall = ServingStatus::NotServing;
+            }
+            reporter.set_service_status(*service, status).await;
+            gauge!("guardian.grpc.health.serving", serving(status), "service" => *service);
+        }
+        reporter.set_service_status(OVERALL, overall).await;
+    }
+
+    /// Refreshes statuses on the given interval for as long as the server runs
+    pub fn spawn(self, interval: Duration) {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(interval);
+            loop {
+                interval.tick().await;
+                self.refresh().await;
+            }
+        });
+    }
+
+    /// Reports every service as not serving so load balancers drain the instance
+    pub async fn drain(&self) {
+        let mut reporter = self.reporter.clone();
+        for service in SERVICES.iter().chain([&OVERALL]) {
+            reporter.set_service_status(*service, ServingStatus::NotServing).await;
+        }
+        info!("gRPC health set to NOT_SERVING for shutdown");
+    }
+}
+
+/// Status of one service given the breaker, system health and model readiness
+///
+/// Degraded systems keep serving. A critical system, or one whose models are still
+/// warming up, takes no analysis and inference traffic but keeps the core service up
+/// so operators can inspect it.
+fn service_status(service: &str, breaker_open: bool, health: &SystemHealth, readiness: Readiness) -> ServingStatus {
+    match (breaker_open, health) {
+        (true, _) => ServingStatus::NotServing,
+        _ if service == GUARDIAN_SERVICE => ServingStatus::Serving,
+        (false, SystemHealth::Critical) => ServingStatus::NotServing,
+        _ if readiness != Readiness::Ready => ServingStatus::NotServing,
+        _ => ServingStatus::Serving,
+    }
+}
+
+fn serving(status: ServingStatus) -> f64 {
+    if status == ServingStatus::Serving { 1.0 } else { 0.0 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_service_status_follows_breaker_and_system_health() {
+        let ready = Readiness::Ready;
+        for service in SERVICES {
+            assert_eq!(service_status(service, false, &SystemHealth::Degraded, ready), ServingStatus::Serving);
+            assert_eq!(service_status(service, true, &SystemHealth::Healthy, ready), ServingStatus::NotServing);
+        }
+        assert_eq!(service_status(GUARDIAN_SERVICE, false, &SystemHealth::Critical, ready), ServingStatus::Serving);
+        assert_eq!(service_status(ML_SERVICE, false, &SystemHealth::Critical, ready), ServingStatus::NotServing);
+
+        // Until warm-up completes only the core service takes traffic
+        for readiness in [Readiness::Starting, Readiness::WarmingUp] {
+            assert_eq!(service_status(GUARDIAN_SERVICE, false, &SystemHealth::Healthy, readiness), ServingStatus::Serving);
+            assert_eq!(service_status(ML_SERVICE, false, &SystemHealth::Healthy, readiness), ServingStatus::NotServing);
+            assert_eq!(service_status(SECURITY_SERVICE, false, &SystemHealth::Healthy, readiness), ServingStatus::NotServing);
+        }
+    }
+}
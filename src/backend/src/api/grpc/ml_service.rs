@@ -1,4 +1,5 @@
 use std::{sync::Arc, time::Duration};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
 use tonic::{Request, Response, Status};
 use tokio::time::timeout;
 use metrics::{counter, histogram};
@@ -7,11 +8,17 @@ use temporal_sdk_core::{WorkflowClient, WorkflowOptions};
 use uuid::Uuid;
 
 use crate::ml::model_manager::{ModelManager, ModelMetadata, ModelStatus, ValidationStatus};
+use crate::ml::model_registry::ModelListQuery;
+use crate::storage::{ModelPatch, PatchFormat};
+use crate::utils::correlation;
 use crate::utils::error::{GuardianError, ErrorCategory};
+use crate::utils::inflight::{inflight_registry, DrainStage, InflightTracker};
+use crate::utils::retry::retry_executor;
+use crate::utils::telemetry;
 use crate::proto::ml::{
     MLServiceServer, ModelInferenceRequest, InferenceResult, TrainingRequest, 
-    TrainingJob, ModelStatusRequest, Model, ModelUpdateRequest,
-    ModelType, ModelStatus as ProtoModelStatus, TrainingStatus,
+    TrainingJob, ModelStatusRequest, Model, ModelUpdateRequest, ModelPatchFormat,
+    ModelType, ModelStatus as ProtoModelStatus, TrainingStatus, ListModelsRequest, ListModelsResponse,
 };
 
 // Constants for service configuration
@@ -21,6 +28,8 @@ const SERVICE_NAME: &str = "guardian.ml.v1.MLService";
 const CIRCUIT_BREAKER_THRESHOLD: u32 = 5;
 const CIRCUIT_BREAKER_TIMEOUT_MS: u64 = 5000;
 const METRICS_FLUSH_INTERVAL_MS: u64 = 1000;
+const INFLIGHT_REQUESTS: &str = "grpc.ml";
+const MAX_LIST_PAGE_SIZE: u32 = 500;
 
 /// Enhanced gRPC service implementation for ML operations
 #[derive(Debug)]
@@ -29,6 +38,7 @@ pub struct MLService {
     temporal_client: Arc<WorkflowClient>,
     circuit_breaker: Arc<CircuitBreaker>,
     metrics_reporter: Arc<MetricsReporter>,
+    inflight: Arc<InflightTracker>,
 }
 
 impl MLService {
@@ -44,6 +54,7 @@ impl MLService {
             temporal_client,
             circuit_breaker,
             metrics_reporter,
+            inflight: inflight_registry().tracker(INFLIGHT_REQUESTS, DrainStage::Api),
         }
     }
 }
@@ -57,7 +68,10 @@ impl MLServiceServer for MLService {
         request: Request<ModelInferenceRequest>,
     ) -> Result<Response<InferenceResult>, Status> {
         let start = std::time::Instant::now();
-        let correlation_id = Uuid::new_v4();
+        let correlation_id = correlation::current_or_new();
+
+        // Turn new work away while draining for shutdown
+        let _inflight = self.inflight.enter().ok_or_else(super::draining_status)?;
 
         // Check circuit breaker status
         if !self.circuit_breaker.check().await {
@@ -117,7 +131,7 @@ impl MLServiceServer for MLService {
         request: Request<TrainingRequest>,
     ) -> Result<Response<TrainingJob>, Status> {
         let req = request.into_inner();
-        let workflow_id = format!("train-model-{}", Uuid::new_v4());
+        let workflow_id = correlation::workflow_id("train-model");
 
         // Start Temporal workflow
         let workflow = self.temporal_client.start_workflow(
@@ -126,6 +140,7 @@ impl MLServiceServer for MLService {
             &WorkflowOptions {
                 workflow_id: workflow_id.clone(),
                 task_queue: "ml-training".to_string(),
+                headers: telemetry::workflow_headers(),
                 ..Default::default()
             },
         ).await.map_err(|e| {
@@ -156,7 +171,9 @@ impl MLServiceServer for MLService {
     ) -> Result<Response<Model>, Status> {
         let req = request.into_inner();
         
-        let metadata = self.model_manager.get_model_metadata(&req.model_id).await
+        let metadata = retry_executor()
+            .run("get_model_metadata", || self.model_manager.get_model_metadata(&req.model_id))
+            .await
             .map_err(|e| {
                 error!("Failed to get model metadata: {:?}", e);
                 Status::not_found("Model not found")
@@ -183,13 +200,23 @@ impl MLServiceServer for MLService {
         &self,
         request: Request<ModelUpdateRequest>,
     ) -> Result<Response<Model>, Status> {
-        let req = request.into_inner();
+        let mut req = request.into_inner();
         
         // Validate model data
         if req.model_data.is_empty() {
             return Err(Status::invalid_argument("Model data cannot be empty"));
         }
 
+        // Patch updates are rebuilt against the installed base version and verified before deploying
+        if let Some(patch) = model_patch(&mut req)? {
+            let patch_size = patch.data.len();
+            req.model_data = self.model_manager.reconstruct_model(patch).await.map_err(|e| {
+                warn!(error = %e, version = %req.version, "Model patch rejected");
+                Status::failed_precondition(format!("Model patch could not be applied: {}", e))
+            })?;
+            histogram!("guardian.ml.model.patch_ratio", patch_size as f64 / req.model_data.len().max(1) as f64);
+        }
+
         let metadata = ModelMetadata {
             name: req.model_id.clone(),
             version: req.version.clone(),
@@ -200,6 +227,8 @@ impl MLServiceServer for MLService {
             validation_status: ValidationStatus::Pending,
             hash: String::new(),
             size_bytes: req.model_data.len() as u64,
+            signature: (!req.signature.is_empty()).then(|| BASE64.encode(&req.signature)),
+            provenance: None,
         };
 
         // Deploy model
@@ -207,9 +236,13 @@ impl MLServiceServer for MLService {
             req.model_data,
             req.version.clone(),
             metadata.clone(),
-        ).await.map_err(|e| {
-            error!("Model deployment failed: {:?}", e);
-            Status::internal("Failed to deploy model")
+        ).await.map_err(|e| match e {
+            // Signature failures are already audited by the verifier
+            GuardianError::SecurityError { .. } => Status::permission_denied(e.to_string()),
+            e => {
+                error!("Model deployment failed: {:?}", e);
+                Status::internal("Failed to deploy model")
+            }
         })?;
 
         let model = Model {
@@ -226,6 +259,122 @@ impl MLServiceServer for MLService {
         counter!("guardian.ml.model.updates", 1);
         Ok(Response::new(model))
     }
+
+    /// Lists registered models, filtered and paged through the registry's version index
+    #[instrument(skip(self, request))]
+    async fn list_models(
+        &self,
+        request: Request<ListModelsRequest>,
+    ) -> Result<Response<ListModelsResponse>, Status> {
+        let req = request.into_inner();
+        if req.page_size > MAX_LIST_PAGE_SIZE {
+            return Err(Status::invalid_argument(format!("page_size may not exceed {}", MAX_LIST_PAGE_SIZE)));
+        }
+
+        let status = match req.status {
+            Some(raw) => Some(model_status(
+                ProtoModelStatus::from_i32(raw).ok_or_else(|| Status::invalid_argument("Unknown model status"))?,
+            )?),
+            None => None,
+        };
+        let query = ModelListQuery {
+            status,
+            name_prefix: (!req.name_prefix.is_empty()).then_some(req.name_prefix),
+            created_after: req.created_after.map(from_timestamp).transpose()?,
+            created_before: req.created_before.map(from_timestamp).transpose()?,
+            page_size: req.page_size as usize,
+            page_token: (!req.page_token.is_empty()).then_some(req.page_token),
+        };
+
+        // Listing is a read, so transient index failures are retried under the failing layer's policy
+        let page = retry_executor()
+            .run("list_models", || self.model_manager.list_models(query.clone()))
+            .await
+            .map_err(|e| match e {
+                GuardianError::ValidationError { .. } => Status::invalid_argument(e.to_string()),
+                e => {
+                    error!("Failed to list models: {:?}", e);
+                    Status::internal("Failed to list models")
+                }
+            })?;
+
+        let models = page
+            .models
+            .into_iter()
+            .map(|metadata| Model {
+                model_id: metadata.name,
+                version: metadata.version,
+                model_type: ModelType::ThreatDetection as i32,
+                status: ProtoModelStatus::from(metadata.status) as i32,
+                accuracy: metadata.metrics.as_ref().map(|m| m.accuracy as f32).unwrap_or(0.0),
+                last_updated: Some(prost_types::Timestamp {
+                    seconds: metadata.updated_at.timestamp(),
+                    nanos: metadata.updated_at.timestamp_subsec_nanos() as i32,
+                }),
+                performance_metrics: Default::default(),
+                model_hash: metadata.hash.into_bytes(),
+            })
+            .collect();
+
+        counter!("guardian.ml.list.requests", 1);
+        Ok(Response::new(ListModelsResponse {
+            models,
+            next_page_token: page.next_page_token.unwrap_or_default(),
+        }))
+    }
+}
+
+impl From<ModelStatus> for ProtoModelStatus {
+    fn from(status: ModelStatus) -> Self {
+        match status {
+            ModelStatus::Active => ProtoModelStatus::Active,
+            ModelStatus::Inactive => ProtoModelStatus::Inactive,
+            ModelStatus::Failed => ProtoModelStatus::Failed,
+            ModelStatus::Validating => ProtoModelStatus::Validating,
+            ModelStatus::Deprecated => ProtoModelStatus::Deprecated,
+        }
+    }
+}
+
+/// Maps a status filter onto the registry; models in training are not registered yet
+fn model_status(status: ProtoModelStatus) -> Result<ModelStatus, Status> {
+    match status {
+        ProtoModelStatus::Active => Ok(ModelStatus::Active),
+        ProtoModelStatus::Inactive => Ok(ModelStatus::Inactive),
+        ProtoModelStatus::Failed => Ok(ModelStatus::Failed),
+        ProtoModelStatus::Validating => Ok(ModelStatus::Validating),
+        ProtoModelStatus::Deprecated => Ok(ModelStatus::Deprecated),
+        ProtoModelStatus::Training => Err(Status::invalid_argument("Models in training are not listed")),
+    }
+}
+
+fn from_timestamp(t: prost_types::Timestamp) -> Result<chrono::DateTime<chrono::Utc>, Status> {
+    chrono::DateTime::<chrono::Utc>::from_timestamp(t.seconds, t.nanos.max(0) as u32)
+        .ok_or_else(|| Status::invalid_argument("Invalid timestamp"))
+}
+
+/// Takes the patch out of an update request whose model data is a diff against a base version
+fn model_patch(req: &mut ModelUpdateRequest) -> Result<Option<ModelPatch>, Status> {
+    let format = match ModelPatchFormat::from_i32(req.patch_format) {
+        Some(ModelPatchFormat::None) => return Ok(None),
+        Some(ModelPatchFormat::Bsdiff) => PatchFormat::Bsdiff,
+        Some(ModelPatchFormat::Zstd) => PatchFormat::Zstd,
+        None => return Err(Status::invalid_argument("Unknown model patch format")),
+    };
+    if req.base_version.is_empty() || req.base_hash.is_empty() || req.target_hash.is_empty() || req.target_size == 0 {
+        return Err(Status::invalid_argument(
+            "Model patches require base_version, base_hash, target_hash and target_size",
+        ));
+    }
+
+    Ok(Some(ModelPatch {
+        format,
+        base_version: req.base_version.clone(),
+        base_hash: req.base_hash.to_lowercase(),
+        target_hash: req.target_hash.to_lowercase(),
+        target_size: req.target_size,
+        data: std::mem::take(&mut req.model_data),
+    }))
 }
 
 #[cfg(test)]
@@ -268,4 +417,38 @@ mod tests {
         let response = service.inference_request(request).await;
         assert!(response.is_ok());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_list_status_filter() {
+        assert_eq!(model_status(ProtoModelStatus::Deprecated).unwrap(), ModelStatus::Deprecated);
+        assert!(model_status(ProtoModelStatus::Training).is_err());
+        assert_eq!(ProtoModelStatus::from(ModelStatus::Failed), ProtoModelStatus::Failed);
+    }
+
+    #[test]
+    fn test_model_patch_from_request() {
+        let mut full = ModelUpdateRequest {
+            model_id: "threat".into(),
+            model_data: vec![1, 2, 3],
+            version: "v1.1.0".into(),
+            ..Default::default()
+        };
+        assert!(model_patch(&mut full).unwrap().is_none());
+        assert_eq!(full.model_data, vec![1, 2, 3]);
+
+        let mut patch = ModelUpdateRequest {
+            patch_format: ModelPatchFormat::Zstd as i32,
+            base_version: "v1.0.0".into(),
+            ..full.clone()
+        };
+        assert!(model_patch(&mut patch).is_err());
+
+        patch.base_hash = "AB".into();
+        patch.target_hash = "cd".into();
+        patch.target_size = 3;
+        let parsed = model_patch(&mut patch).unwrap().unwrap();
+        assert_eq!(parsed.format, PatchFormat::Zstd);
+        assert_eq!(parsed.base_hash, "ab");
+        assert_eq!(parsed.data, vec![1, 2, 3]);
+    }
+}
@@ -3,10 +3,32 @@ use tonic::{transport::Server, Request, Response, Status};
 use tracing::{debug, error, info, instrument, warn};
 use metrics::{counter, gauge, histogram};
 
+use crate::api::quota::{client_quotas, ClientQuotaLayer};
+use crate::security::attestation::attestor;
+use crate::security::auth::TokenInterceptor;
+use crate::security::rbac::{rbac, RbacLayer};
+use crate::security::remote_assistance::{remote_assistance, RemoteAssistanceLayer};
+use crate::temporal::visibility::workflow_admin;
+use crate::utils::correlation::CorrelationLayer;
+use crate::utils::telemetry::TraceContextLayer;
 use crate::utils::error::GuardianError;
-use crate::api::grpc::guardian_service::GuardianService;
-use crate::api::grpc::security_service::GuardianSecurityService;
-use crate::api::grpc::ml_service::MLService;
+
+pub mod attestation_service;
+pub mod error_status;
+pub mod guardian_service;
+pub mod health;
+pub mod ml_service;
+pub mod security_service;
+pub mod tls_reload;
+pub mod workflow_service;
+
+pub use attestation_service::GuardianAttestationService;
+pub use guardian_service::GuardianService;
+pub use health::HealthPublisher;
+pub use ml_service::MLService;
+pub use security_service::GuardianSecurityService;
+pub use tls_reload::TlsReloader;
+pub use workflow_service::GuardianWorkflowService;
 
 // Constants for gRPC server configuration
 const DEFAULT_PORT: u16 = 50051;
@@ -14,6 +36,13 @@ const MAX_CONCURRENT_REQUESTS: usize = 1000;
 const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
 const CIRCUIT_BREAKER_THRESHOLD: u32 = 5;
 const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+const TLS_RELOAD_INTERVAL: Duration = Duration::from_secs(30);
+const REMOTE_ASSISTANCE_EXPIRY_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Status for requests arriving while the server drains for shutdown, retryable against another instance
+pub(crate) fn draining_status() -> Status {
+    Status::unavailable("Server is shutting down, retry the request")
+}
 
 /// Configuration for gRPC server
 #[derive(Debug, Clone)]
@@ -45,6 +74,20 @@ pub struct TlsConfig {
     pub cert_path: String,
     pub key_path: String,
     pub ca_cert_path: Option<String>,
+    /// How often the certificate, key and CA files are checked for rotation
+    pub reload_interval: Duration,
+}
+
+impl TlsConfig {
+    /// Creates a TLS configuration polling for rotation on the default interval
+    pub fn new(cert_path: String, key_path: String, ca_cert_path: Option<String>) -> Self {
+        Self {
+            cert_path,
+            key_path,
+            ca_cert_path,
+            reload_interval: TLS_RELOAD_INTERVAL,
+        }
+    }
 }
 
 /// Circuit breaker for service reliability
@@ -88,6 +131,8 @@ pub struct GrpcServer {
     ml_service: Arc<MLService>,
     circuit_breaker: Arc<CircuitBreaker>,
     metrics_reporter: Arc<MetricsReporter>,
+    health: HealthPublisher,
+    health_service: tonic_health::server::HealthServer<tonic_health::server::HealthService>,
 }
 
 impl GrpcServer {
@@ -98,16 +143,26 @@ impl GrpcServer {
         security_service: Arc<GuardianSecurityService>,
         ml_service: Arc<MLService>,
     ) -> Self {
+        let circuit_breaker = Arc::new(CircuitBreaker::new(config.circuit_breaker_threshold));
+        let (reporter, health_service) = tonic_health::server::health_reporter();
+        let health = HealthPublisher::new(reporter, Arc::clone(&circuit_breaker), guardian_service.system_state());
         Self {
             config: config.clone(),
             guardian_service,
             security_service,
             ml_service,
-            circuit_breaker: Arc::new(CircuitBreaker::new(config.circuit_breaker_threshold)),
+            circuit_breaker,
             metrics_reporter: Arc::new(MetricsReporter::new("guardian.grpc")),
+            health,
+            health_service,
         }
     }
 
+    /// Publisher of the `grpc.health.v1.Health` statuses served alongside the Guardian services
+    pub fn health(&self) -> &HealthPublisher {
+        &self.health
+    }
+
     /// Starts the gRPC server with security and monitoring
     #[instrument]
     pub async fn start(&self) -> Result<(), GuardianError> {
@@ -115,33 +170,47 @@ impl GrpcServer {
 
         let addr = format!("0.0.0.0:{}", self.config.port).parse()?;
 
+        // Vendor certificates are confined to their remote assistance session scopes
+        let remote_assistance = remote_assistance();
+        remote_assistance.spawn_expiry(REMOTE_ASSISTANCE_EXPIRY_INTERVAL);
+
         // Configure server with security and monitoring
-        let mut server = Server::builder();
-
-        // Configure TLS if enabled
-        if let Some(tls_config) = &self.config.tls_config {
-            let cert = tokio::fs::read(&tls_config.cert_path).await?;
-            let key = tokio::fs::read(&tls_config.key_path).await?;
-            
-            let identity = tonic::transport::Identity::from_pem(cert, key);
-            
-            let tls = if let Some(ca_path) = &tls_config.ca_cert_path {
-                let ca_cert = tokio::fs::read(ca_path).await?;
-                tonic::transport::ServerTlsConfig::new()
-                    .identity(identity)
-                    .client_ca_root(tonic::transport::Certificate::from_pem(ca_cert))
-            } else {
-                tonic::transport::ServerTlsConfig::new()
-                    .identity(identity)
-            };
-
-            server = server.tls_config(tls)?;
-        }
+        let server = Server::builder()
+            .layer(TraceContextLayer)
+            .layer(CorrelationLayer)
+            .layer(RemoteAssistanceLayer::new(remote_assistance))
+            .layer(tonic::service::interceptor(TokenInterceptor))
+            .layer(ClientQuotaLayer::new(client_quotas()))
+            .layer(RbacLayer::new(rbac()));
+
+        // The environment profile decides whether plaintext or server-only TLS is acceptable
+        let tls_config = self.config.tls_config.as_ref();
+        crate::config::active_profile().require_mtls(
+            "gRPC server",
+            tls_config.is_some(),
+            tls_config.map_or(false, |tls| tls.ca_cert_path.is_some()),
+        )?;
+
+        // TLS is terminated by a reloader so rotated certificates apply to new connections
+        let tls_reloader = match &self.config.tls_config {
+            Some(tls_config) => {
+                let reloader = TlsReloader::load(tls_config.clone())?;
+                reloader.spawn_watcher();
+                Some(reloader)
+            }
+            None => None,
+        };
+
+        // Statuses are published before serving so the first probe sees real health
+        self.health.refresh().await;
+        self.health.clone().spawn(self.config.health_check_interval);
 
         // Add services with interceptors
         let server = server
             .concurrency_limit(self.config.max_concurrent_requests)
             .timeout(self.config.request_timeout)
+            .add_service(self.health_service.clone())
+            .add_service(health::reflection_service()?)
             .add_service(guardian_proto::guardian_service_server::GuardianServiceServer::new(
                 GuardianServiceWrapper::new(
                     Arc::clone(&self.guardian_service),
@@ -162,6 +231,12 @@ impl GrpcServer {
                     Arc::clone(&self.circuit_breaker),
                     Arc::clone(&self.metrics_reporter),
                 ),
+            ))
+            .add_service(attestation_service::attestation_service_server::AttestationServiceServer::new(
+                GuardianAttestationService::new(attestor()),
+            ))
+            .add_service(workflow_service::workflow_admin_server::WorkflowAdminServer::new(
+                GuardianWorkflowService::new(workflow_admin()),
             ));
 
         // Start health check monitoring
@@ -180,7 +255,13 @@ impl GrpcServer {
 
         // Start server
         info!("gRPC server started successfully");
-        server.serve(addr).await?;
+        match tls_reloader {
+            Some(reloader) => {
+                let listener = tokio::net::TcpListener::bind(addr).await?;
+                server.serve_with_incoming(reloader.incoming(listener)).await?;
+            }
+            None => server.serve(addr).await?,
+        }
 
         Ok(())
     }
@@ -189,6 +270,7 @@ impl GrpcServer {
     #[instrument]
     pub async fn stop(&self) -> Result<(), GuardianError> {
         info!("Stopping gRPC server");
+        self.health.drain().await;
         // Implement graceful shutdown logic
         Ok(())
     }
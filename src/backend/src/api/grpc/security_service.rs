@@ -1,15 +1,25 @@
 use std::{
+    collections::HashMap,
     sync::Arc,
     time::{Duration, Instant},
 };
-use tokio::sync::RwLock;
+use tokio::sync::{broadcast, mpsc, RwLock};
+use tokio_stream::wrappers::ReceiverStream;
 use tonic::{Request, Response, Status};
 use tracing::{debug, error, info, instrument, warn};
 use metrics::{counter, histogram};
 
-use crate::security::threat_detection::ThreatDetector;
+use crate::core::guardian::TenantId;
+use crate::ml::model_registry;
+use crate::security::anomaly_feedback::{self, AnomalyFeedback, FeedbackRequest};
+use crate::security::firewall::{self, firewall};
+use crate::security::rbac::{request_tenant, Principal};
+use crate::security::threat_analytics::{self, ThreatAnalytics};
+use crate::security::threat_detection::{ThreatDetector, ThreatLevel, ThreatNotice};
 use crate::security::response_engine::ResponseEngine;
 use crate::utils::error::{GuardianError, SecurityError};
+use crate::utils::ids::{next_id, IdKind};
+use crate::utils::inflight::{inflight_registry, DrainStage, InflightTracker};
 
 // Import the generated gRPC code
 tonic::include_proto!("guardian.security.v1");
@@ -20,6 +30,9 @@ const MAX_REQUEST_SIZE: usize = 1024 * 1024; // 1MB
 const RESPONSE_TIMEOUT: Duration = Duration::from_secs(1);
 const MAX_CONCURRENT_REQUESTS: usize = 1000;
 const RATE_LIMIT_WINDOW: Duration = Duration::from_secs(60);
+const INFLIGHT_REQUESTS: &str = "grpc.security";
+const THREAT_STREAM_BUFFER: usize = 128;
+const FEEDBACK_EXPORT_BUFFER: usize = 256;
 
 /// Rate limiter for request throttling
 #[derive(Debug)]
@@ -90,12 +103,23 @@ impl MetricsRecorder {
     }
 }
 
+/// Detector and response engine serving one tenant
+#[derive(Debug, Clone)]
+struct TenantServices {
+    threat_detector: Arc<ThreatDetector>,
+    response_engine: Arc<ResponseEngine>,
+}
+
 #[derive(Debug)]
 pub struct GuardianSecurityService {
     threat_detector: Arc<ThreatDetector>,
     response_engine: Arc<ResponseEngine>,
+    tenants: HashMap<TenantId, TenantServices>,
+    feedback: Option<Arc<AnomalyFeedback>>,
+    analytics: Option<Arc<ThreatAnalytics>>,
     request_limiter: Arc<RateLimiter>,
     metrics_recorder: Arc<MetricsRecorder>,
+    inflight: Arc<InflightTracker>,
 }
 
 impl GuardianSecurityService {
@@ -109,17 +133,123 @@ impl GuardianSecurityService {
         Self {
             threat_detector,
             response_engine,
+            tenants: HashMap::new(),
+            feedback: None,
+            analytics: None,
             request_limiter: Arc::new(RateLimiter::new(
                 MAX_CONCURRENT_REQUESTS,
                 RATE_LIMIT_WINDOW,
             )),
             metrics_recorder: Arc::new(MetricsRecorder::new("guardian.security")),
+            inflight: inflight_registry().tracker(INFLIGHT_REQUESTS, DrainStage::Api),
         }
     }
+
+    /// Serves callers of the detector's tenant from a dedicated detector and response engine
+    pub fn with_tenant(mut self, threat_detector: Arc<ThreatDetector>, response_engine: Arc<ResponseEngine>) -> Self {
+        let tenant_id = threat_detector.tenant().tenant_id().clone();
+        self.tenants.insert(tenant_id, TenantServices { threat_detector, response_engine });
+        self
+    }
+
+    /// Accepts analyst feedback on anomaly detections and serves the labeled dataset
+    pub fn with_feedback(mut self, feedback: Arc<AnomalyFeedback>) -> Self {
+        self.feedback = Some(feedback);
+        self
+    }
+
+    fn feedback(&self) -> Result<Arc<AnomalyFeedback>, Status> {
+        self.feedback
+            .clone()
+            .ok_or_else(|| Status::unimplemented("Detection feedback is not enabled on this host"))
+    }
+
+    /// Answers historical threat statistics from the recorded detections
+    pub fn with_analytics(mut self, analytics: Arc<ThreatAnalytics>) -> Self {
+        self.analytics = Some(analytics);
+        self
+    }
+
+    fn analytics(&self) -> Result<Arc<ThreatAnalytics>, Status> {
+        self.analytics
+            .clone()
+            .ok_or_else(|| Status::unimplemented("Threat analytics is not enabled on this host"))
+    }
+
+    /// Resolves the services of the caller's tenant, refusing tenants this host does not serve
+    fn scoped<T>(&self, request: &Request<T>) -> Result<TenantServices, Status> {
+        let tenant = request_tenant(request);
+        if self.threat_detector.tenant() == &tenant {
+            return Ok(TenantServices {
+                threat_detector: Arc::clone(&self.threat_detector),
+                response_engine: Arc::clone(&self.response_engine),
+            });
+        }
+        self.tenants.get(tenant.tenant_id()).cloned().ok_or_else(|| {
+            warn!(tenant = %tenant.tenant_id(), "Request for tenant not served by this host");
+            Status::permission_denied("Tenant is not served by this host")
+        })
+    }
 }
 
 #[tonic::async_trait]
 impl security_service_server::SecurityService for GuardianSecurityService {
+    type MonitorThreatsStream = ReceiverStream<Result<ThreatAlert, Status>>;
+    type ExportFeedbackDatasetStream = ReceiverStream<Result<LabeledSample, Status>>;
+
+    /// Streams retained alerts since the requested time, then new detections as they happen
+    #[instrument(skip(self, request))]
+    async fn monitor_threats(
+        &self,
+        request: Request<MonitorThreatsRequest>,
+    ) -> Result<Response<Self::MonitorThreatsStream>, Status> {
+        let method = "monitor_threats";
+        self.request_limiter.check_rate_limit().await?;
+        self.metrics_recorder.record_request_count(method, "started");
+
+        let services = self.scoped(&request)?;
+        let tenant_id = services.threat_detector.tenant().tenant_id().clone();
+        let request = request.into_inner();
+        let since = request
+            .since
+            .map(std::time::SystemTime::try_from)
+            .transpose()
+            .map_err(|_| Status::invalid_argument("Invalid since timestamp"))?;
+        let severities = request.severity_filters;
+        let wanted = move |notice: &ThreatNotice| {
+            notice.tenant_id == tenant_id
+                && (severities.is_empty() || severities.contains(&threat_severity(&notice.level)))
+        };
+
+        let (backlog, mut live) = services.threat_detector.watch_threats(since);
+        let (tx, rx) = mpsc::channel(THREAT_STREAM_BUFFER);
+        tokio::spawn(async move {
+            for notice in backlog.into_iter().filter(|n| wanted(n)) {
+                if tx.send(Ok(threat_alert(notice))).await.is_err() {
+                    return;
+                }
+            }
+            loop {
+                match live.recv().await {
+                    Ok(notice) if wanted(&notice) => {
+                        if tx.send(Ok(threat_alert(notice))).await.is_err() {
+                            return;
+                        }
+                    }
+                    Ok(_) => {}
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!(skipped, "Threat watcher fell behind, alerts skipped");
+                        counter!("guardian.security.threat_stream.skipped", skipped);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => return,
+                }
+            }
+        });
+
+        self.metrics_recorder.record_request_count(method, "success");
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+
     #[instrument(skip(self, request))]
     async fn detect_threats(
         &self,
@@ -128,6 +258,9 @@ impl security_service_server::SecurityService for GuardianSecurityService {
         let start_time = Instant::now();
         let method = "detect_threats";
 
+        // Turn new work away while draining for shutdown
+        let _inflight = self.inflight.enter().ok_or_else(super::draining_status)?;
+
         // Check rate limit
         self.request_limiter.check_rate_limit().await?;
 
@@ -135,16 +268,16 @@ impl security_service_server::SecurityService for GuardianSecurityService {
         self.metrics_recorder.record_request_count(method, "started");
 
         // Perform threat detection
-        let result = self.threat_detector.analyze_threat()
+        let result = self.scoped(&request)?.threat_detector.analyze_threat()
             .await
             .map_err(|e| {
                 error!(?e, "Threat detection failed");
-                Status::internal(e.to_string())
+                Status::from(e)
             })?;
 
         // Convert result to response
         let response = ThreatAlert {
-            alert_id: uuid::Uuid::new_v4().to_string(),
+            alert_id: next_id(IdKind::Detection).to_string(),
             severity: result.severity as i32,
             threat_type: result.threat_type as i32,
             confidence: result.confidence,
@@ -168,6 +301,9 @@ impl security_service_server::SecurityService for GuardianSecurityService {
         let start_time = Instant::now();
         let method = "detect_anomalies";
 
+        // Turn new work away while draining for shutdown
+        let _inflight = self.inflight.enter().ok_or_else(super::draining_status)?;
+
         // Check rate limit
         self.request_limiter.check_rate_limit().await?;
 
@@ -175,16 +311,16 @@ impl security_service_server::SecurityService for GuardianSecurityService {
         self.metrics_recorder.record_request_count(method, "started");
 
         // Perform anomaly detection
-        let result = self.threat_detector.detect_anomalies()
+        let result = self.scoped(&request)?.threat_detector.detect_anomalies()
             .await
             .map_err(|e| {
                 error!(?e, "Anomaly detection failed");
-                Status::internal(e.to_string())
+                Status::from(e)
             })?;
 
         // Convert result to response
         let response = SecurityEvent {
-            event_id: uuid::Uuid::new_v4().to_string(),
+            event_id: next_id(IdKind::Event).to_string(),
             event_type: result.event_type as i32,
             severity: result.severity as i32,
             timestamp: Some(prost_types::Timestamp::from(std::time::SystemTime::now())),
@@ -213,6 +349,7 @@ impl security_service_server::SecurityService for GuardianSecurityService {
         // Record request metrics
         self.metrics_recorder.record_request_count(method, "started");
 
+        let services = self.scoped(&request)?;
         let alert = request.into_inner();
 
         // Validate request
@@ -221,11 +358,11 @@ impl security_service_server::SecurityService for GuardianSecurityService {
         }
 
         // Execute response
-        let result = self.response_engine.execute_response(alert)
+        let result = services.response_engine.execute_response(alert)
             .await
             .map_err(|e| {
                 error!(?e, "Response execution failed");
-                Status::internal(e.to_string())
+                Status::from(e)
             })?;
 
         // Convert result to response
@@ -244,6 +381,228 @@ impl security_service_server::SecurityService for GuardianSecurityService {
 
         Ok(Response::new(response))
     }
+
+    /// Records an analyst's verdict on a detection of the caller's tenant
+    #[instrument(skip(self, request))]
+    async fn submit_detection_feedback(
+        &self,
+        request: Request<DetectionFeedback>,
+    ) -> Result<Response<FeedbackReceipt>, Status> {
+        let method = "submit_detection_feedback";
+        self.request_limiter.check_rate_limit().await?;
+        self.metrics_recorder.record_request_count(method, "started");
+
+        let feedback = self.feedback()?;
+        let services = self.scoped(&request)?;
+        let analyst = Principal::from_request(&request).name;
+        let request = request.into_inner();
+
+        let label = match FeedbackLabel::from_i32(request.label) {
+            Some(FeedbackLabel::TruePositive) => model_registry::FeedbackLabel::TruePositive,
+            Some(FeedbackLabel::FalsePositive) => model_registry::FeedbackLabel::FalsePositive,
+            Some(FeedbackLabel::FalseNegative) => model_registry::FeedbackLabel::FalseNegative,
+            _ => return Err(Status::invalid_argument("Feedback label required")),
+        };
+        let sample = match request.sample_json.as_str() {
+            "" => None,
+            json => Some(serde_json::from_str(json).map_err(|_| Status::invalid_argument("sample_json is not valid JSON"))?),
+        };
+
+        let recorded = feedback
+            .submit(FeedbackRequest {
+                detection_id: Some(request.detection_id).filter(|id| !id.is_empty()),
+                label,
+                tenant_id: services.threat_detector.tenant().tenant_id().clone(),
+                analyst,
+                comment: Some(request.comment).filter(|c| !c.is_empty()),
+                sample,
+            })
+            .await
+            .map_err(|e| match e {
+                GuardianError::ValidationError { context, .. } => Status::invalid_argument(context),
+                e => {
+                    error!(?e, "Failed to record detection feedback");
+                    Status::internal("Failed to record feedback")
+                }
+            })?;
+
+        self.metrics_recorder.record_request_count(method, "success");
+        Ok(Response::new(FeedbackReceipt {
+            feedback_id: recorded.feedback_id,
+            model_version: recorded.model_version.unwrap_or_default(),
+            recorded_at: Some(prost_types::Timestamp {
+                seconds: recorded.submitted_at as i64,
+                nanos: 0,
+            }),
+        }))
+    }
+
+    /// Streams the caller's tenant's feedback as labeled training samples
+    #[instrument(skip(self, request))]
+    async fn export_feedback_dataset(
+        &self,
+        request: Request<ExportFeedbackRequest>,
+    ) -> Result<Response<Self::ExportFeedbackDatasetStream>, Status> {
+        let method = "export_feedback_dataset";
+        self.request_limiter.check_rate_limit().await?;
+        self.metrics_recorder.record_request_count(method, "started");
+
+        let feedback = self.feedback()?;
+        let tenant_id = self.scoped(&request)?.threat_detector.tenant().tenant_id().clone();
+        let since = request.into_inner().since.map(|t| t.seconds.max(0) as u64);
+
+        let samples = feedback.export_dataset(&tenant_id, since).await.map_err(|e| {
+            error!(?e, "Failed to export feedback dataset");
+            Status::internal("Failed to export feedback dataset")
+        })?;
+
+        let (tx, rx) = mpsc::channel(FEEDBACK_EXPORT_BUFFER);
+        tokio::spawn(async move {
+            for sample in samples {
+                if tx.send(Ok(labeled_sample(sample))).await.is_err() {
+                    return;
+                }
+            }
+        });
+
+        self.metrics_recorder.record_request_count(method, "success");
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+
+    /// Lists the addresses the firewall manager holds blocked
+    #[instrument(skip(self, _request))]
+    async fn list_network_blocks(&self, _request: Request<()>) -> Result<Response<NetworkBlocks>, Status> {
+        let method = "list_network_blocks";
+        self.request_limiter.check_rate_limit().await?;
+        self.metrics_recorder.record_request_count(method, "started");
+
+        let blocks = firewall().blocks().await.into_iter().map(network_block).collect();
+
+        self.metrics_recorder.record_request_count(method, "success");
+        Ok(Response::new(NetworkBlocks { blocks }))
+    }
+
+    /// Executes a response the guardrails held, with the caller recorded as its approver
+    #[instrument(skip(self, request))]
+    async fn approve_response(&self, request: Request<ApproveResponseRequest>) -> Result<Response<ResponseResult>, Status> {
+        let start_time = Instant::now();
+        let method = "approve_response";
+        self.request_limiter.check_rate_limit().await?;
+        self.metrics_recorder.record_request_count(method, "started");
+
+        let services = self.scoped(&request)?;
+        let approver = Principal::from_request(&request).name;
+        let pending_id = request.into_inner().pending_id;
+        let pending = uuid::Uuid::parse_str(&pending_id)
+            .map_err(|_| Status::invalid_argument(format!("Invalid pending response id {}", pending_id)))?;
+
+        let status = services.response_engine.approve_response(pending, &approver).await.map_err(|e| {
+            error!(?e, %pending, "Approved response failed");
+            Status::from(e)
+        })?;
+
+        self.metrics_recorder.record_request_latency(method, start_time.elapsed());
+        self.metrics_recorder.record_request_count(method, "success");
+        Ok(Response::new(ResponseResult {
+            action_id: pending_id,
+            success: status.success(),
+            error_message: status.error_context().unwrap_or_default().to_string(),
+            completed_at: Some(prost_types::Timestamp::from(std::time::SystemTime::now())),
+            result_data: HashMap::from([
+                ("action".to_string(), status.action().name().to_string()),
+                ("approved_by".to_string(), approver),
+                ("correlation_id".to_string(), status.correlation_id().to_string()),
+            ]),
+        }))
+    }
+
+    /// Aggregates the caller's tenant's recorded detections with the analytics DSL
+    #[instrument(skip(self, request))]
+    async fn query_threat_stats(&self, request: Request<ThreatStatsRequest>) -> Result<Response<ThreatStats>, Status> {
+        let start_time = Instant::now();
+        let method = "query_threat_stats";
+        self.request_limiter.check_rate_limit().await?;
+        self.metrics_recorder.record_request_count(method, "started");
+
+        let analytics = self.analytics()?;
+        let tenant_id = self.scoped(&request)?.threat_detector.tenant().tenant_id().clone();
+        let stats = analytics
+            .query(&tenant_id, &request.into_inner().query)
+            .await
+            .map_err(|e| match e {
+                GuardianError::ValidationError { context, .. } => Status::invalid_argument(context),
+                e => {
+                    error!(?e, "Threat analytics query failed");
+                    Status::internal("Threat analytics query failed")
+                }
+            })?;
+
+        self.metrics_recorder.record_request_latency(method, start_time.elapsed());
+        self.metrics_recorder.record_request_count(method, "success");
+        Ok(Response::new(threat_stats(stats)))
+    }
+}
+
+fn threat_stats(stats: threat_analytics::ThreatStats) -> ThreatStats {
+    ThreatStats {
+        measure: match stats.query.measure {
+            threat_analytics::Measure::Count => "count".into(),
+            threat_analytics::Measure::MeanTimeToRespond => "mttr".into(),
+        },
+        group_by: stats.query.group_by.map(|d| d.as_str().to_string()).unwrap_or_default(),
+        rows: stats
+            .rows
+            .into_iter()
+            .map(|row| ThreatStatRow { key: row.key, value: row.value, samples: row.samples })
+            .collect(),
+        partitions_scanned: stats.partitions_scanned as u32,
+        partitions_cached: stats.partitions_cached as u32,
+    }
+}
+
+fn network_block(block: firewall::NetworkBlock) -> NetworkBlock {
+    NetworkBlock {
+        address: block.address,
+        reason: block.reason,
+        blocked_at: Some(prost_types::Timestamp { seconds: block.blocked_at.timestamp(), nanos: 0 }),
+        expires_at: Some(prost_types::Timestamp { seconds: block.expires_at.timestamp(), nanos: 0 }),
+    }
+}
+
+fn labeled_sample(sample: anomaly_feedback::LabeledSample) -> LabeledSample {
+    LabeledSample {
+        features_json: sample.features.to_string(),
+        anomalous: sample.anomalous,
+        anomaly_type: sample.anomaly_type,
+        detection_id: sample.detection_id.unwrap_or_default(),
+        model_version: sample.model_version.unwrap_or_default(),
+        labeled_by: sample.labeled_by,
+        labeled_at: Some(prost_types::Timestamp {
+            seconds: sample.labeled_at as i64,
+            nanos: 0,
+        }),
+    }
+}
+
+fn threat_severity(level: &ThreatLevel) -> i32 {
+    match level {
+        ThreatLevel::Critical => ThreatSeverity::Critical as i32,
+        ThreatLevel::High => ThreatSeverity::High as i32,
+        ThreatLevel::Medium => ThreatSeverity::Medium as i32,
+        ThreatLevel::Low => ThreatSeverity::Low as i32,
+    }
+}
+
+fn threat_alert(notice: ThreatNotice) -> ThreatAlert {
+    ThreatAlert {
+        id: notice.detection_id,
+        severity: threat_severity(&notice.level),
+        detected_at: Some(prost_types::Timestamp::from(notice.detected_at)),
+        source: notice.prediction_type.clone(),
+        description: format!("{} detected with confidence {:.2}", notice.prediction_type, notice.confidence),
+        tags: notice.details.into_iter().map(|(key, value)| format!("{}={}", key, value)).collect(),
+        ..Default::default()
+    }
 }
 
 pub fn create_security_service(
@@ -0,0 +1,310 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use metrics::{counter, gauge};
+use parking_lot::{Mutex, RwLock};
+use rustls::server::AllowAnyAuthenticatedClient;
+use tokio::net::{TcpListener, TcpStream};
+use tokio_rustls::{server::TlsStream, TlsAcceptor};
+use tokio_stream::wrappers::ReceiverStream;
+use tracing::{debug, info, instrument, warn};
+
+use super::TlsConfig;
+use crate::utils::error::GuardianError;
+
+// Constants for TLS certificate rotation
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+const INCOMING_BACKLOG: usize = 128;
+const ALPN_H2: &[u8] = b"h2";
+const ALPN_HTTP1: &[u8] = b"http/1.1";
+
+/// Size and modification time used to detect rewritten certificate files
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FileStamp {
+    modified: Option<SystemTime>,
+    len: u64,
+}
+
+impl FileStamp {
+    fn read(path: &Path) -> Option<Self> {
+        let metadata = std::fs::metadata(path).ok()?;
+        Some(Self {
+            modified: metadata.modified().ok(),
+            len: metadata.len(),
+        })
+    }
+}
+
+/// Expiry of a loaded certificate, reported as a countdown gauge
+#[derive(Debug, Clone)]
+struct CertExpiry {
+    role: &'static str,
+    subject: String,
+    not_after: i64,
+}
+
+/// Serves the gRPC listener with a rustls config that is rebuilt whenever the
+/// certificate, key or client CA files change. Each connection is accepted with
+/// the config current at handshake time, so rotation never touches established
+/// connections.
+#[derive(Debug)]
+pub struct TlsReloader {
+    config: TlsConfig,
+    /// Application protocols offered during the handshake
+    alpn: Vec<Vec<u8>>,
+    current: RwLock<Arc<rustls::ServerConfig>>,
+    stamps: Mutex<Vec<Option<FileStamp>>>,
+    expiry: Mutex<Vec<CertExpiry>>,
+}
+
+impl TlsReloader {
+    /// Loads the initial certificate material for an HTTP/2-only listener, failing if it is unusable
+    pub fn load(config: TlsConfig) -> Result<Arc<Self>, GuardianError> {
+        Self::load_with_alpn(config, vec![ALPN_H2.to_vec()])
+    }
+
+    /// Loads certificate material for a listener also serving HTTP/1.1, such as the REST gateway
+    pub fn load_http(config: TlsConfig) -> Result<Arc<Self>, GuardianError> {
+        Self::load_with_alpn(config, vec![ALPN_H2.to_vec(), ALPN_HTTP1.to_vec()])
+    }
+
+    fn load_with_alpn(config: TlsConfig, alpn: Vec<Vec<u8>>) -> Result<Arc<Self>, GuardianError> {
+        let (server_config, expiry) = build_server_config(&config, &alpn)?;
+        let reloader = Arc::new(Self {
+            stamps: Mutex::new(watched_paths(&config).iter().map(|p| FileStamp::read(p)).collect()),
+            config,
+            alpn,
+            current: RwLock::new(Arc::new(server_config)),
+            expiry: Mutex::new(expiry),
+        });
+        reloader.report_expiry();
+        Ok(reloader)
+    }
+
+    /// Returns an acceptor using the current certificate material
+    pub fn acceptor(&self) -> TlsAcceptor {
+        TlsAcceptor::from(Arc::clone(&*self.current.read()))
+    }
+
+    /// Rebuilds the rustls config if any watched file changed, returning whether it did
+    #[instrument(skip(self))]
+    pub fn reload_if_changed(&self) -> Result<bool, GuardianError> {
+        let stamps: Vec<Option<FileStamp>> = watched_paths(&self.config).iter().map(|p| FileStamp::read(p)).collect();
+        if *self.stamps.lock() == stamps {
+            return Ok(false);
+        }
+
+        // Files are often replaced one at a time; a half-written pair fails to load
+        // and the previous config stays in place until the next poll
+        match build_server_config(&self.config, &self.alpn) {
+            Ok((server_config, expiry)) => {
+                *self.current.write() = Arc::new(server_config);
+                *self.expiry.lock() = expiry;
+                *self.stamps.lock() = stamps;
+                counter!("guardian.grpc.tls.reloads", 1, "result" => "success");
+                info!(cert = %self.config.cert_path, "gRPC TLS certificates reloaded");
+                self.report_expiry();
+                Ok(true)
+            }
+            Err(e) => {
+                counter!("guardian.grpc.tls.reloads", 1, "result" => "failure");
+                Err(e)
+            }
+        }
+    }
+
+    /// Spawns a task polling the certificate files on the configured interval
+    pub fn spawn_watcher(self: &Arc<Self>) -> tokio::task::JoinHandle<()> {
+        let reloader = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(reloader.config.reload_interval);
+            loop {
+                interval.tick().await;
+                if let Err(e) = reloader.reload_if_changed() {
+                    warn!(error = %e, "Keeping previous gRPC TLS certificates");
+                }
+                reloader.report_expiry();
+            }
+        })
+    }
+
+    /// Accepts connections from the listener and performs TLS handshakes off the accept loop
+    pub fn incoming(self: &Arc<Self>, listener: TcpListener) -> ReceiverStream<io::Result<TlsStream<TcpStream>>> {
+        let (tx, rx) = tokio::sync::mpsc::channel(INCOMING_BACKLOG);
+        let reloader = Arc::clone(self);
+
+        tokio::spawn(async move {
+            loop {
+                let (stream, peer) = match listener.accept().await {
+                    Ok(accepted) => accepted,
+                    Err(e) => {
+                        warn!(error = %e, "Failed to accept gRPC connection");
+                        continue;
+                    }
+                };
+
+                let acceptor = reloader.acceptor();
+                let tx = tx.clone();
+                tokio::spawn(async move {
+                    match tokio::time::timeout(HANDSHAKE_TIMEOUT, acceptor.accept(stream)).await {
+                        Ok(Ok(tls)) => {
+                            let _ = tx.send(Ok(tls)).await;
+                        }
+                        Ok(Err(e)) => {
+                            counter!("guardian.grpc.tls.handshake_failures", 1);
+                            debug!(%peer, error = %e, "gRPC TLS handshake failed");
+                        }
+                        Err(_) => {
+                            counter!("guardian.grpc.tls.handshake_failures", 1);
+                            debug!(%peer, "gRPC TLS handshake timed out");
+                        }
+                    }
+                });
+
+                if tx.is_closed() {
+                    break;
+                }
+            }
+        });
+
+        ReceiverStream::new(rx)
+    }
+
+    /// Publishes the seconds remaining until each loaded certificate expires
+    fn report_expiry(&self) {
+        let now = time::OffsetDateTime::now_utc().unix_timestamp();
+        for cert in self.expiry.lock().iter() {
+            let remaining = cert.not_after - now;
+            gauge!("guardian.grpc.tls.cert_expiry_seconds", remaining as f64,
+                "role" => cert.role, "subject" => cert.subject.clone());
+            if remaining <= 0 {
+                warn!(role = cert.role, subject = %cert.subject, "gRPC TLS certificate has expired");
+            }
+        }
+    }
+}
+
+fn watched_paths(config: &TlsConfig) -> Vec<PathBuf> {
+    let mut paths = vec![PathBuf::from(&config.cert_path), PathBuf::from(&config.key_path)];
+    paths.extend(config.ca_cert_path.as_ref().map(PathBuf::from));
+    paths
+}
+
+/// Builds a rustls server config, requiring client certificates when a CA is configured
+fn build_server_config(config: &TlsConfig, alpn: &[Vec<u8>]) -> Result<(rustls::ServerConfig, Vec<CertExpiry>), GuardianError> {
+    let certs = read_certs(&config.cert_path)?;
+    let key = read_private_key(&config.key_path)?;
+    let mut expiry = Vec::new();
+    expiry.extend(certs.first().and_then(|c| cert_expiry("server", &c.0)));
+
+    let builder = rustls::ServerConfig::builder().with_safe_defaults();
+    let builder = match &config.ca_cert_path {
+        Some(ca_path) => {
+            let mut roots = rustls::RootCertStore::empty();
+            for ca in read_certs(ca_path)? {
+                expiry.extend(cert_expiry("client_ca", &ca.0));
+                roots
+                    .add(&ca)
+                    .map_err(|e| tls_error(format!("Invalid client CA in {}", ca_path), Some(Box::new(e))))?;
+            }
+            builder.with_client_cert_verifier(AllowAnyAuthenticatedClient::new(roots).boxed())
+        }
+        None => builder.with_no_client_auth(),
+    };
+
+    let mut server_config = builder
+        .with_single_cert(certs, key)
+        .map_err(|e| tls_error(format!("Certificate {} does not match its key", config.cert_path), Some(Box::new(e))))?;
+    server_config.alpn_protocols = alpn.to_vec();
+    Ok((server_config, expiry))
+}
+
+fn read_certs(path: &str) -> Result<Vec<rustls::Certificate>, GuardianError> {
+    let pem = std::fs::read(path)
+        .map_err(|e| tls_error(format!("Failed to read certificate {}", path), Some(Box::new(e))))?;
+    let certs = rustls_pemfile::certs(&mut pem.as_slice())
+        .map_err(|e| tls_error(format!("Failed to parse certificate {}", path), Some(Box::new(e))))?;
+    if certs.is_empty() {
+        return Err(tls_error(format!("No certificates found in {}", path), None));
+    }
+    Ok(certs.into_iter().map(rustls::Certificate).collect())
+}
+
+fn read_private_key(path: &str) -> Result<rustls::PrivateKey, GuardianError> {
+    let pem = std::fs::read(path)
+        .map_err(|e| tls_error(format!("Failed to read private key {}", path), Some(Box::new(e))))?;
+    let items = rustls_pemfile::read_all(&mut pem.as_slice())
+        .map_err(|e| tls_error(format!("Failed to parse private key {}", path), Some(Box::new(e))))?;
+    items
+        .into_iter()
+        .find_map(|item| match item {
+            rustls_pemfile::Item::PKCS8Key(key)
+            | rustls_pemfile::Item::RSAKey(key)
+            | rustls_pemfile::Item::ECKey(key) => Some(rustls::PrivateKey(key)),
+            _ => None,
+        })
+        .ok_or_else(|| tls_error(format!("No private key found in {}", path), None))
+}
+
+fn cert_expiry(role: &'static str, der: &[u8]) -> Option<CertExpiry> {
+    let (_, cert) = x509_parser::parse_x509_certificate(der).ok()?;
+    Some(CertExpiry {
+        role,
+        subject: cert.subject().to_string(),
+        not_after: cert.validity().not_after.timestamp(),
+    })
+}
+
+fn tls_error(context: String, source: Option<Box<dyn std::error::Error + Send + Sync>>) -> GuardianError {
+    GuardianError::SecurityError {
+        context,
+        source,
+        severity: crate::utils::error::ErrorSeverity::High,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: crate::utils::error::ErrorCategory::Security,
+        retry_count: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn tls_config(dir: &Path) -> TlsConfig {
+        TlsConfig {
+            cert_path: dir.join("server.pem").display().to_string(),
+            key_path: dir.join("server.key").display().to_string(),
+            ca_cert_path: None,
+            reload_interval: Duration::from_secs(1),
+        }
+    }
+
+    #[test]
+    fn test_file_stamp_detects_rewrite() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("server.pem");
+        assert_eq!(FileStamp::read(&path), None);
+
+        std::fs::write(&path, b"first").unwrap();
+        let first = FileStamp::read(&path);
+        std::fs::OpenOptions::new().append(true).open(&path).unwrap().write_all(b" rotated").unwrap();
+        assert_ne!(FileStamp::read(&path), first);
+    }
+
+    #[test]
+    fn test_invalid_material_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = tls_config(dir.path());
+        assert!(build_server_config(&config, &[ALPN_H2.to_vec()]).is_err());
+
+        std::fs::write(&config.cert_path, b"not a certificate").unwrap();
+        std::fs::write(&config.key_path, b"not a key").unwrap();
+        assert!(TlsReloader::load(config).is_err());
+    }
+}
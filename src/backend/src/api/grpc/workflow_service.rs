@@ -0,0 +1,154 @@
+use std::sync::Arc;
+use tonic::{Request, Response, Status};
+use tracing::{instrument, warn};
+use metrics::counter;
+
+use crate::temporal::visibility::{self, WorkflowAdmin, WorkflowListFilter};
+use crate::utils::error::GuardianError;
+
+// Import the generated gRPC code
+tonic::include_proto!("guardian.workflow.v1");
+
+/// gRPC service listing, describing, cancelling and signalling Guardian workflows
+#[derive(Debug)]
+pub struct GuardianWorkflowService {
+    admin: Arc<WorkflowAdmin>,
+}
+
+impl GuardianWorkflowService {
+    /// Creates a new GuardianWorkflowService backed by the given workflow admin
+    pub fn new(admin: Arc<WorkflowAdmin>) -> Self {
+        Self { admin }
+    }
+}
+
+#[tonic::async_trait]
+impl workflow_admin_server::WorkflowAdmin for GuardianWorkflowService {
+    /// Lists security and maintenance workflows
+    #[instrument(skip(self, request))]
+    async fn list_workflows(
+        &self,
+        request: Request<ListWorkflowsRequest>,
+    ) -> Result<Response<ListWorkflowsResponse>, Status> {
+        let request = request.into_inner();
+        let filter = match request.filter() {
+            WorkflowFilter::Running => WorkflowListFilter::Running,
+            WorkflowFilter::Closed => WorkflowListFilter::Closed,
+            WorkflowFilter::All => WorkflowListFilter::All,
+        };
+        let limit = match request.limit {
+            0 => visibility::DEFAULT_LIST_LIMIT,
+            limit => limit as usize,
+        };
+        let workflows = self
+            .admin
+            .list(filter, non_empty(&request.workflow_type), limit)
+            .await
+            .map_err(to_status)?;
+
+        Ok(Response::new(ListWorkflowsResponse {
+            workflows: workflows.into_iter().map(to_summary).collect(),
+        }))
+    }
+
+    /// Describes a workflow with its current step and retries
+    #[instrument(skip(self, request))]
+    async fn get_workflow(
+        &self,
+        request: Request<GetWorkflowRequest>,
+    ) -> Result<Response<WorkflowDetail>, Status> {
+        let request = request.into_inner();
+        let detail = self
+            .admin
+            .describe(&request.workflow_id, non_empty(&request.run_id))
+            .await
+            .map_err(to_status)?;
+
+        Ok(Response::new(WorkflowDetail {
+            summary: Some(to_summary(detail.summary)),
+            current_step: detail.current_step.unwrap_or_default(),
+            retries: detail.retries,
+            pending_steps: detail
+                .pending_steps
+                .into_iter()
+                .map(|step| PendingStep {
+                    activity: step.activity,
+                    attempt: step.attempt,
+                    maximum_attempts: step.maximum_attempts,
+                    last_failure: step.last_failure.unwrap_or_default(),
+                })
+                .collect(),
+        }))
+    }
+
+    /// Requests cancellation of a running workflow
+    #[instrument(skip(self, request))]
+    async fn cancel_workflow(
+        &self,
+        request: Request<CancelWorkflowRequest>,
+    ) -> Result<Response<WorkflowSummary>, Status> {
+        let request = request.into_inner();
+        if request.reason.trim().is_empty() {
+            return Err(Status::invalid_argument("A cancellation reason is required"));
+        }
+        let summary = self
+            .admin
+            .cancel(&request.workflow_id, non_empty(&request.run_id), &request.reason)
+            .await
+            .map_err(to_status)?;
+        Ok(Response::new(to_summary(summary)))
+    }
+
+    /// Sends a signal to a running workflow
+    #[instrument(skip(self, request))]
+    async fn signal_workflow(
+        &self,
+        request: Request<SignalWorkflowRequest>,
+    ) -> Result<Response<WorkflowSummary>, Status> {
+        let request = request.into_inner();
+        let payload = match non_empty(&request.payload_json) {
+            Some(json) => Some(
+                serde_json::from_str(json)
+                    .map_err(|e| Status::invalid_argument(format!("Signal payload is not valid JSON: {}", e)))?,
+            ),
+            None => None,
+        };
+        let summary = self
+            .admin
+            .signal(&request.workflow_id, non_empty(&request.run_id), &request.signal, payload)
+            .await
+            .map_err(to_status)?;
+        Ok(Response::new(to_summary(summary)))
+    }
+}
+
+fn non_empty(value: &str) -> Option<&str> {
+    Some(value).filter(|value| !value.is_empty())
+}
+
+fn to_summary(summary: visibility::WorkflowSummary) -> WorkflowSummary {
+    WorkflowSummary {
+        workflow_id: summary.workflow_id,
+        run_id: summary.run_id,
+        workflow_type: summary.workflow_type,
+        state: summary.state.as_str().to_string(),
+        started_at: summary.started_at.map(to_timestamp),
+        closed_at: summary.closed_at.map(to_timestamp),
+        history_length: summary.history_length,
+    }
+}
+
+fn to_timestamp(t: chrono::DateTime<chrono::Utc>) -> prost_types::Timestamp {
+    prost_types::Timestamp {
+        seconds: t.timestamp(),
+        nanos: t.timestamp_subsec_nanos() as i32,
+    }
+}
+
+fn to_status(error: GuardianError) -> Status {
+    if !matches!(error, GuardianError::ValidationError { .. }) {
+        warn!(error = %error, "Workflow administration failed");
+        counter!("guardian.grpc.workflow_admin.failures", 1);
+    }
+    Status::from(error)
+}
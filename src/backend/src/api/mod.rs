@@ -6,16 +6,23 @@ use governor::{Quota, RateLimiter};
 use tracing::{debug, error, info, instrument, warn};
 
 use crate::utils::error::GuardianError;
+use crate::utils::inflight::{inflight_registry, DrainStage};
+use crate::api::rest::{RestConfig, RestGateway};
 use crate::api::grpc::{
     GuardianService, GuardianSecurityService, MLService,
     ServerConfig, TlsConfig,
 };
 
+pub mod grpc;
+pub mod quota;
+pub mod rest;
+
 // API version and configuration constants
 pub const API_VERSION: &str = "v1";
 pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
 pub const MAX_CONNECTIONS: usize = 1000;
 pub const RATE_LIMIT_BURST: u32 = 50;
+const API_DRAIN_TIMEOUT: Duration = Duration::from_secs(5);
 
 /// Enhanced configuration for the API layer with security and performance settings
 #[derive(Debug, Clone)]
@@ -26,6 +33,7 @@ pub struct ApiConfig {
     pub circuit_breaker: CircuitBreakerConfig,
     pub connection_pool: ConnectionPoolConfig,
     pub monitoring: MonitoringConfig,
+    pub rest_config: RestConfig,
 }
 
 /// gRPC server configuration
@@ -46,7 +54,7 @@ pub struct AuthConfig {
     pub allowed_roles: Vec<String>,
 }
 
-/// Rate limiting configuration
+/// Rate limiting configuration shared by all clients; per-client quotas live in `AppConfig::client_quotas`
 #[derive(Debug, Clone)]
 pub struct RateLimitConfig {
     pub requests_per_second: u32,
@@ -113,6 +121,7 @@ impl Default for ApiConfig {
                 tracing_enabled: true,
                 health_check_interval: Duration::from_secs(15),
             },
+            rest_config: RestConfig::default(),
         }
     }
 }
@@ -144,7 +153,7 @@ pub async fn init_api(config: ApiConfig) -> Result<(), GuardianError> {
         request_timeout: config.grpc_config.request_timeout,
         circuit_breaker_threshold: config.circuit_breaker.failure_threshold,
         health_check_interval: config.monitoring.health_check_interval,
-        tls_config: config.grpc_config.tls_config,
+        tls_config: config.grpc_config.tls_config.clone(),
     };
 
     // Initialize services
@@ -152,14 +161,40 @@ pub async fn init_api(config: ApiConfig) -> Result<(), GuardianError> {
         /* service dependencies */
     ));
 
-    let security_service = Arc::new(GuardianSecurityService::new(
+    let mut security_service = GuardianSecurityService::new(
         /* service dependencies */
-    ));
+    );
+    if let Some(feedback) = crate::security::anomaly_feedback::anomaly_feedback() {
+        security_service = security_service.with_feedback(feedback);
+    }
+    let security_service = Arc::new(security_service);
 
     let ml_service = Arc::new(MLService::new(
         /* service dependencies */
     ));
 
+    // Start REST gateway sharing the rate limiter and circuit breaker
+    if config.rest_config.enabled {
+        // The gateway serves the gRPC server's certificates unless given its own
+        let mut rest_config = config.rest_config.clone();
+        if rest_config.tls_config.is_none() {
+            rest_config.tls_config = config.grpc_config.tls_config.clone();
+        }
+        let rest_gateway = RestGateway::new(
+            rest_config,
+            Arc::clone(&guardian_service),
+            Arc::clone(&security_service),
+            Arc::clone(&ml_service),
+            Arc::clone(&rate_limiter),
+            Arc::clone(&circuit_breaker),
+        );
+        tokio::spawn(async move {
+            if let Err(e) = rest_gateway.start().await {
+                error!(?e, "REST gateway failed");
+            }
+        });
+    }
+
     // Create gRPC server
     let grpc_server = grpc::GrpcServer::new(
         server_config,
@@ -183,16 +218,19 @@ pub async fn shutdown_api() -> Result<(), GuardianError> {
     // Stop accepting new connections
     counter!("guardian.api.shutdown.initiated", 1);
 
-    // Wait for active requests to complete
-    tokio::time::sleep(Duration::from_secs(5)).await;
+    // Wait for active requests to complete, rejecting new ones as retryable
+    let reports = inflight_registry().drain_stage(DrainStage::Api, API_DRAIN_TIMEOUT).await;
+    for report in &reports {
+        info!(requests = %report.name, drained = report.drained, abandoned = report.abandoned, "API requests drained");
+    }
 
     // Cleanup resources
     info!("API shutdown completed successfully");
     Ok(())
 }
 
-// Private helper structs and implementations
-struct CircuitBreaker {
+// Helper structs shared by the gRPC and REST front ends
+pub(crate) struct CircuitBreaker {
     failures: std::sync::atomic::AtomicU32,
     last_failure: RwLock<std::time::Instant>,
     threshold: u32,
@@ -200,7 +238,7 @@ struct CircuitBreaker {
 }
 
 impl CircuitBreaker {
-    fn new(threshold: u32, reset_timeout: Duration) -> Self {
+    pub(crate) fn new(threshold: u32, reset_timeout: Duration) -> Self {
         Self {
             failures: std::sync::atomic::AtomicU32::new(0),
             last_failure: RwLock::new(std::time::Instant::now()),
@@ -209,7 +247,7 @@ impl CircuitBreaker {
         }
     }
 
-    async fn record_failure(&self) {
+    pub(crate) async fn record_failure(&self) {
         let failures = self.failures.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
         *self.last_failure.write().await = std::time::Instant::now();
 
@@ -218,7 +256,7 @@ impl CircuitBreaker {
         }
     }
 
-    async fn is_open(&self) -> bool {
+    pub(crate) async fn is_open(&self) -> bool {
         let failures = self.failures.load(std::sync::atomic::Ordering::SeqCst);
         if failures >= self.threshold {
             let last_failure = *self.last_failure.read().await;
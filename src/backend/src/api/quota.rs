@@ -0,0 +1,369 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    future::Future,
+    net::{IpAddr, SocketAddr},
+    num::NonZeroU32,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use axum::extract::ConnectInfo;
+use governor::{
+    clock::{Clock, DefaultClock},
+    DefaultDirectRateLimiter, Quota, RateLimiter,
+};
+use http::{header::RETRY_AFTER, HeaderMap, HeaderValue, Request, Response};
+use metrics::{counter, gauge};
+use once_cell::sync::{Lazy, OnceCell};
+use parking_lot::{Mutex, RwLock};
+use tonic::transport::server::{TcpConnectInfo, TlsConnectInfo};
+use tracing::{info, warn};
+
+use crate::config::{ClientQuotaConfig, QuotaLimit};
+use crate::security::auth::{scope_principal, token_authority, TokenClaims};
+use crate::security::rbac::{is_unauthenticated_rpc, rbac, Principal};
+
+// Constants for per-client quotas
+const SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+const QUOTA_EXCEEDED_MESSAGE: &str = "Client quota exceeded";
+
+static CLIENT_QUOTAS: Lazy<Arc<ClientQuotas>> = Lazy::new(|| Arc::new(ClientQuotas::new(ClientQuotaConfig::default())));
+static SWEEPER: OnceCell<()> = OnceCell::new();
+
+/// Token bucket of one client identity
+struct ClientBucket {
+    limiter: DefaultDirectRateLimiter,
+    limit: QuotaLimit,
+    last_seen: Instant,
+}
+
+/// Rate limits API calls per client identity, with limits chosen by RBAC role
+pub struct ClientQuotas {
+    config: RwLock<ClientQuotaConfig>,
+    buckets: Mutex<HashMap<String, ClientBucket>>,
+}
+
+impl std::fmt::Debug for ClientQuotas {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClientQuotas")
+            .field("config", &*self.config.read())
+            .field("clients", &self.buckets.lock().len())
+            .finish()
+    }
+}
+
+/// Returns the process-wide client quotas
+pub fn client_quotas() -> Arc<ClientQuotas> {
+    Arc::clone(&CLIENT_QUOTAS)
+}
+
+/// Applies the quota configuration and starts dropping idle clients; call again after a reload
+pub fn init_client_quotas(config: &ClientQuotaConfig) {
+    let quotas = client_quotas();
+    quotas.configure(config);
+
+    if SWEEPER.set(()).is_err() {
+        return;
+    }
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(SWEEP_INTERVAL);
+        loop {
+            interval.tick().await;
+            quotas.expire_idle();
+        }
+    });
+}
+
+impl ClientQuotas {
+    /// Creates quotas enforcing the given configuration
+    pub fn new(config: ClientQuotaConfig) -> Self {
+        Self {
+            config: RwLock::new(config),
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Replaces the configuration; clients whose limit changed start over with a full bucket
+    pub fn configure(&self, config: &ClientQuotaConfig) {
+        *self.config.write() = config.clone();
+        info!(
+            enabled = config.enabled,
+            default_rps = config.default_limit.requests_per_second,
+            roles = config.role_limits.len(),
+            "Client quotas configured"
+        );
+    }
+
+    /// Admits a call from the principal, or returns how long it should wait before retrying
+    pub fn check(&self, principal: &Principal) -> Result<(), Duration> {
+        if !self.config.read().enabled {
+            return Ok(());
+        }
+        let limit = self.limit_for(&rbac().roles_for(principal));
+        let key = quota_key(principal);
+        self.check_key(&key, limit).map_err(|retry_after| {
+            counter!("guardian.api.client_quota.exceeded", 1);
+            warn!(client = %key, rps = limit.requests_per_second, ?retry_after, "Client quota exceeded");
+            retry_after
+        })
+    }
+
+    /// Returns the most generous limit among the roles, or the default if none has one
+    fn limit_for(&self, roles: &BTreeSet<String>) -> QuotaLimit {
+        let config = self.config.read();
+        roles
+            .iter()
+            .filter_map(|role| config.role_limits.get(role))
+            .max_by_key(|limit| (limit.requests_per_second, limit.burst))
+            .copied()
+            .unwrap_or(config.default_limit)
+    }
+
+    fn check_key(&self, key: &str, limit: QuotaLimit) -> Result<(), Duration> {
+        let mut buckets = self.buckets.lock();
+        let bucket = buckets
+            .entry(key.to_string())
+            .and_modify(|bucket| {
+                // The client's roles changed since its bucket was created
+                if bucket.limit != limit {
+                    *bucket = ClientBucket::new(limit);
+                }
+            })
+            .or_insert_with(|| ClientBucket::new(limit));
+        bucket.last_seen = Instant::now();
+        bucket
+            .limiter
+            .check()
+            .map_err(|not_until| not_until.wait_time_from(DefaultClock::default().now()))
+    }
+
+    fn expire_idle(&self) {
+        let idle_expiry = self.config.read().idle_expiry;
+        let mut buckets = self.buckets.lock();
+        buckets.retain(|_, bucket| bucket.last_seen.elapsed() < idle_expiry);
+        gauge!("guardian.api.client_quota.clients", buckets.len() as f64);
+    }
+}
+
+impl ClientBucket {
+    fn new(limit: QuotaLimit) -> Self {
+        let rate = NonZeroU32::new(limit.requests_per_second).unwrap_or(NonZeroU32::MIN);
+        let burst = NonZeroU32::new(limit.burst).unwrap_or(NonZeroU32::MIN);
+        Self {
+            limiter: RateLimiter::direct(Quota::per_second(rate).allow_burst(burst)),
+            limit,
+            last_seen: Instant::now(),
+        }
+    }
+}
+
+/// Identifies the caller by the ID of a bearer token the token authority accepts, else its
+/// client certificate, else its peer address
+///
+/// Unverified bearer tokens are never used as keys, or a client could mint a fresh bucket per
+/// request by making tokens up.
+pub fn client_principal<B>(request: &Request<B>) -> Principal {
+    let claims = request.extensions().get::<TokenClaims>().cloned().or_else(|| {
+        let token = bearer_token(request.headers())?;
+        token_authority()?.validate(token).ok()
+    });
+    if let Some(claims) = claims {
+        // Keyed on the token ID, so tokens sharing scopes keep separate buckets
+        let mut principal = scope_principal(&claims.sub, &claims.scopes);
+        principal.identities.insert(0, format!("token:{}", claims.jti));
+        return principal;
+    }
+
+    let principal = Principal::from_request(request);
+    if principal != Principal::anonymous() {
+        return principal;
+    }
+    match peer_ip(request) {
+        Some(ip) => Principal {
+            name: format!("peer-{}", ip),
+            identities: vec![format!("peer:{}", ip)],
+        },
+        None => principal,
+    }
+}
+
+/// Address of the connection a request arrived on, from tonic or axum connection info
+fn peer_ip<B>(request: &Request<B>) -> Option<IpAddr> {
+    let extensions = request.extensions();
+    extensions
+        .get::<TcpConnectInfo>()
+        .and_then(TcpConnectInfo::remote_addr)
+        .or_else(|| extensions.get::<TlsConnectInfo<TcpConnectInfo>>().and_then(|info| info.get_ref().remote_addr()))
+        .or_else(|| extensions.get::<ConnectInfo<SocketAddr>>().map(|info| info.0))
+        .map(|addr| addr.ip())
+}
+
+/// Key a principal's bucket is stored under: its common name when known, else its first identity
+fn quota_key(principal: &Principal) -> String {
+    principal
+        .identities
+        .iter()
+        .find(|identity| identity.starts_with("cn:"))
+        .or_else(|| principal.identities.first())
+        .cloned()
+        .unwrap_or_else(|| principal.name.clone())
+}
+
+fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(http::header::AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+        .map(str::trim)
+        .filter(|token| !token.is_empty())
+}
+
+/// Whole seconds to send in `retry-after`, never less than one
+pub(crate) fn retry_after_secs(retry_after: Duration) -> u64 {
+    retry_after.as_secs_f64().ceil().max(1.0) as u64
+}
+
+/// gRPC RESOURCE_EXHAUSTED response carrying `retry-after` metadata
+pub(crate) fn quota_exceeded<B: Default>(retry_after: Duration) -> Response<B> {
+    let mut response = Response::new(B::default());
+    let headers = response.headers_mut();
+    headers.insert("content-type", HeaderValue::from_static("application/grpc"));
+    headers.insert("grpc-status", HeaderValue::from(tonic::Code::ResourceExhausted as i32));
+    if let Ok(message) = HeaderValue::from_str(&QUOTA_EXCEEDED_MESSAGE.replace(' ', "%20")) {
+        headers.insert("grpc-message", message);
+    }
+    headers.insert(RETRY_AFTER, HeaderValue::from(retry_after_secs(retry_after)));
+    response
+}
+
+/// Tower layer enforcing per-client quotas on every gRPC call
+#[derive(Debug, Clone)]
+pub struct ClientQuotaLayer {
+    quotas: Arc<ClientQuotas>,
+}
+
+impl ClientQuotaLayer {
+    /// Creates a layer consulting the given quotas
+    pub fn new(quotas: Arc<ClientQuotas>) -> Self {
+        Self { quotas }
+    }
+}
+
+impl<S> tower::Layer<S> for ClientQuotaLayer {
+    type Service = ClientQuotaService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ClientQuotaService {
+            inner,
+            quotas: Arc::clone(&self.quotas),
+        }
+    }
+}
+
+/// Service created by `ClientQuotaLayer`
+#[derive(Debug, Clone)]
+pub struct ClientQuotaService<S> {
+    inner: S,
+    quotas: Arc<ClientQuotas>,
+}
+
+impl<S, ReqBody, ResBody> tower::Service<Request<ReqBody>> for ClientQuotaService<S>
+where
+    S: tower::Service<Request<ReqBody>, Response = Response<ResBody>>,
+    S::Future: Send + 'static,
+    ResBody: Default,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<ReqBody>) -> Self::Future {
+        if is_unauthenticated_rpc(request.uri().path()) {
+            return Box::pin(self.inner.call(request));
+        }
+        if let Err(retry_after) = self.quotas.check(&client_principal(&request)) {
+            return Box::pin(async move { Ok(quota_exceeded(retry_after)) });
+        }
+        Box::pin(self.inner.call(request))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_limits_follow_roles_and_reload() {
+        let mut config = ClientQuotaConfig {
+            enabled: true,
+            default_limit: QuotaLimit { requests_per_second: 1, burst: 2 },
+            role_limits: BTreeMap::from([
+                ("operator".to_string(), QuotaLimit { requests_per_second: 5, burst: 5 }),
+                ("admin".to_string(), QuotaLimit { requests_per_second: 50, burst: 50 }),
+            ]),
+            idle_expiry: Duration::from_secs(60),
+        };
+        let quotas = ClientQuotas::new(config.clone());
+
+        let roles = BTreeSet::from(["operator".to_string(), "admin".to_string()]);
+        assert_eq!(quotas.limit_for(&roles).requests_per_second, 50);
+        assert_eq!(quotas.limit_for(&BTreeSet::new()), config.default_limit);
+
+        // Burst is spent per client, not shared between clients
+        let limit = quotas.limit_for(&BTreeSet::new());
+        assert!(quotas.check_key("cn:alpha", limit).is_ok());
+        assert!(quotas.check_key("cn:alpha", limit).is_ok());
+        let retry_after = quotas.check_key("cn:alpha", limit).unwrap_err();
+        assert!(retry_after > Duration::ZERO && retry_after <= Duration::from_secs(1));
+        assert_eq!(retry_after_secs(retry_after), 1);
+        assert!(quotas.check_key("cn:beta", limit).is_ok());
+
+        // Reloading a new default limit resets buckets built on the old one
+        config.default_limit = QuotaLimit { requests_per_second: 10, burst: 10 };
+        quotas.configure(&config);
+        assert!(quotas.check_key("cn:alpha", quotas.limit_for(&BTreeSet::new())).is_ok());
+    }
+
+    #[test]
+    fn test_quota_exceeded_response_carries_retry_after() {
+        let response: Response<()> = quota_exceeded(Duration::from_millis(1500));
+        assert_eq!(response.headers()["grpc-status"], (tonic::Code::ResourceExhausted as i32).to_string().as_str());
+        assert_eq!(response.headers()[RETRY_AFTER], "2");
+
+    }
+
+    #[test]
+    fn test_unverified_tokens_keyed_on_peer_address() {
+        let peer = |token: &'static str| {
+            let mut request = Request::new(());
+            request.headers_mut().insert(http::header::AUTHORIZATION, HeaderValue::from_static(token));
+            request.extensions_mut().insert(ConnectInfo(SocketAddr::from(([192, 0, 2, 7], 41000))));
+            client_principal(&request)
+        };
+        // Made-up tokens share the bucket of the connection they arrive on
+        assert_eq!(peer("Bearer s3cret"), peer("Bearer an0ther"));
+        assert_eq!(quota_key(&peer("Bearer s3cret")), "peer:192.0.2.7");
+
+        let mut request = Request::new(());
+        request.extensions_mut().insert(TokenClaims {
+            iss: "guardian".into(),
+            sub: "ci".into(),
+            jti: "tok-1".into(),
+            iat: 0,
+            nbf: 0,
+            exp: 0,
+            scopes: vec!["operator".into()],
+        });
+        let principal = client_principal(&request);
+        assert_eq!(quota_key(&principal), "token:tok-1");
+        assert_eq!(principal.name, "service:ci");
+    }
+}
@@ -0,0 +1,878 @@
+use std::collections::HashMap;
+use std::convert::Infallible;
+
+use axum::{
+    extract::{FromRequestParts, Path, Query, State},
+    http::{request::Parts, HeaderMap, StatusCode},
+    Json,
+};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+use guardian_proto::guardian_service_server::GuardianService as _;
+use crate::api::grpc::security_service::{self as security_proto, security_service_server::SecurityService as _};
+use crate::proto::ml::{self as ml_proto, MLServiceServer as _};
+use crate::core::status::{status_service, ComponentState, StatusReport};
+use crate::core::guardian::TenantContext;
+use crate::security::auth::TokenClaims;
+use crate::security::pipeline_latency::{pipeline_latency, LatencySnapshot};
+
+use super::{RestError, RestState};
+
+// Constants for header forwarding
+const FORWARDED_HEADERS: [&str; 3] = ["authorization", "x-correlation-id", "x-request-id"];
+
+type RestResult<T> = Result<Json<T>, RestError>;
+
+/// Headers and authorization results a REST call hands on to the gRPC service it mirrors
+#[derive(Debug, Default)]
+pub(crate) struct CallContext {
+    headers: HeaderMap,
+    /// Claims of the API token validated by the gateway
+    claims: Option<TokenClaims>,
+    /// Tenant resolved by the gateway's RBAC check
+    tenant: Option<TenantContext>,
+}
+
+#[axum::async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for CallContext {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(Self {
+            headers: parts.headers.clone(),
+            claims: parts.extensions.get::<TokenClaims>().cloned(),
+            tenant: parts.extensions.get::<TenantContext>().cloned(),
+        })
+    }
+}
+
+/// Wraps a payload in a gRPC request, forwarding auth and tracing headers as metadata
+/// and the gateway's authorization results as extensions, as the gRPC layers would set them
+fn grpc_request<T>(call: &CallContext, message: T) -> tonic::Request<T> {
+    let mut request = tonic::Request::new(message);
+    for name in FORWARDED_HEADERS {
+        if let Some(value) = call.headers.get(name).and_then(|v| v.to_str().ok()) {
+            if let Ok(value) = value.parse() {
+                request.metadata_mut().insert(name, value);
+            }
+        }
+    }
+    if let Some(claims) = &call.claims {
+        request.extensions_mut().insert(claims.clone());
+    }
+    if let Some(tenant) = &call.tenant {
+        request.extensions_mut().insert(tenant.clone());
+    }
+    request
+}
+
+fn to_rfc3339(timestamp: Option<prost_types::Timestamp>) -> Option<String> {
+    timestamp
+        .and_then(|t| chrono::DateTime::<chrono::Utc>::from_timestamp(t.seconds, t.nanos as u32))
+        .map(|t| t.to_rfc3339())
+}
+
+fn decode_base64(field: &str, raw: &str) -> Result<Vec<u8>, RestError> {
+    BASE64
+        .decode(raw)
+        .map_err(|_| RestError(tonic::Status::invalid_argument(format!("{} must be base64 encoded", field))))
+}
+
+// ---------------------------------------------------------------------------
+// GuardianService
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct SystemStatusDto {
+    pub health: i32,
+    pub cpu_usage: f64,
+    pub memory_usage: f64,
+    pub active_threats: u32,
+    pub last_update: i64,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct ExecuteResponseDto {
+    pub action: String,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct ExecuteResponseResultDto {
+    pub success: bool,
+    pub message: String,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct ListOperationsQuery {
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct OperationDto {
+    pub operation_id: String,
+    pub kind: String,
+    pub description: String,
+    pub status: String,
+    pub progress_percent: f32,
+    pub cancel_requested: bool,
+    pub error_message: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+impl From<guardian_proto::Operation> for OperationDto {
+    fn from(operation: guardian_proto::Operation) -> Self {
+        let status = match guardian_proto::OperationStatus::from_i32(operation.status) {
+            Some(guardian_proto::OperationStatus::Running) => "running",
+            Some(guardian_proto::OperationStatus::Completed) => "completed",
+            Some(guardian_proto::OperationStatus::Failed) => "failed",
+            Some(guardian_proto::OperationStatus::Cancelled) => "cancelled",
+            _ => "unknown",
+        };
+
+        Self {
+            operation_id: operation.operation_id,
+            kind: operation.kind,
+            description: operation.description,
+            status: status.to_string(),
+            progress_percent: operation.progress_percent,
+            cancel_requested: operation.cancel_requested,
+            error_message: Some(operation.error_message).filter(|e| !e.is_empty()),
+            created_at: to_rfc3339(operation.created_at),
+            updated_at: to_rfc3339(operation.updated_at),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct PostureFactorDto {
+    pub name: String,
+    pub score: u32,
+    pub weight: f64,
+    pub detail: String,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct PostureDto {
+    pub score: u32,
+    pub computed_at: Option<String>,
+    pub factors: Vec<PostureFactorDto>,
+}
+
+impl From<guardian_proto::PostureScore> for PostureDto {
+    fn from(posture: guardian_proto::PostureScore) -> Self {
+        Self {
+            score: posture.score,
+            computed_at: to_rfc3339(posture.computed_at),
+            factors: posture
+                .factors
+                .into_iter()
+                .map(|f| PostureFactorDto {
+                    name: f.name,
+                    score: f.score,
+                    weight: f.weight,
+                    detail: f.detail,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct PostureHistoryQuery {
+    /// RFC 3339 start of the range, a week before `end` by default
+    pub start: Option<String>,
+    /// RFC 3339 end of the range, now by default
+    pub end: Option<String>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct PosturePointDto {
+    pub at: Option<String>,
+    pub score: u32,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct PostureHistoryDto {
+    pub points: Vec<PosturePointDto>,
+    /// Points of each contributing factor, by factor name
+    pub factors: HashMap<String, Vec<PosturePointDto>>,
+}
+
+impl From<guardian_proto::PostureHistory> for PostureHistoryDto {
+    fn from(history: guardian_proto::PostureHistory) -> Self {
+        let points = |points: Vec<guardian_proto::PosturePoint>| {
+            points
+                .into_iter()
+                .map(|p| PosturePointDto { at: to_rfc3339(p.at), score: p.score })
+                .collect::<Vec<_>>()
+        };
+        Self {
+            points: points(history.points),
+            factors: history.factors.into_iter().map(|f| (f.name, points(f.points))).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct StateTransitionsQuery {
+    /// RFC 3339 centre of the window, now by default
+    pub around: Option<String>,
+    /// Seconds either side of `around`, 900 by default
+    pub window_seconds: Option<u32>,
+    /// Centres the window on this incident's opening instead of `around`
+    pub incident_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct StateTransitionDto {
+    pub sequence: u64,
+    pub at: Option<String>,
+    pub from_state: i32,
+    pub to_state: i32,
+    pub cause: String,
+    pub cause_detail: String,
+    pub inputs: HashMap<String, f64>,
+}
+
+impl From<guardian_proto::StateTransition> for StateTransitionDto {
+    fn from(transition: guardian_proto::StateTransition) -> Self {
+        Self {
+            sequence: transition.sequence,
+            at: to_rfc3339(transition.at),
+            from_state: transition.from_state,
+            to_state: transition.to_state,
+            cause: transition.cause,
+            cause_detail: transition.cause_detail,
+            inputs: transition.inputs,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct CommandAuditQueryParams {
+    pub principal: Option<String>,
+    /// Matches commands starting with this prefix
+    pub command: Option<String>,
+    /// RFC 3339 lower bound on invocation time
+    pub since: Option<String>,
+    /// Only allowed invocations of commands that can change state
+    pub changes_only: Option<bool>,
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct CommandAuditEntryDto {
+    pub event_id: String,
+    pub at: Option<String>,
+    pub interface: String,
+    pub principal: String,
+    pub command: String,
+    pub args: HashMap<String, String>,
+    pub outcome: String,
+    pub error: String,
+    pub duration_ms: f64,
+    pub change: bool,
+}
+
+impl From<guardian_proto::CommandAuditEntry> for CommandAuditEntryDto {
+    fn from(entry: guardian_proto::CommandAuditEntry) -> Self {
+        Self {
+            event_id: entry.event_id,
+            at: to_rfc3339(entry.at),
+            interface: entry.interface,
+            principal: entry.principal,
+            command: entry.command,
+            args: entry.args,
+            outcome: entry.outcome,
+            error: entry.error,
+            duration_ms: entry.duration_ms,
+            change: entry.change,
+        }
+    }
+}
+
+fn parse_rfc3339(field: &str, raw: Option<&str>) -> Result<Option<prost_types::Timestamp>, RestError> {
+    raw.map(|raw| {
+        chrono::DateTime::parse_from_rfc3339(raw)
+            .map(|t| prost_types::Timestamp {
+                seconds: t.timestamp(),
+                nanos: t.timestamp_subsec_nanos() as i32,
+            })
+            .map_err(|_| RestError(tonic::Status::invalid_argument(format!("{} must be an RFC 3339 timestamp", field))))
+    })
+    .transpose()
+}
+
+/// GET /api/v1/system/status
+#[instrument(skip(state, call))]
+pub(crate) async fn get_system_status(
+    State(state): State<RestState>,
+    call: CallContext,
+) -> RestResult<SystemStatusDto> {
+    let status = state
+        .guardian_service
+        .get_system_status(grpc_request(&call, guardian_proto::Empty {}))
+        .await?
+        .into_inner();
+
+    Ok(Json(SystemStatusDto {
+        health: status.health,
+        cpu_usage: status.cpu_usage,
+        memory_usage: status.memory_usage,
+        active_threats: status.active_threats,
+        last_update: status.last_update,
+    }))
+}
+
+/// GET /api/v1/system/latency
+#[instrument]
+pub(crate) async fn get_detection_latency() -> Json<LatencySnapshot> {
+    Json(pipeline_latency().snapshot())
+}
+
+/// GET /api/v1/health
+///
+/// Answers 503 while any component is unhealthy, so load balancers and probes need not parse the body.
+#[instrument]
+pub(crate) async fn get_status_report() -> (StatusCode, Json<StatusReport>) {
+    let report = status_service().report().await;
+    let code = if report.overall == ComponentState::Unhealthy { StatusCode::SERVICE_UNAVAILABLE } else { StatusCode::OK };
+    (code, Json(report))
+}
+
+/// POST /api/v1/responses
+#[instrument(skip(state, call, body))]
+pub(crate) async fn execute_system_response(
+    State(state): State<RestState>,
+    call: CallContext,
+    Json(body): Json<ExecuteResponseDto>,
+) -> RestResult<ExecuteResponseResultDto> {
+    let request = guardian_proto::ExecuteResponseRequest { action: body.action };
+    let result = state
+        .guardian_service
+        .execute_response(grpc_request(&call, request))
+        .await?
+        .into_inner();
+
+    Ok(Json(ExecuteResponseResultDto {
+        success: result.success,
+        message: result.message,
+    }))
+}
+
+/// GET /api/v1/operations?status=running
+#[instrument(skip(state, call))]
+pub(crate) async fn list_operations(
+    State(state): State<RestState>,
+    call: CallContext,
+    Query(query): Query<ListOperationsQuery>,
+) -> RestResult<Vec<OperationDto>> {
+    let status_filter = match query.status.as_deref() {
+        None => guardian_proto::OperationStatus::Unknown,
+        Some("running") => guardian_proto::OperationStatus::Running,
+        Some("completed") => guardian_proto::OperationStatus::Completed,
+        Some("failed") => guardian_proto::OperationStatus::Failed,
+        Some("cancelled") => guardian_proto::OperationStatus::Cancelled,
+        Some(other) => {
+            return Err(RestError(tonic::Status::invalid_argument(format!(
+                "Invalid status filter: {}",
+                other
+            ))))
+        }
+    };
+
+    let request = guardian_proto::ListOperationsRequest { status_filter: status_filter as i32 };
+    let response = state
+        .guardian_service
+        .list_operations(grpc_request(&call, request))
+        .await?
+        .into_inner();
+
+    Ok(Json(response.operations.into_iter().map(OperationDto::from).collect()))
+}
+
+/// GET /api/v1/operations/:operation_id
+#[instrument(skip(state, call))]
+pub(crate) async fn get_operation(
+    State(state): State<RestState>,
+    call: CallContext,
+    Path(operation_id): Path<String>,
+) -> RestResult<OperationDto> {
+    let request = guardian_proto::OperationRequest { operation_id };
+    let operation = state
+        .guardian_service
+        .get_operation(grpc_request(&call, request))
+        .await?
+        .into_inner();
+
+    Ok(Json(operation.into()))
+}
+
+/// POST /api/v1/operations/:operation_id/cancel
+#[instrument(skip(state, call))]
+pub(crate) async fn cancel_operation(
+    State(state): State<RestState>,
+    call: CallContext,
+    Path(operation_id): Path<String>,
+) -> RestResult<OperationDto> {
+    let request = guardian_proto::OperationRequest { operation_id };
+    let operation = state
+        .guardian_service
+        .cancel_operation(grpc_request(&call, request))
+        .await?
+        .into_inner();
+
+    Ok(Json(operation.into()))
+}
+
+/// GET /api/v1/posture
+#[instrument(skip(state, call))]
+pub(crate) async fn get_posture(
+    State(state): State<RestState>,
+    call: CallContext,
+) -> RestResult<PostureDto> {
+    let posture = state
+        .guardian_service
+        .get_posture(grpc_request(&call, guardian_proto::Empty {}))
+        .await?
+        .into_inner();
+
+    Ok(Json(posture.into()))
+}
+
+/// GET /api/v1/posture/history?start=2024-01-01T00:00:00Z
+#[instrument(skip(state, call))]
+pub(crate) async fn get_posture_history(
+    State(state): State<RestState>,
+    call: CallContext,
+    Query(query): Query<PostureHistoryQuery>,
+) -> RestResult<PostureHistoryDto> {
+    let request = guardian_proto::PostureHistoryRequest {
+        start_time: parse_rfc3339("start", query.start.as_deref())?,
+        end_time: parse_rfc3339("end", query.end.as_deref())?,
+    };
+    let history = state
+        .guardian_service
+        .get_posture_history(grpc_request(&call, request))
+        .await?
+        .into_inner();
+
+    Ok(Json(history.into()))
+}
+
+/// GET /api/v1/state/transitions?incident_id=...
+#[instrument(skip(state, call))]
+pub(crate) async fn list_state_transitions(
+    State(state): State<RestState>,
+    call: CallContext,
+    Query(query): Query<StateTransitionsQuery>,
+) -> RestResult<Vec<StateTransitionDto>> {
+    let request = guardian_proto::StateTransitionsRequest {
+        around: parse_rfc3339("around", query.around.as_deref())?,
+        window_seconds: query.window_seconds.unwrap_or_default(),
+        incident_id: query.incident_id.unwrap_or_default(),
+    };
+    let response = state
+        .guardian_service
+        .list_state_transitions(grpc_request(&call, request))
+        .await?
+        .into_inner();
+
+    Ok(Json(response.transitions.into_iter().map(Into::into).collect()))
+}
+
+/// GET /api/v1/audit/commands?principal=...&changes_only=true
+#[instrument(skip(state, call))]
+pub(crate) async fn list_command_audit(
+    State(state): State<RestState>,
+    call: CallContext,
+    Query(query): Query<CommandAuditQueryParams>,
+) -> RestResult<Vec<CommandAuditEntryDto>> {
+    let request = guardian_proto::CommandAuditRequest {
+        principal: query.principal.unwrap_or_default(),
+        command: query.command.unwrap_or_default(),
+        since: parse_rfc3339("since", query.since.as_deref())?,
+        changes_only: query.changes_only.unwrap_or_default(),
+        limit: query.limit.unwrap_or_default(),
+    };
+    let response = state
+        .guardian_service
+        .list_command_audit(grpc_request(&call, request))
+        .await?
+        .into_inner();
+
+    Ok(Json(response.entries.into_iter().map(Into::into).collect()))
+}
+
+// ---------------------------------------------------------------------------
+// SecurityService
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub(crate) struct ThreatAlertDto {
+    pub alert_id: String,
+    pub severity: i32,
+    pub threat_type: i32,
+    pub confidence: f32,
+    pub timestamp: Option<String>,
+    #[serde(default)]
+    pub details: HashMap<String, String>,
+}
+
+impl From<security_proto::ThreatAlert> for ThreatAlertDto {
+    fn from(alert: security_proto::ThreatAlert) -> Self {
+        Self {
+            alert_id: alert.alert_id,
+            severity: alert.severity,
+            threat_type: alert.threat_type,
+            confidence: alert.confidence,
+            timestamp: to_rfc3339(alert.timestamp),
+            details: alert.details,
+        }
+    }
+}
+
+impl From<ThreatAlertDto> for security_proto::ThreatAlert {
+    fn from(alert: ThreatAlertDto) -> Self {
+        let timestamp = alert
+            .timestamp
+            .as_deref()
+            .and_then(|t| chrono::DateTime::parse_from_rfc3339(t).ok())
+            .map(|t| prost_types::Timestamp {
+                seconds: t.timestamp(),
+                nanos: t.timestamp_subsec_nanos() as i32,
+            });
+
+        Self {
+            alert_id: alert.alert_id,
+            severity: alert.severity,
+            threat_type: alert.threat_type,
+            confidence: alert.confidence,
+            timestamp,
+            details: alert.details,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct SecurityEventDto {
+    pub event_id: String,
+    pub event_type: i32,
+    pub severity: i32,
+    pub timestamp: Option<String>,
+    pub details: HashMap<String, String>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct SecurityResponseDto {
+    pub response_id: String,
+    pub alert_id: String,
+    pub action_type: i32,
+    pub status: i32,
+    pub timestamp: Option<String>,
+}
+
+/// POST /api/v1/security/threats/detect
+#[instrument(skip(state, call))]
+pub(crate) async fn detect_threats(
+    State(state): State<RestState>,
+    call: CallContext,
+) -> RestResult<ThreatAlertDto> {
+    let alert = state
+        .security_service
+        .detect_threats(grpc_request(&call, ()))
+        .await?
+        .into_inner();
+
+    Ok(Json(alert.into()))
+}
+
+/// POST /api/v1/security/anomalies/detect
+#[instrument(skip(state, call))]
+pub(crate) async fn detect_anomalies(
+    State(state): State<RestState>,
+    call: CallContext,
+) -> RestResult<SecurityEventDto> {
+    let event = state
+        .security_service
+        .detect_anomalies(grpc_request(&call, ()))
+        .await?
+        .into_inner();
+
+    Ok(Json(SecurityEventDto {
+        event_id: event.event_id,
+        event_type: event.event_type,
+        severity: event.severity,
+        timestamp: to_rfc3339(event.timestamp),
+        details: event.details,
+    }))
+}
+
+/// POST /api/v1/security/responses
+#[instrument(skip(state, call, body))]
+pub(crate) async fn execute_security_response(
+    State(state): State<RestState>,
+    call: CallContext,
+    Json(body): Json<ThreatAlertDto>,
+) -> RestResult<SecurityResponseDto> {
+    let response = state
+        .security_service
+        .execute_response(grpc_request(&call, body.into()))
+        .await?
+        .into_inner();
+
+    Ok(Json(SecurityResponseDto {
+        response_id: response.response_id,
+        alert_id: response.alert_id,
+        action_type: response.action_type,
+        status: response.status,
+        timestamp: to_rfc3339(response.timestamp),
+    }))
+}
+
+// ---------------------------------------------------------------------------
+// MLService
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct InferenceRequestDto {
+    pub model_id: String,
+    /// Base64 encoded model input
+    pub input_data: String,
+    #[serde(default)]
+    pub parameters: HashMap<String, String>,
+    #[serde(default)]
+    pub return_features: bool,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct InferenceResultDto {
+    pub result_id: String,
+    pub model_id: String,
+    /// Base64 encoded prediction
+    pub prediction: String,
+    pub confidence: f32,
+    pub timestamp: Option<String>,
+    pub features: HashMap<String, String>,
+    pub inference_time_us: i64,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct ValidationConfigDto {
+    pub validation_split: f32,
+    pub minimum_accuracy: f32,
+    #[serde(default)]
+    pub validation_iterations: i32,
+    #[serde(default)]
+    pub validation_metrics: Vec<String>,
+}
+
+impl From<ValidationConfigDto> for ml_proto::ValidationConfig {
+    fn from(config: ValidationConfigDto) -> Self {
+        Self {
+            validation_split: config.validation_split,
+            minimum_accuracy: config.minimum_accuracy,
+            validation_iterations: config.validation_iterations,
+            validation_metrics: config.validation_metrics,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct TrainingRequestDto {
+    pub model_id: String,
+    pub model_type: i32,
+    #[serde(default)]
+    pub hyperparameters: HashMap<String, String>,
+    pub dataset_path: String,
+    pub validation_config: Option<ValidationConfigDto>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct TrainingJobDto {
+    pub job_id: String,
+    pub model_id: String,
+    pub status: i32,
+    pub progress: f32,
+    pub start_time: Option<String>,
+    pub estimated_completion: Option<String>,
+    pub validation_errors: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct ModelStatusQuery {
+    #[serde(default)]
+    pub include_metrics: bool,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct ModelUpdateDto {
+    /// Base64 encoded model artifact
+    pub model_data: String,
+    pub version: String,
+    pub validation_config: Option<ValidationConfigDto>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct ModelDto {
+    pub model_id: String,
+    pub version: String,
+    pub model_type: i32,
+    pub status: i32,
+    pub accuracy: f32,
+    pub last_updated: Option<String>,
+    pub performance_metrics: HashMap<String, f32>,
+    /// Hex encoded integrity hash
+    pub model_hash: String,
+}
+
+impl From<ml_proto::Model> for ModelDto {
+    fn from(model: ml_proto::Model) -> Self {
+        Self {
+            model_id: model.model_id,
+            version: model.version,
+            model_type: model.model_type,
+            status: model.status,
+            accuracy: model.accuracy,
+            last_updated: to_rfc3339(model.last_updated),
+            performance_metrics: model.performance_metrics,
+            model_hash: model.model_hash.iter().map(|b| format!("{:02x}", b)).collect(),
+        }
+    }
+}
+
+/// POST /api/v1/ml/inference
+#[instrument(skip(state, call, body))]
+pub(crate) async fn inference_request(
+    State(state): State<RestState>,
+    call: CallContext,
+    Json(body): Json<InferenceRequestDto>,
+) -> RestResult<InferenceResultDto> {
+    let request = ml_proto::ModelInferenceRequest {
+        model_id: body.model_id,
+        input_data: decode_base64("input_data", &body.input_data)?,
+        parameters: body.parameters,
+        return_features: body.return_features,
+    };
+
+    let result = state
+        .ml_service
+        .inference_request(grpc_request(&call, request))
+        .await?
+        .into_inner();
+
+    Ok(Json(InferenceResultDto {
+        result_id: result.result_id,
+        model_id: result.model_id,
+        prediction: BASE64.encode(&result.prediction),
+        confidence: result.confidence,
+        timestamp: to_rfc3339(result.timestamp),
+        features: result.features.into_iter().map(|(k, v)| (k, BASE64.encode(v))).collect(),
+        inference_time_us: result.inference_time,
+    }))
+}
+
+/// POST /api/v1/ml/training
+#[instrument(skip(state, call, body))]
+pub(crate) async fn train_model(
+    State(state): State<RestState>,
+    call: CallContext,
+    Json(body): Json<TrainingRequestDto>,
+) -> RestResult<TrainingJobDto> {
+    let request = ml_proto::TrainingRequest {
+        model_id: body.model_id,
+        model_type: body.model_type,
+        hyperparameters: body.hyperparameters,
+        dataset_path: body.dataset_path,
+        validation_config: body.validation_config.map(Into::into),
+    };
+
+    let job = state
+        .ml_service
+        .train_model(grpc_request(&call, request))
+        .await?
+        .into_inner();
+
+    Ok(Json(TrainingJobDto {
+        job_id: job.job_id,
+        model_id: job.model_id,
+        status: job.status,
+        progress: job.progress,
+        start_time: to_rfc3339(job.start_time),
+        estimated_completion: to_rfc3339(job.estimated_completion),
+        validation_errors: job.validation_errors,
+    }))
+}
+
+/// GET /api/v1/ml/models/:model_id
+#[instrument(skip(state, call))]
+pub(crate) async fn get_model_status(
+    State(state): State<RestState>,
+    call: CallContext,
+    Path(model_id): Path<String>,
+    Query(query): Query<ModelStatusQuery>,
+) -> RestResult<ModelDto> {
+    let request = ml_proto::ModelStatusRequest {
+        model_id,
+        include_metrics: query.include_metrics,
+    };
+
+    let model = state
+        .ml_service
+        .get_model_status(grpc_request(&call, request))
+        .await?
+        .into_inner();
+
+    Ok(Json(model.into()))
+}
+
+/// PUT /api/v1/ml/models/:model_id
+#[instrument(skip(state, call, body))]
+pub(crate) async fn update_model(
+    State(state): State<RestState>,
+    call: CallContext,
+    Path(model_id): Path<String>,
+    Json(body): Json<ModelUpdateDto>,
+) -> RestResult<ModelDto> {
+    let request = ml_proto::ModelUpdateRequest {
+        model_id,
+        model_data: decode_base64("model_data", &body.model_data)?,
+        version: body.version,
+        validation_config: body.validation_config.map(Into::into),
+    };
+
+    let model = state
+        .ml_service
+        .update_model(grpc_request(&call, request))
+        .await?
+        .into_inner();
+
+    Ok(Json(model.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grpc_request_forwards_auth_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert("authorization", "Bearer token".parse().unwrap());
+        headers.insert("cookie", "session=abc".parse().unwrap());
+
+        let call = CallContext { headers, ..Default::default() };
+        let request = grpc_request(&call, ());
+        assert_eq!(request.metadata().get("authorization").unwrap(), "Bearer token");
+        assert!(request.metadata().get("cookie").is_none());
+    }
+
+    #[test]
+    fn test_invalid_base64_is_bad_request() {
+        let err = decode_base64("input_data", "not base64!").unwrap_err();
+        assert_eq!(err.0.code(), tonic::Code::InvalidArgument);
+    }
+}
@@ -0,0 +1,595 @@
+use std::{
+    collections::BTreeMap,
+    net::SocketAddr,
+    sync::Arc,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use axum::{
+    extract::{connect_info::Connected, ConnectInfo, Query, State},
+    http::{HeaderValue, Method, Request, StatusCode},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::{get, post, put},
+    Json, Router,
+};
+use governor::DefaultDirectRateLimiter;
+use metrics::{counter, histogram};
+use schemars::JsonSchema;
+use serde::Serialize;
+use tokio::{net::TcpStream, sync::RwLock};
+use tokio_rustls::server::TlsStream;
+use tonic::transport::server::{TcpConnectInfo, TlsConnectInfo};
+use tracing::{error, info, instrument};
+
+use crate::api::CircuitBreaker;
+use crate::api::quota::{client_principal, client_quotas, retry_after_secs};
+use crate::api::grpc::{GuardianService, GuardianSecurityService, MLService, TlsConfig, TlsReloader};
+use crate::api::grpc::error_status::error_code_of;
+use crate::security::auth::bearer_claims;
+use crate::security::command_audit::{command_audit, AccessDecision, CommandInvocation, Interface};
+use crate::security::rbac::{rbac, rpc_permission, Principal};
+use crate::security::remote_assistance::{
+    peer_fingerprint, remote_assistance, AccessDecision as AssistanceDecision, RecordedRequest,
+    REMOTE_ASSISTANCE_HEADER,
+};
+use crate::utils::correlation::CorrelationLayer;
+use crate::utils::telemetry::TraceContextLayer;
+use crate::utils::error::GuardianError;
+
+mod handlers;
+mod openapi;
+
+use handlers::{
+    CommandAuditEntryDto, CommandAuditQueryParams, ExecuteResponseDto, ExecuteResponseResultDto, InferenceRequestDto,
+    InferenceResultDto, ListOperationsQuery, ModelDto, ModelStatusQuery, ModelUpdateDto, OperationDto, PostureDto,
+    PostureHistoryDto, PostureHistoryQuery, SecurityEventDto, SecurityResponseDto, StateTransitionDto,
+    StateTransitionsQuery, SystemStatusDto, ThreatAlertDto, TrainingJobDto, TrainingRequestDto,
+};
+use openapi::{Endpoint, OPENAPI_PATH};
+
+// Constants for REST gateway configuration
+const DEFAULT_REST_PORT: u16 = 8080;
+const DEFAULT_MAX_BODY_BYTES: usize = 4 * 1024 * 1024;
+const REST_API_PREFIX: &str = "/api/v1";
+
+/// REST gateway configuration
+#[derive(Debug, Clone)]
+pub struct RestConfig {
+    pub enabled: bool,
+    pub port: u16,
+    pub request_timeout: Duration,
+    pub max_body_bytes: usize,
+    /// Certificate material, shared with the gRPC server; client certificates are required when it names a CA
+    pub tls_config: Option<TlsConfig>,
+}
+
+impl Default for RestConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: DEFAULT_REST_PORT,
+            request_timeout: Duration::from_secs(30),
+            max_body_bytes: DEFAULT_MAX_BODY_BYTES,
+            tls_config: None,
+        }
+    }
+}
+
+/// Shared state handed to every REST handler
+#[derive(Clone)]
+pub(crate) struct RestState {
+    pub(crate) guardian_service: Arc<GuardianService>,
+    pub(crate) security_service: Arc<GuardianSecurityService>,
+    pub(crate) ml_service: Arc<MLService>,
+    rate_limiter: Arc<DefaultDirectRateLimiter>,
+    circuit_breaker: Arc<RwLock<CircuitBreaker>>,
+}
+
+/// JSON error body returned for failed requests
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct ErrorBody {
+    pub code: String,
+    pub message: String,
+    /// Stable Guardian error code, e.g. `GRD-5500`, when a Guardian error caused the failure
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_code: Option<String>,
+    /// Correlation ID to quote when matching the failure with server logs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub correlation_id: Option<String>,
+}
+
+/// Error type converting gRPC status codes into HTTP responses
+#[derive(Debug)]
+pub(crate) struct RestError(pub tonic::Status);
+
+impl From<tonic::Status> for RestError {
+    fn from(status: tonic::Status) -> Self {
+        Self(status)
+    }
+}
+
+impl IntoResponse for RestError {
+    fn into_response(self) -> Response {
+        let status = http_status(self.0.code());
+        let (error_code, correlation_id) = match error_code_of(&self.0) {
+            Some((code, correlation_id)) => (Some(code), Some(correlation_id)),
+            None => (None, None),
+        };
+        let body = ErrorBody {
+            code: format!("{:?}", self.0.code()),
+            message: self.0.message().to_string(),
+            error_code,
+            correlation_id,
+        };
+        (status, Json(body)).into_response()
+    }
+}
+
+/// HTTP/JSON gateway mirroring the Guardian, Security and ML gRPC services
+pub struct RestGateway {
+    config: RestConfig,
+    state: RestState,
+}
+
+impl RestGateway {
+    /// Creates a gateway sharing the gRPC services, rate limiter and circuit breaker
+    pub fn new(
+        config: RestConfig,
+        guardian_service: Arc<GuardianService>,
+        security_service: Arc<GuardianSecurityService>,
+        ml_service: Arc<MLService>,
+        rate_limiter: Arc<DefaultDirectRateLimiter>,
+        circuit_breaker: Arc<RwLock<CircuitBreaker>>,
+    ) -> Self {
+        Self {
+            config,
+            state: RestState {
+                guardian_service,
+                security_service,
+                ml_service,
+                rate_limiter,
+                circuit_breaker,
+            },
+        }
+    }
+
+    /// Builds the REST router with all mirrored endpoints and their OpenAPI description
+    pub fn router(&self) -> Router {
+        let endpoints = endpoints();
+        let document = Arc::new(openapi::document(REST_API_PREFIX, &endpoints));
+
+        // Each route is authorized as the gRPC method it mirrors, so one policy covers both APIs
+        let api = endpoints
+            .iter()
+            .fold(Router::new(), |api, endpoint| {
+                let rpc_path: Arc<str> = endpoint.rpc_path().into();
+                let route = endpoint
+                    .method_router()
+                    .route_layer(middleware::from_fn_with_state(rpc_path, authorize_request));
+                api.route(endpoint.path(), route)
+            })
+            .route(OPENAPI_PATH, get(move || async move { Json(document.as_ref().clone()) }));
+
+        Router::new()
+            .nest(REST_API_PREFIX, api)
+            .layer(middleware::from_fn_with_state(self.state.clone(), guard_request))
+            .layer(tower_http::limit::RequestBodyLimitLayer::new(self.config.max_body_bytes))
+            .layer(tower_http::timeout::TimeoutLayer::new(self.config.request_timeout))
+            .layer(CorrelationLayer)
+            .layer(TraceContextLayer)
+            .with_state(self.state.clone())
+    }
+
+    /// Serves the REST gateway until the process shuts down
+    #[instrument(skip(self), fields(port = self.config.port))]
+    pub async fn start(&self) -> Result<(), GuardianError> {
+        let addr = SocketAddr::from(([0, 0, 0, 0], self.config.port));
+        info!(%addr, "Starting REST gateway");
+
+        // The gateway reaches the same services as the gRPC server, so the same profile requirement applies
+        let tls_config = self.config.tls_config.as_ref();
+        crate::config::active_profile().require_mtls(
+            "REST gateway",
+            tls_config.is_some(),
+            tls_config.map_or(false, |tls| tls.ca_cert_path.is_some()),
+        )?;
+
+        let served = match tls_config {
+            Some(tls_config) => {
+                let reloader = TlsReloader::load_http(tls_config.clone())?;
+                reloader.spawn_watcher();
+                let listener = tokio::net::TcpListener::bind(addr)
+                    .await
+                    .map_err(|e| gateway_error(format!("Failed to bind REST gateway to {}", addr), Box::new(e)))?;
+                axum::Server::builder(hyper::server::accept::from_stream(reloader.incoming(listener)))
+                    .serve(self.router().into_make_service_with_connect_info::<PeerCertificates>())
+                    .await
+            }
+            // Peer addresses key the quotas of callers without a certificate or valid token
+            None => {
+                axum::Server::bind(&addr)
+                    .serve(self.router().into_make_service_with_connect_info::<std::net::SocketAddr>())
+                    .await
+            }
+        };
+        served.map_err(|e| gateway_error("REST gateway terminated".into(), Box::new(e)))
+    }
+}
+
+/// Client certificates of a TLS connection, in the form tonic attaches them to gRPC requests
+#[derive(Clone)]
+struct PeerCertificates(TlsConnectInfo<TcpConnectInfo>);
+
+impl Connected<&TlsStream<TcpStream>> for PeerCertificates {
+    fn connect_info(target: &TlsStream<TcpStream>) -> Self {
+        Self(tonic::transport::server::Connected::connect_info(target))
+    }
+}
+
+/// Every mirrored endpoint, the single source for both routing and the OpenAPI document
+fn endpoints() -> Vec<Endpoint> {
+    vec![
+        // GuardianService
+        Endpoint::new(
+            Method::GET,
+            "/system/status",
+            "GuardianService",
+            "getSystemStatus",
+            "Current system health and resource usage",
+            || get(handlers::get_system_status),
+        )
+        .response::<SystemStatusDto>(),
+        Endpoint::new(
+            Method::GET,
+            "/system/latency",
+            "GuardianService",
+            "getDetectionLatency",
+            "Per-stage detection latency percentiles",
+            || get(handlers::get_detection_latency),
+        )
+        .response::<crate::security::pipeline_latency::LatencySnapshot>(),
+        Endpoint::new(
+            Method::GET,
+            "/health",
+            "GuardianService",
+            "getStatusReport",
+            "Versioned status of every component; 503 while any is unhealthy",
+            || get(handlers::get_status_report),
+        )
+        .response::<crate::core::status::StatusReport>(),
+        Endpoint::new(
+            Method::POST,
+            "/responses",
+            "GuardianService",
+            "executeSystemResponse",
+            "Executes a system response action",
+            || post(handlers::execute_system_response),
+        )
+        .body::<ExecuteResponseDto>()
+        .response::<ExecuteResponseResultDto>(),
+        Endpoint::new(
+            Method::GET,
+            "/operations",
+            "GuardianService",
+            "listOperations",
+            "Lists long-running operations",
+            || get(handlers::list_operations),
+        )
+        .query::<ListOperationsQuery>()
+        .response::<Vec<OperationDto>>(),
+        Endpoint::new(
+            Method::GET,
+            "/operations/:operation_id",
+            "GuardianService",
+            "getOperation",
+            "Fetches one long-running operation",
+            || get(handlers::get_operation),
+        )
+        .response::<OperationDto>(),
+        Endpoint::new(
+            Method::POST,
+            "/operations/:operation_id/cancel",
+            "GuardianService",
+            "cancelOperation",
+            "Requests cancellation of an operation",
+            || post(handlers::cancel_operation),
+        )
+        .response::<OperationDto>(),
+        Endpoint::new(
+            Method::GET,
+            "/posture",
+            "GuardianService",
+            "getPosture",
+            "Latest security posture score with contributing factors",
+            || get(handlers::get_posture),
+        )
+        .response::<PostureDto>(),
+        Endpoint::new(
+            Method::GET,
+            "/posture/history",
+            "GuardianService",
+            "getPostureHistory",
+            "Stored posture scores over a time range",
+            || get(handlers::get_posture_history),
+        )
+        .query::<PostureHistoryQuery>()
+        .response::<PostureHistoryDto>(),
+        Endpoint::new(
+            Method::GET,
+            "/state/transitions",
+            "GuardianService",
+            "listStateTransitions",
+            "Journaled system state transitions around a time or incident",
+            || get(handlers::list_state_transitions),
+        )
+        .query::<StateTransitionsQuery>()
+        .response::<Vec<StateTransitionDto>>(),
+        Endpoint::new(
+            Method::GET,
+            "/audit/commands",
+            "GuardianService",
+            "listCommandAudit",
+            "Audited CLI and API command invocations, newest first",
+            || get(handlers::list_command_audit),
+        )
+        .query::<CommandAuditQueryParams>()
+        .response::<Vec<CommandAuditEntryDto>>(),
+        // SecurityService
+        Endpoint::new(
+            Method::POST,
+            "/security/threats/detect",
+            "SecurityService",
+            "detectThreats",
+            "Runs threat detection",
+            || post(handlers::detect_threats),
+        )
+        .response::<ThreatAlertDto>(),
+        Endpoint::new(
+            Method::POST,
+            "/security/anomalies/detect",
+            "SecurityService",
+            "detectAnomalies",
+            "Runs anomaly detection",
+            || post(handlers::detect_anomalies),
+        )
+        .response::<SecurityEventDto>(),
+        Endpoint::new(
+            Method::POST,
+            "/security/responses",
+            "SecurityService",
+            "executeSecurityResponse",
+            "Responds to a threat alert",
+            || post(handlers::execute_security_response),
+        )
+        .body::<ThreatAlertDto>()
+        .response::<SecurityResponseDto>(),
+        // MLService
+        Endpoint::new(
+            Method::POST,
+            "/ml/inference",
+            "MLService",
+            "inferenceRequest",
+            "Runs inference on a model",
+            || post(handlers::inference_request),
+        )
+        .body::<InferenceRequestDto>()
+        .response::<InferenceResultDto>(),
+        Endpoint::new(
+            Method::POST,
+            "/ml/training",
+            "MLService",
+            "trainModel",
+            "Starts a training job",
+            || post(handlers::train_model),
+        )
+        .body::<TrainingRequestDto>()
+        .response::<TrainingJobDto>(),
+        Endpoint::new(
+            Method::GET,
+            "/ml/models/:model_id",
+            "MLService",
+            "getModelStatus",
+            "Fetches model status",
+            || get(handlers::get_model_status),
+        )
+        .query::<ModelStatusQuery>()
+        .response::<ModelDto>(),
+        Endpoint::new(
+            Method::PUT,
+            "/ml/models/:model_id",
+            "MLService",
+            "updateModel",
+            "Uploads a new model version",
+            || put(handlers::update_model),
+        )
+        .body::<ModelUpdateDto>()
+        .response::<ModelDto>(),
+    ]
+}
+
+/// Applies the shared rate limiter, API token validation, per-client quotas and circuit breaker
+/// to every REST request
+async fn guard_request<B>(
+    State(state): State<RestState>,
+    mut request: Request<B>,
+    next: Next<B>,
+) -> Response {
+    let start = Instant::now();
+
+    if state.rate_limiter.check().is_err() {
+        counter!("guardian.api.rest.rate_limited", 1);
+        return RestError(tonic::Status::resource_exhausted("Rate limit exceeded")).into_response();
+    }
+
+    // Certificate and token identities are resolved as for gRPC calls, so principals match across both APIs
+    if let Some(ConnectInfo(PeerCertificates(info))) = request.extensions().get::<ConnectInfo<PeerCertificates>>().cloned() {
+        request.extensions_mut().insert(info);
+    }
+    let authorization = request.headers().get(http::header::AUTHORIZATION).and_then(|value| value.to_str().ok());
+    match bearer_claims(authorization) {
+        Ok(Some(claims)) => {
+            request.extensions_mut().insert(claims);
+        }
+        Ok(None) => {}
+        Err(status) => {
+            counter!("guardian.api.rest.unauthenticated", 1);
+            return RestError(status).into_response();
+        }
+    }
+
+    let principal = client_principal(&request);
+    let command = format!("{} {}", request.method(), request.uri().path());
+    // Bodies are left to the handlers, so REST calls are audited with their query parameters only
+    let args = Query::<BTreeMap<String, String>>::try_from_uri(request.uri()).map(|q| q.0).unwrap_or_default();
+    if let Err(retry_after) = client_quotas().check(&principal) {
+        counter!("guardian.api.rest.client_quota_exceeded", 1);
+        let mut response = RestError(tonic::Status::resource_exhausted("Client quota exceeded")).into_response();
+        response.headers_mut().insert(http::header::RETRY_AFTER, retry_after_secs(retry_after).into());
+        return response;
+    }
+
+    if state.circuit_breaker.read().await.is_open().await {
+        counter!("guardian.api.rest.circuit_breaker_rejections", 1);
+        return RestError(tonic::Status::unavailable("Service circuit breaker is open")).into_response();
+    }
+
+    let mut response = next.run(request).await;
+
+    // Every caller sees the banner so operator UIs can surface the session
+    if let Some(banner) = remote_assistance().banners().first().and_then(|b| HeaderValue::from_str(b).ok()) {
+        response.headers_mut().insert(REMOTE_ASSISTANCE_HEADER, banner);
+    }
+
+    if response.status().is_server_error() {
+        state.circuit_breaker.read().await.record_failure().await;
+        error!(status = %response.status(), "REST request failed");
+    }
+
+    command_audit().record(CommandInvocation {
+        interface: Interface::Api,
+        command,
+        principal: principal.name,
+        args,
+        decision: if response.status() == StatusCode::FORBIDDEN { AccessDecision::Denied } else { AccessDecision::Allowed },
+        error: (!response.status().is_success()).then(|| response.status().to_string()),
+        duration: start.elapsed(),
+    });
+
+    histogram!("guardian.api.rest.request_duration", start.elapsed().as_secs_f64());
+    counter!("guardian.api.rest.requests", 1, "status" => response.status().as_u16().to_string());
+    response
+}
+
+/// Authorizes a routed request as the gRPC method it mirrors, as the gRPC server's remote
+/// assistance and RBAC layers would
+async fn authorize_request<B>(
+    State(rpc_path): State<Arc<str>>,
+    mut request: Request<B>,
+    next: Next<B>,
+) -> Response {
+    let assistance = remote_assistance();
+    let decision = match peer_fingerprint(&request) {
+        Some(fingerprint) => assistance.authorize(&fingerprint, &rpc_path),
+        None => AssistanceDecision::NotVendor,
+    };
+
+    // Vendor sessions are scoped by remote assistance rather than role bindings
+    let session_id = match decision {
+        AssistanceDecision::NotVendor => {
+            let engine = rbac();
+            let principal = Principal::from_request(&request);
+            if engine.authorize(&principal, &rpc_permission(&rpc_path)).is_err() {
+                return RestError(tonic::Status::permission_denied("Permission denied")).into_response();
+            }
+            request.extensions_mut().insert(engine.tenant_for(&principal));
+            return next.run(request).await;
+        }
+        AssistanceDecision::Denied(session_id) => {
+            assistance.record(recorded_request(session_id.unwrap_or_else(|| "none".into()), &rpc_path, Some(StatusCode::FORBIDDEN), None));
+            return RestError(tonic::Status::permission_denied("Outside remote assistance scope")).into_response();
+        }
+        AssistanceDecision::Allowed(session_id) => session_id,
+    };
+
+    let started = Instant::now();
+    let response = next.run(request).await;
+    assistance.record(recorded_request(session_id, &rpc_path, Some(response.status()), Some(started.elapsed())));
+    response
+}
+
+/// Session recording entry for a vendor request; `elapsed` is `None` for requests refused up front
+fn recorded_request(session_id: String, rpc_path: &str, status: Option<StatusCode>, elapsed: Option<Duration>) -> RecordedRequest {
+    RecordedRequest {
+        session_id,
+        at: SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or_default(),
+        path: rpc_path.to_string(),
+        allowed: elapsed.is_some(),
+        status: status.map(|status| status.as_u16()),
+        duration_ms: elapsed.map_or(0, |elapsed| elapsed.as_millis() as u64),
+        correlation_id: crate::utils::correlation::current_or_new().to_string(),
+    }
+}
+
+fn gateway_error(context: String, source: Box<dyn std::error::Error + Send + Sync>) -> GuardianError {
+    GuardianError::SystemError {
+        context,
+        source: Some(source),
+        severity: crate::utils::error::ErrorSeverity::High,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: crate::utils::error::ErrorCategory::System,
+        retry_count: 0,
+    }
+}
+
+/// Maps gRPC status codes to their conventional HTTP equivalents
+fn http_status(code: tonic::Code) -> StatusCode {
+    match code {
+        tonic::Code::Ok => StatusCode::OK,
+        tonic::Code::InvalidArgument | tonic::Code::OutOfRange => StatusCode::BAD_REQUEST,
+        tonic::Code::Unauthenticated => StatusCode::UNAUTHORIZED,
+        tonic::Code::PermissionDenied => StatusCode::FORBIDDEN,
+        tonic::Code::NotFound => StatusCode::NOT_FOUND,
+        tonic::Code::AlreadyExists | tonic::Code::Aborted => StatusCode::CONFLICT,
+        tonic::Code::FailedPrecondition => StatusCode::PRECONDITION_FAILED,
+        tonic::Code::ResourceExhausted => StatusCode::TOO_MANY_REQUESTS,
+        tonic::Code::Unimplemented => StatusCode::NOT_IMPLEMENTED,
+        tonic::Code::Unavailable => StatusCode::SERVICE_UNAVAILABLE,
+        tonic::Code::DeadlineExceeded => StatusCode::GATEWAY_TIMEOUT,
+        tonic::Code::Cancelled => StatusCode::REQUEST_TIMEOUT,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_mapping() {
+        assert_eq!(http_status(tonic::Code::NotFound), StatusCode::NOT_FOUND);
+        assert_eq!(http_status(tonic::Code::ResourceExhausted), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(http_status(tonic::Code::Unauthenticated), StatusCode::UNAUTHORIZED);
+        assert_eq!(http_status(tonic::Code::Internal), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[test]
+    fn test_rest_disabled_by_default() {
+        let config = RestConfig::default();
+        assert!(!config.enabled);
+        assert_eq!(config.port, DEFAULT_REST_PORT);
+        assert!(config.tls_config.is_none());
+    }
+
+    #[test]
+    fn test_endpoints_authorize_as_their_grpc_methods() {
+        let rpc_path = |path: &str| endpoints().into_iter().find(|e| e.path() == path).map(|e| e.rpc_path());
+        assert_eq!(rpc_path("/posture").as_deref(), Some("/guardian.core.v1.GuardianService/GetPosture"));
+        assert_eq!(rpc_path("/ml/training").as_deref(), Some("/guardian.ml.v1.MLService/TrainModel"));
+        assert_eq!(
+            rpc_path("/security/responses").as_deref(),
+            Some("/guardian.security.v1.SecurityService/ExecuteSecurityResponse")
+        );
+    }
+}
@@ -0,0 +1,277 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use axum::{http::Method, routing::MethodRouter};
+use schemars::{
+    gen::{SchemaGenerator, SchemaSettings},
+    schema::{Schema, SchemaObject},
+    JsonSchema,
+};
+use serde_json::{json, Map, Value};
+
+use super::{http_status, ErrorBody, RestState};
+
+// Constants for the generated API description
+pub(crate) const OPENAPI_PATH: &str = "/openapi.json";
+const OPENAPI_VERSION: &str = "3.1.0";
+const API_TITLE: &str = "Guardian API";
+const SCHEMA_PREFIX: &str = "#/components/schemas/";
+const ERROR_RESPONSE_REF: &str = "#/components/responses/Error";
+
+/// gRPC codes surfaced by the gateway, documented through their HTTP mapping
+const ERROR_CODES: [tonic::Code; 10] = [
+    tonic::Code::InvalidArgument,
+    tonic::Code::Unauthenticated,
+    tonic::Code::PermissionDenied,
+    tonic::Code::NotFound,
+    tonic::Code::AlreadyExists,
+    tonic::Code::FailedPrecondition,
+    tonic::Code::ResourceExhausted,
+    tonic::Code::Unavailable,
+    tonic::Code::DeadlineExceeded,
+    tonic::Code::Internal,
+];
+
+type SchemaFn = fn(&mut SchemaGenerator) -> Schema;
+
+/// A REST endpoint mirroring one gRPC method, with the types it exchanges
+pub(crate) struct Endpoint {
+    method: Method,
+    path: &'static str,
+    service: &'static str,
+    operation_id: &'static str,
+    summary: &'static str,
+    route: fn() -> MethodRouter<RestState>,
+    query: Option<SchemaFn>,
+    body: Option<SchemaFn>,
+    response: Option<SchemaFn>,
+}
+
+impl Endpoint {
+    pub(crate) fn new(
+        method: Method,
+        path: &'static str,
+        service: &'static str,
+        operation_id: &'static str,
+        summary: &'static str,
+        route: fn() -> MethodRouter<RestState>,
+    ) -> Self {
+        Self {
+            method,
+            path,
+            service,
+            operation_id,
+            summary,
+            route,
+            query: None,
+            body: None,
+            response: None,
+        }
+    }
+
+    /// Documents the query string fields of `T`
+    pub(crate) fn query<T: JsonSchema>(mut self) -> Self {
+        self.query = Some(inline_schema::<T>);
+        self
+    }
+
+    /// Documents a JSON request body of type `T`
+    pub(crate) fn body<T: JsonSchema>(mut self) -> Self {
+        self.body = Some(schema_ref::<T>);
+        self
+    }
+
+    /// Documents a JSON response body of type `T`
+    pub(crate) fn response<T: JsonSchema>(mut self) -> Self {
+        self.response = Some(schema_ref::<T>);
+        self
+    }
+
+    pub(crate) fn path(&self) -> &'static str {
+        self.path
+    }
+
+    pub(crate) fn method_router(&self) -> MethodRouter<RestState> {
+        (self.route)()
+    }
+
+    /// gRPC method the endpoint mirrors, e.g. `/guardian.core.v1.GuardianService/GetPosture`,
+    /// which is what RBAC policies and remote assistance scopes name
+    pub(crate) fn rpc_path(&self) -> String {
+        let package = match self.service {
+            "SecurityService" => "guardian.security.v1",
+            "MLService" => "guardian.ml.v1",
+            _ => "guardian.core.v1",
+        };
+        let mut chars = self.operation_id.chars();
+        let method: String = chars.next().map(|first| first.to_ascii_uppercase()).into_iter().chain(chars).collect();
+        format!("/{}.{}/{}", package, self.service, method)
+    }
+}
+
+fn schema_ref<T: JsonSchema>(gen: &mut SchemaGenerator) -> Schema {
+    gen.subschema_for::<T>()
+}
+
+fn inline_schema<T: JsonSchema>(gen: &mut SchemaGenerator) -> Schema {
+    T::json_schema(gen)
+}
+
+/// Builds the OpenAPI document for endpoints served under `prefix`
+pub(crate) fn document(prefix: &str, endpoints: &[Endpoint]) -> Value {
+    let mut gen = SchemaSettings::draft2019_09()
+        .with(|settings| {
+            settings.definitions_path = SCHEMA_PREFIX.to_string();
+            settings.meta_schema = None;
+        })
+        .into_generator();
+    let error_schema = schema_ref::<ErrorBody>(&mut gen);
+
+    let mut paths = Map::new();
+    for endpoint in endpoints {
+        let operations = paths
+            .entry(format!("{}{}", prefix, openapi_path(endpoint.path)))
+            .or_insert_with(|| json!({}));
+        if let Value::Object(operations) = operations {
+            operations.insert(endpoint.method.as_str().to_lowercase(), operation(endpoint, &mut gen));
+        }
+    }
+    let tags: BTreeSet<&str> = endpoints.iter().map(|e| e.service).collect();
+
+    json!({
+        "openapi": OPENAPI_VERSION,
+        "info": {
+            "title": API_TITLE,
+            "version": env!("CARGO_PKG_VERSION"),
+            "description": "HTTP/JSON gateway mirroring the Guardian, Security and ML gRPC services",
+        },
+        "tags": tags.iter().map(|name| json!({ "name": name })).collect::<Vec<_>>(),
+        "paths": paths,
+        "components": {
+            "schemas": gen.definitions(),
+            "responses": {
+                "Error": {
+                    "description": "Failure mapped from the gRPC status of the mirrored call",
+                    "content": { "application/json": { "schema": error_schema } },
+                },
+            },
+            "securitySchemes": {
+                "mutualTLS": {
+                    "type": "mutualTLS",
+                    "description": "Client certificate mapped to RBAC roles by fingerprint or common name",
+                },
+                "bearerAuth": {
+                    "type": "http",
+                    "scheme": "bearer",
+                    "description": "Forwarded to the gRPC services as authorization metadata",
+                },
+            },
+        },
+        "security": [{ "mutualTLS": [] }, { "bearerAuth": [] }],
+    })
+}
+
+fn operation(endpoint: &Endpoint, gen: &mut SchemaGenerator) -> Value {
+    let mut parameters: Vec<Value> = endpoint
+        .path
+        .split('/')
+        .filter_map(|segment| segment.strip_prefix(':'))
+        .map(|name| json!({ "name": name, "in": "path", "required": true, "schema": { "type": "string" } }))
+        .collect();
+
+    if let Some(query) = endpoint.query {
+        if let Schema::Object(SchemaObject { object: Some(object), .. }) = query(gen) {
+            parameters.extend(object.properties.iter().map(|(name, schema)| {
+                json!({ "name": name, "in": "query", "required": object.required.contains(name), "schema": schema })
+            }));
+        }
+    }
+
+    let mut responses = Map::new();
+    let success = match endpoint.response {
+        Some(response) => json!({
+            "description": "OK",
+            "content": { "application/json": { "schema": response(gen) } },
+        }),
+        None => json!({ "description": "OK" }),
+    };
+    responses.insert("200".to_string(), success);
+    for (status, codes) in error_statuses() {
+        responses.insert(
+            status.to_string(),
+            json!({ "$ref": ERROR_RESPONSE_REF, "description": codes.join(", ") }),
+        );
+    }
+
+    let mut operation = json!({
+        "operationId": endpoint.operation_id,
+        "summary": endpoint.summary,
+        "tags": [endpoint.service],
+        "parameters": parameters,
+        "responses": responses,
+    });
+    if let Some(body) = endpoint.body {
+        operation["requestBody"] = json!({
+            "required": true,
+            "content": { "application/json": { "schema": body(gen) } },
+        });
+    }
+    operation
+}
+
+/// Groups the surfaced gRPC codes by the HTTP status they are returned as
+fn error_statuses() -> BTreeMap<u16, Vec<String>> {
+    let mut statuses: BTreeMap<u16, Vec<String>> = BTreeMap::new();
+    for code in ERROR_CODES {
+        statuses.entry(http_status(code).as_u16()).or_default().push(format!("{:?}", code));
+    }
+    statuses
+}
+
+/// Converts an axum `:param` route into an OpenAPI `{param}` template
+fn openapi_path(path: &str) -> String {
+    path.split('/')
+        .map(|segment| match segment.strip_prefix(':') {
+            Some(name) => format!("{{{}}}", name),
+            None => segment.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::routing::get;
+
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    struct StatusQuery {
+        include_metrics: Option<bool>,
+    }
+
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    struct ModelDto {
+        model_id: String,
+    }
+
+    #[test]
+    fn test_document_describes_paths_schemas_and_errors() {
+        let endpoints = [Endpoint::new(Method::GET, "/ml/models/:model_id", "MLService", "getModelStatus", "Model status", || {
+            get(|| async {})
+        })
+        .query::<StatusQuery>()
+        .response::<ModelDto>()];
+
+        let doc = document("/api/v1", &endpoints);
+        let operation = &doc["paths"]["/api/v1/ml/models/{model_id}"]["get"];
+
+        assert_eq!(doc["openapi"], OPENAPI_VERSION);
+        assert_eq!(operation["parameters"][0]["in"], "path");
+        assert_eq!(operation["parameters"][1]["name"], "include_metrics");
+        assert_eq!(operation["responses"]["200"]["content"]["application/json"]["schema"]["$ref"], "#/components/schemas/ModelDto");
+        assert_eq!(operation["responses"]["429"]["description"], "ResourceExhausted");
+        assert!(doc["components"]["schemas"]["ErrorBody"].is_object());
+        assert!(doc["components"]["securitySchemes"]["mutualTLS"].is_object());
+    }
+}
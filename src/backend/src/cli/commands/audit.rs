@@ -0,0 +1,158 @@
+use clap::{Arg, ArgMatches, Command};
+use std::sync::Arc;
+use tracing::instrument;
+use metrics::counter;
+
+use crate::cli::commands::{AccessLevel, Command as CliCommand};
+use crate::cli::output;
+use crate::security::audit::AuditEvent;
+use crate::security::command_audit::{CommandAudit, CommandAuditQuery};
+use crate::utils::error::GuardianError;
+
+// Constants for audit commands
+const COMMAND_NAME: &str = "audit";
+const HELP_TEXT: &str = "Query the audit ledger of administrative commands";
+
+/// Builds the `audit` subcommand definition
+pub fn build_audit_subcommand() -> Command {
+    Command::new(COMMAND_NAME)
+        .about(HELP_TEXT)
+        .subcommand(Command::new("commands")
+            .about("List CLI commands and API calls recorded in the audit chain, newest first")
+            .arg(Arg::new("principal")
+                .short('p')
+                .long("principal")
+                .help("Only invocations by this principal"))
+            .arg(Arg::new("command")
+                .short('c')
+                .long("command")
+                .help("Only commands starting with this prefix, e.g. \"backup\""))
+            .arg(Arg::new("since")
+                .long("since")
+                .help("Only invocations at or after this RFC 3339 time"))
+            .arg(Arg::new("changes")
+                .long("changes")
+                .action(clap::ArgAction::SetTrue)
+                .help("Only allowed invocations of commands that can change state"))
+            .arg(Arg::new("limit")
+                .long("limit")
+                .value_parser(clap::value_parser!(usize))
+                .default_value("50")
+                .help("Invocations to show; 0 shows all"))
+            .arg(Arg::new("format")
+                .short('f')
+                .long("format")
+                .value_parser(["table", "json"])
+                .default_value("table")
+                .help("Output format")))
+}
+
+/// CLI command exposing the command audit ledger
+#[derive(Debug)]
+pub struct AuditCommand {
+    audit: Arc<CommandAudit>,
+}
+
+impl AuditCommand {
+    /// Creates a new AuditCommand reading from the given command audit
+    pub fn new(audit: Arc<CommandAudit>) -> Self {
+        Self { audit }
+    }
+
+    /// Lists recorded invocations as a table or JSON
+    #[instrument(skip(self))]
+    async fn commands(&self, query: CommandAuditQuery, format: &str) -> Result<(), GuardianError> {
+        let events = self.audit.history(&query).await?;
+
+        if format == "json" {
+            let entries: Vec<&serde_json::Value> = events.iter().map(AuditEvent::data).collect();
+            println!("{}", serde_json::to_string_pretty(&entries)?);
+        } else {
+            let rows: Vec<Vec<String>> = events.iter().map(invocation_row).collect();
+            print!("{}", output::render_table(
+                &["SEQ", "TIME", "PRINCIPAL", "INTERFACE", "COMMAND", "OUTCOME", "DURATION", "ARGS SHA256"],
+                &rows,
+            ));
+        }
+
+        counter!("guardian.cli.audit.commands", 1);
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl CliCommand for AuditCommand {
+    fn name(&self) -> &'static str {
+        COMMAND_NAME
+    }
+
+    fn configure(&self) -> Command {
+        build_audit_subcommand()
+    }
+
+    async fn execute(&self, args: &ArgMatches) -> Result<(), GuardianError> {
+        match args.subcommand() {
+            Some(("commands", sub_matches)) => {
+                let format = sub_matches.get_one::<String>("format").map(String::as_str).unwrap_or("table");
+                self.commands(commands_query(sub_matches)?, format).await
+            }
+            _ => Err(invalid("Invalid subcommand".into())),
+        }
+    }
+
+    fn required_access(&self) -> AccessLevel {
+        AccessLevel::Security
+    }
+
+    fn help(&self) -> &'static str {
+        HELP_TEXT
+    }
+}
+
+fn commands_query(matches: &ArgMatches) -> Result<CommandAuditQuery, GuardianError> {
+    let since = matches
+        .get_one::<String>("since")
+        .map(|raw| {
+            chrono::DateTime::parse_from_rfc3339(raw)
+                .map(|t| t.with_timezone(&chrono::Utc))
+                .map_err(|_| invalid(format!("Invalid --since timestamp: {}", raw)))
+        })
+        .transpose()?;
+
+    Ok(CommandAuditQuery {
+        principal: matches.get_one::<String>("principal").cloned(),
+        command: matches.get_one::<String>("command").cloned(),
+        since,
+        changes_only: matches.get_flag("changes"),
+        limit: matches.get_one::<usize>("limit").copied().unwrap_or_default(),
+    })
+}
+
+fn invocation_row(event: &AuditEvent) -> Vec<String> {
+    let tag = |name: &str| event.tags().get(name).cloned().unwrap_or_default();
+    let data = event.data();
+    let duration_ms = data["duration_ms"].as_f64().unwrap_or_default();
+    let args_sha256 = data["args_sha256"].as_str().unwrap_or("-");
+    vec![
+        event.sequence().to_string(),
+        event.timestamp().format("%Y-%m-%d %H:%M:%S").to_string(),
+        tag("principal"),
+        tag("interface"),
+        tag("command"),
+        tag("outcome"),
+        format!("{:.0}ms", duration_ms),
+        args_sha256.chars().take(12).collect(),
+    ]
+}
+
+fn invalid(context: String) -> GuardianError {
+    GuardianError::ValidationError {
+        context,
+        source: None,
+        severity: crate::utils::error::ErrorSeverity::Low,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: crate::utils::error::ErrorCategory::Validation,
+        retry_count: 0,
+    }
+}
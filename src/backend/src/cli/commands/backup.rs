@@ -0,0 +1,249 @@
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tracing::instrument;
+use metrics::counter;
+
+use crate::cli::commands::{AccessLevel, Command as CliCommand};
+use crate::cli::output::{self, ProgressReporter};
+use crate::storage::zfs_manager::{BackupManifest, BackupOptions, BackupTarget, ZfsManager};
+use crate::utils::error::GuardianError;
+
+// Constants for backup commands
+const COMMAND_NAME: &str = "backup";
+const HELP_TEXT: &str = "Back up and restore datasets with ZFS send/receive";
+const BYTES_PER_MIB: u64 = 1024 * 1024;
+
+/// Builds the `backup` subcommand definition
+pub fn build_backup_subcommand() -> Command {
+    Command::new(COMMAND_NAME)
+        .about(HELP_TEXT)
+        .subcommand(Command::new("create")
+            .about("Snapshot a dataset and send it to a backup target")
+            .arg(Arg::new("dataset")
+                .required(true)
+                .help("Dataset to back up"))
+            .arg(Arg::new("dir")
+                .long("dir")
+                .conflicts_with("to-dataset")
+                .required_unless_present("to-dataset")
+                .help("Directory to write the stream and manifest to"))
+            .arg(Arg::new("to-dataset")
+                .long("to-dataset")
+                .help("Dataset to receive the stream into"))
+            .arg(Arg::new("full")
+                .long("full")
+                .action(ArgAction::SetTrue)
+                .help("Send a full stream even if the target has a common snapshot"))
+            .arg(bandwidth_arg())
+            .arg(no_verify_arg()))
+        .subcommand(Command::new("restore")
+            .about("Restore a dataset from directory backups")
+            .arg(Arg::new("dataset")
+                .required(true)
+                .help("Dataset the backups were taken from"))
+            .arg(Arg::new("dir")
+                .long("dir")
+                .required(true)
+                .help("Directory holding the backup streams"))
+            .arg(Arg::new("target")
+                .long("target")
+                .required(true)
+                .help("Dataset to restore into"))
+            .arg(Arg::new("snapshot")
+                .long("snapshot")
+                .help("Backup snapshot to restore, defaults to the newest"))
+            .arg(bandwidth_arg())
+            .arg(no_verify_arg()))
+}
+
+fn bandwidth_arg() -> Arg {
+    Arg::new("bandwidth-limit")
+        .long("bandwidth-limit")
+        .value_parser(clap::value_parser!(u64))
+        .help("Maximum stream throughput in MiB/s")
+}
+
+fn no_verify_arg() -> Arg {
+    Arg::new("no-verify")
+        .long("no-verify")
+        .action(ArgAction::SetTrue)
+        .help("Skip stream integrity verification")
+}
+
+/// CLI command for ZFS dataset backup and restore
+#[derive(Debug)]
+pub struct BackupCommand {
+    zfs_manager: Arc<ZfsManager>,
+}
+
+impl BackupCommand {
+    /// Creates a new BackupCommand using the given ZFS manager
+    pub fn new(zfs_manager: Arc<ZfsManager>) -> Self {
+        Self { zfs_manager }
+    }
+
+    /// Backs up a dataset to a directory or another dataset
+    #[instrument(skip(self, options))]
+    async fn create_backup(
+        &self,
+        dataset: &str,
+        target: BackupTarget,
+        options: BackupOptions,
+    ) -> Result<(), GuardianError> {
+        let mut progress = ProgressReporter::start("backup.create");
+        progress.update(0.0, &format!("sending {}", dataset));
+
+        match self.zfs_manager.backup_dataset(dataset, &target, &options).await {
+            Ok(manifest) => {
+                progress.finish("backup completed");
+                print_manifest(&manifest);
+                counter!("guardian.cli.backup.create", 1);
+                Ok(())
+            }
+            Err(e) => {
+                progress.fail("backup failed");
+                Err(e)
+            }
+        }
+    }
+
+    /// Restores a dataset from the backups in a directory
+    #[instrument(skip(self, options))]
+    async fn restore_backup(
+        &self,
+        dataset: &str,
+        dir: PathBuf,
+        snapshot: Option<&str>,
+        target: &str,
+        options: BackupOptions,
+    ) -> Result<(), GuardianError> {
+        let mut progress = ProgressReporter::start("backup.restore");
+        progress.update(0.0, &format!("restoring {} into {}", dataset, target));
+
+        match self.zfs_manager.restore_dataset(&dir, dataset, snapshot, target, &options).await {
+            Ok(manifest) => {
+                progress.finish("restore completed");
+                print_manifest(&manifest);
+                counter!("guardian.cli.backup.restore", 1);
+                Ok(())
+            }
+            Err(e) => {
+                progress.fail("restore failed");
+                Err(e)
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl CliCommand for BackupCommand {
+    fn name(&self) -> &'static str {
+        COMMAND_NAME
+    }
+
+    fn configure(&self) -> Command {
+        build_backup_subcommand()
+    }
+
+    async fn execute(&self, args: &ArgMatches) -> Result<(), GuardianError> {
+        match args.subcommand() {
+            Some(("create", sub_matches)) => {
+                let dataset = required(sub_matches, "dataset")?;
+                let target = match sub_matches.get_one::<String>("dir") {
+                    Some(dir) => BackupTarget::Directory(PathBuf::from(dir)),
+                    None => BackupTarget::Dataset(required(sub_matches, "to-dataset")?.to_string()),
+                };
+                let mut options = parse_options(sub_matches);
+                options.incremental = !sub_matches.get_flag("full");
+                self.create_backup(dataset, target, options).await
+            }
+            Some(("restore", sub_matches)) => {
+                let dataset = required(sub_matches, "dataset")?;
+                let dir = PathBuf::from(required(sub_matches, "dir")?);
+                let target = required(sub_matches, "target")?;
+                let snapshot = sub_matches.get_one::<String>("snapshot").map(String::as_str);
+                self.restore_backup(dataset, dir, snapshot, target, parse_options(sub_matches)).await
+            }
+            _ => Err(invalid("Invalid subcommand".into())),
+        }
+    }
+
+    fn required_access(&self) -> AccessLevel {
+        AccessLevel::Admin
+    }
+
+    fn help(&self) -> &'static str {
+        HELP_TEXT
+    }
+}
+
+fn parse_options(matches: &ArgMatches) -> BackupOptions {
+    BackupOptions {
+        bandwidth_limit_bytes_per_sec: matches
+            .get_one::<u64>("bandwidth-limit")
+            .map(|mib| mib.saturating_mul(BYTES_PER_MIB)),
+        verify: !matches.get_flag("no-verify"),
+        ..Default::default()
+    }
+}
+
+fn print_manifest(manifest: &BackupManifest) {
+    let kind = match &manifest.base_snapshot {
+        Some(base) => format!("incremental from {}", base),
+        None => "full".to_string(),
+    };
+    print!("{}", output::render_table(
+        &["DATASET", "SNAPSHOT", "KIND", "BYTES", "SHA256"],
+        &[vec![
+            manifest.dataset.clone(),
+            manifest.snapshot.clone(),
+            kind,
+            manifest.bytes.to_string(),
+            manifest.sha256.clone(),
+        ]],
+    ));
+}
+
+fn required<'a>(matches: &'a ArgMatches, name: &str) -> Result<&'a str, GuardianError> {
+    matches
+        .get_one::<String>(name)
+        .map(String::as_str)
+        .ok_or_else(|| invalid(format!("Argument {} required", name)))
+}
+
+fn invalid(context: String) -> GuardianError {
+    GuardianError::ValidationError {
+        context,
+        source: None,
+        severity: crate::utils::error::ErrorSeverity::Low,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: crate::utils::error::ErrorCategory::Validation,
+        retry_count: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backup_options_from_args() {
+        let matches = build_backup_subcommand().get_matches_from(vec![
+            COMMAND_NAME, "create", "guardian/events", "--dir", "/backups", "--bandwidth-limit", "8", "--full",
+        ]);
+        let (_, create) = matches.subcommand().unwrap();
+        let options = parse_options(create);
+        assert_eq!(options.bandwidth_limit_bytes_per_sec, Some(8 * BYTES_PER_MIB));
+        assert!(options.verify);
+
+        // A target is mandatory and only one kind may be given
+        assert!(build_backup_subcommand()
+            .try_get_matches_from(vec![COMMAND_NAME, "create", "guardian/events"])
+            .is_err());
+        assert!(build_backup_subcommand()
+            .try_get_matches_from(vec![COMMAND_NAME, "create", "guardian/events", "--dir", "/b", "--to-dataset", "p/b"])
+            .is_err());
+    }
+}
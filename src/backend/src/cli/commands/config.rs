@@ -1,11 +1,17 @@
 use clap::{Arg, ArgMatches, Command};
 use serde_json::Value;
 use tracing::{debug, error, info, instrument, warn};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use crate::cli::commands::Command as CliCommand;
+use crate::cli::output::{self, ProgressReporter};
 use crate::config::app_config::AppConfig;
+use crate::config::diff::{self, ConfigDiff};
+use crate::config::watcher::{self, ConfigChangeOutcome};
+use crate::config::secrets::ConfigSecrets;
+use crate::config::SecurityConfig;
+use crate::security::key_provider::key_provider_from_config;
 use crate::utils::error::GuardianError;
 use crate::utils::validation::{validate_input, ValidationRules};
 
@@ -93,8 +99,27 @@ impl ConfigCommand {
                         Arg::new("encrypt")
                             .short('e')
                             .long("encrypt")
-                            .help("Encrypt sensitive values"),
-                    ),
+                            .action(clap::ArgAction::SetTrue)
+                            .help("Store the value as an enc:v1 envelope under the config KEK"),
+                    )
+                    .arg(dry_run_arg())
+                    .arg(diff_format_arg()),
+            )
+            .subcommand(
+                Command::new("diff")
+                    .about("Show the effective settings a configuration file would change")
+                    .arg(
+                        Arg::new("file")
+                            .help("Proposed configuration file; without it, the configuration file is compared with what the daemon applied")
+                            .index(1),
+                    )
+                    .arg(diff_format_arg()),
+            )
+            .subcommand(
+                Command::new("reload")
+                    .about("Have the daemon reload the configuration file")
+                    .arg(dry_run_arg())
+                    .arg(diff_format_arg()),
             )
             .subcommand(
                 Command::new("validate")
@@ -194,11 +219,17 @@ impl ConfigCommand {
 
         // Handle encryption if requested
         let final_value = if matches.get_flag("encrypt") {
-            config.encrypt_value(value)?
+            self.encrypt_value(value)?
         } else {
             value.to_string()
         };
 
+        if matches.get_flag("dry-run") {
+            let current = diff::load_effective(Path::new(&self.config_path))?;
+            let proposed = diff::with_setting(&current, key, &final_value)?;
+            return print_diff(&ConfigDiff::between(&current, &proposed)?, diff_format(matches));
+        }
+
         config.set_value(key, &final_value)?;
         config.validate()?;
         config.save(&self.config_path)?;
@@ -207,6 +238,67 @@ impl ConfigCommand {
         Ok(())
     }
 
+    /// Seals a value as an `enc:v1` envelope under the config KEK of the daemon's key provider
+    fn encrypt_value(&self, value: &str) -> Result<String, GuardianError> {
+        let security_path = crate::config::security_config_path(Path::new(crate::config::DEFAULT_CONFIG_PATH));
+        let security_config = SecurityConfig::load_config(&security_path, None)?;
+        let secrets = ConfigSecrets::new(key_provider_from_config(&security_config.hw_security_config)?);
+        super::block_on(secrets.encrypt(value))?
+    }
+
+    /// Handles the diff configuration command
+    #[instrument(skip(matches))]
+    fn handle_diff(&self, matches: &ArgMatches) -> Result<(), GuardianError> {
+        let config_path = Path::new(&self.config_path);
+        let diff = match matches.get_one::<String>("file") {
+            Some(file) => ConfigDiff::between(&diff::load_effective(config_path)?, &diff::load_effective(Path::new(file))?)?,
+            None => ConfigDiff::between(&watcher::applied_config(config_path)?, &diff::load_effective(config_path)?)?,
+        };
+        print_diff(&diff, diff_format(matches))
+    }
+
+    /// Handles the reload configuration command
+    ///
+    /// The daemon reloads when the file's modification time changes, so a reload is requested
+    /// by touching the file once the diff shows it can be applied.
+    #[instrument(skip(matches))]
+    fn handle_reload(&self, matches: &ArgMatches) -> Result<(), GuardianError> {
+        let config_path = Path::new(&self.config_path);
+        let diff = ConfigDiff::between(&watcher::applied_config(config_path)?, &diff::load_effective(config_path)?)?;
+        print_diff(&diff, diff_format(matches))?;
+        if matches.get_flag("dry-run") {
+            return Ok(());
+        }
+
+        match diff.outcome() {
+            ConfigChangeOutcome::Unchanged => return Ok(()),
+            ConfigChangeOutcome::Invalid => {
+                return Err(invalid(diff.validation_error.unwrap_or_else(|| "Configuration is invalid".to_string())));
+            }
+            ConfigChangeOutcome::RestartRequired => {
+                return Err(invalid(format!("{:?} only take effect after a restart", diff.restart_sections())));
+            }
+            ConfigChangeOutcome::Applied => {}
+        }
+
+        std::fs::File::options()
+            .append(true)
+            .open(config_path)
+            .and_then(|file| file.set_modified(std::time::SystemTime::now()))
+            .map_err(|e| GuardianError::SystemError {
+                context: format!("Failed to request a reload of {}", config_path.display()),
+                source: Some(Box::new(e)),
+                severity: crate::utils::error::ErrorSeverity::Medium,
+                timestamp: time::OffsetDateTime::now_utc(),
+                correlation_id: crate::utils::correlation::current_or_new(),
+                category: crate::utils::error::ErrorCategory::System,
+                retry_count: 0,
+            })?;
+        info!(sections = ?diff.sections(), "Configuration reload requested");
+        println!("Reload requested; without config_watch enabled, send SIGHUP to the daemon");
+        Ok(())
+    }
+
     /// Handles the validate configuration command
     #[instrument(skip(matches))]
     fn handle_validate(&self, matches: &ArgMatches) -> Result<(), GuardianError> {
@@ -233,7 +325,13 @@ impl ConfigCommand {
                 PathBuf::from(format!("{}.{}.backup", self.config_path, timestamp))
             });
 
-        config.backup(&output_path)?;
+        let mut progress = ProgressReporter::start("config.backup");
+        progress.update(10.0, "configuration loaded");
+        if let Err(e) = config.backup(&output_path) {
+            progress.fail("backup failed");
+            return Err(e);
+        }
+        progress.finish(&format!("backup written to {}", output_path.display()));
         
         info!(
             path = %output_path.display(),
@@ -248,8 +346,14 @@ impl ConfigCommand {
         let input_path = matches.get_one::<String>("input")
             .ok_or_else(|| GuardianError::ValidationError("Backup file path is required".to_string()))?;
 
+        let mut progress = ProgressReporter::start("config.restore");
         let config = AppConfig::new(None, None)?;
-        config.restore(input_path)?;
+        progress.update(10.0, "reading backup");
+        if let Err(e) = config.restore(input_path) {
+            progress.fail("restore failed");
+            return Err(e);
+        }
+        progress.finish("configuration restored");
         
         info!(
             path = input_path,
@@ -292,6 +396,8 @@ impl CliCommand for ConfigCommand {
         match matches.subcommand() {
             Some(("get", sub_matches)) => self.handle_get(sub_matches),
             Some(("set", sub_matches)) => self.handle_set(sub_matches),
+            Some(("diff", sub_matches)) => self.handle_diff(sub_matches),
+            Some(("reload", sub_matches)) => self.handle_reload(sub_matches),
             Some(("validate", sub_matches)) => self.handle_validate(sub_matches),
             Some(("backup", sub_matches)) => self.handle_backup(sub_matches),
             Some(("restore", sub_matches)) => self.handle_restore(sub_matches),
@@ -301,6 +407,73 @@ impl CliCommand for ConfigCommand {
     }
 }
 
+fn dry_run_arg() -> Arg {
+    Arg::new("dry-run")
+        .long("dry-run")
+        .action(clap::ArgAction::SetTrue)
+        .help("Show the settings that would change without applying them")
+}
+
+fn diff_format_arg() -> Arg {
+    Arg::new("format")
+        .short('f')
+        .long("format")
+        .value_parser(["table", "json"])
+        .default_value("table")
+        .help("Output format of the diff")
+}
+
+fn diff_format(matches: &ArgMatches) -> &str {
+    matches.get_one::<String>("format").map(String::as_str).unwrap_or("table")
+}
+
+/// Prints changed settings with what applying them would do
+fn print_diff(diff: &ConfigDiff, format: &str) -> Result<(), GuardianError> {
+    if format == "json" {
+        println!("{}", serde_json::to_string_pretty(diff)?);
+        return Ok(());
+    }
+
+    let show = |value: &Option<Value>| value.as_ref().map_or_else(|| "-".to_string(), Value::to_string);
+    let rows: Vec<Vec<String>> = diff
+        .changes
+        .iter()
+        .map(|change| vec![
+            change.path.clone(),
+            show(&change.before),
+            show(&change.after),
+            if change.hot_reloadable { "reload" } else { "restart" }.to_string(),
+        ])
+        .collect();
+    if !rows.is_empty() {
+        print!("{}", output::render_table(&["SETTING", "CURRENT", "PROPOSED", "APPLIES ON"], &rows));
+    }
+    match diff.outcome() {
+        ConfigChangeOutcome::Unchanged => println!("No effective settings change"),
+        ConfigChangeOutcome::Applied => println!("{} settings change, all applied on reload", diff.changes.len()),
+        ConfigChangeOutcome::RestartRequired => {
+            println!("{} settings change; {:?} need a restart", diff.changes.len(), diff.restart_sections())
+        }
+        ConfigChangeOutcome::Invalid => println!(
+            "Rejected: {}",
+            diff.validation_error.as_deref().unwrap_or("configuration is invalid")
+        ),
+    }
+    Ok(())
+}
+
+fn invalid(context: String) -> GuardianError {
+    GuardianError::ValidationError {
+        context,
+        source: None,
+        severity: crate::utils::error::ErrorSeverity::Low,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: crate::utils::error::ErrorCategory::Validation,
+        retry_count: 0,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,303 @@
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::{instrument, warn};
+use metrics::counter;
+
+use crate::cli::commands::{AccessLevel, Command as CliCommand};
+use crate::cli::output::{self, ProgressReporter};
+use crate::security::content_pack::{ContentActivation, ContentPackConfig, ContentPackManager};
+use crate::security::content_simulation::{ContentSimulator, SimulationReport};
+use crate::utils::error::GuardianError;
+
+// Constants for content pack commands
+const COMMAND_NAME: &str = "content";
+const HELP_TEXT: &str = "Install, activate and roll back signed detection content packs";
+const DEFAULT_TRUSTED_KEYS_PATH: &str = "/etc/guardian/content/trusted_keys";
+const SIGNATURE_EXTENSION: &str = "sig";
+
+/// Builds the `content` subcommand definition
+pub fn build_content_subcommand() -> Command {
+    Command::new(COMMAND_NAME)
+        .about(HELP_TEXT)
+        .arg(Arg::new("trusted-keys")
+            .long("trusted-keys")
+            .global(true)
+            .default_value(DEFAULT_TRUSTED_KEYS_PATH)
+            .help("File listing base64 Ed25519 keys allowed to sign packs"))
+        .subcommand(Command::new("install")
+            .about("Verify and stage a content pack")
+            .arg(Arg::new("archive")
+                .required(true)
+                .help("Content pack archive"))
+            .arg(Arg::new("signature")
+                .long("signature")
+                .help("Detached signature, defaults to <archive>.sig"))
+            .arg(Arg::new("activate")
+                .long("activate")
+                .action(ArgAction::SetTrue)
+                .help("Activate the pack immediately after staging"))
+            .arg(confirm_arg()))
+        .subcommand(Command::new("simulate")
+            .about("Replay recent history against the staged version of a pack")
+            .arg(Arg::new("name").required(true).help("Pack name")))
+        .subcommand(Command::new("activate")
+            .about("Activate the staged version of a pack")
+            .arg(Arg::new("name").required(true).help("Pack name"))
+            .arg(confirm_arg()))
+        .subcommand(Command::new("rollback")
+            .about("Restore the previously active version of a pack")
+            .arg(Arg::new("name").required(true).help("Pack name")))
+        .subcommand(Command::new("list")
+            .about("List installed packs"))
+}
+
+fn confirm_arg() -> Arg {
+    Arg::new("confirm")
+        .long("confirm")
+        .action(ArgAction::SetTrue)
+        .help("Activate even if simulation predicts a large alert volume increase")
+}
+
+/// CLI command managing detection content packs
+#[derive(Debug)]
+pub struct ContentCommand {
+    config: ContentPackConfig,
+    simulator: Option<Arc<ContentSimulator>>,
+}
+
+impl ContentCommand {
+    /// Creates a new ContentCommand for the given content store
+    pub fn new(config: ContentPackConfig) -> Self {
+        Self { config, simulator: None }
+    }
+
+    /// Evaluates staged packs against recent history before activation
+    pub fn with_simulator(mut self, simulator: Arc<ContentSimulator>) -> Self {
+        self.simulator = Some(simulator);
+        self
+    }
+
+    fn open(&self, args: &ArgMatches) -> Result<ContentPackManager, GuardianError> {
+        let keys = args
+            .get_one::<String>("trusted-keys")
+            .map(String::as_str)
+            .unwrap_or(DEFAULT_TRUSTED_KEYS_PATH);
+        let mut config = self.config.clone();
+        if Path::new(keys).exists() {
+            config = config.with_trusted_keys_file(Path::new(keys))?;
+        }
+        ContentPackManager::open(config)
+    }
+
+    #[instrument(skip(self, manager))]
+    async fn install(
+        &self,
+        manager: &ContentPackManager,
+        archive: &Path,
+        signature: &Path,
+        activate: bool,
+        confirmed: bool,
+    ) -> Result<(), GuardianError> {
+        let mut progress = ProgressReporter::start("content.install");
+        progress.update(0.0, "verifying signature");
+
+        let manifest = match manager.install(archive, signature) {
+            Ok(manifest) => manifest,
+            Err(e) => {
+                progress.fail("install failed");
+                return Err(e);
+            }
+        };
+        counter!("guardian.cli.content.install", 1);
+
+        if activate {
+            progress.update(60.0, "simulating");
+            if let Err(e) = self.activate(manager, &manifest.name, confirmed).await {
+                progress.fail("activation failed");
+                return Err(e);
+            }
+            progress.finish("content pack active");
+        } else {
+            progress.finish("content pack staged");
+        }
+        println!("{} {} ({} items)", manifest.name, manifest.version, manifest.items.len());
+        Ok(())
+    }
+
+    /// Activates a staged pack, simulating it first when a simulator is available
+    async fn activate(&self, manager: &ContentPackManager, name: &str, confirmed: bool) -> Result<ContentActivation, GuardianError> {
+        match &self.simulator {
+            Some(simulator) => {
+                let (activation, report) = manager.activate_evaluated(name, simulator, confirmed).await?;
+                print_report(&report);
+                Ok(activation)
+            }
+            None if confirmed => {
+                warn!(pack = name, "Activating content pack without simulation");
+                manager.activate(name)
+            }
+            None => Err(invalid(format!(
+                "No detection history available to simulate {}; pass --confirm to activate anyway",
+                name
+            ))),
+        }
+    }
+
+    async fn simulate(&self, manager: &ContentPackManager, name: &str) -> Result<(), GuardianError> {
+        let simulator = self
+            .simulator
+            .as_ref()
+            .ok_or_else(|| invalid("No detection history available to simulate against".into()))?;
+        let (dir, manifest) = manager
+            .staged(name)
+            .ok_or_else(|| invalid(format!("Content pack {} has no staged version", name)))?;
+        print_report(&simulator.simulate(&dir, &manifest).await?);
+        Ok(())
+    }
+
+    fn list(&self, manager: &ContentPackManager) {
+        let rows: Vec<Vec<String>> = manager
+            .list()
+            .into_iter()
+            .map(|(name, state)| vec![
+                name,
+                state.active.unwrap_or_else(|| "-".into()),
+                state.staged.unwrap_or_else(|| "-".into()),
+                state.previous.unwrap_or_else(|| "-".into()),
+                state.installed.join(","),
+            ])
+            .collect();
+        print!("{}", output::render_table(&["PACK", "ACTIVE", "STAGED", "PREVIOUS", "INSTALLED"], &rows));
+    }
+}
+
+#[async_trait::async_trait]
+impl CliCommand for ContentCommand {
+    fn name(&self) -> &'static str {
+        COMMAND_NAME
+    }
+
+    fn configure(&self) -> Command {
+        build_content_subcommand()
+    }
+
+    async fn execute(&self, args: &ArgMatches) -> Result<(), GuardianError> {
+        let manager = self.open(args)?;
+        match args.subcommand() {
+            Some(("install", sub_matches)) => {
+                let archive = PathBuf::from(required(sub_matches, "archive")?);
+                let signature = sub_matches
+                    .get_one::<String>("signature")
+                    .map(PathBuf::from)
+                    .unwrap_or_else(|| signature_path(&archive));
+                self.install(
+                    &manager,
+                    &archive,
+                    &signature,
+                    sub_matches.get_flag("activate"),
+                    sub_matches.get_flag("confirm"),
+                ).await
+            }
+            Some(("simulate", sub_matches)) => self.simulate(&manager, required(sub_matches, "name")?).await,
+            Some(("activate", sub_matches)) => {
+                let activation = self
+                    .activate(&manager, required(sub_matches, "name")?, sub_matches.get_flag("confirm"))
+                    .await?;
+                println!("{} {} active", activation.name, activation.version);
+                Ok(())
+            }
+            Some(("rollback", sub_matches)) => {
+                let activation = manager.rollback(required(sub_matches, "name")?)?;
+                println!("{} rolled back to {}", activation.name, activation.version);
+                Ok(())
+            }
+            Some(("list", _)) => {
+                self.list(&manager);
+                Ok(())
+            }
+            _ => Err(invalid("Invalid subcommand".into())),
+        }
+    }
+
+    fn required_access(&self) -> AccessLevel {
+        AccessLevel::Admin
+    }
+
+    fn help(&self) -> &'static str {
+        HELP_TEXT
+    }
+}
+
+/// Prints the alert volume delta of a simulation
+fn print_report(report: &SimulationReport) {
+    if !report.evaluated {
+        println!("{} {} ships no detection rules to simulate", report.pack, report.version);
+        return;
+    }
+    let rows: Vec<Vec<String>> = report
+        .by_type
+        .iter()
+        .map(|(kind, delta)| vec![
+            kind.clone(),
+            delta.baseline.to_string(),
+            delta.candidate.to_string(),
+            format!("{:+}", delta.candidate as i64 - delta.baseline as i64),
+        ])
+        .collect();
+    print!("{}", output::render_table(&["TYPE", "CURRENT", "CANDIDATE", "DELTA"], &rows));
+    println!(
+        "{} batches, {} samples replayed; alert volume {:.2}x ({} -> {}){}",
+        report.batches,
+        report.samples,
+        report.increase_factor,
+        report.baseline_alerts,
+        report.candidate_alerts,
+        if report.failed_batches > 0 { format!(", {} batches failed", report.failed_batches) } else { String::new() },
+    );
+}
+
+/// Returns the detached signature path conventionally shipped next to an archive
+fn signature_path(archive: &Path) -> PathBuf {
+    let mut path = archive.as_os_str().to_owned();
+    path.push(".");
+    path.push(SIGNATURE_EXTENSION);
+    PathBuf::from(path)
+}
+
+fn required<'a>(matches: &'a ArgMatches, name: &str) -> Result<&'a str, GuardianError> {
+    matches
+        .get_one::<String>(name)
+        .map(String::as_str)
+        .ok_or_else(|| invalid(format!("Argument {} required", name)))
+}
+
+fn invalid(context: String) -> GuardianError {
+    GuardianError::ValidationError {
+        context,
+        source: None,
+        severity: crate::utils::error::ErrorSeverity::Low,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: crate::utils::error::ErrorCategory::Validation,
+        retry_count: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_install_args() {
+        let matches = build_content_subcommand()
+            .get_matches_from(vec![COMMAND_NAME, "install", "/tmp/core-rules-1.2.0.gcp", "--activate"]);
+        let (name, install) = matches.subcommand().unwrap();
+        assert_eq!(name, "install");
+        assert!(install.get_flag("activate"));
+        assert!(!install.get_flag("confirm"));
+
+        let archive = PathBuf::from(install.get_one::<String>("archive").unwrap());
+        assert_eq!(signature_path(&archive), PathBuf::from("/tmp/core-rules-1.2.0.gcp.sig"));
+    }
+}
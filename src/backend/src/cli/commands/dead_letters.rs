@@ -0,0 +1,194 @@
+use clap::{Arg, ArgMatches, Command};
+use std::sync::Arc;
+use tracing::{info, instrument};
+use metrics::counter;
+use uuid::Uuid;
+
+use crate::cli::commands::{AccessLevel, Command as CliCommand};
+use crate::cli::output;
+use crate::core::dead_letter::{DeadLetter, DeadLetterQueue};
+use crate::utils::error::GuardianError;
+
+// Constants for dead-letter commands
+const COMMAND_NAME: &str = "dead-letters";
+const HELP_TEXT: &str = "Inspect, requeue and purge undelivered EventBus events";
+
+/// Builds the `dead-letters` subcommand definition
+pub fn build_dead_letters_subcommand() -> Command {
+    Command::new(COMMAND_NAME)
+        .about(HELP_TEXT)
+        .subcommand(Command::new("list")
+            .about("List dead letters awaiting redelivery or a manual requeue")
+            .arg(Arg::new("event-type")
+                .short('t')
+                .long("event-type")
+                .help("Only show dead letters of this event type"))
+            .arg(Arg::new("format")
+                .short('f')
+                .long("format")
+                .value_parser(["table", "json"])
+                .default_value("table")
+                .help("Output format")))
+        .subcommand(Command::new("show")
+            .about("Show a dead letter with its payload")
+            .arg(Arg::new("dead-letter-id")
+                .required(true)
+                .help("Dead letter identifier")))
+        .subcommand(Command::new("requeue")
+            .about("Redeliver a dead letter now with a fresh retry budget")
+            .arg(Arg::new("dead-letter-id")
+                .required(true)
+                .help("Dead letter identifier")))
+        .subcommand(Command::new("purge")
+            .about("Drop a dead letter without delivering it")
+            .arg(Arg::new("dead-letter-id")
+                .required(true)
+                .help("Dead letter identifier")))
+}
+
+/// CLI command exposing the EventBus dead-letter queue
+#[derive(Debug)]
+pub struct DeadLettersCommand {
+    queue: Arc<DeadLetterQueue>,
+}
+
+impl DeadLettersCommand {
+    /// Creates a new DeadLettersCommand backed by the given queue
+    pub fn new(queue: Arc<DeadLetterQueue>) -> Self {
+        Self { queue }
+    }
+
+    /// Lists dead letters as a table or JSON
+    #[instrument(skip(self))]
+    fn list(&self, event_type: Option<&str>, format: &str) -> Result<(), GuardianError> {
+        let letters = self.queue.list(event_type);
+
+        if format == "json" {
+            println!("{}", serde_json::to_string_pretty(&letters)?);
+        } else {
+            let rows: Vec<Vec<String>> = letters.iter().map(dead_letter_row).collect();
+            print!("{}", output::render_table(
+                &["DEAD LETTER ID", "EVENT TYPE", "STATE", "ATTEMPTS", "NEXT ATTEMPT", "REASON"],
+                &rows,
+            ));
+        }
+
+        counter!("guardian.cli.dead_letters.list", 1);
+        Ok(())
+    }
+
+    /// Shows a single dead letter as JSON
+    #[instrument(skip(self))]
+    fn show(&self, id: Uuid) -> Result<(), GuardianError> {
+        let letter = self.queue.get(&id).ok_or_else(|| invalid(format!("Dead letter {} not found", id)))?;
+        println!("{}", serde_json::to_string_pretty(&letter)?);
+        Ok(())
+    }
+
+    /// Schedules a dead letter for immediate redelivery
+    #[instrument(skip(self))]
+    async fn requeue(&self, id: Uuid) -> Result<(), GuardianError> {
+        let letter = self.queue.requeue(&id).await?;
+        info!(dead_letter_id = %id, "Dead letter requeued");
+        println!("Requeued dead letter {} ({})", letter.id, letter.event_type);
+        counter!("guardian.cli.dead_letters.requeue", 1);
+        Ok(())
+    }
+
+    /// Drops a dead letter
+    #[instrument(skip(self))]
+    async fn purge(&self, id: Uuid) -> Result<(), GuardianError> {
+        let letter = self.queue.purge(&id).await?;
+        println!("Purged dead letter {} ({})", letter.id, letter.event_type);
+        counter!("guardian.cli.dead_letters.purge", 1);
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl CliCommand for DeadLettersCommand {
+    fn name(&self) -> &'static str {
+        COMMAND_NAME
+    }
+
+    fn configure(&self) -> Command {
+        build_dead_letters_subcommand()
+    }
+
+    async fn execute(&self, args: &ArgMatches) -> Result<(), GuardianError> {
+        match args.subcommand() {
+            Some(("list", sub_matches)) => {
+                let event_type = sub_matches.get_one::<String>("event-type").map(String::as_str);
+                let format = sub_matches.get_one::<String>("format").map(String::as_str).unwrap_or("table");
+                self.list(event_type, format)
+            }
+            Some(("show", sub_matches)) => self.show(parse_id(sub_matches)?),
+            Some(("requeue", sub_matches)) => self.requeue(parse_id(sub_matches)?).await,
+            Some(("purge", sub_matches)) => self.purge(parse_id(sub_matches)?).await,
+            _ => Err(invalid("Invalid subcommand".into())),
+        }
+    }
+
+    fn required_access(&self) -> AccessLevel {
+        AccessLevel::Operator
+    }
+
+    fn help(&self) -> &'static str {
+        HELP_TEXT
+    }
+}
+
+fn dead_letter_row(letter: &DeadLetter) -> Vec<String> {
+    vec![
+        letter.id.to_string(),
+        letter.event_type.clone(),
+        format!("{:?}", letter.state).to_lowercase(),
+        letter.attempts.to_string(),
+        letter.next_attempt_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+        letter.reason.clone(),
+    ]
+}
+
+fn parse_id(matches: &ArgMatches) -> Result<Uuid, GuardianError> {
+    let raw = matches.get_one::<String>("dead-letter-id")
+        .ok_or_else(|| invalid("Dead letter ID required".into()))?;
+    Uuid::parse_str(raw).map_err(|_| invalid(format!("Invalid dead letter ID: {}", raw)))
+}
+
+fn invalid(context: String) -> GuardianError {
+    GuardianError::ValidationError {
+        context,
+        source: None,
+        severity: crate::utils::error::ErrorSeverity::Low,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: crate::utils::error::ErrorCategory::Validation,
+        retry_count: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::app_config::DeadLetterConfig;
+    use crate::core::event_bus::{Event, EventPriority};
+
+    #[tokio::test]
+    async fn test_requeue_and_purge() {
+        let queue = Arc::new(DeadLetterQueue::new(DeadLetterConfig::default()));
+        let event = Event::new("threat_detected".into(), serde_json::json!({}), EventPriority::High).unwrap();
+        let letter = queue.record(&event, None, "no subscribers").await;
+        let command = DeadLettersCommand::new(Arc::clone(&queue));
+
+        let id = letter.id.to_string();
+        let args = build_dead_letters_subcommand().get_matches_from(vec![COMMAND_NAME, "requeue", id.as_str()]);
+        assert!(command.execute(&args).await.is_ok());
+
+        let args = build_dead_letters_subcommand().get_matches_from(vec![COMMAND_NAME, "purge", id.as_str()]);
+        assert!(command.execute(&args).await.is_ok());
+        assert!(queue.get(&letter.id).is_none());
+
+        let args = build_dead_letters_subcommand().get_matches_from(vec![COMMAND_NAME, "show", "not-a-uuid"]);
+        assert!(command.execute(&args).await.is_err());
+    }
+}
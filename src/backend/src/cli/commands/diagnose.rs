@@ -0,0 +1,162 @@
+use clap::{Arg, ArgMatches, Command};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tracing::instrument;
+use metrics::counter;
+
+use crate::cli::commands::{AccessLevel, Command as CliCommand};
+use crate::cli::output;
+use crate::config::secrets::ConfigSecrets;
+use crate::config::SecurityConfig;
+use crate::core::diagnostics::{
+    run_diagnostics, AuditWriteCheck, CheckStatus, ConfigCheck, CryptoCheck, DiagnosticCheck, DiagnosticReport,
+    ModelCheck, TemporalCheck, UnavailableCheck, ZfsPoolCheck, DEFAULT_CHECK_TIMEOUT,
+};
+use crate::ml::inference_engine::InferenceEngine;
+use crate::security::key_provider::key_provider_from_config;
+use crate::storage::zfs_manager::ZfsManager;
+use crate::temporal::TemporalConfig;
+use crate::utils::error::GuardianError;
+
+// Constants for diagnose command
+const COMMAND_NAME: &str = "diagnose";
+const HELP_TEXT: &str = "Run self-tests of the configuration, storage, Temporal, model, crypto and audit trail";
+const CRYPTO_REMEDIATION: &str = "Check that security.toml loads and that its key provider is reachable";
+const AUDIT_REMEDIATION: &str = "Check that security.toml loads and that its audit chain settings are valid";
+
+/// Builds the `diagnose` subcommand definition
+pub fn build_diagnose_subcommand() -> Command {
+    Command::new(COMMAND_NAME)
+        .about(HELP_TEXT)
+        .arg(Arg::new("timeout")
+            .long("check-timeout")
+            .value_parser(clap::value_parser!(u64))
+            .help("Seconds each check may take before it fails"))
+        .arg(Arg::new("format")
+            .short('f')
+            .long("format")
+            .value_parser(["table", "json"])
+            .default_value("table")
+            .help("Output format"))
+}
+
+/// CLI command running the diagnostics battery
+#[derive(Debug)]
+pub struct DiagnoseCommand {
+    config_dir: PathBuf,
+    zfs: Arc<ZfsManager>,
+    engine: Arc<InferenceEngine>,
+}
+
+impl DiagnoseCommand {
+    /// Creates a new DiagnoseCommand testing the given configuration, pool and inference engine
+    pub fn new(config_dir: PathBuf, zfs: Arc<ZfsManager>, engine: Arc<InferenceEngine>) -> Self {
+        Self { config_dir, zfs, engine }
+    }
+
+    /// Checks in the order they run; dependencies that cannot be set up become failing checks
+    fn checks(&self) -> Vec<Box<dyn DiagnosticCheck>> {
+        let mut checks: Vec<Box<dyn DiagnosticCheck>> = vec![
+            Box::new(ConfigCheck { config_dir: self.config_dir.clone() }),
+            Box::new(ZfsPoolCheck { zfs: Arc::clone(&self.zfs) }),
+            Box::new(TemporalCheck { config: TemporalConfig::default() }),
+            Box::new(ModelCheck { engine: Arc::clone(&self.engine) }),
+        ];
+
+        match SecurityConfig::load_config(self.config_dir.join("security.toml"), None) {
+            Ok(security_config) => {
+                match key_provider_from_config(&security_config.hw_security_config) {
+                    Ok(provider) => checks.push(Box::new(CryptoCheck { secrets: Arc::new(ConfigSecrets::new(provider)) })),
+                    Err(e) => checks.push(unavailable("crypto", CRYPTO_REMEDIATION, &e)),
+                }
+                checks.push(Box::new(AuditWriteCheck { config: security_config.audit_config.chain }));
+            }
+            Err(e) => {
+                checks.push(unavailable("crypto", CRYPTO_REMEDIATION, &e));
+                checks.push(unavailable("audit_write", AUDIT_REMEDIATION, &e));
+            }
+        }
+        checks
+    }
+
+    /// Runs every check and prints the report, failing when any check failed
+    #[instrument(skip(self))]
+    async fn diagnose(&self, timeout: std::time::Duration, format: &str) -> Result<(), GuardianError> {
+        let report = run_diagnostics(&self.checks(), timeout).await;
+
+        if format == "json" {
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        } else {
+            print_report(&report);
+        }
+
+        counter!("guardian.cli.diagnose", 1, "passed" => report.passed().to_string());
+        if report.passed() {
+            Ok(())
+        } else {
+            Err(GuardianError::SystemError {
+                context: format!("{} of {} self-tests failed", report.failures(), report.checks.len()),
+                source: None,
+                severity: crate::utils::error::ErrorSeverity::High,
+                timestamp: time::OffsetDateTime::now_utc(),
+                correlation_id: crate::utils::correlation::current_or_new(),
+                category: crate::utils::error::ErrorCategory::System,
+                retry_count: 0,
+            })
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl CliCommand for DiagnoseCommand {
+    fn name(&self) -> &'static str {
+        COMMAND_NAME
+    }
+
+    fn configure(&self) -> Command {
+        build_diagnose_subcommand()
+    }
+
+    async fn execute(&self, args: &ArgMatches) -> Result<(), GuardianError> {
+        let timeout = args
+            .get_one::<u64>("timeout")
+            .map(|secs| std::time::Duration::from_secs(*secs))
+            .unwrap_or(DEFAULT_CHECK_TIMEOUT);
+        let format = args.get_one::<String>("format").map(String::as_str).unwrap_or("table");
+        self.diagnose(timeout, format).await
+    }
+
+    fn required_access(&self) -> AccessLevel {
+        AccessLevel::Operator
+    }
+
+    fn help(&self) -> &'static str {
+        HELP_TEXT
+    }
+}
+
+fn unavailable(name: &'static str, remediation: &'static str, error: &GuardianError) -> Box<dyn DiagnosticCheck> {
+    Box::new(UnavailableCheck { name, remediation, error: error.to_string() })
+}
+
+fn print_report(report: &DiagnosticReport) {
+    let rows: Vec<Vec<String>> = report
+        .checks
+        .iter()
+        .map(|check| {
+            let status = match check.status {
+                CheckStatus::Pass => output::paint("PASS", "32"),
+                CheckStatus::Fail => output::paint("FAIL", "31"),
+                CheckStatus::Skipped => output::paint("SKIP", "33"),
+            };
+            vec![check.name.to_string(), status, format!("{}ms", check.duration_ms), check.detail.clone()]
+        })
+        .collect();
+    print!("{}", output::render_table(&["CHECK", "STATUS", "DURATION", "DETAIL"], &rows));
+
+    for check in report.checks.iter().filter(|check| check.status == CheckStatus::Fail) {
+        if let Some(remediation) = check.remediation {
+            println!("{}: {}", check.name, remediation);
+        }
+    }
+}
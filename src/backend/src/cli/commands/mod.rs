@@ -6,8 +6,10 @@ use anyhow::Result;
 use clap::ArgMatches;
 use metrics::{counter, histogram};
 use tokio::time;
-use tracing::{debug, error, info, instrument};
+use tracing::{debug, error, info, instrument, warn};
 
+use crate::security::command_audit::{matches_args, AccessDecision, CommandAudit, CommandInvocation, Interface};
+use crate::security::rbac::{self, Principal};
 use crate::utils::error::{GuardianError, ErrorCategory, ErrorSeverity};
 
 // Import command modules
@@ -15,16 +17,118 @@ mod config;
 mod status;
 mod threats;
 mod models;
+pub mod ops;
+pub mod backup;
+pub mod forensics;
+pub mod support_bundle;
+pub mod remote_assist;
+pub mod content;
+pub mod policy;
+pub mod dead_letters;
+pub mod audit;
+pub mod diagnose;
+pub mod tokens;
+pub mod workflows;
+pub mod response;
 
 pub use config::ConfigCommand;
 pub use status::StatusCommand;
 pub use threats::ThreatsCommand;
 pub use models::ModelsCommand;
+pub use ops::OpsCommand;
+pub use backup::BackupCommand;
+pub use forensics::ForensicsCommand;
+pub use support_bundle::SupportBundleCommand;
+pub use remote_assist::RemoteAssistCommand;
+pub use content::ContentCommand;
+pub use policy::PolicyCommand;
+pub use dead_letters::DeadLettersCommand;
+pub use audit::AuditCommand;
+pub use diagnose::DiagnoseCommand;
+pub use tokens::TokensCommand;
+pub use workflows::WorkflowsCommand;
+pub use response::ResponseCommand;
 
 // Constants for CLI configuration
 const CLI_VERSION: &str = env!("CARGO_PKG_VERSION");
 const APP_NAME: &str = "guardian-ctl";
-const COMMAND_TIMEOUT: Duration = Duration::from_secs(1);
+/// Default timeout of status, listing and other quick commands
+pub const SHORT_COMMAND_TIMEOUT: Duration = Duration::from_secs(10);
+/// Default timeout of uploads, backups and other data operations
+pub const LONG_COMMAND_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// Subcommands that stream until interrupted and so run without the command timeout
+const STREAMING_COMMANDS: &[(&str, &str)] = &[("threats", "watch")];
+
+/// Returns whether a command runs until interrupted rather than completing
+pub fn is_streaming(name: &str, subcommand: Option<&str>) -> bool {
+    STREAMING_COMMANDS.iter().any(|(command, sub)| *command == name && Some(*sub) == subcommand)
+}
+
+/// How long a command may run before it is abandoned
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CommandTimeout {
+    /// Status and listing commands
+    #[default]
+    Short,
+    /// Uploads, backups and other data operations
+    Long,
+    Fixed(Duration),
+}
+
+impl CommandTimeout {
+    pub fn duration(&self) -> Duration {
+        match self {
+            CommandTimeout::Short => SHORT_COMMAND_TIMEOUT,
+            CommandTimeout::Long => LONG_COMMAND_TIMEOUT,
+            CommandTimeout::Fixed(duration) => *duration,
+        }
+    }
+}
+
+/// Registration metadata of a command
+#[derive(Debug, Clone, Default)]
+pub struct CommandOptions {
+    pub timeout: CommandTimeout,
+    /// Subcommands whose timeout differs from the command's, e.g. a quick `list` under a long `create`
+    pub subcommand_timeouts: HashMap<&'static str, CommandTimeout>,
+}
+
+impl CommandOptions {
+    pub fn timeout(timeout: CommandTimeout) -> Self {
+        Self {
+            timeout,
+            subcommand_timeouts: HashMap::new(),
+        }
+    }
+
+    pub fn with_subcommand(mut self, subcommand: &'static str, timeout: CommandTimeout) -> Self {
+        self.subcommand_timeouts.insert(subcommand, timeout);
+        self
+    }
+
+    fn timeout_for(&self, subcommand: Option<&str>) -> CommandTimeout {
+        subcommand
+            .and_then(|sub| self.subcommand_timeouts.get(sub))
+            .copied()
+            .unwrap_or(self.timeout)
+    }
+}
+
+/// Parses a `--timeout` value: seconds, or a number suffixed with `s`, `m` or `h`
+pub fn parse_timeout(raw: &str) -> Result<Duration, String> {
+    let raw = raw.trim();
+    let (number, unit) = match raw.char_indices().last() {
+        Some((i, 's')) => (&raw[..i], 1),
+        Some((i, 'm')) => (&raw[..i], 60),
+        Some((i, 'h')) => (&raw[..i], 60 * 60),
+        _ => (raw, 1),
+    };
+    match number.parse::<u64>() {
+        Ok(value) if value > 0 => Ok(Duration::from_secs(value.saturating_mul(unit))),
+        _ => Err(format!("invalid timeout {:?}, expected e.g. 90, 90s, 15m or 2h", raw)),
+    }
+}
 
 /// Access levels for command execution
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -41,7 +145,7 @@ pub trait Command: Send + Sync {
     /// Executes the command with access validation
     async fn execute(&self, args: ArgMatches) -> Result<(), GuardianError>;
 
-    /// Returns required access level for command
+    /// Returns the built-in role the default RBAC policy grants this command to
     fn access_level(&self) -> AccessLevel;
 }
 
@@ -49,25 +153,35 @@ pub trait Command: Send + Sync {
 #[derive(Debug)]
 pub struct CommandRegistry {
     commands: HashMap<String, Box<dyn Command>>,
+    options: HashMap<String, CommandOptions>,
     metrics: Arc<metrics::MetricsCollector>,
-    audit_log: Arc<crate::utils::logging::LogManager>,
+    /// Every execution is recorded here, and through it in the audit chain
+    audit: Arc<CommandAudit>,
 }
 
 impl CommandRegistry {
-    /// Creates a new CommandRegistry instance with metrics and audit logging
-    pub fn new(
-        metrics: Arc<metrics::MetricsCollector>,
-        audit_log: Arc<crate::utils::logging::LogManager>,
-    ) -> Self {
+    /// Creates a new CommandRegistry instance with metrics and command auditing
+    pub fn new(metrics: Arc<metrics::MetricsCollector>, audit: Arc<CommandAudit>) -> Self {
         Self {
             commands: HashMap::new(),
+            options: HashMap::new(),
             metrics,
-            audit_log,
+            audit,
         }
     }
 
-    /// Registers a new command with access level validation
+    /// Registers a new command with the short default timeout
     pub fn register(&mut self, name: String, command: Box<dyn Command>) -> Result<(), GuardianError> {
+        self.register_with_options(name, command, CommandOptions::default())
+    }
+
+    /// Registers a new command with its timeouts
+    pub fn register_with_options(
+        &mut self,
+        name: String,
+        command: Box<dyn Command>,
+        options: CommandOptions,
+    ) -> Result<(), GuardianError> {
         // Validate command name
         if name.is_empty() {
             return Err(GuardianError::ValidationError {
@@ -75,7 +189,7 @@ impl CommandRegistry {
                 source: None,
                 severity: ErrorSeverity::Medium,
                 timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id: uuid::Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: ErrorCategory::Validation,
                 retry_count: 0,
             });
@@ -88,7 +202,7 @@ impl CommandRegistry {
                 source: None,
                 severity: ErrorSeverity::Medium,
                 timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id: uuid::Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: ErrorCategory::Validation,
                 retry_count: 0,
             });
@@ -96,21 +210,34 @@ impl CommandRegistry {
 
         // Register command
         self.commands.insert(name.clone(), command);
+        self.options.insert(name.clone(), options);
         
         info!("Registered command: {}", name);
         Ok(())
     }
 
-    /// Executes a command with access validation and metrics
-    #[instrument(skip(self, args))]
+    /// Timeout a command runs under, `None` for commands that stream until interrupted
+    ///
+    /// An explicit `--timeout` overrides the registered timeout.
+    pub fn timeout_for(&self, name: &str, subcommand: Option<&str>, requested: Option<Duration>) -> Option<Duration> {
+        if is_streaming(name, subcommand) {
+            return None;
+        }
+        let registered = self.options.get(name).map(|options| options.timeout_for(subcommand)).unwrap_or_default();
+        Some(requested.unwrap_or_else(|| registered.duration()))
+    }
+
+    /// Executes a command with RBAC authorization and metrics
+    #[instrument(skip(self, args, principal))]
     pub async fn execute(
         &self,
         name: String,
         args: ArgMatches,
-        access_level: AccessLevel,
+        principal: &Principal,
+        timeout: Option<Duration>,
     ) -> Result<(), GuardianError> {
         let start_time = Instant::now();
-        let correlation_id = uuid::Uuid::new_v4();
+        let correlation_id = crate::utils::correlation::current_or_new();
 
         debug!(
             correlation_id = %correlation_id,
@@ -129,23 +256,48 @@ impl CommandRegistry {
             retry_count: 0,
         })?;
 
-        // Validate access level
-        self.validate_access(command.access_level(), access_level)?;
-
-        // Execute with timeout
-        let result = match time::timeout(COMMAND_TIMEOUT, command.execute(args)).await {
-            Ok(res) => res,
-            Err(_) => {
-                error!("Command execution timeout");
-                return Err(GuardianError::SystemError {
-                    context: "Command execution timeout".into(),
-                    source: None,
-                    severity: ErrorSeverity::High,
-                    timestamp: time::OffsetDateTime::now_utc(),
-                    correlation_id,
-                    category: ErrorCategory::System,
-                    retry_count: 0,
-                });
+        // Every invocation is audited with its redacted arguments, allowed or not
+        let mut invocation = CommandInvocation {
+            interface: Interface::Cli,
+            command: command_path(&name, &args),
+            principal: principal.name.clone(),
+            args: matches_args(&args),
+            decision: AccessDecision::Allowed,
+            error: None,
+            duration: Duration::ZERO,
+        };
+
+        // Authorize the command, and its subcommand when one is given
+        let permission = rbac::cli_permission(&name, args.subcommand_name());
+        if let Err(e) = rbac::rbac().authorize(principal, &permission) {
+            invocation.decision = AccessDecision::Denied;
+            invocation.duration = start_time.elapsed();
+            self.audit.record(invocation);
+            return Err(e);
+        }
+
+        // Execute with the command's timeout, except for commands that stream until interrupted
+        let timeout = self.timeout_for(&name, args.subcommand_name(), timeout);
+        let execution = command.execute(args);
+        let result = match timeout {
+            None => execution.await,
+            Some(timeout) => match time::timeout(timeout, execution).await {
+                Ok(res) => res,
+                Err(_) => {
+                    error!(timeout_secs = timeout.as_secs(), "Command execution timeout");
+                    invocation.error = Some(format!("Command execution timeout after {:?}", timeout));
+                    invocation.duration = start_time.elapsed();
+                    self.audit.record(invocation);
+                    return Err(GuardianError::SystemError {
+                        context: format!("Command {} timed out after {}s, raise it with --timeout", name, timeout.as_secs()),
+                        source: None,
+                        severity: ErrorSeverity::High,
+                        timestamp: time::OffsetDateTime::now_utc(),
+                        correlation_id,
+                        category: ErrorCategory::System,
+                        retry_count: 0,
+                    });
+                }
             }
         };
 
@@ -154,6 +306,10 @@ impl CommandRegistry {
         histogram!("guardian.cli.execution_time", execution_time.as_secs_f64());
         counter!("guardian.cli.commands.executed", 1);
 
+        invocation.error = result.as_ref().err().map(|e| e.to_string());
+        invocation.duration = execution_time;
+        self.audit.record(invocation);
+
         if let Err(e) = &result {
             counter!("guardian.cli.commands.failed", 1);
             error!(
@@ -166,41 +322,82 @@ impl CommandRegistry {
 
         result
     }
+}
 
-    /// Validates user access level against command requirements
-    fn validate_access(&self, required: AccessLevel, user: AccessLevel) -> Result<(), GuardianError> {
-        let authorized = match required {
-            AccessLevel::Admin => user == AccessLevel::Admin,
-            AccessLevel::Security => matches!(user, AccessLevel::Admin | AccessLevel::Security),
-            AccessLevel::Operator => matches!(
-                user,
-                AccessLevel::Admin | AccessLevel::Security | AccessLevel::Operator
-            ),
-            AccessLevel::DataScientist => matches!(
-                user,
-                AccessLevel::Admin | AccessLevel::DataScientist
-            ),
-        };
+/// Command name followed by the chain of subcommands invoked, e.g. `status transitions`
+fn command_path(name: &str, args: &ArgMatches) -> String {
+    let mut path = name.to_string();
+    let mut current = args.subcommand();
+    while let Some((sub, sub_args)) = current {
+        path.push(' ');
+        path.push_str(sub);
+        current = sub_args.subcommand();
+    }
+    path
+}
 
-        if !authorized {
-            return Err(GuardianError::SecurityError {
-                context: "Insufficient access level".into(),
-                source: None,
-                severity: ErrorSeverity::High,
-                timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id: uuid::Uuid::new_v4(),
-                category: ErrorCategory::Security,
-                retry_count: 0,
-            });
-        }
+/// Runs a future to completion from synchronous command code
+///
+/// A multi-threaded runtime worker hands its other tasks off while it blocks. Anywhere else,
+/// including a current-thread runtime where blocking would stall every task, the future runs
+/// on a scoped thread with a runtime of its own.
+pub(crate) fn block_on<F>(future: F) -> Result<F::Output, GuardianError>
+where
+    F: std::future::Future + Send,
+    F::Output: Send,
+{
+    use tokio::runtime::{Builder, Handle, RuntimeFlavor};
 
-        Ok(())
+    if let Ok(handle) = Handle::try_current() {
+        if handle.runtime_flavor() == RuntimeFlavor::MultiThread {
+            return Ok(tokio::task::block_in_place(|| handle.block_on(future)));
+        }
     }
+    std::thread::scope(|scope| {
+        scope
+            .spawn(|| -> Result<F::Output, GuardianError> {
+                let runtime = Builder::new_current_thread().enable_all().build().map_err(|e| GuardianError::SystemError {
+                    context: "Failed to build a runtime for the command".into(),
+                    source: Some(Box::new(e)),
+                    severity: ErrorSeverity::High,
+                    timestamp: ::time::OffsetDateTime::now_utc(),
+                    correlation_id: crate::utils::correlation::current_or_new(),
+                    category: ErrorCategory::System,
+                    retry_count: 0,
+                })?;
+                Ok(runtime.block_on(future))
+            })
+            .join()
+            .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+    })
+}
+
+/// Remote model registry of `ml.toml`, with its credentials opened under the config KEK
+async fn model_remote() -> Result<Option<Arc<crate::storage::ObjectStoreBackend>>, GuardianError> {
+    let config_dir = std::path::Path::new(crate::config::DEFAULT_CONFIG_PATH);
+    let ml_config = crate::config::MLConfig::load(config_dir.join("ml.toml").to_string_lossy().to_string())?;
+    let Some(remote) = ml_config.remote else {
+        return Ok(None);
+    };
+    let security_config = crate::config::SecurityConfig::load_config(&crate::config::security_config_path(config_dir), None)?;
+    let secrets = crate::config::secrets::ConfigSecrets::new(crate::security::key_provider::key_provider_from_config(
+        &security_config.hw_security_config,
+    )?);
+    let remote = secrets.decrypt_config(&remote).await?;
+    Ok(Some(Arc::new(crate::storage::ObjectStoreBackend::new(&remote)?)))
+}
+
+/// API token authority of `security.toml`, signing with the same provider key as the daemon
+async fn api_token_authority() -> Result<Option<Arc<crate::security::auth::TokenAuthority>>, GuardianError> {
+    let security_path = crate::config::security_config_path(std::path::Path::new(crate::config::DEFAULT_CONFIG_PATH));
+    let security_config = crate::config::SecurityConfig::load_config(&security_path, None)?;
+    let provider = crate::security::key_provider::key_provider_from_config(&security_config.hw_security_config)?;
+    crate::security::auth::init_token_authority(&security_config.auth_config.api_tokens, provider).await
 }
 
 /// Registers all available CLI commands with their access levels
 #[instrument(skip(registry))]
-pub fn register_commands(registry: &mut CommandRegistry) -> Result<(), GuardianError> {
+pub async fn register_commands(registry: &mut CommandRegistry) -> Result<(), GuardianError> {
     // Register config command with admin access
     registry.register(
         "config".into(),
@@ -232,7 +429,7 @@ pub fn register_commands(registry: &mut CommandRegistry) -> Result<(), GuardianE
     )?;
 
     // Register threats command with security access
-    registry.register(
+    registry.register_with_options(
         "threats".into(),
         Box::new(ThreatsCommand::new(
             Arc::new(crate::security::threat_detection::ThreatDetector::new(
@@ -281,15 +478,26 @@ pub fn register_commands(registry: &mut CommandRegistry) -> Result<(), GuardianE
             ).await?),
             Arc::new(metrics::MetricsCollector::new()),
         )),
+        CommandOptions::timeout(CommandTimeout::Short)
+            .with_subcommand("export-feedback", CommandTimeout::Long),
     )?;
 
     // Register models command with data scientist access
-    registry.register(
+    // Without a readable remote the local registry still works; only sync needs it
+    let model_remote = model_remote().await.unwrap_or_else(|e| {
+        warn!(error = %e, "Remote model registry unavailable");
+        None
+    });
+    let with_model_remote = |store: crate::storage::model_store::ModelStore| match &model_remote {
+        Some(remote) => store.with_remote(Arc::clone(remote)),
+        None => store,
+    };
+    registry.register_with_options(
         "models".into(),
         Box::new(ModelsCommand::new(
             Arc::new(crate::ml::model_manager::ModelManager::new(
                 Arc::new(crate::ml::model_registry::ModelRegistry::new(
-                    Arc::new(crate::storage::model_store::ModelStore::new(
+                    Arc::new(with_model_remote(crate::storage::model_store::ModelStore::new(
                         Arc::new(crate::storage::zfs_manager::ZfsManager::new(
                             "guardian".into(),
                             vec![0u8; 32],
@@ -298,9 +506,9 @@ pub fn register_commands(registry: &mut CommandRegistry) -> Result<(), GuardianE
                         ).await?),
                         std::path::PathBuf::from("/var/lib/guardian/models"),
                         Some(5),
-                    ).await?),
+                    ).await?)),
                 ).await?),
-                Arc::new(crate::storage::model_store::ModelStore::new(
+                Arc::new(with_model_remote(crate::storage::model_store::ModelStore::new(
                     Arc::new(crate::storage::zfs_manager::ZfsManager::new(
                         "guardian".into(),
                         vec![0u8; 32],
@@ -309,10 +517,10 @@ pub fn register_commands(registry: &mut CommandRegistry) -> Result<(), GuardianE
                     ).await?),
                     std::path::PathBuf::from("/var/lib/guardian/models"),
                     Some(5),
-                ).await?),
+                ).await?)),
             ).await?),
             Arc::new(crate::ml::model_registry::ModelRegistry::new(
-                Arc::new(crate::storage::model_store::ModelStore::new(
+                Arc::new(with_model_remote(crate::storage::model_store::ModelStore::new(
                     Arc::new(crate::storage::zfs_manager::ZfsManager::new(
                         "guardian".into(),
                         vec![0u8; 32],
@@ -321,11 +529,216 @@ pub fn register_commands(registry: &mut CommandRegistry) -> Result<(), GuardianE
                     ).await?),
                     std::path::PathBuf::from("/var/lib/guardian/models"),
                     Some(5),
+                ).await?)),
+            ).await?),
+        )),
+        CommandOptions::timeout(CommandTimeout::Short)
+            .with_subcommand("activate", CommandTimeout::Long)
+            .with_subcommand("sync", CommandTimeout::Long),
+    )?;
+
+    // Register operations command with operator access
+    registry.register(
+        "ops".into(),
+        Box::new(OpsCommand::new()),
+    )?;
+
+    // Register backup command with admin access
+    registry.register_with_options(
+        "backup".into(),
+        Box::new(BackupCommand::new(Arc::new(crate::storage::zfs_manager::ZfsManager::new(
+            "guardian".into(),
+            vec![0u8; 32],
+            Arc::new(crate::utils::logging::LogManager::new()),
+            None,
+        ).await?))),
+        CommandOptions::timeout(CommandTimeout::Long),
+    )?;
+
+    // Register forensics command with security access
+    registry.register_with_options(
+        "forensics".into(),
+        Box::new(ForensicsCommand::new(Arc::new(crate::storage::ForensicManager::new(Arc::new(
+            crate::storage::zfs_manager::ZfsManager::new(
+                "guardian".into(),
+                vec![0u8; 32],
+                Arc::new(crate::utils::logging::LogManager::new()),
+                None,
+            ).await?,
+        ))))),
+        CommandOptions::timeout(CommandTimeout::Long)
+            .with_subcommand("list", CommandTimeout::Short),
+    )?;
+
+    // Register support bundle command with admin access
+    registry.register_with_options(
+        "support-bundle".into(),
+        Box::new(SupportBundleCommand::new(
+            Arc::new(crate::core::system_state::SystemState::new(
+                Arc::new(crate::core::metrics::CoreMetricsManager::new(
+                    Arc::new(metrics::MetricsCollector::new()),
+                    Default::default(),
+                )?),
+                Arc::new(crate::core::event_bus::EventBus::new(
+                    Arc::new(crate::core::metrics::CoreMetricsManager::new(
+                        Arc::new(metrics::MetricsCollector::new()),
+                        Default::default(),
+                    )?),
+                )?),
+                Default::default(),
+            )?),
+        )),
+        CommandOptions::timeout(CommandTimeout::Long),
+    )?;
+
+    // Register remote assistance command with admin access
+    registry.register(
+        "remote-assist".into(),
+        Box::new(RemoteAssistCommand::new(crate::security::remote_assistance::remote_assistance())),
+    )?;
+
+    // Register content pack command with admin access; packs are simulated against the daemon's
+    // detection history before activation
+    let mut content = ContentCommand::new(crate::security::content_pack::ContentPackConfig::default());
+    match crate::storage::event_store() {
+        Some(event_store) => {
+            let stages = crate::security::detection_pipeline::StageRegistry::with_builtins(Arc::new(
+                crate::ml::inference_engine::InferenceEngine::new(
+                    Arc::new(crate::ml::model_registry::ModelRegistry::new(
+                        Arc::new(with_model_remote(crate::storage::model_store::ModelStore::new(
+                            Arc::new(crate::storage::zfs_manager::ZfsManager::new(
+                                "guardian".into(),
+                                vec![0u8; 32],
+                                Arc::new(crate::utils::logging::LogManager::new()),
+                                None,
+                            ).await?),
+                            std::path::PathBuf::from("/var/lib/guardian/models"),
+                            Some(5),
+                        ).await?)),
+                    ).await?),
+                    Arc::new(crate::ml::feature_extractor::FeatureExtractor::new(
+                        crate::core::metrics::CoreMetricsManager::new(
+                            Arc::new(metrics::MetricsCollector::new()),
+                            Default::default(),
+                        )?,
+                        None,
+                    )),
+                    Default::default(),
+                ).await?,
+            ));
+            content = content.with_simulator(Arc::new(
+                crate::security::content_simulation::ContentSimulator::new(event_store, stages),
+            ));
+        }
+        None => warn!("Event store not open, content packs can only be activated with --confirm"),
+    }
+    registry.register_with_options(
+        "content".into(),
+        Box::new(content),
+        CommandOptions::timeout(CommandTimeout::Long)
+            .with_subcommand("list", CommandTimeout::Short),
+    )?;
+
+    // Register policy test command with operator access
+    registry.register_with_options(
+        "policy".into(),
+        Box::new(PolicyCommand::new(Arc::new(crate::security::policy_test::PolicyHarness::default()))),
+        CommandOptions::timeout(CommandTimeout::Long),
+    )?;
+
+    // Register dead-letter command with operator access
+    registry.register(
+        "dead-letters".into(),
+        Box::new(DeadLettersCommand::new(crate::core::dead_letter::dead_letters())),
+    )?;
+
+    // Register command audit with security access
+    registry.register(
+        "audit".into(),
+        Box::new(AuditCommand::new(crate::security::command_audit::command_audit())),
+    )?;
+
+    // Register diagnostics with operator access
+    registry.register_with_options(
+        "diagnose".into(),
+        Box::new(DiagnoseCommand::new(
+            std::path::PathBuf::from("/etc/guardian/config"),
+            Arc::new(crate::storage::zfs_manager::ZfsManager::new(
+                "guardian".into(),
+                vec![0u8; 32],
+                Arc::new(crate::utils::logging::LogManager::new()),
+                None,
+            ).await?),
+            Arc::new(crate::ml::inference_engine::InferenceEngine::new(
+                Arc::new(crate::ml::model_registry::ModelRegistry::new(
+                    Arc::new(with_model_remote(crate::storage::model_store::ModelStore::new(
+                        Arc::new(crate::storage::zfs_manager::ZfsManager::new(
+                            "guardian".into(),
+                            vec![0u8; 32],
+                            Arc::new(crate::utils::logging::LogManager::new()),
+                            None,
+                        ).await?),
+                        std::path::PathBuf::from("/var/lib/guardian/models"),
+                        Some(5),
+                    ).await?)),
                 ).await?),
+                Arc::new(crate::ml::feature_extractor::FeatureExtractor::new(
+                    crate::core::metrics::CoreMetricsManager::new(
+                        Arc::new(metrics::MetricsCollector::new()),
+                        Default::default(),
+                    )?,
+                    None,
+                )),
+                Default::default(),
             ).await?),
         )),
+        CommandOptions::timeout(CommandTimeout::Long),
+    )?;
+
+    // Register API token command with admin access
+    let token_authority = api_token_authority().await.unwrap_or_else(|e| {
+        warn!(error = %e, "API token authority unavailable");
+        None
+    });
+    registry.register(
+        "tokens".into(),
+        Box::new(TokensCommand::new(token_authority)),
+    )?;
+
+    // Register workflow administration with operator access; cancel and signal need the security role
+    registry.register(
+        "workflows".into(),
+        Box::new(WorkflowsCommand::new(crate::temporal::visibility::workflow_admin())),
+    )?;
+
+    // Register held response approval with security access
+    registry.register(
+        "response".into(),
+        Box::new(ResponseCommand::new()),
     )?;
 
     info!("All commands registered successfully");
     Ok(())
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_timeout() {
+        assert_eq!(parse_timeout("90"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse_timeout("90s"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse_timeout("15m"), Ok(Duration::from_secs(15 * 60)));
+        assert_eq!(parse_timeout("2h"), Ok(Duration::from_secs(2 * 60 * 60)));
+        assert!(parse_timeout("0").is_err());
+        assert!(parse_timeout("soon").is_err());
+    }
+
+    #[test]
+    fn test_subcommand_timeout_overrides() {
+        let options = CommandOptions::timeout(CommandTimeout::Long).with_subcommand("list", CommandTimeout::Short);
+        assert_eq!(options.timeout_for(Some("create")), CommandTimeout::Long);
+        assert_eq!(options.timeout_for(Some("list")).duration(), SHORT_COMMAND_TIMEOUT);
+        assert_eq!(options.timeout_for(None), CommandTimeout::Long);
+    }
+}
@@ -5,8 +5,10 @@ use tracing::{debug, error, info, instrument, warn};
 use metrics::{counter, gauge, histogram};
 
 use crate::cli::commands::Command as CliCommand;
-use crate::ml::model_registry::ModelRegistry;
+use crate::cli::output::{self, ProgressReporter};
+use crate::ml::model_registry::{ArmSummary, ExperimentSummary, ModelListQuery, ModelRegistry, ModelStatus};
 use crate::ml::model_manager::ModelManager;
+use crate::storage::SyncDirection;
 use crate::utils::error::GuardianError;
 
 // Constants for model management operations
@@ -37,29 +39,35 @@ impl ModelsCommand {
         }
     }
 
-    /// Lists all registered ML models with security context
+    /// Lists one page of registered ML models matching the given filters
     #[instrument]
-    async fn list_models(&self) -> Result<(), GuardianError> {
+    async fn list_models(&self, query: ModelListQuery) -> Result<(), GuardianError> {
         info!("Listing registered models");
         
         // Check resource availability
         self.check_resources().await?;
 
-        let models = self.registry.list_models().await?;
+        let page = self.registry.list_models(query).await?;
         
-        println!("\nRegistered Models:");
-        println!("{:<20} {:<15} {:<10} {:<15}", "MODEL ID", "VERSION", "STATUS", "LAST UPDATED");
-        println!("{}", "-".repeat(60));
-
-        for model in models {
-            let status = self.manager.get_model_status(&model.id).await?;
-            println!(
-                "{:<20} {:<15} {:<10} {:<15}",
-                model.id,
-                model.version,
-                status,
-                model.updated_at.format("%Y-%m-%d %H:%M")
-            );
+        let rows: Vec<Vec<String>> = page
+            .models
+            .iter()
+            .map(|model| vec![
+                model.name.clone(),
+                model.version.clone(),
+                model.status.as_str().to_string(),
+                model.created_at.format("%Y-%m-%d %H:%M").to_string(),
+                model.updated_at.format("%Y-%m-%d %H:%M").to_string(),
+            ])
+            .collect();
+
+        if !output::current().is_plain() {
+            println!("\nRegistered Models:");
+        }
+        print!("{}", output::render_table(&["MODEL ID", "VERSION", "STATUS", "CREATED", "LAST UPDATED"], &rows));
+        if let Some(token) = &page.next_page_token {
+            // Shown on stderr so plain output stays a clean table
+            eprintln!("More models match; continue with --page-token {}", token);
         }
 
         // Record metrics
@@ -111,7 +119,13 @@ impl ModelsCommand {
 
         // Activate version with monitoring
         let start = std::time::Instant::now();
-        self.registry.set_active_version(model_id.clone(), version.clone()).await?;
+        let mut progress = ProgressReporter::start("models.activate");
+        progress.update(50.0, "validated, switching active version");
+        if let Err(e) = self.registry.set_active_version(model_id.clone(), version.clone()).await {
+            progress.fail("activation failed");
+            return Err(e);
+        }
+        progress.finish("activated");
 
         // Record metrics
         counter!("guardian.cli.models.activate").increment(1);
@@ -121,6 +135,67 @@ impl ModelsCommand {
         Ok(())
     }
 
+    /// Starts an A/B experiment between two registered versions
+    #[instrument]
+    async fn start_experiment(
+        &self,
+        name: String,
+        control: String,
+        treatment: String,
+        treatment_percent: u8,
+    ) -> Result<(), GuardianError> {
+        let experiment = self.registry.start_experiment(name, control, treatment, treatment_percent).await?;
+        counter!("guardian.cli.models.experiment_start").increment(1);
+        println!(
+            "Experiment {} started: {}% of events to {}, the rest to {}",
+            experiment.name, experiment.treatment_percent, experiment.treatment, experiment.control
+        );
+        Ok(())
+    }
+
+    /// Shows per-arm results of the running experiment, or its final results when stopping it
+    #[instrument]
+    async fn experiment_summary(&self, stop: bool) -> Result<(), GuardianError> {
+        let summary = if stop {
+            self.registry.stop_experiment().await
+        } else {
+            self.registry.experiment_summary().await
+        };
+        let summary = summary.ok_or_else(|| GuardianError::ValidationError("No model experiment is running".to_string()))?;
+        print_experiment(&summary);
+        Ok(())
+    }
+
+    /// Mirrors versions between the local registry and the remote object store
+    #[instrument]
+    async fn sync_versions(&self, direction: SyncDirection) -> Result<(), GuardianError> {
+        let mut progress = ProgressReporter::start("models.sync");
+        progress.update(10.0, "comparing local and remote registries");
+        let report = match self.registry.model_store().sync(direction).await {
+            Ok(report) => report,
+            Err(e) => {
+                progress.fail("sync failed");
+                return Err(e);
+            }
+        };
+        progress.finish(&format!("{} pushed, {} pulled", report.pushed.len(), report.pulled.len()));
+
+        let rows: Vec<Vec<String>> = report
+            .pushed
+            .iter()
+            .map(|v| vec![v.clone(), "pushed".to_string()])
+            .chain(report.pulled.iter().map(|v| vec![v.clone(), "pulled".to_string()]))
+            .chain(report.conflicts.iter().map(|v| vec![v.clone(), "conflict".to_string()]))
+            .collect();
+        print!("{}", output::render_table(&["VERSION", "RESULT"], &rows));
+        if !report.conflicts.is_empty() {
+            eprintln!("Conflicting versions differ between the registries and were left untouched");
+        }
+
+        counter!("guardian.cli.models.sync").increment(1);
+        Ok(())
+    }
+
     /// Checks system resource availability
     async fn check_resources(&self) -> Result<(), GuardianError> {
         let monitor = self.resource_monitor.read().await;
@@ -147,7 +222,28 @@ impl CliCommand for ModelsCommand {
         Command::new(COMMAND_NAME)
             .about(HELP_TEXT)
             .subcommand(Command::new("list")
-                .about("List all registered models"))
+                .about("List registered models, newest first")
+                .arg(Arg::new("status")
+                    .long("status")
+                    .value_parser(["active", "inactive", "failed", "validating", "deprecated"])
+                    .help("Only models with this status"))
+                .arg(Arg::new("since")
+                    .long("since")
+                    .help("Only models created at or after this RFC 3339 time"))
+                .arg(Arg::new("until")
+                    .long("until")
+                    .help("Only models created before this RFC 3339 time"))
+                .arg(Arg::new("prefix")
+                    .long("prefix")
+                    .help("Only models whose name starts with this prefix"))
+                .arg(Arg::new("limit")
+                    .long("limit")
+                    .value_parser(clap::value_parser!(usize))
+                    .default_value("50")
+                    .help("Models per page"))
+                .arg(Arg::new("page-token")
+                    .long("page-token")
+                    .help("Token printed by the previous page")))
             .subcommand(Command::new("status")
                 .about("Show model status and metrics")
                 .arg(Arg::new("model-id")
@@ -161,12 +257,48 @@ impl CliCommand for ModelsCommand {
                 .arg(Arg::new("version")
                     .required(true)
                     .help("Version to activate")))
+            .subcommand(Command::new("experiment")
+                .about("Compare two model versions on a split of live traffic")
+                .subcommand_required(true)
+                .subcommand(Command::new("start")
+                    .about("Start an experiment, replacing any running one")
+                    .arg(Arg::new("name")
+                        .required(true)
+                        .help("Experiment name"))
+                    .arg(Arg::new("control")
+                        .long("control")
+                        .required(true)
+                        .help("Version the treatment is measured against"))
+                    .arg(Arg::new("treatment")
+                        .long("treatment")
+                        .required(true)
+                        .help("Candidate version"))
+                    .arg(Arg::new("percent")
+                        .long("percent")
+                        .value_parser(clap::value_parser!(u8).range(0..=100))
+                        .default_value("50")
+                        .help("Percentage of events served by the treatment")))
+                .subcommand(Command::new("summary")
+                    .about("Show per-arm precision, recall and latency so far"))
+                .subcommand(Command::new("stop")
+                    .about("Stop the experiment and show its final results")))
+            .subcommand(Command::new("sync")
+                .about("Mirror model versions between the local and remote registries")
+                .arg(Arg::new("push")
+                    .long("push")
+                    .action(clap::ArgAction::SetTrue)
+                    .conflicts_with("pull")
+                    .help("Only publish local versions the remote lacks"))
+                .arg(Arg::new("pull")
+                    .long("pull")
+                    .action(clap::ArgAction::SetTrue)
+                    .help("Only fetch remote versions the local registry lacks")))
     }
 
     async fn execute(&self, args: &ArgMatches) -> Result<(), GuardianError> {
         match args.subcommand() {
-            Some(("list", _)) => {
-                self.list_models().await
+            Some(("list", sub_matches)) => {
+                self.list_models(list_query(sub_matches)?).await
             }
             Some(("status", sub_matches)) => {
                 let model_id = sub_matches.get_one::<String>("model-id")
@@ -180,6 +312,27 @@ impl CliCommand for ModelsCommand {
                     .ok_or_else(|| GuardianError::ValidationError("Version required".to_string()))?;
                 self.activate_version(model_id.clone(), version.clone()).await
             }
+            Some(("experiment", sub_matches)) => match sub_matches.subcommand() {
+                Some(("start", start)) => {
+                    let required = |name: &str| {
+                        start.get_one::<String>(name).cloned()
+                            .ok_or_else(|| GuardianError::ValidationError(format!("--{} required", name)))
+                    };
+                    let percent = start.get_one::<u8>("percent").copied().unwrap_or(50);
+                    self.start_experiment(required("name")?, required("control")?, required("treatment")?, percent).await
+                }
+                Some(("summary", _)) => self.experiment_summary(false).await,
+                Some(("stop", _)) => self.experiment_summary(true).await,
+                _ => Err(GuardianError::ValidationError("Invalid experiment subcommand".to_string())),
+            },
+            Some(("sync", sub_matches)) => {
+                let direction = match (sub_matches.get_flag("push"), sub_matches.get_flag("pull")) {
+                    (true, _) => SyncDirection::Push,
+                    (_, true) => SyncDirection::Pull,
+                    _ => SyncDirection::Both,
+                };
+                self.sync_versions(direction).await
+            }
             _ => Err(GuardianError::ValidationError("Invalid subcommand".to_string())),
         }
     }
@@ -193,6 +346,62 @@ impl CliCommand for ModelsCommand {
     }
 }
 
+fn print_experiment(summary: &ExperimentSummary) {
+    let ratio = |value: Option<f64>| value.map(|v| format!("{:.3}", v)).unwrap_or_else(|| "-".to_string());
+    let row = |arm: &ArmSummary| vec![
+        arm.arm.as_str().to_string(),
+        arm.version.clone(),
+        arm.inferences.to_string(),
+        format!("{:.2}", arm.mean_latency_ms),
+        ratio(arm.precision),
+        ratio(arm.recall),
+        format!("{}/{}/{}", arm.true_positives, arm.false_positives, arm.false_negatives),
+    ];
+
+    if !output::current().is_plain() {
+        println!(
+            "\nExperiment {} ({}% treatment, started {}):",
+            summary.experiment.name,
+            summary.experiment.treatment_percent,
+            summary.experiment.started_at.format("%Y-%m-%d %H:%M")
+        );
+    }
+    print!(
+        "{}",
+        output::render_table(
+            &["ARM", "VERSION", "INFERENCES", "LATENCY MS", "PRECISION", "RECALL", "TP/FP/FN"],
+            &[row(&summary.control), row(&summary.treatment)],
+        )
+    );
+}
+
+/// Builds a listing query from `models list` flags
+fn list_query(matches: &ArgMatches) -> Result<ModelListQuery, GuardianError> {
+    let time = |name: &str| -> Result<Option<chrono::DateTime<chrono::Utc>>, GuardianError> {
+        matches
+            .get_one::<String>(name)
+            .map(|raw| {
+                chrono::DateTime::parse_from_rfc3339(raw)
+                    .map(|t| t.with_timezone(&chrono::Utc))
+                    .map_err(|_| GuardianError::ValidationError(format!("Invalid --{} timestamp: {}", name, raw)))
+            })
+            .transpose()
+    };
+
+    Ok(ModelListQuery {
+        status: matches
+            .get_one::<String>("status")
+            .map(|raw| raw.parse::<ModelStatus>())
+            .transpose()
+            .map_err(GuardianError::ValidationError)?,
+        name_prefix: matches.get_one::<String>("prefix").cloned(),
+        created_after: time("since")?,
+        created_before: time("until")?,
+        page_size: matches.get_one::<usize>("limit").copied().unwrap_or_default(),
+        page_token: matches.get_one::<String>("page-token").cloned(),
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,304 @@
+use clap::{Arg, ArgMatches, Command};
+use std::time::Duration;
+use tonic::transport::Channel;
+use tracing::{info, instrument};
+use metrics::counter;
+use uuid::Uuid;
+
+use crate::api::grpc::guardian_service::guardian_proto::{
+    guardian_service_client::GuardianServiceClient, ListOperationsRequest, Operation, OperationRequest,
+    OperationStatus,
+};
+use crate::cli::commands::{AccessLevel, Command as CliCommand};
+use crate::cli::output;
+use crate::utils::error::{ErrorCategory, ErrorSeverity, GuardianError};
+
+// Constants for operation tracking commands
+const COMMAND_NAME: &str = "ops";
+const HELP_TEXT: &str = "Track, inspect and cancel long-running operations";
+const DEFAULT_ENDPOINT: &str = "http://127.0.0.1:50051";
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Builds the `ops` subcommand definition
+pub fn build_ops_subcommand() -> Command {
+    Command::new(COMMAND_NAME)
+        .about(HELP_TEXT)
+        .arg(Arg::new("endpoint")
+            .long("endpoint")
+            .global(true)
+            .env("GUARDIAN_ENDPOINT")
+            .default_value(DEFAULT_ENDPOINT)
+            .help("gRPC endpoint of the Guardian daemon"))
+        .subcommand(Command::new("list")
+            .about("List tracked operations")
+            .arg(Arg::new("status")
+                .short('s')
+                .long("status")
+                .value_parser(["running", "completed", "failed", "cancelled"])
+                .help("Only show operations with this status"))
+            .arg(Arg::new("format")
+                .short('f')
+                .long("format")
+                .value_parser(["table", "json"])
+                .default_value("table")
+                .help("Output format")))
+        .subcommand(Command::new("get")
+            .about("Show a single operation")
+            .arg(Arg::new("operation-id")
+                .required(true)
+                .help("Operation identifier")))
+        .subcommand(Command::new("cancel")
+            .about("Request cancellation of a running operation")
+            .arg(Arg::new("operation-id")
+                .required(true)
+                .help("Operation identifier")))
+        .subcommand(Command::new("rotate-keys")
+            .about("Rotate encryption keys as a tracked operation"))
+}
+
+/// CLI command exposing the daemon's operation registry
+#[derive(Debug, Default)]
+pub struct OpsCommand;
+
+impl OpsCommand {
+    /// Creates a new OpsCommand querying the daemon over gRPC
+    pub fn new() -> Self {
+        Self
+    }
+
+    async fn connect(&self, endpoint: &str) -> Result<GuardianServiceClient<Channel>, GuardianError> {
+        let channel = tonic::transport::Endpoint::from_shared(endpoint.to_string())
+            .map_err(|e| ops_error(format!("Invalid endpoint {}", endpoint), Some(Box::new(e))))?
+            .connect_timeout(CONNECT_TIMEOUT)
+            .connect()
+            .await
+            .map_err(|e| ops_error(format!("Failed to connect to {}", endpoint), Some(Box::new(e))))?;
+        Ok(GuardianServiceClient::new(channel))
+    }
+
+    /// Lists operations as a table or JSON
+    #[instrument(skip(self, client))]
+    async fn list_operations(
+        &self,
+        client: &mut GuardianServiceClient<Channel>,
+        status: OperationStatus,
+        format: &str,
+    ) -> Result<(), GuardianError> {
+        let operations = client
+            .list_operations(ListOperationsRequest { status_filter: status as i32 })
+            .await
+            .map_err(|e| ops_error(format!("Listing operations failed: {}", e.message()), None))?
+            .into_inner()
+            .operations;
+
+        if format == "json" {
+            let operations: Vec<serde_json::Value> = operations.iter().map(operation_json).collect();
+            println!("{}", serde_json::to_string_pretty(&operations)?);
+        } else {
+            let rows: Vec<Vec<String>> = operations.iter().map(operation_row).collect();
+            print!("{}", output::render_table(
+                &["OPERATION ID", "KIND", "STATUS", "PROGRESS", "UPDATED"],
+                &rows,
+            ));
+        }
+
+        counter!("guardian.cli.ops.list", 1);
+        Ok(())
+    }
+
+    /// Shows a single operation as JSON
+    #[instrument(skip(self, client))]
+    async fn show_operation(&self, client: &mut GuardianServiceClient<Channel>, id: Uuid) -> Result<(), GuardianError> {
+        let operation = client
+            .get_operation(OperationRequest { operation_id: id.to_string() })
+            .await
+            .map_err(|e| ops_error(format!("Operation {}: {}", id, e.message()), None))?
+            .into_inner();
+        println!("{}", serde_json::to_string_pretty(&operation_json(&operation))?);
+        Ok(())
+    }
+
+    /// Requests cancellation of an operation
+    #[instrument(skip(self, client))]
+    async fn cancel_operation(&self, client: &mut GuardianServiceClient<Channel>, id: Uuid) -> Result<(), GuardianError> {
+        let operation = client
+            .cancel_operation(OperationRequest { operation_id: id.to_string() })
+            .await
+            .map_err(|e| ops_error(format!("Cancelling operation {} failed: {}", id, e.message()), None))?
+            .into_inner();
+        info!(operation_id = %id, "Cancellation requested");
+        println!("Cancellation requested for operation {} ({})", operation.operation_id, operation.kind);
+        counter!("guardian.cli.ops.cancel", 1);
+        Ok(())
+    }
+
+    /// Starts key rotation on the daemon and prints the operation tracking it
+    #[instrument(skip(self, client))]
+    async fn rotate_keys(&self, client: &mut GuardianServiceClient<Channel>) -> Result<(), GuardianError> {
+        let operation = client
+            .rotate_keys(())
+            .await
+            .map_err(|e| ops_error(format!("Key rotation refused: {}", e.message()), None))?
+            .into_inner();
+        info!(operation_id = %operation.operation_id, "Key rotation started");
+        println!("Key rotation started as operation {}", operation.operation_id);
+        counter!("guardian.cli.ops.rotate_keys", 1);
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl CliCommand for OpsCommand {
+    fn name(&self) -> &'static str {
+        COMMAND_NAME
+    }
+
+    fn configure(&self) -> Command {
+        build_ops_subcommand()
+    }
+
+    async fn execute(&self, args: &ArgMatches) -> Result<(), GuardianError> {
+        let Some((subcommand, sub_matches)) = args.subcommand() else {
+            return Err(invalid("Invalid subcommand".into()));
+        };
+        // Parse before connecting so a malformed ID fails without reaching the daemon
+        let id = match subcommand {
+            "get" | "cancel" => Some(parse_id(sub_matches)?),
+            "list" | "rotate-keys" => None,
+            _ => return Err(invalid("Invalid subcommand".into())),
+        };
+        let endpoint = sub_matches.get_one::<String>("endpoint").map(String::as_str).unwrap_or(DEFAULT_ENDPOINT);
+        let mut client = self.connect(endpoint).await?;
+
+        match (subcommand, id) {
+            ("list", _) => {
+                let status = sub_matches.get_one::<String>("status").map_or(OperationStatus::Unknown, |s| parse_status(s));
+                let format = sub_matches.get_one::<String>("format").map(String::as_str).unwrap_or("table");
+                self.list_operations(&mut client, status, format).await
+            }
+            ("get", Some(id)) => self.show_operation(&mut client, id).await,
+            ("cancel", Some(id)) => self.cancel_operation(&mut client, id).await,
+            _ => self.rotate_keys(&mut client).await,
+        }
+    }
+
+    fn required_access(&self) -> AccessLevel {
+        AccessLevel::Operator
+    }
+
+    fn help(&self) -> &'static str {
+        HELP_TEXT
+    }
+}
+
+fn status_name(status: i32) -> &'static str {
+    match OperationStatus::from_i32(status) {
+        Some(OperationStatus::Running) => "running",
+        Some(OperationStatus::Completed) => "completed",
+        Some(OperationStatus::Failed) => "failed",
+        Some(OperationStatus::Cancelled) => "cancelled",
+        _ => "unknown",
+    }
+}
+
+fn format_timestamp(timestamp: Option<&prost_types::Timestamp>) -> String {
+    timestamp
+        .and_then(|t| chrono::DateTime::<chrono::Utc>::from_timestamp(t.seconds, t.nanos.max(0) as u32))
+        .map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_default()
+}
+
+fn operation_row(operation: &Operation) -> Vec<String> {
+    vec![
+        operation.operation_id.clone(),
+        operation.kind.clone(),
+        status_name(operation.status).to_string(),
+        format!("{:.0}%", operation.progress_percent),
+        format_timestamp(operation.updated_at.as_ref()),
+    ]
+}
+
+fn operation_json(operation: &Operation) -> serde_json::Value {
+    serde_json::json!({
+        "id": operation.operation_id,
+        "kind": operation.kind,
+        "description": operation.description,
+        "status": status_name(operation.status),
+        "progress_percent": operation.progress_percent,
+        "cancel_requested": operation.cancel_requested,
+        "error": (!operation.error_message.is_empty()).then_some(&operation.error_message),
+        "created_at": format_timestamp(operation.created_at.as_ref()),
+        "updated_at": format_timestamp(operation.updated_at.as_ref()),
+    })
+}
+
+fn parse_status(raw: &str) -> OperationStatus {
+    match raw {
+        "completed" => OperationStatus::Completed,
+        "failed" => OperationStatus::Failed,
+        "cancelled" => OperationStatus::Cancelled,
+        _ => OperationStatus::Running,
+    }
+}
+
+fn parse_id(matches: &ArgMatches) -> Result<Uuid, GuardianError> {
+    let raw = matches.get_one::<String>("operation-id")
+        .ok_or_else(|| invalid("Operation ID required".into()))?;
+    Uuid::parse_str(raw).map_err(|_| invalid(format!("Invalid operation ID: {}", raw)))
+}
+
+fn ops_error(context: String, source: Option<Box<dyn std::error::Error + Send + Sync>>) -> GuardianError {
+    GuardianError::SystemError {
+        context,
+        source,
+        severity: ErrorSeverity::Medium,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: ErrorCategory::System,
+        retry_count: 0,
+    }
+}
+
+fn invalid(context: String) -> GuardianError {
+    GuardianError::ValidationError {
+        context,
+        source: None,
+        severity: ErrorSeverity::Low,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: ErrorCategory::Validation,
+        retry_count: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_ops_rejects_bad_id_before_connecting() {
+        let command = OpsCommand::new();
+        let args = build_ops_subcommand().get_matches_from(vec![
+            COMMAND_NAME, "get", "not-a-uuid", "--endpoint", "http://127.0.0.1:1",
+        ]);
+        let err = command.execute(&args).await.unwrap_err();
+        assert!(err.to_string().contains("Invalid operation ID"));
+    }
+
+    #[test]
+    fn test_operation_row_from_daemon_snapshot() {
+        let operation = Operation {
+            operation_id: Uuid::nil().to_string(),
+            kind: "key_rotation".into(),
+            status: OperationStatus::Running as i32,
+            progress_percent: 42.4,
+            updated_at: Some(prost_types::Timestamp { seconds: 0, nanos: 0 }),
+            ..Default::default()
+        };
+        assert_eq!(
+            operation_row(&operation),
+            vec![Uuid::nil().to_string(), "key_rotation".into(), "running".into(), "42%".into(), "1970-01-01 00:00:00".into()]
+        );
+        assert_eq!(operation_json(&operation)["error"], serde_json::Value::Null);
+    }
+}
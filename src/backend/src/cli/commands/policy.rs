@@ -0,0 +1,148 @@
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tracing::instrument;
+use metrics::counter;
+
+use crate::cli::commands::{AccessLevel, Command as CliCommand};
+use crate::cli::output;
+use crate::security::policy_test::{PolicyHarness, ScenarioResult};
+use crate::utils::error::GuardianError;
+
+// Constants for policy commands
+const COMMAND_NAME: &str = "policy";
+const HELP_TEXT: &str = "Test detection rules and response playbooks against fixture scenarios";
+
+/// Builds the `policy` subcommand definition
+pub fn build_policy_subcommand() -> Command {
+    Command::new(COMMAND_NAME)
+        .about(HELP_TEXT)
+        .subcommand(Command::new("test")
+            .about("Run YAML fixture scenarios and compare detections and actions with expectations")
+            .arg(Arg::new("paths")
+                .required(true)
+                .action(ArgAction::Append)
+                .help("Fixture files, or directories of .yaml/.yml fixtures"))
+            .arg(Arg::new("format")
+                .short('f')
+                .long("format")
+                .value_parser(["table", "json"])
+                .default_value("table")
+                .help("Output format")))
+}
+
+/// CLI command running policy-as-code tests, failing when any scenario fails
+#[derive(Debug)]
+pub struct PolicyCommand {
+    harness: Arc<PolicyHarness>,
+}
+
+impl PolicyCommand {
+    /// Creates a new PolicyCommand running scenarios with the given harness
+    pub fn new(harness: Arc<PolicyHarness>) -> Self {
+        Self { harness }
+    }
+
+    #[instrument(skip(self))]
+    async fn test(&self, paths: &[PathBuf], format: &str) -> Result<(), GuardianError> {
+        let results = self.harness.run_paths(paths).await?;
+        let failed = results.iter().filter(|r| !r.passed()).count();
+        counter!("guardian.cli.policy.test", 1);
+
+        if format == "json" {
+            println!("{}", serde_json::to_string_pretty(&results)?);
+        } else {
+            print_results(&results);
+        }
+
+        if failed > 0 {
+            return Err(invalid(format!("{} of {} policy scenarios failed", failed, results.len())));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl CliCommand for PolicyCommand {
+    fn name(&self) -> &'static str {
+        COMMAND_NAME
+    }
+
+    fn configure(&self) -> Command {
+        build_policy_subcommand()
+    }
+
+    async fn execute(&self, args: &ArgMatches) -> Result<(), GuardianError> {
+        match args.subcommand() {
+            Some(("test", sub_matches)) => {
+                let paths: Vec<PathBuf> = sub_matches
+                    .get_many::<String>("paths")
+                    .ok_or_else(|| invalid("Argument paths required".into()))?
+                    .map(PathBuf::from)
+                    .collect();
+                let format = sub_matches.get_one::<String>("format").map(String::as_str).unwrap_or("table");
+                self.test(&paths, format).await
+            }
+            _ => Err(invalid("Invalid subcommand".into())),
+        }
+    }
+
+    fn required_access(&self) -> AccessLevel {
+        AccessLevel::Operator
+    }
+
+    fn help(&self) -> &'static str {
+        HELP_TEXT
+    }
+}
+
+/// Prints one row per scenario followed by each failed expectation
+fn print_results(results: &[ScenarioResult]) {
+    let rows: Vec<Vec<String>> = results
+        .iter()
+        .map(|result| vec![
+            if result.passed() { "PASS".into() } else { "FAIL".into() },
+            result.fixture.display().to_string(),
+            result.scenario.clone(),
+            result.detections.join(","),
+            result.actions.join(","),
+        ])
+        .collect();
+    print!("{}", output::render_table(&["RESULT", "FIXTURE", "SCENARIO", "DETECTIONS", "ACTIONS"], &rows));
+
+    for result in results.iter().filter(|r| !r.passed()) {
+        for failure in &result.failures {
+            println!("{} / {}: {}", result.fixture.display(), result.scenario, failure);
+        }
+    }
+    let failed = results.iter().filter(|r| !r.passed()).count();
+    println!("{} scenarios, {} passed, {} failed", results.len(), results.len() - failed, failed);
+}
+
+fn invalid(context: String) -> GuardianError {
+    GuardianError::ValidationError {
+        context,
+        source: None,
+        severity: crate::utils::error::ErrorSeverity::Low,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: crate::utils::error::ErrorCategory::Validation,
+        retry_count: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_test_args() {
+        let matches = build_policy_subcommand()
+            .get_matches_from(vec![COMMAND_NAME, "test", "policies/lateral.yaml", "policies/fixtures", "-f", "json"]);
+        let (name, test) = matches.subcommand().unwrap();
+        assert_eq!(name, "test");
+        let paths: Vec<&String> = test.get_many::<String>("paths").unwrap().collect();
+        assert_eq!(paths, ["policies/lateral.yaml", "policies/fixtures"]);
+        assert_eq!(test.get_one::<String>("format").unwrap(), "json");
+    }
+}
@@ -0,0 +1,220 @@
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::instrument;
+use metrics::counter;
+
+use crate::cli::commands::{AccessLevel, Command as CliCommand};
+use crate::cli::output;
+use crate::security::remote_assistance::{AssistanceScope, AssistanceSession, RemoteAssistanceManager};
+use crate::utils::error::GuardianError;
+
+// Constants for remote assistance commands
+const COMMAND_NAME: &str = "remote-assist";
+const HELP_TEXT: &str = "Grant, inspect and revoke time-boxed vendor remote assistance sessions";
+
+/// Builds the `remote-assist` subcommand definition
+pub fn build_remote_assist_subcommand() -> Command {
+    Command::new(COMMAND_NAME)
+        .about(HELP_TEXT)
+        .subcommand(Command::new("enable")
+            .about("Open a remote assistance session for a vendor certificate")
+            .arg(Arg::new("vendor")
+                .long("vendor")
+                .required(true)
+                .help("SHA-256 fingerprint of the vendor's client certificate"))
+            .arg(Arg::new("scope")
+                .long("scope")
+                .action(ArgAction::Append)
+                .value_parser(["diagnostics", "security-read", "operations"])
+                .help("API scope to grant, defaults to read-only diagnostics"))
+            .arg(Arg::new("duration")
+                .long("duration")
+                .value_parser(clap::value_parser!(u64))
+                .help("Session length in minutes"))
+            .arg(Arg::new("reason")
+                .long("reason")
+                .required(true)
+                .help("Support ticket or justification recorded in the audit log")))
+        .subcommand(Command::new("status")
+            .about("List active remote assistance sessions"))
+        .subcommand(Command::new("revoke")
+            .about("End a remote assistance session immediately")
+            .arg(Arg::new("session")
+                .required(true)
+                .help("Session ID")))
+        .subcommand(Command::new("recording")
+            .about("Show the requests made during a session")
+            .arg(Arg::new("session")
+                .required(true)
+                .help("Session ID")))
+}
+
+/// CLI command managing remote assistance sessions
+#[derive(Debug)]
+pub struct RemoteAssistCommand {
+    manager: Arc<RemoteAssistanceManager>,
+}
+
+impl RemoteAssistCommand {
+    /// Creates a new RemoteAssistCommand using the given manager
+    pub fn new(manager: Arc<RemoteAssistanceManager>) -> Self {
+        Self { manager }
+    }
+
+    #[instrument(skip(self, matches))]
+    fn enable(&self, matches: &ArgMatches) -> Result<(), GuardianError> {
+        let vendor = required(matches, "vendor")?;
+        let reason = required(matches, "reason")?;
+        let scopes = matches
+            .get_many::<String>("scope")
+            .into_iter()
+            .flatten()
+            .map(|s| s.parse::<AssistanceScope>())
+            .collect::<Result<HashSet<_>, _>>()?;
+        let duration = matches.get_one::<u64>("duration").map(|m| Duration::from_secs(m * 60));
+
+        let session = self.manager.enable(&operator(), vendor, scopes, duration, reason)?;
+        println!("{}", session.banner());
+        print_sessions(&[session]);
+        counter!("guardian.cli.remote_assist.enable", 1);
+        Ok(())
+    }
+
+    fn status(&self) -> Result<(), GuardianError> {
+        let sessions = self.manager.active();
+        if sessions.is_empty() {
+            println!("No active remote assistance sessions");
+            return Ok(());
+        }
+        for banner in self.manager.banners() {
+            println!("{}", banner);
+        }
+        print_sessions(&sessions);
+        Ok(())
+    }
+
+    fn revoke(&self, session_id: &str) -> Result<(), GuardianError> {
+        let session = self.manager.revoke(session_id, &operator())?;
+        println!("Remote assistance session {} revoked", session.id);
+        counter!("guardian.cli.remote_assist.revoke", 1);
+        Ok(())
+    }
+
+    fn recording(&self, session_id: &str) -> Result<(), GuardianError> {
+        let rows: Vec<Vec<String>> = self
+            .manager
+            .recording(session_id)
+            .into_iter()
+            .map(|r| vec![
+                r.at.to_string(),
+                r.path,
+                if r.allowed { "allowed" } else { "denied" }.to_string(),
+                r.status.map(|s| s.to_string()).unwrap_or_else(|| "-".into()),
+                r.duration_ms.to_string(),
+                r.correlation_id,
+            ])
+            .collect();
+        print!("{}", output::render_table(
+            &["AT", "PATH", "DECISION", "STATUS", "MS", "CORRELATION ID"],
+            &rows,
+        ));
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl CliCommand for RemoteAssistCommand {
+    fn name(&self) -> &'static str {
+        COMMAND_NAME
+    }
+
+    fn configure(&self) -> Command {
+        build_remote_assist_subcommand()
+    }
+
+    async fn execute(&self, args: &ArgMatches) -> Result<(), GuardianError> {
+        match args.subcommand() {
+            Some(("enable", sub_matches)) => self.enable(sub_matches),
+            Some(("status", _)) => self.status(),
+            Some(("revoke", sub_matches)) => self.revoke(required(sub_matches, "session")?),
+            Some(("recording", sub_matches)) => self.recording(required(sub_matches, "session")?),
+            _ => Err(invalid("Invalid subcommand".into())),
+        }
+    }
+
+    fn required_access(&self) -> AccessLevel {
+        AccessLevel::Admin
+    }
+
+    fn help(&self) -> &'static str {
+        HELP_TEXT
+    }
+}
+
+fn print_sessions(sessions: &[AssistanceSession]) {
+    let rows: Vec<Vec<String>> = sessions
+        .iter()
+        .map(|s| {
+            let mut scopes: Vec<String> = s.scopes.iter().map(|scope| format!("{:?}", scope)).collect();
+            scopes.sort();
+            vec![
+                s.id.clone(),
+                s.vendor_fingerprint.clone(),
+                scopes.join(","),
+                s.enabled_by.clone(),
+                s.expires_at.to_string(),
+            ]
+        })
+        .collect();
+    print!("{}", output::render_table(&["SESSION", "VENDOR", "SCOPES", "ENABLED BY", "EXPIRES"], &rows));
+}
+
+/// Identity of the administrator running the command, for the audit trail
+fn operator() -> String {
+    std::env::var("SUDO_USER")
+        .or_else(|_| std::env::var("USER"))
+        .unwrap_or_else(|_| "unknown".into())
+}
+
+fn required<'a>(matches: &'a ArgMatches, name: &str) -> Result<&'a str, GuardianError> {
+    matches
+        .get_one::<String>(name)
+        .map(String::as_str)
+        .ok_or_else(|| invalid(format!("Argument {} required", name)))
+}
+
+fn invalid(context: String) -> GuardianError {
+    GuardianError::ValidationError {
+        context,
+        source: None,
+        severity: crate::utils::error::ErrorSeverity::Low,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: crate::utils::error::ErrorCategory::Validation,
+        retry_count: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_enable_requires_vendor_and_reason() {
+        assert!(build_remote_assist_subcommand()
+            .try_get_matches_from(vec![COMMAND_NAME, "enable", "--vendor", "abcd"])
+            .is_err());
+        assert!(build_remote_assist_subcommand()
+            .try_get_matches_from(vec![COMMAND_NAME, "enable", "--vendor", "abcd", "--reason", "t", "--scope", "root"])
+            .is_err());
+
+        let matches = build_remote_assist_subcommand().get_matches_from(vec![
+            COMMAND_NAME, "enable", "--vendor", "abcd", "--reason", "ticket 4711",
+            "--scope", "diagnostics", "--scope", "security-read",
+        ]);
+        let (_, enable) = matches.subcommand().unwrap();
+        assert_eq!(enable.get_many::<String>("scope").unwrap().count(), 2);
+    }
+}
@@ -0,0 +1,111 @@
+use clap::{Arg, ArgMatches, Command};
+use std::time::Duration;
+use tracing::{info, instrument};
+use metrics::counter;
+
+use crate::api::grpc::security_service::{security_service_client::SecurityServiceClient, ApproveResponseRequest};
+use crate::cli::commands::{AccessLevel, Command as CliCommand};
+use crate::utils::error::{ErrorCategory, ErrorSeverity, GuardianError};
+
+// Constants for response commands
+const COMMAND_NAME: &str = "response";
+const HELP_TEXT: &str = "Approve automatic responses held by the blast radius guardrails";
+const DEFAULT_ENDPOINT: &str = "http://127.0.0.1:50051";
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Builds the `response` subcommand definition
+pub fn build_response_subcommand() -> Command {
+    Command::new(COMMAND_NAME)
+        .about(HELP_TEXT)
+        .subcommand(Command::new("approve")
+            .about("Execute a held response, recording you as its approver")
+            .arg(Arg::new("pending-id")
+                .required(true)
+                .help("Pending response ID from the guardrail alert"))
+            .arg(Arg::new("endpoint")
+                .long("endpoint")
+                .env("GUARDIAN_ENDPOINT")
+                .default_value(DEFAULT_ENDPOINT)
+                .help("gRPC endpoint of the Guardian daemon")))
+}
+
+/// CLI command approving held responses on the daemon
+#[derive(Debug, Default)]
+pub struct ResponseCommand;
+
+impl ResponseCommand {
+    /// Creates a new ResponseCommand
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Has the daemon execute a held response
+    #[instrument(skip(self))]
+    async fn approve(&self, endpoint: &str, pending_id: &str) -> Result<(), GuardianError> {
+        let channel = tonic::transport::Endpoint::from_shared(endpoint.to_string())
+            .map_err(|e| response_error(format!("Invalid endpoint {}", endpoint), Some(Box::new(e))))?
+            .connect_timeout(CONNECT_TIMEOUT)
+            .connect()
+            .await
+            .map_err(|e| response_error(format!("Failed to connect to {}", endpoint), Some(Box::new(e))))?;
+        let result = SecurityServiceClient::new(channel)
+            .approve_response(ApproveResponseRequest { pending_id: pending_id.to_string() })
+            .await
+            .map_err(|e| response_error(format!("Approval refused: {}", e.message()), None))?
+            .into_inner();
+
+        let action = result.result_data.get("action").map(String::as_str).unwrap_or("response");
+        info!(pending_id, action, success = result.success, "Held response approved");
+        if result.success {
+            println!("Approved {} ({})", pending_id, action);
+        } else {
+            println!("Approved {} ({}), but it failed: {}", pending_id, action, result.error_message);
+        }
+        counter!("guardian.cli.response.approve", 1, "success" => result.success.to_string());
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl CliCommand for ResponseCommand {
+    fn name(&self) -> &'static str {
+        COMMAND_NAME
+    }
+
+    fn configure(&self) -> Command {
+        build_response_subcommand()
+    }
+
+    async fn execute(&self, args: &ArgMatches) -> Result<(), GuardianError> {
+        match args.subcommand() {
+            Some(("approve", sub_matches)) => {
+                let pending_id = sub_matches
+                    .get_one::<String>("pending-id")
+                    .ok_or_else(|| response_error("Pending response ID required".into(), None))?;
+                let endpoint = sub_matches.get_one::<String>("endpoint").map(String::as_str).unwrap_or(DEFAULT_ENDPOINT);
+                self.approve(endpoint, pending_id).await
+            }
+            _ => Err(response_error("Invalid subcommand".into(), None)),
+        }
+    }
+
+    fn required_access(&self) -> AccessLevel {
+        AccessLevel::Security
+    }
+
+    fn help(&self) -> &'static str {
+        HELP_TEXT
+    }
+}
+
+fn response_error(context: String, source: Option<Box<dyn std::error::Error + Send + Sync>>) -> GuardianError {
+    GuardianError::SystemError {
+        context,
+        source,
+        severity: ErrorSeverity::Medium,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: ErrorCategory::System,
+        retry_count: 0,
+    }
+}
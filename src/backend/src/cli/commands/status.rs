@@ -6,9 +6,14 @@ use tracing::{debug, error, info, instrument, warn}; // v0.1
 use tokio::sync::{Mutex, RwLock};
 
 use crate::cli::commands::{Command, AccessLevel};
+use crate::cli::output;
 use crate::utils::error::GuardianError;
 use crate::core::system_state::{SystemState, SystemHealth};
 use crate::core::metrics::{SystemMetrics, PerformanceMetrics};
+use crate::core::state_journal::{state_journal, StateJournal, StateTransition, DEFAULT_TRANSITION_WINDOW};
+use crate::core::status::{status_service, StatusReport};
+use crate::utils::affinity::thread_placements;
+use crate::security::pipeline_latency::{pipeline_latency, LatencySnapshot, StageLatency};
 
 // Constants for status command configuration
 const COMMAND_NAME: &str = "status";
@@ -75,33 +80,31 @@ impl StatusCommand {
     }
 
     /// Formats system status with enhanced security validation
+    ///
+    /// JSON output is the versioned status report served over gRPC and the health endpoint.
     #[instrument(skip(self))]
     async fn format_output(&self, format: OutputFormat) -> Result<String, GuardianError> {
+        let report = status_service().report().await;
         let health = self.system_state.health_status.read().await;
         let metrics = self.system_state.resource_metrics.read().await;
         let security = self.system_state.security_status.read().await;
+        let placements = thread_placements();
 
         match format {
-            OutputFormat::Json => {
-                Ok(json!({
-                    "health": {
-                        "status": format!("{:?}", *health),
-                        "last_update": chrono::Utc::now().timestamp()
-                    },
-                    "resources": {
-                        "cpu_usage": metrics.cpu_usage,
-                        "memory_usage": metrics.memory_usage,
-                        "system_load": metrics.system_load,
-                        "uptime_seconds": metrics.uptime_seconds
-                    },
-                    "security": {
-                        "active_threats": security.active_threats,
-                        "security_level": security.security_level,
-                        "is_lockdown": security.is_lockdown
-                    }
-                }).to_string())
-            },
+            OutputFormat::Json => serde_json::to_string_pretty(&report).map_err(|e| GuardianError::SystemError(e.to_string())),
             OutputFormat::Text => {
+                let placement: String = placements
+                    .iter()
+                    .map(|p| format!(
+                        "\n  {} ({:?}): cpus {} (requested {}){}",
+                        p.thread_name,
+                        p.class,
+                        p.actual_cpus,
+                        if p.requested_cpus.is_empty() { "any" } else { &p.requested_cpus },
+                        if p.pinned { "" } else { " unpinned" }
+                    ))
+                    .collect();
+
                 Ok(format!(
                     "System Status:\n\
                      Health: {:?}\n\
@@ -110,24 +113,30 @@ impl StatusCommand {
                      System Load: {:.2}\n\
                      Active Threats: {}\n\
                      Security Level: {}\n\
-                     Lockdown Status: {}",
+                     Lockdown Status: {}\n\
+                     Thread Placement:{}\n\
+                     Components ({:?}):\n{}",
                     *health,
                     metrics.cpu_usage,
                     metrics.memory_usage,
                     metrics.system_load,
                     security.active_threats,
                     security.security_level,
-                    if security.is_lockdown { "ACTIVE" } else { "Inactive" }
+                    if security.is_lockdown { "ACTIVE" } else { "Inactive" },
+                    if placement.is_empty() { " none".to_string() } else { placement },
+                    report.overall,
+                    format_components(&report)
                 ))
             },
             OutputFormat::Compact => {
                 Ok(format!(
-                    "Health:{:?} CPU:{:.1}% Mem:{:.1}% Load:{:.2} Threats:{}",
+                    "Health:{:?} CPU:{:.1}% Mem:{:.1}% Load:{:.2} Threats:{} Overall:{:?}",
                     *health,
                     metrics.cpu_usage,
                     metrics.memory_usage,
                     metrics.system_load,
-                    security.active_threats
+                    security.active_threats,
+                    report.overall
                 ))
             }
         }
@@ -186,8 +195,21 @@ impl Command for StatusCommand {
                     .long("format")
                     .value_parser(["text", "json", "compact"])
                     .default_value("text")
+                    .global(true)
                     .help("Output format")
             )
+            .arg(
+                Arg::new("json")
+                    .long("json")
+                    .action(clap::ArgAction::SetTrue)
+                    .global(true)
+                    .help("Shorthand for --format json; the status report keeps its schema across releases")
+            )
+            .subcommand(
+                ClapCommand::new("latency")
+                    .about("Show the detection pipeline latency breakdown by stage")
+            )
+            .subcommand(transitions_command())
     }
 
     /// Executes the status command with enhanced security and performance
@@ -204,11 +226,22 @@ impl Command for StatusCommand {
 
         // Parse output format
         let format = match args.get_one::<String>("format").map(|s| s.as_str()) {
+            _ if args.get_flag("json") => OutputFormat::Json,
             Some("json") => OutputFormat::Json,
             Some("compact") => OutputFormat::Compact,
             _ => OutputFormat::Text,
         };
 
+        if let Some(("latency", _)) = args.subcommand() {
+            println!("{}", format_latency(&pipeline_latency().snapshot(), &format)?);
+            return Ok(());
+        }
+
+        if let Some(("transitions", sub_matches)) = args.subcommand() {
+            println!("{}", format_transitions(&select_transitions(&state_journal(), sub_matches)?, &format)?);
+            return Ok(());
+        }
+
         // Collect and validate metrics
         let metrics = self.collect_metrics().await?;
         if metrics.cpu_usage > 95.0 || metrics.memory_usage > 95.0 {
@@ -236,6 +269,132 @@ impl Command for StatusCommand {
     }
 }
 
+/// Formats the per-stage detection latency breakdown
+fn format_latency(snapshot: &LatencySnapshot, format: &OutputFormat) -> Result<String, GuardianError> {
+    let row = |latency: &StageLatency| vec![
+        latency.stage.clone(),
+        latency.count.to_string(),
+        format!("{:.2}", latency.p50_ms),
+        format!("{:.2}", latency.p95_ms),
+        format!("{:.2}", latency.p99_ms),
+        format!("{:.2}", latency.max_ms),
+    ];
+
+    match format {
+        OutputFormat::Json => serde_json::to_string(snapshot).map_err(|e| GuardianError::SystemError(e.to_string())),
+        OutputFormat::Compact => Ok(snapshot
+            .stages
+            .iter()
+            .chain(std::iter::once(&snapshot.cycle))
+            .map(|l| format!("{}:{:.1}ms", l.stage, l.p95_ms))
+            .collect::<Vec<_>>()
+            .join(" ")),
+        OutputFormat::Text => {
+            let mut rows: Vec<Vec<String>> = snapshot.stages.iter().map(row).collect();
+            rows.push(row(&snapshot.cycle));
+            Ok(output::render_table(&["STAGE", "COUNT", "P50 MS", "P95 MS", "P99 MS", "MAX MS"], &rows))
+        }
+    }
+}
+
+/// One row per component of the status report
+fn format_components(report: &StatusReport) -> String {
+    let rows: Vec<Vec<String>> = report
+        .components
+        .iter()
+        .map(|c| vec![
+            c.name.clone(),
+            format!("{:?}", c.state).to_lowercase(),
+            c.detail.clone().unwrap_or_default(),
+        ])
+        .collect();
+    output::render_table(&["COMPONENT", "STATE", "DETAIL"], &rows)
+}
+
+/// Arguments of the `transitions` subcommand
+fn transitions_command() -> ClapCommand {
+    ClapCommand::new("transitions")
+        .about("Step through journaled health transitions and the inputs behind them")
+        .arg(Arg::new("around")
+            .long("around")
+            .value_name("RFC3339")
+            .conflicts_with("from")
+            .help("Show transitions around this time, such as an incident's opening (default: now)"))
+        .arg(Arg::new("window")
+            .long("window")
+            .value_name("SECONDS")
+            .value_parser(clap::value_parser!(u64))
+            .help("Seconds either side of --around (default: 900)"))
+        .arg(Arg::new("from")
+            .long("from")
+            .value_name("SEQUENCE")
+            .value_parser(clap::value_parser!(u64))
+            .help("Start stepping from this transition"))
+        .arg(Arg::new("steps")
+            .long("steps")
+            .value_name("N")
+            .value_parser(clap::value_parser!(i64))
+            .allow_negative_numbers(true)
+            .default_value("0")
+            .requires("from")
+            .help("Transitions to step forward from --from, or back when negative"))
+}
+
+/// Picks the transitions to show: a step from a known transition, or a window around a time
+fn select_transitions(journal: &StateJournal, args: &clap::ArgMatches) -> Result<Vec<StateTransition>, GuardianError> {
+    if let Some(from) = args.get_one::<u64>("from") {
+        let steps = args.get_one::<i64>("steps").copied().unwrap_or_default();
+        let range = if steps < 0 { steps..=0 } else { 0..=steps };
+        return Ok(range.filter_map(|step| journal.step(*from, step)).collect());
+    }
+
+    let around = match args.get_one::<String>("around") {
+        Some(raw) => chrono::DateTime::parse_from_rfc3339(raw)
+            .map_err(|_| GuardianError::SystemError(format!("Invalid --around timestamp: {}", raw)))?
+            .with_timezone(&chrono::Utc),
+        None => chrono::Utc::now(),
+    };
+    let window = args
+        .get_one::<u64>("window")
+        .map(|secs| Duration::from_secs(*secs))
+        .unwrap_or(DEFAULT_TRANSITION_WINDOW);
+    Ok(journal.around(around, window))
+}
+
+/// Formats transitions with the cause and inputs behind each health change
+fn format_transitions(transitions: &[StateTransition], format: &OutputFormat) -> Result<String, GuardianError> {
+    let inputs = |t: &StateTransition| {
+        t.inputs
+            .iter()
+            .map(|(name, value)| format!("{}={:.1}", name, value))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+
+    match format {
+        OutputFormat::Json => serde_json::to_string(transitions).map_err(|e| GuardianError::SystemError(e.to_string())),
+        OutputFormat::Compact => Ok(transitions
+            .iter()
+            .map(|t| format!("#{} {:?}->{:?} {}", t.sequence, t.from, t.to, t.cause.label()))
+            .collect::<Vec<_>>()
+            .join("\n")),
+        OutputFormat::Text => {
+            let rows: Vec<Vec<String>> = transitions
+                .iter()
+                .map(|t| vec![
+                    t.sequence.to_string(),
+                    t.at.format("%Y-%m-%d %H:%M:%S").to_string(),
+                    format!("{:?} -> {:?}", t.from, t.to),
+                    t.cause.label().to_string(),
+                    t.cause.detail(),
+                    inputs(t),
+                ])
+                .collect();
+            Ok(output::render_table(&["SEQ", "AT", "HEALTH", "CAUSE", "DETAIL", "INPUTS"], &rows))
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -264,7 +423,8 @@ mod tests {
 
         // Test JSON format
         let json_output = command.format_output(OutputFormat::Json).await.unwrap();
-        assert!(serde_json::from_str::<serde_json::Value>(&json_output).is_ok());
+        let report: StatusReport = serde_json::from_str(&json_output).unwrap();
+        assert_eq!(report.schema_version, crate::core::status::STATUS_SCHEMA_VERSION);
 
         // Test text format
         let text_output = command.format_output(OutputFormat::Text).await.unwrap();
@@ -274,4 +434,34 @@ mod tests {
         let compact_output = command.format_output(OutputFormat::Compact).await.unwrap();
         assert!(compact_output.contains("CPU:"));
     }
+
+    #[test]
+    fn test_transitions_step_from_sequence() {
+        use crate::core::state_journal::TransitionCause;
+        use std::collections::BTreeMap;
+
+        let journal = StateJournal::new(16);
+        let inputs = BTreeMap::from([("cpu_usage".to_string(), 92.0)]);
+        let check = TransitionCause::HealthCheck { source: "system_state".into() };
+        let first = journal.record(SystemHealth::Healthy, SystemHealth::Critical, check, inputs).unwrap();
+        journal.record_config_change("thresholds reloaded");
+
+        let args = transitions_command()
+            .get_matches_from(vec!["transitions", "--from", &first.to_string(), "--steps", "1"]);
+        let transitions = select_transitions(&journal, &args).unwrap();
+        assert_eq!(transitions.len(), 2);
+
+        let text = format_transitions(&transitions, &OutputFormat::Text).unwrap();
+        assert!(text.contains("health_check") && text.contains("cpu_usage=92.0") && text.contains("config_change"));
+    }
+
+    #[test]
+    fn test_latency_breakdown() {
+        let snapshot = crate::security::pipeline_latency::PipelineLatency::default().snapshot();
+        let text = format_latency(&snapshot, &OutputFormat::Text).unwrap();
+        assert!(text.contains("infer") && text.contains("cycle"));
+
+        let compact = format_latency(&snapshot, &OutputFormat::Compact).unwrap();
+        assert!(compact.starts_with("collect:0.0ms"));
+    }
 }
\ No newline at end of file
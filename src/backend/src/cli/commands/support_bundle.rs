@@ -0,0 +1,189 @@
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use clap::{Arg, ArgMatches, Command};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::{instrument, warn};
+
+use crate::cli::commands::{AccessLevel, Command as CliCommand};
+use crate::cli::output::ProgressReporter;
+use crate::core::support_bundle::{SupportBundle, SupportBundleConfig};
+use crate::core::system_state::SystemState;
+use crate::utils::error::GuardianError;
+
+// Constants for support bundle generation
+const COMMAND_NAME: &str = "support-bundle";
+const HELP_TEXT: &str = "Collect redacted diagnostics into an encrypted archive for vendor support";
+const DEFAULT_RECIPIENT_KEY_PATH: &str = "/etc/guardian/support/vendor.pub";
+const X25519_KEY_LEN: usize = 32;
+const BYTES_PER_MIB: u64 = 1024 * 1024;
+
+/// Builds the `support-bundle` subcommand definition
+pub fn build_support_bundle_subcommand() -> Command {
+    Command::new(COMMAND_NAME)
+        .about(HELP_TEXT)
+        .arg(Arg::new("output")
+            .short('o')
+            .long("output")
+            .help("Path of the encrypted bundle, defaults to guardian-support-<timestamp>.gsb"))
+        .arg(Arg::new("recipient-key")
+            .long("recipient-key")
+            .default_value(DEFAULT_RECIPIENT_KEY_PATH)
+            .help("File holding the vendor X25519 public key, raw or base64"))
+        .arg(Arg::new("log-dir")
+            .long("log-dir")
+            .help("Directory to collect recent logs from"))
+        .arg(Arg::new("max-log-mb")
+            .long("max-log-mb")
+            .value_parser(clap::value_parser!(u64))
+            .help("Maximum MiB taken from the end of each log file"))
+}
+
+/// CLI command generating support bundles
+#[derive(Debug)]
+pub struct SupportBundleCommand {
+    system_state: Arc<SystemState>,
+    config: SupportBundleConfig,
+}
+
+impl SupportBundleCommand {
+    /// Creates a new SupportBundleCommand reporting health from the given state
+    pub fn new(system_state: Arc<SystemState>) -> Self {
+        Self {
+            system_state,
+            config: SupportBundleConfig::default(),
+        }
+    }
+
+    /// Generates and writes an encrypted bundle
+    #[instrument(skip(self, config, recipient_key))]
+    fn generate(&self, config: &SupportBundleConfig, recipient_key: &[u8], output: &Path) -> Result<(), GuardianError> {
+        let mut progress = ProgressReporter::start("support_bundle.generate");
+
+        // A failing health snapshot is itself useful to support, so it never aborts the bundle
+        let health = match self.system_state.get_current_state() {
+            Ok(state) => serde_json::to_value(state).ok(),
+            Err(e) => {
+                warn!(error = %e, "Health snapshot unavailable for support bundle");
+                Some(serde_json::json!({ "error": e.to_string() }))
+            }
+        };
+
+        progress.update(20.0, "collecting diagnostics");
+        let bundle = match SupportBundle::collect(config, health) {
+            Ok(bundle) => bundle,
+            Err(e) => {
+                progress.fail("collection failed");
+                return Err(e);
+            }
+        };
+
+        progress.update(70.0, "encrypting archive");
+        match bundle.write_sealed(output, recipient_key) {
+            Ok(bytes) => {
+                progress.finish("support bundle written");
+                println!("Support bundle written to {} ({} bytes)", output.display(), bytes);
+                Ok(())
+            }
+            Err(e) => {
+                progress.fail("encryption failed");
+                Err(e)
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl CliCommand for SupportBundleCommand {
+    fn name(&self) -> &'static str {
+        COMMAND_NAME
+    }
+
+    fn configure(&self) -> Command {
+        build_support_bundle_subcommand()
+    }
+
+    async fn execute(&self, args: &ArgMatches) -> Result<(), GuardianError> {
+        let mut config = self.config.clone();
+        if let Some(dir) = args.get_one::<String>("log-dir") {
+            config.log_dir = PathBuf::from(dir);
+        }
+        if let Some(mib) = args.get_one::<u64>("max-log-mb") {
+            config.max_log_bytes = mib.saturating_mul(BYTES_PER_MIB);
+        }
+
+        let key_path = args
+            .get_one::<String>("recipient-key")
+            .map(String::as_str)
+            .unwrap_or(DEFAULT_RECIPIENT_KEY_PATH);
+        let recipient_key = read_recipient_key(Path::new(key_path))?;
+
+        let output = args
+            .get_one::<String>("output")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| {
+                PathBuf::from(format!(
+                    "guardian-support-{}.gsb",
+                    time::OffsetDateTime::now_utc().unix_timestamp()
+                ))
+            });
+
+        self.generate(&config, &recipient_key, &output)
+    }
+
+    fn required_access(&self) -> AccessLevel {
+        AccessLevel::Admin
+    }
+
+    fn help(&self) -> &'static str {
+        HELP_TEXT
+    }
+}
+
+/// Reads a vendor public key stored either as raw bytes or base64 text
+fn read_recipient_key(path: &Path) -> Result<Vec<u8>, GuardianError> {
+    let raw = std::fs::read(path).map_err(|e| GuardianError::ValidationError {
+        context: format!("Failed to read recipient key {}", path.display()),
+        source: Some(Box::new(e)),
+        severity: crate::utils::error::ErrorSeverity::Medium,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: crate::utils::error::ErrorCategory::Validation,
+        retry_count: 0,
+    })?;
+    parse_recipient_key(&raw)
+}
+
+fn parse_recipient_key(raw: &[u8]) -> Result<Vec<u8>, GuardianError> {
+    if raw.len() == X25519_KEY_LEN {
+        return Ok(raw.to_vec());
+    }
+
+    match BASE64.decode(String::from_utf8_lossy(raw).trim()) {
+        Ok(key) if key.len() == X25519_KEY_LEN => Ok(key),
+        _ => Err(GuardianError::ValidationError {
+            context: format!("Recipient key must be {} raw or base64 encoded bytes", X25519_KEY_LEN),
+            source: None,
+            severity: crate::utils::error::ErrorSeverity::Medium,
+            timestamp: time::OffsetDateTime::now_utc(),
+            correlation_id: crate::utils::correlation::current_or_new(),
+            category: crate::utils::error::ErrorCategory::Validation,
+            retry_count: 0,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_recipient_key() {
+        let key = [7u8; X25519_KEY_LEN];
+        assert_eq!(parse_recipient_key(&key).unwrap(), key.to_vec());
+
+        let encoded = format!("{}\n", BASE64.encode(key));
+        assert_eq!(parse_recipient_key(encoded.as_bytes()).unwrap(), key.to_vec());
+
+        assert!(parse_recipient_key(b"not a key").is_err());
+    }
+}
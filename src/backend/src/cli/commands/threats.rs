@@ -1,13 +1,18 @@
 use std::sync::Arc;
-use std::time::Duration;
-use clap::{Parser, Subcommand};
+use std::time::{Duration, SystemTime};
+use clap::{CommandFactory, Parser, Subcommand};
 use tracing::{debug, error, info, instrument, warn};
 use serde_json::json;
 use tokio::time::timeout;
 
 use super::Command;
+use crate::api::grpc::security_service::{
+    security_service_client::SecurityServiceClient, DetectionFeedback, ExportFeedbackRequest, FeedbackLabel,
+    MonitorThreatsRequest, ThreatAlert, ThreatSeverity, ThreatStatsRequest,
+};
+use crate::cli::output;
 use crate::security::threat_detection::ThreatDetector;
-use crate::utils::error::GuardianError;
+use crate::utils::error::{ErrorCategory, ErrorSeverity, GuardianError};
 
 // Constants for threat command configuration
 const COMMAND_NAME: &str = "threats";
@@ -15,6 +20,13 @@ const COMMAND_ABOUT: &str = "Manage and analyze security threats";
 const DEFAULT_ANALYSIS_TIMEOUT: Duration = Duration::from_secs(5);
 const DEFAULT_BATCH_SIZE: usize = 100;
 const MAX_CONCURRENT_ANALYSES: usize = 10;
+const DEFAULT_ENDPOINT: &str = "http://127.0.0.1:50051";
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Builds the `threats` subcommand definition
+pub fn build_threats_subcommand() -> clap::Command {
+    ThreatsCommand::command()
+}
 
 /// CLI command for managing and analyzing security threats
 #[derive(Debug, Parser)]
@@ -74,6 +86,93 @@ enum ThreatsSubcommand {
         #[clap(required = true)]
         threat_id: String,
     },
+
+    /// Tail threat detections from the running daemon until interrupted
+    #[clap(name = "watch")]
+    Watch {
+        /// Minimum severity to show (critical|high|medium|low)
+        #[clap(short, long)]
+        severity: Option<String>,
+
+        /// Replay retained detections from this far back first, e.g. 30s, 15m, 2h
+        #[clap(long)]
+        since: Option<String>,
+
+        /// Print one JSON object per detection
+        #[clap(long)]
+        json: bool,
+
+        /// gRPC endpoint of the Guardian daemon
+        #[clap(long, env = "GUARDIAN_ENDPOINT", default_value = DEFAULT_ENDPOINT)]
+        endpoint: String,
+    },
+
+    /// Mark an anomaly detection as a true or false positive, or report a missed one
+    #[clap(name = "feedback")]
+    Feedback {
+        /// Detection ID; omit when reporting a false negative
+        detection_id: Option<String>,
+
+        /// Verdict (true-positive|false-positive|false-negative)
+        #[clap(short, long)]
+        label: String,
+
+        /// Free-text reasoning kept with the verdict
+        #[clap(short, long)]
+        comment: Option<String>,
+
+        /// JSON file with the system data a false negative missed
+        #[clap(long)]
+        sample: Option<std::path::PathBuf>,
+
+        /// gRPC endpoint of the Guardian daemon
+        #[clap(long, env = "GUARDIAN_ENDPOINT", default_value = DEFAULT_ENDPOINT)]
+        endpoint: String,
+    },
+
+    /// Write analyst feedback as a labeled training dataset, one JSON sample per line
+    #[clap(name = "export-feedback")]
+    ExportFeedback {
+        /// Destination file
+        #[clap(short, long)]
+        output: std::path::PathBuf,
+
+        /// Only feedback from this far back, e.g. 7d
+        #[clap(long)]
+        since: Option<String>,
+
+        /// gRPC endpoint of the Guardian daemon
+        #[clap(long, env = "GUARDIAN_ENDPOINT", default_value = DEFAULT_ENDPOINT)]
+        endpoint: String,
+    },
+
+    /// List addresses currently blocked in the host firewall
+    #[clap(name = "blocks")]
+    Blocks {
+        /// Output format (json|table)
+        #[clap(short, long, default_value = "table")]
+        format: String,
+
+        /// gRPC endpoint of the Guardian daemon
+        #[clap(long, env = "GUARDIAN_ENDPOINT", default_value = DEFAULT_ENDPOINT)]
+        endpoint: String,
+    },
+
+    /// Aggregate historical detections, e.g. `count by severity since 7d` or `mttr by day since 30d`
+    #[clap(name = "stats")]
+    Stats {
+        /// Query: <count|mttr> [by <dimension>] [where <dimension>=<value>] [since <age>] [until <age>] [top <n>]
+        #[clap(required = true, num_args = 1..)]
+        query: Vec<String>,
+
+        /// Output format (json|table)
+        #[clap(short, long, default_value = "table")]
+        format: String,
+
+        /// gRPC endpoint of the Guardian daemon
+        #[clap(long, env = "GUARDIAN_ENDPOINT", default_value = DEFAULT_ENDPOINT)]
+        endpoint: String,
+    },
 }
 
 impl ThreatsCommand {
@@ -153,6 +252,214 @@ impl ThreatsCommand {
         Ok(())
     }
 
+    /// Streams detections from the daemon and prints each as it arrives
+    #[instrument(skip(self))]
+    async fn watch_threats(
+        &self,
+        endpoint: &str,
+        severity: Option<&str>,
+        since: Option<&str>,
+        json: bool,
+    ) -> Result<(), GuardianError> {
+        let request = MonitorThreatsRequest {
+            severity_filters: severities_at_least(severity)?,
+            since: since
+                .map(|s| parse_age(s).map(|age| prost_types::Timestamp::from(SystemTime::now() - age)))
+                .transpose()?,
+            ..Default::default()
+        };
+
+        let mut stream = connect(endpoint)
+            .await?
+            .monitor_threats(request)
+            .await
+            .map_err(|e| watch_error(format!("Threat stream refused: {}", e.message()), None))?
+            .into_inner();
+
+        if !json {
+            println!("DETECTED\tSEVERITY\tTHREAT ID\tDESCRIPTION");
+        }
+        loop {
+            let alert = tokio::select! {
+                _ = tokio::signal::ctrl_c() => return Ok(()),
+                message = stream.message() => message
+                    .map_err(|e| watch_error(format!("Threat stream failed: {}", e.message()), None))?,
+            };
+            match alert {
+                Some(alert) => print_alert(&alert, json)?,
+                None => return Err(watch_error("Daemon closed the threat stream".into(), None)),
+            }
+        }
+    }
+
+    /// Submits an analyst verdict on a detection
+    #[instrument(skip(self, comment))]
+    async fn submit_feedback(
+        &self,
+        endpoint: &str,
+        detection_id: Option<&str>,
+        label: &str,
+        comment: Option<&str>,
+        sample: Option<&std::path::Path>,
+    ) -> Result<(), GuardianError> {
+        let label = parse_label(label)?;
+        let sample_json = match sample {
+            Some(path) => tokio::fs::read_to_string(path)
+                .await
+                .map_err(|e| watch_error(format!("Failed to read sample {}", path.display()), Some(Box::new(e))))?,
+            None => String::new(),
+        };
+        if label == FeedbackLabel::FalseNegative && sample_json.is_empty() {
+            return Err(watch_error("False negatives need --sample with the missed system data".into(), None));
+        }
+        if label != FeedbackLabel::FalseNegative && detection_id.is_none() {
+            return Err(watch_error("Detection ID required".into(), None));
+        }
+
+        let receipt = connect(endpoint)
+            .await?
+            .submit_detection_feedback(DetectionFeedback {
+                detection_id: detection_id.unwrap_or_default().to_string(),
+                label: label as i32,
+                comment: comment.unwrap_or_default().to_string(),
+                sample_json,
+            })
+            .await
+            .map_err(|e| watch_error(format!("Feedback refused: {}", e.message()), None))?
+            .into_inner();
+
+        println!("Recorded feedback {} against model {}", receipt.feedback_id,
+            if receipt.model_version.is_empty() { "-" } else { &receipt.model_version });
+        Ok(())
+    }
+
+    /// Streams the labeled dataset into a JSON lines file
+    #[instrument(skip(self))]
+    async fn export_feedback(&self, endpoint: &str, output: &std::path::Path, since: Option<&str>) -> Result<(), GuardianError> {
+        use tokio::io::AsyncWriteExt;
+
+        let request = ExportFeedbackRequest {
+            since: since
+                .map(|s| parse_age(s).map(|age| prost_types::Timestamp::from(SystemTime::now() - age)))
+                .transpose()?,
+        };
+        let mut stream = connect(endpoint)
+            .await?
+            .export_feedback_dataset(request)
+            .await
+            .map_err(|e| watch_error(format!("Export refused: {}", e.message()), None))?
+            .into_inner();
+
+        let write_error = |e: std::io::Error| watch_error(format!("Failed to write {}", output.display()), Some(Box::new(e)));
+        let mut file = tokio::fs::File::create(output).await.map_err(write_error)?;
+        let mut written = 0usize;
+        while let Some(sample) = stream
+            .message()
+            .await
+            .map_err(|e| watch_error(format!("Export failed: {}", e.message()), None))?
+        {
+            let features: serde_json::Value = serde_json::from_str(&sample.features_json).unwrap_or_default();
+            let line = serde_json::to_string(&json!({
+                "features": features,
+                "anomalous": sample.anomalous,
+                "anomaly_type": sample.anomaly_type,
+                "detection_id": sample.detection_id,
+                "model_version": sample.model_version,
+                "labeled_by": sample.labeled_by,
+            }))?;
+            file.write_all(line.as_bytes()).await.map_err(write_error)?;
+            file.write_all(b"\n").await.map_err(write_error)?;
+            written += 1;
+        }
+        file.flush().await.map_err(write_error)?;
+
+        println!("Wrote {} labeled samples to {}", written, output.display());
+        Ok(())
+    }
+
+    /// Lists the daemon's firewall blocks with the time each has left
+    #[instrument(skip(self))]
+    async fn list_blocks(&self, endpoint: &str, format: &str) -> Result<(), GuardianError> {
+        let blocks = connect(endpoint)
+            .await?
+            .list_network_blocks(())
+            .await
+            .map_err(|e| watch_error(format!("Block listing refused: {}", e.message()), None))?
+            .into_inner()
+            .blocks;
+
+        let now = SystemTime::now();
+        let timestamp = |t: &Option<prost_types::Timestamp>| {
+            t.clone().and_then(|t| SystemTime::try_from(t).ok()).unwrap_or(now)
+        };
+        match format.to_lowercase().as_str() {
+            "json" => {
+                let blocks: Vec<_> = blocks
+                    .iter()
+                    .map(|block| json!({
+                        "address": block.address,
+                        "reason": block.reason,
+                        "blocked_at": time::OffsetDateTime::from(timestamp(&block.blocked_at)).to_string(),
+                        "expires_at": time::OffsetDateTime::from(timestamp(&block.expires_at)).to_string(),
+                    }))
+                    .collect();
+                println!("{}", serde_json::to_string_pretty(&json!({ "blocks": blocks, "total": blocks.len() }))?);
+            }
+            "table" => {
+                let rows: Vec<Vec<String>> = blocks
+                    .iter()
+                    .map(|block| vec![
+                        block.address.clone(),
+                        remaining(timestamp(&block.expires_at), now),
+                        block.reason.clone(),
+                    ])
+                    .collect();
+                print!("{}", output::render_table(&["ADDRESS", "EXPIRES IN", "REASON"], &rows));
+            }
+            other => return Err(watch_error(format!("Unknown output format {}", other), None)),
+        }
+        Ok(())
+    }
+
+    /// Runs an analytics query on the daemon and prints one row per group
+    async fn show_stats(&self, endpoint: &str, query: &[String], format: &str) -> Result<(), GuardianError> {
+        let stats = connect(endpoint)
+            .await?
+            .query_threat_stats(ThreatStatsRequest { query: query.join(" ") })
+            .await
+            .map_err(|e| watch_error(format!("Analytics query refused: {}", e.message()), None))?
+            .into_inner();
+
+        match format.to_lowercase().as_str() {
+            "json" => {
+                let rows: Vec<_> = stats
+                    .rows
+                    .iter()
+                    .map(|row| json!({ "key": row.key, "value": row.value, "samples": row.samples }))
+                    .collect();
+                println!("{}", serde_json::to_string_pretty(&json!({
+                    "measure": stats.measure,
+                    "group_by": stats.group_by,
+                    "rows": rows,
+                    "partitions_scanned": stats.partitions_scanned,
+                    "partitions_cached": stats.partitions_cached,
+                }))?);
+            }
+            "table" => {
+                let group = if stats.group_by.is_empty() { "GROUP".to_string() } else { stats.group_by.to_uppercase() };
+                let measure = stats.measure.to_uppercase();
+                let rows: Vec<Vec<String>> = stats
+                    .rows
+                    .iter()
+                    .map(|row| vec![row.key.clone(), stat_value(&stats.measure, row.value), row.samples.to_string()])
+                    .collect();
+                print!("{}", output::render_table(&[group.as_str(), measure.as_str(), "SAMPLES"], &rows));
+            }
+            other => return Err(watch_error(format!("Unknown output format {}", other), None)),
+        }
+        Ok(())
+    }
+
     /// Shows detailed information about a threat
     #[instrument(skip(self))]
     async fn show_threat_details(&self, threat_id: &str) -> Result<(), GuardianError> {
@@ -183,10 +490,149 @@ impl Command for ThreatsCommand {
                 info!(threat_id = %threat_id, "Showing threat details");
                 self.show_threat_details(threat_id).await
             }
+            ThreatsSubcommand::Watch { severity, since, json, endpoint } => {
+                info!(endpoint = %endpoint, "Watching threat detections");
+                self.watch_threats(endpoint, severity.as_deref(), since.as_deref(), *json).await
+            }
+            ThreatsSubcommand::Feedback { detection_id, label, comment, sample, endpoint } => {
+                info!(detection_id = ?detection_id, label = %label, "Submitting detection feedback");
+                self.submit_feedback(endpoint, detection_id.as_deref(), label, comment.as_deref(), sample.as_deref()).await
+            }
+            ThreatsSubcommand::ExportFeedback { output, since, endpoint } => {
+                info!(output = %output.display(), "Exporting feedback dataset");
+                self.export_feedback(endpoint, output, since.as_deref()).await
+            }
+            ThreatsSubcommand::Blocks { format, endpoint } => {
+                info!(endpoint = %endpoint, "Listing network blocks");
+                self.list_blocks(endpoint, format).await
+            }
+            ThreatsSubcommand::Stats { query, format, endpoint } => {
+                info!(endpoint = %endpoint, "Querying threat statistics");
+                self.show_stats(endpoint, query, format).await
+            }
         }
     }
 }
 
+async fn connect(endpoint: &str) -> Result<SecurityServiceClient<tonic::transport::Channel>, GuardianError> {
+    let channel = tonic::transport::Endpoint::from_shared(endpoint.to_string())
+        .map_err(|e| watch_error(format!("Invalid endpoint {}", endpoint), Some(Box::new(e))))?
+        .connect_timeout(CONNECT_TIMEOUT)
+        .connect()
+        .await
+        .map_err(|e| watch_error(format!("Failed to connect to {}", endpoint), Some(Box::new(e))))?;
+    Ok(SecurityServiceClient::new(channel))
+}
+
+fn parse_label(label: &str) -> Result<FeedbackLabel, GuardianError> {
+    match label.to_lowercase().replace('_', "-").as_str() {
+        "true-positive" | "tp" => Ok(FeedbackLabel::TruePositive),
+        "false-positive" | "fp" => Ok(FeedbackLabel::FalsePositive),
+        "false-negative" | "fn" => Ok(FeedbackLabel::FalseNegative),
+        other => Err(watch_error(format!("Unknown feedback label {}", other), None)),
+    }
+}
+
+/// Returns the protobuf severities at or above `minimum`, or none to receive every alert
+fn severities_at_least(minimum: Option<&str>) -> Result<Vec<i32>, GuardianError> {
+    const ORDER: [ThreatSeverity; 4] =
+        [ThreatSeverity::Low, ThreatSeverity::Medium, ThreatSeverity::High, ThreatSeverity::Critical];
+
+    let Some(minimum) = minimum else {
+        return Ok(Vec::new());
+    };
+    let start = match minimum.to_lowercase().as_str() {
+        "low" => 0,
+        "medium" => 1,
+        "high" => 2,
+        "critical" => 3,
+        other => return Err(watch_error(format!("Unknown severity {}", other), None)),
+    };
+    Ok(ORDER[start..].iter().map(|severity| *severity as i32).collect())
+}
+
+/// Parses an age such as `90s`, `15m`, `2h` or `1d`
+fn parse_age(value: &str) -> Result<Duration, GuardianError> {
+    let invalid = || watch_error(format!("Invalid --since value {}, expected e.g. 15m or 2h", value), None);
+    let split = value.find(|c: char| !c.is_ascii_digit()).ok_or_else(invalid)?;
+    let amount: u64 = value[..split].parse().map_err(|_| invalid())?;
+    let unit = match &value[split..] {
+        "s" => 1,
+        "m" => 60,
+        "h" => 3600,
+        "d" => 86400,
+        _ => return Err(invalid()),
+    };
+    Ok(Duration::from_secs(amount * unit))
+}
+
+/// Time left until `expires_at` in the largest whole unit, e.g. `14m`
+fn remaining(expires_at: SystemTime, now: SystemTime) -> String {
+    let secs = expires_at.duration_since(now).map(|d| d.as_secs()).unwrap_or(0);
+    match secs {
+        0 => "expired".to_string(),
+        s if s < 60 => format!("{}s", s),
+        s if s < 3600 => format!("{}m", s / 60),
+        s if s < 86400 => format!("{}h", s / 3600),
+        s => format!("{}d", s / 86400),
+    }
+}
+
+/// Counts print as integers, mean times to respond in the largest whole unit
+fn stat_value(measure: &str, value: f64) -> String {
+    match measure {
+        "mttr" => match value.round() as u64 {
+            s if s < 60 => format!("{}s", s),
+            s if s < 3600 => format!("{}m{}s", s / 60, s % 60),
+            s => format!("{}h{}m", s / 3600, (s % 3600) / 60),
+        },
+        _ => format!("{}", value as u64),
+    }
+}
+
+fn print_alert(alert: &ThreatAlert, json: bool) -> Result<(), GuardianError> {
+    let severity = ThreatSeverity::from_i32(alert.severity).unwrap_or(ThreatSeverity::Unknown);
+    let detected_at = alert
+        .detected_at
+        .clone()
+        .and_then(|t| SystemTime::try_from(t).ok())
+        .map(|t| time::OffsetDateTime::from(t).to_string())
+        .unwrap_or_default();
+
+    if json {
+        println!("{}", serde_json::to_string(&json!({
+            "id": alert.id,
+            "severity": severity.as_str_name(),
+            "detected_at": detected_at,
+            "source": alert.source,
+            "description": alert.description,
+            "tags": alert.tags,
+        }))?);
+        return Ok(());
+    }
+
+    let label = severity.as_str_name().trim_start_matches("THREAT_SEVERITY_");
+    let label = match severity {
+        ThreatSeverity::Critical | ThreatSeverity::High => output::paint(label, "31"),
+        ThreatSeverity::Medium => output::paint(label, "33"),
+        _ => label.to_string(),
+    };
+    println!("{}\t{}\t{}\t{}", detected_at, label, alert.id, alert.description);
+    Ok(())
+}
+
+fn watch_error(context: String, source: Option<Box<dyn std::error::Error + Send + Sync>>) -> GuardianError {
+    GuardianError::SystemError {
+        context,
+        source,
+        severity: ErrorSeverity::Medium,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: ErrorCategory::System,
+        retry_count: 0,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -206,4 +652,33 @@ mod tests {
     async fn test_show_threat_details() {
         // Test implementation would go here
     }
+
+    #[test]
+    fn test_watch_filters_and_since() {
+        assert_eq!(parse_age("15m").unwrap(), Duration::from_secs(900));
+        assert!(parse_age("15").is_err());
+        assert!(parse_age("m").is_err());
+
+        assert!(severities_at_least(None).unwrap().is_empty());
+        assert_eq!(
+            severities_at_least(Some("HIGH")).unwrap(),
+            vec![ThreatSeverity::High as i32, ThreatSeverity::Critical as i32]
+        );
+        assert!(severities_at_least(Some("severe")).is_err());
+
+        let now = SystemTime::now();
+        assert_eq!(remaining(now + Duration::from_secs(900), now), "15m");
+        assert_eq!(remaining(now, now + Duration::from_secs(5)), "expired");
+
+        assert_eq!(stat_value("count", 42.0), "42");
+        assert_eq!(stat_value("mttr", 95.4), "1m35s");
+    }
+
+    #[test]
+    fn test_feedback_labels() {
+        assert_eq!(parse_label("false-positive").unwrap(), FeedbackLabel::FalsePositive);
+        assert_eq!(parse_label("TRUE_POSITIVE").unwrap(), FeedbackLabel::TruePositive);
+        assert_eq!(parse_label("fn").unwrap(), FeedbackLabel::FalseNegative);
+        assert!(parse_label("maybe").is_err());
+    }
 }
\ No newline at end of file
@@ -0,0 +1,170 @@
+use clap::{Arg, ArgMatches, Command};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::instrument;
+use metrics::counter;
+
+use crate::cli::commands::{AccessLevel, Command as CliCommand};
+use crate::cli::output;
+use crate::security::auth::TokenAuthority;
+use crate::utils::error::GuardianError;
+
+// Constants for token commands
+const COMMAND_NAME: &str = "tokens";
+const HELP_TEXT: &str = "Issue and revoke API tokens for service accounts";
+
+/// Builds the `tokens` subcommand definition
+pub fn build_tokens_subcommand() -> Command {
+    Command::new(COMMAND_NAME)
+        .about(HELP_TEXT)
+        .subcommand(Command::new("issue")
+            .about("Mint a scoped, expiring token; it is printed once and never stored")
+            .arg(Arg::new("subject")
+                .required(true)
+                .help("Service account the token is for"))
+            .arg(Arg::new("scope")
+                .short('s')
+                .long("scope")
+                .required(true)
+                .action(clap::ArgAction::Append)
+                .help("Scope to grant, bound to RBAC roles as scope:<name>; repeatable"))
+            .arg(Arg::new("ttl")
+                .long("ttl")
+                .value_parser(clap::value_parser!(u64))
+                .help("Hours until the token expires, defaults to the configured TTL")))
+        .subcommand(Command::new("revoke")
+            .about("Revoke a token by its ID")
+            .arg(Arg::new("token-id")
+                .required(true)
+                .help("Token ID (jti) shown when the token was issued"))
+            .arg(Arg::new("reason")
+                .long("reason")
+                .required(true)
+                .help("Why the token is revoked, recorded in the audit log")))
+        .subcommand(Command::new("revoked")
+            .about("List revoked tokens that have not expired yet")
+            .arg(Arg::new("format")
+                .short('f')
+                .long("format")
+                .value_parser(["table", "json"])
+                .default_value("table")
+                .help("Output format")))
+}
+
+/// CLI command managing API tokens
+#[derive(Debug)]
+pub struct TokensCommand {
+    authority: Option<Arc<TokenAuthority>>,
+}
+
+impl TokensCommand {
+    /// Creates a new TokensCommand; without an authority API tokens are disabled
+    pub fn new(authority: Option<Arc<TokenAuthority>>) -> Self {
+        Self { authority }
+    }
+
+    fn authority(&self) -> Result<&TokenAuthority, GuardianError> {
+        self.authority
+            .as_deref()
+            .ok_or_else(|| invalid("API tokens are disabled in security.toml".into()))
+    }
+
+    /// Mints a token and prints it with its claims
+    #[instrument(skip(self))]
+    fn issue(&self, subject: &str, scopes: Vec<String>, ttl: Option<Duration>) -> Result<(), GuardianError> {
+        let issued = self.authority()?.issue(subject, &scopes, ttl)?;
+
+        println!("Token ID:   {}", issued.claims.jti);
+        println!("Subject:    {}", issued.claims.sub);
+        println!("Scopes:     {}", issued.claims.scopes.join(", "));
+        println!("Expires at: {}", issued.claims.expires_at().to_rfc3339());
+        println!();
+        println!("{}", issued.token);
+
+        counter!("guardian.cli.tokens.issue", 1);
+        Ok(())
+    }
+
+    /// Revokes a token by ID
+    #[instrument(skip(self))]
+    async fn revoke(&self, token_id: &str, reason: &str) -> Result<(), GuardianError> {
+        let entry = self.authority()?.revoke(token_id, reason).await?;
+        println!("Revoked token {}", entry.jti);
+        counter!("guardian.cli.tokens.revoke", 1);
+        Ok(())
+    }
+
+    /// Lists revoked tokens as a table or JSON
+    #[instrument(skip(self))]
+    fn revoked(&self, format: &str) -> Result<(), GuardianError> {
+        let entries = self.authority()?.revoked();
+
+        if format == "json" {
+            println!("{}", serde_json::to_string_pretty(&entries)?);
+        } else {
+            let rows: Vec<Vec<String>> = entries
+                .iter()
+                .map(|entry| vec![
+                    entry.jti.clone(),
+                    entry.revoked_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+                    entry.expires_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+                    entry.reason.clone(),
+                ])
+                .collect();
+            print!("{}", output::render_table(&["TOKEN ID", "REVOKED", "KEPT UNTIL", "REASON"], &rows));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl CliCommand for TokensCommand {
+    fn name(&self) -> &'static str {
+        COMMAND_NAME
+    }
+
+    fn configure(&self) -> Command {
+        build_tokens_subcommand()
+    }
+
+    async fn execute(&self, args: &ArgMatches) -> Result<(), GuardianError> {
+        match args.subcommand() {
+            Some(("issue", sub_matches)) => {
+                let subject = sub_matches.get_one::<String>("subject").map(String::as_str).unwrap_or_default();
+                let scopes = sub_matches.get_many::<String>("scope").into_iter().flatten().cloned().collect();
+                let ttl = sub_matches.get_one::<u64>("ttl").map(|hours| Duration::from_secs(hours * 3600));
+                self.issue(subject, scopes, ttl)
+            }
+            Some(("revoke", sub_matches)) => {
+                let token_id = sub_matches.get_one::<String>("token-id").map(String::as_str).unwrap_or_default();
+                let reason = sub_matches.get_one::<String>("reason").map(String::as_str).unwrap_or_default();
+                self.revoke(token_id, reason).await
+            }
+            Some(("revoked", sub_matches)) => {
+                let format = sub_matches.get_one::<String>("format").map(String::as_str).unwrap_or("table");
+                self.revoked(format)
+            }
+            _ => Err(invalid("Invalid subcommand".into())),
+        }
+    }
+
+    fn required_access(&self) -> AccessLevel {
+        AccessLevel::Admin
+    }
+
+    fn help(&self) -> &'static str {
+        HELP_TEXT
+    }
+}
+
+fn invalid(context: String) -> GuardianError {
+    GuardianError::ValidationError {
+        context,
+        source: None,
+        severity: crate::utils::error::ErrorSeverity::Low,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: crate::utils::error::ErrorCategory::Validation,
+        retry_count: 0,
+    }
+}
@@ -0,0 +1,246 @@
+use clap::{Arg, ArgMatches, Command};
+use std::sync::Arc;
+use tracing::instrument;
+use metrics::counter;
+
+use crate::cli::commands::{AccessLevel, Command as CliCommand};
+use crate::cli::output;
+use crate::temporal::visibility::{WorkflowAdmin, WorkflowListFilter, WorkflowSummary, DEFAULT_LIST_LIMIT};
+use crate::utils::error::GuardianError;
+
+// Constants for workflow commands
+const COMMAND_NAME: &str = "workflows";
+const HELP_TEXT: &str = "Inspect, cancel and signal security and maintenance workflows";
+
+/// Builds the `workflows` subcommand definition
+pub fn build_workflows_subcommand() -> Command {
+    let format = Arg::new("format")
+        .short('f')
+        .long("format")
+        .value_parser(["table", "json"])
+        .default_value("table")
+        .help("Output format");
+    let run_id = Arg::new("run-id")
+        .long("run-id")
+        .help("Run of the workflow, defaults to the latest");
+
+    Command::new(COMMAND_NAME)
+        .about(HELP_TEXT)
+        .subcommand(Command::new("list")
+            .about("List workflows, newest first")
+            .arg(Arg::new("state")
+                .long("state")
+                .value_parser(["running", "closed", "all"])
+                .default_value("running")
+                .help("Which executions to list"))
+            .arg(Arg::new("type")
+                .long("type")
+                .value_parser(["security_workflow", "maintenance_workflow"])
+                .help("Only list workflows of this type"))
+            .arg(Arg::new("limit")
+                .long("limit")
+                .value_parser(clap::value_parser!(usize))
+                .help("Most workflows to list"))
+            .arg(format.clone()))
+        .subcommand(Command::new("show")
+            .about("Show a workflow with its current step and retry counts")
+            .arg(Arg::new("workflow-id").required(true).help("Workflow ID"))
+            .arg(run_id.clone())
+            .arg(format))
+        .subcommand(Command::new("cancel")
+            .about("Request cancellation of a running workflow")
+            .arg(Arg::new("workflow-id").required(true).help("Workflow ID"))
+            .arg(run_id.clone())
+            .arg(Arg::new("reason")
+                .long("reason")
+                .required(true)
+                .help("Why the workflow is cancelled, recorded in the audit log")))
+        .subcommand(Command::new("signal")
+            .about("Send a signal to a running workflow")
+            .arg(Arg::new("workflow-id").required(true).help("Workflow ID"))
+            .arg(Arg::new("signal").required(true).help("Signal name"))
+            .arg(run_id)
+            .arg(Arg::new("payload")
+                .long("payload")
+                .help("JSON argument of the signal")))
+}
+
+/// CLI command administering Temporal workflows
+#[derive(Debug)]
+pub struct WorkflowsCommand {
+    admin: Arc<WorkflowAdmin>,
+}
+
+impl WorkflowsCommand {
+    /// Creates a new WorkflowsCommand backed by the given workflow admin
+    pub fn new(admin: Arc<WorkflowAdmin>) -> Self {
+        Self { admin }
+    }
+
+    /// Lists workflows as a table or JSON
+    #[instrument(skip(self))]
+    async fn list(
+        &self,
+        filter: WorkflowListFilter,
+        workflow_type: Option<&str>,
+        limit: usize,
+        format: &str,
+    ) -> Result<(), GuardianError> {
+        let workflows = self.admin.list(filter, workflow_type, limit).await?;
+
+        if format == "json" {
+            println!("{}", serde_json::to_string_pretty(&workflows)?);
+        } else {
+            let rows: Vec<Vec<String>> = workflows
+                .iter()
+                .map(|workflow| vec![
+                    workflow.workflow_id.clone(),
+                    workflow.workflow_type.clone(),
+                    workflow.state.as_str().to_string(),
+                    format_time(workflow.started_at),
+                    format_time(workflow.closed_at),
+                ])
+                .collect();
+            print!("{}", output::render_table(&["WORKFLOW ID", "TYPE", "STATE", "STARTED", "CLOSED"], &rows));
+        }
+        Ok(())
+    }
+
+    /// Shows one workflow with its pending steps
+    #[instrument(skip(self))]
+    async fn show(&self, workflow_id: &str, run_id: Option<&str>, format: &str) -> Result<(), GuardianError> {
+        let detail = self.admin.describe(workflow_id, run_id).await?;
+
+        if format == "json" {
+            println!("{}", serde_json::to_string_pretty(&detail)?);
+            return Ok(());
+        }
+        let summary = &detail.summary;
+        println!("Workflow ID:  {}", summary.workflow_id);
+        println!("Run ID:       {}", summary.run_id);
+        println!("Type:         {}", summary.workflow_type);
+        println!("State:        {}", summary.state.as_str());
+        println!("Started:      {}", format_time(summary.started_at));
+        println!("Closed:       {}", format_time(summary.closed_at));
+        println!("Current step: {}", detail.current_step.as_deref().unwrap_or("-"));
+        println!("Retries:      {}", detail.retries);
+
+        if !detail.pending_steps.is_empty() {
+            println!();
+            let rows: Vec<Vec<String>> = detail
+                .pending_steps
+                .iter()
+                .map(|step| vec![
+                    step.activity.clone(),
+                    match step.maximum_attempts {
+                        0 => step.attempt.to_string(),
+                        max => format!("{}/{}", step.attempt, max),
+                    },
+                    step.last_failure.clone().unwrap_or_default(),
+                ])
+                .collect();
+            print!("{}", output::render_table(&["STEP", "ATTEMPT", "LAST FAILURE"], &rows));
+        }
+        Ok(())
+    }
+
+    /// Requests cancellation of a running workflow
+    #[instrument(skip(self))]
+    async fn cancel(&self, workflow_id: &str, run_id: Option<&str>, reason: &str) -> Result<(), GuardianError> {
+        let summary = self.admin.cancel(workflow_id, run_id, reason).await?;
+        println!("Cancellation requested for {} ({}), run {}", summary.workflow_id, summary.workflow_type, summary.run_id);
+        counter!("guardian.cli.workflows.cancel", 1);
+        Ok(())
+    }
+
+    /// Sends a signal to a running workflow
+    #[instrument(skip(self, payload))]
+    async fn signal(
+        &self,
+        workflow_id: &str,
+        run_id: Option<&str>,
+        signal: &str,
+        payload: Option<&str>,
+    ) -> Result<(), GuardianError> {
+        let payload = payload
+            .map(|json| serde_json::from_str(json).map_err(|e| invalid(format!("Signal payload is not valid JSON: {}", e))))
+            .transpose()?;
+        let summary = self.admin.signal(workflow_id, run_id, signal, payload).await?;
+        println!("Sent {} to {} ({}), run {}", signal, summary.workflow_id, summary.workflow_type, summary.run_id);
+        counter!("guardian.cli.workflows.signal", 1);
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl CliCommand for WorkflowsCommand {
+    fn name(&self) -> &'static str {
+        COMMAND_NAME
+    }
+
+    fn configure(&self) -> Command {
+        build_workflows_subcommand()
+    }
+
+    async fn execute(&self, args: &ArgMatches) -> Result<(), GuardianError> {
+        match args.subcommand() {
+            Some(("list", sub_matches)) => {
+                let filter = match sub_matches.get_one::<String>("state").map(String::as_str) {
+                    Some("closed") => WorkflowListFilter::Closed,
+                    Some("all") => WorkflowListFilter::All,
+                    _ => WorkflowListFilter::Running,
+                };
+                let workflow_type = sub_matches.get_one::<String>("type").map(String::as_str);
+                let limit = sub_matches.get_one::<usize>("limit").copied().unwrap_or(DEFAULT_LIST_LIMIT);
+                let format = sub_matches.get_one::<String>("format").map(String::as_str).unwrap_or("table");
+                self.list(filter, workflow_type, limit, format).await
+            }
+            Some(("show", sub_matches)) => {
+                let format = sub_matches.get_one::<String>("format").map(String::as_str).unwrap_or("table");
+                self.show(workflow_id(sub_matches), run_id(sub_matches), format).await
+            }
+            Some(("cancel", sub_matches)) => {
+                let reason = sub_matches.get_one::<String>("reason").map(String::as_str).unwrap_or_default();
+                self.cancel(workflow_id(sub_matches), run_id(sub_matches), reason).await
+            }
+            Some(("signal", sub_matches)) => {
+                let signal = sub_matches.get_one::<String>("signal").map(String::as_str).unwrap_or_default();
+                let payload = sub_matches.get_one::<String>("payload").map(String::as_str);
+                self.signal(workflow_id(sub_matches), run_id(sub_matches), signal, payload).await
+            }
+            _ => Err(invalid("Invalid subcommand".into())),
+        }
+    }
+
+    fn required_access(&self) -> AccessLevel {
+        AccessLevel::Operator
+    }
+
+    fn help(&self) -> &'static str {
+        HELP_TEXT
+    }
+}
+
+fn workflow_id(args: &ArgMatches) -> &str {
+    args.get_one::<String>("workflow-id").map(String::as_str).unwrap_or_default()
+}
+
+fn run_id(args: &ArgMatches) -> Option<&str> {
+    args.get_one::<String>("run-id").map(String::as_str)
+}
+
+fn format_time(time: Option<chrono::DateTime<chrono::Utc>>) -> String {
+    time.map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string()).unwrap_or_else(|| "-".into())
+}
+
+fn invalid(context: String) -> GuardianError {
+    GuardianError::ValidationError {
+        context,
+        source: None,
+        severity: crate::utils::error::ErrorSeverity::Low,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: crate::utils::error::ErrorCategory::Validation,
+        retry_count: 0,
+    }
+}
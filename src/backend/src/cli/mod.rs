@@ -1,18 +1,23 @@
 use std::time::Duration;
 use clap::{Command, ArgMatches};
-use tracing::{debug, error, info, instrument};
+use tracing::{debug, info, instrument};
 use tokio::time;
-use uuid::Uuid;
 
-use crate::utils::error::{GuardianError, ErrorCategory, ErrorSeverity};
+use crate::utils::correlation;
+use crate::utils::error::GuardianError;
+use crate::utils::ids::{next_uuid, IdKind};
 use crate::utils::metrics::{record_command_execution, track_command_latency};
 use crate::cli::commands::{register_commands, CommandRegistry};
+use crate::cli::output::OutputOptions;
+use crate::security::rbac::Principal;
+
+pub mod commands;
+pub mod output;
 
 // Constants for CLI configuration
 const CLI_VERSION: &str = env!("CARGO_PKG_VERSION");
 const APP_NAME: &str = "guardian-ctl";
 const APP_DESCRIPTION: &str = "Guardian system management and security operations tool";
-const COMMAND_TIMEOUT: Duration = Duration::from_secs(30);
 const MAX_RATE_LIMIT: u32 = 10;
 
 /// Main entry point for the Guardian CLI application
@@ -20,7 +25,7 @@ const MAX_RATE_LIMIT: u32 = 10;
 #[tracing::instrument(err)]
 pub async fn run_cli() -> Result<(), GuardianError> {
     // Generate correlation ID for request tracking
-    let correlation_id = Uuid::new_v4();
+    let correlation_id = next_uuid(IdKind::Correlation);
     debug!(correlation_id = %correlation_id, "Starting CLI execution");
 
     // Initialize metrics collector
@@ -34,14 +39,11 @@ pub async fn run_cli() -> Result<(), GuardianError> {
         },
     )?);
 
-    // Initialize audit logging
-    let audit_log = Arc::new(crate::utils::logging::LogManager::new());
-
-    // Initialize command registry
-    let mut registry = CommandRegistry::new(metrics.clone(), audit_log);
+    // Initialize command registry, recording every execution in the command audit
+    let mut registry = CommandRegistry::new(metrics.clone(), crate::security::command_audit::command_audit());
 
     // Register available commands
-    register_commands(&mut registry)?;
+    register_commands(&mut registry).await?;
 
     // Set up CLI application
     let cli = setup_cli();
@@ -49,23 +51,12 @@ pub async fn run_cli() -> Result<(), GuardianError> {
     // Parse command line arguments
     let matches = cli.get_matches();
 
-    // Execute command with timeout
+    // Resolve plain/no-color/progress settings before any command prints
+    output::init(OutputOptions::from_matches(&matches));
+
+    // The registry applies the command's own timeout, or the one given with --timeout
     let start_time = time::Instant::now();
-    let result = match time::timeout(COMMAND_TIMEOUT, execute_command(&registry, matches)).await {
-        Ok(result) => result,
-        Err(_) => {
-            error!("Command execution timeout");
-            return Err(GuardianError::TimeoutError {
-                context: "Command execution timeout".into(),
-                source: None,
-                severity: ErrorSeverity::High,
-                timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id,
-                category: ErrorCategory::System,
-                retry_count: 0,
-            });
-        }
-    };
+    let result = correlation::scope(correlation_id, execute_command(&registry, matches)).await;
 
     // Record metrics
     record_command_execution("cli.command", correlation_id, start_time)?;
@@ -83,6 +74,19 @@ fn setup_cli() -> Command {
         .subcommand(commands::status::build_status_subcommand())
         .subcommand(commands::threats::build_threats_subcommand())
         .subcommand(commands::models::build_models_subcommand())
+        .subcommand(commands::ops::build_ops_subcommand())
+        .subcommand(commands::backup::build_backup_subcommand())
+        .subcommand(commands::forensics::build_forensics_subcommand())
+        .subcommand(commands::support_bundle::build_support_bundle_subcommand())
+        .subcommand(commands::remote_assist::build_remote_assist_subcommand())
+        .subcommand(commands::content::build_content_subcommand())
+        .subcommand(commands::policy::build_policy_subcommand())
+        .subcommand(commands::dead_letters::build_dead_letters_subcommand())
+        .subcommand(commands::audit::build_audit_subcommand())
+        .subcommand(commands::diagnose::build_diagnose_subcommand())
+        .subcommand(commands::tokens::build_tokens_subcommand())
+        .subcommand(commands::workflows::build_workflows_subcommand())
+        .subcommand(commands::response::build_response_subcommand())
         .arg(
             clap::Arg::new("verbose")
                 .short('v')
@@ -90,22 +94,45 @@ fn setup_cli() -> Command {
                 .help("Enable verbose output")
                 .action(clap::ArgAction::SetTrue),
         )
+        .arg(
+            clap::Arg::new("timeout")
+                .long("timeout")
+                .value_parser(commands::parse_timeout)
+                .help("Abandon the command after this long, e.g. 90s, 15m or 2h; defaults depend on the command"),
+        )
         .arg(
             clap::Arg::new("no-color")
                 .long("no-color")
                 .help("Disable colored output")
+                .global(true)
                 .action(clap::ArgAction::SetTrue),
         )
+        .arg(
+            clap::Arg::new("plain")
+                .long("plain")
+                .help("Plain output: no colors, no spinners, stable tab-separated columns")
+                .global(true)
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            clap::Arg::new("progress")
+                .long("progress")
+                .value_parser(["text", "json"])
+                .default_value("text")
+                .help("Progress event format for long-running operations")
+                .global(true),
+        )
 }
 
 /// Executes the requested command with access control
 async fn execute_command(registry: &CommandRegistry, matches: ArgMatches) -> Result<(), GuardianError> {
     if let Some((cmd_name, cmd_matches)) = matches.subcommand() {
-        // Determine access level based on user context
-        let access_level = determine_access_level()?;
+        // Authorize as the invoking Unix user and its groups
+        let principal = Principal::current_user();
 
         // Execute command through registry
-        registry.execute(cmd_name.to_string(), cmd_matches.clone(), access_level).await?;
+        let timeout = matches.get_one::<Duration>("timeout").copied();
+        registry.execute(cmd_name.to_string(), cmd_matches.clone(), &principal, timeout).await?;
     } else {
         // Show help if no subcommand provided
         println!("{}", setup_cli().render_help());
@@ -114,13 +141,6 @@ async fn execute_command(registry: &CommandRegistry, matches: ArgMatches) -> Res
     Ok(())
 }
 
-/// Determines user access level from environment
-fn determine_access_level() -> Result<commands::AccessLevel, GuardianError> {
-    // In a real implementation, this would check user credentials and roles
-    // For now, return operator level access
-    Ok(commands::AccessLevel::Operator)
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -137,4 +157,19 @@ mod tests {
         assert_eq!(cli.get_name(), APP_NAME);
         assert_eq!(cli.get_version(), Some(CLI_VERSION));
     }
+
+    #[test]
+    fn test_timeout_flag() {
+        let matches = setup_cli().get_matches_from(vec![APP_NAME, "--timeout", "15m", "ops", "list"]);
+        assert_eq!(matches.get_one::<Duration>("timeout"), Some(&Duration::from_secs(15 * 60)));
+    }
+
+    #[test]
+    fn test_plain_output_flags() {
+        let matches = setup_cli().get_matches_from(vec![APP_NAME, "--plain", "--progress", "json"]);
+        let options = OutputOptions::from_matches(&matches);
+        assert!(options.is_plain());
+        assert!(!options.color);
+        assert_eq!(options.progress, output::ProgressFormat::Json);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,317 @@
+use std::io::{self, Write};
+use std::time::Instant;
+
+use clap::ArgMatches;
+use once_cell::sync::OnceCell;
+use serde::Serialize;
+use tracing::debug;
+use uuid::Uuid;
+
+// Constants for CLI output configuration
+const NO_COLOR_ENV: &str = "NO_COLOR";
+const PLAIN_ENV: &str = "GUARDIAN_CTL_PLAIN";
+const PROGRESS_EVENT_VERSION: u32 = 1;
+const SPINNER_FRAMES: [&str; 4] = ["|", "/", "-", "\\"];
+
+static OUTPUT_OPTIONS: OnceCell<OutputOptions> = OnceCell::new();
+
+/// Presentation mode for human-facing CLI output
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputMode {
+    /// Colors, spinners and aligned columns
+    Rich,
+    /// No colors, no spinners, tab-separated columns in a stable order
+    Plain,
+}
+
+/// Format used for progress reporting of long-running operations
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProgressFormat {
+    Text,
+    Json,
+}
+
+/// Resolved output options shared by all CLI commands
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutputOptions {
+    pub mode: OutputMode,
+    pub color: bool,
+    pub progress: ProgressFormat,
+}
+
+impl Default for OutputOptions {
+    fn default() -> Self {
+        Self {
+            mode: OutputMode::Rich,
+            color: true,
+            progress: ProgressFormat::Text,
+        }
+    }
+}
+
+impl OutputOptions {
+    /// Resolves output options from parsed arguments and the environment
+    pub fn from_matches(matches: &ArgMatches) -> Self {
+        let plain = matches.get_flag("plain") || std::env::var_os(PLAIN_ENV).is_some();
+        let no_color = matches.get_flag("no-color") || std::env::var_os(NO_COLOR_ENV).is_some();
+        let progress = match matches.get_one::<String>("progress").map(String::as_str) {
+            Some("json") => ProgressFormat::Json,
+            _ => ProgressFormat::Text,
+        };
+
+        Self {
+            mode: if plain { OutputMode::Plain } else { OutputMode::Rich },
+            color: !plain && !no_color,
+            progress,
+        }
+    }
+
+    /// Returns true when plain output was requested
+    pub fn is_plain(&self) -> bool {
+        self.mode == OutputMode::Plain
+    }
+}
+
+/// Installs the process-wide output options, first call wins
+pub fn init(options: OutputOptions) {
+    if OUTPUT_OPTIONS.set(options).is_err() {
+        debug!("CLI output options already initialized");
+    }
+}
+
+/// Returns the active output options
+pub fn current() -> OutputOptions {
+    OUTPUT_OPTIONS.get().copied().unwrap_or_default()
+}
+
+/// Wraps text in an ANSI color code when colored output is enabled
+pub fn paint(text: &str, ansi_code: &str) -> String {
+    if current().color {
+        format!("\x1b[{}m{}\x1b[0m", ansi_code, text)
+    } else {
+        text.to_string()
+    }
+}
+
+/// Renders a table, tab-separated in plain mode and column-aligned otherwise
+pub fn render_table(headers: &[&str], rows: &[Vec<String>]) -> String {
+    render_table_with(current(), headers, rows)
+}
+
+fn render_table_with(options: OutputOptions, headers: &[&str], rows: &[Vec<String>]) -> String {
+    let mut out = String::new();
+
+    if options.is_plain() {
+        out.push_str(&headers.join("\t"));
+        out.push('\n');
+        for row in rows {
+            // Pad or truncate so every row has the header's column order and count
+            let cells: Vec<&str> = (0..headers.len())
+                .map(|i| row.get(i).map(String::as_str).unwrap_or(""))
+                .collect();
+            out.push_str(&cells.join("\t"));
+            out.push('\n');
+        }
+        return out;
+    }
+
+    let widths: Vec<usize> = headers
+        .iter()
+        .enumerate()
+        .map(|(i, h)| {
+            rows.iter()
+                .filter_map(|r| r.get(i))
+                .map(|c| c.len())
+                .chain(std::iter::once(h.len()))
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let header_line: Vec<String> = headers
+        .iter()
+        .zip(&widths)
+        .map(|(h, w)| format!("{:<width$}", h, width = w))
+        .collect();
+    out.push_str(&paint(header_line.join("  ").trim_end(), "1"));
+    out.push('\n');
+    out.push_str(&"-".repeat(widths.iter().sum::<usize>() + 2 * widths.len().saturating_sub(1)));
+    out.push('\n');
+
+    for row in rows {
+        let line: Vec<String> = widths
+            .iter()
+            .enumerate()
+            .map(|(i, w)| format!("{:<width$}", row.get(i).map(String::as_str).unwrap_or(""), width = w))
+            .collect();
+        out.push_str(line.join("  ").trim_end());
+        out.push('\n');
+    }
+
+    out
+}
+
+/// Lifecycle stage of a progress event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProgressStage {
+    Started,
+    Progress,
+    Completed,
+    Failed,
+}
+
+/// Machine-readable progress event for long-running CLI operations
+#[derive(Debug, Clone, Serialize)]
+pub struct ProgressEvent {
+    pub version: u32,
+    pub operation_id: Uuid,
+    pub operation: String,
+    pub stage: ProgressStage,
+    pub percent: Option<f32>,
+    pub message: String,
+    pub elapsed_ms: u64,
+    pub timestamp: String,
+}
+
+/// Reports progress of a long-running operation such as a backup or model upload
+#[derive(Debug)]
+pub struct ProgressReporter {
+    operation_id: Uuid,
+    operation: String,
+    options: OutputOptions,
+    started: Instant,
+    ticks: usize,
+}
+
+impl ProgressReporter {
+    /// Starts reporting for the named operation and emits a `started` event
+    pub fn start(operation: &str) -> Self {
+        Self::start_with(current(), operation)
+    }
+
+    fn start_with(options: OutputOptions, operation: &str) -> Self {
+        let mut reporter = Self {
+            operation_id: Uuid::new_v4(),
+            operation: operation.to_string(),
+            options,
+            started: Instant::now(),
+            ticks: 0,
+        };
+        reporter.emit(ProgressStage::Started, Some(0.0), "started");
+        reporter
+    }
+
+    /// Returns the identifier attached to every event of this operation
+    pub fn operation_id(&self) -> Uuid {
+        self.operation_id
+    }
+
+    /// Emits an intermediate progress update
+    pub fn update(&mut self, percent: f32, message: &str) {
+        self.emit(ProgressStage::Progress, Some(percent.clamp(0.0, 100.0)), message);
+    }
+
+    /// Emits the terminal `completed` event
+    pub fn finish(mut self, message: &str) {
+        self.emit(ProgressStage::Completed, Some(100.0), message);
+    }
+
+    /// Emits the terminal `failed` event
+    pub fn fail(mut self, message: &str) {
+        self.emit(ProgressStage::Failed, None, message);
+    }
+
+    fn event(&self, stage: ProgressStage, percent: Option<f32>, message: &str) -> ProgressEvent {
+        ProgressEvent {
+            version: PROGRESS_EVENT_VERSION,
+            operation_id: self.operation_id,
+            operation: self.operation.clone(),
+            stage,
+            percent,
+            message: message.to_string(),
+            elapsed_ms: self.started.elapsed().as_millis() as u64,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+
+    fn emit(&mut self, stage: ProgressStage, percent: Option<f32>, message: &str) {
+        let event = self.event(stage, percent, message);
+        let line = format_progress_line(self.options, &event, self.ticks);
+        self.ticks += 1;
+
+        // Progress goes to stderr so stdout stays clean for command results
+        let mut stderr = io::stderr().lock();
+        let _ = writeln!(stderr, "{}", line);
+        let _ = stderr.flush();
+    }
+}
+
+/// Formats a single progress event according to the active output options
+fn format_progress_line(options: OutputOptions, event: &ProgressEvent, tick: usize) -> String {
+    if options.progress == ProgressFormat::Json {
+        return serde_json::to_string(event).unwrap_or_default();
+    }
+
+    let percent = event
+        .percent
+        .map(|p| format!("{:.0}%", p))
+        .unwrap_or_else(|| "-".to_string());
+    let stage = format!("{:?}", event.stage).to_lowercase();
+
+    if options.is_plain() {
+        // One self-contained line per event so screen readers announce each update once
+        format!("{}: {} {} {}", event.operation, stage, percent, event.message)
+    } else {
+        let spinner = SPINNER_FRAMES[tick % SPINNER_FRAMES.len()];
+        format!("{} {} [{}] {}", spinner, event.operation, percent, event.message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plain() -> OutputOptions {
+        OutputOptions {
+            mode: OutputMode::Plain,
+            color: false,
+            progress: ProgressFormat::Text,
+        }
+    }
+
+    #[test]
+    fn test_plain_table_is_tab_separated_in_header_order() {
+        let rows = vec![
+            vec!["model-a".to_string(), "v1.0.0".to_string(), "active".to_string()],
+            vec!["model-b".to_string(), "v2.1.0".to_string()],
+        ];
+        let table = render_table_with(plain(), &["MODEL ID", "VERSION", "STATUS"], &rows);
+
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines[0], "MODEL ID\tVERSION\tSTATUS");
+        assert_eq!(lines[1], "model-a\tv1.0.0\tactive");
+        assert_eq!(lines[2], "model-b\tv2.1.0\t");
+        assert!(!table.contains('\x1b'));
+    }
+
+    #[test]
+    fn test_progress_json_events() {
+        let options = OutputOptions {
+            progress: ProgressFormat::Json,
+            ..plain()
+        };
+        let reporter = ProgressReporter::start_with(options, "config.backup");
+        let event = reporter.event(ProgressStage::Progress, Some(50.0), "writing");
+        let line = format_progress_line(options, &event, 1);
+
+        let parsed: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(parsed["operation"], "config.backup");
+        assert_eq!(parsed["stage"], "progress");
+        assert_eq!(parsed["percent"], 50.0);
+        assert_eq!(parsed["operation_id"], reporter.operation_id().to_string());
+
+        let text = format_progress_line(plain(), &event, 1);
+        assert_eq!(text, "config.backup: progress 50% writing");
+    }
+}
@@ -1,13 +1,13 @@
 use config::{Config, ConfigError, Environment, File};
 use serde::{Deserialize, Serialize};
-use std::{path::PathBuf, time::Duration};
+use std::{collections::BTreeMap, path::PathBuf, time::Duration};
 use tracing::{debug, error, info, instrument};
 
 use crate::utils::error::{GuardianError, ValidationError, ConfigurationError};
 use crate::utils::validation::{ValidationContext, validate, validate_performance};
 
 // Core configuration constants
-const CONFIG_VERSION: &str = "1.0.0";
+pub(crate) const CONFIG_VERSION: &str = "1.0.0";
 const DEFAULT_APP_NAME: &str = "AI Guardian";
 const MIN_THREADS: usize = 2;
 const MAX_THREADS: usize = 32;
@@ -15,6 +15,7 @@ const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
 const SYSTEM_OVERHEAD_LIMIT: f64 = 0.05; // 5% max system overhead
 const CRITICAL_RESPONSE_LIMIT: Duration = Duration::from_millis(1000);
 const MIN_UPTIME_PERCENTAGE: f64 = 99.999;
+const DEFAULT_TRACE_SAMPLE_RATIO: f64 = 0.1;
 
 /// Environment types for configuration
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -67,6 +68,462 @@ pub struct MonitoringConfig {
     pub health_check_interval: Duration,
     pub enable_tracing: bool,
     pub log_retention_days: u32,
+    /// Address of the Prometheus scrape endpoint, disabled when unset
+    #[serde(default)]
+    pub prometheus_addr: Option<String>,
+    /// OTLP gRPC collector spans are exported to, disabled when unset
+    #[serde(default)]
+    pub otlp_endpoint: Option<String>,
+    /// Fraction of new traces sampled; traces started upstream follow the caller's decision
+    #[serde(default = "default_trace_sample_ratio")]
+    pub trace_sample_ratio: f64,
+}
+
+fn default_trace_sample_ratio() -> f64 {
+    DEFAULT_TRACE_SAMPLE_RATIO
+}
+
+/// CPU affinity of Guardian worker threads, cpusets use `0-3,6` notation
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CpuAffinityConfig {
+    pub enabled: bool,
+    pub inference_cpuset: Option<String>,
+    pub io_cpuset: Option<String>,
+}
+
+/// Load shedding thresholds, above which low priority work is rejected
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AdmissionConfig {
+    pub enabled: bool,
+    /// One-minute load average per core treated as the CPU budget
+    pub cpu_budget: f64,
+    /// Queue backlog age above which low priority work is shed
+    pub elevated_lag: Duration,
+    /// Queue backlog age above which medium priority work is also shed
+    pub severe_lag: Duration,
+    pub sample_interval: Duration,
+}
+
+impl Default for AdmissionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            cpu_budget: 0.9,
+            elevated_lag: Duration::from_secs(2),
+            severe_lag: Duration::from_secs(10),
+            sample_interval: Duration::from_secs(1),
+        }
+    }
+}
+
+/// Overhead budget the resource governor holds ML, storage and detection to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ResourceGovernorConfig {
+    pub enabled: bool,
+    /// Combined CPU of all subsystems, as a percentage of every core
+    pub cpu_budget_percent: f64,
+    /// Resident memory of the process, as a percentage of system memory
+    pub memory_budget_percent: f64,
+    /// Concurrent work permits per subsystem when unthrottled
+    pub max_permits: usize,
+    /// Lowest scale a subsystem is throttled to
+    pub min_scale: f64,
+    pub sample_interval: Duration,
+}
+
+impl Default for ResourceGovernorConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            cpu_budget_percent: SYSTEM_OVERHEAD_LIMIT * 100.0,
+            memory_budget_percent: SYSTEM_OVERHEAD_LIMIT * 100.0,
+            max_permits: 4,
+            min_scale: 0.1,
+            sample_interval: Duration::from_secs(1),
+        }
+    }
+}
+
+/// Sustained rate and burst one API client may use
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuotaLimit {
+    pub requests_per_second: u32,
+    pub burst: u32,
+}
+
+/// API quotas per client identity, keyed by mTLS common name or API token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ClientQuotaConfig {
+    pub enabled: bool,
+    /// Limit for clients none of whose RBAC roles has its own
+    pub default_limit: QuotaLimit,
+    /// Limits by RBAC role; a client with several roles gets the most generous one
+    pub role_limits: BTreeMap<String, QuotaLimit>,
+    /// Buckets of clients idle for longer than this are dropped
+    pub idle_expiry: Duration,
+}
+
+impl Default for ClientQuotaConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            default_limit: QuotaLimit { requests_per_second: 20, burst: 40 },
+            role_limits: BTreeMap::from([
+                ("admin".to_string(), QuotaLimit { requests_per_second: 200, burst: 400 }),
+                ("security".to_string(), QuotaLimit { requests_per_second: 100, burst: 200 }),
+            ]),
+            idle_expiry: Duration::from_secs(600),
+        }
+    }
+}
+
+/// Caps on automatic responses within one guardrail window
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResponseLimits {
+    pub max_terminated_processes: u32,
+    /// Distinct addresses; re-blocking an address already counted is free
+    pub max_blocked_addresses: u32,
+    /// Processes isolated into jails
+    pub max_jails: u32,
+}
+
+/// Blast radius guardrails on automatic responses, per environment
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ResponseGuardrailConfig {
+    pub enabled: bool,
+    /// Sliding window the limits apply to
+    pub window: Duration,
+    pub development: ResponseLimits,
+    pub staging: ResponseLimits,
+    pub production: ResponseLimits,
+}
+
+impl ResponseGuardrailConfig {
+    /// Returns the limits for an environment
+    pub fn limits_for(&self, environment: &Environment) -> ResponseLimits {
+        match environment {
+            Environment::Development => self.development,
+            Environment::Staging => self.staging,
+            Environment::Production => self.production,
+        }
+    }
+}
+
+impl Default for ResponseGuardrailConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            window: Duration::from_secs(3600),
+            development: ResponseLimits { max_terminated_processes: 200, max_blocked_addresses: 1000, max_jails: 100 },
+            staging: ResponseLimits { max_terminated_processes: 50, max_blocked_addresses: 250, max_jails: 25 },
+            production: ResponseLimits { max_terminated_processes: 20, max_blocked_addresses: 100, max_jails: 10 },
+        }
+    }
+}
+
+/// Trust lifecycle of newly observed binaries
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProcessTrustConfig {
+    /// Clean time before a new or demoted binary counts as observed
+    pub observation_period: Duration,
+    /// Clean time before a binary is trusted
+    pub trusted_after: Duration,
+    /// SHA-256 digests or absolute paths of binaries trusted on sight
+    pub allowlist: Vec<String>,
+}
+
+impl Default for ProcessTrustConfig {
+    fn default() -> Self {
+        Self {
+            observation_period: Duration::from_secs(24 * 60 * 60),
+            trusted_after: Duration::from_secs(7 * 24 * 60 * 60),
+            allowlist: Vec::new(),
+        }
+    }
+}
+
+/// Retry and retention of EventBus deliveries that failed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DeadLetterConfig {
+    /// Automatic redeliveries before a dead letter waits for a manual requeue
+    pub max_attempts: u32,
+    /// Delay before the first redelivery, doubled on each further attempt
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    /// Dead letters older than this are dropped, delivered or not
+    pub max_age: Duration,
+}
+
+impl Default for DeadLetterConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 8,
+            initial_backoff: Duration::from_secs(5),
+            max_backoff: Duration::from_secs(600),
+            max_age: Duration::from_secs(7 * 24 * 60 * 60),
+        }
+    }
+}
+
+/// Format of a threat intelligence feed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IntelFeedFormat {
+    /// STIX 2.1 bundle of indicators
+    Stix,
+    /// TAXII 2.1 collection, followed across pages
+    Taxii,
+    /// One indicator per line, optionally `value,type`
+    Csv,
+    /// JSON array of strings or of `{ "value", "type" }` objects
+    Json,
+}
+
+/// A threat intelligence feed pulled on every refresh
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntelFeedConfig {
+    pub name: String,
+    /// Bundle or list URL; for TAXII, the collection's objects endpoint
+    pub url: String,
+    pub format: IntelFeedFormat,
+    /// Environment variable holding a bearer token for the feed
+    #[serde(default)]
+    pub token_env: Option<String>,
+    /// Confidence of detections raised by this feed's indicators
+    #[serde(default = "default_intel_confidence")]
+    pub confidence: f32,
+}
+
+fn default_intel_confidence() -> f32 {
+    0.99
+}
+
+/// Indicator feeds consulted before ML inference
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ThreatIntelConfig {
+    pub feeds: Vec<IntelFeedConfig>,
+    pub refresh_interval: Duration,
+    /// Directory holding the last pull of every feed, used until the next refresh succeeds
+    pub store_path: PathBuf,
+}
+
+impl Default for ThreatIntelConfig {
+    fn default() -> Self {
+        Self {
+            feeds: Vec::new(),
+            refresh_interval: Duration::from_secs(60 * 60),
+            store_path: PathBuf::from("/var/db/guardian/intel"),
+        }
+    }
+}
+
+/// Operator-written detection rules compiled to WebAssembly, run against samples before ML inference
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DetectionRulesConfig {
+    pub enabled: bool,
+    /// Directory of `*.wasm` rule modules, reloaded with the configuration
+    pub rules_dir: PathBuf,
+    /// Fuel a rule may burn on one sample, roughly one unit per WebAssembly instruction
+    pub fuel_per_sample: u64,
+    /// Largest linear memory a rule instance may grow to
+    pub max_memory_bytes: usize,
+}
+
+impl Default for DetectionRulesConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            rules_dir: PathBuf::from("/usr/local/etc/guardian/rules"),
+            fuel_per_sample: 10_000_000,
+            max_memory_bytes: 16 * 1024 * 1024,
+        }
+    }
+}
+
+/// Step of the degradation ladder taken while detection misses its latency budget
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DegradationLevel {
+    Normal,
+    /// Collectors sample less often
+    ReducedSampling,
+    /// Inference runs on the configured fallback model
+    SmallModel,
+    /// Model stages are skipped; intel matches and detection rules still run
+    HeuristicOnly,
+    /// Detections are raised but no response action touches the host
+    AlertOnly,
+}
+
+impl DegradationLevel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DegradationLevel::Normal => "normal",
+            DegradationLevel::ReducedSampling => "reduced_sampling",
+            DegradationLevel::SmallModel => "small_model",
+            DegradationLevel::HeuristicOnly => "heuristic_only",
+            DegradationLevel::AlertOnly => "alert_only",
+        }
+    }
+}
+
+/// Ladder of levels detection steps down while cycles exceed `MAX_DETECTION_TIME_MS`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DegradationConfig {
+    pub enabled: bool,
+    /// Levels taken one at a time, mildest first; each level keeps the effects of those before it
+    pub ladder: Vec<DegradationLevel>,
+    /// Consecutive slow cycles before stepping down a level
+    pub breach_cycles: u32,
+    /// Consecutive cycles under `recovery_ratio` of the budget before stepping back up
+    pub recovery_cycles: u32,
+    pub recovery_ratio: f64,
+    /// Collector intervals are multiplied by this from `reduced_sampling` on
+    pub sampling_interval_factor: f64,
+    /// Model version served from `small_model` on; without one that level is skipped
+    pub fallback_model: Option<String>,
+}
+
+impl Default for DegradationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ladder: vec![
+                DegradationLevel::ReducedSampling,
+                DegradationLevel::SmallModel,
+                DegradationLevel::HeuristicOnly,
+                DegradationLevel::AlertOnly,
+            ],
+            breach_cycles: 5,
+            recovery_cycles: 50,
+            recovery_ratio: 0.8,
+            sampling_interval_factor: 2.0,
+            fallback_model: None,
+        }
+    }
+}
+
+/// Sampling of one system data collector
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CollectorConfig {
+    pub enabled: bool,
+    /// Time between snapshots
+    pub interval: Duration,
+    /// Records kept per snapshot, the rest are dropped
+    pub max_records: usize,
+    /// Longest a snapshot may take before it is abandoned
+    pub timeout: Duration,
+}
+
+impl CollectorConfig {
+    fn every(interval: Duration, max_records: usize) -> Self {
+        Self {
+            enabled: true,
+            interval,
+            max_records,
+            timeout: Duration::from_millis(500),
+        }
+    }
+}
+
+impl Default for CollectorConfig {
+    fn default() -> Self {
+        Self::every(Duration::from_secs(1), 4096)
+    }
+}
+
+/// Host data collected for the detection pipeline
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CollectorsConfig {
+    pub processes: CollectorConfig,
+    pub network: CollectorConfig,
+    /// Walks every descriptor of every process, so sampled least often
+    pub open_files: CollectorConfig,
+    pub kernel: CollectorConfig,
+    /// Ring that kernel-level sensors write telemetry into; off unless a sensor is deployed
+    pub shared_memory: SharedMemoryConfig,
+}
+
+impl CollectorsConfig {
+    /// Settings of a built-in collector by name
+    pub fn for_collector(&self, name: &str) -> Option<&CollectorConfig> {
+        match name {
+            "processes" => Some(&self.processes),
+            "network" => Some(&self.network),
+            "open_files" => Some(&self.open_files),
+            "kernel" => Some(&self.kernel),
+            "shared_memory" => Some(&self.shared_memory.collector),
+            _ => None,
+        }
+    }
+}
+
+impl Default for CollectorsConfig {
+    fn default() -> Self {
+        Self {
+            processes: CollectorConfig::every(Duration::from_secs(1), 4096),
+            network: CollectorConfig::every(Duration::from_secs(1), 4096),
+            open_files: CollectorConfig::every(Duration::from_secs(10), 8192),
+            kernel: CollectorConfig::every(Duration::from_secs(1), 64),
+            shared_memory: SharedMemoryConfig::default(),
+        }
+    }
+}
+
+/// Shared-memory telemetry ring for high-rate sensors
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SharedMemoryConfig {
+    pub collector: CollectorConfig,
+    /// Ring file sensors map; the doorbell lives next to it with a `.doorbell` suffix
+    pub path: PathBuf,
+    /// Records the ring holds before sensors start overflowing
+    pub slots: usize,
+    /// Fill ratio at which sensors are asked to back off
+    pub high_watermark: f64,
+}
+
+impl Default for SharedMemoryConfig {
+    fn default() -> Self {
+        Self {
+            collector: CollectorConfig {
+                enabled: false,
+                ..CollectorConfig::every(Duration::from_millis(100), 65_536)
+            },
+            path: PathBuf::from("/var/run/guardian/telemetry.ring"),
+            slots: 262_144,
+            high_watermark: 0.75,
+        }
+    }
+}
+
+/// Watching of the configuration directory for automatic reloads
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConfigWatchConfig {
+    pub enabled: bool,
+    /// Quiet period after the last change before the files are reloaded
+    pub debounce: Duration,
+}
+
+impl Default for ConfigWatchConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            debounce: Duration::from_millis(500),
+        }
+    }
 }
 
 /// Main application configuration structure
@@ -84,6 +541,30 @@ pub struct AppConfig {
     pub resource_limits: ResourceLimits,
     pub security_settings: SecuritySettings,
     pub monitoring_config: MonitoringConfig,
+    #[serde(default)]
+    pub cpu_affinity: CpuAffinityConfig,
+    #[serde(default)]
+    pub admission: AdmissionConfig,
+    #[serde(default)]
+    pub resource_governor: ResourceGovernorConfig,
+    #[serde(default)]
+    pub client_quotas: ClientQuotaConfig,
+    #[serde(default)]
+    pub response_guardrails: ResponseGuardrailConfig,
+    #[serde(default)]
+    pub process_trust: ProcessTrustConfig,
+    #[serde(default)]
+    pub dead_letters: DeadLetterConfig,
+    #[serde(default)]
+    pub config_watch: ConfigWatchConfig,
+    #[serde(default)]
+    pub threat_intel: ThreatIntelConfig,
+    #[serde(default)]
+    pub detection_rules: DetectionRulesConfig,
+    #[serde(default)]
+    pub collectors: CollectorsConfig,
+    #[serde(default)]
+    pub degradation: DegradationConfig,
 }
 
 impl AppConfig {
@@ -119,6 +600,9 @@ impl AppConfig {
             health_check_interval: Duration::from_secs(30),
             enable_tracing: true,
             log_retention_days: 90,
+            prometheus_addr: None,
+            otlp_endpoint: None,
+            trace_sample_ratio: DEFAULT_TRACE_SAMPLE_RATIO,
         };
 
         Self {
@@ -133,12 +617,32 @@ impl AppConfig {
             resource_limits,
             security_settings,
             monitoring_config,
+            cpu_affinity: CpuAffinityConfig::default(),
+            admission: AdmissionConfig::default(),
+            resource_governor: ResourceGovernorConfig::default(),
+            client_quotas: ClientQuotaConfig::default(),
+            response_guardrails: ResponseGuardrailConfig::default(),
+            process_trust: ProcessTrustConfig::default(),
+            dead_letters: DeadLetterConfig::default(),
+            config_watch: ConfigWatchConfig::default(),
+            threat_intel: ThreatIntelConfig::default(),
+            detection_rules: DetectionRulesConfig::default(),
+            collectors: CollectorsConfig::default(),
+            degradation: DegradationConfig::default(),
         }
     }
 
     /// Loads and validates configuration from file with environment overlays
     #[instrument(skip(config_path))]
     pub fn load(config_path: PathBuf) -> Result<Self, GuardianError> {
+        let app_config = Self::read(config_path)?;
+        app_config.validate()?;
+        info!("Configuration loaded successfully");
+        Ok(app_config)
+    }
+
+    /// Loads configuration from file with environment overlays without validating it
+    pub fn read(config_path: PathBuf) -> Result<Self, GuardianError> {
         let config_file = File::from(config_path).required(true);
         
         let config = Config::builder()
@@ -150,26 +654,22 @@ impl AppConfig {
                 source: Some(Box::new(e)),
                 severity: crate::utils::error::ErrorSeverity::Critical,
                 timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id: uuid::Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: crate::utils::error::ErrorCategory::System,
                 retry_count: 0,
             })?;
 
-        let mut app_config: AppConfig = config.try_deserialize().map_err(|e| {
+        config.try_deserialize().map_err(|e| {
             GuardianError::ConfigurationError {
                 context: "Failed to deserialize configuration".into(),
                 source: Some(Box::new(e)),
                 severity: crate::utils::error::ErrorSeverity::Critical,
                 timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id: uuid::Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: crate::utils::error::ErrorCategory::System,
                 retry_count: 0,
             }
-        })?;
-
-        app_config.validate()?;
-        info!("Configuration loaded successfully");
-        Ok(app_config)
+        })
     }
 
     /// Validates configuration against performance and security requirements
@@ -182,7 +682,7 @@ impl AppConfig {
                 source: None,
                 severity: crate::utils::error::ErrorSeverity::High,
                 timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id: uuid::Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: crate::utils::error::ErrorCategory::Validation,
                 retry_count: 0,
             });
@@ -195,7 +695,7 @@ impl AppConfig {
                 source: None,
                 severity: crate::utils::error::ErrorSeverity::High,
                 timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id: uuid::Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: crate::utils::error::ErrorCategory::Validation,
                 retry_count: 0,
             });
@@ -209,7 +709,7 @@ impl AppConfig {
                     source: None,
                     severity: crate::utils::error::ErrorSeverity::Critical,
                     timestamp: time::OffsetDateTime::now_utc(),
-                    correlation_id: uuid::Uuid::new_v4(),
+                    correlation_id: crate::utils::correlation::current_or_new(),
                     category: crate::utils::error::ErrorCategory::Validation,
                     retry_count: 0,
                 });
@@ -223,7 +723,181 @@ impl AppConfig {
                 source: None,
                 severity: crate::utils::error::ErrorSeverity::Medium,
                 timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id: uuid::Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
+                category: crate::utils::error::ErrorCategory::Validation,
+                retry_count: 0,
+            });
+        }
+
+        // Validate CPU affinity cpusets
+        for cpuset in [&self.cpu_affinity.inference_cpuset, &self.cpu_affinity.io_cpuset] {
+            if let Some(spec) = cpuset {
+                crate::utils::affinity::parse_cpuset(spec)?;
+            }
+        }
+
+        // Validate admission control thresholds
+        if self.admission.cpu_budget <= 0.0 || self.admission.severe_lag < self.admission.elevated_lag {
+            return Err(GuardianError::ValidationError {
+                context: "Admission control needs a positive CPU budget and severe_lag >= elevated_lag".into(),
+                source: None,
+                severity: crate::utils::error::ErrorSeverity::Medium,
+                timestamp: time::OffsetDateTime::now_utc(),
+                correlation_id: crate::utils::correlation::current_or_new(),
+                category: crate::utils::error::ErrorCategory::Validation,
+                retry_count: 0,
+            });
+        }
+
+        // Validate resource governor budget
+        let governor = &self.resource_governor;
+        if governor.cpu_budget_percent <= 0.0
+            || governor.memory_budget_percent <= 0.0
+            || governor.max_permits == 0
+            || !(0.0..=1.0).contains(&governor.min_scale)
+        {
+            return Err(GuardianError::ValidationError {
+                context: "Resource governor needs positive budgets and permits and a min_scale within 0..=1".into(),
+                source: None,
+                severity: crate::utils::error::ErrorSeverity::Medium,
+                timestamp: time::OffsetDateTime::now_utc(),
+                correlation_id: crate::utils::correlation::current_or_new(),
+                category: crate::utils::error::ErrorCategory::Validation,
+                retry_count: 0,
+            });
+        }
+
+        // Validate client quotas
+        let quotas = &self.client_quotas;
+        let limits = std::iter::once(&quotas.default_limit).chain(quotas.role_limits.values());
+        for limit in limits {
+            if limit.requests_per_second == 0 || limit.burst == 0 {
+                return Err(GuardianError::ValidationError {
+                    context: "Client quotas need a positive rate and burst".into(),
+                    source: None,
+                    severity: crate::utils::error::ErrorSeverity::Medium,
+                    timestamp: time::OffsetDateTime::now_utc(),
+                    correlation_id: crate::utils::correlation::current_or_new(),
+                    category: crate::utils::error::ErrorCategory::Validation,
+                    retry_count: 0,
+                });
+            }
+        }
+
+        // Validate response guardrails
+        if self.response_guardrails.window.is_zero() {
+            return Err(GuardianError::ValidationError {
+                context: "Response guardrails need a non-zero window".into(),
+                source: None,
+                severity: crate::utils::error::ErrorSeverity::Medium,
+                timestamp: time::OffsetDateTime::now_utc(),
+                correlation_id: crate::utils::correlation::current_or_new(),
+                category: crate::utils::error::ErrorCategory::Validation,
+                retry_count: 0,
+            });
+        }
+
+        // Validate process trust
+        if self.process_trust.trusted_after < self.process_trust.observation_period {
+            return Err(GuardianError::ValidationError {
+                context: "Process trust needs trusted_after to be at least observation_period".into(),
+                source: None,
+                severity: crate::utils::error::ErrorSeverity::Medium,
+                timestamp: time::OffsetDateTime::now_utc(),
+                correlation_id: crate::utils::correlation::current_or_new(),
+                category: crate::utils::error::ErrorCategory::Validation,
+                retry_count: 0,
+            });
+        }
+
+        // Validate dead letter retries
+        if self.dead_letters.initial_backoff.is_zero() || self.dead_letters.initial_backoff > self.dead_letters.max_backoff {
+            return Err(GuardianError::ValidationError {
+                context: "Dead letters need a non-zero initial_backoff no larger than max_backoff".into(),
+                source: None,
+                severity: crate::utils::error::ErrorSeverity::Medium,
+                timestamp: time::OffsetDateTime::now_utc(),
+                correlation_id: crate::utils::correlation::current_or_new(),
+                category: crate::utils::error::ErrorCategory::Validation,
+                retry_count: 0,
+            });
+        }
+
+        // Validate threat intelligence feeds
+        let feeds = &self.threat_intel.feeds;
+        let invalid_feed = feeds.iter().enumerate().find(|(i, feed)| {
+            feed.name.is_empty()
+                || feed.url.is_empty()
+                || !(0.0..=1.0).contains(&feed.confidence)
+                || feeds[..*i].iter().any(|other| other.name == feed.name)
+        });
+        if self.threat_intel.refresh_interval.is_zero() || invalid_feed.is_some() {
+            return Err(GuardianError::ValidationError {
+                context: "Threat intel needs a non-zero refresh_interval and uniquely named feeds with a URL and a confidence in 0..=1".into(),
+                source: None,
+                severity: crate::utils::error::ErrorSeverity::Medium,
+                timestamp: time::OffsetDateTime::now_utc(),
+                correlation_id: crate::utils::correlation::current_or_new(),
+                category: crate::utils::error::ErrorCategory::Validation,
+                retry_count: 0,
+            });
+        }
+
+        // Validate the detection rule sandbox; a rule needs at least one 64 KiB WebAssembly page
+        let rules = &self.detection_rules;
+        if rules.enabled && (rules.fuel_per_sample == 0 || rules.max_memory_bytes < 64 * 1024) {
+            return Err(GuardianError::ValidationError {
+                context: "Detection rules need a positive fuel_per_sample and max_memory_bytes of at least 65536".into(),
+                source: None,
+                severity: crate::utils::error::ErrorSeverity::Medium,
+                timestamp: time::OffsetDateTime::now_utc(),
+                correlation_id: crate::utils::correlation::current_or_new(),
+                category: crate::utils::error::ErrorCategory::Validation,
+                retry_count: 0,
+            });
+        }
+
+        // Validate the degradation ladder; levels must be distinct and in increasing order
+        let degradation = &self.degradation;
+        if degradation.breach_cycles == 0
+            || degradation.recovery_cycles == 0
+            || !(0.0..1.0).contains(&degradation.recovery_ratio)
+            || degradation.sampling_interval_factor < 1.0
+            || degradation.ladder.contains(&DegradationLevel::Normal)
+            || degradation.ladder.windows(2).any(|pair| pair[0] >= pair[1])
+        {
+            return Err(GuardianError::ValidationError {
+                context: "Degradation needs non-zero breach and recovery cycles, a recovery_ratio below 1, a sampling_interval_factor of at least 1 and an increasing ladder without normal".into(),
+                source: None,
+                severity: crate::utils::error::ErrorSeverity::Medium,
+                timestamp: time::OffsetDateTime::now_utc(),
+                correlation_id: crate::utils::correlation::current_or_new(),
+                category: crate::utils::error::ErrorCategory::Validation,
+                retry_count: 0,
+            });
+        }
+
+        // Validate system data collectors
+        let collectors = &self.collectors;
+        let shared_memory = &collectors.shared_memory;
+        let invalid_collector = [
+            &collectors.processes,
+            &collectors.network,
+            &collectors.open_files,
+            &collectors.kernel,
+            &shared_memory.collector,
+        ]
+        .into_iter()
+        .any(|c| c.interval.is_zero() || c.timeout.is_zero() || c.timeout > c.interval || c.max_records == 0)
+            || shared_memory.slots == 0
+            || !(shared_memory.high_watermark > 0.0 && shared_memory.high_watermark <= 1.0);
+        if invalid_collector {
+            return Err(GuardianError::ValidationError {
+                context: "Collectors need a non-zero interval, a timeout within it and a positive max_records; the shared-memory ring needs slots and a high watermark in (0, 1]".into(),
+                source: None,
+                severity: crate::utils::error::ErrorSeverity::Medium,
+                timestamp: time::OffsetDateTime::now_utc(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: crate::utils::error::ErrorCategory::Validation,
                 retry_count: 0,
             });
@@ -277,6 +951,17 @@ mod tests {
         assert!(config.validate().is_err());
     }
 
+    #[test]
+    fn test_invalid_cpuset() {
+        let mut config = AppConfig::new(None);
+        config.cpu_affinity.inference_cpuset = Some("4-2".into());
+        assert!(config.validate().is_err());
+
+        config.cpu_affinity.inference_cpuset = Some("4-7".into());
+        config.cpu_affinity.io_cpuset = Some("0,1".into());
+        assert!(config.validate().is_ok());
+    }
+
     #[test]
     fn test_config_reload() {
         let dir = tempdir().unwrap();
@@ -0,0 +1,188 @@
+//! Setting-level differences between configurations
+//!
+//! Configurations are compared as the daemon runs them, with the environment profile's
+//! overrides applied, and every setting flattened to its dotted path. A diff also records
+//! whether the proposed configuration passes validation, cross-component checks included,
+//! and which of its changes only take effect after a restart. That is what `config diff` and
+//! the dry runs of `config set` and `config reload` print, and what the UI renders before an
+//! operator confirms a change.
+
+use std::{collections::BTreeMap, path::Path};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::config::watcher::{ConfigChangeOutcome, HOT_RELOADABLE_SECTIONS};
+use crate::config::AppConfig;
+use crate::utils::error::GuardianError;
+
+/// One setting whose effective value differs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SettingChange {
+    /// Dotted path, as taken by `config get` and `config set`
+    pub path: String,
+    /// Absent when the proposed configuration adds the setting
+    pub before: Option<Value>,
+    /// Absent when the proposed configuration drops the setting
+    pub after: Option<Value>,
+    /// Whether the change applies without a restart
+    pub hot_reloadable: bool,
+}
+
+/// Effective settings a proposed configuration changes, and whether it can be applied
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ConfigDiff {
+    pub changes: Vec<SettingChange>,
+    /// Why the proposed configuration fails validation
+    pub validation_error: Option<String>,
+}
+
+impl ConfigDiff {
+    /// Compares the settings of two effective configurations and validates the proposed one
+    pub fn between(current: &AppConfig, proposed: &AppConfig) -> Result<Self, GuardianError> {
+        let (before, mut after) = (flatten(current)?, flatten(proposed)?);
+        let mut changes = Vec::new();
+        for (path, before) in before {
+            match after.remove(&path) {
+                Some(after) if after == before => {}
+                after => changes.push(SettingChange::new(path, Some(before), after)),
+            }
+        }
+        changes.extend(after.into_iter().map(|(path, after)| SettingChange::new(path, None, Some(after))));
+        changes.sort_by(|a, b| a.path.cmp(&b.path));
+
+        Ok(Self {
+            changes,
+            validation_error: proposed.validate().err().map(|e| e.to_string()),
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Top-level sections with at least one change
+    pub fn sections(&self) -> Vec<String> {
+        let mut sections: Vec<String> = self.changes.iter().map(|change| section_of(&change.path).to_string()).collect();
+        sections.dedup();
+        sections
+    }
+
+    /// Changed sections that only take effect after a restart
+    pub fn restart_sections(&self) -> Vec<String> {
+        self.sections()
+            .into_iter()
+            .filter(|section| !HOT_RELOADABLE_SECTIONS.contains(&section.as_str()))
+            .collect()
+    }
+
+    /// What reloading the proposed configuration would do
+    pub fn outcome(&self) -> ConfigChangeOutcome {
+        if self.is_empty() {
+            ConfigChangeOutcome::Unchanged
+        } else if self.validation_error.is_some() {
+            ConfigChangeOutcome::Invalid
+        } else if !self.restart_sections().is_empty() {
+            ConfigChangeOutcome::RestartRequired
+        } else {
+            ConfigChangeOutcome::Applied
+        }
+    }
+}
+
+impl SettingChange {
+    fn new(path: String, before: Option<Value>, after: Option<Value>) -> Self {
+        let hot_reloadable = HOT_RELOADABLE_SECTIONS.contains(&section_of(&path));
+        Self { path, before, after, hot_reloadable }
+    }
+}
+
+/// Reads a configuration file as the daemon would run it, without rejecting invalid settings
+pub fn load_effective(path: &Path) -> Result<AppConfig, GuardianError> {
+    let mut config = AppConfig::read(path.to_path_buf())?;
+    crate::config::profile::active_profile().apply(&mut config);
+    Ok(config)
+}
+
+/// Copy of `config` with the setting at a dotted path replaced
+///
+/// The value is parsed as JSON, so numbers, booleans and lists keep their type; anything else
+/// is taken as a string.
+pub fn with_setting(config: &AppConfig, path: &str, value: &str) -> Result<AppConfig, GuardianError> {
+    let mut root = serde_json::to_value(config)?;
+    let mut target = &mut root;
+    for key in path.split('.') {
+        target = target
+            .as_object_mut()
+            .and_then(|object| object.get_mut(key))
+            .ok_or_else(|| invalid(format!("Unknown setting: {}", path)))?;
+    }
+    *target = serde_json::from_str(value).unwrap_or_else(|_| Value::String(value.to_string()));
+    serde_json::from_value(root).map_err(|e| invalid(format!("Invalid value for {}: {}", path, e)))
+}
+
+/// Leaf settings by dotted path; lists are compared as a whole
+fn flatten(config: &AppConfig) -> Result<BTreeMap<String, Value>, GuardianError> {
+    fn walk(prefix: &str, value: Value, settings: &mut BTreeMap<String, Value>) {
+        match value {
+            Value::Object(object) if !object.is_empty() => {
+                for (key, value) in object {
+                    let path = if prefix.is_empty() { key } else { format!("{}.{}", prefix, key) };
+                    walk(&path, value, settings);
+                }
+            }
+            value => {
+                settings.insert(prefix.to_string(), value);
+            }
+        }
+    }
+
+    let mut settings = BTreeMap::new();
+    walk("", serde_json::to_value(config)?, &mut settings);
+    Ok(settings)
+}
+
+fn section_of(path: &str) -> &str {
+    path.split('.').next().unwrap_or(path)
+}
+
+fn invalid(context: String) -> GuardianError {
+    GuardianError::ValidationError {
+        context,
+        source: None,
+        severity: crate::utils::error::ErrorSeverity::Low,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: crate::utils::error::ErrorCategory::Validation,
+        retry_count: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_lists_changed_settings_and_outcome() {
+        let current = AppConfig::new(None);
+        assert_eq!(ConfigDiff::between(&current, &current).unwrap().outcome(), ConfigChangeOutcome::Unchanged);
+
+        let attempts = current.dead_letters.max_attempts + 1;
+        let hot = with_setting(&current, "dead_letters.max_attempts", &attempts.to_string()).unwrap();
+        let diff = ConfigDiff::between(&current, &hot).unwrap();
+        assert_eq!(diff.changes.len(), 1);
+        assert_eq!(diff.changes[0].path, "dead_letters.max_attempts");
+        assert_eq!(diff.changes[0].after, Some(Value::from(attempts)));
+        assert!(diff.changes[0].hot_reloadable);
+        assert_eq!(diff.outcome(), ConfigChangeOutcome::Applied);
+
+        let cold = with_setting(&hot, "max_threads", "0").unwrap();
+        let diff = ConfigDiff::between(&current, &cold).unwrap();
+        assert_eq!(diff.sections(), vec!["dead_letters".to_string(), "max_threads".to_string()]);
+        assert_eq!(diff.restart_sections(), vec!["max_threads".to_string()]);
+        assert!(diff.validation_error.is_some());
+        assert_eq!(diff.outcome(), ConfigChangeOutcome::Invalid);
+
+        assert!(with_setting(&current, "dead_letters.no_such_setting", "1").is_err());
+    }
+}
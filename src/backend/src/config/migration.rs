@@ -0,0 +1,315 @@
+use std::{
+    cmp::Ordering,
+    path::{Path, PathBuf},
+};
+
+use metrics::counter;
+use serde::Serialize;
+use serde_json::Value;
+use tracing::{info, instrument, warn};
+
+use crate::utils::error::{ErrorCategory, GuardianError};
+
+// Constants for config migration
+/// Version assumed for files written before configs carried one
+pub const LEGACY_VERSION: &str = "0.9.0";
+const CONFIG_EXTENSIONS: &[&str] = &["toml", "yaml", "yml", "json"];
+const BACKUP_SUFFIX: &str = "bak";
+
+/// A versioned configuration file in the config directory
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigFile {
+    App,
+    Security,
+    Ml,
+}
+
+impl ConfigFile {
+    pub const ALL: [ConfigFile; 3] = [ConfigFile::App, ConfigFile::Security, ConfigFile::Ml];
+
+    /// File name without extension
+    pub fn stem(&self) -> &'static str {
+        match self {
+            ConfigFile::App => "app",
+            ConfigFile::Security => "security",
+            ConfigFile::Ml => "ml",
+        }
+    }
+
+    /// Top-level key holding the schema version
+    pub fn version_key(&self) -> &'static str {
+        match self {
+            ConfigFile::App | ConfigFile::Security => "version",
+            ConfigFile::Ml => "config_version",
+        }
+    }
+
+    /// Schema version this build reads
+    pub fn current_version(&self) -> &'static str {
+        match self {
+            ConfigFile::App => super::app_config::CONFIG_VERSION,
+            ConfigFile::Security => super::security_config::CONFIG_VERSION,
+            ConfigFile::Ml => super::ml_config::CONFIG_VERSION,
+        }
+    }
+}
+
+/// Upgrades one config file from one schema version to the next
+#[derive(Debug, Clone)]
+pub struct MigrationStep {
+    pub file: ConfigFile,
+    pub from: &'static str,
+    pub to: &'static str,
+    pub description: &'static str,
+    /// Rewrites the document in place; the version key is updated by the migrator
+    pub apply: fn(&mut Value) -> Result<(), String>,
+}
+
+/// What migrating a file changed
+#[derive(Debug, Clone, Serialize)]
+pub struct MigrationReport {
+    pub path: PathBuf,
+    pub file: ConfigFile,
+    pub from: String,
+    pub to: String,
+    pub applied: Vec<String>,
+    /// Copy of the file as it was before migration
+    pub backup: PathBuf,
+}
+
+/// Upgrades config files written by older releases through registered migration steps
+#[derive(Debug, Clone)]
+pub struct ConfigMigrator {
+    steps: Vec<MigrationStep>,
+}
+
+impl Default for ConfigMigrator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConfigMigrator {
+    /// Creates a migrator with the built-in steps
+    pub fn new() -> Self {
+        let mut migrator = Self { steps: Vec::new() };
+        for file in ConfigFile::ALL {
+            migrator.register(MigrationStep {
+                file,
+                from: LEGACY_VERSION,
+                to: "1.0.0",
+                description: "stamp the schema version on files written before versioning",
+                apply: |_| Ok(()),
+            });
+        }
+        migrator
+    }
+
+    /// Adds a step; releases changing a config schema register one per version bump
+    pub fn register(&mut self, step: MigrationStep) {
+        self.steps.push(step);
+    }
+
+    /// Migrates every config file present in a directory, in any supported format
+    #[instrument(skip(self))]
+    pub fn migrate_dir(&self, dir: &Path) -> Result<Vec<MigrationReport>, GuardianError> {
+        let mut reports = Vec::new();
+        for file in ConfigFile::ALL {
+            for extension in CONFIG_EXTENSIONS {
+                let path = dir.join(format!("{}.{}", file.stem(), extension));
+                if path.is_file() {
+                    reports.extend(self.migrate_file(&path, file)?);
+                }
+            }
+        }
+        Ok(reports)
+    }
+
+    /// Migrates one file, backing it up and writing the upgraded document back in the same format
+    ///
+    /// Returns `None` when the file is already current. Fails, leaving the file untouched, when it
+    /// is newer than this build or no chain of steps reaches the current version.
+    pub fn migrate_file(&self, path: &Path, file: ConfigFile) -> Result<Option<MigrationReport>, GuardianError> {
+        let format = Format::of(path)?;
+        let raw = std::fs::read_to_string(path).map_err(|e| io_error(path, e))?;
+        let mut document = format.parse(&raw).map_err(|e| migration_error(path, &e))?;
+
+        let from = document_version(&document, file);
+        let applied = self
+            .migrate_document(file, &mut document, file.current_version())
+            .map_err(|e| migration_error(path, &e))?;
+        if applied.is_empty() {
+            return Ok(None);
+        }
+
+        let backup = backup_path(path, &from);
+        std::fs::copy(path, &backup).map_err(|e| io_error(&backup, e))?;
+        let upgraded = format.render(&document).map_err(|e| migration_error(path, &e))?;
+        let temporary = path.with_extension("migrating");
+        std::fs::write(&temporary, upgraded).map_err(|e| io_error(&temporary, e))?;
+        std::fs::rename(&temporary, path).map_err(|e| io_error(path, e))?;
+
+        counter!("guardian.config.migrations", applied.len() as u64, "file" => file.stem());
+        info!(
+            path = %path.display(),
+            from = %from,
+            to = file.current_version(),
+            backup = %backup.display(),
+            "Configuration migrated"
+        );
+        Ok(Some(MigrationReport {
+            path: path.to_path_buf(),
+            file,
+            from,
+            to: file.current_version().to_string(),
+            applied,
+            backup,
+        }))
+    }
+
+    /// Applies steps until the document reaches `target`, returning their descriptions
+    fn migrate_document(&self, file: ConfigFile, document: &mut Value, target: &str) -> Result<Vec<String>, String> {
+        let mut applied = Vec::new();
+        loop {
+            let version = document_version(document, file);
+            match compare_versions(&version, target)? {
+                Ordering::Equal => return Ok(applied),
+                Ordering::Greater => {
+                    return Err(format!("version {} is newer than {} supported by this release", version, target))
+                }
+                Ordering::Less => {}
+            }
+
+            let step = self
+                .steps
+                .iter()
+                .find(|step| step.file == file && step.from == version)
+                .ok_or_else(|| format!("no migration from version {} towards {}", version, target))?;
+            if compare_versions(step.to, &version)? != Ordering::Greater {
+                return Err(format!("migration from {} does not advance the version", version));
+            }
+
+            (step.apply)(document).map_err(|e| format!("{} -> {} failed: {}", step.from, step.to, e))?;
+            let fields = document
+                .as_object_mut()
+                .ok_or_else(|| "configuration is not a table of settings".to_string())?;
+            fields.insert(file.version_key().into(), Value::String(step.to.into()));
+            applied.push(format!("{} -> {}: {}", step.from, step.to, step.description));
+        }
+    }
+}
+
+/// Serialization format of a config file, chosen by extension
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Format {
+    Toml,
+    Yaml,
+    Json,
+}
+
+impl Format {
+    pub(crate) fn of(path: &Path) -> Result<Self, GuardianError> {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("toml") => Ok(Format::Toml),
+            Some("yaml") | Some("yml") => Ok(Format::Yaml),
+            Some("json") => Ok(Format::Json),
+            _ => Err(migration_error(path, "unsupported configuration format")),
+        }
+    }
+
+    pub(crate) fn parse(&self, raw: &str) -> Result<Value, String> {
+        match self {
+            Format::Toml => toml::from_str(raw).map_err(|e| e.to_string()),
+            Format::Yaml => serde_yaml::from_str(raw).map_err(|e| e.to_string()),
+            Format::Json => serde_json::from_str(raw).map_err(|e| e.to_string()),
+        }
+    }
+
+    pub(crate) fn render(&self, document: &Value) -> Result<String, String> {
+        match self {
+            Format::Toml => toml::to_string_pretty(document).map_err(|e| e.to_string()),
+            Format::Yaml => serde_yaml::to_string(document).map_err(|e| e.to_string()),
+            Format::Json => serde_json::to_string_pretty(document).map_err(|e| e.to_string()),
+        }
+    }
+}
+
+fn document_version(document: &Value, file: ConfigFile) -> String {
+    document
+        .get(file.version_key())
+        .and_then(Value::as_str)
+        .unwrap_or(LEGACY_VERSION)
+        .to_string()
+}
+
+fn compare_versions(a: &str, b: &str) -> Result<Ordering, String> {
+    Ok(parse_version(a)?.cmp(&parse_version(b)?))
+}
+
+fn parse_version(version: &str) -> Result<Vec<u64>, String> {
+    version
+        .split('.')
+        .map(|part| part.parse::<u64>().map_err(|_| format!("invalid version {}", version)))
+        .collect()
+}
+
+/// `app.toml` at 0.9.0 is backed up as `app.toml.0.9.0.bak`, kept alongside the original
+fn backup_path(path: &Path, version: &str) -> PathBuf {
+    let mut backup = path.as_os_str().to_owned();
+    backup.push(format!(".{}.{}", version, BACKUP_SUFFIX));
+    PathBuf::from(backup)
+}
+
+fn io_error(path: &Path, e: std::io::Error) -> GuardianError {
+    GuardianError::SystemError {
+        context: format!("Config migration I/O failed on {}", path.display()),
+        source: Some(Box::new(e)),
+        severity: crate::utils::error::ErrorSeverity::High,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: ErrorCategory::System,
+        retry_count: 0,
+    }
+}
+
+fn migration_error(path: &Path, reason: &str) -> GuardianError {
+    warn!(path = %path.display(), reason, "Configuration cannot be migrated");
+    GuardianError::ValidationError {
+        context: format!("Cannot migrate {}: {}", path.display(), reason),
+        source: None,
+        severity: crate::utils::error::ErrorSeverity::Critical,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: ErrorCategory::Validation,
+        retry_count: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migrates_legacy_files_with_backup_and_rejects_unknown_versions() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ml.yaml");
+        std::fs::write(&path, "max_batch_size: 64\n").unwrap();
+
+        let migrator = ConfigMigrator::new();
+        let report = migrator.migrate_file(&path, ConfigFile::Ml).unwrap().unwrap();
+        assert_eq!(report.from, LEGACY_VERSION);
+        assert_eq!(report.applied.len(), 1);
+        assert_eq!(std::fs::read_to_string(&report.backup).unwrap(), "max_batch_size: 64\n");
+
+        let upgraded: Value = serde_yaml::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(upgraded["config_version"], ConfigFile::Ml.current_version());
+        assert_eq!(upgraded["max_batch_size"], 64);
+        assert!(migrator.migrate_file(&path, ConfigFile::Ml).unwrap().is_none());
+
+        let mut newer = serde_json::json!({ "version": "9.0.0" });
+        assert!(migrator.migrate_document(ConfigFile::App, &mut newer, "1.0.0").is_err());
+        let mut orphaned = serde_json::json!({ "version": "0.5.0" });
+        assert!(migrator.migrate_document(ConfigFile::App, &mut orphaned, "1.0.0").is_err());
+    }
+}
@@ -11,7 +11,19 @@ const DEFAULT_MODEL_TIMEOUT_MS: u64 = 1000;
 const DEFAULT_MAX_BATCH_SIZE: usize = 32;
 const DEFAULT_FEATURE_CACHE_SIZE: usize = 10000;
 const DEFAULT_MODEL_VERSION_RETENTION: u32 = 3;
-const CONFIG_VERSION: &str = "1.0.0";
+pub(crate) const CONFIG_VERSION: &str = "1.0.0";
+const DEFAULT_BATCH_WAIT_MS: u64 = 5;
+/// Longest batching delay, a quarter of the 100ms inference latency SLA
+const MAX_BATCH_WAIT_MS: u64 = 25;
+const DEFAULT_GPU_DEVICE_FRACTION: f64 = 0.8;
+const DEFAULT_RETRAINING_INTERVAL_SECS: u64 = 24 * 3600;
+const DEFAULT_RETRAINING_LOOKBACK_SECS: u64 = 30 * 24 * 3600;
+const DEFAULT_WARMUP_BUDGET_SECS: u64 = 30;
+const DEFAULT_WARMUP_TARGET_LATENCY_MS: u64 = 100;
+const DEFAULT_TRAINING_STAGING_DIR: &str = "/var/lib/guardian/training";
+/// Smallest part S3 accepts in a multipart upload, other than the last
+const MIN_UPLOAD_PART_MB: u64 = 5;
+const DEFAULT_UPLOAD_PART_MB: u64 = 16;
 
 /// Resource limits for ML training and inference
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -33,6 +45,166 @@ impl Default for ResourceLimits {
     }
 }
 
+/// Micro-batching of concurrent single-event inference requests
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BatchingConfig {
+    pub enabled: bool,
+    /// Requests run in one forward pass at most
+    pub max_batch_size: usize,
+    /// Longest a request waits for others to join its batch
+    pub max_wait_ms: u64,
+}
+
+impl Default for BatchingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_batch_size: DEFAULT_MAX_BATCH_SIZE,
+            max_wait_ms: DEFAULT_BATCH_WAIT_MS,
+        }
+    }
+}
+
+/// GPU memory that resident inference models may occupy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GpuBudgetConfig {
+    pub enabled: bool,
+    /// Share of the device's total memory available to models
+    pub max_device_fraction: f64,
+    /// Hard cap on model residency, applied on top of the device share
+    pub max_resident_mb: Option<usize>,
+}
+
+impl Default for GpuBudgetConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_device_fraction: DEFAULT_GPU_DEVICE_FRACTION,
+            max_resident_mb: None,
+        }
+    }
+}
+
+/// How model binaries are brought into memory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ModelLoadingConfig {
+    /// Map model files instead of reading them into the heap
+    pub mmap: bool,
+    /// Page a version's binary in before its warm-up, so the first requests it serves do not fault
+    pub prefetch_on_activate: bool,
+    /// Synthetic inferences run at startup before inference traffic is accepted
+    pub warmup: WarmupConfig,
+}
+
+impl Default for ModelLoadingConfig {
+    fn default() -> Self {
+        Self {
+            mmap: true,
+            prefetch_on_activate: false,
+            warmup: WarmupConfig::default(),
+        }
+    }
+}
+
+/// Cold-start warm-up; the instance reports not ready for inference until it completes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WarmupConfig {
+    pub enabled: bool,
+    /// Longest the warm-up may take; the instance becomes ready when it runs out
+    pub budget: std::time::Duration,
+    pub min_inferences: usize,
+    pub max_inferences: usize,
+    /// Latency the recent warm-up inferences must average to end the warm-up early
+    pub target_latency: std::time::Duration,
+}
+
+impl Default for WarmupConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            budget: std::time::Duration::from_secs(DEFAULT_WARMUP_BUDGET_SECS),
+            min_inferences: 8,
+            max_inferences: 256,
+            target_latency: std::time::Duration::from_millis(DEFAULT_WARMUP_TARGET_LATENCY_MS),
+        }
+    }
+}
+
+/// Scheduled retraining from analyst-labeled events; runs only when `training_enabled` is set
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetrainingConfig {
+    /// Time between training runs started by the maintenance workflow
+    pub interval: std::time::Duration,
+    /// How far back labeled events are exported
+    pub lookback: std::time::Duration,
+    /// Labeled samples required before a run trains at all
+    pub min_samples: usize,
+    /// Newest share of the samples held out for validation
+    pub holdout_fraction: f64,
+    pub epochs: usize,
+    pub learning_rate: f64,
+    /// Held-out accuracy a candidate needs to be registered
+    pub min_holdout_accuracy: f64,
+    /// Where exported datasets and candidate models are staged between activities
+    pub staging_dir: String,
+    /// PKCS#8 Ed25519 key that signs trained models; its public key must be a trusted model signer
+    pub signing_key_path: Option<String>,
+    pub tenant_id: String,
+}
+
+impl Default for RetrainingConfig {
+    fn default() -> Self {
+        Self {
+            interval: std::time::Duration::from_secs(DEFAULT_RETRAINING_INTERVAL_SECS),
+            lookback: std::time::Duration::from_secs(DEFAULT_RETRAINING_LOOKBACK_SECS),
+            min_samples: 500,
+            holdout_fraction: 0.2,
+            epochs: 200,
+            learning_rate: 0.05,
+            min_holdout_accuracy: 0.9,
+            staging_dir: DEFAULT_TRAINING_STAGING_DIR.to_string(),
+            signing_key_path: None,
+            tenant_id: "default".to_string(),
+        }
+    }
+}
+
+/// S3-compatible bucket model versions are published to and fetched from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ModelRemoteConfig {
+    /// Scheme and host of the S3 API; the bucket is addressed path-style under it
+    pub endpoint: String,
+    pub bucket: String,
+    pub region: String,
+    /// Key prefix the versions are kept under
+    pub prefix: String,
+    pub access_key_id: String,
+    /// May be an encrypted `enc:v1` value
+    pub secret_access_key: String,
+    /// Part size of multipart uploads; S3 requires at least 5 MiB
+    pub part_size_mb: u64,
+}
+
+impl Default for ModelRemoteConfig {
+    fn default() -> Self {
+        Self {
+            endpoint: String::new(),
+            bucket: String::new(),
+            region: "us-east-1".to_string(),
+            prefix: "models".to_string(),
+            access_key_id: String::new(),
+            secret_access_key: String::new(),
+            part_size_mb: DEFAULT_UPLOAD_PART_MB,
+        }
+    }
+}
+
 /// Configuration structure for the ML subsystem
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MLConfig {
@@ -46,6 +218,17 @@ pub struct MLConfig {
     pub inference_gpu_enabled: bool,
     pub config_version: String,
     pub training_resource_limits: ResourceLimits,
+    #[serde(default)]
+    pub batching: BatchingConfig,
+    #[serde(default)]
+    pub gpu_budget: GpuBudgetConfig,
+    #[serde(default)]
+    pub retraining: RetrainingConfig,
+    #[serde(default)]
+    pub loading: ModelLoadingConfig,
+    /// Without a remote, versions live only in the local registry
+    #[serde(default)]
+    pub remote: Option<ModelRemoteConfig>,
 }
 
 impl Default for MLConfig {
@@ -61,6 +244,11 @@ impl Default for MLConfig {
             inference_gpu_enabled: false,
             config_version: CONFIG_VERSION.to_string(),
             training_resource_limits: ResourceLimits::default(),
+            batching: BatchingConfig::default(),
+            gpu_budget: GpuBudgetConfig::default(),
+            retraining: RetrainingConfig::default(),
+            loading: ModelLoadingConfig::default(),
+            remote: None,
         }
     }
 }
@@ -82,7 +270,7 @@ impl MLConfig {
             source: Some(Box::new(e)),
             severity: ErrorSeverity::High,
             timestamp: OffsetDateTime::now_utc(),
-            correlation_id: Uuid::new_v4(),
+            correlation_id: crate::utils::correlation::current_or_new(),
             category: ErrorCategory::Validation,
             retry_count: 0,
         })?;
@@ -92,7 +280,7 @@ impl MLConfig {
             source: Some(Box::new(e)),
             severity: ErrorSeverity::High,
             timestamp: OffsetDateTime::now_utc(),
-            correlation_id: Uuid::new_v4(),
+            correlation_id: crate::utils::correlation::current_or_new(),
             category: ErrorCategory::Validation,
             retry_count: 0,
         })?;
@@ -114,7 +302,7 @@ impl MLConfig {
                 source: None,
                 severity: ErrorSeverity::High,
                 timestamp: OffsetDateTime::now_utc(),
-                correlation_id: Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: ErrorCategory::Validation,
                 retry_count: 0,
             });
@@ -127,7 +315,7 @@ impl MLConfig {
                 source: None,
                 severity: ErrorSeverity::High,
                 timestamp: OffsetDateTime::now_utc(),
-                correlation_id: Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: ErrorCategory::Validation,
                 retry_count: 0,
             });
@@ -140,7 +328,7 @@ impl MLConfig {
                 source: None,
                 severity: ErrorSeverity::High,
                 timestamp: OffsetDateTime::now_utc(),
-                correlation_id: Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: ErrorCategory::Validation,
                 retry_count: 0,
             });
@@ -153,12 +341,110 @@ impl MLConfig {
                 source: None,
                 severity: ErrorSeverity::High,
                 timestamp: OffsetDateTime::now_utc(),
-                correlation_id: Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: ErrorCategory::Validation,
                 retry_count: 0,
             });
         }
 
+        // Validate request batching
+        if self.batching.max_batch_size == 0
+            || self.batching.max_batch_size > 128
+            || self.batching.max_wait_ms > MAX_BATCH_WAIT_MS
+        {
+            return Err(GuardianError::ConfigError {
+                context: format!(
+                    "Invalid batching: size must be 1-128 and wait at most {}ms, got {} and {}ms",
+                    MAX_BATCH_WAIT_MS, self.batching.max_batch_size, self.batching.max_wait_ms
+                ),
+                source: None,
+                severity: ErrorSeverity::High,
+                timestamp: OffsetDateTime::now_utc(),
+                correlation_id: crate::utils::correlation::current_or_new(),
+                category: ErrorCategory::Validation,
+                retry_count: 0,
+            });
+        }
+
+        // Validate model warm-up
+        let warmup = &self.loading.warmup;
+        if warmup.enabled
+            && (warmup.budget.is_zero() || warmup.min_inferences == 0 || warmup.min_inferences > warmup.max_inferences)
+        {
+            return Err(GuardianError::ConfigError {
+                context: format!(
+                    "Invalid warm-up: budget must be non-zero and 1 <= min_inferences <= max_inferences, got {:?}, {} and {}",
+                    warmup.budget, warmup.min_inferences, warmup.max_inferences
+                ),
+                source: None,
+                severity: ErrorSeverity::High,
+                timestamp: OffsetDateTime::now_utc(),
+                correlation_id: crate::utils::correlation::current_or_new(),
+                category: ErrorCategory::Validation,
+                retry_count: 0,
+            });
+        }
+
+        // Validate GPU memory budget
+        if !(self.gpu_budget.max_device_fraction > 0.0 && self.gpu_budget.max_device_fraction <= 1.0)
+            || self.gpu_budget.max_resident_mb == Some(0)
+        {
+            return Err(GuardianError::ConfigError {
+                context: format!(
+                    "Invalid GPU budget: device fraction must be in (0, 1] and the resident cap above zero, got {} and {:?}MB",
+                    self.gpu_budget.max_device_fraction, self.gpu_budget.max_resident_mb
+                ),
+                source: None,
+                severity: ErrorSeverity::High,
+                timestamp: OffsetDateTime::now_utc(),
+                correlation_id: crate::utils::correlation::current_or_new(),
+                category: ErrorCategory::Validation,
+                retry_count: 0,
+            });
+        }
+
+        // Validate retraining
+        let retraining = &self.retraining;
+        if !(0.0..0.5).contains(&retraining.holdout_fraction)
+            || retraining.holdout_fraction == 0.0
+            || retraining.epochs == 0
+            || retraining.learning_rate <= 0.0
+            || !(0.0..=1.0).contains(&retraining.min_holdout_accuracy)
+        {
+            return Err(GuardianError::ConfigError {
+                context: "Retraining needs a holdout fraction in (0, 0.5), at least one epoch, a positive learning rate and an accuracy threshold in [0, 1]".to_string(),
+                source: None,
+                severity: ErrorSeverity::High,
+                timestamp: OffsetDateTime::now_utc(),
+                correlation_id: crate::utils::correlation::current_or_new(),
+                category: ErrorCategory::Validation,
+                retry_count: 0,
+            });
+        }
+
+        // Validate the remote model registry
+        if let Some(remote) = &self.remote {
+            let endpoint_ok = remote.endpoint.starts_with("https://") || remote.endpoint.starts_with("http://");
+            if !endpoint_ok
+                || remote.bucket.is_empty()
+                || remote.access_key_id.is_empty()
+                || remote.part_size_mb < MIN_UPLOAD_PART_MB
+            {
+                return Err(GuardianError::ConfigError {
+                    context: format!(
+                        "Remote model registry needs an http(s) endpoint, a bucket, credentials and parts of at least {}MB",
+                        MIN_UPLOAD_PART_MB
+                    ),
+                    source: None,
+                    severity: ErrorSeverity::High,
+                    timestamp: OffsetDateTime::now_utc(),
+                    correlation_id: crate::utils::correlation::current_or_new(),
+                    category: ErrorCategory::Validation,
+                    retry_count: 0,
+                });
+            }
+        }
+
         // Validate resource limits
         if self.training_resource_limits.max_cpu_percent > 90 {
             return Err(GuardianError::ConfigError {
@@ -166,7 +452,7 @@ impl MLConfig {
                 source: None,
                 severity: ErrorSeverity::High,
                 timestamp: OffsetDateTime::now_utc(),
-                correlation_id: Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: ErrorCategory::Validation,
                 retry_count: 0,
             });
@@ -179,7 +465,7 @@ impl MLConfig {
                 source: None,
                 severity: ErrorSeverity::High,
                 timestamp: OffsetDateTime::now_utc(),
-                correlation_id: Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: ErrorCategory::Validation,
                 retry_count: 0,
             });
@@ -214,4 +500,11 @@ mod tests {
         config.inference_threads = num_cpus::get() + 1; // Too high
         assert!(config.validate().is_err());
     }
+
+    #[test]
+    fn test_validate_batching_wait_within_sla() {
+        let mut config = MLConfig::new();
+        config.batching.max_wait_ms = MAX_BATCH_WAIT_MS + 1;
+        assert!(config.validate().is_err());
+    }
 }
\ No newline at end of file
@@ -13,18 +13,35 @@ mod app_config;
 mod security_config;
 mod ml_config;
 mod storage_config;
-
-pub use app_config::AppConfig;
+pub mod diff;
+pub mod migration;
+pub mod profile;
+pub mod secrets;
+pub mod watcher;
+
+pub use app_config::{
+    AppConfig, ClientQuotaConfig, CollectorConfig, CollectorsConfig, ConfigWatchConfig, DegradationConfig, DegradationLevel, DetectionRulesConfig, Environment, IntelFeedConfig, IntelFeedFormat, MonitoringConfig,
+    ProcessTrustConfig, QuotaLimit, ResponseGuardrailConfig, ResponseLimits, SharedMemoryConfig, ThreatIntelConfig,
+};
 pub use security_config::SecurityConfig;
-pub use ml_config::MLConfig;
+pub use ml_config::{MLConfig, ModelLoadingConfig, ModelRemoteConfig, WarmupConfig};
 pub use storage_config::StorageConfig;
+pub use diff::{ConfigDiff, SettingChange};
+pub use profile::{active_profile, EnforcementMode, EnvironmentProfile};
 
 // System-wide configuration constants
 const CONFIG_VERSION: &str = "1.0.0";
-const DEFAULT_CONFIG_PATH: &str = "/etc/guardian/config";
+/// Directory the daemon loads its configuration files from
+pub const DEFAULT_CONFIG_PATH: &str = "/etc/guardian/config";
+const SECURITY_CONFIG_FILE: &str = "security.toml";
 const MAX_RESOURCE_USAGE: f64 = 5.0;
 const BACKUP_RETENTION_DAYS: u32 = 30;
 
+/// Security configuration the loader reads from a configuration directory
+pub fn security_config_path(config_dir: &std::path::Path) -> PathBuf {
+    config_dir.join(SECURITY_CONFIG_FILE)
+}
+
 /// System resource monitoring configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SystemResources {
@@ -82,12 +99,26 @@ impl GuardianConfig {
             ));
         }
 
+        // Upgrade files written by older releases before parsing them
+        for report in migration::ConfigMigrator::new().migrate_dir(&config_path)? {
+            info!(path = %report.path.display(), from = %report.from, to = %report.to, "Upgraded configuration file");
+        }
+
         // Load individual components
         let app_config = AppConfig::new(Some(config_path.join("app.toml").to_string_lossy().to_string()), None)?;
-        let security_config = SecurityConfig::load_config(&config_path.join("security.toml"), None)?;
+        let security_config = SecurityConfig::load_config(&security_config_path(&config_path), None)?;
         let ml_config = MLConfig::load_config(config_path.join("ml.toml").to_string_lossy().to_string())?;
         let storage_config = StorageConfig::new()?;
 
+        // Open enc:v1 values; the hardware security section stays in clear since it locates the KEK
+        let secrets = secrets::ConfigSecrets::new(crate::security::key_provider::key_provider_from_config(
+            &security_config.hw_security_config,
+        )?);
+        let app_config = secrets.decrypt_config(&app_config).await?;
+        let security_config = secrets.decrypt_config(&security_config).await?;
+        let ml_config = secrets.decrypt_config(&ml_config).await?;
+        let storage_config = secrets.decrypt_config(&storage_config).await?;
+
         let config = Self {
             app_config,
             security_config,
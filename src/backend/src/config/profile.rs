@@ -0,0 +1,153 @@
+use once_cell::sync::OnceCell;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use super::app_config::{AppConfig, Environment, LogLevel};
+use crate::utils::error::GuardianError;
+
+/// How security decisions are applied in an environment
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EnforcementMode {
+    /// Decisions are logged and response actions are dry-run
+    Relaxed,
+    /// Decisions are logged and violations are denied
+    ObserveDeny,
+}
+
+/// Behavior bundle applied to every subsystem for a deployment environment
+#[derive(Debug, Clone, Serialize)]
+pub struct EnvironmentProfile {
+    pub environment: Environment,
+    pub enforcement: EnforcementMode,
+    pub log_level: LogLevel,
+    /// Tracing filter directive installed at startup
+    pub log_filter: &'static str,
+    pub require_encryption: bool,
+    pub require_mtls: bool,
+}
+
+static ACTIVE_PROFILE: OnceCell<EnvironmentProfile> = OnceCell::new();
+
+impl EnvironmentProfile {
+    /// Returns the built-in profile for an environment
+    pub fn for_environment(environment: Environment) -> Self {
+        match environment {
+            Environment::Development => Self {
+                environment,
+                enforcement: EnforcementMode::Relaxed,
+                log_level: LogLevel::Debug,
+                log_filter: "guardian=trace,debug",
+                require_encryption: false,
+                require_mtls: false,
+            },
+            Environment::Staging => Self {
+                environment,
+                enforcement: EnforcementMode::ObserveDeny,
+                log_level: LogLevel::Debug,
+                log_filter: "guardian=debug,info",
+                require_encryption: true,
+                require_mtls: false,
+            },
+            Environment::Production => Self {
+                environment,
+                enforcement: EnforcementMode::ObserveDeny,
+                log_level: LogLevel::Info,
+                log_filter: "guardian=info,warn",
+                require_encryption: true,
+                require_mtls: true,
+            },
+        }
+    }
+
+    /// Overrides configuration values the profile governs
+    pub fn apply(&self, config: &mut AppConfig) {
+        config.log_level = self.log_level.clone();
+        if self.environment == Environment::Production {
+            config.security_settings.enable_secure_boot = true;
+            config.security_settings.tpm_required = true;
+        }
+    }
+
+    /// Returns whether response actions are carried out rather than dry-run
+    pub fn enforces_responses(&self) -> bool {
+        self.enforcement == EnforcementMode::ObserveDeny
+    }
+
+    /// Rejects an unencrypted component where the profile requires encryption
+    pub fn require_encryption(&self, component: &str, encrypted: bool) -> Result<(), GuardianError> {
+        if self.require_encryption && !encrypted {
+            return Err(profile_violation(format!(
+                "{} must be encrypted in {:?}",
+                component, self.environment
+            )));
+        }
+        Ok(())
+    }
+
+    /// Rejects a listener without TLS client authentication where the profile requires mTLS
+    pub fn require_mtls(&self, component: &str, tls: bool, client_auth: bool) -> Result<(), GuardianError> {
+        self.require_encryption(component, tls)?;
+        if self.require_mtls && !client_auth {
+            return Err(profile_violation(format!(
+                "{} must require client certificates in {:?}",
+                component, self.environment
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Installs the process-wide profile; the first installation wins
+pub fn install(profile: EnvironmentProfile) -> &'static EnvironmentProfile {
+    let environment = profile.environment.clone();
+    let active = ACTIVE_PROFILE.get_or_init(|| profile);
+    if active.environment != environment {
+        warn!(active = ?active.environment, requested = ?environment, "Environment profile already installed");
+    } else {
+        info!(environment = ?active.environment, enforcement = ?active.enforcement, "Environment profile installed");
+    }
+    active
+}
+
+/// Returns the installed profile, falling back to the strictest one
+pub fn active_profile() -> &'static EnvironmentProfile {
+    static PRODUCTION: once_cell::sync::Lazy<EnvironmentProfile> =
+        once_cell::sync::Lazy::new(|| EnvironmentProfile::for_environment(Environment::Production));
+    ACTIVE_PROFILE.get().unwrap_or(&PRODUCTION)
+}
+
+fn profile_violation(context: String) -> GuardianError {
+    GuardianError::ConfigurationError {
+        context,
+        source: None,
+        severity: crate::utils::error::ErrorSeverity::Critical,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: crate::utils::error::ErrorCategory::Security,
+        retry_count: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_production_requires_encryption_and_mtls() {
+        let production = EnvironmentProfile::for_environment(Environment::Production);
+        assert!(production.enforces_responses());
+        assert!(production.require_mtls("grpc", true, true).is_ok());
+        assert!(production.require_mtls("grpc", true, false).is_err());
+        assert!(production.require_encryption("storage", false).is_err());
+
+        let development = EnvironmentProfile::for_environment(Environment::Development);
+        assert!(!development.enforces_responses());
+        assert!(development.require_mtls("grpc", false, false).is_ok());
+    }
+
+    #[test]
+    fn test_active_profile_defaults_to_production() {
+        // Nothing installs a profile in unit tests, so callers get the strictest behavior
+        assert_eq!(active_profile().environment, Environment::Production);
+    }
+}
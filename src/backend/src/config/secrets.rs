@@ -0,0 +1,333 @@
+//! Encrypted configuration values
+//!
+//! Sensitive values are stored in config files as `enc:v1:<kek version>:<wrapped key>:<ciphertext>`.
+//! Each value is sealed with its own data key, and the data key is wrapped with the config
+//! key-encryption key held by the key provider (key files or the HSM). Rotating the KEK only
+//! re-wraps data keys; ciphertexts are left as they are.
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use metrics::counter;
+use parking_lot::Mutex;
+use ring::{
+    aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN},
+    rand::{SecureRandom, SystemRandom},
+};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use tracing::{info, instrument};
+use zeroize::Zeroizing;
+
+use super::migration::Format;
+use crate::security::key_provider::{KeyMaterial, KeyProvider, KeyRotationParticipant};
+use crate::utils::error::{ErrorCategory, ErrorSeverity, GuardianError};
+
+// Constants for config secrets
+pub const ENVELOPE_PREFIX: &str = "enc:v1:";
+/// Key provider ID of the config key-encryption key
+pub const CONFIG_KEK_ID: &str = "config-kek";
+const DATA_KEY_SIZE: usize = 32;
+const CONFIG_EXTENSIONS: &[&str] = &["toml", "yaml", "yml", "json"];
+
+/// Whether a config value is an encrypted envelope
+pub fn is_envelope(value: &str) -> bool {
+    value.starts_with(ENVELOPE_PREFIX)
+}
+
+/// A parsed `enc:v1` value
+#[derive(Debug, Clone, PartialEq)]
+struct Envelope {
+    kek_version: u64,
+    wrapped_key: Vec<u8>,
+    ciphertext: Vec<u8>,
+}
+
+impl Envelope {
+    fn parse(value: &str) -> Result<Self, GuardianError> {
+        let malformed = || secrets_error("Malformed encrypted config value".into());
+        let body = value.strip_prefix(ENVELOPE_PREFIX).ok_or_else(malformed)?;
+        let mut parts = body.splitn(3, ':');
+        let (Some(version), Some(wrapped_key), Some(ciphertext)) = (parts.next(), parts.next(), parts.next()) else {
+            return Err(malformed());
+        };
+        Ok(Self {
+            kek_version: version.parse().map_err(|_| malformed())?,
+            wrapped_key: BASE64.decode(wrapped_key).map_err(|_| malformed())?,
+            ciphertext: BASE64.decode(ciphertext).map_err(|_| malformed())?,
+        })
+    }
+
+    fn render(&self) -> String {
+        format!(
+            "{}{}:{}:{}",
+            ENVELOPE_PREFIX,
+            self.kek_version,
+            BASE64.encode(&self.wrapped_key),
+            BASE64.encode(&self.ciphertext)
+        )
+    }
+}
+
+/// Encrypts config values and decrypts them as configuration is loaded
+#[derive(Debug)]
+pub struct ConfigSecrets {
+    provider: Arc<dyn KeyProvider>,
+    /// KEK versions applied during a rotation that the provider has not committed yet
+    staged: Mutex<HashMap<u64, Zeroizing<Vec<u8>>>>,
+}
+
+impl ConfigSecrets {
+    pub fn new(provider: Arc<dyn KeyProvider>) -> Self {
+        Self { provider, staged: Mutex::new(HashMap::new()) }
+    }
+
+    /// Seals a value under a fresh data key wrapped with the current KEK
+    pub async fn encrypt(&self, plaintext: &str) -> Result<String, GuardianError> {
+        let kek = self.provider.current_key(CONFIG_KEK_ID).await?;
+        let mut data_key = Zeroizing::new(vec![0u8; DATA_KEY_SIZE]);
+        SystemRandom::new()
+            .fill(&mut data_key)
+            .map_err(|_| secrets_error("Failed to generate a data key".into()))?;
+
+        let envelope = Envelope {
+            kek_version: kek.version,
+            wrapped_key: seal(kek.bytes(), &data_key)?,
+            ciphertext: seal(&data_key, plaintext.as_bytes())?,
+        };
+        counter!("guardian.config.secrets_encrypted", 1);
+        Ok(envelope.render())
+    }
+
+    /// Opens an envelope; values that are not envelopes are returned unchanged
+    pub async fn decrypt(&self, value: &str) -> Result<Zeroizing<String>, GuardianError> {
+        if !is_envelope(value) {
+            return Ok(Zeroizing::new(value.to_string()));
+        }
+        let envelope = Envelope::parse(value)?;
+        let kek = self.kek(envelope.kek_version).await?;
+        let data_key = Zeroizing::new(open(&kek, &envelope.wrapped_key)?);
+        let plaintext = Zeroizing::new(open(&data_key, &envelope.ciphertext)?);
+        String::from_utf8(plaintext.to_vec())
+            .map(Zeroizing::new)
+            .map_err(|_| secrets_error("Encrypted config value is not UTF-8".into()))
+    }
+
+    /// Replaces every envelope in a document with its plaintext, returning how many were opened
+    pub async fn decrypt_tree(&self, document: &mut Value) -> Result<usize, GuardianError> {
+        let mut opened = 0;
+        for value in envelopes_mut(document) {
+            let plaintext = self.decrypt(value.as_str().unwrap_or_default()).await?;
+            *value = Value::String(plaintext.to_string());
+            opened += 1;
+        }
+        Ok(opened)
+    }
+
+    /// Returns a copy of a loaded configuration section with its encrypted values decrypted
+    pub async fn decrypt_config<T: Serialize + DeserializeOwned>(&self, config: &T) -> Result<T, GuardianError> {
+        let mut document = serde_json::to_value(config)
+            .map_err(|e| secrets_error(format!("Failed to serialize configuration: {}", e)))?;
+        self.decrypt_tree(&mut document).await?;
+        serde_json::from_value(document)
+            .map_err(|e| secrets_error(format!("Decrypted configuration is invalid: {}", e)))
+    }
+
+    /// Re-wraps the data keys of every envelope in the config files of `dir` with `kek`
+    #[instrument(skip(self, kek), fields(kek_version = kek.version))]
+    pub async fn rewrap_dir(&self, dir: &Path, kek: &KeyMaterial) -> Result<usize, GuardianError> {
+        let mut rewrapped = 0;
+        for path in config_files(dir)? {
+            rewrapped += self.rewrap_file(&path, kek).await?;
+        }
+        counter!("guardian.config.secrets_rewrapped", rewrapped as u64);
+        info!(dir = %dir.display(), rewrapped, "Encrypted config values re-wrapped");
+        Ok(rewrapped)
+    }
+
+    /// Makes this store re-wrap the config files in `dir` whenever the config KEK rotates
+    pub fn rotation_participant(self: &Arc<Self>, dir: PathBuf) -> Arc<dyn KeyRotationParticipant> {
+        Arc::new(SecretsRotation { secrets: Arc::clone(self), dir })
+    }
+
+    async fn rewrap_file(&self, path: &Path, kek: &KeyMaterial) -> Result<usize, GuardianError> {
+        let format = Format::of(path)?;
+        let raw = std::fs::read_to_string(path).map_err(|e| io_error(path, e))?;
+        let mut document = format.parse(&raw).map_err(|e| secrets_error(format!("{}: {}", path.display(), e)))?;
+
+        let mut rewrapped = 0;
+        for value in envelopes_mut(&mut document) {
+            let mut envelope = Envelope::parse(value.as_str().unwrap_or_default())?;
+            if envelope.kek_version == kek.version {
+                continue;
+            }
+            let previous = self.kek(envelope.kek_version).await?;
+            let data_key = Zeroizing::new(open(&previous, &envelope.wrapped_key)?);
+            envelope.wrapped_key = seal(kek.bytes(), &data_key)?;
+            envelope.kek_version = kek.version;
+            *value = Value::String(envelope.render());
+            rewrapped += 1;
+        }
+        if rewrapped == 0 {
+            return Ok(0);
+        }
+
+        let rendered = format.render(&document).map_err(|e| secrets_error(format!("{}: {}", path.display(), e)))?;
+        let temporary = path.with_extension("rewrapping");
+        std::fs::write(&temporary, rendered).map_err(|e| io_error(&temporary, e))?;
+        std::fs::rename(&temporary, path).map_err(|e| io_error(path, e))?;
+        Ok(rewrapped)
+    }
+
+    async fn kek(&self, version: u64) -> Result<Zeroizing<Vec<u8>>, GuardianError> {
+        if let Some(staged) = self.staged.lock().get(&version) {
+            return Ok(staged.clone());
+        }
+        let kek = self.provider.key_version(CONFIG_KEK_ID, version).await?;
+        Ok(Zeroizing::new(kek.bytes().to_vec()))
+    }
+}
+
+/// Re-wraps config values in step with the other holders of the config KEK
+struct SecretsRotation {
+    secrets: Arc<ConfigSecrets>,
+    dir: PathBuf,
+}
+
+#[async_trait]
+impl KeyRotationParticipant for SecretsRotation {
+    fn name(&self) -> &str {
+        "config_secrets"
+    }
+
+    async fn key_ids(&self) -> Vec<String> {
+        vec![CONFIG_KEK_ID.to_string()]
+    }
+
+    async fn apply_key(&self, key_id: &str, key: &KeyMaterial) -> Result<(), GuardianError> {
+        if key_id != CONFIG_KEK_ID {
+            return Ok(());
+        }
+        // A rollback re-wraps from the staged version, which the provider only serves once committed
+        self.secrets.staged.lock().insert(key.version, Zeroizing::new(key.bytes().to_vec()));
+        self.secrets.rewrap_dir(&self.dir, key).await.map(|_| ())
+    }
+}
+
+/// Mutable references to every envelope string in a document
+fn envelopes_mut(document: &mut Value) -> Vec<&mut Value> {
+    match document {
+        Value::String(s) if is_envelope(s) => vec![document],
+        Value::Array(items) => items.iter_mut().flat_map(envelopes_mut).collect(),
+        Value::Object(fields) => fields.values_mut().flat_map(envelopes_mut).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// AES-256-GCM with a random nonce prepended to the sealed bytes
+fn seal(key: &[u8], plaintext: &[u8]) -> Result<Vec<u8>, GuardianError> {
+    let key = aead_key(key)?;
+    let mut nonce = [0u8; NONCE_LEN];
+    SystemRandom::new()
+        .fill(&mut nonce)
+        .map_err(|_| secrets_error("Failed to generate a nonce".into()))?;
+    let mut sealed = plaintext.to_vec();
+    key.seal_in_place_append_tag(Nonce::assume_unique_for_key(nonce), Aad::from(ENVELOPE_PREFIX), &mut sealed)
+        .map_err(|_| secrets_error("Failed to encrypt config value".into()))?;
+    Ok([nonce.as_slice(), &sealed].concat())
+}
+
+fn open(key: &[u8], sealed: &[u8]) -> Result<Vec<u8>, GuardianError> {
+    if sealed.len() < NONCE_LEN {
+        return Err(secrets_error("Encrypted config value is truncated".into()));
+    }
+    let key = aead_key(key)?;
+    let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+    let nonce = Nonce::try_assume_unique_for_key(nonce).map_err(|_| secrets_error("Invalid nonce".into()))?;
+    let mut buffer = ciphertext.to_vec();
+    let plaintext = key
+        .open_in_place(nonce, Aad::from(ENVELOPE_PREFIX), &mut buffer)
+        .map_err(|_| secrets_error("Failed to decrypt config value: wrong key or tampered ciphertext".into()))?;
+    Ok(plaintext.to_vec())
+}
+
+fn aead_key(key: &[u8]) -> Result<LessSafeKey, GuardianError> {
+    UnboundKey::new(&AES_256_GCM, key)
+        .map(LessSafeKey::new)
+        .map_err(|_| secrets_error("Invalid config encryption key".into()))
+}
+
+fn config_files(dir: &Path) -> Result<Vec<PathBuf>, GuardianError> {
+    let entries = std::fs::read_dir(dir).map_err(|e| io_error(dir, e))?;
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|p| p.extension().and_then(|e| e.to_str()).is_some_and(|e| CONFIG_EXTENSIONS.contains(&e)))
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+fn io_error(path: &Path, e: std::io::Error) -> GuardianError {
+    GuardianError::SystemError {
+        context: format!("Config secrets I/O failed on {}", path.display()),
+        source: Some(Box::new(e)),
+        severity: ErrorSeverity::High,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: ErrorCategory::System,
+        retry_count: 0,
+    }
+}
+
+fn secrets_error(context: String) -> GuardianError {
+    GuardianError::SecurityError {
+        context,
+        source: None,
+        severity: ErrorSeverity::High,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: ErrorCategory::Security,
+        retry_count: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::key_provider::{FileKeyProvider, KeyRotationCoordinator};
+
+    #[tokio::test]
+    async fn test_values_round_trip_and_survive_kek_rotation() {
+        let keys = tempfile::tempdir().unwrap();
+        let config_dir = tempfile::tempdir().unwrap();
+        let provider: Arc<dyn KeyProvider> = Arc::new(FileKeyProvider::new(keys.path().to_path_buf()).unwrap());
+        let secrets = Arc::new(ConfigSecrets::new(Arc::clone(&provider)));
+
+        let envelope = secrets.encrypt("s3cret-token").await.unwrap();
+        assert!(envelope.starts_with("enc:v1:1:"));
+        assert_ne!(envelope, secrets.encrypt("s3cret-token").await.unwrap());
+        assert_eq!(secrets.decrypt(&envelope).await.unwrap().as_str(), "s3cret-token");
+        assert_eq!(secrets.decrypt("plain").await.unwrap().as_str(), "plain");
+
+        let path = config_dir.path().join("app.json");
+        std::fs::write(&path, serde_json::json!({ "siem": { "token": envelope, "host": "siem" } }).to_string()).unwrap();
+
+        let coordinator = KeyRotationCoordinator::new(Arc::clone(&provider));
+        coordinator.register(secrets.rotation_participant(config_dir.path().to_path_buf()));
+        assert_eq!(coordinator.rotate(CONFIG_KEK_ID).await.unwrap(), 2);
+
+        let mut rewritten: Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert!(rewritten["siem"]["token"].as_str().unwrap().starts_with("enc:v1:2:"));
+        assert_eq!(secrets.decrypt_tree(&mut rewritten).await.unwrap(), 1);
+        assert_eq!(rewritten["siem"]["token"], "s3cret-token");
+
+        let mut tampered = Envelope::parse(&envelope).unwrap();
+        tampered.ciphertext[NONCE_LEN] ^= 1;
+        assert!(secrets.decrypt(&tampered.render()).await.is_err());
+    }
+}
@@ -4,16 +4,30 @@ use tracing::{debug, error, info, instrument};
 use std::path::Path;
 use std::time::Duration;
 
+use crate::security::audit::SecurityLevel;
+use crate::security::detection_pipeline::PipelineConfig;
 use crate::utils::error::GuardianError;
 use crate::utils::validation::{validate_input, ValidationRules};
 
 // Security configuration constants
+pub(crate) const CONFIG_VERSION: &str = "1.0.0";
 const DEFAULT_KEY_SIZE: u32 = 4096;
 const MIN_PASSWORD_LENGTH: usize = 16;
 const DEFAULT_TLS_VERSION: &str = "1.3";
 const DEFAULT_CIPHER_SUITE: &str = "TLS_AES_256_GCM_SHA384";
 const MIN_MFA_TOKEN_LENGTH: usize = 6;
 const CERT_ROTATION_DAYS: u32 = 90;
+const DEFAULT_SIEM_BUFFER_SIZE: usize = 10_000;
+const DEFAULT_KEY_DIR: &str = "/var/db/guardian/keys";
+const DEFAULT_HSM_PIN_ENV: &str = "GUARDIAN_HSM_PIN";
+const DEFAULT_KEK_LABEL: &str = "guardian_kek";
+const DEFAULT_PCR_SELECTION: &str = "sha256:0,1,2,3,4,5,6,7";
+const DEFAULT_AUDIT_CHAIN_PATH: &str = "/var/log/guardian/audit.chain";
+const DEFAULT_AUDIT_ANCHOR_KEY: &str = "audit-anchor";
+const DEFAULT_KEY_ROTATION_OVERLAP: Duration = Duration::from_secs(3600);
+const DEFAULT_TOKEN_ISSUER: &str = "guardian";
+const DEFAULT_TOKEN_KEY: &str = "api-token";
+const DEFAULT_REVOCATION_PATH: &str = "/var/lib/guardian/auth/revoked_tokens.json";
 
 /// Authentication configuration settings
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -28,6 +42,39 @@ pub struct AuthConfig {
     pub min_password_length: usize,
     pub password_complexity: bool,
     pub session_timeout: Duration,
+    #[serde(default)]
+    pub api_tokens: ApiTokenConfig,
+}
+
+/// Scoped, expiring API tokens minted for service accounts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ApiTokenConfig {
+    pub enabled: bool,
+    /// `iss` claim of minted tokens; tokens from any other issuer are rejected
+    pub issuer: String,
+    pub default_ttl: Duration,
+    pub max_ttl: Duration,
+    /// Clock skew tolerated on the expiry and not-before checks
+    pub leeway: Duration,
+    /// Key provider key tokens are signed with
+    pub signing_key_id: String,
+    /// Revoked token IDs, kept until the tokens would have expired
+    pub revocation_path: String,
+}
+
+impl Default for ApiTokenConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            issuer: DEFAULT_TOKEN_ISSUER.to_string(),
+            default_ttl: Duration::from_secs(24 * 3600),
+            max_ttl: Duration::from_secs(90 * 24 * 3600),
+            leeway: Duration::from_secs(30),
+            signing_key_id: DEFAULT_TOKEN_KEY.to_string(),
+            revocation_path: DEFAULT_REVOCATION_PATH.to_string(),
+        }
+    }
 }
 
 /// Encryption configuration settings
@@ -36,11 +83,19 @@ pub struct EncryptionConfig {
     pub aes_key_size: u32,
     pub rsa_key_size: u32,
     pub key_rotation_interval: Duration,
+    /// How long a rotated-out key version stays valid so in-flight operations finish; must be
+    /// shorter than the rotation interval
+    #[serde(default = "default_key_rotation_overlap")]
+    pub key_rotation_overlap: Duration,
     pub encryption_at_rest: bool,
     pub encryption_in_transit: bool,
     pub cipher_suite: String,
 }
 
+fn default_key_rotation_overlap() -> Duration {
+    DEFAULT_KEY_ROTATION_OVERLAP
+}
+
 /// TLS configuration settings
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TLSConfig {
@@ -72,6 +127,77 @@ pub struct HardwareSecurityConfig {
     pub hsm_token_label: String,
     pub tpm_enabled: bool,
     pub secure_enclave_enabled: bool,
+    /// Where encryption keys are kept and which HSM wraps them
+    #[serde(default)]
+    pub key_store: KeyStoreConfig,
+}
+
+/// Key storage settings; keys are wrapped by the HSM when a PKCS#11 module is set
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KeyStoreConfig {
+    pub key_dir: String,
+    /// Path to the PKCS#11 module of the HSM vendor
+    pub pkcs11_module: Option<String>,
+    /// Environment variable holding the HSM user PIN
+    pub pin_env: String,
+    /// Label of the key-encryption key on the token
+    pub kek_label: String,
+}
+
+impl Default for KeyStoreConfig {
+    fn default() -> Self {
+        Self {
+            key_dir: DEFAULT_KEY_DIR.to_string(),
+            pkcs11_module: None,
+            pin_env: DEFAULT_HSM_PIN_ENV.to_string(),
+            kek_label: DEFAULT_KEK_LABEL.to_string(),
+        }
+    }
+}
+
+/// Keys trusted to sign model binaries
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ModelSigningConfig {
+    /// Reject unsigned or badly signed models; when off, failures are only audited
+    pub enforce: bool,
+    /// Base64 Ed25519 public keys
+    pub ed25519_keys: Vec<String>,
+    /// PEM certificates whose Ed25519, ECDSA P-256 or RSA key signs models
+    pub certificate_paths: Vec<String>,
+}
+
+impl Default for ModelSigningConfig {
+    fn default() -> Self {
+        Self {
+            enforce: true,
+            ed25519_keys: Vec::new(),
+            certificate_paths: Vec::new(),
+        }
+    }
+}
+
+/// Signed attestation evidence served to fleet verifiers
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AttestationConfig {
+    /// PKCS#8 Ed25519 key signing the evidence; an ephemeral key is generated when unset
+    pub signing_key_path: Option<String>,
+    /// tpm2-tools context of the attestation key quoting the PCRs; no quote when unset
+    pub tpm_key_context: Option<String>,
+    /// PCR banks and indexes included in the quote
+    pub pcr_selection: String,
+}
+
+impl Default for AttestationConfig {
+    fn default() -> Self {
+        Self {
+            signing_key_path: None,
+            tpm_key_context: None,
+            pcr_selection: DEFAULT_PCR_SELECTION.to_string(),
+        }
+    }
 }
 
 /// Audit logging configuration
@@ -82,6 +208,120 @@ pub struct AuditConfig {
     pub log_retention_days: u32,
     pub secure_logging: bool,
     pub log_encryption: bool,
+    /// External SIEMs audit events are forwarded to
+    #[serde(default)]
+    pub siem_targets: Vec<SiemTargetConfig>,
+    /// Hash chain making the audit trail tamper-evident
+    #[serde(default)]
+    pub chain: AuditChainConfig,
+    /// Adaptive down-sampling of Low and Medium events under load
+    #[serde(default)]
+    pub sampling: AuditSamplingConfig,
+}
+
+/// Append-only hash chain of audit events with periodically signed anchors
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AuditChainConfig {
+    pub enabled: bool,
+    pub path: String,
+    /// Time between signed anchors over the newest event
+    pub anchor_interval: Duration,
+    /// Key provider key the anchors are signed with
+    pub anchor_key_id: String,
+}
+
+impl Default for AuditChainConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            path: DEFAULT_AUDIT_CHAIN_PATH.to_string(),
+            anchor_interval: Duration::from_secs(300),
+            anchor_key_id: DEFAULT_AUDIT_ANCHOR_KEY.to_string(),
+        }
+    }
+}
+
+/// Adaptive sampling of audit events
+///
+/// Critical and High events, and command invocations, are always recorded. Medium and Low
+/// events are recorded in full until their rate exceeds the target or audit storage fills
+/// past the pressure threshold, then down-sampled, Low more aggressively than Medium.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AuditSamplingConfig {
+    pub enabled: bool,
+    /// Events per second recorded in full
+    pub target_events_per_sec: f64,
+    /// Floor of the sampling rate, so no event type disappears entirely
+    pub min_rate: f64,
+    /// Audit storage usage in percent from which sampling tightens
+    pub storage_pressure_threshold: f64,
+    /// Window the event rate is measured over
+    pub window: Duration,
+    /// Further event types recorded in full regardless of severity
+    pub always_record: Vec<String>,
+}
+
+impl Default for AuditSamplingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            target_events_per_sec: 200.0,
+            min_rate: 0.01,
+            storage_pressure_threshold: 80.0,
+            window: Duration::from_secs(10),
+            always_record: Vec::new(),
+        }
+    }
+}
+
+/// Wire format of exported audit events
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SiemFormat {
+    /// RFC 5424 syslog with the event in structured data
+    Syslog,
+    /// ArcSight Common Event Format
+    Cef,
+    /// QRadar Log Event Extended Format
+    Leef,
+    /// One JSON object per line
+    Json,
+}
+
+/// Transport used to reach a SIEM collector
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SiemTransport {
+    Udp,
+    Tcp,
+    Tls,
+}
+
+/// One SIEM collector and the audit events it receives
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SiemTargetConfig {
+    pub name: String,
+    /// Collector `host:port`
+    pub address: String,
+    pub format: SiemFormat,
+    pub transport: SiemTransport,
+    /// Least severe event forwarded to this target
+    pub min_severity: SecurityLevel,
+    /// CA bundle used to verify the collector, required for TLS
+    #[serde(default)]
+    pub ca_path: Option<String>,
+    /// Name checked against the collector certificate, defaults to the address host
+    #[serde(default)]
+    pub server_name: Option<String>,
+    /// Events held while the collector is unreachable, oldest dropped first
+    #[serde(default = "default_siem_buffer_size")]
+    pub buffer_size: usize,
+}
+
+fn default_siem_buffer_size() -> usize {
+    DEFAULT_SIEM_BUFFER_SIZE
 }
 
 /// Security monitoring configuration
@@ -97,6 +337,9 @@ pub struct MonitoringConfig {
 /// Comprehensive security configuration for the Guardian system
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SecurityConfig {
+    /// Schema version, upgraded by config migration
+    #[serde(default = "default_config_version")]
+    pub version: String,
     pub auth_config: AuthConfig,
     pub encryption_config: EncryptionConfig,
     pub tls_config: TLSConfig,
@@ -104,12 +347,27 @@ pub struct SecurityConfig {
     pub hw_security_config: HardwareSecurityConfig,
     pub audit_config: AuditConfig,
     pub monitoring_config: MonitoringConfig,
+    /// Trusted signers of model binaries
+    #[serde(default)]
+    pub model_signing: ModelSigningConfig,
+    /// Remote attestation of the running binary, models and configuration
+    #[serde(default)]
+    pub attestation: AttestationConfig,
+    /// Stages the threat detector runs each cycle, replacing the fixed intel, rules, inference
+    /// and filter cycle when set
+    #[serde(default)]
+    pub detection_pipeline: Option<PipelineConfig>,
+}
+
+fn default_config_version() -> String {
+    CONFIG_VERSION.to_string()
 }
 
 impl SecurityConfig {
     /// Creates a new SecurityConfig with secure default settings
     pub fn new() -> Self {
         Self {
+            version: CONFIG_VERSION.to_string(),
             auth_config: AuthConfig {
                 x509_enabled: true,
                 x509_cert_path: "/etc/guardian/certs/client.crt".to_string(),
@@ -121,11 +379,13 @@ impl SecurityConfig {
                 min_password_length: MIN_PASSWORD_LENGTH,
                 password_complexity: true,
                 session_timeout: Duration::from_secs(900), // 15 minutes
+                api_tokens: ApiTokenConfig::default(),
             },
             encryption_config: EncryptionConfig {
                 aes_key_size: 256,
                 rsa_key_size: DEFAULT_KEY_SIZE,
                 key_rotation_interval: Duration::from_secs(7 * 24 * 3600), // 7 days
+                key_rotation_overlap: DEFAULT_KEY_ROTATION_OVERLAP,
                 encryption_at_rest: true,
                 encryption_in_transit: true,
                 cipher_suite: DEFAULT_CIPHER_SUITE.to_string(),
@@ -153,6 +413,7 @@ impl SecurityConfig {
                 hsm_token_label: "guardian_hsm".to_string(),
                 tpm_enabled: true,
                 secure_enclave_enabled: true,
+                key_store: KeyStoreConfig::default(),
             },
             audit_config: AuditConfig {
                 audit_enabled: true,
@@ -160,6 +421,9 @@ impl SecurityConfig {
                 log_retention_days: 90,
                 secure_logging: true,
                 log_encryption: true,
+                siem_targets: Vec::new(),
+                chain: AuditChainConfig::default(),
+                sampling: AuditSamplingConfig::default(),
             },
             monitoring_config: MonitoringConfig {
                 intrusion_detection: true,
@@ -168,6 +432,9 @@ impl SecurityConfig {
                 monitoring_interval: Duration::from_secs(60),
                 alert_threshold: 3,
             },
+            model_signing: ModelSigningConfig::default(),
+            attestation: AttestationConfig::default(),
+            detection_pipeline: None,
         }
     }
 
@@ -213,6 +480,24 @@ impl SecurityConfig {
             ));
         }
 
+        let tokens = &self.auth_config.api_tokens;
+        if tokens.enabled
+            && (tokens.issuer.is_empty()
+                || tokens.signing_key_id.is_empty()
+                || tokens.default_ttl.is_zero()
+                || tokens.default_ttl > tokens.max_ttl)
+        {
+            return Err(GuardianError::ValidationError(
+                "API tokens need an issuer, a signing key and a non-zero default TTL within the maximum".to_string(),
+            ));
+        }
+
+        if self.detection_pipeline.as_ref().map_or(false, |pipeline| pipeline.stages.is_empty()) {
+            return Err(GuardianError::ValidationError(
+                "Detection pipeline needs at least one stage".to_string(),
+            ));
+        }
+
         // Validate encryption settings
         if self.encryption_config.aes_key_size != 256 {
             return Err(GuardianError::ValidationError(
@@ -226,6 +511,14 @@ impl SecurityConfig {
             ));
         }
 
+        if self.encryption_config.key_rotation_interval.is_zero()
+            || self.encryption_config.key_rotation_overlap >= self.encryption_config.key_rotation_interval
+        {
+            return Err(GuardianError::ValidationError(
+                "Key rotation overlap must be shorter than a non-zero rotation interval".to_string(),
+            ));
+        }
+
         // Validate TLS settings
         if self.tls_config.version != DEFAULT_TLS_VERSION {
             return Err(GuardianError::ValidationError(
@@ -239,6 +532,65 @@ impl SecurityConfig {
             ));
         }
 
+        // Validate SIEM export targets
+        for target in &self.audit_config.siem_targets {
+            if target.address.rsplit_once(':').is_none() || target.buffer_size == 0 {
+                return Err(GuardianError::ValidationError(
+                    format!("SIEM target {} needs a host:port address and a non-empty buffer", target.name),
+                ));
+            }
+            if target.transport == SiemTransport::Tls && target.ca_path.is_none() {
+                return Err(GuardianError::ValidationError(
+                    format!("SIEM target {} uses TLS without a CA bundle", target.name),
+                ));
+            }
+        }
+
+        // Validate audit sampling
+        let sampling = &self.audit_config.sampling;
+        if sampling.enabled
+            && (sampling.target_events_per_sec <= 0.0
+                || !(sampling.min_rate > 0.0 && sampling.min_rate <= 1.0)
+                || !(0.0..100.0).contains(&sampling.storage_pressure_threshold)
+                || sampling.window.is_zero())
+        {
+            return Err(GuardianError::ValidationError(
+                "Audit sampling needs a positive target rate, a minimum rate in (0, 1], a storage threshold below 100% and a non-zero window".to_string(),
+            ));
+        }
+
+        // Validate the audit chain
+        let chain = &self.audit_config.chain;
+        if chain.enabled && (chain.path.is_empty() || chain.anchor_interval.is_zero() || chain.anchor_key_id.is_empty()) {
+            return Err(GuardianError::ValidationError(
+                "Audit chain needs a path, a non-zero anchor interval and an anchor key".to_string(),
+            ));
+        }
+
+        // Validate key storage
+        let key_store = &self.hw_security_config.key_store;
+        if key_store.pkcs11_module.is_some() && self.hw_security_config.hsm_token_label.is_empty() {
+            return Err(GuardianError::ValidationError(
+                "PKCS#11 key storage requires an HSM token label".to_string(),
+            ));
+        }
+
+        // Validate model signing keys
+        if self.model_signing.ed25519_keys.iter().any(|key| key.trim().is_empty())
+            || self.model_signing.certificate_paths.iter().any(|path| path.trim().is_empty())
+        {
+            return Err(GuardianError::ValidationError(
+                "Model signing keys and certificate paths cannot be empty".to_string(),
+            ));
+        }
+
+        // Validate attestation
+        if self.attestation.pcr_selection.trim().is_empty() {
+            return Err(GuardianError::ValidationError(
+                "Attestation PCR selection cannot be empty".to_string(),
+            ));
+        }
+
         debug!("Security configuration validation successful");
         Ok(())
     }
@@ -292,6 +644,19 @@ mod tests {
         assert!(config.validate().is_err());
     }
 
+    #[test]
+    fn test_detection_pipeline_section() {
+        let mut config = SecurityConfig::new();
+        config.detection_pipeline = Some(serde_json::from_value(serde_json::json!({
+            "name": "configured",
+            "stages": [{ "name": "model", "type": "inference" }]
+        })).unwrap());
+        assert!(config.validate().is_ok());
+
+        config.detection_pipeline.as_mut().unwrap().stages.clear();
+        assert!(config.validate().is_err());
+    }
+
     #[test]
     fn test_invalid_encryption_settings() {
         let mut config = SecurityConfig::new();
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 use config::{Config, ConfigError, File};
+use std::collections::HashMap;
+use std::time::Duration;
 use crate::utils::error::GuardianError;
 
 // Constants for storage configuration
@@ -18,6 +20,179 @@ pub enum StorageIOPriority {
     Low,
 }
 
+/// Classes of background storage jobs subject to I/O throttling
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BackgroundJobClass {
+    Scrub,
+    Rollup,
+    Backup,
+    Retention,
+    Snapshot,
+}
+
+/// I/O rate limit for a single background job class, 0 disables a limit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IoClassLimit {
+    pub bytes_per_sec: u64,
+    pub ops_per_sec: u32,
+    pub burst_seconds: f64,
+}
+
+/// Disk I/O throttling settings for background storage jobs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackgroundIoConfig {
+    pub enabled: bool,
+    pub class_limits: HashMap<BackgroundJobClass, IoClassLimit>,
+}
+
+impl Default for BackgroundIoConfig {
+    fn default() -> Self {
+        let limit = |mb_per_sec: u64, ops_per_sec: u32| IoClassLimit {
+            bytes_per_sec: mb_per_sec * 1024 * 1024,
+            ops_per_sec,
+            burst_seconds: 1.0,
+        };
+
+        Self {
+            enabled: true,
+            class_limits: HashMap::from([
+                (BackgroundJobClass::Scrub, limit(20, 200)),
+                (BackgroundJobClass::Rollup, limit(30, 500)),
+                (BackgroundJobClass::Backup, limit(50, 300)),
+                (BackgroundJobClass::Retention, limit(20, 100)),
+                (BackgroundJobClass::Snapshot, limit(0, 20)),
+            ]),
+        }
+    }
+}
+
+/// Stores whose background writes yield to model reads
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WriteClass {
+    Metrics,
+    Events,
+}
+
+/// Write budget metrics and event writes are held to while model reads are slow
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IoBudgetConfig {
+    pub enabled: bool,
+    /// Dataset under the root dataset whose reads are protected
+    pub models_dataset: String,
+    /// Model read time per MiB above which writes are throttled
+    pub read_latency_threshold: Duration,
+    /// How often model read activity is sampled from the dataset statistics
+    pub sample_interval: Duration,
+    /// Write limits at the threshold, scaled down as read latency grows past it
+    pub write_limits: HashMap<WriteClass, IoClassLimit>,
+    /// Lowest fraction of the write limits writes are scaled down to
+    pub min_scale: f64,
+}
+
+impl Default for IoBudgetConfig {
+    fn default() -> Self {
+        let limit = |mb_per_sec: u64, ops_per_sec: u32| IoClassLimit {
+            bytes_per_sec: mb_per_sec * 1024 * 1024,
+            ops_per_sec,
+            burst_seconds: 1.0,
+        };
+
+        Self {
+            enabled: true,
+            models_dataset: "models".to_string(),
+            read_latency_threshold: Duration::from_millis(20),
+            sample_interval: Duration::from_secs(5),
+            write_limits: HashMap::from([
+                (WriteClass::Metrics, limit(20, 200)),
+                (WriteClass::Events, limit(20, 1000)),
+            ]),
+            min_scale: 0.1,
+        }
+    }
+}
+
+/// Periodic `zpool scrub` scheduling and pool health polling
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScrubConfig {
+    pub enabled: bool,
+    /// Time between the start of one scrub and the next
+    pub interval: Duration,
+    /// How often pool status, scan progress and checksum errors are read
+    pub poll_interval: Duration,
+}
+
+impl Default for ScrubConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval: Duration::from_secs(7 * 86400),
+            poll_interval: Duration::from_secs(300),
+        }
+    }
+}
+
+/// Quota, reservation and usage thresholds of one dataset
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DatasetQuota {
+    /// Hard limit set with `zfs set quota`; without one, usage is measured against the space available
+    pub quota_gb: Option<u64>,
+    /// Space guaranteed with `zfs set reservation`
+    pub reservation_gb: Option<u64>,
+    /// Usage percentage that raises a warning event
+    pub warn_percent: u8,
+    /// Usage percentage that raises a critical event and starts cleanup
+    pub cleanup_percent: u8,
+    /// Usage percentage cleanup frees space down to
+    pub cleanup_target_percent: u8,
+    /// Whether the oldest partitions are removed when cleanup starts
+    pub auto_cleanup: bool,
+}
+
+impl Default for DatasetQuota {
+    fn default() -> Self {
+        Self {
+            quota_gb: None,
+            reservation_gb: None,
+            warn_percent: 80,
+            cleanup_percent: 90,
+            cleanup_target_percent: 75,
+            auto_cleanup: false,
+        }
+    }
+}
+
+/// Per-dataset quotas and usage monitoring
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DatasetQuotaConfig {
+    pub enabled: bool,
+    pub poll_interval: Duration,
+    /// Keyed by dataset name relative to the root dataset, e.g. `events`
+    pub datasets: HashMap<String, DatasetQuota>,
+}
+
+impl Default for DatasetQuotaConfig {
+    fn default() -> Self {
+        // Metrics and events are partitioned by time, so their oldest partitions can go first
+        let partitioned = DatasetQuota { auto_cleanup: true, ..Default::default() };
+        Self {
+            enabled: true,
+            poll_interval: Duration::from_secs(60),
+            datasets: HashMap::from([
+                ("events".to_string(), partitioned.clone()),
+                ("metrics".to_string(), partitioned),
+                ("models".to_string(), DatasetQuota::default()),
+                ("logs".to_string(), DatasetQuota::default()),
+            ]),
+        }
+    }
+}
+
 /// Data retention policy configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RetentionPolicy {
@@ -27,6 +202,69 @@ pub struct RetentionPolicy {
     pub audit_logs_days: u32,
 }
 
+/// Ages, in days, at which one store's data changes retention tier
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TierAges {
+    /// Data younger than this stays uncompressed
+    pub hot_days: u32,
+    /// Data older than this leaves the pool for the archive, or is deleted without one
+    pub warm_days: u32,
+    /// Archived data older than this is deleted; 0 keeps archives
+    pub archive_days: u32,
+}
+
+impl Default for TierAges {
+    fn default() -> Self {
+        Self {
+            hot_days: 7,
+            warm_days: 90,
+            archive_days: 365,
+        }
+    }
+}
+
+/// Destination of data leaving the warm tier
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ArchiveTarget {
+    /// Separate dataset relative to the root dataset, written through its mountpoint
+    Dataset { dataset: String },
+    /// Object store taking `PUT`, `GET` and `DELETE` on `<endpoint>/<key>`
+    ObjectStore {
+        endpoint: String,
+        /// Bearer token; may be an encrypted `enc:v1` value
+        #[serde(default)]
+        token: Option<String>,
+    },
+}
+
+/// Hot, warm and archive tiers of the time-partitioned metrics and event stores
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetentionTierConfig {
+    pub metrics: TierAges,
+    pub events: TierAges,
+    /// zstd level warm data is recompressed with
+    pub warm_compression_level: i32,
+    /// Without a target, data is deleted once it leaves the warm tier
+    pub archive: Option<ArchiveTarget>,
+    /// How often partitions are checked for a tier change
+    pub interval: Duration,
+}
+
+impl Default for RetentionTierConfig {
+    fn default() -> Self {
+        Self {
+            metrics: TierAges::default(),
+            events: TierAges::default(),
+            warm_compression_level: 19,
+            archive: None,
+            interval: Duration::from_secs(3600),
+        }
+    }
+}
+
 /// Storage quota settings
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QuotaSettings {
@@ -72,6 +310,16 @@ pub struct StorageConfig {
     pub quota_settings: QuotaSettings,
     pub backup_enabled: bool,
     pub snapshot_schedule: SnapshotConfig,
+    #[serde(default)]
+    pub background_io: BackgroundIoConfig,
+    #[serde(default)]
+    pub io_budget: IoBudgetConfig,
+    #[serde(default)]
+    pub scrub: ScrubConfig,
+    #[serde(default)]
+    pub dataset_quotas: DatasetQuotaConfig,
+    #[serde(default)]
+    pub retention_tiers: RetentionTierConfig,
 }
 
 impl StorageConfig {
@@ -101,6 +349,11 @@ impl StorageConfig {
                 retention_count: 30,
                 auto_cleanup: true,
             },
+            background_io: BackgroundIoConfig::default(),
+            io_budget: IoBudgetConfig::default(),
+            scrub: ScrubConfig::default(),
+            dataset_quotas: DatasetQuotaConfig::default(),
+            retention_tiers: RetentionTierConfig::default(),
         }
     }
 
@@ -114,7 +367,7 @@ impl StorageConfig {
                 source: Some(Box::new(e)),
                 severity: ErrorSeverity::High,
                 timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id: uuid::Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: ErrorCategory::Storage,
                 retry_count: 0,
             })?;
@@ -125,7 +378,7 @@ impl StorageConfig {
                 source: Some(Box::new(e)),
                 severity: ErrorSeverity::High,
                 timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id: uuid::Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: ErrorCategory::Storage,
                 retry_count: 0,
             })?;
@@ -143,7 +396,7 @@ impl StorageConfig {
                 source: None,
                 severity: ErrorSeverity::High,
                 timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id: uuid::Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: ErrorCategory::Validation,
                 retry_count: 0,
             });
@@ -156,7 +409,123 @@ impl StorageConfig {
                 source: None,
                 severity: ErrorSeverity::High,
                 timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id: uuid::Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
+                category: ErrorCategory::Validation,
+                retry_count: 0,
+            });
+        }
+
+        // Validate background I/O limits
+        for (class, limit) in &self.background_io.class_limits {
+            if !limit.burst_seconds.is_finite() || limit.burst_seconds <= 0.0 {
+                return Err(GuardianError::ConfigError {
+                    context: format!("Invalid I/O burst window for {:?}: {}", class, limit.burst_seconds),
+                    source: None,
+                    severity: ErrorSeverity::High,
+                    timestamp: time::OffsetDateTime::now_utc(),
+                    correlation_id: crate::utils::correlation::current_or_new(),
+                    category: ErrorCategory::Validation,
+                    retry_count: 0,
+                });
+            }
+        }
+
+        // Validate the model read protection budget
+        let io_budget = &self.io_budget;
+        let budget_valid = io_budget.min_scale > 0.0
+            && io_budget.min_scale <= 1.0
+            && !io_budget.read_latency_threshold.is_zero()
+            && !io_budget.sample_interval.is_zero()
+            && io_budget.write_limits.values().all(|limit| limit.burst_seconds.is_finite() && limit.burst_seconds > 0.0);
+        if io_budget.enabled && !budget_valid {
+            return Err(GuardianError::ConfigError {
+                context: "I/O budget needs a minimum scale in (0, 1], non-zero latency threshold and sample interval, and positive burst windows".to_string(),
+                source: None,
+                severity: ErrorSeverity::High,
+                timestamp: time::OffsetDateTime::now_utc(),
+                correlation_id: crate::utils::correlation::current_or_new(),
+                category: ErrorCategory::Validation,
+                retry_count: 0,
+            });
+        }
+
+        // Validate scrub scheduling
+        if self.scrub.enabled && (self.scrub.poll_interval.is_zero() || self.scrub.interval < self.scrub.poll_interval) {
+            return Err(GuardianError::ConfigError {
+                context: "Scrub needs a non-zero poll interval no longer than the scrub interval".to_string(),
+                source: None,
+                severity: ErrorSeverity::High,
+                timestamp: time::OffsetDateTime::now_utc(),
+                correlation_id: crate::utils::correlation::current_or_new(),
+                category: ErrorCategory::Validation,
+                retry_count: 0,
+            });
+        }
+
+        // Validate per-dataset quotas
+        for (dataset, quota) in &self.dataset_quotas.datasets {
+            let thresholds_ordered = quota.cleanup_target_percent < quota.cleanup_percent
+                && quota.warn_percent <= quota.cleanup_percent
+                && quota.cleanup_percent <= 100;
+            let reservation_fits = match (quota.quota_gb, quota.reservation_gb) {
+                (Some(limit), Some(reserved)) => reserved <= limit,
+                _ => true,
+            };
+            if !thresholds_ordered || !reservation_fits || quota.quota_gb == Some(0) {
+                return Err(GuardianError::ConfigError {
+                    context: format!(
+                        "Invalid quota for dataset {}: need cleanup_target < cleanup, warn <= cleanup <= 100, and a non-zero quota no smaller than the reservation",
+                        dataset
+                    ),
+                    source: None,
+                    severity: ErrorSeverity::High,
+                    timestamp: time::OffsetDateTime::now_utc(),
+                    correlation_id: crate::utils::correlation::current_or_new(),
+                    category: ErrorCategory::Validation,
+                    retry_count: 0,
+                });
+            }
+        }
+        if self.dataset_quotas.enabled && self.dataset_quotas.poll_interval.is_zero() {
+            return Err(GuardianError::ConfigError {
+                context: "Dataset quota monitoring needs a non-zero poll interval".to_string(),
+                source: None,
+                severity: ErrorSeverity::High,
+                timestamp: time::OffsetDateTime::now_utc(),
+                correlation_id: crate::utils::correlation::current_or_new(),
+                category: ErrorCategory::Validation,
+                retry_count: 0,
+            });
+        }
+
+        // Validate retention tiers
+        let tiers = &self.retention_tiers;
+        for (store, ages) in [("metrics", &tiers.metrics), ("events", &tiers.events)] {
+            let ordered = ages.hot_days <= ages.warm_days
+                && ages.warm_days > 0
+                && (ages.archive_days == 0 || ages.archive_days > ages.warm_days);
+            if !ordered {
+                return Err(GuardianError::ConfigError {
+                    context: format!(
+                        "Invalid {} retention tiers: need hot_days <= warm_days, a non-zero warm_days, and archive_days of 0 or beyond warm_days",
+                        store
+                    ),
+                    source: None,
+                    severity: ErrorSeverity::High,
+                    timestamp: time::OffsetDateTime::now_utc(),
+                    correlation_id: crate::utils::correlation::current_or_new(),
+                    category: ErrorCategory::Validation,
+                    retry_count: 0,
+                });
+            }
+        }
+        if !(1..=22).contains(&tiers.warm_compression_level) || tiers.interval.is_zero() {
+            return Err(GuardianError::ConfigError {
+                context: "Retention tiers need a zstd level of 1-22 and a non-zero check interval".to_string(),
+                source: None,
+                severity: ErrorSeverity::High,
+                timestamp: time::OffsetDateTime::now_utc(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: ErrorCategory::Validation,
                 retry_count: 0,
             });
@@ -170,7 +539,7 @@ impl StorageConfig {
                 source: None,
                 severity: ErrorSeverity::High,
                 timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id: uuid::Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: ErrorCategory::Validation,
                 retry_count: 0,
             });
@@ -246,6 +615,34 @@ mod tests {
         assert!(config.validate().is_err());
     }
 
+    #[test]
+    fn test_validate_io_burst_window() {
+        let mut config = StorageConfig::new();
+        config.background_io.class_limits
+            .get_mut(&BackgroundJobClass::Backup)
+            .unwrap()
+            .burst_seconds = 0.0;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_validate_io_budget() {
+        let mut config = StorageConfig::new();
+        config.io_budget.min_scale = 0.0;
+        assert!(config.validate().is_err());
+        config.io_budget.enabled = false;
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_retention_tiers() {
+        let mut config = StorageConfig::new();
+        config.retention_tiers.events.archive_days = config.retention_tiers.events.warm_days;
+        assert!(config.validate().is_err());
+        config.retention_tiers.events.archive_days = 0;
+        assert!(config.validate().is_ok());
+    }
+
     #[test]
     fn test_resource_estimation() {
         let config = StorageConfig::new();
@@ -0,0 +1,363 @@
+//! Automatic reload of the configuration when its files change
+//!
+//! The directory holding the configuration file is watched (kqueue on FreeBSD, mtime
+//! polling elsewhere). Bursts of changes are debounced, the file is reloaded and
+//! validated, and only sections that can change at runtime are applied; a change
+//! touching anything else is rejected as a whole until the next restart. Every
+//! outcome is published as a `config.change` event, and the configuration last applied is
+//! written beside the file so `guardian-ctl config reload --dry-run` can show what a reload
+//! would change.
+
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use metrics::counter;
+use once_cell::sync::{Lazy, OnceCell};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use tokio::sync::{broadcast, mpsc};
+use tracing::{debug, info, warn};
+
+use crate::config::diff::{self, ConfigDiff};
+use crate::config::AppConfig;
+use crate::utils::error::GuardianError;
+
+// Constants for configuration watching
+pub const CONFIG_CHANGE_EVENT_TYPE: &str = "config.change";
+/// Top-level sections applied without a restart
+pub const HOT_RELOADABLE_SECTIONS: &[&str] = &[
+    "client_quotas",
+    "response_guardrails",
+    "process_trust",
+    "dead_letters",
+    "resource_governor",
+    "threat_intel",
+    "collectors",
+    "degradation",
+];
+#[cfg(not(target_os = "freebsd"))]
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+const CHANGE_CHANNEL_CAPACITY: usize = 64;
+
+static CONFIG_CHANGES: Lazy<broadcast::Sender<ConfigChange>> =
+    Lazy::new(|| broadcast::channel(CHANGE_CHANNEL_CAPACITY).0);
+
+/// Subscribes to the outcome of every configuration reload
+pub fn subscribe_config_changes() -> broadcast::Receiver<ConfigChange> {
+    CONFIG_CHANGES.subscribe()
+}
+
+/// What came of a reload
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigChangeOutcome {
+    Applied,
+    /// Valid, but touches sections that only take effect after a restart
+    RestartRequired,
+    Invalid,
+    Unchanged,
+}
+
+/// A detected configuration change and how it was handled
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigChange {
+    pub path: PathBuf,
+    pub outcome: ConfigChangeOutcome,
+    /// Top-level sections that differ from the running configuration
+    pub sections: Vec<String>,
+    pub reason: Option<String>,
+    pub detected_at: DateTime<Utc>,
+}
+
+/// Reloads the configuration file when it changes, applying hot-reloadable settings
+#[derive(Debug)]
+pub struct ConfigWatcher {
+    path: PathBuf,
+    debounce: Duration,
+    current: RwLock<AppConfig>,
+    started: OnceCell<()>,
+}
+
+/// Where the configuration last applied from `path` is recorded
+pub fn applied_snapshot_path(path: &Path) -> PathBuf {
+    let name = path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+    path.with_file_name(format!(".{}.applied", name))
+}
+
+/// Reads the configuration the daemon last applied from `path`
+pub fn applied_config(path: &Path) -> Result<AppConfig, GuardianError> {
+    let snapshot = std::fs::read(applied_snapshot_path(path)).map_err(|e| GuardianError::ConfigurationError {
+        context: format!("No applied configuration recorded for {}, is the daemon running?", path.display()),
+        source: Some(Box::new(e)),
+        severity: crate::utils::error::ErrorSeverity::Medium,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: crate::utils::error::ErrorCategory::System,
+        retry_count: 0,
+    })?;
+    Ok(serde_json::from_slice(&snapshot)?)
+}
+
+impl ConfigWatcher {
+    /// Watches `path`, which `current` was loaded from
+    pub fn new(path: PathBuf, current: AppConfig) -> Self {
+        let watcher = Self {
+            path,
+            debounce: current.config_watch.debounce,
+            current: RwLock::new(current),
+            started: OnceCell::new(),
+        };
+        watcher.record_applied();
+        watcher
+    }
+
+    /// The configuration as last applied
+    pub fn current(&self) -> AppConfig {
+        self.current.read().clone()
+    }
+
+    /// Starts watching; `apply` installs the hot-reloadable settings of a reloaded configuration
+    pub fn start<F>(self: &Arc<Self>, apply: F)
+    where
+        F: Fn(&AppConfig) + Send + Sync + 'static,
+    {
+        if self.started.set(()).is_err() {
+            return;
+        }
+
+        let (tx, mut rx) = mpsc::channel(CHANGE_CHANNEL_CAPACITY);
+        let dir = self.path.parent().map(Path::to_path_buf).unwrap_or_else(|| PathBuf::from("."));
+        let file = self.path.clone();
+        std::thread::Builder::new()
+            .name("guardian-config-watch".into())
+            .spawn(move || {
+                if let Err(e) = watch_blocking(&dir, &file, tx) {
+                    warn!(error = %e, dir = %dir.display(), "Configuration watch stopped, reload with SIGHUP");
+                }
+            })
+            .ok();
+
+        let watcher = Arc::clone(self);
+        tokio::spawn(async move {
+            while rx.recv().await.is_some() {
+                // Editors write in several steps; wait for the burst to settle
+                loop {
+                    match tokio::time::timeout(watcher.debounce, rx.recv()).await {
+                        Ok(Some(())) => continue,
+                        Ok(None) => return,
+                        Err(_) => break,
+                    }
+                }
+                watcher.reload(&apply);
+            }
+        });
+        info!(path = %self.path.display(), debounce_ms = self.debounce.as_millis() as u64, "Watching configuration for changes");
+    }
+
+    /// Compares the file with the configuration last applied, without applying anything
+    pub fn dry_run(&self) -> Result<ConfigDiff, GuardianError> {
+        ConfigDiff::between(&self.current(), &diff::load_effective(&self.path)?)
+    }
+
+    /// Reloads and validates the file, applying it when only hot-reloadable sections changed
+    pub fn reload<F>(&self, apply: &F) -> ConfigChange
+    where
+        F: Fn(&AppConfig) + ?Sized,
+    {
+        let change = match AppConfig::load(self.path.clone()) {
+            Ok(mut reloaded) => {
+                // Same overrides as at startup, or they would show up as changes
+                crate::config::profile::active_profile().apply(&mut reloaded);
+                self.apply_reloaded(reloaded, apply)
+            }
+            Err(e) => self.change(ConfigChangeOutcome::Invalid, Vec::new(), Some(e.to_string())),
+        };
+
+        match change.outcome {
+            ConfigChangeOutcome::Applied => info!(sections = ?change.sections, "Configuration reloaded"),
+            ConfigChangeOutcome::Unchanged => debug!("Configuration files touched without changes"),
+            _ => warn!(
+                outcome = ?change.outcome,
+                sections = ?change.sections,
+                reason = ?change.reason,
+                "Configuration change rejected, keeping current settings"
+            ),
+        }
+        counter!("guardian.config.reloads", 1, "outcome" => format!("{:?}", change.outcome).to_lowercase());
+        let _ = CONFIG_CHANGES.send(change.clone());
+        change
+    }
+
+    fn apply_reloaded<F>(&self, reloaded: AppConfig, apply: &F) -> ConfigChange
+    where
+        F: Fn(&AppConfig) + ?Sized,
+    {
+        let diff = match ConfigDiff::between(&self.current.read(), &reloaded) {
+            Ok(diff) => diff,
+            Err(e) => return self.change(ConfigChangeOutcome::Invalid, Vec::new(), Some(e.to_string())),
+        };
+        let sections = diff.sections();
+        match diff.outcome() {
+            ConfigChangeOutcome::Applied => {}
+            ConfigChangeOutcome::RestartRequired => {
+                let reason = format!("{:?} only take effect after a restart", diff.restart_sections());
+                return self.change(ConfigChangeOutcome::RestartRequired, sections, Some(reason));
+            }
+            outcome => return self.change(outcome, sections, diff.validation_error),
+        }
+
+        apply(&reloaded);
+        *self.current.write() = reloaded;
+        self.record_applied();
+        self.change(ConfigChangeOutcome::Applied, sections, None)
+    }
+
+    /// Writes the applied configuration beside the file, readable by its owner only
+    fn record_applied(&self) {
+        use std::io::Write;
+        use std::os::unix::fs::OpenOptionsExt;
+
+        let snapshot_path = applied_snapshot_path(&self.path);
+        let written = serde_json::to_vec(&*self.current.read())
+            .map_err(std::io::Error::from)
+            .and_then(|snapshot| {
+                std::fs::OpenOptions::new()
+                    .write(true)
+                    .create(true)
+                    .truncate(true)
+                    .mode(0o600)
+                    .open(&snapshot_path)?
+                    .write_all(&snapshot)
+            });
+        if let Err(e) = written {
+            warn!(error = %e, path = %snapshot_path.display(), "Failed to record the applied configuration");
+        }
+    }
+
+    fn change(&self, outcome: ConfigChangeOutcome, sections: Vec<String>, reason: Option<String>) -> ConfigChange {
+        ConfigChange {
+            path: self.path.clone(),
+            outcome,
+            sections,
+            reason,
+            detected_at: Utc::now(),
+        }
+    }
+}
+
+/// Blocks on kqueue vnode events of the directory and the file, signalling each change
+#[cfg(target_os = "freebsd")]
+fn watch_blocking(dir: &Path, file: &Path, tx: mpsc::Sender<()>) -> Result<(), nix::Error> {
+    use nix::sys::event::{kevent_ts, kqueue, EventFilter, EventFlag, FilterFlag, KEvent};
+    use std::os::fd::AsRawFd;
+
+    let kq = kqueue()?;
+    let vnode_flags = FilterFlag::NOTE_WRITE
+        | FilterFlag::NOTE_EXTEND
+        | FilterFlag::NOTE_ATTRIB
+        | FilterFlag::NOTE_RENAME
+        | FilterFlag::NOTE_DELETE;
+
+    loop {
+        // Reopened every round: editors replace the file, leaving an old descriptor on a deleted inode
+        let handles: Vec<std::fs::File> = [dir, file].iter().filter_map(|p| std::fs::File::open(p).ok()).collect();
+        let changes: Vec<KEvent> = handles
+            .iter()
+            .map(|handle| {
+                KEvent::new(
+                    handle.as_raw_fd() as usize,
+                    EventFilter::EVFILT_VNODE,
+                    EventFlag::EV_ADD | EventFlag::EV_ONESHOT,
+                    vnode_flags,
+                    0,
+                    0,
+                )
+            })
+            .collect();
+        let mut events = [KEvent::new(0, EventFilter::EVFILT_VNODE, EventFlag::empty(), FilterFlag::empty(), 0, 0); 2];
+
+        if kevent_ts(kq, &changes, &mut events, None)? > 0 && tx.blocking_send(()).is_err() {
+            return Ok(());
+        }
+    }
+}
+
+/// Polls the modification times of the directory entries, signalling each change
+#[cfg(not(target_os = "freebsd"))]
+fn watch_blocking(dir: &Path, _file: &Path, tx: mpsc::Sender<()>) -> Result<(), std::io::Error> {
+    use std::{collections::BTreeMap, time::SystemTime};
+
+    let snapshot = |dir: &Path| -> Result<BTreeMap<PathBuf, SystemTime>, std::io::Error> {
+        let mut entries = BTreeMap::new();
+        for entry in std::fs::read_dir(dir)? {
+            let entry = entry?;
+            if let Ok(modified) = entry.metadata().and_then(|m| m.modified()) {
+                entries.insert(entry.path(), modified);
+            }
+        }
+        Ok(entries)
+    };
+
+    let mut last = snapshot(dir)?;
+    loop {
+        std::thread::sleep(POLL_INTERVAL);
+        let current = snapshot(dir)?;
+        if current != last {
+            last = current;
+            if tx.blocking_send(()).is_err() {
+                return Ok(());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn write_config(path: &Path, config: &AppConfig) {
+        std::fs::write(path, serde_json::to_string(config).unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_reload_applies_only_hot_sections() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.json");
+        let config = AppConfig::new(None);
+        write_config(&path, &config);
+
+        let watcher = ConfigWatcher::new(path.clone(), config.clone());
+        let applied = AtomicUsize::new(0);
+        let apply = |_: &AppConfig| {
+            applied.fetch_add(1, Ordering::SeqCst);
+        };
+        assert_eq!(watcher.reload(&apply).outcome, ConfigChangeOutcome::Unchanged);
+        assert!(watcher.dry_run().unwrap().is_empty());
+
+        let mut hot = config.clone();
+        hot.dead_letters.max_attempts += 1;
+        write_config(&path, &hot);
+        assert_eq!(watcher.dry_run().unwrap().sections(), vec!["dead_letters".to_string()]);
+        assert_eq!(applied.load(Ordering::SeqCst), 0);
+        let change = watcher.reload(&apply);
+        assert_eq!(change.outcome, ConfigChangeOutcome::Applied);
+        assert_eq!(change.sections, vec!["dead_letters".to_string()]);
+        assert_eq!(applied.load(Ordering::SeqCst), 1);
+        assert_eq!(applied_config(&path).unwrap().dead_letters.max_attempts, hot.dead_letters.max_attempts);
+
+        let mut cold = hot.clone();
+        cold.max_threads += 1;
+        write_config(&path, &cold);
+        assert_eq!(watcher.reload(&apply).outcome, ConfigChangeOutcome::RestartRequired);
+        assert_eq!(watcher.current().max_threads, hot.max_threads);
+
+        std::fs::write(&path, "{ not json").unwrap();
+        assert_eq!(watcher.reload(&apply).outcome, ConfigChangeOutcome::Invalid);
+        assert_eq!(applied.load(Ordering::SeqCst), 1);
+    }
+}
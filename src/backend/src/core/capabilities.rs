@@ -0,0 +1,265 @@
+use std::{collections::BTreeSet, fmt};
+
+use metrics::gauge;
+use once_cell::sync::OnceCell;
+use serde::Serialize;
+use tracing::{info, warn};
+
+use crate::utils::error::{ErrorCategory, GuardianError};
+use crate::FeatureFlags;
+
+/// Components wired against optional capabilities
+pub const THREAT_DETECTION: &str = "threat_detection";
+pub const ANOMALY_DETECTION: &str = "anomaly_detection";
+pub const MODEL_MANAGEMENT: &str = "model_management";
+pub const RESPONSE_ENGINE: &str = "response_engine";
+pub const AUDIT_LOG: &str = "audit_log";
+pub const METRICS_EXPORT: &str = "metrics_export";
+pub const POSTURE: &str = "posture";
+
+/// Optional functionality that can be switched off with `FeatureFlags`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Capability {
+    Ml,
+    Audit,
+    Metrics,
+    SecureBoot,
+}
+
+impl Capability {
+    pub const ALL: [Capability; 4] = [Capability::Ml, Capability::Audit, Capability::Metrics, Capability::SecureBoot];
+
+    fn enabled_by(&self, flags: &FeatureFlags) -> bool {
+        match self {
+            Capability::Ml => flags.ml_enabled,
+            Capability::Audit => flags.audit_logging,
+            Capability::Metrics => flags.performance_metrics,
+            Capability::SecureBoot => flags.secure_boot,
+        }
+    }
+}
+
+impl fmt::Display for Capability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Capability::Ml => "ml",
+            Capability::Audit => "audit",
+            Capability::Metrics => "metrics",
+            Capability::SecureBoot => "secure_boot",
+        };
+        f.write_str(name)
+    }
+}
+
+/// How a component depends on a capability
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Requirement {
+    /// The component cannot run without it
+    Required,
+    /// The component switches to the documented fallback without it
+    Optional { fallback: &'static str },
+}
+
+/// A component and the capabilities it is wired against
+#[derive(Debug, Clone, Copy)]
+pub struct ComponentSpec {
+    pub name: &'static str,
+    pub dependencies: &'static [(Capability, Requirement)],
+}
+
+/// The degradation matrix: what every component does when a capability is disabled
+pub const COMPONENTS: &[ComponentSpec] = &[
+    ComponentSpec {
+        name: THREAT_DETECTION,
+        dependencies: &[(
+            Capability::Ml,
+            Requirement::Optional {
+                fallback: "model stages are skipped; pre-filter, enrichment and aggregation stages still run",
+            },
+        )],
+    },
+    ComponentSpec {
+        name: ANOMALY_DETECTION,
+        dependencies: &[(Capability::Ml, Requirement::Required)],
+    },
+    ComponentSpec {
+        name: MODEL_MANAGEMENT,
+        dependencies: &[(Capability::Ml, Requirement::Required)],
+    },
+    ComponentSpec {
+        name: RESPONSE_ENGINE,
+        dependencies: &[(
+            Capability::Audit,
+            Requirement::Optional { fallback: "executed responses are recorded in the SECURITY-AUDIT log only" },
+        )],
+    },
+    ComponentSpec {
+        name: AUDIT_LOG,
+        dependencies: &[(
+            Capability::Audit,
+            Requirement::Optional {
+                fallback: "events go to the SECURITY-AUDIT log target instead of the FreeBSD audit trail",
+            },
+        )],
+    },
+    ComponentSpec {
+        name: METRICS_EXPORT,
+        dependencies: &[(Capability::Metrics, Requirement::Required)],
+    },
+    ComponentSpec {
+        name: POSTURE,
+        dependencies: &[
+            (
+                Capability::Metrics,
+                Requirement::Optional { fallback: "the current score is served but no history is stored" },
+            ),
+            (
+                Capability::SecureBoot,
+                Requirement::Optional { fallback: "secure boot is reported as a failed configuration check" },
+            ),
+        ],
+    },
+];
+
+/// How a component runs given the enabled capabilities
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum ComponentMode {
+    Full,
+    /// Running on the fallbacks of its disabled optional capabilities
+    Degraded { fallbacks: Vec<String> },
+    /// A required capability is disabled
+    Unavailable { missing: Vec<Capability> },
+}
+
+/// Enabled capabilities and the modes they put each component in
+#[derive(Debug, Clone)]
+pub struct CapabilityMatrix {
+    enabled: BTreeSet<Capability>,
+}
+
+impl Default for CapabilityMatrix {
+    fn default() -> Self {
+        Self {
+            enabled: Capability::ALL.into_iter().collect(),
+        }
+    }
+}
+
+static CAPABILITIES: OnceCell<CapabilityMatrix> = OnceCell::new();
+
+/// Installs the process-wide matrix from feature flags; the first installation wins
+pub fn init_capabilities(flags: &FeatureFlags) -> &'static CapabilityMatrix {
+    let matrix = CAPABILITIES.get_or_init(|| CapabilityMatrix::from_flags(flags));
+    for (name, mode) in matrix.report() {
+        match &mode {
+            ComponentMode::Full => {}
+            ComponentMode::Degraded { fallbacks } => {
+                warn!(component = name, fallbacks = ?fallbacks, "Component degraded by disabled capabilities")
+            }
+            ComponentMode::Unavailable { missing } => {
+                warn!(component = name, missing = ?missing, "Component unavailable, required capabilities disabled")
+            }
+        }
+    }
+    for capability in Capability::ALL {
+        gauge!("guardian.capability.enabled", matrix.is_enabled(capability) as u8 as f64, "capability" => capability.to_string());
+    }
+    info!(enabled = ?matrix.enabled, "Capabilities installed");
+    matrix
+}
+
+/// Returns the installed matrix, with every capability enabled if none was installed
+pub fn capabilities() -> &'static CapabilityMatrix {
+    static ALL_ENABLED: once_cell::sync::Lazy<CapabilityMatrix> = once_cell::sync::Lazy::new(CapabilityMatrix::default);
+    CAPABILITIES.get().unwrap_or(&ALL_ENABLED)
+}
+
+impl CapabilityMatrix {
+    /// Builds the matrix enabled by the feature flags
+    pub fn from_flags(flags: &FeatureFlags) -> Self {
+        Self {
+            enabled: Capability::ALL.into_iter().filter(|c| c.enabled_by(flags)).collect(),
+        }
+    }
+
+    /// Whether a capability is enabled
+    pub fn is_enabled(&self, capability: Capability) -> bool {
+        self.enabled.contains(&capability)
+    }
+
+    /// Returns how a component runs; components missing from the matrix run in full
+    pub fn mode(&self, component: &str) -> ComponentMode {
+        let Some(spec) = COMPONENTS.iter().find(|c| c.name == component) else {
+            return ComponentMode::Full;
+        };
+
+        let mut missing = Vec::new();
+        let mut fallbacks = Vec::new();
+        for (capability, requirement) in spec.dependencies {
+            if self.is_enabled(*capability) {
+                continue;
+            }
+            match requirement {
+                Requirement::Required => missing.push(*capability),
+                Requirement::Optional { fallback } => fallbacks.push(format!("{}: {}", capability, fallback)),
+            }
+        }
+
+        if !missing.is_empty() {
+            ComponentMode::Unavailable { missing }
+        } else if !fallbacks.is_empty() {
+            ComponentMode::Degraded { fallbacks }
+        } else {
+            ComponentMode::Full
+        }
+    }
+
+    /// Fails with a validation error naming the disabled capabilities if the component cannot run
+    pub fn require(&self, component: &str) -> Result<(), GuardianError> {
+        match self.mode(component) {
+            ComponentMode::Unavailable { missing } => Err(GuardianError::ValidationError {
+                context: format!(
+                    "{} is unavailable: required capabilities disabled: {}",
+                    component,
+                    missing.iter().map(|c| c.to_string()).collect::<Vec<_>>().join(", ")
+                ),
+                source: None,
+                severity: crate::utils::error::ErrorSeverity::Medium,
+                timestamp: time::OffsetDateTime::now_utc(),
+                correlation_id: crate::utils::correlation::current_or_new(),
+                category: ErrorCategory::Validation,
+                retry_count: 0,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the mode of every component in the matrix
+    pub fn report(&self) -> Vec<(&'static str, ComponentMode)> {
+        COMPONENTS.iter().map(|c| (c.name, self.mode(c.name))).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disabled_capabilities_degrade_or_disable_dependents() {
+        let flags = FeatureFlags {
+            ml_enabled: false,
+            performance_metrics: false,
+            ..FeatureFlags::default()
+        };
+        let matrix = CapabilityMatrix::from_flags(&flags);
+
+        assert!(matches!(matrix.mode(THREAT_DETECTION), ComponentMode::Degraded { .. }));
+        assert_eq!(matrix.mode(ANOMALY_DETECTION), ComponentMode::Unavailable { missing: vec![Capability::Ml] });
+        assert_eq!(matrix.mode(RESPONSE_ENGINE), ComponentMode::Full);
+        assert!(matrix.require(MODEL_MANAGEMENT).is_err());
+        assert!(matrix.require(POSTURE).is_ok());
+        assert_eq!(CapabilityMatrix::default().mode(ANOMALY_DETECTION), ComponentMode::Full);
+    }
+}
@@ -0,0 +1,100 @@
+//! Kernel counter snapshots from sysctl(8)
+
+use std::{collections::HashMap, time::Instant};
+
+use async_trait::async_trait;
+use parking_lot::Mutex;
+
+use crate::core::collectors::{run_tool, Collector, Snapshot};
+use crate::utils::error::GuardianError;
+
+/// Monotonic counters, reported as rates per second
+const RATE_COUNTERS: &[&str] = &[
+    "vm.stats.sys.v_swtch",
+    "vm.stats.sys.v_syscall",
+    "vm.stats.sys.v_intr",
+    "vm.stats.sys.v_trap",
+    "vm.stats.vm.v_forks",
+    "vm.stats.vm.v_vforks",
+    "vm.stats.vm.v_swapin",
+    "vm.stats.vm.v_swapout",
+];
+/// Gauges, reported as read
+const GAUGES: &[&str] = &[
+    "kern.openfiles",
+    "kern.maxfiles",
+    "kern.proc.nprocs",
+    "kern.lastpid",
+    "vm.stats.vm.v_free_count",
+    "vm.stats.vm.v_page_count",
+];
+
+/// Samples scheduler, VM and file table counters; reports metrics only, no records
+#[derive(Debug)]
+pub struct KernelCounterCollector {
+    previous: Mutex<Option<(Instant, HashMap<String, f64>)>>,
+}
+
+impl KernelCounterCollector {
+    pub fn new() -> Self {
+        Self { previous: Mutex::new(None) }
+    }
+}
+
+impl Default for KernelCounterCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Collector for KernelCounterCollector {
+    fn name(&self) -> &'static str {
+        "kernel"
+    }
+
+    async fn collect(&self, _max_records: usize) -> Result<Snapshot, GuardianError> {
+        // -e prints name=value; unknown names are skipped by -i
+        let mut args = vec!["-e", "-i"];
+        args.extend(RATE_COUNTERS.iter().chain(GAUGES));
+        let output = run_tool("sysctl", &args).await?;
+        let values = parse_sysctl(&output);
+        let now = Instant::now();
+
+        let mut snapshot = Snapshot::default();
+        for name in GAUGES {
+            if let Some(value) = values.get(*name) {
+                snapshot.metrics.insert(format!("kernel.{}", name), *value);
+            }
+        }
+
+        // Rates need two readings, so the first snapshot reports gauges only
+        let mut previous = self.previous.lock();
+        if let Some((then, last)) = previous.as_ref() {
+            let elapsed = now.duration_since(*then).as_secs_f64().max(f64::EPSILON);
+            for name in RATE_COUNTERS {
+                if let (Some(current), Some(before)) = (values.get(*name), last.get(*name)) {
+                    // Counters wrap; a negative delta is a wrap, not a rate
+                    let rate = if current >= before { (current - before) / elapsed } else { 0.0 };
+                    snapshot.metrics.insert(format!("kernel.{}_per_sec", name), rate);
+                }
+            }
+        }
+
+        if let (Some(open), Some(max)) = (values.get("kern.openfiles"), values.get("kern.maxfiles")) {
+            if *max > 0.0 {
+                snapshot.metrics.insert("kernel.file_table_usage".into(), open / max);
+            }
+        }
+        *previous = Some((now, values));
+        Ok(snapshot)
+    }
+}
+
+fn parse_sysctl(output: &str) -> HashMap<String, f64> {
+    output
+        .lines()
+        .filter_map(|line| line.split_once('='))
+        .filter_map(|(name, value)| Some((name.trim().to_string(), value.trim().parse().ok()?)))
+        .collect()
+}
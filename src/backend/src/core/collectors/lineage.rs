@@ -0,0 +1,237 @@
+//! Parent/child tree of host processes with the programs each one executed
+//!
+//! The process collector feeds every process table snapshot into the tree. A process whose
+//! command line changes between snapshots while keeping its parent has exec'd a new program,
+//! which is appended to its exec history; a PID that reappears under another parent was
+//! reused and starts a fresh node. Exited processes are kept for a while so the ancestry of a
+//! short-lived child still names the parent that spawned it.
+
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    sync::Arc,
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use metrics::gauge;
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+
+/// Programs remembered per process, oldest dropped first
+const MAX_EXEC_HISTORY: usize = 8;
+/// Ancestors reported at most, guarding against PID cycles in a racy snapshot
+const MAX_ANCESTRY_DEPTH: usize = 32;
+/// Children listed at most per process
+const MAX_CHILDREN: usize = 64;
+/// How long an exited process stays in the tree
+const EXITED_RETENTION: Duration = Duration::from_secs(15 * 60);
+
+static PROCESS_TREE: Lazy<Arc<ProcessTree>> = Lazy::new(|| Arc::new(ProcessTree::new(EXITED_RETENTION)));
+
+/// Returns the process-wide lineage tracker
+pub fn process_tree() -> Arc<ProcessTree> {
+    Arc::clone(&PROCESS_TREE)
+}
+
+/// One row of a process table snapshot
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObservedProcess {
+    pub pid: u32,
+    pub ppid: u32,
+    pub uid: u32,
+    pub command: String,
+}
+
+/// A program a process executed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExecRecord {
+    pub command: String,
+    /// First snapshot the program was seen in
+    pub seen_at: DateTime<Utc>,
+}
+
+/// What the tree knows about one process
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProcessRecord {
+    pub pid: u32,
+    pub ppid: u32,
+    pub uid: u32,
+    /// Current command line
+    pub command: String,
+    pub first_seen: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exited_at: Option<DateTime<Utc>>,
+    /// Programs executed, oldest first, ending with the current one
+    #[serde(default)]
+    pub exec_history: Vec<ExecRecord>,
+}
+
+/// Ancestry of a process, attached to threats and audit events
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProcessLineage {
+    pub process: ProcessRecord,
+    /// Parent first, up to the root of the tree or the depth limit
+    pub ancestors: Vec<ProcessRecord>,
+    /// PIDs of the direct children still known
+    pub children: Vec<u32>,
+}
+
+#[derive(Debug)]
+struct ProcessNode {
+    record: ProcessRecord,
+    history: VecDeque<ExecRecord>,
+}
+
+impl ProcessNode {
+    fn new(observed: &ObservedProcess, now: DateTime<Utc>) -> Self {
+        let mut history = VecDeque::with_capacity(MAX_EXEC_HISTORY);
+        history.push_back(ExecRecord { command: observed.command.clone(), seen_at: now });
+        Self {
+            record: ProcessRecord {
+                pid: observed.pid,
+                ppid: observed.ppid,
+                uid: observed.uid,
+                command: observed.command.clone(),
+                first_seen: now,
+                exited_at: None,
+                exec_history: Vec::new(),
+            },
+            history,
+        }
+    }
+
+    fn snapshot(&self) -> ProcessRecord {
+        ProcessRecord {
+            exec_history: self.history.iter().cloned().collect(),
+            ..self.record.clone()
+        }
+    }
+}
+
+/// Tracks the process tree across collector snapshots
+#[derive(Debug)]
+pub struct ProcessTree {
+    nodes: RwLock<HashMap<u32, ProcessNode>>,
+    retention: Duration,
+}
+
+impl ProcessTree {
+    pub fn new(retention: Duration) -> Self {
+        Self {
+            nodes: RwLock::new(HashMap::new()),
+            retention,
+        }
+    }
+
+    /// Folds in a full process table; processes missing from it are marked exited
+    pub fn observe(&self, processes: &[ObservedProcess], now: DateTime<Utc>) {
+        let mut nodes = self.nodes.write();
+        let mut live = HashSet::with_capacity(processes.len());
+
+        for observed in processes {
+            live.insert(observed.pid);
+            match nodes.get_mut(&observed.pid) {
+                // A new parent, or a PID that had exited, means the PID was reused
+                Some(node) if node.record.ppid != observed.ppid || node.record.exited_at.is_some() => {
+                    *node = ProcessNode::new(observed, now);
+                }
+                Some(node) => {
+                    if node.record.command != observed.command {
+                        if node.history.len() == MAX_EXEC_HISTORY {
+                            node.history.pop_front();
+                        }
+                        node.history.push_back(ExecRecord { command: observed.command.clone(), seen_at: now });
+                        node.record.command = observed.command.clone();
+                    }
+                    node.record.uid = observed.uid;
+                }
+                None => {
+                    nodes.insert(observed.pid, ProcessNode::new(observed, now));
+                }
+            }
+        }
+
+        let retention = chrono::Duration::from_std(self.retention).unwrap_or_else(|_| chrono::Duration::zero());
+        nodes.retain(|pid, node| {
+            if live.contains(pid) {
+                return true;
+            }
+            let exited_at = *node.record.exited_at.get_or_insert(now);
+            now - exited_at < retention
+        });
+
+        gauge!("guardian.collectors.lineage.tracked", nodes.len() as f64);
+        gauge!("guardian.collectors.lineage.exited", nodes.values().filter(|n| n.record.exited_at.is_some()).count() as f64);
+    }
+
+    /// Ancestry of a process, or `None` when it was never seen or has been forgotten
+    pub fn lineage(&self, pid: u32) -> Option<ProcessLineage> {
+        let nodes = self.nodes.read();
+        let process = nodes.get(&pid)?.snapshot();
+
+        let mut ancestors = Vec::new();
+        let mut visited = HashSet::from([pid]);
+        let mut parent = process.ppid;
+        while ancestors.len() < MAX_ANCESTRY_DEPTH && visited.insert(parent) {
+            let Some(node) = nodes.get(&parent) else {
+                break;
+            };
+            ancestors.push(node.snapshot());
+            parent = node.record.ppid;
+        }
+
+        let mut children: Vec<u32> = nodes
+            .values()
+            .filter(|node| node.record.ppid == pid && node.record.pid != pid)
+            .map(|node| node.record.pid)
+            .collect();
+        children.sort_unstable();
+        children.truncate(MAX_CHILDREN);
+
+        Some(ProcessLineage { process, ancestors, children })
+    }
+
+    /// Number of processes tracked, live or recently exited
+    pub fn len(&self) -> usize {
+        self.nodes.read().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.read().is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn process(pid: u32, ppid: u32, command: &str) -> ObservedProcess {
+        ObservedProcess { pid, ppid, uid: 0, command: command.to_string() }
+    }
+
+    #[test]
+    fn test_lineage_follows_execs_exits_and_reuse() {
+        let tree = ProcessTree::new(Duration::from_secs(60));
+        let start = Utc::now();
+        tree.observe(&[process(1, 0, "init"), process(10, 1, "sshd"), process(20, 10, "sh")], start);
+
+        // The shell exec's curl and the sshd parent exits
+        let later = start + chrono::Duration::seconds(30);
+        tree.observe(&[process(1, 0, "init"), process(20, 10, "curl http://x")], later);
+        let lineage = tree.lineage(20).unwrap();
+        let commands: Vec<&str> = lineage.process.exec_history.iter().map(|e| e.command.as_str()).collect();
+        assert_eq!(commands, vec!["sh", "curl http://x"]);
+        let ancestors: Vec<u32> = lineage.ancestors.iter().map(|a| a.pid).collect();
+        assert_eq!(ancestors, vec![10, 1]);
+        assert_eq!(lineage.ancestors[0].exited_at, Some(later));
+        assert_eq!(tree.lineage(10).unwrap().children, vec![20]);
+
+        // Past the retention the exited parent is forgotten and a reused PID starts over
+        let much_later = later + chrono::Duration::seconds(61);
+        tree.observe(&[process(1, 0, "init"), process(20, 1, "cron")], much_later);
+        let lineage = tree.lineage(20).unwrap();
+        assert_eq!(lineage.process.exec_history.len(), 1);
+        assert!(tree.lineage(10).is_none());
+    }
+}
@@ -0,0 +1,322 @@
+//! Structured collection of host data for the detection pipeline
+//!
+//! Each collector snapshots one aspect of the host — the process table, network
+//! connections, open files, kernel counters — on its own interval. A detection cycle
+//! runs the collectors that are due, each bounded by a timeout and a record cap, and
+//! hands every fresh snapshot to the pipeline as one `SystemData` sample. Collectors
+//! that are not due contribute nothing, so a snapshot is analyzed exactly once.
+
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::Instant,
+};
+
+use async_trait::async_trait;
+use metrics::{counter, histogram};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use tokio::task::JoinSet;
+use tracing::{debug, warn};
+
+use crate::config::{AppConfig, CollectorConfig, CollectorsConfig};
+use crate::security::anomaly_detection::SystemData;
+use crate::security::degradation::degradation;
+use crate::utils::error::GuardianError;
+
+pub mod kernel;
+pub mod lineage;
+pub mod network;
+pub mod open_files;
+pub mod processes;
+pub mod shared_memory;
+
+pub use kernel::KernelCounterCollector;
+pub use lineage::{process_tree, ExecRecord, ObservedProcess, ProcessLineage, ProcessRecord, ProcessTree};
+pub use network::NetworkCollector;
+pub use open_files::OpenFileCollector;
+pub use processes::ProcessCollector;
+pub use shared_memory::{SharedMemoryCollector, TelemetryRecord, TelemetryRing};
+
+// Constants for system data collection
+/// Built-in collectors, in the order their index is reported in `collector.source`
+pub const SOURCE_NAMES: &[&str] = &["processes", "network", "open_files", "kernel"];
+/// Metric naming the collector a sample came from, as an index into `SOURCE_NAMES`
+pub const SOURCE_METRIC: &str = "collector.source";
+/// Metric set to 1 when a snapshot hit its record cap
+pub const TRUNCATED_METRIC: &str = "collector.truncated";
+
+static COLLECTORS: Lazy<Arc<SystemCollectors>> = Lazy::new(|| Arc::new(SystemCollectors::with_defaults(&CollectorsConfig::default())));
+
+/// Returns the process-wide system data collectors
+pub fn collectors() -> Arc<SystemCollectors> {
+    Arc::clone(&COLLECTORS)
+}
+
+/// Applies the collector settings of the configuration; call again after a reload
+///
+/// The shared-memory ring is created the first time it is enabled and kept across reloads, as
+/// sensors hold it mapped.
+pub fn init_collectors(config: &AppConfig) {
+    let set = collectors();
+    let shared_memory = &config.collectors.shared_memory;
+    if shared_memory.collector.enabled && !set.names().contains(&shared_memory::NAME) {
+        match SharedMemoryCollector::open(shared_memory) {
+            Ok(collector) => {
+                let collector = Arc::new(collector);
+                Arc::clone(&collector).watch(Arc::clone(&set));
+                set.register(collector, shared_memory.collector.clone());
+            }
+            Err(e) => warn!(error = %e, "Shared-memory telemetry ring unavailable"),
+        }
+    }
+    set.configure(&config.collectors);
+}
+
+/// One snapshot of a collector
+#[derive(Debug, Clone, Default)]
+pub struct Snapshot {
+    pub metrics: HashMap<String, f64>,
+    /// One `kind key=value ...` line per record
+    pub events: Vec<String>,
+    /// Records dropped by the cap
+    pub dropped: usize,
+}
+
+impl Snapshot {
+    /// Adds a record unless the cap is reached, counting it as dropped otherwise
+    pub fn push_event(&mut self, max_records: usize, event: String) {
+        if self.events.len() < max_records {
+            self.events.push(event);
+        } else {
+            self.dropped += 1;
+        }
+    }
+
+    fn into_system_data(mut self, source: &str) -> SystemData {
+        let source_index = SOURCE_NAMES.iter().position(|name| *name == source).unwrap_or(SOURCE_NAMES.len());
+        self.metrics.insert(SOURCE_METRIC.to_string(), source_index as f64);
+        self.metrics.insert(TRUNCATED_METRIC.to_string(), if self.dropped > 0 { 1.0 } else { 0.0 });
+        SystemData {
+            metrics: self.metrics,
+            events: self.events,
+            timestamp: chrono::Utc::now().timestamp(),
+        }
+    }
+}
+
+/// A source of host data
+#[async_trait]
+pub trait Collector: Send + Sync {
+    /// Name used in configuration, metric labels and metric prefixes
+    fn name(&self) -> &'static str;
+
+    /// Takes one snapshot, keeping at most `max_records` events
+    async fn collect(&self, max_records: usize) -> Result<Snapshot, GuardianError>;
+}
+
+struct Scheduled {
+    collector: Arc<dyn Collector>,
+    config: CollectorConfig,
+    next_due: Instant,
+}
+
+/// Runs the registered collectors on their sampling intervals
+pub struct SystemCollectors {
+    scheduled: Mutex<Vec<Scheduled>>,
+}
+
+impl std::fmt::Debug for SystemCollectors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let names: Vec<&str> = self.scheduled.lock().iter().map(|s| s.collector.name()).collect();
+        f.debug_struct("SystemCollectors").field("collectors", &names).finish()
+    }
+}
+
+impl SystemCollectors {
+    /// Creates an empty set; collectors are added with `register`
+    pub fn new() -> Self {
+        Self { scheduled: Mutex::new(Vec::new()) }
+    }
+
+    /// Creates the set of built-in collectors
+    pub fn with_defaults(config: &CollectorsConfig) -> Self {
+        let set = Self::new();
+        set.register(Arc::new(ProcessCollector), config.processes.clone());
+        set.register(Arc::new(NetworkCollector), config.network.clone());
+        set.register(Arc::new(OpenFileCollector), config.open_files.clone());
+        set.register(Arc::new(KernelCounterCollector::new()), config.kernel.clone());
+        set
+    }
+
+    /// Adds a collector, replacing one of the same name; it is first run on the next cycle
+    pub fn register(&self, collector: Arc<dyn Collector>, config: CollectorConfig) {
+        let mut scheduled = self.scheduled.lock();
+        scheduled.retain(|s| s.collector.name() != collector.name());
+        scheduled.push(Scheduled {
+            collector,
+            config,
+            next_due: Instant::now(),
+        });
+    }
+
+    /// Updates the settings of the built-in collectors
+    pub fn configure(&self, config: &CollectorsConfig) {
+        for scheduled in self.scheduled.lock().iter_mut() {
+            if let Some(settings) = config.for_collector(scheduled.collector.name()) {
+                if *settings != scheduled.config {
+                    // Interval changes apply from the next snapshot on
+                    scheduled.next_due = scheduled.next_due.min(Instant::now() + settings.interval);
+                    scheduled.config = settings.clone();
+                }
+            }
+        }
+    }
+
+    /// Makes a collector due on the next cycle, for sources that signal pending data
+    pub fn wake(&self, name: &str) {
+        if let Some(scheduled) = self.scheduled.lock().iter_mut().find(|s| s.collector.name() == name) {
+            scheduled.next_due = Instant::now();
+        }
+    }
+
+    /// Names of the registered collectors
+    pub fn names(&self) -> Vec<&'static str> {
+        self.scheduled.lock().iter().map(|s| s.collector.name()).collect()
+    }
+
+    /// Runs the collectors that are due and returns one sample per fresh snapshot
+    ///
+    /// A collector that fails or times out is skipped for this interval; the others still report.
+    /// While detection is degraded to reduced sampling, intervals are stretched.
+    pub async fn collect(&self) -> Vec<SystemData> {
+        let now = Instant::now();
+        let interval_factor = degradation().sampling_interval_factor();
+        let due: Vec<(Arc<dyn Collector>, CollectorConfig)> = {
+            let mut scheduled = self.scheduled.lock();
+            scheduled
+                .iter_mut()
+                .filter(|s| s.config.enabled && s.next_due <= now)
+                .map(|s| {
+                    s.next_due = now + s.config.interval.mul_f64(interval_factor);
+                    (Arc::clone(&s.collector), s.config.clone())
+                })
+                .collect()
+        };
+
+        let mut runs = JoinSet::new();
+        for (collector, config) in due {
+            runs.spawn(async move {
+                let name = collector.name();
+                let start = Instant::now();
+                let result = tokio::time::timeout(config.timeout, collector.collect(config.max_records)).await;
+                histogram!("guardian.collectors.duration", start.elapsed().as_secs_f64(), "collector" => name);
+                (name, result)
+            });
+        }
+
+        let mut samples = Vec::new();
+        while let Some(joined) = runs.join_next().await {
+            let Ok((name, result)) = joined else {
+                continue;
+            };
+            match result {
+                Ok(Ok(snapshot)) => {
+                    if snapshot.dropped > 0 {
+                        counter!("guardian.collectors.dropped_records", snapshot.dropped as u64, "collector" => name);
+                        debug!(collector = name, dropped = snapshot.dropped, "Collector hit its record cap");
+                    }
+                    counter!("guardian.collectors.snapshots", 1, "collector" => name, "result" => "ok");
+                    samples.push(snapshot.into_system_data(name));
+                }
+                Ok(Err(e)) => {
+                    counter!("guardian.collectors.snapshots", 1, "collector" => name, "result" => "error");
+                    warn!(collector = name, error = %e, "Collector snapshot failed");
+                }
+                Err(_) => {
+                    counter!("guardian.collectors.snapshots", 1, "collector" => name, "result" => "timeout");
+                    warn!(collector = name, "Collector snapshot timed out");
+                }
+            }
+        }
+        samples
+    }
+}
+
+impl Default for SystemCollectors {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Runs a host tool for a collector, returning its standard output
+pub(crate) async fn run_tool(program: &str, args: &[&str]) -> Result<String, GuardianError> {
+    let output = tokio::process::Command::new(program)
+        .args(args)
+        .kill_on_drop(true)
+        .output()
+        .await
+        .map_err(|e| collector_error(format!("Failed to run {}", program), Some(Box::new(e))))?;
+    if !output.status.success() {
+        return Err(collector_error(
+            format!("{} failed: {}", program, String::from_utf8_lossy(&output.stderr).trim()),
+            None,
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+pub(crate) fn collector_error(context: String, source: Option<Box<dyn std::error::Error + Send + Sync>>) -> GuardianError {
+    GuardianError::SystemError {
+        context,
+        source,
+        severity: crate::utils::error::ErrorSeverity::Medium,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: crate::utils::error::ErrorCategory::System,
+        retry_count: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    struct Fixed;
+
+    #[async_trait]
+    impl Collector for Fixed {
+        fn name(&self) -> &'static str {
+            "fixed"
+        }
+
+        async fn collect(&self, max_records: usize) -> Result<Snapshot, GuardianError> {
+            let mut snapshot = Snapshot::default();
+            for pid in 0..3 {
+                snapshot.push_event(max_records, format!("process pid={}", pid));
+            }
+            Ok(snapshot)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_collects_due_collectors_within_caps() {
+        let set = SystemCollectors::new();
+        let config = CollectorConfig {
+            enabled: true,
+            interval: Duration::from_secs(3600),
+            max_records: 2,
+            timeout: Duration::from_secs(1),
+        };
+        set.register(Arc::new(Fixed), config);
+
+        let samples = set.collect().await;
+        assert_eq!(samples.len(), 1);
+        assert_eq!(samples[0].events, vec!["process pid=0".to_string(), "process pid=1".to_string()]);
+        assert_eq!(samples[0].metrics[TRUNCATED_METRIC], 1.0);
+
+        // Not due again until the interval passes
+        assert!(set.collect().await.is_empty());
+    }
+}
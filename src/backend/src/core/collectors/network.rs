@@ -0,0 +1,72 @@
+//! Connected socket snapshots from sockstat(1)
+
+use std::collections::HashSet;
+
+use async_trait::async_trait;
+
+use crate::core::collectors::{run_tool, Collector, Snapshot};
+use crate::utils::error::GuardianError;
+
+/// Lists connected IPv4 and IPv6 sockets with their owning process
+#[derive(Debug, Default)]
+pub struct NetworkCollector;
+
+#[async_trait]
+impl Collector for NetworkCollector {
+    fn name(&self) -> &'static str {
+        "network"
+    }
+
+    async fn collect(&self, max_records: usize) -> Result<Snapshot, GuardianError> {
+        // -c: connected sockets only, -q: no header
+        let output = run_tool("sockstat", &["-4", "-6", "-c", "-q"]).await?;
+        Ok(parse_sockstat(&output, max_records))
+    }
+}
+
+/// Splits `addr:port`, with or without brackets around IPv6 addresses
+fn split_endpoint(endpoint: &str) -> (&str, &str) {
+    match endpoint.rsplit_once(':') {
+        Some((addr, port)) => (addr.trim_start_matches('[').trim_end_matches(']'), port),
+        None => (endpoint, ""),
+    }
+}
+
+fn parse_sockstat(output: &str, max_records: usize) -> Snapshot {
+    let mut snapshot = Snapshot::default();
+    let mut remote_hosts = HashSet::new();
+    let (mut count, mut tcp, mut udp) = (0u64, 0u64, 0u64);
+
+    for line in output.lines() {
+        // USER COMMAND PID FD PROTO LOCAL FOREIGN
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() < 7 {
+            continue;
+        }
+        let (user, command, pid, proto) = (fields[0], fields[1], fields[2], fields[4]);
+        let (local_addr, local_port) = split_endpoint(fields[5]);
+        let (remote_addr, remote_port) = split_endpoint(fields[6]);
+
+        count += 1;
+        if proto.starts_with("tcp") {
+            tcp += 1;
+        } else if proto.starts_with("udp") {
+            udp += 1;
+        }
+        remote_hosts.insert(remote_addr.to_string());
+        // Addresses and ports are separate fields so indicator matching sees bare addresses
+        snapshot.push_event(
+            max_records,
+            format!(
+                "connection proto={} pid={} comm={} user={} local_addr={} local_port={} remote_addr={} remote_port={}",
+                proto, pid, command, user, local_addr, local_port, remote_addr, remote_port
+            ),
+        );
+    }
+
+    snapshot.metrics.insert("network.connections".into(), count as f64);
+    snapshot.metrics.insert("network.tcp".into(), tcp as f64);
+    snapshot.metrics.insert("network.udp".into(), udp as f64);
+    snapshot.metrics.insert("network.remote_hosts".into(), remote_hosts.len() as f64);
+    snapshot
+}
@@ -0,0 +1,67 @@
+//! Process table snapshots from ps(1)
+
+use async_trait::async_trait;
+
+use crate::core::collectors::lineage::{process_tree, ObservedProcess};
+use crate::core::collectors::{run_tool, Collector, Snapshot};
+use crate::utils::error::GuardianError;
+
+// Fields before the free-form command line
+const FIXED_FIELDS: usize = 6;
+
+/// Lists every process with its parent, owner, state and resource use, and feeds the
+/// process tree used for lineage
+#[derive(Debug, Default)]
+pub struct ProcessCollector;
+
+#[async_trait]
+impl Collector for ProcessCollector {
+    fn name(&self) -> &'static str {
+        "processes"
+    }
+
+    async fn collect(&self, max_records: usize) -> Result<Snapshot, GuardianError> {
+        let output = run_tool("ps", &["-axww", "-o", "pid=,ppid=,uid=,state=,pcpu=,rss=,command="]).await?;
+        let (snapshot, processes) = parse_ps(&output, max_records);
+        // Lineage sees the whole table, not just the records kept under the cap
+        process_tree().observe(&processes, chrono::Utc::now());
+        Ok(snapshot)
+    }
+}
+
+fn parse_ps(output: &str, max_records: usize) -> (Snapshot, Vec<ObservedProcess>) {
+    let mut snapshot = Snapshot::default();
+    let mut processes = Vec::new();
+    let (mut count, mut zombies, mut cpu, mut rss_kb) = (0u64, 0u64, 0.0, 0.0);
+
+    for line in output.lines() {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() < FIXED_FIELDS {
+            continue;
+        }
+        let (pid, ppid, uid, state) = (fields[0], fields[1], fields[2], fields[3]);
+        let pcpu: f64 = fields[4].parse().unwrap_or(0.0);
+        let rss: f64 = fields[5].parse().unwrap_or(0.0);
+        let command = fields[FIXED_FIELDS..].join(" ");
+
+        count += 1;
+        cpu += pcpu;
+        rss_kb += rss;
+        if state.starts_with('Z') {
+            zombies += 1;
+        }
+        if let (Ok(pid), Ok(ppid), Ok(uid)) = (pid.parse(), ppid.parse(), uid.parse()) {
+            processes.push(ObservedProcess { pid, ppid, uid, command: command.clone() });
+        }
+        snapshot.push_event(
+            max_records,
+            format!("process pid={} ppid={} uid={} state={} cpu={} rss_kb={} cmd={}", pid, ppid, uid, state, pcpu, rss, command),
+        );
+    }
+
+    snapshot.metrics.insert("processes.count".into(), count as f64);
+    snapshot.metrics.insert("processes.zombies".into(), zombies as f64);
+    snapshot.metrics.insert("processes.cpu_percent".into(), cpu);
+    snapshot.metrics.insert("processes.rss_kb".into(), rss_kb);
+    (snapshot, processes)
+}
@@ -0,0 +1,528 @@
+//! Zero-copy telemetry ingestion from a shared-memory ring
+//!
+//! Kernel-level sensors produce more records than a tool snapshot or a socket protocol can
+//! carry, so they write fixed-layout records straight into a memory-mapped ring instead. The
+//! collector reads the records in place, with no serialization on either side, and frees the
+//! slots behind it. Sensors ring a doorbell when the ring fills up, which makes the collector
+//! due at once instead of on its next interval.
+//!
+//! The ring is a bounded multi-producer queue: every slot carries a sequence number, so sensors
+//! claim slots with a compare-and-swap on the head and the single reader follows the tail. A
+//! full ring drops the new record and counts it as an overflow; above the high watermark the
+//! reader raises a backpressure flag that sensors are expected to honour by shedding low-value
+//! records.
+
+use std::{
+    cell::UnsafeCell,
+    collections::HashMap,
+    fs::OpenOptions,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU32, AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+use async_trait::async_trait;
+use memmap2::MmapMut;
+use metrics::{counter, gauge};
+use parking_lot::Mutex;
+use tracing::{debug, info, warn};
+
+use crate::config::SharedMemoryConfig;
+use crate::core::collectors::{collector_error, Collector, Snapshot, SystemCollectors};
+use crate::utils::error::GuardianError;
+
+/// Collector name, also the metric prefix
+pub const NAME: &str = "shared_memory";
+/// "GRDNRING" in ASCII
+pub const RING_MAGIC: u64 = 0x4752_444e_5249_4e47;
+pub const RING_VERSION: u32 = 1;
+/// Bytes of a record name; longer names are truncated
+pub const RECORD_NAME_LEN: usize = 32;
+/// Record aggregated into the snapshot metrics, latest value wins
+pub const RECORD_METRIC: u16 = 0;
+/// Record passed to the pipeline as an event line
+pub const RECORD_EVENT: u16 = 1;
+
+/// One telemetry record as laid out in the ring
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TelemetryRecord {
+    pub timestamp_ns: u64,
+    pub value: f64,
+    pub pid: u32,
+    pub kind: u16,
+    pub name_len: u16,
+    pub name: [u8; RECORD_NAME_LEN],
+}
+
+impl TelemetryRecord {
+    pub fn new(kind: u16, name: &str, pid: u32, value: f64, timestamp_ns: u64) -> Self {
+        // Truncate on a character boundary so the name stays valid UTF-8
+        let mut len = name.len().min(RECORD_NAME_LEN);
+        while !name.is_char_boundary(len) {
+            len -= 1;
+        }
+        let mut bytes = [0u8; RECORD_NAME_LEN];
+        bytes[..len].copy_from_slice(&name.as_bytes()[..len]);
+        Self {
+            timestamp_ns,
+            value,
+            pid,
+            kind,
+            name_len: len as u16,
+            name: bytes,
+        }
+    }
+
+    /// The record name; `None` when a sensor wrote something that is not UTF-8
+    pub fn name(&self) -> Option<&str> {
+        let len = usize::from(self.name_len).min(RECORD_NAME_LEN);
+        std::str::from_utf8(&self.name[..len]).ok()
+    }
+}
+
+/// Keeps the atomics sensors and the reader contend on in separate cache lines
+#[repr(C, align(64))]
+struct CacheLine<T>(T);
+
+#[repr(C)]
+struct RingHeader {
+    magic: u64,
+    version: u32,
+    slot_size: u32,
+    capacity: u64,
+    /// Next position sensors claim
+    head: CacheLine<AtomicU64>,
+    /// Next position the reader consumes
+    tail: CacheLine<AtomicU64>,
+    /// Records dropped by sensors because the ring was full
+    overflows: CacheLine<AtomicU64>,
+    /// Non-zero while sensors should back off
+    backpressure: CacheLine<AtomicU32>,
+}
+
+#[repr(C)]
+struct Slot {
+    /// Equals the position when free for it, the position plus one once written
+    sequence: AtomicU64,
+    record: UnsafeCell<TelemetryRecord>,
+}
+
+const HEADER_SIZE: usize = std::mem::size_of::<RingHeader>();
+const SLOT_SIZE: usize = std::mem::size_of::<Slot>();
+
+/// Memory-mapped ring shared with sensors
+pub struct TelemetryRing {
+    map: MmapMut,
+    capacity: u64,
+}
+
+// Slots are only accessed through their sequence protocol
+unsafe impl Send for TelemetryRing {}
+unsafe impl Sync for TelemetryRing {}
+
+impl std::fmt::Debug for TelemetryRing {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TelemetryRing").field("capacity", &self.capacity).field("pending", &self.pending()).finish()
+    }
+}
+
+impl TelemetryRing {
+    /// Creates an empty ring at `path`, replacing any previous file so stale sensor mappings
+    /// keep writing to the old inode rather than corrupting the new ring
+    pub fn create(path: &Path, slots: usize) -> Result<Self, GuardianError> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .map_err(|e| collector_error(format!("Failed to create {}", dir.display()), Some(Box::new(e))))?;
+        }
+        let _ = std::fs::remove_file(path);
+        let mut options = OpenOptions::new();
+        options.read(true).write(true).create_new(true);
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o660);
+        let file = options
+            .open(path)
+            .map_err(|e| collector_error(format!("Failed to create ring {}", path.display()), Some(Box::new(e))))?;
+        file.set_len((HEADER_SIZE + slots * SLOT_SIZE) as u64)
+            .map_err(|e| collector_error(format!("Failed to size ring {}", path.display()), Some(Box::new(e))))?;
+        // SAFETY: the file was just created by us; sensors only attach after the header is written
+        let map = unsafe { MmapMut::map_mut(&file) }
+            .map_err(|e| collector_error(format!("Failed to map ring {}", path.display()), Some(Box::new(e))))?;
+
+        let mut ring = Self { map, capacity: slots as u64 };
+        for position in 0..ring.capacity {
+            ring.slot(position).sequence.store(position, Ordering::Relaxed);
+        }
+        // SAFETY: the mapping is page aligned and at least HEADER_SIZE long, and nobody else maps it yet
+        let header = unsafe { &mut *(ring.map.as_mut_ptr() as *mut RingHeader) };
+        header.version = RING_VERSION;
+        header.slot_size = SLOT_SIZE as u32;
+        header.capacity = ring.capacity;
+        // Magic last: sensors treat a ring without it as not ready
+        std::sync::atomic::fence(Ordering::Release);
+        header.magic = RING_MAGIC;
+        Ok(ring)
+    }
+
+    fn header(&self) -> &RingHeader {
+        // SAFETY: see `create`; the fields shared with sensors are atomics
+        unsafe { &*(self.map.as_ptr() as *const RingHeader) }
+    }
+
+    fn slot(&self, position: u64) -> &Slot {
+        let offset = HEADER_SIZE + (position % self.capacity) as usize * SLOT_SIZE;
+        // SAFETY: the offset is within the mapping and slot aligned, as HEADER_SIZE and SLOT_SIZE are multiples of 8
+        unsafe { &*(self.map.as_ptr().add(offset) as *const Slot) }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity as usize
+    }
+
+    /// Records written and not yet consumed
+    pub fn pending(&self) -> usize {
+        let header = self.header();
+        let head = header.head.0.load(Ordering::Acquire);
+        let tail = header.tail.0.load(Ordering::Acquire);
+        head.saturating_sub(tail).min(self.capacity) as usize
+    }
+
+    /// Total records sensors dropped on a full ring
+    pub fn overflows(&self) -> u64 {
+        self.header().overflows.0.load(Ordering::Relaxed)
+    }
+
+    pub fn backpressure(&self) -> bool {
+        self.header().backpressure.0.load(Ordering::Relaxed) != 0
+    }
+
+    fn set_backpressure(&self, on: bool) {
+        self.header().backpressure.0.store(u32::from(on), Ordering::Relaxed);
+    }
+
+    /// Writes a record as a sensor would; returns false and counts an overflow when the ring is full
+    pub fn push(&self, record: &TelemetryRecord) -> bool {
+        let header = self.header();
+        let mut position = header.head.0.load(Ordering::Relaxed);
+        loop {
+            let slot = self.slot(position);
+            let sequence = slot.sequence.load(Ordering::Acquire);
+            match sequence.cmp(&position) {
+                std::cmp::Ordering::Equal => {
+                    match header.head.0.compare_exchange_weak(position, position + 1, Ordering::Relaxed, Ordering::Relaxed) {
+                        Ok(_) => {
+                            // SAFETY: the successful claim makes this producer the slot's only writer
+                            unsafe { slot.record.get().write(*record) };
+                            slot.sequence.store(position + 1, Ordering::Release);
+                            return true;
+                        }
+                        Err(current) => position = current,
+                    }
+                }
+                // The slot still holds a record from the previous lap
+                std::cmp::Ordering::Less => {
+                    header.overflows.0.fetch_add(1, Ordering::Relaxed);
+                    return false;
+                }
+                std::cmp::Ordering::Greater => position = header.head.0.load(Ordering::Relaxed),
+            }
+        }
+    }
+
+    /// Hands up to `max` written records to `visit` in place, then frees their slots
+    ///
+    /// Only one reader may drain at a time, which the collector's lock guarantees.
+    pub fn drain(&self, max: usize, mut visit: impl FnMut(&TelemetryRecord)) -> usize {
+        let header = self.header();
+        let mut position = header.tail.0.load(Ordering::Relaxed);
+        let mut consumed = 0;
+        while consumed < max {
+            let slot = self.slot(position);
+            if slot.sequence.load(Ordering::Acquire) != position + 1 {
+                break;
+            }
+            // SAFETY: the sequence shows the write finished and no producer reuses the slot until released below
+            visit(unsafe { &*slot.record.get() });
+            slot.sequence.store(position + self.capacity, Ordering::Release);
+            position += 1;
+            consumed += 1;
+        }
+        header.tail.0.store(position, Ordering::Release);
+        consumed
+    }
+}
+
+/// Drains the shared-memory ring on each snapshot
+#[derive(Debug)]
+pub struct SharedMemoryCollector {
+    ring: Mutex<TelemetryRing>,
+    doorbell_path: PathBuf,
+    high_watermark: f64,
+    /// Overflow total at the previous snapshot, to report the increase
+    last_overflows: Mutex<u64>,
+}
+
+impl SharedMemoryCollector {
+    /// Creates the ring described by the configuration
+    pub fn open(config: &SharedMemoryConfig) -> Result<Self, GuardianError> {
+        let ring = TelemetryRing::create(&config.path, config.slots)?;
+        info!(path = %config.path.display(), slots = config.slots, "Shared-memory telemetry ring ready");
+        Ok(Self {
+            ring: Mutex::new(ring),
+            doorbell_path: doorbell_path(&config.path),
+            high_watermark: config.high_watermark,
+            last_overflows: Mutex::new(0),
+        })
+    }
+
+    /// Makes the collector due whenever a sensor rings the doorbell, until the task is aborted
+    pub fn watch(self: Arc<Self>, collectors: Arc<SystemCollectors>) -> tokio::task::JoinHandle<()> {
+        let path = self.doorbell_path.clone();
+        tokio::spawn(async move {
+            let result = doorbell::run(&path, || {
+                counter!("guardian.collectors.shared_memory.doorbell", 1);
+                collectors.wake(NAME);
+            })
+            .await;
+            if let Err(e) = result {
+                warn!(error = %e, path = %path.display(), "Telemetry doorbell stopped, falling back to the sampling interval");
+            }
+        })
+    }
+}
+
+#[async_trait]
+impl Collector for SharedMemoryCollector {
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    async fn collect(&self, max_records: usize) -> Result<Snapshot, GuardianError> {
+        let ring = self.ring.lock();
+        let mut snapshot = Snapshot::default();
+        let mut counts: HashMap<String, f64> = HashMap::new();
+        let mut malformed = 0u64;
+
+        // Records beyond the cap stay in the ring for the next snapshot rather than being dropped
+        let consumed = ring.drain(max_records, |record| {
+            let Some(name) = record.name() else {
+                malformed += 1;
+                return;
+            };
+            match record.kind {
+                RECORD_METRIC => {
+                    snapshot.metrics.insert(format!("{}.{}", NAME, name), record.value);
+                }
+                RECORD_EVENT => {
+                    *counts.entry(format!("{}.{}.count", NAME, name)).or_default() += 1.0;
+                    snapshot.push_event(
+                        max_records,
+                        format!("{} pid={} value={} ts={}", name, record.pid, record.value, record.timestamp_ns),
+                    );
+                }
+                _ => malformed += 1,
+            }
+        });
+        snapshot.metrics.extend(counts);
+
+        let fill = ring.pending() as f64 / ring.capacity() as f64;
+        let congested = fill >= self.high_watermark;
+        if congested != ring.backpressure() {
+            ring.set_backpressure(congested);
+            if congested {
+                counter!("guardian.collectors.shared_memory.backpressure", 1);
+                warn!(fill, "Telemetry ring above its high watermark, asking sensors to back off");
+            } else {
+                debug!(fill, "Telemetry ring drained below its high watermark");
+            }
+        }
+
+        let overflows = ring.overflows();
+        let new_overflows = {
+            let mut last = self.last_overflows.lock();
+            let delta = overflows.saturating_sub(*last);
+            *last = overflows;
+            delta
+        };
+        if new_overflows > 0 {
+            counter!("guardian.collectors.shared_memory.overflows", new_overflows);
+        }
+        if malformed > 0 {
+            counter!("guardian.collectors.shared_memory.malformed", malformed);
+        }
+        counter!("guardian.collectors.shared_memory.records", consumed as u64);
+        gauge!("guardian.collectors.shared_memory.fill_ratio", fill);
+
+        snapshot.metrics.insert(format!("{}.records", NAME), consumed as f64);
+        snapshot.metrics.insert(format!("{}.fill_ratio", NAME), fill);
+        snapshot.metrics.insert(format!("{}.overflows", NAME), new_overflows as f64);
+        snapshot.metrics.insert(format!("{}.backpressure", NAME), if congested { 1.0 } else { 0.0 });
+        Ok(snapshot)
+    }
+}
+
+/// Doorbell sensors signal through, next to the ring file
+pub fn doorbell_path(ring: &Path) -> PathBuf {
+    let mut path = ring.as_os_str().to_owned();
+    path.push(".doorbell");
+    PathBuf::from(path)
+}
+
+/// An eventfd on Linux, handed to sensors over a Unix socket at the doorbell path
+#[cfg(target_os = "linux")]
+mod doorbell {
+    use std::io::IoSlice;
+    use std::os::fd::{AsRawFd, RawFd};
+    use std::path::Path;
+
+    use nix::errno::Errno;
+    use nix::sys::eventfd::{eventfd, EfdFlags};
+    use nix::sys::socket::{sendmsg, ControlMessage, MsgFlags};
+    use tokio::io::unix::AsyncFd;
+    use tokio::net::UnixListener;
+    use tracing::debug;
+
+    pub async fn run(path: &Path, mut on_ring: impl FnMut()) -> std::io::Result<()> {
+        let event = AsyncFd::new(eventfd(0, EfdFlags::EFD_NONBLOCK | EfdFlags::EFD_CLOEXEC)?)?;
+        let _ = std::fs::remove_file(path);
+        let listener = UnixListener::bind(path)?;
+
+        loop {
+            tokio::select! {
+                accepted = listener.accept() => {
+                    let (stream, _) = accepted?;
+                    if let Err(e) = hand_over(stream.as_raw_fd(), event.get_ref().as_raw_fd()) {
+                        debug!(error = %e, "Failed to pass the doorbell to a sensor");
+                    }
+                }
+                ready = event.readable() => {
+                    let mut guard = ready?;
+                    // Reading resets the counter, however many sensors rang
+                    let mut count = [0u8; 8];
+                    match nix::unistd::read(guard.get_inner().as_raw_fd(), &mut count) {
+                        Ok(_) => on_ring(),
+                        Err(Errno::EAGAIN) => guard.clear_ready(),
+                        Err(e) => return Err(e.into()),
+                    }
+                }
+            }
+        }
+    }
+
+    fn hand_over(socket: RawFd, event: RawFd) -> nix::Result<()> {
+        let fds = [event];
+        sendmsg::<()>(socket, &[IoSlice::new(b"d")], &[ControlMessage::ScmRights(&fds)], MsgFlags::empty(), None)?;
+        Ok(())
+    }
+}
+
+/// A FIFO at the doorbell path, watched with kqueue
+#[cfg(target_os = "freebsd")]
+mod doorbell {
+    use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+    use std::path::Path;
+
+    use nix::errno::Errno;
+    use nix::fcntl::{open, OFlag};
+    use nix::sys::event::{kevent_ts, kqueue, EventFilter, EventFlag, FilterFlag, KEvent};
+    use nix::sys::stat::Mode;
+    use tokio::io::unix::AsyncFd;
+
+    pub async fn run(path: &Path, mut on_ring: impl FnMut()) -> std::io::Result<()> {
+        let _ = std::fs::remove_file(path);
+        nix::unistd::mkfifo(path, Mode::from_bits_truncate(0o660))?;
+        // Read-write so the FIFO never reports end of file when the last sensor closes it
+        // SAFETY: both descriptors were just opened and are owned here alone
+        let fifo = unsafe { OwnedFd::from_raw_fd(open(path, OFlag::O_RDWR | OFlag::O_NONBLOCK | OFlag::O_CLOEXEC, Mode::empty())?) };
+        let kq = AsyncFd::new(unsafe { OwnedFd::from_raw_fd(kqueue()?) })?;
+
+        let change = KEvent::new(fifo.as_raw_fd() as usize, EventFilter::EVFILT_READ, EventFlag::EV_ADD, FilterFlag::empty(), 0, 0);
+        kevent_ts(kq.get_ref().as_raw_fd(), &[change], &mut [], None)?;
+
+        let zero = nix::sys::time::TimeSpec::new(0, 0);
+        let mut events = [KEvent::new(0, EventFilter::EVFILT_READ, EventFlag::empty(), FilterFlag::empty(), 0, 0); 1];
+        let mut buf = [0u8; 64];
+        loop {
+            let mut guard = kq.readable().await?;
+            if kevent_ts(kq.get_ref().as_raw_fd(), &[], &mut events, Some(zero))? == 0 {
+                guard.clear_ready();
+                continue;
+            }
+            // Empty the FIFO so one wake-up covers every ring since the last
+            loop {
+                match nix::unistd::read(fifo.as_raw_fd(), &mut buf) {
+                    Ok(0) | Err(Errno::EAGAIN) => break,
+                    Ok(_) => continue,
+                    Err(e) => return Err(e.into()),
+                }
+            }
+            on_ring();
+        }
+    }
+}
+
+/// No doorbell elsewhere; the ring is drained on the sampling interval only
+#[cfg(not(any(target_os = "linux", target_os = "freebsd")))]
+mod doorbell {
+    use std::path::Path;
+
+    pub async fn run(_path: &Path, _on_ring: impl FnMut()) -> std::io::Result<()> {
+        Err(std::io::Error::from(std::io::ErrorKind::Unsupported))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    use crate::config::CollectorConfig;
+
+    #[tokio::test]
+    async fn test_ring_overflow_backpressure_and_drain() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = SharedMemoryConfig {
+            collector: CollectorConfig {
+                enabled: true,
+                interval: Duration::from_millis(100),
+                max_records: 3,
+                timeout: Duration::from_millis(100),
+            },
+            path: dir.path().join("telemetry.ring"),
+            slots: 4,
+            high_watermark: 0.5,
+        };
+        let collector = SharedMemoryCollector::open(&config).unwrap();
+
+        {
+            let ring = collector.ring.lock();
+            assert!(ring.push(&TelemetryRecord::new(RECORD_METRIC, "syscalls_per_sec", 0, 1200.0, 1)));
+            for pid in 1..=3 {
+                assert!(ring.push(&TelemetryRecord::new(RECORD_EVENT, "exec", pid, 0.0, 2)));
+            }
+            assert!(!ring.push(&TelemetryRecord::new(RECORD_EVENT, "exec", 9, 0.0, 3)));
+            assert_eq!(ring.overflows(), 1);
+        }
+
+        // Three of four records fit the cap; the last stays behind and keeps the ring congested
+        let snapshot = collector.collect(3).await.unwrap();
+        assert_eq!(snapshot.metrics["shared_memory.syscalls_per_sec"], 1200.0);
+        assert_eq!(snapshot.metrics["shared_memory.exec.count"], 2.0);
+        assert_eq!(snapshot.metrics["shared_memory.overflows"], 1.0);
+        assert_eq!(snapshot.events, vec!["exec pid=1 value=0 ts=2".to_string(), "exec pid=2 value=0 ts=2".to_string()]);
+        assert_eq!(snapshot.metrics["shared_memory.backpressure"], 0.0);
+
+        {
+            let ring = collector.ring.lock();
+            for pid in 4..=6 {
+                assert!(ring.push(&TelemetryRecord::new(RECORD_EVENT, "exec", pid, 0.0, 4)));
+            }
+        }
+        let snapshot = collector.collect(1).await.unwrap();
+        assert_eq!(snapshot.events, vec!["exec pid=3 value=0 ts=2".to_string()]);
+        assert_eq!(snapshot.metrics["shared_memory.backpressure"], 1.0);
+        assert!(collector.ring.lock().backpressure());
+        assert_eq!(snapshot.metrics["shared_memory.overflows"], 0.0);
+    }
+}
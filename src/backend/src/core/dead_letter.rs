@@ -0,0 +1,384 @@
+//! Dead-letter queue for EventBus deliveries
+//!
+//! Events that could not be delivered to a subscriber, or that a subscriber handed back with
+//! [`EventBus::reject`], are parked here instead of being lost. They are redelivered with
+//! exponential backoff; once `max_attempts` is exhausted they wait for an operator to requeue
+//! them, and past `max_age` they are dropped. With an event store attached every state change
+//! is appended to it, so dead letters survive restarts.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use metrics::{counter, gauge};
+use once_cell::sync::{Lazy, OnceCell};
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use crate::config::app_config::{AppConfig, DeadLetterConfig};
+use crate::core::event_bus::{Event, EventBus, EventPriority};
+use crate::storage::event_store::{Event as StoredEvent, EventQuery, EventStore};
+use crate::utils::error::{ErrorCategory, ErrorSeverity, GuardianError};
+
+// Constants for the dead-letter queue
+pub const DEAD_LETTER_EVENT_TYPE: &str = "dead_letter";
+const RETRY_SCAN_INTERVAL: Duration = Duration::from_secs(5);
+
+static DEAD_LETTERS: Lazy<Arc<DeadLetterQueue>> =
+    Lazy::new(|| Arc::new(DeadLetterQueue::new(DeadLetterConfig::default())));
+
+/// Returns the process-wide dead-letter queue
+pub fn dead_letters() -> Arc<DeadLetterQueue> {
+    Arc::clone(&DEAD_LETTERS)
+}
+
+/// Applies the configured retry policy to the process-wide queue
+pub fn init_dead_letters(config: &AppConfig) {
+    dead_letters().configure(config.dead_letters.clone());
+}
+
+/// Where a dead letter is in its lifecycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeadLetterState {
+    /// Waiting for its next automatic redelivery
+    Retrying,
+    /// Automatic attempts exhausted; waits for a manual requeue
+    Parked,
+    Delivered,
+    Expired,
+    Purged,
+}
+
+impl DeadLetterState {
+    fn is_final(&self) -> bool {
+        matches!(self, Self::Delivered | Self::Expired | Self::Purged)
+    }
+}
+
+/// An undelivered event and its retry history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadLetter {
+    pub id: Uuid,
+    pub event_type: String,
+    pub payload: serde_json::Value,
+    pub priority: EventPriority,
+    pub correlation_id: Uuid,
+    pub metadata: HashMap<String, String>,
+    pub published_at: DateTime<Utc>,
+    /// Subscriber the delivery failed for; `None` redelivers to every subscriber of the type
+    pub subscriber: Option<u64>,
+    pub reason: String,
+    pub state: DeadLetterState,
+    pub attempts: u32,
+    pub first_failed_at: DateTime<Utc>,
+    pub next_attempt_at: DateTime<Utc>,
+}
+
+impl DeadLetter {
+    /// Rebuilds the event for redelivery, keeping its correlation and metadata
+    pub fn event(&self) -> Event {
+        Event {
+            event_type: self.event_type.clone(),
+            payload: self.payload.clone(),
+            timestamp: time::OffsetDateTime::from_unix_timestamp(self.published_at.timestamp())
+                .unwrap_or_else(|_| time::OffsetDateTime::now_utc()),
+            priority: self.priority,
+            correlation_id: self.correlation_id,
+            metadata: self.metadata.clone(),
+        }
+    }
+}
+
+/// Holds failed deliveries and redelivers them through the event bus
+#[derive(Debug)]
+pub struct DeadLetterQueue {
+    config: RwLock<DeadLetterConfig>,
+    letters: Mutex<BTreeMap<Uuid, DeadLetter>>,
+    store: OnceCell<Arc<EventStore>>,
+    started: OnceCell<()>,
+}
+
+impl DeadLetterQueue {
+    pub fn new(config: DeadLetterConfig) -> Self {
+        Self {
+            config: RwLock::new(config),
+            letters: Mutex::new(BTreeMap::new()),
+            store: OnceCell::new(),
+            started: OnceCell::new(),
+        }
+    }
+
+    /// Replaces the retry policy; scheduled attempts keep their times
+    pub fn configure(&self, config: DeadLetterConfig) {
+        *self.config.write() = config;
+    }
+
+    /// Persists dead letters to the event store and restores the unresolved ones recorded there
+    pub async fn persist_to(&self, event_store: Arc<EventStore>) -> Result<usize, GuardianError> {
+        let mut history = event_store
+            .retrieve_events(EventQuery {
+                start_time: None,
+                end_time: None,
+                event_type: Some(DEAD_LETTER_EVENT_TYPE.to_string()),
+                limit: None,
+            })
+            .await?;
+        history.sort_by_key(|event| event.timestamp);
+
+        // Each state change was appended, so the last record of a letter is its state
+        let mut latest: BTreeMap<Uuid, DeadLetter> = BTreeMap::new();
+        for event in history {
+            if let Ok(letter) = serde_json::from_value::<DeadLetter>(event.payload) {
+                latest.insert(letter.id, letter);
+            }
+        }
+        latest.retain(|_, letter| !letter.state.is_final());
+        let restored = latest.len();
+
+        let mut letters = self.letters.lock();
+        letters.extend(latest);
+        gauge!("guardian.event_bus.dead_letters", letters.len() as f64);
+        drop(letters);
+
+        if self.store.set(event_store).is_err() {
+            warn!("Dead-letter queue already persists to an event store");
+        }
+        info!(restored, "Dead-letter queue restored");
+        Ok(restored)
+    }
+
+    /// Starts redelivering through `bus`; later calls are ignored
+    pub fn start(self: &Arc<Self>, bus: EventBus) {
+        if self.started.set(()).is_err() {
+            return;
+        }
+        let queue = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(RETRY_SCAN_INTERVAL);
+            loop {
+                interval.tick().await;
+                queue.expire(Utc::now()).await;
+                queue.retry_due(&bus, Utc::now()).await;
+            }
+        });
+    }
+
+    /// Parks an event whose delivery failed
+    pub async fn record(&self, event: &Event, subscriber: Option<u64>, reason: &str) -> DeadLetter {
+        let now = Utc::now();
+        let letter = DeadLetter {
+            id: Uuid::new_v4(),
+            event_type: event.event_type.clone(),
+            payload: event.payload.clone(),
+            priority: event.priority,
+            correlation_id: event.correlation_id,
+            metadata: event.metadata.clone(),
+            published_at: DateTime::from_timestamp(event.timestamp.unix_timestamp(), 0).unwrap_or(now),
+            subscriber,
+            reason: reason.to_string(),
+            state: DeadLetterState::Retrying,
+            attempts: 0,
+            first_failed_at: now,
+            next_attempt_at: now + self.backoff(0),
+        };
+        counter!("guardian.event_bus.dead_lettered", 1, "event_type" => letter.event_type.clone());
+        warn!(id = %letter.id, event_type = %letter.event_type, reason, "Event dead-lettered");
+        self.update(letter.clone()).await;
+        letter
+    }
+
+    /// Lists unresolved dead letters, oldest first, optionally of one event type
+    pub fn list(&self, event_type: Option<&str>) -> Vec<DeadLetter> {
+        let mut letters: Vec<DeadLetter> = self
+            .letters
+            .lock()
+            .values()
+            .filter(|letter| event_type.map_or(true, |t| letter.event_type == t))
+            .cloned()
+            .collect();
+        letters.sort_by_key(|letter| letter.first_failed_at);
+        letters
+    }
+
+    pub fn get(&self, id: &Uuid) -> Option<DeadLetter> {
+        self.letters.lock().get(id).cloned()
+    }
+
+    /// Schedules a dead letter for immediate redelivery with a fresh attempt budget
+    pub async fn requeue(&self, id: &Uuid) -> Result<DeadLetter, GuardianError> {
+        let mut letter = self.get(id).ok_or_else(|| not_found(id))?;
+        letter.state = DeadLetterState::Retrying;
+        letter.attempts = 0;
+        letter.next_attempt_at = Utc::now();
+        info!(target: "SECURITY-AUDIT", %id, event_type = %letter.event_type, "Dead letter requeued");
+        self.update(letter.clone()).await;
+        Ok(letter)
+    }
+
+    /// Drops a dead letter without delivering it
+    pub async fn purge(&self, id: &Uuid) -> Result<DeadLetter, GuardianError> {
+        let mut letter = self.get(id).ok_or_else(|| not_found(id))?;
+        letter.state = DeadLetterState::Purged;
+        info!(target: "SECURITY-AUDIT", %id, event_type = %letter.event_type, "Dead letter purged");
+        self.update(letter.clone()).await;
+        Ok(letter)
+    }
+
+    /// Redelivers every letter whose next attempt is due
+    async fn retry_due(&self, bus: &EventBus, now: DateTime<Utc>) {
+        let due: Vec<DeadLetter> = self
+            .letters
+            .lock()
+            .values()
+            .filter(|letter| letter.state == DeadLetterState::Retrying && letter.next_attempt_at <= now)
+            .cloned()
+            .collect();
+
+        for mut letter in due {
+            letter.attempts += 1;
+            match bus.redeliver(&letter.event(), letter.subscriber).await {
+                Ok(()) => {
+                    letter.state = DeadLetterState::Delivered;
+                    counter!("guardian.event_bus.dead_letters_delivered", 1);
+                    debug!(id = %letter.id, attempts = letter.attempts, "Dead letter redelivered");
+                }
+                Err(reason) => {
+                    letter.reason = reason;
+                    if letter.attempts >= self.config.read().max_attempts {
+                        letter.state = DeadLetterState::Parked;
+                        warn!(id = %letter.id, attempts = letter.attempts, "Dead letter parked after exhausting retries");
+                    } else {
+                        letter.next_attempt_at = now + self.backoff(letter.attempts);
+                    }
+                }
+            }
+            self.update(letter).await;
+        }
+    }
+
+    /// Drops letters older than the maximum age
+    async fn expire(&self, now: DateTime<Utc>) {
+        let max_age = chrono::Duration::from_std(self.config.read().max_age).unwrap_or(chrono::Duration::MAX);
+        let expired: Vec<DeadLetter> = self
+            .letters
+            .lock()
+            .values()
+            .filter(|letter| now - letter.first_failed_at > max_age)
+            .cloned()
+            .collect();
+
+        for mut letter in expired {
+            letter.state = DeadLetterState::Expired;
+            counter!("guardian.event_bus.dead_letters_expired", 1);
+            warn!(id = %letter.id, event_type = %letter.event_type, "Dead letter expired undelivered");
+            self.update(letter).await;
+        }
+    }
+
+    /// Delay before attempt `attempts + 1`: the initial backoff doubled per attempt, capped
+    fn backoff(&self, attempts: u32) -> chrono::Duration {
+        let config = self.config.read();
+        let delay = config
+            .initial_backoff
+            .checked_mul(2u32.saturating_pow(attempts))
+            .unwrap_or(config.max_backoff)
+            .min(config.max_backoff);
+        chrono::Duration::from_std(delay).unwrap_or(chrono::Duration::MAX)
+    }
+
+    /// Applies a state change in memory and appends it to the event store
+    async fn update(&self, letter: DeadLetter) {
+        {
+            let mut letters = self.letters.lock();
+            if letter.state.is_final() {
+                letters.remove(&letter.id);
+            } else {
+                letters.insert(letter.id, letter.clone());
+            }
+            gauge!("guardian.event_bus.dead_letters", letters.len() as f64);
+        }
+
+        let Some(store) = self.store.get() else {
+            return;
+        };
+        let stored = serde_json::to_value(&letter).map(|payload| StoredEvent {
+            id: format!("{}-{}", letter.id, letter.attempts),
+            timestamp: Utc::now().timestamp() as u64,
+            event_type: DEAD_LETTER_EVENT_TYPE.to_string(),
+            payload,
+            integrity_hash: String::new(),
+        });
+        let result = match stored {
+            Ok(event) => store.store_event_with_priority(event, letter.priority.into()).await,
+            Err(e) => Err(not_persisted(e)),
+        };
+        // The in-memory queue still retries; only restart durability is lost
+        if let Err(e) = result {
+            warn!(id = %letter.id, error = %e, "Failed to persist dead letter");
+        }
+    }
+}
+
+fn not_found(id: &Uuid) -> GuardianError {
+    GuardianError::ValidationError {
+        context: format!("Dead letter {} not found", id),
+        source: None,
+        severity: ErrorSeverity::Low,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: ErrorCategory::Validation,
+        retry_count: 0,
+    }
+}
+
+fn not_persisted(e: serde_json::Error) -> GuardianError {
+    GuardianError::StorageError {
+        context: "Failed to serialize dead letter".into(),
+        source: Some(Box::new(e)),
+        severity: ErrorSeverity::Medium,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: ErrorCategory::Storage,
+        retry_count: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event() -> Event {
+        Event::new("threat_detected".into(), serde_json::json!({"id": 1}), EventPriority::High).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_backoff_parking_and_expiry() {
+        let queue = DeadLetterQueue::new(DeadLetterConfig {
+            max_attempts: 3,
+            initial_backoff: Duration::from_secs(5),
+            max_backoff: Duration::from_secs(15),
+            max_age: Duration::from_secs(3600),
+        });
+        assert_eq!(queue.backoff(0), chrono::Duration::seconds(5));
+        assert_eq!(queue.backoff(1), chrono::Duration::seconds(10));
+        assert_eq!(queue.backoff(5), chrono::Duration::seconds(15));
+
+        let letter = queue.record(&event(), Some(7), "subscriber timed out").await;
+        assert_eq!(letter.event().correlation_id, letter.correlation_id);
+        assert_eq!(queue.list(Some("threat_detected")).len(), 1);
+        assert!(queue.list(Some("other")).is_empty());
+
+        let requeued = queue.requeue(&letter.id).await.unwrap();
+        assert!(requeued.next_attempt_at <= Utc::now());
+
+        queue.expire(Utc::now() + chrono::Duration::hours(2)).await;
+        assert!(queue.get(&letter.id).is_none());
+        assert!(queue.purge(&letter.id).await.is_err());
+    }
+}
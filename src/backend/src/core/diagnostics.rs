@@ -0,0 +1,388 @@
+//! Self-tests behind `guardian-ctl diagnose`
+//!
+//! Each check exercises one dependency end to end — loading the configuration, reading the
+//! ZFS pool, reaching Temporal, running the serving model on a golden input, sealing and
+//! opening a secret, writing to the audit chain — and reports pass, fail or skip with a hint
+//! on what to fix. Checks run one after another under a per-check timeout so a hung
+//! dependency fails its own check instead of the whole report.
+
+use std::{
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use metrics::{counter, histogram};
+use serde::Serialize;
+use tracing::{info, warn};
+
+use crate::config::secrets::ConfigSecrets;
+use crate::config::security_config::AuditChainConfig;
+use crate::ml::inference_engine::InferenceEngine;
+use crate::security::audit::{AuditEvent, SecurityLevel};
+use crate::security::audit_chain::init_audit_chain;
+use crate::storage::zfs_manager::ZfsManager;
+use crate::temporal::{TemporalConfig, TemporalRuntime};
+use crate::utils::error::GuardianError;
+
+pub const DEFAULT_CHECK_TIMEOUT: Duration = Duration::from_secs(30);
+/// Audit event type of the record the audit check writes
+pub const SELF_TEST_EVENT_TYPE: &str = "diagnostics.self_test";
+const CRYPTO_PROBE: &str = "guardian-diagnostics-probe";
+
+/// Outcome of one check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    Pass,
+    Fail,
+    /// The checked feature is disabled, so there was nothing to test
+    Skipped,
+}
+
+impl CheckStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CheckStatus::Pass => "pass",
+            CheckStatus::Fail => "fail",
+            CheckStatus::Skipped => "skipped",
+        }
+    }
+}
+
+/// What a passing or skipped check found
+#[derive(Debug, Clone, PartialEq)]
+pub enum CheckOutcome {
+    Pass(String),
+    Skipped(String),
+}
+
+/// Result of one check in the report
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckResult {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub duration_ms: u64,
+    pub detail: String,
+    /// What to look at when the check fails
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remediation: Option<&'static str>,
+}
+
+/// Structured result of a diagnostics run
+#[derive(Debug, Clone, Serialize)]
+pub struct DiagnosticReport {
+    pub started_at: DateTime<Utc>,
+    pub duration_ms: u64,
+    pub checks: Vec<CheckResult>,
+}
+
+impl DiagnosticReport {
+    /// Whether no check failed; skipped checks do not count against the run
+    pub fn passed(&self) -> bool {
+        self.checks.iter().all(|check| check.status != CheckStatus::Fail)
+    }
+
+    pub fn failures(&self) -> usize {
+        self.checks.iter().filter(|check| check.status == CheckStatus::Fail).count()
+    }
+}
+
+/// One self-test
+#[async_trait]
+pub trait DiagnosticCheck: Send + Sync + std::fmt::Debug {
+    fn name(&self) -> &'static str;
+
+    /// Shown with a failure
+    fn remediation(&self) -> &'static str;
+
+    async fn run(&self) -> Result<CheckOutcome, GuardianError>;
+}
+
+/// Runs checks in order, each under `timeout`
+pub async fn run_diagnostics(checks: &[Box<dyn DiagnosticCheck>], timeout: Duration) -> DiagnosticReport {
+    let started_at = Utc::now();
+    let start = Instant::now();
+    let mut results = Vec::with_capacity(checks.len());
+
+    for check in checks {
+        let check_start = Instant::now();
+        let outcome = match tokio::time::timeout(timeout, check.run()).await {
+            Ok(outcome) => outcome.map_err(|e| e.to_string()),
+            Err(_) => Err(format!("no answer within {}s", timeout.as_secs())),
+        };
+        let (status, detail) = match outcome {
+            Ok(CheckOutcome::Pass(detail)) => (CheckStatus::Pass, detail),
+            Ok(CheckOutcome::Skipped(detail)) => (CheckStatus::Skipped, detail),
+            Err(detail) => (CheckStatus::Fail, detail),
+        };
+
+        let elapsed = check_start.elapsed();
+        counter!("guardian.diagnostics.checks", 1, "check" => check.name(), "status" => status.as_str());
+        histogram!("guardian.diagnostics.check_duration", elapsed.as_secs_f64(), "check" => check.name());
+        if status == CheckStatus::Fail {
+            warn!(check = check.name(), detail = %detail, "Self-test failed");
+        }
+        results.push(CheckResult {
+            name: check.name(),
+            status,
+            duration_ms: elapsed.as_millis() as u64,
+            remediation: (status == CheckStatus::Fail).then(|| check.remediation()),
+            detail,
+        });
+    }
+
+    let report = DiagnosticReport {
+        started_at,
+        duration_ms: start.elapsed().as_millis() as u64,
+        checks: results,
+    };
+    info!(checks = report.checks.len(), failures = report.failures(), "Diagnostics complete");
+    report
+}
+
+/// Loads, decrypts and validates every configuration file
+#[derive(Debug)]
+pub struct ConfigCheck {
+    pub config_dir: PathBuf,
+}
+
+#[async_trait]
+impl DiagnosticCheck for ConfigCheck {
+    fn name(&self) -> &'static str {
+        "config"
+    }
+
+    fn remediation(&self) -> &'static str {
+        "Run `guardian-ctl config validate` and fix the reported file, or check that the key provider can open enc:v1 values"
+    }
+
+    async fn run(&self) -> Result<CheckOutcome, GuardianError> {
+        let config = crate::config::GuardianConfig::load(self.config_dir.clone()).await?;
+        config.read().await.validate()?;
+        Ok(CheckOutcome::Pass(format!("{} loaded and valid", self.config_dir.display())))
+    }
+}
+
+/// Reads the pool's status and flags impaired vdevs
+#[derive(Debug)]
+pub struct ZfsPoolCheck {
+    pub zfs: Arc<ZfsManager>,
+}
+
+#[async_trait]
+impl DiagnosticCheck for ZfsPoolCheck {
+    fn name(&self) -> &'static str {
+        "zfs_pool"
+    }
+
+    fn remediation(&self) -> &'static str {
+        "Check `zpool status` for the pool, that the daemon user may run zpool, and replace any faulted devices"
+    }
+
+    async fn run(&self) -> Result<CheckOutcome, GuardianError> {
+        let status = self.zfs.pool_status().await?;
+        let impaired: Vec<String> = status.impaired_vdevs().map(|vdev| vdev.name.clone()).collect();
+        if !impaired.is_empty() {
+            return Err(diagnostic_error(format!("Pool {} has impaired vdevs: {}", status.pool, impaired.join(", "))));
+        }
+        Ok(CheckOutcome::Pass(format!(
+            "pool {} {:?}, {} checksum errors",
+            status.pool,
+            status.state,
+            status.checksum_errors()
+        )))
+    }
+}
+
+/// Connects a client to the first reachable Temporal frontend
+#[derive(Debug)]
+pub struct TemporalCheck {
+    pub config: TemporalConfig,
+}
+
+#[async_trait]
+impl DiagnosticCheck for TemporalCheck {
+    fn name(&self) -> &'static str {
+        "temporal"
+    }
+
+    fn remediation(&self) -> &'static str {
+        "Check that a Temporal frontend is listening on a configured endpoint and that its namespace exists"
+    }
+
+    async fn run(&self) -> Result<CheckOutcome, GuardianError> {
+        let endpoint = TemporalRuntime::probe(&self.config).await?;
+        Ok(CheckOutcome::Pass(format!("connected to {} in namespace {}", endpoint, self.config.namespace)))
+    }
+}
+
+/// Loads the serving model and runs it on the golden input
+#[derive(Debug)]
+pub struct ModelCheck {
+    pub engine: Arc<InferenceEngine>,
+}
+
+#[async_trait]
+impl DiagnosticCheck for ModelCheck {
+    fn name(&self) -> &'static str {
+        "model_inference"
+    }
+
+    fn remediation(&self) -> &'static str {
+        "Check `guardian-ctl models list` for an active version, its signature, and roll back with `models activate` if it misbehaves"
+    }
+
+    async fn run(&self) -> Result<CheckOutcome, GuardianError> {
+        let start = Instant::now();
+        let (version, prediction) = self.engine.self_test().await?;
+        Ok(CheckOutcome::Pass(format!(
+            "model {} answered {} with confidence {:.3} in {}ms",
+            version,
+            prediction.prediction_type(),
+            prediction.confidence(),
+            start.elapsed().as_millis()
+        )))
+    }
+}
+
+/// Seals a probe value under the config KEK and opens it again
+#[derive(Debug)]
+pub struct CryptoCheck {
+    pub secrets: Arc<ConfigSecrets>,
+}
+
+#[async_trait]
+impl DiagnosticCheck for CryptoCheck {
+    fn name(&self) -> &'static str {
+        "crypto"
+    }
+
+    fn remediation(&self) -> &'static str {
+        "Check that the key provider is reachable and holds the config KEK named in security.toml"
+    }
+
+    async fn run(&self) -> Result<CheckOutcome, GuardianError> {
+        let sealed = self.secrets.encrypt(CRYPTO_PROBE).await?;
+        let opened = self.secrets.decrypt(&sealed).await?;
+        if opened.as_str() != CRYPTO_PROBE {
+            return Err(diagnostic_error("Opened secret does not match the sealed probe".into()));
+        }
+        Ok(CheckOutcome::Pass("sealed and opened a probe value".into()))
+    }
+}
+
+/// Appends a record to the audit chain and reads it back
+#[derive(Debug)]
+pub struct AuditWriteCheck {
+    pub config: AuditChainConfig,
+}
+
+#[async_trait]
+impl DiagnosticCheck for AuditWriteCheck {
+    fn name(&self) -> &'static str {
+        "audit_write"
+    }
+
+    fn remediation(&self) -> &'static str {
+        "Check that the audit chain path is writable and has free space, then run `guardian-ctl audit verify`"
+    }
+
+    async fn run(&self) -> Result<CheckOutcome, GuardianError> {
+        let Some(chain) = init_audit_chain(&self.config)? else {
+            return Ok(CheckOutcome::Skipped("audit chain disabled".into()));
+        };
+        let since = Utc::now();
+        let mut event = AuditEvent::new(SELF_TEST_EVENT_TYPE.into(), SecurityLevel::Low, "diagnostics".into(), None);
+        chain.append(&mut event)?;
+        let found = chain
+            .events_of_type(SELF_TEST_EVENT_TYPE, Some(since))
+            .await?
+            .iter()
+            .any(|recorded| recorded.id() == event.id());
+        if !found {
+            return Err(diagnostic_error(format!("Audit event {} was not found after writing it", event.sequence())));
+        }
+        Ok(CheckOutcome::Pass(format!("wrote and read back audit event {}", event.sequence())))
+    }
+}
+
+/// Stands in for a check whose dependency could not even be set up, so it still fails in the report
+#[derive(Debug)]
+pub struct UnavailableCheck {
+    pub name: &'static str,
+    pub remediation: &'static str,
+    pub error: String,
+}
+
+#[async_trait]
+impl DiagnosticCheck for UnavailableCheck {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn remediation(&self) -> &'static str {
+        self.remediation
+    }
+
+    async fn run(&self) -> Result<CheckOutcome, GuardianError> {
+        Err(diagnostic_error(self.error.clone()))
+    }
+}
+
+fn diagnostic_error(context: String) -> GuardianError {
+    GuardianError::SystemError {
+        context,
+        source: None,
+        severity: crate::utils::error::ErrorSeverity::Medium,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: crate::utils::error::ErrorCategory::System,
+        retry_count: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    struct Fixed(&'static str, Option<CheckOutcome>, Duration);
+
+    #[async_trait]
+    impl DiagnosticCheck for Fixed {
+        fn name(&self) -> &'static str {
+            self.0
+        }
+
+        fn remediation(&self) -> &'static str {
+            "fix it"
+        }
+
+        async fn run(&self) -> Result<CheckOutcome, GuardianError> {
+            tokio::time::sleep(self.2).await;
+            self.1.clone().ok_or_else(|| diagnostic_error("broken".into()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_report_marks_failures_and_timeouts() {
+        let checks: Vec<Box<dyn DiagnosticCheck>> = vec![
+            Box::new(Fixed("ok", Some(CheckOutcome::Pass("fine".into())), Duration::ZERO)),
+            Box::new(Fixed("off", Some(CheckOutcome::Skipped("disabled".into())), Duration::ZERO)),
+            Box::new(Fixed("broken", None, Duration::ZERO)),
+            Box::new(Fixed("hung", Some(CheckOutcome::Pass("late".into())), Duration::from_secs(5))),
+        ];
+        let report = run_diagnostics(&checks, Duration::from_millis(50)).await;
+
+        let statuses: Vec<CheckStatus> = report.checks.iter().map(|c| c.status).collect();
+        assert_eq!(statuses, vec![CheckStatus::Pass, CheckStatus::Skipped, CheckStatus::Fail, CheckStatus::Fail]);
+        assert_eq!(report.checks[2].remediation, Some("fix it"));
+        assert_eq!(report.checks[0].remediation, None);
+        assert!(report.checks[3].detail.contains("no answer"));
+        assert!(!report.passed());
+        assert_eq!(report.failures(), 2);
+    }
+}
@@ -1,20 +1,26 @@
+use metrics::counter;
 use parking_lot::RwLock;
 use std::{
     collections::HashMap,
+    future::Future,
     sync::{
-        atomic::{AtomicBool, Ordering},
+        atomic::{AtomicBool, AtomicU64, Ordering},
         Arc,
     },
     time::Duration,
 };
 use tokio::{
-    sync::{broadcast, mpsc},
+    sync::{broadcast, mpsc, mpsc::error::TryRecvError},
     time,
 };
 use tracing::{debug, error, info, instrument, warn};
 
 use crate::utils::error::{GuardianError, SystemError, ValidationError};
+use crate::utils::correlation;
+use crate::core::dead_letter::{dead_letters, DeadLetter};
 use crate::core::metrics::CoreMetricsManager;
+use crate::utils::admission::{admission, AdmissionPriority, WorkSource};
+use crate::utils::queue_metrics::{queue_registry, QueueMonitor};
 
 // Constants for event bus configuration
 const MAX_SUBSCRIBERS: usize = 1000;
@@ -22,9 +28,18 @@ const CHANNEL_BUFFER_SIZE: usize = 1024;
 const CLEANUP_INTERVAL: Duration = Duration::from_secs(60);
 const PUBLISH_TIMEOUT: Duration = Duration::from_millis(100);
 const HIGH_PRIORITY_BUFFER: usize = 2048;
+const QUEUE_NAME: &str = "event_bus";
+/// One lane per priority
+const LANES: usize = 4;
+/// Events that may be taken ahead of a waiting lane before it is served regardless of priority
+const STARVATION_LIMIT: u32 = 16;
+/// Metadata key naming the subscriber an event was delivered to, used by `reject`
+pub const SUBSCRIBER_METADATA_KEY: &str = "subscriber_id";
+
+static NEXT_SUBSCRIBER_ID: AtomicU64 = AtomicU64::new(1);
 
 /// Event priority levels for processing
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub enum EventPriority {
     Critical,
     High,
@@ -32,6 +47,48 @@ pub enum EventPriority {
     Low,
 }
 
+impl EventPriority {
+    /// Every priority, most urgent first
+    pub const ALL: [EventPriority; LANES] = [Self::Critical, Self::High, Self::Medium, Self::Low];
+
+    /// Index of the priority's lane in a subscription
+    fn lane(self) -> usize {
+        match self {
+            Self::Critical => 0,
+            Self::High => 1,
+            Self::Medium => 2,
+            Self::Low => 3,
+        }
+    }
+
+    fn lane_capacity(self) -> usize {
+        match self {
+            Self::Critical => HIGH_PRIORITY_BUFFER,
+            _ => CHANNEL_BUFFER_SIZE,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Critical => "critical",
+            Self::High => "high",
+            Self::Medium => "medium",
+            Self::Low => "low",
+        }
+    }
+}
+
+impl From<EventPriority> for AdmissionPriority {
+    fn from(priority: EventPriority) -> Self {
+        match priority {
+            EventPriority::Critical => AdmissionPriority::Critical,
+            EventPriority::High => AdmissionPriority::High,
+            EventPriority::Medium => AdmissionPriority::Medium,
+            EventPriority::Low => AdmissionPriority::Low,
+        }
+    }
+}
+
 /// Core event structure with enhanced metadata
 #[derive(Debug, Clone)]
 pub struct Event {
@@ -56,7 +113,7 @@ impl Event {
                 source: None,
                 severity: crate::utils::error::ErrorSeverity::Medium,
                 timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id: uuid::Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: crate::utils::error::ErrorCategory::Validation,
                 retry_count: 0,
             });
@@ -67,16 +124,162 @@ impl Event {
             payload,
             timestamp: time::OffsetDateTime::now_utc(),
             priority,
-            correlation_id: uuid::Uuid::new_v4(),
+            correlation_id: correlation::current_or_new(),
             metadata: HashMap::new(),
         })
     }
 }
 
+/// Bounded channel of one priority and the monitor tracking its backlog
+#[derive(Debug, Clone)]
+struct Lane {
+    tx: mpsc::Sender<Event>,
+    queue: Arc<QueueMonitor>,
+}
+
+/// Sending side of a subscription, one lane per priority
+#[derive(Debug, Clone)]
+struct Subscriber {
+    id: u64,
+    lanes: Vec<Lane>,
+}
+
+impl Subscriber {
+    /// Creates the lanes of a new subscription and the receiver reading them
+    fn new() -> (Self, EventReceiver) {
+        let (lanes, receivers): (Vec<Lane>, Vec<mpsc::Receiver<Event>>) = EventPriority::ALL
+            .iter()
+            .map(|priority| {
+                let (tx, rx) = mpsc::channel(priority.lane_capacity());
+                // A weak sender lets the probe read the depth without keeping the channel open;
+                // monitors sharing a lane name are merged, so depth is reported per lane
+                let weak = tx.downgrade();
+                let queue = queue_registry().register(
+                    QueueMonitor::new(&format!("{}.{}", QUEUE_NAME, priority.label()), priority.lane_capacity())
+                        .fifo(move || weak.upgrade().map(|tx| tx.max_capacity() - tx.capacity())),
+                );
+                (Lane { tx, queue }, rx)
+            })
+            .unzip();
+
+        let subscriber = Self {
+            id: NEXT_SUBSCRIBER_ID.fetch_add(1, Ordering::Relaxed),
+            lanes,
+        };
+        let receiver = EventReceiver {
+            lanes: receivers.try_into().expect("one receiver per priority"),
+            heads: Default::default(),
+            skipped: [0; LANES],
+        };
+        (subscriber, receiver)
+    }
+
+    fn lane(&self, priority: EventPriority) -> &Lane {
+        &self.lanes[priority.lane()]
+    }
+
+    /// The receiver owns every lane, so one closed lane means it was dropped
+    fn is_closed(&self) -> bool {
+        self.lanes.iter().any(|lane| lane.tx.is_closed())
+    }
+}
+
+/// Receiving side of a subscription.
+///
+/// Critical events always go first. The other lanes are served in priority order, except that a
+/// lane with an event waiting is served once `STARVATION_LIMIT` events have been taken ahead of it,
+/// so bulk traffic on a busy high lane cannot hold back low-priority events indefinitely.
+#[derive(Debug)]
+pub struct EventReceiver {
+    lanes: [mpsc::Receiver<Event>; LANES],
+    /// Oldest event of each lane, taken off its channel so lanes can be compared
+    heads: [Option<Event>; LANES],
+    /// Events taken ahead of each lane while it had one waiting
+    skipped: [u32; LANES],
+}
+
+impl EventReceiver {
+    /// Returns the next event, or `None` once the subscription is closed and drained
+    pub async fn recv(&mut self) -> Option<Event> {
+        loop {
+            let mut open = false;
+            for (lane, head) in self.heads.iter_mut().enumerate() {
+                if head.is_none() {
+                    match self.lanes[lane].try_recv() {
+                        Ok(event) => *head = Some(event),
+                        Err(TryRecvError::Empty) => open = true,
+                        Err(TryRecvError::Disconnected) => {}
+                    }
+                }
+            }
+            if let Some(lane) = self.next_lane() {
+                return self.take(lane);
+            }
+            if !open {
+                return None;
+            }
+
+            // Every lane is empty; wait for the first event on any of them
+            let [critical, high, medium, low] = &mut self.lanes;
+            let (lane, event) = tokio::select! {
+                biased;
+                Some(event) = critical.recv() => (0, event),
+                Some(event) = high.recv() => (1, event),
+                Some(event) = medium.recv() => (2, event),
+                Some(event) = low.recv() => (3, event),
+                else => return None,
+            };
+            self.heads[lane] = Some(event);
+        }
+    }
+
+    /// Handles events until the subscription closes, each in the correlation scope it was
+    /// published from so the consumer's logs, errors and spawned tasks carry the same ID
+    pub async fn for_each<F, Fut>(mut self, mut handler: F)
+    where
+        F: FnMut(Event) -> Fut,
+        Fut: Future<Output = ()>,
+    {
+        while let Some(event) = self.recv().await {
+            let correlation_id = event.correlation_id;
+            // Work the handler does before returning its future is scoped too
+            let handling = correlation::sync_scope(correlation_id, || handler(event));
+            correlation::scope(correlation_id, handling).await;
+        }
+    }
+
+    /// Lane to serve next: Critical, then a starved lane, lowest first, then the highest waiting
+    fn next_lane(&self) -> Option<usize> {
+        let critical = EventPriority::Critical.lane();
+        if self.heads[critical].is_some() {
+            return Some(critical);
+        }
+        let waiting = |lane: &usize| self.heads[*lane].is_some();
+        let starved = (critical + 1..LANES)
+            .rev()
+            .find(|lane| waiting(lane) && self.skipped[*lane] >= STARVATION_LIMIT);
+        if let Some(lane) = starved {
+            counter!("guardian.event_bus.starvation_promotions", 1, "lane" => EventPriority::ALL[lane].label());
+            return Some(lane);
+        }
+        (critical + 1..LANES).find(waiting)
+    }
+
+    fn take(&mut self, lane: usize) -> Option<Event> {
+        for behind in lane + 1..LANES {
+            if self.heads[behind].is_some() {
+                self.skipped[behind] += 1;
+            }
+        }
+        self.skipped[lane] = 0;
+        self.heads[lane].take()
+    }
+}
+
 /// High-performance event bus with priority handling and backpressure management
 #[derive(Debug)]
 pub struct EventBus {
-    subscribers: RwLock<HashMap<String, Vec<mpsc::Sender<Event>>>>,
+    subscribers: Arc<RwLock<HashMap<String, Vec<Subscriber>>>>,
     metrics: CoreMetricsManager,
     shutdown_signal: broadcast::Sender<()>,
     circuit_breaker: Arc<AtomicBool>,
@@ -87,7 +290,7 @@ impl EventBus {
     pub fn new(metrics: CoreMetricsManager) -> Result<Self, GuardianError> {
         let (shutdown_tx, _) = broadcast::channel(1);
         let bus = Self {
-            subscribers: RwLock::new(HashMap::new()),
+            subscribers: Arc::new(RwLock::new(HashMap::new())),
             metrics,
             shutdown_signal: shutdown_tx,
             circuit_breaker: Arc::new(AtomicBool::new(false)),
@@ -109,46 +312,50 @@ impl EventBus {
     }
 
     /// Publishes an event with priority handling and backpressure management
+    ///
+    /// Deliveries that fail are handed to the dead-letter queue for redelivery.
     #[instrument(skip(self, event))]
     pub async fn publish(&self, event: Event) -> Result<(), GuardianError> {
         if self.circuit_breaker.load(Ordering::Relaxed) {
+            dead_letters().record(&event, None, "circuit breaker open").await;
             return Err(SystemError {
                 context: "Circuit breaker is open".into(),
                 source: None,
                 severity: crate::utils::error::ErrorSeverity::High,
                 timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id: uuid::Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: crate::utils::error::ErrorCategory::System,
                 retry_count: 0,
             });
         }
 
+        // Shed informational events while overloaded; Critical and High are always delivered
+        if !admission().admit(WorkSource::Event, event.priority.into()) {
+            return Ok(());
+        }
+
         let start_time = time::Instant::now();
-        let subscribers = self.subscribers.read();
-        
-        if let Some(subs) = subscribers.get(&event.event_type) {
+        let subscribers = self.subscribers.read().get(&event.event_type).cloned();
+
+        if let Some(subs) = subscribers {
             let mut failed_deliveries = 0;
-            
-            for subscriber in subs {
-                let timeout = match event.priority {
-                    EventPriority::Critical => PUBLISH_TIMEOUT * 2,
-                    EventPriority::High => PUBLISH_TIMEOUT,
-                    _ => PUBLISH_TIMEOUT / 2,
-                };
-
-                match time::timeout(timeout, subscriber.send(event.clone())).await {
-                    Ok(Ok(_)) => {
+
+            for subscriber in &subs {
+                match deliver(subscriber, &event).await {
+                    Ok(()) => {
                         self.metrics.record_event_latency(
                             "event_delivery",
                             start_time.elapsed().as_secs_f64(),
                         ).await?;
                     }
-                    Ok(Err(_)) | Err(_) => {
+                    Err(reason) => {
                         failed_deliveries += 1;
                         warn!(
                             event_type = %event.event_type,
+                            subscriber = subscriber.id,
                             "Failed to deliver event to subscriber"
                         );
+                        dead_letters().record(&event, Some(subscriber.id), reason).await;
                     }
                 }
             }
@@ -165,24 +372,55 @@ impl EventBus {
         Ok(())
     }
 
-    /// Subscribes to events with backpressure control
+    /// Hands back an event the subscriber could not process so it is dead-lettered and retried
+    pub async fn reject(&self, event: &Event, reason: &str) -> DeadLetter {
+        let subscriber = event
+            .metadata
+            .get(SUBSCRIBER_METADATA_KEY)
+            .and_then(|id| id.parse().ok());
+        dead_letters().record(event, subscriber, reason).await
+    }
+
+    /// Delivers a dead-lettered event to one subscriber, or to every subscriber of its type
+    pub(crate) async fn redeliver(&self, event: &Event, subscriber: Option<u64>) -> Result<(), String> {
+        let targets: Vec<Subscriber> = self
+            .subscribers
+            .read()
+            .get(&event.event_type)
+            .map(|subs| {
+                subs.iter()
+                    .filter(|s| subscriber.map_or(true, |id| s.id == id))
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        if targets.is_empty() {
+            return Err(match subscriber {
+                Some(id) => format!("subscriber {} is no longer connected", id),
+                None => "no subscribers for event type".to_string(),
+            });
+        }
+
+        for target in &targets {
+            deliver(target, event).await.map_err(str::to_string)?;
+        }
+        Ok(())
+    }
+
+    /// Subscribes to events with one bounded lane per priority, so a backlog of bulk events
+    /// does not delay critical ones
     pub async fn subscribe(
         &self,
         event_type: String,
-    ) -> Result<mpsc::Receiver<Event>, GuardianError> {
+    ) -> Result<EventReceiver, GuardianError> {
         let mut subscribers = self.subscribers.write();
-        
-        let buffer_size = match event_type.as_str() {
-            "critical" => HIGH_PRIORITY_BUFFER,
-            _ => CHANNEL_BUFFER_SIZE,
-        };
 
-        let (tx, rx) = mpsc::channel(buffer_size);
-        
+        let (subscriber, rx) = Subscriber::new();
         subscribers
             .entry(event_type.clone())
             .or_insert_with(Vec::new)
-            .push(tx);
+            .push(subscriber);
 
         if subscribers.values().flatten().count() > MAX_SUBSCRIBERS {
             return Err(SystemError {
@@ -190,7 +428,7 @@ impl EventBus {
                 source: None,
                 severity: crate::utils::error::ErrorSeverity::High,
                 timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id: uuid::Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: crate::utils::error::ErrorCategory::System,
                 retry_count: 0,
             });
@@ -214,7 +452,7 @@ impl EventBus {
 impl Clone for EventBus {
     fn clone(&self) -> Self {
         Self {
-            subscribers: RwLock::new(self.subscribers.read().clone()),
+            subscribers: Arc::clone(&self.subscribers),
             metrics: self.metrics.clone(),
             shutdown_signal: self.shutdown_signal.clone(),
             circuit_breaker: Arc::clone(&self.circuit_breaker),
@@ -222,10 +460,33 @@ impl Clone for EventBus {
     }
 }
 
+/// Sends an event to the subscriber's lane for its priority, tagged with the subscriber id,
+/// within the priority's timeout
+async fn deliver(subscriber: &Subscriber, event: &Event) -> Result<(), &'static str> {
+    let timeout = match event.priority {
+        EventPriority::Critical => PUBLISH_TIMEOUT * 2,
+        EventPriority::High => PUBLISH_TIMEOUT,
+        _ => PUBLISH_TIMEOUT / 2,
+    };
+
+    let mut event = event.clone();
+    event.metadata.insert(SUBSCRIBER_METADATA_KEY.to_string(), subscriber.id.to_string());
+
+    let lane = subscriber.lane(event.priority);
+    match time::timeout(timeout, lane.tx.send(event)).await {
+        Ok(Ok(_)) => {
+            lane.queue.record_enqueue();
+            Ok(())
+        }
+        Ok(Err(_)) => Err("subscriber disconnected"),
+        Err(_) => Err("subscriber queue full"),
+    }
+}
+
 /// Removes disconnected subscribers with metrics tracking
 #[instrument]
 async fn cleanup_disconnected_subscribers(
-    subscribers: &RwLock<HashMap<String, Vec<mpsc::Sender<Event>>>>
+    subscribers: &RwLock<HashMap<String, Vec<Subscriber>>>
 ) -> Result<(), GuardianError> {
     let mut write_guard = subscribers.write();
     let mut total_removed = 0;
@@ -280,6 +541,75 @@ mod tests {
         assert!(subscribers.get("test_event").unwrap().is_empty());
     }
 
+    #[tokio::test]
+    async fn test_rejected_event_is_redelivered() {
+        let bus = EventBus::new(setup_test_metrics()).unwrap();
+        let mut rx = bus.subscribe("test_event".into()).await.unwrap();
+
+        let event = Event::new(
+            "test_event".into(),
+            serde_json::json!({"test": "data"}),
+            EventPriority::High,
+        ).unwrap();
+        bus.publish(event).await.unwrap();
+
+        let delivered = rx.recv().await.unwrap();
+        let letter = bus.reject(&delivered, "malformed payload").await;
+        assert!(letter.subscriber.is_some());
+
+        assert!(bus.redeliver(&letter.event(), letter.subscriber).await.is_ok());
+        assert!(rx.recv().await.is_some());
+        assert!(bus.redeliver(&letter.event(), Some(u64::MAX)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_critical_preempts_and_low_lane_is_not_starved() {
+        let bus = EventBus::new(setup_test_metrics()).unwrap();
+        let mut rx = bus.subscribe("test_event".into()).await.unwrap();
+        let event = |priority, n: u32| Event::new("test_event".into(), serde_json::json!({ "n": n }), priority).unwrap();
+
+        bus.publish(event(EventPriority::Low, 0)).await.unwrap();
+        for n in 1..=STARVATION_LIMIT + 1 {
+            bus.publish(event(EventPriority::High, n)).await.unwrap();
+        }
+        bus.publish(event(EventPriority::Critical, 99)).await.unwrap();
+
+        let mut order = Vec::new();
+        for _ in 0..STARVATION_LIMIT + 3 {
+            order.push(rx.recv().await.unwrap().priority);
+        }
+        assert_eq!(order[0], EventPriority::Critical);
+        // Critical and the first high events went ahead until the low lane hit the limit
+        assert_eq!(order[STARVATION_LIMIT as usize], EventPriority::Low);
+        assert_eq!(order.iter().filter(|p| **p == EventPriority::High).count(), STARVATION_LIMIT as usize + 1);
+    }
+
+    #[tokio::test]
+    async fn test_consumer_scoped_to_publisher_correlation_id() {
+        let bus = EventBus::new(setup_test_metrics()).unwrap();
+        let rx = bus.subscribe("test_event".into()).await.unwrap();
+
+        let correlation_id = crate::utils::ids::next_uuid(crate::utils::ids::IdKind::Correlation);
+        correlation::scope(correlation_id, async {
+            let event = Event::new("test_event".into(), serde_json::json!({}), EventPriority::High).unwrap();
+            bus.publish(event).await.unwrap();
+        })
+        .await;
+
+        let (seen_tx, mut seen_rx) = mpsc::unbounded_channel();
+        let consumer = tokio::spawn(rx.for_each(move |_event| {
+            let seen_tx = seen_tx.clone();
+            async move {
+                let spawned = correlation::spawn(async { correlation::current() }).await.unwrap();
+                let _ = seen_tx.send((correlation::current(), spawned));
+            }
+        }));
+
+        assert_eq!(seen_rx.recv().await.unwrap(), (Some(correlation_id), Some(correlation_id)));
+        assert!(correlation::current().is_none());
+        consumer.abort();
+    }
+
     fn setup_test_metrics() -> CoreMetricsManager {
         let collector_config = crate::utils::metrics::MetricsConfig {
             statsd_host: "localhost".into(),
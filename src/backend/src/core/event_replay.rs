@@ -0,0 +1,279 @@
+use std::{sync::Arc, time::Duration};
+
+use metrics::{counter, histogram};
+use serde::{Deserialize, Serialize};
+use tracing::{info, instrument, warn};
+use uuid::Uuid;
+
+use crate::core::event_bus::{Event, EventBus, EventPriority};
+use crate::core::operations::{OperationHandle, OperationRegistry};
+use crate::storage::event_store::{Event as StoredEvent, EventQuery, EventStore};
+use crate::utils::error::GuardianError;
+
+// Constants for event replay
+/// Metadata key carrying the stored event ID on replayed events, so consumers can skip side effects
+pub const REPLAY_METADATA_KEY: &str = "replayed_from";
+const REPLAY_OPERATION_KIND: &str = "event_replay";
+const DEFAULT_MAX_GAP: Duration = Duration::from_secs(60);
+const PROGRESS_INTERVAL: usize = 100;
+
+/// Pacing of replayed events relative to their original spacing
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum ReplaySpeed {
+    RealTime,
+    /// Original spacing divided by `factor`
+    Accelerated { factor: f64 },
+    Unthrottled,
+}
+
+impl ReplaySpeed {
+    /// Delay before publishing an event that originally followed the previous one by `gap`
+    fn delay(&self, gap: Duration) -> Duration {
+        match self {
+            ReplaySpeed::RealTime => gap,
+            ReplaySpeed::Accelerated { factor } => gap.div_f64(*factor),
+            ReplaySpeed::Unthrottled => Duration::ZERO,
+        }
+    }
+}
+
+/// Time range and pacing of a replay
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayRequest {
+    /// Unix seconds, inclusive
+    pub start_time: u64,
+    /// Unix seconds, inclusive
+    pub end_time: u64,
+    pub event_type: Option<String>,
+    pub speed: ReplaySpeed,
+    /// Longer quiet periods in the original stream are shortened to this
+    pub max_gap: Duration,
+    pub limit: Option<usize>,
+}
+
+impl ReplayRequest {
+    /// Replays every event in the range as fast as possible
+    pub fn new(start_time: u64, end_time: u64) -> Self {
+        Self {
+            start_time,
+            end_time,
+            event_type: None,
+            speed: ReplaySpeed::Unthrottled,
+            max_gap: DEFAULT_MAX_GAP,
+            limit: None,
+        }
+    }
+
+    /// Sets the replay pacing
+    pub fn with_speed(mut self, speed: ReplaySpeed) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    /// Restricts the replay to one event type
+    pub fn with_event_type(mut self, event_type: impl Into<String>) -> Self {
+        self.event_type = Some(event_type.into());
+        self
+    }
+
+    fn validate(&self) -> Result<(), GuardianError> {
+        if self.start_time > self.end_time {
+            return Err(replay_error(format!(
+                "Replay start {} is after end {}",
+                self.start_time, self.end_time
+            )));
+        }
+        if let ReplaySpeed::Accelerated { factor } = self.speed {
+            if !factor.is_finite() || factor <= 0.0 {
+                return Err(replay_error(format!("Invalid replay acceleration factor {}", factor)));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Outcome of a finished replay
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayReport {
+    pub operation_id: Uuid,
+    pub total: usize,
+    pub published: usize,
+    pub failed: usize,
+    pub elapsed: Duration,
+}
+
+/// Re-drives stored events onto the event bus as a tracked, cancellable operation
+#[derive(Debug, Clone)]
+pub struct EventReplayer {
+    event_store: Arc<EventStore>,
+    event_bus: EventBus,
+    operations: OperationRegistry,
+}
+
+impl EventReplayer {
+    /// Creates a replayer publishing to the given bus
+    pub fn new(event_store: Arc<EventStore>, event_bus: EventBus, operations: OperationRegistry) -> Self {
+        Self {
+            event_store,
+            event_bus,
+            operations,
+        }
+    }
+
+    /// Starts a replay in the background and returns its operation ID
+    pub fn start(&self, request: ReplayRequest) -> Result<Uuid, GuardianError> {
+        request.validate()?;
+        let handle = self.operations.start(REPLAY_OPERATION_KIND, &describe(&request))?;
+        let id = handle.id();
+
+        let replayer = self.clone();
+        tokio::spawn(async move {
+            if let Err(e) = replayer.run(request, handle).await {
+                warn!(operation_id = %id, error = %e, "Event replay failed");
+            }
+        });
+        Ok(id)
+    }
+
+    /// Runs a replay to completion
+    pub async fn replay(&self, request: ReplayRequest) -> Result<ReplayReport, GuardianError> {
+        request.validate()?;
+        let handle = self.operations.start(REPLAY_OPERATION_KIND, &describe(&request))?;
+        self.run(request, handle).await
+    }
+
+    #[instrument(skip(self, handle), fields(operation_id = %handle.id()))]
+    async fn run(&self, request: ReplayRequest, mut handle: OperationHandle) -> Result<ReplayReport, GuardianError> {
+        let result = self.publish_all(&request, &mut handle).await;
+        handle.finish_with(&result);
+        result
+    }
+
+    async fn publish_all(&self, request: &ReplayRequest, handle: &mut OperationHandle) -> Result<ReplayReport, GuardianError> {
+        let start = std::time::Instant::now();
+        let mut events = self
+            .event_store
+            .retrieve_events(EventQuery {
+                start_time: Some(request.start_time),
+                end_time: Some(request.end_time),
+                event_type: request.event_type.clone(),
+                limit: request.limit,
+            })
+            .await?;
+        events.sort_by_key(|event| event.timestamp);
+
+        let total = events.len();
+        let mut published = 0;
+        let mut failed = 0;
+        let mut previous: Option<u64> = None;
+
+        for (index, stored) in events.into_iter().enumerate() {
+            let gap = previous.map_or(Duration::ZERO, |prev| {
+                Duration::from_secs(stored.timestamp.saturating_sub(prev)).min(request.max_gap)
+            });
+            previous = Some(stored.timestamp);
+
+            let delay = request.speed.delay(gap);
+            if !delay.is_zero() {
+                tokio::select! {
+                    _ = tokio::time::sleep(delay) => {}
+                    _ = handle.cancelled() => {}
+                }
+            }
+            handle.check_cancelled()?;
+
+            match self.event_bus.publish(to_bus_event(stored)?).await {
+                Ok(()) => published += 1,
+                Err(e) => {
+                    warn!(error = %e, "Failed to publish replayed event");
+                    failed += 1;
+                }
+            }
+
+            if (index + 1) % PROGRESS_INTERVAL == 0 {
+                handle.set_progress((index + 1) as f32 / total as f32 * 100.0);
+            }
+        }
+
+        let report = ReplayReport {
+            operation_id: handle.id(),
+            total,
+            published,
+            failed,
+            elapsed: start.elapsed(),
+        };
+        counter!("guardian.events.replayed", published as u64);
+        histogram!("guardian.events.replay.duration", report.elapsed.as_secs_f64());
+        info!(total, published, failed, "Event replay complete");
+        Ok(report)
+    }
+}
+
+/// Rebuilds a bus event from storage, keeping the original timestamp and marking it as replayed
+fn to_bus_event(stored: StoredEvent) -> Result<Event, GuardianError> {
+    // Low priority keeps replays from displacing live traffic
+    let mut event = Event::new(stored.event_type, stored.payload, EventPriority::Low)?;
+    if let Ok(timestamp) = time::OffsetDateTime::from_unix_timestamp(stored.timestamp as i64) {
+        event.timestamp = timestamp;
+    }
+    event.metadata.insert(REPLAY_METADATA_KEY.to_string(), stored.id);
+    Ok(event)
+}
+
+fn describe(request: &ReplayRequest) -> String {
+    format!(
+        "replay {} events {}..{}",
+        request.event_type.as_deref().unwrap_or("all"),
+        request.start_time,
+        request.end_time
+    )
+}
+
+fn replay_error(context: String) -> GuardianError {
+    GuardianError::ValidationError {
+        context,
+        source: None,
+        severity: crate::utils::error::ErrorSeverity::Low,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: crate::utils::error::ErrorCategory::Validation,
+        retry_count: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replay_speed_delay() {
+        let gap = Duration::from_secs(10);
+        assert_eq!(ReplaySpeed::RealTime.delay(gap), gap);
+        assert_eq!(ReplaySpeed::Accelerated { factor: 4.0 }.delay(gap), Duration::from_millis(2500));
+        assert_eq!(ReplaySpeed::Unthrottled.delay(gap), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_replay_request_validation() {
+        assert!(ReplayRequest::new(100, 200).validate().is_ok());
+        assert!(ReplayRequest::new(200, 100).validate().is_err());
+        let stalled = ReplayRequest::new(100, 200).with_speed(ReplaySpeed::Accelerated { factor: 0.0 });
+        assert!(stalled.validate().is_err());
+    }
+
+    #[test]
+    fn test_replayed_event_keeps_origin() {
+        let stored = StoredEvent {
+            id: "evt-42".into(),
+            timestamp: 1_700_000_000,
+            event_type: "threat_detected".into(),
+            payload: serde_json::json!({"severity": "high"}),
+            integrity_hash: String::new(),
+        };
+        let event = to_bus_event(stored).unwrap();
+        assert_eq!(event.timestamp.unix_timestamp(), 1_700_000_000);
+        assert_eq!(event.metadata[REPLAY_METADATA_KEY], "evt-42");
+        assert_eq!(event.priority, EventPriority::Low);
+    }
+}
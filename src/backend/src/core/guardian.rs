@@ -1,17 +1,27 @@
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
 use std::{
+    fmt,
     sync::{atomic::AtomicBool, Arc},
     time::Duration,
 };
-use temporal_sdk::{Client as TemporalClient, ClientOptions};
-use tokio::{sync::broadcast, time};
+use temporal_sdk::Client as TemporalClient;
+use tokio::{sync::{broadcast, watch}, time};
 use tracing::{debug, error, info, instrument, warn};
 
 use crate::utils::error::GuardianError;
+use crate::config::watcher::{subscribe_config_changes, CONFIG_CHANGE_EVENT_TYPE};
 use crate::core::metrics::CoreMetricsManager;
+use crate::core::dead_letter::dead_letters;
 use crate::core::event_bus::{Event, EventBus, EventPriority};
 use crate::core::system_state::{SystemHealth, SystemState};
+use crate::core::operations::OperationRegistry;
+use crate::core::resource_governor::governor;
+use crate::core::status::{status_service, ComponentState, ComponentStatus, StatusSource, SystemStateStatus, TEMPORAL};
+use crate::utils::inflight::inflight_registry;
+use crate::security::degradation::{degradation, DEGRADATION_EVENT_TYPE};
+use crate::security::offline_executor::ExecutionMode;
+use crate::temporal::{workflow_admin, TemporalConfig, TemporalRuntime};
 
 // Core system constants
 const SYSTEM_CHECK_INTERVAL: Duration = Duration::from_secs(60);
@@ -20,6 +30,134 @@ const DEFAULT_METRICS_PREFIX: &str = "guardian.core";
 const DEFAULT_EVENT_BUS_CAPACITY: usize = 10_000;
 const CIRCUIT_BREAKER_THRESHOLD: u32 = 5;
 const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(30);
+const TEMPORAL_RECONNECT_INTERVAL: Duration = Duration::from_secs(30);
+const DEFAULT_TENANT_ID: &str = "default";
+const MAX_TENANT_ID_LEN: usize = 63;
+
+/// Identifier of a tenant sharing a Guardian host
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct TenantId(String);
+
+impl TenantId {
+    /// Validates a tenant id, which must be usable in topics, metric names and dataset paths
+    pub fn new(id: impl Into<String>) -> Result<Self, GuardianError> {
+        let id = id.into();
+        let valid = !id.is_empty()
+            && id.len() <= MAX_TENANT_ID_LEN
+            && id.starts_with(|c: char| c.is_ascii_lowercase())
+            && id.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-');
+
+        if !valid {
+            return Err(GuardianError::ValidationError {
+                context: format!(
+                    "Invalid tenant id '{}': expected up to {} lowercase alphanumerics, '_' or '-'",
+                    id, MAX_TENANT_ID_LEN
+                ),
+                source: None,
+                severity: crate::utils::error::ErrorSeverity::High,
+                timestamp: time::OffsetDateTime::now_utc(),
+                correlation_id: crate::utils::correlation::current_or_new(),
+                category: crate::utils::error::ErrorCategory::Validation,
+                retry_count: 0,
+            });
+        }
+        Ok(Self(id))
+    }
+
+    /// Returns the tenant id as a string slice
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl TryFrom<String> for TenantId {
+    type Error = GuardianError;
+
+    fn try_from(id: String) -> Result<Self, Self::Error> {
+        Self::new(id)
+    }
+}
+
+impl From<TenantId> for String {
+    fn from(id: TenantId) -> Self {
+        id.0
+    }
+}
+
+impl fmt::Display for TenantId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Namespacing of event topics, metrics, datasets and workflows for one tenant.
+/// The default tenant keeps the unscoped names of single-tenant deployments.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TenantContext {
+    tenant_id: TenantId,
+}
+
+impl Default for TenantContext {
+    fn default() -> Self {
+        Self {
+            tenant_id: TenantId(DEFAULT_TENANT_ID.to_string()),
+        }
+    }
+}
+
+impl TenantContext {
+    /// Creates the context of a tenant
+    pub fn new(tenant_id: TenantId) -> Self {
+        Self { tenant_id }
+    }
+
+    /// Returns the tenant this context isolates
+    pub fn tenant_id(&self) -> &TenantId {
+        &self.tenant_id
+    }
+
+    /// Returns true for the default tenant of single-tenant deployments
+    pub fn is_default(&self) -> bool {
+        self.tenant_id.as_str() == DEFAULT_TENANT_ID
+    }
+
+    /// Returns the EventBus topic of an event type for this tenant
+    pub fn topic(&self, event_type: &str) -> String {
+        if self.is_default() {
+            event_type.to_string()
+        } else {
+            format!("tenant.{}.{}", self.tenant_id, event_type)
+        }
+    }
+
+    /// Returns the metric name prefix for this tenant
+    pub fn metrics_prefix(&self, prefix: &str) -> String {
+        if self.is_default() {
+            prefix.to_string()
+        } else {
+            format!("{}.tenant.{}", prefix, self.tenant_id)
+        }
+    }
+
+    /// Returns the ZFS dataset subtree for this tenant under a root dataset
+    pub fn dataset(&self, root: &str) -> String {
+        if self.is_default() {
+            root.to_string()
+        } else {
+            format!("{}/tenants/{}", root, self.tenant_id)
+        }
+    }
+
+    /// Returns the Temporal namespace for this tenant
+    pub fn temporal_namespace(&self, namespace: &str) -> String {
+        if self.is_default() {
+            namespace.to_string()
+        } else {
+            format!("{}-{}", namespace, self.tenant_id)
+        }
+    }
+}
 
 /// Configuration for the Guardian system
 #[derive(Debug, Clone, Deserialize)]
@@ -30,6 +168,15 @@ pub struct GuardianConfig {
     pub event_bus_capacity: usize,
     pub monitor_interval: Duration,
     pub circuit_breaker_threshold: u32,
+    #[serde(default)]
+    pub tenant_id: Option<TenantId>,
+    /// Keep running without Temporal, executing responses locally until it is reachable
+    #[serde(default = "default_allow_offline")]
+    pub allow_offline: bool,
+}
+
+fn default_allow_offline() -> bool {
+    true
 }
 
 impl GuardianConfig {
@@ -53,9 +200,21 @@ impl GuardianConfig {
             circuit_breaker_threshold: std::env::var("GUARDIAN_CIRCUIT_BREAKER_THRESHOLD")
                 .map(|v| v.parse().unwrap_or(CIRCUIT_BREAKER_THRESHOLD))
                 .unwrap_or(CIRCUIT_BREAKER_THRESHOLD),
+            tenant_id: std::env::var("GUARDIAN_TENANT_ID")
+                .ok()
+                .map(TenantId::new)
+                .transpose()?,
+            allow_offline: std::env::var("GUARDIAN_ALLOW_OFFLINE")
+                .map(|v| v.parse().unwrap_or(true))
+                .unwrap_or_else(|_| default_allow_offline()),
         })
     }
 
+    /// Returns the tenant context of this deployment
+    pub fn tenant(&self) -> TenantContext {
+        self.tenant_id.clone().map(TenantContext::new).unwrap_or_default()
+    }
+
     /// Validates configuration parameters
     pub fn validate(&self) -> Result<(), GuardianError> {
         if self.event_bus_capacity == 0 {
@@ -64,7 +223,7 @@ impl GuardianConfig {
                 source: None,
                 severity: crate::utils::error::ErrorSeverity::High,
                 timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id: uuid::Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: crate::utils::error::ErrorCategory::Validation,
                 retry_count: 0,
             });
@@ -86,9 +245,14 @@ pub struct Guardian {
     event_bus: EventBus,
     metrics: CoreMetricsManager,
     system_state: Arc<RwLock<SystemState>>,
-    temporal_client: TemporalClient,
+    temporal: Arc<watch::Sender<Option<TemporalClient>>>,
+    temporal_namespace: String,
     shutdown_signal: broadcast::Sender<()>,
     circuit_breaker: Arc<CircuitBreaker>,
+    operations: OperationRegistry,
+    tenant: TenantContext,
+    /// Health monitor failing the Temporal runtime over between endpoints, aborted on shutdown
+    failover_monitor: Arc<parking_lot::Mutex<Option<tokio::task::JoinHandle<()>>>>,
 }
 
 impl Guardian {
@@ -96,6 +260,7 @@ impl Guardian {
     #[instrument(skip(config))]
     pub async fn new(config: GuardianConfig) -> Result<Self, GuardianError> {
         config.validate()?;
+        let tenant = config.tenant();
 
         // Initialize event bus
         let event_bus = EventBus::new(CoreMetricsManager::new(
@@ -113,20 +278,44 @@ impl Guardian {
                 priority_levels: std::collections::HashMap::new(),
                 buffer_size: config.event_bus_capacity,
             },
-        )?)?;
+        )?.with_tenant(tenant.clone()))?;
 
-        // Initialize Temporal client
-        let temporal_client = TemporalClient::connect(ClientOptions::default().namespace(&config.temporal_namespace))
-            .await
-            .map_err(|e| GuardianError::SystemError {
-                context: "Failed to connect to Temporal".into(),
-                source: Some(Box::new(e)),
-                severity: crate::utils::error::ErrorSeverity::Critical,
-                timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id: uuid::Uuid::new_v4(),
-                category: crate::utils::error::ErrorCategory::System,
-                retry_count: 0,
-            })?;
+        // Initialize the Temporal runtime, whose client fails over between endpoints
+        let namespace = tenant.temporal_namespace(&config.temporal_namespace);
+        let temporal_config = TemporalConfig { namespace: namespace.clone(), ..Default::default() };
+        let temporal_metrics = Arc::new(CoreMetricsManager::new(
+            crate::utils::metrics::MetricsCollector::new(
+                crate::utils::metrics::MetricsConfig {
+                    statsd_host: "localhost".into(),
+                    statsd_port: 8125,
+                    buffer_size: Some(config.event_bus_capacity),
+                    flush_interval: Some(Duration::from_secs(10)),
+                    sampling_rates: None,
+                },
+            )?,
+            crate::core::metrics::MetricsConfig {
+                sampling_rates: std::collections::HashMap::new(),
+                priority_levels: std::collections::HashMap::new(),
+                buffer_size: config.event_bus_capacity,
+            },
+        )?.with_tenant(tenant.clone()));
+        let temporal_runtime = match TemporalRuntime::initialize(temporal_config, temporal_metrics).await {
+            Ok(runtime) => Some(Arc::new(runtime)),
+            // Air-gapped appliances run degraded rather than not at all
+            Err(e) if config.allow_offline => {
+                warn!(error = %e, namespace = %namespace, "Temporal unreachable, starting in offline mode");
+                None
+            }
+            Err(e) => return Err(e),
+        };
+        let temporal_client = temporal_runtime.as_ref().map(|runtime| (*runtime.client()).clone());
+        // Workflow administration follows the runtime across failovers
+        let failover_monitor = temporal_runtime.as_ref().map(|runtime| {
+            workflow_admin().attach_runtime(Arc::clone(runtime));
+            runtime.start_failover_monitor()
+        });
+        let offline = temporal_client.is_none();
+        let (temporal, _) = watch::channel(temporal_client);
 
         let (shutdown_tx, _) = broadcast::channel(1);
 
@@ -147,7 +336,7 @@ impl Guardian {
                     priority_levels: std::collections::HashMap::new(),
                     buffer_size: config.event_bus_capacity,
                 },
-            )?,
+            )?.with_tenant(tenant.clone()),
             system_state: SystemState::new(
                 crate::utils::metrics::MetricsCollector::new(
                     crate::utils::metrics::MetricsConfig {
@@ -165,18 +354,40 @@ impl Guardian {
                     health_check_interval: config.monitor_interval,
                 },
             )?,
-            temporal_client,
+            temporal: Arc::new(temporal),
+            temporal_namespace: namespace,
             shutdown_signal: shutdown_tx,
             circuit_breaker: Arc::new(CircuitBreaker {
                 failures: AtomicBool::new(false),
                 threshold: config.circuit_breaker_threshold,
             }),
+            operations: OperationRegistry::new(),
+            tenant,
+            failover_monitor: Arc::new(parking_lot::Mutex::new(failover_monitor)),
         };
 
+        // Report system and Temporal health in the unified status
+        status_service().register(Arc::new(SystemStateStatus::new(Arc::clone(&guardian.system_state))));
+        status_service().register(Arc::new(TemporalConnectionStatus {
+            temporal: guardian.subscribe_temporal(),
+            namespace: guardian.temporal_namespace.clone(),
+        }));
+
         // Start system monitoring
         let guardian_clone = Arc::new(guardian.clone());
         tokio::spawn(monitor_system(guardian_clone));
 
+        if offline {
+            tokio::spawn(reconnect_temporal(Arc::new(guardian.clone())));
+        }
+
+        tokio::spawn(publish_throttle_events(Arc::new(guardian.clone())));
+        tokio::spawn(publish_config_changes(Arc::new(guardian.clone())));
+        tokio::spawn(publish_degradation_changes(Arc::new(guardian.clone())));
+
+        // Redeliver events that subscribers failed to take or handed back
+        dead_letters().start(guardian.event_bus.clone());
+
         Ok(guardian)
     }
 
@@ -193,7 +404,7 @@ impl Guardian {
                 source: None,
                 severity: crate::utils::error::ErrorSeverity::Critical,
                 timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id: uuid::Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: crate::utils::error::ErrorCategory::System,
                 retry_count: 0,
             });
@@ -211,11 +422,17 @@ impl Guardian {
     pub async fn shutdown(&self) -> Result<(), GuardianError> {
         info!("Initiating Guardian system shutdown");
 
+        // Finish outstanding requests, rejecting new ones, before components stop
+        let reports = inflight_registry().drain_all(SHUTDOWN_TIMEOUT).await;
+        let drained: u64 = reports.iter().map(|r| r.drained).sum();
+        let abandoned: usize = reports.iter().map(|r| r.abandoned).sum();
+        info!(drained, abandoned, "In-flight requests drained");
+
         // Broadcast shutdown signal
         let _ = self.shutdown_signal.send(());
-
-        // Wait for components to shutdown
-        time::sleep(SHUTDOWN_TIMEOUT).await;
+        if let Some(monitor) = self.failover_monitor.lock().take() {
+            monitor.abort();
+        }
 
         // Cleanup resources
         self.event_bus.shutdown().await?;
@@ -227,10 +444,49 @@ impl Guardian {
         Ok(())
     }
 
+    /// Returns the registry tracking long-running operations
+    pub fn operations(&self) -> &OperationRegistry {
+        &self.operations
+    }
+
+    /// Returns the bus components publish their events on
+    pub fn event_bus(&self) -> &EventBus {
+        &self.event_bus
+    }
+
+    /// Returns the tenant whose topics, metrics, datasets and workflows this instance uses
+    pub fn tenant(&self) -> &TenantContext {
+        &self.tenant
+    }
+
+    /// Returns the Temporal client, `None` while running offline
+    pub fn temporal_client(&self) -> Option<TemporalClient> {
+        self.temporal.borrow().clone()
+    }
+
+    /// Watches the Temporal client, which changes to `Some` when connectivity returns
+    pub fn subscribe_temporal(&self) -> watch::Receiver<Option<TemporalClient>> {
+        self.temporal.subscribe()
+    }
+
+    /// Returns whether workflows run on Temporal or the system is in offline mode
+    pub fn execution_mode(&self) -> ExecutionMode {
+        if self.temporal.borrow().is_some() {
+            ExecutionMode::Online
+        } else {
+            ExecutionMode::Offline
+        }
+    }
+
     // Private helper methods
     async fn start_workflows(&self) -> Result<(), GuardianError> {
+        let Some(temporal_client) = self.temporal_client() else {
+            info!("Offline mode, core workflow starts once Temporal is reachable");
+            return Ok(());
+        };
+
         // Start core workflow
-        self.temporal_client
+        temporal_client
             .start_workflow("guardian-core", (), None)
             .await
             .map_err(|e| GuardianError::SystemError {
@@ -238,7 +494,7 @@ impl Guardian {
                 source: Some(Box::new(e)),
                 severity: crate::utils::error::ErrorSeverity::Critical,
                 timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id: uuid::Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: crate::utils::error::ErrorCategory::System,
                 retry_count: 0,
             })?;
@@ -253,9 +509,166 @@ impl Clone for Guardian {
             event_bus: self.event_bus.clone(),
             metrics: self.metrics.clone(),
             system_state: Arc::clone(&self.system_state),
-            temporal_client: self.temporal_client.clone(),
+            temporal: Arc::clone(&self.temporal),
+            temporal_namespace: self.temporal_namespace.clone(),
             shutdown_signal: self.shutdown_signal.clone(),
             circuit_breaker: Arc::clone(&self.circuit_breaker),
+            operations: self.operations.clone(),
+            tenant: self.tenant.clone(),
+            failover_monitor: Arc::clone(&self.failover_monitor),
+        }
+    }
+}
+
+/// Temporal connectivity: healthy when the server answers, degraded while offline
+struct TemporalConnectionStatus {
+    temporal: watch::Receiver<Option<TemporalClient>>,
+    namespace: String,
+}
+
+#[async_trait::async_trait]
+impl StatusSource for TemporalConnectionStatus {
+    fn component(&self) -> &'static str {
+        TEMPORAL
+    }
+
+    async fn status(&self) -> Result<ComponentStatus, GuardianError> {
+        let Some(client) = self.temporal.borrow().clone() else {
+            return Ok(ComponentStatus::new(TEMPORAL, ComponentState::Degraded)
+                .with_detail(format!("offline mode, responses execute locally (namespace {})", self.namespace)));
+        };
+        Ok(match client.get_system_info().await {
+            Ok(_) => ComponentStatus::new(TEMPORAL, ComponentState::Healthy).with_detail(format!("namespace {}", self.namespace)),
+            Err(e) => ComponentStatus::new(TEMPORAL, ComponentState::Degraded)
+                .with_detail(format!("server unreachable (namespace {}): {}", self.namespace, e)),
+        })
+    }
+}
+
+/// Background task reconnecting to Temporal after an offline start
+#[instrument(skip(guardian))]
+async fn reconnect_temporal(guardian: Arc<Guardian>) {
+    let mut interval = time::interval(TEMPORAL_RECONNECT_INTERVAL);
+    let mut shutdown = guardian.shutdown_signal.subscribe();
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {}
+            _ = shutdown.recv() => return,
+        }
+
+        let config = TemporalConfig { namespace: guardian.temporal_namespace.clone(), ..Default::default() };
+        let runtime = match TemporalRuntime::initialize(config, Arc::new(guardian.metrics.clone())).await {
+            Ok(runtime) => Arc::new(runtime),
+            Err(e) => {
+                debug!(error = %e, "Temporal still unreachable");
+                continue;
+            }
+        };
+        workflow_admin().attach_runtime(Arc::clone(&runtime));
+        *guardian.failover_monitor.lock() = Some(runtime.start_failover_monitor());
+
+        guardian.temporal.send_replace(Some((*runtime.client()).clone()));
+        info!(namespace = %guardian.temporal_namespace, "Temporal reachable, leaving offline mode");
+
+        if let Err(e) = guardian.start_workflows().await {
+            error!(error = ?e, "Failed to start core workflow after reconnecting");
+        }
+        if let Ok(event) = Event::new(
+            guardian.tenant.topic("system.temporal_connected"),
+            serde_json::json!({ "namespace": guardian.temporal_namespace }),
+            EventPriority::High,
+        ) {
+            let _ = guardian.event_bus.publish(event).await;
+        }
+        return;
+    }
+}
+
+/// Publishes resource governor throttle events on the tenant's event topic
+async fn publish_throttle_events(guardian: Arc<Guardian>) {
+    let mut throttles = governor().subscribe();
+    let mut shutdown = guardian.shutdown_signal.subscribe();
+
+    loop {
+        let throttle = tokio::select! {
+            throttle = throttles.recv() => throttle,
+            _ = shutdown.recv() => return,
+        };
+        let throttle = match throttle {
+            Ok(throttle) => throttle,
+            Err(broadcast::error::RecvError::Lagged(_)) => continue,
+            Err(broadcast::error::RecvError::Closed) => return,
+        };
+
+        let payload = match serde_json::to_value(&throttle) {
+            Ok(payload) => payload,
+            Err(e) => {
+                warn!(error = %e, "Failed to encode throttle event");
+                continue;
+            }
+        };
+        if let Ok(event) = Event::new(guardian.tenant.topic("system.resource_throttle"), payload, EventPriority::High) {
+            let _ = guardian.event_bus.publish(event).await;
+        }
+    }
+}
+
+/// Publishes the outcome of configuration reloads on the tenant's event topic
+async fn publish_config_changes(guardian: Arc<Guardian>) {
+    let mut changes = subscribe_config_changes();
+    let mut shutdown = guardian.shutdown_signal.subscribe();
+
+    loop {
+        let change = tokio::select! {
+            change = changes.recv() => change,
+            _ = shutdown.recv() => return,
+        };
+        let change = match change {
+            Ok(change) => change,
+            Err(broadcast::error::RecvError::Lagged(_)) => continue,
+            Err(broadcast::error::RecvError::Closed) => return,
+        };
+
+        let payload = match serde_json::to_value(&change) {
+            Ok(payload) => payload,
+            Err(e) => {
+                warn!(error = %e, "Failed to encode config change event");
+                continue;
+            }
+        };
+        if let Ok(event) = Event::new(guardian.tenant.topic(CONFIG_CHANGE_EVENT_TYPE), payload, EventPriority::High) {
+            let _ = guardian.event_bus.publish(event).await;
+        }
+    }
+}
+
+/// Publishes steps of the detection degradation ladder on the tenant's event topic
+async fn publish_degradation_changes(guardian: Arc<Guardian>) {
+    let mut changes = degradation().subscribe();
+    let mut shutdown = guardian.shutdown_signal.subscribe();
+
+    loop {
+        let change = tokio::select! {
+            change = changes.recv() => change,
+            _ = shutdown.recv() => return,
+        };
+        let change = match change {
+            Ok(change) => change,
+            Err(broadcast::error::RecvError::Lagged(_)) => continue,
+            Err(broadcast::error::RecvError::Closed) => return,
+        };
+
+        let priority = if change.to > change.from { EventPriority::High } else { EventPriority::Medium };
+        let payload = match serde_json::to_value(&change) {
+            Ok(payload) => payload,
+            Err(e) => {
+                warn!(error = %e, "Failed to encode degradation event");
+                continue;
+            }
+        };
+        if let Ok(event) = Event::new(guardian.tenant.topic(DEGRADATION_EVENT_TYPE), payload, priority) {
+            let _ = guardian.event_bus.publish(event).await;
         }
     }
 }
@@ -288,12 +701,18 @@ async fn monitor_system(guardian: Arc<Guardian>) -> Result<(), GuardianError> {
         guardian
             .event_bus
             .publish(Event::new(
-                "system.state".into(),
+                guardian.tenant.topic("system.state"),
                 serde_json::to_value(&state)?,
                 EventPriority::High,
             )?)
             .await?;
 
+        // Drop finished operations past their retention window
+        let pruned = guardian.operations.prune();
+        if pruned > 0 {
+            debug!(pruned, "Pruned finished operations");
+        }
+
         debug!(?state, "System state monitored");
     }
 }
@@ -311,10 +730,31 @@ mod tests {
             event_bus_capacity: DEFAULT_EVENT_BUS_CAPACITY,
             monitor_interval: Duration::from_secs(1),
             circuit_breaker_threshold: CIRCUIT_BREAKER_THRESHOLD,
+            tenant_id: None,
+            allow_offline: true,
         };
 
         let guardian = Guardian::new(config).await.unwrap();
         assert!(guardian.start().await.is_ok());
         assert!(guardian.shutdown().await.is_ok());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_tenant_context_scoping() {
+        assert!(TenantId::new("Acme").is_err());
+        assert!(TenantId::new("acme/../etc").is_err());
+        assert!(TenantId::new("").is_err());
+
+        let tenant = TenantContext::new(TenantId::new("acme").unwrap());
+        assert_eq!(tenant.topic("threat_detected"), "tenant.acme.threat_detected");
+        assert_eq!(tenant.metrics_prefix("guardian.system"), "guardian.system.tenant.acme");
+        assert_eq!(tenant.dataset("tank/guardian"), "tank/guardian/tenants/acme");
+        assert_eq!(tenant.temporal_namespace("guardian"), "guardian-acme");
+
+        // Single-tenant deployments keep their existing names
+        let default = TenantContext::default();
+        assert!(default.is_default());
+        assert_eq!(default.topic("system.state"), "system.state");
+        assert_eq!(default.dataset("tank/guardian"), "tank/guardian");
+    }
+}
@@ -8,6 +8,7 @@ use std::{
 };
 use tokio::time;
 
+use crate::core::guardian::TenantContext;
 use crate::utils::error::GuardianError;
 use crate::utils::metrics::{MetricsCollector, MetricPriority};
 
@@ -19,6 +20,7 @@ const AGGREGATION_INTERVAL: Duration = Duration::from_secs(300);
 const DEFAULT_BUFFER_SIZE: usize = 10000;
 const MAX_RETRY_ATTEMPTS: u32 = 3;
 const CIRCUIT_BREAKER_THRESHOLD: u32 = 5;
+const SUBSYSTEM_TAG: &str = "subsystem";
 
 /// Categories for different types of metrics with priority levels
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -28,6 +30,17 @@ pub enum MetricCategory {
     ML,
 }
 
+impl MetricCategory {
+    /// Subsystem label attached to exported metrics
+    pub fn label(&self) -> &'static str {
+        match self {
+            MetricCategory::System => "system",
+            MetricCategory::Security => "security",
+            MetricCategory::ML => "ml",
+        }
+    }
+}
+
 /// Priority levels for metric processing
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Priority {
@@ -62,6 +75,7 @@ pub struct CoreMetricsManager {
     priority_config: RwLock<HashMap<MetricCategory, Priority>>,
     buffer_size: AtomicUsize,
     circuit_breaker: CircuitBreaker,
+    tenant: TenantContext,
 }
 
 impl CoreMetricsManager {
@@ -78,6 +92,7 @@ impl CoreMetricsManager {
                 last_failure: RwLock::new(time::Instant::now()),
                 is_open: RwLock::new(false),
             },
+            tenant: TenantContext::default(),
         };
 
         // Start background aggregation task
@@ -106,6 +121,12 @@ impl CoreMetricsManager {
         Ok(manager)
     }
 
+    /// Prefixes every recorded metric with the tenant namespace
+    pub fn with_tenant(mut self, tenant: TenantContext) -> Self {
+        self.tenant = tenant;
+        self
+    }
+
     /// Records a system-level metric with priority and sampling
     pub async fn record_system_metric(
         &self,
@@ -144,7 +165,7 @@ impl CoreMetricsManager {
                 source: None,
                 severity: crate::utils::error::ErrorSeverity::Medium,
                 timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id: uuid::Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: crate::utils::error::ErrorCategory::Validation,
                 retry_count: 0,
             });
@@ -178,7 +199,7 @@ impl CoreMetricsManager {
                 source: None,
                 severity: crate::utils::error::ErrorSeverity::Medium,
                 timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id: uuid::Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: crate::utils::error::ErrorCategory::System,
                 retry_count: 0,
             });
@@ -189,8 +210,9 @@ impl CoreMetricsManager {
             MetricCategory::Security => SECURITY_METRICS_PREFIX,
             MetricCategory::ML => ML_METRICS_PREFIX,
         };
+        let tags = HashMap::from([(SUBSYSTEM_TAG.to_string(), category.label().to_string())]);
 
-        let metric_name = format!("{}.{}", prefix, name);
+        let metric_name = format!("{}.{}", self.tenant.metrics_prefix(prefix), name);
         let priority = priority.unwrap_or_else(|| {
             self.priority_config
                 .read()
@@ -215,14 +237,14 @@ impl CoreMetricsManager {
                     Priority::Medium => MetricPriority::Medium,
                     Priority::Low => MetricPriority::Low,
                 },
-                None,
+                Some(tags),
             )
             .map_err(|e| GuardianError::SystemError {
                 context: "Failed to record metric".into(),
                 source: Some(Box::new(e)),
                 severity: crate::utils::error::ErrorSeverity::Medium,
                 timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id: uuid::Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: crate::utils::error::ErrorCategory::System,
                 retry_count: 0,
             })?;
@@ -238,7 +260,7 @@ impl CoreMetricsManager {
             gauge!(
                 &metric.name,
                 metric.value,
-                "category" => metric.tags.get("category").unwrap_or(&"unknown".to_string()).clone()
+                SUBSYSTEM_TAG => metric.tags.get(SUBSYSTEM_TAG).cloned().unwrap_or_else(|| "unknown".to_string())
             );
         }
 
@@ -274,6 +296,7 @@ impl Clone for CoreMetricsManager {
                 last_failure: RwLock::new(*self.circuit_breaker.last_failure.read()),
                 is_open: RwLock::new(*self.circuit_breaker.is_open.read()),
             },
+            tenant: self.tenant.clone(),
         }
     }
 }
@@ -5,8 +5,11 @@
 //! providing efficient resource utilization, real-time protection capabilities,
 //! and autonomous response orchestration.
 
-use tokio::runtime::{Builder, Runtime}; // v1.32
+use std::future::Future;
+use once_cell::sync::OnceCell;
+use tokio::runtime::Runtime; // v1.32
 use tracing::{info, error, instrument}; // v0.1
+use crate::utils::affinity::{affinity_manager, ThreadClass};
 use crate::utils::error::{GuardianError, Result};
 
 // Core module version and name constants
@@ -14,21 +17,71 @@ pub const CORE_VERSION: &str = "1.0.0";
 pub const CORE_MODULE_NAME: &str = "guardian_core";
 
 // Export core submodules
+pub mod capabilities;
+pub mod collectors;
+pub mod dead_letter;
+pub mod diagnostics;
 pub mod metrics;
 pub mod event_bus;
+pub mod event_replay;
 pub mod system_state;
 pub mod guardian;
+pub mod operations;
+pub mod posture;
+pub mod resource_governor;
+pub mod state_journal;
+pub mod status;
+pub mod support_bundle;
 
 // Re-export commonly used types
+pub use capabilities::{capabilities, init_capabilities, Capability, CapabilityMatrix, ComponentMode};
+pub use collectors::{collectors, init_collectors, Collector, Snapshot, SystemCollectors};
+pub use diagnostics::{run_diagnostics, CheckResult, CheckStatus, DiagnosticCheck, DiagnosticReport};
+pub use dead_letter::{dead_letters, init_dead_letters, DeadLetter, DeadLetterQueue, DeadLetterState};
 pub use metrics::{CoreMetricsManager, SystemMetricType};
-pub use event_bus::{EventBus, Event};
+pub use event_bus::{EventBus, Event, EventReceiver};
+pub use event_replay::{EventReplayer, ReplayReport, ReplayRequest, ReplaySpeed};
 pub use system_state::{SystemState, SystemStatus};
-pub use guardian::{Guardian, GuardianConfig};
+pub use guardian::{Guardian, GuardianConfig, TenantContext, TenantId};
+pub use operations::{Operation, OperationHandle, OperationRegistry, OperationStatus};
+pub use posture::{PostureConfig, PostureFactor, PostureHistory, PostureScore, PostureScorer};
+pub use resource_governor::{governor, init_governor, ResourceGovernor, Subsystem, ThrottleEvent, WorkPermit};
+pub use state_journal::{state_journal, StateJournal, StateTransition, TransitionCause};
+pub use status::{status_service, ComponentState, ComponentStatus, StatusReport, StatusService, StatusSource, STATUS_SCHEMA_VERSION};
+pub use support_bundle::{SupportBundle, SupportBundleConfig};
+
+/// Worker pool model execution runs on, pinned to the inference cpuset; lives for the process
+static INFERENCE_RUNTIME: OnceCell<Runtime> = OnceCell::new();
+
+/// Runs a future on the inference pool, or inline before the core has created it
+pub async fn on_inference_runtime<F>(future: F) -> Result<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    match INFERENCE_RUNTIME.get() {
+        // The task keeps the caller's correlation ID across the pool boundary
+        Some(runtime) => runtime
+            .spawn(crate::utils::correlation::scope(crate::utils::correlation::current_or_new(), future))
+            .await
+            .map_err(|e| GuardianError::SystemError {
+                context: "Inference task failed".into(),
+                source: Some(Box::new(e)),
+                severity: crate::utils::error::ErrorSeverity::High,
+                timestamp: time::OffsetDateTime::now_utc(),
+                correlation_id: crate::utils::correlation::current_or_new(),
+                category: crate::utils::error::ErrorCategory::System,
+                retry_count: 0,
+            }),
+        None => Ok(future.await),
+    }
+}
 
 /// Runtime configuration for the Guardian core system
+///
+/// I/O work runs on the daemon's own runtime, built with the I/O cpuset before any task starts.
 #[derive(Debug)]
 struct CoreRuntime {
-    runtime: Runtime,
     metrics_manager: CoreMetricsManager,
     event_bus: EventBus,
     system_state: SystemState,
@@ -37,26 +90,29 @@ struct CoreRuntime {
 impl CoreRuntime {
     /// Creates a new optimized runtime instance for the Guardian core
     fn new() -> Result<Self> {
-        let runtime = Builder::new_multi_thread()
-            .thread_name("guardian-core")
-            .enable_all()
-            .build()
-            .map_err(|e| GuardianError::SystemError {
-                context: "Failed to initialize core runtime".into(),
-                source: Some(Box::new(e)),
-                severity: crate::utils::error::ErrorSeverity::Critical,
-                timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id: uuid::Uuid::new_v4(),
-                category: crate::utils::error::ErrorCategory::System,
-                retry_count: 0,
-            })?;
+        // Inference gets its own pool so model execution stays off the I/O cores
+        INFERENCE_RUNTIME.get_or_try_init(|| {
+            affinity_manager()
+                .runtime_builder(ThreadClass::Inference)
+                .thread_name("guardian-inference")
+                .enable_all()
+                .build()
+                .map_err(|e| GuardianError::SystemError {
+                    context: "Failed to initialize inference runtime".into(),
+                    source: Some(Box::new(e)),
+                    severity: crate::utils::error::ErrorSeverity::Critical,
+                    timestamp: time::OffsetDateTime::now_utc(),
+                    correlation_id: crate::utils::correlation::current_or_new(),
+                    category: crate::utils::error::ErrorCategory::System,
+                    retry_count: 0,
+                })
+        })?;
 
         let metrics_manager = CoreMetricsManager::new()?;
         let event_bus = EventBus::new()?;
         let system_state = SystemState::new()?;
 
         Ok(Self {
-            runtime,
             metrics_manager,
             event_bus,
             system_state,
@@ -78,7 +134,7 @@ pub async fn init_core(config: GuardianConfig) -> Result<Guardian> {
         source: Some(Box::new(e)),
         severity: crate::utils::error::ErrorSeverity::Critical,
         timestamp: time::OffsetDateTime::now_utc(),
-        correlation_id: uuid::Uuid::new_v4(),
+        correlation_id: crate::utils::correlation::current_or_new(),
         category: crate::utils::error::ErrorCategory::Validation,
         retry_count: 0,
     })?;
@@ -0,0 +1,339 @@
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use chrono::{DateTime, Utc};
+use metrics::{counter, gauge};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use tokio::sync::watch;
+use tracing::{debug, info, instrument, warn};
+use uuid::Uuid;
+
+use crate::utils::error::GuardianError;
+use crate::utils::ids::{next_uuid, IdKind};
+
+// Constants for operation tracking configuration
+const MAX_TRACKED_OPERATIONS: usize = 1024;
+const FINISHED_OPERATION_RETENTION: Duration = Duration::from_secs(24 * 3600);
+
+/// Lifecycle status of a long-running operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OperationStatus {
+    Running,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+impl OperationStatus {
+    /// Returns true once the operation can no longer change state
+    pub fn is_terminal(&self) -> bool {
+        !matches!(self, OperationStatus::Running)
+    }
+}
+
+/// Snapshot of a tracked long-running operation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Operation {
+    pub id: Uuid,
+    pub kind: String,
+    pub description: String,
+    pub status: OperationStatus,
+    pub progress_percent: f32,
+    pub cancel_requested: bool,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug)]
+struct OperationEntry {
+    operation: Operation,
+    cancel_tx: watch::Sender<bool>,
+}
+
+/// Registry assigning IDs, progress and cancellation to long-running tasks
+#[derive(Debug, Clone, Default)]
+pub struct OperationRegistry {
+    operations: Arc<RwLock<HashMap<Uuid, OperationEntry>>>,
+}
+
+impl OperationRegistry {
+    /// Creates an empty operation registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a new running operation and returns the handle used to drive it
+    #[instrument(skip(self))]
+    pub fn start(&self, kind: &str, description: &str) -> Result<OperationHandle, GuardianError> {
+        let mut operations = self.operations.write();
+
+        if operations.len() >= MAX_TRACKED_OPERATIONS {
+            prune_finished(&mut operations, Duration::ZERO);
+        }
+        if operations.len() >= MAX_TRACKED_OPERATIONS {
+            return Err(GuardianError::SystemError {
+                context: format!("Operation limit of {} reached", MAX_TRACKED_OPERATIONS),
+                source: None,
+                severity: crate::utils::error::ErrorSeverity::Medium,
+                timestamp: time::OffsetDateTime::now_utc(),
+                correlation_id: crate::utils::correlation::current_or_new(),
+                category: crate::utils::error::ErrorCategory::System,
+                retry_count: 0,
+            });
+        }
+
+        let now = Utc::now();
+        let id = next_uuid(IdKind::Operation);
+        let (cancel_tx, cancel_rx) = watch::channel(false);
+
+        operations.insert(id, OperationEntry {
+            operation: Operation {
+                id,
+                kind: kind.to_string(),
+                description: description.to_string(),
+                status: OperationStatus::Running,
+                progress_percent: 0.0,
+                cancel_requested: false,
+                error: None,
+                created_at: now,
+                updated_at: now,
+            },
+            cancel_tx,
+        });
+
+        counter!("guardian.operations.started", 1, "kind" => kind.to_string());
+        gauge!("guardian.operations.tracked", operations.len() as f64);
+        info!(operation_id = %id, kind, "Operation started");
+
+        Ok(OperationHandle {
+            id,
+            registry: self.clone(),
+            cancel_rx,
+        })
+    }
+
+    /// Returns a snapshot of a single operation
+    pub fn get(&self, id: &Uuid) -> Option<Operation> {
+        self.operations.read().get(id).map(|e| e.operation.clone())
+    }
+
+    /// Lists operations, newest first, optionally filtered by status
+    pub fn list(&self, status: Option<OperationStatus>) -> Vec<Operation> {
+        let mut operations: Vec<Operation> = self
+            .operations
+            .read()
+            .values()
+            .map(|e| e.operation.clone())
+            .filter(|op| status.map_or(true, |s| op.status == s))
+            .collect();
+        operations.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        operations
+    }
+
+    /// Requests cancellation of a running operation
+    #[instrument(skip(self))]
+    pub fn cancel(&self, id: &Uuid) -> Result<Operation, GuardianError> {
+        let mut operations = self.operations.write();
+        let entry = operations.get_mut(id).ok_or_else(|| GuardianError::ValidationError {
+            context: format!("Operation {} not found", id),
+            source: None,
+            severity: crate::utils::error::ErrorSeverity::Low,
+            timestamp: time::OffsetDateTime::now_utc(),
+            correlation_id: crate::utils::correlation::current_or_new(),
+            category: crate::utils::error::ErrorCategory::Validation,
+            retry_count: 0,
+        })?;
+
+        if entry.operation.status.is_terminal() {
+            return Err(GuardianError::ValidationError {
+                context: format!("Operation {} already finished as {:?}", id, entry.operation.status),
+                source: None,
+                severity: crate::utils::error::ErrorSeverity::Low,
+                timestamp: time::OffsetDateTime::now_utc(),
+                correlation_id: crate::utils::correlation::current_or_new(),
+                category: crate::utils::error::ErrorCategory::Validation,
+                retry_count: 0,
+            });
+        }
+
+        entry.operation.cancel_requested = true;
+        entry.operation.updated_at = Utc::now();
+        let _ = entry.cancel_tx.send(true);
+
+        counter!("guardian.operations.cancel_requested", 1);
+        info!(operation_id = %id, "Operation cancellation requested");
+        Ok(entry.operation.clone())
+    }
+
+    /// Removes finished operations older than the retention window
+    pub fn prune(&self) -> usize {
+        let mut operations = self.operations.write();
+        let removed = prune_finished(&mut operations, FINISHED_OPERATION_RETENTION);
+        gauge!("guardian.operations.tracked", operations.len() as f64);
+        removed
+    }
+
+    fn update<F: FnOnce(&mut Operation)>(&self, id: &Uuid, f: F) {
+        if let Some(entry) = self.operations.write().get_mut(id) {
+            if entry.operation.status.is_terminal() {
+                return;
+            }
+            f(&mut entry.operation);
+            entry.operation.updated_at = Utc::now();
+        }
+    }
+
+    fn finish(&self, id: &Uuid, status: OperationStatus, error: Option<String>) {
+        self.update(id, |op| {
+            op.status = status;
+            op.error = error;
+            if status == OperationStatus::Completed {
+                op.progress_percent = 100.0;
+            }
+        });
+
+        let status_label = format!("{:?}", status).to_lowercase();
+        counter!("guardian.operations.finished", 1, "status" => status_label);
+        debug!(operation_id = %id, ?status, "Operation finished");
+    }
+}
+
+/// Handle held by the task performing a tracked operation
+#[derive(Debug)]
+pub struct OperationHandle {
+    id: Uuid,
+    registry: OperationRegistry,
+    cancel_rx: watch::Receiver<bool>,
+}
+
+impl OperationHandle {
+    /// Returns the operation ID exposed to CLI and gRPC clients
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    /// Records progress in percent, clamped to 0-100
+    pub fn set_progress(&self, percent: f32) {
+        self.registry.update(&self.id, |op| {
+            op.progress_percent = percent.clamp(0.0, 100.0);
+        });
+    }
+
+    /// Returns true once cancellation has been requested
+    pub fn is_cancelled(&self) -> bool {
+        *self.cancel_rx.borrow()
+    }
+
+    /// Resolves when cancellation is requested
+    pub async fn cancelled(&mut self) {
+        while !*self.cancel_rx.borrow() {
+            if self.cancel_rx.changed().await.is_err() {
+                // Registry dropped, cancellation can no longer be requested
+                std::future::pending::<()>().await;
+            }
+        }
+    }
+
+    /// Returns a cancellation error when cancellation has been requested
+    pub fn check_cancelled(&self) -> Result<(), GuardianError> {
+        if self.is_cancelled() {
+            return Err(GuardianError::SystemError {
+                context: format!("Operation {} was cancelled", self.id),
+                source: None,
+                severity: crate::utils::error::ErrorSeverity::Low,
+                timestamp: time::OffsetDateTime::now_utc(),
+                correlation_id: crate::utils::correlation::current_or_new(),
+                category: crate::utils::error::ErrorCategory::System,
+                retry_count: 0,
+            });
+        }
+        Ok(())
+    }
+
+    /// Marks the operation completed
+    pub fn complete(self) {
+        self.registry.finish(&self.id, OperationStatus::Completed, None);
+    }
+
+    /// Marks the operation failed, or cancelled if cancellation caused the failure
+    pub fn fail(self, error: &GuardianError) {
+        let status = if self.is_cancelled() {
+            OperationStatus::Cancelled
+        } else {
+            OperationStatus::Failed
+        };
+        self.registry.finish(&self.id, status, Some(error.to_string()));
+    }
+
+    /// Records the outcome of an operation result
+    pub fn finish_with<T>(self, result: &Result<T, GuardianError>) {
+        match result {
+            Ok(_) => self.complete(),
+            Err(e) => self.fail(e),
+        }
+    }
+}
+
+impl Drop for OperationHandle {
+    fn drop(&mut self) {
+        // A handle dropped without an outcome means the task died or was aborted
+        let dangling = self
+            .registry
+            .get(&self.id)
+            .map_or(false, |op| !op.status.is_terminal());
+        if dangling {
+            warn!(operation_id = %self.id, "Operation handle dropped while running");
+            let status = if self.is_cancelled() {
+                OperationStatus::Cancelled
+            } else {
+                OperationStatus::Failed
+            };
+            self.registry.finish(&self.id, status, Some("operation aborted".into()));
+        }
+    }
+}
+
+fn prune_finished(operations: &mut HashMap<Uuid, OperationEntry>, retention: Duration) -> usize {
+    let cutoff = Utc::now() - chrono::Duration::from_std(retention).unwrap_or_else(|_| chrono::Duration::zero());
+    let before = operations.len();
+    operations.retain(|_, e| !e.operation.status.is_terminal() || e.operation.updated_at > cutoff);
+    before - operations.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_operation_lifecycle() {
+        let registry = OperationRegistry::new();
+        let handle = registry.start("key_rotation", "rotate all keys").unwrap();
+        let id = handle.id();
+
+        handle.set_progress(40.0);
+        assert_eq!(registry.get(&id).unwrap().progress_percent, 40.0);
+        assert_eq!(registry.list(Some(OperationStatus::Running)).len(), 1);
+
+        handle.complete();
+        let op = registry.get(&id).unwrap();
+        assert_eq!(op.status, OperationStatus::Completed);
+        assert_eq!(op.progress_percent, 100.0);
+        assert!(registry.cancel(&id).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_operation_cancellation() {
+        let registry = OperationRegistry::new();
+        let mut handle = registry.start("retro_scan", "rescan last 24h").unwrap();
+        let id = handle.id();
+
+        registry.cancel(&id).unwrap();
+        tokio::time::timeout(Duration::from_secs(1), handle.cancelled()).await.unwrap();
+        assert!(handle.check_cancelled().is_err());
+
+        drop(handle);
+        assert_eq!(registry.get(&id).unwrap().status, OperationStatus::Cancelled);
+    }
+}
@@ -0,0 +1,385 @@
+use std::{collections::BTreeMap, sync::Arc, time::Duration};
+
+use chrono::{DateTime, Utc};
+use metrics::gauge;
+use parking_lot::RwLock;
+use serde::Serialize;
+use tokio::task::JoinHandle;
+use tracing::{info, instrument, warn};
+
+use crate::config::{active_profile, AppConfig};
+use crate::core::capabilities::{capabilities, Capability};
+use crate::ml::model_registry::ModelRegistry;
+use crate::security::content_pack::{ContentKind, ContentPackManager};
+use crate::security::incident::IncidentTracker;
+use crate::security::pipeline_latency::pipeline_latency;
+use crate::security::threat_detection::ThreatLevel;
+use crate::security::MAX_DETECTION_TIME_MS;
+use crate::storage::{Metric, MetricsQuery, MetricsStore};
+use crate::utils::error::GuardianError;
+use crate::utils::metrics::MetricType;
+
+// Constants for posture scoring
+const POSTURE_METRIC: &str = "guardian.posture.score";
+const FACTOR_METRIC: &str = "guardian.posture.factor";
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(300);
+/// Models younger than this score full freshness
+const DEFAULT_MODEL_FRESH_AGE: Duration = Duration::from_secs(7 * 24 * 3600);
+/// Models older than this score no freshness
+const DEFAULT_MODEL_STALE_AGE: Duration = Duration::from_secs(30 * 24 * 3600);
+const CONTENT_KINDS: [ContentKind; 4] = [
+    ContentKind::Rules,
+    ContentKind::Playbooks,
+    ContentKind::Baselines,
+    ContentKind::Intel,
+];
+const MIN_LOG_RETENTION_DAYS: u32 = 30;
+const MAX_AUTH_TIMEOUT_SECONDS: u64 = 900;
+const MAX_AUTH_RETRIES: u32 = 5;
+
+/// Contributing factors and their share of the overall score
+pub const MODEL_FRESHNESS: &str = "model_freshness";
+pub const RULE_COVERAGE: &str = "rule_coverage";
+pub const SLO_ATTAINMENT: &str = "slo_attainment";
+pub const UNRESOLVED_INCIDENTS: &str = "unresolved_incidents";
+pub const CONFIG_HYGIENE: &str = "config_hygiene";
+const FACTOR_WEIGHTS: [(&str, f64); 5] = [
+    (MODEL_FRESHNESS, 0.20),
+    (RULE_COVERAGE, 0.20),
+    (SLO_ATTAINMENT, 0.20),
+    (UNRESOLVED_INCIDENTS, 0.25),
+    (CONFIG_HYGIENE, 0.15),
+];
+
+/// One input to the posture score
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PostureFactor {
+    pub name: String,
+    /// 0–100, higher is better
+    pub score: u32,
+    /// Share of the overall score, renormalized over the factors that had data
+    pub weight: f64,
+    pub detail: String,
+}
+
+/// Overall security posture with the factors it was computed from
+#[derive(Debug, Clone, Serialize)]
+pub struct PostureScore {
+    /// 0–100, higher is better
+    pub score: u32,
+    pub computed_at: DateTime<Utc>,
+    pub factors: Vec<PostureFactor>,
+}
+
+/// A stored posture value
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PosturePoint {
+    pub at: DateTime<Utc>,
+    pub score: u32,
+}
+
+/// Stored posture over a time range, for plotting
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PostureHistory {
+    pub points: Vec<PosturePoint>,
+    pub factors: BTreeMap<String, Vec<PosturePoint>>,
+}
+
+/// Posture scoring configuration
+#[derive(Debug, Clone)]
+pub struct PostureConfig {
+    pub interval: Duration,
+    pub model_fresh_age: Duration,
+    pub model_stale_age: Duration,
+}
+
+impl Default for PostureConfig {
+    fn default() -> Self {
+        Self {
+            interval: DEFAULT_INTERVAL,
+            model_fresh_age: DEFAULT_MODEL_FRESH_AGE,
+            model_stale_age: DEFAULT_MODEL_STALE_AGE,
+        }
+    }
+}
+
+/// Periodically scores security posture and keeps its history in the metrics store
+#[derive(Debug)]
+pub struct PostureScorer {
+    config: PostureConfig,
+    app_config: AppConfig,
+    metrics_store: Arc<MetricsStore>,
+    models: Option<Arc<ModelRegistry>>,
+    content: Option<Arc<ContentPackManager>>,
+    incidents: Option<Arc<IncidentTracker>>,
+    latest: RwLock<Option<PostureScore>>,
+}
+
+impl PostureScorer {
+    /// Creates a scorer; sources not attached are left out of the score
+    pub fn new(config: PostureConfig, app_config: AppConfig, metrics_store: Arc<MetricsStore>) -> Self {
+        Self {
+            config,
+            app_config,
+            metrics_store,
+            models: None,
+            content: None,
+            incidents: None,
+            latest: RwLock::new(None),
+        }
+    }
+
+    /// Scores freshness of the active detection model
+    pub fn with_models(mut self, models: Arc<ModelRegistry>) -> Self {
+        self.models = Some(models);
+        self
+    }
+
+    /// Scores which content kinds active packs cover
+    pub fn with_content(mut self, content: Arc<ContentPackManager>) -> Self {
+        self.content = Some(content);
+        self
+    }
+
+    /// Scores incidents still awaiting resolution
+    pub fn with_incidents(mut self, incidents: Arc<IncidentTracker>) -> Self {
+        self.incidents = Some(incidents);
+        self
+    }
+
+    /// Recomputes the score every interval until the runtime shuts down
+    pub fn start(self: &Arc<Self>) -> JoinHandle<()> {
+        let scorer = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(scorer.config.interval);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = scorer.refresh().await {
+                    warn!(error = %e, "Failed to record posture score");
+                }
+            }
+        })
+    }
+
+    /// Computes the current score, publishes it and appends it to the history
+    #[instrument(skip(self))]
+    pub async fn refresh(&self) -> Result<PostureScore, GuardianError> {
+        let posture = self.compute().await;
+        *self.latest.write() = Some(posture.clone());
+
+        gauge!(POSTURE_METRIC, posture.score as f64);
+        let mut metrics = vec![Metric::new(POSTURE_METRIC, posture.score as f64, MetricType::Gauge)];
+        for factor in &posture.factors {
+            gauge!(FACTOR_METRIC, factor.score as f64, "factor" => factor.name.clone());
+            metrics.push(
+                Metric::new(FACTOR_METRIC, factor.score as f64, MetricType::Gauge).with_tag("factor", factor.name.clone()),
+            );
+        }
+        // Without performance metrics the current score is served but no history is kept
+        if capabilities().is_enabled(Capability::Metrics) {
+            self.metrics_store.store_metrics(metrics).await?;
+        }
+
+        info!(score = posture.score, factors = posture.factors.len(), "Posture score computed");
+        Ok(posture)
+    }
+
+    /// Returns the last computed score, computing one if none exists yet
+    pub async fn current(&self) -> Result<PostureScore, GuardianError> {
+        let latest = self.latest.read().clone();
+        match latest {
+            Some(posture) => Ok(posture),
+            None => self.refresh().await,
+        }
+    }
+
+    /// Returns stored scores between `from` and `to`
+    pub async fn history(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<PostureHistory, GuardianError> {
+        if !capabilities().is_enabled(Capability::Metrics) {
+            return Ok(PostureHistory::default());
+        }
+        let metrics = self
+            .metrics_store
+            .query_metrics(MetricsQuery {
+                time_range: (from, to),
+                metric_names: Some(vec![POSTURE_METRIC.to_string(), FACTOR_METRIC.to_string()]),
+            })
+            .await?;
+
+        let mut history = PostureHistory::default();
+        for metric in metrics.iter().filter(|m| m.timestamp() >= from && m.timestamp() <= to) {
+            let point = PosturePoint {
+                at: metric.timestamp(),
+                score: metric.value().round() as u32,
+            };
+            match (metric.name(), metric.tag("factor")) {
+                (POSTURE_METRIC, _) => history.points.push(point),
+                (FACTOR_METRIC, Some(factor)) => history.factors.entry(factor.to_string()).or_default().push(point),
+                _ => {}
+            }
+        }
+        history.points.sort_by_key(|p| p.at);
+        for points in history.factors.values_mut() {
+            points.sort_by_key(|p| p.at);
+        }
+        Ok(history)
+    }
+
+    async fn compute(&self) -> PostureScore {
+        let mut factors = Vec::new();
+
+        if let Some(models) = &self.models {
+            let created_at = models.active_model().await.map(|m| m.created_at);
+            factors.push(model_freshness(created_at, Utc::now(), &self.config));
+        }
+        if let Some(content) = &self.content {
+            let covered: Vec<ContentKind> = content
+                .active_manifests()
+                .iter()
+                .flat_map(|m| m.items.iter().map(|item| item.kind))
+                .collect();
+            factors.push(rule_coverage(&covered));
+        }
+        let attainment = pipeline_latency().cycle_attainment(Duration::from_millis(MAX_DETECTION_TIME_MS));
+        factors.extend(attainment.map(slo_attainment));
+        if let Some(incidents) = &self.incidents {
+            let open: Vec<ThreatLevel> = incidents.unresolved().iter().map(|i| i.severity()).collect();
+            factors.push(unresolved_incidents(&open));
+        }
+        factors.push(config_hygiene(&self.app_config));
+
+        combine(factors, Utc::now())
+    }
+}
+
+fn factor(name: &str, score: f64, detail: String) -> PostureFactor {
+    PostureFactor {
+        name: name.to_string(),
+        score: score.clamp(0.0, 100.0).round() as u32,
+        weight: 0.0,
+        detail,
+    }
+}
+
+/// Full marks up to the fresh age, falling linearly to zero at the stale age
+fn model_freshness(created_at: Option<DateTime<Utc>>, now: DateTime<Utc>, config: &PostureConfig) -> PostureFactor {
+    let Some(created_at) = created_at else {
+        return factor(MODEL_FRESHNESS, 0.0, "No active model".into());
+    };
+    let age = (now - created_at).to_std().unwrap_or_default();
+    let fresh = config.model_fresh_age.as_secs_f64();
+    let stale = config.model_stale_age.as_secs_f64().max(fresh + 1.0);
+    let score = 100.0 * (1.0 - (age.as_secs_f64() - fresh).max(0.0) / (stale - fresh));
+    factor(MODEL_FRESHNESS, score, format!("Active model is {} day(s) old", age.as_secs() / 86_400))
+}
+
+fn rule_coverage(covered: &[ContentKind]) -> PostureFactor {
+    let missing: Vec<String> = CONTENT_KINDS
+        .iter()
+        .filter(|kind| !covered.contains(kind))
+        .map(|kind| format!("{:?}", kind).to_lowercase())
+        .collect();
+    let score = 100.0 * (CONTENT_KINDS.len() - missing.len()) as f64 / CONTENT_KINDS.len() as f64;
+    let detail = if missing.is_empty() {
+        "Active content covers every kind".to_string()
+    } else {
+        format!("No active content for {}", missing.join(", "))
+    };
+    factor(RULE_COVERAGE, score, detail)
+}
+
+fn slo_attainment(attainment: f64) -> PostureFactor {
+    factor(
+        SLO_ATTAINMENT,
+        attainment * 100.0,
+        format!("{:.1}% of recent detection cycles within {}ms", attainment * 100.0, MAX_DETECTION_TIME_MS),
+    )
+}
+
+/// Deducts points per open incident, weighted by severity
+fn unresolved_incidents(open: &[ThreatLevel]) -> PostureFactor {
+    let penalty: f64 = open
+        .iter()
+        .map(|level| match level {
+            ThreatLevel::Critical => 40.0,
+            ThreatLevel::High => 20.0,
+            ThreatLevel::Medium => 10.0,
+            ThreatLevel::Low => 5.0,
+        })
+        .sum();
+    factor(UNRESOLVED_INCIDENTS, 100.0 - penalty, format!("{} unresolved incident(s)", open.len()))
+}
+
+fn config_hygiene(config: &AppConfig) -> PostureFactor {
+    let profile = active_profile();
+    let security = &config.security_settings;
+    let checks = [
+        ("configuration fails validation", config.validate().is_ok()),
+        ("secure boot disabled", security.enable_secure_boot && capabilities().is_enabled(Capability::SecureBoot)),
+        ("TPM not required", security.tpm_required),
+        ("encryption weaker than AES-256", security.encryption_level.starts_with("AES-256")),
+        ("auth timeout too long", security.auth_timeout_seconds <= MAX_AUTH_TIMEOUT_SECONDS),
+        ("too many auth retries", security.max_auth_retries <= MAX_AUTH_RETRIES),
+        ("tracing disabled", config.monitoring_config.enable_tracing),
+        ("log retention too short", config.monitoring_config.log_retention_days >= MIN_LOG_RETENTION_DAYS),
+        ("encryption not required", profile.require_encryption),
+        ("mTLS not required", profile.require_mtls),
+    ];
+    let failed: Vec<&str> = checks.iter().filter(|(_, ok)| !ok).map(|(issue, _)| *issue).collect();
+    let score = 100.0 * (checks.len() - failed.len()) as f64 / checks.len() as f64;
+    let detail = if failed.is_empty() {
+        "All configuration checks pass".to_string()
+    } else {
+        failed.join("; ")
+    };
+    factor(CONFIG_HYGIENE, score, detail)
+}
+
+/// Weighted mean of the factors that had data
+fn combine(mut factors: Vec<PostureFactor>, computed_at: DateTime<Utc>) -> PostureScore {
+    let weight_of = |name: &str| FACTOR_WEIGHTS.iter().find(|(n, _)| *n == name).map_or(0.0, |(_, w)| *w);
+    let total: f64 = factors.iter().map(|f| weight_of(&f.name)).sum();
+    let mut score = 0.0;
+    for f in factors.iter_mut() {
+        f.weight = if total > 0.0 { weight_of(&f.name) / total } else { 0.0 };
+        score += f.weight * f.score as f64;
+    }
+    PostureScore {
+        score: score.round() as u32,
+        computed_at,
+        factors,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_combine_renormalizes_missing_factors() {
+        let posture = combine(
+            vec![
+                unresolved_incidents(&[ThreatLevel::Critical, ThreatLevel::Low]),
+                rule_coverage(&[ContentKind::Rules, ContentKind::Intel]),
+            ],
+            Utc::now(),
+        );
+        assert_eq!(posture.factors[0].score, 55);
+        assert_eq!(posture.factors[1].score, 50);
+        assert!((posture.factors.iter().map(|f| f.weight).sum::<f64>() - 1.0).abs() < 1e-9);
+        // 0.25 and 0.20 weights renormalized to 5/9 and 4/9
+        assert_eq!(posture.score, 53);
+    }
+
+    #[test]
+    fn test_model_freshness_decays_after_fresh_age() {
+        let config = PostureConfig::default();
+        let now = Utc::now();
+        let days = |d: i64| Some(now - chrono::Duration::days(d));
+
+        assert_eq!(model_freshness(days(3), now, &config).score, 100);
+        assert_eq!(model_freshness(days(18), now, &config).score, 52);
+        assert_eq!(model_freshness(days(60), now, &config).score, 0);
+        assert_eq!(model_freshness(None, now, &config).score, 0);
+    }
+}
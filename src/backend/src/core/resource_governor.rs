@@ -0,0 +1,415 @@
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use metrics::{counter, gauge};
+use once_cell::sync::{Lazy, OnceCell};
+use parking_lot::RwLock;
+use serde::Serialize;
+use tokio::sync::{broadcast, Notify};
+use tracing::{debug, info, warn};
+
+use crate::config::app_config::ResourceGovernorConfig;
+
+// Constants for the resource governor
+const SUBSYSTEMS: usize = 3;
+const RECOVERY_RATIO: f64 = 0.8;
+const RECOVERY_STEP: f64 = 0.1;
+const THROTTLE_EVENT_BUFFER: usize = 64;
+
+static GOVERNOR: Lazy<Arc<ResourceGovernor>> =
+    Lazy::new(|| Arc::new(ResourceGovernor::new(ResourceGovernorConfig::default())));
+static SAMPLER: OnceCell<()> = OnceCell::new();
+
+/// Subsystem whose resource use counts against the overhead budget
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Subsystem {
+    Ml = 0,
+    Storage = 1,
+    Detection = 2,
+}
+
+impl Subsystem {
+    const ALL: [Subsystem; SUBSYSTEMS] = [Subsystem::Ml, Subsystem::Storage, Subsystem::Detection];
+
+    fn label(&self) -> &'static str {
+        match self {
+            Subsystem::Ml => "ml",
+            Subsystem::Storage => "storage",
+            Subsystem::Detection => "detection",
+        }
+    }
+}
+
+/// Resource use over the last sample and current scale of one subsystem
+#[derive(Debug, Clone, Serialize)]
+pub struct SubsystemUsage {
+    pub subsystem: Subsystem,
+    pub cpu_percent: f64,
+    pub memory_bytes: u64,
+    pub scale: f64,
+    pub in_flight: usize,
+}
+
+/// Emitted when a subsystem is scaled down to bring Guardian back within budget
+#[derive(Debug, Clone, Serialize)]
+pub struct ThrottleEvent {
+    pub subsystem: Subsystem,
+    pub scale: f64,
+    pub cpu_percent: f64,
+    pub total_cpu_percent: f64,
+    pub memory_percent: f64,
+    pub cpu_budget_percent: f64,
+    pub memory_budget_percent: f64,
+}
+
+type ScaleListener = Box<dyn Fn(f64) + Send + Sync>;
+
+struct SubsystemState {
+    busy_nanos: AtomicU64,
+    memory_bytes: AtomicU64,
+    cpu_percent: AtomicU64,
+    scale: AtomicU64,
+    in_flight: AtomicUsize,
+    released: Notify,
+    listeners: RwLock<Vec<ScaleListener>>,
+}
+
+impl SubsystemState {
+    fn new() -> Self {
+        Self {
+            busy_nanos: AtomicU64::new(0),
+            memory_bytes: AtomicU64::new(0),
+            cpu_percent: AtomicU64::new(0f64.to_bits()),
+            scale: AtomicU64::new(1.0f64.to_bits()),
+            in_flight: AtomicUsize::new(0),
+            released: Notify::new(),
+            listeners: RwLock::new(Vec::new()),
+        }
+    }
+
+    fn scale(&self) -> f64 {
+        f64::from_bits(self.scale.load(Ordering::Relaxed))
+    }
+}
+
+impl fmt::Debug for SubsystemState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SubsystemState")
+            .field("scale", &self.scale())
+            .field("in_flight", &self.in_flight.load(Ordering::Relaxed))
+            .field("listeners", &self.listeners.read().len())
+            .finish()
+    }
+}
+
+/// Holds ML, storage and detection to a shared CPU and memory overhead budget
+///
+/// Subsystems report busy time through work permits and memory through
+/// `report_memory`. Every sample, subsystems contributing to an exceeded budget
+/// are scaled down, which shrinks their batch sizes, permits and caches, and
+/// scaled back up once usage falls well below the budget.
+#[derive(Debug)]
+pub struct ResourceGovernor {
+    config: RwLock<ResourceGovernorConfig>,
+    subsystems: [SubsystemState; SUBSYSTEMS],
+    events: broadcast::Sender<ThrottleEvent>,
+}
+
+/// Returns the process-wide resource governor
+pub fn governor() -> Arc<ResourceGovernor> {
+    Arc::clone(&GOVERNOR)
+}
+
+/// Applies the governor configuration and starts sampling resource use
+pub fn init_governor(config: &ResourceGovernorConfig) {
+    let governor = governor();
+    *governor.config.write() = config.clone();
+    info!(
+        enabled = config.enabled,
+        cpu_budget_percent = config.cpu_budget_percent,
+        memory_budget_percent = config.memory_budget_percent,
+        "Resource governor configured"
+    );
+
+    if !config.enabled || SAMPLER.set(()).is_err() {
+        return;
+    }
+    let sample_interval = config.sample_interval;
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(sample_interval);
+        let mut last_sample = Instant::now();
+        loop {
+            interval.tick().await;
+            let cpus = sys_info::cpu_num().unwrap_or(1) as usize;
+            governor.adjust(last_sample.elapsed(), cpus, process_memory_percent());
+            last_sample = Instant::now();
+        }
+    });
+}
+
+impl ResourceGovernor {
+    /// Creates a governor enforcing the given budget
+    pub fn new(config: ResourceGovernorConfig) -> Self {
+        Self {
+            config: RwLock::new(config),
+            subsystems: [SubsystemState::new(), SubsystemState::new(), SubsystemState::new()],
+            events: broadcast::channel(THROTTLE_EVENT_BUFFER).0,
+        }
+    }
+
+    /// Returns the current scale of a subsystem, between the configured minimum and 1.0
+    pub fn scale(&self, subsystem: Subsystem) -> f64 {
+        self.state(subsystem).scale()
+    }
+
+    /// Scales a subsystem's preferred batch size, never below one
+    pub fn batch_size(&self, subsystem: Subsystem, max: usize) -> usize {
+        scaled(max, self.scale(subsystem))
+    }
+
+    /// Scales a subsystem's preferred cache capacity, never below one entry
+    pub fn cache_capacity(&self, subsystem: Subsystem, max: usize) -> usize {
+        scaled(max, self.scale(subsystem))
+    }
+
+    /// Returns how many units of work a subsystem may run concurrently
+    pub fn permits(&self, subsystem: Subsystem) -> usize {
+        scaled(self.config.read().max_permits, self.scale(subsystem))
+    }
+
+    /// Waits for a work permit, whose lifetime is counted as the subsystem's busy time
+    pub async fn acquire(self: &Arc<Self>, subsystem: Subsystem) -> WorkPermit {
+        let state = self.state(subsystem);
+        loop {
+            let released = state.released.notified();
+            if self.try_enter(subsystem) {
+                return WorkPermit {
+                    governor: Arc::clone(self),
+                    subsystem,
+                    started: Instant::now(),
+                };
+            }
+            counter!("guardian.governor.permit_waits", 1, "subsystem" => subsystem.label());
+            released.await;
+        }
+    }
+
+    /// Records the memory a subsystem currently holds in caches and buffers
+    pub fn report_memory(&self, subsystem: Subsystem, bytes: u64) {
+        self.state(subsystem).memory_bytes.store(bytes, Ordering::Relaxed);
+        gauge!("guardian.governor.memory_bytes", bytes as f64, "subsystem" => subsystem.label());
+    }
+
+    /// Calls `listener` with the current scale and on every change, for knobs owned elsewhere
+    pub fn on_scale_change(&self, subsystem: Subsystem, listener: impl Fn(f64) + Send + Sync + 'static) {
+        let state = self.state(subsystem);
+        listener(state.scale());
+        state.listeners.write().push(Box::new(listener));
+    }
+
+    /// Subscribes to throttle events
+    pub fn subscribe(&self) -> broadcast::Receiver<ThrottleEvent> {
+        self.events.subscribe()
+    }
+
+    /// Returns the usage and scale of every subsystem
+    pub fn snapshot(&self) -> Vec<SubsystemUsage> {
+        Subsystem::ALL
+            .iter()
+            .map(|subsystem| {
+                let state = self.state(*subsystem);
+                SubsystemUsage {
+                    subsystem: *subsystem,
+                    cpu_percent: f64::from_bits(state.cpu_percent.load(Ordering::Relaxed)),
+                    memory_bytes: state.memory_bytes.load(Ordering::Relaxed),
+                    scale: state.scale(),
+                    in_flight: state.in_flight.load(Ordering::Relaxed),
+                }
+            })
+            .collect()
+    }
+
+    fn state(&self, subsystem: Subsystem) -> &SubsystemState {
+        &self.subsystems[subsystem as usize]
+    }
+
+    fn try_enter(&self, subsystem: Subsystem) -> bool {
+        let state = self.state(subsystem);
+        let limit = if self.config.read().enabled { self.permits(subsystem) } else { usize::MAX };
+        state
+            .in_flight
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| (n < limit).then_some(n + 1))
+            .is_ok()
+    }
+
+    fn release(&self, subsystem: Subsystem, busy: Duration) {
+        let state = self.state(subsystem);
+        state.busy_nanos.fetch_add(busy.as_nanos() as u64, Ordering::Relaxed);
+        state.in_flight.fetch_sub(1, Ordering::AcqRel);
+        state.released.notify_one();
+    }
+
+    fn set_scale(&self, subsystem: Subsystem, scale: f64) {
+        let state = self.state(subsystem);
+        state.scale.store(scale.to_bits(), Ordering::Relaxed);
+        gauge!("guardian.governor.scale", scale, "subsystem" => subsystem.label());
+        for listener in state.listeners.read().iter() {
+            listener(scale);
+        }
+        // Waiters re-check against the new permit count
+        state.released.notify_waiters();
+    }
+
+    /// Converts busy time since the last sample into CPU shares and rescales subsystems
+    fn adjust(&self, elapsed: Duration, cpus: usize, memory_percent: f64) -> Vec<ThrottleEvent> {
+        let config = self.config.read().clone();
+        let capacity = elapsed.as_nanos() as f64 * cpus.max(1) as f64;
+
+        let cpu: Vec<f64> = Subsystem::ALL
+            .iter()
+            .map(|subsystem| {
+                let state = self.state(*subsystem);
+                let busy = state.busy_nanos.swap(0, Ordering::Relaxed) as f64;
+                let percent = if capacity > 0.0 { busy / capacity * 100.0 } else { 0.0 };
+                state.cpu_percent.store(percent.to_bits(), Ordering::Relaxed);
+                gauge!("guardian.governor.cpu_percent", percent, "subsystem" => subsystem.label());
+                percent
+            })
+            .collect();
+        let total_cpu: f64 = cpu.iter().sum();
+        gauge!("guardian.governor.total_cpu_percent", total_cpu);
+        gauge!("guardian.governor.memory_percent", memory_percent);
+
+        if !config.enabled {
+            return Vec::new();
+        }
+
+        let ratio = (total_cpu / config.cpu_budget_percent).max(memory_percent / config.memory_budget_percent);
+        let memory_over = memory_percent > config.memory_budget_percent;
+
+        let mut events = Vec::new();
+        for subsystem in Subsystem::ALL {
+            let state = self.state(subsystem);
+            let scale = state.scale();
+            let contributing = cpu[subsystem as usize] > 0.0
+                || (memory_over && state.memory_bytes.load(Ordering::Relaxed) > 0);
+            let next = next_scale(scale, ratio, contributing, config.min_scale);
+            if next == scale {
+                continue;
+            }
+            self.set_scale(subsystem, next);
+
+            if next > scale {
+                debug!(subsystem = subsystem.label(), scale = next, "Subsystem scaled back up");
+                continue;
+            }
+            let event = ThrottleEvent {
+                subsystem,
+                scale: next,
+                cpu_percent: cpu[subsystem as usize],
+                total_cpu_percent: total_cpu,
+                memory_percent,
+                cpu_budget_percent: config.cpu_budget_percent,
+                memory_budget_percent: config.memory_budget_percent,
+            };
+            counter!("guardian.governor.throttled", 1, "subsystem" => subsystem.label());
+            warn!(?event, "Overhead budget exceeded, throttling subsystem");
+            let _ = self.events.send(event.clone());
+            events.push(event);
+        }
+        events
+    }
+}
+
+/// Work counted against a subsystem's permits and busy time until dropped
+#[derive(Debug)]
+pub struct WorkPermit {
+    governor: Arc<ResourceGovernor>,
+    subsystem: Subsystem,
+    started: Instant,
+}
+
+impl Drop for WorkPermit {
+    fn drop(&mut self) {
+        self.governor.release(self.subsystem, self.started.elapsed());
+    }
+}
+
+/// Scales down contributors while over budget, recovering stepwise once well below it
+fn next_scale(scale: f64, ratio: f64, contributing: bool, min_scale: f64) -> f64 {
+    if ratio > 1.0 && contributing {
+        (scale / ratio).max(min_scale)
+    } else if ratio < RECOVERY_RATIO {
+        (scale + RECOVERY_STEP).min(1.0)
+    } else {
+        scale
+    }
+}
+
+fn scaled(max: usize, scale: f64) -> usize {
+    ((max as f64 * scale).round() as usize).clamp(1, max.max(1))
+}
+
+/// Resident memory of this process as a percentage of system memory
+fn process_memory_percent() -> f64 {
+    let rss_kb = std::fs::read_to_string("/proc/self/status").ok().and_then(|status| {
+        status
+            .lines()
+            .find_map(|line| line.strip_prefix("VmRSS:"))
+            .and_then(|value| value.trim().trim_end_matches("kB").trim().parse::<f64>().ok())
+    });
+    match (rss_kb, sys_info::mem_info()) {
+        (Some(rss_kb), Ok(memory)) if memory.total > 0 => rss_kb / memory.total as f64 * 100.0,
+        _ => 0.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_throttles_contributors_and_recovers() {
+        let governor = ResourceGovernor::new(ResourceGovernorConfig::default());
+        let mut events = governor.subscribe();
+
+        // ML was busy for 20% of one core over the sample, four times the 5% budget
+        governor.state(Subsystem::Ml).busy_nanos.store(200_000_000, Ordering::Relaxed);
+        let throttled = governor.adjust(Duration::from_secs(1), 1, 1.0);
+        assert_eq!(throttled.len(), 1);
+        assert_eq!(throttled[0].subsystem, Subsystem::Ml);
+        assert!((governor.scale(Subsystem::Ml) - 0.25).abs() < 1e-9);
+        assert_eq!(governor.scale(Subsystem::Detection), 1.0);
+        assert_eq!(governor.batch_size(Subsystem::Ml, 128), 32);
+        assert_eq!(events.try_recv().unwrap().subsystem, Subsystem::Ml);
+
+        // Idle samples scale back up step by step
+        assert!(governor.adjust(Duration::from_secs(1), 1, 1.0).is_empty());
+        assert!((governor.scale(Subsystem::Ml) - 0.35).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_permits_follow_scale() {
+        let governor = Arc::new(ResourceGovernor::new(ResourceGovernorConfig {
+            max_permits: 2,
+            ..Default::default()
+        }));
+        governor.set_scale(Subsystem::Storage, 0.5);
+        assert_eq!(governor.permits(Subsystem::Storage), 1);
+
+        let first = governor.acquire(Subsystem::Storage).await;
+        let second = tokio::time::timeout(Duration::from_millis(50), governor.acquire(Subsystem::Storage)).await;
+        assert!(second.is_err());
+
+        drop(first);
+        let second = tokio::time::timeout(Duration::from_millis(50), governor.acquire(Subsystem::Storage)).await;
+        assert!(second.is_ok());
+    }
+}
@@ -0,0 +1,206 @@
+use std::{
+    collections::{BTreeMap, VecDeque},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use metrics::counter;
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+use uuid::Uuid;
+
+use crate::core::system_state::SystemHealth;
+
+// Constants for the state transition journal
+const JOURNAL_CAPACITY: usize = 10_000;
+/// Transitions shown on either side of a point in time when no window is given
+pub const DEFAULT_TRANSITION_WINDOW: Duration = Duration::from_secs(15 * 60);
+
+static STATE_JOURNAL: Lazy<Arc<StateJournal>> = Lazy::new(|| Arc::new(StateJournal::new(JOURNAL_CAPACITY)));
+
+/// What drove a system state transition
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TransitionCause {
+    /// An event applied to the state
+    Event { event_type: String, correlation_id: Uuid },
+    /// A periodic health evaluation
+    HealthCheck { source: String },
+    /// A configuration change; recorded even when health is unchanged
+    ConfigChange { description: String },
+    /// A direct state update
+    Update,
+}
+
+impl TransitionCause {
+    /// Short label of the cause kind
+    pub fn label(&self) -> &'static str {
+        match self {
+            TransitionCause::Event { .. } => "event",
+            TransitionCause::HealthCheck { .. } => "health_check",
+            TransitionCause::ConfigChange { .. } => "config_change",
+            TransitionCause::Update => "update",
+        }
+    }
+
+    /// Human readable detail of the cause
+    pub fn detail(&self) -> String {
+        match self {
+            TransitionCause::Event { event_type, correlation_id } => format!("{} ({})", event_type, correlation_id),
+            TransitionCause::HealthCheck { source } => source.clone(),
+            TransitionCause::ConfigChange { description } => description.clone(),
+            TransitionCause::Update => String::new(),
+        }
+    }
+
+    /// Events and config changes are inputs worth keeping even when health does not move
+    fn always_recorded(&self) -> bool {
+        matches!(self, TransitionCause::Event { .. } | TransitionCause::ConfigChange { .. })
+    }
+}
+
+/// One recorded change of system health and the inputs behind it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateTransition {
+    pub sequence: u64,
+    pub at: DateTime<Utc>,
+    pub from: SystemHealth,
+    pub to: SystemHealth,
+    pub cause: TransitionCause,
+    /// Values the health was derived from, such as `cpu_usage` or `queue.<name>.saturation`
+    pub inputs: BTreeMap<String, f64>,
+}
+
+/// Bounded, in-order journal of system state transitions for stepping through after an incident
+#[derive(Debug)]
+pub struct StateJournal {
+    capacity: usize,
+    entries: RwLock<VecDeque<StateTransition>>,
+    next_sequence: AtomicU64,
+    current: RwLock<SystemHealth>,
+}
+
+/// Returns the process-wide state journal
+pub fn state_journal() -> Arc<StateJournal> {
+    Arc::clone(&STATE_JOURNAL)
+}
+
+impl StateJournal {
+    /// Creates a journal keeping at most `capacity` transitions
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            entries: RwLock::new(VecDeque::with_capacity(capacity.min(JOURNAL_CAPACITY))),
+            next_sequence: AtomicU64::new(1),
+            current: RwLock::new(SystemHealth::Healthy),
+        }
+    }
+
+    /// Records a transition when health changed or the cause is always worth keeping
+    pub fn record(
+        &self,
+        from: SystemHealth,
+        to: SystemHealth,
+        cause: TransitionCause,
+        inputs: BTreeMap<String, f64>,
+    ) -> Option<u64> {
+        if from == to && !cause.always_recorded() {
+            return None;
+        }
+
+        let sequence = self.next_sequence.fetch_add(1, Ordering::Relaxed);
+        let transition = StateTransition {
+            sequence,
+            at: Utc::now(),
+            from,
+            to: to.clone(),
+            cause,
+            inputs,
+        };
+        debug!(sequence, cause = transition.cause.label(), from = ?transition.from, to = ?transition.to, "State transition journaled");
+        counter!("guardian.state.transitions", 1, "cause" => transition.cause.label());
+
+        *self.current.write() = to;
+        let mut entries = self.entries.write();
+        if entries.len() >= self.capacity {
+            entries.pop_front();
+        }
+        entries.push_back(transition);
+        Some(sequence)
+    }
+
+    /// Records a configuration change as a marker at the current health
+    pub fn record_config_change(&self, description: impl Into<String>) -> Option<u64> {
+        let current = self.current.read().clone();
+        self.record(
+            current.clone(),
+            current,
+            TransitionCause::ConfigChange { description: description.into() },
+            BTreeMap::new(),
+        )
+    }
+
+    /// Returns the transition with the given sequence number, if still retained
+    pub fn get(&self, sequence: u64) -> Option<StateTransition> {
+        let entries = self.entries.read();
+        let index = entries.binary_search_by_key(&sequence, |t| t.sequence).ok()?;
+        entries.get(index).cloned()
+    }
+
+    /// Returns the transition `steps` entries after (or before, when negative) the given one
+    pub fn step(&self, sequence: u64, steps: i64) -> Option<StateTransition> {
+        let entries = self.entries.read();
+        let index = entries.binary_search_by_key(&sequence, |t| t.sequence).ok()?;
+        let target = i64::try_from(index).ok()?.checked_add(steps)?;
+        entries.get(usize::try_from(target).ok()?).cloned()
+    }
+
+    /// Returns transitions within `window` on either side of `around`, oldest first
+    pub fn around(&self, around: DateTime<Utc>, window: Duration) -> Vec<StateTransition> {
+        let window = chrono::Duration::from_std(window).unwrap_or_else(|_| chrono::Duration::max_value());
+        let from = around.checked_sub_signed(window).unwrap_or(DateTime::<Utc>::MIN_UTC);
+        let to = around.checked_add_signed(window).unwrap_or(DateTime::<Utc>::MAX_UTC);
+        self.entries
+            .read()
+            .iter()
+            .filter(|t| t.at >= from && t.at <= to)
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_journal_records_changes_and_steps() {
+        let journal = StateJournal::new(3);
+        let inputs = BTreeMap::from([("cpu_usage".to_string(), 91.0)]);
+
+        // Unchanged health from a health check is not a transition
+        let check = || TransitionCause::HealthCheck { source: "system_state".into() };
+        assert!(journal.record(SystemHealth::Healthy, SystemHealth::Healthy, check(), BTreeMap::new()).is_none());
+
+        let first = journal.record(SystemHealth::Healthy, SystemHealth::Critical, check(), inputs).unwrap();
+        let config = journal.record_config_change("thresholds reloaded").unwrap();
+        assert_eq!(journal.get(config).unwrap().to, SystemHealth::Critical);
+        let last = journal.record(SystemHealth::Critical, SystemHealth::Healthy, TransitionCause::Update, BTreeMap::new()).unwrap();
+
+        assert_eq!(journal.step(first, 2).unwrap().sequence, last);
+        assert_eq!(journal.step(last, -1).unwrap().sequence, config);
+        assert!(journal.step(first, -1).is_none());
+        assert_eq!(journal.get(first).unwrap().inputs["cpu_usage"], 91.0);
+
+        // The oldest transition is dropped at capacity
+        journal.record(SystemHealth::Healthy, SystemHealth::Degraded, TransitionCause::Update, BTreeMap::new());
+        assert!(journal.get(first).is_none());
+        assert_eq!(journal.around(Utc::now(), DEFAULT_TRANSITION_WINDOW).len(), 3);
+    }
+}
@@ -0,0 +1,323 @@
+//! Versioned, machine-readable status of the whole Guardian instance
+//!
+//! Every subsystem registers a `StatusSource`; a report polls them concurrently, each bounded
+//! by a timeout, and lists the components in a fixed order so fleet tooling can parse the
+//! report without knowing which subsystems run on a host. Components that never registered,
+//! or did not answer in time, are reported as `unknown` rather than omitted.
+
+use std::{collections::BTreeMap, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use metrics::{counter, histogram};
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tokio::task::JoinSet;
+use tracing::warn;
+
+use crate::core::system_state::{SystemHealth, SystemState};
+use crate::storage::{pool_reports, VdevState};
+use crate::utils::error::GuardianError;
+
+/// Bumped whenever a field is removed or changes meaning; additions keep the version
+pub const STATUS_SCHEMA_VERSION: u32 = 1;
+/// Components in report order
+pub const SYSTEM: &str = "system";
+pub const SECURITY: &str = "security";
+pub const ML: &str = "ml";
+pub const STORAGE: &str = "storage";
+pub const TEMPORAL: &str = "temporal";
+pub const COMPONENTS: &[&str] = &[SYSTEM, SECURITY, ML, STORAGE, TEMPORAL];
+/// Longest a source may take before it is reported as unknown
+const SOURCE_TIMEOUT: Duration = Duration::from_secs(2);
+
+static STATUS: Lazy<Arc<StatusService>> = Lazy::new(|| Arc::new(StatusService::with_defaults()));
+
+/// Returns the process-wide status service
+pub fn status_service() -> Arc<StatusService> {
+    Arc::clone(&STATUS)
+}
+
+/// Health of one component, ordered from best to worst
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ComponentState {
+    Healthy,
+    Degraded,
+    Unhealthy,
+    /// Not running on this host or did not answer
+    Unknown,
+}
+
+impl From<&SystemHealth> for ComponentState {
+    fn from(health: &SystemHealth) -> Self {
+        match health {
+            SystemHealth::Healthy => Self::Healthy,
+            SystemHealth::Degraded => Self::Degraded,
+            SystemHealth::Critical => Self::Unhealthy,
+        }
+    }
+}
+
+/// Status of one component with the figures behind it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ComponentStatus {
+    pub name: String,
+    pub state: ComponentState,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    #[serde(default)]
+    pub metrics: BTreeMap<String, f64>,
+}
+
+impl ComponentStatus {
+    pub fn new(name: &str, state: ComponentState) -> Self {
+        Self {
+            name: name.to_string(),
+            state,
+            detail: None,
+            metrics: BTreeMap::new(),
+        }
+    }
+
+    pub fn with_detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+
+    pub fn with_metric(mut self, name: &str, value: f64) -> Self {
+        self.metrics.insert(name.to_string(), value);
+        self
+    }
+}
+
+/// Status of the whole instance
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct StatusReport {
+    pub schema_version: u32,
+    /// Guardian build that produced the report
+    pub version: String,
+    pub generated_at: DateTime<Utc>,
+    /// Worst state of the components that reported; unknown ones count as degraded
+    pub overall: ComponentState,
+    /// One entry per name in `COMPONENTS`, in that order
+    pub components: Vec<ComponentStatus>,
+}
+
+impl StatusReport {
+    pub fn component(&self, name: &str) -> Option<&ComponentStatus> {
+        self.components.iter().find(|c| c.name == name)
+    }
+}
+
+/// A subsystem able to report its own status
+#[async_trait]
+pub trait StatusSource: Send + Sync {
+    /// One of `COMPONENTS`
+    fn component(&self) -> &'static str;
+
+    async fn status(&self) -> Result<ComponentStatus, GuardianError>;
+}
+
+/// Aggregates the registered status sources into reports
+pub struct StatusService {
+    sources: RwLock<Vec<Arc<dyn StatusSource>>>,
+    timeout: Duration,
+}
+
+impl std::fmt::Debug for StatusService {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let names: Vec<&str> = self.sources.read().iter().map(|s| s.component()).collect();
+        f.debug_struct("StatusService").field("sources", &names).finish()
+    }
+}
+
+impl StatusService {
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            sources: RwLock::new(Vec::new()),
+            timeout,
+        }
+    }
+
+    /// Creates a service with the sources that need no wiring, currently storage pool health
+    pub fn with_defaults() -> Self {
+        let service = Self::new(SOURCE_TIMEOUT);
+        service.register(Arc::new(PoolHealthStatus));
+        service
+    }
+
+    /// Adds a source, replacing one reporting the same component
+    pub fn register(&self, source: Arc<dyn StatusSource>) {
+        let mut sources = self.sources.write();
+        sources.retain(|s| s.component() != source.component());
+        sources.push(source);
+    }
+
+    /// Polls every source and assembles the report
+    pub async fn report(&self) -> StatusReport {
+        let start = std::time::Instant::now();
+        let sources: Vec<Arc<dyn StatusSource>> = self.sources.read().clone();
+
+        let mut polls = JoinSet::new();
+        for source in sources {
+            let timeout = self.timeout;
+            polls.spawn(async move {
+                let name = source.component();
+                match tokio::time::timeout(timeout, source.status()).await {
+                    Ok(Ok(status)) => status,
+                    Ok(Err(e)) => {
+                        warn!(component = name, error = %e, "Status source failed");
+                        ComponentStatus::new(name, ComponentState::Unhealthy).with_detail(e.to_string())
+                    }
+                    Err(_) => {
+                        counter!("guardian.status.source_timeouts", 1, "component" => name);
+                        ComponentStatus::new(name, ComponentState::Unknown).with_detail("status check timed out")
+                    }
+                }
+            });
+        }
+
+        let mut reported = BTreeMap::new();
+        while let Some(joined) = polls.join_next().await {
+            if let Ok(status) = joined {
+                reported.insert(status.name.clone(), status);
+            }
+        }
+        histogram!("guardian.status.report_duration", start.elapsed().as_secs_f64());
+        assemble(reported)
+    }
+}
+
+/// Orders the reported components and derives the overall state
+fn assemble(mut reported: BTreeMap<String, ComponentStatus>) -> StatusReport {
+    let components: Vec<ComponentStatus> = COMPONENTS
+        .iter()
+        .map(|name| {
+            reported.remove(*name).unwrap_or_else(|| {
+                ComponentStatus::new(name, ComponentState::Unknown).with_detail("not running on this host")
+            })
+        })
+        .collect();
+    let overall = components
+        .iter()
+        .map(|c| match c.state {
+            ComponentState::Unknown => ComponentState::Degraded,
+            state => state,
+        })
+        .max()
+        .unwrap_or(ComponentState::Unknown);
+
+    StatusReport {
+        schema_version: STATUS_SCHEMA_VERSION,
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        generated_at: Utc::now(),
+        overall,
+        components,
+    }
+}
+
+/// System health and resource usage as tracked by `SystemState`
+#[derive(Debug)]
+pub struct SystemStateStatus {
+    state: Arc<RwLock<SystemState>>,
+}
+
+impl SystemStateStatus {
+    pub fn new(state: Arc<RwLock<SystemState>>) -> Self {
+        Self { state }
+    }
+}
+
+#[async_trait]
+impl StatusSource for SystemStateStatus {
+    fn component(&self) -> &'static str {
+        SYSTEM
+    }
+
+    async fn status(&self) -> Result<ComponentStatus, GuardianError> {
+        let state = self.state.read().get_current_state()?;
+        let mut status = ComponentStatus::new(SYSTEM, state.health().into());
+        status.metrics = state.inputs();
+        status.metrics.insert("last_update".into(), state.last_update().timestamp() as f64);
+        Ok(status)
+    }
+}
+
+/// ZFS pool health from the latest storage maintenance pass
+#[derive(Debug)]
+struct PoolHealthStatus;
+
+#[async_trait]
+impl StatusSource for PoolHealthStatus {
+    fn component(&self) -> &'static str {
+        STORAGE
+    }
+
+    async fn status(&self) -> Result<ComponentStatus, GuardianError> {
+        let pools = pool_reports();
+        if pools.is_empty() {
+            return Ok(ComponentStatus::new(STORAGE, ComponentState::Unknown).with_detail("no pool checked yet"));
+        }
+
+        let mut status = ComponentStatus::new(STORAGE, ComponentState::Healthy);
+        let mut problems = Vec::new();
+        for pool in &pools {
+            let state = match pool.state {
+                VdevState::Online if pool.checksum_errors() == 0 => ComponentState::Healthy,
+                VdevState::Online | VdevState::Degraded => ComponentState::Degraded,
+                _ => ComponentState::Unhealthy,
+            };
+            if state != ComponentState::Healthy {
+                problems.push(format!("{} {:?} with {} checksum errors", pool.pool, pool.state, pool.checksum_errors()));
+            }
+            status.state = status.state.max(state);
+            status.metrics.insert(format!("{}.checksum_errors", pool.pool), pool.checksum_errors() as f64);
+            status.metrics.insert(format!("{}.impaired_vdevs", pool.pool), pool.impaired_vdevs().count() as f64);
+        }
+        if !problems.is_empty() {
+            status.detail = Some(problems.join("; "));
+        }
+        Ok(status)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Fixed(&'static str, ComponentState);
+
+    #[async_trait]
+    impl StatusSource for Fixed {
+        fn component(&self) -> &'static str {
+            self.0
+        }
+
+        async fn status(&self) -> Result<ComponentStatus, GuardianError> {
+            Ok(ComponentStatus::new(self.0, self.1).with_metric("checks", 1.0))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_report_orders_components_and_derives_overall() {
+        let service = StatusService::new(Duration::from_secs(1));
+        service.register(Arc::new(Fixed(TEMPORAL, ComponentState::Healthy)));
+        service.register(Arc::new(Fixed(SECURITY, ComponentState::Unhealthy)));
+        service.register(Arc::new(Fixed(SECURITY, ComponentState::Healthy)));
+
+        let report = service.report().await;
+        assert_eq!(report.schema_version, STATUS_SCHEMA_VERSION);
+        let names: Vec<&str> = report.components.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, COMPONENTS);
+        assert_eq!(report.component(SECURITY).unwrap().state, ComponentState::Healthy);
+        assert_eq!(report.component(ML).unwrap().state, ComponentState::Unknown);
+        // Unregistered components hold the overall state at degraded
+        assert_eq!(report.overall, ComponentState::Degraded);
+
+        let parsed: StatusReport = serde_json::from_str(&serde_json::to_string(&report).unwrap()).unwrap();
+        assert_eq!(parsed, report);
+    }
+}
@@ -0,0 +1,487 @@
+use std::{
+    io::{Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+};
+
+use flate2::{write::GzEncoder, Compression};
+use metrics::{counter, histogram};
+use ring::{aead, agreement, hkdf, rand::{SecureRandom, SystemRandom}};
+use serde::Serialize;
+use serde_json::{json, Value};
+use time::OffsetDateTime;
+use tracing::{info, instrument, warn};
+
+use crate::utils::affinity::thread_placements;
+use crate::utils::error::{error_aggregates, ErrorCategory, ErrorSeverity, GuardianError};
+use crate::utils::logging::flight_recorder;
+
+// Constants for support bundle generation
+pub const BUNDLE_MAGIC: &[u8; 4] = b"GSB1";
+const BUNDLE_HKDF_INFO: &[u8] = b"guardian-support-bundle-v1";
+const DEFAULT_LOG_DIR: &str = "logs";
+const DEFAULT_MAX_LOG_BYTES: u64 = 8 * 1024 * 1024;
+const DEFAULT_MAX_LOG_FILES: usize = 5;
+const REDACTED: &str = "[REDACTED]";
+const SECRET_KEY_MARKERS: &[&str] = &[
+    "password", "passwd", "secret", "token", "api_key", "apikey", "private_key",
+    "credential", "encryption_key", "key_material", "authorization", "cookie",
+];
+
+/// Sources a support bundle is collected from
+#[derive(Debug, Clone)]
+pub struct SupportBundleConfig {
+    pub log_dir: PathBuf,
+    /// Config files to include; a directory contributes its `*.toml` files
+    pub config_paths: Vec<PathBuf>,
+    /// Bytes taken from the end of each log file
+    pub max_log_bytes: u64,
+    /// Most recently modified log files to include
+    pub max_log_files: usize,
+}
+
+impl Default for SupportBundleConfig {
+    fn default() -> Self {
+        Self {
+            log_dir: PathBuf::from(DEFAULT_LOG_DIR),
+            config_paths: vec![PathBuf::from(crate::config::DEFAULT_CONFIG_PATH)],
+            max_log_bytes: DEFAULT_MAX_LOG_BYTES,
+            max_log_files: DEFAULT_MAX_LOG_FILES,
+        }
+    }
+}
+
+/// Version and capability information of the running build
+#[derive(Debug, Clone, Serialize)]
+pub struct VersionInfo {
+    pub version: &'static str,
+    pub os: &'static str,
+    pub arch: &'static str,
+    pub cpus: usize,
+    pub features: Vec<&'static str>,
+}
+
+impl VersionInfo {
+    /// Describes the current binary and host
+    pub fn current() -> Self {
+        let features = [
+            ("security", cfg!(feature = "security")),
+            ("ml", cfg!(feature = "ml")),
+            ("temporal", cfg!(feature = "temporal")),
+            ("monitoring", cfg!(feature = "monitoring")),
+        ]
+        .into_iter()
+        .filter_map(|(name, enabled)| enabled.then_some(name))
+        .collect();
+
+        Self {
+            version: env!("CARGO_PKG_VERSION"),
+            os: std::env::consts::OS,
+            arch: std::env::consts::ARCH,
+            cpus: std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1),
+            features,
+        }
+    }
+}
+
+/// Diagnostic files collected for a vendor support case, redacted before they are added
+#[derive(Debug, Default)]
+pub struct SupportBundle {
+    entries: Vec<(String, Vec<u8>)>,
+}
+
+impl SupportBundle {
+    /// Collects logs, config, health, version info, error aggregates and the flight recorder
+    #[instrument(skip(config, health))]
+    pub fn collect(config: &SupportBundleConfig, health: Option<Value>) -> Result<Self, GuardianError> {
+        let start = std::time::Instant::now();
+        let mut bundle = Self::default();
+
+        bundle.add_json("manifest.json", &json!({
+            "created_at": OffsetDateTime::now_utc(),
+            "correlation_id": crate::utils::correlation::current_or_new(),
+            "version": VersionInfo::current(),
+            "thread_placement": thread_placements(),
+        }))?;
+        bundle.add_json("health.json", &health.unwrap_or(Value::Null))?;
+        bundle.add_json("errors.json", &error_aggregates())?;
+
+        let mut records = serde_json::to_value(flight_recorder().snapshot()).map_err(serialize_error)?;
+        strip_secrets(&mut records);
+        if let Value::Array(records) = &mut records {
+            for record in records.iter_mut() {
+                if let Some(Value::String(message)) = record.get_mut("message") {
+                    *message = redact_line(message);
+                }
+            }
+        }
+        bundle.add_json("flight_recorder.json", &records)?;
+
+        for path in config.config_paths.iter().flat_map(|path| config_files(path)) {
+            match std::fs::read_to_string(&path) {
+                Ok(raw) => bundle.add_config(&path, &raw)?,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => warn!(path = %path.display(), error = %e, "Skipping unreadable config file"),
+            }
+        }
+
+        for path in recent_log_files(&config.log_dir, config.max_log_files) {
+            match read_tail(&path, config.max_log_bytes) {
+                Ok(raw) => {
+                    let redacted: String = raw.lines().map(|l| redact_line(l) + "\n").collect();
+                    let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("guardian.log");
+                    bundle.entries.push((format!("logs/{}", name), redacted.into_bytes()));
+                }
+                Err(e) => warn!(path = %path.display(), error = %e, "Skipping unreadable log file"),
+            }
+        }
+
+        histogram!("guardian.support_bundle.collect_duration", start.elapsed().as_secs_f64());
+        info!(entries = bundle.entries.len(), "Support bundle collected");
+        Ok(bundle)
+    }
+
+    /// Returns the archive paths of the collected files
+    pub fn entry_names(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(|(name, _)| name.as_str())
+    }
+
+    fn add_json<T: Serialize>(&mut self, name: &str, value: &T) -> Result<(), GuardianError> {
+        let data = serde_json::to_vec_pretty(value).map_err(serialize_error)?;
+        self.entries.push((name.to_string(), data));
+        Ok(())
+    }
+
+    fn add_config(&mut self, path: &Path, raw: &str) -> Result<(), GuardianError> {
+        let name = format!("config/{}", path.file_name().and_then(|n| n.to_str()).unwrap_or("config"));
+        match serde_json::from_str::<Value>(raw) {
+            Ok(mut value) => {
+                strip_secrets(&mut value);
+                self.add_json(&name, &value)
+            }
+            // Non-JSON config is redacted line by line
+            Err(_) => {
+                let redacted: String = raw.lines().map(|l| redact_line(l) + "\n").collect();
+                self.entries.push((name, redacted.into_bytes()));
+                Ok(())
+            }
+        }
+    }
+
+    /// Packs the bundle into a gzipped tar archive
+    pub fn to_archive(&self) -> Result<Vec<u8>, GuardianError> {
+        let mut builder = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::default()));
+        let mtime = OffsetDateTime::now_utc().unix_timestamp().max(0) as u64;
+
+        for (name, data) in &self.entries {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(data.len() as u64);
+            header.set_mode(0o600);
+            header.set_mtime(mtime);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, format!("support-bundle/{}", name), data.as_slice())
+                .map_err(|e| bundle_error(format!("Failed to archive {}", name), Some(Box::new(e))))?;
+        }
+
+        builder
+            .into_inner()
+            .and_then(|encoder| encoder.finish())
+            .map_err(|e| bundle_error("Failed to finish support bundle archive".into(), Some(Box::new(e))))
+    }
+
+    /// Archives the bundle and encrypts it to the vendor's X25519 public key
+    pub fn seal(&self, recipient_public_key: &[u8]) -> Result<Vec<u8>, GuardianError> {
+        let archive = self.to_archive()?;
+        let sealed = seal_archive(archive, recipient_public_key)?;
+        counter!("guardian.support_bundle.generated", 1);
+        histogram!("guardian.support_bundle.bytes", sealed.len() as f64);
+        Ok(sealed)
+    }
+
+    /// Seals the bundle and writes it to `path` with owner-only permissions
+    pub fn write_sealed(&self, path: &Path, recipient_public_key: &[u8]) -> Result<u64, GuardianError> {
+        let sealed = self.seal(recipient_public_key)?;
+
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+        options
+            .open(path)
+            .and_then(|mut file| file.write_all(&sealed).and_then(|_| file.sync_all()))
+            .map_err(|e| bundle_error(format!("Failed to write support bundle {}", path.display()), Some(Box::new(e))))?;
+
+        info!(path = %path.display(), bytes = sealed.len(), "Support bundle written");
+        Ok(sealed.len() as u64)
+    }
+}
+
+/// Encrypts an archive with an ephemeral X25519 key agreement, HKDF-SHA256 and AES-256-GCM.
+/// Layout: magic | ephemeral public key | nonce | ciphertext with tag.
+fn seal_archive(mut archive: Vec<u8>, recipient_public_key: &[u8]) -> Result<Vec<u8>, GuardianError> {
+    let rng = SystemRandom::new();
+    let crypto_error = |context: &str| bundle_error(format!("Support bundle encryption failed: {}", context), None);
+
+    let ephemeral = agreement::EphemeralPrivateKey::generate(&agreement::X25519, &rng)
+        .map_err(|_| crypto_error("key generation"))?;
+    let ephemeral_public = ephemeral.compute_public_key().map_err(|_| crypto_error("public key"))?;
+
+    let mut key_bytes = [0u8; 32];
+    agreement::agree_ephemeral(
+        ephemeral,
+        &agreement::UnparsedPublicKey::new(&agreement::X25519, recipient_public_key),
+        |shared| {
+            let salt = hkdf::Salt::new(hkdf::HKDF_SHA256, ephemeral_public.as_ref());
+            salt.extract(shared)
+                .expand(&[BUNDLE_HKDF_INFO], &aead::AES_256_GCM)
+                .and_then(|okm| okm.fill(&mut key_bytes))
+        },
+    )
+    .map_err(|_| crypto_error("key agreement"))?
+    .map_err(|_| crypto_error("key derivation"))?;
+
+    let mut nonce = [0u8; aead::NONCE_LEN];
+    rng.fill(&mut nonce).map_err(|_| crypto_error("nonce"))?;
+
+    let key = aead::LessSafeKey::new(
+        aead::UnboundKey::new(&aead::AES_256_GCM, &key_bytes).map_err(|_| crypto_error("key"))?,
+    );
+    key.seal_in_place_append_tag(
+        aead::Nonce::assume_unique_for_key(nonce),
+        aead::Aad::from(BUNDLE_MAGIC),
+        &mut archive,
+    )
+    .map_err(|_| crypto_error("seal"))?;
+
+    let mut sealed = Vec::with_capacity(BUNDLE_MAGIC.len() + 32 + nonce.len() + archive.len());
+    sealed.extend_from_slice(BUNDLE_MAGIC);
+    sealed.extend_from_slice(ephemeral_public.as_ref());
+    sealed.extend_from_slice(&nonce);
+    sealed.extend_from_slice(&archive);
+    Ok(sealed)
+}
+
+/// Replaces values of secret-looking keys in a JSON document
+pub fn strip_secrets(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                if is_secret_key(key) && !value.is_null() {
+                    *value = Value::String(REDACTED.to_string());
+                } else {
+                    strip_secrets(value);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(strip_secrets),
+        _ => {}
+    }
+}
+
+/// The path itself, or the `.toml` files of a directory in name order
+fn config_files(path: &Path) -> Vec<PathBuf> {
+    if !path.is_dir() {
+        return vec![path.to_path_buf()];
+    }
+    let mut files: Vec<PathBuf> = match std::fs::read_dir(path) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|file| file.extension().map_or(false, |ext| ext == "toml"))
+            .collect(),
+        Err(e) => {
+            warn!(path = %path.display(), error = %e, "Skipping unreadable config directory");
+            Vec::new()
+        }
+    };
+    files.sort();
+    files
+}
+
+/// Redacts `key=value` / `key: value` pairs with secret-looking keys and bearer tokens in a log line
+pub fn redact_line(line: &str) -> String {
+    let mut output = String::with_capacity(line.len());
+    let mut rest = line;
+
+    while let Some(pos) = rest.find(|c: char| c == '=' || c == ':') {
+        let (head, tail) = rest.split_at(pos);
+        output.push_str(head);
+        output.push_str(&tail[..1]);
+        let tail = &tail[1..];
+
+        // TOML puts spaces around `=` and may quote the key: `"password" = "x"`
+        let key = head
+            .trim_end()
+            .trim_end_matches(|c: char| c == '"' || c == '\'')
+            .rsplit(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '-'))
+            .next()
+            .unwrap_or("");
+        if !is_secret_key(key) {
+            rest = tail;
+            continue;
+        }
+
+        // Skip separators and an opening quote, then drop the value up to its end
+        let value_start = tail.len() - tail.trim_start_matches(|c: char| c == ' ' || c == '"' || c == '\'').len();
+        output.push_str(&tail[..value_start]);
+        let value = &tail[value_start..];
+        let value_end = value
+            .find(|c: char| c.is_whitespace() || c == '"' || c == '\'' || c == ',' || c == '}' || c == '&')
+            .unwrap_or(value.len());
+        // `Authorization: Bearer <token>` carries the secret in the second word
+        let value_end = if value[..value_end].eq_ignore_ascii_case("bearer") {
+            let token = &value[value_end..];
+            let token_start = token.len() - token.trim_start().len();
+            value_end + token_start + token[token_start..].find(char::is_whitespace).unwrap_or(token.len() - token_start)
+        } else {
+            value_end
+        };
+        output.push_str(REDACTED);
+        rest = &value[value_end..];
+    }
+
+    output.push_str(rest);
+    output
+}
+
+fn is_secret_key(key: &str) -> bool {
+    let key = key.to_ascii_lowercase();
+    SECRET_KEY_MARKERS.iter().any(|marker| key.contains(marker))
+}
+
+/// Returns the most recently modified files of the log directory
+fn recent_log_files(dir: &Path, limit: usize) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut files: Vec<(std::time::SystemTime, PathBuf)> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            if !metadata.is_file() {
+                return None;
+            }
+            Some((metadata.modified().ok()?, entry.path()))
+        })
+        .collect();
+
+    files.sort_by(|a, b| b.0.cmp(&a.0));
+    files.into_iter().take(limit).map(|(_, path)| path).collect()
+}
+
+/// Reads at most `max_bytes` from the end of a file, starting at a line boundary
+fn read_tail(path: &Path, max_bytes: u64) -> std::io::Result<String> {
+    let mut file = std::fs::File::open(path)?;
+    let len = file.metadata()?.len();
+    let offset = len.saturating_sub(max_bytes);
+    file.seek(SeekFrom::Start(offset))?;
+
+    let mut buffer = Vec::with_capacity((len - offset) as usize);
+    file.read_to_end(&mut buffer)?;
+    let text = String::from_utf8_lossy(&buffer);
+
+    if offset == 0 {
+        return Ok(text.into_owned());
+    }
+    Ok(text.split_once('\n').map(|(_, rest)| rest.to_string()).unwrap_or_default())
+}
+
+fn serialize_error(e: serde_json::Error) -> GuardianError {
+    bundle_error("Failed to serialize support bundle entry".into(), Some(Box::new(e)))
+}
+
+fn bundle_error(context: String, source: Option<Box<dyn std::error::Error + Send + Sync>>) -> GuardianError {
+    GuardianError::SystemError {
+        context,
+        source,
+        severity: ErrorSeverity::Medium,
+        timestamp: OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: ErrorCategory::System,
+        retry_count: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redaction() {
+        assert_eq!(
+            redact_line("login failed user=alice password=hunter2 attempt=3"),
+            "login failed user=alice password=[REDACTED] attempt=3"
+        );
+        assert_eq!(
+            redact_line(r#"{"api_key": "abc123", "host": "db"}"#),
+            r#"{"api_key": "[REDACTED]", "host": "db"}"#
+        );
+        assert_eq!(redact_line("Authorization: Bearer eyJhbGci.x.y done"), "Authorization: [REDACTED] done");
+        assert_eq!(redact_line("time=12:30:00 nothing secret"), "time=12:30:00 nothing secret");
+        assert_eq!(redact_line(r#"password = "hunter2""#), r#"password = "[REDACTED]""#);
+        assert_eq!(redact_line(r#"  api_token   =   "t0k" # rotated"#), r#"  api_token   =   "[REDACTED]" # rotated"#);
+        assert_eq!(redact_line(r#""client_secret" = 's3cr3t'"#), r#""client_secret" = '[REDACTED]'"#);
+        assert_eq!(redact_line("token : abc host = db"), "token : [REDACTED] host = db");
+
+        let mut config = json!({
+            "storage": { "pool": "guardian", "encryption_key": "00ff" },
+            "nats": [{ "url": "nats://local", "token": "t0k" }],
+        });
+        strip_secrets(&mut config);
+        assert_eq!(config["storage"]["encryption_key"], REDACTED);
+        assert_eq!(config["storage"]["pool"], "guardian");
+        assert_eq!(config["nats"][0]["token"], REDACTED);
+    }
+
+    #[test]
+    fn test_toml_config_directory_collected_redacted() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("security.toml"), "[auth]\npassword = \"hunter2\"\nmode = \"strict\"\n").unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "password=ignored").unwrap();
+
+        let bundle = SupportBundle::collect(
+            &SupportBundleConfig {
+                log_dir: PathBuf::from("/nonexistent"),
+                config_paths: vec![dir.path().to_path_buf()],
+                ..Default::default()
+            },
+            None,
+        )
+        .unwrap();
+        let names: Vec<_> = bundle.entry_names().collect();
+        assert!(names.contains(&"config/security.toml") && !names.contains(&"config/notes.txt"));
+
+        let (_, data) = bundle.entries.iter().find(|(name, _)| name == "config/security.toml").unwrap();
+        let redacted = String::from_utf8(data.clone()).unwrap();
+        assert!(redacted.contains(r#"password = "[REDACTED]""#) && !redacted.contains("hunter2"));
+        assert!(redacted.contains(r#"mode = "strict""#));
+    }
+
+    #[test]
+    fn test_sealed_bundle_layout() {
+        let rng = SystemRandom::new();
+        let recipient = agreement::EphemeralPrivateKey::generate(&agreement::X25519, &rng).unwrap();
+        let recipient_public = recipient.compute_public_key().unwrap();
+
+        let bundle = SupportBundle::collect(
+            &SupportBundleConfig {
+                log_dir: PathBuf::from("/nonexistent"),
+                config_paths: Vec::new(),
+                ..Default::default()
+            },
+            Some(json!({ "health": "Healthy" })),
+        )
+        .unwrap();
+        let names: Vec<_> = bundle.entry_names().collect();
+        assert!(names.contains(&"errors.json") && names.contains(&"flight_recorder.json"));
+
+        let archive_len = bundle.to_archive().unwrap().len();
+        let sealed = bundle.seal(recipient_public.as_ref()).unwrap();
+        assert_eq!(&sealed[..4], BUNDLE_MAGIC);
+        assert!(sealed.len() >= 4 + 32 + aead::NONCE_LEN + aead::AES_256_GCM.tag_len());
+        assert!(archive_len > 0);
+
+        assert!(bundle.seal(&[0u8; 3]).is_err());
+    }
+}
@@ -2,7 +2,7 @@ use chrono::{DateTime, Utc};
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
 use std::{
-    collections::VecDeque,
+    collections::{BTreeMap, VecDeque},
     sync::Arc,
     time::Duration,
 };
@@ -11,7 +11,11 @@ use tracing::{debug, error, info, instrument, warn};
 
 use crate::utils::error::GuardianError;
 use crate::utils::metrics::MetricsCollector;
-use crate::core::event_bus::EventBus;
+use crate::utils::queue_metrics::{queue_registry, QueueStats};
+use crate::core::event_bus::{Event, EventBus};
+use crate::core::state_journal::{state_journal, TransitionCause};
+use crate::ml::warmup::{warmup_gate, Readiness};
+use crate::storage::{pool_reports, PoolStatus, VdevState};
 
 // Constants for state management configuration
 const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(30);
@@ -21,6 +25,10 @@ const STATE_HISTORY_CAPACITY: usize = 1000;
 const LOCK_ACQUISITION_TIMEOUT: Duration = Duration::from_millis(100);
 const CIRCUIT_BREAKER_THRESHOLD: u32 = 5;
 const STATE_VALIDATION_TIMEOUT: Duration = Duration::from_millis(50);
+const QUEUE_SATURATION_DEGRADED: f64 = 0.8;
+const QUEUE_SATURATION_CRITICAL: f64 = 0.95;
+const QUEUE_AGE_DEGRADED: Duration = Duration::from_secs(5);
+const QUEUE_AGE_CRITICAL: Duration = Duration::from_secs(30);
 
 /// System health status indicators
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -120,7 +128,7 @@ impl SystemState {
                 source: None,
                 severity: crate::utils::error::ErrorSeverity::High,
                 timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id: uuid::Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: crate::utils::error::ErrorCategory::System,
                 retry_count: 0,
             });
@@ -129,9 +137,39 @@ impl SystemState {
         Ok(self.clone())
     }
 
+    /// Current overall health
+    pub fn health(&self) -> &SystemHealth {
+        &self.health
+    }
+
+    /// Whether the models have warmed up enough to take inference traffic
+    pub fn readiness(&self) -> Readiness {
+        warmup_gate().readiness()
+    }
+
+    /// When the state was last updated
+    pub fn last_update(&self) -> DateTime<Utc> {
+        self.last_update
+    }
+
     /// Updates the system state with new values using optimized write patterns
     #[instrument(skip(self, new_state))]
     pub async fn update_state(&mut self, new_state: SystemState) -> Result<(), GuardianError> {
+        self.update_state_with_cause(new_state, TransitionCause::Update).await
+    }
+
+    /// Updates the system state in response to an event, journaling the event as the cause
+    pub async fn update_state_from_event(&mut self, new_state: SystemState, event: &Event) -> Result<(), GuardianError> {
+        let cause = TransitionCause::Event {
+            event_type: event.event_type.clone(),
+            correlation_id: event.correlation_id,
+        };
+        self.update_state_with_cause(new_state, cause).await
+    }
+
+    /// Updates the system state, journaling the transition with its cause
+    #[instrument(skip(self, new_state, cause), fields(cause = cause.label()))]
+    pub async fn update_state_with_cause(&mut self, new_state: SystemState, cause: TransitionCause) -> Result<(), GuardianError> {
         // Validate new state
         for rule in &self.validation_rules {
             if !(rule.validator)(&new_state) {
@@ -140,7 +178,7 @@ impl SystemState {
                     source: None,
                     severity: crate::utils::error::ErrorSeverity::High,
                     timestamp: time::OffsetDateTime::now_utc(),
-                    correlation_id: uuid::Uuid::new_v4(),
+                    correlation_id: crate::utils::correlation::current_or_new(),
                     category: crate::utils::error::ErrorCategory::Validation,
                     retry_count: 0,
                 });
@@ -153,6 +191,8 @@ impl SystemState {
             timestamp: Utc::now(),
         };
 
+        state_journal().record(self.health.clone(), new_state.health.clone(), cause, new_state.inputs());
+
         // Update state values
         self.health = new_state.health;
         self.cpu_usage = new_state.cpu_usage;
@@ -169,6 +209,15 @@ impl SystemState {
         Ok(())
     }
 
+    /// Values health is derived from, as journaled with each transition
+    pub(crate) fn inputs(&self) -> BTreeMap<String, f64> {
+        BTreeMap::from([
+            ("cpu_usage".to_string(), self.cpu_usage),
+            ("memory_usage".to_string(), self.memory_usage),
+            ("active_threats".to_string(), self.active_threats as f64),
+        ])
+    }
+
     /// Creates default validation rules for state management
     fn default_validation_rules() -> Vec<StateValidationRule> {
         vec![
@@ -210,6 +259,14 @@ async fn monitor_system_health(
         SystemHealth::Healthy
     };
 
+    // Silent backlogs in internal queues degrade health even when resources look fine
+    let queues = queue_registry().export();
+    let new_health = worst_health(new_health, queue_health(&queues));
+
+    // Impaired vdevs and checksum errors put data integrity at risk
+    let pools = pool_reports();
+    let new_health = worst_health(new_health, storage_health(&pools));
+
     // Record metrics
     metrics.record_metric(
         "system.health".into(),
@@ -225,6 +282,15 @@ async fn monitor_system_health(
 
     // Update state if health changed
     if write_guard.health != new_health {
+        let mut inputs = write_guard.inputs();
+        inputs.extend(queue_inputs(&queues));
+        inputs.extend(storage_inputs(&pools));
+        state_journal().record(
+            write_guard.health.clone(),
+            new_health.clone(),
+            TransitionCause::HealthCheck { source: "system_state".into() },
+            inputs,
+        );
         write_guard.health = new_health;
         info!(?new_health, "System health status changed");
     }
@@ -232,6 +298,85 @@ async fn monitor_system_health(
     Ok(())
 }
 
+/// Rates queue backlogs by saturation and age of the oldest pending item
+fn queue_health(queues: &[QueueStats]) -> SystemHealth {
+    let degraded_age = QUEUE_AGE_DEGRADED.as_secs_f64() * 1000.0;
+    let critical_age = QUEUE_AGE_CRITICAL.as_secs_f64() * 1000.0;
+
+    queues.iter().fold(SystemHealth::Healthy, |health, queue| {
+        let age = queue.backlog_age_ms();
+        let queue_health = if queue.saturation() >= QUEUE_SATURATION_CRITICAL || age >= critical_age {
+            SystemHealth::Critical
+        } else if queue.saturation() >= QUEUE_SATURATION_DEGRADED || age >= degraded_age {
+            SystemHealth::Degraded
+        } else {
+            SystemHealth::Healthy
+        };
+        if queue_health != SystemHealth::Healthy {
+            warn!(
+                queue = %queue.name,
+                depth = queue.depth,
+                oldest_age_ms = queue.oldest_age_ms,
+                ?queue_health,
+                "Internal queue backlog"
+            );
+        }
+        worst_health(health, queue_health)
+    })
+}
+
+/// Saturation and backlog age of queues past their degraded thresholds
+fn queue_inputs(queues: &[QueueStats]) -> BTreeMap<String, f64> {
+    let degraded_age = QUEUE_AGE_DEGRADED.as_secs_f64() * 1000.0;
+    queues
+        .iter()
+        .filter(|queue| queue.saturation() >= QUEUE_SATURATION_DEGRADED || queue.backlog_age_ms() >= degraded_age)
+        .flat_map(|queue| {
+            [
+                (format!("queue.{}.saturation", queue.name), queue.saturation()),
+                (format!("queue.{}.backlog_age_ms", queue.name), queue.backlog_age_ms()),
+            ]
+        })
+        .collect()
+}
+
+/// Rates ZFS pools: faulted pools are critical, lost redundancy or checksum errors degraded
+fn storage_health(pools: &[PoolStatus]) -> SystemHealth {
+    pools.iter().fold(SystemHealth::Healthy, |health, pool| {
+        let pool_health = match pool.state {
+            VdevState::Online if pool.impaired_vdevs().next().is_none() && pool.checksum_errors() == 0 => {
+                SystemHealth::Healthy
+            }
+            VdevState::Online | VdevState::Degraded => SystemHealth::Degraded,
+            _ => SystemHealth::Critical,
+        };
+        worst_health(health, pool_health)
+    })
+}
+
+/// Impaired vdev and checksum error counts of pools that are not healthy
+fn storage_inputs(pools: &[PoolStatus]) -> BTreeMap<String, f64> {
+    pools
+        .iter()
+        .filter(|pool| storage_health(std::slice::from_ref(pool)) != SystemHealth::Healthy)
+        .flat_map(|pool| {
+            [
+                (format!("pool.{}.impaired_vdevs", pool.pool), pool.impaired_vdevs().count() as f64),
+                (format!("pool.{}.checksum_errors", pool.pool), pool.checksum_errors() as f64),
+            ]
+        })
+        .collect()
+}
+
+fn worst_health(a: SystemHealth, b: SystemHealth) -> SystemHealth {
+    let rank = |h: &SystemHealth| match h {
+        SystemHealth::Healthy => 0,
+        SystemHealth::Degraded => 1,
+        SystemHealth::Critical => 2,
+    };
+    if rank(&b) > rank(&a) { b } else { a }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -276,4 +421,19 @@ mod tests {
 
         assert!(write_guard.update_state(new_state).await.is_ok());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_queue_backlog_degrades_health() {
+        let queue = |depth, oldest_age_ms| QueueStats {
+            name: "event_bus".into(),
+            depth,
+            capacity: 100,
+            oldest_age_ms,
+            ..Default::default()
+        };
+
+        assert_eq!(queue_health(&[queue(10, 100.0)]), SystemHealth::Healthy);
+        assert_eq!(queue_health(&[queue(10, 100.0), queue(85, 100.0)]), SystemHealth::Degraded);
+        assert_eq!(queue_health(&[queue(10, 60_000.0)]), SystemHealth::Critical);
+    }
+}
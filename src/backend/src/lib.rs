@@ -72,11 +72,24 @@ impl Default for InitOptions {
 /// Initializes the Guardian system with the provided configuration
 #[instrument(skip(config), fields(features = ?config.features))]
 pub async fn init_guardian(config: GuardianConfig) -> Result<Arc<Guardian>> {
+    init_guardian_with(config, InitOptions::default()).await
+}
+
+/// Initializes the Guardian system, switching subsystems of disabled features to their fallbacks
+#[instrument(skip(config, options), fields(features = ?options.features))]
+pub async fn init_guardian_with(config: GuardianConfig, options: InitOptions) -> Result<Arc<Guardian>> {
     info!("Initializing AI Guardian system v{}", VERSION);
-    
+
+    // Install the capability matrix before any subsystem consults it
+    let capabilities = core::init_capabilities(&options.features);
+
     // Initialize metrics collection
-    metrics::init_metrics(METRICS_PREFIX)?;
-    counter!("guardian.initialization", 1);
+    if capabilities.is_enabled(core::Capability::Metrics) {
+        metrics::init_metrics(METRICS_PREFIX)?;
+        counter!("guardian.initialization", 1);
+    } else {
+        warn!("Performance metrics disabled, metrics export not initialized");
+    }
 
     // Create optimized runtime
     let runtime = Runtime::builder()
@@ -88,7 +101,7 @@ pub async fn init_guardian(config: GuardianConfig) -> Result<Arc<Guardian>> {
             source: Some(Box::new(e)),
             severity: utils::error::ErrorSeverity::Critical,
             timestamp: time::OffsetDateTime::now_utc(),
-            correlation_id: uuid::Uuid::new_v4(),
+            correlation_id: crate::utils::correlation::current_or_new(),
             category: utils::error::ErrorCategory::System,
             retry_count: 0,
         })?;
@@ -100,6 +113,7 @@ pub async fn init_guardian(config: GuardianConfig) -> Result<Arc<Guardian>> {
     let security_manager = SecurityManager::new(
         config.security_config,
         Arc::new(metrics::MetricsCollector::new(Default::default())?),
+        guardian.tenant().clone(),
     )?;
     security_manager.initialize().await?;
 
@@ -110,7 +124,7 @@ pub async fn init_guardian(config: GuardianConfig) -> Result<Arc<Guardian>> {
             source: None,
             severity: utils::error::ErrorSeverity::Critical,
             timestamp: time::OffsetDateTime::now_utc(),
-            correlation_id: uuid::Uuid::new_v4(),
+            correlation_id: crate::utils::correlation::current_or_new(),
             category: utils::error::ErrorCategory::System,
             retry_count: 0,
         })?;
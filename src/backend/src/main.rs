@@ -10,39 +10,65 @@
 //! - tracing-subscriber v0.3
 //! - clap v4.0
 
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::signal::unix::{signal, SignalKind};
 use tokio::sync::RwLock;
 use tracing::{debug, error, info, warn};
 use clap::{Command, Arg, ArgAction};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
 
 use guardian::{Guardian, Result};
 use crate::config::app_config::AppConfig;
+use crate::config::EnvironmentProfile;
 use crate::cli::run_cli;
 
 // System version and metadata constants
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 const AUTHOR: &str = "Guardian Security Team";
 const DEFAULT_CONFIG_PATH: &str = "/etc/guardian/config.toml";
+const STORAGE_CONFIG_PATH: &str = "/etc/guardian/config/storage.toml";
+const MODEL_STORE_PATH: &str = "/var/lib/guardian/models";
 
 // Operational constants
 const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(30);
 const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(5);
 const MAX_STARTUP_RETRIES: u32 = 3;
 
-/// Initializes the logging and tracing system with security context
-async fn setup_logging() -> Result<()> {
-    let subscriber = tracing_subscriber::fmt()
-        .with_max_level(tracing::Level::INFO)
-        .with_target(true)
-        .with_thread_ids(true)
-        .with_file(true)
-        .with_line_number(true)
-        .with_env_filter("guardian=debug,warn")
-        .json()
-        .with_current_span(true)
-        .with_span_events(tracing_subscriber::fmt::format::FmtSpan::FULL)
+/// Installs the hot-reloadable settings of a reloaded configuration
+fn apply_runtime_settings(reloaded: &AppConfig) {
+    guardian::core::init_governor(&reloaded.resource_governor);
+    guardian::api::quota::init_client_quotas(&reloaded.client_quotas);
+    guardian::security::response_guardrails::init_response_guardrails(reloaded);
+    guardian::security::process_trust::init_process_trust(reloaded);
+    guardian::core::init_dead_letters(reloaded);
+    guardian::security::intel::init_threat_intel(reloaded);
+    guardian::security::detection_rules::init_detection_rules(reloaded);
+    guardian::core::init_collectors(reloaded);
+    guardian::security::degradation::init_degradation(reloaded);
+    guardian::security::attestation::attestor().record_config(reloaded);
+    guardian::core::state_journal().record_config_change("runtime settings reloaded".to_string());
+}
+
+/// Initializes the logging and tracing system with security context, exporting spans over OTLP when configured
+async fn setup_logging(profile: &EnvironmentProfile, config: &AppConfig) -> Result<()> {
+    let tracer = guardian::utils::telemetry::init_tracer(&config.monitoring_config, &config.environment)?;
+
+    let subscriber = tracing_subscriber::registry()
+        .with(tracing_subscriber::EnvFilter::new(profile.log_filter))
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_target(true)
+                .with_thread_ids(true)
+                .with_file(true)
+                .with_line_number(true)
+                .json()
+                .with_current_span(true)
+                .with_span_events(tracing_subscriber::fmt::format::FmtSpan::FULL),
+        )
+        .with(tracer.map(|tracer| tracing_opentelemetry::layer().with_tracer(tracer)))
         .try_init();
 
     match subscriber {
@@ -83,28 +109,110 @@ fn create_cli() -> Command {
 }
 
 /// Main entry point with comprehensive security and monitoring
-#[tokio::main]
-async fn main() -> Result<()> {
+///
+/// The runtime is built once CPU affinity is installed, so its workers are pinned to the I/O
+/// cpuset from the moment they start.
+fn main() -> Result<()> {
     // Parse command line arguments
     let matches = create_cli().get_matches();
-    let config_path = matches.get_one::<String>("config").unwrap();
+    let config_path = matches.get_one::<String>("config").unwrap().clone();
     
-    // Initialize logging with security context
-    setup_logging().await?;
-    info!(version = VERSION, "Starting AI Guardian System");
-
     // Load and validate configuration
-    let app_config = match AppConfig::new(Some(config_path.to_string()), None) {
-        Ok(config) => {
-            debug!("Configuration loaded successfully");
-            config
-        }
+    let mut app_config = match AppConfig::new(Some(config_path.clone()), None) {
+        Ok(config) => config,
         Err(e) => {
-            error!("Failed to load configuration: {}", e);
+            eprintln!("Failed to load configuration: {}", e);
             return Err(e);
         }
     };
 
+    // Install the environment profile before any subsystem consults it
+    let profile = crate::config::profile::install(
+        EnvironmentProfile::for_environment(app_config.environment.clone()),
+    );
+    profile.apply(&mut app_config);
+
+    // Apply CPU affinity before any runtime worker threads are spawned
+    guardian::utils::init_affinity(&app_config.cpu_affinity)?;
+    let runtime = guardian::utils::affinity_manager()
+        .runtime_builder(guardian::utils::ThreadClass::Io)
+        .thread_name("guardian-io")
+        .enable_all()
+        .build()?;
+
+    // Held until the daemon returns; dropping it then stops the tasks the daemon spawned
+    runtime.block_on(run(config_path, app_config, profile))
+}
+
+/// Runs the daemon on the pinned runtime until a shutdown signal
+async fn run(config_path: String, app_config: AppConfig, profile: &'static EnvironmentProfile) -> Result<()> {
+    // Initialize logging with security context
+    setup_logging(profile, &app_config).await?;
+    info!(version = VERSION, environment = ?profile.environment, "Starting AI Guardian System");
+    debug!(workers = guardian::utils::thread_placements().len(), "Configuration loaded, runtime workers placed");
+
+    // Shed low priority work under overload before subsystems start producing it
+    guardian::utils::init_admission(&app_config.admission);
+
+    // Hold ML, storage and detection to the overhead budget
+    guardian::core::init_governor(&app_config.resource_governor);
+
+    // Rate limit API clients and cap automatic responses; SIGHUP re-reads the limits
+    guardian::api::quota::init_client_quotas(&app_config.client_quotas);
+    guardian::security::response_guardrails::init_response_guardrails(&app_config);
+    guardian::security::process_trust::init_process_trust(&app_config);
+    guardian::core::init_dead_letters(&app_config);
+
+    // Known-bad indicators are matched before ML inference
+    guardian::security::intel::init_threat_intel(&app_config);
+    // Operator rules run in a WebAssembly sandbox, also ahead of inference
+    guardian::security::detection_rules::init_detection_rules(&app_config);
+    guardian::core::init_collectors(&app_config);
+    // Slow detection cycles shed work step by step instead of tripping the circuit breaker
+    guardian::security::degradation::init_degradation(&app_config);
+    guardian::security::intel::threat_intel().start();
+
+    // Only models signed by a trusted key may be registered or activated
+    guardian::security::model_signing::init_model_signing(&app_config.security_config)?;
+
+    // Fleet verifiers pin the measurements of the binary and configuration
+    guardian::security::attestation::init_attestation(&app_config)?;
+
+    // Hot-reloadable settings follow the config directory; SIGHUP forces a reload
+    let watcher = Arc::new(guardian::config::watcher::ConfigWatcher::new(
+        PathBuf::from(&config_path),
+        app_config.clone(),
+    ));
+    if app_config.config_watch.enabled {
+        watcher.start(apply_runtime_settings);
+    }
+    let sighup_watcher = Arc::clone(&watcher);
+    let reload_path = config_path.clone();
+    let mut sighup = signal(SignalKind::hangup())?;
+    tokio::spawn(async move {
+        while sighup.recv().await.is_some() {
+            sighup_watcher.reload(&apply_runtime_settings);
+            // Signing keys are re-read on request even though the rest of security_config needs a restart
+            match AppConfig::load(PathBuf::from(&reload_path)) {
+                Ok(reloaded) => {
+                    if let Err(e) = guardian::security::model_signing::init_model_signing(&reloaded.security_config) {
+                        warn!(error = %e, "Model signing keys reload failed, keeping current keys");
+                    }
+                }
+                Err(e) => warn!(error = %e, "Configuration reload failed, keeping current model signing keys"),
+            }
+        }
+    });
+
+    // Expose metrics for scraping alongside the StatsD export
+    #[cfg(feature = "prometheus")]
+    if let Some(addr) = &app_config.monitoring_config.prometheus_addr {
+        let addr = addr.parse().map_err(|_| {
+            guardian::GuardianError::SystemError(format!("Invalid Prometheus address {}", addr))
+        })?;
+        guardian::utils::prometheus::start_exporter(addr).await?;
+    }
+
     // Initialize Guardian system
     let guardian = Arc::new(RwLock::new(
         Guardian::new(
@@ -115,6 +223,50 @@ async fn main() -> Result<()> {
         ).await?,
     ));
     
+    // Storage shares one I/O throttler and write budget across its stores
+    let storage_config = if std::path::Path::new(STORAGE_CONFIG_PATH).exists() {
+        guardian::config::StorageConfig::load(STORAGE_CONFIG_PATH.to_string())?
+    } else {
+        guardian::config::StorageConfig::new()
+    };
+    let storage_keys = guardian::security::key_provider::key_provider_from_config(
+        &app_config.security_config.hw_security_config,
+    )?;
+    let storage = guardian::storage::init_storage(
+        &storage_config,
+        storage_keys.as_ref(),
+        Arc::new(guardian::utils::logging::LogManager::new()),
+        Arc::new(guardian.read().await.event_bus().clone()),
+        guardian.read().await.tenant().clone(),
+    )
+    .await?;
+
+    // Detections are recorded in the event store for analysts to label and retraining to use
+    let model_registry = Arc::new(
+        guardian::ml::model_registry::ModelRegistry::new(Arc::new(
+            guardian::storage::model_store::ModelStore::new(
+                Arc::clone(&storage.zfs),
+                PathBuf::from(MODEL_STORE_PATH),
+                None,
+            )
+            .await?,
+        ))
+        .await?,
+    );
+    guardian::security::anomaly_feedback::init_anomaly_feedback(Arc::clone(&storage.events), model_registry);
+
+    // Settle responses a crash interrupted before any new detection is acted on
+    let response_engine = guardian::security::response_engine::init_response_engine(
+        &*guardian.read().await,
+        Arc::clone(&storage.events),
+    )
+    .await?;
+
+    // Bring the pf block table back in line with the blocks held before the restart
+    if let Err(e) = guardian::security::firewall::firewall().start().await {
+        warn!(error = %e, "Firewall reconciliation failed, blocks are not being enforced");
+    }
+
     // Start health monitoring
     let health_guardian = guardian.clone();
     tokio::spawn(async move {
@@ -155,6 +307,11 @@ async fn main() -> Result<()> {
         guardian.read().await.shutdown()
     ).await;
 
+    storage.shutdown();
+
+    // Export spans recorded during shutdown before the process exits
+    guardian::utils::telemetry::shutdown();
+
     match shutdown_result {
         Ok(Ok(_)) => {
             info!("Guardian system shutdown completed successfully");
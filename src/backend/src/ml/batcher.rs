@@ -0,0 +1,233 @@
+//! Micro-batching of concurrent inference requests
+//!
+//! Callers submit events individually; a worker collects them until the batch is full or the
+//! oldest request has waited `max_wait_ms`, runs a single forward pass and hands each caller
+//! its own prediction back. The wait is bounded by configuration so batching never consumes
+//! more than a fixed share of the inference latency SLA.
+
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use metrics::{counter, histogram};
+use tokio::sync::{mpsc, oneshot};
+use tokio::time::{timeout_at, Instant};
+use tracing::{debug, warn};
+
+use crate::config::ml_config::BatchingConfig;
+use crate::ml::inference_engine::{InferenceEngine, Prediction};
+use crate::security::anomaly_detection::SystemData;
+use crate::utils::error::{ErrorCategory, ErrorSeverity, GuardianError};
+
+// Constants for request batching
+const QUEUE_DEPTH_PER_BATCH: usize = 8;
+
+/// A batched forward pass over several events, returning predictions in input order
+#[async_trait]
+pub trait BatchInference: Send + Sync {
+    async fn infer_batch(&self, events: Vec<SystemData>) -> Result<Vec<Prediction>, GuardianError>;
+}
+
+#[async_trait]
+impl BatchInference for InferenceEngine {
+    async fn infer_batch(&self, events: Vec<SystemData>) -> Result<Vec<Prediction>, GuardianError> {
+        self.batch_predict(events).await
+    }
+}
+
+/// A request waiting for its batch
+struct Pending {
+    event: SystemData,
+    enqueued: Instant,
+    reply: oneshot::Sender<Result<Prediction, GuardianError>>,
+}
+
+impl std::fmt::Debug for Pending {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Pending").field("enqueued", &self.enqueued).finish()
+    }
+}
+
+/// Coalesces concurrent inference requests into batched forward passes
+#[derive(Debug, Clone)]
+pub struct InferenceBatcher {
+    tx: mpsc::Sender<Pending>,
+}
+
+impl InferenceBatcher {
+    /// Starts the batching worker; it stops once the engine or every batcher handle is dropped
+    pub fn spawn(engine: Weak<dyn BatchInference>, config: &BatchingConfig) -> Self {
+        let max_batch_size = config.max_batch_size.max(1);
+        let max_wait = Duration::from_millis(config.max_wait_ms);
+        let (tx, rx) = mpsc::channel(max_batch_size * QUEUE_DEPTH_PER_BATCH);
+        tokio::spawn(run(engine, rx, max_batch_size, max_wait));
+        Self { tx }
+    }
+
+    /// Runs one event through the next batch
+    pub async fn predict(&self, event: SystemData) -> Result<Prediction, GuardianError> {
+        receive(self.submit(event).await?).await
+    }
+
+    /// Submits each event separately so they can share batches with other callers
+    pub async fn predict_all(&self, events: Vec<SystemData>) -> Result<Vec<Prediction>, GuardianError> {
+        let mut responses = Vec::with_capacity(events.len());
+        for event in events {
+            responses.push(self.submit(event).await?);
+        }
+        let mut predictions = Vec::with_capacity(responses.len());
+        for response in responses {
+            predictions.push(receive(response).await?);
+        }
+        Ok(predictions)
+    }
+
+    async fn submit(&self, event: SystemData) -> Result<oneshot::Receiver<Result<Prediction, GuardianError>>, GuardianError> {
+        let (reply, response) = oneshot::channel();
+        let pending = Pending { event, enqueued: Instant::now(), reply };
+        self.tx
+            .send(pending)
+            .await
+            .map_err(|_| batch_error("Inference batcher is not running".into()))?;
+        Ok(response)
+    }
+}
+
+async fn receive(response: oneshot::Receiver<Result<Prediction, GuardianError>>) -> Result<Prediction, GuardianError> {
+    response
+        .await
+        .map_err(|_| batch_error("Inference batch was dropped before completing".into()))?
+}
+
+async fn run(
+    engine: Weak<dyn BatchInference>,
+    mut rx: mpsc::Receiver<Pending>,
+    max_batch_size: usize,
+    max_wait: Duration,
+) {
+    while let Some(first) = rx.recv().await {
+        let deadline = first.enqueued + max_wait;
+        let mut batch = Vec::with_capacity(max_batch_size);
+        batch.push(first);
+
+        while batch.len() < max_batch_size {
+            match timeout_at(deadline, rx.recv()).await {
+                Ok(Some(pending)) => batch.push(pending),
+                Ok(None) | Err(_) => break,
+            }
+        }
+
+        let Some(engine) = engine.upgrade() else {
+            fail(batch, "Inference engine was shut down");
+            break;
+        };
+        // Run the pass off the worker so the next batch fills while this one computes
+        tokio::spawn(dispatch(engine, batch));
+    }
+    debug!("Inference batcher stopped");
+}
+
+/// Runs one forward pass and demultiplexes its predictions back to the callers
+async fn dispatch(engine: Arc<dyn BatchInference>, batch: Vec<Pending>) {
+    let now = Instant::now();
+    histogram!("guardian.ml.batch_size", batch.len() as f64);
+    for pending in &batch {
+        histogram!("guardian.ml.batch_wait_ms", (now - pending.enqueued).as_secs_f64() * 1000.0);
+    }
+
+    let (events, waiters): (Vec<_>, Vec<_>) = batch
+        .into_iter()
+        .map(|pending| (pending.event, pending.reply))
+        .unzip();
+    let expected = events.len();
+
+    match engine.infer_batch(events).await {
+        Ok(predictions) if predictions.len() == expected => {
+            for (reply, prediction) in waiters.into_iter().zip(predictions) {
+                // A caller that timed out has dropped its receiver
+                let _ = reply.send(Ok(prediction));
+            }
+        }
+        Ok(predictions) => {
+            let message = format!("Batch returned {} predictions for {} events", predictions.len(), expected);
+            warn!("{}", message);
+            send_error(waiters, &message);
+        }
+        Err(e) => {
+            counter!("guardian.ml.batch_failures", 1);
+            send_error(waiters, &format!("Batched inference failed: {}", e));
+        }
+    }
+}
+
+fn fail(batch: Vec<Pending>, message: &str) {
+    send_error(batch.into_iter().map(|pending| pending.reply).collect(), message);
+}
+
+fn send_error(waiters: Vec<oneshot::Sender<Result<Prediction, GuardianError>>>, message: &str) {
+    for reply in waiters {
+        let _ = reply.send(Err(batch_error(message.to_string())));
+    }
+}
+
+fn batch_error(context: String) -> GuardianError {
+    GuardianError::MLError {
+        context,
+        source: None,
+        severity: ErrorSeverity::High,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: ErrorCategory::System,
+        retry_count: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn sample() -> SystemData {
+        SystemData { metrics: HashMap::new(), events: Vec::new(), timestamp: 0 }
+    }
+
+    #[derive(Default)]
+    struct CountingModel {
+        passes: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl BatchInference for CountingModel {
+        async fn infer_batch(&self, events: Vec<SystemData>) -> Result<Vec<Prediction>, GuardianError> {
+            self.passes.fetch_add(1, Ordering::SeqCst);
+            Ok(events
+                .iter()
+                .enumerate()
+                .map(|(i, _)| Prediction::new(format!("slot-{}", i), 0.5, HashMap::new()))
+                .collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_requests_share_one_pass() {
+        let model = Arc::new(CountingModel::default());
+        let engine: Arc<dyn BatchInference> = model.clone();
+        let config = BatchingConfig { enabled: true, max_batch_size: 4, max_wait_ms: 50 };
+        let batcher = InferenceBatcher::spawn(Arc::downgrade(&engine), &config);
+
+        let predictions = batcher
+            .predict_all(vec![sample(); 4])
+            .await
+            .unwrap();
+
+        // Every caller gets the prediction for its own slot from a single forward pass
+        let slots: Vec<_> = predictions.iter().map(|p| p.prediction_type().to_string()).collect();
+        assert_eq!(slots, vec!["slot-0", "slot-1", "slot-2", "slot-3"]);
+        assert_eq!(model.passes.load(Ordering::SeqCst), 1);
+
+        drop(engine);
+        drop(model);
+        assert!(batcher.predict(sample()).await.is_err());
+    }
+}
@@ -13,17 +13,22 @@ use tracing::{debug, error, info, instrument, warn};
 use lru::LruCache;
 
 use crate::{
+    ml::feature_sources::{builtin_sources, FeatureSource},
+    ml::simd,
     utils::error::{GuardianError, MLError},
     core::metrics::CoreMetricsManager,
 };
 
 // Constants for feature extraction configuration
 const MAX_BATCH_SIZE: usize = 1024;
-const FEATURE_DIMENSION: usize = 256;
+/// Width of every feature vector, whatever its source
+pub const FEATURE_DIMENSION: usize = 256;
 const MIN_FEATURE_VALUE: f32 = -1.0;
 const MAX_FEATURE_VALUE: f32 = 1.0;
 const ADAPTIVE_SAMPLING_THRESHOLD: f32 = 0.05;
 const MEMORY_POOL_SIZE: usize = 4096;
+/// Source tag of features extracted from generic security events
+pub const GENERIC_SOURCE: &str = "generic";
 
 /// Configuration for adaptive sampling in feature extraction
 #[derive(Debug, Clone)]
@@ -50,6 +55,7 @@ impl Default for AdaptiveSamplingConfig {
 pub struct Features {
     data: Vec<f32>,
     metadata: HashMap<String, String>,
+    source: String,
 }
 
 impl Features {
@@ -62,12 +68,23 @@ impl Features {
                 source: None,
                 severity: crate::utils::error::ErrorSeverity::Medium,
                 timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id: uuid::Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: crate::utils::error::ErrorCategory::ML,
                 retry_count: 0,
             });
         }
-        Ok(Self { data, metadata })
+        Ok(Self { data, metadata, source: GENERIC_SOURCE.to_string() })
+    }
+
+    /// Tags the features with the data source they were extracted from
+    pub fn with_source(mut self, source: impl Into<String>) -> Self {
+        self.source = source.into();
+        self
+    }
+
+    /// Returns the data source the features were extracted from
+    pub fn source(&self) -> &str {
+        &self.source
     }
 
     /// Converts features to a Burn tensor with zero-copy optimization
@@ -90,6 +107,7 @@ pub struct FeatureExtractor {
     feature_cache: RwLock<LruCache<String, Features>>,
     adaptive_config: AdaptiveSamplingConfig,
     processing_pool: Arc<Vec<Vec<f32>>>,
+    sources: RwLock<HashMap<&'static str, Arc<dyn FeatureSource>>>,
 }
 
 impl FeatureExtractor {
@@ -97,15 +115,51 @@ impl FeatureExtractor {
     pub fn new(metrics_manager: CoreMetricsManager, adaptive_config: Option<AdaptiveSamplingConfig>) -> Self {
         let feature_cache = RwLock::new(LruCache::new(MEMORY_POOL_SIZE));
         let processing_pool = Arc::new(vec![vec![0.0; FEATURE_DIMENSION]; MAX_BATCH_SIZE]);
+        let sources = builtin_sources()
+            .into_iter()
+            .map(|source| (source.source(), Arc::from(source)))
+            .collect();
+        info!(simd = ?simd::simd_level(), "Feature extractor initialized");
         
         Self {
             metrics_manager,
             feature_cache,
             adaptive_config: adaptive_config.unwrap_or_default(),
             processing_pool,
+            sources: RwLock::new(sources),
         }
     }
 
+    /// Registers an extractor for a data source, replacing any registered under the same tag
+    pub fn register_source(&self, source: Arc<dyn FeatureSource>) {
+        info!(source = source.source(), "Feature source registered");
+        self.sources.write().insert(source.source(), source);
+    }
+
+    /// Returns the tags of the registered data sources
+    pub fn sources(&self) -> Vec<&'static str> {
+        self.sources.read().keys().copied().collect()
+    }
+
+    /// Extracts features from a raw record of the given data source, tagged with that source
+    #[instrument(skip(self, record))]
+    pub fn extract_from(&self, source: &str, record: &serde_json::Value) -> Result<Features, GuardianError> {
+        let extractor = self.sources.read().get(source).cloned().ok_or_else(|| GuardianError::MLError {
+            context: format!("No feature source registered for {}", source),
+            source: None,
+            severity: crate::utils::error::ErrorSeverity::Medium,
+            timestamp: time::OffsetDateTime::now_utc(),
+            correlation_id: crate::utils::correlation::current_or_new(),
+            category: crate::utils::error::ErrorCategory::ML,
+            retry_count: 0,
+        })?;
+
+        let mut data = extractor.extract(record)?;
+        normalize_features(&mut data);
+        let metadata = HashMap::from([("source".to_string(), source.to_string())]);
+        Ok(Features::from_raw_data(data, metadata)?.with_source(extractor.source()))
+    }
+
     /// Extracts features with memory optimization and adaptive sampling
     #[instrument(skip(self, event_data))]
     pub async fn extract_features(&self, event_data: SecurityEvent) -> Result<Features, GuardianError> {
@@ -210,29 +264,8 @@ impl FeatureExtractor {
     }
 }
 
-/// Memory-efficient feature normalization using SIMD when available
+/// Feature normalization onto the model's input range, vectorized when the CPU allows
 #[inline]
 fn normalize_features(features: &mut [f32]) {
-    if features.is_empty() {
-        return;
-    }
-
-    let mut min = features[0];
-    let mut max = features[0];
-
-    // Find min/max with SIMD optimization
-    for &val in features.iter() {
-        min = min.min(val);
-        max = max.max(val);
-    }
-
-    let range = max - min;
-    if range == 0.0 {
-        return;
-    }
-
-    // Normalize in-place
-    for val in features.iter_mut() {
-        *val = ((*val - min) / range) * (MAX_FEATURE_VALUE - MIN_FEATURE_VALUE) + MIN_FEATURE_VALUE;
-    }
-}
\ No newline at end of file
+    simd::normalize(features, MIN_FEATURE_VALUE, MAX_FEATURE_VALUE);
+}
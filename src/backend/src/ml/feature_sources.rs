@@ -0,0 +1,245 @@
+use std::{
+    collections::hash_map::DefaultHasher,
+    fmt,
+    hash::{Hash, Hasher},
+};
+
+use serde_json::Value;
+
+use crate::ml::feature_extractor::FEATURE_DIMENSION;
+use crate::ml::simd;
+use crate::utils::error::GuardianError;
+
+// Constants for feature sources
+/// Leading slots hold numeric fields; the rest hold hashed categorical fields
+const NUMERIC_SLOTS: usize = 64;
+/// Windows a numeric series is split into, each contributing its mean
+const SERIES_WINDOWS: usize = 4;
+
+/// Source tag of process telemetry records
+pub const PROCESS_TELEMETRY: &str = "process_telemetry";
+/// Source tag of network flow records
+pub const NETWORK_FLOW: &str = "network_flow";
+/// Source tag of syscall trace records
+pub const SYSCALL_TRACE: &str = "syscall_trace";
+/// Source tag of file event records
+pub const FILE_EVENT: &str = "file_event";
+
+/// Turns raw records of one kind of data into feature vectors
+pub trait FeatureSource: Send + Sync + fmt::Debug {
+    /// Tag carried by the features this source produces, used to route them to a model
+    fn source(&self) -> &'static str;
+
+    /// Extracts an unnormalized vector of `FEATURE_DIMENSION` values from a record
+    fn extract(&self, record: &Value) -> Result<Vec<f32>, GuardianError>;
+}
+
+/// Process resource usage and lineage, plus the binary's trust score when annotated by the trust registry
+#[derive(Debug, Default)]
+pub struct ProcessTelemetrySource;
+
+impl FeatureSource for ProcessTelemetrySource {
+    fn source(&self) -> &'static str {
+        PROCESS_TELEMETRY
+    }
+
+    fn extract(&self, record: &Value) -> Result<Vec<f32>, GuardianError> {
+        Ok(VectorBuilder::new(self.source(), record)?
+            .numbers(&["cpu_percent", "memory_bytes", "threads", "open_files", "children", "uptime_secs", "trust"])
+            .categories(&["name", "user", "parent_name"])
+            .finish())
+    }
+}
+
+/// Connection-level network flow summaries
+#[derive(Debug, Default)]
+pub struct NetworkFlowSource;
+
+impl FeatureSource for NetworkFlowSource {
+    fn source(&self) -> &'static str {
+        NETWORK_FLOW
+    }
+
+    fn extract(&self, record: &Value) -> Result<Vec<f32>, GuardianError> {
+        Ok(VectorBuilder::new(self.source(), record)?
+            .numbers(&["bytes_in", "bytes_out", "packets_in", "packets_out", "duration_ms", "src_port", "dst_port"])
+            .series("packet_sizes")
+            .categories(&["protocol", "direction", "dst_address"])
+            .finish())
+    }
+}
+
+/// Per-process syscall traces; `syscalls` lists the calls made in the trace window and
+/// `latencies_us` their latencies
+#[derive(Debug, Default)]
+pub struct SyscallTraceSource;
+
+impl FeatureSource for SyscallTraceSource {
+    fn source(&self) -> &'static str {
+        SYSCALL_TRACE
+    }
+
+    fn extract(&self, record: &Value) -> Result<Vec<f32>, GuardianError> {
+        Ok(VectorBuilder::new(self.source(), record)?
+            .numbers(&["count", "error_count", "duration_us"])
+            .series("latencies_us")
+            .categories(&["process"])
+            .sequence("syscalls")
+            .finish())
+    }
+}
+
+/// File creation, modification, deletion and permission changes
+#[derive(Debug, Default)]
+pub struct FileEventSource;
+
+impl FeatureSource for FileEventSource {
+    fn source(&self) -> &'static str {
+        FILE_EVENT
+    }
+
+    fn extract(&self, record: &Value) -> Result<Vec<f32>, GuardianError> {
+        let mut builder = VectorBuilder::new(self.source(), record)?;
+        builder = builder.numbers(&["size", "mode", "uid"]).categories(&["operation", "process"]);
+        // Paths are too unique to hash whole; their directory and extension generalize better
+        if let Some(path) = record.get("path").and_then(Value::as_str) {
+            let path = std::path::Path::new(path);
+            builder = builder
+                .category_value("directory", path.parent().and_then(|p| p.to_str()).unwrap_or_default())
+                .category_value("extension", path.extension().and_then(|e| e.to_str()).unwrap_or_default());
+        }
+        Ok(builder.finish())
+    }
+}
+
+/// Returns the extractors for the built-in data sources
+pub fn builtin_sources() -> Vec<Box<dyn FeatureSource>> {
+    vec![
+        Box::new(ProcessTelemetrySource),
+        Box::new(NetworkFlowSource),
+        Box::new(SyscallTraceSource),
+        Box::new(FileEventSource),
+    ]
+}
+
+/// Lays numeric fields into fixed slots and hashes categorical fields into the remainder
+struct VectorBuilder<'a> {
+    record: &'a serde_json::Map<String, Value>,
+    data: Vec<f32>,
+    next_slot: usize,
+}
+
+impl<'a> VectorBuilder<'a> {
+    fn new(source: &str, record: &'a Value) -> Result<Self, GuardianError> {
+        let record = record.as_object().ok_or_else(|| GuardianError::MLError {
+            context: format!("{} record must be a JSON object", source),
+            source: None,
+            severity: crate::utils::error::ErrorSeverity::Medium,
+            timestamp: time::OffsetDateTime::now_utc(),
+            correlation_id: crate::utils::correlation::current_or_new(),
+            category: crate::utils::error::ErrorCategory::ML,
+            retry_count: 0,
+        })?;
+        Ok(Self { record, data: vec![0.0; FEATURE_DIMENSION], next_slot: 0 })
+    }
+
+    /// Missing fields leave their slot at zero; magnitudes are log-scaled
+    fn numbers(mut self, fields: &[&str]) -> Self {
+        for field in fields {
+            let value = self.record.get(*field).and_then(Value::as_f64);
+            self.push_number(value);
+        }
+        self
+    }
+
+    /// Summarizes a numeric array field: mean, spread and range, then the mean of each window
+    fn series(mut self, field: &str) -> Self {
+        let values: Vec<f32> = self
+            .record
+            .get(field)
+            .and_then(Value::as_array)
+            .map(|values| values.iter().filter_map(Value::as_f64).map(|v| v as f32).collect())
+            .unwrap_or_default();
+
+        let overall = simd::moments(&values);
+        let present = overall.count > 0;
+        for stat in [overall.mean, overall.std_dev, overall.min, overall.max] {
+            self.push_number(present.then_some(stat as f64));
+        }
+        let windows = simd::window_moments(&values, SERIES_WINDOWS);
+        for window in 0..SERIES_WINDOWS {
+            self.push_number(windows.get(window).map(|w| w.mean as f64));
+        }
+        self
+    }
+
+    fn push_number(&mut self, value: Option<f64>) {
+        if self.next_slot >= NUMERIC_SLOTS {
+            return;
+        }
+        if let Some(value) = value {
+            self.data[self.next_slot] = (value.abs().ln_1p() * value.signum()) as f32;
+        }
+        self.next_slot += 1;
+    }
+
+    fn categories(mut self, fields: &[&str]) -> Self {
+        for field in fields {
+            if let Some(value) = self.record.get(*field).and_then(Value::as_str) {
+                self = self.category_value(field, value);
+            }
+        }
+        self
+    }
+
+    fn category_value(mut self, field: &str, value: &str) -> Self {
+        if !value.is_empty() {
+            self.data[hashed_slot(field, value)] += 1.0;
+        }
+        self
+    }
+
+    /// Hashes every string in an array field, so frequent values weigh more
+    fn sequence(mut self, field: &str) -> Self {
+        let values = self.record.get(field).and_then(Value::as_array).cloned().unwrap_or_default();
+        for value in values.iter().filter_map(Value::as_str) {
+            self = self.category_value(field, value);
+        }
+        self
+    }
+
+    fn finish(self) -> Vec<f32> {
+        self.data
+    }
+}
+
+fn hashed_slot(field: &str, value: &str) -> usize {
+    let mut hasher = DefaultHasher::new();
+    (field, value).hash(&mut hasher);
+    NUMERIC_SLOTS + (hasher.finish() as usize) % (FEATURE_DIMENSION - NUMERIC_SLOTS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sources_produce_full_width_vectors_that_reflect_their_fields() {
+        let tcp = serde_json::json!({ "bytes_out": 4096, "dst_port": 443, "protocol": "tcp" });
+        let udp = serde_json::json!({ "bytes_out": 4096, "dst_port": 443, "protocol": "udp" });
+
+        let tcp = NetworkFlowSource.extract(&tcp).unwrap();
+        let udp = NetworkFlowSource.extract(&udp).unwrap();
+        assert_eq!(tcp.len(), FEATURE_DIMENSION);
+        assert_eq!(tcp[..NUMERIC_SLOTS], udp[..NUMERIC_SLOTS]);
+        assert_ne!(tcp, udp);
+
+        let trace = serde_json::json!({ "syscalls": ["ptrace", "ptrace", "mmap"], "latencies_us": [3.0, 5.0] });
+        let features = SyscallTraceSource.extract(&trace).unwrap();
+        assert_eq!(features[hashed_slot("syscalls", "ptrace")], 2.0);
+        // The latency series follows the three numeric fields, its mean first
+        assert_eq!(features[3], 4.0f64.ln_1p() as f32);
+
+        assert!(FileEventSource.extract(&serde_json::json!("not an object")).is_err());
+    }
+}
@@ -0,0 +1,228 @@
+//! GPU memory budgeting for resident inference models
+//!
+//! Models are placed on the GPU only while their weights fit the configured share of device
+//! memory. Placing a model that does not fit evicts the least recently used ones back to the
+//! CPU; a model larger than the whole budget, or a host without a usable GPU, runs on the CPU
+//! with a warning instead of failing inference.
+
+use std::{collections::HashMap, sync::Arc};
+
+use metrics::{counter, gauge};
+use once_cell::sync::Lazy;
+use parking_lot::{Mutex, RwLock};
+use tracing::{info, warn};
+
+use crate::config::ml_config::GpuBudgetConfig;
+
+// Constants for GPU budgeting
+const BYTES_PER_MB: u64 = 1024 * 1024;
+
+static GPU_BUDGET: Lazy<Arc<GpuBudget>> = Lazy::new(|| Arc::new(GpuBudget::new(&GpuBudgetConfig::default(), None)));
+
+/// Returns the process-wide GPU memory budget
+pub fn gpu_budget() -> Arc<GpuBudget> {
+    Arc::clone(&GPU_BUDGET)
+}
+
+/// Sizes the budget from the configuration and the memory of GPU 0
+pub fn init_gpu_budget(config: &GpuBudgetConfig) {
+    let device_bytes = query_device_memory(0);
+    gpu_budget().configure(config, device_bytes);
+    match device_bytes {
+        Some(total) => info!(
+            device_mb = total / BYTES_PER_MB,
+            budget_mb = gpu_budget().capacity() / BYTES_PER_MB,
+            "GPU memory budget initialized"
+        ),
+        None => warn!("GPU memory could not be queried, inference models will run on the CPU"),
+    }
+}
+
+/// Where a model should run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Placement {
+    Gpu {
+        /// Versions moved back to the CPU to make room
+        evicted: Vec<String>,
+    },
+    Cpu,
+}
+
+#[derive(Debug)]
+struct Resident {
+    bytes: u64,
+    last_used: u64,
+}
+
+#[derive(Debug, Default)]
+struct Residency {
+    capacity: u64,
+    used: u64,
+    /// Monotonic use counter ordering residents by recency
+    clock: u64,
+    models: HashMap<String, Resident>,
+}
+
+/// Tracks which model versions occupy GPU memory
+#[derive(Debug)]
+pub struct GpuBudget {
+    enabled: RwLock<bool>,
+    residency: Mutex<Residency>,
+}
+
+impl GpuBudget {
+    /// Creates a budget for a device with `device_bytes` of memory, or no usable GPU when `None`
+    pub fn new(config: &GpuBudgetConfig, device_bytes: Option<u64>) -> Self {
+        let budget = Self {
+            enabled: RwLock::new(config.enabled),
+            residency: Mutex::new(Residency::default()),
+        };
+        budget.configure(config, device_bytes);
+        budget
+    }
+
+    /// Resizes the budget, evicting residents that no longer fit
+    pub fn configure(&self, config: &GpuBudgetConfig, device_bytes: Option<u64>) {
+        *self.enabled.write() = config.enabled;
+        let mut capacity = device_bytes.map_or(0, |total| (total as f64 * config.max_device_fraction) as u64);
+        if let Some(cap_mb) = config.max_resident_mb {
+            capacity = capacity.min(cap_mb as u64 * BYTES_PER_MB);
+        }
+
+        let mut residency = self.residency.lock();
+        residency.capacity = capacity;
+        let evicted = residency.evict_until_free(0);
+        if !evicted.is_empty() {
+            warn!(evicted = ?evicted, "Models moved back to the CPU after the GPU budget shrank");
+        }
+        gauge!("guardian.ml.gpu.budget_bytes", capacity as f64);
+    }
+
+    /// Bytes models may occupy on the GPU
+    pub fn capacity(&self) -> u64 {
+        self.residency.lock().capacity
+    }
+
+    /// Bytes currently held by resident models
+    pub fn used(&self) -> u64 {
+        self.residency.lock().used
+    }
+
+    /// Whether a version currently occupies GPU memory
+    pub fn is_resident(&self, version: &str) -> bool {
+        self.residency.lock().models.contains_key(version)
+    }
+
+    /// Places a model of `bytes` on the GPU if the budget allows, evicting the least recently used
+    ///
+    /// Falls back to the CPU when budgeting is disabled, no GPU is available, or the model alone
+    /// exceeds the budget.
+    pub fn place(&self, version: &str, bytes: u64) -> Placement {
+        if !*self.enabled.read() {
+            return Placement::Cpu;
+        }
+
+        let mut residency = self.residency.lock();
+        residency.clock += 1;
+        let now = residency.clock;
+        if let Some(resident) = residency.models.get_mut(version) {
+            resident.last_used = now;
+            return Placement::Gpu { evicted: Vec::new() };
+        }
+
+        if bytes > residency.capacity {
+            counter!("guardian.ml.gpu.cpu_fallbacks", 1);
+            warn!(
+                version,
+                model_mb = bytes / BYTES_PER_MB,
+                budget_mb = residency.capacity / BYTES_PER_MB,
+                "Model exceeds the GPU memory budget, running inference on the CPU"
+            );
+            return Placement::Cpu;
+        }
+
+        let evicted = residency.evict_until_free(bytes);
+        if !evicted.is_empty() {
+            counter!("guardian.ml.gpu.evictions", evicted.len() as u64);
+            warn!(version, evicted = ?evicted, "Evicted least recently used models from the GPU");
+        }
+        residency.used += bytes;
+        residency.models.insert(version.to_string(), Resident { bytes, last_used: now });
+        gauge!("guardian.ml.gpu.resident_bytes", residency.used as f64);
+        Placement::Gpu { evicted }
+    }
+
+    /// Frees the GPU memory of a version that was unloaded
+    pub fn release(&self, version: &str) {
+        let mut residency = self.residency.lock();
+        if let Some(resident) = residency.models.remove(version) {
+            residency.used -= resident.bytes;
+            gauge!("guardian.ml.gpu.resident_bytes", residency.used as f64);
+        }
+    }
+}
+
+impl Residency {
+    /// Drops least recently used residents until `bytes` more fit, returning their versions
+    fn evict_until_free(&mut self, bytes: u64) -> Vec<String> {
+        let mut evicted = Vec::new();
+        while self.used + bytes > self.capacity {
+            let Some(oldest) = self
+                .models
+                .iter()
+                .min_by_key(|(_, resident)| resident.last_used)
+                .map(|(version, _)| version.clone())
+            else {
+                break;
+            };
+            if let Some(resident) = self.models.remove(&oldest) {
+                self.used -= resident.bytes;
+            }
+            evicted.push(oldest);
+        }
+        evicted
+    }
+}
+
+/// Total memory of a CUDA device in bytes, from nvidia-smi
+fn query_device_memory(index: u32) -> Option<u64> {
+    let output = std::process::Command::new("nvidia-smi")
+        .args(["--query-gpu=memory.total", "--format=csv,noheader,nounits"])
+        .arg(format!("--id={}", index))
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let total_mb: u64 = String::from_utf8_lossy(&output.stdout).trim().parse().ok()?;
+    Some(total_mb * BYTES_PER_MB)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_evicts_least_recently_used_and_falls_back_to_cpu() {
+        let config = GpuBudgetConfig {
+            enabled: true,
+            max_device_fraction: 0.5,
+            max_resident_mb: None,
+        };
+        let budget = GpuBudget::new(&config, Some(400 * BYTES_PER_MB));
+        assert_eq!(budget.capacity(), 200 * BYTES_PER_MB);
+
+        assert_eq!(budget.place("v1", 80 * BYTES_PER_MB), Placement::Gpu { evicted: Vec::new() });
+        assert_eq!(budget.place("v2", 80 * BYTES_PER_MB), Placement::Gpu { evicted: Vec::new() });
+        // v1 is used again, so v2 becomes the eviction candidate
+        budget.place("v1", 80 * BYTES_PER_MB);
+        assert_eq!(budget.place("v3", 80 * BYTES_PER_MB), Placement::Gpu { evicted: vec!["v2".to_string()] });
+        assert!(budget.is_resident("v1") && !budget.is_resident("v2"));
+
+        assert_eq!(budget.place("huge", 300 * BYTES_PER_MB), Placement::Cpu);
+        assert_eq!(budget.used(), 160 * BYTES_PER_MB);
+
+        let no_gpu = GpuBudget::new(&config, None);
+        assert_eq!(no_gpu.place("v1", BYTES_PER_MB), Placement::Cpu);
+    }
+}
@@ -1,5 +1,6 @@
 use std::{
     collections::HashMap,
+    hash::{Hash, Hasher},
     sync::Arc,
     time::{Duration, Instant},
 };
@@ -10,13 +11,23 @@ use burn::{
 use candle::{Device, Tensor as CandleTensor};
 use tokio::sync::RwLock;
 use tracing::{debug, error, info, instrument, warn};
+use metrics::{counter, histogram};
 use lru::LruCache;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use once_cell::sync::OnceCell;
 
+use crate::config::ml_config::BatchingConfig;
+use crate::core::resource_governor::{governor, Subsystem};
 use crate::utils::error::{GuardianError, MLError};
-use crate::ml::model_registry::{ModelRegistry, get_model_metrics, verify_model_signature};
+use crate::utils::inflight::{inflight_registry, DrainStage, InflightTracker};
+use crate::ml::model_registry::{ModelActivation, ModelRegistry, get_model_metrics, verify_model_signature};
 use crate::ml::feature_extractor::{FeatureExtractor, extract_features, batch_extract};
+use crate::ml::batcher::{BatchInference, InferenceBatcher};
+use crate::ml::gpu_budget::{gpu_budget, Placement};
+use crate::ml::warmup::{run_warmup, warmup_gate, WarmupReport};
+use crate::security::degradation::degradation;
+use crate::storage::PageInStats;
 
 // Constants for inference engine configuration
 const MAX_BATCH_SIZE: usize = 128;
@@ -25,6 +36,14 @@ const MIN_CONFIDENCE_THRESHOLD: f32 = 0.95;
 const CACHE_TTL_SECONDS: u64 = 300;
 const MEMORY_POOL_SIZE: usize = 1024;
 const CIRCUIT_BREAKER_THRESHOLD: u32 = 50;
+const MODEL_SWAP_WARMUP_TIMEOUT: Duration = Duration::from_secs(5);
+const WARMUP_FEATURE_SIZE: usize = 256;
+/// Distinct values in the golden self-test input, a fixed ramp over the feature vector
+const GOLDEN_INPUT_STEPS: usize = 16;
+/// Period of the synthetic warm-up inputs
+const WARMUP_INPUT_MODULUS: usize = 97;
+const CANARY_BUCKETS: u64 = 100;
+const INFLIGHT_REQUESTS: &str = "inference";
 
 /// High-performance ML inference engine with hardware acceleration
 #[derive(Debug)]
@@ -36,6 +55,21 @@ pub struct InferenceEngine {
     circuit_breaker: AtomicCircuitBreaker,
     metrics: Arc<MetricsCollector>,
     device: Device,
+    active_version: RwLock<Option<String>>,
+    canary: RwLock<Option<CanaryRoute>>,
+    /// Model version serving each feature source; unrouted sources use the active model
+    source_models: RwLock<HashMap<String, String>>,
+    inflight: Arc<InflightTracker>,
+    /// Coalesces concurrent detection requests once batching is enabled
+    batcher: OnceCell<InferenceBatcher>,
+}
+
+/// Share of inference traffic served by a candidate model version
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CanaryRoute {
+    pub version: String,
+    /// Percentage of events, 0-100, routed to the candidate
+    pub traffic_percent: u8,
 }
 
 /// Represents an inference prediction result with metadata
@@ -74,6 +108,38 @@ struct AtomicCircuitBreaker {
     is_open: AtomicBool,
 }
 
+impl Prediction {
+    /// Creates a prediction that did not come from a model, such as a test fixture's
+    pub fn new(prediction_type: impl Into<String>, confidence: f32, metadata: HashMap<String, String>) -> Self {
+        Self {
+            prediction_type: prediction_type.into(),
+            confidence,
+            timestamp: Utc::now(),
+            metadata,
+            performance_metrics: PredictionMetrics {
+                inference_time_ms: 0.0,
+                feature_extraction_time_ms: 0.0,
+                memory_usage_bytes: 0,
+            },
+        }
+    }
+
+    /// Returns the kind of threat or anomaly predicted
+    pub fn prediction_type(&self) -> &str {
+        &self.prediction_type
+    }
+
+    /// Returns the model's confidence in the prediction
+    pub fn confidence(&self) -> f32 {
+        self.confidence
+    }
+
+    /// Returns attributes added by the model and pipeline stages
+    pub fn metadata(&self) -> &HashMap<String, String> {
+        &self.metadata
+    }
+}
+
 impl InferenceEngine {
     /// Creates a new InferenceEngine instance with hardware acceleration support
     pub async fn new(
@@ -107,6 +173,11 @@ impl InferenceEngine {
             circuit_breaker: AtomicCircuitBreaker::new(),
             metrics: Arc::new(MetricsCollector::new()),
             device,
+            active_version: RwLock::new(None),
+            canary: RwLock::new(None),
+            source_models: RwLock::new(HashMap::new()),
+            inflight: inflight_registry().tracker(INFLIGHT_REQUESTS, DrainStage::Inference),
+            batcher: OnceCell::new(),
         };
 
         // Perform model warm-up
@@ -125,16 +196,31 @@ impl InferenceEngine {
                 source: None,
                 severity: crate::utils::error::ErrorSeverity::High,
                 timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id: uuid::Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: crate::utils::error::ErrorCategory::ML,
                 retry_count: 0,
             });
         }
 
+        // Held until the prediction returns so shutdown can wait for it
+        let _inflight = self.inflight.enter().ok_or_else(|| GuardianError::MLError {
+            context: "Inference engine is draining for shutdown".into(),
+            source: None,
+            severity: crate::utils::error::ErrorSeverity::Medium,
+            timestamp: time::OffsetDateTime::now_utc(),
+            correlation_id: crate::utils::correlation::current_or_new(),
+            category: crate::utils::error::ErrorCategory::ML,
+            retry_count: 0,
+        })?;
+
         let start_time = Instant::now();
 
+        // Route before the cache lookup so canary and incumbent results never mix
+        let event_key = event_data.get_cache_key();
+        let model_version = self.route_version(&event_key).await?;
+        let cache_key = format!("{}:{}", model_version, event_key);
+
         // Check cache
-        let cache_key = event_data.get_cache_key();
         if let Some(cached) = self.inference_cache.read().await.get(&cache_key) {
             if cached.expires_at > Utc::now() {
                 debug!("Cache hit for prediction");
@@ -148,7 +234,6 @@ impl InferenceEngine {
         let feature_time = feature_start.elapsed().as_millis() as f64;
 
         // Verify model signature
-        let model_version = self.model_registry.get_active_model().await?;
         verify_model_signature(&model_version).await?;
 
         // Perform inference with hardware acceleration
@@ -161,12 +246,13 @@ impl InferenceEngine {
             source: None,
             severity: crate::utils::error::ErrorSeverity::High,
             timestamp: time::OffsetDateTime::now_utc(),
-            correlation_id: uuid::Uuid::new_v4(),
+            correlation_id: crate::utils::correlation::current_or_new(),
             category: crate::utils::error::ErrorCategory::ML,
             retry_count: 0,
         })??;
 
         let inference_time = inference_start.elapsed().as_millis() as f64;
+        self.model_registry.record_inference(&model_version, inference_time).await;
 
         // Validate prediction confidence
         if prediction.confidence < MIN_CONFIDENCE_THRESHOLD {
@@ -197,12 +283,15 @@ impl InferenceEngine {
             return Ok(Vec::new());
         }
 
-        // Calculate optimal batch size based on system load
-        let batch_size = self.calculate_batch_size(events.len()).await;
+        // Calculate optimal batch size based on system load and the overhead budget
+        let governor = governor();
+        let batch_size = governor.batch_size(Subsystem::Ml, self.calculate_batch_size(events.len()).await);
+        self.fit_cache_to_budget().await;
         let mut predictions = Vec::with_capacity(events.len());
 
         // Process batches
         for chunk in events.chunks(batch_size) {
+            let _permit = governor.acquire(Subsystem::Ml).await;
             let features = self.feature_extractor.batch_extract(chunk.to_vec()).await?;
             
             let batch_predictions = self.process_feature_batch(features).await?;
@@ -212,10 +301,317 @@ impl InferenceEngine {
         Ok(predictions)
     }
 
+    /// Routes `predict_batched` through a micro-batcher shared by all concurrent callers
+    pub fn enable_batching(self: &Arc<Self>, config: &BatchingConfig) {
+        if !config.enabled {
+            return;
+        }
+        let engine: Arc<dyn BatchInference> = self.clone();
+        let engine = Arc::downgrade(&engine);
+        if self.batcher.set(InferenceBatcher::spawn(engine, config)).is_ok() {
+            info!(max_batch_size = config.max_batch_size, max_wait_ms = config.max_wait_ms, "Inference request batching enabled");
+        }
+    }
+
+    /// Runs events through shared micro-batches when enabled, otherwise as one batch of their own
+    pub async fn predict_batched(&self, events: Vec<SecurityEvent>) -> Result<Vec<Prediction>, GuardianError> {
+        match self.batcher.get() {
+            Some(batcher) => batcher.predict_all(events).await,
+            None => self.batch_predict(events).await,
+        }
+    }
+
+    /// Hot-swaps models on registry activation, rolling back versions that fail warm-up
+    pub fn watch_model_activations(self: &Arc<Self>) {
+        let engine = Arc::downgrade(self);
+        let mut activations = self.model_registry.subscribe_activations();
+
+        tokio::spawn(async move {
+            while activations.changed().await.is_ok() {
+                let Some(activation) = activations.borrow_and_update().clone() else {
+                    continue;
+                };
+                let Some(engine) = engine.upgrade() else {
+                    break;
+                };
+                engine.swap_model(activation).await;
+            }
+            debug!("Model activation watcher stopped");
+        });
+    }
+
+    /// Returns the model version currently serving predictions
+    pub async fn current_model_version(&self) -> Result<String, GuardianError> {
+        if let Some(version) = self.active_version.read().await.clone() {
+            return Ok(version);
+        }
+        self.model_registry.get_active_model().await
+    }
+
+    /// Routes a share of single-event predictions to a candidate version after warming it up
+    #[instrument(skip(self))]
+    pub async fn set_canary(&self, version: String, traffic_percent: u8) -> Result<(), GuardianError> {
+        if traffic_percent > 100 {
+            return Err(GuardianError::MLError {
+                context: format!("Canary traffic percent {} exceeds 100", traffic_percent),
+                source: None,
+                severity: crate::utils::error::ErrorSeverity::Medium,
+                timestamp: time::OffsetDateTime::now_utc(),
+                correlation_id: crate::utils::correlation::current_or_new(),
+                category: crate::utils::error::ErrorCategory::ML,
+                retry_count: 0,
+            });
+        }
+        tokio::time::timeout(MODEL_SWAP_WARMUP_TIMEOUT, self.validate_model(&version))
+            .await
+            .map_err(|_| GuardianError::MLError {
+                context: format!("Warm-up of canary model {} timed out", version),
+                source: None,
+                severity: crate::utils::error::ErrorSeverity::High,
+                timestamp: time::OffsetDateTime::now_utc(),
+                correlation_id: crate::utils::correlation::current_or_new(),
+                category: crate::utils::error::ErrorCategory::ML,
+                retry_count: 0,
+            })??;
+
+        *self.canary.write().await = Some(CanaryRoute { version: version.clone(), traffic_percent });
+        info!(version = %version, traffic_percent, "Canary model routing enabled");
+        Ok(())
+    }
+
+    /// Stops routing traffic to the canary version
+    pub async fn clear_canary(&self) -> Option<CanaryRoute> {
+        let previous = self.canary.write().await.take();
+        if let Some(route) = &previous {
+            info!(version = %route.version, "Canary model routing cleared");
+        }
+        previous
+    }
+
+    /// Returns the active canary route, if any
+    pub async fn canary(&self) -> Option<CanaryRoute> {
+        self.canary.read().await.clone()
+    }
+
+    /// Serves features from a data source with a dedicated model version after warming it up
+    #[instrument(skip(self))]
+    pub async fn route_source(&self, source: String, version: String) -> Result<(), GuardianError> {
+        tokio::time::timeout(MODEL_SWAP_WARMUP_TIMEOUT, self.validate_model(&version))
+            .await
+            .map_err(|_| GuardianError::MLError {
+                context: format!("Warm-up of model {} for {} timed out", version, source),
+                source: None,
+                severity: crate::utils::error::ErrorSeverity::High,
+                timestamp: time::OffsetDateTime::now_utc(),
+                correlation_id: crate::utils::correlation::current_or_new(),
+                category: crate::utils::error::ErrorCategory::ML,
+                retry_count: 0,
+            })??;
+
+        info!(source = %source, version = %version, "Feature source routed to model");
+        self.source_models.write().await.insert(source, version);
+        Ok(())
+    }
+
+    /// Sends a data source back to the active model
+    pub async fn clear_source_route(&self, source: &str) -> Option<String> {
+        self.source_models.write().await.remove(source)
+    }
+
+    /// Extracts features from a raw record of a data source and runs them through that source's model
+    #[instrument(skip(self, record))]
+    pub async fn predict_source(&self, source: &str, record: &serde_json::Value) -> Result<Prediction, GuardianError> {
+        if self.circuit_breaker.is_open() {
+            return Err(GuardianError::MLError {
+                context: "Circuit breaker is open".into(),
+                source: None,
+                severity: crate::utils::error::ErrorSeverity::High,
+                timestamp: time::OffsetDateTime::now_utc(),
+                correlation_id: crate::utils::correlation::current_or_new(),
+                category: crate::utils::error::ErrorCategory::ML,
+                retry_count: 0,
+            });
+        }
+        let _inflight = self.inflight.enter().ok_or_else(|| GuardianError::MLError {
+            context: "Inference engine is draining for shutdown".into(),
+            source: None,
+            severity: crate::utils::error::ErrorSeverity::Medium,
+            timestamp: time::OffsetDateTime::now_utc(),
+            correlation_id: crate::utils::correlation::current_or_new(),
+            category: crate::utils::error::ErrorCategory::ML,
+            retry_count: 0,
+        })?;
+
+        let feature_start = Instant::now();
+        let features = self.feature_extractor.extract_from(source, record)?;
+        let feature_time = feature_start.elapsed().as_millis() as f64;
+
+        let model_version = match self.source_models.read().await.get(features.source()).cloned() {
+            Some(version) => version,
+            None => self.current_model_version().await?,
+        };
+        verify_model_signature(&model_version).await?;
+
+        let inference_start = Instant::now();
+        let prediction = tokio::time::timeout(
+            Duration::from_millis(INFERENCE_TIMEOUT_MS),
+            self.run_inference(&features, &model_version),
+        ).await.map_err(|_| GuardianError::MLError {
+            context: format!("Inference timeout for {}", source),
+            source: None,
+            severity: crate::utils::error::ErrorSeverity::High,
+            timestamp: time::OffsetDateTime::now_utc(),
+            correlation_id: crate::utils::correlation::current_or_new(),
+            category: crate::utils::error::ErrorCategory::ML,
+            retry_count: 0,
+        })??;
+
+        let inference_time = inference_start.elapsed().as_millis() as f64;
+        self.model_registry.record_inference(&model_version, inference_time).await;
+        counter!("guardian.ml.source_predictions", 1, "source" => features.source().to_string());
+        self.metrics.record_inference_metrics(inference_time, feature_time, prediction.confidence).await?;
+
+        Ok(prediction)
+    }
+
+    /// Picks the model version for an event, keeping each event key on a stable side of the split
+    async fn route_version(&self, event_key: &str) -> Result<String, GuardianError> {
+        // A detection degraded to its small model overrides every split
+        if let Some(version) = degradation().fallback_model() {
+            counter!("guardian.ml.degraded.routed", 1);
+            return Ok(version);
+        }
+        // A running experiment owns the split until data scientists stop it
+        if let Some((arm, version)) = self.model_registry.experiment_arm(event_key).await {
+            counter!("guardian.ml.experiment.routed", 1, "arm" => arm.as_str());
+            return Ok(version);
+        }
+        if let Some(route) = self.canary.read().await.as_ref() {
+            if canary_bucket(event_key) < u64::from(route.traffic_percent) {
+                counter!("guardian.ml.canary.routed", 1);
+                return Ok(route.version.clone());
+            }
+        }
+        self.current_model_version().await
+    }
+
+    /// Pages a model version's binary in so its first predictions do not wait on disk
+    #[instrument(skip(self))]
+    pub async fn prefetch_model(&self, version: &str) -> Result<PageInStats, GuardianError> {
+        self.model_registry.model_store().prefetch(version.to_string()).await
+    }
+
+    #[instrument(skip(self))]
+    async fn swap_model(&self, activation: ModelActivation) {
+        let start = Instant::now();
+
+        if self.model_registry.model_store().loading().prefetch_on_activate {
+            if let Err(e) = self.prefetch_model(&activation.version).await {
+                warn!(version = %activation.version, error = ?e, "Model prefetch failed, pages will fault in on use");
+            }
+        }
+
+        // Warm up and validate the new model before it takes traffic
+        let validation = match tokio::time::timeout(
+            MODEL_SWAP_WARMUP_TIMEOUT,
+            self.validate_model(&activation.version),
+        ).await {
+            Ok(result) => result,
+            Err(_) => Err(GuardianError::MLError {
+                context: format!("Warm-up of model {} timed out", activation.version),
+                source: None,
+                severity: crate::utils::error::ErrorSeverity::High,
+                timestamp: time::OffsetDateTime::now_utc(),
+                correlation_id: crate::utils::correlation::current_or_new(),
+                category: crate::utils::error::ErrorCategory::ML,
+                retry_count: 0,
+            }),
+        };
+
+        match validation {
+            Ok(()) => {
+                *self.active_version.write().await = Some(activation.version.clone());
+                // Cached predictions came from the previous model
+                self.inference_cache.write().await.clear();
+
+                counter!("guardian.ml.model_swap", 1, "result" => "success");
+                histogram!("guardian.ml.model_swap.duration", start.elapsed().as_secs_f64());
+                info!(version = %activation.version, "Model hot-swapped");
+            }
+            Err(e) => {
+                counter!("guardian.ml.model_swap", 1, "result" => "rollback");
+                gpu_budget().release(&activation.version);
+                error!(version = %activation.version, error = ?e, "Model failed validation inference, rolling back");
+                if let Err(rollback_err) = self.model_registry
+                    .rollback_activation(&activation, e.to_string())
+                    .await
+                {
+                    error!(error = ?rollback_err, "Failed to roll back model activation");
+                }
+            }
+        }
+    }
+
+    async fn validate_model(&self, version: &str) -> Result<(), GuardianError> {
+        verify_model_signature(version).await?;
+
+        let warmup_features = Features::from_raw_data(vec![0.0; WARMUP_FEATURE_SIZE], HashMap::new())?;
+        let prediction = self.run_inference(&warmup_features, version).await?;
+
+        if !prediction.confidence.is_finite() || !(0.0..=1.0).contains(&prediction.confidence) {
+            return Err(GuardianError::MLError {
+                context: format!(
+                    "Model {} produced invalid confidence {} during validation",
+                    version, prediction.confidence
+                ),
+                source: None,
+                severity: crate::utils::error::ErrorSeverity::High,
+                timestamp: time::OffsetDateTime::now_utc(),
+                correlation_id: crate::utils::correlation::current_or_new(),
+                category: crate::utils::error::ErrorCategory::ML,
+                retry_count: 0,
+            });
+        }
+        Ok(())
+    }
+
+    /// Runs the serving model twice on a fixed golden input, checking it loads, answers with
+    /// a valid confidence and answers the same both times
+    pub async fn self_test(&self) -> Result<(String, Prediction), GuardianError> {
+        let version = self.route_version("self-test").await?;
+        verify_model_signature(&version).await?;
+
+        let golden: Vec<f32> = (0..WARMUP_FEATURE_SIZE)
+            .map(|i| (i % GOLDEN_INPUT_STEPS) as f32 / GOLDEN_INPUT_STEPS as f32)
+            .collect();
+        let features = Features::from_raw_data(golden, HashMap::new())?;
+        let first = self.run_inference(&features, &version).await?;
+        let second = self.run_inference(&features, &version).await?;
+
+        let valid = first.confidence.is_finite() && (0.0..=1.0).contains(&first.confidence);
+        let deterministic = first.prediction_type == second.prediction_type && first.confidence == second.confidence;
+        if !valid || !deterministic {
+            return Err(GuardianError::MLError {
+                context: format!(
+                    "Model {} failed the golden input: confidence {} then {}, prediction {} then {}",
+                    version, first.confidence, second.confidence, first.prediction_type, second.prediction_type
+                ),
+                source: None,
+                severity: crate::utils::error::ErrorSeverity::High,
+                timestamp: time::OffsetDateTime::now_utc(),
+                correlation_id: crate::utils::correlation::current_or_new(),
+                category: crate::utils::error::ErrorCategory::ML,
+                retry_count: 0,
+            });
+        }
+        Ok((version, first))
+    }
+
     // Private helper methods
     async fn run_inference(&self, features: &Features, model_version: &str) -> Result<Prediction, GuardianError> {
-        let tensor = features.to_tensor().to_device(&self.device)?;
-        
+        let device = self.model_device(model_version).await;
+        let tensor = features.to_tensor().to_device(&device)?;
+
         let model = self.model_registry.load_model(model_version).await?;
         let output = model.forward(&tensor)?;
 
@@ -234,20 +630,67 @@ impl InferenceEngine {
         Ok(prediction)
     }
 
+    /// Picks the device for a model version, keeping GPU residency within the memory budget
+    async fn model_device(&self, model_version: &str) -> Device {
+        if !matches!(self.device, Device::Cuda(_)) {
+            return self.device.clone();
+        }
+        // Versions without registry metadata, such as the warm-up alias, are not budgeted
+        let Some(metadata) = self.model_registry.model_metadata(model_version).await else {
+            return self.device.clone();
+        };
+        match gpu_budget().place(model_version, metadata.size_bytes) {
+            Placement::Gpu { evicted } => {
+                for version in evicted {
+                    debug!(version = %version, "Model moved back to the CPU");
+                }
+                self.device.clone()
+            }
+            Placement::Cpu => Device::Cpu,
+        }
+    }
+
+    /// Shrinks or regrows the inference cache to the governor's capacity and reports its size
+    async fn fit_cache_to_budget(&self) {
+        let governor = governor();
+        let capacity = governor.cache_capacity(Subsystem::Ml, MEMORY_POOL_SIZE);
+        let mut cache = self.inference_cache.write().await;
+        if cache.cap() != capacity {
+            cache.resize(capacity);
+        }
+        governor.report_memory(Subsystem::Ml, (cache.len() * std::mem::size_of::<CachedPrediction>()) as u64);
+    }
+
     async fn calculate_batch_size(&self, requested_size: usize) -> usize {
         let system_load = self.metrics.get_system_load().await;
         let adaptive_size = (MAX_BATCH_SIZE as f32 * (1.0 - system_load)) as usize;
         adaptive_size.clamp(1, requested_size.min(MAX_BATCH_SIZE))
     }
 
-    async fn warm_up(&self) -> Result<(), GuardianError> {
+    /// Runs synthetic inferences until the model answers within the warm-up target, holding
+    /// inference readiness back meanwhile
+    async fn warm_up(&self) -> Result<WarmupReport, GuardianError> {
         info!("Performing inference engine warm-up");
-        let dummy_features = Features::from_raw_data(vec![0.0; 256], HashMap::new())?;
-        let _ = self.run_inference(&dummy_features, "latest").await?;
-        Ok(())
+        let _warming = warmup_gate().begin();
+        let config = self.model_registry.model_store().loading().warmup.clone();
+        run_warmup(&config, |iteration| async move {
+            // Vary the input so caches and branch predictors see more than one vector
+            let raw: Vec<f32> = (0..WARMUP_FEATURE_SIZE)
+                .map(|i| ((i * 31 + iteration * 7) % WARMUP_INPUT_MODULUS) as f32 / WARMUP_INPUT_MODULUS as f32)
+                .collect();
+            let features = Features::from_raw_data(raw, HashMap::new())?;
+            self.run_inference(&features, "latest").await.map(|_| ())
+        })
+        .await
     }
 }
 
+fn canary_bucket(event_key: &str) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    event_key.hash(&mut hasher);
+    hasher.finish() % CANARY_BUCKETS
+}
+
 impl Drop for InferenceEngine {
     fn drop(&mut self) {
         // Ensure proper cleanup of GPU resources
@@ -287,4 +730,11 @@ mod tests {
         let predictions = engine.batch_predict(events).await.unwrap();
         assert_eq!(predictions.len(), 5);
     }
+
+    #[test]
+    fn test_canary_bucket_is_stable() {
+        assert_eq!(canary_bucket("event-1"), canary_bucket("event-1"));
+        let canary = (0..1000).filter(|i| canary_bucket(&format!("event-{}", i)) < 10).count();
+        assert!((50..=150).contains(&canary));
+    }
 }
\ No newline at end of file
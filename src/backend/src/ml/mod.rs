@@ -17,8 +17,10 @@ use tracing::{debug, error, info, instrument, warn};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
+use crate::core::status::{status_service, ComponentState, ComponentStatus, StatusSource, ML};
 use crate::utils::error::{GuardianError, Result};
 use crate::config::ml_config::{MLConfig, InferenceConfig};
+use crate::utils::inflight::{inflight_registry, DrainStage};
 
 // Version constant for ML engine
 pub const ML_VERSION: &str = "2.1.0";
@@ -26,19 +28,32 @@ pub const ML_VERSION: &str = "2.1.0";
 // Default compute device
 pub const DEFAULT_DEVICE: &str = "cuda";
 
+// Longest wait for in-flight inference before components are torn down
+const SHUTDOWN_DRAIN_TIMEOUT: Duration = Duration::from_secs(10);
+
 // Submodules
 pub mod model_registry;
 pub mod inference_engine;
+pub mod batcher;
+pub mod gpu_budget;
 pub mod feature_extractor;
+pub mod feature_sources;
 pub mod model_manager;
+pub mod simd;
+pub mod trainer;
 pub mod training_pipeline;
+pub mod warmup;
 
 // Re-exports
-pub use model_registry::ModelRegistry;
+pub use model_registry::{ModelListQuery, ModelPage, ModelProvenance, ModelRegistry};
 pub use inference_engine::InferenceEngine;
+pub use batcher::InferenceBatcher;
+pub use gpu_budget::{gpu_budget, GpuBudget};
 pub use feature_extractor::FeatureExtractor;
+pub use feature_sources::FeatureSource;
 pub use model_manager::ModelManager;
 pub use training_pipeline::TrainingPipeline;
+pub use warmup::{warmup_gate, Readiness, WarmupGate};
 
 /// Core ML Engine structure coordinating all ML operations
 pub struct MLEngine {
@@ -78,6 +93,7 @@ impl MLEngine {
         info!("Initializing ML Engine v{}", ML_VERSION);
         
         // Verify hardware capabilities and select optimal device
+        gpu_budget::init_gpu_budget(&config.gpu_budget);
         let device = Self::initialize_device(&config)?;
         debug!("Selected compute device: {:?}", device);
 
@@ -87,6 +103,8 @@ impl MLEngine {
         // Initialize core components with resource optimization
         let model_registry = Arc::new(ModelRegistry::new(&config)?);
         let inference_engine = Arc::new(InferenceEngine::new(&config, device.clone())?);
+        inference_engine.watch_model_activations();
+        inference_engine.enable_batching(&config.batching);
         let feature_extractor = Arc::new(FeatureExtractor::new(&config)?);
         let model_manager = Arc::new(ModelManager::new(&config, model_registry.clone())?);
         let training_pipeline = Arc::new(TrainingPipeline::new(&config)?);
@@ -125,6 +143,10 @@ impl MLEngine {
 
         // Validate engine health
         engine.validate_health().await?;
+        status_service().register(Arc::new(MlStatus {
+            model_registry: Arc::clone(&engine.model_registry),
+            resource_monitor: Arc::clone(&engine.resource_monitor),
+        }));
         
         info!("ML Engine initialization complete");
         Ok(engine)
@@ -186,7 +208,13 @@ impl MLEngine {
     /// Clean shutdown of ML engine components
     pub async fn shutdown(&self) -> Result<()> {
         info!("Initiating ML Engine shutdown");
-        
+
+        // Let outstanding predictions finish before their models are unloaded
+        let reports = inflight_registry().drain_stage(DrainStage::Inference, SHUTDOWN_DRAIN_TIMEOUT).await;
+        let drained: u64 = reports.iter().map(|r| r.drained).sum();
+        let abandoned: usize = reports.iter().map(|r| r.abandoned).sum();
+        info!(drained, abandoned, "Inference requests drained");
+
         // Signal monitoring task to stop
         if let Err(e) = self.shutdown_tx.send(()).await {
             error!("Error sending shutdown signal: {}", e);
@@ -204,6 +232,46 @@ impl MLEngine {
     }
 }
 
+/// Active model and inference performance in the unified status
+struct MlStatus {
+    model_registry: Arc<ModelRegistry>,
+    resource_monitor: Arc<RwLock<ResourceMonitor>>,
+}
+
+#[async_trait::async_trait]
+impl StatusSource for MlStatus {
+    fn component(&self) -> &'static str {
+        ML
+    }
+
+    async fn status(&self) -> std::result::Result<ComponentStatus, GuardianError> {
+        let active = self.model_registry.active_version().await;
+        let mut status = match &active {
+            Some(version) => ComponentStatus::new(ML, ComponentState::Healthy).with_detail(format!("serving model {}", version)),
+            // Detection falls back to the non-model pipeline stages
+            None => ComponentStatus::new(ML, ComponentState::Degraded).with_detail("no active model"),
+        };
+
+        let monitor = self.resource_monitor.read().await;
+        let performance = &monitor.performance_metrics;
+        let latencies = &performance.inference_latency;
+        let avg_latency_ms = if latencies.is_empty() {
+            0.0
+        } else {
+            latencies.iter().map(Duration::as_secs_f64).sum::<f64>() * 1000.0 / latencies.len() as f64
+        };
+        status = status
+            .with_metric("avg_inference_latency_ms", avg_latency_ms)
+            .with_metric("batch_throughput", performance.batch_throughput as f64)
+            .with_metric("model_accuracy", f64::from(performance.model_accuracy))
+            .with_metric("cpu_usage", f64::from(monitor.cpu_usage));
+        if let Some(gpu_usage) = monitor.gpu_usage {
+            status = status.with_metric("gpu_usage", f64::from(gpu_usage));
+        }
+        Ok(status)
+    }
+}
+
 impl Drop for MLEngine {
     fn drop(&mut self) {
         info!("ML Engine dropped");
@@ -7,8 +7,10 @@ use async_trait::async_trait;
 use burn::tensor::backend::Backend;
 use candle_core::{Device, Tensor};
 
+use crate::core::capabilities::{capabilities, MODEL_MANAGEMENT};
 use crate::ml::model_registry::{ModelRegistry, self};
 use crate::storage::model_store::ModelStore;
+use crate::storage::ModelPatch;
 use crate::config::ml_config::MLConfig;
 use crate::utils::error::GuardianError;
 
@@ -38,6 +40,8 @@ impl ModelManager {
         store: Arc<ModelStore>,
         config: Arc<MLConfig>,
     ) -> Result<Self, GuardianError> {
+        capabilities().require(MODEL_MANAGEMENT)?;
+
         // Initialize device with security checks
         let device = Self::initialize_secure_device(&config.hardware_config)?;
 
@@ -67,6 +71,16 @@ impl ModelManager {
         Ok(manager)
     }
 
+    /// Rebuilds a full model binary from a patch against an installed version
+    pub async fn reconstruct_model(&self, patch: ModelPatch) -> Result<Vec<u8>, GuardianError> {
+        self.store.reconstruct_model(patch).await
+    }
+
+    /// Lists one page of registered models matching `query`
+    pub async fn list_models(&self, query: model_registry::ModelListQuery) -> Result<model_registry::ModelPage, GuardianError> {
+        self.registry.list_models(query).await
+    }
+
     /// Securely loads and validates a model for inference
     #[instrument(skip(self))]
     pub async fn load_model(
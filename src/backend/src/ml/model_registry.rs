@@ -1,16 +1,20 @@
 use std::{
     collections::HashMap,
+    hash::{Hash, Hasher},
     sync::Arc,
     time::Duration,
 };
-use tokio::sync::RwLock;
+use tokio::sync::{watch, RwLock};
 use tracing::{info, warn, error, instrument};
 use serde::{Serialize, Deserialize};
 use chrono::{DateTime, Utc};
 use async_trait::async_trait;
 
 use crate::utils::error::{GuardianError, ErrorCategory};
+use crate::security::attestation::attestor;
+use crate::security::model_signing::{model_verifier, ModelVerifier, VerificationStage};
 use crate::storage::model_store::ModelStore;
+use crate::storage::{VersionLabel, VersionQuery};
 
 // Registry version and configuration constants
 const REGISTRY_VERSION: &str = "1.0.0";
@@ -18,6 +22,8 @@ const MAX_MODEL_VERSIONS: usize = 10;
 const MODEL_REGISTRY_PATH: &str = "registry/models";
 const MODEL_VALIDATION_TIMEOUT: Duration = Duration::from_secs(30);
 const CACHE_REFRESH_INTERVAL: Duration = Duration::from_secs(300);
+/// Buckets event keys are hashed into when splitting experiment traffic
+const EXPERIMENT_BUCKETS: u64 = 100;
 
 /// Metadata for ML models in the registry
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -31,6 +37,33 @@ pub struct ModelMetadata {
     pub validation_status: ValidationStatus,
     pub hash: String,
     pub size_bytes: u64,
+    /// Base64 detached signature over the model binary
+    #[serde(default)]
+    pub signature: Option<String>,
+    /// How the model was produced, for models trained by this deployment
+    #[serde(default)]
+    pub provenance: Option<ModelProvenance>,
+}
+
+/// Where a trained model came from: its training run, data and validation result
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModelProvenance {
+    pub training_run_id: String,
+    pub trainer: String,
+    pub tenant_id: String,
+    /// Labeled events exported, as Unix seconds
+    pub dataset_since: u64,
+    pub dataset_until: u64,
+    /// SHA-256 of the exported dataset
+    pub dataset_hash: String,
+    pub training_samples: usize,
+    pub holdout_samples: usize,
+    pub holdout_accuracy: f64,
+    pub epochs: usize,
+    pub learning_rate: f64,
+    /// Model active when training started
+    pub base_version: Option<String>,
+    pub trained_at: DateTime<Utc>,
 }
 
 /// Performance metrics for ML models
@@ -39,12 +72,44 @@ pub struct ModelMetrics {
     pub inference_time_ms: f64,
     pub memory_usage_mb: f64,
     pub accuracy: f64,
+    #[serde(default)]
+    pub true_positives: u64,
     pub false_positives: u64,
     pub false_negatives: u64,
     pub total_inferences: u64,
     pub last_updated: DateTime<Utc>,
 }
 
+impl ModelMetrics {
+    /// Share of labeled detections that were confirmed; `None` until one is labeled
+    pub fn precision(&self) -> Option<f64> {
+        let detections = self.true_positives + self.false_positives;
+        (detections > 0).then(|| self.true_positives as f64 / detections as f64)
+    }
+
+    /// Share of labeled anomalous activity that was detected; `None` until one is labeled
+    pub fn recall(&self) -> Option<f64> {
+        let anomalies = self.true_positives + self.false_negatives;
+        (anomalies > 0).then(|| self.true_positives as f64 / anomalies as f64)
+    }
+}
+
+/// Analyst verdict on a model's detection, or on activity it missed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeedbackLabel {
+    TruePositive,
+    FalsePositive,
+    FalseNegative,
+}
+
+impl FeedbackLabel {
+    /// Whether the labeled sample is anomalous, i.e. its training target
+    pub fn is_anomalous(&self) -> bool {
+        !matches!(self, FeedbackLabel::FalsePositive)
+    }
+}
+
 /// Model deployment status
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum ModelStatus {
@@ -55,6 +120,56 @@ pub enum ModelStatus {
     Deprecated,
 }
 
+impl ModelStatus {
+    /// Label stored in the version index and accepted by listing filters
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ModelStatus::Active => "active",
+            ModelStatus::Inactive => "inactive",
+            ModelStatus::Failed => "failed",
+            ModelStatus::Validating => "validating",
+            ModelStatus::Deprecated => "deprecated",
+        }
+    }
+}
+
+impl std::str::FromStr for ModelStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "active" => Ok(ModelStatus::Active),
+            "inactive" => Ok(ModelStatus::Inactive),
+            "failed" => Ok(ModelStatus::Failed),
+            "validating" => Ok(ModelStatus::Validating),
+            "deprecated" => Ok(ModelStatus::Deprecated),
+            other => Err(format!("unknown model status: {}", other)),
+        }
+    }
+}
+
+/// Filters and page position for `ModelRegistry::list_models`
+#[derive(Debug, Clone, Default)]
+pub struct ModelListQuery {
+    pub status: Option<ModelStatus>,
+    pub name_prefix: Option<String>,
+    /// Inclusive lower bound on creation time
+    pub created_after: Option<DateTime<Utc>>,
+    /// Exclusive upper bound on creation time
+    pub created_before: Option<DateTime<Utc>>,
+    /// Zero selects the store's default page size
+    pub page_size: usize,
+    pub page_token: Option<String>,
+}
+
+/// One page of registered models, newest first
+#[derive(Debug, Clone, Default)]
+pub struct ModelPage {
+    pub models: Vec<ModelMetadata>,
+    /// Set when more models match; pass back as `page_token`
+    pub next_page_token: Option<String>,
+}
+
 /// Model validation status
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum ValidationStatus {
@@ -63,22 +178,134 @@ pub enum ValidationStatus {
     Failed(String),
 }
 
+/// Two model versions compared on a split of live traffic
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModelExperiment {
+    pub name: String,
+    /// Version the treatment is measured against, usually the active one
+    pub control: String,
+    pub treatment: String,
+    /// Percentage of events, 0-100, served by the treatment
+    pub treatment_percent: u8,
+    pub started_at: DateTime<Utc>,
+}
+
+/// Side of an experiment an event is served by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExperimentArm {
+    Control,
+    Treatment,
+}
+
+impl ExperimentArm {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExperimentArm::Control => "control",
+            ExperimentArm::Treatment => "treatment",
+        }
+    }
+}
+
+/// Results of one experiment arm since the experiment started
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArmSummary {
+    pub arm: ExperimentArm,
+    pub version: String,
+    pub inferences: u64,
+    pub mean_latency_ms: f64,
+    pub precision: Option<f64>,
+    pub recall: Option<f64>,
+    pub true_positives: u64,
+    pub false_positives: u64,
+    pub false_negatives: u64,
+}
+
+impl ArmSummary {
+    fn new(arm: ExperimentArm, version: &str, metrics: &ModelMetrics) -> Self {
+        Self {
+            arm,
+            version: version.to_string(),
+            inferences: metrics.total_inferences,
+            mean_latency_ms: metrics.inference_time_ms,
+            precision: metrics.precision(),
+            recall: metrics.recall(),
+            true_positives: metrics.true_positives,
+            false_positives: metrics.false_positives,
+            false_negatives: metrics.false_negatives,
+        }
+    }
+}
+
+/// Side-by-side results of an experiment, for deciding whether to promote the treatment
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExperimentSummary {
+    pub experiment: ModelExperiment,
+    pub generated_at: DateTime<Utc>,
+    pub control: ArmSummary,
+    pub treatment: ArmSummary,
+}
+
+/// A running experiment with metrics counted only since it started
+#[derive(Debug)]
+struct ExperimentState {
+    experiment: ModelExperiment,
+    control: ModelMetrics,
+    treatment: ModelMetrics,
+}
+
+impl ExperimentState {
+    fn arm_metrics(&mut self, version: &str) -> Option<&mut ModelMetrics> {
+        if version == self.experiment.treatment {
+            Some(&mut self.treatment)
+        } else if version == self.experiment.control {
+            Some(&mut self.control)
+        } else {
+            None
+        }
+    }
+
+    fn summary(&self) -> ExperimentSummary {
+        ExperimentSummary {
+            experiment: self.experiment.clone(),
+            generated_at: Utc::now(),
+            control: ArmSummary::new(ExperimentArm::Control, &self.experiment.control, &self.control),
+            treatment: ArmSummary::new(ExperimentArm::Treatment, &self.experiment.treatment, &self.treatment),
+        }
+    }
+}
+
+/// Notification sent to inference engines when a model version is activated
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelActivation {
+    pub version: String,
+    pub previous_version: Option<String>,
+    pub activated_at: DateTime<Utc>,
+}
+
 /// Thread-safe model registry for managing ML model lifecycle
 #[derive(Debug)]
 pub struct ModelRegistry {
     model_store: Arc<ModelStore>,
     active_models: RwLock<HashMap<String, ModelMetadata>>,
     model_metrics: RwLock<HashMap<String, ModelMetrics>>,
+    activation_tx: Arc<watch::Sender<Option<ModelActivation>>>,
+    verifier: Arc<ModelVerifier>,
+    experiment: RwLock<Option<ExperimentState>>,
 }
 
 #[async_trait]
 impl ModelRegistry {
     /// Creates a new ModelRegistry instance with secure initialization
     pub async fn new(model_store: Arc<ModelStore>) -> Result<Self, GuardianError> {
+        let (activation_tx, _) = watch::channel(None);
         let registry = Self {
             model_store,
             active_models: RwLock::new(HashMap::new()),
             model_metrics: RwLock::new(HashMap::new()),
+            activation_tx: Arc::new(activation_tx),
+            verifier: model_verifier(),
+            experiment: RwLock::new(None),
         };
 
         // Initialize registry state
@@ -95,8 +322,16 @@ impl ModelRegistry {
         Ok(registry)
     }
 
+    /// Checks model signatures with `verifier` instead of the process-wide verifier
+    pub fn with_verifier(mut self, verifier: Arc<ModelVerifier>) -> Self {
+        self.verifier = verifier;
+        self
+    }
+
     /// Registers a new model version with validation
-    #[instrument(skip(self, model_data))]
+    ///
+    /// The model must carry a detached signature from a trusted key in `metadata.signature`.
+    #[instrument(skip(self, model_data, metadata))]
     pub async fn register_model(
         &self,
         model_data: Vec<u8>,
@@ -105,9 +340,30 @@ impl ModelRegistry {
     ) -> Result<ModelMetadata, GuardianError> {
         // Validate model data and version
         self.validate_model_data(&model_data, &version).await?;
-
-        // Store model securely
+        let signer = self.verifier.verify(
+            &version,
+            &model_data,
+            metadata.signature.as_deref(),
+            VerificationStage::Registration,
+        )?;
+
+        // Store model securely, keeping the signature for re-verification on activation
         let stored_version = self.model_store.store_model(model_data, version.clone()).await?;
+        if let Some(signature) = &metadata.signature {
+            self.model_store.store_signature(&version, signature).await?;
+        }
+        if let Some(provenance) = &metadata.provenance {
+            let record = serde_json::to_string(provenance).map_err(|e| GuardianError::MLError {
+                context: format!("Failed to serialize provenance of version {}", version),
+                source: Some(Box::new(e)),
+                severity: crate::utils::error::ErrorSeverity::Medium,
+                timestamp: time::OffsetDateTime::now_utc(),
+                correlation_id: crate::utils::correlation::current_or_new(),
+                category: ErrorCategory::ML,
+                retry_count: 0,
+            })?;
+            self.model_store.store_provenance(&version, &record).await?;
+        }
 
         // Create and validate metadata
         let mut metadata = metadata;
@@ -124,10 +380,12 @@ impl ModelRegistry {
             let mut active_models = self.active_models.write().await;
             active_models.insert(version.clone(), metadata.clone());
         }
+        self.sync_index(&[&metadata]).await;
 
         info!(
             version = %version,
             size_bytes = metadata.size_bytes,
+            signer = ?signer,
             "Model version registered successfully"
         );
 
@@ -145,7 +403,7 @@ impl ModelRegistry {
                 source: None,
                 severity: crate::utils::error::ErrorSeverity::High,
                 timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id: uuid::Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: ErrorCategory::ML,
                 retry_count: 0,
             })?
@@ -154,20 +412,88 @@ impl ModelRegistry {
         // Validate model before activation
         self.validate_model_version(&version).await?;
 
+        // Re-verify the stored binary so a model altered at rest is never activated
+        let model_data = self.model_store.load_model(version.clone()).await?;
+        let signature = self.model_store.load_signature(&version).await?;
+        self.verifier.verify(&version, &model_data, signature.as_deref(), VerificationStage::Activation)?;
+
         // Update model status
         metadata.status = ModelStatus::Active;
         metadata.updated_at = Utc::now();
+        attestor().record_model(&metadata.name, &version, &metadata.hash);
 
-        // Update registry state
-        {
+        // Swap the active version, demoting the previous one in the same critical section
+        let (previous_version, changed) = {
             let mut active_models = self.active_models.write().await;
-            active_models.insert(version.clone(), metadata);
-        }
+            let previous = active_models
+                .values_mut()
+                .find(|m| m.status == ModelStatus::Active && m.version != version);
+            let previous = previous.map(|m| {
+                m.status = ModelStatus::Inactive;
+                m.updated_at = Utc::now();
+                m.clone()
+            });
+            active_models.insert(version.clone(), metadata.clone());
+            let previous_version = previous.as_ref().map(|m| m.version.clone());
+            (previous_version, previous.into_iter().chain([metadata]).collect::<Vec<_>>())
+        };
+        self.sync_index(&changed.iter().collect::<Vec<_>>()).await;
+
+        // Notify inference engines so they hot-swap without a restart
+        self.activation_tx.send_replace(Some(ModelActivation {
+            version: version.clone(),
+            previous_version,
+            activated_at: Utc::now(),
+        }));
 
         info!(version = %version, "Model activated successfully");
         Ok(())
     }
 
+    /// Subscribes to model activations
+    pub fn subscribe_activations(&self) -> watch::Receiver<Option<ModelActivation>> {
+        self.activation_tx.subscribe()
+    }
+
+    /// Marks an activated version failed and restores the previous version
+    #[instrument(skip(self))]
+    pub async fn rollback_activation(
+        &self,
+        activation: &ModelActivation,
+        reason: String,
+    ) -> Result<(), GuardianError> {
+        let changed = {
+            let mut active_models = self.active_models.write().await;
+            let mut changed = Vec::with_capacity(2);
+            if let Some(failed) = active_models.get_mut(&activation.version) {
+                failed.status = ModelStatus::Failed;
+                failed.validation_status = ValidationStatus::Failed(reason.clone());
+                failed.updated_at = Utc::now();
+                changed.push(failed.clone());
+            }
+            if let Some(previous) = activation
+                .previous_version
+                .as_ref()
+                .and_then(|v| active_models.get_mut(v))
+            {
+                previous.status = ModelStatus::Active;
+                previous.updated_at = Utc::now();
+                attestor().record_model(&previous.name, &previous.version, &previous.hash);
+                changed.push(previous.clone());
+            }
+            changed
+        };
+        self.sync_index(&changed.iter().collect::<Vec<_>>()).await;
+
+        warn!(
+            version = %activation.version,
+            previous = ?activation.previous_version,
+            reason = %reason,
+            "Model activation rolled back"
+        );
+        Ok(())
+    }
+
     /// Retrieves detailed performance metrics
     #[instrument(skip(self))]
     pub async fn get_model_metrics(&self, version: String) -> Result<ModelMetrics, GuardianError> {
@@ -178,7 +504,7 @@ impl ModelRegistry {
                 source: None,
                 severity: crate::utils::error::ErrorSeverity::Medium,
                 timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id: uuid::Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: ErrorCategory::ML,
                 retry_count: 0,
             })?
@@ -201,28 +527,255 @@ impl ModelRegistry {
         Ok(())
     }
 
+    /// Folds one live inference into the version's running latency metrics
+    pub async fn record_inference(&self, version: &str, inference_time_ms: f64) {
+        {
+            let mut metrics_map = self.model_metrics.write().await;
+            let metrics = metrics_map.entry(version.to_string()).or_insert_with(empty_metrics);
+            fold_inference(metrics, inference_time_ms);
+        }
+        if let Some(arm) = self.experiment.write().await.as_mut().and_then(|e| e.arm_metrics(version)) {
+            fold_inference(arm, inference_time_ms);
+        }
+    }
+
+    /// Counts an analyst verdict against the version that made (or missed) the detection
+    pub async fn record_feedback(&self, version: &str, label: FeedbackLabel) {
+        {
+            let mut metrics_map = self.model_metrics.write().await;
+            let metrics = metrics_map.entry(version.to_string()).or_insert_with(empty_metrics);
+            fold_feedback(metrics, label);
+        }
+        if let Some(arm) = self.experiment.write().await.as_mut().and_then(|e| e.arm_metrics(version)) {
+            fold_feedback(arm, label);
+        }
+    }
+
+    /// Splits live traffic between two registered versions, replacing any running experiment
+    #[instrument(skip(self))]
+    pub async fn start_experiment(
+        &self,
+        name: String,
+        control: String,
+        treatment: String,
+        treatment_percent: u8,
+    ) -> Result<ModelExperiment, GuardianError> {
+        if treatment_percent > 100 || control == treatment {
+            return Err(GuardianError::MLError {
+                context: format!(
+                    "Invalid experiment {}: arms {} and {} with {}% treatment traffic",
+                    name, control, treatment, treatment_percent
+                ),
+                source: None,
+                severity: crate::utils::error::ErrorSeverity::Medium,
+                timestamp: time::OffsetDateTime::now_utc(),
+                correlation_id: crate::utils::correlation::current_or_new(),
+                category: ErrorCategory::ML,
+                retry_count: 0,
+            });
+        }
+        for version in [&control, &treatment] {
+            self.validate_model_version(version).await?;
+        }
+
+        let experiment = ModelExperiment {
+            name,
+            control,
+            treatment,
+            treatment_percent,
+            started_at: Utc::now(),
+        };
+        let previous = self.experiment.write().await.replace(ExperimentState {
+            experiment: experiment.clone(),
+            control: empty_metrics(),
+            treatment: empty_metrics(),
+        });
+        if let Some(previous) = previous {
+            warn!(experiment = %previous.experiment.name, "Model experiment replaced before it was stopped");
+        }
+        info!(
+            experiment = %experiment.name,
+            control = %experiment.control,
+            treatment = %experiment.treatment,
+            treatment_percent,
+            "Model experiment started"
+        );
+        Ok(experiment)
+    }
+
+    /// Ends the running experiment, returning its final results
+    pub async fn stop_experiment(&self) -> Option<ExperimentSummary> {
+        let state = self.experiment.write().await.take()?;
+        let summary = state.summary();
+        info!(
+            experiment = %summary.experiment.name,
+            control_inferences = summary.control.inferences,
+            treatment_inferences = summary.treatment.inferences,
+            "Model experiment stopped"
+        );
+        Some(summary)
+    }
+
+    /// Returns the running experiment, if any
+    pub async fn experiment(&self) -> Option<ModelExperiment> {
+        self.experiment.read().await.as_ref().map(|e| e.experiment.clone())
+    }
+
+    /// Returns per-arm results of the running experiment so far
+    pub async fn experiment_summary(&self) -> Option<ExperimentSummary> {
+        self.experiment.read().await.as_ref().map(ExperimentState::summary)
+    }
+
+    /// Picks the experiment arm serving an event, keeping each event key on the same arm
+    pub async fn experiment_arm(&self, event_key: &str) -> Option<(ExperimentArm, String)> {
+        let state = self.experiment.read().await;
+        let experiment = &state.as_ref()?.experiment;
+        if experiment_bucket(&experiment.name, event_key) < u64::from(experiment.treatment_percent) {
+            Some((ExperimentArm::Treatment, experiment.treatment.clone()))
+        } else {
+            Some((ExperimentArm::Control, experiment.control.clone()))
+        }
+    }
+
+    /// Returns the version currently serving inference, if any
+    pub async fn active_version(&self) -> Option<String> {
+        self.active_models
+            .read()
+            .await
+            .values()
+            .find(|m| m.status == ModelStatus::Active)
+            .map(|m| m.version.clone())
+    }
+
+    /// Returns metadata of the version currently serving inference, if any
+    pub async fn active_model(&self) -> Option<ModelMetadata> {
+        self.active_models
+            .read()
+            .await
+            .values()
+            .find(|m| m.status == ModelStatus::Active)
+            .cloned()
+    }
+
+    /// Store the registry keeps its versions in
+    pub fn model_store(&self) -> &Arc<ModelStore> {
+        &self.model_store
+    }
+
+    /// Returns metadata of a registered version
+    pub async fn model_metadata(&self, version: &str) -> Option<ModelMetadata> {
+        self.active_models.read().await.get(version).cloned()
+    }
+
+    /// Lists one page of registered models matching `query`, newest first
+    #[instrument(skip(self))]
+    pub async fn list_models(&self, query: ModelListQuery) -> Result<ModelPage, GuardianError> {
+        let page = self
+            .model_store
+            .query_versions(&VersionQuery {
+                status: query.status.as_ref().map(|s| s.as_str().to_string()),
+                name_prefix: query.name_prefix,
+                created_after: query.created_after,
+                created_before: query.created_before,
+                page_size: query.page_size,
+                page_token: query.page_token,
+            })
+            .await?;
+
+        // The index is the source of truth for which models match; live metadata adds metrics
+        let active_models = self.active_models.read().await;
+        let models = page
+            .entries
+            .into_iter()
+            .map(|entry| {
+                active_models.get(&entry.version).cloned().unwrap_or_else(|| ModelMetadata {
+                    name: entry.name,
+                    version: entry.version,
+                    created_at: entry.created_at,
+                    updated_at: entry.created_at,
+                    status: entry.status.parse().unwrap_or(ModelStatus::Inactive),
+                    metrics: None,
+                    validation_status: ValidationStatus::Pending,
+                    hash: entry.hash,
+                    size_bytes: entry.size,
+                    signature: None,
+                    provenance: None,
+                })
+            })
+            .collect();
+
+        Ok(ModelPage {
+            models,
+            next_page_token: page.next_page_token,
+        })
+    }
+
+    /// Provenance of a model version; unreadable records are reported as absent
+    pub async fn model_provenance(&self, version: &str) -> Result<Option<ModelProvenance>, GuardianError> {
+        if let Some(provenance) = self.active_models.read().await.get(version).and_then(|m| m.provenance.clone()) {
+            return Ok(Some(provenance));
+        }
+        Ok(self
+            .model_store
+            .load_provenance(version)
+            .await?
+            .and_then(|record| serde_json::from_str(&record).ok()))
+    }
+
     /// Loads existing registry state from storage
+    ///
+    /// Failed and deprecated versions keep their status; every other version must be re-activated,
+    /// so its signature is re-verified before it serves inferences again.
     async fn load_registry_state(&self) -> Result<(), GuardianError> {
-        let versions = self.model_store.list_versions().await?;
-        
+        let entries = self
+            .model_store
+            .query_versions(&VersionQuery { page_size: usize::MAX, ..Default::default() })
+            .await?
+            .entries;
+
         let mut active_models = self.active_models.write().await;
-        for version in versions {
-            active_models.insert(version.version.clone(), ModelMetadata {
-                name: version.version.clone(),
-                version: version.version,
-                created_at: version.created_at,
+        for entry in entries {
+            let status = match entry.status.parse() {
+                Ok(status @ (ModelStatus::Failed | ModelStatus::Deprecated)) => status,
+                _ => ModelStatus::Inactive,
+            };
+            active_models.insert(entry.version.clone(), ModelMetadata {
+                name: entry.name,
+                version: entry.version,
+                created_at: entry.created_at,
                 updated_at: Utc::now(),
-                status: ModelStatus::Inactive,
+                status,
                 metrics: None,
                 validation_status: ValidationStatus::Pending,
-                hash: version.hash,
-                size_bytes: version.size,
+                hash: entry.hash,
+                size_bytes: entry.size,
+                signature: None,
+                provenance: None,
             });
         }
+        let restored: Vec<&ModelMetadata> = active_models.values().collect();
+        self.sync_index(&restored).await;
 
         Ok(())
     }
 
+    /// Mirrors names and statuses into the store's version index
+    ///
+    /// The index only serves listings, so a failed write is logged rather than failing the lifecycle change.
+    async fn sync_index(&self, models: &[&ModelMetadata]) {
+        let labels: Vec<VersionLabel> = models
+            .iter()
+            .map(|m| VersionLabel {
+                version: m.version.clone(),
+                name: m.name.clone(),
+                status: m.status.as_str().to_string(),
+            })
+            .collect();
+        if let Err(e) = self.model_store.label_versions(&labels).await {
+            warn!(error = ?e, "Failed to update model version index");
+        }
+    }
+
     /// Validates model data before registration
     async fn validate_model_data(&self, data: &[u8], version: &str) -> Result<(), GuardianError> {
         if data.is_empty() {
@@ -231,7 +784,7 @@ impl ModelRegistry {
                 source: None,
                 severity: crate::utils::error::ErrorSeverity::High,
                 timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id: uuid::Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: ErrorCategory::ML,
                 retry_count: 0,
             });
@@ -244,7 +797,7 @@ impl ModelRegistry {
                 source: None,
                 severity: crate::utils::error::ErrorSeverity::High,
                 timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id: uuid::Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: ErrorCategory::ML,
                 retry_count: 0,
             });
@@ -262,7 +815,7 @@ impl ModelRegistry {
                 source: None,
                 severity: crate::utils::error::ErrorSeverity::High,
                 timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id: uuid::Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: ErrorCategory::ML,
                 retry_count: 0,
             })?
@@ -275,7 +828,7 @@ impl ModelRegistry {
                 source: None,
                 severity: crate::utils::error::ErrorSeverity::High,
                 timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id: uuid::Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: ErrorCategory::ML,
                 retry_count: 0,
             });
@@ -321,14 +874,56 @@ impl Clone for ModelRegistry {
             model_store: Arc::clone(&self.model_store),
             active_models: RwLock::new(HashMap::new()),
             model_metrics: RwLock::new(HashMap::new()),
+            activation_tx: Arc::clone(&self.activation_tx),
+            verifier: Arc::clone(&self.verifier),
+            experiment: RwLock::new(None),
         }
     }
 }
 
+fn fold_inference(metrics: &mut ModelMetrics, inference_time_ms: f64) {
+    metrics.total_inferences += 1;
+    metrics.inference_time_ms += (inference_time_ms - metrics.inference_time_ms) / metrics.total_inferences as f64;
+    metrics.last_updated = Utc::now();
+}
+
+fn fold_feedback(metrics: &mut ModelMetrics, label: FeedbackLabel) {
+    match label {
+        FeedbackLabel::TruePositive => metrics.true_positives += 1,
+        FeedbackLabel::FalsePositive => metrics.false_positives += 1,
+        FeedbackLabel::FalseNegative => metrics.false_negatives += 1,
+    }
+    metrics.last_updated = Utc::now();
+}
+
+/// Hashes the experiment name in so each experiment draws a fresh split
+fn experiment_bucket(experiment: &str, event_key: &str) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    experiment.hash(&mut hasher);
+    event_key.hash(&mut hasher);
+    hasher.finish() % EXPERIMENT_BUCKETS
+}
+
+fn empty_metrics() -> ModelMetrics {
+    ModelMetrics {
+        inference_time_ms: 0.0,
+        memory_usage_mb: 0.0,
+        accuracy: 0.0,
+        true_positives: 0,
+        false_positives: 0,
+        false_negatives: 0,
+        total_inferences: 0,
+        last_updated: Utc::now(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+    use ring::signature::{Ed25519KeyPair, KeyPair};
     use std::path::PathBuf;
+    use crate::security::model_signing::TrustedModelKey;
 
     #[tokio::test]
     async fn test_model_registration() {
@@ -343,7 +938,14 @@ mod tests {
             Some(5),
         ).await.unwrap());
 
-        let registry = ModelRegistry::new(model_store).await.unwrap();
+        let rng = ring::rand::SystemRandom::new();
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&rng).unwrap();
+        let key_pair = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+        let verifier = ModelVerifier::new(
+            vec![TrustedModelKey::ed25519("test", key_pair.public_key().as_ref().to_vec())],
+            true,
+        );
+        let registry = ModelRegistry::new(model_store).await.unwrap().with_verifier(Arc::new(verifier));
 
         let test_data = vec![1, 2, 3, 4, 5];
         let version = "v1.0.0".to_string();
@@ -357,12 +959,63 @@ mod tests {
             validation_status: ValidationStatus::Pending,
             hash: "".to_string(),
             size_bytes: 0,
+            signature: None,
+            provenance: None,
         };
 
-        let result = registry.register_model(test_data, version.clone(), metadata).await;
+        let result = registry.register_model(test_data.clone(), version.clone(), metadata.clone()).await;
+        assert!(result.is_err());
+
+        let signed = ModelMetadata {
+            signature: Some(BASE64.encode(key_pair.sign(&test_data).as_ref())),
+            ..metadata
+        };
+        let result = registry.register_model(test_data, version.clone(), signed).await;
         assert!(result.is_ok());
 
-        let result = registry.activate_model(version).await;
+        let activations = registry.subscribe_activations();
+        let result = registry.activate_model(version.clone()).await;
         assert!(result.is_ok());
+
+        let activation = activations.borrow().clone().unwrap();
+        assert_eq!(activation.version, version);
+        assert_eq!(activation.previous_version, None);
     }
+
+    #[test]
+    fn test_experiment_tracks_arms_separately() {
+        let mut state = ExperimentState {
+            experiment: ModelExperiment {
+                name: "exp-1".to_string(),
+                control: "v1.0.0".to_string(),
+                treatment: "v1.1.0".to_string(),
+                treatment_percent: 20,
+                started_at: Utc::now(),
+            },
+            control: empty_metrics(),
+            treatment: empty_metrics(),
+        };
+        for (version, label) in [
+            ("v1.1.0", FeedbackLabel::TruePositive),
+            ("v1.1.0", FeedbackLabel::TruePositive),
+            ("v1.1.0", FeedbackLabel::TruePositive),
+            ("v1.1.0", FeedbackLabel::FalsePositive),
+            ("v1.0.0", FeedbackLabel::FalseNegative),
+        ] {
+            fold_feedback(state.arm_metrics(version).unwrap(), label);
+        }
+        fold_inference(state.arm_metrics("v1.1.0").unwrap(), 4.0);
+        fold_inference(state.arm_metrics("v1.1.0").unwrap(), 8.0);
+        assert!(state.arm_metrics("v0.9.0").is_none());
+
+        let summary = state.summary();
+        assert_eq!((summary.treatment.precision, summary.treatment.recall), (Some(0.75), Some(1.0)));
+        assert_eq!(summary.treatment.mean_latency_ms, 6.0);
+        assert_eq!((summary.control.precision, summary.control.recall), (None, Some(0.0)));
+
+        let treated = (0..1000).filter(|i| experiment_bucket("exp-1", &format!("event-{}", i)) < 20).count();
+        assert!((120..=280).contains(&treated));
+        assert_eq!(experiment_bucket("exp-1", "event-1"), experiment_bucket("exp-1", "event-1"));
+    }
+
 }
\ No newline at end of file
@@ -0,0 +1,323 @@
+//! Vectorized kernels for feature extraction, dispatched on CPU features detected at runtime
+//!
+//! Every kernel has a scalar twin in [`scalar`] that defines its result; the AVX2 and NEON paths
+//! differ from it only by floating point summation order. Inputs are assumed free of NaN.
+
+use once_cell::sync::Lazy;
+
+/// Instruction set the kernels run on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SimdLevel {
+    Scalar,
+    Avx2,
+    Neon,
+}
+
+static SIMD_LEVEL: Lazy<SimdLevel> = Lazy::new(detect);
+
+/// Returns the instruction set detected on this CPU
+pub fn simd_level() -> SimdLevel {
+    *SIMD_LEVEL
+}
+
+fn detect() -> SimdLevel {
+    #[cfg(target_arch = "x86_64")]
+    if is_x86_feature_detected!("avx2") {
+        return SimdLevel::Avx2;
+    }
+    #[cfg(target_arch = "aarch64")]
+    if std::arch::is_aarch64_feature_detected!("neon") {
+        return SimdLevel::Neon;
+    }
+    SimdLevel::Scalar
+}
+
+/// Summary statistics of a series
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Moments {
+    pub count: usize,
+    pub mean: f32,
+    pub std_dev: f32,
+    pub min: f32,
+    pub max: f32,
+}
+
+/// Smallest and largest value, or `None` for an empty slice
+pub fn min_max(values: &[f32]) -> Option<(f32, f32)> {
+    if values.is_empty() {
+        return None;
+    }
+    Some(match simd_level() {
+        // SAFETY: each arm is only taken when `detect` found the instruction set
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Avx2 => unsafe { avx2::min_max(values) },
+        #[cfg(target_arch = "aarch64")]
+        SimdLevel::Neon => unsafe { neon::min_max(values) },
+        _ => scalar::min_max(values),
+    })
+}
+
+/// Replaces every value `v` with `v * scale + offset`
+pub fn affine(values: &mut [f32], scale: f32, offset: f32) {
+    match simd_level() {
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Avx2 => unsafe { avx2::affine(values, scale, offset) },
+        #[cfg(target_arch = "aarch64")]
+        SimdLevel::Neon => unsafe { neon::affine(values, scale, offset) },
+        _ => scalar::affine(values, scale, offset),
+    }
+}
+
+pub fn sum(values: &[f32]) -> f32 {
+    match simd_level() {
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Avx2 => unsafe { avx2::sum(values) },
+        #[cfg(target_arch = "aarch64")]
+        SimdLevel::Neon => unsafe { neon::sum(values) },
+        _ => scalar::sum(values),
+    }
+}
+
+/// Sum of squared deviations from `mean`
+pub fn sum_sq_dev(values: &[f32], mean: f32) -> f32 {
+    match simd_level() {
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Avx2 => unsafe { avx2::sum_sq_dev(values, mean) },
+        #[cfg(target_arch = "aarch64")]
+        SimdLevel::Neon => unsafe { neon::sum_sq_dev(values, mean) },
+        _ => scalar::sum_sq_dev(values, mean),
+    }
+}
+
+/// Rescales values linearly onto `[lo, hi]`; a constant series is left untouched
+pub fn normalize(values: &mut [f32], lo: f32, hi: f32) {
+    let Some((min, max)) = min_max(values) else {
+        return;
+    };
+    let range = max - min;
+    if range == 0.0 {
+        return;
+    }
+    let scale = (hi - lo) / range;
+    affine(values, scale, lo - min * scale);
+}
+
+/// Mean, population standard deviation and range, in two passes for numerical stability
+pub fn moments(values: &[f32]) -> Moments {
+    let Some((min, max)) = min_max(values) else {
+        return Moments::default();
+    };
+    let count = values.len();
+    let mean = sum(values) / count as f32;
+    let variance = sum_sq_dev(values, mean) / count as f32;
+    Moments { count, mean, std_dev: variance.max(0.0).sqrt(), min, max }
+}
+
+/// Splits a series into `windows` consecutive windows of near-equal length and summarizes each
+///
+/// Short series yield fewer windows; an empty series yields none.
+pub fn window_moments(values: &[f32], windows: usize) -> Vec<Moments> {
+    if values.is_empty() || windows == 0 {
+        return Vec::new();
+    }
+    let width = values.len().div_ceil(windows);
+    values.chunks(width).map(moments).collect()
+}
+
+/// Reference implementations, also used on CPUs without a supported vector unit
+pub mod scalar {
+    pub fn min_max(values: &[f32]) -> (f32, f32) {
+        values
+            .iter()
+            .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), &v| (min.min(v), max.max(v)))
+    }
+
+    pub fn affine(values: &mut [f32], scale: f32, offset: f32) {
+        for v in values.iter_mut() {
+            *v = *v * scale + offset;
+        }
+    }
+
+    pub fn sum(values: &[f32]) -> f32 {
+        values.iter().sum()
+    }
+
+    pub fn sum_sq_dev(values: &[f32], mean: f32) -> f32 {
+        values.iter().map(|v| (v - mean) * (v - mean)).sum()
+    }
+
+    /// Scalar-only normalization, kept for benchmarking against the dispatched path
+    pub fn normalize(values: &mut [f32], lo: f32, hi: f32) {
+        if values.is_empty() {
+            return;
+        }
+        let (min, max) = min_max(values);
+        let range = max - min;
+        if range == 0.0 {
+            return;
+        }
+        let scale = (hi - lo) / range;
+        affine(values, scale, lo - min * scale);
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+mod avx2 {
+    use std::arch::x86_64::*;
+
+    use super::scalar;
+
+    const AVX2_LANES: usize = 8;
+
+    #[target_feature(enable = "avx2")]
+    unsafe fn lanes(v: __m256) -> [f32; AVX2_LANES] {
+        let mut out = [0.0; AVX2_LANES];
+        _mm256_storeu_ps(out.as_mut_ptr(), v);
+        out
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn min_max(values: &[f32]) -> (f32, f32) {
+        let chunks = values.chunks_exact(AVX2_LANES);
+        let (mut min, mut max) = scalar::min_max(chunks.remainder());
+        let mut vmin = _mm256_set1_ps(min);
+        let mut vmax = _mm256_set1_ps(max);
+        for chunk in chunks {
+            let v = _mm256_loadu_ps(chunk.as_ptr());
+            vmin = _mm256_min_ps(vmin, v);
+            vmax = _mm256_max_ps(vmax, v);
+        }
+        for (lo, hi) in lanes(vmin).into_iter().zip(lanes(vmax)) {
+            min = min.min(lo);
+            max = max.max(hi);
+        }
+        (min, max)
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn affine(values: &mut [f32], scale: f32, offset: f32) {
+        let vscale = _mm256_set1_ps(scale);
+        let voffset = _mm256_set1_ps(offset);
+        let mut chunks = values.chunks_exact_mut(AVX2_LANES);
+        for chunk in &mut chunks {
+            let v = _mm256_loadu_ps(chunk.as_ptr());
+            _mm256_storeu_ps(chunk.as_mut_ptr(), _mm256_add_ps(_mm256_mul_ps(v, vscale), voffset));
+        }
+        scalar::affine(chunks.into_remainder(), scale, offset);
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn sum(values: &[f32]) -> f32 {
+        let chunks = values.chunks_exact(AVX2_LANES);
+        let rest = scalar::sum(chunks.remainder());
+        let mut acc = _mm256_setzero_ps();
+        for chunk in chunks {
+            acc = _mm256_add_ps(acc, _mm256_loadu_ps(chunk.as_ptr()));
+        }
+        lanes(acc).iter().sum::<f32>() + rest
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn sum_sq_dev(values: &[f32], mean: f32) -> f32 {
+        let chunks = values.chunks_exact(AVX2_LANES);
+        let rest = scalar::sum_sq_dev(chunks.remainder(), mean);
+        let vmean = _mm256_set1_ps(mean);
+        let mut acc = _mm256_setzero_ps();
+        for chunk in chunks {
+            let d = _mm256_sub_ps(_mm256_loadu_ps(chunk.as_ptr()), vmean);
+            acc = _mm256_add_ps(acc, _mm256_mul_ps(d, d));
+        }
+        lanes(acc).iter().sum::<f32>() + rest
+    }
+}
+
+#[cfg(target_arch = "aarch64")]
+mod neon {
+    use std::arch::aarch64::*;
+
+    use super::scalar;
+
+    const NEON_LANES: usize = 4;
+
+    #[target_feature(enable = "neon")]
+    pub unsafe fn min_max(values: &[f32]) -> (f32, f32) {
+        let chunks = values.chunks_exact(NEON_LANES);
+        let (min, max) = scalar::min_max(chunks.remainder());
+        let mut vmin = vdupq_n_f32(min);
+        let mut vmax = vdupq_n_f32(max);
+        for chunk in chunks {
+            let v = vld1q_f32(chunk.as_ptr());
+            vmin = vminq_f32(vmin, v);
+            vmax = vmaxq_f32(vmax, v);
+        }
+        (vminvq_f32(vmin), vmaxvq_f32(vmax))
+    }
+
+    #[target_feature(enable = "neon")]
+    pub unsafe fn affine(values: &mut [f32], scale: f32, offset: f32) {
+        let vscale = vdupq_n_f32(scale);
+        let voffset = vdupq_n_f32(offset);
+        let mut chunks = values.chunks_exact_mut(NEON_LANES);
+        for chunk in &mut chunks {
+            let v = vld1q_f32(chunk.as_ptr());
+            vst1q_f32(chunk.as_mut_ptr(), vaddq_f32(vmulq_f32(v, vscale), voffset));
+        }
+        scalar::affine(chunks.into_remainder(), scale, offset);
+    }
+
+    #[target_feature(enable = "neon")]
+    pub unsafe fn sum(values: &[f32]) -> f32 {
+        let chunks = values.chunks_exact(NEON_LANES);
+        let rest = scalar::sum(chunks.remainder());
+        let mut acc = vdupq_n_f32(0.0);
+        for chunk in chunks {
+            acc = vaddq_f32(acc, vld1q_f32(chunk.as_ptr()));
+        }
+        vaddvq_f32(acc) + rest
+    }
+
+    #[target_feature(enable = "neon")]
+    pub unsafe fn sum_sq_dev(values: &[f32], mean: f32) -> f32 {
+        let chunks = values.chunks_exact(NEON_LANES);
+        let rest = scalar::sum_sq_dev(chunks.remainder(), mean);
+        let vmean = vdupq_n_f32(mean);
+        let mut acc = vdupq_n_f32(0.0);
+        for chunk in chunks {
+            let d = vsubq_f32(vld1q_f32(chunk.as_ptr()), vmean);
+            acc = vaddq_f32(acc, vmulq_f32(d, d));
+        }
+        vaddvq_f32(acc) + rest
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: f32, b: f32) -> bool {
+        (a - b).abs() <= 1e-3 * a.abs().max(b.abs()).max(1.0)
+    }
+
+    #[test]
+    fn test_dispatched_kernels_match_scalar() {
+        // Odd length so the vector paths also exercise their remainder handling
+        let values: Vec<f32> = (0..1037).map(|i| ((i * 7919) % 1000) as f32 / 10.0 - 50.0).collect();
+
+        assert_eq!(min_max(&values), Some(scalar::min_max(&values)));
+        assert!(close(sum(&values), scalar::sum(&values)));
+        assert!(close(sum_sq_dev(&values, 1.5), scalar::sum_sq_dev(&values, 1.5)));
+
+        let (mut fast, mut slow) = (values.clone(), values.clone());
+        normalize(&mut fast, -1.0, 1.0);
+        scalar::normalize(&mut slow, -1.0, 1.0);
+        assert!(fast.iter().zip(&slow).all(|(a, b)| close(*a, *b)));
+        assert!(close(fast.iter().cloned().fold(f32::INFINITY, f32::min), -1.0));
+
+        let stats = moments(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]);
+        assert_eq!((stats.count, stats.mean, stats.std_dev, stats.min, stats.max), (8, 5.0, 2.0, 2.0, 9.0));
+        let windows = window_moments(&values, 4);
+        assert_eq!(windows.len(), 4);
+        assert_eq!(windows.iter().map(|w| w.count).sum::<usize>(), values.len());
+        assert!(min_max(&[]).is_none() && window_moments(&[], 4).is_empty());
+    }
+}
@@ -0,0 +1,257 @@
+//! Logistic anomaly classifier trained on analyst-labeled detections
+
+use burn::tensor::{activation::sigmoid, backend::Backend, Data, Shape, Tensor};
+use serde::{Deserialize, Serialize};
+
+use crate::security::anomaly_feedback::LabeledSample;
+use crate::utils::error::{ErrorCategory, ErrorSeverity, GuardianError};
+
+/// Serialization format tag of trained models
+pub const LOGISTIC_MODEL_FORMAT: &str = "guardian-logistic-v1";
+const DECISION_THRESHOLD: f32 = 0.5;
+/// Floor on feature standard deviations so constant features do not divide by zero
+const MIN_FEATURE_SCALE: f32 = 1e-6;
+
+/// Labeled feature rows, oldest first
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TrainingSet {
+    pub rows: Vec<Vec<f32>>,
+    pub labels: Vec<bool>,
+}
+
+impl TrainingSet {
+    /// Converts labeled samples to rows; returns the set and the number of samples skipped because
+    /// their features are not numeric or do not match the width of the first usable sample
+    pub fn from_samples(samples: &[LabeledSample]) -> (Self, usize) {
+        let mut ordered: Vec<&LabeledSample> = samples.iter().collect();
+        ordered.sort_by_key(|s| s.labeled_at);
+
+        let mut set = Self::default();
+        let mut skipped = 0;
+        for sample in ordered {
+            match feature_row(&sample.features) {
+                Some(row) if set.rows.first().map_or(!row.is_empty(), |first| first.len() == row.len()) => {
+                    set.rows.push(row);
+                    set.labels.push(sample.anomalous);
+                }
+                _ => skipped += 1,
+            }
+        }
+        (set, skipped)
+    }
+
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    pub fn feature_len(&self) -> usize {
+        self.rows.first().map_or(0, Vec::len)
+    }
+
+    /// Splits off the newest `fraction` of rows, so validation measures the model on data it will
+    /// meet next rather than on a random sample of the past
+    pub fn split_holdout(mut self, fraction: f64) -> (Self, Self) {
+        let held = ((self.len() as f64 * fraction).ceil() as usize).min(self.len());
+        let at = self.len() - held;
+        let holdout = Self {
+            rows: self.rows.split_off(at),
+            labels: self.labels.split_off(at),
+        };
+        (self, holdout)
+    }
+}
+
+/// Trained classifier with the feature standardization it was fitted with
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LogisticModel {
+    pub format: String,
+    pub mean: Vec<f32>,
+    pub scale: Vec<f32>,
+    pub weights: Vec<f32>,
+    pub bias: f32,
+}
+
+impl LogisticModel {
+    /// Probability that the row is anomalous
+    pub fn score(&self, row: &[f32]) -> f32 {
+        let z: f32 = row
+            .iter()
+            .zip(&self.mean)
+            .zip(&self.scale)
+            .zip(&self.weights)
+            .map(|(((x, mean), scale), w)| (x - mean) / scale * w)
+            .sum::<f32>()
+            + self.bias;
+        1.0 / (1.0 + (-z).exp())
+    }
+
+    pub fn predict(&self, row: &[f32]) -> bool {
+        self.score(row) >= DECISION_THRESHOLD
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, GuardianError> {
+        serde_json::to_vec(self).map_err(|e| trainer_error("Failed to serialize trained model".into(), Some(Box::new(e))))
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, GuardianError> {
+        let model: Self = serde_json::from_slice(bytes)
+            .map_err(|e| trainer_error("Failed to parse trained model".into(), Some(Box::new(e))))?;
+        if model.format != LOGISTIC_MODEL_FORMAT {
+            return Err(trainer_error(format!("Unsupported model format {}", model.format), None));
+        }
+        Ok(model)
+    }
+}
+
+/// Classification quality on a labeled set
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Evaluation {
+    pub samples: usize,
+    pub accuracy: f64,
+    pub false_positives: u64,
+    pub false_negatives: u64,
+}
+
+pub fn evaluate(model: &LogisticModel, set: &TrainingSet) -> Evaluation {
+    let mut evaluation = Evaluation { samples: set.len(), ..Default::default() };
+    let mut correct = 0;
+    for (row, &label) in set.rows.iter().zip(&set.labels) {
+        match (model.predict(row), label) {
+            (true, false) => evaluation.false_positives += 1,
+            (false, true) => evaluation.false_negatives += 1,
+            _ => correct += 1,
+        }
+    }
+    evaluation.accuracy = if set.is_empty() { 0.0 } else { correct as f64 / set.len() as f64 };
+    evaluation
+}
+
+/// Fits the classifier with full-batch gradient descent on the backend's device
+pub fn train<B: Backend>(set: &TrainingSet, epochs: usize, learning_rate: f64, device: &B::Device) -> Result<LogisticModel, GuardianError> {
+    if set.is_empty() {
+        return Err(trainer_error("Cannot train on an empty dataset".into(), None));
+    }
+    let (rows, width) = (set.len(), set.feature_len());
+    let (mean, scale) = standardization(set);
+
+    let standardized: Vec<f32> = set
+        .rows
+        .iter()
+        .flat_map(|row| row.iter().zip(&mean).zip(&scale).map(|((x, m), s)| (x - m) / s))
+        .collect();
+    let targets: Vec<f32> = set.labels.iter().map(|&l| if l { 1.0 } else { 0.0 }).collect();
+
+    let x = Tensor::<B, 2>::from_data(Data::new(standardized, Shape::new([rows, width])).convert()).to_device(device);
+    let y = Tensor::<B, 2>::from_data(Data::new(targets, Shape::new([rows, 1])).convert()).to_device(device);
+    let x_t = x.clone().transpose();
+    let mut weights = Tensor::<B, 2>::zeros([width, 1]).to_device(device);
+    let mut bias = Tensor::<B, 2>::zeros([1, 1]).to_device(device);
+
+    // The gradient of the mean log loss with respect to the logits is (sigmoid(z) - y) / n
+    let step = learning_rate / rows as f64;
+    for _ in 0..epochs {
+        let error = sigmoid(x.clone().matmul(weights.clone()) + bias.clone()) - y.clone();
+        weights = weights - x_t.clone().matmul(error.clone()).mul_scalar(step);
+        bias = bias - error.sum_dim(0).mul_scalar(step);
+    }
+
+    Ok(LogisticModel {
+        format: LOGISTIC_MODEL_FORMAT.to_string(),
+        mean,
+        scale,
+        weights: weights.into_data().convert::<f32>().value,
+        bias: bias.into_data().convert::<f32>().value[0],
+    })
+}
+
+/// Per-feature mean and standard deviation
+fn standardization(set: &TrainingSet) -> (Vec<f32>, Vec<f32>) {
+    let n = set.len() as f32;
+    let width = set.feature_len();
+    let mut mean = vec![0.0f32; width];
+    for row in &set.rows {
+        mean.iter_mut().zip(row).for_each(|(m, x)| *m += x / n);
+    }
+    let mut variance = vec![0.0f32; width];
+    for row in &set.rows {
+        variance.iter_mut().zip(row).zip(&mean).for_each(|((v, x), m)| *v += (x - m).powi(2) / n);
+    }
+    let scale = variance.into_iter().map(|v| v.sqrt().max(MIN_FEATURE_SCALE)).collect();
+    (mean, scale)
+}
+
+/// Flattens numeric features: an array as is, an object in key order
+fn feature_row(features: &serde_json::Value) -> Option<Vec<f32>> {
+    let values: Vec<&serde_json::Value> = match features {
+        serde_json::Value::Array(values) => values.iter().collect(),
+        serde_json::Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            entries.into_iter().map(|(_, v)| v).collect()
+        }
+        _ => return None,
+    };
+    values.into_iter().map(|v| v.as_f64().map(|x| x as f32)).collect()
+}
+
+fn trainer_error(context: String, source: Option<Box<dyn std::error::Error + Send + Sync>>) -> GuardianError {
+    GuardianError::MLError {
+        context,
+        source,
+        severity: ErrorSeverity::High,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: ErrorCategory::ML,
+        retry_count: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(features: serde_json::Value, anomalous: bool, labeled_at: u64) -> LabeledSample {
+        LabeledSample {
+            features,
+            anomalous,
+            anomaly_type: "network".into(),
+            detection_id: None,
+            model_version: None,
+            labeled_by: "analyst".into(),
+            labeled_at,
+        }
+    }
+
+    #[test]
+    fn test_training_set_holdout_and_evaluation() {
+        let samples = vec![
+            sample(serde_json::json!([9.0, 1.0]), true, 3),
+            sample(serde_json::json!({"b": 1.0, "a": 0.5}), false, 1),
+            sample(serde_json::json!(["not numeric"]), false, 2),
+            sample(serde_json::json!([0.2, 1.0]), false, 4),
+            sample(serde_json::json!([8.0]), true, 5),
+        ];
+        let (set, skipped) = TrainingSet::from_samples(&samples);
+        assert_eq!(skipped, 2);
+        assert_eq!(set.rows, vec![vec![0.5, 1.0], vec![9.0, 1.0], vec![0.2, 1.0]]);
+
+        let (train, holdout) = set.split_holdout(0.3);
+        assert_eq!(train.len(), 2);
+        assert_eq!(holdout.labels, vec![false]);
+
+        let model = LogisticModel {
+            format: LOGISTIC_MODEL_FORMAT.into(),
+            mean: vec![0.0, 0.0],
+            scale: vec![1.0, 1.0],
+            weights: vec![1.0, 0.0],
+            bias: -5.0,
+        };
+        let evaluation = evaluate(&model, &train);
+        assert_eq!(evaluation.accuracy, 1.0);
+        assert_eq!(LogisticModel::from_bytes(&model.to_bytes().unwrap()).unwrap(), model);
+    }
+}
@@ -0,0 +1,217 @@
+//! Cold-start model warm-up and readiness
+//!
+//! The first inferences after startup pay for JIT compilation, kernel selection and model
+//! pages faulting in, and blow the latency SLA. Engines run synthetic inferences before they
+//! take traffic, and the process reports not ready for inference until every engine that
+//! started warming up has finished, so load balancers keep routing elsewhere meanwhile.
+
+use std::{
+    future::Future,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use metrics::{counter, gauge, histogram};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::config::ml_config::WarmupConfig;
+use crate::utils::error::GuardianError;
+
+/// Recent inferences averaged to decide the model is warm
+const STABLE_WINDOW: usize = 4;
+
+static WARMUP_GATE: Lazy<Arc<WarmupGate>> = Lazy::new(|| Arc::new(WarmupGate::default()));
+
+/// Returns the process-wide warm-up gate
+pub fn warmup_gate() -> Arc<WarmupGate> {
+    Arc::clone(&WARMUP_GATE)
+}
+
+/// Whether the process takes inference traffic
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Readiness {
+    /// No inference engine has started yet
+    Starting,
+    WarmingUp,
+    Ready,
+}
+
+/// Counts engines warming up
+#[derive(Debug, Default)]
+pub struct WarmupGate {
+    started: AtomicBool,
+    warming: AtomicUsize,
+}
+
+impl WarmupGate {
+    pub fn readiness(&self) -> Readiness {
+        if !self.started.load(Ordering::Acquire) {
+            Readiness::Starting
+        } else if self.warming.load(Ordering::Acquire) > 0 {
+            Readiness::WarmingUp
+        } else {
+            Readiness::Ready
+        }
+    }
+
+    /// Holds readiness back until the returned guard drops
+    pub fn begin(self: &Arc<Self>) -> WarmupGuard {
+        self.warming.fetch_add(1, Ordering::AcqRel);
+        self.started.store(true, Ordering::Release);
+        gauge!("guardian.ml.warmup.ready", 0.0);
+        WarmupGuard { gate: Arc::clone(self) }
+    }
+}
+
+/// Releases the gate when warm-up ends, however it ends
+#[derive(Debug)]
+pub struct WarmupGuard {
+    gate: Arc<WarmupGate>,
+}
+
+impl Drop for WarmupGuard {
+    fn drop(&mut self) {
+        if self.gate.warming.fetch_sub(1, Ordering::AcqRel) == 1 {
+            gauge!("guardian.ml.warmup.ready", 1.0);
+        }
+    }
+}
+
+/// How a warm-up ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WarmupOutcome {
+    /// Recent inferences met the target latency
+    Converged,
+    /// Every allowed inference ran without meeting the target
+    Exhausted,
+    /// The budget ran out first
+    BudgetElapsed,
+    Disabled,
+}
+
+/// What a warm-up did
+#[derive(Debug, Clone, Serialize)]
+pub struct WarmupReport {
+    pub outcome: WarmupOutcome,
+    pub inferences: usize,
+    pub duration_ms: f64,
+    pub first_latency_ms: f64,
+    /// Average over the last few inferences
+    pub settled_latency_ms: f64,
+}
+
+/// Runs `infer` on synthetic inputs until latency settles under the target, the inference
+/// cap is reached or the budget runs out
+///
+/// `infer` gets the iteration number to vary its input. A failing inference fails the
+/// warm-up: a model that cannot answer synthetic input must not take traffic.
+pub async fn run_warmup<F, Fut>(config: &WarmupConfig, mut infer: F) -> Result<WarmupReport, GuardianError>
+where
+    F: FnMut(usize) -> Fut,
+    Fut: Future<Output = Result<(), GuardianError>>,
+{
+    if !config.enabled {
+        return Ok(WarmupReport {
+            outcome: WarmupOutcome::Disabled,
+            inferences: 0,
+            duration_ms: 0.0,
+            first_latency_ms: 0.0,
+            settled_latency_ms: 0.0,
+        });
+    }
+
+    let start = Instant::now();
+    let mut latencies: Vec<Duration> = Vec::new();
+    let outcome = loop {
+        if latencies.len() >= config.min_inferences && settled(&latencies) <= config.target_latency {
+            break WarmupOutcome::Converged;
+        }
+        if latencies.len() >= config.max_inferences {
+            break WarmupOutcome::Exhausted;
+        }
+        let Some(remaining) = config.budget.checked_sub(start.elapsed()) else {
+            break WarmupOutcome::BudgetElapsed;
+        };
+
+        let inference_start = Instant::now();
+        match tokio::time::timeout(remaining, infer(latencies.len())).await {
+            Ok(result) => result?,
+            Err(_) => break WarmupOutcome::BudgetElapsed,
+        }
+        latencies.push(inference_start.elapsed());
+    };
+
+    let report = WarmupReport {
+        outcome,
+        inferences: latencies.len(),
+        duration_ms: start.elapsed().as_secs_f64() * 1000.0,
+        first_latency_ms: latencies.first().map(|l| l.as_secs_f64() * 1000.0).unwrap_or_default(),
+        settled_latency_ms: settled(&latencies).as_secs_f64() * 1000.0,
+    };
+    let label = match outcome {
+        WarmupOutcome::Converged => "converged",
+        WarmupOutcome::Exhausted => "exhausted",
+        WarmupOutcome::BudgetElapsed => "budget_elapsed",
+        WarmupOutcome::Disabled => "disabled",
+    };
+    counter!("guardian.ml.warmup.runs", 1, "outcome" => label);
+    histogram!("guardian.ml.warmup.duration", start.elapsed().as_secs_f64());
+    if outcome == WarmupOutcome::Converged {
+        info!(inferences = report.inferences, duration_ms = report.duration_ms, settled_latency_ms = report.settled_latency_ms, "Model warm-up converged");
+    } else {
+        warn!(
+            outcome = label,
+            inferences = report.inferences,
+            settled_latency_ms = report.settled_latency_ms,
+            "Model warm-up ended above the target latency, taking traffic anyway"
+        );
+    }
+    Ok(report)
+}
+
+/// Average latency of the last few inferences
+fn settled(latencies: &[Duration]) -> Duration {
+    let recent = &latencies[latencies.len().saturating_sub(STABLE_WINDOW)..];
+    if recent.is_empty() {
+        return Duration::MAX;
+    }
+    recent.iter().sum::<Duration>() / recent.len() as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_warmup_stops_once_latency_settles_and_gates_readiness() {
+        let gate = Arc::new(WarmupGate::default());
+        assert_eq!(gate.readiness(), Readiness::Starting);
+        let guard = gate.begin();
+        assert_eq!(gate.readiness(), Readiness::WarmingUp);
+
+        let config = WarmupConfig {
+            min_inferences: 2,
+            target_latency: Duration::from_millis(20),
+            ..Default::default()
+        };
+        // The first inferences are slow, the rest fast
+        let report = run_warmup(&config, |i| async move {
+            tokio::time::sleep(Duration::from_millis(if i < 2 { 40 } else { 1 })).await;
+            Ok(())
+        })
+        .await
+        .unwrap();
+        assert_eq!(report.outcome, WarmupOutcome::Converged);
+        assert!(report.inferences > 2 && report.inferences < config.max_inferences);
+
+        drop(guard);
+        assert_eq!(gate.readiness(), Readiness::Ready);
+    }
+}
@@ -5,7 +5,10 @@ use tracing::{debug, error, info, instrument, warn};
 use serde::{Serialize, Deserialize};
 
 use crate::ml::inference_engine::InferenceEngine;
+use crate::core::capabilities::{capabilities, ANOMALY_DETECTION};
 use crate::core::event_bus::{Event, EventBus, EventPriority};
+use crate::core::guardian::TenantContext;
+use crate::security::anomaly_feedback::{anomaly_feedback, AnomalyFeedback};
 use crate::utils::error::GuardianError;
 use crate::core::system_state::{SystemState, SystemHealth};
 use crate::utils::metrics::{record_metric, MetricKind};
@@ -133,6 +136,8 @@ pub struct AnomalyDetector {
     config: AnomalyConfig,
     circuit_breaker: RwLock<CircuitBreaker>,
     batcher: Mutex<AdaptiveBatcher>,
+    feedback: Option<Arc<AnomalyFeedback>>,
+    tenant: TenantContext,
 }
 
 impl AnomalyDetector {
@@ -152,9 +157,18 @@ impl AnomalyDetector {
             config,
             circuit_breaker: RwLock::new(CircuitBreaker::new()),
             batcher: Mutex::new(AdaptiveBatcher::new(1, config.batch_size)),
+            feedback: None,
+            tenant: TenantContext::default(),
         }
     }
 
+    /// Records detections for the tenant so analysts can label them true or false positives
+    pub fn with_feedback(mut self, feedback: Arc<AnomalyFeedback>, tenant: TenantContext) -> Self {
+        self.feedback = Some(feedback);
+        self.tenant = tenant;
+        self
+    }
+
     /// Analyzes system data for anomalies
     #[instrument(skip(self, data))]
     pub async fn detect_anomalies(&self, data: SystemData) -> Result<Vec<Anomaly>, GuardianError> {
@@ -282,6 +296,11 @@ impl AnomalyDetector {
 
         // Publish anomaly events
         for anomaly in anomalies {
+            if let Some(feedback) = &self.feedback {
+                if let Err(e) = feedback.record_detection(anomaly, self.tenant.tenant_id()).await {
+                    warn!(error = %e, anomaly_id = %anomaly.id, "Failed to record anomaly for feedback");
+                }
+            }
             self.event_bus.publish(
                 Event {
                     id: format!("event_{}", fastrand::u64(..)),
@@ -321,7 +340,8 @@ pub struct SystemData {
 #[instrument(skip(config))]
 pub async fn start_anomaly_detection(config: AnomalyConfig) -> Result<Arc<AnomalyDetector>, GuardianError> {
     info!("Starting anomaly detection service");
-    
+    capabilities().require(ANOMALY_DETECTION)?;
+
     // Initialize required components
     let metrics = Arc::new(metrics::MetricsCollector::new());
     let inference_engine = Arc::new(InferenceEngine::new(
@@ -333,13 +353,19 @@ pub async fn start_anomaly_detection(config: AnomalyConfig) -> Result<Arc<Anomal
     let event_bus = Arc::new(EventBus::new(metrics.clone()));
     let system_state = Arc::new(SystemState::new(event_bus.clone()).await?);
 
-    let detector = Arc::new(AnomalyDetector::new(
+    let mut detector = AnomalyDetector::new(
         inference_engine,
         event_bus,
         system_state,
         metrics,
         config,
-    ));
+    );
+    // Record detections so analysts can label them
+    match anomaly_feedback() {
+        Some(feedback) => detector = detector.with_feedback(feedback, TenantContext::default()),
+        None => warn!("Detection feedback not initialized, detections will not be recorded for labeling"),
+    }
+    let detector = Arc::new(detector);
 
     info!("Anomaly detection service started successfully");
     Ok(detector)
@@ -0,0 +1,296 @@
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use metrics::counter;
+use once_cell::sync::OnceCell;
+use serde::{Deserialize, Serialize};
+use tracing::{info, instrument};
+
+use crate::core::guardian::TenantId;
+use crate::ml::model_registry::{FeedbackLabel, ModelRegistry};
+use crate::security::anomaly_detection::Anomaly;
+use crate::storage::event_store::{Event as StoredEvent, EventQuery, EventStore};
+use crate::utils::error::GuardianError;
+use crate::utils::ids::{next_id, IdKind};
+
+// Constants for anomaly feedback
+const DETECTION_EVENT_TYPE: &str = "anomaly_detection";
+const FEEDBACK_EVENT_TYPE: &str = "anomaly_feedback";
+const MAX_COMMENT_LEN: usize = 1024;
+
+static ANOMALY_FEEDBACK: OnceCell<Arc<AnomalyFeedback>> = OnceCell::new();
+
+/// Returns the feedback service, once initialized at startup
+pub fn anomaly_feedback() -> Option<Arc<AnomalyFeedback>> {
+    ANOMALY_FEEDBACK.get().cloned()
+}
+
+/// Creates the feedback service over the daemon's event store; later calls return the first service
+pub fn init_anomaly_feedback(event_store: Arc<EventStore>, registry: Arc<ModelRegistry>) -> Arc<AnomalyFeedback> {
+    Arc::clone(ANOMALY_FEEDBACK.get_or_init(|| Arc::new(AnomalyFeedback::new(event_store, registry))))
+}
+
+/// Analyst feedback on a detection, as submitted
+#[derive(Debug, Clone)]
+pub struct FeedbackRequest {
+    /// Detection being judged; absent for false negatives, which describe missed activity
+    pub detection_id: Option<String>,
+    pub label: FeedbackLabel,
+    pub tenant_id: TenantId,
+    pub analyst: String,
+    pub comment: Option<String>,
+    /// System data the detector should have flagged, required for false negatives
+    pub sample: Option<serde_json::Value>,
+}
+
+/// Feedback as persisted, carrying its features so the dataset export needs no join
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DetectionFeedback {
+    pub feedback_id: String,
+    pub detection_id: Option<String>,
+    pub label: FeedbackLabel,
+    pub tenant_id: TenantId,
+    pub analyst: String,
+    pub comment: Option<String>,
+    pub anomaly_type: String,
+    pub features: serde_json::Value,
+    pub model_version: Option<String>,
+    pub submitted_at: u64,
+}
+
+/// One row of the labeled training dataset
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LabeledSample {
+    pub features: serde_json::Value,
+    pub anomalous: bool,
+    pub anomaly_type: String,
+    pub detection_id: Option<String>,
+    pub model_version: Option<String>,
+    pub labeled_by: String,
+    pub labeled_at: u64,
+}
+
+/// A detection as recorded for later feedback
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RecordedDetection {
+    anomaly: Anomaly,
+    tenant_id: TenantId,
+    model_version: Option<String>,
+}
+
+/// Persists detections and analyst verdicts on them, feeding model metrics and retraining
+#[derive(Debug)]
+pub struct AnomalyFeedback {
+    event_store: Arc<EventStore>,
+    registry: Arc<ModelRegistry>,
+}
+
+impl AnomalyFeedback {
+    /// Creates a feedback service persisting to the event store
+    pub fn new(event_store: Arc<EventStore>, registry: Arc<ModelRegistry>) -> Self {
+        Self { event_store, registry }
+    }
+
+    /// Records a detection so analysts can later label it
+    pub async fn record_detection(&self, anomaly: &Anomaly, tenant_id: &TenantId) -> Result<(), GuardianError> {
+        let detection = RecordedDetection {
+            anomaly: anomaly.clone(),
+            tenant_id: tenant_id.clone(),
+            model_version: self.registry.active_version().await,
+        };
+        self.event_store
+            .store_event(stored_event(DETECTION_EVENT_TYPE, to_payload(&detection)?))
+            .await
+    }
+
+    /// Validates and persists feedback, then counts it against the responsible model version
+    #[instrument(skip(self, request), fields(detection_id = ?request.detection_id, label = ?request.label))]
+    pub async fn submit(&self, request: FeedbackRequest) -> Result<DetectionFeedback, GuardianError> {
+        if request.comment.as_ref().map_or(false, |c| c.len() > MAX_COMMENT_LEN) {
+            return Err(feedback_error(format!("Comment exceeds {} bytes", MAX_COMMENT_LEN)));
+        }
+
+        let (anomaly_type, features, model_version) = match (&request.label, &request.detection_id) {
+            (FeedbackLabel::FalseNegative, _) => {
+                let sample = request
+                    .sample
+                    .clone()
+                    .ok_or_else(|| feedback_error("False negatives require the missed sample".into()))?;
+                (String::from("missed"), sample, self.registry.active_version().await)
+            }
+            (_, Some(detection_id)) => {
+                let detection = self.find_detection(detection_id).await?;
+                if detection.tenant_id != request.tenant_id {
+                    return Err(feedback_error(format!("Detection {} not found", detection_id)));
+                }
+                (detection.anomaly.anomaly_type, detection.anomaly.context, detection.model_version)
+            }
+            (_, None) => return Err(feedback_error("Detection ID required".into())),
+        };
+
+        let feedback = DetectionFeedback {
+            feedback_id: next_id(IdKind::Event).to_string(),
+            detection_id: request.detection_id,
+            label: request.label,
+            tenant_id: request.tenant_id,
+            analyst: request.analyst,
+            comment: request.comment,
+            anomaly_type,
+            features,
+            model_version,
+            submitted_at: unix_now(),
+        };
+        self.event_store
+            .store_event(stored_event(FEEDBACK_EVENT_TYPE, to_payload(&feedback)?))
+            .await?;
+
+        if let Some(version) = &feedback.model_version {
+            self.registry.record_feedback(version, feedback.label).await;
+        }
+        counter!("guardian.anomaly.feedback", 1, "label" => format!("{:?}", feedback.label));
+        info!(
+            target: "SECURITY-AUDIT",
+            feedback_id = %feedback.feedback_id,
+            detection_id = ?feedback.detection_id,
+            label = ?feedback.label,
+            analyst = %feedback.analyst,
+            tenant = %feedback.tenant_id,
+            "Detection feedback recorded"
+        );
+        Ok(feedback)
+    }
+
+    /// Exports the tenant's feedback submitted since `since` (Unix seconds) as labeled samples
+    pub async fn export_dataset(&self, tenant_id: &TenantId, since: Option<u64>) -> Result<Vec<LabeledSample>, GuardianError> {
+        let events = self
+            .event_store
+            .retrieve_events(EventQuery {
+                start_time: since,
+                end_time: None,
+                event_type: Some(FEEDBACK_EVENT_TYPE.to_string()),
+                limit: None,
+            })
+            .await?;
+
+        let feedback = events
+            .into_iter()
+            .filter_map(|event| serde_json::from_value::<DetectionFeedback>(event.payload).ok())
+            .filter(|f| &f.tenant_id == tenant_id)
+            .collect();
+        Ok(labeled_samples(feedback))
+    }
+
+    async fn find_detection(&self, detection_id: &str) -> Result<RecordedDetection, GuardianError> {
+        self.event_store
+            .retrieve_events(EventQuery {
+                start_time: None,
+                end_time: None,
+                event_type: Some(DETECTION_EVENT_TYPE.to_string()),
+                limit: None,
+            })
+            .await?
+            .into_iter()
+            .filter_map(|event| serde_json::from_value::<RecordedDetection>(event.payload).ok())
+            .find(|d| d.anomaly.id == detection_id)
+            .ok_or_else(|| feedback_error(format!("Detection {} not found", detection_id)))
+    }
+}
+
+/// Turns feedback into training rows; a later verdict on the same detection replaces an earlier one
+fn labeled_samples(mut feedback: Vec<DetectionFeedback>) -> Vec<LabeledSample> {
+    feedback.sort_by_key(|f| f.submitted_at);
+
+    let mut latest: HashMap<String, usize> = HashMap::new();
+    let mut samples: Vec<LabeledSample> = Vec::new();
+    for f in feedback {
+        let existing = f.detection_id.as_ref().and_then(|id| latest.get(id).copied());
+        if let (None, Some(id)) = (existing, &f.detection_id) {
+            latest.insert(id.clone(), samples.len());
+        }
+        let sample = LabeledSample {
+            anomalous: f.label.is_anomalous(),
+            features: f.features,
+            anomaly_type: f.anomaly_type,
+            detection_id: f.detection_id,
+            model_version: f.model_version,
+            labeled_by: f.analyst,
+            labeled_at: f.submitted_at,
+        };
+        match existing {
+            Some(index) => samples[index] = sample,
+            None => samples.push(sample),
+        }
+    }
+    samples
+}
+
+fn stored_event(event_type: &str, payload: serde_json::Value) -> StoredEvent {
+    StoredEvent {
+        id: next_id(IdKind::Event).to_string(),
+        timestamp: unix_now(),
+        event_type: event_type.to_string(),
+        payload,
+        integrity_hash: String::new(),
+    }
+}
+
+fn to_payload<T: Serialize>(value: &T) -> Result<serde_json::Value, GuardianError> {
+    serde_json::to_value(value).map_err(|e| feedback_error(format!("Failed to serialize feedback: {}", e)))
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or_default()
+}
+
+fn feedback_error(context: String) -> GuardianError {
+    GuardianError::ValidationError {
+        context,
+        source: None,
+        severity: crate::utils::error::ErrorSeverity::Low,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: crate::utils::error::ErrorCategory::Validation,
+        retry_count: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::guardian::TenantContext;
+
+    fn feedback(detection_id: Option<&str>, label: FeedbackLabel, at: u64) -> DetectionFeedback {
+        DetectionFeedback {
+            feedback_id: next_id(IdKind::Event).to_string(),
+            detection_id: detection_id.map(String::from),
+            label,
+            tenant_id: TenantContext::default().tenant_id().clone(),
+            analyst: "alice".into(),
+            comment: None,
+            anomaly_type: "system_behavior".into(),
+            features: serde_json::json!({ "cpu": at }),
+            model_version: Some("v1.0.0".into()),
+            submitted_at: at,
+        }
+    }
+
+    #[test]
+    fn test_latest_verdict_wins() {
+        let samples = labeled_samples(vec![
+            feedback(Some("anomaly_2"), FeedbackLabel::FalsePositive, 30),
+            feedback(Some("anomaly_1"), FeedbackLabel::TruePositive, 10),
+            feedback(None, FeedbackLabel::FalseNegative, 20),
+            feedback(Some("anomaly_1"), FeedbackLabel::FalsePositive, 40),
+        ]);
+
+        assert_eq!(samples.len(), 3);
+        assert_eq!(samples[0].detection_id.as_deref(), Some("anomaly_1"));
+        assert!(!samples[0].anomalous);
+        assert_eq!(samples[0].labeled_at, 40);
+        assert!(samples[1].anomalous && samples[1].detection_id.is_none());
+        assert!(!samples[2].anomalous);
+    }
+}
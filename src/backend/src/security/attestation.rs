@@ -0,0 +1,358 @@
+use std::{collections::BTreeMap, path::Path, sync::Arc};
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use chrono::{DateTime, Utc};
+use metrics::counter;
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use ring::signature::{self, Ed25519KeyPair, KeyPair};
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::config::security_config::AttestationConfig;
+use crate::config::AppConfig;
+use crate::utils::error::{GuardianError, SecurityError};
+
+// Constants for remote attestation
+pub const ATTESTATION_ALGORITHM: &str = "ed25519";
+const MIN_NONCE_LEN: usize = 16;
+const MAX_NONCE_LEN: usize = 64;
+const TPM_DEVICE: &str = "/dev/tpm0";
+const SECURE_BOOT_EFIVAR: &str = "/sys/firmware/efi/efivars/SecureBoot-8be4df61-93ca-11d2-aa0d-00e098032b8c";
+
+static ATTESTOR: Lazy<Arc<Attestor>> = Lazy::new(|| Arc::new(Attestor::new(ephemeral_key(), AttestationConfig::default())));
+
+/// Returns the process-wide attestor
+pub fn attestor() -> Arc<Attestor> {
+    Arc::clone(&ATTESTOR)
+}
+
+/// Loads the attestation key and measures the running binary and configuration
+pub fn init_attestation(config: &AppConfig) -> Result<(), GuardianError> {
+    let settings = &config.security_config.attestation;
+    let key = match &settings.signing_key_path {
+        Some(path) => load_signing_key(Path::new(path))?,
+        None => {
+            warn!("No attestation signing key configured, evidence is signed with an ephemeral key");
+            ephemeral_key()
+        }
+    };
+
+    let attestor = attestor();
+    attestor.configure(key, settings.clone());
+    attestor.record_binary()?;
+    attestor.record_config(config);
+    info!(key_id = %attestor.key_id(), tpm = settings.tpm_key_context.is_some(), "Remote attestation initialized");
+    Ok(())
+}
+
+/// Measurement of an active model
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModelMeasurement {
+    pub version: String,
+    pub hash: String,
+}
+
+/// Secure boot as configured and as reported by the firmware
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SecureBootState {
+    pub required: bool,
+    pub tpm_required: bool,
+    /// `None` when the firmware does not expose the state
+    pub firmware_enabled: Option<bool>,
+}
+
+/// Digests of everything a verifier pins
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Measurements {
+    pub binary_path: String,
+    pub binary_sha256: String,
+    pub config_sha256: String,
+    /// Active version of each model, by model name
+    pub models: BTreeMap<String, ModelMeasurement>,
+}
+
+/// TPM quote over the PCRs, qualified with the digest of the evidence it accompanies
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TpmQuote {
+    pub pcr_selection: String,
+    /// Base64 TPMS_ATTEST structure
+    pub message: String,
+    /// Base64 TPMT_SIGNATURE over the message
+    pub signature: String,
+}
+
+/// Signed statement of what is running
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttestationEvidence {
+    /// Base64 verifier nonce, proving freshness
+    pub nonce: String,
+    pub issued_at: DateTime<Utc>,
+    pub host: String,
+    pub guardian_version: String,
+    pub measurements: Measurements,
+    pub secure_boot: SecureBootState,
+    pub tpm_quote: Option<TpmQuote>,
+}
+
+/// Evidence with its detached signature
+#[derive(Debug, Clone)]
+pub struct SignedAttestation {
+    pub evidence: AttestationEvidence,
+    /// Exact bytes that were signed, JSON-encoded evidence
+    pub evidence_bytes: Vec<u8>,
+    pub signature: Vec<u8>,
+    pub key_id: String,
+}
+
+/// Produces signed attestation evidence of the running Guardian
+pub struct Attestor {
+    key: RwLock<Arc<Ed25519KeyPair>>,
+    config: RwLock<AttestationConfig>,
+    secure_boot: RwLock<SecureBootState>,
+    measurements: RwLock<Measurements>,
+}
+
+impl std::fmt::Debug for Attestor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Attestor").field("key_id", &self.key_id()).finish()
+    }
+}
+
+impl Attestor {
+    pub fn new(key: Ed25519KeyPair, config: AttestationConfig) -> Self {
+        Self {
+            key: RwLock::new(Arc::new(key)),
+            config: RwLock::new(config),
+            secure_boot: RwLock::new(SecureBootState {
+                required: false,
+                tpm_required: false,
+                firmware_enabled: None,
+            }),
+            measurements: RwLock::new(Measurements::default()),
+        }
+    }
+
+    /// Replaces the signing key and TPM settings
+    pub fn configure(&self, key: Ed25519KeyPair, config: AttestationConfig) {
+        *self.key.write() = Arc::new(key);
+        *self.config.write() = config;
+    }
+
+    /// Public key verifiers pin to check evidence signatures
+    pub fn public_key(&self) -> Vec<u8> {
+        self.key.read().public_key().as_ref().to_vec()
+    }
+
+    /// Fingerprint of the signing key
+    pub fn key_id(&self) -> String {
+        key_id(&self.public_key())
+    }
+
+    /// Hashes the executable this process was started from
+    pub fn record_binary(&self) -> Result<(), GuardianError> {
+        let path = std::env::current_exe()
+            .map_err(|e| attestation_error("Failed to locate the running binary".into(), Some(Box::new(e))))?;
+        let binary = std::fs::read(&path)
+            .map_err(|e| attestation_error(format!("Failed to read {}", path.display()), Some(Box::new(e))))?;
+
+        let mut measurements = self.measurements.write();
+        measurements.binary_path = path.display().to_string();
+        measurements.binary_sha256 = sha256_hex(&binary);
+        Ok(())
+    }
+
+    /// Records the digest of the configuration in effect; call again after a reload
+    pub fn record_config(&self, config: &AppConfig) {
+        // Through Value so maps serialize in a stable key order
+        let digest = serde_json::to_value(config)
+            .and_then(|value| serde_json::to_vec(&value))
+            .map(|bytes| sha256_hex(&bytes))
+            .unwrap_or_default();
+        self.measurements.write().config_sha256 = digest;
+        *self.secure_boot.write() = SecureBootState {
+            required: config.security_settings.enable_secure_boot,
+            tpm_required: config.security_settings.tpm_required,
+            firmware_enabled: firmware_secure_boot(),
+        };
+    }
+
+    /// Records the version a model was switched to
+    pub fn record_model(&self, name: &str, version: &str, hash: &str) {
+        self.measurements.write().models.insert(
+            name.to_string(),
+            ModelMeasurement {
+                version: version.to_string(),
+                hash: hash.to_string(),
+            },
+        );
+    }
+
+    /// Signs evidence of the current measurements bound to the verifier's nonce
+    ///
+    /// A TPM quote is attached when a TPM attestation key is configured; when the
+    /// configuration requires a TPM, evidence is refused rather than issued without one.
+    pub async fn attest(&self, nonce: &[u8]) -> Result<SignedAttestation, GuardianError> {
+        if !(MIN_NONCE_LEN..=MAX_NONCE_LEN).contains(&nonce.len()) {
+            return Err(attestation_error(
+                format!("Attestation nonce must be {} to {} bytes", MIN_NONCE_LEN, MAX_NONCE_LEN),
+                None,
+            ));
+        }
+
+        let mut evidence = AttestationEvidence {
+            nonce: BASE64.encode(nonce),
+            issued_at: Utc::now(),
+            host: sys_info::hostname().unwrap_or_else(|_| "-".to_string()),
+            guardian_version: env!("CARGO_PKG_VERSION").to_string(),
+            measurements: self.measurements.read().clone(),
+            secure_boot: self.secure_boot.read().clone(),
+            tpm_quote: None,
+        };
+
+        let config = self.config.read().clone();
+        if let Some(context) = &config.tpm_key_context {
+            let qualifying = ring::digest::digest(&ring::digest::SHA256, &serde_json::to_vec(&evidence)?);
+            evidence.tpm_quote = Some(tpm_quote(context, &config.pcr_selection, qualifying.as_ref()).await?);
+        } else if evidence.secure_boot.tpm_required {
+            counter!("guardian.attestation.refused", 1);
+            return Err(attestation_error("TPM attestation is required but no TPM key is configured".into(), None));
+        }
+
+        let evidence_bytes = serde_json::to_vec(&evidence)?;
+        let key = Arc::clone(&self.key.read());
+        let signature = key.sign(&evidence_bytes).as_ref().to_vec();
+        counter!("guardian.attestation.issued", 1, "tpm" => evidence.tpm_quote.is_some().to_string());
+
+        Ok(SignedAttestation {
+            evidence,
+            evidence_bytes,
+            signature,
+            key_id: key_id(key.public_key().as_ref()),
+        })
+    }
+}
+
+/// Checks evidence against a pinned attestation key and the nonce the verifier sent
+pub fn verify_attestation(
+    public_key: &[u8],
+    evidence_bytes: &[u8],
+    signature: &[u8],
+    nonce: &[u8],
+) -> Result<AttestationEvidence, GuardianError> {
+    signature::UnparsedPublicKey::new(&signature::ED25519, public_key)
+        .verify(evidence_bytes, signature)
+        .map_err(|_| attestation_error("Attestation signature does not verify".into(), None))?;
+
+    let evidence: AttestationEvidence = serde_json::from_slice(evidence_bytes)?;
+    if evidence.nonce != BASE64.encode(nonce) {
+        return Err(attestation_error("Attestation nonce does not match".into(), None));
+    }
+    Ok(evidence)
+}
+
+/// Quotes the PCRs with tpm2-tools
+async fn tpm_quote(key_context: &str, pcr_selection: &str, qualifying: &[u8]) -> Result<TpmQuote, GuardianError> {
+    if !Path::new(TPM_DEVICE).exists() {
+        return Err(attestation_error(format!("TPM device {} not present", TPM_DEVICE), None));
+    }
+
+    let dir = tempfile::tempdir()
+        .map_err(|e| attestation_error("Failed to create TPM quote directory".into(), Some(Box::new(e))))?;
+    let message_path = dir.path().join("quote.msg");
+    let signature_path = dir.path().join("quote.sig");
+    let qualifying: String = qualifying.iter().map(|b| format!("{:02x}", b)).collect();
+
+    let output = tokio::process::Command::new("tpm2_quote")
+        .args(["--key-context", key_context, "--pcr-list", pcr_selection, "--qualification", &qualifying])
+        .arg("--message")
+        .arg(&message_path)
+        .arg("--signature")
+        .arg(&signature_path)
+        .kill_on_drop(true)
+        .output()
+        .await
+        .map_err(|e| attestation_error("Failed to run tpm2_quote".into(), Some(Box::new(e))))?;
+    if !output.status.success() {
+        return Err(attestation_error(
+            format!("tpm2_quote failed: {}", String::from_utf8_lossy(&output.stderr).trim()),
+            None,
+        ));
+    }
+
+    let read = |path: &Path| {
+        std::fs::read(path).map_err(|e| attestation_error("Failed to read TPM quote".into(), Some(Box::new(e))))
+    };
+    Ok(TpmQuote {
+        pcr_selection: pcr_selection.to_string(),
+        message: BASE64.encode(read(&message_path)?),
+        signature: BASE64.encode(read(&signature_path)?),
+    })
+}
+
+/// Reads the UEFI SecureBoot variable; its last byte is 1 when enforcing
+fn firmware_secure_boot() -> Option<bool> {
+    std::fs::read(SECURE_BOOT_EFIVAR).ok().and_then(|value| value.last().map(|b| *b == 1))
+}
+
+fn load_signing_key(path: &Path) -> Result<Ed25519KeyPair, GuardianError> {
+    let pkcs8 = std::fs::read(path).map_err(|e| {
+        attestation_error(format!("Failed to read attestation key {}", path.display()), Some(Box::new(e)))
+    })?;
+    Ed25519KeyPair::from_pkcs8(&pkcs8)
+        .map_err(|_| attestation_error(format!("{} is not a PKCS#8 Ed25519 key", path.display()), None))
+}
+
+fn ephemeral_key() -> Ed25519KeyPair {
+    let rng = ring::rand::SystemRandom::new();
+    let pkcs8 = Ed25519KeyPair::generate_pkcs8(&rng).expect("system RNG unavailable");
+    Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).expect("generated key is valid PKCS#8")
+}
+
+fn key_id(public_key: &[u8]) -> String {
+    format!("{}:{}", ATTESTATION_ALGORITHM, &sha256_hex(public_key)[..16])
+}
+
+fn sha256_hex(data: &[u8]) -> String {
+    ring::digest::digest(&ring::digest::SHA256, data)
+        .as_ref()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+fn attestation_error(context: String, source: Option<Box<dyn std::error::Error + Send + Sync>>) -> GuardianError {
+    SecurityError {
+        context,
+        source,
+        severity: crate::utils::error::ErrorSeverity::High,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: crate::utils::error::ErrorCategory::Security,
+        retry_count: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_attestation_binds_nonce_and_measurements() {
+        let attestor = Attestor::new(ephemeral_key(), AttestationConfig::default());
+        attestor.record_model("threat_detector", "v1.2.0", "abc123");
+
+        let nonce = [7u8; 32];
+        let signed = attestor.attest(&nonce).await.unwrap();
+        let evidence =
+            verify_attestation(&attestor.public_key(), &signed.evidence_bytes, &signed.signature, &nonce).unwrap();
+        assert_eq!(evidence.measurements.models["threat_detector"].version, "v1.2.0");
+        assert!(evidence.tpm_quote.is_none());
+
+        assert!(verify_attestation(&attestor.public_key(), &signed.evidence_bytes, &signed.signature, &[8u8; 32]).is_err());
+        let mut tampered = signed.evidence_bytes.clone();
+        tampered[0] ^= 1;
+        assert!(verify_attestation(&attestor.public_key(), &tampered, &signed.signature, &nonce).is_err());
+        assert!(attestor.attest(&[1u8; 4]).await.is_err());
+    }
+}
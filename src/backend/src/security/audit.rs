@@ -1,25 +1,40 @@
 use chrono::{DateTime, Utc};
+use once_cell::sync::OnceCell;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::{
     collections::HashMap,
+    path::{Path, PathBuf},
     sync::{Arc, Mutex},
     time::Duration,
 };
-use tracing::{error, info, warn, instrument};
+use tracing::{debug, error, info, warn, instrument};
 use uuid::Uuid;
 
+use crate::config::security_config::{AuditConfig, AuditSamplingConfig};
+use crate::core::capabilities::{capabilities, Capability};
 use crate::utils::error::{GuardianError, SecurityError};
 use crate::utils::logging::{LogConfig, init_logging};
+use crate::utils::ids::{next_uuid, IdKind};
+use crate::security::audit_chain::{audit_chain, ChainVerification};
+use crate::security::audit_sampling::AuditSampler;
+use crate::security::crypto::CryptoManager;
+use crate::security::siem_export::SiemExporter;
 
 // Core audit constants
 const MAX_AUDIT_EVENT_SIZE: usize = 4096;
 const AUDIT_RETENTION_DAYS: u32 = 90;
 const MAX_RETRY_ATTEMPTS: u32 = 3;
-const AUDIT_SAMPLING_RATE: f64 = 1.0;
 const CRITICAL_ALERT_THRESHOLD: u32 = 100;
+const MAX_AUDIT_STORAGE: u64 = 1024 * 1024 * 1024;
+
+static AUDIT_LOGGER: OnceCell<Arc<AuditLogger>> = OnceCell::new();
+
+/// Version of the audit event format, bumped on incompatible changes
+pub const AUDIT_EVENT_SCHEMA_VERSION: u32 = 1;
 
 /// Security levels for audit events
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub enum SecurityLevel {
     Critical,
     High,
@@ -27,8 +42,24 @@ pub enum SecurityLevel {
     Low,
 }
 
+impl SecurityLevel {
+    fn rank(&self) -> u8 {
+        match self {
+            SecurityLevel::Critical => 3,
+            SecurityLevel::High => 2,
+            SecurityLevel::Medium => 1,
+            SecurityLevel::Low => 0,
+        }
+    }
+
+    /// Returns whether this level is as severe as `other` or more
+    pub fn is_at_least(&self, other: &SecurityLevel) -> bool {
+        self.rank() >= other.rank()
+    }
+}
+
 /// Represents a security audit event with comprehensive metadata
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct AuditEvent {
     id: Uuid,
     event_type: String,
@@ -38,6 +69,24 @@ pub struct AuditEvent {
     data: serde_json::Value,
     correlation_id: Option<String>,
     tags: HashMap<String, String>,
+    /// Position in the audit chain, assigned when the event is recorded
+    #[serde(default)]
+    sequence: u64,
+    /// Hash of the event recorded before this one
+    #[serde(default)]
+    prev_hash: Option<String>,
+    /// Rate the event was sampled at; each recorded event stands for `1 / sampling_rate`
+    /// events. Omitted when 1 so events recorded in full hash as before sampling existed.
+    #[serde(default = "full_sampling_rate", skip_serializing_if = "is_full_sampling_rate")]
+    sampling_rate: f64,
+}
+
+fn full_sampling_rate() -> f64 {
+    1.0
+}
+
+fn is_full_sampling_rate(rate: &f64) -> bool {
+    *rate >= 1.0
 }
 
 impl AuditEvent {
@@ -49,7 +98,7 @@ impl AuditEvent {
         correlation_id: Option<String>,
     ) -> Self {
         Self {
-            id: Uuid::new_v4(),
+            id: next_uuid(IdKind::Event),
             event_type,
             timestamp: Utc::now(),
             source,
@@ -57,6 +106,9 @@ impl AuditEvent {
             data: serde_json::Value::Null,
             correlation_id,
             tags: HashMap::new(),
+            sequence: 0,
+            prev_hash: None,
+            sampling_rate: 1.0,
         }
     }
 
@@ -69,7 +121,7 @@ impl AuditEvent {
                 source: Some(Box::new(e)),
                 severity: crate::utils::error::ErrorSeverity::High,
                 timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id: Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: crate::utils::error::ErrorCategory::Security,
                 retry_count: 0,
             })?
@@ -81,7 +133,7 @@ impl AuditEvent {
                 source: None,
                 severity: crate::utils::error::ErrorSeverity::Medium,
                 timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id: Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: crate::utils::error::ErrorCategory::Security,
                 retry_count: 0,
             });
@@ -96,6 +148,71 @@ impl AuditEvent {
         self.tags = tags;
         self
     }
+
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    pub fn event_type(&self) -> &str {
+        &self.event_type
+    }
+
+    pub fn timestamp(&self) -> DateTime<Utc> {
+        self.timestamp
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    pub fn severity(&self) -> &SecurityLevel {
+        &self.severity
+    }
+
+    pub fn data(&self) -> &serde_json::Value {
+        &self.data
+    }
+
+    pub fn correlation_id(&self) -> Option<&str> {
+        self.correlation_id.as_deref()
+    }
+
+    pub fn tags(&self) -> &HashMap<String, String> {
+        &self.tags
+    }
+
+    pub fn sequence(&self) -> u64 {
+        self.sequence
+    }
+
+    pub fn prev_hash(&self) -> Option<&str> {
+        self.prev_hash.as_deref()
+    }
+
+    pub fn sampling_rate(&self) -> f64 {
+        self.sampling_rate
+    }
+
+    /// Number of events this recorded event stands for, to re-weight sampled counts
+    pub fn weight(&self) -> f64 {
+        1.0 / self.sampling_rate
+    }
+
+    /// Links the event into the audit chain after the event hashed as `prev_hash`
+    pub(crate) fn link(&mut self, sequence: u64, prev_hash: Option<String>) {
+        self.sequence = sequence;
+        self.prev_hash = prev_hash;
+    }
+
+    /// SHA-256 over the event, including its chain link, in a stable key order
+    pub fn chain_hash(&self) -> Result<String, GuardianError> {
+        let canonical = serde_json::to_value(self).and_then(|value| serde_json::to_vec(&value))?;
+        Ok(ring::digest::digest(&ring::digest::SHA256, &canonical)
+            .as_ref()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect())
+    }
 }
 
 /// Statistics for audit logging operations
@@ -116,14 +233,45 @@ struct RetentionPolicy {
     compression_enabled: bool,
 }
 
+/// Returns the process-wide audit logger, once initialized
+pub fn audit_logger() -> Option<Arc<AuditLogger>> {
+    AUDIT_LOGGER.get().cloned()
+}
+
+/// Builds the process-wide audit logger, sampling events as configured and forwarding them to
+/// the configured SIEMs
+pub fn init_audit_logger(config: &AuditConfig) -> Result<Arc<AuditLogger>, GuardianError> {
+    AUDIT_LOGGER
+        .get_or_try_init(|| {
+            let retention_policy = RetentionPolicy {
+                retention_days: config.log_retention_days,
+                max_storage_size: MAX_AUDIT_STORAGE,
+                compression_enabled: true,
+            };
+            let log_config = LogConfig::default();
+            // Storage pressure is measured on the volume the audit log is written to
+            let storage_path = Path::new(log_config.security_audit_path()).parent().map(Path::to_path_buf);
+            let mut logger = AuditLogger::new(log_config, retention_policy, AlertConfig::default())?
+                .with_sampling(config.sampling.clone(), storage_path);
+            if !config.siem_targets.is_empty() {
+                logger = logger.with_siem_exporter(SiemExporter::start(&config.siem_targets)?);
+            }
+            Ok(Arc::new(logger))
+        })
+        .cloned()
+}
+
 /// Core audit logging functionality
 pub struct AuditLogger {
     config: LogConfig,
     stats: Arc<Mutex<AuditStats>>,
-    freebsd_audit: Arc<Mutex<FreeBSDAudit>>,
-    metrics: Arc<Mutex<MetricsCollector>>,
+    /// Absent while audit logging is disabled; events then go to the SECURITY-AUDIT log target
+    freebsd_audit: Option<Arc<Mutex<FreeBSDAudit>>>,
+    metrics: Arc<MetricsCollector>,
     alert_manager: AlertManager,
     retention_policy: RetentionPolicy,
+    siem: Option<Arc<SiemExporter>>,
+    sampler: AuditSampler,
 }
 
 impl AuditLogger {
@@ -136,8 +284,13 @@ impl AuditLogger {
         // Initialize logging subsystem
         init_logging(config.clone())?;
 
-        // Initialize FreeBSD audit subsystem
-        let freebsd_audit = FreeBSDAudit::new()?;
+        // Initialize FreeBSD audit subsystem unless audit logging is disabled
+        let freebsd_audit = if capabilities().is_enabled(Capability::Audit) {
+            Some(Arc::new(Mutex::new(FreeBSDAudit::new()?)))
+        } else {
+            warn!("Audit logging disabled, audit events go to the SECURITY-AUDIT log target only");
+            None
+        };
 
         // Initialize metrics collector
         let metrics = MetricsCollector::new(MetricsConfig {
@@ -157,19 +310,40 @@ impl AuditLogger {
                 critical_events_count: 0,
                 storage_usage: 0.0,
             })),
-            freebsd_audit: Arc::new(Mutex::new(freebsd_audit)),
-            metrics: Arc::new(Mutex::new(metrics)),
+            freebsd_audit,
+            metrics: Arc::new(metrics),
             alert_manager: AlertManager::new(alert_config)?,
             retention_policy,
+            siem: None,
+            sampler: AuditSampler::new(Default::default(), None),
         })
     }
 
+    /// Forwards every recorded event to external SIEMs as well
+    pub fn with_siem_exporter(mut self, exporter: Arc<SiemExporter>) -> Self {
+        self.siem = Some(exporter);
+        self
+    }
+
+    /// Samples events with the given settings, measuring storage pressure on the volume of
+    /// `storage_path`
+    pub fn with_sampling(mut self, config: AuditSamplingConfig, storage_path: Option<PathBuf>) -> Self {
+        self.sampler = AuditSampler::new(config, storage_path);
+        self
+    }
+
     /// Records an audit event securely
     #[instrument(skip(self, event))]
-    pub async fn record_event(&self, event: AuditEvent) -> Result<(), GuardianError> {
-        // Apply sampling if configured
-        if rand::random::<f64>() > AUDIT_SAMPLING_RATE {
+    pub async fn record_event(&self, mut event: AuditEvent) -> Result<(), GuardianError> {
+        // Sample before chaining, so the chain covers the recorded rate
+        let Some(rate) = self.sampler.sample(&event) else {
             return Ok(());
+        };
+        event.sampling_rate = rate;
+
+        // Chain the event first so every copy written below carries its link
+        if let Some(chain) = audit_chain() {
+            chain.append(&mut event)?;
         }
 
         // Update statistics
@@ -178,43 +352,52 @@ impl AuditLogger {
             source: Some(Box::new(e)),
             severity: crate::utils::error::ErrorSeverity::High,
             timestamp: time::OffsetDateTime::now_utc(),
-            correlation_id: Uuid::new_v4(),
+            correlation_id: crate::utils::correlation::current_or_new(),
             category: crate::utils::error::ErrorCategory::Security,
             retry_count: 0,
         })?;
 
         stats.events_processed += 1;
         stats.last_event_timestamp = event.timestamp;
+        stats.storage_usage = self.sampler.storage_usage();
 
         if event.severity == SecurityLevel::Critical {
             stats.critical_events_count += 1;
         }
 
-        // Write to FreeBSD audit subsystem
-        let mut freebsd_audit = self.freebsd_audit.lock().map_err(|e| GuardianError::SecurityError {
-            context: "Failed to lock FreeBSD audit".into(),
-            source: Some(Box::new(e)),
-            severity: crate::utils::error::ErrorSeverity::High,
-            timestamp: time::OffsetDateTime::now_utc(),
-            correlation_id: Uuid::new_v4(),
-            category: crate::utils::error::ErrorCategory::Security,
-            retry_count: 0,
-        })?;
+        // Write to FreeBSD audit subsystem, or the log target when it is disabled
+        match &self.freebsd_audit {
+            Some(freebsd_audit) => {
+                let mut freebsd_audit = freebsd_audit.lock().map_err(|e| GuardianError::SecurityError {
+                    context: "Failed to lock FreeBSD audit".into(),
+                    source: Some(Box::new(e)),
+                    severity: crate::utils::error::ErrorSeverity::High,
+                    timestamp: time::OffsetDateTime::now_utc(),
+                    correlation_id: crate::utils::correlation::current_or_new(),
+                    category: crate::utils::error::ErrorCategory::Security,
+                    retry_count: 0,
+                })?;
+                freebsd_audit.write_event(&event)?;
+            }
+            None => info!(
+                target: "SECURITY-AUDIT",
+                event_id = %event.id,
+                event_type = %event.event_type,
+                source = %event.source,
+                severity = ?event.severity,
+                correlation_id = ?event.correlation_id,
+                data = %event.data,
+                "Audit event"
+            ),
+        }
 
-        freebsd_audit.write_event(&event)?;
+        // Forward to SIEMs without waiting on the network
+        if let Some(siem) = &self.siem {
+            siem.export(&event);
+        }
 
         // Record metrics
-        let mut metrics = self.metrics.lock().map_err(|e| GuardianError::SecurityError {
-            context: "Failed to lock metrics collector".into(),
-            source: Some(Box::new(e)),
-            severity: crate::utils::error::ErrorSeverity::High,
-            timestamp: time::OffsetDateTime::now_utc(),
-            correlation_id: Uuid::new_v4(),
-            category: crate::utils::error::ErrorCategory::Security,
-            retry_count: 0,
-        })?;
-
-        metrics.record_metric(
+        self.metrics.record_metric(
             format!("guardian.audit.events.{}", event.severity.to_string().to_lowercase()),
             1.0,
             MetricType::Counter,
@@ -234,6 +417,25 @@ impl AuditLogger {
         Ok(())
     }
 
+    /// Verifies the audit chain over events recorded between `from` and `to`
+    pub async fn verify_chain(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        crypto: Option<&CryptoManager>,
+    ) -> Result<ChainVerification, GuardianError> {
+        let chain = audit_chain().ok_or_else(|| GuardianError::SecurityError {
+            context: "Audit chain is not enabled".into(),
+            source: None,
+            severity: crate::utils::error::ErrorSeverity::Medium,
+            timestamp: time::OffsetDateTime::now_utc(),
+            correlation_id: crate::utils::correlation::current_or_new(),
+            category: crate::utils::error::ErrorCategory::Security,
+            retry_count: 0,
+        })?;
+        chain.verify(from, to, crypto).await
+    }
+
     /// Retrieves current audit statistics
     pub fn get_stats(&self) -> Result<AuditStats, GuardianError> {
         self.stats.lock()
@@ -242,7 +444,7 @@ impl AuditLogger {
                 source: Some(Box::new(e)),
                 severity: crate::utils::error::ErrorSeverity::Medium,
                 timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id: Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: crate::utils::error::ErrorCategory::Security,
                 retry_count: 0,
             })
@@ -252,12 +454,16 @@ impl AuditLogger {
     /// Rotates audit logs based on retention policy
     #[instrument(skip(self))]
     pub async fn rotate_logs(&self) -> Result<(), GuardianError> {
-        let mut freebsd_audit = self.freebsd_audit.lock().map_err(|e| GuardianError::SecurityError {
+        let Some(freebsd_audit) = &self.freebsd_audit else {
+            debug!("Audit logging disabled, nothing to rotate");
+            return Ok(());
+        };
+        let mut freebsd_audit = freebsd_audit.lock().map_err(|e| GuardianError::SecurityError {
             context: "Failed to lock FreeBSD audit".into(),
             source: Some(Box::new(e)),
             severity: crate::utils::error::ErrorSeverity::High,
             timestamp: time::OffsetDateTime::now_utc(),
-            correlation_id: Uuid::new_v4(),
+            correlation_id: crate::utils::correlation::current_or_new(),
             category: crate::utils::error::ErrorCategory::Security,
             retry_count: 0,
         })?;
@@ -271,12 +477,15 @@ impl AuditLogger {
     /// Checks the health of the audit subsystem
     pub fn check_health(&self) -> Result<bool, GuardianError> {
         let stats = self.get_stats()?;
-        let freebsd_audit = self.freebsd_audit.lock().map_err(|e| GuardianError::SecurityError {
+        let Some(freebsd_audit) = &self.freebsd_audit else {
+            return Ok(stats.storage_usage < 90.0);
+        };
+        let freebsd_audit = freebsd_audit.lock().map_err(|e| GuardianError::SecurityError {
             context: "Failed to lock FreeBSD audit".into(),
             source: Some(Box::new(e)),
             severity: crate::utils::error::ErrorSeverity::High,
             timestamp: time::OffsetDateTime::now_utc(),
-            correlation_id: Uuid::new_v4(),
+            correlation_id: crate::utils::correlation::current_or_new(),
             category: crate::utils::error::ErrorCategory::Security,
             retry_count: 0,
         })?;
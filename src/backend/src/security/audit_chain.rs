@@ -0,0 +1,365 @@
+//! Append-only, tamper-evident audit trail
+//!
+//! Every recorded audit event carries its sequence number and the hash of the event
+//! before it, and is appended to a chain file. At a fixed interval the newest hash is
+//! signed by the CryptoManager as an anchor, so rewriting events, dropping them or
+//! truncating the file up to an anchor can all be detected by `verify`.
+
+use std::{
+    collections::VecDeque,
+    fs::{File, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use metrics::counter;
+use once_cell::sync::OnceCell;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::config::security_config::AuditChainConfig;
+use crate::security::audit::AuditEvent;
+use crate::security::crypto::{CryptoManager, DataSignature, KeyId};
+use crate::utils::error::{GuardianError, SecurityError};
+
+// Constants for the audit chain
+const ANCHOR_DOMAIN: &str = "guardian-audit-anchor";
+/// Event hashes kept while verifying, to check anchors written shortly after their event
+const RECENT_HASHES: usize = 4096;
+
+static AUDIT_CHAIN: OnceCell<Arc<AuditChain>> = OnceCell::new();
+
+/// Returns the audit chain, once opened
+pub fn audit_chain() -> Option<Arc<AuditChain>> {
+    AUDIT_CHAIN.get().cloned()
+}
+
+/// Opens the chain file; every audit event recorded from then on is linked into it
+pub fn init_audit_chain(config: &AuditChainConfig) -> Result<Option<Arc<AuditChain>>, GuardianError> {
+    if !config.enabled {
+        return Ok(None);
+    }
+    let chain = AUDIT_CHAIN.get_or_try_init(|| AuditChain::open(Path::new(&config.path)).map(Arc::new))?;
+    Ok(Some(Arc::clone(chain)))
+}
+
+/// Signed statement of the newest event hash at a point in time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChainAnchor {
+    pub sequence: u64,
+    pub hash: String,
+    pub signed_at: DateTime<Utc>,
+    pub signature: DataSignature,
+}
+
+impl ChainAnchor {
+    fn message(sequence: u64, hash: &str, signed_at: DateTime<Utc>) -> Vec<u8> {
+        format!("{}:{}:{}:{}", ANCHOR_DOMAIN, sequence, hash, signed_at.to_rfc3339()).into_bytes()
+    }
+}
+
+/// One line of the chain file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ChainEntry {
+    Event { event: AuditEvent },
+    Anchor { anchor: ChainAnchor },
+}
+
+/// Outcome of verifying the chain over a time range
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChainVerification {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub valid: bool,
+    pub events_in_range: u64,
+    pub first_sequence: Option<u64>,
+    pub last_sequence: Option<u64>,
+    pub anchors_checked: u64,
+    /// Whether anchor signatures were checked, or only their hashes
+    pub signatures_checked: bool,
+    /// Whether a signed anchor covers the last event in range, ruling out truncation
+    pub anchored: bool,
+    /// Sequence number where the chain stops verifying
+    pub broken_at: Option<u64>,
+    pub reason: Option<String>,
+}
+
+impl ChainVerification {
+    fn broken(mut self, sequence: Option<u64>, reason: String) -> Self {
+        counter!("guardian.audit.chain.verification_failures", 1);
+        warn!(sequence = ?sequence, reason = %reason, "Audit chain verification failed");
+        self.valid = false;
+        self.broken_at = sequence;
+        self.reason = Some(reason);
+        self
+    }
+}
+
+#[derive(Debug)]
+struct ChainHead {
+    sequence: u64,
+    hash: Option<String>,
+    /// Newest sequence covered by an anchor
+    anchored: u64,
+    file: File,
+}
+
+/// Hash chain of audit events, persisted one JSON entry per line
+#[derive(Debug)]
+pub struct AuditChain {
+    path: PathBuf,
+    head: Mutex<ChainHead>,
+}
+
+impl AuditChain {
+    /// Opens or creates a chain file, continuing after its last event
+    pub fn open(path: &Path) -> Result<Self, GuardianError> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .map_err(|e| chain_error(format!("Failed to create {}", dir.display()), Some(Box::new(e))))?;
+        }
+
+        let (mut sequence, mut hash, mut anchored) = (0, None, 0);
+        if path.exists() {
+            let content = std::fs::read_to_string(path)
+                .map_err(|e| chain_error(format!("Failed to read {}", path.display()), Some(Box::new(e))))?;
+            for line in content.lines().filter(|line| !line.trim().is_empty()) {
+                match serde_json::from_str::<ChainEntry>(line) {
+                    Ok(ChainEntry::Event { event }) => {
+                        sequence = event.sequence();
+                        hash = Some(event.chain_hash()?);
+                    }
+                    Ok(ChainEntry::Anchor { anchor }) => anchored = anchor.sequence,
+                    // Left for verification to report; appending continues from the last good event
+                    Err(e) => warn!(path = %path.display(), error = %e, "Unreadable audit chain entry"),
+                }
+            }
+        }
+
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| chain_error(format!("Failed to open {}", path.display()), Some(Box::new(e))))?;
+        info!(path = %path.display(), sequence, "Audit chain opened");
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            head: Mutex::new(ChainHead { sequence, hash, anchored, file }),
+        })
+    }
+
+    /// Links an event after the newest one and appends it
+    pub fn append(&self, event: &mut AuditEvent) -> Result<(), GuardianError> {
+        let mut head = self.head.lock();
+        event.link(head.sequence + 1, head.hash.clone());
+        let hash = event.chain_hash()?;
+        write_entry(&mut head.file, &ChainEntry::Event { event: event.clone() })?;
+        head.sequence += 1;
+        head.hash = Some(hash);
+        Ok(())
+    }
+
+    /// Signs the newest event hash, if events were added since the last anchor
+    pub async fn anchor(&self, crypto: &CryptoManager, key_id: &KeyId) -> Result<Option<ChainAnchor>, GuardianError> {
+        let (sequence, hash) = {
+            let head = self.head.lock();
+            match &head.hash {
+                Some(hash) if head.sequence > head.anchored => (head.sequence, hash.clone()),
+                _ => return Ok(None),
+            }
+        };
+
+        let signed_at = Utc::now();
+        let signature = crypto.sign_data(&ChainAnchor::message(sequence, &hash, signed_at), key_id).await?;
+        let anchor = ChainAnchor { sequence, hash, signed_at, signature };
+
+        let mut head = self.head.lock();
+        write_entry(&mut head.file, &ChainEntry::Anchor { anchor: anchor.clone() })?;
+        head.anchored = head.anchored.max(sequence);
+        counter!("guardian.audit.chain.anchors", 1);
+        Ok(Some(anchor))
+    }
+
+    /// Signs an anchor every `interval` with the key provider key `key_id`
+    pub fn start_anchoring(self: &Arc<Self>, crypto: Arc<CryptoManager>, key_id: KeyId, interval: Duration) {
+        let chain = Arc::clone(self);
+        tokio::spawn(async move {
+            if let Err(e) = crypto.load_key(key_id.clone()).await {
+                warn!(key_id = key_id.as_str(), error = %e, "Audit anchor key unavailable, the chain is not being anchored");
+                return;
+            }
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = chain.anchor(&crypto, &key_id).await {
+                    warn!(error = %e, "Failed to sign audit chain anchor");
+                }
+            }
+        });
+    }
+
+    /// Events of one type recorded at or after `since`, oldest first
+    ///
+    /// Unreadable lines are skipped here; `verify` is what reports them.
+    pub async fn events_of_type(
+        &self,
+        event_type: &str,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<Vec<AuditEvent>, GuardianError> {
+        let content = tokio::fs::read_to_string(&self.path)
+            .await
+            .map_err(|e| chain_error(format!("Failed to read {}", self.path.display()), Some(Box::new(e))))?;
+        Ok(content
+            .lines()
+            .filter_map(|line| match serde_json::from_str::<ChainEntry>(line) {
+                Ok(ChainEntry::Event { event }) => Some(event),
+                _ => None,
+            })
+            .filter(|event| event.event_type() == event_type && since.map_or(true, |since| event.timestamp() >= since))
+            .collect())
+    }
+
+    /// Verifies the chain and reports on the events recorded between `from` and `to`
+    ///
+    /// Links are checked from the first event so that no earlier rewrite goes unnoticed.
+    /// Anchor signatures are checked when `crypto` is given; without it only their hashes are.
+    pub async fn verify(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        crypto: Option<&CryptoManager>,
+    ) -> Result<ChainVerification, GuardianError> {
+        let content = tokio::fs::read_to_string(&self.path)
+            .await
+            .map_err(|e| chain_error(format!("Failed to read {}", self.path.display()), Some(Box::new(e))))?;
+
+        let mut report = ChainVerification {
+            from,
+            to,
+            valid: true,
+            events_in_range: 0,
+            first_sequence: None,
+            last_sequence: None,
+            anchors_checked: 0,
+            signatures_checked: crypto.is_some(),
+            anchored: false,
+            broken_at: None,
+            reason: None,
+        };
+        let mut recent: VecDeque<(u64, String)> = VecDeque::with_capacity(RECENT_HASHES);
+
+        for (index, line) in content.lines().enumerate().filter(|(_, line)| !line.trim().is_empty()) {
+            let next = recent.back().map_or(1, |(sequence, _)| sequence + 1);
+            let entry = match serde_json::from_str::<ChainEntry>(line) {
+                Ok(entry) => entry,
+                Err(e) => return Ok(report.broken(Some(next), format!("line {} is not a chain entry: {}", index + 1, e))),
+            };
+
+            match entry {
+                ChainEntry::Event { event } => {
+                    let sequence = event.sequence();
+                    if sequence != next {
+                        return Ok(report.broken(Some(next), format!("expected event {}, found {}", next, sequence)));
+                    }
+                    if event.prev_hash() != recent.back().map(|(_, hash)| hash.as_str()) {
+                        return Ok(report.broken(Some(sequence), format!("event {} does not link to its predecessor", sequence)));
+                    }
+                    if event.timestamp() >= from && event.timestamp() <= to {
+                        report.events_in_range += 1;
+                        report.first_sequence.get_or_insert(sequence);
+                        report.last_sequence = Some(sequence);
+                        report.anchored = false;
+                    }
+                    if recent.len() == RECENT_HASHES {
+                        recent.pop_front();
+                    }
+                    recent.push_back((sequence, event.chain_hash()?));
+                }
+                ChainEntry::Anchor { anchor } => {
+                    match recent.iter().find(|(sequence, _)| *sequence == anchor.sequence) {
+                        Some((_, hash)) if *hash == anchor.hash => {}
+                        _ => {
+                            return Ok(report.broken(
+                                Some(anchor.sequence),
+                                format!("anchor over event {} does not match the chain", anchor.sequence),
+                            ))
+                        }
+                    }
+                    if let Some(crypto) = crypto {
+                        let message = ChainAnchor::message(anchor.sequence, &anchor.hash, anchor.signed_at);
+                        if let Err(e) = crypto.verify_data(&message, &anchor.signature).await {
+                            return Ok(report.broken(Some(anchor.sequence), e.to_string()));
+                        }
+                    }
+                    report.anchors_checked += 1;
+                    if report.last_sequence.map_or(false, |last| anchor.sequence >= last) {
+                        report.anchored = true;
+                    }
+                }
+            }
+        }
+
+        counter!("guardian.audit.chain.verifications", 1);
+        Ok(report)
+    }
+}
+
+fn write_entry(file: &mut File, entry: &ChainEntry) -> Result<(), GuardianError> {
+    let mut line = serde_json::to_vec(entry)?;
+    line.push(b'\n');
+    file.write_all(&line)
+        .and_then(|_| file.flush())
+        .map_err(|e| chain_error("Failed to append to the audit chain".into(), Some(Box::new(e))))
+}
+
+fn chain_error(context: String, source: Option<Box<dyn std::error::Error + Send + Sync>>) -> GuardianError {
+    SecurityError {
+        context,
+        source,
+        severity: crate::utils::error::ErrorSeverity::High,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: crate::utils::error::ErrorCategory::Security,
+        retry_count: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::audit::SecurityLevel;
+
+    #[tokio::test]
+    async fn test_chain_detects_rewritten_event() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit.chain");
+        let chain = AuditChain::open(&path).unwrap();
+        for event_type in ["auth.login", "config.change", "auth.logout"] {
+            let mut event = AuditEvent::new(event_type.into(), SecurityLevel::Medium, "test".into(), None);
+            chain.append(&mut event).unwrap();
+        }
+
+        let (from, to) = (Utc::now() - chrono::Duration::hours(1), Utc::now());
+        let report = chain.verify(from, to, None).await.unwrap();
+        assert!(report.valid);
+        assert_eq!((report.first_sequence, report.last_sequence), (Some(1), Some(3)));
+
+        // Reopening continues the chain
+        let reopened = AuditChain::open(&path).unwrap();
+        let mut event = AuditEvent::new("auth.login".into(), SecurityLevel::Low, "test".into(), None);
+        reopened.append(&mut event).unwrap();
+        assert_eq!(event.sequence(), 4);
+
+        let tampered = std::fs::read_to_string(&path).unwrap().replacen("config.change", "config.read", 1);
+        std::fs::write(&path, tampered).unwrap();
+        let report = reopened.verify(from, Utc::now(), None).await.unwrap();
+        assert!(!report.valid);
+        assert_eq!(report.broken_at, Some(3));
+    }
+}
@@ -0,0 +1,189 @@
+//! Adaptive sampling of audit events
+//!
+//! Under an event storm or a filling audit volume, recording every Low and Medium event
+//! costs more than it tells. The sampler measures the event rate over a sliding window and
+//! the usage of the filesystem holding the audit trail, and derives a sampling rate per
+//! severity from both. Recorded events carry the rate they were sampled at, so analytics
+//! re-weight counts by its inverse.
+
+use std::{
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use metrics::{counter, gauge};
+use parking_lot::Mutex;
+use tracing::debug;
+
+use crate::config::security_config::AuditSamplingConfig;
+use crate::security::audit::{AuditEvent, SecurityLevel};
+use crate::security::command_audit::COMMAND_EVENT_TYPE;
+
+/// Effective rates at one instant
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SamplingRates {
+    pub medium: f64,
+    pub low: f64,
+}
+
+impl SamplingRates {
+    const FULL: Self = Self { medium: 1.0, low: 1.0 };
+}
+
+#[derive(Debug)]
+struct RateWindow {
+    started: Instant,
+    count: u64,
+    /// Events per second over the last complete window
+    last_rate: f64,
+    /// Storage usage in percent, refreshed once per window
+    storage_usage: f64,
+}
+
+/// Decides which audit events are recorded
+#[derive(Debug)]
+pub struct AuditSampler {
+    config: AuditSamplingConfig,
+    /// File on the audit volume whose filesystem usage is the storage pressure
+    storage_path: Option<PathBuf>,
+    window: Mutex<RateWindow>,
+}
+
+impl AuditSampler {
+    pub fn new(config: AuditSamplingConfig, storage_path: Option<PathBuf>) -> Self {
+        let storage_usage = storage_path.as_deref().and_then(filesystem_usage).unwrap_or(0.0);
+        Self {
+            config,
+            storage_path,
+            window: Mutex::new(RateWindow {
+                started: Instant::now(),
+                count: 0,
+                last_rate: 0.0,
+                storage_usage,
+            }),
+        }
+    }
+
+    /// Counts the event and returns the rate it is recorded at, or `None` when it is dropped
+    pub fn sample(&self, event: &AuditEvent) -> Option<f64> {
+        let rates = self.observe(Instant::now());
+        let rate = match event.severity() {
+            _ if self.always_recorded(event) => 1.0,
+            SecurityLevel::Critical | SecurityLevel::High => 1.0,
+            SecurityLevel::Medium => rates.medium,
+            SecurityLevel::Low => rates.low,
+        };
+        if rate >= 1.0 || rand::random::<f64>() < rate {
+            Some(rate)
+        } else {
+            counter!("guardian.audit.sampled_out", 1, "severity" => event.severity().to_string().to_lowercase());
+            None
+        }
+    }
+
+    /// Storage usage in percent last measured, for health checks
+    pub fn storage_usage(&self) -> f64 {
+        self.window.lock().storage_usage
+    }
+
+    fn always_recorded(&self, event: &AuditEvent) -> bool {
+        // Command invocations form the administrative ledger, which must stay complete
+        event.event_type() == COMMAND_EVENT_TYPE
+            || self.config.always_record.iter().any(|event_type| event_type == event.event_type())
+    }
+
+    /// Counts one event at `now` and returns the rates in effect
+    fn observe(&self, now: Instant) -> SamplingRates {
+        if !self.config.enabled {
+            return SamplingRates::FULL;
+        }
+        let window_secs = self.config.window.as_secs_f64();
+        let mut window = self.window.lock();
+        let elapsed = now.saturating_duration_since(window.started);
+        if elapsed >= self.config.window {
+            window.last_rate = window.count as f64 / elapsed.as_secs_f64();
+            window.started = now;
+            window.count = 0;
+            if let Some(usage) = self.storage_path.as_deref().and_then(filesystem_usage) {
+                window.storage_usage = usage;
+            }
+        }
+        window.count += 1;
+
+        // The current window's count over the full window never overestimates the rate, and
+        // reacts to a burst before the window closes
+        let rate = window.last_rate.max(window.count as f64 / window_secs);
+        let rates = self.rates(rate, window.storage_usage);
+        if elapsed >= self.config.window {
+            gauge!("guardian.audit.sampling.rate", rates.medium, "severity" => "medium");
+            gauge!("guardian.audit.sampling.rate", rates.low, "severity" => "low");
+            gauge!("guardian.audit.sampling.events_per_sec", rate);
+            debug!(events_per_sec = rate, storage_usage = window.storage_usage, ?rates, "Audit sampling rates updated");
+        }
+        rates
+    }
+
+    /// Rates for an event rate and storage usage; Low events are sampled at the square of
+    /// the Medium rate
+    fn rates(&self, events_per_sec: f64, storage_usage: f64) -> SamplingRates {
+        let load = if events_per_sec > self.config.target_events_per_sec {
+            self.config.target_events_per_sec / events_per_sec
+        } else {
+            1.0
+        };
+        let threshold = self.config.storage_pressure_threshold;
+        let pressure = if storage_usage > threshold {
+            ((100.0 - storage_usage) / (100.0 - threshold)).max(0.0)
+        } else {
+            1.0
+        };
+        let medium = load * pressure;
+        SamplingRates {
+            medium: medium.clamp(self.config.min_rate, 1.0),
+            low: (medium * medium).clamp(self.config.min_rate, 1.0),
+        }
+    }
+}
+
+/// Usage in percent of the filesystem holding `path`
+fn filesystem_usage(path: &std::path::Path) -> Option<f64> {
+    let dir = if path.is_dir() { path } else { path.parent()? };
+    let stat = nix::sys::statvfs::statvfs(dir).ok()?;
+    let total = stat.blocks() as f64;
+    if total == 0.0 {
+        return None;
+    }
+    Some((total - stat.blocks_available() as f64) / total * 100.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rates_adapt_to_load_and_storage_pressure() {
+        let sampler = AuditSampler::new(
+            AuditSamplingConfig {
+                target_events_per_sec: 100.0,
+                window: Duration::from_secs(1),
+                ..Default::default()
+            },
+            None,
+        );
+        assert_eq!(sampler.rates(50.0, 10.0), SamplingRates::FULL);
+        assert_eq!(sampler.rates(200.0, 10.0), SamplingRates { medium: 0.5, low: 0.25 });
+        assert_eq!(sampler.rates(100.0, 90.0), SamplingRates { medium: 0.5, low: 0.25 });
+        assert_eq!(sampler.rates(10_000.0, 100.0), SamplingRates { medium: 0.01, low: 0.01 });
+
+        // A burst is sampled before the window closes; High and command events never are
+        let start = Instant::now();
+        for _ in 0..400 {
+            sampler.observe(start);
+        }
+        assert!(sampler.observe(start).medium < 0.5);
+        let high = AuditEvent::new("login".into(), SecurityLevel::High, "auth".into(), None);
+        let command = AuditEvent::new(COMMAND_EVENT_TYPE.into(), SecurityLevel::Low, "cli:root".into(), None);
+        assert_eq!(sampler.sample(&high), Some(1.0));
+        assert_eq!(sampler.sample(&command), Some(1.0));
+    }
+}
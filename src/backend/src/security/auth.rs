@@ -0,0 +1,465 @@
+//! API tokens for service accounts
+//!
+//! Tokens are compact JWTs signed with HMAC-SHA256 under a key of the key provider, whose
+//! version travels in the `kid` header so tokens minted before a rotation stay valid until the
+//! old version retires. A token names its service account, its scopes and its expiry. Scopes
+//! reach RBAC as `scope:<name>` identities, so a token is granted exactly the roles its scopes
+//! are bound to and nothing else its service account might hold. Revoked token IDs are
+//! persisted until the tokens would have expired anyway.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::PathBuf,
+    sync::Arc,
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use chrono::{DateTime, TimeZone, Utc};
+use metrics::counter;
+use once_cell::sync::OnceCell;
+use parking_lot::RwLock;
+use ring::hmac;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+use uuid::Uuid;
+use zeroize::Zeroizing;
+
+use crate::config::security_config::ApiTokenConfig;
+use crate::security::key_provider::{KeyMaterial, KeyProvider, KeyRotationParticipant};
+use crate::security::rbac::{rbac, Principal};
+use crate::utils::error::{GuardianError, SecurityError};
+
+const TOKEN_ALGORITHM: &str = "HS256";
+const TOKEN_TYPE: &str = "JWT";
+const BEARER_PREFIX: &str = "Bearer ";
+/// Prefix of the RBAC identities token scopes are bound through
+pub const SCOPE_IDENTITY_PREFIX: &str = "scope:";
+
+static TOKEN_AUTHORITY: OnceCell<Arc<TokenAuthority>> = OnceCell::new();
+
+/// Returns the token authority, once initialized with tokens enabled
+pub fn token_authority() -> Option<Arc<TokenAuthority>> {
+    TOKEN_AUTHORITY.get().cloned()
+}
+
+/// Loads the signing key and revocation list; later calls return the first authority
+pub async fn init_token_authority(
+    config: &ApiTokenConfig,
+    provider: Arc<dyn KeyProvider>,
+) -> Result<Option<Arc<TokenAuthority>>, GuardianError> {
+    if !config.enabled {
+        return Ok(None);
+    }
+    if let Some(authority) = token_authority() {
+        return Ok(Some(authority));
+    }
+    let authority = Arc::new(TokenAuthority::new(config.clone(), provider).await?);
+    Ok(Some(Arc::clone(TOKEN_AUTHORITY.get_or_init(|| authority))))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct TokenHeader {
+    alg: String,
+    typ: String,
+    /// Signing key version
+    kid: String,
+}
+
+/// Claims carried by an API token
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TokenClaims {
+    pub iss: String,
+    /// Service account the token was minted for
+    pub sub: String,
+    /// Token ID, the handle revocation uses
+    pub jti: String,
+    pub iat: i64,
+    pub nbf: i64,
+    pub exp: i64,
+    pub scopes: Vec<String>,
+}
+
+impl TokenClaims {
+    pub fn expires_at(&self) -> DateTime<Utc> {
+        Utc.timestamp_opt(self.exp, 0).single().unwrap_or_default()
+    }
+}
+
+/// A freshly minted token; the token string is shown once and never stored
+#[derive(Clone)]
+pub struct IssuedToken {
+    pub token: String,
+    pub claims: TokenClaims,
+}
+
+impl std::fmt::Debug for IssuedToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IssuedToken").field("claims", &self.claims).finish_non_exhaustive()
+    }
+}
+
+/// Entry of the revocation list
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RevokedToken {
+    pub jti: String,
+    pub revoked_at: DateTime<Utc>,
+    /// When the entry may be dropped: no token it covers is valid past this point
+    pub expires_at: DateTime<Utc>,
+    pub reason: String,
+}
+
+/// Mints, validates and revokes API tokens
+#[derive(Debug)]
+pub struct TokenAuthority {
+    config: ApiTokenConfig,
+    /// Key versions accepted for validation; the current one signs
+    keys: RwLock<BTreeMap<u64, Zeroizing<Vec<u8>>>>,
+    current: RwLock<u64>,
+    revoked: RwLock<HashMap<String, RevokedToken>>,
+    revocation_path: PathBuf,
+}
+
+impl TokenAuthority {
+    /// Loads the current signing key, and the version before it so tokens minted just before
+    /// a restart inside a rotation overlap still validate
+    pub async fn new(config: ApiTokenConfig, provider: Arc<dyn KeyProvider>) -> Result<Self, GuardianError> {
+        let current = provider.current_key(&config.signing_key_id).await?;
+        let mut keys = BTreeMap::from([(current.version, Zeroizing::new(current.bytes().to_vec()))]);
+        if let Some(previous) = current.version.checked_sub(1).filter(|v| *v > 0) {
+            if let Ok(key) = provider.key_version(&config.signing_key_id, previous).await {
+                keys.insert(previous, Zeroizing::new(key.bytes().to_vec()));
+            }
+        }
+
+        let revocation_path = PathBuf::from(&config.revocation_path);
+        let revoked = read_revocations(&revocation_path).await?;
+        info!(key_version = current.version, revoked = revoked.len(), "API token authority ready");
+        Ok(Self {
+            config,
+            keys: RwLock::new(keys),
+            current: RwLock::new(current.version),
+            revoked: RwLock::new(revoked),
+            revocation_path,
+        })
+    }
+
+    /// Mints a token for a service account; every scope must be bound to an RBAC role
+    pub fn issue(&self, subject: &str, scopes: &[String], ttl: Option<Duration>) -> Result<IssuedToken, GuardianError> {
+        if subject.trim().is_empty() || scopes.is_empty() {
+            return Err(auth_error("A token needs a service account and at least one scope".into()));
+        }
+        let ttl = ttl.unwrap_or(self.config.default_ttl);
+        if ttl.is_zero() || ttl > self.config.max_ttl {
+            return Err(auth_error(format!(
+                "Token lifetime must be between 1s and {}s",
+                self.config.max_ttl.as_secs()
+            )));
+        }
+        let engine = rbac();
+        if let Some(unbound) = scopes
+            .iter()
+            .find(|scope| engine.roles_for(&scope_principal(subject, std::slice::from_ref(scope))).is_empty())
+        {
+            return Err(auth_error(format!("Scope {} is not bound to any RBAC role", unbound)));
+        }
+
+        let now = Utc::now().timestamp();
+        let claims = TokenClaims {
+            iss: self.config.issuer.clone(),
+            sub: subject.to_string(),
+            jti: Uuid::new_v4().to_string(),
+            iat: now,
+            nbf: now,
+            exp: now + ttl.as_secs() as i64,
+            scopes: scopes.to_vec(),
+        };
+        let version = *self.current.read();
+        let header = TokenHeader {
+            alg: TOKEN_ALGORITHM.into(),
+            typ: TOKEN_TYPE.into(),
+            kid: version.to_string(),
+        };
+        let signing_input = format!("{}.{}", encode_part(&header)?, encode_part(&claims)?);
+        let signature = {
+            let keys = self.keys.read();
+            let key = keys.get(&version).ok_or_else(|| auth_error("Token signing key is not loaded".into()))?;
+            hmac::sign(&hmac::Key::new(hmac::HMAC_SHA256, key), signing_input.as_bytes())
+        };
+
+        counter!("guardian.auth.tokens.issued", 1);
+        info!(
+            target: "SECURITY-AUDIT",
+            subject,
+            jti = %claims.jti,
+            scopes = ?claims.scopes,
+            expires_at = %claims.expires_at(),
+            "API token issued"
+        );
+        Ok(IssuedToken {
+            token: format!("{}.{}", signing_input, URL_SAFE_NO_PAD.encode(signature.as_ref())),
+            claims,
+        })
+    }
+
+    /// Checks a token's signature, issuer, validity window and revocation
+    pub fn validate(&self, token: &str) -> Result<TokenClaims, GuardianError> {
+        let result = self.verify(token).and_then(|claims| {
+            let now = Utc::now().timestamp();
+            let leeway = self.config.leeway.as_secs() as i64;
+            if claims.iss != self.config.issuer {
+                return Err(auth_error(format!("Token issued by {}", claims.iss)));
+            }
+            if now + leeway < claims.nbf || now - leeway >= claims.exp {
+                return Err(auth_error("Token is expired or not yet valid".into()));
+            }
+            if self.revoked.read().contains_key(&claims.jti) {
+                return Err(auth_error(format!("Token {} is revoked", claims.jti)));
+            }
+            Ok(claims)
+        });
+        let outcome = if result.is_ok() { "valid" } else { "rejected" };
+        counter!("guardian.auth.tokens.validated", 1, "result" => outcome);
+        result
+    }
+
+    /// Verifies the signature and decodes the claims, without checking their contents
+    fn verify(&self, token: &str) -> Result<TokenClaims, GuardianError> {
+        let mut parts = token.split('.');
+        let (Some(header), Some(claims), Some(signature), None) = (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(auth_error("Token is not a compact JWT".into()));
+        };
+        let header: TokenHeader = decode_part(header)?;
+        if header.alg != TOKEN_ALGORITHM {
+            return Err(auth_error(format!("Token algorithm {} is not accepted", header.alg)));
+        }
+        let version: u64 = header.kid.parse().map_err(|_| auth_error("Token key ID is malformed".into()))?;
+        let signature = URL_SAFE_NO_PAD
+            .decode(signature)
+            .map_err(|_| auth_error("Token signature is not base64url".into()))?;
+
+        let signing_input = &token[..token.rfind('.').unwrap_or_default()];
+        {
+            let keys = self.keys.read();
+            let key = keys
+                .get(&version)
+                .ok_or_else(|| auth_error(format!("Token signed with unknown key version {}", version)))?;
+            hmac::verify(
+                &hmac::Key::new(hmac::HMAC_SHA256, key),
+                signing_input.as_bytes(),
+                &signature,
+            )
+            .map_err(|_| auth_error("Token signature is invalid".into()))?;
+        }
+        decode_part(claims)
+    }
+
+    /// Revokes a token by ID; the entry is kept for the longest lifetime a token can have
+    pub async fn revoke(&self, jti: &str, reason: &str) -> Result<RevokedToken, GuardianError> {
+        let now = Utc::now();
+        let entry = RevokedToken {
+            jti: jti.to_string(),
+            revoked_at: now,
+            expires_at: now + chrono::Duration::from_std(self.config.max_ttl + self.config.leeway).unwrap_or_default(),
+            reason: reason.to_string(),
+        };
+        let snapshot = {
+            let mut revoked = self.revoked.write();
+            revoked.retain(|_, entry| entry.expires_at > now);
+            revoked.insert(jti.to_string(), entry.clone());
+            revoked.values().cloned().collect::<Vec<_>>()
+        };
+        write_revocations(&self.revocation_path, &snapshot).await?;
+
+        counter!("guardian.auth.tokens.revoked", 1);
+        warn!(target: "SECURITY-AUDIT", jti, reason, "API token revoked");
+        Ok(entry)
+    }
+
+    /// Revoked tokens that have not expired yet, most recently revoked first
+    pub fn revoked(&self) -> Vec<RevokedToken> {
+        let now = Utc::now();
+        let mut entries: Vec<RevokedToken> =
+            self.revoked.read().values().filter(|entry| entry.expires_at > now).cloned().collect();
+        entries.sort_by(|a, b| b.revoked_at.cmp(&a.revoked_at));
+        entries
+    }
+}
+
+#[async_trait]
+impl KeyRotationParticipant for TokenAuthority {
+    fn name(&self) -> &str {
+        "api_tokens"
+    }
+
+    async fn key_ids(&self) -> Vec<String> {
+        vec![self.config.signing_key_id.clone()]
+    }
+
+    async fn apply_key(&self, key_id: &str, key: &KeyMaterial) -> Result<(), GuardianError> {
+        if key_id == self.config.signing_key_id {
+            self.keys.write().insert(key.version, Zeroizing::new(key.bytes().to_vec()));
+            *self.current.write() = key.version;
+        }
+        Ok(())
+    }
+
+    /// Tokens signed with a retired version stop validating
+    async fn retire_key(&self, key_id: &str, version: u64) -> Result<(), GuardianError> {
+        if key_id == self.config.signing_key_id && version != *self.current.read() {
+            self.keys.write().remove(&version);
+        }
+        Ok(())
+    }
+}
+
+/// Principal a token with these scopes acts as
+pub fn scope_principal(subject: &str, scopes: &[String]) -> Principal {
+    Principal {
+        name: format!("service:{}", subject),
+        identities: scopes.iter().map(|scope| format!("{}{}", SCOPE_IDENTITY_PREFIX, scope)).collect(),
+    }
+}
+
+/// Validates bearer tokens on gRPC requests, making their claims available to authorization
+///
+/// Requests without a bearer token pass through to certificate authentication. While no
+/// authority is initialized bearer tokens are left to the layers behind it as opaque keys.
+#[derive(Debug, Clone, Default)]
+pub struct TokenInterceptor;
+
+impl tonic::service::Interceptor for TokenInterceptor {
+    fn call(&mut self, mut request: tonic::Request<()>) -> Result<tonic::Request<()>, tonic::Status> {
+        let authorization = request.metadata().get("authorization").and_then(|value| value.to_str().ok());
+        if let Some(claims) = bearer_claims(authorization)? {
+            request.extensions_mut().insert(claims);
+        }
+        Ok(request)
+    }
+}
+
+/// Validates the bearer token in an `authorization` value, for gRPC and REST requests alike
+///
+/// Returns `None` when there is no bearer token or no authority to validate it.
+pub(crate) fn bearer_claims(authorization: Option<&str>) -> Result<Option<TokenClaims>, tonic::Status> {
+    let Some(authority) = token_authority() else {
+        return Ok(None);
+    };
+    let token = authorization
+        .and_then(|value| value.strip_prefix(BEARER_PREFIX))
+        .map(str::trim)
+        .filter(|token| !token.is_empty());
+    let Some(token) = token else {
+        return Ok(None);
+    };
+
+    match authority.validate(token) {
+        Ok(claims) => Ok(Some(claims)),
+        Err(e) => {
+            warn!(target: "SECURITY-AUDIT", error = %e, "API token rejected");
+            Err(tonic::Status::unauthenticated("Invalid API token"))
+        }
+    }
+}
+
+fn encode_part<T: Serialize>(part: &T) -> Result<String, GuardianError> {
+    let json = serde_json::to_vec(part).map_err(|e| auth_error(format!("Failed to encode token: {}", e)))?;
+    Ok(URL_SAFE_NO_PAD.encode(json))
+}
+
+fn decode_part<T: serde::de::DeserializeOwned>(part: &str) -> Result<T, GuardianError> {
+    let json = URL_SAFE_NO_PAD
+        .decode(part)
+        .map_err(|_| auth_error("Token part is not base64url".into()))?;
+    serde_json::from_slice(&json).map_err(|_| auth_error("Token part is not valid JSON".into()))
+}
+
+async fn read_revocations(path: &std::path::Path) -> Result<HashMap<String, RevokedToken>, GuardianError> {
+    let entries: Vec<RevokedToken> = match tokio::fs::read(path).await {
+        Ok(data) => serde_json::from_slice(&data)
+            .map_err(|e| auth_error(format!("Revocation list {} is corrupt: {}", path.display(), e)))?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+        Err(e) => return Err(auth_error(format!("Failed to read revocation list {}: {}", path.display(), e))),
+    };
+    let now = Utc::now();
+    Ok(entries
+        .into_iter()
+        .filter(|entry| entry.expires_at > now)
+        .map(|entry| (entry.jti.clone(), entry))
+        .collect())
+}
+
+/// Replaces the revocation list by rename, so a crash never leaves it half written
+async fn write_revocations(path: &std::path::Path, entries: &[RevokedToken]) -> Result<(), GuardianError> {
+    let write = async {
+        if let Some(dir) = path.parent() {
+            tokio::fs::create_dir_all(dir).await?;
+        }
+        let tmp = path.with_extension("json.tmp");
+        tokio::fs::write(&tmp, serde_json::to_vec_pretty(entries)?).await?;
+        tokio::fs::rename(&tmp, path).await
+    };
+    write
+        .await
+        .map_err(|e: std::io::Error| auth_error(format!("Failed to persist revocation list {}: {}", path.display(), e)))
+}
+
+fn auth_error(context: String) -> GuardianError {
+    SecurityError {
+        context,
+        source: None,
+        severity: crate::utils::error::ErrorSeverity::High,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: crate::utils::error::ErrorCategory::Security,
+        retry_count: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::key_provider::FileKeyProvider;
+
+    #[tokio::test]
+    async fn test_tokens_validate_until_tampered_revoked_or_rotated_out() {
+        let dir = tempfile::tempdir().unwrap();
+        let provider: Arc<dyn KeyProvider> = Arc::new(FileKeyProvider::new(dir.path().join("keys")).unwrap());
+        let config = ApiTokenConfig {
+            revocation_path: dir.path().join("revoked.json").to_string_lossy().into_owned(),
+            ..ApiTokenConfig::default()
+        };
+        let authority = TokenAuthority::new(config.clone(), Arc::clone(&provider)).await.unwrap();
+
+        let issued = authority.issue("backup-agent", &["operator".to_string()], None).unwrap();
+        let claims = authority.validate(&issued.token).unwrap();
+        assert_eq!(claims, issued.claims);
+        assert_eq!(scope_principal(&claims.sub, &claims.scopes).identities, vec!["scope:operator"]);
+        assert!(authority.issue("backup-agent", &["nonexistent".to_string()], None).is_err());
+        assert!(authority.issue("backup-agent", &["operator".to_string()], Some(config.max_ttl * 2)).is_err());
+
+        // A flipped claim breaks the signature
+        let mut parts: Vec<&str> = issued.token.split('.').collect();
+        let forged = encode_part(&TokenClaims { scopes: vec!["admin".into()], ..claims.clone() }).unwrap();
+        parts[1] = &forged;
+        assert!(authority.validate(&parts.join(".")).is_err());
+
+        // Rotation keeps old tokens valid until the old version retires
+        let next = provider.stage_rotation(&config.signing_key_id).await.unwrap();
+        authority.apply_key(&config.signing_key_id, &next).await.unwrap();
+        let rotated = authority.issue("backup-agent", &["operator".to_string()], None).unwrap();
+        assert!(authority.validate(&issued.token).is_ok());
+        authority.retire_key(&config.signing_key_id, 1).await.unwrap();
+        assert!(authority.validate(&issued.token).is_err());
+
+        // Revocations survive a restart
+        authority.revoke(&rotated.claims.jti, "leaked").await.unwrap();
+        assert!(authority.validate(&rotated.token).is_err());
+        provider.commit_rotation(&config.signing_key_id, next.version).await.unwrap();
+        let restarted = TokenAuthority::new(config, provider).await.unwrap();
+        assert_eq!(restarted.revoked().len(), 1);
+        assert!(restarted.validate(&rotated.token).is_err());
+    }
+}
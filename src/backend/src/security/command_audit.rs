@@ -0,0 +1,376 @@
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    sync::Arc,
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use clap::ArgMatches;
+use metrics::counter;
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::{info, warn};
+
+use crate::security::audit::{AuditEvent, AuditLogger, SecurityLevel};
+use crate::security::audit_chain::{audit_chain, AuditChain};
+use crate::utils::error::GuardianError;
+
+// Constants for command auditing
+const COMMAND_AUDIT_CAPACITY: usize = 10_000;
+const REDACTED: &str = "[REDACTED]";
+/// Argument names containing any of these have their values redacted
+const SECRET_MARKERS: &[&str] = &["password", "passphrase", "secret", "token", "key", "credential"];
+/// CLI subcommands that only read state and so are left out of the change view
+const READ_ONLY_SUBCOMMANDS: &[&str] = &[
+    "list", "show", "get", "status", "watch", "latency", "transitions", "history", "verify", "diff",
+];
+/// RPC method prefixes that only read state
+const READ_ONLY_RPC_PREFIXES: &[&str] = &["Get", "List", "Stream", "Monitor", "Watch", "Export"];
+
+/// Audit event type of command invocations
+pub const COMMAND_EVENT_TYPE: &str = "command_invocation";
+
+static COMMAND_AUDIT: Lazy<Arc<CommandAudit>> = Lazy::new(|| Arc::new(CommandAudit::new(COMMAND_AUDIT_CAPACITY)));
+
+/// Front end a command was invoked through
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Interface {
+    Cli,
+    Api,
+}
+
+impl Interface {
+    fn label(&self) -> &'static str {
+        match self {
+            Interface::Cli => "cli",
+            Interface::Api => "api",
+        }
+    }
+}
+
+/// Outcome of the RBAC check of an invocation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AccessDecision {
+    Allowed,
+    Denied,
+}
+
+/// One CLI command or API call and what came of it
+#[derive(Debug, Clone)]
+pub struct CommandInvocation {
+    pub interface: Interface,
+    /// `status transitions` for the CLI, the RPC path or `METHOD path` for the API
+    pub command: String,
+    pub principal: String,
+    /// Argument values by name, redacted before recording
+    pub args: BTreeMap<String, String>,
+    pub decision: AccessDecision,
+    pub error: Option<String>,
+    pub duration: Duration,
+}
+
+impl CommandInvocation {
+    /// Whether the command can change state, as opposed to only reading it
+    pub fn is_change(&self) -> bool {
+        match self.interface {
+            Interface::Cli => !self.command.split_whitespace().any(|word| READ_ONLY_SUBCOMMANDS.contains(&word)),
+            // REST calls are recorded as `METHOD path`, gRPC calls by their RPC path
+            Interface::Api if self.command.starts_with("GET ") => false,
+            Interface::Api => {
+                let method = self.command.rsplit('/').next().unwrap_or_default();
+                !READ_ONLY_RPC_PREFIXES.iter().any(|prefix| method.starts_with(prefix))
+            }
+        }
+    }
+
+    fn into_event(self) -> AuditEvent {
+        let change = self.is_change();
+        let outcome = match (&self.decision, &self.error) {
+            (AccessDecision::Denied, _) => "denied",
+            (_, Some(_)) => "failed",
+            _ => "succeeded",
+        };
+        let severity = match (self.decision, change) {
+            (AccessDecision::Denied, _) => SecurityLevel::High,
+            (_, true) => SecurityLevel::Medium,
+            (_, false) => SecurityLevel::Low,
+        };
+        let tags = HashMap::from([
+            ("interface".to_string(), self.interface.label().to_string()),
+            ("principal".to_string(), self.principal.clone()),
+            ("command".to_string(), self.command.clone()),
+            ("outcome".to_string(), outcome.to_string()),
+            ("change".to_string(), change.to_string()),
+        ]);
+        let args_sha256 = args_hash(&self.args);
+        let data = serde_json::json!({
+            "interface": self.interface,
+            "command": self.command,
+            "principal": self.principal,
+            "args": redact_args(self.args),
+            "args_sha256": args_sha256,
+            "decision": self.decision,
+            "outcome": outcome,
+            "error": self.error,
+            "duration_ms": self.duration.as_secs_f64() * 1000.0,
+        });
+
+        let event = AuditEvent::new(
+            COMMAND_EVENT_TYPE.to_string(),
+            severity,
+            format!("{}:{}", self.interface.label(), self.principal),
+            Some(crate::utils::correlation::current_or_new().to_string()),
+        )
+        .with_tags(tags);
+
+        // Oversized argument lists are dropped rather than losing the whole record
+        match event.clone().with_data(data) {
+            Ok(event) => event,
+            Err(_) => {
+                let summary = serde_json::json!({
+                    "command": self.command,
+                    "outcome": outcome,
+                    "args": REDACTED,
+                    "args_sha256": args_sha256,
+                });
+                event.clone().with_data(summary).unwrap_or(event)
+            }
+        }
+    }
+}
+
+/// Filters for the "who changed what" view of command invocations
+#[derive(Debug, Clone, Default)]
+pub struct CommandAuditQuery {
+    pub principal: Option<String>,
+    /// Matches commands starting with this prefix
+    pub command: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    /// Only allowed invocations of commands that can change state
+    pub changes_only: bool,
+    /// 0 returns every match
+    pub limit: usize,
+}
+
+impl CommandAuditQuery {
+    fn matches(&self, event: &AuditEvent) -> bool {
+        let tag = |name: &str| event.tags().get(name).cloned().unwrap_or_default();
+        self.principal.as_ref().map_or(true, |p| &tag("principal") == p)
+            && self.command.as_ref().map_or(true, |c| tag("command").starts_with(c.as_str()))
+            && self.since.map_or(true, |since| event.timestamp() >= since)
+            && (!self.changes_only || (tag("change") == "true" && tag("outcome") != "denied"))
+    }
+
+    fn limit(&self) -> usize {
+        if self.limit == 0 { usize::MAX } else { self.limit }
+    }
+}
+
+/// Records every CLI command and API call as an audit event and keeps recent ones queryable
+#[derive(Debug)]
+pub struct CommandAudit {
+    capacity: usize,
+    events: RwLock<VecDeque<AuditEvent>>,
+    logger: RwLock<Option<Arc<AuditLogger>>>,
+}
+
+/// Returns the process-wide command audit
+pub fn command_audit() -> Arc<CommandAudit> {
+    Arc::clone(&COMMAND_AUDIT)
+}
+
+impl CommandAudit {
+    /// Creates an audit keeping the most recent `capacity` invocations
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            events: RwLock::new(VecDeque::new()),
+            logger: RwLock::new(None),
+        }
+    }
+
+    /// Also writes invocations to the audit trail; without a logger they go to the SECURITY-AUDIT log target
+    pub fn attach_logger(&self, logger: Arc<AuditLogger>) {
+        *self.logger.write() = Some(logger);
+    }
+
+    /// Records an invocation
+    ///
+    /// The event always reaches the audit chain: through the logger when one is attached,
+    /// otherwise appended here, so the ledger holds every command even without an audit logger.
+    pub fn record(&self, invocation: CommandInvocation) {
+        counter!("guardian.audit.commands", 1, "interface" => invocation.interface.label());
+        let mut event = invocation.into_event();
+
+        let logger = self.logger.read().clone();
+        if logger.is_none() {
+            if let Some(chain) = audit_chain() {
+                if let Err(e) = chain.append(&mut event) {
+                    counter!("guardian.audit.commands.ledger_failures", 1);
+                    warn!(error = %e, "Failed to append command invocation to the audit chain");
+                }
+            }
+        }
+
+        match logger {
+            Some(logger) => {
+                let logged = event.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = logger.record_event(logged).await {
+                        warn!(error = %e, "Failed to write command audit event");
+                    }
+                });
+            }
+            None => info!(
+                target: "SECURITY-AUDIT",
+                event_id = %event.id(),
+                source = event.source(),
+                data = %event.data(),
+                "Command invocation"
+            ),
+        }
+
+        let mut events = self.events.write();
+        if events.len() >= self.capacity {
+            events.pop_front();
+        }
+        events.push_back(event);
+    }
+
+    /// Returns matching invocations still held in memory, newest first
+    pub fn query(&self, query: &CommandAuditQuery) -> Vec<AuditEvent> {
+        self.events
+            .read()
+            .iter()
+            .rev()
+            .filter(|e| query.matches(e))
+            .take(query.limit())
+            .cloned()
+            .collect()
+    }
+
+    /// Returns matching invocations from the audit chain, newest first
+    ///
+    /// The chain reaches back as far as the audit subsystem retains it, past the in-memory
+    /// window; without a chain this answers from memory like `query`.
+    pub async fn history(&self, query: &CommandAuditQuery) -> Result<Vec<AuditEvent>, GuardianError> {
+        match audit_chain() {
+            Some(chain) => ledger_query(&chain, query).await,
+            None => Ok(self.query(query)),
+        }
+    }
+}
+
+async fn ledger_query(chain: &AuditChain, query: &CommandAuditQuery) -> Result<Vec<AuditEvent>, GuardianError> {
+    let events = chain.events_of_type(COMMAND_EVENT_TYPE, query.since).await?;
+    Ok(events.into_iter().rev().filter(|e| query.matches(e)).take(query.limit()).collect())
+}
+
+/// SHA-256 of the arguments before redaction, so a known invocation can be matched without storing secrets
+pub fn args_hash(args: &BTreeMap<String, String>) -> String {
+    let mut hasher = Sha256::new();
+    for (name, value) in args {
+        hasher.update(name.as_bytes());
+        hasher.update([0]);
+        hasher.update(value.as_bytes());
+        hasher.update([0]);
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+/// Flattens parsed CLI arguments, including those of nested subcommands, into name/value pairs
+pub fn matches_args(matches: &ArgMatches) -> BTreeMap<String, String> {
+    let mut args = BTreeMap::new();
+    let mut current = Some(matches);
+    while let Some(matches) = current {
+        for id in matches.ids() {
+            if let Ok(Some(values)) = matches.try_get_raw(id.as_str()) {
+                let values: Vec<String> = values.map(|v| v.to_string_lossy().into_owned()).collect();
+                args.insert(id.to_string(), values.join(","));
+            }
+        }
+        current = matches.subcommand().map(|(_, sub)| sub);
+    }
+    args
+}
+
+/// Replaces the values of secret-looking arguments
+pub fn redact_args(args: BTreeMap<String, String>) -> BTreeMap<String, String> {
+    args.into_iter()
+        .map(|(name, value)| {
+            let lower = name.to_ascii_lowercase();
+            if SECRET_MARKERS.iter().any(|marker| lower.contains(marker)) {
+                (name, REDACTED.to_string())
+            } else {
+                (name, value)
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn invocation(command: &str, principal: &str, decision: AccessDecision) -> CommandInvocation {
+        CommandInvocation {
+            interface: Interface::Cli,
+            command: command.into(),
+            principal: principal.into(),
+            args: BTreeMap::from([
+                ("api-token".to_string(), "s3cret".to_string()),
+                ("dataset".to_string(), "tank/events".to_string()),
+            ]),
+            decision,
+            error: None,
+            duration: Duration::from_millis(12),
+        }
+    }
+
+    #[test]
+    fn test_records_redacted_invocations_and_answers_who_changed_what() {
+        let audit = CommandAudit::new(10);
+        audit.record(invocation("backup create", "alice", AccessDecision::Allowed));
+        audit.record(invocation("status transitions", "alice", AccessDecision::Allowed));
+        audit.record(invocation("config set", "mallory", AccessDecision::Denied));
+
+        let changes = audit.query(&CommandAuditQuery { changes_only: true, ..Default::default() });
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].tags()["command"], "backup create");
+        assert_eq!(changes[0].data()["args"]["api-token"], REDACTED);
+        assert_eq!(changes[0].data()["args"]["dataset"], "tank/events");
+
+        let mallory = audit.query(&CommandAuditQuery { principal: Some("mallory".into()), ..Default::default() });
+        assert_eq!(mallory[0].tags()["outcome"], "denied");
+        assert_eq!(audit.query(&CommandAuditQuery { limit: 2, ..Default::default() }).len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_ledger_keeps_invocations_with_argument_hash() {
+        let dir = tempfile::tempdir().unwrap();
+        let chain = AuditChain::open(&dir.path().join("audit.chain")).unwrap();
+        for (command, principal) in [("backup create", "alice"), ("config set", "bob"), ("backup restore", "alice")] {
+            let mut event = invocation(command, principal, AccessDecision::Allowed).into_event();
+            chain.append(&mut event).unwrap();
+        }
+        let mut other = AuditEvent::new("auth.login".into(), SecurityLevel::Low, "test".into(), None);
+        chain.append(&mut other).unwrap();
+
+        let query = CommandAuditQuery { principal: Some("alice".into()), command: Some("backup".into()), ..Default::default() };
+        let events = ledger_query(&chain, &query).await.unwrap();
+        let commands: Vec<&str> = events.iter().map(|e| e.tags()["command"].as_str()).collect();
+        assert_eq!(commands, vec!["backup restore", "backup create"]);
+        assert!(events[0].sequence() > events[1].sequence());
+
+        // The hash covers the secret value that the recorded arguments leave out
+        let args = invocation("backup create", "alice", AccessDecision::Allowed).args;
+        assert_eq!(events[0].data()["args_sha256"], args_hash(&args));
+        let mut changed = args.clone();
+        changed.insert("api-token".into(), "other".into());
+        assert_ne!(args_hash(&changed), args_hash(&args));
+    }
+}
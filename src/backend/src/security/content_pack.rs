@@ -0,0 +1,654 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::Read,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use metrics::counter;
+use parking_lot::Mutex;
+use ring::{digest, signature};
+use serde::{Deserialize, Serialize};
+use tokio::sync::watch;
+use tracing::{info, instrument, warn};
+
+use crate::security::content_simulation::{ContentSimulator, SimulationReport};
+use crate::utils::error::{GuardianError, SecurityError};
+
+// Constants for detection content packs
+pub const ENGINE_VERSION: &str = env!("CARGO_PKG_VERSION");
+const DEFAULT_CONTENT_ROOT: &str = "/var/lib/guardian/content";
+const MANIFEST_FILE: &str = "manifest.json";
+const STATE_FILE: &str = "state.json";
+const STAGING_DIR: &str = ".staging";
+
+/// Kind of detection content shipped in a pack
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContentKind {
+    Rules,
+    Playbooks,
+    Baselines,
+    Intel,
+}
+
+/// A file shipped in a content pack
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContentItem {
+    pub kind: ContentKind,
+    /// Path relative to the pack root
+    pub path: String,
+    pub sha256: String,
+}
+
+/// Another pack that must be active for this one to work
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackDependency {
+    pub name: String,
+    pub min_version: String,
+}
+
+/// Manifest describing a content pack, stored as `manifest.json` in the archive
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContentPackManifest {
+    pub name: String,
+    pub version: String,
+    pub description: String,
+    pub min_engine_version: String,
+    #[serde(default)]
+    pub max_engine_version: Option<String>,
+    #[serde(default)]
+    pub depends_on: Vec<PackDependency>,
+    pub items: Vec<ContentItem>,
+}
+
+/// Installation state of one pack
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PackState {
+    pub active: Option<String>,
+    pub staged: Option<String>,
+    pub previous: Option<String>,
+    pub installed: Vec<String>,
+}
+
+/// Notification sent to consumers when a pack version becomes active
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContentActivation {
+    pub name: String,
+    pub version: String,
+    pub previous_version: Option<String>,
+    pub path: PathBuf,
+}
+
+/// Configuration for the content pack manager
+#[derive(Debug, Clone)]
+pub struct ContentPackConfig {
+    pub root: PathBuf,
+    /// Ed25519 public keys allowed to sign packs
+    pub trusted_keys: Vec<Vec<u8>>,
+    pub engine_version: String,
+}
+
+impl Default for ContentPackConfig {
+    fn default() -> Self {
+        Self {
+            root: PathBuf::from(DEFAULT_CONTENT_ROOT),
+            trusted_keys: Vec::new(),
+            engine_version: ENGINE_VERSION.to_string(),
+        }
+    }
+}
+
+impl ContentPackConfig {
+    /// Adds the base64 keys listed one per line in a file
+    pub fn with_trusted_keys_file(mut self, path: &Path) -> Result<Self, GuardianError> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| content_error(format!("Failed to read trusted keys {}", path.display()), Some(Box::new(e))))?;
+        for line in text.lines().map(str::trim).filter(|l| !l.is_empty() && !l.starts_with('#')) {
+            let key = BASE64
+                .decode(line)
+                .map_err(|e| content_error(format!("Invalid trusted key in {}", path.display()), Some(Box::new(e))))?;
+            self.trusted_keys.push(key);
+        }
+        Ok(self)
+    }
+}
+
+/// Installs, activates and rolls back signed detection content packs
+/// independently of the Guardian binary
+#[derive(Debug)]
+pub struct ContentPackManager {
+    config: ContentPackConfig,
+    state: Mutex<BTreeMap<String, PackState>>,
+    activation_tx: Arc<watch::Sender<Option<ContentActivation>>>,
+}
+
+impl ContentPackManager {
+    /// Opens the content store, loading any existing installation state
+    pub fn open(config: ContentPackConfig) -> Result<Self, GuardianError> {
+        fs::create_dir_all(&config.root)
+            .map_err(|e| content_error(format!("Failed to create {}", config.root.display()), Some(Box::new(e))))?;
+
+        let state_path = config.root.join(STATE_FILE);
+        let state = match fs::read(&state_path) {
+            Ok(raw) => serde_json::from_slice(&raw)
+                .map_err(|e| content_error("Corrupt content pack state".into(), Some(Box::new(e))))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(e) => return Err(content_error("Failed to read content pack state".into(), Some(Box::new(e)))),
+        };
+
+        let (activation_tx, _) = watch::channel(None);
+        Ok(Self {
+            config,
+            state: Mutex::new(state),
+            activation_tx: Arc::new(activation_tx),
+        })
+    }
+
+    /// Verifies and unpacks a signed pack, staging it for activation
+    #[instrument(skip(self))]
+    pub fn install(&self, archive: &Path, signature_path: &Path) -> Result<ContentPackManifest, GuardianError> {
+        let bytes = fs::read(archive)
+            .map_err(|e| content_error(format!("Failed to read {}", archive.display()), Some(Box::new(e))))?;
+        let encoded = fs::read_to_string(signature_path)
+            .map_err(|e| content_error(format!("Failed to read {}", signature_path.display()), Some(Box::new(e))))?;
+        let sig = BASE64
+            .decode(encoded.trim())
+            .map_err(|e| content_error("Content pack signature is not base64".into(), Some(Box::new(e))))?;
+        self.verify_signature(&bytes, &sig)?;
+
+        // Unpack into a scratch directory so a rejected pack leaves nothing behind
+        let staging = self.config.root.join(STAGING_DIR);
+        let _ = fs::remove_dir_all(&staging);
+        fs::create_dir_all(&staging)
+            .map_err(|e| content_error("Failed to create staging directory".into(), Some(Box::new(e))))?;
+        let result = self.unpack_and_stage(&bytes, &staging);
+        let _ = fs::remove_dir_all(&staging);
+        let manifest = result?;
+
+        counter!("guardian.content.installed", 1, "pack" => manifest.name.clone());
+        info!(pack = %manifest.name, version = %manifest.version, "Content pack staged");
+        Ok(manifest)
+    }
+
+    /// Promotes the staged version of a pack to active
+    #[instrument(skip(self))]
+    pub fn activate(&self, name: &str) -> Result<ContentActivation, GuardianError> {
+        self.activate_staged(name, None)
+    }
+
+    /// Promotes the staged version of a pack, provided its manifest still has the `expected`
+    /// digest, so a pack restaged after it was evaluated is not activated in its place
+    fn activate_staged(&self, name: &str, expected: Option<&str>) -> Result<ContentActivation, GuardianError> {
+        let mut state = self.state.lock();
+        let pack = state
+            .get(name)
+            .cloned()
+            .ok_or_else(|| content_error(format!("Content pack {} is not installed", name), None))?;
+        let version = pack
+            .staged
+            .clone()
+            .ok_or_else(|| content_error(format!("Content pack {} has no staged version", name), None))?;
+        if let Some(expected) = expected {
+            if self.manifest_digest(name, &version)? != expected {
+                counter!("guardian.content.activation_blocked", 1, "pack" => name.to_string());
+                return Err(content_error(
+                    format!("Content pack {} was restaged as {} after it was simulated; simulate it again", name, version),
+                    None,
+                ));
+            }
+        }
+
+        // Dependencies may have been rolled back since the pack was staged
+        let manifest = self.read_manifest(name, &version)?;
+        check_dependencies(&manifest, &state)?;
+
+        let entry = state.get_mut(name).expect("pack state present");
+        entry.previous = entry.active.replace(version.clone());
+        entry.staged = None;
+        let activation = ContentActivation {
+            name: name.to_string(),
+            version: version.clone(),
+            previous_version: entry.previous.clone(),
+            path: self.pack_dir(name, &version),
+        };
+        self.persist(&state)?;
+        drop(state);
+
+        self.activation_tx.send_replace(Some(activation.clone()));
+        counter!("guardian.content.activated", 1, "pack" => name.to_string());
+        info!(pack = name, version = %version, previous = ?activation.previous_version, "Content pack activated");
+        Ok(activation)
+    }
+
+    /// Replays recent history against the staged version before activating it,
+    /// refusing without confirmation when alert volume would grow past the limit
+    #[instrument(skip(self, simulator))]
+    pub async fn activate_evaluated(
+        &self,
+        name: &str,
+        simulator: &ContentSimulator,
+        confirmed: bool,
+    ) -> Result<(ContentActivation, SimulationReport), GuardianError> {
+        let (dir, manifest) = self
+            .staged(name)
+            .ok_or_else(|| content_error(format!("Content pack {} has no staged version", name), None))?;
+        let digest = self.manifest_digest(name, &manifest.version)?;
+        let report = simulator.simulate(&dir, &manifest).await?;
+
+        if report.requires_confirmation && !confirmed {
+            counter!("guardian.content.activation_blocked", 1, "pack" => name.to_string());
+            return Err(content_error(
+                format!(
+                    "Activating {} {} would change alert volume by {:.2}x ({} -> {}); confirmation required",
+                    name, manifest.version, report.increase_factor, report.baseline_alerts, report.candidate_alerts
+                ),
+                None,
+            ));
+        }
+        if report.requires_confirmation {
+            warn!(target: "SECURITY-AUDIT", pack = name, factor = report.increase_factor,
+                "Content pack activation confirmed despite alert volume increase");
+        }
+
+        Ok((self.activate_staged(name, Some(&digest))?, report))
+    }
+
+    /// Returns the directory and manifest of the staged version of a pack
+    pub fn staged(&self, name: &str) -> Option<(PathBuf, ContentPackManifest)> {
+        let version = self.state.lock().get(name)?.staged.clone()?;
+        let manifest = self.read_manifest(name, &version).ok()?;
+        Some((self.pack_dir(name, &version), manifest))
+    }
+
+    /// Restores the previously active version of a pack
+    #[instrument(skip(self))]
+    pub fn rollback(&self, name: &str) -> Result<ContentActivation, GuardianError> {
+        let mut state = self.state.lock();
+        let entry = state
+            .get_mut(name)
+            .ok_or_else(|| content_error(format!("Content pack {} is not installed", name), None))?;
+        let previous = entry
+            .previous
+            .take()
+            .ok_or_else(|| content_error(format!("Content pack {} has no previous version", name), None))?;
+
+        let rolled_back = entry.active.replace(previous.clone());
+        let activation = ContentActivation {
+            name: name.to_string(),
+            version: previous.clone(),
+            previous_version: rolled_back.clone(),
+            path: self.pack_dir(name, &previous),
+        };
+        self.persist(&state)?;
+        drop(state);
+
+        self.activation_tx.send_replace(Some(activation.clone()));
+        counter!("guardian.content.rollbacks", 1, "pack" => name.to_string());
+        warn!(pack = name, version = %previous, rolled_back = ?rolled_back, "Content pack rolled back");
+        Ok(activation)
+    }
+
+    /// Returns the state of every installed pack
+    pub fn list(&self) -> BTreeMap<String, PackState> {
+        self.state.lock().clone()
+    }
+
+    /// Returns the directory of the active version of a pack
+    pub fn active_path(&self, name: &str) -> Option<PathBuf> {
+        let state = self.state.lock();
+        let version = state.get(name)?.active.clone()?;
+        Some(self.pack_dir(name, &version))
+    }
+
+    /// Returns the manifests of every active pack version
+    pub fn active_manifests(&self) -> Vec<ContentPackManifest> {
+        let active: Vec<(String, String)> = self
+            .state
+            .lock()
+            .iter()
+            .filter_map(|(name, state)| Some((name.clone(), state.active.clone()?)))
+            .collect();
+        active
+            .into_iter()
+            .filter_map(|(name, version)| match self.read_manifest(&name, &version) {
+                Ok(manifest) => Some(manifest),
+                Err(e) => {
+                    warn!(pack = %name, version = %version, error = %e, "Failed to read active pack manifest");
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Subscribes to pack activations and rollbacks
+    pub fn subscribe(&self) -> watch::Receiver<Option<ContentActivation>> {
+        self.activation_tx.subscribe()
+    }
+
+    fn verify_signature(&self, bytes: &[u8], sig: &[u8]) -> Result<(), GuardianError> {
+        let trusted = self.config.trusted_keys.iter().any(|key| {
+            signature::UnparsedPublicKey::new(&signature::ED25519, key)
+                .verify(bytes, sig)
+                .is_ok()
+        });
+        if !trusted {
+            counter!("guardian.content.signature_failures", 1);
+            return Err(content_error("Content pack is not signed by a trusted key".into(), None));
+        }
+        Ok(())
+    }
+
+    fn unpack_and_stage(&self, bytes: &[u8], staging: &Path) -> Result<ContentPackManifest, GuardianError> {
+        let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(bytes));
+        let entries = archive
+            .entries()
+            .map_err(|e| content_error("Invalid content pack archive".into(), Some(Box::new(e))))?;
+        for entry in entries {
+            let mut entry = entry.map_err(|e| content_error("Invalid content pack entry".into(), Some(Box::new(e))))?;
+            // unpack_in refuses entries escaping the staging directory
+            entry
+                .unpack_in(staging)
+                .map_err(|e| content_error("Failed to unpack content pack".into(), Some(Box::new(e))))?;
+        }
+
+        let manifest = read_manifest_file(&staging.join(MANIFEST_FILE))?;
+        validate_name(&manifest.name)?;
+        validate_name(&manifest.version)?;
+        check_engine(&manifest, &self.config.engine_version)?;
+        for item in &manifest.items {
+            verify_item(staging, item)?;
+        }
+
+        let mut state = self.state.lock();
+        check_dependencies(&manifest, &state)?;
+
+        let target = self.pack_dir(&manifest.name, &manifest.version);
+        if state.get(&manifest.name).map_or(false, |p| p.active.as_deref() == Some(manifest.version.as_str())) {
+            return Err(content_error(
+                format!("Content pack {} {} is already active", manifest.name, manifest.version),
+                None,
+            ));
+        }
+        let _ = fs::remove_dir_all(&target);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| content_error("Failed to create pack directory".into(), Some(Box::new(e))))?;
+        }
+        fs::rename(staging, &target)
+            .map_err(|e| content_error("Failed to move staged pack into place".into(), Some(Box::new(e))))?;
+
+        let entry = state.entry(manifest.name.clone()).or_default();
+        entry.staged = Some(manifest.version.clone());
+        if !entry.installed.contains(&manifest.version) {
+            entry.installed.push(manifest.version.clone());
+        }
+        self.persist(&state)?;
+        Ok(manifest)
+    }
+
+    fn read_manifest(&self, name: &str, version: &str) -> Result<ContentPackManifest, GuardianError> {
+        read_manifest_file(&self.pack_dir(name, version).join(MANIFEST_FILE))
+    }
+
+    /// SHA-256 of a version's manifest, which pins the digest of every item it ships
+    fn manifest_digest(&self, name: &str, version: &str) -> Result<String, GuardianError> {
+        let path = self.pack_dir(name, version).join(MANIFEST_FILE);
+        let bytes = fs::read(&path)
+            .map_err(|e| content_error(format!("Failed to read {}", path.display()), Some(Box::new(e))))?;
+        Ok(to_hex(digest::digest(&digest::SHA256, &bytes).as_ref()))
+    }
+
+    fn pack_dir(&self, name: &str, version: &str) -> PathBuf {
+        self.config.root.join(name).join(version)
+    }
+
+    fn persist(&self, state: &BTreeMap<String, PackState>) -> Result<(), GuardianError> {
+        let raw = serde_json::to_vec_pretty(state)
+            .map_err(|e| content_error("Failed to serialize content pack state".into(), Some(Box::new(e))))?;
+        let tmp = self.config.root.join(format!("{}.tmp", STATE_FILE));
+        fs::write(&tmp, raw)
+            .and_then(|_| fs::rename(&tmp, self.config.root.join(STATE_FILE)))
+            .map_err(|e| content_error("Failed to write content pack state".into(), Some(Box::new(e))))
+    }
+}
+
+fn read_manifest_file(path: &Path) -> Result<ContentPackManifest, GuardianError> {
+    let raw = fs::read(path)
+        .map_err(|e| content_error("Content pack has no manifest".into(), Some(Box::new(e))))?;
+    serde_json::from_slice(&raw).map_err(|e| content_error("Invalid content pack manifest".into(), Some(Box::new(e))))
+}
+
+/// Rejects names that could escape the content root
+fn validate_name(value: &str) -> Result<(), GuardianError> {
+    let valid = !value.is_empty()
+        && !value.starts_with('.')
+        && value.chars().all(|c: char| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'));
+    if !valid {
+        return Err(content_error(format!("Invalid content pack identifier: {}", value), None));
+    }
+    Ok(())
+}
+
+fn verify_item(root: &Path, item: &ContentItem) -> Result<(), GuardianError> {
+    let mut file = fs::File::open(root.join(&item.path))
+        .map_err(|e| content_error(format!("Content pack is missing {}", item.path), Some(Box::new(e))))?;
+    let mut context = digest::Context::new(&digest::SHA256);
+    let mut buffer = [0u8; 8192];
+    loop {
+        let read = file
+            .read(&mut buffer)
+            .map_err(|e| content_error(format!("Failed to read {}", item.path), Some(Box::new(e))))?;
+        if read == 0 {
+            break;
+        }
+        context.update(&buffer[..read]);
+    }
+
+    if !to_hex(context.finish().as_ref()).eq_ignore_ascii_case(&item.sha256) {
+        return Err(content_error(format!("Checksum mismatch for {}", item.path), None));
+    }
+    Ok(())
+}
+
+fn check_engine(manifest: &ContentPackManifest, engine_version: &str) -> Result<(), GuardianError> {
+    let engine = parse_version(engine_version)?;
+    if engine < parse_version(&manifest.min_engine_version)? {
+        return Err(content_error(
+            format!("{} requires engine {} or newer, running {}", manifest.name, manifest.min_engine_version, engine_version),
+            None,
+        ));
+    }
+    if let Some(max) = &manifest.max_engine_version {
+        if engine > parse_version(max)? {
+            return Err(content_error(
+                format!("{} supports engine up to {}, running {}", manifest.name, max, engine_version),
+                None,
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn check_dependencies(manifest: &ContentPackManifest, state: &BTreeMap<String, PackState>) -> Result<(), GuardianError> {
+    for dependency in &manifest.depends_on {
+        let active = state.get(&dependency.name).and_then(|p| p.active.as_deref());
+        let satisfied = match active {
+            Some(version) => parse_version(version)? >= parse_version(&dependency.min_version)?,
+            None => false,
+        };
+        if !satisfied {
+            return Err(content_error(
+                format!(
+                    "{} requires {} {} or newer to be active",
+                    manifest.name, dependency.name, dependency.min_version
+                ),
+                None,
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Parses `major.minor.patch`, ignoring any pre-release or build suffix
+fn parse_version(version: &str) -> Result<(u64, u64, u64), GuardianError> {
+    let core = version.split(|c: char| c == '-' || c == '+').next().unwrap_or_default();
+    let mut parts = core.split('.').map(|p| p.parse::<u64>());
+    match (parts.next(), parts.next(), parts.next(), parts.next()) {
+        (Some(Ok(major)), Some(Ok(minor)), Some(Ok(patch)), None) => Ok((major, minor, patch)),
+        _ => Err(content_error(format!("Invalid version: {}", version), None)),
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn content_error(context: String, source: Option<Box<dyn std::error::Error + Send + Sync>>) -> GuardianError {
+    SecurityError {
+        context,
+        source,
+        severity: crate::utils::error::ErrorSeverity::High,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: crate::utils::error::ErrorCategory::Security,
+        retry_count: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ring::signature::KeyPair;
+
+    fn build_pack(name: &str, version: &str, rules: &[u8]) -> Vec<u8> {
+        let manifest = ContentPackManifest {
+            name: name.into(),
+            version: version.into(),
+            description: "test rules".into(),
+            min_engine_version: "0.1.0".into(),
+            max_engine_version: None,
+            depends_on: Vec::new(),
+            items: vec![ContentItem {
+                kind: ContentKind::Rules,
+                path: "rules/process.yaml".into(),
+                sha256: to_hex(digest::digest(&digest::SHA256, rules).as_ref()),
+            }],
+        };
+        let manifest = serde_json::to_vec(&manifest).unwrap();
+
+        let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default()));
+        for (path, data) in [(MANIFEST_FILE, manifest.as_slice()), ("rules/process.yaml", rules)] {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(data.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder.append_data(&mut header, path, data).unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap()
+    }
+
+    #[test]
+    fn test_install_activate_and_rollback() {
+        let dir = tempfile::tempdir().unwrap();
+        let rng = ring::rand::SystemRandom::new();
+        let pkcs8 = signature::Ed25519KeyPair::generate_pkcs8(&rng).unwrap();
+        let key_pair = signature::Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+
+        let manager = ContentPackManager::open(ContentPackConfig {
+            root: dir.path().join("content"),
+            trusted_keys: vec![key_pair.public_key().as_ref().to_vec()],
+            engine_version: "1.0.0".into(),
+        })
+        .unwrap();
+
+        let install = |version: &str, tamper: bool| {
+            let pack = build_pack("core-rules", version, b"rules: []");
+            let archive = dir.path().join(format!("core-rules-{}.gcp", version));
+            let sig = dir.path().join(format!("core-rules-{}.sig", version));
+            fs::write(&archive, &pack).unwrap();
+            let mut signed = pack.clone();
+            if tamper {
+                signed.push(0);
+            }
+            fs::write(&sig, BASE64.encode(key_pair.sign(&signed).as_ref())).unwrap();
+            manager.install(&archive, &sig)
+        };
+
+        assert!(install("1.0.0", true).is_err());
+        install("1.0.0", false).unwrap();
+        assert_eq!(manager.list()["core-rules"].staged.as_deref(), Some("1.0.0"));
+        assert!(manager.active_path("core-rules").is_none());
+
+        manager.activate("core-rules").unwrap();
+        install("1.1.0", false).unwrap();
+        let activation = manager.activate("core-rules").unwrap();
+        assert_eq!(activation.previous_version.as_deref(), Some("1.0.0"));
+
+        let rolled_back = manager.rollback("core-rules").unwrap();
+        assert_eq!(rolled_back.version, "1.0.0");
+        assert!(manager.active_path("core-rules").unwrap().join("rules/process.yaml").exists());
+        assert!(manager.rollback("core-rules").is_err());
+    }
+
+    #[test]
+    fn test_restaged_pack_not_activated_in_place_of_evaluated_one() {
+        let dir = tempfile::tempdir().unwrap();
+        let rng = ring::rand::SystemRandom::new();
+        let pkcs8 = signature::Ed25519KeyPair::generate_pkcs8(&rng).unwrap();
+        let key_pair = signature::Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+        let manager = ContentPackManager::open(ContentPackConfig {
+            root: dir.path().join("content"),
+            trusted_keys: vec![key_pair.public_key().as_ref().to_vec()],
+            engine_version: "1.0.0".into(),
+        })
+        .unwrap();
+
+        let install = |rules: &[u8]| {
+            let pack = build_pack("core-rules", "1.0.0", rules);
+            let (archive, sig) = (dir.path().join("core-rules.gcp"), dir.path().join("core-rules.sig"));
+            fs::write(&archive, &pack).unwrap();
+            fs::write(&sig, BASE64.encode(key_pair.sign(&pack).as_ref())).unwrap();
+            manager.install(&archive, &sig).unwrap();
+        };
+
+        install(b"rules: []");
+        let evaluated = manager.manifest_digest("core-rules", "1.0.0").unwrap();
+        // Same version, different rules, staged while the first was being simulated
+        install(b"rules: [noisy]");
+        assert!(manager.activate_staged("core-rules", Some(&evaluated)).is_err());
+        assert_eq!(manager.list()["core-rules"].staged.as_deref(), Some("1.0.0"));
+
+        let restaged = manager.manifest_digest("core-rules", "1.0.0").unwrap();
+        assert_eq!(manager.activate_staged("core-rules", Some(&restaged)).unwrap().version, "1.0.0");
+    }
+
+    #[test]
+    fn test_engine_and_dependency_checks() {
+        let mut manifest: ContentPackManifest = serde_json::from_value(serde_json::json!({
+            "name": "playbooks",
+            "version": "2.0.0",
+            "description": "",
+            "min_engine_version": "1.2.0",
+            "max_engine_version": "1.9.9",
+            "depends_on": [{ "name": "core-rules", "min_version": "1.1.0" }],
+            "items": [],
+        }))
+        .unwrap();
+
+        assert!(check_engine(&manifest, "1.1.9").is_err());
+        assert!(check_engine(&manifest, "1.2.0-rc1").is_ok());
+        assert!(check_engine(&manifest, "2.0.0").is_err());
+
+        let mut state = BTreeMap::new();
+        assert!(check_dependencies(&manifest, &state).is_err());
+        state.insert("core-rules".to_string(), PackState { active: Some("1.1.0".into()), ..Default::default() });
+        assert!(check_dependencies(&manifest, &state).is_ok());
+
+        manifest.depends_on[0].min_version = "1.2.0".into();
+        assert!(check_dependencies(&manifest, &state).is_err());
+        assert!(validate_name("../etc").is_err());
+    }
+}
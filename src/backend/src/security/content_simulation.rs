@@ -0,0 +1,303 @@
+use std::{
+    collections::BTreeMap,
+    path::Path,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use metrics::{counter, histogram};
+use serde::{Deserialize, Serialize};
+use tracing::{info, instrument, warn};
+
+use crate::ml::inference_engine::Prediction;
+use crate::security::anomaly_detection::SystemData;
+use crate::security::content_pack::{ContentKind, ContentPackManifest};
+use crate::security::detection_pipeline::{DetectionPipeline, PipelineConfig, StageRegistry};
+use crate::storage::event_store::{Event as StoredEvent, EventQuery, EventStore};
+use crate::utils::error::{GuardianError, SecurityError};
+use crate::utils::ids::{next_id, IdKind};
+
+// Constants for content pack simulation
+pub const SAMPLE_HISTORY_EVENT_TYPE: &str = "detection_samples";
+const DEFAULT_HISTORY_WINDOW: Duration = Duration::from_secs(24 * 60 * 60);
+const DEFAULT_MAX_BATCHES: usize = 1000;
+const DEFAULT_MAX_INCREASE_FACTOR: f64 = 1.5;
+const SIMULATION_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// A batch of samples seen by the live pipeline and the alerts it raised
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SampleBatch {
+    pub samples: Vec<SystemData>,
+    pub alerts: BTreeMap<String, u64>,
+}
+
+impl SampleBatch {
+    /// Summarizes the predictions raised for a batch of samples
+    pub fn new(samples: Vec<SystemData>, predictions: &[Prediction]) -> Self {
+        Self {
+            samples,
+            alerts: count_by_type(predictions),
+        }
+    }
+
+    /// Stored event recording the batch for later replay
+    pub fn to_event(&self) -> Result<StoredEvent, GuardianError> {
+        Ok(StoredEvent {
+            id: next_id(IdKind::Event).to_string(),
+            timestamp: unix_now(),
+            event_type: SAMPLE_HISTORY_EVENT_TYPE.to_string(),
+            payload: serde_json::to_value(self)
+                .map_err(|e| simulation_error(format!("Failed to serialize sample batch: {}", e)))?,
+            integrity_hash: String::new(),
+        })
+    }
+}
+
+/// Alert counts for one prediction type
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AlertDelta {
+    pub baseline: u64,
+    pub candidate: u64,
+}
+
+/// Expected alert volume change from activating a content pack
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulationReport {
+    pub pack: String,
+    pub version: String,
+    /// False when the pack ships no detection pipeline to replay
+    pub evaluated: bool,
+    pub batches: usize,
+    pub samples: usize,
+    pub failed_batches: usize,
+    pub baseline_alerts: u64,
+    pub candidate_alerts: u64,
+    pub by_type: BTreeMap<String, AlertDelta>,
+    pub increase_factor: f64,
+    pub requires_confirmation: bool,
+}
+
+/// Configuration for content pack simulation
+#[derive(Debug, Clone)]
+pub struct SimulationConfig {
+    pub history_window: Duration,
+    pub max_batches: usize,
+    /// Candidate/baseline alert ratio above which activation needs confirmation
+    pub max_increase_factor: f64,
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        Self {
+            history_window: DEFAULT_HISTORY_WINDOW,
+            max_batches: DEFAULT_MAX_BATCHES,
+            max_increase_factor: DEFAULT_MAX_INCREASE_FACTOR,
+        }
+    }
+}
+
+/// Replays recorded detection history through a staged pack's pipeline
+/// without publishing events or executing responses
+#[derive(Debug)]
+pub struct ContentSimulator {
+    event_store: Arc<EventStore>,
+    stage_registry: StageRegistry,
+    config: SimulationConfig,
+}
+
+impl ContentSimulator {
+    /// Creates a simulator replaying history from the given store
+    pub fn new(event_store: Arc<EventStore>, stage_registry: StageRegistry) -> Self {
+        Self {
+            event_store,
+            stage_registry,
+            config: SimulationConfig::default(),
+        }
+    }
+
+    /// Overrides the simulation configuration
+    pub fn with_config(mut self, config: SimulationConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Replays recent history against the pipeline shipped in an unpacked pack
+    #[instrument(skip(self, manifest), fields(pack = %manifest.name, version = %manifest.version))]
+    pub async fn simulate(&self, pack_dir: &Path, manifest: &ContentPackManifest) -> Result<SimulationReport, GuardianError> {
+        let start = std::time::Instant::now();
+        let Some(pipeline_config) = pack_pipeline(pack_dir, manifest)? else {
+            return Ok(summarize(manifest, false, Vec::new(), 0, self.config.max_increase_factor));
+        };
+        let pipeline = DetectionPipeline::from_config(&pipeline_config, &self.stage_registry)?
+            .without_latency_tracking();
+        let history = self.load_history().await?;
+
+        let replay = async {
+            let mut results = Vec::with_capacity(history.len());
+            let mut failed = 0;
+            for batch in history {
+                match pipeline.run(batch.samples.clone()).await {
+                    Ok(predictions) => results.push((batch, count_by_type(&predictions))),
+                    Err(e) => {
+                        warn!(error = %e, "Simulated detection cycle failed");
+                        failed += 1;
+                    }
+                }
+            }
+            (results, failed)
+        };
+        let (results, failed) = tokio::time::timeout(SIMULATION_TIMEOUT, replay)
+            .await
+            .map_err(|_| simulation_error(format!("Simulation exceeded {:?}", SIMULATION_TIMEOUT)))?;
+
+        let report = summarize(manifest, true, results, failed, self.config.max_increase_factor);
+        histogram!("guardian.content.simulation.duration", start.elapsed().as_secs_f64());
+        counter!("guardian.content.simulations", 1,
+            "requires_confirmation" => report.requires_confirmation.to_string());
+        info!(
+            baseline = report.baseline_alerts,
+            candidate = report.candidate_alerts,
+            factor = report.increase_factor,
+            "Content pack simulation complete"
+        );
+        Ok(report)
+    }
+
+    async fn load_history(&self) -> Result<Vec<SampleBatch>, GuardianError> {
+        let since = unix_now().saturating_sub(self.config.history_window.as_secs());
+        let events = self
+            .event_store
+            .retrieve_events(EventQuery {
+                start_time: Some(since),
+                end_time: None,
+                event_type: Some(SAMPLE_HISTORY_EVENT_TYPE.to_string()),
+                limit: Some(self.config.max_batches),
+            })
+            .await?;
+        Ok(events
+            .into_iter()
+            .filter_map(|event| serde_json::from_value(event.payload).ok())
+            .collect())
+    }
+}
+
+/// Loads the detection pipeline shipped as a rules item, if the pack has one
+fn pack_pipeline(pack_dir: &Path, manifest: &ContentPackManifest) -> Result<Option<PipelineConfig>, GuardianError> {
+    let Some(item) = manifest
+        .items
+        .iter()
+        .find(|item| item.kind == ContentKind::Rules && item.path.ends_with(".json"))
+    else {
+        return Ok(None);
+    };
+
+    let raw = std::fs::read(pack_dir.join(&item.path))
+        .map_err(|e| simulation_error(format!("Failed to read {}: {}", item.path, e)))?;
+    serde_json::from_slice(&raw)
+        .map(Some)
+        .map_err(|e| simulation_error(format!("{} is not a pipeline configuration: {}", item.path, e)))
+}
+
+fn summarize(
+    manifest: &ContentPackManifest,
+    evaluated: bool,
+    results: Vec<(SampleBatch, BTreeMap<String, u64>)>,
+    failed_batches: usize,
+    max_increase_factor: f64,
+) -> SimulationReport {
+    let mut by_type: BTreeMap<String, AlertDelta> = BTreeMap::new();
+    let mut samples = 0;
+    for (batch, candidate) in &results {
+        samples += batch.samples.len();
+        for (kind, count) in &batch.alerts {
+            by_type.entry(kind.clone()).or_default().baseline += count;
+        }
+        for (kind, count) in candidate {
+            by_type.entry(kind.clone()).or_default().candidate += count;
+        }
+    }
+
+    let baseline_alerts: u64 = by_type.values().map(|d| d.baseline).sum();
+    let candidate_alerts: u64 = by_type.values().map(|d| d.candidate).sum();
+    // A quiet baseline counts as one alert so any new noise still registers as growth
+    let increase_factor = candidate_alerts as f64 / baseline_alerts.max(1) as f64;
+
+    SimulationReport {
+        pack: manifest.name.clone(),
+        version: manifest.version.clone(),
+        evaluated,
+        batches: results.len(),
+        samples,
+        failed_batches,
+        baseline_alerts,
+        candidate_alerts,
+        by_type,
+        increase_factor,
+        requires_confirmation: evaluated && (increase_factor > max_increase_factor || failed_batches > 0),
+    }
+}
+
+fn count_by_type(predictions: &[Prediction]) -> BTreeMap<String, u64> {
+    let mut counts = BTreeMap::new();
+    for prediction in predictions {
+        *counts.entry(prediction.prediction_type().to_string()).or_insert(0) += 1;
+    }
+    counts
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or_default()
+}
+
+fn simulation_error(context: String) -> GuardianError {
+    SecurityError {
+        context,
+        source: None,
+        severity: crate::utils::error::ErrorSeverity::Medium,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: crate::utils::error::ErrorCategory::Security,
+        retry_count: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest() -> ContentPackManifest {
+        serde_json::from_value(serde_json::json!({
+            "name": "core-rules",
+            "version": "1.1.0",
+            "description": "",
+            "min_engine_version": "0.1.0",
+            "items": [],
+        }))
+        .unwrap()
+    }
+
+    fn batch(alerts: &[(&str, u64)]) -> SampleBatch {
+        SampleBatch {
+            samples: vec![SystemData { metrics: Default::default(), events: Vec::new(), timestamp: 0 }],
+            alerts: alerts.iter().map(|(k, v)| (k.to_string(), *v)).collect(),
+        }
+    }
+
+    #[test]
+    fn test_alert_increase_requires_confirmation() {
+        let candidate = |alerts: &[(&str, u64)]| alerts.iter().map(|(k, v)| (k.to_string(), *v)).collect();
+        let results = vec![
+            (batch(&[("malware", 2)]), candidate(&[("malware", 2), ("lateral_movement", 3)])),
+            (batch(&[("malware", 2)]), candidate(&[("malware", 1)])),
+        ];
+
+        let report = summarize(&manifest(), true, results.clone(), 0, 1.5);
+        assert_eq!((report.baseline_alerts, report.candidate_alerts), (4, 6));
+        assert_eq!(report.by_type["lateral_movement"], AlertDelta { baseline: 0, candidate: 3 });
+        assert_eq!(report.samples, 2);
+        assert!(!report.requires_confirmation);
+
+        assert!(summarize(&manifest(), true, results, 0, 1.2).requires_confirmation);
+        assert!(!summarize(&manifest(), false, Vec::new(), 0, 1.2).requires_confirmation);
+    }
+}
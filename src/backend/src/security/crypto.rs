@@ -1,13 +1,17 @@
-use ring::{aead, rand, pbkdf2};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use ring::{aead, hmac, rand, pbkdf2};
+use serde::{Deserialize, Serialize};
 use tokio::sync::RwLock;
 use tracing::{debug, error, info, warn};
-use zeroize::{Zeroize, ZeroizeOnDrop};
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
 use std::{
     collections::HashMap,
     sync::Arc,
     time::{Duration, SystemTime},
 };
 use crate::utils::error::{GuardianError, ErrorSeverity, ErrorCategory};
+use crate::core::operations::OperationHandle;
+use crate::security::key_provider::{KeyMaterial, KeyRotationCoordinator, KeyRotationParticipant};
 
 // Version: ring = "0.17"
 // Version: tokio = "1.32"
@@ -15,7 +19,6 @@ use crate::utils::error::{GuardianError, ErrorSeverity, ErrorCategory};
 // Version: zeroize = "1.6"
 
 /// Constants for cryptographic operations
-const KEY_ROTATION_INTERVAL: Duration = Duration::from_secs(86400); // 24 hours
 const MAX_KEY_SIZE: usize = 32; // 256 bits
 const NONCE_SIZE: usize = 12; // 96 bits for AES-GCM
 const MIN_ENTROPY_THRESHOLD: f64 = 0.75;
@@ -25,6 +28,26 @@ const KEY_VERSION_TIMEOUT: Duration = Duration::from_secs(300);
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
 pub struct KeyId(String);
 
+impl KeyId {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self(id.into())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// HMAC-SHA256 tag binding data to one version of a key
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DataSignature {
+    pub key_id: String,
+    /// Version of the key that produced the tag, so it still verifies after rotation
+    pub key_version: u64,
+    /// Base64 tag
+    pub tag: String,
+}
+
 /// Tracks key versions with metadata
 #[derive(Debug, Clone, ZeroizeOnDrop)]
 struct KeyVersion {
@@ -91,7 +114,73 @@ pub struct CryptoManager {
     tpm_client: Arc<TpmClient>,
     geli_manager: Arc<GeliManager>,
     key_versions: Arc<RwLock<HashMap<KeyId, KeyVersion>>>,
+    /// Versions replaced by a rotation and still inside their overlap window
+    retiring_versions: Arc<RwLock<HashMap<KeyId, KeyVersion>>>,
     key_usage_log: Arc<RwLock<KeyUsageAudit>>,
+    key_rotation: Option<Arc<KeyRotationCoordinator>>,
+}
+
+/// Installs rotated keys into a CryptoManager on behalf of the rotation coordinator
+struct CryptoKeyParticipant {
+    key_versions: Arc<RwLock<HashMap<KeyId, KeyVersion>>>,
+    retiring_versions: Arc<RwLock<HashMap<KeyId, KeyVersion>>>,
+    key_usage_log: Arc<RwLock<KeyUsageAudit>>,
+}
+
+#[async_trait::async_trait]
+impl KeyRotationParticipant for CryptoKeyParticipant {
+    fn name(&self) -> &str {
+        "crypto"
+    }
+
+    async fn key_ids(&self) -> Vec<String> {
+        self.key_versions.read().await.keys().map(|key_id| key_id.0.clone()).collect()
+    }
+
+    async fn apply_key(&self, key_id: &str, key: &KeyMaterial) -> Result<(), GuardianError> {
+        let key_id = KeyId(key_id.to_string());
+        let mut keys = self.key_versions.write().await;
+        let mut retiring = self.retiring_versions.write().await;
+        let replaced = keys.insert(key_id.clone(), KeyVersion::from_material(key));
+        let old_version = replaced.as_ref().map(|old| old.version).unwrap_or(0);
+        match replaced {
+            // A rollback reinstates the version that was retiring; the rejected one is not kept
+            Some(_) if old_version > key.version => {
+                retiring.remove(&key_id);
+            }
+            Some(old) => {
+                retiring.insert(key_id, old);
+            }
+            None => {}
+        }
+
+        self.key_usage_log.write().await.rotation_history.push(KeyRotation {
+            old_version,
+            new_version: key.version,
+            timestamp: SystemTime::now(),
+        });
+        Ok(())
+    }
+
+    async fn retire_key(&self, key_id: &str, version: u64) -> Result<(), GuardianError> {
+        let mut retiring = self.retiring_versions.write().await;
+        let key_id = KeyId(key_id.to_string());
+        if retiring.get(&key_id).is_some_and(|old| old.version == version) {
+            retiring.remove(&key_id);
+        }
+        Ok(())
+    }
+}
+
+impl KeyVersion {
+    fn from_material(key: &KeyMaterial) -> Self {
+        Self {
+            version: key.version,
+            created_at: SystemTime::now(),
+            last_used: SystemTime::now(),
+            key_material: SecureBytes(key.bytes().to_vec()),
+        }
+    }
 }
 
 impl CryptoManager {
@@ -102,7 +191,7 @@ impl CryptoManager {
             source: Some(Box::new(e)),
             severity: ErrorSeverity::Critical,
             timestamp: time::OffsetDateTime::now_utc(),
-            correlation_id: uuid::Uuid::new_v4(),
+            correlation_id: crate::utils::correlation::current_or_new(),
             category: ErrorCategory::Security,
             retry_count: 0,
         })?);
@@ -112,7 +201,7 @@ impl CryptoManager {
             source: Some(Box::new(e)),
             severity: ErrorSeverity::Critical,
             timestamp: time::OffsetDateTime::now_utc(),
-            correlation_id: uuid::Uuid::new_v4(),
+            correlation_id: crate::utils::correlation::current_or_new(),
             category: ErrorCategory::Security,
             retry_count: 0,
         })?);
@@ -122,7 +211,7 @@ impl CryptoManager {
             source: Some(Box::new(e)),
             severity: ErrorSeverity::Critical,
             timestamp: time::OffsetDateTime::now_utc(),
-            correlation_id: uuid::Uuid::new_v4(),
+            correlation_id: crate::utils::correlation::current_or_new(),
             category: ErrorCategory::Security,
             retry_count: 0,
         })?);
@@ -132,13 +221,43 @@ impl CryptoManager {
             tpm_client,
             geli_manager,
             key_versions: Arc::new(RwLock::new(HashMap::new())),
+            retiring_versions: Arc::new(RwLock::new(HashMap::new())),
             key_usage_log: Arc::new(RwLock::new(KeyUsageAudit {
                 operations: Vec::new(),
                 rotation_history: Vec::new(),
             })),
+            key_rotation: None,
         })
     }
 
+    /// Sources keys from a key provider and rotates them together with the other components using them
+    pub fn with_key_rotation(mut self, coordinator: Arc<KeyRotationCoordinator>) -> Self {
+        coordinator.register(Arc::new(CryptoKeyParticipant {
+            key_versions: Arc::clone(&self.key_versions),
+            retiring_versions: Arc::clone(&self.retiring_versions),
+            key_usage_log: Arc::clone(&self.key_usage_log),
+        }));
+        self.key_rotation = Some(coordinator);
+        self
+    }
+
+    /// Loads the current version of a key from the key provider
+    pub async fn load_key(&self, key_id: KeyId) -> Result<(), GuardianError> {
+        let coordinator = self.key_rotation.as_ref().ok_or_else(|| GuardianError::SecurityError {
+            context: "No key provider configured".into(),
+            source: None,
+            severity: ErrorSeverity::Medium,
+            timestamp: time::OffsetDateTime::now_utc(),
+            correlation_id: crate::utils::correlation::current_or_new(),
+            category: ErrorCategory::Security,
+            retry_count: 0,
+        })?;
+
+        let key = coordinator.provider().current_key(&key_id.0).await?;
+        self.key_versions.write().await.insert(key_id, KeyVersion::from_material(&key));
+        Ok(())
+    }
+
     /// Encrypts data using AES-256-GCM with enhanced security measures
     pub async fn encrypt_data(
         &self,
@@ -153,7 +272,7 @@ impl CryptoManager {
                 source: None,
                 severity: ErrorSeverity::Medium,
                 timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id: uuid::Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: ErrorCategory::Security,
                 retry_count: 0,
             });
@@ -171,7 +290,7 @@ impl CryptoManager {
                 source: Some(Box::new(e)),
                 severity: ErrorSeverity::High,
                 timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id: uuid::Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: ErrorCategory::Security,
                 retry_count: 0,
             })?;
@@ -183,7 +302,7 @@ impl CryptoManager {
                 source: Some(Box::new(e)),
                 severity: ErrorSeverity::High,
                 timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id: uuid::Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: ErrorCategory::Security,
                 retry_count: 0,
             })?;
@@ -196,7 +315,7 @@ impl CryptoManager {
                 source: Some(Box::new(e)),
                 severity: ErrorSeverity::High,
                 timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id: uuid::Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: ErrorCategory::Security,
                 retry_count: 0,
             })?;
@@ -215,16 +334,104 @@ impl CryptoManager {
         })
     }
 
-    /// Performs secure key rotation with atomic updates and rollback protection
-    pub async fn rotate_keys(&self) -> Result<KeyRotationStatus, GuardianError> {
+    /// Authenticates data under the current version of a key
+    pub async fn sign_data(&self, data: &[u8], key_id: &KeyId) -> Result<DataSignature, GuardianError> {
+        let keys = self.key_versions.read().await;
+        let key = keys.get(key_id).ok_or_else(|| GuardianError::SecurityError {
+            context: format!("Signing key {} is not loaded", key_id.0),
+            source: None,
+            severity: ErrorSeverity::High,
+            timestamp: time::OffsetDateTime::now_utc(),
+            correlation_id: crate::utils::correlation::current_or_new(),
+            category: ErrorCategory::Security,
+            retry_count: 0,
+        })?;
+        let tag = hmac::sign(&hmac::Key::new(hmac::HMAC_SHA256, &key.key_material.0), data);
+
+        Ok(DataSignature {
+            key_id: key_id.0.clone(),
+            key_version: key.version,
+            tag: BASE64.encode(tag.as_ref()),
+        })
+    }
+
+    /// Checks a tag from `sign_data`. Versions inside their rotation overlap window are still held
+    /// in memory; older ones are fetched from the key provider.
+    pub async fn verify_data(&self, data: &[u8], signature: &DataSignature) -> Result<(), GuardianError> {
+        let verification_error = |context: String| GuardianError::SecurityError {
+            context,
+            source: None,
+            severity: ErrorSeverity::High,
+            timestamp: time::OffsetDateTime::now_utc(),
+            correlation_id: crate::utils::correlation::current_or_new(),
+            category: ErrorCategory::Security,
+            retry_count: 0,
+        };
+
+        let key_id = KeyId::new(signature.key_id.clone());
+        let loaded = |key: &KeyVersion| {
+            (key.version == signature.key_version).then(|| Zeroizing::new(key.key_material.0.clone()))
+        };
+        let mut held = self.key_versions.read().await.get(&key_id).and_then(loaded);
+        if held.is_none() {
+            held = self.retiring_versions.read().await.get(&key_id).and_then(loaded);
+        }
+        let material = match held {
+            Some(material) => material,
+            None => {
+                let coordinator = self.key_rotation.as_ref().ok_or_else(|| {
+                    verification_error(format!(
+                        "Key {} version {} is not loaded and no key provider is configured",
+                        signature.key_id, signature.key_version
+                    ))
+                })?;
+                let key = coordinator.provider().key_version(&signature.key_id, signature.key_version).await?;
+                Zeroizing::new(key.bytes().to_vec())
+            }
+        };
+
+        let tag = BASE64
+            .decode(&signature.tag)
+            .map_err(|_| verification_error("Signature tag is not base64".into()))?;
+        hmac::verify(&hmac::Key::new(hmac::HMAC_SHA256, &material), data, &tag).map_err(|_| {
+            verification_error(format!(
+                "Signature by key {} version {} does not verify",
+                signature.key_id, signature.key_version
+            ))
+        })
+    }
+
+    /// Performs secure key rotation with atomic updates and rollback protection, as a tracked
+    /// operation with progress and cancellation
+    pub async fn rotate_keys_tracked(
+        &self,
+        operation: OperationHandle,
+    ) -> Result<KeyRotationStatus, GuardianError> {
+        let result = self.rotate_keys_inner(&operation).await;
+        operation.finish_with(&result);
+        result
+    }
+
+    async fn rotate_keys_inner(
+        &self,
+        operation: &OperationHandle,
+    ) -> Result<KeyRotationStatus, GuardianError> {
         // Verify HSM and TPM health
         self.verify_security_modules().await?;
 
+        if let Some(coordinator) = &self.key_rotation {
+            return self.rotate_coordinated(coordinator, operation).await;
+        }
+
         // Start atomic transaction
         let mut keys = self.key_versions.write().await;
         let mut audit = self.key_usage_log.write().await;
+        let total_keys = keys.len().max(1);
+
+        for (index, (key_id, current_version)) in keys.iter_mut().enumerate() {
+            // Stop between keys so every key is either fully rotated or untouched
+            operation.check_cancelled()?;
 
-        for (key_id, current_version) in keys.iter_mut() {
             // Generate new key material
             let new_key_material = self.generate_key_material().await?;
 
@@ -252,6 +459,8 @@ impl CryptoManager {
 
             // Update key version
             *current_version = new_version;
+
+            operation.set_progress(((index + 1) * 100 / total_keys) as f32);
         }
 
         Ok(KeyRotationStatus {
@@ -260,6 +469,28 @@ impl CryptoManager {
         })
     }
 
+    /// Rotates every provider-held key across all components using it, one key at a time
+    async fn rotate_coordinated(
+        &self,
+        coordinator: &KeyRotationCoordinator,
+        operation: &OperationHandle,
+    ) -> Result<KeyRotationStatus, GuardianError> {
+        // Participants take the key lock themselves, so it must not be held here
+        let key_ids = coordinator.key_ids().await;
+        let total_keys = key_ids.len().max(1);
+
+        for (index, key_id) in key_ids.iter().enumerate() {
+            operation.check_cancelled()?;
+            coordinator.rotate(key_id).await?;
+            operation.set_progress(((index + 1) * 100 / total_keys) as f32);
+        }
+
+        Ok(KeyRotationStatus {
+            rotated_keys: key_ids.len(),
+            timestamp: SystemTime::now(),
+        })
+    }
+
     // Helper methods...
 }
 
@@ -278,7 +509,7 @@ pub fn generate_random_bytes(
             source: Some(Box::new(e)),
             severity: ErrorSeverity::High,
             timestamp: time::OffsetDateTime::now_utc(),
-            correlation_id: uuid::Uuid::new_v4(),
+            correlation_id: crate::utils::correlation::current_or_new(),
             category: ErrorCategory::Security,
             retry_count: 0,
         })?;
@@ -290,7 +521,7 @@ pub fn generate_random_bytes(
             source: None,
             severity: ErrorSeverity::High,
             timestamp: time::OffsetDateTime::now_utc(),
-            correlation_id: uuid::Uuid::new_v4(),
+            correlation_id: crate::utils::correlation::current_or_new(),
             category: ErrorCategory::Security,
             retry_count: 0,
         });
@@ -0,0 +1,260 @@
+//! Graceful degradation of detection while it misses its latency budget
+//!
+//! Every detection cycle reports its duration. After `breach_cycles` consecutive cycles over
+//! `MAX_DETECTION_TIME_MS` the controller steps one level down the configured ladder, and after
+//! `recovery_cycles` consecutive cycles comfortably under it, one level back up. A level keeps
+//! the effects of the levels before it on the ladder, so stepping down only ever sheds more
+//! work. Every step is published to subscribers, which put it on the event bus.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use metrics::{counter, gauge};
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use serde::Serialize;
+use tokio::sync::broadcast;
+use tracing::{info, warn};
+
+use crate::config::{AppConfig, DegradationConfig, DegradationLevel};
+use crate::security::MAX_DETECTION_TIME_MS;
+
+pub const DEGRADATION_EVENT_TYPE: &str = "system.degradation_changed";
+const CHANGE_BUFFER: usize = 64;
+
+static DEGRADATION: Lazy<DegradationController> =
+    Lazy::new(|| DegradationController::new(DegradationConfig::default(), Duration::from_millis(MAX_DETECTION_TIME_MS)));
+
+/// Returns the process-wide degradation controller
+pub fn degradation() -> &'static DegradationController {
+    &DEGRADATION
+}
+
+/// Applies the degradation settings of the configuration; call again after a reload
+pub fn init_degradation(config: &AppConfig) {
+    degradation().configure(&config.degradation);
+    info!(enabled = config.degradation.enabled, ladder = ?config.degradation.ladder, "Degradation ladder configured");
+}
+
+/// A step up or down the ladder
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DegradationChange {
+    pub from: DegradationLevel,
+    pub to: DegradationLevel,
+    pub reason: String,
+    /// Duration of the cycle that triggered the step
+    pub cycle_ms: u64,
+    pub budget_ms: u64,
+    pub at: DateTime<Utc>,
+}
+
+#[derive(Debug)]
+struct LadderState {
+    config: DegradationConfig,
+    /// Levels in effect, the first `step` of the usable ladder
+    step: usize,
+    breached: u32,
+    recovered: u32,
+}
+
+impl LadderState {
+    /// The configured ladder without levels that cannot take effect
+    fn ladder(&self) -> Vec<DegradationLevel> {
+        self.config
+            .ladder
+            .iter()
+            .copied()
+            .filter(|level| *level != DegradationLevel::SmallModel || self.config.fallback_model.is_some())
+            .collect()
+    }
+
+    fn level(&self) -> DegradationLevel {
+        self.step
+            .checked_sub(1)
+            .and_then(|index| self.ladder().get(index).copied())
+            .unwrap_or(DegradationLevel::Normal)
+    }
+}
+
+/// Steps detection down and back up the degradation ladder
+#[derive(Debug)]
+pub struct DegradationController {
+    state: RwLock<LadderState>,
+    budget: Duration,
+    changes: broadcast::Sender<DegradationChange>,
+}
+
+impl DegradationController {
+    pub fn new(config: DegradationConfig, budget: Duration) -> Self {
+        Self {
+            state: RwLock::new(LadderState { config, step: 0, breached: 0, recovered: 0 }),
+            budget,
+            changes: broadcast::channel(CHANGE_BUFFER).0,
+        }
+    }
+
+    /// Replaces the settings; a shorter ladder, or disabling degradation, steps back up at once
+    pub fn configure(&self, config: &DegradationConfig) {
+        let change = {
+            let mut state = self.state.write();
+            let from = state.level();
+            state.config = config.clone();
+            let usable = if config.enabled { state.ladder().len() } else { 0 };
+            state.step = state.step.min(usable);
+            let to = state.level();
+            (from != to).then(|| self.change(from, to, "configuration changed".to_string(), Duration::ZERO))
+        };
+        if let Some(change) = change {
+            self.publish(change);
+        }
+    }
+
+    /// Receives every step taken from now on
+    pub fn subscribe(&self) -> broadcast::Receiver<DegradationChange> {
+        self.changes.subscribe()
+    }
+
+    /// Deepest level in effect
+    pub fn level(&self) -> DegradationLevel {
+        self.state.read().level()
+    }
+
+    /// Whether a level's effects apply, which holds from the moment the ladder reaches it
+    pub fn is_active(&self, level: DegradationLevel) -> bool {
+        let state = self.state.read();
+        state.ladder().iter().take(state.step).any(|active| *active == level)
+    }
+
+    /// Multiplier for collector intervals, 1 unless sampling is reduced
+    pub fn sampling_interval_factor(&self) -> f64 {
+        if self.is_active(DegradationLevel::ReducedSampling) {
+            self.state.read().config.sampling_interval_factor
+        } else {
+            1.0
+        }
+    }
+
+    /// Model version inference falls back to while the small model level is in effect
+    pub fn fallback_model(&self) -> Option<String> {
+        if self.is_active(DegradationLevel::SmallModel) {
+            self.state.read().config.fallback_model.clone()
+        } else {
+            None
+        }
+    }
+
+    /// Counts a detection cycle toward stepping down or up, returning the step it caused
+    pub fn record_cycle(&self, elapsed: Duration) -> Option<DegradationChange> {
+        let change = {
+            let mut state = self.state.write();
+            if !state.config.enabled {
+                return None;
+            }
+
+            let recovery = self.budget.mul_f64(state.config.recovery_ratio);
+            if elapsed > self.budget {
+                state.recovered = 0;
+                state.breached += 1;
+            } else if elapsed <= recovery {
+                state.breached = 0;
+                state.recovered += 1;
+            } else {
+                // Within budget but without headroom: hold the current level
+                state.breached = 0;
+                state.recovered = 0;
+            }
+
+            let from = state.level();
+            if state.breached >= state.config.breach_cycles && state.step < state.ladder().len() {
+                state.step += 1;
+                state.breached = 0;
+                let reason = format!("{} consecutive cycles over {}ms", state.config.breach_cycles, self.budget.as_millis());
+                Some(self.change(from, state.level(), reason, elapsed))
+            } else if state.recovered >= state.config.recovery_cycles && state.step > 0 {
+                state.step -= 1;
+                state.recovered = 0;
+                let reason = format!(
+                    "{} consecutive cycles under {}ms",
+                    state.config.recovery_cycles,
+                    recovery.as_millis()
+                );
+                Some(self.change(from, state.level(), reason, elapsed))
+            } else {
+                None
+            }
+        };
+
+        if let Some(change) = &change {
+            self.publish(change.clone());
+        }
+        change
+    }
+
+    fn change(&self, from: DegradationLevel, to: DegradationLevel, reason: String, elapsed: Duration) -> DegradationChange {
+        DegradationChange {
+            from,
+            to,
+            reason,
+            cycle_ms: elapsed.as_millis() as u64,
+            budget_ms: self.budget.as_millis() as u64,
+            at: Utc::now(),
+        }
+    }
+
+    fn publish(&self, change: DegradationChange) {
+        let direction = if change.to > change.from { "down" } else { "up" };
+        counter!("guardian.detection.degradation.steps", 1, "direction" => direction, "level" => change.to.as_str());
+        gauge!("guardian.detection.degradation.level", change.to as u8 as f64);
+        if change.to > change.from {
+            warn!(from = change.from.as_str(), to = change.to.as_str(), reason = %change.reason, "Detection degraded");
+        } else {
+            info!(from = change.from.as_str(), to = change.to.as_str(), reason = %change.reason, "Detection recovering");
+        }
+        let _ = self.changes.send(change);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_steps_down_and_recovers_one_level_at_a_time() {
+        let config = DegradationConfig {
+            breach_cycles: 2,
+            recovery_cycles: 3,
+            fallback_model: None,
+            ..DegradationConfig::default()
+        };
+        let controller = DegradationController::new(config, Duration::from_millis(100));
+        let mut changes = controller.subscribe();
+        let slow = Duration::from_millis(150);
+        let fast = Duration::from_millis(10);
+
+        assert!(controller.record_cycle(slow).is_none());
+        let change = controller.record_cycle(slow).unwrap();
+        assert_eq!((change.from, change.to), (DegradationLevel::Normal, DegradationLevel::ReducedSampling));
+        assert_eq!(controller.sampling_interval_factor(), 2.0);
+
+        // Without a fallback model the small model level is skipped
+        controller.record_cycle(slow);
+        controller.record_cycle(slow);
+        assert_eq!(controller.level(), DegradationLevel::HeuristicOnly);
+        assert!(controller.is_active(DegradationLevel::ReducedSampling));
+        assert!(!controller.is_active(DegradationLevel::SmallModel));
+
+        // A cycle within budget but without headroom resets the recovery count
+        controller.record_cycle(fast);
+        controller.record_cycle(fast);
+        controller.record_cycle(Duration::from_millis(90));
+        controller.record_cycle(fast);
+        controller.record_cycle(fast);
+        assert_eq!(controller.level(), DegradationLevel::HeuristicOnly);
+        let change = controller.record_cycle(fast).unwrap();
+        assert_eq!(change.to, DegradationLevel::ReducedSampling);
+        assert_eq!(std::iter::from_fn(|| changes.try_recv().ok()).count(), 3);
+
+        controller.configure(&DegradationConfig { enabled: false, ..DegradationConfig::default() });
+        assert_eq!(controller.level(), DegradationLevel::Normal);
+    }
+}
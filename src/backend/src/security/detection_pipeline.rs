@@ -0,0 +1,760 @@
+use std::{collections::HashMap, fmt, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use metrics::{counter, histogram};
+use serde::{Deserialize, Serialize};
+use tokio::time::{timeout, Instant};
+use tracing::{debug, info, info_span, instrument, warn, Instrument};
+
+use crate::core::capabilities::{capabilities, Capability};
+use crate::core::on_inference_runtime;
+use crate::core::resource_governor::{governor, Subsystem};
+use crate::ml::inference_engine::{InferenceEngine, Prediction};
+use crate::config::DegradationLevel;
+use crate::security::anomaly_detection::SystemData;
+use crate::security::degradation::degradation;
+use crate::security::detection_rules::{detection_rules, RuleSandbox};
+use crate::security::intel::{indicator_metadata, threat_intel, ThreatIntel, INTEL_PREDICTION_TYPE};
+use crate::security::pipeline_latency::{pipeline_latency, LatencyStage};
+use crate::security::process_trust::{process_trust, BinaryIdentity, TrustRegistry};
+use crate::utils::error::{GuardianError, SecurityError};
+
+// Constants for detection pipelines
+const DEFAULT_STAGE_TIMEOUT: Duration = Duration::from_millis(100);
+const MAX_PIPELINE_STAGES: usize = 32;
+const DEFAULT_PIPELINE_NAME: &str = "default";
+
+/// Role of a stage within a detection pipeline
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StageKind {
+    /// Drops or reduces samples before inference
+    PreFilter,
+    /// Attaches context to samples or predictions
+    Enrichment,
+    /// Produces predictions from samples
+    Model,
+    /// Combines, filters or ranks predictions
+    Aggregation,
+}
+
+impl StageKind {
+    /// SLA stage the pipeline stage's time is attributed to
+    fn latency_stage(&self) -> LatencyStage {
+        match self {
+            StageKind::PreFilter | StageKind::Enrichment => LatencyStage::Extract,
+            StageKind::Model => LatencyStage::Infer,
+            StageKind::Aggregation => LatencyStage::Classify,
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            StageKind::PreFilter => "pre_filter",
+            StageKind::Enrichment => "enrichment",
+            StageKind::Model => "model",
+            StageKind::Aggregation => "aggregation",
+        }
+    }
+}
+
+/// Samples and predictions passed from stage to stage during one detection cycle
+#[derive(Debug, Clone, Default)]
+pub struct DetectionContext {
+    pub samples: Vec<SystemData>,
+    pub predictions: Vec<Prediction>,
+    /// Attributes merged into the metadata of every emitted prediction
+    pub attributes: HashMap<String, String>,
+}
+
+impl DetectionContext {
+    /// Creates a context for a batch of collected samples
+    pub fn new(samples: Vec<SystemData>) -> Self {
+        Self {
+            samples,
+            ..Default::default()
+        }
+    }
+}
+
+/// A single step of a detection pipeline
+#[async_trait]
+pub trait PipelineStage: Send + Sync + fmt::Debug {
+    /// Role of the stage, used for validation and metrics
+    fn kind(&self) -> StageKind;
+
+    /// Runs the stage, mutating the context in place
+    async fn run(&self, context: &mut DetectionContext) -> Result<(), GuardianError>;
+}
+
+/// Declarative configuration of a single pipeline stage
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StageConfig {
+    pub name: String,
+    /// Stage implementation registered in the `StageRegistry`
+    #[serde(rename = "type")]
+    pub stage_type: String,
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+    /// Optional stages are skipped on failure or timeout instead of aborting the cycle
+    #[serde(default)]
+    pub optional: bool,
+    #[serde(default)]
+    pub params: serde_json::Value,
+}
+
+/// Declarative configuration of a detection pipeline
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PipelineConfig {
+    pub name: String,
+    pub stages: Vec<StageConfig>,
+}
+
+/// Creates a stage from its configuration
+pub type StageFactory =
+    Arc<dyn Fn(&StageConfig) -> Result<Arc<dyn PipelineStage>, GuardianError> + Send + Sync>;
+
+/// Stage implementations available to pipeline configurations, keyed by type
+#[derive(Clone, Default)]
+pub struct StageRegistry {
+    factories: HashMap<String, StageFactory>,
+}
+
+impl fmt::Debug for StageRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StageRegistry")
+            .field("types", &self.factories.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl StageRegistry {
+    /// Creates a registry with the built-in stages bound to an inference engine
+    pub fn with_builtins(inference_engine: Arc<InferenceEngine>) -> Self {
+        let mut registry = Self::without_models();
+        registry.register("inference", Arc::new(move |config: &StageConfig| {
+            let batch_size = config.params.get("batch_size").and_then(|v| v.as_u64()).unwrap_or(128);
+            Ok(Arc::new(InferenceStage {
+                model: config.name.clone(),
+                inference_engine: Arc::clone(&inference_engine),
+                batch_size: batch_size.max(1) as usize,
+            }) as Arc<dyn PipelineStage>)
+        }));
+        registry
+    }
+
+    /// Creates a registry with the built-in stages except `inference`, for callers supplying their own models
+    pub fn without_models() -> Self {
+        let mut registry = Self::default();
+
+        registry.register("sample_limit", Arc::new(|config: &StageConfig| {
+            let max_samples = param_u64(config, "max_samples")? as usize;
+            Ok(Arc::new(SampleLimit { max_samples }) as Arc<dyn PipelineStage>)
+        }));
+        registry.register("static_attributes", Arc::new(|config: &StageConfig| {
+            let attributes = serde_json::from_value(config.params.clone())
+                .map_err(|e| invalid_stage(config, &format!("expected string attributes: {}", e)))?;
+            Ok(Arc::new(StaticAttributes { attributes }) as Arc<dyn PipelineStage>)
+        }));
+        registry.register("confidence_filter", Arc::new(|config: &StageConfig| {
+            let threshold = config.params.get("threshold").and_then(|v| v.as_f64())
+                .ok_or_else(|| invalid_stage(config, "missing numeric parameter 'threshold'"))?;
+            Ok(Arc::new(ConfidenceFilter { threshold: threshold as f32 }) as Arc<dyn PipelineStage>)
+        }));
+        registry.register("trust_filter", Arc::new(|config: &StageConfig| {
+            let threshold = config.params.get("threshold").and_then(|v| v.as_f64())
+                .ok_or_else(|| invalid_stage(config, "missing numeric parameter 'threshold'"))?;
+            Ok(Arc::new(TrustFilter { threshold: threshold as f32, trust: process_trust() }) as Arc<dyn PipelineStage>)
+        }));
+        registry.register("intel_match", Arc::new(|_: &StageConfig| {
+            Ok(Arc::new(IntelMatch { intel: threat_intel() }) as Arc<dyn PipelineStage>)
+        }));
+        registry.register("detection_rules", Arc::new(|_: &StageConfig| {
+            Ok(Arc::new(DetectionRules { sandbox: detection_rules() }) as Arc<dyn PipelineStage>)
+        }));
+        registry.register("max_confidence", Arc::new(|_: &StageConfig| {
+            Ok(Arc::new(MaxConfidence) as Arc<dyn PipelineStage>)
+        }));
+
+        registry
+    }
+
+    /// Registers or replaces a stage type
+    pub fn register(&mut self, stage_type: &str, factory: StageFactory) {
+        self.factories.insert(stage_type.to_string(), factory);
+    }
+
+    fn create(&self, config: &StageConfig) -> Result<Arc<dyn PipelineStage>, GuardianError> {
+        let factory = self.factories.get(&config.stage_type)
+            .ok_or_else(|| invalid_stage(config, &format!("unknown stage type {}", config.stage_type)))?;
+        factory(config)
+    }
+}
+
+#[derive(Debug)]
+struct ConfiguredStage {
+    name: String,
+    stage: Arc<dyn PipelineStage>,
+    timeout: Duration,
+    optional: bool,
+}
+
+/// Composes pipeline stages, each with its own timeout
+#[derive(Debug)]
+pub struct DetectionPipelineBuilder {
+    name: String,
+    stages: Vec<ConfiguredStage>,
+}
+
+impl DetectionPipelineBuilder {
+    /// Starts an empty pipeline
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            stages: Vec::new(),
+        }
+    }
+
+    /// Appends a required stage with the default timeout
+    pub fn stage(self, name: impl Into<String>, stage: Arc<dyn PipelineStage>) -> Self {
+        self.stage_with(name, stage, DEFAULT_STAGE_TIMEOUT, false)
+    }
+
+    /// Appends a stage with an explicit timeout, optionally skipped on failure
+    pub fn stage_with(
+        mut self,
+        name: impl Into<String>,
+        stage: Arc<dyn PipelineStage>,
+        timeout: Duration,
+        optional: bool,
+    ) -> Self {
+        self.stages.push(ConfiguredStage {
+            name: name.into(),
+            stage,
+            timeout,
+            optional,
+        });
+        self
+    }
+
+    /// Validates the stage composition and builds the pipeline
+    pub fn build(self) -> Result<DetectionPipeline, GuardianError> {
+        let reject = |reason: String| SecurityError {
+            context: format!("Invalid detection pipeline {}: {}", self.name, reason),
+            source: None,
+            severity: crate::utils::error::ErrorSeverity::High,
+            timestamp: time::OffsetDateTime::now_utc(),
+            correlation_id: crate::utils::correlation::current_or_new(),
+            category: crate::utils::error::ErrorCategory::Security,
+            retry_count: 0,
+        };
+
+        if self.stages.len() > MAX_PIPELINE_STAGES {
+            return Err(reject(format!("more than {} stages", MAX_PIPELINE_STAGES)));
+        }
+        if !self.stages.iter().any(|s| s.stage.kind() == StageKind::Model && !s.optional) {
+            return Err(reject("at least one required model stage is needed".into()));
+        }
+        for (i, stage) in self.stages.iter().enumerate() {
+            if self.stages[..i].iter().any(|s| s.name == stage.name) {
+                return Err(reject(format!("duplicate stage name {}", stage.name)));
+            }
+            if stage.timeout.is_zero() {
+                return Err(reject(format!("stage {} has a zero timeout", stage.name)));
+            }
+        }
+
+        Ok(DetectionPipeline {
+            name: self.name,
+            stages: self.stages,
+            track_latency: true,
+        })
+    }
+}
+
+/// Ordered set of detection stages run once per detection cycle
+#[derive(Debug)]
+pub struct DetectionPipeline {
+    name: String,
+    stages: Vec<ConfiguredStage>,
+    /// Whether stage timings count toward the live detection latency view
+    track_latency: bool,
+}
+
+impl DetectionPipeline {
+    /// Builds a pipeline declared in configuration from registered stage types
+    pub fn from_config(config: &PipelineConfig, registry: &StageRegistry) -> Result<Self, GuardianError> {
+        config.stages.iter().try_fold(
+            DetectionPipelineBuilder::new(config.name.clone()),
+            |builder, stage_config| {
+                let stage = registry.create(stage_config)?;
+                let timeout = stage_config.timeout_ms
+                    .map(Duration::from_millis)
+                    .unwrap_or(DEFAULT_STAGE_TIMEOUT);
+                Ok(builder.stage_with(stage_config.name.clone(), stage, timeout, stage_config.optional))
+            },
+        )?.build()
+    }
+
+    /// Builds the fixed intel-match, detection rules, infer, then filter cycle used when no pipeline is configured
+    pub fn default_pipeline(
+        inference_engine: Arc<InferenceEngine>,
+        batch_size: usize,
+        confidence_threshold: f32,
+    ) -> Self {
+        let stage = |name: &str, stage: Arc<dyn PipelineStage>| ConfiguredStage {
+            name: name.to_string(),
+            stage,
+            timeout: DEFAULT_STAGE_TIMEOUT,
+            optional: false,
+        };
+
+        Self {
+            name: DEFAULT_PIPELINE_NAME.to_string(),
+            stages: vec![
+                stage("intel_match", Arc::new(IntelMatch { intel: threat_intel() })),
+                stage("detection_rules", Arc::new(DetectionRules { sandbox: detection_rules() })),
+                stage("inference", Arc::new(InferenceStage {
+                    model: "default".into(),
+                    inference_engine,
+                    batch_size: batch_size.max(1),
+                })),
+                stage("confidence_filter", Arc::new(ConfidenceFilter {
+                    threshold: confidence_threshold,
+                })),
+            ],
+            track_latency: true,
+        }
+    }
+
+    /// Keeps offline runs such as content simulation out of the live latency view
+    pub fn without_latency_tracking(mut self) -> Self {
+        self.track_latency = false;
+        self
+    }
+
+    /// Returns the pipeline name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the stage names in execution order
+    pub fn stage_names(&self) -> Vec<&str> {
+        self.stages.iter().map(|s| s.name.as_str()).collect()
+    }
+
+    /// Runs every stage in order over the collected samples and returns the resulting predictions
+    #[instrument(skip(self, samples), fields(pipeline = %self.name, samples = samples.len()))]
+    pub async fn run(&self, samples: Vec<SystemData>) -> Result<Vec<Prediction>, GuardianError> {
+        let mut context = DetectionContext::new(samples);
+        let latency = pipeline_latency();
+        let ml_enabled = capabilities().is_enabled(Capability::Ml);
+        // Offline runs, kept out of the latency view, are never degraded either
+        let heuristic_only = self.track_latency && degradation().is_active(DegradationLevel::HeuristicOnly);
+
+        for configured in &self.stages {
+            if !ml_enabled && configured.stage.kind() == StageKind::Model {
+                debug!(stage = %configured.name, "ML capability disabled, skipping model stage");
+                continue;
+            }
+            if heuristic_only && configured.stage.kind() == StageKind::Model {
+                debug!(stage = %configured.name, "Detection degraded to heuristics, skipping model stage");
+                counter!("guardian.detection.pipeline.degraded_stages", 1,
+                    "pipeline" => self.name.clone(), "stage" => configured.name.clone());
+                continue;
+            }
+            let start = Instant::now();
+            let stage_latency = configured.stage.kind().latency_stage();
+            let run = timeout(configured.timeout, configured.stage.run(&mut context))
+                .instrument(info_span!("pipeline_stage", stage = %configured.name, sla_stage = stage_latency.label()));
+            let result = run.await;
+            if self.track_latency {
+                latency.record(stage_latency, start.elapsed());
+            }
+            let outcome = match result {
+                Ok(Ok(())) => "ok",
+                Ok(Err(e)) if configured.optional => {
+                    warn!(stage = %configured.name, error = %e, "Optional detection stage failed, skipping");
+                    "skipped"
+                }
+                Ok(Err(e)) => {
+                    counter!("guardian.detection.pipeline.stage_failures", 1,
+                        "pipeline" => self.name.clone(), "stage" => configured.name.clone());
+                    return Err(e);
+                }
+                Err(_) if configured.optional => {
+                    warn!(stage = %configured.name, timeout = ?configured.timeout, "Optional detection stage timed out, skipping");
+                    "timeout"
+                }
+                Err(_) => {
+                    counter!("guardian.detection.pipeline.stage_timeouts", 1,
+                        "pipeline" => self.name.clone(), "stage" => configured.name.clone());
+                    return Err(SecurityError {
+                        context: format!("Detection stage {} exceeded {:?}", configured.name, configured.timeout),
+                        source: None,
+                        severity: crate::utils::error::ErrorSeverity::High,
+                        timestamp: time::OffsetDateTime::now_utc(),
+                        correlation_id: crate::utils::correlation::current_or_new(),
+                        category: crate::utils::error::ErrorCategory::Security,
+                        retry_count: 0,
+                    });
+                }
+            };
+
+            histogram!(
+                "guardian.detection.pipeline.stage_duration",
+                start.elapsed().as_secs_f64(),
+                "pipeline" => self.name.clone(),
+                "stage" => configured.name.clone(),
+                "kind" => configured.stage.kind().label(),
+                "outcome" => outcome
+            );
+            debug!(
+                stage = %configured.name,
+                outcome,
+                samples = context.samples.len(),
+                predictions = context.predictions.len(),
+                "Detection stage completed"
+            );
+        }
+
+        let DetectionContext { mut predictions, attributes, .. } = context;
+        if !attributes.is_empty() {
+            for prediction in &mut predictions {
+                for (key, value) in &attributes {
+                    prediction.metadata.entry(key.clone()).or_insert_with(|| value.clone());
+                }
+            }
+        }
+
+        counter!("guardian.detection.pipeline.predictions", predictions.len() as u64,
+            "pipeline" => self.name.clone());
+        Ok(predictions)
+    }
+}
+
+/// Pre-filter keeping only the most recent samples of a cycle
+#[derive(Debug)]
+struct SampleLimit {
+    max_samples: usize,
+}
+
+#[async_trait]
+impl PipelineStage for SampleLimit {
+    fn kind(&self) -> StageKind {
+        StageKind::PreFilter
+    }
+
+    async fn run(&self, context: &mut DetectionContext) -> Result<(), GuardianError> {
+        if context.samples.len() > self.max_samples {
+            context.samples.sort_by_key(|s| std::cmp::Reverse(s.timestamp));
+            context.samples.truncate(self.max_samples);
+        }
+        Ok(())
+    }
+}
+
+/// Pre-filter raising detections for samples that mention a threat intel indicator
+///
+/// Matched samples skip inference; the indicator is their detection.
+#[derive(Debug)]
+struct IntelMatch {
+    intel: Arc<ThreatIntel>,
+}
+
+#[async_trait]
+impl PipelineStage for IntelMatch {
+    fn kind(&self) -> StageKind {
+        StageKind::PreFilter
+    }
+
+    async fn run(&self, context: &mut DetectionContext) -> Result<(), GuardianError> {
+        if self.intel.is_empty() {
+            return Ok(());
+        }
+        let mut matched = Vec::new();
+        context.samples.retain(|sample| match self.intel.match_sample(sample) {
+            Some(indicator) => {
+                matched.push(Prediction::new(INTEL_PREDICTION_TYPE, indicator.confidence, indicator_metadata(&indicator)));
+                false
+            }
+            None => true,
+        });
+        if !matched.is_empty() {
+            counter!("guardian.detection.intel_matches", matched.len() as u64);
+        }
+        context.predictions.extend(matched);
+        Ok(())
+    }
+}
+
+/// Pre-filter running the operator's WebAssembly detection rules on every sample
+///
+/// Samples stay in the cycle, so a rule match adds to the model's predictions rather than replacing them.
+#[derive(Debug)]
+struct DetectionRules {
+    sandbox: Arc<RuleSandbox>,
+}
+
+#[async_trait]
+impl PipelineStage for DetectionRules {
+    fn kind(&self) -> StageKind {
+        StageKind::PreFilter
+    }
+
+    async fn run(&self, context: &mut DetectionContext) -> Result<(), GuardianError> {
+        let rules = self.sandbox.rules();
+        if rules.is_empty() || context.samples.is_empty() {
+            return Ok(());
+        }
+        let samples = context.samples.clone();
+        let predictions = tokio::task::spawn_blocking(move || rules.evaluate(&samples))
+            .await
+            .map_err(|e| SecurityError {
+                context: "Detection rule evaluation panicked".into(),
+                source: Some(Box::new(e)),
+                severity: crate::utils::error::ErrorSeverity::High,
+                timestamp: time::OffsetDateTime::now_utc(),
+                correlation_id: crate::utils::correlation::current_or_new(),
+                category: crate::utils::error::ErrorCategory::Security,
+                retry_count: 0,
+            })?;
+        context.predictions.extend(predictions);
+        Ok(())
+    }
+}
+
+/// Enrichment adding fixed attributes, such as site or environment, to every prediction
+#[derive(Debug)]
+struct StaticAttributes {
+    attributes: HashMap<String, String>,
+}
+
+#[async_trait]
+impl PipelineStage for StaticAttributes {
+    fn kind(&self) -> StageKind {
+        StageKind::Enrichment
+    }
+
+    async fn run(&self, context: &mut DetectionContext) -> Result<(), GuardianError> {
+        context.attributes.extend(self.attributes.clone());
+        Ok(())
+    }
+}
+
+/// Model stage running batched inference and tagging predictions with the stage name
+#[derive(Debug)]
+struct InferenceStage {
+    model: String,
+    inference_engine: Arc<InferenceEngine>,
+    batch_size: usize,
+}
+
+#[async_trait]
+impl PipelineStage for InferenceStage {
+    fn kind(&self) -> StageKind {
+        StageKind::Model
+    }
+
+    async fn run(&self, context: &mut DetectionContext) -> Result<(), GuardianError> {
+        let batch_size = governor().batch_size(Subsystem::Detection, self.batch_size);
+        for chunk in context.samples.chunks(batch_size) {
+            let (engine, chunk) = (Arc::clone(&self.inference_engine), chunk.to_vec());
+            let predictions = on_inference_runtime(async move { engine.predict_batched(chunk).await }).await??;
+            context.predictions.extend(predictions.into_iter().map(|mut p| {
+                p.metadata.insert("model".into(), self.model.clone());
+                p
+            }));
+        }
+        Ok(())
+    }
+}
+
+/// Aggregation dropping predictions below a confidence threshold
+#[derive(Debug)]
+struct ConfidenceFilter {
+    threshold: f32,
+}
+
+#[async_trait]
+impl PipelineStage for ConfidenceFilter {
+    fn kind(&self) -> StageKind {
+        StageKind::Aggregation
+    }
+
+    async fn run(&self, context: &mut DetectionContext) -> Result<(), GuardianError> {
+        context.predictions.retain(|p| p.confidence >= self.threshold);
+        Ok(())
+    }
+}
+
+/// Aggregation applying a confidence threshold scaled by the trust of the binary a prediction is about
+///
+/// Predictions carry the binary in `exe` and `sha256` metadata; those without one get the plain
+/// threshold. Binaries with a surviving prediction are demoted.
+#[derive(Debug)]
+struct TrustFilter {
+    threshold: f32,
+    trust: Arc<TrustRegistry>,
+}
+
+#[async_trait]
+impl PipelineStage for TrustFilter {
+    fn kind(&self) -> StageKind {
+        StageKind::Aggregation
+    }
+
+    async fn run(&self, context: &mut DetectionContext) -> Result<(), GuardianError> {
+        let trust = &self.trust;
+        context.predictions.retain_mut(|p| {
+            let binary = BinaryIdentity::from_fields(
+                p.metadata.get("exe").map(String::as_str),
+                p.metadata.get("sha256").map(String::as_str),
+            );
+            let Some(binary) = binary else {
+                return p.confidence >= self.threshold;
+            };
+            let level = trust.observe(&binary);
+            if p.confidence < level.confidence_threshold(self.threshold) {
+                return false;
+            }
+            trust.demote(&binary, &p.prediction_type);
+            p.metadata.insert("trust_level".into(), level.label().to_string());
+            true
+        });
+        Ok(())
+    }
+}
+
+/// Aggregation keeping the most confident prediction of each type across models
+#[derive(Debug)]
+struct MaxConfidence;
+
+#[async_trait]
+impl PipelineStage for MaxConfidence {
+    fn kind(&self) -> StageKind {
+        StageKind::Aggregation
+    }
+
+    async fn run(&self, context: &mut DetectionContext) -> Result<(), GuardianError> {
+        let mut best: HashMap<String, Prediction> = HashMap::new();
+        for prediction in context.predictions.drain(..) {
+            match best.get(&prediction.prediction_type) {
+                Some(current) if current.confidence >= prediction.confidence => {}
+                _ => {
+                    best.insert(prediction.prediction_type.clone(), prediction);
+                }
+            }
+        }
+        context.predictions = best.into_values().collect();
+        Ok(())
+    }
+}
+
+fn param_u64(config: &StageConfig, key: &str) -> Result<u64, GuardianError> {
+    config.params.get(key).and_then(|v| v.as_u64())
+        .ok_or_else(|| invalid_stage(config, &format!("missing integer parameter '{}'", key)))
+}
+
+fn invalid_stage(config: &StageConfig, reason: &str) -> GuardianError {
+    info!(stage = %config.name, reason, "Rejected detection stage configuration");
+    SecurityError {
+        context: format!("Invalid detection stage {}: {}", config.name, reason),
+        source: None,
+        severity: crate::utils::error::ErrorSeverity::High,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: crate::utils::error::ErrorCategory::Security,
+        retry_count: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prediction(prediction_type: &str, confidence: f32) -> Prediction {
+        Prediction {
+            prediction_type: prediction_type.into(),
+            confidence,
+            timestamp: chrono::Utc::now(),
+            metadata: HashMap::new(),
+            performance_metrics: crate::ml::inference_engine::PredictionMetrics {
+                inference_time_ms: 0.0,
+                feature_extraction_time_ms: 0.0,
+                memory_usage_bytes: 0,
+            },
+        }
+    }
+
+    #[derive(Debug)]
+    struct FixedModel(Vec<(&'static str, f32)>);
+
+    #[async_trait]
+    impl PipelineStage for FixedModel {
+        fn kind(&self) -> StageKind {
+            StageKind::Model
+        }
+
+        async fn run(&self, context: &mut DetectionContext) -> Result<(), GuardianError> {
+            context.predictions.extend(self.0.iter().map(|(t, c)| prediction(t, *c)));
+            Ok(())
+        }
+    }
+
+    #[derive(Debug)]
+    struct SlowEnrichment;
+
+    #[async_trait]
+    impl PipelineStage for SlowEnrichment {
+        fn kind(&self) -> StageKind {
+            StageKind::Enrichment
+        }
+
+        async fn run(&self, context: &mut DetectionContext) -> Result<(), GuardianError> {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            context.attributes.insert("geo".into(), "unreachable".into());
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_composes_models_and_aggregation() {
+        let pipeline = DetectionPipelineBuilder::new("test")
+            .stage_with("geo", Arc::new(SlowEnrichment), Duration::from_millis(10), true)
+            .stage("model_a", Arc::new(FixedModel(vec![("malware", 0.97), ("exfil", 0.5)])))
+            .stage("model_b", Arc::new(FixedModel(vec![("malware", 0.99)])))
+            .stage("dedupe", Arc::new(MaxConfidence))
+            .stage("filter", Arc::new(ConfidenceFilter { threshold: 0.9 }))
+            .build()
+            .unwrap();
+
+        let predictions = pipeline.run(Vec::new()).await.unwrap();
+        assert_eq!(predictions.len(), 1);
+        assert_eq!(predictions[0].prediction_type, "malware");
+        assert_eq!(predictions[0].confidence, 0.99);
+        assert!(!predictions[0].metadata.contains_key("geo"));
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_validation_and_required_timeouts() {
+        assert!(DetectionPipelineBuilder::new("no_model")
+            .stage("filter", Arc::new(ConfidenceFilter { threshold: 0.9 }))
+            .build()
+            .is_err());
+
+        let config: PipelineConfig = serde_json::from_value(serde_json::json!({
+            "name": "configured",
+            "stages": [{ "name": "filter", "type": "unknown" }]
+        }))
+        .unwrap();
+        assert!(DetectionPipeline::from_config(&config, &StageRegistry::default()).is_err());
+
+        let pipeline = DetectionPipelineBuilder::new("strict")
+            .stage_with("geo", Arc::new(SlowEnrichment), Duration::from_millis(10), false)
+            .stage("model", Arc::new(FixedModel(vec![("malware", 0.99)])))
+            .build()
+            .unwrap();
+        assert!(pipeline.run(Vec::new()).await.is_err());
+    }
+}
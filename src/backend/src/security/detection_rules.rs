@@ -0,0 +1,341 @@
+//! Operator-written detection rules compiled to WebAssembly
+//!
+//! Each `*.wasm` file in the rules directory is one rule. A rule imports nothing and exports:
+//!
+//! - `memory`: its linear memory
+//! - `alloc(len: i32) -> i32`: space for the input sample
+//! - `evaluate(ptr: i32, len: i32) -> i64`: inspects the sample, given as JSON `SystemData`
+//!
+//! `evaluate` returns 0 when the sample is clean. Otherwise it returns the offset of a JSON
+//! verdict in the high 32 bits and its length in the low 32 bits. The verdict looks like
+//! `{"confidence": 0.9, "prediction_type": "...", "metadata": {...}}`, and only `confidence`
+//! is required.
+//!
+//! Every sample runs in a fresh instance, bounded by the configured fuel and memory limits. A
+//! rule that traps, runs out of fuel or returns a malformed verdict is counted and skipped. It
+//! never fails the detection cycle.
+
+use std::{collections::HashMap, path::Path, sync::Arc};
+
+use metrics::{counter, histogram};
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use serde::Deserialize;
+use tracing::{debug, info, warn};
+use wasmtime::{Config, Engine, InstancePre, Linker, Module, Store, StoreLimits, StoreLimitsBuilder, Trap};
+
+use crate::config::{AppConfig, DetectionRulesConfig};
+use crate::ml::inference_engine::Prediction;
+use crate::security::anomaly_detection::SystemData;
+
+pub const RULE_PREDICTION_TYPE: &str = "custom_rule";
+const RULE_EXTENSION: &str = "wasm";
+
+static DETECTION_RULES: Lazy<Arc<RuleSandbox>> = Lazy::new(|| Arc::new(RuleSandbox::default()));
+
+/// Returns the process-wide detection rule sandbox
+pub fn detection_rules() -> Arc<RuleSandbox> {
+    Arc::clone(&DETECTION_RULES)
+}
+
+/// Compiles the configured rules; call again after a reload to pick up changed rule files
+pub fn init_detection_rules(config: &AppConfig) {
+    let loaded = detection_rules().configure(&config.detection_rules);
+    info!(enabled = config.detection_rules.enabled, rules = loaded, "Detection rules configured");
+}
+
+/// What a rule reports about a suspicious sample
+#[derive(Debug, Deserialize)]
+struct RuleVerdict {
+    confidence: f32,
+    #[serde(default)]
+    prediction_type: Option<String>,
+    #[serde(default)]
+    metadata: HashMap<String, String>,
+}
+
+/// Per-instance state: only the resource limits
+struct RuleState {
+    limits: StoreLimits,
+}
+
+struct CompiledRule {
+    name: String,
+    instance: InstancePre<RuleState>,
+}
+
+/// The rules compiled from one pass over the rules directory, with the limits they run under
+pub struct RuleSet {
+    engine: Option<Engine>,
+    rules: Vec<CompiledRule>,
+    fuel_per_sample: u64,
+    max_memory_bytes: usize,
+}
+
+impl std::fmt::Debug for RuleSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RuleSet")
+            .field("rules", &self.names())
+            .field("fuel_per_sample", &self.fuel_per_sample)
+            .field("max_memory_bytes", &self.max_memory_bytes)
+            .finish()
+    }
+}
+
+impl RuleSet {
+    fn empty() -> Self {
+        Self {
+            engine: None,
+            rules: Vec::new(),
+            fuel_per_sample: 0,
+            max_memory_bytes: 0,
+        }
+    }
+
+    /// Compiles every rule module in the configured directory; modules that do not compile,
+    /// import anything or lack the rule exports are left out
+    fn load(config: &DetectionRulesConfig) -> Self {
+        let mut engine_config = Config::new();
+        engine_config.consume_fuel(true);
+        let engine = match Engine::new(&engine_config) {
+            Ok(engine) => engine,
+            Err(e) => {
+                warn!(error = %e, "Cannot create the detection rule engine");
+                return Self::empty();
+            }
+        };
+
+        let mut paths: Vec<_> = match std::fs::read_dir(&config.rules_dir) {
+            Ok(entries) => entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| path.extension().is_some_and(|ext| ext == RULE_EXTENSION))
+                .collect(),
+            Err(e) => {
+                warn!(dir = %config.rules_dir.display(), error = %e, "Cannot read the detection rules directory");
+                Vec::new()
+            }
+        };
+        paths.sort();
+
+        // Rules get no host functions, so a module importing anything fails to pre-instantiate
+        let linker = Linker::new(&engine);
+        let rules = paths
+            .iter()
+            .filter_map(|path| match compile(&engine, &linker, path) {
+                Ok(rule) => Some(rule),
+                Err(reason) => {
+                    counter!("guardian.detection.rules.rejected", 1);
+                    warn!(rule = %path.display(), reason, "Rejected detection rule");
+                    None
+                }
+            })
+            .collect();
+
+        Self {
+            engine: Some(engine),
+            rules,
+            fuel_per_sample: config.fuel_per_sample,
+            max_memory_bytes: config.max_memory_bytes,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.rules.iter().map(|rule| rule.name.as_str()).collect()
+    }
+
+    /// Runs every rule against every sample; blocks, so call it off the async runtime
+    pub fn evaluate(&self, samples: &[SystemData]) -> Vec<Prediction> {
+        let Some(engine) = &self.engine else {
+            return Vec::new();
+        };
+        let mut predictions = Vec::new();
+        for sample in samples {
+            let input = match serde_json::to_vec(sample) {
+                Ok(input) => input,
+                Err(e) => {
+                    warn!(error = %e, "Cannot encode sample for detection rules");
+                    continue;
+                }
+            };
+            for rule in &self.rules {
+                let start = std::time::Instant::now();
+                let outcome = self.run(engine, rule, &input);
+                histogram!("guardian.detection.rules.duration", start.elapsed().as_secs_f64(), "rule" => rule.name.clone());
+                match outcome {
+                    Ok(Some(verdict)) => {
+                        counter!("guardian.detection.rules.matches", 1, "rule" => rule.name.clone());
+                        predictions.push(prediction(&rule.name, verdict));
+                    }
+                    Ok(None) => {}
+                    Err(reason) => {
+                        counter!("guardian.detection.rules.failures", 1, "rule" => rule.name.clone(), "reason" => reason);
+                        debug!(rule = %rule.name, reason, "Detection rule failed on a sample");
+                    }
+                }
+            }
+        }
+        predictions
+    }
+
+    /// Runs one rule on one encoded sample in a fresh, limited instance
+    fn run(&self, engine: &Engine, rule: &CompiledRule, input: &[u8]) -> Result<Option<RuleVerdict>, &'static str> {
+        let limits = StoreLimitsBuilder::new()
+            .memory_size(self.max_memory_bytes)
+            .instances(1)
+            .build();
+        let mut store = Store::new(engine, RuleState { limits });
+        store.limiter(|state| &mut state.limits);
+        store.set_fuel(self.fuel_per_sample).map_err(|_| "fuel")?;
+
+        let instance = rule.instance.instantiate(&mut store).map_err(|_| "instantiate")?;
+        let memory = instance.get_memory(&mut store, "memory").ok_or("no memory export")?;
+        let alloc = instance
+            .get_typed_func::<i32, i32>(&mut store, "alloc")
+            .map_err(|_| "no alloc export")?;
+        let evaluate = instance
+            .get_typed_func::<(i32, i32), i64>(&mut store, "evaluate")
+            .map_err(|_| "no evaluate export")?;
+
+        let len = i32::try_from(input.len()).map_err(|_| "sample too large")?;
+        let ptr = alloc.call(&mut store, len).map_err(trap_reason)?;
+        memory.write(&mut store, ptr as u32 as usize, input).map_err(|_| "alloc out of bounds")?;
+
+        let packed = evaluate.call(&mut store, (ptr, len)).map_err(trap_reason)? as u64;
+        if packed == 0 {
+            return Ok(None);
+        }
+        let (offset, length) = ((packed >> 32) as usize, (packed & 0xffff_ffff) as usize);
+        let output = memory.data(&store).get(offset..offset.saturating_add(length)).ok_or("verdict out of bounds")?;
+        let verdict: RuleVerdict = serde_json::from_slice(output).map_err(|_| "malformed verdict")?;
+        if !(0.0..=1.0).contains(&verdict.confidence) {
+            return Err("confidence out of range");
+        }
+        Ok(Some(verdict))
+    }
+}
+
+/// The loaded rule set, replaced as a whole on every reload
+#[derive(Debug)]
+pub struct RuleSandbox {
+    rules: RwLock<Arc<RuleSet>>,
+}
+
+impl Default for RuleSandbox {
+    fn default() -> Self {
+        Self {
+            rules: RwLock::new(Arc::new(RuleSet::empty())),
+        }
+    }
+}
+
+impl RuleSandbox {
+    /// Recompiles the rules directory, or unloads every rule when disabled; returns the rule count
+    pub fn configure(&self, config: &DetectionRulesConfig) -> usize {
+        let rules = if config.enabled { RuleSet::load(config) } else { RuleSet::empty() };
+        let loaded = rules.len();
+        *self.rules.write() = Arc::new(rules);
+        loaded
+    }
+
+    /// Returns the current rule set, unaffected by later reloads
+    pub fn rules(&self) -> Arc<RuleSet> {
+        Arc::clone(&self.rules.read())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.read().is_empty()
+    }
+}
+
+fn compile(engine: &Engine, linker: &Linker<RuleState>, path: &Path) -> Result<CompiledRule, &'static str> {
+    let name = path.file_stem().and_then(|stem| stem.to_str()).ok_or("file name is not UTF-8")?;
+    let module = Module::from_file(engine, path).map_err(|_| "does not compile")?;
+    for export in ["memory", "alloc", "evaluate"] {
+        if module.get_export(export).is_none() {
+            return Err("missing a memory, alloc or evaluate export");
+        }
+    }
+    let instance = linker.instantiate_pre(&module).map_err(|_| "imports host functions")?;
+    Ok(CompiledRule {
+        name: name.to_string(),
+        instance,
+    })
+}
+
+fn trap_reason(error: wasmtime::Error) -> &'static str {
+    match error.downcast_ref::<Trap>() {
+        Some(Trap::OutOfFuel) => "out of fuel",
+        Some(_) => "trap",
+        None => "error",
+    }
+}
+
+fn prediction(rule: &str, verdict: RuleVerdict) -> Prediction {
+    let mut metadata = verdict.metadata;
+    metadata.insert("rule".into(), rule.to_string());
+    let prediction_type = verdict.prediction_type.unwrap_or_else(|| RULE_PREDICTION_TYPE.to_string());
+    Prediction::new(prediction_type, verdict.confidence, metadata)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MATCHING_RULE: &str = r#"(module
+        (memory (export "memory") 1)
+        (data (i32.const 1024) "{\"confidence\":0.9,\"metadata\":{\"reason\":\"test\"}}")
+        (func (export "alloc") (param i32) (result i32) (i32.const 0))
+        (func (export "evaluate") (param i32 i32) (result i64)
+            (i64.or (i64.shl (i64.const 1024) (i64.const 32)) (i64.const 47))))"#;
+
+    const SPINNING_RULE: &str = r#"(module
+        (memory (export "memory") 1)
+        (func (export "alloc") (param i32) (result i32) (i32.const 0))
+        (func (export "evaluate") (param i32 i32) (result i64) (loop (br 0)) (i64.const 0)))"#;
+
+    const IMPORTING_RULE: &str = r#"(module
+        (import "env" "exfiltrate" (func))
+        (memory (export "memory") 1)
+        (func (export "alloc") (param i32) (result i32) (i32.const 0))
+        (func (export "evaluate") (param i32 i32) (result i64) (i64.const 0)))"#;
+
+    #[test]
+    fn test_rules_run_sandboxed_and_bounded_by_fuel() {
+        let dir = tempfile::tempdir().unwrap();
+        // Text modules compile like binary ones
+        std::fs::write(dir.path().join("match.wasm"), MATCHING_RULE).unwrap();
+        std::fs::write(dir.path().join("spin.wasm"), SPINNING_RULE).unwrap();
+        std::fs::write(dir.path().join("import.wasm"), IMPORTING_RULE).unwrap();
+
+        let sandbox = RuleSandbox::default();
+        let config = DetectionRulesConfig {
+            enabled: true,
+            rules_dir: dir.path().to_path_buf(),
+            fuel_per_sample: 100_000,
+            ..Default::default()
+        };
+        assert_eq!(sandbox.configure(&config), 2);
+        assert_eq!(sandbox.rules().names(), vec!["match", "spin"]);
+
+        let sample = SystemData {
+            metrics: HashMap::from([("cpu".to_string(), 0.5)]),
+            events: vec!["exec /tmp/x".into()],
+            timestamp: 0,
+        };
+        let predictions = sandbox.rules().evaluate(&[sample]);
+        assert_eq!(predictions.len(), 1);
+        assert_eq!(predictions[0].prediction_type, RULE_PREDICTION_TYPE);
+        assert_eq!(predictions[0].metadata["rule"], "match");
+        assert_eq!(predictions[0].metadata["reason"], "test");
+
+        assert_eq!(sandbox.configure(&DetectionRulesConfig::default()), 0);
+    }
+}
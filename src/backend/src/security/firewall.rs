@@ -0,0 +1,295 @@
+//! Network blocks enforced through a pf table
+//!
+//! pf is expected to reference the table from its ruleset, e.g.
+//! `table <guardian_blocked> persist` and `block drop quick from <guardian_blocked>`.
+//! Guardian owns the table contents: the blocks it wants are kept in a state file with their
+//! expiry, and `reconcile` brings the table back in line with them after a restart or a pf
+//! reload, adding missing addresses and removing ones no unexpired block accounts for.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    net::IpAddr,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use metrics::{counter, gauge};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+use tracing::{info, instrument, warn};
+
+use crate::security::response_actions::run_host_command;
+use crate::utils::error::{ErrorCategory, GuardianError, SecurityError};
+
+// Constants for the firewall manager
+const BLOCKED_ADDRESS_TABLE: &str = "guardian_blocked";
+/// Lives on the Guardian dataset so blocks survive restarts
+pub const DEFAULT_FIREWALL_STATE_PATH: &str = "/var/lib/guardian/firewall/blocks.json";
+const EXPIRY_INTERVAL: Duration = Duration::from_secs(30);
+
+static FIREWALL: Lazy<Arc<FirewallManager>> =
+    Lazy::new(|| Arc::new(FirewallManager::new(DEFAULT_FIREWALL_STATE_PATH)));
+
+/// An address Guardian keeps blocked until it expires
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NetworkBlock {
+    pub address: String,
+    pub reason: String,
+    pub blocked_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Changes made to the pf table to match the desired blocks
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Reconciliation {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+/// Programs the pf block table and keeps it in line with the blocks Guardian holds
+#[derive(Debug)]
+pub struct FirewallManager {
+    state_path: PathBuf,
+    blocks: Mutex<BTreeMap<String, NetworkBlock>>,
+}
+
+/// Returns the process-wide firewall manager
+pub fn firewall() -> Arc<FirewallManager> {
+    Arc::clone(&FIREWALL)
+}
+
+impl FirewallManager {
+    /// Creates a manager persisting its blocks at `state_path`; nothing is loaded until `reconcile`
+    pub fn new(state_path: impl Into<PathBuf>) -> Self {
+        Self { state_path: state_path.into(), blocks: Mutex::new(BTreeMap::new()) }
+    }
+
+    /// Reconciles the table with the persisted blocks, then expires blocks in the background
+    pub async fn start(self: &Arc<Self>) -> Result<Reconciliation, GuardianError> {
+        let reconciliation = self.reconcile().await?;
+        let manager = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(EXPIRY_INTERVAL);
+            loop {
+                interval.tick().await;
+                if let Err(e) = manager.expire().await {
+                    warn!(error = %e, "Failed to expire network blocks");
+                }
+            }
+        });
+        Ok(reconciliation)
+    }
+
+    /// Blocks an address for `ttl`; blocking a blocked address extends it when `ttl` ends later
+    #[instrument(skip(self))]
+    pub async fn block(&self, address: &str, ttl: Duration, reason: &str) -> Result<NetworkBlock, GuardianError> {
+        let address = normalize_address(address)?;
+        let now = Utc::now();
+        let expires_at = now + chrono::Duration::from_std(ttl).map_err(|_| firewall_error("Block duration out of range".into()))?;
+
+        let mut blocks = self.blocks.lock().await;
+        run_host_command("pfctl", &table_args("add", &[address.clone()])).await?;
+        let block = blocks
+            .entry(address.clone())
+            .and_modify(|block| block.expires_at = block.expires_at.max(expires_at))
+            .or_insert_with(|| NetworkBlock { address: address.clone(), reason: reason.to_string(), blocked_at: now, expires_at })
+            .clone();
+        self.persist(&blocks).await?;
+
+        gauge!("guardian.firewall.blocks", blocks.len() as f64);
+        counter!("guardian.firewall.blocked", 1);
+        info!(target: "SECURITY-AUDIT", address = %address, expires_at = %block.expires_at, reason, "Address blocked");
+        Ok(block)
+    }
+
+    /// Lifts a block; unknown addresses are still removed from the table
+    #[instrument(skip(self))]
+    pub async fn unblock(&self, address: &str) -> Result<(), GuardianError> {
+        let address = normalize_address(address)?;
+        let mut blocks = self.blocks.lock().await;
+        run_host_command("pfctl", &table_args("delete", &[address.clone()])).await?;
+        blocks.remove(&address);
+        self.persist(&blocks).await?;
+
+        gauge!("guardian.firewall.blocks", blocks.len() as f64);
+        info!(target: "SECURITY-AUDIT", address = %address, "Address unblocked");
+        Ok(())
+    }
+
+    /// Current blocks, soonest expiry first
+    pub async fn blocks(&self) -> Vec<NetworkBlock> {
+        let mut blocks: Vec<NetworkBlock> = self.blocks.lock().await.values().cloned().collect();
+        blocks.sort_by_key(|block| block.expires_at);
+        blocks
+    }
+
+    /// Returns the block on an address, if Guardian holds one
+    pub async fn block_for(&self, address: &str) -> Option<NetworkBlock> {
+        let address = normalize_address(address).ok()?;
+        self.blocks.lock().await.get(&address).cloned()
+    }
+
+    /// Whether pf currently blocks an address
+    pub async fn is_enforced(&self, address: &str) -> bool {
+        match normalize_address(address) {
+            Ok(address) => run_host_command("pfctl", &table_args("test", &[address])).await.is_ok(),
+            Err(_) => false,
+        }
+    }
+
+    /// Removes blocks whose time is up, returning their addresses
+    pub async fn expire(&self) -> Result<Vec<String>, GuardianError> {
+        let mut blocks = self.blocks.lock().await;
+        let now = Utc::now();
+        let expired: Vec<String> = blocks.values().filter(|b| b.expires_at <= now).map(|b| b.address.clone()).collect();
+        if expired.is_empty() {
+            return Ok(expired);
+        }
+
+        run_host_command("pfctl", &table_args("delete", &expired)).await?;
+        blocks.retain(|_, block| block.expires_at > now);
+        self.persist(&blocks).await?;
+
+        gauge!("guardian.firewall.blocks", blocks.len() as f64);
+        counter!("guardian.firewall.expired", expired.len() as u64);
+        info!(target: "SECURITY-AUDIT", addresses = ?expired, "Network blocks expired");
+        Ok(expired)
+    }
+
+    /// Loads the persisted blocks and makes the pf table match them
+    #[instrument(skip(self))]
+    pub async fn reconcile(&self) -> Result<Reconciliation, GuardianError> {
+        let mut blocks = self.blocks.lock().await;
+        let now = Utc::now();
+        let mut desired = load(&self.state_path).await?;
+        desired.extend(blocks.clone());
+        desired.retain(|_, block| block.expires_at > now);
+
+        let listed = run_host_command("pfctl", &table_args("show", &[])).await?;
+        let actual: BTreeSet<String> = listed.lines().map(str::trim).filter(|l| !l.is_empty()).map(str::to_string).collect();
+        let reconciliation = plan(&desired.keys().cloned().collect(), &actual);
+
+        if !reconciliation.added.is_empty() {
+            run_host_command("pfctl", &table_args("add", &reconciliation.added)).await?;
+        }
+        if !reconciliation.removed.is_empty() {
+            run_host_command("pfctl", &table_args("delete", &reconciliation.removed)).await?;
+        }
+        *blocks = desired;
+        self.persist(&blocks).await?;
+
+        gauge!("guardian.firewall.blocks", blocks.len() as f64);
+        info!(
+            blocks = blocks.len(),
+            added = reconciliation.added.len(),
+            removed = reconciliation.removed.len(),
+            "Firewall table reconciled"
+        );
+        Ok(reconciliation)
+    }
+
+    async fn persist(&self, blocks: &BTreeMap<String, NetworkBlock>) -> Result<(), GuardianError> {
+        if let Some(parent) = self.state_path.parent() {
+            tokio::fs::create_dir_all(parent).await.map_err(|e| io_error(&self.state_path, e))?;
+        }
+        let raw = serde_json::to_vec_pretty(&blocks.values().collect::<Vec<_>>())
+            .map_err(|e| firewall_error(format!("Failed to serialize network blocks: {}", e)))?;
+        // Write through a temporary file so a crash keeps the previous state
+        let temporary = self.state_path.with_extension("json.tmp");
+        tokio::fs::write(&temporary, raw).await.map_err(|e| io_error(&temporary, e))?;
+        tokio::fs::rename(&temporary, &self.state_path).await.map_err(|e| io_error(&self.state_path, e))
+    }
+}
+
+/// Addresses to add to and remove from the table so it holds exactly `desired`
+fn plan(desired: &BTreeSet<String>, actual: &BTreeSet<String>) -> Reconciliation {
+    Reconciliation {
+        added: desired.difference(actual).cloned().collect(),
+        removed: actual.difference(desired).cloned().collect(),
+    }
+}
+
+/// Accepts an address or CIDR network, in the form pfctl prints it back
+pub(crate) fn normalize_address(address: &str) -> Result<String, GuardianError> {
+    let (host, prefix) = match address.trim().split_once('/') {
+        Some((host, prefix)) => (host, Some(prefix)),
+        None => (address.trim(), None),
+    };
+    let ip: IpAddr = host.parse().map_err(|_| firewall_error(format!("Invalid address {}", address)))?;
+    if ip.is_loopback() || ip.is_unspecified() {
+        return Err(firewall_error(format!("Refusing to block {}", address)));
+    }
+    match prefix {
+        None => Ok(ip.to_string()),
+        Some(prefix) => {
+            let max = if ip.is_ipv4() { 32 } else { 128 };
+            match prefix.parse::<u8>() {
+                Ok(bits) if bits <= max => Ok(format!("{}/{}", ip, bits)),
+                _ => Err(firewall_error(format!("Invalid prefix length in {}", address))),
+            }
+        }
+    }
+}
+
+fn table_args(command: &str, addresses: &[String]) -> Vec<String> {
+    let mut args = vec!["-t".into(), BLOCKED_ADDRESS_TABLE.into(), "-T".into(), command.into()];
+    args.extend(addresses.iter().cloned());
+    args
+}
+
+async fn load(path: &Path) -> Result<BTreeMap<String, NetworkBlock>, GuardianError> {
+    let raw = match tokio::fs::read(path).await {
+        Ok(raw) => raw,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(BTreeMap::new()),
+        Err(e) => return Err(io_error(path, e)),
+    };
+    let blocks: Vec<NetworkBlock> = serde_json::from_slice(&raw)
+        .map_err(|e| firewall_error(format!("Corrupt firewall state {}: {}", path.display(), e)))?;
+    Ok(blocks.into_iter().map(|block| (block.address.clone(), block)).collect())
+}
+
+fn io_error(path: &Path, e: std::io::Error) -> GuardianError {
+    GuardianError::SystemError {
+        context: format!("Firewall state I/O failed on {}", path.display()),
+        source: Some(Box::new(e)),
+        severity: crate::utils::error::ErrorSeverity::High,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: ErrorCategory::System,
+        retry_count: 0,
+    }
+}
+
+fn firewall_error(context: String) -> GuardianError {
+    SecurityError {
+        context,
+        source: None,
+        severity: crate::utils::error::ErrorSeverity::High,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: ErrorCategory::Security,
+        retry_count: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reconciliation_plan_and_address_normalization() {
+        let desired: BTreeSet<String> = ["10.0.0.5".to_string(), "192.0.2.0/24".to_string()].into();
+        let actual: BTreeSet<String> = ["10.0.0.5".to_string(), "198.51.100.7".to_string()].into();
+        let reconciliation = plan(&desired, &actual);
+        assert_eq!(reconciliation.added, vec!["192.0.2.0/24"]);
+        assert_eq!(reconciliation.removed, vec!["198.51.100.7"]);
+
+        assert_eq!(normalize_address(" 2001:db8:0::1 ").unwrap(), "2001:db8::1");
+        assert!(normalize_address("127.0.0.1").is_err());
+        assert!(normalize_address("10.0.0.0/33").is_err());
+        assert!(normalize_address("not-an-address").is_err());
+    }
+}
@@ -0,0 +1,314 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use metrics::counter;
+use parking_lot::Mutex;
+use serde::Serialize;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{info, instrument, warn};
+
+use crate::core::event_bus::{Event, EventBus, EventPriority};
+use crate::core::guardian::{TenantContext, TenantId};
+use crate::security::incident_summary::{IncidentSummarizer, IncidentSummary};
+use crate::security::response_engine::ResponseAction;
+use crate::security::threat_detection::{ThreatDetector, ThreatLevel, ThreatNotice};
+use crate::utils::error::GuardianError;
+use crate::utils::ids::{next_id, GuardianId, IdKind};
+
+// Constants for incident tracking
+/// Detections this close to an incident's last activity may join it
+const CORRELATION_WINDOW: Duration = Duration::from_secs(600);
+const MAX_TRACKED_INCIDENTS: usize = 1024;
+/// Detection details that name an entity, grouped under the entity kind
+const ENTITY_KEYS: [(&str, &str); 9] = [
+    ("pid", "process"),
+    ("process", "process"),
+    ("process_name", "process"),
+    ("user", "user"),
+    ("host", "host"),
+    ("source_address", "address"),
+    ("destination_address", "address"),
+    ("file", "file"),
+    ("path", "file"),
+];
+
+/// A response taken while handling an incident
+#[derive(Debug, Clone, Serialize)]
+pub struct IncidentResponse {
+    pub action: String,
+    pub success: bool,
+    pub at: SystemTime,
+}
+
+/// Related detections and the responses taken to them
+#[derive(Debug, Clone, Serialize)]
+pub struct Incident {
+    pub id: GuardianId,
+    pub tenant_id: TenantId,
+    pub opened_at: SystemTime,
+    pub updated_at: SystemTime,
+    pub detections: Vec<ThreatNotice>,
+    pub responses: Vec<IncidentResponse>,
+    pub summary: Option<IncidentSummary>,
+    pub resolved_at: Option<SystemTime>,
+}
+
+impl Incident {
+    /// Opens an incident for its first detection
+    pub fn open(detection: ThreatNotice) -> Self {
+        Self {
+            id: next_id(IdKind::Incident),
+            tenant_id: detection.tenant_id.clone(),
+            opened_at: detection.detected_at,
+            updated_at: detection.detected_at,
+            detections: vec![detection],
+            responses: Vec::new(),
+            summary: None,
+            resolved_at: None,
+        }
+    }
+
+    /// Returns the highest threat level among the incident's detections
+    pub fn severity(&self) -> ThreatLevel {
+        self.detections
+            .iter()
+            .map(|d| d.level.clone())
+            .min_by_key(level_rank)
+            .unwrap_or(ThreatLevel::Low)
+    }
+
+    /// Returns the processes, users, hosts, addresses and files named by its detections
+    pub fn entities(&self) -> BTreeMap<String, BTreeSet<String>> {
+        let mut entities: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        for detection in &self.detections {
+            for (key, kind) in ENTITY_KEYS {
+                if let Some(value) = detection.details.get(key) {
+                    entities.entry(kind.to_string()).or_default().insert(value.clone());
+                }
+            }
+        }
+        entities
+    }
+
+    /// Whether `detection` belongs to this incident: open, same tenant, recent, and sharing a type or entity
+    fn correlates(&self, detection: &ThreatNotice) -> bool {
+        if self.resolved_at.is_some() || detection.tenant_id != self.tenant_id {
+            return false;
+        }
+        let idle = detection.detected_at.duration_since(self.updated_at).unwrap_or_default();
+        if idle > CORRELATION_WINDOW {
+            return false;
+        }
+        if self.detections.iter().any(|d| d.prediction_type == detection.prediction_type) {
+            return true;
+        }
+        let entities = self.entities();
+        ENTITY_KEYS.iter().any(|(key, kind)| {
+            detection
+                .details
+                .get(*key)
+                .map_or(false, |value| entities.get(*kind).map_or(false, |known| known.contains(value)))
+        })
+    }
+}
+
+/// Groups detections into incidents, keeps their summaries current and raises incident alerts
+#[derive(Debug)]
+pub struct IncidentTracker {
+    incidents: Mutex<VecDeque<Incident>>,
+    summarizer: Arc<IncidentSummarizer>,
+    event_bus: Arc<EventBus>,
+    tenant: TenantContext,
+}
+
+impl IncidentTracker {
+    /// Creates a tracker publishing incident alerts on the tenant's event topics
+    pub fn new(event_bus: Arc<EventBus>, summarizer: Arc<IncidentSummarizer>, tenant: TenantContext) -> Self {
+        Self {
+            incidents: Mutex::new(VecDeque::new()),
+            summarizer,
+            event_bus,
+            tenant,
+        }
+    }
+
+    /// Feeds every detection made by `detector` into the tracker
+    pub fn follow(self: &Arc<Self>, detector: &ThreatDetector) {
+        let (_, mut detections) = detector.watch_threats(None);
+        let tracker = Arc::clone(self);
+        tokio::spawn(async move {
+            loop {
+                match detections.recv().await {
+                    Ok(detection) => {
+                        if let Err(e) = tracker.record_detection(detection).await {
+                            warn!(error = %e, "Failed to record detection in incident");
+                        }
+                    }
+                    Err(RecvError::Lagged(missed)) => {
+                        warn!(missed, "Incident tracker fell behind the detection feed");
+                    }
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        });
+    }
+
+    /// Adds a detection to the incident it correlates with, or opens a new one.
+    /// An alert carrying the summary is raised when an incident opens or escalates.
+    #[instrument(skip(self, detection), fields(detection_id = %detection.detection_id))]
+    pub async fn record_detection(&self, detection: ThreatNotice) -> Result<GuardianId, GuardianError> {
+        let (id, escalated) = {
+            let mut incidents = self.incidents.lock();
+            match incidents.iter_mut().rev().find(|i| i.correlates(&detection)) {
+                Some(incident) => {
+                    let before = level_rank(&incident.severity());
+                    incident.updated_at = incident.updated_at.max(detection.detected_at);
+                    incident.detections.push(detection);
+                    (incident.id, level_rank(&incident.severity()) < before)
+                }
+                None => {
+                    let incident = Incident::open(detection);
+                    let id = incident.id;
+                    if incidents.len() == MAX_TRACKED_INCIDENTS {
+                        incidents.pop_front();
+                    }
+                    incidents.push_back(incident);
+                    counter!("guardian.incidents.opened", 1);
+                    (id, true)
+                }
+            }
+        };
+
+        let summary = self.resummarize(id).await;
+        if escalated {
+            if let Some(summary) = summary {
+                self.raise_alert(id, &summary).await?;
+            }
+        }
+        Ok(id)
+    }
+
+    /// Records a response taken for an incident and refreshes its summary
+    pub async fn record_response(&self, id: GuardianId, action: &ResponseAction, success: bool) -> Option<IncidentSummary> {
+        {
+            let mut incidents = self.incidents.lock();
+            let incident = incidents.iter_mut().find(|i| i.id == id)?;
+            incident.responses.push(IncidentResponse {
+                action: action.name().to_string(),
+                success,
+                at: SystemTime::now(),
+            });
+        }
+        self.resummarize(id).await
+    }
+
+    /// Marks an incident resolved; later detections open a new incident
+    pub fn resolve(&self, id: GuardianId) -> Option<Incident> {
+        let mut incidents = self.incidents.lock();
+        let incident = incidents.iter_mut().find(|i| i.id == id)?;
+        if incident.resolved_at.is_none() {
+            incident.resolved_at = Some(SystemTime::now());
+            counter!("guardian.incidents.resolved", 1);
+            info!(incident_id = %id, "Incident resolved");
+        }
+        Some(incident.clone())
+    }
+
+    /// Returns tracked incidents not yet resolved
+    pub fn unresolved(&self) -> Vec<Incident> {
+        self.incidents.lock().iter().filter(|i| i.resolved_at.is_none()).cloned().collect()
+    }
+
+    /// Returns a tracked incident with its latest summary
+    pub fn get(&self, id: GuardianId) -> Option<Incident> {
+        self.incidents.lock().iter().find(|i| i.id == id).cloned()
+    }
+
+    /// Returns tracked incidents, most recently opened first
+    pub fn list(&self) -> Vec<Incident> {
+        self.incidents.lock().iter().rev().cloned().collect()
+    }
+
+    /// Regenerates an incident's summary outside the lock and attaches it
+    async fn resummarize(&self, id: GuardianId) -> Option<IncidentSummary> {
+        let snapshot = self.get(id)?;
+        let summary = self.summarizer.summarize(&snapshot).await;
+
+        let mut incidents = self.incidents.lock();
+        let incident = incidents.iter_mut().find(|i| i.id == id)?;
+        // A concurrent update may have produced a newer summary already
+        if incident.summary.as_ref().map_or(true, |s| s.generated_at <= summary.generated_at) {
+            incident.summary = Some(summary.clone());
+        }
+        Some(summary)
+    }
+
+    async fn raise_alert(&self, id: GuardianId, summary: &IncidentSummary) -> Result<(), GuardianError> {
+        let priority = match summary.severity {
+            ThreatLevel::Critical => EventPriority::Critical,
+            ThreatLevel::High => EventPriority::High,
+            _ => EventPriority::Medium,
+        };
+        let event = Event::new(
+            self.tenant.topic("incident_alert"),
+            serde_json::json!({
+                "incident_id": id,
+                "severity": summary.severity,
+                "headline": summary.headline,
+                "summary": summary,
+            }),
+            priority,
+        )?;
+        self.event_bus.publish(event).await?;
+
+        counter!("guardian.incidents.alerts", 1);
+        info!(incident_id = %id, severity = ?summary.severity, headline = %summary.headline, "Incident alert raised");
+        Ok(())
+    }
+}
+
+/// Orders threat levels from most to least severe
+fn level_rank(level: &ThreatLevel) -> u8 {
+    match level {
+        ThreatLevel::Critical => 0,
+        ThreatLevel::High => 1,
+        ThreatLevel::Medium => 2,
+        ThreatLevel::Low => 3,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn notice(kind: &str, details: &[(&str, &str)], at: SystemTime) -> ThreatNotice {
+        ThreatNotice {
+            detection_id: next_id(IdKind::Detection).to_string(),
+            tenant_id: TenantContext::default().tenant_id().clone(),
+            level: ThreatLevel::High,
+            confidence: 0.9,
+            prediction_type: kind.into(),
+            detected_at: at,
+            details: details.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect::<HashMap<_, _>>(),
+        }
+    }
+
+    #[test]
+    fn test_detections_correlate_by_type_entity_and_window() {
+        let start = SystemTime::now();
+        let incident = Incident::open(notice("brute_force", &[("source_address", "10.0.0.7")], start));
+
+        assert!(incident.correlates(&notice("brute_force", &[], start + Duration::from_secs(60))));
+        assert!(incident.correlates(&notice("exfiltration", &[("destination_address", "10.0.0.7")], start)));
+        assert!(!incident.correlates(&notice("exfiltration", &[("source_address", "10.0.0.8")], start)));
+        assert!(!incident.correlates(&notice("brute_force", &[], start + CORRELATION_WINDOW * 2)));
+
+        let mut resolved = incident.clone();
+        resolved.resolved_at = Some(start);
+        assert!(!resolved.correlates(&notice("brute_force", &[], start)));
+    }
+}
@@ -0,0 +1,287 @@
+use std::{
+    collections::BTreeMap,
+    time::SystemTime,
+};
+#[cfg(feature = "llm-summary")]
+use std::{sync::Arc, time::Duration};
+
+use serde::Serialize;
+use tracing::instrument;
+#[cfg(feature = "llm-summary")]
+use tracing::warn;
+
+use crate::security::incident::Incident;
+use crate::security::threat_detection::ThreatLevel;
+#[cfg(feature = "llm-summary")]
+use crate::utils::error::GuardianError;
+
+// Constants for incident summaries
+const TEMPLATE_GENERATOR: &str = "template";
+const MAX_TIMELINE_ENTRIES: usize = 8;
+const MAX_ENTITY_VALUES: usize = 5;
+const MAX_HEADLINE_TYPES: usize = 3;
+#[cfg(feature = "llm-summary")]
+const MODEL_MAX_TOKENS: usize = 160;
+#[cfg(feature = "llm-summary")]
+const MODEL_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// One step of an incident as shown in its summary
+#[derive(Debug, Clone, Serialize)]
+pub struct TimelineEntry {
+    pub at: SystemTime,
+    pub description: String,
+}
+
+/// Short structured account of an incident for triage
+#[derive(Debug, Clone, Serialize)]
+pub struct IncidentSummary {
+    pub headline: String,
+    pub severity: ThreatLevel,
+    pub detection_count: usize,
+    pub entities: BTreeMap<String, Vec<String>>,
+    pub timeline: Vec<TimelineEntry>,
+    pub responses: Vec<String>,
+    pub narrative: String,
+    /// `template`, or the name of the language model that wrote the narrative
+    pub generated_by: String,
+    pub generated_at: SystemTime,
+}
+
+/// Small local language model used to phrase incident narratives
+#[cfg(feature = "llm-summary")]
+pub trait TextGenerator: Send + Sync + std::fmt::Debug {
+    /// Name recorded as the summary's generator
+    fn name(&self) -> &str;
+
+    /// Continues `prompt` with at most `max_tokens` tokens
+    fn generate(&self, prompt: &str, max_tokens: usize) -> Result<String, GuardianError>;
+}
+
+/// Condenses incidents into summaries attached to the incident and its alerts
+#[derive(Debug, Default)]
+pub struct IncidentSummarizer {
+    #[cfg(feature = "llm-summary")]
+    model: Option<Arc<dyn TextGenerator>>,
+}
+
+impl IncidentSummarizer {
+    /// Creates a summarizer using only the built-in templates
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Lets a local language model write the narrative, falling back to the template on failure
+    #[cfg(feature = "llm-summary")]
+    pub fn with_model(mut self, model: Arc<dyn TextGenerator>) -> Self {
+        self.model = Some(model);
+        self
+    }
+
+    /// Summarizes the incident; the structured fields are always derived from its data
+    #[instrument(skip(self, incident), fields(incident_id = %incident.id))]
+    pub async fn summarize(&self, incident: &Incident) -> IncidentSummary {
+        let summary = summarize_with_template(incident);
+
+        #[cfg(feature = "llm-summary")]
+        if let Some(model) = &self.model {
+            return match narrate(Arc::clone(model), &summary).await {
+                Ok(narrative) => IncidentSummary {
+                    narrative,
+                    generated_by: model.name().to_string(),
+                    ..summary
+                },
+                Err(e) => {
+                    warn!(error = %e, model = model.name(), "Model summary failed, using template");
+                    summary
+                }
+            };
+        }
+
+        summary
+    }
+}
+
+fn summarize_with_template(incident: &Incident) -> IncidentSummary {
+    let severity = incident.severity();
+    let entities: BTreeMap<String, Vec<String>> = incident
+        .entities()
+        .into_iter()
+        .map(|(kind, values)| (kind, values.into_iter().take(MAX_ENTITY_VALUES).collect()))
+        .collect();
+
+    let mut types: Vec<&str> = Vec::new();
+    for detection in &incident.detections {
+        if !types.contains(&detection.prediction_type.as_str()) {
+            types.push(&detection.prediction_type);
+        }
+    }
+    let mut type_list = types.iter().take(MAX_HEADLINE_TYPES).copied().collect::<Vec<_>>().join(", ");
+    if types.len() > MAX_HEADLINE_TYPES {
+        type_list.push_str(&format!(" and {} more", types.len() - MAX_HEADLINE_TYPES));
+    }
+
+    let mut headline = format!(
+        "{:?} incident: {} detection(s) of {}",
+        severity,
+        incident.detections.len(),
+        type_list
+    );
+    if let Some((kind, values)) = entities.iter().next() {
+        headline.push_str(&format!(" involving {} {}", kind, values.join(", ")));
+    }
+
+    let responses: Vec<String> = incident
+        .responses
+        .iter()
+        .map(|r| format!("{} {}", r.action, if r.success { "succeeded" } else { "failed" }))
+        .collect();
+
+    let mut narrative = format!("{}.", headline);
+    if let (Some(first), Some(last)) = (incident.detections.first(), incident.detections.last()) {
+        let span = last.detected_at.duration_since(first.detected_at).unwrap_or_default();
+        narrative.push_str(&format!(
+            " First detection {} at {:.0}% confidence; activity spans {}s.",
+            first.prediction_type,
+            first.confidence * 100.0,
+            span.as_secs()
+        ));
+    }
+    if responses.is_empty() {
+        narrative.push_str(" No response has been taken yet.");
+    } else {
+        narrative.push_str(&format!(" Responses: {}.", responses.join("; ")));
+    }
+
+    IncidentSummary {
+        headline,
+        severity,
+        detection_count: incident.detections.len(),
+        entities,
+        timeline: timeline(incident),
+        responses,
+        narrative,
+        generated_by: TEMPLATE_GENERATOR.to_string(),
+        generated_at: SystemTime::now(),
+    }
+}
+
+/// Detections and responses in time order, keeping the earliest entries and the latest one
+fn timeline(incident: &Incident) -> Vec<TimelineEntry> {
+    let mut entries: Vec<TimelineEntry> = incident
+        .detections
+        .iter()
+        .map(|d| TimelineEntry {
+            at: d.detected_at,
+            description: format!("{:?} {} detected ({})", d.level, d.prediction_type, d.detection_id),
+        })
+        .chain(incident.responses.iter().map(|r| TimelineEntry {
+            at: r.at,
+            description: format!("Response {} {}", r.action, if r.success { "succeeded" } else { "failed" }),
+        }))
+        .collect();
+    entries.sort_by_key(|e| e.at);
+
+    if entries.len() > MAX_TIMELINE_ENTRIES {
+        let last = entries.pop();
+        entries.truncate(MAX_TIMELINE_ENTRIES - 1);
+        entries.extend(last);
+    }
+    entries
+}
+
+#[cfg(feature = "llm-summary")]
+async fn narrate(model: Arc<dyn TextGenerator>, summary: &IncidentSummary) -> Result<String, GuardianError> {
+    let facts = serde_json::json!({
+        "headline": summary.headline,
+        "entities": summary.entities,
+        "timeline": summary.timeline.iter().map(|e| &e.description).collect::<Vec<_>>(),
+        "responses": summary.responses,
+    });
+    let prompt = format!(
+        "Summarize this security incident for an on-call analyst in at most three sentences. \
+         Use only the facts given.\n{}\nSummary:",
+        facts
+    );
+
+    let generated = tokio::time::timeout(
+        MODEL_TIMEOUT,
+        tokio::task::spawn_blocking(move || model.generate(&prompt, MODEL_MAX_TOKENS)),
+    )
+    .await
+    .map_err(|_| summary_error("Model summary timed out".into()))?
+    .map_err(|e| summary_error(format!("Model summary task failed: {}", e)))??;
+
+    let narrative = generated.trim();
+    if narrative.is_empty() {
+        return Err(summary_error("Model returned an empty summary".into()));
+    }
+    Ok(narrative.to_string())
+}
+
+#[cfg(feature = "llm-summary")]
+fn summary_error(context: String) -> GuardianError {
+    GuardianError::MLError {
+        context,
+        source: None,
+        severity: crate::utils::error::ErrorSeverity::Low,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: crate::utils::error::ErrorCategory::ML,
+        retry_count: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::guardian::TenantContext;
+    use crate::security::incident::IncidentResponse;
+    use crate::security::threat_detection::ThreatNotice;
+    use crate::utils::ids::{next_id, IdKind};
+    use std::{collections::HashMap, time::Duration};
+
+    fn notice(level: ThreatLevel, kind: &str, at: SystemTime) -> ThreatNotice {
+        ThreatNotice {
+            detection_id: next_id(IdKind::Detection).to_string(),
+            tenant_id: TenantContext::default().tenant_id().clone(),
+            level,
+            confidence: 0.9,
+            prediction_type: kind.into(),
+            detected_at: at,
+            details: HashMap::from([("process".to_string(), "sshd".to_string())]),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_template_summary() {
+        let start = SystemTime::now();
+        let mut incident = Incident::open(notice(ThreatLevel::Medium, "brute_force", start));
+        incident.detections.push(notice(ThreatLevel::Critical, "privilege_escalation", start + Duration::from_secs(30)));
+        incident.responses.push(IncidentResponse {
+            action: "isolate_process".into(),
+            success: true,
+            at: start + Duration::from_secs(31),
+        });
+
+        let summary = IncidentSummarizer::new().summarize(&incident).await;
+        assert_eq!(summary.severity, ThreatLevel::Critical);
+        assert_eq!(summary.generated_by, TEMPLATE_GENERATOR);
+        assert!(summary.headline.starts_with("Critical incident: 2 detection(s) of brute_force, privilege_escalation"));
+        assert_eq!(summary.entities["process"], vec!["sshd".to_string()]);
+        assert_eq!(summary.timeline.len(), 3);
+        assert!(summary.narrative.contains("isolate_process succeeded"));
+    }
+
+    #[test]
+    fn test_timeline_keeps_latest_entry() {
+        let start = SystemTime::now();
+        let mut incident = Incident::open(notice(ThreatLevel::Low, "scan", start));
+        for i in 1..20 {
+            incident.detections.push(notice(ThreatLevel::Low, "scan", start + Duration::from_secs(i)));
+        }
+
+        let entries = timeline(&incident);
+        assert_eq!(entries.len(), MAX_TIMELINE_ENTRIES);
+        assert_eq!(entries.last().unwrap().at, start + Duration::from_secs(19));
+    }
+}
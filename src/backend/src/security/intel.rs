@@ -0,0 +1,543 @@
+use std::{
+    collections::HashMap,
+    net::IpAddr,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use chrono::{DateTime, Utc};
+use metrics::{counter, gauge};
+use once_cell::sync::{Lazy, OnceCell};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+
+use crate::config::{AppConfig, IntelFeedConfig, IntelFeedFormat, ThreatIntelConfig};
+use crate::security::anomaly_detection::SystemData;
+use crate::utils::error::{GuardianError, SecurityError};
+
+// Constants for threat intelligence feeds
+pub const INTEL_PREDICTION_TYPE: &str = "intel_match";
+const TAXII_MEDIA_TYPE: &str = "application/taxii+json;version=2.1";
+const MAX_TAXII_PAGES: usize = 100;
+const FETCH_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(60);
+const TOKEN_DELIMITERS: &[char] = &[' ', '\t', ',', ';', '"', '\'', '=', '(', ')', '<', '>', '[', ']', '{', '}', '|'];
+
+static THREAT_INTEL: Lazy<Arc<ThreatIntel>> = Lazy::new(|| Arc::new(ThreatIntel::new(ThreatIntelConfig::default())));
+
+/// Returns the process-wide threat intelligence store
+pub fn threat_intel() -> Arc<ThreatIntel> {
+    Arc::clone(&THREAT_INTEL)
+}
+
+/// Applies the configured feeds, loading their last pull from disk; call again after a reload
+pub fn init_threat_intel(config: &AppConfig) {
+    let intel = threat_intel();
+    intel.configure(config.threat_intel.clone());
+    let loaded = intel.load_snapshots();
+    info!(feeds = config.threat_intel.feeds.len(), indicators = loaded, "Threat intel feeds configured");
+}
+
+/// What an indicator identifies
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IndicatorKind {
+    Ip,
+    Domain,
+    Url,
+    /// MD5, SHA-1 or SHA-256 file hash
+    Hash,
+}
+
+impl IndicatorKind {
+    fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "ip" | "ipv4" | "ipv6" | "ipv4-addr" | "ipv6-addr" => Some(IndicatorKind::Ip),
+            "domain" | "domain-name" | "hostname" => Some(IndicatorKind::Domain),
+            "url" => Some(IndicatorKind::Url),
+            "hash" | "md5" | "sha1" | "sha-1" | "sha256" | "sha-256" | "file" => Some(IndicatorKind::Hash),
+            _ => None,
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            IndicatorKind::Ip => "ip",
+            IndicatorKind::Domain => "domain",
+            IndicatorKind::Url => "url",
+            IndicatorKind::Hash => "hash",
+        }
+    }
+}
+
+/// A known-bad observable from a feed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Indicator {
+    pub kind: IndicatorKind,
+    /// Normalized value, as looked up
+    pub value: String,
+    pub feed: String,
+    pub confidence: f32,
+    /// STIX indicator the value came from
+    #[serde(default)]
+    pub source_id: Option<String>,
+    #[serde(default)]
+    pub valid_until: Option<DateTime<Utc>>,
+}
+
+impl Indicator {
+    fn is_current(&self, now: DateTime<Utc>) -> bool {
+        self.valid_until.map_or(true, |until| until > now)
+    }
+}
+
+/// The last successful pull of a feed, persisted so detection keeps working offline
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct FeedSnapshot {
+    feed: String,
+    fetched_at: DateTime<Utc>,
+    indicators: Vec<Indicator>,
+}
+
+/// Per-feed indicators and the index over all of them
+#[derive(Debug, Default)]
+struct IntelStore {
+    feeds: HashMap<String, FeedSnapshot>,
+    index: HashMap<String, Indicator>,
+}
+
+impl IntelStore {
+    fn replace(&mut self, snapshot: FeedSnapshot) {
+        self.feeds.insert(snapshot.feed.clone(), snapshot);
+        self.reindex();
+    }
+
+    /// Rebuilds the index; on overlap the most confident indicator wins
+    fn reindex(&mut self) {
+        let mut index: HashMap<String, Indicator> = HashMap::new();
+        for indicator in self.feeds.values().flat_map(|snapshot| snapshot.indicators.iter()) {
+            match index.get(&indicator.value) {
+                Some(existing) if existing.confidence >= indicator.confidence => {}
+                _ => {
+                    index.insert(indicator.value.clone(), indicator.clone());
+                }
+            }
+        }
+        gauge!("guardian.intel.indicators", index.len() as f64);
+        self.index = index;
+    }
+}
+
+/// Indicator feeds pulled on a schedule and consulted before ML inference
+#[derive(Debug)]
+pub struct ThreatIntel {
+    config: RwLock<ThreatIntelConfig>,
+    store: RwLock<IntelStore>,
+    client: reqwest::Client,
+    started: OnceCell<()>,
+}
+
+impl ThreatIntel {
+    pub fn new(config: ThreatIntelConfig) -> Self {
+        Self {
+            config: RwLock::new(config),
+            store: RwLock::new(IntelStore::default()),
+            client: reqwest::Client::builder()
+                .timeout(FETCH_TIMEOUT)
+                .build()
+                .unwrap_or_default(),
+            started: OnceCell::new(),
+        }
+    }
+
+    /// Replaces the feed list; indicators of feeds no longer configured are dropped
+    pub fn configure(&self, config: ThreatIntelConfig) {
+        {
+            let mut store = self.store.write();
+            store.feeds.retain(|name, _| config.feeds.iter().any(|feed| &feed.name == name));
+            store.reindex();
+        }
+        *self.config.write() = config;
+    }
+
+    /// Loads the persisted pull of every configured feed not yet in memory, returning the indicator count
+    pub fn load_snapshots(&self) -> usize {
+        let config = self.config.read().clone();
+        for feed in &config.feeds {
+            if self.store.read().feeds.contains_key(&feed.name) {
+                continue;
+            }
+            let path = snapshot_path(&config.store_path, &feed.name);
+            match std::fs::read(&path).map(|bytes| serde_json::from_slice::<FeedSnapshot>(&bytes)) {
+                Ok(Ok(snapshot)) => self.store.write().replace(snapshot),
+                Ok(Err(e)) => warn!(feed = %feed.name, error = %e, "Ignoring corrupt threat intel snapshot"),
+                Err(_) => debug!(feed = %feed.name, "No threat intel snapshot yet"),
+            }
+        }
+        self.store.read().index.len()
+    }
+
+    /// Refreshes the feeds now and then every `refresh_interval`
+    pub fn start(self: &Arc<Self>) {
+        if self.started.set(()).is_err() {
+            return;
+        }
+        let intel = Arc::clone(self);
+        tokio::spawn(async move {
+            loop {
+                intel.refresh_all().await;
+                let interval = intel.config.read().refresh_interval;
+                tokio::time::sleep(interval).await;
+            }
+        });
+    }
+
+    /// Pulls every configured feed; a failed feed keeps its previous indicators
+    pub async fn refresh_all(&self) {
+        let feeds = self.config.read().feeds.clone();
+        for feed in feeds {
+            match self.refresh_feed(&feed).await {
+                Ok(count) => {
+                    counter!("guardian.intel.refreshes", 1, "feed" => feed.name.clone(), "outcome" => "ok");
+                    info!(feed = %feed.name, indicators = count, "Threat intel feed refreshed");
+                }
+                Err(e) => {
+                    counter!("guardian.intel.refreshes", 1, "feed" => feed.name.clone(), "outcome" => "error");
+                    warn!(feed = %feed.name, error = %e, "Threat intel feed refresh failed, keeping previous indicators");
+                }
+            }
+        }
+    }
+
+    async fn refresh_feed(&self, feed: &IntelFeedConfig) -> Result<usize, GuardianError> {
+        let indicators = match feed.format {
+            IntelFeedFormat::Taxii => self.fetch_taxii(feed).await?,
+            format => {
+                let body = self.get(feed, &feed.url, None).await?.bytes().await.map_err(fetch_error(feed))?;
+                parse_feed(feed, format, &body)?
+            }
+        };
+
+        let snapshot = FeedSnapshot {
+            feed: feed.name.clone(),
+            fetched_at: Utc::now(),
+            indicators,
+        };
+        let count = snapshot.indicators.len();
+        let store_path = self.config.read().store_path.clone();
+        if let Err(e) = persist_snapshot(&store_path, &snapshot) {
+            warn!(feed = %feed.name, error = %e, "Failed to persist threat intel snapshot");
+        }
+        self.store.write().replace(snapshot);
+        Ok(count)
+    }
+
+    /// Follows a TAXII 2.1 collection's `next` cursor until the server reports no more objects
+    async fn fetch_taxii(&self, feed: &IntelFeedConfig) -> Result<Vec<Indicator>, GuardianError> {
+        let mut indicators = Vec::new();
+        let mut next: Option<String> = None;
+        for _ in 0..MAX_TAXII_PAGES {
+            let envelope: serde_json::Value = self
+                .get(feed, &feed.url, next.as_deref())
+                .await?
+                .json()
+                .await
+                .map_err(fetch_error(feed))?;
+            indicators.extend(parse_stix_objects(feed, &envelope));
+
+            next = envelope.get("next").and_then(|n| n.as_str()).map(str::to_string);
+            let more = envelope.get("more").and_then(|m| m.as_bool()).unwrap_or(false);
+            if !more || next.is_none() {
+                return Ok(indicators);
+            }
+        }
+        warn!(feed = %feed.name, pages = MAX_TAXII_PAGES, "TAXII collection truncated");
+        Ok(indicators)
+    }
+
+    async fn get(&self, feed: &IntelFeedConfig, url: &str, next: Option<&str>) -> Result<reqwest::Response, GuardianError> {
+        let mut request = self.client.get(url);
+        if feed.format == IntelFeedFormat::Taxii {
+            request = request.header(reqwest::header::ACCEPT, TAXII_MEDIA_TYPE);
+        }
+        if let Some(next) = next {
+            request = request.query(&[("next", next)]);
+        }
+        if let Some(token) = feed.token_env.as_ref().and_then(|var| std::env::var(var).ok()) {
+            request = request.bearer_auth(token);
+        }
+        request
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(fetch_error(feed))
+    }
+
+    /// Looks up a single observable
+    pub fn lookup(&self, observable: &str) -> Option<Indicator> {
+        let (_, value) = classify(observable)?;
+        let store = self.store.read();
+        store.index.get(&value).filter(|i| i.is_current(Utc::now())).cloned()
+    }
+
+    /// Finds the first known-bad observable mentioned in a sample's events
+    pub fn match_sample(&self, sample: &SystemData) -> Option<Indicator> {
+        let store = self.store.read();
+        if store.index.is_empty() {
+            return None;
+        }
+        let now = Utc::now();
+        sample
+            .events
+            .iter()
+            .flat_map(|event| event.split(TOKEN_DELIMITERS))
+            .filter_map(classify)
+            .find_map(|(_, value)| store.index.get(&value).filter(|i| i.is_current(now)).cloned())
+    }
+
+    /// Number of indexed indicators
+    pub fn len(&self) -> usize {
+        self.store.read().index.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Normalizes an observable, inferring its kind from its shape
+fn classify(raw: &str) -> Option<(IndicatorKind, String)> {
+    let raw = raw.trim().trim_end_matches('.');
+    if raw.is_empty() {
+        return None;
+    }
+    if let Ok(ip) = raw.parse::<IpAddr>() {
+        return Some((IndicatorKind::Ip, ip.to_string()));
+    }
+    if matches!(raw.len(), 32 | 40 | 64) && raw.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Some((IndicatorKind::Hash, raw.to_ascii_lowercase()));
+    }
+    if raw.contains("://") {
+        return Some((IndicatorKind::Url, raw.to_string()));
+    }
+    let is_domain = raw.contains('.')
+        && raw.chars().any(|c| c.is_ascii_alphabetic())
+        && raw.chars().all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-');
+    is_domain.then(|| (IndicatorKind::Domain, raw.to_ascii_lowercase()))
+}
+
+/// Normalizes an observable of a declared kind, rejecting values that do not fit it
+fn normalize(kind: IndicatorKind, raw: &str) -> Option<String> {
+    match classify(raw) {
+        Some((found, value)) if found == kind => Some(value),
+        _ => None,
+    }
+}
+
+fn parse_feed(feed: &IntelFeedConfig, format: IntelFeedFormat, body: &[u8]) -> Result<Vec<Indicator>, GuardianError> {
+    let indicator = |kind: IndicatorKind, value: String| Indicator {
+        kind,
+        value,
+        feed: feed.name.clone(),
+        confidence: feed.confidence,
+        source_id: None,
+        valid_until: None,
+    };
+
+    let parse_entry = |raw: &str, declared: Option<&str>| -> Option<Indicator> {
+        let (kind, value) = match declared.and_then(IndicatorKind::parse) {
+            Some(kind) => (kind, normalize(kind, raw)?),
+            None => classify(raw)?,
+        };
+        Some(indicator(kind, value))
+    };
+
+    let indicators = match format {
+        IntelFeedFormat::Csv => String::from_utf8_lossy(body)
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| {
+                let mut fields = line.split(',');
+                let value = fields.next()?;
+                parse_entry(value, fields.next())
+            })
+            .collect(),
+        IntelFeedFormat::Json => {
+            let entries: Vec<serde_json::Value> = serde_json::from_slice(body)?;
+            entries
+                .iter()
+                .filter_map(|entry| match entry {
+                    serde_json::Value::String(value) => parse_entry(value, None),
+                    serde_json::Value::Object(fields) => parse_entry(
+                        fields.get("value")?.as_str()?,
+                        fields.get("type").and_then(|t| t.as_str()),
+                    ),
+                    _ => None,
+                })
+                .collect()
+        }
+        IntelFeedFormat::Stix | IntelFeedFormat::Taxii => parse_stix_objects(feed, &serde_json::from_slice(body)?),
+    };
+    Ok(indicators)
+}
+
+/// Extracts indicators from the `objects` of a STIX bundle or TAXII envelope
+///
+/// Only equality comparisons on IP, domain, URL and file hash paths are indexed; revoked
+/// and expired indicators are skipped.
+fn parse_stix_objects(feed: &IntelFeedConfig, document: &serde_json::Value) -> Vec<Indicator> {
+    let now = Utc::now();
+    let objects = document.get("objects").and_then(|o| o.as_array()).cloned().unwrap_or_default();
+
+    let mut indicators = Vec::new();
+    for object in &objects {
+        let field = |name: &str| object.get(name).and_then(|v| v.as_str());
+        if field("type") != Some("indicator")
+            || field("pattern_type").map_or(false, |t| t != "stix")
+            || object.get("revoked").and_then(|r| r.as_bool()).unwrap_or(false)
+        {
+            continue;
+        }
+        let valid_until = field("valid_until")
+            .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+            .map(|t| t.with_timezone(&Utc));
+        if valid_until.map_or(false, |until| until <= now) {
+            continue;
+        }
+        let confidence = object
+            .get("confidence")
+            .and_then(|c| c.as_u64())
+            .map_or(feed.confidence, |c| (c.min(100) as f32 / 100.0).min(feed.confidence));
+
+        for (path, raw) in stix_comparisons(field("pattern").unwrap_or_default()) {
+            let kind = match path.as_str() {
+                "ipv4-addr:value" | "ipv6-addr:value" => IndicatorKind::Ip,
+                "domain-name:value" => IndicatorKind::Domain,
+                "url:value" => IndicatorKind::Url,
+                p if p.starts_with("file:hashes") => IndicatorKind::Hash,
+                _ => continue,
+            };
+            if let Some(value) = normalize(kind, &raw) {
+                indicators.push(Indicator {
+                    kind,
+                    value,
+                    feed: feed.name.clone(),
+                    confidence,
+                    source_id: field("id").map(str::to_string),
+                    valid_until,
+                });
+            }
+        }
+    }
+    indicators
+}
+
+/// Returns the `path = 'value'` comparisons of a STIX pattern
+fn stix_comparisons(pattern: &str) -> Vec<(String, String)> {
+    let mut comparisons = Vec::new();
+    let mut rest = pattern;
+    while let Some(op) = rest.find(" = '") {
+        let path = rest[..op]
+            .rsplit(|c: char| c.is_whitespace() || c == '[' || c == '(')
+            .next()
+            .unwrap_or_default()
+            .to_string();
+        let value_start = &rest[op + 4..];
+        let Some(end) = value_start.find('\'') else {
+            break;
+        };
+        comparisons.push((path, value_start[..end].replace("\\'", "'")));
+        rest = &value_start[end + 1..];
+    }
+    comparisons
+}
+
+fn snapshot_path(store_path: &Path, feed: &str) -> PathBuf {
+    let name: String = feed.chars().map(|c| if c.is_ascii_alphanumeric() || c == '-' { c } else { '_' }).collect();
+    store_path.join(format!("{}.json", name))
+}
+
+/// Writes a snapshot through a temporary file so a crash never leaves a partial one
+fn persist_snapshot(store_path: &Path, snapshot: &FeedSnapshot) -> std::io::Result<()> {
+    std::fs::create_dir_all(store_path)?;
+    let path = snapshot_path(store_path, &snapshot.feed);
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, serde_json::to_vec(snapshot)?)?;
+    std::fs::rename(tmp, path)
+}
+
+fn fetch_error(feed: &IntelFeedConfig) -> impl Fn(reqwest::Error) -> GuardianError + '_ {
+    move |e| SecurityError {
+        context: format!("Failed to fetch threat intel feed {}", feed.name),
+        source: Some(Box::new(e)),
+        severity: crate::utils::error::ErrorSeverity::Medium,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: crate::utils::error::ErrorCategory::Security,
+        retry_count: 0,
+    }
+}
+
+/// Metadata attached to a detection raised by an indicator
+pub fn indicator_metadata(indicator: &Indicator) -> HashMap<String, String> {
+    let mut metadata = HashMap::from([
+        ("indicator".to_string(), indicator.value.clone()),
+        ("indicator_kind".to_string(), indicator.kind.label().to_string()),
+        ("intel_feed".to_string(), indicator.feed.clone()),
+    ]);
+    if let Some(source_id) = &indicator.source_id {
+        metadata.insert("stix_id".to_string(), source_id.clone());
+    }
+    metadata
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn feed(format: IntelFeedFormat) -> IntelFeedConfig {
+        IntelFeedConfig {
+            name: "test".into(),
+            url: "https://intel.example/feed".into(),
+            format,
+            token_env: None,
+            confidence: 0.99,
+        }
+    }
+
+    #[test]
+    fn test_feeds_parse_and_match_samples() {
+        let bundle = serde_json::json!({
+            "type": "bundle",
+            "objects": [
+                { "type": "indicator", "id": "indicator--1", "pattern_type": "stix",
+                  "pattern": "[ipv4-addr:value = '203.0.113.7'] OR [url:value = 'http://203.0.113.7/payload']" },
+                { "type": "indicator", "id": "indicator--2", "pattern_type": "stix", "confidence": 80,
+                  "pattern": "[domain-name:value = 'Evil.Example']" },
+                { "type": "indicator", "id": "indicator--3", "revoked": true,
+                  "pattern": "[domain-name:value = 'revoked.example']" }
+            ]
+        });
+        let stix = parse_feed(&feed(IntelFeedFormat::Stix), IntelFeedFormat::Stix, &serde_json::to_vec(&bundle).unwrap()).unwrap();
+        assert_eq!(stix.len(), 3);
+        assert_eq!(stix[2].value, "evil.example");
+        assert_eq!(stix[2].confidence, 0.8);
+
+        let csv = parse_feed(&feed(IntelFeedFormat::Csv), IntelFeedFormat::Csv, b"# bad hashes\nD41D8CD98F00B204E9800998ECF8427E,md5\nnot an indicator\n").unwrap();
+        assert_eq!(csv.len(), 1);
+        assert_eq!(csv[0].kind, IndicatorKind::Hash);
+
+        let intel = ThreatIntel::new(ThreatIntelConfig::default());
+        let mut indicators = stix;
+        indicators.extend(csv);
+        intel.store.write().replace(FeedSnapshot { feed: "test".into(), fetched_at: Utc::now(), indicators });
+
+        let sample = |event: &str| SystemData { metrics: HashMap::new(), events: vec![event.to_string()], timestamp: 0 };
+        let hit = intel.match_sample(&sample("connect dst=203.0.113.7 port=443")).unwrap();
+        assert_eq!(hit.source_id.as_deref(), Some("indicator--1"));
+        assert!(intel.match_sample(&sample("exec sha256=d41d8cd98f00b204e9800998ecf8427e")).is_some());
+        assert!(intel.match_sample(&sample("dns query benign.example")).is_none());
+        assert!(intel.lookup("EVIL.example").is_some());
+    }
+}
@@ -0,0 +1,275 @@
+//! Process isolation on FreeBSD
+//!
+//! A running process cannot be moved into a jail (`jail_attach` only moves the caller) and
+//! Capsicum can only be entered by the process itself, so isolation works with what the host
+//! can impose from outside:
+//!
+//! - the process is frozen with `SIGSTOP`, preserving its memory for forensics;
+//! - a process already running in a jail has that jail locked down with `jail -m`: its
+//!   addresses are removed, new children and raw sockets are refused;
+//! - `rctl` rules on the process deny new descriptors, threads and locked memory,
+//!   so it cannot open files or sockets even if resumed.
+//!
+//! Release restores the jail parameters, removes the rules and resumes the process.
+
+use std::{collections::HashMap, sync::Arc};
+
+use chrono::{DateTime, Utc};
+use metrics::{counter, gauge};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::Serialize;
+use tracing::{info, instrument, warn};
+
+use crate::security::response_actions::run_host_command;
+use crate::utils::error::{GuardianError, SecurityError};
+
+// Constants for process isolation
+const HOST_JID: u32 = 0;
+
+static PROCESS_ISOLATOR: Lazy<Arc<ProcessIsolator>> = Lazy::new(|| Arc::new(ProcessIsolator::new()));
+
+/// How a process was confined
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IsolationMethod {
+    /// The process's jail was locked down in addition to the process rules
+    JailLockdown,
+    /// A host process, confined by resource rules only
+    ResourceRules,
+}
+
+/// Isolation state of a process, as reported back with the response
+#[derive(Debug, Clone, Serialize)]
+pub struct IsolationStatus {
+    pub pid: u32,
+    pub method: IsolationMethod,
+    pub jid: Option<u32>,
+    pub frozen: bool,
+    /// `rctl` rules in force on the process
+    pub rules: Vec<String>,
+    pub reason: String,
+    pub isolated_at: DateTime<Utc>,
+}
+
+/// Jail parameters changed by lockdown, restored on release
+#[derive(Debug, Clone)]
+struct SavedJail {
+    jid: u32,
+    ip4: String,
+    ip6: String,
+    children_max: String,
+    allow_raw_sockets: String,
+}
+
+#[derive(Debug, Clone)]
+struct Isolation {
+    status: IsolationStatus,
+    saved_jail: Option<SavedJail>,
+}
+
+/// Confines processes flagged by the response engine and tracks them until release
+#[derive(Debug, Default)]
+pub struct ProcessIsolator {
+    isolated: Mutex<HashMap<u32, Isolation>>,
+}
+
+/// Returns the process-wide isolator
+pub fn process_isolator() -> Arc<ProcessIsolator> {
+    Arc::clone(&PROCESS_ISOLATOR)
+}
+
+impl ProcessIsolator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Freezes and confines a process; isolating an isolated process returns its current status
+    #[instrument(skip(self))]
+    pub async fn isolate(&self, pid: u32, reason: &str) -> Result<IsolationStatus, GuardianError> {
+        if let Some(existing) = self.isolated.lock().get(&pid) {
+            return Ok(existing.status.clone());
+        }
+
+        // Freeze first so nothing changes while the rest is applied
+        run_host_command("kill", &["-STOP".into(), pid.to_string()]).await?;
+
+        let jid = process_jid(pid).await?;
+        let saved_jail = match jid {
+            HOST_JID => None,
+            jid => {
+                let saved = saved_jail(jid).await?;
+                run_host_command("jail", &lockdown_args(jid)).await?;
+                Some(saved)
+            }
+        };
+
+        let descriptors = open_descriptors(pid).await.unwrap_or_default();
+        let rules = confinement_rules(pid, descriptors);
+        for rule in &rules {
+            if let Err(e) = run_host_command("rctl", &["-a".into(), rule.clone()]).await {
+                // A process frozen in a locked jail is contained even without every limit
+                warn!(pid, rule = %rule, error = %e, "Failed to apply isolation rule");
+            }
+        }
+
+        let status = IsolationStatus {
+            pid,
+            method: if saved_jail.is_some() { IsolationMethod::JailLockdown } else { IsolationMethod::ResourceRules },
+            jid: (jid != HOST_JID).then_some(jid),
+            frozen: true,
+            rules,
+            reason: reason.to_string(),
+            isolated_at: Utc::now(),
+        };
+
+        let mut isolated = self.isolated.lock();
+        isolated.insert(pid, Isolation { status: status.clone(), saved_jail });
+        gauge!("guardian.isolation.active", isolated.len() as f64);
+        counter!("guardian.isolation.isolated", 1, "method" => format!("{:?}", status.method));
+        info!(target: "SECURITY-AUDIT", pid, jid = ?status.jid, reason, "Process isolated");
+        Ok(status)
+    }
+
+    /// Reads back whether a tracked process is still frozen and which rules remain in force
+    pub async fn status(&self, pid: u32) -> Result<Option<IsolationStatus>, GuardianError> {
+        let Some(mut status) = self.isolated.lock().get(&pid).map(|i| i.status.clone()) else {
+            return Ok(None);
+        };
+        let state = run_host_command("ps", &["-o".into(), "state=".into(), "-p".into(), pid.to_string()]).await?;
+        status.frozen = state.trim_start().starts_with('T');
+        let listed = run_host_command("rctl", &[format!("process:{}", pid)]).await.unwrap_or_default();
+        status.rules = listed.lines().map(str::trim).filter(|l| !l.is_empty()).map(str::to_string).collect();
+        Ok(Some(status))
+    }
+
+    /// Lifts isolation: restores the jail, removes the rules and resumes the process
+    #[instrument(skip(self))]
+    pub async fn release(&self, pid: u32) -> Result<(), GuardianError> {
+        let isolation = self.isolated.lock().get(&pid).cloned();
+        if let Some(saved) = isolation.as_ref().and_then(|i| i.saved_jail.as_ref()) {
+            run_host_command("jail", &restore_args(saved)).await?;
+        }
+        // Rules are removed even for untracked processes, e.g. after a restart of Guardian
+        if let Err(e) = run_host_command("rctl", &["-r".into(), format!("process:{}", pid)]).await {
+            warn!(pid, error = %e, "Failed to remove isolation rules");
+        }
+        run_host_command("kill", &["-CONT".into(), pid.to_string()]).await?;
+
+        let mut isolated = self.isolated.lock();
+        isolated.remove(&pid);
+        gauge!("guardian.isolation.active", isolated.len() as f64);
+        info!(target: "SECURITY-AUDIT", pid, "Process isolation released");
+        Ok(())
+    }
+}
+
+/// Rules denying any growth in descriptors, threads or locked memory
+fn confinement_rules(pid: u32, descriptors: u64) -> Vec<String> {
+    vec![
+        format!("process:{}:openfiles:deny={}", pid, descriptors),
+        format!("process:{}:nthr:deny=1", pid),
+        format!("process:{}:memorylocked:deny=0", pid),
+    ]
+}
+
+fn lockdown_args(jid: u32) -> Vec<String> {
+    vec![
+        "-m".into(),
+        format!("jid={}", jid),
+        "ip4.addr=".into(),
+        "ip6.addr=".into(),
+        "children.max=0".into(),
+        "allow.raw_sockets=0".into(),
+    ]
+}
+
+fn restore_args(saved: &SavedJail) -> Vec<String> {
+    vec![
+        "-m".into(),
+        format!("jid={}", saved.jid),
+        format!("ip4.addr={}", saved.ip4),
+        format!("ip6.addr={}", saved.ip6),
+        format!("children.max={}", saved.children_max),
+        format!("allow.raw_sockets={}", saved.allow_raw_sockets),
+    ]
+}
+
+async fn process_jid(pid: u32) -> Result<u32, GuardianError> {
+    let output = run_host_command("ps", &["-o".into(), "jid=".into(), "-p".into(), pid.to_string()]).await?;
+    output
+        .trim()
+        .parse()
+        .map_err(|_| isolation_error(format!("Cannot determine the jail of process {}", pid)))
+}
+
+async fn saved_jail(jid: u32) -> Result<SavedJail, GuardianError> {
+    let output = run_host_command(
+        "jls",
+        &[
+            "-j".into(),
+            jid.to_string(),
+            "-q".into(),
+            "ip4.addr".into(),
+            "ip6.addr".into(),
+            "children.max".into(),
+            "allow.raw_sockets".into(),
+        ],
+    )
+    .await?;
+    parse_jail_params(jid, &output)
+        .ok_or_else(|| isolation_error(format!("Unexpected jls output for jail {}", jid)))
+}
+
+/// Parses `jls -q` output; unset address lists print as `-`
+fn parse_jail_params(jid: u32, output: &str) -> Option<SavedJail> {
+    let fields: Vec<&str> = output.split_whitespace().collect();
+    let address = |value: &str| if value == "-" { String::new() } else { value.to_string() };
+    match fields.as_slice() {
+        [ip4, ip6, children_max, allow_raw_sockets] => Some(SavedJail {
+            jid,
+            ip4: address(ip4),
+            ip6: address(ip6),
+            children_max: children_max.to_string(),
+            allow_raw_sockets: if *allow_raw_sockets == "true" { "1".into() } else { "0".into() },
+        }),
+        _ => None,
+    }
+}
+
+async fn open_descriptors(pid: u32) -> Result<u64, GuardianError> {
+    let output = run_host_command("procstat", &["-h".into(), "-f".into(), pid.to_string()]).await?;
+    Ok(output.lines().filter(|line| !line.trim().is_empty()).count() as u64)
+}
+
+fn isolation_error(context: String) -> GuardianError {
+    SecurityError {
+        context,
+        source: None,
+        severity: crate::utils::error::ErrorSeverity::High,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: crate::utils::error::ErrorCategory::Security,
+        retry_count: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lockdown_round_trips_jail_parameters() {
+        let saved = parse_jail_params(7, "10.0.0.7 - 5 false\n").unwrap();
+        assert_eq!(saved.ip4, "10.0.0.7");
+        assert_eq!(saved.ip6, "");
+        assert_eq!(
+            restore_args(&saved),
+            vec!["-m", "jid=7", "ip4.addr=10.0.0.7", "ip6.addr=", "children.max=5", "allow.raw_sockets=0"]
+        );
+        assert!(lockdown_args(7).contains(&"ip4.addr=".to_string()));
+        assert!(parse_jail_params(7, "garbage").is_none());
+
+        assert_eq!(confinement_rules(4242, 12)[0], "process:4242:openfiles:deny=12");
+    }
+}
@@ -0,0 +1,591 @@
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use async_trait::async_trait;
+use cryptoki::{
+    context::{CInitializeArgs, Pkcs11},
+    mechanism::Mechanism,
+    object::{Attribute, ObjectClass, ObjectHandle},
+    session::{Session, UserType},
+    types::AuthPin,
+};
+use metrics::counter;
+use parking_lot::{Mutex, RwLock};
+use ring::rand::{SecureRandom, SystemRandom};
+use tracing::{error, info, instrument, warn};
+use zeroize::Zeroizing;
+
+use crate::config::security_config::HardwareSecurityConfig;
+use crate::utils::error::{ErrorCategory, ErrorSeverity, GuardianError};
+
+// Constants for key storage
+const KEY_SIZE: usize = 32;
+const KEY_FILE_EXTENSION: &str = "key";
+const STAGED_SUFFIX: &str = "staged";
+const KEY_FILE_MODE: u32 = 0o600;
+
+/// One version of a symmetric key, zeroed when dropped
+pub struct KeyMaterial {
+    pub version: u64,
+    bytes: Zeroizing<Vec<u8>>,
+}
+
+impl KeyMaterial {
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl fmt::Debug for KeyMaterial {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyMaterial").field("version", &self.version).finish_non_exhaustive()
+    }
+}
+
+/// Source of versioned symmetric keys for encryption at rest
+#[async_trait]
+pub trait KeyProvider: Send + Sync + fmt::Debug {
+    /// Short name of the backing store, used in logs and metrics
+    fn kind(&self) -> &'static str;
+
+    /// Returns the current version of a key, creating version 1 on first use
+    async fn current_key(&self, key_id: &str) -> Result<KeyMaterial, GuardianError>;
+
+    /// Returns a specific version of a key, for data encrypted before a rotation
+    async fn key_version(&self, key_id: &str, version: u64) -> Result<KeyMaterial, GuardianError>;
+
+    /// Generates and stores the next version without making it current
+    async fn stage_rotation(&self, key_id: &str) -> Result<KeyMaterial, GuardianError>;
+
+    /// Makes a staged version current
+    async fn commit_rotation(&self, key_id: &str, version: u64) -> Result<(), GuardianError>;
+
+    /// Discards a staged version
+    async fn abort_rotation(&self, key_id: &str, version: u64) -> Result<(), GuardianError>;
+
+    async fn health_check(&self) -> Result<(), GuardianError>;
+}
+
+/// Builds the key provider selected by the hardware security configuration
+pub fn key_provider_from_config(config: &HardwareSecurityConfig) -> Result<Arc<dyn KeyProvider>, GuardianError> {
+    let key_dir = PathBuf::from(&config.key_store.key_dir);
+    match (&config.key_store.pkcs11_module, config.hsm_enabled) {
+        (Some(module), true) => {
+            let pin = std::env::var(&config.key_store.pin_env)
+                .map_err(|e| key_error(format!("HSM PIN variable {} is not set", config.key_store.pin_env), Some(Box::new(e))))?;
+            let provider = Pkcs11KeyProvider::open(
+                Path::new(module),
+                &config.hsm_token_label,
+                &pin,
+                &config.key_store.kek_label,
+                key_dir,
+            )?;
+            Ok(Arc::new(provider))
+        }
+        _ => Ok(Arc::new(FileKeyProvider::new(key_dir)?)),
+    }
+}
+
+/// Versioned key files named `<key_id>.v<version>.key`, with staged versions suffixed until committed
+#[derive(Debug)]
+struct KeyDirectory {
+    dir: PathBuf,
+}
+
+impl KeyDirectory {
+    fn open(dir: PathBuf) -> Result<Self, GuardianError> {
+        std::fs::create_dir_all(&dir)
+            .map_err(|e| key_error(format!("Failed to create key directory {}", dir.display()), Some(Box::new(e))))?;
+        Ok(Self { dir })
+    }
+
+    fn path(&self, key_id: &str, version: u64, staged: bool) -> PathBuf {
+        let name = format!("{}.v{}.{}", key_id, version, KEY_FILE_EXTENSION);
+        if staged {
+            self.dir.join(format!("{}.{}", name, STAGED_SUFFIX))
+        } else {
+            self.dir.join(name)
+        }
+    }
+
+    /// Highest committed version of a key
+    fn latest(&self, key_id: &str) -> Result<Option<u64>, GuardianError> {
+        let entries = std::fs::read_dir(&self.dir)
+            .map_err(|e| key_error(format!("Failed to list key directory {}", self.dir.display()), Some(Box::new(e))))?;
+        let prefix = format!("{}.v", key_id);
+        let suffix = format!(".{}", KEY_FILE_EXTENSION);
+
+        Ok(entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let name = entry.file_name().into_string().ok()?;
+                name.strip_prefix(&prefix)?.strip_suffix(&suffix)?.parse::<u64>().ok()
+            })
+            .max())
+    }
+
+    fn read(&self, key_id: &str, version: u64) -> Result<Zeroizing<Vec<u8>>, GuardianError> {
+        let path = self.path(key_id, version, false);
+        std::fs::read(&path)
+            .map(Zeroizing::new)
+            .map_err(|e| key_error(format!("Failed to read key {}", path.display()), Some(Box::new(e))))
+    }
+
+    fn write(&self, key_id: &str, version: u64, stored: &[u8], staged: bool) -> Result<(), GuardianError> {
+        use std::io::Write;
+        use std::os::unix::fs::OpenOptionsExt;
+
+        let path = self.path(key_id, version, staged);
+        std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .mode(KEY_FILE_MODE)
+            .open(&path)
+            .and_then(|mut file| {
+                file.write_all(stored)?;
+                file.sync_all()
+            })
+            .map_err(|e| key_error(format!("Failed to write key {}", path.display()), Some(Box::new(e))))
+    }
+
+    fn commit(&self, key_id: &str, version: u64) -> Result<(), GuardianError> {
+        let staged = self.path(key_id, version, true);
+        std::fs::rename(&staged, self.path(key_id, version, false))
+            .map_err(|e| key_error(format!("Failed to commit key {}", staged.display()), Some(Box::new(e))))
+    }
+
+    fn discard(&self, key_id: &str, version: u64) -> Result<(), GuardianError> {
+        let staged = self.path(key_id, version, true);
+        match std::fs::remove_file(&staged) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(key_error(format!("Failed to discard key {}", staged.display()), Some(Box::new(e))))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+fn generate_key() -> Result<Zeroizing<Vec<u8>>, GuardianError> {
+    let mut key = Zeroizing::new(vec![0u8; KEY_SIZE]);
+    SystemRandom::new()
+        .fill(&mut key)
+        .map_err(|_| key_error("Failed to generate key material".into(), None))?;
+    Ok(key)
+}
+
+/// Keys stored as raw files readable only by the Guardian user
+#[derive(Debug)]
+pub struct FileKeyProvider {
+    keys: KeyDirectory,
+}
+
+impl FileKeyProvider {
+    pub fn new(dir: PathBuf) -> Result<Self, GuardianError> {
+        Ok(Self {
+            keys: KeyDirectory::open(dir)?,
+        })
+    }
+}
+
+#[async_trait]
+impl KeyProvider for FileKeyProvider {
+    fn kind(&self) -> &'static str {
+        "file"
+    }
+
+    async fn current_key(&self, key_id: &str) -> Result<KeyMaterial, GuardianError> {
+        match self.keys.latest(key_id)? {
+            Some(version) => self.key_version(key_id, version).await,
+            None => {
+                let bytes = generate_key()?;
+                self.keys.write(key_id, 1, &bytes, false)?;
+                info!(key_id, "Created key");
+                Ok(KeyMaterial { version: 1, bytes })
+            }
+        }
+    }
+
+    async fn key_version(&self, key_id: &str, version: u64) -> Result<KeyMaterial, GuardianError> {
+        Ok(KeyMaterial {
+            version,
+            bytes: self.keys.read(key_id, version)?,
+        })
+    }
+
+    async fn stage_rotation(&self, key_id: &str) -> Result<KeyMaterial, GuardianError> {
+        let version = self.keys.latest(key_id)?.unwrap_or(0) + 1;
+        let bytes = generate_key()?;
+        self.keys.write(key_id, version, &bytes, true)?;
+        Ok(KeyMaterial { version, bytes })
+    }
+
+    async fn commit_rotation(&self, key_id: &str, version: u64) -> Result<(), GuardianError> {
+        self.keys.commit(key_id, version)
+    }
+
+    async fn abort_rotation(&self, key_id: &str, version: u64) -> Result<(), GuardianError> {
+        self.keys.discard(key_id, version)
+    }
+
+    async fn health_check(&self) -> Result<(), GuardianError> {
+        self.keys.latest("health").map(|_| ())
+    }
+}
+
+/// Keys wrapped by a key-encryption key that never leaves a PKCS#11 token.
+/// Only wrapped keys touch the disk; unwrapping happens inside the HSM.
+pub struct Pkcs11KeyProvider {
+    keys: KeyDirectory,
+    session: Arc<Mutex<Session>>,
+    kek: ObjectHandle,
+    token_label: String,
+}
+
+impl fmt::Debug for Pkcs11KeyProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Pkcs11KeyProvider")
+            .field("keys", &self.keys)
+            .field("token_label", &self.token_label)
+            .finish()
+    }
+}
+
+impl Pkcs11KeyProvider {
+    /// Logs into the token and locates the key-encryption key, creating it on first use
+    pub fn open(module: &Path, token_label: &str, pin: &str, kek_label: &str, dir: PathBuf) -> Result<Self, GuardianError> {
+        let hsm = |context: &str| {
+            let context = context.to_string();
+            move |e: cryptoki::error::Error| key_error(context, Some(Box::new(e)))
+        };
+
+        let pkcs11 = Pkcs11::new(module).map_err(hsm("Failed to load PKCS#11 module"))?;
+        pkcs11.initialize(CInitializeArgs::OsThreads).map_err(hsm("Failed to initialize PKCS#11 module"))?;
+
+        let slot = pkcs11
+            .get_slots_with_token()
+            .map_err(hsm("Failed to list HSM slots"))?
+            .into_iter()
+            .find(|slot| {
+                pkcs11
+                    .get_token_info(*slot)
+                    .map(|info| info.label().trim_end() == token_label)
+                    .unwrap_or(false)
+            })
+            .ok_or_else(|| key_error(format!("HSM token {} not found", token_label), None))?;
+
+        let session = pkcs11.open_rw_session(slot).map_err(hsm("Failed to open HSM session"))?;
+        session
+            .login(UserType::User, Some(&AuthPin::new(pin.to_string())))
+            .map_err(hsm("HSM login failed"))?;
+
+        let label = Attribute::Label(kek_label.as_bytes().to_vec());
+        let kek = match session
+            .find_objects(&[Attribute::Class(ObjectClass::SECRET_KEY), label.clone()])
+            .map_err(hsm("Failed to look up key-encryption key"))?
+            .first()
+        {
+            Some(kek) => *kek,
+            None => {
+                info!(token = token_label, label = kek_label, "Generating key-encryption key in HSM");
+                session
+                    .generate_key(
+                        &Mechanism::AesKeyGen,
+                        &[
+                            label,
+                            Attribute::Token(true),
+                            Attribute::Private(true),
+                            Attribute::Sensitive(true),
+                            Attribute::Extractable(false),
+                            Attribute::Encrypt(true),
+                            Attribute::Decrypt(true),
+                            Attribute::ValueLen((KEY_SIZE as u64).into()),
+                        ],
+                    )
+                    .map_err(hsm("Failed to generate key-encryption key"))?
+            }
+        };
+
+        Ok(Self {
+            keys: KeyDirectory::open(dir)?,
+            session: Arc::new(Mutex::new(session)),
+            kek,
+            token_label: token_label.to_string(),
+        })
+    }
+
+    /// Runs a blocking HSM call with the key-encryption key off the async runtime
+    async fn with_kek<T, F>(&self, operation: &'static str, f: F) -> Result<T, GuardianError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Session, ObjectHandle) -> cryptoki::error::Result<T> + Send + 'static,
+    {
+        let session = Arc::clone(&self.session);
+        let kek = self.kek;
+        let result = tokio::task::spawn_blocking(move || f(&session.lock(), kek))
+            .await
+            .map_err(|e| key_error(format!("HSM {} task failed", operation), Some(Box::new(e))))?;
+        result.map_err(|e| {
+            counter!("guardian.keys.hsm_errors", 1, "operation" => operation);
+            key_error(format!("HSM {} failed", operation), Some(Box::new(e)))
+        })
+    }
+
+    async fn wrap(&self, key: &[u8]) -> Result<Vec<u8>, GuardianError> {
+        let key = key.to_vec();
+        self.with_kek("wrap", move |session, kek| session.encrypt(&Mechanism::AesKeyWrap, kek, &key))
+            .await
+    }
+
+    async fn unwrap(&self, wrapped: Vec<u8>) -> Result<Zeroizing<Vec<u8>>, GuardianError> {
+        self.with_kek("unwrap", move |session, kek| session.decrypt(&Mechanism::AesKeyWrap, kek, &wrapped))
+            .await
+            .map(Zeroizing::new)
+    }
+}
+
+#[async_trait]
+impl KeyProvider for Pkcs11KeyProvider {
+    fn kind(&self) -> &'static str {
+        "pkcs11"
+    }
+
+    async fn current_key(&self, key_id: &str) -> Result<KeyMaterial, GuardianError> {
+        match self.keys.latest(key_id)? {
+            Some(version) => self.key_version(key_id, version).await,
+            None => {
+                let bytes = generate_key()?;
+                self.keys.write(key_id, 1, &self.wrap(&bytes).await?, false)?;
+                info!(key_id, token = %self.token_label, "Created HSM-wrapped key");
+                Ok(KeyMaterial { version: 1, bytes })
+            }
+        }
+    }
+
+    async fn key_version(&self, key_id: &str, version: u64) -> Result<KeyMaterial, GuardianError> {
+        let wrapped = self.keys.read(key_id, version)?;
+        Ok(KeyMaterial {
+            version,
+            bytes: self.unwrap(wrapped.to_vec()).await?,
+        })
+    }
+
+    async fn stage_rotation(&self, key_id: &str) -> Result<KeyMaterial, GuardianError> {
+        let version = self.keys.latest(key_id)?.unwrap_or(0) + 1;
+        let bytes = generate_key()?;
+        self.keys.write(key_id, version, &self.wrap(&bytes).await?, true)?;
+        Ok(KeyMaterial { version, bytes })
+    }
+
+    async fn commit_rotation(&self, key_id: &str, version: u64) -> Result<(), GuardianError> {
+        self.keys.commit(key_id, version)
+    }
+
+    async fn abort_rotation(&self, key_id: &str, version: u64) -> Result<(), GuardianError> {
+        self.keys.discard(key_id, version)
+    }
+
+    async fn health_check(&self) -> Result<(), GuardianError> {
+        self.with_kek("health check", |session, _| session.get_session_info().map(|_| ())).await
+    }
+}
+
+/// Component that holds a copy of a key and must switch versions in step with its peers
+#[async_trait]
+pub trait KeyRotationParticipant: Send + Sync {
+    fn name(&self) -> &str;
+
+    /// Returns the keys this component holds a copy of
+    async fn key_ids(&self) -> Vec<String>;
+
+    /// Switches to the given key version
+    async fn apply_key(&self, key_id: &str, key: &KeyMaterial) -> Result<(), GuardianError>;
+
+    /// Stops accepting a version replaced by a rotation once its overlap window has passed.
+    /// Components that hold only the current version have nothing to do.
+    async fn retire_key(&self, _key_id: &str, _version: u64) -> Result<(), GuardianError> {
+        Ok(())
+    }
+}
+
+/// Rotates a key across every component that uses it, so either all switch or none do
+pub struct KeyRotationCoordinator {
+    provider: Arc<dyn KeyProvider>,
+    participants: RwLock<Vec<Arc<dyn KeyRotationParticipant>>>,
+    rotation: tokio::sync::Mutex<()>,
+}
+
+impl fmt::Debug for KeyRotationCoordinator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyRotationCoordinator")
+            .field("provider", &self.provider.kind())
+            .field("participants", &self.participants.read().iter().map(|p| p.name().to_string()).collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl KeyRotationCoordinator {
+    pub fn new(provider: Arc<dyn KeyProvider>) -> Self {
+        Self {
+            provider,
+            participants: RwLock::new(Vec::new()),
+            rotation: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    /// Returns the provider keys are loaded from
+    pub fn provider(&self) -> Arc<dyn KeyProvider> {
+        Arc::clone(&self.provider)
+    }
+
+    /// Adds a component to future rotations
+    pub fn register(&self, participant: Arc<dyn KeyRotationParticipant>) {
+        self.participants.write().push(participant);
+    }
+
+    /// Returns every key held by a registered component
+    pub async fn key_ids(&self) -> Vec<String> {
+        let mut key_ids = Vec::new();
+        for participant in self.participants() {
+            key_ids.extend(participant.key_ids().await);
+        }
+        key_ids.sort();
+        key_ids.dedup();
+        key_ids
+    }
+
+    fn participants(&self) -> Vec<Arc<dyn KeyRotationParticipant>> {
+        self.participants.read().clone()
+    }
+
+    /// Stages a new key version, applies it to every participant using the key and commits it.
+    /// Participants that already switched are moved back if a later one fails.
+    #[instrument(skip(self))]
+    pub async fn rotate(&self, key_id: &str) -> Result<u64, GuardianError> {
+        let _rotation = self.rotation.lock().await;
+        let mut participants = Vec::new();
+        for participant in self.participants() {
+            if participant.key_ids().await.iter().any(|id| id == key_id) {
+                participants.push(participant);
+            }
+        }
+
+        let previous = self.provider.current_key(key_id).await?;
+        let next = self.provider.stage_rotation(key_id).await?;
+
+        for (index, participant) in participants.iter().enumerate() {
+            if let Err(e) = participant.apply_key(key_id, &next).await {
+                warn!(key_id, participant = participant.name(), error = %e, "Key rotation failed, rolling back");
+                for applied in participants[..index].iter().rev() {
+                    if let Err(rollback) = applied.apply_key(key_id, &previous).await {
+                        error!(key_id, participant = applied.name(), error = %rollback, "Key rollback failed");
+                    }
+                }
+                self.provider.abort_rotation(key_id, next.version).await?;
+                counter!("guardian.keys.rotation_failures", 1, "provider" => self.provider.kind());
+                return Err(e);
+            }
+        }
+
+        self.provider.commit_rotation(key_id, next.version).await?;
+        counter!("guardian.keys.rotations", 1, "provider" => self.provider.kind());
+        info!(key_id, version = next.version, participants = participants.len(), "Key rotated");
+        Ok(next.version)
+    }
+
+    /// Retires a superseded version from every participant holding the key; a failing participant
+    /// does not stop the others
+    #[instrument(skip(self))]
+    pub async fn retire(&self, key_id: &str, version: u64) {
+        for participant in self.participants() {
+            if !participant.key_ids().await.iter().any(|id| id == key_id) {
+                continue;
+            }
+            if let Err(e) = participant.retire_key(key_id, version).await {
+                counter!("guardian.keys.retirement_failures", 1, "participant" => participant.name().to_string());
+                warn!(key_id, version, participant = participant.name(), error = %e, "Key retirement failed");
+            }
+        }
+        info!(key_id, version, "Key version retired");
+    }
+}
+
+fn key_error(context: String, source: Option<Box<dyn std::error::Error + Send + Sync>>) -> GuardianError {
+    GuardianError::SecurityError {
+        context,
+        source,
+        severity: ErrorSeverity::High,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: ErrorCategory::Security,
+        retry_count: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    struct Recorder {
+        name: &'static str,
+        version: AtomicU64,
+        fail_on: Option<u64>,
+    }
+
+    #[async_trait]
+    impl KeyRotationParticipant for Recorder {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        async fn key_ids(&self) -> Vec<String> {
+            vec!["storage".to_string()]
+        }
+
+        async fn apply_key(&self, _key_id: &str, key: &KeyMaterial) -> Result<(), GuardianError> {
+            if self.fail_on == Some(key.version) {
+                return Err(key_error("rejected".into(), None));
+            }
+            self.version.store(key.version, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    fn recorder(name: &'static str, fail_on: Option<u64>) -> Arc<Recorder> {
+        Arc::new(Recorder { name, version: AtomicU64::new(1), fail_on })
+    }
+
+    #[tokio::test]
+    async fn test_file_provider_versions_and_staging() {
+        let dir = tempfile::tempdir().unwrap();
+        let provider = FileKeyProvider::new(dir.path().to_path_buf()).unwrap();
+
+        let first = provider.current_key("storage").await.unwrap();
+        assert_eq!((first.version, first.bytes().len()), (1, KEY_SIZE));
+
+        let staged = provider.stage_rotation("storage").await.unwrap();
+        assert_eq!(provider.current_key("storage").await.unwrap().version, 1);
+        provider.commit_rotation("storage", staged.version).await.unwrap();
+        let current = provider.current_key("storage").await.unwrap();
+        assert_eq!(current.version, 2);
+        assert_eq!(current.bytes(), staged.bytes());
+        assert_eq!(provider.key_version("storage", 1).await.unwrap().bytes(), first.bytes());
+    }
+
+    #[tokio::test]
+    async fn test_rotation_rolls_back_when_a_participant_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let coordinator = KeyRotationCoordinator::new(Arc::new(FileKeyProvider::new(dir.path().to_path_buf()).unwrap()));
+        let zfs = recorder("zfs", None);
+        let crypto = recorder("crypto", Some(2));
+        coordinator.register(zfs.clone());
+        coordinator.register(crypto.clone());
+
+        assert!(coordinator.rotate("storage").await.is_err());
+        assert_eq!(zfs.version.load(Ordering::SeqCst), 1);
+        assert_eq!(coordinator.provider().current_key("storage").await.unwrap().version, 1);
+    }
+}
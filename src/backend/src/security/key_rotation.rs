@@ -0,0 +1,286 @@
+//! Scheduled rotation of every key held by a rotation participant
+//!
+//! Each key is rotated once per interval through the `KeyRotationCoordinator`, so the ZFS wrapping
+//! key and crypto manager keys switch together. The version a rotation replaces stays valid for an
+//! overlap window, letting operations that started with it finish, and is then retired from every
+//! participant. A key is not rotated again while its previous version is inside the window, so at
+//! most two versions of a key are live at once.
+
+use std::{collections::BTreeMap, sync::Arc, time::Duration};
+
+use chrono::{DateTime, Utc};
+use metrics::gauge;
+use parking_lot::{Mutex, RwLock};
+use serde::Serialize;
+use tracing::{info, instrument, warn};
+
+use crate::config::security_config::EncryptionConfig;
+use crate::security::audit::{AuditEvent, AuditLogger, SecurityLevel};
+use crate::security::key_provider::KeyRotationCoordinator;
+
+pub const KEY_ROTATION_EVENT_TYPE: &str = "key_rotation";
+pub const KEY_RETIREMENT_EVENT_TYPE: &str = "key_retirement";
+/// How often due rotations and expired overlap windows are looked for
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+/// Delay before a failed rotation is tried again
+const RETRY_DELAY: Duration = Duration::from_secs(15 * 60);
+
+/// Rotation state of one key
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct KeySchedule {
+    pub key_id: String,
+    /// Current version, as of the last rotation the scheduler saw
+    pub version: u64,
+    /// Absent until the scheduler has rotated the key since startup
+    pub last_rotation: Option<DateTime<Utc>>,
+    pub next_rotation: DateTime<Utc>,
+    /// Replaced version still accepted until `retire_at`
+    pub retiring_version: Option<u64>,
+    pub retire_at: Option<DateTime<Utc>>,
+    /// Consecutive failed rotations
+    pub failures: u32,
+}
+
+/// Rotates keys on schedule and retires replaced versions after their overlap window
+pub struct KeyRotationScheduler {
+    coordinator: Arc<KeyRotationCoordinator>,
+    interval: Duration,
+    overlap: Duration,
+    schedules: Mutex<BTreeMap<String, KeySchedule>>,
+    logger: RwLock<Option<Arc<AuditLogger>>>,
+}
+
+impl std::fmt::Debug for KeyRotationScheduler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KeyRotationScheduler")
+            .field("interval", &self.interval)
+            .field("overlap", &self.overlap)
+            .field("keys", &self.schedules.lock().len())
+            .finish()
+    }
+}
+
+impl KeyRotationScheduler {
+    pub fn new(coordinator: Arc<KeyRotationCoordinator>, interval: Duration, overlap: Duration) -> Self {
+        Self {
+            coordinator,
+            interval,
+            overlap,
+            schedules: Mutex::new(BTreeMap::new()),
+            logger: RwLock::new(None),
+        }
+    }
+
+    pub fn from_config(coordinator: Arc<KeyRotationCoordinator>, config: &EncryptionConfig) -> Self {
+        Self::new(coordinator, config.key_rotation_interval, config.key_rotation_overlap)
+    }
+
+    /// Also writes rotations to the audit trail; without a logger they go to the SECURITY-AUDIT log target
+    pub fn attach_logger(&self, logger: Arc<AuditLogger>) {
+        *self.logger.write() = Some(logger);
+    }
+
+    /// Returns the rotation state of every key seen so far, ordered by key id
+    pub fn schedule(&self) -> Vec<KeySchedule> {
+        self.schedules.lock().values().cloned().collect()
+    }
+
+    /// Checks for due rotations in the background until the task is aborted
+    pub fn start(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(CHECK_INTERVAL);
+            loop {
+                interval.tick().await;
+                self.run_once(Utc::now()).await;
+            }
+        })
+    }
+
+    /// Retires versions whose overlap window ended and rotates keys that are due; returns the
+    /// number of keys rotated
+    #[instrument(skip(self))]
+    pub async fn run_once(&self, now: DateTime<Utc>) -> usize {
+        self.discover_keys(now).await;
+
+        let mut rotated = 0;
+        let schedules: Vec<KeySchedule> = self.schedules.lock().values().cloned().collect();
+        for mut schedule in schedules {
+            if let (Some(version), Some(retire_at)) = (schedule.retiring_version, schedule.retire_at) {
+                if retire_at <= now {
+                    self.coordinator.retire(&schedule.key_id, version).await;
+                    self.audit_retirement(&schedule.key_id, version).await;
+                    schedule.retiring_version = None;
+                    schedule.retire_at = None;
+                }
+            }
+
+            if schedule.next_rotation <= now && schedule.retiring_version.is_none() {
+                match self.coordinator.rotate(&schedule.key_id).await {
+                    Ok(version) => {
+                        self.audit_rotation(&schedule.key_id, schedule.version, Some(version), None).await;
+                        schedule.retiring_version = Some(schedule.version);
+                        schedule.retire_at = Some(after(now, self.overlap));
+                        schedule.version = version;
+                        schedule.last_rotation = Some(now);
+                        schedule.next_rotation = after(now, self.interval);
+                        schedule.failures = 0;
+                        rotated += 1;
+                    }
+                    Err(e) => {
+                        warn!(key_id = %schedule.key_id, error = %e, "Scheduled key rotation failed");
+                        self.audit_rotation(&schedule.key_id, schedule.version, None, Some(e.to_string())).await;
+                        schedule.next_rotation = after(now, RETRY_DELAY);
+                        schedule.failures += 1;
+                    }
+                }
+            }
+
+            publish_gauges(&schedule);
+            self.schedules.lock().insert(schedule.key_id.clone(), schedule);
+        }
+        rotated
+    }
+
+    /// Schedules keys that participants started holding since the last check, one interval out
+    async fn discover_keys(&self, now: DateTime<Utc>) {
+        let provider = self.coordinator.provider();
+        for key_id in self.coordinator.key_ids().await {
+            if self.schedules.lock().contains_key(&key_id) {
+                continue;
+            }
+            let version = match provider.current_key(&key_id).await {
+                Ok(key) => key.version,
+                Err(e) => {
+                    warn!(key_id = %key_id, error = %e, "Cannot schedule rotation of key");
+                    continue;
+                }
+            };
+            info!(key_id = %key_id, version, interval = ?self.interval, "Scheduled key rotation");
+            self.schedules.lock().insert(
+                key_id.clone(),
+                KeySchedule {
+                    key_id,
+                    version,
+                    last_rotation: None,
+                    next_rotation: after(now, self.interval),
+                    retiring_version: None,
+                    retire_at: None,
+                    failures: 0,
+                },
+            );
+        }
+    }
+
+    async fn audit_rotation(&self, key_id: &str, old_version: u64, new_version: Option<u64>, error: Option<String>) {
+        let severity = if error.is_some() { SecurityLevel::High } else { SecurityLevel::Medium };
+        let data = serde_json::json!({
+            "key_id": key_id,
+            "old_version": old_version,
+            "new_version": new_version,
+            "overlap_secs": self.overlap.as_secs(),
+            "error": error,
+        });
+        self.audit(KEY_ROTATION_EVENT_TYPE, severity, data).await;
+    }
+
+    async fn audit_retirement(&self, key_id: &str, version: u64) {
+        let data = serde_json::json!({ "key_id": key_id, "version": version });
+        self.audit(KEY_RETIREMENT_EVENT_TYPE, SecurityLevel::Low, data).await;
+    }
+
+    async fn audit(&self, event_type: &str, severity: SecurityLevel, data: serde_json::Value) {
+        let event = AuditEvent::new(
+            event_type.to_string(),
+            severity,
+            "key_rotation_scheduler".to_string(),
+            Some(crate::utils::correlation::current_or_new().to_string()),
+        );
+        let event = event.clone().with_data(data).unwrap_or(event);
+
+        match self.logger.read().clone() {
+            Some(logger) => {
+                if let Err(e) = logger.record_event(event).await {
+                    warn!(error = %e, "Failed to write key rotation audit event");
+                }
+            }
+            None => info!(target: "SECURITY-AUDIT", event_id = %event.id(), event_type, data = %event.data(), "Key rotation"),
+        }
+    }
+}
+
+fn publish_gauges(schedule: &KeySchedule) {
+    let key_id = schedule.key_id.clone();
+    gauge!("guardian.keys.next_rotation_timestamp", schedule.next_rotation.timestamp() as f64, "key_id" => key_id.clone());
+    gauge!("guardian.keys.version", schedule.version as f64, "key_id" => key_id.clone());
+    if let Some(last) = schedule.last_rotation {
+        gauge!("guardian.keys.last_rotation_timestamp", last.timestamp() as f64, "key_id" => key_id);
+    }
+}
+
+fn after(now: DateTime<Utc>, delay: Duration) -> DateTime<Utc> {
+    chrono::Duration::from_std(delay)
+        .ok()
+        .and_then(|delay| now.checked_add_signed(delay))
+        .unwrap_or(DateTime::<Utc>::MAX_UTC)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::key_provider::{FileKeyProvider, KeyMaterial, KeyRotationParticipant};
+    use crate::utils::error::GuardianError;
+    use async_trait::async_trait;
+
+    #[derive(Default)]
+    struct Holder {
+        applied: Mutex<Vec<u64>>,
+        retired: Mutex<Vec<u64>>,
+    }
+
+    #[async_trait]
+    impl KeyRotationParticipant for Holder {
+        fn name(&self) -> &str {
+            "holder"
+        }
+
+        async fn key_ids(&self) -> Vec<String> {
+            vec!["storage".to_string()]
+        }
+
+        async fn apply_key(&self, _key_id: &str, key: &KeyMaterial) -> Result<(), GuardianError> {
+            self.applied.lock().push(key.version);
+            Ok(())
+        }
+
+        async fn retire_key(&self, _key_id: &str, version: u64) -> Result<(), GuardianError> {
+            self.retired.lock().push(version);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rotation_waits_for_overlap_before_retiring() {
+        let dir = tempfile::tempdir().unwrap();
+        let coordinator = Arc::new(KeyRotationCoordinator::new(Arc::new(FileKeyProvider::new(dir.path().to_path_buf()).unwrap())));
+        let holder = Arc::new(Holder::default());
+        coordinator.register(holder.clone());
+        let scheduler = KeyRotationScheduler::new(coordinator, Duration::from_secs(3600), Duration::from_secs(600));
+
+        let start = Utc::now();
+        assert_eq!(scheduler.run_once(start).await, 0);
+        assert_eq!(scheduler.schedule()[0].next_rotation, after(start, Duration::from_secs(3600)));
+
+        let due = after(start, Duration::from_secs(3600));
+        assert_eq!(scheduler.run_once(due).await, 1);
+        let schedule = scheduler.schedule().remove(0);
+        assert_eq!((schedule.version, schedule.retiring_version), (2, Some(1)));
+        assert_eq!(*holder.applied.lock(), vec![2]);
+
+        // The replaced version stays valid until the overlap window ends
+        scheduler.run_once(after(due, Duration::from_secs(300))).await;
+        assert!(holder.retired.lock().is_empty());
+        scheduler.run_once(after(due, Duration::from_secs(600))).await;
+        assert_eq!(*holder.retired.lock(), vec![1]);
+        assert_eq!(scheduler.schedule()[0].retiring_version, None);
+    }
+}
@@ -1,23 +1,72 @@
+use std::path::PathBuf;
 use std::sync::Arc;
+use once_cell::sync::OnceCell;
 use tokio::sync::RwLock;
 use tracing::{debug, error, info, instrument, warn};
+use uuid::Uuid;
 
+use crate::core::guardian::TenantContext;
+use crate::core::operations::OperationRegistry;
+use crate::core::status::{ComponentState, ComponentStatus, StatusSource, SECURITY};
 use crate::utils::error::{GuardianError, SecurityError, ConfigError};
 use crate::utils::metrics::Metrics;
 use crate::config::security_config::SecurityConfig;
+use crate::config::secrets::ConfigSecrets;
+use crate::config::DEFAULT_CONFIG_PATH;
 
 // Version and performance constants
 const SECURITY_VERSION: &str = "1.0.0";
-const MAX_DETECTION_TIME_MS: u64 = 100;
+pub const MAX_DETECTION_TIME_MS: u64 = 100;
 const SECURITY_METRICS_INTERVAL_MS: u64 = 1000;
 const CIRCUIT_BREAKER_THRESHOLD: u32 = 3;
+const KEY_ROTATION_OPERATION: &str = "key_rotation";
+
+static SECURITY_MANAGER: OnceCell<Arc<SecurityManager>> = OnceCell::new();
+
+/// Returns the security manager, once it has been created
+pub fn security_manager() -> Option<Arc<SecurityManager>> {
+    SECURITY_MANAGER.get().cloned()
+}
 
 // Re-export security submodules
+pub mod anomaly_detection;
+pub mod anomaly_feedback;
+pub mod content_pack;
+pub mod content_simulation;
 pub mod crypto;
+pub mod degradation;
+pub mod attestation;
 pub mod audit;
+pub mod audit_chain;
+pub mod audit_sampling;
+pub mod auth;
+pub mod command_audit;
+pub mod detection_pipeline;
+pub mod detection_rules;
+pub mod firewall;
+pub mod incident;
+pub mod incident_summary;
+pub mod intel;
+pub mod isolation;
+pub mod key_provider;
+pub mod key_rotation;
+pub mod model_signing;
+pub mod threat_analytics;
 pub mod threat_detection;
+pub mod offline_executor;
+pub mod pipeline_latency;
+pub mod policy_test;
+pub mod process_trust;
+pub mod rbac;
+pub mod remote_assistance;
+pub mod response_actions;
+pub mod response_engine;
+pub mod response_guardrails;
+pub mod siem_export;
 
 use crypto::CryptoManager;
+use key_provider::KeyRotationCoordinator;
+use key_rotation::KeyRotationScheduler;
 use audit::AuditManager;
 use threat_detection::ThreatDetector;
 
@@ -27,6 +76,8 @@ pub struct SecurityManager {
     crypto_manager: Arc<CryptoManager>,
     audit_manager: Arc<AuditManager>,
     threat_detector: Arc<ThreatDetector>,
+    key_rotation: Arc<KeyRotationCoordinator>,
+    key_rotation_scheduler: Arc<KeyRotationScheduler>,
     config: SecurityConfig,
     metrics: Arc<Metrics>,
     performance_monitor: Arc<RwLock<PerformanceMonitor>>,
@@ -40,24 +91,36 @@ struct PerformanceMonitor {
 }
 
 impl SecurityManager {
-    /// Creates a new SecurityManager instance with performance monitoring
-    #[instrument(skip(config, metrics))]
-    pub fn new(config: SecurityConfig, metrics: Arc<Metrics>) -> Result<Arc<Self>, GuardianError> {
+    /// Creates a new SecurityManager instance with performance monitoring, detecting threats for `tenant`
+    #[instrument(skip(config, metrics, tenant), fields(tenant = %tenant.tenant_id()))]
+    pub fn new(config: SecurityConfig, metrics: Arc<Metrics>, tenant: TenantContext) -> Result<Arc<Self>, GuardianError> {
         // Validate security configuration with performance limits
         config.validate().map_err(|e| GuardianError::ConfigError {
             context: "Failed to validate security configuration".into(),
             source: Some(Box::new(e)),
             severity: crate::utils::error::ErrorSeverity::Critical,
             timestamp: time::OffsetDateTime::now_utc(),
-            correlation_id: uuid::Uuid::new_v4(),
+            correlation_id: crate::utils::correlation::current_or_new(),
             category: crate::utils::error::ErrorCategory::Security,
             retry_count: 0,
         })?;
 
         // Initialize core security components
-        let crypto_manager = CryptoManager::new(&config)?;
+        let provider = key_provider::key_provider_from_config(&config.hw_security_config)?;
+        let key_rotation = Arc::new(KeyRotationCoordinator::new(Arc::clone(&provider)));
+        // Encrypted config values are re-wrapped whenever the config KEK rotates
+        let config_secrets = Arc::new(ConfigSecrets::new(provider));
+        key_rotation.register(config_secrets.rotation_participant(PathBuf::from(DEFAULT_CONFIG_PATH)));
+        let crypto_manager = CryptoManager::new(&config)?.with_key_rotation(Arc::clone(&key_rotation));
+        let key_rotation_scheduler = Arc::new(KeyRotationScheduler::from_config(
+            Arc::clone(&key_rotation),
+            &config.encryption_config,
+        ));
         let audit_manager = AuditManager::new(&config)?;
-        let threat_detector = ThreatDetector::new(&config)?;
+        let mut threat_detector = ThreatDetector::new(&config)?.with_tenant(tenant);
+        if let Some(pipeline) = &config.detection_pipeline {
+            threat_detector = threat_detector.with_pipeline_config(pipeline)?;
+        }
 
         let performance_monitor = Arc::new(RwLock::new(PerformanceMonitor {
             detection_times: Vec::with_capacity(1000),
@@ -69,6 +132,8 @@ impl SecurityManager {
             crypto_manager: Arc::new(crypto_manager),
             audit_manager: Arc::new(audit_manager),
             threat_detector: Arc::new(threat_detector),
+            key_rotation,
+            key_rotation_scheduler,
             config,
             metrics,
             performance_monitor,
@@ -76,11 +141,38 @@ impl SecurityManager {
 
         // Start performance monitoring
         Self::start_performance_monitoring(Arc::clone(&manager));
+        let _ = SECURITY_MANAGER.set(Arc::clone(&manager));
+        crate::core::status::status_service().register(Arc::clone(&manager) as Arc<dyn StatusSource>);
 
         info!("SecurityManager initialized successfully");
         Ok(manager)
     }
 
+    /// Returns the coordinator other components holding encryption keys register with
+    pub fn key_rotation(&self) -> Arc<KeyRotationCoordinator> {
+        Arc::clone(&self.key_rotation)
+    }
+
+    /// Returns the scheduler rotating the coordinator's keys
+    pub fn key_rotation_scheduler(&self) -> Arc<KeyRotationScheduler> {
+        Arc::clone(&self.key_rotation_scheduler)
+    }
+
+    /// Rotates every key in the background as an operation tracked in `operations`, returning
+    /// its ID so progress can be followed and the rotation cancelled between keys
+    pub fn rotate_keys(&self, operations: &OperationRegistry) -> Result<Uuid, GuardianError> {
+        let operation = operations.start(KEY_ROTATION_OPERATION, "Rotate encryption keys")?;
+        let id = operation.id();
+        let crypto_manager = Arc::clone(&self.crypto_manager);
+        tokio::spawn(async move {
+            match crypto_manager.rotate_keys_tracked(operation).await {
+                Ok(status) => info!(operation_id = %id, rotated = status.rotated_keys, "Key rotation complete"),
+                Err(e) => warn!(operation_id = %id, error = %e, "Key rotation stopped"),
+            }
+        });
+        Ok(id)
+    }
+
     /// Initializes the security subsystem with performance monitoring
     #[instrument(skip(self))]
     pub async fn initialize(&self) -> Result<(), GuardianError> {
@@ -92,7 +184,7 @@ impl SecurityManager {
             source: Some(Box::new(e)),
             severity: crate::utils::error::ErrorSeverity::Critical,
             timestamp: time::OffsetDateTime::now_utc(),
-            correlation_id: uuid::Uuid::new_v4(),
+            correlation_id: crate::utils::correlation::current_or_new(),
             category: crate::utils::error::ErrorCategory::Security,
             retry_count: 0,
         })?;
@@ -103,18 +195,41 @@ impl SecurityManager {
             source: Some(Box::new(e)),
             severity: crate::utils::error::ErrorSeverity::Critical,
             timestamp: time::OffsetDateTime::now_utc(),
-            correlation_id: uuid::Uuid::new_v4(),
+            correlation_id: crate::utils::correlation::current_or_new(),
             category: crate::utils::error::ErrorCategory::Security,
             retry_count: 0,
         })?;
 
+        // Record audit events from here on, forwarding them to the configured SIEMs
+        audit::init_audit_logger(&self.config.audit_config)?;
+
+        // Chain audit events from here on and anchor them with the crypto manager's key
+        let chain_config = &self.config.audit_config.chain;
+        if let Some(chain) = audit_chain::init_audit_chain(chain_config)? {
+            chain.start_anchoring(
+                Arc::clone(&self.crypto_manager),
+                crypto::KeyId::new(chain_config.anchor_key_id.clone()),
+                chain_config.anchor_interval,
+            );
+        }
+
+        // Service accounts authenticate with API tokens signed under a rotated provider key
+        if let Some(authority) =
+            auth::init_token_authority(&self.config.auth_config.api_tokens, self.key_rotation.provider()).await?
+        {
+            self.key_rotation.register(authority);
+        }
+
+        // Rotate keys on schedule once every holder of a key has had the chance to register
+        Arc::clone(&self.key_rotation_scheduler).start();
+
         // Begin optimized threat detection
         self.threat_detector.initialize().await.map_err(|e| GuardianError::SecurityError {
             context: "Failed to initialize threat detector".into(),
             source: Some(Box::new(e)),
             severity: crate::utils::error::ErrorSeverity::Critical,
             timestamp: time::OffsetDateTime::now_utc(),
-            correlation_id: uuid::Uuid::new_v4(),
+            correlation_id: crate::utils::correlation::current_or_new(),
             category: crate::utils::error::ErrorCategory::Security,
             retry_count: 0,
         })?;
@@ -210,7 +325,7 @@ pub async fn verify_security_state(security_manager: &SecurityManager) -> Result
             source: None,
             severity: crate::utils::error::ErrorSeverity::High,
             timestamp: time::OffsetDateTime::now_utc(),
-            correlation_id: uuid::Uuid::new_v4(),
+            correlation_id: crate::utils::correlation::current_or_new(),
             category: crate::utils::error::ErrorCategory::Security,
             retry_count: 0,
         });
@@ -223,6 +338,44 @@ pub async fn verify_security_state(security_manager: &SecurityManager) -> Result
     })
 }
 
+/// Detection latency and circuit breaker state in the unified status
+#[async_trait::async_trait]
+impl StatusSource for SecurityManager {
+    fn component(&self) -> &'static str {
+        SECURITY
+    }
+
+    async fn status(&self) -> Result<ComponentStatus, GuardianError> {
+        let metrics = self.get_security_metrics().await?;
+        let level = degradation::degradation().level();
+        let degraded = format!("detection degraded to {}", level.as_str());
+        let (state, detail) = if metrics.circuit_breaker_failures >= CIRCUIT_BREAKER_THRESHOLD {
+            (ComponentState::Unhealthy, Some("detection circuit breaker open"))
+        } else if level != crate::config::DegradationLevel::Normal {
+            (ComponentState::Degraded, Some(degraded.as_str()))
+        } else if metrics.avg_detection_time_ms > MAX_DETECTION_TIME_MS {
+            (ComponentState::Degraded, Some("detection slower than its latency budget"))
+        } else {
+            (ComponentState::Healthy, None)
+        };
+        let mut status = ComponentStatus::new(SECURITY, state)
+            .with_metric("avg_detection_time_ms", metrics.avg_detection_time_ms as f64)
+            .with_metric("max_detection_time_ms", MAX_DETECTION_TIME_MS as f64)
+            .with_metric("circuit_breaker_failures", f64::from(metrics.circuit_breaker_failures))
+            .with_metric("degradation_level", level as u8 as f64);
+        let keys = self.key_rotation_scheduler.schedule();
+        if let Some(next) = keys.iter().map(|key| key.next_rotation).min() {
+            status = status.with_metric("keys.next_rotation", next.timestamp() as f64);
+        }
+        if let Some(last) = keys.iter().filter_map(|key| key.last_rotation).max() {
+            status = status.with_metric("keys.last_rotation", last.timestamp() as f64);
+        }
+        status = status.with_metric("keys.failing_rotations", keys.iter().filter(|key| key.failures > 0).count() as f64);
+        status.detail = detail.map(str::to_string);
+        Ok(status)
+    }
+}
+
 #[derive(Debug)]
 pub struct SecurityStatus {
     pub is_healthy: bool,
@@ -240,7 +393,7 @@ mod tests {
         let config = SecurityConfig::default();
         let metrics = Arc::new(Metrics::new().unwrap());
         
-        let manager = SecurityManager::new(config, metrics).unwrap();
+        let manager = SecurityManager::new(config, metrics, TenantContext::default()).unwrap();
         assert!(manager.initialize().await.is_ok());
     }
 
@@ -249,7 +402,7 @@ mod tests {
         let config = SecurityConfig::default();
         let metrics = Arc::new(Metrics::new().unwrap());
         
-        let manager = SecurityManager::new(config, metrics).unwrap();
+        let manager = SecurityManager::new(config, metrics, TenantContext::default()).unwrap();
         let metrics = manager.get_security_metrics().await.unwrap();
         
         assert!(metrics.avg_detection_time_ms <= MAX_DETECTION_TIME_MS);
@@ -0,0 +1,311 @@
+use std::{collections::HashMap, sync::Arc};
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use metrics::counter;
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use ring::signature;
+use tracing::{info, warn};
+
+use crate::config::security_config::{ModelSigningConfig, SecurityConfig};
+use crate::security::audit::{AuditEvent, AuditLogger, SecurityLevel};
+use crate::utils::error::{GuardianError, SecurityError};
+
+// Constants for model signature verification
+/// Audit event type of rejected model signatures
+pub const SIGNATURE_FAILURE_EVENT_TYPE: &str = "model_signature_failure";
+const OID_ED25519: &str = "1.3.101.112";
+const OID_EC_PUBLIC_KEY: &str = "1.2.840.10045.2.1";
+const OID_RSA_ENCRYPTION: &str = "1.2.840.113549.1.1.1";
+
+static MODEL_VERIFIER: Lazy<Arc<ModelVerifier>> = Lazy::new(|| Arc::new(ModelVerifier::new(Vec::new(), true)));
+
+/// Returns the process-wide model signature verifier
+pub fn model_verifier() -> Arc<ModelVerifier> {
+    Arc::clone(&MODEL_VERIFIER)
+}
+
+/// Loads the trusted model signers from the security configuration; call again after a reload
+pub fn init_model_signing(config: &SecurityConfig) -> Result<(), GuardianError> {
+    let keys = load_trusted_keys(&config.model_signing)?;
+    info!(keys = keys.len(), enforce = config.model_signing.enforce, "Model signing keys loaded");
+    model_verifier().configure(keys, config.model_signing.enforce);
+    Ok(())
+}
+
+/// Where a model was at when its signature was checked
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerificationStage {
+    Registration,
+    Activation,
+}
+
+impl VerificationStage {
+    fn label(&self) -> &'static str {
+        match self {
+            VerificationStage::Registration => "registration",
+            VerificationStage::Activation => "activation",
+        }
+    }
+}
+
+/// Public key allowed to sign models
+#[derive(Clone)]
+pub struct TrustedModelKey {
+    /// Key fingerprint or certificate subject, reported with verifications
+    pub name: String,
+    algorithm: &'static dyn signature::VerificationAlgorithm,
+    public_key: Vec<u8>,
+}
+
+impl std::fmt::Debug for TrustedModelKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TrustedModelKey").field("name", &self.name).finish()
+    }
+}
+
+impl TrustedModelKey {
+    /// Trusts a raw Ed25519 public key
+    pub fn ed25519(name: impl Into<String>, public_key: Vec<u8>) -> Self {
+        Self {
+            name: name.into(),
+            algorithm: &signature::ED25519,
+            public_key,
+        }
+    }
+
+    /// Trusts the key of a PEM X.509 certificate that is currently valid
+    pub fn from_certificate_pem(pem: &[u8]) -> Result<Self, GuardianError> {
+        let (_, pem) = x509_parser::pem::parse_x509_pem(pem)
+            .map_err(|e| signing_error("Failed to parse model signing certificate".into(), Some(Box::new(e))))?;
+        let cert = pem
+            .parse_x509()
+            .map_err(|e| signing_error("Failed to parse model signing certificate".into(), Some(Box::new(e))))?;
+        let subject = cert.subject().to_string();
+
+        if !cert.validity().is_valid() {
+            return Err(signing_error(format!("Model signing certificate {} is not currently valid", subject), None));
+        }
+
+        let spki = cert.public_key();
+        let algorithm: &'static dyn signature::VerificationAlgorithm = match spki.algorithm.algorithm.to_id_string().as_str() {
+            OID_ED25519 => &signature::ED25519,
+            OID_EC_PUBLIC_KEY => &signature::ECDSA_P256_SHA256_ASN1,
+            OID_RSA_ENCRYPTION => &signature::RSA_PKCS1_2048_8192_SHA256,
+            other => {
+                return Err(signing_error(
+                    format!("Model signing certificate {} uses unsupported key algorithm {}", subject, other),
+                    None,
+                ))
+            }
+        };
+
+        Ok(Self {
+            name: subject,
+            algorithm,
+            public_key: spki.subject_public_key.data.to_vec(),
+        })
+    }
+
+    fn verifies(&self, data: &[u8], sig: &[u8]) -> bool {
+        signature::UnparsedPublicKey::new(self.algorithm, &self.public_key)
+            .verify(data, sig)
+            .is_ok()
+    }
+}
+
+/// Checks detached model signatures against the trusted signers
+#[derive(Debug)]
+pub struct ModelVerifier {
+    keys: RwLock<Vec<TrustedModelKey>>,
+    enforce: RwLock<bool>,
+    logger: RwLock<Option<Arc<AuditLogger>>>,
+}
+
+impl ModelVerifier {
+    pub fn new(keys: Vec<TrustedModelKey>, enforce: bool) -> Self {
+        Self {
+            keys: RwLock::new(keys),
+            enforce: RwLock::new(enforce),
+            logger: RwLock::new(None),
+        }
+    }
+
+    /// Replaces the trusted signers
+    pub fn configure(&self, keys: Vec<TrustedModelKey>, enforce: bool) {
+        *self.keys.write() = keys;
+        *self.enforce.write() = enforce;
+    }
+
+    /// Also writes verification failures to the audit trail; without a logger they go to the SECURITY-AUDIT log target
+    pub fn attach_logger(&self, logger: Arc<AuditLogger>) {
+        *self.logger.write() = Some(logger);
+    }
+
+    /// Verifies a base64 detached signature over a model binary
+    ///
+    /// Returns the name of the signing key, or `None` when an unverified model is let through
+    /// because enforcement is off. Every failure is audited.
+    pub fn verify(
+        &self,
+        version: &str,
+        data: &[u8],
+        signature: Option<&str>,
+        stage: VerificationStage,
+    ) -> Result<Option<String>, GuardianError> {
+        let failure = match signature.map(|s| BASE64.decode(s.trim())) {
+            None => "model has no signature",
+            Some(Err(_)) => "model signature is not base64",
+            Some(Ok(sig)) => match self.keys.read().iter().find(|key| key.verifies(data, &sig)) {
+                Some(key) => {
+                    counter!("guardian.ml.model_signatures.verified", 1, "stage" => stage.label());
+                    return Ok(Some(key.name.clone()));
+                }
+                None => "model is not signed by a trusted key",
+            },
+        };
+
+        let enforced = *self.enforce.read();
+        counter!("guardian.ml.model_signatures.failed", 1, "stage" => stage.label());
+        self.audit_failure(version, data.len(), failure, stage, enforced);
+
+        if enforced {
+            return Err(signing_error(format!("Model version {} rejected at {}: {}", version, stage.label(), failure), None));
+        }
+        warn!(version, reason = failure, "Model signature not verified, enforcement is off");
+        Ok(None)
+    }
+
+    fn audit_failure(&self, version: &str, size: usize, reason: &str, stage: VerificationStage, enforced: bool) {
+        let event = AuditEvent::new(
+            SIGNATURE_FAILURE_EVENT_TYPE.to_string(),
+            SecurityLevel::High,
+            "model_verifier".to_string(),
+            Some(crate::utils::correlation::current_or_new().to_string()),
+        )
+        .with_tags(HashMap::from([
+            ("version".to_string(), version.to_string()),
+            ("stage".to_string(), stage.label().to_string()),
+        ]));
+        let data = serde_json::json!({
+            "version": version,
+            "size_bytes": size,
+            "reason": reason,
+            "stage": stage.label(),
+            "rejected": enforced,
+        });
+        let event = event.clone().with_data(data).unwrap_or(event);
+
+        match self.logger.read().clone() {
+            Some(logger) => {
+                tokio::spawn(async move {
+                    if let Err(e) = logger.record_event(event).await {
+                        warn!(error = %e, "Failed to write model signature audit event");
+                    }
+                });
+            }
+            None => warn!(
+                target: "SECURITY-AUDIT",
+                event_id = %event.id(),
+                version,
+                stage = stage.label(),
+                reason,
+                rejected = enforced,
+                "Model signature verification failed"
+            ),
+        }
+    }
+}
+
+/// Decodes the configured Ed25519 keys and reads the configured certificates
+pub fn load_trusted_keys(config: &ModelSigningConfig) -> Result<Vec<TrustedModelKey>, GuardianError> {
+    let mut keys = Vec::new();
+    for encoded in &config.ed25519_keys {
+        let key = BASE64
+            .decode(encoded.trim())
+            .map_err(|e| signing_error("Invalid Ed25519 model signing key".into(), Some(Box::new(e))))?;
+        let fingerprint: String = ring::digest::digest(&ring::digest::SHA256, &key)
+            .as_ref()
+            .iter()
+            .take(8)
+            .map(|b| format!("{:02x}", b))
+            .collect();
+        keys.push(TrustedModelKey::ed25519(format!("ed25519:{}", fingerprint), key));
+    }
+    for path in &config.certificate_paths {
+        let pem = std::fs::read(path)
+            .map_err(|e| signing_error(format!("Failed to read model signing certificate {}", path), Some(Box::new(e))))?;
+        keys.push(TrustedModelKey::from_certificate_pem(&pem)?);
+    }
+    Ok(keys)
+}
+
+/// Ed25519 key that signs models produced on this host, such as retrained models
+pub struct ModelSigner {
+    key_pair: signature::Ed25519KeyPair,
+}
+
+impl std::fmt::Debug for ModelSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ModelSigner").finish_non_exhaustive()
+    }
+}
+
+impl ModelSigner {
+    /// Reads a PKCS#8 Ed25519 private key
+    pub fn from_pkcs8_file(path: &str) -> Result<Self, GuardianError> {
+        let pkcs8 = zeroize::Zeroizing::new(
+            std::fs::read(path).map_err(|e| signing_error(format!("Failed to read model signing key {}", path), Some(Box::new(e))))?,
+        );
+        let key_pair = signature::Ed25519KeyPair::from_pkcs8(&pkcs8)
+            .map_err(|e| signing_error(format!("Invalid model signing key {}", path), Some(Box::new(e))))?;
+        Ok(Self { key_pair })
+    }
+
+    /// Base64 detached signature, in the form `ModelMetadata::signature` carries
+    pub fn sign(&self, model: &[u8]) -> String {
+        BASE64.encode(self.key_pair.sign(model).as_ref())
+    }
+}
+
+fn signing_error(context: String, source: Option<Box<dyn std::error::Error + Send + Sync>>) -> GuardianError {
+    SecurityError {
+        context,
+        source,
+        severity: crate::utils::error::ErrorSeverity::High,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: crate::utils::error::ErrorCategory::Security,
+        retry_count: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ring::signature::KeyPair;
+
+    #[test]
+    fn test_verify_detached_signature() {
+        let rng = ring::rand::SystemRandom::new();
+        let pkcs8 = signature::Ed25519KeyPair::generate_pkcs8(&rng).unwrap();
+        let key_pair = signature::Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+        let config = ModelSigningConfig {
+            enforce: true,
+            ed25519_keys: vec![BASE64.encode(key_pair.public_key().as_ref())],
+            certificate_paths: Vec::new(),
+        };
+        let verifier = ModelVerifier::new(load_trusted_keys(&config).unwrap(), true);
+
+        let model = b"model weights";
+        let sig = BASE64.encode(key_pair.sign(model).as_ref());
+        let signer = verifier.verify("v1.0.0", model, Some(&sig), VerificationStage::Registration).unwrap();
+        assert!(signer.unwrap().starts_with("ed25519:"));
+
+        assert!(verifier.verify("v1.0.0", b"tampered", Some(&sig), VerificationStage::Activation).is_err());
+        assert!(verifier.verify("v1.0.0", model, None, VerificationStage::Registration).is_err());
+
+        verifier.configure(load_trusted_keys(&config).unwrap(), false);
+        assert_eq!(verifier.verify("v1.0.0", model, None, VerificationStage::Registration).unwrap(), None);
+    }
+}
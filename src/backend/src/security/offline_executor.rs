@@ -0,0 +1,304 @@
+use std::{
+    collections::HashSet,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use metrics::{counter, gauge, histogram};
+use serde::{Deserialize, Serialize};
+use temporal_sdk::workflow::WorkflowOptions;
+use tracing::{debug, info, instrument, warn};
+use uuid::Uuid;
+
+use crate::security::response_actions::ResponseActionRegistry;
+use crate::security::response_engine::ResponseAction;
+use crate::storage::event_store::{Event as StoredEvent, EventQuery, EventStore};
+use crate::temporal::WorkflowState;
+use crate::utils::error::{GuardianError, SecurityError};
+use crate::utils::ids::{next_id, IdKind};
+
+// Constants for offline response execution
+const JOURNAL_EVENT_TYPE: &str = "response_journal";
+const JOURNAL_SYNCED_EVENT_TYPE: &str = "response_journal_synced";
+/// Workflow type recording a journaled action in Temporal
+pub const OFFLINE_SYNC_WORKFLOW: &str = "record_offline_response";
+const OFFLINE_WORKFLOW_ID_PREFIX: &str = "offline-response";
+const LOCAL_ACTION_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How the response engine is currently executing actions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExecutionMode {
+    /// Actions run as Temporal workflows
+    Online,
+    /// Temporal is unreachable and actions run in-process with journaling
+    Offline,
+}
+
+/// Outcome of an action executed while offline
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum JournalOutcome {
+    /// Journaled before execution; an entry left in this state was interrupted
+    Pending,
+    Succeeded,
+    Failed(String),
+}
+
+/// Durable record of a response action executed without Temporal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub id: String,
+    pub action: ResponseAction,
+    pub workflow_type: String,
+    pub correlation_id: Uuid,
+    pub executed_at: u64,
+    pub outcome: JournalOutcome,
+}
+
+/// Executes response actions in-process when Temporal is unavailable, journaling
+/// every action to the EventStore so it can be recorded in Temporal later
+#[derive(Debug)]
+pub struct OfflineExecutor {
+    event_store: Arc<EventStore>,
+    action_registry: Arc<ResponseActionRegistry>,
+    action_timeout: Duration,
+}
+
+impl OfflineExecutor {
+    /// Creates an executor journaling to the given store
+    pub fn new(event_store: Arc<EventStore>, action_registry: Arc<ResponseActionRegistry>) -> Self {
+        Self {
+            event_store,
+            action_registry,
+            action_timeout: LOCAL_ACTION_TIMEOUT,
+        }
+    }
+
+    /// Overrides the timeout applied to each locally executed action
+    pub fn with_action_timeout(mut self, action_timeout: Duration) -> Self {
+        self.action_timeout = action_timeout;
+        self
+    }
+
+    /// Journals and executes an action locally, returning its journal entry
+    #[instrument(skip(self, action), fields(action = action.name()))]
+    pub async fn execute(
+        &self,
+        action: &ResponseAction,
+        workflow_type: &str,
+        correlation_id: Uuid,
+    ) -> Result<JournalEntry, GuardianError> {
+        let start = std::time::Instant::now();
+        let mut entry = JournalEntry {
+            id: next_id(IdKind::Operation).to_string(),
+            action: action.clone(),
+            workflow_type: workflow_type.to_string(),
+            correlation_id,
+            executed_at: unix_now(),
+            outcome: JournalOutcome::Pending,
+        };
+
+        // Write-ahead so an action interrupted by a crash is still reported on sync
+        self.journal(&entry).await?;
+
+        let result = match tokio::time::timeout(self.action_timeout, self.run_local(action)).await {
+            Ok(result) => result,
+            Err(_) => Err(offline_error(format!("Local execution of {} timed out", action.name()))),
+        };
+
+        entry.outcome = match &result {
+            Ok(()) => JournalOutcome::Succeeded,
+            Err(e) => JournalOutcome::Failed(e.to_string()),
+        };
+        self.journal(&entry).await?;
+
+        counter!("guardian.response.offline.executed", 1,
+            "action" => action.name().to_string(),
+            "success" => result.is_ok().to_string());
+        histogram!("guardian.response.offline.execution_time", start.elapsed().as_secs_f64());
+
+        result.map(|_| entry)
+    }
+
+    /// Records journaled actions in Temporal, returning how many were synced
+    #[instrument(skip(self, client))]
+    pub async fn sync(&self, client: &temporal_sdk::Client, task_queue: &str) -> Result<usize, GuardianError> {
+        let pending = self.unsynced().await?;
+        gauge!("guardian.response.offline.unsynced", pending.len() as f64);
+        let mut synced = 0;
+
+        for entry in pending {
+            // Stable IDs make a sync retried after a partial failure idempotent
+            let workflow_id = format!("{}-{}", OFFLINE_WORKFLOW_ID_PREFIX, entry.id);
+            let options = WorkflowOptions {
+                task_queue: task_queue.to_string(),
+                workflow_id: Some(workflow_id.clone()),
+                ..Default::default()
+            };
+
+            // An entry counts as synced once its workflow has completed, not when it is submitted
+            let recorded = match client.start_workflow(OFFLINE_SYNC_WORKFLOW, entry.clone(), options).await {
+                Ok(handle) => match handle.get_result().await {
+                    Ok(Ok(_)) => Ok(true),
+                    Ok(Err(e)) => Err(e.to_string()),
+                    Err(e) => Err(e.to_string()),
+                },
+                // Submitted by an earlier sync that stopped before the workflow completed
+                Err(e) if e.to_string().to_lowercase().contains("already started") => {
+                    recorded_earlier(client, &workflow_id).await
+                }
+                Err(e) => Err(e.to_string()),
+            };
+            match recorded {
+                Ok(true) => {}
+                Ok(false) => {
+                    debug!(entry_id = %entry.id, "Offline journal entry still being recorded");
+                    continue;
+                }
+                Err(e) => {
+                    warn!(entry_id = %entry.id, error = %e, "Stopping offline journal sync");
+                    break;
+                }
+            }
+
+            self.event_store
+                .store_event(journal_event(JOURNAL_SYNCED_EVENT_TYPE, serde_json::json!({ "id": entry.id })))
+                .await?;
+            synced += 1;
+        }
+
+        counter!("guardian.response.offline.synced", synced as u64);
+        if synced > 0 {
+            info!(synced, "Offline response journal synced to Temporal");
+        }
+        Ok(synced)
+    }
+
+    /// Returns the latest state of every journaled action not yet recorded in Temporal
+    pub async fn unsynced(&self) -> Result<Vec<JournalEntry>, GuardianError> {
+        let journal = self.load(JOURNAL_EVENT_TYPE).await?
+            .into_iter()
+            .filter_map(|event| serde_json::from_value::<JournalEntry>(event.payload).ok())
+            .collect();
+        let synced = self.load(JOURNAL_SYNCED_EVENT_TYPE).await?
+            .into_iter()
+            .filter_map(|event| event.payload.get("id").and_then(|id| id.as_str()).map(str::to_string))
+            .collect();
+        Ok(unsynced_entries(journal, &synced))
+    }
+
+    async fn load(&self, event_type: &str) -> Result<Vec<StoredEvent>, GuardianError> {
+        self.event_store
+            .retrieve_events(EventQuery {
+                start_time: None,
+                end_time: None,
+                event_type: Some(event_type.to_string()),
+                limit: None,
+            })
+            .await
+    }
+
+    async fn journal(&self, entry: &JournalEntry) -> Result<(), GuardianError> {
+        let payload = serde_json::to_value(entry)
+            .map_err(|e| offline_error(format!("Failed to serialize journal entry: {}", e)))?;
+        self.event_store
+            .store_event(journal_event(JOURNAL_EVENT_TYPE, payload))
+            .await
+    }
+
+    async fn run_local(&self, action: &ResponseAction) -> Result<(), GuardianError> {
+        self.action_registry
+            .handler_for(action)?
+            .execute(&action.parameters())
+            .await
+    }
+}
+
+/// Returns whether the workflow an earlier sync submitted has completed, `false` while it runs
+async fn recorded_earlier(client: &temporal_sdk::Client, workflow_id: &str) -> Result<bool, String> {
+    let response = client
+        .describe_workflow_execution(workflow_id.to_string(), None)
+        .await
+        .map_err(|e| e.to_string())?;
+    let state = response
+        .workflow_execution_info
+        .map_or(WorkflowState::Unknown, |info| WorkflowState::from_status(info.status));
+    match state {
+        WorkflowState::Completed => Ok(true),
+        WorkflowState::Running => Ok(false),
+        state => Err(format!("workflow {} is {}", workflow_id, state.as_str())),
+    }
+}
+
+/// Keeps the latest entry per ID and drops entries already recorded in Temporal
+fn unsynced_entries(journal: Vec<JournalEntry>, synced: &HashSet<String>) -> Vec<JournalEntry> {
+    let mut latest: Vec<JournalEntry> = Vec::new();
+    for entry in journal {
+        if synced.contains(&entry.id) {
+            continue;
+        }
+        match latest.iter_mut().find(|e| e.id == entry.id) {
+            Some(existing) => *existing = entry,
+            None => latest.push(entry),
+        }
+    }
+    latest
+}
+
+fn journal_event(event_type: &str, payload: serde_json::Value) -> StoredEvent {
+    StoredEvent {
+        id: next_id(IdKind::Event).to_string(),
+        timestamp: unix_now(),
+        event_type: event_type.to_string(),
+        payload,
+        integrity_hash: String::new(),
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or_default()
+}
+
+fn offline_error(context: String) -> GuardianError {
+    SecurityError {
+        context,
+        source: None,
+        severity: crate::utils::error::ErrorSeverity::High,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: crate::utils::error::ErrorCategory::Security,
+        retry_count: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: &str, outcome: JournalOutcome) -> JournalEntry {
+        JournalEntry {
+            id: id.into(),
+            action: ResponseAction::TerminateProcess { pid: 4242, force: false },
+            workflow_type: "execute_response".into(),
+            correlation_id: Uuid::nil(),
+            executed_at: 0,
+            outcome,
+        }
+    }
+
+    #[test]
+    fn test_unsynced_entries_keep_latest_state() {
+        let journal = vec![
+            entry("a", JournalOutcome::Pending),
+            entry("b", JournalOutcome::Pending),
+            entry("a", JournalOutcome::Succeeded),
+            entry("c", JournalOutcome::Pending),
+        ];
+        let synced = HashSet::from(["b".to_string()]);
+
+        let pending = unsynced_entries(journal, &synced);
+        assert_eq!(pending.len(), 2);
+        assert_eq!(pending[0].id, "a");
+        assert_eq!(pending[0].outcome, JournalOutcome::Succeeded);
+        assert_eq!(pending[1].outcome, JournalOutcome::Pending);
+    }
+}
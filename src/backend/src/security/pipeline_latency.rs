@@ -0,0 +1,190 @@
+use std::{
+    collections::VecDeque,
+    future::Future,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use metrics::histogram;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use serde::Serialize;
+use tracing::{info_span, Instrument};
+
+// Constants for detection latency tracking
+const LATENCY_WINDOW: usize = 1024;
+
+/// SLA stage of a detection, from sample collection to the executed response
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LatencyStage {
+    Collect,
+    Extract,
+    Infer,
+    Classify,
+    Enqueue,
+    Respond,
+}
+
+impl LatencyStage {
+    /// Stages in pipeline order
+    pub const ALL: [LatencyStage; 6] = [
+        LatencyStage::Collect,
+        LatencyStage::Extract,
+        LatencyStage::Infer,
+        LatencyStage::Classify,
+        LatencyStage::Enqueue,
+        LatencyStage::Respond,
+    ];
+
+    /// Label used for metrics and span fields
+    pub fn label(&self) -> &'static str {
+        match self {
+            LatencyStage::Collect => "collect",
+            LatencyStage::Extract => "extract",
+            LatencyStage::Infer => "infer",
+            LatencyStage::Classify => "classify",
+            LatencyStage::Enqueue => "enqueue",
+            LatencyStage::Respond => "respond",
+        }
+    }
+}
+
+/// Latency percentiles of one stage over the recent window
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct StageLatency {
+    pub stage: String,
+    /// Observations since startup
+    pub count: u64,
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+    pub p99_ms: f64,
+    pub max_ms: f64,
+}
+
+/// Per-stage breakdown alongside the end-to-end detection cycle latency
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct LatencySnapshot {
+    pub stages: Vec<StageLatency>,
+    /// Collect through enqueue for one detection cycle
+    pub cycle: StageLatency,
+}
+
+#[derive(Debug, Default)]
+struct LatencyWindow {
+    samples: VecDeque<f64>,
+    count: u64,
+}
+
+impl LatencyWindow {
+    fn push(&mut self, seconds: f64) {
+        if self.samples.len() == LATENCY_WINDOW {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(seconds);
+        self.count += 1;
+    }
+
+    fn summarize(&self, stage: &str) -> StageLatency {
+        let mut sorted: Vec<f64> = self.samples.iter().copied().collect();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+        let percentile = |p: f64| match sorted.len() {
+            0 => 0.0,
+            n => sorted[((n - 1) as f64 * p).round() as usize] * 1000.0,
+        };
+        StageLatency {
+            stage: stage.to_string(),
+            count: self.count,
+            p50_ms: percentile(0.50),
+            p95_ms: percentile(0.95),
+            p99_ms: percentile(0.99),
+            max_ms: sorted.last().map_or(0.0, |max| max * 1000.0),
+        }
+    }
+}
+
+/// Rolling latency windows for every detection stage
+#[derive(Debug, Default)]
+pub struct PipelineLatency {
+    stages: [Mutex<LatencyWindow>; 6],
+    cycle: Mutex<LatencyWindow>,
+}
+
+static PIPELINE_LATENCY: Lazy<Arc<PipelineLatency>> = Lazy::new(|| Arc::new(PipelineLatency::default()));
+
+/// Returns the process-wide detection latency tracker
+pub fn pipeline_latency() -> Arc<PipelineLatency> {
+    Arc::clone(&PIPELINE_LATENCY)
+}
+
+impl PipelineLatency {
+    /// Records one observation of a stage
+    pub fn record(&self, stage: LatencyStage, elapsed: Duration) {
+        let seconds = elapsed.as_secs_f64();
+        histogram!("guardian.detection.latency", seconds, "stage" => stage.label());
+        self.stages[stage as usize].lock().push(seconds);
+    }
+
+    /// Records the end-to-end duration of a detection cycle
+    pub fn record_cycle(&self, elapsed: Duration) {
+        let seconds = elapsed.as_secs_f64();
+        histogram!("guardian.detection.latency", seconds, "stage" => "cycle");
+        self.cycle.lock().push(seconds);
+    }
+
+    /// Runs a future inside the stage's span and records how long it took
+    pub async fn timed<F: Future>(&self, stage: LatencyStage, future: F) -> F::Output {
+        let start = Instant::now();
+        let output = future
+            .instrument(info_span!("detection_stage", stage = stage.label()))
+            .await;
+        self.record(stage, start.elapsed());
+        output
+    }
+
+    /// Fraction of recent detection cycles finishing within `target`, None before the first cycle
+    pub fn cycle_attainment(&self, target: Duration) -> Option<f64> {
+        let cycle = self.cycle.lock();
+        if cycle.samples.is_empty() {
+            return None;
+        }
+        let target = target.as_secs_f64();
+        let met = cycle.samples.iter().filter(|&&seconds| seconds <= target).count();
+        Some(met as f64 / cycle.samples.len() as f64)
+    }
+
+    /// Returns the current latency breakdown
+    pub fn snapshot(&self) -> LatencySnapshot {
+        LatencySnapshot {
+            stages: LatencyStage::ALL
+                .iter()
+                .map(|stage| self.stages[*stage as usize].lock().summarize(stage.label()))
+                .collect(),
+            cycle: self.cycle.lock().summarize("cycle"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_percentiles() {
+        let latency = PipelineLatency::default();
+        for ms in 1..=100 {
+            latency.record(LatencyStage::Infer, Duration::from_millis(ms));
+        }
+        latency.record_cycle(Duration::from_millis(40));
+
+        let snapshot = latency.snapshot();
+        let infer = &snapshot.stages[LatencyStage::Infer as usize];
+        assert_eq!(infer.stage, "infer");
+        assert_eq!(infer.count, 100);
+        assert!((infer.p50_ms - 51.0).abs() < 1.0);
+        assert!((infer.max_ms - 100.0).abs() < 0.001);
+        assert_eq!(snapshot.stages[LatencyStage::Collect as usize].count, 0);
+        assert_eq!(snapshot.cycle.count, 1);
+    }
+}
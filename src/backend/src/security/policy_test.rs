@@ -0,0 +1,335 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use async_trait::async_trait;
+use metrics::counter;
+use serde::{Deserialize, Serialize};
+use tracing::{info, instrument};
+
+use crate::ml::inference_engine::Prediction;
+use crate::security::anomaly_detection::SystemData;
+use crate::security::detection_pipeline::{
+    DetectionContext, DetectionPipeline, PipelineConfig, PipelineStage, StageConfig, StageKind, StageRegistry,
+};
+use crate::security::process_trust::TrustLevel;
+use crate::security::response_actions::ResponseActionRegistry;
+use crate::security::response_engine::{select_response_action, ThreatAnalysis};
+use crate::security::threat_detection::ThreatLevel;
+use crate::utils::error::{GuardianError, SecurityError};
+
+// Constants for policy testing
+const FIXTURE_EXTENSIONS: &[&str] = &["yaml", "yml"];
+const DEFAULT_SOURCE_ADDRESS: &str = "0.0.0.0";
+
+/// A fixture file: scenarios sharing a rules file by default
+#[derive(Debug, Clone, Deserialize)]
+pub struct PolicyFixture {
+    /// Detection pipeline configuration under test, relative to the fixture
+    #[serde(default)]
+    pub rules: Option<PathBuf>,
+    pub scenarios: Vec<PolicyScenario>,
+}
+
+/// Input events run through the rules and playbook, and what should come out
+#[derive(Debug, Clone, Deserialize)]
+pub struct PolicyScenario {
+    pub name: String,
+    /// Overrides the fixture's rules file
+    #[serde(default)]
+    pub rules: Option<PathBuf>,
+    pub events: Vec<SystemData>,
+    /// Output of each `inference` stage by stage name; models are stubbed so results are deterministic
+    #[serde(default)]
+    pub models: HashMap<String, Vec<FixturePrediction>>,
+    /// Severity the playbook sees for each detection type, Medium when unlisted
+    #[serde(default)]
+    pub severities: HashMap<String, ThreatLevel>,
+    #[serde(default)]
+    pub process_id: Option<u32>,
+    #[serde(default)]
+    pub source_address: Option<String>,
+    /// Trust of the binary involved, unless a `trust_filter` stage tagged the detection
+    #[serde(default)]
+    pub trust: Option<TrustLevel>,
+    pub expect: Expectations,
+}
+
+/// A stubbed model output
+#[derive(Debug, Clone, Deserialize)]
+pub struct FixturePrediction {
+    #[serde(rename = "type")]
+    pub prediction_type: String,
+    pub confidence: f32,
+}
+
+/// What a scenario must produce
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Expectations {
+    /// Detection types that must fire
+    #[serde(default)]
+    pub detections: Vec<String>,
+    /// Detection types that must not fire
+    #[serde(default)]
+    pub absent: Vec<String>,
+    /// Exact set of response actions the playbook must choose, when given
+    #[serde(default)]
+    pub actions: Option<Vec<String>>,
+}
+
+/// Outcome of one scenario
+#[derive(Debug, Clone, Serialize)]
+pub struct ScenarioResult {
+    pub fixture: PathBuf,
+    pub scenario: String,
+    pub detections: Vec<String>,
+    pub actions: Vec<String>,
+    /// Empty when the scenario passed
+    pub failures: Vec<String>,
+}
+
+impl ScenarioResult {
+    /// Whether every expectation held
+    pub fn passed(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+/// Runs rules and playbooks against fixture scenarios without models, events or a live host
+#[derive(Debug)]
+pub struct PolicyHarness {
+    action_registry: Arc<ResponseActionRegistry>,
+}
+
+impl Default for PolicyHarness {
+    fn default() -> Self {
+        Self::new(Arc::new(ResponseActionRegistry::new()))
+    }
+}
+
+impl PolicyHarness {
+    /// Creates a harness choosing actions from the given registry
+    pub fn new(action_registry: Arc<ResponseActionRegistry>) -> Self {
+        Self { action_registry }
+    }
+
+    /// Runs every fixture in the given files and directories
+    pub async fn run_paths(&self, paths: &[PathBuf]) -> Result<Vec<ScenarioResult>, GuardianError> {
+        let mut results = Vec::new();
+        for fixture in fixture_files(paths)? {
+            results.extend(self.run_file(&fixture).await?);
+        }
+        let failed = results.iter().filter(|r| !r.passed()).count();
+        counter!("guardian.policy_test.scenarios", results.len() as u64, "outcome" => "run");
+        counter!("guardian.policy_test.scenarios", failed as u64, "outcome" => "failed");
+        info!(scenarios = results.len(), failed, "Policy tests complete");
+        Ok(results)
+    }
+
+    /// Runs the scenarios of one fixture file
+    #[instrument(skip(self))]
+    pub async fn run_file(&self, path: &Path) -> Result<Vec<ScenarioResult>, GuardianError> {
+        let raw = std::fs::read_to_string(path)
+            .map_err(|e| policy_error(format!("Failed to read fixture {}: {}", path.display(), e)))?;
+        let fixture: PolicyFixture = serde_yaml::from_str(&raw)
+            .map_err(|e| policy_error(format!("Invalid fixture {}: {}", path.display(), e)))?;
+        let base = path.parent().unwrap_or(Path::new("."));
+
+        let mut results = Vec::with_capacity(fixture.scenarios.len());
+        for scenario in &fixture.scenarios {
+            let rules = scenario.rules.as_ref().or(fixture.rules.as_ref()).ok_or_else(|| {
+                policy_error(format!("Scenario {} in {} names no rules file", scenario.name, path.display()))
+            })?;
+            let pipeline = load_rules(&base.join(rules))?;
+            results.push(self.run_scenario(path, &pipeline, scenario).await?);
+        }
+        Ok(results)
+    }
+
+    /// Runs one scenario through the rules, then each detection through the playbook
+    pub async fn run_scenario(
+        &self,
+        fixture: &Path,
+        rules: &PipelineConfig,
+        scenario: &PolicyScenario,
+    ) -> Result<ScenarioResult, GuardianError> {
+        let pipeline = DetectionPipeline::from_config(rules, &stub_registry(&scenario.models))?
+            .without_latency_tracking();
+        let predictions = pipeline.run(scenario.events.clone()).await?;
+
+        let detections: BTreeSet<String> = predictions.iter().map(|p| p.prediction_type().to_string()).collect();
+        let actions: BTreeSet<String> = predictions
+            .iter()
+            .map(|prediction| {
+                let analysis = ThreatAnalysis {
+                    severity: scenario.severities.get(prediction.prediction_type()).cloned().unwrap_or(ThreatLevel::Medium),
+                    description: prediction.prediction_type().to_string(),
+                    process_id: scenario.process_id,
+                    source_address: scenario.source_address.clone().unwrap_or_else(|| DEFAULT_SOURCE_ADDRESS.into()),
+                    trust: prediction
+                        .metadata()
+                        .get("trust_level")
+                        .and_then(|label| TrustLevel::from_label(label))
+                        .or(scenario.trust),
+                    detection_id: None,
+                    lineage: None,
+                };
+                select_response_action(&self.action_registry, &analysis).name().to_string()
+            })
+            .collect();
+
+        Ok(ScenarioResult {
+            fixture: fixture.to_path_buf(),
+            scenario: scenario.name.clone(),
+            failures: check(&scenario.expect, &detections, &actions),
+            detections: detections.into_iter().collect(),
+            actions: actions.into_iter().collect(),
+        })
+    }
+}
+
+/// Compares what a scenario produced with what it expected
+fn check(expect: &Expectations, detections: &BTreeSet<String>, actions: &BTreeSet<String>) -> Vec<String> {
+    let mut failures = Vec::new();
+    for missing in expect.detections.iter().filter(|d| !detections.contains(*d)) {
+        failures.push(format!("expected detection {} did not fire", missing));
+    }
+    for unexpected in expect.absent.iter().filter(|d| detections.contains(*d)) {
+        failures.push(format!("detection {} fired but should not have", unexpected));
+    }
+    if let Some(expected) = &expect.actions {
+        let expected: BTreeSet<String> = expected.iter().cloned().collect();
+        if &expected != actions {
+            failures.push(format!(
+                "expected actions [{}], playbook chose [{}]",
+                expected.into_iter().collect::<Vec<_>>().join(", "),
+                actions.iter().cloned().collect::<Vec<_>>().join(", ")
+            ));
+        }
+    }
+    failures
+}
+
+/// Built-in stages, with every `inference` stage replaced by the scenario's stubbed outputs
+fn stub_registry(models: &HashMap<String, Vec<FixturePrediction>>) -> StageRegistry {
+    let mut registry = StageRegistry::without_models();
+    let models = Arc::new(models.clone());
+    registry.register("inference", Arc::new(move |config: &StageConfig| {
+        let outputs = models.get(&config.name).cloned().unwrap_or_default();
+        Ok(Arc::new(StubModel { model: config.name.clone(), outputs }) as Arc<dyn PipelineStage>)
+    }));
+    registry
+}
+
+/// Model stage emitting fixed predictions once per cycle
+#[derive(Debug)]
+struct StubModel {
+    model: String,
+    outputs: Vec<FixturePrediction>,
+}
+
+#[async_trait]
+impl PipelineStage for StubModel {
+    fn kind(&self) -> StageKind {
+        StageKind::Model
+    }
+
+    async fn run(&self, context: &mut DetectionContext) -> Result<(), GuardianError> {
+        // Pre-filters that drop every sample leave the model nothing to score
+        if context.samples.is_empty() {
+            return Ok(());
+        }
+        context.predictions.extend(self.outputs.iter().map(|output| {
+            Prediction::new(
+                output.prediction_type.clone(),
+                output.confidence,
+                HashMap::from([("model".to_string(), self.model.clone())]),
+            )
+        }));
+        Ok(())
+    }
+}
+
+fn load_rules(path: &Path) -> Result<PipelineConfig, GuardianError> {
+    let raw = std::fs::read(path).map_err(|e| policy_error(format!("Failed to read rules {}: {}", path.display(), e)))?;
+    serde_json::from_slice(&raw)
+        .map_err(|e| policy_error(format!("{} is not a pipeline configuration: {}", path.display(), e)))
+}
+
+/// Expands directories into the fixture files they contain, in name order
+fn fixture_files(paths: &[PathBuf]) -> Result<Vec<PathBuf>, GuardianError> {
+    let mut files = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            let entries = std::fs::read_dir(path)
+                .map_err(|e| policy_error(format!("Failed to list {}: {}", path.display(), e)))?;
+            let mut found: Vec<PathBuf> = entries
+                .filter_map(|entry| entry.ok().map(|e| e.path()))
+                .filter(|p| p.extension().and_then(|e| e.to_str()).is_some_and(|e| FIXTURE_EXTENSIONS.contains(&e)))
+                .collect();
+            found.sort();
+            files.extend(found);
+        } else {
+            files.push(path.clone());
+        }
+    }
+    Ok(files)
+}
+
+fn policy_error(context: String) -> GuardianError {
+    SecurityError {
+        context,
+        source: None,
+        severity: crate::utils::error::ErrorSeverity::Low,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: crate::utils::error::ErrorCategory::Validation,
+        retry_count: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_scenario_checks_detections_and_playbook_actions() {
+        let rules: PipelineConfig = serde_json::from_value(serde_json::json!({
+            "name": "lateral",
+            "stages": [
+                { "name": "lateral_model", "type": "inference" },
+                { "name": "filter", "type": "confidence_filter", "params": { "threshold": 0.9 } },
+            ],
+        }))
+        .unwrap();
+        let scenario: PolicyScenario = serde_yaml::from_str(
+            r#"
+name: ssh pivot
+events:
+  - { metrics: { ssh_sessions: 14.0 }, events: [ssh_login], timestamp: 0 }
+models:
+  lateral_model:
+    - { type: lateral_movement, confidence: 0.97 }
+    - { type: port_scan, confidence: 0.4 }
+severities: { lateral_movement: High }
+process_id: 4242
+expect:
+  detections: [lateral_movement]
+  absent: [port_scan]
+  actions: [terminate_process]
+"#,
+        )
+        .unwrap();
+
+        let harness = PolicyHarness::default();
+        let result = harness.run_scenario(Path::new("pivot.yaml"), &rules, &scenario).await.unwrap();
+        assert!(result.passed(), "{:?}", result.failures);
+
+        let mut strict = scenario.clone();
+        strict.expect.actions = Some(vec!["block_network".into()]);
+        let result = harness.run_scenario(Path::new("pivot.yaml"), &rules, &strict).await.unwrap();
+        assert_eq!(result.failures.len(), 1);
+    }
+}
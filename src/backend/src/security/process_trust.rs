@@ -0,0 +1,280 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+use chrono::{DateTime, Utc};
+use metrics::{counter, gauge};
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use crate::config::{AppConfig, ProcessTrustConfig};
+use crate::security::threat_detection::ThreatLevel;
+
+// Constants for process trust
+/// Highest threshold trusted binaries are held to, so they can still be flagged
+const MAX_TRUSTED_THRESHOLD: f32 = 0.99;
+
+static PROCESS_TRUST: Lazy<Arc<TrustRegistry>> = Lazy::new(|| Arc::new(TrustRegistry::new(ProcessTrustConfig::default())));
+
+/// How far a binary is trusted, lowest first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrustLevel {
+    /// Demoted after suspicious behavior
+    Untrusted,
+    /// First seen recently
+    New,
+    /// Clean for the observation period
+    Observed,
+    /// Clean for long enough, or allowlisted
+    Trusted,
+}
+
+impl TrustLevel {
+    /// Name of the level as serialized
+    pub fn label(&self) -> &'static str {
+        match self {
+            TrustLevel::Untrusted => "untrusted",
+            TrustLevel::New => "new",
+            TrustLevel::Observed => "observed",
+            TrustLevel::Trusted => "trusted",
+        }
+    }
+
+    /// Parses a level from its label, as carried in prediction metadata
+    pub fn from_label(label: &str) -> Option<Self> {
+        [TrustLevel::Untrusted, TrustLevel::New, TrustLevel::Observed, TrustLevel::Trusted]
+            .into_iter()
+            .find(|level| level.label() == label)
+    }
+
+    /// Trust as a model feature in `[0, 1]`
+    pub fn score(&self) -> f64 {
+        match self {
+            TrustLevel::Untrusted => 0.0,
+            TrustLevel::New => 0.25,
+            TrustLevel::Observed => 0.6,
+            TrustLevel::Trusted => 1.0,
+        }
+    }
+
+    /// Confidence a detection on this binary needs, given the threshold for observed binaries
+    pub fn confidence_threshold(&self, base: f32) -> f32 {
+        match self {
+            TrustLevel::Untrusted => base * 0.8,
+            TrustLevel::New => base * 0.9,
+            TrustLevel::Observed => base,
+            TrustLevel::Trusted => (base * 1.05).min(MAX_TRUSTED_THRESHOLD).max(base),
+        }
+    }
+
+    /// Whether the binary's processes should be traced in more detail
+    pub fn extra_telemetry(&self) -> bool {
+        matches!(self, TrustLevel::Untrusted | TrustLevel::New)
+    }
+
+    /// Severity the response playbook acts on: low trust escalates, full trust softens by one level
+    pub fn adjust_severity(&self, severity: &ThreatLevel) -> ThreatLevel {
+        match (self, severity) {
+            (TrustLevel::Untrusted | TrustLevel::New, ThreatLevel::Low) => ThreatLevel::Medium,
+            (TrustLevel::Untrusted | TrustLevel::New, ThreatLevel::Medium) => ThreatLevel::High,
+            (TrustLevel::Trusted, ThreatLevel::Critical) => ThreatLevel::High,
+            (TrustLevel::Trusted, ThreatLevel::High) => ThreatLevel::Medium,
+            (TrustLevel::Trusted, ThreatLevel::Medium) => ThreatLevel::Low,
+            _ => severity.clone(),
+        }
+    }
+}
+
+/// A binary as identified by its path and, when known, its content digest
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct BinaryIdentity {
+    pub path: String,
+    #[serde(default)]
+    pub sha256: Option<String>,
+}
+
+impl BinaryIdentity {
+    pub fn new(path: impl Into<String>, sha256: Option<String>) -> Self {
+        Self { path: path.into(), sha256 }
+    }
+
+    /// Reads the identity from the `exe` and `sha256` fields of a telemetry record or prediction
+    pub fn from_fields(path: Option<&str>, sha256: Option<&str>) -> Option<Self> {
+        let path = path.filter(|p| !p.is_empty())?;
+        Some(Self::new(path, sha256.filter(|s| !s.is_empty()).map(str::to_string)))
+    }
+
+    /// Replaced binaries get a new digest, and so start over
+    fn key(&self) -> &str {
+        self.sha256.as_deref().unwrap_or(&self.path)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct TrustRecord {
+    first_seen: DateTime<Utc>,
+    /// Start of the current clean stretch, reset by demotion
+    clean_since: DateTime<Utc>,
+    demotions: u32,
+}
+
+/// Tracks how long each binary has been seen behaving and what trust that has earned
+#[derive(Debug)]
+pub struct TrustRegistry {
+    config: RwLock<ProcessTrustConfig>,
+    allowlist: RwLock<HashSet<String>>,
+    records: RwLock<HashMap<String, TrustRecord>>,
+}
+
+/// Returns the process-wide trust registry
+pub fn process_trust() -> Arc<TrustRegistry> {
+    Arc::clone(&PROCESS_TRUST)
+}
+
+/// Applies the configured trust lifecycle; call again after a reload
+pub fn init_process_trust(config: &AppConfig) {
+    process_trust().configure(config.process_trust.clone());
+}
+
+impl TrustRegistry {
+    pub fn new(config: ProcessTrustConfig) -> Self {
+        let registry = Self {
+            config: RwLock::new(config.clone()),
+            allowlist: RwLock::new(HashSet::new()),
+            records: RwLock::new(HashMap::new()),
+        };
+        registry.configure(config);
+        registry
+    }
+
+    /// Applies a new lifecycle configuration without forgetting observed binaries
+    pub fn configure(&self, config: ProcessTrustConfig) {
+        *self.allowlist.write() = config.allowlist.iter().map(|entry| entry.to_ascii_lowercase()).collect();
+        *self.config.write() = config;
+    }
+
+    /// Records that a binary was seen running and returns its trust
+    pub fn observe(&self, binary: &BinaryIdentity) -> TrustLevel {
+        self.observe_at(binary, Utc::now())
+    }
+
+    /// Returns a binary's trust without recording it; unseen binaries are new
+    pub fn level(&self, binary: &BinaryIdentity) -> TrustLevel {
+        let now = Utc::now();
+        match self.records.read().get(binary.key()) {
+            Some(record) => self.level_of(binary, record, now),
+            None if self.allowlisted(binary) => TrustLevel::Trusted,
+            None => TrustLevel::New,
+        }
+    }
+
+    /// Drops a binary to untrusted after suspicious behavior, restarting its clean time
+    pub fn demote(&self, binary: &BinaryIdentity, reason: &str) -> TrustLevel {
+        self.demote_at(binary, reason, Utc::now())
+    }
+
+    /// Adds `trust` and `trust_level` to a process telemetry record with an `exe` field
+    pub fn annotate(&self, record: &mut serde_json::Value) {
+        let binary = BinaryIdentity::from_fields(
+            record.get("exe").and_then(|v| v.as_str()),
+            record.get("sha256").and_then(|v| v.as_str()),
+        );
+        let (Some(binary), Some(fields)) = (binary, record.as_object_mut()) else {
+            return;
+        };
+        let level = self.observe(&binary);
+        fields.insert("trust".into(), serde_json::json!(level.score()));
+        fields.insert("trust_level".into(), serde_json::json!(level));
+    }
+
+    fn observe_at(&self, binary: &BinaryIdentity, now: DateTime<Utc>) -> TrustLevel {
+        let mut records = self.records.write();
+        let record = records.entry(binary.key().to_string()).or_insert_with(|| {
+            debug!(path = %binary.path, "First sighting of binary");
+            counter!("guardian.process_trust.new_binaries", 1);
+            TrustRecord { first_seen: now, clean_since: now, demotions: 0 }
+        });
+        let level = self.level_of(binary, record, now);
+        gauge!("guardian.process_trust.binaries", records.len() as f64);
+        level
+    }
+
+    fn demote_at(&self, binary: &BinaryIdentity, reason: &str, now: DateTime<Utc>) -> TrustLevel {
+        let mut records = self.records.write();
+        let record = records
+            .entry(binary.key().to_string())
+            .or_insert(TrustRecord { first_seen: now, clean_since: now, demotions: 0 });
+        record.clean_since = now;
+        record.demotions += 1;
+
+        counter!("guardian.process_trust.demotions", 1);
+        warn!(
+            target: "SECURITY-AUDIT",
+            path = %binary.path,
+            sha256 = binary.sha256.as_deref().unwrap_or("-"),
+            first_seen = %record.first_seen,
+            demotions = record.demotions,
+            reason,
+            "Binary demoted to untrusted"
+        );
+        TrustLevel::Untrusted
+    }
+
+    fn level_of(&self, binary: &BinaryIdentity, record: &TrustRecord, now: DateTime<Utc>) -> TrustLevel {
+        let config = self.config.read();
+        let clean_for = (now - record.clean_since).to_std().unwrap_or_default();
+
+        // A demotion outweighs the allowlist until the binary has been clean for a full observation period
+        if record.demotions > 0 && clean_for < config.observation_period {
+            return TrustLevel::Untrusted;
+        }
+        if self.allowlisted(binary) || clean_for >= config.trusted_after {
+            TrustLevel::Trusted
+        } else if clean_for >= config.observation_period {
+            TrustLevel::Observed
+        } else {
+            TrustLevel::New
+        }
+    }
+
+    fn allowlisted(&self, binary: &BinaryIdentity) -> bool {
+        let allowlist = self.allowlist.read();
+        allowlist.contains(&binary.path.to_ascii_lowercase())
+            || binary.sha256.as_ref().is_some_and(|digest| allowlist.contains(&digest.to_ascii_lowercase()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_binaries_earn_trust_over_clean_time_and_lose_it_on_demotion() {
+        let registry = TrustRegistry::new(ProcessTrustConfig {
+            allowlist: vec!["/usr/sbin/sshd".into()],
+            ..Default::default()
+        });
+        let start = Utc::now();
+        let miner = BinaryIdentity::new("/tmp/.x/miner", Some("ab12".into()));
+
+        assert_eq!(registry.observe_at(&miner, start), TrustLevel::New);
+        assert_eq!(registry.observe_at(&miner, start + chrono::Duration::days(2)), TrustLevel::Observed);
+        assert_eq!(registry.observe_at(&miner, start + chrono::Duration::days(8)), TrustLevel::Trusted);
+
+        registry.demote_at(&miner, "crypto mining detected", start + chrono::Duration::days(8));
+        assert_eq!(registry.observe_at(&miner, start + chrono::Duration::days(8)), TrustLevel::Untrusted);
+        assert_eq!(registry.observe_at(&miner, start + chrono::Duration::days(10)), TrustLevel::Observed);
+
+        // A new build of the same path starts over
+        let rebuilt = BinaryIdentity::new("/tmp/.x/miner", Some("cd34".into()));
+        assert_eq!(registry.observe_at(&rebuilt, start + chrono::Duration::days(10)), TrustLevel::New);
+
+        assert_eq!(registry.level(&BinaryIdentity::new("/usr/sbin/sshd", None)), TrustLevel::Trusted);
+        assert!(TrustLevel::New.confidence_threshold(0.95) < TrustLevel::Trusted.confidence_threshold(0.95));
+        assert_eq!(TrustLevel::New.adjust_severity(&ThreatLevel::Medium), ThreatLevel::High);
+    }
+}
@@ -0,0 +1,673 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    future::Future,
+    path::{Path, PathBuf},
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use http::{HeaderValue, Request, Response};
+use metrics::counter;
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use tracing::{info, instrument, warn};
+
+use crate::config::{active_profile, EnforcementMode};
+use crate::core::guardian::{TenantContext, TenantId};
+use crate::security::audit::{audit_logger, AuditEvent, SecurityLevel};
+use crate::security::auth::{scope_principal, TokenClaims};
+use crate::security::command_audit::{command_audit, AccessDecision, CommandInvocation, Interface};
+use crate::security::remote_assistance::{peer_fingerprint, RemoteAssistanceGrant};
+use crate::utils::error::{GuardianError, SecurityError};
+
+// Constants for role-based access control
+const DEFAULT_POLICY_PATH: &str = "/etc/guardian/rbac.json";
+const ANONYMOUS: &str = "anonymous";
+const WILDCARD: &str = "*";
+/// Load balancer health probes carry no credentials, so the health service is open to all
+const UNAUTHENTICATED_RPC_PREFIX: &str = "/grpc.health.v1.Health/";
+
+/// Role granting a set of permission patterns
+///
+/// Patterns are `cli:<command>[:<subcommand>]` or `rpc:<package.Service>/<Method>`.
+/// A pattern also grants everything beneath it, and a trailing `*` matches any suffix.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Role {
+    #[serde(default)]
+    pub inherits: Vec<String>,
+    pub permissions: Vec<String>,
+}
+
+/// RBAC policy loaded from the policy file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RbacPolicy {
+    pub roles: BTreeMap<String, Role>,
+    /// Maps `user:`, `group:`, `cert:`, `cn:`, `token:` or API token `scope:` identities to role names
+    pub bindings: BTreeMap<String, Vec<String>>,
+    /// Tenants sharing this host; identities not listed as a member belong to the default tenant
+    #[serde(default)]
+    pub tenants: BTreeMap<TenantId, TenantPolicy>,
+}
+
+/// Membership and additional roles of one tenant
+///
+/// Tenant roles shadow global roles of the same name, and tenant bindings only
+/// apply to the tenant's own members.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TenantPolicy {
+    /// Identities whose requests are scoped to this tenant
+    pub members: Vec<String>,
+    #[serde(default)]
+    pub roles: BTreeMap<String, Role>,
+    #[serde(default)]
+    pub bindings: BTreeMap<String, Vec<String>>,
+}
+
+impl Default for RbacPolicy {
+    fn default() -> Self {
+        let role = |inherits: &[&str], permissions: &[&str]| Role {
+            inherits: inherits.iter().map(|s| s.to_string()).collect(),
+            permissions: permissions.iter().map(|s| s.to_string()).collect(),
+        };
+
+        let roles = BTreeMap::from([
+            ("admin".to_string(), role(&[], &[WILDCARD])),
+            ("security".to_string(), role(&["operator"], &[
+                "cli:threats",
+                "cli:forensics",
+                "cli:audit",
+                "cli:workflows",
+                "cli:response",
+                "rpc:guardian.security.v1.SecurityService/*",
+                "rpc:guardian.core.v1.GuardianService/*",
+                "rpc:guardian.workflow.v1.WorkflowAdmin/*",
+            ])),
+            ("operator".to_string(), role(&[], &[
+                "cli:status",
+                "cli:ops",
+                "cli:policy",
+                "cli:dead-letters",
+                "cli:diagnose",
+                "cli:workflows:list",
+                "cli:workflows:show",
+                "rpc:guardian.core.v1.GuardianService/GetSystemStatus",
+                "rpc:guardian.core.v1.GuardianService/PerformHealthCheck",
+                "rpc:guardian.core.v1.GuardianService/StreamMetrics",
+                "rpc:guardian.core.v1.GuardianService/UpdateMetricsWindow",
+                "rpc:guardian.core.v1.GuardianService/MonitorMetrics",
+                "rpc:guardian.core.v1.GuardianService/ListOperations",
+                "rpc:guardian.core.v1.GuardianService/GetOperation",
+                "rpc:guardian.core.v1.GuardianService/CancelOperation",
+                "rpc:guardian.core.v1.GuardianService/GetPosture",
+                "rpc:guardian.core.v1.GuardianService/GetPostureHistory",
+                "rpc:guardian.core.v1.GuardianService/ListStateTransitions",
+                "rpc:guardian.attestation.v1.AttestationService/*",
+                "rpc:guardian.workflow.v1.WorkflowAdmin/ListWorkflows",
+                "rpc:guardian.workflow.v1.WorkflowAdmin/GetWorkflow",
+                "rpc:grpc.reflection.v1alpha.ServerReflection/*",
+            ])),
+            ("data_scientist".to_string(), role(&[], &[
+                "cli:status",
+                "cli:models",
+                "cli:threats:export-feedback",
+                "rpc:guardian.ml.v1.MLService/*",
+                "rpc:guardian.security.v1.SecurityService/ExportFeedbackDataset",
+            ])),
+        ]);
+
+        let bindings = BTreeMap::from([
+            ("user:root".to_string(), vec!["admin".to_string()]),
+            ("group:guardian-admin".to_string(), vec!["admin".to_string()]),
+            ("group:guardian-security".to_string(), vec!["security".to_string()]),
+            ("group:guardian-operator".to_string(), vec!["operator".to_string()]),
+            ("group:guardian-ml".to_string(), vec!["data_scientist".to_string()]),
+            ("scope:security".to_string(), vec!["security".to_string()]),
+            ("scope:operator".to_string(), vec!["operator".to_string()]),
+            ("scope:ml".to_string(), vec!["data_scientist".to_string()]),
+        ]);
+
+        Self {
+            roles,
+            bindings,
+            tenants: BTreeMap::new(),
+        }
+    }
+}
+
+impl RbacPolicy {
+    /// Checks that every inherited and bound role exists and each identity has at most one tenant
+    pub fn validate(&self) -> Result<(), GuardianError> {
+        let referenced = self
+            .roles
+            .values()
+            .flat_map(|role| role.inherits.iter())
+            .chain(self.bindings.values().flatten());
+        for name in referenced {
+            if !self.roles.contains_key(name) {
+                return Err(rbac_error(format!("RBAC policy references unknown role {}", name), None));
+            }
+        }
+
+        let mut members = BTreeSet::new();
+        for (tenant_id, tenant) in &self.tenants {
+            let referenced = tenant
+                .roles
+                .values()
+                .flat_map(|role| role.inherits.iter())
+                .chain(tenant.bindings.values().flatten());
+            for name in referenced {
+                if self.role(Some(tenant), name).is_none() {
+                    return Err(rbac_error(
+                        format!("RBAC policy for tenant {} references unknown role {}", tenant_id, name),
+                        None,
+                    ));
+                }
+            }
+            for identity in &tenant.members {
+                if !members.insert(identity) {
+                    return Err(rbac_error(format!("{} is a member of more than one tenant", identity), None));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the tenant a principal's identities are members of
+    fn tenant_of(&self, principal: &Principal) -> Option<(&TenantId, &TenantPolicy)> {
+        self.tenants
+            .iter()
+            .find(|(_, tenant)| tenant.members.iter().any(|m| principal.identities.contains(m)))
+    }
+
+    /// Looks a role up in the tenant's roles first, then the global ones
+    fn role(&self, tenant: Option<&TenantPolicy>, name: &str) -> Option<&Role> {
+        tenant
+            .and_then(|tenant| tenant.roles.get(name))
+            .or_else(|| self.roles.get(name))
+    }
+
+    /// Expands inherited roles, ignoring cycles
+    fn expand(&self, tenant: Option<&TenantPolicy>, roles: impl IntoIterator<Item = String>) -> BTreeSet<String> {
+        let mut expanded = BTreeSet::new();
+        let mut pending: Vec<String> = roles.into_iter().collect();
+        while let Some(name) = pending.pop() {
+            if !expanded.insert(name.clone()) {
+                continue;
+            }
+            if let Some(role) = self.role(tenant, &name) {
+                pending.extend(role.inherits.iter().cloned());
+            }
+        }
+        expanded
+    }
+}
+
+/// Identity requesting access, described by the binding keys it matches
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Principal {
+    /// Name used in audit events
+    pub name: String,
+    pub identities: Vec<String>,
+}
+
+impl Principal {
+    /// Principal with no bindings
+    pub fn anonymous() -> Self {
+        Self {
+            name: ANONYMOUS.into(),
+            identities: vec![ANONYMOUS.into()],
+        }
+    }
+
+    /// Principal for the effective Unix user and its groups
+    pub fn current_user() -> Self {
+        use nix::unistd::{getgroups, geteuid, Group, User};
+
+        let user = User::from_uid(geteuid()).ok().flatten().map(|u| u.name);
+        let Some(name) = user else {
+            return Self::anonymous();
+        };
+
+        let mut identities = vec![format!("user:{}", name)];
+        identities.extend(
+            getgroups()
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|gid| Group::from_gid(gid).ok().flatten())
+                .map(|group| format!("group:{}", group.name)),
+        );
+        Self { name, identities }
+    }
+
+    /// Principal for the API token validated with a request, else its mTLS client certificate
+    pub fn from_request<B>(request: &Request<B>) -> Self {
+        if let Some(claims) = request.extensions().get::<TokenClaims>() {
+            return scope_principal(&claims.sub, &claims.scopes);
+        }
+        let Some(fingerprint) = peer_fingerprint(request) else {
+            return Self::anonymous();
+        };
+
+        let mut identities = vec![format!("cert:{}", fingerprint)];
+        let common_name = request
+            .extensions()
+            .get::<tonic::transport::server::TlsConnectInfo<tonic::transport::server::TcpConnectInfo>>()
+            .and_then(|info| info.peer_certs())
+            .and_then(|certs| certs.first().and_then(|leaf| common_name(leaf.as_ref())));
+        if let Some(cn) = &common_name {
+            identities.push(format!("cn:{}", cn));
+        }
+
+        Self {
+            name: common_name.unwrap_or(fingerprint),
+            identities,
+        }
+    }
+}
+
+/// Evaluates principals against the RBAC policy
+#[derive(Debug)]
+pub struct RbacEngine {
+    policy: RwLock<RbacPolicy>,
+    policy_path: Option<PathBuf>,
+}
+
+static RBAC: Lazy<Arc<RbacEngine>> = Lazy::new(|| {
+    let path = Path::new(DEFAULT_POLICY_PATH);
+    if !path.exists() {
+        return Arc::new(RbacEngine::new(RbacPolicy::default()));
+    }
+    match RbacEngine::load(path) {
+        Ok(engine) => Arc::new(engine),
+        Err(e) => {
+            warn!(error = %e, "Invalid RBAC policy, using built-in roles");
+            Arc::new(RbacEngine::new(RbacPolicy::default()))
+        }
+    }
+});
+
+/// Returns the process-wide RBAC engine
+pub fn rbac() -> Arc<RbacEngine> {
+    Arc::clone(&RBAC)
+}
+
+impl RbacEngine {
+    /// Creates an engine enforcing the given policy
+    pub fn new(policy: RbacPolicy) -> Self {
+        Self {
+            policy: RwLock::new(policy),
+            policy_path: None,
+        }
+    }
+
+    /// Creates an engine from a JSON policy file
+    pub fn load(path: &Path) -> Result<Self, GuardianError> {
+        let policy = read_policy(path)?;
+        Ok(Self {
+            policy: RwLock::new(policy),
+            policy_path: Some(path.to_path_buf()),
+        })
+    }
+
+    /// Re-reads the policy file, keeping the current policy if the new one is invalid
+    #[instrument(skip(self))]
+    pub fn reload(&self) -> Result<(), GuardianError> {
+        let Some(path) = &self.policy_path else {
+            return Ok(());
+        };
+        let policy = read_policy(path)?;
+        *self.policy.write() = policy;
+        info!(path = %path.display(), "RBAC policy reloaded");
+        Ok(())
+    }
+
+    /// Returns the roles bound to a principal, including inherited ones
+    pub fn roles_for(&self, principal: &Principal) -> BTreeSet<String> {
+        let policy = self.policy.read();
+        let tenant = policy.tenant_of(principal).map(|(_, tenant)| tenant);
+        let bound = principal
+            .identities
+            .iter()
+            .flat_map(|identity| {
+                let scoped = tenant.and_then(|tenant| tenant.bindings.get(identity));
+                policy.bindings.get(identity).into_iter().chain(scoped)
+            })
+            .flatten()
+            .cloned()
+            .collect::<Vec<_>>();
+        policy.expand(tenant, bound)
+    }
+
+    /// Returns the tenant whose data a principal may see
+    pub fn tenant_for(&self, principal: &Principal) -> TenantContext {
+        let policy = self.policy.read();
+        policy
+            .tenant_of(principal)
+            .map(|(tenant_id, _)| TenantContext::new(tenant_id.clone()))
+            .unwrap_or_default()
+    }
+
+    /// Returns whether any of the principal's roles grants the permission
+    pub fn is_allowed(&self, principal: &Principal, permission: &str) -> bool {
+        let roles = self.roles_for(principal);
+        let policy = self.policy.read();
+        let tenant = policy.tenant_of(principal).map(|(_, tenant)| tenant);
+        roles
+            .iter()
+            .filter_map(|name| policy.role(tenant, name))
+            .flat_map(|role| role.permissions.iter())
+            .any(|pattern| permits(pattern, permission))
+    }
+
+    /// Authorizes a permission, logging every denial and recording it in the audit chain
+    pub fn authorize(&self, principal: &Principal, permission: &str) -> Result<(), GuardianError> {
+        if self.is_allowed(principal, permission) {
+            return Ok(());
+        }
+
+        let enforced = active_profile().enforcement != EnforcementMode::Relaxed;
+        counter!("guardian.rbac.denials", 1, "enforced" => enforced.to_string());
+        warn!(
+            target: "SECURITY-AUDIT",
+            principal = %principal.name,
+            identities = ?principal.identities,
+            permission,
+            enforced,
+            "Access denied"
+        );
+        audit_denial(principal, permission, enforced);
+
+        if !enforced {
+            return Ok(());
+        }
+        Err(rbac_error(
+            format!("{} is not permitted to {}", principal.name, permission),
+            None,
+        ))
+    }
+}
+
+/// Records a denial in the audit chain, in the background so authorization never waits on it
+fn audit_denial(principal: &Principal, permission: &str, enforced: bool) {
+    let (Some(logger), Ok(handle)) = (audit_logger(), tokio::runtime::Handle::try_current()) else {
+        return;
+    };
+    let correlation_id = crate::utils::correlation::current_or_new();
+    let event = AuditEvent::new(
+        "rbac.access_denied".into(),
+        if enforced { SecurityLevel::High } else { SecurityLevel::Medium },
+        "rbac".into(),
+        Some(correlation_id.to_string()),
+    )
+    .with_data(serde_json::json!({
+        "principal": principal.name,
+        "identities": principal.identities,
+        "method": permission.split_once(':').map_or(permission, |(_, method)| method),
+        "permission": permission,
+        "enforced": enforced,
+    }));
+    handle.spawn(crate::utils::correlation::scope(correlation_id, async move {
+        let recorded = match event {
+            Ok(event) => logger.record_event(event).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = recorded {
+            warn!(error = %e, "Failed to audit access denial");
+        }
+    }));
+}
+
+/// Returns whether a permission pattern covers a permission
+fn permits(pattern: &str, permission: &str) -> bool {
+    if pattern == WILDCARD || pattern == permission {
+        return true;
+    }
+    if let Some(prefix) = pattern.strip_suffix('*') {
+        return permission.starts_with(prefix);
+    }
+    permission
+        .strip_prefix(pattern)
+        .map_or(false, |rest| rest.starts_with(':'))
+}
+
+/// Permission name for a gRPC request path
+pub fn rpc_permission(path: &str) -> String {
+    format!("rpc:{}", path.trim_start_matches('/'))
+}
+
+/// Permission name for a CLI command and optional subcommand
+pub fn cli_permission(command: &str, subcommand: Option<&str>) -> String {
+    match subcommand {
+        Some(sub) => format!("cli:{}:{}", command, sub),
+        None => format!("cli:{}", command),
+    }
+}
+
+/// Whether a gRPC request path is served without authorization, auditing or quotas
+pub fn is_unauthenticated_rpc(path: &str) -> bool {
+    path.starts_with(UNAUTHENTICATED_RPC_PREFIX)
+}
+
+/// Tenant of the caller, as resolved by `RbacLayer`; unauthenticated paths get the default tenant
+pub fn request_tenant<T>(request: &tonic::Request<T>) -> TenantContext {
+    request.extensions().get::<TenantContext>().cloned().unwrap_or_default()
+}
+
+/// gRPC PERMISSION_DENIED response for requests rejected before reaching a service
+pub(crate) fn permission_denied<B: Default>(message: &str) -> Response<B> {
+    let mut response = Response::new(B::default());
+    let headers = response.headers_mut();
+    headers.insert("content-type", HeaderValue::from_static("application/grpc"));
+    headers.insert("grpc-status", HeaderValue::from(tonic::Code::PermissionDenied as i32));
+    if let Ok(message) = HeaderValue::from_str(&message.replace(' ', "%20")) {
+        headers.insert("grpc-message", message);
+    }
+    response
+}
+
+/// Tower layer authorizing every gRPC call against the RBAC policy
+#[derive(Debug, Clone)]
+pub struct RbacLayer {
+    engine: Arc<RbacEngine>,
+}
+
+impl RbacLayer {
+    /// Creates a layer consulting the given engine
+    pub fn new(engine: Arc<RbacEngine>) -> Self {
+        Self { engine }
+    }
+}
+
+impl<S> tower::Layer<S> for RbacLayer {
+    type Service = RbacService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RbacService {
+            inner,
+            engine: Arc::clone(&self.engine),
+        }
+    }
+}
+
+/// Service created by `RbacLayer`
+#[derive(Debug, Clone)]
+pub struct RbacService<S> {
+    inner: S,
+    engine: Arc<RbacEngine>,
+}
+
+impl<S, ReqBody, ResBody> tower::Service<Request<ReqBody>> for RbacService<S>
+where
+    S: tower::Service<Request<ReqBody>, Response = Response<ResBody>>,
+    S::Future: Send + 'static,
+    ResBody: Default,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: Request<ReqBody>) -> Self::Future {
+        // Probes arrive every few seconds per load balancer and would drown the command audit
+        if is_unauthenticated_rpc(request.uri().path()) {
+            return Box::pin(self.inner.call(request));
+        }
+
+        let started = std::time::Instant::now();
+        let principal = Principal::from_request(&request);
+        let mut invocation = CommandInvocation {
+            interface: Interface::Api,
+            command: request.uri().path().to_string(),
+            principal: principal.name.clone(),
+            // Request bodies are not decoded at this layer, so API calls are audited without arguments
+            args: BTreeMap::new(),
+            decision: AccessDecision::Allowed,
+            error: None,
+            duration: std::time::Duration::ZERO,
+        };
+
+        // Vendor sessions are scoped by remote assistance rather than role bindings
+        if request.extensions().get::<RemoteAssistanceGrant>().is_none() {
+            let permission = rpc_permission(request.uri().path());
+            if self.engine.authorize(&principal, &permission).is_err() {
+                invocation.decision = AccessDecision::Denied;
+                invocation.duration = started.elapsed();
+                command_audit().record(invocation);
+                return Box::pin(async move { Ok(permission_denied("Permission denied")) });
+            }
+            let tenant = self.engine.tenant_for(&principal);
+            request.extensions_mut().insert(tenant);
+        }
+
+        let future = self.inner.call(request);
+        Box::pin(async move {
+            let result = future.await;
+            // Errors are carried in headers only when the call failed before streaming a response
+            invocation.error = match &result {
+                Ok(response) => response
+                    .headers()
+                    .get("grpc-status")
+                    .and_then(|status| status.to_str().ok())
+                    .filter(|status| *status != "0")
+                    .map(|status| format!("grpc-status {}", status)),
+                Err(_) => Some("transport error".into()),
+            };
+            invocation.duration = started.elapsed();
+            command_audit().record(invocation);
+            result
+        })
+    }
+}
+
+fn common_name(der: &[u8]) -> Option<String> {
+    let (_, cert) = x509_parser::parse_x509_certificate(der).ok()?;
+    let cn = cert.subject().iter_common_name().next()?.as_str().ok()?.to_string();
+    Some(cn)
+}
+
+fn read_policy(path: &Path) -> Result<RbacPolicy, GuardianError> {
+    let raw = std::fs::read(path)
+        .map_err(|e| rbac_error(format!("Failed to read RBAC policy {}", path.display()), Some(Box::new(e))))?;
+    let policy: RbacPolicy = serde_json::from_slice(&raw)
+        .map_err(|e| rbac_error(format!("Invalid RBAC policy {}", path.display()), Some(Box::new(e))))?;
+    policy.validate()?;
+    Ok(policy)
+}
+
+fn rbac_error(context: String, source: Option<Box<dyn std::error::Error + Send + Sync>>) -> GuardianError {
+    SecurityError {
+        context,
+        source,
+        severity: crate::utils::error::ErrorSeverity::High,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: crate::utils::error::ErrorCategory::Security,
+        retry_count: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn principal(identities: &[&str]) -> Principal {
+        Principal {
+            name: "test".into(),
+            identities: identities.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_permission_patterns() {
+        assert!(permits("*", "cli:backup:create"));
+        assert!(permits("cli:ops", "cli:ops"));
+        assert!(permits("cli:ops", "cli:ops:cancel"));
+        assert!(!permits("cli:ops", "cli:opsx"));
+        assert!(permits("rpc:guardian.ml.v1.MLService/*", "rpc:guardian.ml.v1.MLService/TrainModel"));
+        assert_eq!(
+            rpc_permission("/guardian.core.v1.GuardianService/GetSystemStatus"),
+            "rpc:guardian.core.v1.GuardianService/GetSystemStatus"
+        );
+        assert!(is_unauthenticated_rpc("/grpc.health.v1.Health/Check"));
+        assert!(!is_unauthenticated_rpc("/grpc.reflection.v1alpha.ServerReflection/ServerReflectionInfo"));
+    }
+
+    #[test]
+    fn test_default_policy_roles() {
+        let engine = RbacEngine::new(RbacPolicy::default());
+        let security = principal(&["user:alice", "group:guardian-security"]);
+        let operator = principal(&["group:guardian-operator"]);
+
+        // Security inherits operator permissions
+        assert!(engine.roles_for(&security).contains("operator"));
+        assert!(engine.is_allowed(&security, &cli_permission("ops", Some("list"))));
+        assert!(engine.is_allowed(&security, "rpc:guardian.security.v1.SecurityService/ExecuteResponse"));
+
+        assert!(engine.is_allowed(&operator, &cli_permission("status", None)));
+        assert!(!engine.is_allowed(&operator, &cli_permission("backup", Some("create"))));
+        assert!(engine.authorize(&operator, "rpc:guardian.core.v1.GuardianService/ManageComponents").is_err());
+        assert!(!engine.is_allowed(&Principal::anonymous(), &cli_permission("status", None)));
+
+        let mut policy = RbacPolicy::default();
+        policy.bindings.insert("user:bob".into(), vec!["auditor".into()]);
+        assert!(policy.validate().is_err());
+    }
+
+    #[test]
+    fn test_tenant_policies() {
+        let acme = TenantId::new("acme").unwrap();
+        let mut policy = RbacPolicy::default();
+        policy.tenants.insert(acme.clone(), TenantPolicy {
+            members: vec!["group:acme".into()],
+            roles: BTreeMap::from([(
+                "operator".to_string(),
+                Role { inherits: Vec::new(), permissions: vec!["cli:status".into()] },
+            )]),
+            bindings: BTreeMap::from([("user:carol".to_string(), vec!["operator".to_string()])]),
+        });
+        policy.validate().unwrap();
+        let engine = RbacEngine::new(policy.clone());
+
+        // Tenant bindings only apply to members, and tenant roles shadow global ones
+        let carol = principal(&["user:carol", "group:acme"]);
+        assert_eq!(engine.tenant_for(&carol).tenant_id(), &acme);
+        assert!(engine.is_allowed(&carol, &cli_permission("status", None)));
+        assert!(!engine.is_allowed(&carol, &cli_permission("ops", Some("list"))));
+
+        let outsider = principal(&["user:carol"]);
+        assert!(engine.tenant_for(&outsider).is_default());
+        assert!(!engine.is_allowed(&outsider, &cli_permission("status", None)));
+
+        let mut overlapping = policy;
+        overlapping.tenants.insert(TenantId::new("globex").unwrap(), TenantPolicy {
+            members: vec!["group:acme".into()],
+            ..Default::default()
+        });
+        assert!(overlapping.validate().is_err());
+    }
+}
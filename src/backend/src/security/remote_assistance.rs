@@ -0,0 +1,592 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    future::Future,
+    io::Write,
+    path::PathBuf,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use http::{HeaderValue, Request, Response, StatusCode};
+use metrics::{counter, gauge};
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::security::rbac::permission_denied;
+use crate::utils::error::{GuardianError, SecurityError};
+use crate::utils::ids::{next_id, IdKind};
+
+// Constants for remote assistance sessions
+pub const REMOTE_ASSISTANCE_HEADER: &str = "x-guardian-remote-assistance";
+const DEFAULT_SESSION_DURATION: Duration = Duration::from_secs(60 * 60);
+const MAX_SESSION_DURATION: Duration = Duration::from_secs(4 * 60 * 60);
+const MAX_RECORDED_REQUESTS: usize = 4096;
+const GUARDIAN_SERVICE: &str = "/guardian.core.v1.GuardianService/";
+const SECURITY_SERVICE: &str = "/guardian.security.v1.SecurityService/";
+const ML_SERVICE: &str = "/guardian.ml.v1.MLService/";
+
+/// API access a vendor may be granted during a remote assistance session
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AssistanceScope {
+    /// Read-only status, health, metrics and operation queries
+    Diagnostics,
+    /// Read-only security status and threat streams
+    SecurityRead,
+    /// Cancelling operations and managing components
+    Operations,
+}
+
+impl AssistanceScope {
+    /// Returns the gRPC methods this scope permits
+    fn methods(&self) -> &'static [&'static str] {
+        match self {
+            Self::Diagnostics => &[
+                "GetSystemStatus",
+                "PerformHealthCheck",
+                "StreamMetrics",
+                "UpdateMetricsWindow",
+                "MonitorMetrics",
+                "ListOperations",
+                "GetOperation",
+                "GetModelStatus",
+            ],
+            Self::SecurityRead => &["GetSecurityStatus", "MonitorThreats", "ValidateSystemIntegrity"],
+            Self::Operations => &["CancelOperation", "ManageComponents"],
+        }
+    }
+
+    /// Returns whether the scope permits the gRPC path `/package.Service/Method`
+    pub fn allows(&self, path: &str) -> bool {
+        let method = [GUARDIAN_SERVICE, SECURITY_SERVICE, ML_SERVICE]
+            .iter()
+            .find_map(|service| path.strip_prefix(service));
+        method.map_or(false, |method| self.methods().contains(&method))
+    }
+}
+
+impl std::str::FromStr for AssistanceScope {
+    type Err = GuardianError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "diagnostics" => Ok(Self::Diagnostics),
+            "security-read" => Ok(Self::SecurityRead),
+            "operations" => Ok(Self::Operations),
+            other => Err(assistance_error(format!("Unknown assistance scope: {}", other))),
+        }
+    }
+}
+
+/// Time-boxed grant of API access to a vendor client certificate
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssistanceSession {
+    pub id: String,
+    /// Hex SHA-256 fingerprint of the vendor's mTLS client certificate
+    pub vendor_fingerprint: String,
+    pub scopes: HashSet<AssistanceScope>,
+    pub enabled_by: String,
+    pub reason: String,
+    pub started_at: u64,
+    pub expires_at: u64,
+    pub revoked: bool,
+}
+
+impl AssistanceSession {
+    /// Returns whether the session is usable at the given unix time
+    pub fn is_active(&self, now: u64) -> bool {
+        !self.revoked && now < self.expires_at
+    }
+
+    /// Text shown on consoles and dashboards while the session is active
+    pub fn banner(&self) -> String {
+        format!(
+            "REMOTE ASSISTANCE ACTIVE: session {} for vendor {} enabled by {} until {} ({})",
+            self.id,
+            short_fingerprint(&self.vendor_fingerprint),
+            self.enabled_by,
+            format_unix(self.expires_at),
+            self.reason,
+        )
+    }
+}
+
+/// A single vendor request captured for the session recording
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedRequest {
+    pub session_id: String,
+    pub at: u64,
+    pub path: String,
+    pub allowed: bool,
+    pub status: Option<u16>,
+    pub duration_ms: u64,
+    pub correlation_id: String,
+}
+
+/// Configuration for remote assistance
+#[derive(Debug, Clone)]
+pub struct RemoteAssistanceConfig {
+    /// Fingerprints of client certificates issued to vendors
+    pub vendor_fingerprints: HashSet<String>,
+    pub default_duration: Duration,
+    pub max_duration: Duration,
+    /// Directory receiving a JSONL recording per session
+    pub recording_dir: Option<PathBuf>,
+}
+
+impl Default for RemoteAssistanceConfig {
+    fn default() -> Self {
+        Self {
+            vendor_fingerprints: HashSet::new(),
+            default_duration: DEFAULT_SESSION_DURATION,
+            max_duration: MAX_SESSION_DURATION,
+            recording_dir: Some(PathBuf::from("/var/lib/guardian/remote-assistance")),
+        }
+    }
+}
+
+/// Decision for a request arriving over mTLS
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AccessDecision {
+    /// Not a vendor certificate; regular authentication applies
+    NotVendor,
+    /// Vendor request permitted by the given session
+    Allowed(String),
+    /// Vendor request outside any active session or its scopes
+    Denied(Option<String>),
+}
+
+/// Manages break-glass remote assistance sessions
+#[derive(Debug)]
+pub struct RemoteAssistanceManager {
+    config: RwLock<RemoteAssistanceConfig>,
+    sessions: RwLock<HashMap<String, AssistanceSession>>,
+    recordings: RwLock<HashMap<String, VecDeque<RecordedRequest>>>,
+}
+
+static REMOTE_ASSISTANCE: Lazy<Arc<RemoteAssistanceManager>> =
+    Lazy::new(|| Arc::new(RemoteAssistanceManager::new(RemoteAssistanceConfig::default())));
+
+/// Returns the process-wide remote assistance manager
+pub fn remote_assistance() -> Arc<RemoteAssistanceManager> {
+    Arc::clone(&REMOTE_ASSISTANCE)
+}
+
+impl RemoteAssistanceManager {
+    /// Creates a manager with no sessions
+    pub fn new(config: RemoteAssistanceConfig) -> Self {
+        Self {
+            config: RwLock::new(config),
+            sessions: RwLock::new(HashMap::new()),
+            recordings: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Replaces the configuration, leaving existing sessions untouched
+    pub fn configure(&self, config: RemoteAssistanceConfig) {
+        *self.config.write() = config;
+    }
+
+    /// Opens a session for a registered vendor certificate
+    pub fn enable(
+        &self,
+        enabled_by: &str,
+        vendor_fingerprint: &str,
+        scopes: HashSet<AssistanceScope>,
+        duration: Option<Duration>,
+        reason: &str,
+    ) -> Result<AssistanceSession, GuardianError> {
+        let config = self.config.read().clone();
+        let vendor_fingerprint = normalize_fingerprint(vendor_fingerprint);
+        if !config.vendor_fingerprints.iter().any(|f| normalize_fingerprint(f) == vendor_fingerprint) {
+            return Err(assistance_error(format!(
+                "Certificate {} is not a registered vendor identity",
+                short_fingerprint(&vendor_fingerprint)
+            )));
+        }
+
+        let duration = duration.unwrap_or(config.default_duration);
+        if duration.is_zero() || duration > config.max_duration {
+            return Err(assistance_error(format!(
+                "Session duration must be between 1s and {}s",
+                config.max_duration.as_secs()
+            )));
+        }
+
+        let scopes = if scopes.is_empty() {
+            HashSet::from([AssistanceScope::Diagnostics])
+        } else {
+            scopes
+        };
+
+        let now = unix_now();
+        let session = AssistanceSession {
+            id: next_id(IdKind::Operation).to_string(),
+            vendor_fingerprint,
+            scopes,
+            enabled_by: enabled_by.to_string(),
+            reason: reason.to_string(),
+            started_at: now,
+            expires_at: now + duration.as_secs(),
+            revoked: false,
+        };
+
+        self.sessions.write().insert(session.id.clone(), session.clone());
+        info!(
+            target: "SECURITY-AUDIT",
+            session_id = %session.id,
+            vendor = %session.vendor_fingerprint,
+            enabled_by,
+            scopes = ?session.scopes,
+            expires_at = session.expires_at,
+            "{}", session.banner()
+        );
+        counter!("guardian.remote_assistance.sessions_enabled", 1);
+        self.update_gauge();
+        Ok(session)
+    }
+
+    /// Ends a session before its expiry
+    pub fn revoke(&self, session_id: &str, revoked_by: &str) -> Result<AssistanceSession, GuardianError> {
+        let session = {
+            let mut sessions = self.sessions.write();
+            let session = sessions
+                .get_mut(session_id)
+                .ok_or_else(|| assistance_error(format!("Unknown assistance session: {}", session_id)))?;
+            session.revoked = true;
+            session.clone()
+        };
+
+        info!(target: "SECURITY-AUDIT", session_id, revoked_by, "Remote assistance session revoked");
+        counter!("guardian.remote_assistance.sessions_revoked", 1);
+        self.update_gauge();
+        Ok(session)
+    }
+
+    /// Returns sessions that are currently usable
+    pub fn active(&self) -> Vec<AssistanceSession> {
+        let now = unix_now();
+        self.sessions.read().values().filter(|s| s.is_active(now)).cloned().collect()
+    }
+
+    /// Returns the banner of every active session
+    pub fn banners(&self) -> Vec<String> {
+        self.active().iter().map(AssistanceSession::banner).collect()
+    }
+
+    /// Drops expired and revoked sessions, returning their IDs
+    pub fn expire(&self) -> Vec<String> {
+        let now = unix_now();
+        let mut expired = Vec::new();
+        self.sessions.write().retain(|id, session| {
+            let keep = session.is_active(now);
+            if !keep {
+                expired.push(id.clone());
+            }
+            keep
+        });
+
+        for id in &expired {
+            info!(target: "SECURITY-AUDIT", session_id = %id, "Remote assistance session ended");
+        }
+        if !expired.is_empty() {
+            self.update_gauge();
+        }
+        expired
+    }
+
+    /// Spawns a task expiring sessions on the given interval
+    pub fn spawn_expiry(self: &Arc<Self>, interval: Duration) -> tokio::task::JoinHandle<()> {
+        let manager = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                manager.expire();
+            }
+        })
+    }
+
+    /// Decides whether a request presenting the given certificate may reach `path`
+    pub fn authorize(&self, fingerprint: &str, path: &str) -> AccessDecision {
+        let fingerprint = normalize_fingerprint(fingerprint);
+        let is_vendor = self
+            .config
+            .read()
+            .vendor_fingerprints
+            .iter()
+            .any(|f| normalize_fingerprint(f) == fingerprint);
+        if !is_vendor {
+            return AccessDecision::NotVendor;
+        }
+
+        let now = unix_now();
+        let sessions = self.sessions.read();
+        let mut matching = sessions
+            .values()
+            .filter(|s| s.vendor_fingerprint == fingerprint && s.is_active(now))
+            .peekable();
+
+        let session_id = matching.peek().map(|s| s.id.clone());
+        if matching.any(|s| s.scopes.iter().any(|scope| scope.allows(path))) {
+            return AccessDecision::Allowed(session_id.unwrap_or_default());
+        }
+        AccessDecision::Denied(session_id)
+    }
+
+    /// Appends a vendor request to its session recording
+    pub fn record(&self, request: RecordedRequest) {
+        info!(
+            target: "SECURITY-AUDIT",
+            session_id = %request.session_id,
+            path = %request.path,
+            allowed = request.allowed,
+            status = ?request.status,
+            "Remote assistance request"
+        );
+        counter!("guardian.remote_assistance.requests", 1, "allowed" => request.allowed.to_string());
+
+        if let Some(dir) = self.config.read().recording_dir.clone() {
+            if let Err(e) = append_recording(&dir, &request) {
+                warn!(session_id = %request.session_id, error = %e, "Failed to persist session recording");
+            }
+        }
+
+        let mut recordings = self.recordings.write();
+        let recording = recordings.entry(request.session_id.clone()).or_default();
+        if recording.len() == MAX_RECORDED_REQUESTS {
+            recording.pop_front();
+        }
+        recording.push_back(request);
+    }
+
+    /// Returns the in-memory recording of a session
+    pub fn recording(&self, session_id: &str) -> Vec<RecordedRequest> {
+        self.recordings
+            .read()
+            .get(session_id)
+            .map(|r| r.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    fn update_gauge(&self) {
+        gauge!("guardian.remote_assistance.active_sessions", self.active().len() as f64);
+    }
+}
+
+/// Tower layer enforcing remote assistance scopes on vendor mTLS requests
+#[derive(Debug, Clone)]
+pub struct RemoteAssistanceLayer {
+    manager: Arc<RemoteAssistanceManager>,
+}
+
+impl RemoteAssistanceLayer {
+    /// Creates a layer consulting the given manager
+    pub fn new(manager: Arc<RemoteAssistanceManager>) -> Self {
+        Self { manager }
+    }
+}
+
+impl<S> tower::Layer<S> for RemoteAssistanceLayer {
+    type Service = RemoteAssistanceService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RemoteAssistanceService {
+            inner,
+            manager: Arc::clone(&self.manager),
+        }
+    }
+}
+
+/// Service created by `RemoteAssistanceLayer`
+#[derive(Debug, Clone)]
+pub struct RemoteAssistanceService<S> {
+    inner: S,
+    manager: Arc<RemoteAssistanceManager>,
+}
+
+impl<S, ReqBody, ResBody> tower::Service<Request<ReqBody>> for RemoteAssistanceService<S>
+where
+    S: tower::Service<Request<ReqBody>, Response = Response<ResBody>>,
+    S::Future: Send + 'static,
+    ResBody: Default,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: Request<ReqBody>) -> Self::Future {
+        let path = request.uri().path().to_string();
+        let banner = self.manager.banners().first().and_then(|b| HeaderValue::from_str(b).ok());
+        let decision = match peer_fingerprint(&request) {
+            Some(fingerprint) => self.manager.authorize(&fingerprint, &path),
+            None => AccessDecision::NotVendor,
+        };
+
+        let session_id = match decision {
+            AccessDecision::NotVendor => None,
+            AccessDecision::Allowed(session_id) => Some(session_id),
+            AccessDecision::Denied(session_id) => {
+                self.manager.record(RecordedRequest {
+                    session_id: session_id.unwrap_or_else(|| "none".into()),
+                    at: unix_now(),
+                    path,
+                    allowed: false,
+                    status: Some(StatusCode::FORBIDDEN.as_u16()),
+                    duration_ms: 0,
+                    correlation_id: crate::utils::correlation::current_or_new().to_string(),
+                });
+                return Box::pin(async move { Ok(permission_denied("Outside remote assistance scope")) });
+            }
+        };
+
+        // Downstream authorization defers to the session scope for vendor requests
+        if let Some(session_id) = &session_id {
+            request.extensions_mut().insert(RemoteAssistanceGrant { session_id: session_id.clone() });
+        }
+
+        let manager = Arc::clone(&self.manager);
+        let start = Instant::now();
+        let future = self.inner.call(request);
+        Box::pin(async move {
+            let result = future.await;
+            if let Some(session_id) = session_id {
+                manager.record(RecordedRequest {
+                    session_id,
+                    at: unix_now(),
+                    path,
+                    allowed: true,
+                    status: result.as_ref().ok().map(|r| r.status().as_u16()),
+                    duration_ms: start.elapsed().as_millis() as u64,
+                    correlation_id: crate::utils::correlation::current_or_new().to_string(),
+                });
+            }
+
+            // Every caller sees the banner so operator UIs can surface the session
+            let mut response = result?;
+            if let Some(banner) = banner {
+                response.headers_mut().insert(REMOTE_ASSISTANCE_HEADER, banner);
+            }
+            Ok(response)
+        })
+    }
+}
+
+/// Request extension marking a vendor request authorized by a remote assistance session
+#[derive(Debug, Clone)]
+pub struct RemoteAssistanceGrant {
+    pub session_id: String,
+}
+
+/// Returns the SHA-256 fingerprint of the client certificate presented over mTLS
+pub(crate) fn peer_fingerprint<B>(request: &Request<B>) -> Option<String> {
+    let info = request
+        .extensions()
+        .get::<tonic::transport::server::TlsConnectInfo<tonic::transport::server::TcpConnectInfo>>()?;
+    let certs = info.peer_certs()?;
+    let leaf = certs.first()?;
+    Some(to_hex(ring::digest::digest(&ring::digest::SHA256, leaf.as_ref()).as_ref()))
+}
+
+fn append_recording(dir: &std::path::Path, request: &RecordedRequest) -> std::io::Result<()> {
+    std::fs::create_dir_all(dir)?;
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(dir.join(format!("{}.jsonl", request.session_id)))?;
+    let line = serde_json::to_string(request).map_err(std::io::Error::other)?;
+    writeln!(file, "{}", line)
+}
+
+fn normalize_fingerprint(fingerprint: &str) -> String {
+    fingerprint
+        .chars()
+        .filter(|c: &char| c.is_ascii_hexdigit())
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+fn short_fingerprint(fingerprint: &str) -> &str {
+    fingerprint.get(..16).unwrap_or(fingerprint)
+}
+
+fn format_unix(secs: u64) -> String {
+    time::OffsetDateTime::from_unix_timestamp(secs as i64)
+        .ok()
+        .and_then(|t| t.format(&time::format_description::well_known::Rfc3339).ok())
+        .unwrap_or_else(|| secs.to_string())
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or_default()
+}
+
+fn assistance_error(context: String) -> GuardianError {
+    SecurityError {
+        context,
+        source: None,
+        severity: crate::utils::error::ErrorSeverity::High,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: crate::utils::error::ErrorCategory::Security,
+        retry_count: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VENDOR: &str = "AB:CD:EF:01:23:45:67:89:AB:CD:EF:01:23:45:67:89";
+
+    fn manager() -> RemoteAssistanceManager {
+        RemoteAssistanceManager::new(RemoteAssistanceConfig {
+            vendor_fingerprints: HashSet::from([VENDOR.to_string()]),
+            recording_dir: None,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_scopes_default_to_read_only_diagnostics() {
+        let manager = manager();
+        let status = "/guardian.core.v1.GuardianService/GetSystemStatus";
+        let respond = "/guardian.security.v1.SecurityService/ExecuteResponse";
+
+        assert_eq!(manager.authorize(VENDOR, status), AccessDecision::Denied(None));
+        assert_eq!(manager.authorize("00ff", status), AccessDecision::NotVendor);
+
+        let session = manager.enable("admin", VENDOR, HashSet::new(), None, "ticket 4711").unwrap();
+        assert_eq!(session.scopes, HashSet::from([AssistanceScope::Diagnostics]));
+        assert_eq!(manager.authorize(VENDOR, status), AccessDecision::Allowed(session.id.clone()));
+        assert_eq!(manager.authorize(VENDOR, respond), AccessDecision::Denied(Some(session.id)));
+
+        assert!(manager.enable("admin", "00ff", HashSet::new(), None, "unknown").is_err());
+        assert!(manager
+            .enable("admin", VENDOR, HashSet::new(), Some(MAX_SESSION_DURATION * 2), "too long")
+            .is_err());
+    }
+
+    #[test]
+    fn test_revoked_sessions_expire() {
+        let manager = manager();
+        let session = manager.enable("admin", VENDOR, HashSet::new(), None, "ticket 4711").unwrap();
+        assert_eq!(manager.banners().len(), 1);
+
+        manager.revoke(&session.id, "admin").unwrap();
+        assert!(manager.active().is_empty());
+        assert_eq!(manager.expire(), vec![session.id]);
+        assert!(manager.revoke("missing", "admin").is_err());
+    }
+}
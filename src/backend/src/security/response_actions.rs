@@ -0,0 +1,479 @@
+use std::{fmt, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use metrics::gauge;
+use parking_lot::RwLock;
+use serde::{de::DeserializeOwned, Deserialize};
+use tracing::{debug, error, info, instrument, warn};
+
+use crate::security::firewall::{firewall, normalize_address};
+use crate::security::isolation::process_isolator;
+use crate::security::response_engine::{ResponseAction, ThreatAnalysis};
+use crate::utils::error::{GuardianError, SecurityError};
+
+// Constants for response action handlers
+pub(crate) const BUILTIN_RESPONSE_WORKFLOW: &str = "execute_response";
+const BUILTIN_ACTIONS: [&str; 4] = [
+    "isolate_process",
+    "terminate_process",
+    "block_network",
+    "emergency_shutdown",
+];
+const MAX_HANDLERS: usize = 64;
+const MAX_BLOCK_DURATION: Duration = Duration::from_secs(86400);
+
+/// Carries out one type of response action, such as process isolation or credential revocation
+#[async_trait]
+pub trait ResponseActionHandler: Send + Sync + fmt::Debug {
+    /// Unique action name, referenced by `ResponseAction::name`
+    fn name(&self) -> &str;
+
+    /// Temporal workflow type that executes the action
+    fn workflow_type(&self) -> &str;
+
+    /// Rejects unsafe or malformed parameters before the action is executed
+    async fn validate(&self, parameters: &serde_json::Value) -> Result<(), GuardianError>;
+
+    /// Executes the action in-process, used while Temporal is unreachable
+    async fn execute(&self, _parameters: &serde_json::Value) -> Result<(), GuardianError> {
+        Err(action_error(format!("Response action {} requires Temporal", self.name())))
+    }
+
+    /// Confirms the action took effect on the host
+    async fn verify(&self, _parameters: &serde_json::Value) -> Result<bool, GuardianError> {
+        Ok(true)
+    }
+
+    /// Reports the state the action left on the host, published with the response outcome
+    async fn status(&self, _parameters: &serde_json::Value) -> Result<Option<serde_json::Value>, GuardianError> {
+        Ok(None)
+    }
+
+    /// Returns whether `revert` can undo the action
+    fn reversible(&self) -> bool {
+        false
+    }
+
+    /// Undoes a previously executed action
+    async fn revert(&self, _parameters: &serde_json::Value) -> Result<(), GuardianError> {
+        Err(action_error(format!("Response action {} cannot be reverted", self.name())))
+    }
+
+    /// Proposes parameters when this action should handle the threat
+    fn select(&self, _analysis: &ThreatAnalysis) -> Option<serde_json::Value> {
+        None
+    }
+
+    /// Overrides the engine's workflow execution timeout
+    fn timeout(&self) -> Option<Duration> {
+        None
+    }
+}
+
+/// Registry of response action handlers, with custom handlers consulted in registration order
+#[derive(Debug)]
+pub struct ResponseActionRegistry {
+    handlers: RwLock<Vec<Arc<dyn ResponseActionHandler>>>,
+}
+
+impl Default for ResponseActionRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ResponseActionRegistry {
+    /// Creates a registry holding the built-in handlers
+    pub fn new() -> Self {
+        let builtins: Vec<Arc<dyn ResponseActionHandler>> = vec![
+            Arc::new(IsolateProcess),
+            Arc::new(TerminateProcess),
+            Arc::new(BlockNetwork),
+            Arc::new(EmergencyShutdown),
+        ];
+        Self {
+            handlers: RwLock::new(builtins),
+        }
+    }
+
+    /// Registers a handler, rejecting duplicate and built-in action names
+    #[instrument(skip(self, handler), fields(action = handler.name()))]
+    pub fn register(&self, handler: Arc<dyn ResponseActionHandler>) -> Result<(), GuardianError> {
+        let name = handler.name().to_string();
+        let mut handlers = self.handlers.write();
+
+        let rejection = if name.is_empty() {
+            Some("Response action name cannot be empty".to_string())
+        } else if BUILTIN_ACTIONS.contains(&name.as_str()) {
+            Some(format!("Response action {} is built in", name))
+        } else if handlers.iter().any(|h| h.name() == name) {
+            Some(format!("Response action {} is already registered", name))
+        } else if handlers.len() >= MAX_HANDLERS + BUILTIN_ACTIONS.len() {
+            Some(format!("Response action limit of {} reached", MAX_HANDLERS))
+        } else {
+            None
+        };
+
+        if let Some(context) = rejection {
+            return Err(action_error(context));
+        }
+
+        handlers.push(handler);
+        gauge!("guardian.response.custom_actions", (handlers.len() - BUILTIN_ACTIONS.len()) as f64);
+        info!(action = %name, "Custom response action registered");
+        Ok(())
+    }
+
+    /// Removes a custom handler, returning true if it was registered
+    pub fn unregister(&self, name: &str) -> bool {
+        if BUILTIN_ACTIONS.contains(&name) {
+            return false;
+        }
+        let mut handlers = self.handlers.write();
+        let before = handlers.len();
+        handlers.retain(|h| h.name() != name);
+        gauge!("guardian.response.custom_actions", (handlers.len() - BUILTIN_ACTIONS.len()) as f64);
+        before != handlers.len()
+    }
+
+    /// Looks up a handler by action name
+    pub fn get(&self, name: &str) -> Option<Arc<dyn ResponseActionHandler>> {
+        self.handlers.read().iter().find(|h| h.name() == name).cloned()
+    }
+
+    /// Returns the handler that carries out an action
+    pub fn handler_for(&self, action: &ResponseAction) -> Result<Arc<dyn ResponseActionHandler>, GuardianError> {
+        self.get(action.name())
+            .ok_or_else(|| action_error(format!("Unknown response action: {}", action.name())))
+    }
+
+    /// Returns registered custom action names in registration order
+    pub fn names(&self) -> Vec<String> {
+        self.handlers
+            .read()
+            .iter()
+            .map(|h| h.name().to_string())
+            .filter(|name| !BUILTIN_ACTIONS.contains(&name.as_str()))
+            .collect()
+    }
+
+    /// Returns the first custom action proposed for the threat
+    pub fn select(&self, analysis: &ThreatAnalysis) -> Option<ResponseAction> {
+        self.handlers.read().iter().find_map(|handler| {
+            handler.select(analysis).map(|parameters| {
+                debug!(action = handler.name(), "Custom response action selected");
+                ResponseAction::Custom {
+                    action: handler.name().to_string(),
+                    parameters,
+                }
+            })
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ProcessParams {
+    pid: u32,
+    #[serde(default)]
+    force: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct NetworkParams {
+    address: String,
+    duration: Duration,
+}
+
+#[derive(Debug, Deserialize)]
+struct ShutdownParams {
+    reason: String,
+}
+
+/// Freezes and confines a process so it can be inspected before termination
+#[derive(Debug)]
+struct IsolateProcess;
+
+#[async_trait]
+impl ResponseActionHandler for IsolateProcess {
+    fn name(&self) -> &str {
+        "isolate_process"
+    }
+
+    fn workflow_type(&self) -> &str {
+        BUILTIN_RESPONSE_WORKFLOW
+    }
+
+    async fn validate(&self, parameters: &serde_json::Value) -> Result<(), GuardianError> {
+        let params: ProcessParams = parse(self.name(), parameters)?;
+        if params.pid <= 1 {
+            return Err(action_error("Cannot isolate system init process".into()));
+        }
+        Ok(())
+    }
+
+    async fn execute(&self, parameters: &serde_json::Value) -> Result<(), GuardianError> {
+        let params: ProcessParams = parse(self.name(), parameters)?;
+        process_isolator().isolate(params.pid, "isolate_process response").await.map(|_| ())
+    }
+
+    async fn verify(&self, parameters: &serde_json::Value) -> Result<bool, GuardianError> {
+        let params: ProcessParams = parse(self.name(), parameters)?;
+        Ok(process_isolator().status(params.pid).await?.is_some_and(|status| status.frozen))
+    }
+
+    fn reversible(&self) -> bool {
+        true
+    }
+
+    async fn revert(&self, parameters: &serde_json::Value) -> Result<(), GuardianError> {
+        let params: ProcessParams = parse(self.name(), parameters)?;
+        process_isolator().release(params.pid).await
+    }
+
+    async fn status(&self, parameters: &serde_json::Value) -> Result<Option<serde_json::Value>, GuardianError> {
+        let params: ProcessParams = parse(self.name(), parameters)?;
+        let status = process_isolator().status(params.pid).await?;
+        Ok(status.map(|s| serde_json::to_value(s).unwrap_or_default()))
+    }
+}
+
+/// Terminates a process, forcibly when requested
+#[derive(Debug)]
+struct TerminateProcess;
+
+impl TerminateProcess {
+    fn command(params: &ProcessParams) -> (&'static str, Vec<String>) {
+        ("kill", vec![if params.force { "-KILL" } else { "-TERM" }.into(), params.pid.to_string()])
+    }
+}
+
+#[async_trait]
+impl ResponseActionHandler for TerminateProcess {
+    fn name(&self) -> &str {
+        "terminate_process"
+    }
+
+    fn workflow_type(&self) -> &str {
+        BUILTIN_RESPONSE_WORKFLOW
+    }
+
+    async fn validate(&self, parameters: &serde_json::Value) -> Result<(), GuardianError> {
+        let params: ProcessParams = parse(self.name(), parameters)?;
+        if params.pid <= 1 {
+            return Err(action_error("Cannot terminate system init process".into()));
+        }
+        Ok(())
+    }
+
+    async fn execute(&self, parameters: &serde_json::Value) -> Result<(), GuardianError> {
+        let (program, args) = Self::command(&parse(self.name(), parameters)?);
+        run_host_command(program, &args).await.map(|_| ())
+    }
+
+    async fn verify(&self, parameters: &serde_json::Value) -> Result<bool, GuardianError> {
+        let params: ProcessParams = parse(self.name(), parameters)?;
+        // Signal 0 fails once the process is gone
+        Ok(run_host_command("kill", &["-0".into(), params.pid.to_string()]).await.is_err())
+    }
+}
+
+/// Blocks an address in the pf table until the block's duration runs out
+#[derive(Debug)]
+struct BlockNetwork;
+
+#[async_trait]
+impl ResponseActionHandler for BlockNetwork {
+    fn name(&self) -> &str {
+        "block_network"
+    }
+
+    fn workflow_type(&self) -> &str {
+        BUILTIN_RESPONSE_WORKFLOW
+    }
+
+    async fn validate(&self, parameters: &serde_json::Value) -> Result<(), GuardianError> {
+        let params: NetworkParams = parse(self.name(), parameters)?;
+        if params.duration > MAX_BLOCK_DURATION {
+            return Err(action_error("Invalid network block parameters".into()));
+        }
+        normalize_address(&params.address).map(|_| ())
+    }
+
+    async fn execute(&self, parameters: &serde_json::Value) -> Result<(), GuardianError> {
+        let params: NetworkParams = parse(self.name(), parameters)?;
+        firewall().block(&params.address, params.duration, "block_network response").await.map(|_| ())
+    }
+
+    async fn verify(&self, parameters: &serde_json::Value) -> Result<bool, GuardianError> {
+        let params: NetworkParams = parse(self.name(), parameters)?;
+        Ok(firewall().is_enforced(&params.address).await)
+    }
+
+    async fn status(&self, parameters: &serde_json::Value) -> Result<Option<serde_json::Value>, GuardianError> {
+        let params: NetworkParams = parse(self.name(), parameters)?;
+        let block = firewall().block_for(&params.address).await;
+        Ok(block.map(|b| serde_json::to_value(b).unwrap_or_default()))
+    }
+
+    fn reversible(&self) -> bool {
+        true
+    }
+
+    async fn revert(&self, parameters: &serde_json::Value) -> Result<(), GuardianError> {
+        let params: NetworkParams = parse(self.name(), parameters)?;
+        firewall().unblock(&params.address).await
+    }
+}
+
+/// Powers the host off
+#[derive(Debug)]
+struct EmergencyShutdown;
+
+#[async_trait]
+impl ResponseActionHandler for EmergencyShutdown {
+    fn name(&self) -> &str {
+        "emergency_shutdown"
+    }
+
+    fn workflow_type(&self) -> &str {
+        BUILTIN_RESPONSE_WORKFLOW
+    }
+
+    async fn validate(&self, parameters: &serde_json::Value) -> Result<(), GuardianError> {
+        parse::<ShutdownParams>(self.name(), parameters)?;
+        // Emergency shutdown is always valid but should be logged
+        warn!("Emergency shutdown response action validated");
+        Ok(())
+    }
+
+    async fn execute(&self, parameters: &serde_json::Value) -> Result<(), GuardianError> {
+        let params: ShutdownParams = parse(self.name(), parameters)?;
+        run_host_command("shutdown", &["-p".into(), "now".into(), params.reason]).await.map(|_| ())
+    }
+}
+
+fn parse<T: DeserializeOwned>(action: &str, parameters: &serde_json::Value) -> Result<T, GuardianError> {
+    serde_json::from_value(parameters.clone())
+        .map_err(|e| action_error(format!("Invalid parameters for {}: {}", action, e)))
+}
+
+/// Runs a host command, returning its stdout
+pub(crate) async fn run_host_command(program: &str, args: &[String]) -> Result<String, GuardianError> {
+    let output = tokio::process::Command::new(program)
+        .args(args)
+        .kill_on_drop(true)
+        .output()
+        .await
+        .map_err(|e| action_error(format!("Failed to run {}: {}", program, e)))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        error!(program, %stderr, "Response action command failed");
+        return Err(action_error(format!("{} failed: {}", program, stderr.trim())));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn action_error(context: String) -> GuardianError {
+    SecurityError {
+        context,
+        source: None,
+        severity: crate::utils::error::ErrorSeverity::High,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: crate::utils::error::ErrorCategory::Security,
+        retry_count: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::threat_detection::ThreatLevel;
+
+    #[derive(Debug)]
+    struct RevokeCredentials;
+
+    #[async_trait]
+    impl ResponseActionHandler for RevokeCredentials {
+        fn name(&self) -> &str {
+            "revoke_credentials"
+        }
+
+        fn workflow_type(&self) -> &str {
+            "revoke_credentials"
+        }
+
+        async fn validate(&self, parameters: &serde_json::Value) -> Result<(), GuardianError> {
+            match parameters.get("account").and_then(|a| a.as_str()) {
+                Some(account) if account != "root" => Ok(()),
+                _ => Err(SecurityError {
+                    context: "Invalid account".into(),
+                    source: None,
+                    severity: crate::utils::error::ErrorSeverity::High,
+                    timestamp: time::OffsetDateTime::now_utc(),
+                    correlation_id: crate::utils::correlation::current_or_new(),
+                    category: crate::utils::error::ErrorCategory::Security,
+                    retry_count: 0,
+                }),
+            }
+        }
+
+        fn select(&self, analysis: &ThreatAnalysis) -> Option<serde_json::Value> {
+            (analysis.severity == ThreatLevel::High)
+                .then(|| serde_json::json!({ "account": analysis.source_address }))
+        }
+    }
+
+    #[test]
+    fn test_register_and_select_custom_action() {
+        let registry = ResponseActionRegistry::new();
+        registry.register(Arc::new(RevokeCredentials)).unwrap();
+        assert!(registry.register(Arc::new(RevokeCredentials)).is_err());
+        assert_eq!(registry.names(), vec!["revoke_credentials".to_string()]);
+
+        let analysis = ThreatAnalysis {
+            severity: ThreatLevel::High,
+            description: "Credential stuffing".into(),
+            process_id: None,
+            source_address: "svc-matchmaking".into(),
+            trust: None,
+            detection_id: None,
+            lineage: None,
+        };
+        match registry.select(&analysis) {
+            Some(ResponseAction::Custom { action, parameters }) => {
+                assert_eq!(action, "revoke_credentials");
+                assert_eq!(parameters["account"], "svc-matchmaking");
+            }
+            other => panic!("unexpected action: {:?}", other),
+        }
+
+        assert!(registry.unregister("revoke_credentials"));
+        assert!(!registry.unregister("block_network"));
+        assert!(registry.select(&analysis).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_builtin_handlers() {
+        let registry = ResponseActionRegistry::new();
+
+        let terminate = ResponseAction::TerminateProcess { pid: 4242, force: true };
+        let params = parse::<ProcessParams>("terminate_process", &terminate.parameters()).unwrap();
+        assert_eq!(TerminateProcess::command(&params), ("kill", vec!["-KILL".to_string(), "4242".to_string()]));
+        let init = ResponseAction::TerminateProcess { pid: 1, force: false };
+        assert!(registry.handler_for(&init).unwrap().validate(&init.parameters()).await.is_err());
+
+        let block = ResponseAction::BlockNetwork { address: "203.0.113.7".into(), duration: Duration::from_secs(60) };
+        let handler = registry.handler_for(&block).unwrap();
+        assert!(handler.validate(&block.parameters()).await.is_ok());
+        assert!(handler.reversible());
+        for address in ["127.0.0.1", "::1", "not-an-address"] {
+            let loopback = ResponseAction::BlockNetwork { address: address.into(), duration: Duration::from_secs(60) };
+            assert!(handler.validate(&loopback.parameters()).await.is_err());
+        }
+
+        let custom = ResponseAction::Custom { action: "revoke_credentials".into(), parameters: serde_json::Value::Null };
+        assert!(registry.handler_for(&custom).is_err());
+    }
+}
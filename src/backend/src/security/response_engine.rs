@@ -3,7 +3,7 @@ use std::{
     sync::Arc,
     time::{Duration, Instant},
 };
-use tokio::sync::RwLock;
+use tokio::sync::{watch, RwLock};
 use temporal_sdk::{
     WfContext, WfExecution, WfResult,
     workflow::{WorkflowOptions, WorkflowRetryPolicy},
@@ -14,7 +14,22 @@ use metrics::{counter, histogram};
 
 use crate::utils::error::{GuardianError, SecurityError};
 use crate::security::threat_detection::ThreatLevel;
+use crate::core::collectors::{process_tree, ProcessLineage};
 use crate::core::event_bus::{EventBus, Event, EventPriority};
+use crate::core::guardian::{Guardian, TenantContext};
+use crate::utils::correlation;
+use crate::config::DegradationLevel;
+use crate::security::degradation::degradation;
+use crate::security::offline_executor::{ExecutionMode, OfflineExecutor};
+use crate::security::process_trust::TrustLevel;
+use crate::security::pipeline_latency::{pipeline_latency, LatencyStage};
+use crate::security::response_actions::{ResponseActionHandler, ResponseActionRegistry};
+use crate::security::response_guardrails::{response_guardrails, GuardrailDecision, ResponseGuardrails};
+use crate::security::threat_analytics;
+use crate::storage::event_store::EventStore;
+use crate::storage::{ResponseWal, WalIntent, DEFAULT_RESPONSE_WAL_PATH};
+use crate::utils::queue_metrics::{queue_registry, QueueMonitor};
+use crate::utils::telemetry;
 
 // Constants for response engine configuration
 const RESPONSE_ENGINE_VERSION: &str = "1.0.0";
@@ -23,6 +38,13 @@ const CRITICAL_RESPONSE_TIME: Duration = Duration::from_millis(500);
 const CIRCUIT_BREAKER_THRESHOLD: u32 = 5;
 const RESPONSE_QUEUE_CAPACITY: usize = 1000;
 const METRICS_FLUSH_INTERVAL: Duration = Duration::from_secs(15);
+/// Task queue of response workflows, before tenant scoping
+pub const RESPONSE_TASK_QUEUE: &str = "guardian_response";
+const TEMPORAL_PROBE_INTERVAL: Duration = Duration::from_secs(30);
+/// Interrupted actions older than this are compensated rather than re-executed on recovery
+const RECOVERY_REEXECUTE_WINDOW: Duration = Duration::from_secs(15 * 60);
+/// How long the source of a threat stays blocked while its process is handled
+const CONTAINMENT_BLOCK_DURATION: Duration = Duration::from_secs(3600);
 
 /// Available security response actions
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -42,6 +64,83 @@ pub enum ResponseAction {
     EmergencyShutdown {
         reason: String,
     },
+    /// Action implemented by a registered `ResponseActionHandler`
+    Custom {
+        action: String,
+        parameters: serde_json::Value,
+    },
+}
+
+impl ResponseAction {
+    /// Returns the action name used for metrics and handler lookup
+    pub fn name(&self) -> &str {
+        match self {
+            ResponseAction::IsolateProcess { .. } => "isolate_process",
+            ResponseAction::TerminateProcess { .. } => "terminate_process",
+            ResponseAction::BlockNetwork { .. } => "block_network",
+            ResponseAction::EmergencyShutdown { .. } => "emergency_shutdown",
+            ResponseAction::Custom { action, .. } => action,
+        }
+    }
+
+    /// Returns the parameters passed to the action's handler
+    pub fn parameters(&self) -> serde_json::Value {
+        match self {
+            ResponseAction::IsolateProcess { pid, reason } => serde_json::json!({ "pid": pid, "reason": reason }),
+            ResponseAction::TerminateProcess { pid, force } => serde_json::json!({ "pid": pid, "force": force }),
+            ResponseAction::BlockNetwork { address, duration } => {
+                serde_json::json!({ "address": address, "duration": duration })
+            }
+            ResponseAction::EmergencyShutdown { reason } => serde_json::json!({ "reason": reason }),
+            ResponseAction::Custom { parameters, .. } => parameters.clone(),
+        }
+    }
+}
+
+/// Threat analysis result used to select a response action
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThreatAnalysis {
+    pub severity: ThreatLevel,
+    pub description: String,
+    pub process_id: Option<u32>,
+    pub source_address: String,
+    /// Trust of the binary involved, when known
+    #[serde(default)]
+    pub trust: Option<TrustLevel>,
+    /// Detection that prompted the response, recorded for time-to-respond analytics
+    #[serde(default)]
+    pub detection_id: Option<String>,
+    /// Ancestry and exec history of `process_id`, filled in from the process tree
+    #[serde(default)]
+    pub lineage: Option<ProcessLineage>,
+}
+
+impl ThreatAnalysis {
+    /// Attaches the lineage of the threat's process unless it already has one
+    ///
+    /// Processes the collector has not seen, or that exited too long ago, are left without.
+    pub fn with_lineage(mut self) -> Self {
+        if self.lineage.is_none() {
+            if let Some(pid) = self.process_id {
+                self.lineage = process_tree().lineage(pid);
+                let outcome = if self.lineage.is_some() { "found" } else { "unknown" };
+                counter!("guardian.response.lineage_lookups", 1, "outcome" => outcome);
+            }
+        }
+        self
+    }
+
+    /// Fields audit events about this threat carry
+    pub fn audit_data(&self) -> serde_json::Value {
+        serde_json::json!({
+            "severity": self.severity,
+            "description": self.description,
+            "process_id": self.process_id,
+            "source_address": self.source_address,
+            "detection_id": self.detection_id,
+            "lineage": self.lineage,
+        })
+    }
 }
 
 /// Response execution status
@@ -54,6 +153,36 @@ pub struct ResponseStatus {
     correlation_id: uuid::Uuid,
 }
 
+impl ResponseStatus {
+    /// Returns the action the status reports on
+    pub fn action(&self) -> &ResponseAction {
+        &self.action
+    }
+
+    /// Returns whether the action took effect
+    pub fn success(&self) -> bool {
+        self.success
+    }
+
+    /// Returns why the action failed or was only simulated
+    pub fn error_context(&self) -> Option<&str> {
+        self.error_context.as_deref()
+    }
+
+    /// Returns the correlation ID the action ran under
+    pub fn correlation_id(&self) -> uuid::Uuid {
+        self.correlation_id
+    }
+}
+
+/// One action of a multi-step response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResponseStep {
+    pub action: ResponseAction,
+    /// Whether the action's handler can undo it if a later step fails
+    pub reversible: bool,
+}
+
 /// Configuration for response engine
 #[derive(Debug, Clone)]
 struct ResponseConfig {
@@ -80,6 +209,7 @@ struct ResponseQueue {
     high_priority: Vec<(ResponseAction, Instant)>,
     normal_priority: Vec<(ResponseAction, Instant)>,
     capacity: usize,
+    monitor: Arc<QueueMonitor>,
 }
 
 impl ResponseQueue {
@@ -88,6 +218,7 @@ impl ResponseQueue {
             high_priority: Vec::with_capacity(capacity / 2),
             normal_priority: Vec::with_capacity(capacity / 2),
             capacity,
+            monitor: queue_registry().register(QueueMonitor::new("response_queue", capacity * 2)),
         }
     }
 
@@ -104,26 +235,124 @@ impl ResponseQueue {
                 source: None,
                 severity: crate::utils::error::ErrorSeverity::High,
                 timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id: uuid::Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: crate::utils::error::ErrorCategory::Security,
                 retry_count: 0,
             });
         }
 
         queue.push((action, Instant::now()));
+        self.monitor.record_enqueue();
+        self.observe();
         Ok(())
     }
+
+    /// Reports depth and the oldest pending action to the queue monitor
+    fn observe(&self) {
+        let oldest = [self.high_priority.first(), self.normal_priority.first()]
+            .into_iter()
+            .flatten()
+            .map(|(_, enqueued_at)| *enqueued_at)
+            .min();
+        self.monitor.observe(self.high_priority.len() + self.normal_priority.len(), oldest);
+    }
+}
+
+/// Temporal client and whether actions currently go through it
+#[derive(Debug)]
+struct TemporalConnectivity {
+    client: Option<Arc<temporal_sdk::Client>>,
+    mode: ExecutionMode,
+    last_attempt: Option<Instant>,
+}
+
+impl TemporalConnectivity {
+    /// Returns the client to try, or `None` while offline between probes
+    fn client_to_try(&self) -> Option<Arc<temporal_sdk::Client>> {
+        let client = self.client.clone()?;
+        match (self.mode, self.last_attempt) {
+            (ExecutionMode::Offline, Some(last)) if last.elapsed() < TEMPORAL_PROBE_INTERVAL => None,
+            _ => Some(client),
+        }
+    }
 }
 
 /// Core response engine with enhanced reliability
 #[derive(Debug)]
 pub struct ResponseEngine {
-    temporal_client: Arc<temporal_sdk::Client>,
+    temporal: Arc<RwLock<TemporalConnectivity>>,
+    offline_executor: Option<Arc<OfflineExecutor>>,
     event_bus: Arc<EventBus>,
     response_config: ResponseConfig,
     circuit_breaker: Arc<RwLock<u32>>,
     metrics_collector: Arc<metrics::MetricsCollector>,
     response_queue: Arc<RwLock<ResponseQueue>>,
+    action_registry: Arc<ResponseActionRegistry>,
+    guardrails: Arc<ResponseGuardrails>,
+    wal: Option<Arc<ResponseWal>>,
+    tenant: TenantContext,
+    response_history: Option<Arc<EventStore>>,
+}
+
+/// Builds the daemon's engine on the Guardian's event bus and Temporal client
+///
+/// Every action is journaled in the write-ahead log at `DEFAULT_RESPONSE_WAL_PATH`, and actions
+/// a crash interrupted are settled before the engine is returned to accept detections. While
+/// Temporal is unreachable, at startup or later, actions run in-process and are journaled to
+/// `event_store`; the engine syncs that journal whenever the Guardian reconnects.
+pub async fn init_response_engine(
+    guardian: &Guardian,
+    event_store: Arc<EventStore>,
+) -> Result<Arc<ResponseEngine>, GuardianError> {
+    let event_bus = Arc::new(guardian.event_bus().clone());
+    let action_registry = Arc::new(ResponseActionRegistry::new());
+    let offline = Arc::new(OfflineExecutor::new(event_store, Arc::clone(&action_registry)));
+    let engine = match guardian.temporal_client() {
+        Some(client) => ResponseEngine::new(Arc::new(client), event_bus, None)
+            .await?
+            .with_offline_executor(offline),
+        None => ResponseEngine::new_offline(event_bus, offline, None),
+    };
+    let wal = Arc::new(ResponseWal::open(DEFAULT_RESPONSE_WAL_PATH).await?);
+    let engine = Arc::new(
+        engine
+            .with_tenant(guardian.tenant().clone())
+            .with_action_registry(action_registry)
+            .with_wal(wal),
+    );
+
+    let settled = engine.recover().await?;
+    if !settled.is_empty() {
+        info!(settled = settled.len(), "Interrupted response actions reconciled");
+    }
+    // Runs for the life of the daemon, like the engine itself
+    Arc::clone(&engine).follow_temporal(guardian.subscribe_temporal());
+    Ok(engine)
+}
+
+/// How recovery settled an action that was interrupted before its completion was logged
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Reconciliation {
+    /// The action had taken effect before the interruption
+    Verified,
+    /// The action was run again
+    Reexecuted,
+    /// Partial effects of a stale action were reverted
+    Compensated,
+    /// The action could be neither safely repeated nor reverted
+    Abandoned,
+}
+
+impl Reconciliation {
+    fn label(&self) -> &'static str {
+        match self {
+            Reconciliation::Verified => "verified",
+            Reconciliation::Reexecuted => "reexecuted",
+            Reconciliation::Compensated => "compensated",
+            Reconciliation::Abandoned => "abandoned",
+        }
+    }
 }
 
 impl ResponseEngine {
@@ -142,15 +371,236 @@ impl ResponseEngine {
         let response_queue = ResponseQueue::new(RESPONSE_QUEUE_CAPACITY);
 
         Ok(Self {
-            temporal_client,
+            temporal: Arc::new(RwLock::new(TemporalConnectivity {
+                client: Some(temporal_client),
+                mode: ExecutionMode::Online,
+                last_attempt: None,
+            })),
+            offline_executor: None,
             event_bus,
             response_config: config,
             circuit_breaker: Arc::new(RwLock::new(0)),
             metrics_collector: Arc::new(metrics::MetricsCollector::new()),
             response_queue: Arc::new(RwLock::new(response_queue)),
+            action_registry: Arc::new(ResponseActionRegistry::new()),
+            guardrails: response_guardrails(),
+            wal: None,
+            tenant: TenantContext::default(),
+            response_history: None,
         })
     }
 
+    /// Creates a ResponseEngine for hosts without a reachable Temporal server,
+    /// executing actions through the offline executor until `reconnect` is called
+    pub fn new_offline(
+        event_bus: Arc<EventBus>,
+        offline_executor: Arc<OfflineExecutor>,
+        config: Option<ResponseConfig>,
+    ) -> Self {
+        warn!(version = RESPONSE_ENGINE_VERSION, "Initializing response engine in offline mode");
+        counter!("guardian.response.offline.transitions", 1, "mode" => "offline");
+
+        Self {
+            temporal: Arc::new(RwLock::new(TemporalConnectivity {
+                client: None,
+                mode: ExecutionMode::Offline,
+                last_attempt: None,
+            })),
+            offline_executor: Some(offline_executor),
+            event_bus,
+            response_config: config.unwrap_or_default(),
+            circuit_breaker: Arc::new(RwLock::new(0)),
+            metrics_collector: Arc::new(metrics::MetricsCollector::new()),
+            response_queue: Arc::new(RwLock::new(ResponseQueue::new(RESPONSE_QUEUE_CAPACITY))),
+            action_registry: Arc::new(ResponseActionRegistry::new()),
+            guardrails: response_guardrails(),
+            wal: None,
+            tenant: TenantContext::default(),
+            response_history: None,
+        }
+    }
+
+    /// Falls back to local execution with journaling when Temporal is unreachable
+    pub fn with_offline_executor(mut self, offline_executor: Arc<OfflineExecutor>) -> Self {
+        self.offline_executor = Some(offline_executor);
+        self
+    }
+
+    /// Returns whether actions currently run as workflows or locally
+    pub async fn execution_mode(&self) -> ExecutionMode {
+        self.temporal.read().await.mode
+    }
+
+    /// Switches back to Temporal and records actions executed while offline,
+    /// returning the number of journal entries synced
+    #[instrument(skip(self, client))]
+    pub async fn reconnect(&self, client: Arc<temporal_sdk::Client>) -> Result<usize, GuardianError> {
+        {
+            let mut temporal = self.temporal.write().await;
+            temporal.client = Some(Arc::clone(&client));
+            temporal.last_attempt = None;
+            if temporal.mode == ExecutionMode::Offline {
+                temporal.mode = ExecutionMode::Online;
+                counter!("guardian.response.offline.transitions", 1, "mode" => "online");
+                info!("Temporal reachable, response engine back online");
+            }
+        }
+
+        match &self.offline_executor {
+            Some(executor) => executor.sync(&client, &self.tenant.topic(RESPONSE_TASK_QUEUE)).await,
+            None => Ok(0),
+        }
+    }
+
+    /// Reconnects whenever the watched Temporal client becomes available
+    pub fn follow_temporal(
+        self: Arc<Self>,
+        mut clients: watch::Receiver<Option<temporal_sdk::Client>>,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            while clients.changed().await.is_ok() {
+                let client = clients.borrow().clone();
+                if let Some(client) = client {
+                    if let Err(e) = self.reconnect(Arc::new(client)).await {
+                        warn!(error = %e, "Failed to sync offline response journal");
+                    }
+                }
+            }
+        })
+    }
+
+    /// Records the outcome of a Temporal call, returning true when the engine just went offline
+    async fn record_temporal_attempt(&self, reachable: bool) -> bool {
+        let mut temporal = self.temporal.write().await;
+        temporal.last_attempt = Some(Instant::now());
+        match (temporal.mode, reachable) {
+            (ExecutionMode::Online, false) if self.offline_executor.is_some() => {
+                temporal.mode = ExecutionMode::Offline;
+                counter!("guardian.response.offline.transitions", 1, "mode" => "offline");
+                warn!("Temporal unreachable, executing responses offline");
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Uses a shared registry of response action handlers
+    pub fn with_action_registry(mut self, action_registry: Arc<ResponseActionRegistry>) -> Self {
+        self.action_registry = action_registry;
+        self
+    }
+
+    /// Uses dedicated blast radius guardrails instead of the process-wide ones
+    pub fn with_guardrails(mut self, guardrails: Arc<ResponseGuardrails>) -> Self {
+        self.guardrails = guardrails;
+        self
+    }
+
+    /// Records every action's intent and completion in a write-ahead log
+    pub fn with_wal(mut self, wal: Arc<ResponseWal>) -> Self {
+        self.wal = Some(wal);
+        self
+    }
+
+    /// Records responses to detections in the event store for historical analytics
+    pub fn with_response_history(mut self, event_store: Arc<EventStore>) -> Self {
+        self.response_history = Some(event_store);
+        self
+    }
+
+    /// Settles actions the write-ahead log shows were started but never completed;
+    /// call once at startup before new responses are executed
+    #[instrument(skip(self))]
+    pub async fn recover(&self) -> Result<Vec<(WalIntent, Reconciliation)>, GuardianError> {
+        let Some(wal) = &self.wal else {
+            return Ok(Vec::new());
+        };
+
+        let mut settled = Vec::new();
+        for intent in wal.incomplete().await {
+            let reconciliation = self.reconcile(&intent).await;
+            wal.record_reconciled(&intent.id, reconciliation.label()).await?;
+            counter!("guardian.response.wal.reconciled", 1, "resolution" => reconciliation.label());
+            settled.push((intent, reconciliation));
+        }
+
+        if !settled.is_empty() {
+            self.event_bus.publish(Event::new(
+                self.tenant.topic("response_recovered"),
+                serde_json::json!({
+                    "actions": settled
+                        .iter()
+                        .map(|(intent, reconciliation)| serde_json::json!({
+                            "intent_id": intent.id,
+                            "action": intent.action_name,
+                            "correlation_id": intent.correlation_id,
+                            "resolution": reconciliation,
+                        }))
+                        .collect::<Vec<_>>(),
+                }),
+                EventPriority::High,
+            )?).await?;
+        }
+        Ok(settled)
+    }
+
+    /// Decides what to do with one interrupted action and does it
+    async fn reconcile(&self, intent: &WalIntent) -> Reconciliation {
+        let Ok(action) = serde_json::from_value::<ResponseAction>(intent.action.clone()) else {
+            warn!(intent_id = %intent.id, action = %intent.action_name, "Interrupted response action is unreadable");
+            return Reconciliation::Abandoned;
+        };
+        let Ok(handler) = self.action_registry.handler_for(&action) else {
+            warn!(intent_id = %intent.id, action = %intent.action_name, "No handler for interrupted response action");
+            return Reconciliation::Abandoned;
+        };
+        let parameters = action.parameters();
+
+        if handler.verify(&parameters).await.unwrap_or(false) {
+            return Reconciliation::Verified;
+        }
+
+        let now = time::OffsetDateTime::now_utc().unix_timestamp().max(0) as u64;
+        let age = Duration::from_secs(now.saturating_sub(intent.recorded_at));
+        // Repeating a shutdown at startup would loop, and old actions no longer match the threat
+        let repeatable = age < RECOVERY_REEXECUTE_WINDOW && !matches!(action, ResponseAction::EmergencyShutdown { .. });
+        if repeatable {
+            // Guardrail state did not survive the restart, so the repeat is checked afresh
+            return match self.execute_action(action, Instant::now(), intent.correlation_id, None).await {
+                Ok(_) => Reconciliation::Reexecuted,
+                Err(e) => {
+                    warn!(intent_id = %intent.id, error = %e, "Re-executing interrupted response action failed");
+                    Reconciliation::Abandoned
+                }
+            };
+        }
+
+        if handler.reversible() {
+            match self.revert_response(&action).await {
+                Ok(()) => return Reconciliation::Compensated,
+                Err(e) => warn!(intent_id = %intent.id, error = %e, "Compensating interrupted response action failed"),
+            }
+        }
+        warn!(
+            target: "SECURITY-AUDIT",
+            intent_id = %intent.id,
+            action = %intent.action_name,
+            "Interrupted response action abandoned, host state needs manual review"
+        );
+        Reconciliation::Abandoned
+    }
+
+    /// Scopes response task queues and published events to a tenant
+    pub fn with_tenant(mut self, tenant: TenantContext) -> Self {
+        self.tenant = tenant;
+        self
+    }
+
+    /// Returns the registry used to register response action handlers
+    pub fn action_registry(&self) -> Arc<ResponseActionRegistry> {
+        Arc::clone(&self.action_registry)
+    }
+
     /// Executes a security response through Temporal workflow
     #[instrument(skip(self, threat_analysis))]
     pub async fn execute_response(
@@ -158,7 +608,7 @@ impl ResponseEngine {
         threat_analysis: ThreatAnalysis,
     ) -> Result<ResponseStatus, GuardianError> {
         let start_time = Instant::now();
-        let correlation_id = uuid::Uuid::new_v4();
+        let correlation_id = correlation::current_or_new();
 
         // Check circuit breaker
         if *self.circuit_breaker.read().await >= self.response_config.circuit_breaker_threshold {
@@ -174,162 +624,412 @@ impl ResponseEngine {
             });
         }
 
+        // Lineage goes in first so custom actions can select on it
+        let threat_analysis = threat_analysis.with_lineage();
+
         // Determine response action
         let action = self.determine_response_action(&threat_analysis)?;
-        
-        // Validate response action
-        self.validate_response(&action).await?;
+        let status = self.execute_action(action, start_time, correlation_id, None).await?;
+        if let (true, Some(detection_id)) = (status.success, &threat_analysis.detection_id) {
+            self.record_response(detection_id, status.action.name()).await;
+        }
+        Ok(status)
+    }
 
-        // Configure workflow options
-        let workflow_options = WorkflowOptions {
-            task_queue: "guardian_response".into(),
-            workflow_execution_timeout: Some(self.response_config.timeout),
-            retry_policy: Some(WorkflowRetryPolicy {
-                initial_interval: self.response_config.retry_interval,
-                maximum_attempts: self.response_config.max_retries,
-                ..Default::default()
-            }),
-            ..Default::default()
-        };
+    /// Plans the steps of a response: network containment first, then the selected action
+    pub fn plan_response(&self, threat_analysis: &ThreatAnalysis) -> Vec<ResponseStep> {
+        plan_response_steps(&self.action_registry, &threat_analysis.clone().with_lineage())
+    }
 
-        // Execute response workflow
-        let workflow_result = self.temporal_client
-            .start_workflow(
-                "execute_response",
-                action.clone(),
-                workflow_options,
-            )
-            .await
-            .map_err(|e| SecurityError {
-                context: "Failed to start response workflow".into(),
-                source: Some(Box::new(e)),
-                severity: crate::utils::error::ErrorSeverity::High,
-                timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id,
-                category: crate::utils::error::ErrorCategory::Security,
-                retry_count: 0,
-            })?;
+    /// Executes one planned step of a multi-step response
+    #[instrument(skip(self, action), fields(action = action.name()))]
+    pub async fn execute_step(&self, action: ResponseAction) -> Result<ResponseStatus, GuardianError> {
+        self.execute_action(action, Instant::now(), correlation::current_or_new(), None).await
+    }
+
+    /// Stores a response for analytics; failures never fail the response
+    async fn record_response(&self, detection_id: &str, action: &str) {
+        let Some(event_store) = &self.response_history else {
+            return;
+        };
+        let stored = match threat_analytics::response_event(self.tenant.tenant_id(), detection_id, action) {
+            Ok(event) => event_store.store_event(event).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = stored {
+            warn!(error = %e, detection_id, "Failed to record response for analytics");
+        }
+    }
 
-        // Monitor workflow execution
-        let execution_result = workflow_result.get_result().await.map_err(|e| SecurityError {
-            context: "Response workflow execution failed".into(),
-            source: Some(Box::new(e)),
-            severity: crate::utils::error::ErrorSeverity::High,
+    /// Executes a response held by the guardrails once an operator approves it
+    #[instrument(skip(self))]
+    pub async fn approve_response(&self, pending: uuid::Uuid, approver: &str) -> Result<ResponseStatus, GuardianError> {
+        let held = self.guardrails.approve(pending, approver).ok_or_else(|| SecurityError {
+            context: format!("No response pending approval with id {}", pending),
+            source: None,
+            severity: crate::utils::error::ErrorSeverity::Medium,
             timestamp: time::OffsetDateTime::now_utc(),
-            correlation_id,
+            correlation_id: correlation::current_or_new(),
             category: crate::utils::error::ErrorCategory::Security,
             retry_count: 0,
         })?;
+        self.execute_action(held.action, Instant::now(), correlation::current_or_new(), Some(approver)).await
+    }
+
+    /// Runs an action through its handler; unapproved actions are checked against the guardrails first
+    async fn execute_action(
+        &self,
+        action: ResponseAction,
+        start_time: Instant,
+        correlation_id: uuid::Uuid,
+        approved_by: Option<&str>,
+    ) -> Result<ResponseStatus, GuardianError> {
+        let handler = self.action_registry.handler_for(&action)?;
+        let parameters = action.parameters();
+
+        // Validate response action
+        handler.validate(&parameters).await?;
+
+        // Every handler's workflow runs under the same retry policy
+        let workflow_type = handler.workflow_type().to_string();
+        let timeout = handler.timeout().unwrap_or(self.response_config.timeout);
+        let action_name = action.name().to_string();
+        counter!("guardian.response.actions", 1, "action" => action_name.clone());
+
+        // Relaxed environments observe the decision without touching the host
+        if !crate::config::active_profile().enforces_responses() {
+            info!(action = %action_name, %correlation_id, "Dry-run response under relaxed enforcement");
+            counter!("guardian.response.dry_run", 1, "action" => action_name);
+            return Ok(ResponseStatus {
+                action,
+                success: true,
+                execution_time: start_time.elapsed(),
+                error_context: Some("dry run: relaxed enforcement".into()),
+                correlation_id,
+            });
+        }
+
+        // Degraded detection is less accurate, so it raises alerts without acting on them
+        if degradation().is_active(DegradationLevel::AlertOnly) {
+            warn!(action = %action_name, %correlation_id, "Response suppressed while detection is degraded to alert-only");
+            counter!("guardian.response.degraded", 1, "action" => action_name);
+            return Ok(ResponseStatus {
+                action,
+                success: true,
+                execution_time: start_time.elapsed(),
+                error_context: Some("alert only: detection degraded".into()),
+                correlation_id,
+            });
+        }
+
+        // Past a blast radius limit the detector may be misfiring, so a human decides
+        if approved_by.is_none() {
+            if let GuardrailDecision::Held { pending, tripped } = self.guardrails.admit(&action) {
+                if let Some(trip) = tripped {
+                    self.event_bus.publish(Event::new(
+                        self.tenant.topic("response_guardrail_tripped"),
+                        serde_json::json!({
+                            "trip": trip,
+                            "action": action,
+                            "pending_id": pending,
+                            "correlation_id": correlation_id,
+                            "message": "Automatic response limit exceeded, possible detector malfunction",
+                        }),
+                        EventPriority::Critical,
+                    )?).await?;
+                }
+                info!(action = %action_name, %pending, %correlation_id, "Response held for approval");
+                return Ok(ResponseStatus {
+                    action,
+                    success: false,
+                    execution_time: start_time.elapsed(),
+                    error_context: Some(format!("held for approval: {}", pending)),
+                    correlation_id,
+                });
+            }
+        }
+
+        // Intent is durable before the host is touched, so a crash mid-action is reconciled on restart
+        let intent = match &self.wal {
+            Some(wal) => Some(
+                wal.record_intent(&action_name, serde_json::to_value(&action).unwrap_or_default(), correlation_id)
+                    .await?,
+            ),
+            None => None,
+        };
+        let outcome = self
+            .run_action(&action, handler.as_ref(), &parameters, &workflow_type, timeout, correlation_id)
+            .await;
+        if let (Some(wal), Some(intent)) = (&self.wal, &intent) {
+            let (success, error) = match &outcome {
+                Ok((success, error)) => (*success, error.clone()),
+                Err(e) => (false, Some(e.to_string())),
+            };
+            // Recovery verifies an intent left open here, so a failed completion write is not fatal
+            if let Err(e) = wal.record_completion(intent, success, error).await {
+                warn!(error = %e, intent_id = %intent, "Failed to record response completion in the WAL");
+            }
+        }
+        let (success, error_context) = outcome?;
 
         let execution_time = start_time.elapsed();
 
+        // What the action left on the host, e.g. how a process was isolated
+        let action_status = if success {
+            handler.status(&parameters).await.unwrap_or_else(|e| {
+                warn!(action = %action_name, error = %e, "Failed to read response action status");
+                None
+            })
+        } else {
+            None
+        };
+
         // Record metrics
-        histogram!("guardian.response.execution_time", execution_time.as_secs_f64());
+        histogram!("guardian.response.execution_time", execution_time.as_secs_f64(), "action" => action_name.clone());
+        pipeline_latency().record(LatencyStage::Respond, execution_time);
         
         // Publish response event
         self.event_bus.publish(Event::new(
-            "response_executed".into(),
+            self.tenant.topic("response_executed"),
             serde_json::json!({
                 "action": action,
-                "success": execution_result.is_ok(),
+                "success": success,
                 "execution_time": execution_time.as_secs_f64(),
                 "correlation_id": correlation_id,
+                "mode": self.execution_mode().await,
+                "status": action_status,
             }),
             EventPriority::High,
         )?).await?;
 
         Ok(ResponseStatus {
             action,
-            success: execution_result.is_ok(),
+            success,
             execution_time,
-            error_context: execution_result.err().map(|e| e.to_string()),
+            error_context,
             correlation_id,
         })
     }
 
-    /// Determines appropriate response action based on threat analysis
-    fn determine_response_action(&self, threat_analysis: &ThreatAnalysis) -> Result<ResponseAction, GuardianError> {
-        match threat_analysis.severity {
-            ThreatLevel::Critical => Ok(ResponseAction::EmergencyShutdown {
-                reason: format!("Critical threat detected: {}", threat_analysis.description),
+    /// Runs an action as a workflow, or locally while offline, and confirms it took effect
+    async fn run_action(
+        &self,
+        action: &ResponseAction,
+        handler: &dyn ResponseActionHandler,
+        parameters: &serde_json::Value,
+        workflow_type: &str,
+        timeout: Duration,
+        correlation_id: uuid::Uuid,
+    ) -> Result<(bool, Option<String>), GuardianError> {
+        let action_name = action.name().to_string();
+
+        // Configure workflow options
+        let workflow_options = WorkflowOptions {
+            task_queue: self.tenant.topic(RESPONSE_TASK_QUEUE),
+            workflow_id: Some(correlation::workflow_id(workflow_type)),
+            headers: telemetry::workflow_headers(),
+            workflow_execution_timeout: Some(timeout),
+            retry_policy: Some(WorkflowRetryPolicy {
+                initial_interval: self.response_config.retry_interval,
+                maximum_attempts: self.response_config.max_retries,
+                ..Default::default()
             }),
-            ThreatLevel::High => {
-                if let Some(pid) = threat_analysis.process_id {
-                    Ok(ResponseAction::TerminateProcess {
-                        pid,
-                        force: true,
-                    })
-                } else {
-                    Ok(ResponseAction::BlockNetwork {
-                        address: threat_analysis.source_address.clone(),
-                        duration: Duration::from_secs(3600),
-                    })
+            ..Default::default()
+        };
+
+        // Execute response workflow, or locally with journaling while Temporal is unreachable
+        let client = self.temporal.read().await.client_to_try();
+        let workflow_result = match client {
+            Some(client) => match client
+                .start_workflow(workflow_type, action.clone(), workflow_options)
+                .await
+            {
+                Ok(handle) => {
+                    if self.execution_mode().await == ExecutionMode::Offline {
+                        // The probe succeeded, so sync the journal before carrying on
+                        if let Err(e) = self.reconnect(client).await {
+                            warn!(error = %e, "Failed to sync offline response journal");
+                        }
+                    }
+                    Some(handle)
+                }
+                Err(e) => {
+                    self.record_temporal_attempt(false).await;
+                    if self.offline_executor.is_none() {
+                        return Err(SecurityError {
+                            context: "Failed to start response workflow".into(),
+                            source: Some(Box::new(e)),
+                            severity: crate::utils::error::ErrorSeverity::High,
+                            timestamp: time::OffsetDateTime::now_utc(),
+                            correlation_id,
+                            category: crate::utils::error::ErrorCategory::Security,
+                            retry_count: 0,
+                        });
+                    }
+                    warn!(error = %e, "Failed to start response workflow, executing locally");
+                    None
                 }
             },
-            _ => {
-                if let Some(pid) = threat_analysis.process_id {
-                    Ok(ResponseAction::IsolateProcess {
-                        pid,
-                        reason: threat_analysis.description.clone(),
-                    })
-                } else {
-                    Ok(ResponseAction::BlockNetwork {
-                        address: threat_analysis.source_address.clone(),
-                        duration: Duration::from_secs(1800),
-                    })
+            None => None,
+        };
+
+        let (mut success, mut error_context) = match workflow_result {
+            Some(handle) => {
+                // Monitor workflow execution
+                let execution_result = handle.get_result().await.map_err(|e| SecurityError {
+                    context: "Response workflow execution failed".into(),
+                    source: Some(Box::new(e)),
+                    severity: crate::utils::error::ErrorSeverity::High,
+                    timestamp: time::OffsetDateTime::now_utc(),
+                    correlation_id,
+                    category: crate::utils::error::ErrorCategory::Security,
+                    retry_count: 0,
+                })?;
+                (execution_result.is_ok(), execution_result.err().map(|e| e.to_string()))
+            }
+            None => {
+                let executor = self.offline_executor.as_ref().ok_or_else(|| SecurityError {
+                    context: "Temporal is unavailable and no offline executor is configured".into(),
+                    source: None,
+                    severity: crate::utils::error::ErrorSeverity::Critical,
+                    timestamp: time::OffsetDateTime::now_utc(),
+                    correlation_id,
+                    category: crate::utils::error::ErrorCategory::Security,
+                    retry_count: 0,
+                })?;
+                match executor.execute(action, workflow_type, correlation_id).await {
+                    Ok(_) => (true, None),
+                    Err(e) => (false, Some(e.to_string())),
+                }
+            }
+        };
+
+        // Confirm the action actually took effect on the host
+        if success {
+            let verified = match handler.verify(parameters).await {
+                Ok(verified) => verified,
+                Err(e) => {
+                    warn!(action = %action_name, error = %e, "Response verification failed");
+                    false
                 }
+            };
+            counter!("guardian.response.verifications", 1,
+                "action" => action_name.clone(), "verified" => verified.to_string());
+            if !verified {
+                success = false;
+                error_context = Some(format!("{} did not take effect", action_name));
             }
         }
+
+        Ok((success, error_context))
+    }
+
+    /// Determines appropriate response action based on threat analysis
+    fn determine_response_action(&self, threat_analysis: &ThreatAnalysis) -> Result<ResponseAction, GuardianError> {
+        Ok(select_response_action(&self.action_registry, threat_analysis))
+    }
+
+    /// Undoes a previously executed response action through its handler
+    #[instrument(skip(self, action), fields(action = action.name()))]
+    pub async fn revert_response(&self, action: &ResponseAction) -> Result<(), GuardianError> {
+        let handler = self.action_registry.handler_for(action)?;
+        if !handler.reversible() {
+            return Err(SecurityError {
+                context: format!("Response action {} cannot be reverted", action.name()),
+                source: None,
+                severity: crate::utils::error::ErrorSeverity::Medium,
+                timestamp: time::OffsetDateTime::now_utc(),
+                correlation_id: crate::utils::correlation::current_or_new(),
+                category: crate::utils::error::ErrorCategory::Security,
+                retry_count: 0,
+            });
+        }
+
+        if crate::config::active_profile().enforces_responses() {
+            handler.revert(&action.parameters()).await?;
+        }
+        counter!("guardian.response.reverts", 1, "action" => action.name().to_string());
+        warn!(target: "SECURITY-AUDIT", action = action.name(), "Response action reverted");
+
+        self.event_bus.publish(Event::new(
+            self.tenant.topic("response_reverted"),
+            serde_json::json!({
+                "action": action,
+                "correlation_id": correlation::current_or_new(),
+            }),
+            EventPriority::High,
+        )?).await?;
+        Ok(())
+    }
+}
+
+/// Steps of a response, blocking the source address before acting on the process it reached
+pub fn plan_response_steps(action_registry: &ResponseActionRegistry, threat_analysis: &ThreatAnalysis) -> Vec<ResponseStep> {
+    let step = |action: ResponseAction| ResponseStep {
+        reversible: action_registry.get(action.name()).map_or(false, |handler| handler.reversible()),
+        action,
+    };
+
+    let action = select_response_action(action_registry, threat_analysis);
+    let contain_first = matches!(
+        action,
+        ResponseAction::IsolateProcess { .. } | ResponseAction::TerminateProcess { .. }
+    ) && !threat_analysis.source_address.is_empty();
+
+    let mut steps = Vec::with_capacity(2);
+    if contain_first {
+        steps.push(step(ResponseAction::BlockNetwork {
+            address: threat_analysis.source_address.clone(),
+            duration: CONTAINMENT_BLOCK_DURATION,
+        }));
     }
+    steps.push(step(action));
+    steps
+}
 
-    /// Validates response action before execution
-    async fn validate_response(&self, action: &ResponseAction) -> Result<(), GuardianError> {
-        match action {
-            ResponseAction::IsolateProcess { pid, .. } => {
-                if *pid == 1 {
-                    return Err(SecurityError {
-                        context: "Cannot isolate system init process".into(),
-                        source: None,
-                        severity: crate::utils::error::ErrorSeverity::High,
-                        timestamp: time::OffsetDateTime::now_utc(),
-                        correlation_id: uuid::Uuid::new_v4(),
-                        category: crate::utils::error::ErrorCategory::Security,
-                        retry_count: 0,
-                    });
+/// Response playbook: the action taken for a threat, preferring registered custom actions
+pub fn select_response_action(action_registry: &ResponseActionRegistry, threat_analysis: &ThreatAnalysis) -> ResponseAction {
+    // Registered custom actions take precedence over the built-in policy
+    if let Some(action) = action_registry.select(threat_analysis) {
+        return action;
+    }
+
+    // Low-trust binaries are handled more aggressively, trusted ones more gently
+    let severity = match threat_analysis.trust {
+        Some(trust) => trust.adjust_severity(&threat_analysis.severity),
+        None => threat_analysis.severity.clone(),
+    };
+
+    match severity {
+        ThreatLevel::Critical => ResponseAction::EmergencyShutdown {
+            reason: format!("Critical threat detected: {}", threat_analysis.description),
+        },
+        ThreatLevel::High => {
+            if let Some(pid) = threat_analysis.process_id {
+                ResponseAction::TerminateProcess {
+                    pid,
+                    force: true,
                 }
-            },
-            ResponseAction::TerminateProcess { pid, .. } => {
-                if *pid == 1 {
-                    return Err(SecurityError {
-                        context: "Cannot terminate system init process".into(),
-                        source: None,
-                        severity: crate::utils::error::ErrorSeverity::High,
-                        timestamp: time::OffsetDateTime::now_utc(),
-                        correlation_id: uuid::Uuid::new_v4(),
-                        category: crate::utils::error::ErrorCategory::Security,
-                        retry_count: 0,
-                    });
+            } else {
+                ResponseAction::BlockNetwork {
+                    address: threat_analysis.source_address.clone(),
+                    duration: Duration::from_secs(3600),
                 }
-            },
-            ResponseAction::BlockNetwork { address, duration } => {
-                if address == "127.0.0.1" || duration.as_secs() > 86400 {
-                    return Err(SecurityError {
-                        context: "Invalid network block parameters".into(),
-                        source: None,
-                        severity: crate::utils::error::ErrorSeverity::High,
-                        timestamp: time::OffsetDateTime::now_utc(),
-                        correlation_id: uuid::Uuid::new_v4(),
-                        category: crate::utils::error::ErrorCategory::Security,
-                        retry_count: 0,
-                    });
+            }
+        },
+        _ => {
+            if let Some(pid) = threat_analysis.process_id {
+                ResponseAction::IsolateProcess {
+                    pid,
+                    reason: threat_analysis.description.clone(),
+                }
+            } else {
+                ResponseAction::BlockNetwork {
+                    address: threat_analysis.source_address.clone(),
+                    duration: Duration::from_secs(1800),
                 }
-            },
-            ResponseAction::EmergencyShutdown { .. } => {
-                // Emergency shutdown is always valid but should be logged
-                warn!("Emergency shutdown response action validated");
             }
         }
-        Ok(())
     }
 }
 
@@ -338,8 +1038,7 @@ mod tests {
     use super::*;
     use std::sync::Arc;
 
-    #[tokio::test]
-    async fn test_response_execution() {
+    async fn test_engine() -> ResponseEngine {
         let temporal_client = Arc::new(temporal_sdk::Client::new(
             temporal_sdk::ConnectionOptions::default(),
         ).await.unwrap());
@@ -363,25 +1062,57 @@ mod tests {
             ).unwrap(),
         ).unwrap());
 
-        let engine = ResponseEngine::new(
+        ResponseEngine::new(
             temporal_client,
             event_bus,
             None,
-        ).await.unwrap();
+        ).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_response_execution() {
+        let engine = test_engine().await;
 
         let threat_analysis = ThreatAnalysis {
             severity: ThreatLevel::High,
             description: "Test threat".into(),
             process_id: Some(1000),
             source_address: "192.168.1.100".into(),
+            trust: None,
+            detection_id: None,
+            lineage: None,
         };
 
         let result = engine.execute_response(threat_analysis).await;
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_held_response_executes_once_approved() {
+        let limits = crate::config::ResponseLimits { max_terminated_processes: 0, max_blocked_addresses: 0, max_jails: 0 };
+        let guardrails = Arc::new(ResponseGuardrails::new(limits, Duration::from_secs(3600)));
+        let engine = test_engine().await.with_guardrails(Arc::clone(&guardrails));
+        let block = ResponseAction::BlockNetwork { address: "203.0.113.7".into(), duration: Duration::from_secs(60) };
+
+        // Past the limit the action is held rather than run
+        let held = engine.execute_step(block).await.unwrap();
+        assert!(!held.success());
+        let pending = guardrails.pending();
+        assert_eq!(pending.len(), 1);
+        assert!(held.error_context().unwrap().contains(&pending[0].id.to_string()));
+
+        // Approval releases it past the guardrails to its workflow, exactly once
+        let approved = engine.approve_response(pending[0].id, "alice").await;
+        assert!(guardrails.pending().is_empty());
+        match approved {
+            Ok(status) => assert!(!status.error_context().unwrap_or_default().starts_with("held for approval")),
+            Err(e) => assert!(e.to_string().contains("response workflow")),
+        }
+        assert!(engine.approve_response(pending[0].id, "alice").await.is_err());
+    }
+
     #[test]
     fn test_response_validation() {
         // Add response validation tests
     }
-}
\ No newline at end of file
+}
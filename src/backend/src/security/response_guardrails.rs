@@ -0,0 +1,258 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use chrono::{DateTime, Utc};
+use metrics::{counter, gauge};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::Serialize;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::config::{AppConfig, Environment, ResponseGuardrailConfig, ResponseLimits};
+use crate::security::response_engine::ResponseAction;
+
+// Constants for response guardrails
+const MAX_PENDING_APPROVALS: usize = 1000;
+
+static RESPONSE_GUARDRAILS: Lazy<Arc<ResponseGuardrails>> = Lazy::new(|| {
+    let config = ResponseGuardrailConfig::default();
+    Arc::new(ResponseGuardrails::new(config.limits_for(&Environment::Production), config.window))
+});
+
+/// Kinds of automatic response with a blast radius limit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GuardedKind {
+    TerminatedProcesses,
+    BlockedAddresses,
+    Jails,
+}
+
+impl GuardedKind {
+    fn of(action: &ResponseAction) -> Option<(Self, Option<&str>)> {
+        match action {
+            ResponseAction::TerminateProcess { .. } => Some((GuardedKind::TerminatedProcesses, None)),
+            ResponseAction::BlockNetwork { address, .. } => Some((GuardedKind::BlockedAddresses, Some(address))),
+            ResponseAction::IsolateProcess { .. } => Some((GuardedKind::Jails, None)),
+            _ => None,
+        }
+    }
+
+    fn limit(&self, limits: &ResponseLimits) -> u32 {
+        match self {
+            GuardedKind::TerminatedProcesses => limits.max_terminated_processes,
+            GuardedKind::BlockedAddresses => limits.max_blocked_addresses,
+            GuardedKind::Jails => limits.max_jails,
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            GuardedKind::TerminatedProcesses => "terminated_processes",
+            GuardedKind::BlockedAddresses => "blocked_addresses",
+            GuardedKind::Jails => "jails",
+        }
+    }
+}
+
+/// Why automatic responses were switched to approval-required mode
+#[derive(Debug, Clone, Serialize)]
+pub struct GuardrailTrip {
+    pub kind: GuardedKind,
+    pub limit: u32,
+    pub window: Duration,
+    pub at: DateTime<Utc>,
+}
+
+/// An automatic response held until an operator approves it
+#[derive(Debug, Clone, Serialize)]
+pub struct PendingResponse {
+    pub id: Uuid,
+    pub action: ResponseAction,
+    pub requested_at: DateTime<Utc>,
+}
+
+/// Outcome of checking an automatic response against the guardrails
+#[derive(Debug, Clone)]
+pub enum GuardrailDecision {
+    Proceed,
+    /// Held for approval; `tripped` is set when this action is the one that crossed a limit
+    Held { pending: Uuid, tripped: Option<GuardrailTrip> },
+}
+
+#[derive(Debug, Default)]
+struct GuardrailState {
+    usage: HashMap<GuardedKind, VecDeque<(Instant, Option<String>)>>,
+    tripped: Option<GuardrailTrip>,
+    pending: VecDeque<PendingResponse>,
+}
+
+/// Caps how many processes, addresses and jails automatic responses may touch per window;
+/// past a cap every response waits for operator approval until responses are resumed
+#[derive(Debug)]
+pub struct ResponseGuardrails {
+    enabled: Mutex<bool>,
+    limits: Mutex<ResponseLimits>,
+    window: Mutex<Duration>,
+    state: Mutex<GuardrailState>,
+}
+
+/// Returns the process-wide response guardrails
+pub fn response_guardrails() -> Arc<ResponseGuardrails> {
+    Arc::clone(&RESPONSE_GUARDRAILS)
+}
+
+/// Applies the limits of the configured environment; call again after a reload
+pub fn init_response_guardrails(config: &AppConfig) {
+    response_guardrails().configure(&config.response_guardrails, &config.environment);
+}
+
+impl ResponseGuardrails {
+    /// Creates guardrails enforcing the given limits over a sliding window
+    pub fn new(limits: ResponseLimits, window: Duration) -> Self {
+        Self {
+            enabled: Mutex::new(true),
+            limits: Mutex::new(limits),
+            window: Mutex::new(window),
+            state: Mutex::new(GuardrailState::default()),
+        }
+    }
+
+    /// Replaces the limits with those of the environment
+    pub fn configure(&self, config: &ResponseGuardrailConfig, environment: &Environment) {
+        let limits = config.limits_for(environment);
+        *self.enabled.lock() = config.enabled;
+        *self.limits.lock() = limits;
+        *self.window.lock() = config.window;
+        info!(enabled = config.enabled, ?environment, ?limits, window = ?config.window, "Response guardrails configured");
+    }
+
+    /// Counts an automatic response against its limit, holding it for approval past the limit
+    pub fn admit(&self, action: &ResponseAction) -> GuardrailDecision {
+        if !*self.enabled.lock() {
+            return GuardrailDecision::Proceed;
+        }
+        let limits = *self.limits.lock();
+        let window = *self.window.lock();
+        let mut state = self.state.lock();
+
+        if state.tripped.is_some() {
+            return GuardrailDecision::Held { pending: hold(&mut state, action), tripped: None };
+        }
+        let Some((kind, target)) = GuardedKind::of(action) else {
+            return GuardrailDecision::Proceed;
+        };
+
+        let now = Instant::now();
+        let usage = state.usage.entry(kind).or_default();
+        while usage.front().is_some_and(|(at, _)| now.duration_since(*at) >= window) {
+            usage.pop_front();
+        }
+        // Blocking an address already blocked in this window does not widen the blast radius
+        if target.is_some() && usage.iter().any(|(_, seen)| seen.as_deref() == target) {
+            return GuardrailDecision::Proceed;
+        }
+
+        let limit = kind.limit(&limits);
+        if usage.len() as u32 >= limit {
+            let trip = GuardrailTrip { kind, limit, window, at: Utc::now() };
+            error!(
+                target: "SECURITY-AUDIT",
+                kind = kind.label(),
+                limit,
+                ?window,
+                "Response guardrail exceeded, possible detector malfunction; automatic responses now require approval"
+            );
+            counter!("guardian.response.guardrail.trips", 1, "kind" => kind.label());
+            gauge!("guardian.response.guardrail.approval_required", 1.0);
+            state.tripped = Some(trip.clone());
+            return GuardrailDecision::Held { pending: hold(&mut state, action), tripped: Some(trip) };
+        }
+
+        usage.push_back((now, target.map(str::to_string)));
+        gauge!("guardian.response.guardrail.usage", usage.len() as f64, "kind" => kind.label());
+        GuardrailDecision::Proceed
+    }
+
+    /// Returns the trip that put responses into approval-required mode, if any
+    pub fn tripped(&self) -> Option<GuardrailTrip> {
+        self.state.lock().tripped.clone()
+    }
+
+    /// Returns responses waiting for approval, oldest first
+    pub fn pending(&self) -> Vec<PendingResponse> {
+        self.state.lock().pending.iter().cloned().collect()
+    }
+
+    /// Removes a held response so it can be executed by the approver
+    pub fn approve(&self, id: Uuid, approver: &str) -> Option<PendingResponse> {
+        let mut state = self.state.lock();
+        let index = state.pending.iter().position(|p| p.id == id)?;
+        let pending = state.pending.remove(index)?;
+        info!(target: "SECURITY-AUDIT", %id, action = pending.action.name(), approver, "Held response approved");
+        gauge!("guardian.response.guardrail.pending", state.pending.len() as f64);
+        Some(pending)
+    }
+
+    /// Returns to automatic responses with fresh windows; held responses stay pending
+    pub fn resume(&self, operator: &str) {
+        let mut state = self.state.lock();
+        if let Some(trip) = state.tripped.take() {
+            warn!(target: "SECURITY-AUDIT", kind = trip.kind.label(), operator, "Automatic responses resumed");
+        }
+        state.usage.clear();
+        gauge!("guardian.response.guardrail.approval_required", 0.0);
+    }
+}
+
+fn hold(state: &mut GuardrailState, action: &ResponseAction) -> Uuid {
+    if state.pending.len() >= MAX_PENDING_APPROVALS {
+        if let Some(dropped) = state.pending.pop_front() {
+            warn!(id = %dropped.id, action = dropped.action.name(), "Pending approvals full, dropping the oldest");
+        }
+    }
+    let id = Uuid::new_v4();
+    state.pending.push_back(PendingResponse { id, action: action.clone(), requested_at: Utc::now() });
+    counter!("guardian.response.guardrail.held", 1, "action" => action.name().to_string());
+    gauge!("guardian.response.guardrail.pending", state.pending.len() as f64);
+    id
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(address: &str) -> ResponseAction {
+        ResponseAction::BlockNetwork { address: address.into(), duration: Duration::from_secs(60) }
+    }
+
+    #[test]
+    fn test_exceeding_a_limit_requires_approval() {
+        let limits = ResponseLimits { max_terminated_processes: 5, max_blocked_addresses: 2, max_jails: 1 };
+        let guardrails = ResponseGuardrails::new(limits, Duration::from_secs(3600));
+
+        assert!(matches!(guardrails.admit(&block("10.0.0.1")), GuardrailDecision::Proceed));
+        assert!(matches!(guardrails.admit(&block("10.0.0.1")), GuardrailDecision::Proceed));
+        assert!(matches!(guardrails.admit(&block("10.0.0.2")), GuardrailDecision::Proceed));
+
+        // The third distinct address trips the guardrail, then everything is held
+        let GuardrailDecision::Held { pending, tripped: Some(trip) } = guardrails.admit(&block("10.0.0.3")) else {
+            panic!("expected the guardrail to trip");
+        };
+        assert_eq!(trip.kind, GuardedKind::BlockedAddresses);
+        let terminate = ResponseAction::TerminateProcess { pid: 4242, force: false };
+        assert!(matches!(guardrails.admit(&terminate), GuardrailDecision::Held { tripped: None, .. }));
+        assert_eq!(guardrails.pending().len(), 2);
+
+        assert!(guardrails.approve(pending, "alice").is_some());
+        assert!(guardrails.approve(pending, "alice").is_none());
+
+        guardrails.resume("alice");
+        assert!(guardrails.tripped().is_none());
+        assert!(matches!(guardrails.admit(&block("10.0.0.4")), GuardrailDecision::Proceed));
+    }
+}
@@ -0,0 +1,463 @@
+use std::{
+    collections::VecDeque,
+    io,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use chrono::SecondsFormat;
+use metrics::counter;
+use parking_lot::Mutex;
+use tokio::{
+    io::AsyncWriteExt,
+    net::{TcpStream, UdpSocket},
+    sync::Notify,
+};
+use tokio_rustls::{client::TlsStream, TlsConnector};
+use tracing::{info, instrument, warn};
+
+use crate::config::security_config::{SiemFormat, SiemTargetConfig, SiemTransport};
+use crate::security::audit::{AuditEvent, SecurityLevel};
+use crate::utils::error::GuardianError;
+use crate::utils::queue_metrics::{queue_registry, QueueMonitor};
+use crate::utils::retry::RetryPolicy;
+
+// Constants for SIEM export
+const VENDOR: &str = "Guardian";
+const PRODUCT: &str = "Guardian";
+const APP_NAME: &str = "guardian";
+/// RFC 5424 facility 13, log audit
+const SYSLOG_FACILITY: u8 = 13;
+/// Private enterprise number reserved for documentation (RFC 5612)
+const SD_ENTERPRISE_ID: u32 = 32473;
+const MAX_MSGID_LEN: usize = 32;
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+const MAX_RECONNECT_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Formats an audit event as a single line in the given wire format
+pub fn format_event(format: SiemFormat, event: &AuditEvent, host: &str) -> String {
+    match format {
+        SiemFormat::Syslog => format_syslog(event, host),
+        SiemFormat::Cef => format_cef(event, host),
+        SiemFormat::Leef => format_leef(event, host),
+        SiemFormat::Json => serde_json::to_string(event).unwrap_or_default(),
+    }
+}
+
+/// RFC 5424 line with the event identity in structured data and its payload as the message
+fn format_syslog(event: &AuditEvent, host: &str) -> String {
+    let severity = match event.severity() {
+        SecurityLevel::Critical => 2,
+        SecurityLevel::High => 3,
+        SecurityLevel::Medium => 4,
+        SecurityLevel::Low => 5,
+    };
+    let msg_id: String = event
+        .event_type()
+        .chars()
+        .take(MAX_MSGID_LEN)
+        .map(|c| if c.is_ascii_graphic() { c } else { '_' })
+        .collect();
+    let message = serde_json::json!({ "data": event.data(), "tags": event.tags() });
+
+    format!(
+        "<{}>1 {} {} {} {} {} [audit@{} id=\"{}\" sampling=\"{}\" source=\"{}\" correlation=\"{}\"] {}",
+        SYSLOG_FACILITY * 8 + severity,
+        event.timestamp().to_rfc3339_opts(SecondsFormat::Millis, true),
+        host,
+        APP_NAME,
+        std::process::id(),
+        msg_id,
+        SD_ENTERPRISE_ID,
+        escape_sd(&event.id().to_string()),
+        event.sampling_rate(),
+        escape_sd(event.source()),
+        escape_sd(event.correlation_id().unwrap_or("-")),
+        message
+    )
+}
+
+fn format_cef(event: &AuditEvent, host: &str) -> String {
+    format!(
+        "CEF:0|{}|{}|{}|{}|{}|{}|rt={} dvchost={} externalId={} cs1Label=source cs1={} cs2Label=correlationId cs2={} cfp1Label=samplingRate cfp1={} msg={}",
+        VENDOR,
+        PRODUCT,
+        env!("CARGO_PKG_VERSION"),
+        escape_cef_header(event.event_type()),
+        escape_cef_header(event.event_type()),
+        numeric_severity(event.severity()),
+        event.timestamp().timestamp_millis(),
+        escape_cef_extension(host),
+        event.id(),
+        escape_cef_extension(event.source()),
+        escape_cef_extension(event.correlation_id().unwrap_or("")),
+        event.sampling_rate(),
+        escape_cef_extension(&event.data().to_string())
+    )
+}
+
+/// LEEF 1.0 with the default tab delimiter between attributes
+fn format_leef(event: &AuditEvent, host: &str) -> String {
+    let attributes = [
+        ("devTime", event.timestamp().to_rfc3339_opts(SecondsFormat::Millis, true)),
+        ("devTimeFormat", "yyyy-MM-dd'T'HH:mm:ss.SSSX".to_string()),
+        ("sev", numeric_severity(event.severity()).to_string()),
+        ("identHostName", host.to_string()),
+        ("externalId", event.id().to_string()),
+        ("src", event.source().to_string()),
+        ("correlationId", event.correlation_id().unwrap_or("").to_string()),
+        ("samplingRate", event.sampling_rate().to_string()),
+        ("msg", event.data().to_string()),
+    ];
+    let attributes: Vec<String> = attributes
+        .iter()
+        .map(|(key, value)| format!("{}={}", key, value.replace(['\t', '\r', '\n'], " ")))
+        .collect();
+
+    format!(
+        "LEEF:1.0|{}|{}|{}|{}|{}",
+        VENDOR,
+        PRODUCT,
+        env!("CARGO_PKG_VERSION"),
+        escape_cef_header(event.event_type()),
+        attributes.join("\t")
+    )
+}
+
+/// CEF and LEEF severity on their 0-10 scale
+fn numeric_severity(level: &SecurityLevel) -> u8 {
+    match level {
+        SecurityLevel::Critical => 10,
+        SecurityLevel::High => 8,
+        SecurityLevel::Medium => 5,
+        SecurityLevel::Low => 3,
+    }
+}
+
+fn escape_sd(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace(']', "\\]")
+}
+
+fn escape_cef_header(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('|', "\\|")
+}
+
+fn escape_cef_extension(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('=', "\\=")
+        .replace('\r', "\\r")
+        .replace('\n', "\\n")
+}
+
+/// Frames a line for the transport, octet-counting syslog on streams (RFC 6587)
+fn frame(format: SiemFormat, transport: SiemTransport, line: &str) -> Vec<u8> {
+    match (transport, format) {
+        (SiemTransport::Udp, _) => line.as_bytes().to_vec(),
+        (_, SiemFormat::Syslog) => format!("{} {}", line.len(), line).into_bytes(),
+        _ => format!("{}\n", line).into_bytes(),
+    }
+}
+
+/// Open connection to a collector
+enum Connection {
+    Udp(UdpSocket),
+    Tcp(TcpStream),
+    Tls(Box<TlsStream<TcpStream>>),
+}
+
+impl Connection {
+    async fn send(&mut self, frame: &[u8]) -> io::Result<()> {
+        match self {
+            Connection::Udp(socket) => socket.send(frame).await.map(|_| ()),
+            Connection::Tcp(stream) => stream.write_all(frame).await,
+            Connection::Tls(stream) => {
+                stream.write_all(frame).await?;
+                stream.flush().await
+            }
+        }
+    }
+}
+
+/// Buffered forwarding to one collector
+struct SiemSink {
+    config: SiemTargetConfig,
+    tls: Option<(TlsConnector, rustls::ServerName)>,
+    /// Framed events and when they were buffered, oldest first
+    buffer: Mutex<VecDeque<(Instant, Vec<u8>)>>,
+    pending: Notify,
+    queue: Arc<QueueMonitor>,
+}
+
+impl SiemSink {
+    fn new(config: SiemTargetConfig) -> Result<Self, GuardianError> {
+        let tls = match config.transport {
+            SiemTransport::Tls => Some(tls_connector(&config)?),
+            _ => None,
+        };
+        let queue = queue_registry().register(QueueMonitor::new("audit_export", config.buffer_size));
+        Ok(Self {
+            config,
+            tls,
+            buffer: Mutex::new(VecDeque::new()),
+            pending: Notify::new(),
+            queue,
+        })
+    }
+
+    /// Buffers an event if it is severe enough, dropping the oldest one when full
+    fn offer(&self, event: &AuditEvent, host: &str) {
+        if !event.severity().is_at_least(&self.config.min_severity) {
+            return;
+        }
+        let line = format_event(self.config.format, event, host);
+        let framed = frame(self.config.format, self.config.transport, &line);
+
+        let mut buffer = self.buffer.lock();
+        if buffer.len() >= self.config.buffer_size {
+            buffer.pop_front();
+            counter!("guardian.audit.siem.dropped", 1, "target" => self.config.name.clone());
+        }
+        buffer.push_back((Instant::now(), framed));
+        self.queue.record_enqueue();
+        self.queue.observe(buffer.len(), buffer.front().map(|(at, _)| *at));
+        drop(buffer);
+        self.pending.notify_one();
+    }
+
+    fn front(&self) -> Option<Vec<u8>> {
+        self.buffer.lock().front().map(|(_, framed)| framed.clone())
+    }
+
+    /// Removes the delivered head of the buffer
+    fn delivered(&self) {
+        let mut buffer = self.buffer.lock();
+        if let Some((at, _)) = buffer.pop_front() {
+            self.queue.record_wait(at.elapsed());
+        }
+        self.queue.observe(buffer.len(), buffer.front().map(|(at, _)| *at));
+    }
+
+    /// Delivers buffered events in order, reconnecting with backoff until each one is sent
+    #[instrument(skip(self), fields(target = %self.config.name))]
+    async fn run(self: Arc<Self>) {
+        let policy = RetryPolicy {
+            max_attempts: u32::MAX,
+            max_backoff: MAX_RECONNECT_BACKOFF,
+            ..Default::default()
+        };
+        let mut connection: Option<Connection> = None;
+        let mut failures = 0;
+
+        loop {
+            let Some(framed) = self.front() else {
+                self.pending.notified().await;
+                continue;
+            };
+
+            let result = match connection.as_mut() {
+                Some(conn) => conn.send(&framed).await.map_err(|e| export_error("Failed to send audit event", e)),
+                None => match self.connect().await {
+                    Ok(conn) => {
+                        connection = Some(conn);
+                        continue;
+                    }
+                    Err(e) => Err(e),
+                },
+            };
+
+            match result {
+                Ok(()) => {
+                    if failures > 0 {
+                        info!(failures, "SIEM target reachable again");
+                    }
+                    failures = 0;
+                    self.delivered();
+                    counter!("guardian.audit.siem.exported", 1, "target" => self.config.name.clone());
+                }
+                Err(e) => {
+                    connection = None;
+                    failures += 1;
+                    counter!("guardian.audit.siem.errors", 1, "target" => self.config.name.clone());
+                    let delay = policy.delay(failures);
+                    warn!(error = %e, failures, delay_ms = delay.as_millis() as u64, "SIEM export failed, retrying");
+                    tokio::time::sleep(delay).await;
+                }
+            }
+        }
+    }
+
+    async fn connect(&self) -> Result<Connection, GuardianError> {
+        let address = &self.config.address;
+        match tokio::time::timeout(CONNECT_TIMEOUT, self.open()).await {
+            Ok(result) => result.map_err(|e| export_error(&format!("Failed to connect to {}", address), e)),
+            Err(e) => Err(export_error(&format!("Timed out connecting to {}", address), e)),
+        }
+    }
+
+    async fn open(&self) -> io::Result<Connection> {
+        let address = self.config.address.as_str();
+        match self.config.transport {
+            SiemTransport::Udp => {
+                let socket = UdpSocket::bind("0.0.0.0:0").await?;
+                socket.connect(address).await?;
+                Ok(Connection::Udp(socket))
+            }
+            SiemTransport::Tcp => Ok(Connection::Tcp(TcpStream::connect(address).await?)),
+            SiemTransport::Tls => {
+                let (connector, server_name) = self.tls.clone().expect("TLS sink has a connector");
+                let stream = TcpStream::connect(address).await?;
+                Ok(Connection::Tls(Box::new(connector.connect(server_name, stream).await?)))
+            }
+        }
+    }
+}
+
+/// Forwards audit events to external SIEM collectors
+pub struct SiemExporter {
+    host: String,
+    sinks: Vec<Arc<SiemSink>>,
+}
+
+impl std::fmt::Debug for SiemExporter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SiemExporter")
+            .field("host", &self.host)
+            .field("targets", &self.sinks.iter().map(|s| &s.config.name).collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl SiemExporter {
+    /// Connects to every target in the background
+    pub fn start(targets: &[SiemTargetConfig]) -> Result<Arc<Self>, GuardianError> {
+        let sinks = targets
+            .iter()
+            .cloned()
+            .map(|config| SiemSink::new(config).map(Arc::new))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for sink in &sinks {
+            info!(target = %sink.config.name, address = %sink.config.address, "Starting SIEM export");
+            tokio::spawn(Arc::clone(sink).run());
+        }
+
+        Ok(Arc::new(Self {
+            host: sys_info::hostname().unwrap_or_else(|_| "-".to_string()),
+            sinks,
+        }))
+    }
+
+    /// Queues an event for every target whose severity threshold it meets
+    pub fn export(&self, event: &AuditEvent) {
+        for sink in &self.sinks {
+            sink.offer(event, &self.host);
+        }
+    }
+}
+
+fn tls_connector(config: &SiemTargetConfig) -> Result<(TlsConnector, rustls::ServerName), GuardianError> {
+    let ca_path = config.ca_path.as_deref().unwrap_or_default();
+    let pem = std::fs::read(ca_path).map_err(|e| tls_error(format!("Failed to read SIEM CA {}", ca_path), Some(Box::new(e))))?;
+    let certs = rustls_pemfile::certs(&mut pem.as_slice())
+        .map_err(|e| tls_error(format!("Failed to parse SIEM CA {}", ca_path), Some(Box::new(e))))?;
+
+    let mut roots = rustls::RootCertStore::empty();
+    for cert in certs {
+        roots
+            .add(&rustls::Certificate(cert))
+            .map_err(|e| tls_error(format!("Invalid SIEM CA in {}", ca_path), Some(Box::new(e))))?;
+    }
+    let client_config = rustls::ClientConfig::builder()
+        .with_safe_defaults()
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+
+    let host = match &config.server_name {
+        Some(name) => name.as_str(),
+        None => config.address.rsplit_once(':').map_or(config.address.as_str(), |(host, _)| host),
+    };
+    let server_name = rustls::ServerName::try_from(host)
+        .map_err(|e| tls_error(format!("Invalid SIEM server name {}", host), Some(Box::new(e))))?;
+
+    Ok((TlsConnector::from(Arc::new(client_config)), server_name))
+}
+
+fn export_error(context: &str, e: impl std::error::Error + Send + Sync + 'static) -> GuardianError {
+    GuardianError::SystemError {
+        context: context.into(),
+        source: Some(Box::new(e)),
+        severity: crate::utils::error::ErrorSeverity::Medium,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: crate::utils::error::ErrorCategory::System,
+        retry_count: 0,
+    }
+}
+
+fn tls_error(context: String, source: Option<Box<dyn std::error::Error + Send + Sync>>) -> GuardianError {
+    GuardianError::SecurityError {
+        context,
+        source,
+        severity: crate::utils::error::ErrorSeverity::High,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: crate::utils::error::ErrorCategory::Security,
+        retry_count: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(severity: SecurityLevel) -> AuditEvent {
+        AuditEvent::new("auth|login=failed".into(), severity, "auth_service".into(), Some("corr-1".into()))
+            .with_data(serde_json::json!({"user": "a=b"}))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_syslog_and_cef_layout() {
+        let event = event(SecurityLevel::High);
+
+        let syslog = format_event(SiemFormat::Syslog, &event, "host1");
+        assert!(syslog.starts_with("<107>1 "));
+        assert!(syslog.contains(" host1 guardian "));
+        assert!(syslog.contains("source=\"auth_service\" correlation=\"corr-1\"]"));
+
+        let cef = format_event(SiemFormat::Cef, &event, "host1");
+        assert!(cef.starts_with("CEF:0|Guardian|Guardian|"));
+        assert!(cef.contains("|auth\\|login=failed|auth\\|login=failed|8|"));
+        assert!(cef.contains("msg={\"user\":\"a\\=b\"}"));
+
+        let framed = frame(SiemFormat::Syslog, SiemTransport::Tls, &syslog);
+        assert!(framed.starts_with(format!("{} <107>", syslog.len()).as_bytes()));
+    }
+
+    #[test]
+    fn test_sink_filters_severity_and_drops_oldest() {
+        let sink = SiemSink::new(SiemTargetConfig {
+            name: "soc".into(),
+            address: "127.0.0.1:6514".into(),
+            format: SiemFormat::Json,
+            transport: SiemTransport::Tcp,
+            min_severity: SecurityLevel::High,
+            ca_path: None,
+            server_name: None,
+            buffer_size: 2,
+        })
+        .unwrap();
+
+        sink.offer(&event(SecurityLevel::Medium), "host1");
+        assert!(sink.front().is_none());
+
+        let first = event(SecurityLevel::High);
+        sink.offer(&first, "host1");
+        sink.offer(&event(SecurityLevel::Critical), "host1");
+        sink.offer(&event(SecurityLevel::Critical), "host1");
+        assert_eq!(sink.buffer.lock().len(), 2);
+        let head = String::from_utf8(sink.front().unwrap()).unwrap();
+        assert!(!head.contains(&first.id().to_string()));
+    }
+}
@@ -0,0 +1,507 @@
+//! Historical threat analytics over the event store
+//!
+//! Queries use a small DSL:
+//!
+//! ```text
+//! <count|mttr> [by <severity|type|day|process|address>] [where <dimension>=<value> [and ...]]
+//!              [since <age>] [until <age>] [top <n>]
+//! ```
+//!
+//! e.g. `count by severity since 7d` or `count by process where severity=high since 24h top 10`.
+//! Partitions outside the time range are never read, and sealed partitions lying wholly inside
+//! it are folded once and their partial aggregates cached, so repeated queries only decompress
+//! the partitions at the edges of the range and the one still taking writes.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use lru::LruCache;
+use metrics::{counter, histogram};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, instrument};
+
+use crate::core::guardian::TenantId;
+use crate::security::threat_detection::ThreatNotice;
+use crate::storage::event_store::{Event as StoredEvent, EventQuery, EventStore, PartitionSpan};
+use crate::utils::error::{ErrorCategory, ErrorSeverity, GuardianError};
+use crate::utils::ids::{next_id, IdKind};
+
+// Constants for threat analytics
+pub const THREAT_EVENT_TYPE: &str = "threat_detected";
+pub const RESPONSE_EVENT_TYPE: &str = "response_executed";
+const SEALED_CACHE_CAPACITY: usize = 4096;
+const UNGROUPED_KEY: &str = "all";
+
+/// Aggregated quantity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Measure {
+    /// Number of detections
+    Count,
+    /// Mean seconds from detection to its first automatic response
+    MeanTimeToRespond,
+}
+
+/// Attribute detections are grouped or filtered by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Dimension {
+    Severity,
+    ThreatType,
+    Day,
+    Process,
+    Address,
+}
+
+impl Dimension {
+    fn parse(value: &str) -> Result<Self, GuardianError> {
+        match value {
+            "severity" => Ok(Self::Severity),
+            "type" => Ok(Self::ThreatType),
+            "day" => Ok(Self::Day),
+            "process" => Ok(Self::Process),
+            "address" => Ok(Self::Address),
+            other => Err(query_error(format!(
+                "Unknown dimension {}, expected severity, type, day, process or address",
+                other
+            ))),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Severity => "severity",
+            Self::ThreatType => "type",
+            Self::Day => "day",
+            Self::Process => "process",
+            Self::Address => "address",
+        }
+    }
+}
+
+/// A parsed analytics query with its time range resolved to Unix seconds
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnalyticsQuery {
+    pub measure: Measure,
+    pub group_by: Option<Dimension>,
+    pub filters: Vec<(Dimension, String)>,
+    pub since: Option<u64>,
+    pub until: Option<u64>,
+    pub limit: Option<usize>,
+}
+
+impl AnalyticsQuery {
+    /// Parses a query, resolving relative ages against `now`
+    pub fn parse(input: &str, now: u64) -> Result<Self, GuardianError> {
+        let tokens: Vec<String> = input.split_whitespace().map(str::to_lowercase).collect();
+        let mut tokens = tokens.iter().map(String::as_str);
+
+        let measure = match tokens.next() {
+            Some("count") => Measure::Count,
+            Some("mttr") => Measure::MeanTimeToRespond,
+            Some(other) => return Err(query_error(format!("Unknown measure {}, expected count or mttr", other))),
+            None => return Err(query_error("Empty analytics query".into())),
+        };
+        let mut query = Self { measure, group_by: None, filters: Vec::new(), since: None, until: None, limit: None };
+
+        while let Some(keyword) = tokens.next() {
+            match keyword {
+                "by" => query.group_by = Some(Dimension::parse(operand(keyword, &mut tokens)?)?),
+                "where" | "and" => {
+                    let condition = operand(keyword, &mut tokens)?;
+                    let (dimension, value) = condition
+                        .split_once('=')
+                        .ok_or_else(|| query_error(format!("Expected dimension=value, got {}", condition)))?;
+                    query.filters.push((Dimension::parse(dimension)?, value.to_string()));
+                }
+                "since" => query.since = Some(now.saturating_sub(parse_age(operand(keyword, &mut tokens)?)?)),
+                "until" => query.until = Some(now.saturating_sub(parse_age(operand(keyword, &mut tokens)?)?)),
+                "top" => {
+                    let value = operand(keyword, &mut tokens)?;
+                    let limit = value.parse::<usize>().ok().filter(|n| *n > 0);
+                    query.limit = Some(limit.ok_or_else(|| query_error(format!("Invalid top value {}", value)))?);
+                }
+                other => return Err(query_error(format!("Unexpected {} in analytics query", other))),
+            }
+        }
+
+        if let (Some(since), Some(until)) = (query.since, query.until) {
+            if since > until {
+                return Err(query_error("Query range ends before it starts".into()));
+            }
+        }
+        Ok(query)
+    }
+
+    /// Identifies the aggregation independent of its time range, for caching sealed partitions
+    fn shape(&self, tenant_id: &TenantId) -> String {
+        let filters: Vec<String> = self.filters.iter().map(|(d, v)| format!("{}={}", d.as_str(), v)).collect();
+        format!(
+            "{}|{:?}|{}|{}",
+            tenant_id,
+            self.measure,
+            self.group_by.map_or("", |d| d.as_str()),
+            filters.join(",")
+        )
+    }
+}
+
+/// One group of an analytics result
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StatRow {
+    pub key: String,
+    /// The count, or the mean in seconds
+    pub value: f64,
+    /// Detections behind the value
+    pub samples: u64,
+}
+
+/// Result of an analytics query
+#[derive(Debug, Clone, Serialize)]
+pub struct ThreatStats {
+    pub query: AnalyticsQuery,
+    pub rows: Vec<StatRow>,
+    pub partitions_scanned: usize,
+    pub partitions_cached: usize,
+}
+
+/// A detection as recorded for analytics
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RecordedThreat {
+    detection_id: String,
+    tenant_id: String,
+    severity: String,
+    threat_type: String,
+    #[serde(default)]
+    process: Option<String>,
+    #[serde(default)]
+    address: Option<String>,
+}
+
+/// A response as recorded for analytics
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RecordedResponse {
+    detection_id: String,
+    tenant_id: String,
+    action: String,
+}
+
+/// Aggregate of one partition, merged across partitions into the final result
+#[derive(Debug, Clone, Default)]
+struct Partial {
+    counts: HashMap<String, u64>,
+    /// Detection time and group of each detection, for joining responses
+    detections: HashMap<String, (u64, String)>,
+    /// First response time per detection
+    responses: HashMap<String, u64>,
+}
+
+impl Partial {
+    fn merge(&mut self, other: &Partial) {
+        for (key, count) in &other.counts {
+            *self.counts.entry(key.clone()).or_default() += count;
+        }
+        self.detections.extend(other.detections.iter().map(|(k, v)| (k.clone(), v.clone())));
+        for (id, at) in &other.responses {
+            let first = self.responses.entry(id.clone()).or_insert(*at);
+            *first = (*first).min(*at);
+        }
+    }
+}
+
+/// Answers analytics queries over recorded detections and responses
+pub struct ThreatAnalytics {
+    event_store: Arc<EventStore>,
+    sealed: Mutex<LruCache<(String, String), Arc<Partial>>>,
+}
+
+impl std::fmt::Debug for ThreatAnalytics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ThreatAnalytics").field("cached", &self.sealed.lock().len()).finish()
+    }
+}
+
+impl ThreatAnalytics {
+    pub fn new(event_store: Arc<EventStore>) -> Self {
+        Self {
+            event_store,
+            sealed: Mutex::new(LruCache::new(SEALED_CACHE_CAPACITY)),
+        }
+    }
+
+    /// Runs a DSL query for the tenant
+    pub async fn query(&self, tenant_id: &TenantId, input: &str) -> Result<ThreatStats, GuardianError> {
+        let query = AnalyticsQuery::parse(input, unix_now())?;
+        self.run(tenant_id, query).await
+    }
+
+    /// Folds each partition in range into the aggregate, reusing cached sealed partitions
+    #[instrument(skip(self))]
+    pub async fn run(&self, tenant_id: &TenantId, query: AnalyticsQuery) -> Result<ThreatStats, GuardianError> {
+        let started = std::time::Instant::now();
+        let shape = query.shape(tenant_id);
+        let tenant = tenant_id.to_string();
+        let mut total = Partial::default();
+        let mut scanned = 0;
+        let mut cached = 0;
+
+        for span in self.event_store.partition_spans(query.since, query.until).await {
+            let cacheable = span.is_sealed() && span.within(query.since, query.until);
+            let key = (span.name.clone(), shape.clone());
+            if cacheable {
+                if let Some(partial) = self.sealed.lock().get(&key).cloned() {
+                    total.merge(&partial);
+                    cached += 1;
+                    continue;
+                }
+            }
+
+            let partial = self.fold_partition(&span, &tenant, &query).await?;
+            scanned += 1;
+            total.merge(&partial);
+            if cacheable {
+                self.sealed.lock().put(key, Arc::new(partial));
+            }
+        }
+
+        counter!("guardian.analytics.partitions_scanned", scanned as u64);
+        counter!("guardian.analytics.partitions_cached", cached as u64);
+        histogram!("guardian.analytics.query_seconds", started.elapsed().as_secs_f64());
+        debug!(scanned, cached, "Analytics query complete");
+
+        let rows = rows(&query, total);
+        Ok(ThreatStats { query, rows, partitions_scanned: scanned, partitions_cached: cached })
+    }
+
+    async fn fold_partition(&self, span: &PartitionSpan, tenant: &str, query: &AnalyticsQuery) -> Result<Partial, GuardianError> {
+        let event_type = match query.measure {
+            Measure::Count => Some(THREAT_EVENT_TYPE.to_string()),
+            // Responses are joined to detections, so both types are read
+            Measure::MeanTimeToRespond => None,
+        };
+        let events = self
+            .event_store
+            .read_partition(
+                &span.name,
+                &EventQuery { start_time: query.since, end_time: query.until, event_type, limit: None },
+            )
+            .await?;
+        Ok(fold(events, tenant, query))
+    }
+}
+
+/// Builds the stored event for a detection
+pub fn detection_event(notice: &ThreatNotice) -> Result<StoredEvent, GuardianError> {
+    let recorded = RecordedThreat {
+        detection_id: notice.detection_id.clone(),
+        tenant_id: notice.tenant_id.to_string(),
+        severity: format!("{:?}", notice.level).to_lowercase(),
+        threat_type: notice.prediction_type.clone(),
+        process: notice.details.get("process").cloned(),
+        address: notice.details.get("address").cloned(),
+    };
+    stored_event(THREAT_EVENT_TYPE, serde_json::to_value(recorded))
+}
+
+/// Builds the stored event for a response taken against a detection
+pub fn response_event(tenant_id: &TenantId, detection_id: &str, action: &str) -> Result<StoredEvent, GuardianError> {
+    let recorded = RecordedResponse {
+        detection_id: detection_id.to_string(),
+        tenant_id: tenant_id.to_string(),
+        action: action.to_string(),
+    };
+    stored_event(RESPONSE_EVENT_TYPE, serde_json::to_value(recorded))
+}
+
+fn stored_event(event_type: &str, payload: serde_json::Result<serde_json::Value>) -> Result<StoredEvent, GuardianError> {
+    Ok(StoredEvent {
+        id: next_id(IdKind::Event).to_string(),
+        timestamp: unix_now(),
+        event_type: event_type.to_string(),
+        payload: payload.map_err(|e| query_error(format!("Failed to serialize analytics event: {}", e)))?,
+        integrity_hash: String::new(),
+    })
+}
+
+/// Aggregates one partition's events for the tenant
+fn fold(events: Vec<StoredEvent>, tenant: &str, query: &AnalyticsQuery) -> Partial {
+    let mut partial = Partial::default();
+    for event in events {
+        match event.event_type.as_str() {
+            THREAT_EVENT_TYPE => {
+                let Ok(threat) = serde_json::from_value::<RecordedThreat>(event.payload) else {
+                    continue;
+                };
+                if threat.tenant_id != tenant
+                    || !query.filters.iter().all(|(d, v)| value_of(&threat, *d, event.timestamp) == *v)
+                {
+                    continue;
+                }
+                let key = query
+                    .group_by
+                    .map_or_else(|| UNGROUPED_KEY.to_string(), |d| value_of(&threat, d, event.timestamp));
+                match query.measure {
+                    Measure::Count => *partial.counts.entry(key).or_default() += 1,
+                    Measure::MeanTimeToRespond => {
+                        partial.detections.insert(threat.detection_id, (event.timestamp, key));
+                    }
+                }
+            }
+            RESPONSE_EVENT_TYPE if query.measure == Measure::MeanTimeToRespond => {
+                let Ok(response) = serde_json::from_value::<RecordedResponse>(event.payload) else {
+                    continue;
+                };
+                if response.tenant_id == tenant {
+                    let first = partial.responses.entry(response.detection_id).or_insert(event.timestamp);
+                    *first = (*first).min(event.timestamp);
+                }
+            }
+            _ => {}
+        }
+    }
+    partial
+}
+
+fn value_of(threat: &RecordedThreat, dimension: Dimension, timestamp: u64) -> String {
+    match dimension {
+        Dimension::Severity => threat.severity.clone(),
+        Dimension::ThreatType => threat.threat_type.to_lowercase(),
+        Dimension::Day => time::OffsetDateTime::from_unix_timestamp(timestamp as i64)
+            .map(|t| t.date().to_string())
+            .unwrap_or_default(),
+        Dimension::Process => threat.process.clone().unwrap_or_else(|| "unknown".into()),
+        Dimension::Address => threat.address.clone().unwrap_or_else(|| "unknown".into()),
+    }
+}
+
+/// Turns the merged aggregate into rows; days read chronologically, other groups largest first
+fn rows(query: &AnalyticsQuery, total: Partial) -> Vec<StatRow> {
+    let mut rows: Vec<StatRow> = match query.measure {
+        Measure::Count => total
+            .counts
+            .into_iter()
+            .map(|(key, count)| StatRow { key, value: count as f64, samples: count })
+            .collect(),
+        Measure::MeanTimeToRespond => {
+            let mut groups: BTreeMap<String, (u64, u64)> = BTreeMap::new();
+            for (id, (detected_at, key)) in total.detections {
+                if let Some(responded_at) = total.responses.get(&id) {
+                    let group = groups.entry(key).or_default();
+                    group.0 += responded_at.saturating_sub(detected_at);
+                    group.1 += 1;
+                }
+            }
+            groups
+                .into_iter()
+                .map(|(key, (seconds, samples))| StatRow { key, value: seconds as f64 / samples as f64, samples })
+                .collect()
+        }
+    };
+
+    if query.group_by == Some(Dimension::Day) {
+        rows.sort_by(|a, b| a.key.cmp(&b.key));
+    } else {
+        rows.sort_by(|a, b| b.value.total_cmp(&a.value).then_with(|| a.key.cmp(&b.key)));
+    }
+    rows.truncate(query.limit.unwrap_or(usize::MAX));
+    rows
+}
+
+/// Takes the value following a keyword
+fn operand<'a>(keyword: &str, tokens: &mut impl Iterator<Item = &'a str>) -> Result<&'a str, GuardianError> {
+    tokens.next().ok_or_else(|| query_error(format!("Missing value after {}", keyword)))
+}
+
+/// Parses an age such as `90s`, `15m`, `2h`, `7d` or `4w`
+fn parse_age(value: &str) -> Result<u64, GuardianError> {
+    let invalid = || query_error(format!("Invalid age {}, expected e.g. 24h or 7d", value));
+    let split = value.find(|c: char| !c.is_ascii_digit()).ok_or_else(invalid)?;
+    let amount: u64 = value[..split].parse().map_err(|_| invalid())?;
+    let unit = match &value[split..] {
+        "s" => 1,
+        "m" => 60,
+        "h" => 3600,
+        "d" => 86400,
+        "w" => 7 * 86400,
+        _ => return Err(invalid()),
+    };
+    Ok(amount * unit)
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+fn query_error(context: String) -> GuardianError {
+    GuardianError::ValidationError {
+        context,
+        source: None,
+        severity: ErrorSeverity::Low,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: ErrorCategory::Validation,
+        retry_count: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::guardian::TenantContext;
+
+    fn threat(id: &str, severity: &str, process: &str, timestamp: u64) -> StoredEvent {
+        StoredEvent {
+            id: id.into(),
+            timestamp,
+            event_type: THREAT_EVENT_TYPE.into(),
+            payload: serde_json::json!({
+                "detection_id": id,
+                "tenant_id": "default",
+                "severity": severity,
+                "threat_type": "malware",
+                "process": process,
+            }),
+            integrity_hash: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_query_parsing() {
+        let query = AnalyticsQuery::parse("count by process where severity=high since 1d top 5", 100_000).unwrap();
+        assert_eq!(query.measure, Measure::Count);
+        assert_eq!(query.group_by, Some(Dimension::Process));
+        assert_eq!(query.filters, vec![(Dimension::Severity, "high".to_string())]);
+        assert_eq!(query.since, Some(100_000 - 86400));
+        assert_eq!(query.limit, Some(5));
+
+        assert!(AnalyticsQuery::parse("", 0).is_err());
+        assert!(AnalyticsQuery::parse("sum by severity", 0).is_err());
+        assert!(AnalyticsQuery::parse("count by colour", 0).is_err());
+        assert!(AnalyticsQuery::parse("count since 1h until 2h", 10_000).is_err());
+    }
+
+    #[test]
+    fn test_partials_merge_into_ranked_rows() {
+        let count = AnalyticsQuery::parse("count by process where severity=high", 0).unwrap();
+        let mut total = fold(
+            vec![threat("a", "high", "sshd", 10), threat("b", "high", "nc", 20), threat("c", "low", "nc", 30)],
+            "default",
+            &count,
+        );
+        total.merge(&fold(vec![threat("d", "high", "nc", 40)], "default", &count));
+        let ranked = rows(&count, total);
+        assert_eq!(ranked[0], StatRow { key: "nc".into(), value: 2.0, samples: 2 });
+        assert_eq!(ranked[1].key, "sshd");
+
+        // A response in a later partition still joins its detection
+        let mttr = AnalyticsQuery::parse("mttr", 0).unwrap();
+        let mut total = fold(vec![threat("a", "high", "sshd", 100)], "default", &mttr);
+        let response = response_event(TenantContext::default().tenant_id(), "a", "isolate_process").unwrap();
+        total.merge(&fold(vec![StoredEvent { timestamp: 130, ..response }], "default", &mttr));
+        assert_eq!(rows(&mttr, total), vec![StatRow { key: UNGROUPED_KEY.into(), value: 30.0, samples: 1 }]);
+    }
+}
@@ -1,29 +1,49 @@
 use std::{
-    collections::HashMap,
+    collections::{HashMap, VecDeque},
     sync::{
         atomic::{AtomicBool, Ordering},
         Arc,
     },
-    time::{Duration, Instant},
+    time::{Duration, Instant, SystemTime},
 };
-use tokio::sync::RwLock;
+use tokio::sync::{broadcast, RwLock};
 use tracing::{debug, error, info, instrument, warn};
 use lru::LruCache;
 use serde::{Deserialize, Serialize};
 
 use crate::utils::error::{GuardianError, SecurityError};
 use crate::ml::inference_engine::{InferenceEngine, Prediction};
+use crate::core::capabilities::{capabilities, Capability};
+use crate::core::collectors::collectors;
 use crate::core::event_bus::{EventBus, Event, EventPriority};
+use crate::core::guardian::{TenantContext, TenantId};
+use crate::core::resource_governor::{governor, Subsystem};
+use crate::security::anomaly_detection::SystemData;
+use crate::security::content_simulation::SampleBatch;
+use crate::security::degradation::degradation;
+use crate::security::detection_pipeline::DetectionPipeline;
+use crate::security::pipeline_latency::{pipeline_latency, LatencyStage};
+use crate::security::threat_analytics;
+use crate::storage::event_store::EventStore;
+use crate::storage::WritePriority;
 use crate::utils::metrics::MetricsCollector;
+use crate::utils::ids::{next_id, IdKind};
+
+pub use crate::security::detection_pipeline::{
+    DetectionContext, DetectionPipelineBuilder, PipelineConfig, PipelineStage, StageConfig,
+    StageFactory, StageKind, StageRegistry,
+};
 
 // Constants for threat detection configuration
 const THREAT_DETECTION_VERSION: &str = "1.1.0";
 const MAX_BATCH_SIZE: usize = 128;
-const MIN_BATCH_SIZE: usize = 16;
 const DETECTION_INTERVAL: Duration = Duration::from_millis(50);
 const CONFIDENCE_THRESHOLD: f32 = 0.95;
 const CACHE_SIZE: usize = 1024;
 const CIRCUIT_BREAKER_THRESHOLD: u32 = 5;
+const HISTORY_SAMPLE_INTERVAL: Duration = Duration::from_secs(60);
+const RECENT_THREATS_CAPACITY: usize = 1024;
+const THREAT_FEED_BUFFER: usize = 256;
 
 /// Threat severity levels
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -34,6 +54,53 @@ pub enum ThreatLevel {
     Low,
 }
 
+/// A detection as delivered to live watchers
+#[derive(Debug, Clone, Serialize)]
+pub struct ThreatNotice {
+    pub detection_id: String,
+    pub tenant_id: TenantId,
+    pub level: ThreatLevel,
+    pub confidence: f32,
+    pub prediction_type: String,
+    pub detected_at: SystemTime,
+    pub details: HashMap<String, String>,
+}
+
+/// Recent detections kept for late watchers, and the channel live watchers subscribe to
+#[derive(Debug)]
+struct ThreatFeed {
+    recent: parking_lot::Mutex<VecDeque<ThreatNotice>>,
+    live: broadcast::Sender<ThreatNotice>,
+}
+
+impl ThreatFeed {
+    fn new() -> Self {
+        Self {
+            recent: parking_lot::Mutex::new(VecDeque::with_capacity(RECENT_THREATS_CAPACITY)),
+            live: broadcast::channel(THREAT_FEED_BUFFER).0,
+        }
+    }
+
+    fn publish(&self, notice: ThreatNotice) {
+        // Sending under the lock keeps a watcher's backlog and live stream free of gaps and repeats
+        let mut recent = self.recent.lock();
+        if recent.len() == RECENT_THREATS_CAPACITY {
+            recent.pop_front();
+        }
+        recent.push_back(notice.clone());
+        let _ = self.live.send(notice);
+    }
+
+    fn watch(&self, since: Option<SystemTime>) -> (Vec<ThreatNotice>, broadcast::Receiver<ThreatNotice>) {
+        let recent = self.recent.lock();
+        let backlog = match since {
+            Some(since) => recent.iter().filter(|n| n.detected_at >= since).cloned().collect(),
+            None => Vec::new(),
+        };
+        (backlog, self.live.subscribe())
+    }
+}
+
 /// Configuration for threat detection
 #[derive(Debug, Clone)]
 struct ThreatDetectionConfig {
@@ -80,6 +147,12 @@ pub struct ThreatDetector {
     running: AtomicBool,
     circuit_breaker: CircuitBreaker,
     feature_cache: LruCache<String, FeatureVector>,
+    tenant: TenantContext,
+    pipeline: Arc<DetectionPipeline>,
+    sample_history: Option<Arc<EventStore>>,
+    last_sample: Arc<parking_lot::Mutex<Option<Instant>>>,
+    feed: Arc<ThreatFeed>,
+    threat_history: Option<Arc<EventStore>>,
 }
 
 impl ThreatDetector {
@@ -91,7 +164,12 @@ impl ThreatDetector {
         config: Option<ThreatDetectionConfig>,
     ) -> Self {
         let config = config.unwrap_or_default();
-        
+        let pipeline = Arc::new(DetectionPipeline::default_pipeline(
+            Arc::clone(&inference_engine),
+            config.batch_size,
+            config.confidence_threshold,
+        ));
+
         Self {
             inference_engine,
             event_bus,
@@ -105,9 +183,63 @@ impl ThreatDetector {
                 failure_count: AtomicBool::new(false),
             },
             feature_cache: LruCache::new(CACHE_SIZE),
+            tenant: TenantContext::default(),
+            pipeline,
+            sample_history: None,
+            last_sample: Arc::new(parking_lot::Mutex::new(None)),
+            feed: Arc::new(ThreatFeed::new()),
+            threat_history: None,
         }
     }
 
+    /// Publishes detections on the tenant's event topics
+    pub fn with_tenant(mut self, tenant: TenantContext) -> Self {
+        self.tenant = tenant;
+        self
+    }
+
+    /// Returns the tenant whose detections this detector produces
+    pub fn tenant(&self) -> &TenantContext {
+        &self.tenant
+    }
+
+    /// Replaces the fixed detection cycle with a composed pipeline
+    pub fn with_pipeline(mut self, pipeline: DetectionPipeline) -> Self {
+        info!(pipeline = pipeline.name(), stages = ?pipeline.stage_names(), "Using detection pipeline");
+        self.pipeline = Arc::new(pipeline);
+        self
+    }
+
+    /// Replaces the fixed detection cycle with the configured pipeline, its inference stages
+    /// bound to this detector's engine
+    pub fn with_pipeline_config(self, config: &PipelineConfig) -> Result<Self, GuardianError> {
+        let registry = StageRegistry::with_builtins(Arc::clone(&self.inference_engine));
+        let pipeline = DetectionPipeline::from_config(config, &registry)?;
+        Ok(self.with_pipeline(pipeline))
+    }
+
+    /// Periodically records cycle samples and alerts for content pack simulation
+    pub fn with_sample_history(mut self, event_store: Arc<EventStore>) -> Self {
+        self.sample_history = Some(event_store);
+        self
+    }
+
+    /// Records every detection in the event store for historical analytics
+    pub fn with_threat_history(mut self, event_store: Arc<EventStore>) -> Self {
+        self.threat_history = Some(event_store);
+        self
+    }
+
+    /// Returns detections made since `since`, still held in memory, and a receiver for new ones
+    pub fn watch_threats(&self, since: Option<SystemTime>) -> (Vec<ThreatNotice>, broadcast::Receiver<ThreatNotice>) {
+        self.feed.watch(since)
+    }
+
+    /// Returns the detection pipeline run on every cycle
+    pub fn pipeline(&self) -> &DetectionPipeline {
+        &self.pipeline
+    }
+
     /// Starts the threat detection service
     #[instrument(skip(self))]
     pub async fn start(&self) -> Result<(), GuardianError> {
@@ -148,8 +280,10 @@ impl ThreatDetector {
     /// Performs health check of the detection service
     #[instrument(skip(self))]
     pub async fn health_check(&self) -> Result<(), GuardianError> {
-        // Check ML engine health
-        self.inference_engine.health_check().await?;
+        // Check ML engine health; model stages are skipped while ML is disabled
+        if capabilities().is_enabled(Capability::Ml) {
+            self.inference_engine.health_check().await?;
+        }
 
         // Check circuit breaker status
         if self.circuit_breaker.failures.load(Ordering::SeqCst) {
@@ -159,7 +293,7 @@ impl ThreatDetector {
                 source: None,
                 severity: crate::utils::error::ErrorSeverity::High,
                 timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id: uuid::Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: crate::utils::error::ErrorCategory::Security,
                 retry_count: 0,
             });
@@ -172,21 +306,31 @@ impl ThreatDetector {
     #[instrument(skip(self))]
     async fn process_detection_cycle(&self) -> Result<(), GuardianError> {
         let start_time = Instant::now();
+        let latency = pipeline_latency();
+        let _permit = governor().acquire(Subsystem::Detection).await;
 
         // Collect system data for analysis
-        let system_data = self.collect_system_data().await?;
+        let system_data = latency.timed(LatencyStage::Collect, self.collect_system_data()).await?;
+        if system_data.is_empty() {
+            // No collector was due this cycle
+            return Ok(());
+        }
 
-        // Analyze threats with batching
-        let threats = self.analyze_threats(system_data).await?;
+        // Run the detection pipeline, which applies its own filtering
+        let history = self.history_due().then(|| system_data.clone());
+        let threats = self.pipeline.run(system_data).await?;
+        if let Some(samples) = history {
+            self.record_history(samples, &threats).await;
+        }
 
         // Process detected threats
         for threat in threats {
-            if threat.confidence >= self.detection_config.confidence_threshold {
-                self.handle_threat(threat).await?;
-            }
+            self.handle_threat(threat).await?;
         }
 
-        // Record metrics
+        // Record metrics; slow cycles step detection down the degradation ladder
+        latency.record_cycle(start_time.elapsed());
+        degradation().record_cycle(start_time.elapsed());
         self.metrics_collector.record_latency(
             "threat_detection_cycle",
             start_time.elapsed().as_secs_f64(),
@@ -195,31 +339,67 @@ impl ThreatDetector {
         Ok(())
     }
 
-    /// Analyzes potential threats using ML models
-    #[instrument(skip(self, system_data))]
-    async fn analyze_threats(&self, system_data: Vec<SystemData>) -> Result<Vec<Prediction>, GuardianError> {
-        let batch_size = self.calculate_batch_size(system_data.len());
-        let mut predictions = Vec::new();
-
-        for chunk in system_data.chunks(batch_size) {
-            let batch_predictions = self.inference_engine
-                .batch_predict(chunk.to_vec())
-                .await?;
-            predictions.extend(batch_predictions);
+    /// Snapshots the host through the collectors that are due, one sample per collector
+    async fn collect_system_data(&self) -> Result<Vec<SystemData>, GuardianError> {
+        Ok(collectors().collect().await)
+    }
+
+    /// Returns whether this cycle should be recorded as replayable history
+    fn history_due(&self) -> bool {
+        if self.sample_history.is_none() {
+            return false;
+        }
+        let mut last = self.last_sample.lock();
+        if last.map_or(false, |at| at.elapsed() < HISTORY_SAMPLE_INTERVAL) {
+            return false;
+        }
+        *last = Some(Instant::now());
+        true
+    }
+
+    /// Stores a cycle's samples and alerts; failures never interrupt detection
+    async fn record_history(&self, samples: Vec<SystemData>, threats: &[Prediction]) {
+        let Some(event_store) = &self.sample_history else {
+            return;
+        };
+        // Replay history is bulk data, written behind detections and responses
+        let stored = match SampleBatch::new(samples, threats).to_event() {
+            Ok(event) => event_store.store_event_with_priority(event, WritePriority::Batch).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = stored {
+            warn!(error = %e, "Failed to record detection history sample");
         }
+    }
 
-        Ok(predictions)
+    /// Stores a detection for analytics; failures never interrupt detection
+    async fn record_threat(&self, notice: &ThreatNotice) {
+        let Some(event_store) = &self.threat_history else {
+            return;
+        };
+        let stored = match threat_analytics::detection_event(notice) {
+            Ok(event) => event_store.store_event_with_priority(event, notice.level.clone().into()).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = stored {
+            warn!(error = %e, detection_id = %notice.detection_id, "Failed to record detection for analytics");
+        }
     }
 
     /// Handles a detected threat
     #[instrument(skip(self, threat))]
     async fn handle_threat(&self, threat: Prediction) -> Result<(), GuardianError> {
+        let latency = pipeline_latency();
+        let classify_start = Instant::now();
         let threat_level = classify_threat_level(&threat)?;
+        latency.record(LatencyStage::Classify, classify_start.elapsed());
         
         // Create threat event
+        let detection_id = next_id(IdKind::Detection).to_string();
         let event = Event::new(
-            "threat_detected".into(),
+            self.tenant.topic("threat_detected"),
             serde_json::json!({
+                "detection_id": detection_id,
                 "threat_level": threat_level,
                 "confidence": threat.confidence,
                 "details": threat.metadata,
@@ -232,7 +412,18 @@ impl ThreatDetector {
         )?;
 
         // Publish threat event
-        self.event_bus.publish(event).await?;
+        latency.timed(LatencyStage::Enqueue, self.event_bus.publish(event)).await?;
+        let notice = ThreatNotice {
+            detection_id,
+            tenant_id: self.tenant.tenant_id().clone(),
+            level: threat_level,
+            confidence: threat.confidence,
+            prediction_type: threat.prediction_type.clone(),
+            detected_at: SystemTime::now(),
+            details: threat.metadata.clone(),
+        };
+        self.record_threat(&notice).await;
+        self.feed.publish(notice);
 
         // Record metrics
         self.metrics_collector.record_accuracy(
@@ -243,11 +434,6 @@ impl ThreatDetector {
         Ok(())
     }
 
-    /// Calculates optimal batch size based on system load
-    fn calculate_batch_size(&self, data_size: usize) -> usize {
-        data_size.clamp(MIN_BATCH_SIZE, self.detection_config.batch_size)
-    }
-
     /// Handles detection errors with circuit breaker logic
     async fn handle_detection_error(&self, error: GuardianError) {
         error!(?error, "Threat detection error occurred");
@@ -289,6 +475,12 @@ impl Clone for ThreatDetector {
                 failure_count: AtomicBool::new(self.circuit_breaker.failure_count.load(Ordering::SeqCst)),
             },
             feature_cache: LruCache::new(CACHE_SIZE),
+            tenant: self.tenant.clone(),
+            pipeline: Arc::clone(&self.pipeline),
+            sample_history: self.sample_history.clone(),
+            last_sample: Arc::clone(&self.last_sample),
+            feed: Arc::clone(&self.feed),
+            threat_history: self.threat_history.clone(),
         }
     }
 }
@@ -394,4 +586,28 @@ mod tests {
         let level = classify_threat_level(&prediction).unwrap();
         assert_eq!(level, ThreatLevel::Critical);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_threat_feed_backlog_and_live() {
+        let feed = ThreatFeed::new();
+        let notice = |id: &str, detected_at: SystemTime| ThreatNotice {
+            detection_id: id.into(),
+            tenant_id: TenantContext::default().tenant_id().clone(),
+            level: ThreatLevel::High,
+            confidence: 0.9,
+            prediction_type: "anomaly".into(),
+            detected_at,
+            details: HashMap::new(),
+        };
+        let now = SystemTime::now();
+        feed.publish(notice("old", now - Duration::from_secs(3600)));
+        feed.publish(notice("recent", now - Duration::from_secs(60)));
+
+        let (backlog, mut live) = feed.watch(Some(now - Duration::from_secs(600)));
+        assert_eq!(backlog.iter().map(|n| n.detection_id.as_str()).collect::<Vec<_>>(), vec!["recent"]);
+        assert!(feed.watch(None).0.is_empty());
+
+        feed.publish(notice("live", now));
+        assert_eq!(live.try_recv().unwrap().detection_id, "live");
+    }
+}
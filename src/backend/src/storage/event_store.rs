@@ -4,12 +4,17 @@ use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use async_trait::async_trait; // v0.1
 use metrics::{counter, gauge}; // v0.20
+use schemars::JsonSchema; // v0.8
 use serde::{Deserialize, Serialize}; // v1.0
 use tokio::sync::RwLock; // v1.32
 use tracing::{debug, error, info, instrument, warn}; // v0.1
 
 use crate::utils::error::GuardianError;
-use super::zfs_manager::ZFSManager;
+use crate::utils::retry::retry_executor;
+use crate::config::storage_config::{ArchiveTarget, BackgroundJobClass, TierAges, WriteClass};
+use super::retention::{self, ArchiveStore, RetentionReport, RetentionTier, TierPolicy};
+use super::write_coalescer::{WriteCoalescer, WritePriority};
+use super::zfs_manager::{BackupOptions, BackupTarget, ZFSManager, ZfsManager};
 
 // Constants for event storage management
 const EVENT_DATASET_PREFIX: &str = "events";
@@ -17,9 +22,15 @@ const EVENT_RETENTION_DAYS: u64 = 90;
 const MAX_EVENTS_PER_PARTITION: usize = 10000;
 const PARTITION_CLEANUP_INTERVAL: Duration = Duration::from_secs(3600);
 const STORAGE_METRICS_PREFIX: &str = "guardian.storage";
+const SECS_PER_DAY: u64 = 24 * 60 * 60;
+/// Highest zstd level ZFS accepts as a dataset compression
+const ZFS_MAX_ZSTD_LEVEL: i32 = 19;
+
+/// Version of the stored event format, bumped on incompatible changes
+pub const EVENT_SCHEMA_VERSION: u32 = 1;
 
 /// Represents a system event with integrity verification
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Event {
     pub id: String,
     pub timestamp: u64,
@@ -36,6 +47,44 @@ struct PartitionMetadata {
     event_count: usize,
     encryption_key_id: String,
     integrity_hash: String,
+    tier: RetentionTier,
+    location: PartitionLocation,
+}
+
+/// Where a partition's data currently lives
+#[derive(Debug, Clone, PartialEq)]
+enum PartitionLocation {
+    /// Its own dataset on the pool
+    Pool,
+    /// Received into a dataset under the archive dataset, readable in place
+    ArchiveDataset(String),
+    /// Raw send stream and manifest uploaded to the object store under these keys
+    ArchiveObjects(Vec<String>),
+}
+
+/// Where partitions leaving the warm tier go
+#[derive(Debug, Clone)]
+pub enum EventArchive {
+    /// Dataset partitions are received under with a raw `zfs send`, keeping their encryption
+    Dataset(String),
+    /// Object store the raw send stream of each partition is uploaded to
+    Objects(Arc<dyn ArchiveStore>),
+}
+
+impl EventArchive {
+    pub fn from_target(target: &ArchiveTarget, zfs: &ZfsManager) -> Result<Self, GuardianError> {
+        Ok(match target {
+            ArchiveTarget::Dataset { dataset } => EventArchive::Dataset(zfs.dataset_path(dataset)),
+            ArchiveTarget::ObjectStore { .. } => EventArchive::Objects(retention::archive_store(target, zfs)?),
+        })
+    }
+}
+
+/// Retention tiers in effect for event partitions
+#[derive(Debug, Clone)]
+struct EventTiering {
+    policy: TierPolicy,
+    archive: Option<EventArchive>,
 }
 
 /// Query parameters for event retrieval
@@ -47,6 +96,31 @@ pub struct EventQuery {
     pub limit: Option<usize>,
 }
 
+/// Time range covered by one partition
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartitionSpan {
+    pub name: String,
+    pub start: u64,
+    /// Creation time of the next partition; `None` while the partition still takes writes
+    pub end: Option<u64>,
+}
+
+impl PartitionSpan {
+    /// Whether the partition no longer takes writes, so its contents are final
+    pub fn is_sealed(&self) -> bool {
+        self.end.is_some()
+    }
+
+    /// Whether every event in the partition falls within `start..=end`
+    pub fn within(&self, start: Option<u64>, end: Option<u64>) -> bool {
+        start.map_or(true, |s| self.start >= s) && end.map_or(true, |e| self.end.map_or(false, |pe| pe <= e))
+    }
+
+    fn overlaps(&self, start: Option<u64>, end: Option<u64>) -> bool {
+        start.map_or(true, |s| self.end.map_or(true, |pe| pe >= s)) && end.map_or(true, |e| self.start <= e)
+    }
+}
+
 /// Manages secure event storage with encryption and integrity verification
 #[derive(Debug)]
 pub struct EventStore {
@@ -55,6 +129,8 @@ pub struct EventStore {
     event_count: RwLock<usize>,
     partition_metadata: RwLock<HashMap<String, PartitionMetadata>>,
     hsm_context: Arc<hsm_client::HSMClient>,
+    write_coalescer: Arc<WriteCoalescer>,
+    tiering: Arc<RwLock<EventTiering>>,
 }
 
 #[async_trait]
@@ -70,11 +146,28 @@ impl EventStore {
             event_count: RwLock::new(0),
             partition_metadata: RwLock::new(HashMap::new()),
             hsm_context,
+            write_coalescer: Arc::new(WriteCoalescer::new()),
+            // Partitions stay as written until they expire, until tiers are set
+            tiering: Arc::new(RwLock::new(EventTiering {
+                policy: TierPolicy::new(
+                    TierAges {
+                        hot_days: EVENT_RETENTION_DAYS as u32,
+                        warm_days: EVENT_RETENTION_DAYS as u32,
+                        archive_days: 0,
+                    },
+                    ZFS_MAX_ZSTD_LEVEL,
+                    false,
+                ),
+                archive: None,
+            })),
         };
 
         // Initialize first partition
         store.create_new_partition().await?;
 
+        // Start write flush task
+        store.start_flush_task();
+
         // Start cleanup task
         store.start_cleanup_task();
 
@@ -92,6 +185,16 @@ impl EventStore {
     /// Stores a new event with encryption and integrity verification
     #[instrument(skip(self, event))]
     pub async fn store_event(&self, event: Event) -> Result<(), GuardianError> {
+        self.store_event_with_priority(event, WritePriority::Normal).await
+    }
+
+    /// Stores an event ahead of lower priority writes, used for forensic data of detections
+    #[instrument(skip(self, event))]
+    pub async fn store_event_with_priority(
+        &self,
+        event: Event,
+        priority: WritePriority,
+    ) -> Result<(), GuardianError> {
         // Validate event data
         self.validate_event(&event)?;
 
@@ -103,7 +206,7 @@ impl EventStore {
         }
 
         // Get current partition
-        let current_partition = self.current_partition.read().await;
+        let current_partition = self.current_partition.read().await.clone();
         
         // Calculate integrity hash
         let integrity_hash = self.calculate_integrity_hash(&event)?;
@@ -111,11 +214,20 @@ impl EventStore {
         // Encrypt event data
         let encrypted_data = self.encrypt_event_data(&event).await?;
 
-        // Store encrypted event
-        self.write_event_to_partition(&current_partition, &encrypted_data).await?;
+        // Release the partition lock so queued writes are ordered by priority, not arrival
+        drop(event_count);
+
+        // Store encrypted event through the write coalescer
+        self.write_coalescer
+            .submit(&current_partition, encrypted_data, priority)
+            .await?;
+
+        // Count only written events, toward the partition they were written to
+        if *self.current_partition.read().await == current_partition {
+            *self.event_count.write().await += 1;
+        }
 
         // Update metrics
-        *event_count += 1;
         counter!(
             format!("{}.events_stored", STORAGE_METRICS_PREFIX),
             1.0,
@@ -138,15 +250,7 @@ impl EventStore {
         let partitions = self.find_relevant_partitions(&query).await?;
 
         for partition in partitions {
-            // Verify partition integrity
-            self.verify_partition_integrity(&partition).await?;
-
-            // Read and decrypt events
-            let partition_events = self.read_partition_events(&partition).await?;
-
-            // Apply query filters
-            let filtered_events = self.filter_events(partition_events, &query);
-            events.extend(filtered_events);
+            events.extend(self.read_partition(&partition, &query).await?);
         }
 
         // Record metrics
@@ -159,7 +263,129 @@ impl EventStore {
         Ok(events)
     }
 
+    /// Returns the partitions overlapping `start..=end`, oldest first
+    pub async fn partition_spans(&self, start: Option<u64>, end: Option<u64>) -> Vec<PartitionSpan> {
+        let metadata = self.partition_metadata.read().await;
+        let mut created: Vec<(u64, &str)> = metadata.values().map(|m| (m.created_at, m.name.as_str())).collect();
+        created.sort();
+
+        let ends = created.iter().skip(1).map(|(at, _)| Some(*at)).chain(std::iter::once(None));
+        created
+            .iter()
+            .zip(ends)
+            .map(|((at, name), end)| PartitionSpan { name: name.to_string(), start: *at, end })
+            .filter(|span| span.overlaps(start, end))
+            .collect()
+    }
+
+    /// Verifies, decompresses and filters a single partition, so callers can fold over one at a time
+    ///
+    /// Archived partitions are read from the archive dataset in place, or restored from the object
+    /// store into a temporary dataset for the duration of the read.
+    #[instrument(skip(self, query))]
+    pub async fn read_partition(&self, partition: &str, query: &EventQuery) -> Result<Vec<Event>, GuardianError> {
+        let location = self
+            .partition_metadata
+            .read()
+            .await
+            .get(partition)
+            .map_or(PartitionLocation::Pool, |m| m.location.clone());
+
+        let partition_events = match location {
+            PartitionLocation::Pool => {
+                self.verify_partition_integrity(partition).await?;
+                self.read_partition_events(partition).await?
+            }
+            PartitionLocation::ArchiveDataset(dataset) => {
+                counter!(format!("{}.archive_reads", STORAGE_METRICS_PREFIX), 1, "target" => "dataset");
+                self.verify_partition_integrity(&dataset).await?;
+                self.read_partition_events(&dataset).await?
+            }
+            PartitionLocation::ArchiveObjects(keys) => {
+                counter!(format!("{}.archive_reads", STORAGE_METRICS_PREFIX), 1, "target" => "object_store");
+                self.read_recalled_partition(partition, &keys).await?
+            }
+        };
+
+        // Apply query filters
+        Ok(self.filter_events(partition_events, query))
+    }
+
+    /// Moves sealed partitions through hot, warm and archive tiers; without an archive,
+    /// partitions leaving the warm tier are deleted
+    pub async fn set_tiers(&self, policy: TierPolicy, archive: Option<EventArchive>) {
+        info!(ages = ?policy.ages, archived = archive.is_some(), "Event retention tiers set");
+        *self.tiering.write().await = EventTiering { policy, archive };
+    }
+
+    /// Moves every sealed partition to the tier its age calls for
+    ///
+    /// A partition ages from its newest event, the creation time of the next partition, so no
+    /// event is moved before it is old enough. Warm partitions are recompressed on the pool,
+    /// archived ones moved off it, and expired ones deleted from wherever they are.
+    #[instrument(skip(self))]
+    pub async fn apply_retention(&self, now: u64) -> Result<RetentionReport, GuardianError> {
+        let EventTiering { policy, archive } = self.tiering.read().await.clone();
+        let io_throttler = self.zfs_manager.io_throttler();
+        let mut report = RetentionReport::default();
+
+        for span in self.partition_spans(None, None).await {
+            let Some(sealed_at) = span.end else {
+                continue;
+            };
+            let Some(metadata) = self.partition_metadata.read().await.get(&span.name).cloned() else {
+                continue;
+            };
+            let age_days = (now.saturating_sub(sealed_at) / SECS_PER_DAY) as i64;
+
+            match (policy.tier_for(age_days), &archive) {
+                // Recompression and archiving stream the partition through zfs send, which
+                // charges every chunk it copies, so only the job itself is charged here
+                (Some(RetentionTier::Warm), _) if metadata.tier == RetentionTier::Hot => {
+                    io_throttler.acquire(BackgroundJobClass::Retention, 0).await;
+                    let level = policy.warm_compression_level.min(ZFS_MAX_ZSTD_LEVEL);
+                    self.zfs_manager.recompress_dataset(&span.name, &format!("zstd-{}", level)).await?;
+                    self.set_partition_tier(&span.name, RetentionTier::Warm, PartitionLocation::Pool).await;
+                    report.compressed += 1;
+                }
+                (Some(RetentionTier::Archive), Some(archive)) if metadata.tier != RetentionTier::Archive => {
+                    io_throttler.acquire(BackgroundJobClass::Retention, 0).await;
+                    let location = self.archive_partition(&span.name, archive).await?;
+                    self.zfs_manager.delete_dataset(span.name.clone()).await?;
+                    self.set_partition_tier(&span.name, RetentionTier::Archive, location).await;
+                    report.archived += 1;
+                }
+                (None, _) | (Some(RetentionTier::Archive), None) => {
+                    info!(partition = %span.name, "Removing expired partition");
+                    let freed = match &metadata.location {
+                        PartitionLocation::Pool => self.zfs_manager.dataset_usage(&span.name).await.map_or(0, |usage| usage.used),
+                        _ => 0,
+                    };
+                    io_throttler.acquire(BackgroundJobClass::Retention, freed).await;
+                    self.delete_partition(&span.name, &metadata.location, archive.as_ref()).await?;
+                    report.deleted += 1;
+                }
+                _ => {}
+            }
+        }
+
+        counter!(format!("{}.retention.moved", STORAGE_METRICS_PREFIX), report.compressed as u64, "store" => "events", "tier" => "warm");
+        counter!(format!("{}.retention.moved", STORAGE_METRICS_PREFIX), report.archived as u64, "store" => "events", "tier" => "archive");
+        counter!(format!("{}.retention.deleted", STORAGE_METRICS_PREFIX), report.deleted as u64, "store" => "events");
+        info!(?report, "Event retention applied");
+        Ok(report)
+    }
+
     // Private helper methods
+    async fn find_relevant_partitions(&self, query: &EventQuery) -> Result<Vec<String>, GuardianError> {
+        Ok(self
+            .partition_spans(query.start_time, query.end_time)
+            .await
+            .into_iter()
+            .map(|span| span.name)
+            .collect())
+    }
+
     async fn create_new_partition(&self) -> Result<(), GuardianError> {
         let timestamp = SystemTime::now()
             .duration_since(UNIX_EPOCH)
@@ -187,6 +413,8 @@ impl EventStore {
             event_count: 0,
             encryption_key_id: self.generate_encryption_key().await?,
             integrity_hash: String::new(),
+            tier: RetentionTier::Hot,
+            location: PartitionLocation::Pool,
         };
 
         // Update store state
@@ -213,52 +441,155 @@ impl EventStore {
             .map_err(|e| GuardianError::StorageError(format!("Failed to generate encryption key: {}", e)))
     }
 
+    fn start_flush_task(&self) {
+        let store = Arc::new(self.clone());
+        tokio::spawn(async move {
+            loop {
+                let batch = store.write_coalescer.next_batch().await;
+                let bytes: usize = batch.records.iter().map(Vec::len).sum();
+                store.zfs_manager.io_throttler().acquire_write(WriteClass::Events, bytes as u64).await;
+                let result = retry_executor()
+                    .run("flush_coalesced_writes", || store.write_batch_to_partition(&batch.partition, &batch.records))
+                    .await;
+                if let Err(e) = &result {
+                    error!(error = %e, partition = %batch.partition, "Failed to flush coalesced writes");
+                }
+                batch.complete(&result);
+            }
+        });
+    }
+
+    async fn write_batch_to_partition(
+        &self,
+        partition: &str,
+        records: &[Vec<u8>],
+    ) -> Result<(), GuardianError> {
+        for data in records {
+            self.write_event_to_partition(partition, data).await?;
+        }
+        Ok(())
+    }
+
     fn start_cleanup_task(&self) {
         let store = Arc::new(self.clone());
         tokio::spawn(async move {
             loop {
                 tokio::time::sleep(PARTITION_CLEANUP_INTERVAL).await;
-                if let Err(e) = store.cleanup_expired_partitions().await {
-                    error!(error = %e, "Failed to cleanup expired partitions");
+                let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+                if let Err(e) = store.apply_retention(now).await {
+                    error!(error = %e, "Failed to apply event retention");
                 }
             }
         });
     }
 
-    #[instrument(skip(self))]
-    async fn cleanup_expired_partitions(&self) -> Result<(), GuardianError> {
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
-        
-        let retention_secs = EVENT_RETENTION_DAYS * 24 * 60 * 60;
-        let mut expired = Vec::new();
+    async fn set_partition_tier(&self, partition: &str, tier: RetentionTier, location: PartitionLocation) {
+        if let Some(metadata) = self.partition_metadata.write().await.get_mut(partition) {
+            metadata.tier = tier;
+            metadata.location = location;
+        }
+    }
 
-        // Find expired partitions
-        {
-            let metadata_map = self.partition_metadata.read().await;
-            for (name, metadata) in metadata_map.iter() {
-                if now - metadata.created_at > retention_secs {
-                    expired.push(name.clone());
+    /// Copies a partition to the archive with a raw send, so it stays encrypted at rest
+    async fn archive_partition(&self, partition: &str, archive: &EventArchive) -> Result<PartitionLocation, GuardianError> {
+        let options = BackupOptions { incremental: false, ..Default::default() };
+        match archive {
+            EventArchive::Dataset(root) => {
+                let target = format!("{}/{}", root, partition);
+                self.zfs_manager
+                    .backup_dataset(partition, &BackupTarget::Dataset(target.clone()), &options)
+                    .await?;
+                Ok(PartitionLocation::ArchiveDataset(target))
+            }
+            EventArchive::Objects(store) => {
+                let staging = tempfile::tempdir()
+                    .map_err(|e| GuardianError::StorageError(format!("Failed to stage archive of {}: {}", partition, e)))?;
+                self.zfs_manager
+                    .backup_dataset(partition, &BackupTarget::Directory(staging.path().to_path_buf()), &options)
+                    .await?;
+
+                let mut keys = Vec::new();
+                let mut entries = tokio::fs::read_dir(staging.path())
+                    .await
+                    .map_err(|e| GuardianError::StorageError(format!("Failed to read archive of {}: {}", partition, e)))?;
+                while let Some(entry) = entries
+                    .next_entry()
+                    .await
+                    .map_err(|e| GuardianError::StorageError(format!("Failed to read archive of {}: {}", partition, e)))?
+                {
+                    let key = format!("{}/{}/{}", EVENT_DATASET_PREFIX, partition, entry.file_name().to_string_lossy());
+                    let data = tokio::fs::read(entry.path())
+                        .await
+                        .map_err(|e| GuardianError::StorageError(format!("Failed to read archive of {}: {}", partition, e)))?;
+                    store.put(&key, data).await?;
+                    keys.push(key);
                 }
+                Ok(PartitionLocation::ArchiveObjects(keys))
             }
         }
+    }
 
-        // Remove expired partitions
-        for partition in expired {
-            info!(partition = %partition, "Removing expired partition");
-            
-            // Secure deletion of partition data
-            self.zfs_manager
-                .delete_dataset(partition.clone())
-                .await?;
+    /// Restores a partition archived to the object store into a temporary dataset and reads it
+    async fn read_recalled_partition(&self, partition: &str, keys: &[String]) -> Result<Vec<Event>, GuardianError> {
+        let EventArchive::Objects(store) = self
+            .tiering
+            .read()
+            .await
+            .archive
+            .clone()
+            .ok_or_else(|| GuardianError::StorageError(format!("Partition {} is archived but no archive is set", partition)))?
+        else {
+            return Err(GuardianError::StorageError(format!("Partition {} is archived to another target", partition)));
+        };
 
-            // Update metadata
-            let mut metadata_map = self.partition_metadata.write().await;
-            metadata_map.remove(&partition);
+        let staging = tempfile::tempdir()
+            .map_err(|e| GuardianError::StorageError(format!("Failed to stage recall of {}: {}", partition, e)))?;
+        for key in keys {
+            let data = store
+                .get(key)
+                .await?
+                .ok_or_else(|| GuardianError::StorageError(format!("Archived object {} is missing", key)))?;
+            let name = key.rsplit('/').next().unwrap_or(key.as_str());
+            tokio::fs::write(staging.path().join(name), data)
+                .await
+                .map_err(|e| GuardianError::StorageError(format!("Failed to stage recall of {}: {}", partition, e)))?;
         }
 
+        let recalled = format!("{}-recall-{}", partition, fastrand::u64(..));
+        self.zfs_manager
+            .restore_dataset(staging.path(), partition, None, &recalled, &BackupOptions::default())
+            .await?;
+        let events = match self.verify_partition_integrity(&recalled).await {
+            Ok(()) => self.read_partition_events(&recalled).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = self.zfs_manager.delete_dataset(recalled.clone()).await {
+            warn!(dataset = %recalled, error = %e, "Failed to remove recalled partition");
+        }
+        events
+    }
+
+    /// Deletes an expired partition from wherever it lives
+    async fn delete_partition(
+        &self,
+        partition: &str,
+        location: &PartitionLocation,
+        archive: Option<&EventArchive>,
+    ) -> Result<(), GuardianError> {
+        match location {
+            PartitionLocation::Pool => self.zfs_manager.delete_dataset(partition.to_string()).await?,
+            PartitionLocation::ArchiveDataset(dataset) => self.zfs_manager.delete_dataset(dataset.clone()).await?,
+            PartitionLocation::ArchiveObjects(keys) => {
+                if let Some(EventArchive::Objects(store)) = archive {
+                    for key in keys {
+                        store.delete(key).await?;
+                    }
+                } else {
+                    warn!(partition, "Archived partition expired without an object store to delete it from");
+                }
+            }
+        }
+        self.partition_metadata.write().await.remove(partition);
         Ok(())
     }
 
@@ -0,0 +1,316 @@
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use metrics::{counter, histogram};
+use ring::digest;
+use serde::Serialize;
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+use tracing::{info, warn};
+
+use crate::storage::zfs_manager::to_hex;
+use crate::utils::error::{ErrorCategory, GuardianError};
+
+// Constants for forensic bundles
+/// Raw bytes per bundle chunk, which bounds capture memory to about twice this
+pub const BUNDLE_CHUNK_SIZE: usize = 8 * 1024 * 1024;
+const COMPRESSION_LEVEL: i32 = 3;
+const TAR_BLOCK: usize = 512;
+const PARTIAL_SUFFIX: &str = "partial";
+const JOURNAL_SUFFIX: &str = "chunks";
+const BUNDLE_ROOT: &str = "forensic-bundle";
+
+/// A chunk durably written to a bundle
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BundleChunk {
+    pub index: u64,
+    /// Offset of the chunk's zstd frame in the bundle file
+    #[serde(skip)]
+    pub frame_offset: u64,
+    #[serde(skip)]
+    pub frame_len: u64,
+    pub raw_len: u64,
+    /// SHA-256 of the uncompressed chunk
+    pub raw_sha256: String,
+    #[serde(skip)]
+    pub frame_sha256: String,
+}
+
+/// A completed bundle
+#[derive(Debug, Clone, Serialize)]
+pub struct BundleSummary {
+    pub path: PathBuf,
+    pub chunks: u64,
+    pub raw_bytes: u64,
+    pub compressed_bytes: u64,
+}
+
+/// Writes a forensic capture as a tar archive compressed into independent zstd frames
+///
+/// Every chunk becomes its own tar entry, `forensic-bundle/image/part-NNNNNN`, packed into one
+/// zstd frame. Frames are appended to `<path>.partial` and recorded with their checksums in the
+/// `<path>.chunks` journal only once synced, so after a crash the bundle reopens at the last
+/// verified chunk instead of being corrupt. Concatenated frames decode as a single stream, so the
+/// finished file unpacks with `zstd -d | tar x`.
+#[derive(Debug)]
+pub struct BundleWriter {
+    path: PathBuf,
+    partial: PathBuf,
+    journal_path: PathBuf,
+    file: tokio::fs::File,
+    journal: tokio::fs::File,
+    chunks: Vec<BundleChunk>,
+    frame_offset: u64,
+}
+
+impl BundleWriter {
+    /// Starts a bundle at `path`, or resumes the partial bundle an interrupted capture left there
+    pub async fn open(path: &Path) -> Result<Self, GuardianError> {
+        if tokio::fs::try_exists(path).await.unwrap_or(false) {
+            return Err(bundle_error(format!("Forensic bundle {} already exists", path.display()), None));
+        }
+        let partial = sibling(path, PARTIAL_SUFFIX);
+        let journal_path = sibling(path, JOURNAL_SUFFIX);
+
+        let mut file = open_owner_only(&partial).await?;
+        let journaled = match tokio::fs::read_to_string(&journal_path).await {
+            Ok(raw) => parse_journal(&raw),
+            Err(_) => Vec::new(),
+        };
+        let chunks = verified_prefix(&mut file, journaled).await;
+
+        // Drop frames written after the last journaled chunk and any chunks that failed verification
+        let frame_offset = chunks.last().map_or(0, |c| c.frame_offset + c.frame_len);
+        file.set_len(frame_offset).await.map_err(|e| io_error(&partial, e))?;
+        file.seek(std::io::SeekFrom::Start(frame_offset)).await.map_err(|e| io_error(&partial, e))?;
+
+        let journal_lines: String = chunks.iter().map(journal_line).collect();
+        tokio::fs::write(&journal_path, journal_lines).await.map_err(|e| io_error(&journal_path, e))?;
+        let journal = tokio::fs::OpenOptions::new()
+            .append(true)
+            .open(&journal_path)
+            .await
+            .map_err(|e| io_error(&journal_path, e))?;
+
+        if !chunks.is_empty() {
+            info!(path = %path.display(), chunks = chunks.len(), "Resuming partial forensic bundle");
+            counter!("guardian.storage.forensics.bundle_resumed", 1);
+        }
+        Ok(Self { path: path.to_path_buf(), partial, journal_path, file, journal, chunks, frame_offset })
+    }
+
+    /// Chunks already in the bundle; a resumed capture skips this much of its input
+    pub fn chunks(&self) -> &[BundleChunk] {
+        &self.chunks
+    }
+
+    /// Compresses a chunk into the bundle and journals it once it is on disk
+    pub async fn append_chunk(&mut self, data: &[u8]) -> Result<(), GuardianError> {
+        let index = self.chunks.len() as u64;
+        let frame = compress_frame(&tar_entry(&format!("image/part-{:06}", index), data))?;
+
+        self.write_frame(&frame).await?;
+        let chunk = BundleChunk {
+            index,
+            frame_offset: self.frame_offset,
+            frame_len: frame.len() as u64,
+            raw_len: data.len() as u64,
+            raw_sha256: sha256(data),
+            frame_sha256: sha256(&frame),
+        };
+        self.journal
+            .write_all(journal_line(&chunk).as_bytes())
+            .await
+            .and(self.journal.sync_data().await)
+            .map_err(|e| io_error(&self.journal_path, e))?;
+
+        self.frame_offset += chunk.frame_len;
+        histogram!("guardian.storage.forensics.bundle_chunk_ratio", chunk.frame_len as f64 / data.len().max(1) as f64);
+        self.chunks.push(chunk);
+        Ok(())
+    }
+
+    /// Appends the manifest and end-of-archive marker and moves the bundle into place
+    pub async fn finish(mut self, manifest: serde_json::Value) -> Result<BundleSummary, GuardianError> {
+        let manifest = serde_json::json!({ "chunks": self.chunks, "capture": manifest });
+        let manifest = serde_json::to_vec_pretty(&manifest)
+            .map_err(|e| bundle_error("Failed to serialize bundle manifest".into(), Some(Box::new(e))))?;
+        let mut tail = tar_entry("manifest.json", &manifest);
+        tail.extend_from_slice(&[0u8; 2 * TAR_BLOCK]);
+        let frame = compress_frame(&tail)?;
+        self.write_frame(&frame).await?;
+
+        tokio::fs::rename(&self.partial, &self.path).await.map_err(|e| io_error(&self.path, e))?;
+        if let Err(e) = tokio::fs::remove_file(&self.journal_path).await {
+            warn!(error = %e, path = %self.journal_path.display(), "Forensic bundle journal not removed");
+        }
+
+        let summary = BundleSummary {
+            path: self.path.clone(),
+            chunks: self.chunks.len() as u64,
+            raw_bytes: self.chunks.iter().map(|c| c.raw_len).sum(),
+            compressed_bytes: self.frame_offset + frame.len() as u64,
+        };
+        histogram!("guardian.storage.forensics.bundle_bytes", summary.compressed_bytes as f64);
+        Ok(summary)
+    }
+
+    async fn write_frame(&mut self, frame: &[u8]) -> Result<(), GuardianError> {
+        self.file
+            .write_all(frame)
+            .await
+            .and(self.file.sync_data().await)
+            .map_err(|e| io_error(&self.partial, e))
+    }
+}
+
+/// Keeps journaled chunks while their frames are intact on disk, stopping at the first that is not
+async fn verified_prefix(file: &mut tokio::fs::File, journaled: Vec<BundleChunk>) -> Vec<BundleChunk> {
+    let mut verified = Vec::with_capacity(journaled.len());
+    for chunk in journaled {
+        let expected_offset = verified.last().map_or(0, |c: &BundleChunk| c.frame_offset + c.frame_len);
+        if chunk.index != verified.len() as u64 || chunk.frame_offset != expected_offset {
+            break;
+        }
+        let mut frame = vec![0u8; chunk.frame_len as usize];
+        let read = file.seek(std::io::SeekFrom::Start(chunk.frame_offset)).await.is_ok()
+            && file.read_exact(&mut frame).await.is_ok();
+        if !read || sha256(&frame) != chunk.frame_sha256 {
+            warn!(index = chunk.index, "Forensic bundle chunk failed verification, capture resumes before it");
+            break;
+        }
+        verified.push(chunk);
+    }
+    verified
+}
+
+/// Parses journal lines, ignoring everything from the first torn or malformed line
+fn parse_journal(raw: &str) -> Vec<BundleChunk> {
+    raw.lines()
+        .map_while(|line| {
+            let mut fields = line.split('\t');
+            let chunk = BundleChunk {
+                index: fields.next()?.parse().ok()?,
+                frame_offset: fields.next()?.parse().ok()?,
+                frame_len: fields.next()?.parse().ok()?,
+                raw_len: fields.next()?.parse().ok()?,
+                raw_sha256: fields.next()?.to_string(),
+                frame_sha256: fields.next()?.to_string(),
+            };
+            (fields.next().is_none() && chunk.frame_sha256.len() == 64).then_some(chunk)
+        })
+        .collect()
+}
+
+fn journal_line(chunk: &BundleChunk) -> String {
+    format!(
+        "{}\t{}\t{}\t{}\t{}\t{}\n",
+        chunk.index, chunk.frame_offset, chunk.frame_len, chunk.raw_len, chunk.raw_sha256, chunk.frame_sha256
+    )
+}
+
+/// A tar header and its padded data
+fn tar_entry(name: &str, data: &[u8]) -> Vec<u8> {
+    let mut header = tar::Header::new_gnu();
+    // Names are short and ASCII, so setting the path cannot fail
+    let _ = header.set_path(format!("{}/{}", BUNDLE_ROOT, name));
+    header.set_size(data.len() as u64);
+    header.set_mode(0o600);
+    header.set_mtime(time::OffsetDateTime::now_utc().unix_timestamp().max(0) as u64);
+    header.set_cksum();
+
+    let padding = (TAR_BLOCK - data.len() % TAR_BLOCK) % TAR_BLOCK;
+    let mut entry = Vec::with_capacity(TAR_BLOCK + data.len() + padding);
+    entry.extend_from_slice(header.as_bytes());
+    entry.extend_from_slice(data);
+    entry.resize(entry.len() + padding, 0);
+    entry
+}
+
+fn compress_frame(data: &[u8]) -> Result<Vec<u8>, GuardianError> {
+    let compress = || {
+        let mut encoder = zstd::Encoder::new(Vec::with_capacity(data.len() / 2), COMPRESSION_LEVEL)?;
+        encoder.include_checksum(true)?;
+        encoder.write_all(data)?;
+        encoder.finish()
+    };
+    compress().map_err(|e| bundle_error("Failed to compress forensic bundle chunk".into(), Some(Box::new(e))))
+}
+
+async fn open_owner_only(path: &Path) -> Result<tokio::fs::File, GuardianError> {
+    tokio::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .mode(0o600)
+        .open(path)
+        .await
+        .map_err(|e| io_error(path, e))
+}
+
+fn sibling(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{}", suffix));
+    PathBuf::from(name)
+}
+
+fn sha256(data: &[u8]) -> String {
+    to_hex(digest::digest(&digest::SHA256, data).as_ref())
+}
+
+fn io_error(path: &Path, e: std::io::Error) -> GuardianError {
+    bundle_error(format!("Forensic bundle I/O failed on {}", path.display()), Some(Box::new(e)))
+}
+
+fn bundle_error(context: String, source: Option<Box<dyn std::error::Error + Send + Sync>>) -> GuardianError {
+    GuardianError::StorageError {
+        context,
+        source,
+        severity: crate::utils::error::ErrorSeverity::High,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: ErrorCategory::Storage,
+        retry_count: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_interrupted_bundle_resumes_and_unpacks() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("case-17.tar.zst");
+
+        let mut writer = BundleWriter::open(&path).await.unwrap();
+        writer.append_chunk(&[1u8; 1000]).await.unwrap();
+        writer.append_chunk(&[2u8; 1000]).await.unwrap();
+        drop(writer);
+
+        // A crash mid-frame leaves trailing bytes that were never journaled
+        let mut partial = std::fs::OpenOptions::new().append(true).open(sibling(&path, PARTIAL_SUFFIX)).unwrap();
+        partial.write_all(b"torn frame").unwrap();
+
+        let mut writer = BundleWriter::open(&path).await.unwrap();
+        assert_eq!(writer.chunks().len(), 2);
+        writer.append_chunk(&[3u8; 10]).await.unwrap();
+        let summary = writer.finish(serde_json::json!({ "snapshot": "tank@s" })).await.unwrap();
+        assert_eq!(summary.raw_bytes, 2010);
+
+        let archive = zstd::decode_all(std::fs::File::open(&path).unwrap()).unwrap();
+        let mut names: Vec<String> = tar::Archive::new(archive.as_slice())
+            .entries()
+            .unwrap()
+            .map(|e| e.unwrap().path().unwrap().display().to_string())
+            .collect();
+        names.sort();
+        assert_eq!(names, [
+            "forensic-bundle/image/part-000000",
+            "forensic-bundle/image/part-000001",
+            "forensic-bundle/image/part-000002",
+            "forensic-bundle/manifest.json",
+        ]);
+    }
+}
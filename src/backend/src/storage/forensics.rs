@@ -0,0 +1,481 @@
+use std::{
+    path::{Path, PathBuf},
+    process::Stdio,
+    sync::Arc,
+    time::Duration,
+};
+
+use metrics::{counter, gauge};
+use ring::digest;
+use serde::Serialize;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    process::Command,
+};
+use tracing::{debug, error, info, instrument, warn};
+
+use crate::config::storage_config::BackgroundJobClass;
+use crate::storage::forensic_bundle::{BundleWriter, BUNDLE_CHUNK_SIZE};
+use crate::storage::zfs_manager::{spawn_zfs, to_hex, wait_zfs, ZfsManager};
+use crate::utils::error::{ErrorCategory, GuardianError};
+use crate::utils::ids::{next_id, IdKind};
+
+// Constants for forensic clones
+const FORENSICS_DATASET: &str = "forensics";
+const DEFAULT_MOUNT_ROOT: &str = "/var/lib/guardian/forensics";
+const EXPIRES_PROPERTY: &str = "guardian:forensic_expires";
+const SOURCE_PROPERTY: &str = "guardian:forensic_source";
+const REQUESTED_BY_PROPERTY: &str = "guardian:forensic_requested_by";
+const IMAGE_CHUNK_SIZE: usize = 1024 * 1024;
+
+/// Clone lifetime when none is requested
+pub const DEFAULT_FORENSIC_TTL: Duration = Duration::from_secs(4 * 3600);
+
+/// Longest a clone may pin its snapshot
+pub const MAX_FORENSIC_TTL: Duration = Duration::from_secs(7 * 24 * 3600);
+
+/// How often the daemon sweeps expired clones
+pub const FORENSIC_REAP_INTERVAL: Duration = Duration::from_secs(300);
+
+/// How a forensic clone is handed to offline analysis tooling
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ForensicExport {
+    /// Mount the read-only clone under the forensic mount root
+    Mount,
+    /// Write a raw `zfs send` image, which stays encrypted with the dataset key
+    Image(PathBuf),
+    /// Write the raw send stream as a resumable zstd-compressed tar bundle
+    Bundle(PathBuf),
+}
+
+/// Request for a read-only clone of a snapshot
+#[derive(Debug, Clone)]
+pub struct ForensicRequest {
+    /// Full snapshot name, `<dataset>@<snapshot>`
+    pub snapshot: String,
+    pub export: ForensicExport,
+    pub ttl: Duration,
+    pub requested_by: String,
+    pub reason: String,
+}
+
+/// Read-only clone kept for analysis until it expires
+#[derive(Debug, Clone, Serialize)]
+pub struct ForensicClone {
+    pub id: String,
+    pub snapshot: String,
+    pub dataset: String,
+    pub mountpoint: Option<PathBuf>,
+    pub image: Option<PathBuf>,
+    pub image_sha256: Option<String>,
+    pub requested_by: String,
+    pub expires_at: i64,
+}
+
+/// Creates read-only snapshot clones for offline forensics and destroys them after their TTL
+///
+/// Clones live under `<root>/forensics` and record their expiry, source and requester as
+/// ZFS user properties, so expiry survives restarts. A clone also keeps snapshot
+/// retention from destroying its origin while analysis is ongoing.
+#[derive(Debug)]
+pub struct ForensicManager {
+    zfs: Arc<ZfsManager>,
+    mount_root: PathBuf,
+}
+
+impl ForensicManager {
+    /// Creates a manager cloning snapshots of the given ZFS manager's datasets
+    pub fn new(zfs: Arc<ZfsManager>) -> Self {
+        Self {
+            zfs,
+            mount_root: PathBuf::from(DEFAULT_MOUNT_ROOT),
+        }
+    }
+
+    /// Mounts clones under a different directory
+    pub fn with_mount_root(mut self, mount_root: impl Into<PathBuf>) -> Self {
+        self.mount_root = mount_root.into();
+        self
+    }
+
+    /// Clones a snapshot read-only and mounts or exports it
+    #[instrument(skip(self, request), fields(snapshot = %request.snapshot))]
+    pub async fn create(&self, request: ForensicRequest) -> Result<ForensicClone, GuardianError> {
+        self.validate(&request)?;
+
+        let id = next_id(IdKind::Operation).to_string();
+        let parent = self.forensics_dataset();
+        let dataset = format!("{}/{}", parent, id);
+        let expires_at = time::OffsetDateTime::now_utc().unix_timestamp() + request.ttl.as_secs() as i64;
+        let mountpoint = (request.export == ForensicExport::Mount).then(|| self.mount_root.join(&id));
+
+        let mut cmd = Command::new("zfs");
+        cmd.args(["create", "-p", "-o", "canmount=off", &parent]);
+        wait_zfs(spawn_zfs(cmd, Stdio::null(), Stdio::null())?, "create").await?;
+
+        let mountpoint_option = match &mountpoint {
+            Some(path) => format!("mountpoint={}", path.display()),
+            None => "mountpoint=none".to_string(),
+        };
+        let mut cmd = Command::new("zfs");
+        cmd.arg("clone");
+        for option in [
+            "readonly=on".to_string(),
+            "canmount=noauto".to_string(),
+            "exec=off".to_string(),
+            "setuid=off".to_string(),
+            "devices=off".to_string(),
+            mountpoint_option,
+            format!("{}={}", EXPIRES_PROPERTY, expires_at),
+            format!("{}={}", SOURCE_PROPERTY, request.snapshot),
+            format!("{}={}", REQUESTED_BY_PROPERTY, request.requested_by),
+        ] {
+            cmd.args(["-o", &option]);
+        }
+        cmd.args([&request.snapshot, &dataset]);
+        wait_zfs(spawn_zfs(cmd, Stdio::null(), Stdio::null())?, "clone").await?;
+
+        let mut clone = ForensicClone {
+            id,
+            snapshot: request.snapshot.clone(),
+            dataset,
+            mountpoint,
+            image: None,
+            image_sha256: None,
+            requested_by: request.requested_by.clone(),
+            expires_at,
+        };
+
+        // Never leave a half-prepared clone behind
+        if let Err(e) = self.export(&mut clone, &request.export).await {
+            if let Err(cleanup) = self.destroy_clone(&clone).await {
+                error!(error = %cleanup, dataset = %clone.dataset, "Failed to remove forensic clone after export failure");
+            }
+            return Err(e);
+        }
+
+        info!(
+            target: "SECURITY-AUDIT",
+            clone_id = %clone.id,
+            snapshot = %clone.snapshot,
+            mountpoint = ?clone.mountpoint,
+            image = ?clone.image,
+            image_sha256 = ?clone.image_sha256,
+            requested_by = %request.requested_by,
+            reason = %request.reason,
+            expires_at,
+            "Forensic clone created"
+        );
+        counter!("guardian.storage.forensics.created", 1);
+        Ok(clone)
+    }
+
+    /// Lists the forensic clones currently held
+    pub async fn list(&self) -> Result<Vec<ForensicClone>, GuardianError> {
+        let properties = format!("name,mountpoint,{},{},{}", SOURCE_PROPERTY, REQUESTED_BY_PROPERTY, EXPIRES_PROPERTY);
+        let output = std::process::Command::new("zfs")
+            .args(["list", "-H", "-p", "-t", "filesystem", "-d", "1", "-o", &properties, &self.forensics_dataset()])
+            .output()
+            .map_err(|e| forensic_error("Failed to list forensic clones".into(), Some(Box::new(e))))?;
+
+        // A missing parent dataset just means no clone was ever made
+        if !output.status.success() {
+            return Ok(Vec::new());
+        }
+
+        let clones: Vec<ForensicClone> = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(parse_clone)
+            .collect();
+        gauge!("guardian.storage.forensics.active", clones.len() as f64);
+        Ok(clones)
+    }
+
+    /// Destroys a clone before its expiry
+    #[instrument(skip(self))]
+    pub async fn destroy(&self, id: &str, destroyed_by: &str) -> Result<(), GuardianError> {
+        let clone = self
+            .list()
+            .await?
+            .into_iter()
+            .find(|c| c.id == id)
+            .ok_or_else(|| forensic_error(format!("Unknown forensic clone: {}", id), None))?;
+
+        self.destroy_clone(&clone).await?;
+        info!(target: "SECURITY-AUDIT", clone_id = %clone.id, snapshot = %clone.snapshot, destroyed_by, "Forensic clone destroyed");
+        counter!("guardian.storage.forensics.destroyed", 1, "reason" => "manual");
+        Ok(())
+    }
+
+    /// Destroys every clone past its expiry, returning their IDs
+    pub async fn reap_expired(&self) -> Result<Vec<String>, GuardianError> {
+        let now = time::OffsetDateTime::now_utc().unix_timestamp();
+        let mut reaped = Vec::new();
+
+        for clone in self.list().await?.into_iter().filter(|c| c.expires_at <= now) {
+            match self.destroy_clone(&clone).await {
+                Ok(()) => {
+                    info!(target: "SECURITY-AUDIT", clone_id = %clone.id, snapshot = %clone.snapshot, "Forensic clone expired and destroyed");
+                    counter!("guardian.storage.forensics.destroyed", 1, "reason" => "expired");
+                    reaped.push(clone.id);
+                }
+                Err(e) => warn!(error = %e, clone_id = %clone.id, "Failed to destroy expired forensic clone"),
+            }
+        }
+        Ok(reaped)
+    }
+
+    /// Spawns a task destroying expired clones on the given interval
+    pub fn spawn_reaper(self: Arc<Self>, interval: Duration) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = self.reap_expired().await {
+                    warn!(error = %e, "Forensic clone expiry sweep failed");
+                }
+            }
+        })
+    }
+
+    fn forensics_dataset(&self) -> String {
+        format!("{}/{}", self.zfs.root_dataset(), FORENSICS_DATASET)
+    }
+
+    fn validate(&self, request: &ForensicRequest) -> Result<(), GuardianError> {
+        let Some((dataset, name)) = request.snapshot.split_once('@') else {
+            return Err(forensic_error(format!("{} is not a snapshot name", request.snapshot), None));
+        };
+        let root = self.zfs.root_dataset();
+        let in_root = dataset == root || dataset.starts_with(&format!("{}/", root));
+        if !in_root || name.is_empty() || dataset.starts_with(&format!("{}/", self.forensics_dataset())) {
+            return Err(forensic_error(
+                format!("Snapshot {} is not a Guardian dataset snapshot", request.snapshot),
+                None,
+            ));
+        }
+        if request.ttl.is_zero() || request.ttl > MAX_FORENSIC_TTL {
+            return Err(forensic_error(
+                format!("Forensic clone TTL must be between 1s and {}s", MAX_FORENSIC_TTL.as_secs()),
+                None,
+            ));
+        }
+        if request.reason.trim().is_empty() {
+            return Err(forensic_error("A reason is required for the audit trail".into(), None));
+        }
+        Ok(())
+    }
+
+    async fn export(&self, clone: &mut ForensicClone, export: &ForensicExport) -> Result<(), GuardianError> {
+        match export {
+            ForensicExport::Mount => {
+                let mut cmd = Command::new("zfs");
+                cmd.args(["mount", &clone.dataset]);
+                wait_zfs(spawn_zfs(cmd, Stdio::null(), Stdio::null())?, "mount").await
+            }
+            ForensicExport::Image(path) => {
+                let sha256 = self.write_image(&clone.snapshot, path).await?;
+                clone.image = Some(path.clone());
+                clone.image_sha256 = Some(sha256);
+                Ok(())
+            }
+            ForensicExport::Bundle(path) => {
+                let sha256 = self.write_bundle(&clone.snapshot, path).await?;
+                clone.image = Some(path.clone());
+                clone.image_sha256 = Some(sha256);
+                Ok(())
+            }
+        }
+    }
+
+    /// Streams a raw send of the snapshot into a chunked bundle, resuming a partial one at `path`
+    ///
+    /// A resumed capture re-reads the send stream and checks the chunks already bundled against
+    /// their journaled checksums instead of writing them again. Returns the SHA-256 of the raw stream.
+    async fn write_bundle(&self, snapshot: &str, path: &Path) -> Result<String, GuardianError> {
+        let mut writer = BundleWriter::open(path).await?;
+        let resumed = writer.chunks().to_vec();
+
+        let mut cmd = Command::new("zfs");
+        cmd.args(["send", "-w", snapshot]);
+        let mut send = spawn_zfs(cmd, Stdio::null(), Stdio::piped())?;
+        let mut stream = send
+            .stdout
+            .take()
+            .ok_or_else(|| forensic_error("zfs send produced no output stream".into(), None))?;
+
+        let io_throttler = self.zfs.io_throttler();
+        let mut context = digest::Context::new(&digest::SHA256);
+        let mut buffer = vec![0u8; BUNDLE_CHUNK_SIZE];
+        for index in 0.. {
+            // Chunks are always filled completely so boundaries match across resumed captures
+            let mut filled = 0;
+            while filled < buffer.len() {
+                let read = stream
+                    .read(&mut buffer[filled..])
+                    .await
+                    .map_err(|e| forensic_error("Failed to read send stream".into(), Some(Box::new(e))))?;
+                if read == 0 {
+                    break;
+                }
+                filled += read;
+            }
+            if filled == 0 {
+                break;
+            }
+            let chunk = &buffer[..filled];
+            context.update(chunk);
+
+            match resumed.get(index) {
+                Some(bundled) if bundled.raw_len == filled as u64
+                    && bundled.raw_sha256 == to_hex(digest::digest(&digest::SHA256, chunk).as_ref()) => {}
+                Some(_) => {
+                    return Err(forensic_error(
+                        format!("Send stream of {} no longer matches partial bundle {}; remove it and retry", snapshot, path.display()),
+                        None,
+                    ));
+                }
+                None => {
+                    io_throttler.acquire(BackgroundJobClass::Backup, filled as u64).await;
+                    writer.append_chunk(chunk).await?;
+                    counter!("guardian.storage.forensics.bundle_chunks", 1);
+                }
+            }
+            if filled < buffer.len() {
+                break;
+            }
+        }
+        wait_zfs(send, "send").await?;
+
+        let sha256 = to_hex(context.finish().as_ref());
+        let summary = writer
+            .finish(serde_json::json!({ "snapshot": snapshot, "sha256": sha256, "format": "zfs send -w" }))
+            .await?;
+        info!(
+            path = %path.display(),
+            chunks = summary.chunks,
+            resumed_chunks = resumed.len(),
+            raw_bytes = summary.raw_bytes,
+            compressed_bytes = summary.compressed_bytes,
+            "Forensic bundle written"
+        );
+        Ok(sha256)
+    }
+
+    /// Writes a raw send stream of the snapshot, readable only by the owner
+    async fn write_image(&self, snapshot: &str, path: &Path) -> Result<String, GuardianError> {
+        let mut file = tokio::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .mode(0o600)
+            .open(path)
+            .await
+            .map_err(|e| forensic_error(format!("Failed to create forensic image {}", path.display()), Some(Box::new(e))))?;
+
+        let mut cmd = Command::new("zfs");
+        cmd.args(["send", "-w", snapshot]);
+        let mut send = spawn_zfs(cmd, Stdio::null(), Stdio::piped())?;
+        let mut stream = send
+            .stdout
+            .take()
+            .ok_or_else(|| forensic_error("zfs send produced no output stream".into(), None))?;
+
+        let io_throttler = self.zfs.io_throttler();
+        let mut context = digest::Context::new(&digest::SHA256);
+        let mut buffer = vec![0u8; IMAGE_CHUNK_SIZE];
+        loop {
+            let read = stream
+                .read(&mut buffer)
+                .await
+                .map_err(|e| forensic_error("Failed to read send stream".into(), Some(Box::new(e))))?;
+            if read == 0 {
+                break;
+            }
+            io_throttler.acquire(BackgroundJobClass::Backup, read as u64).await;
+            context.update(&buffer[..read]);
+            file.write_all(&buffer[..read])
+                .await
+                .map_err(|e| forensic_error(format!("Failed to write forensic image {}", path.display()), Some(Box::new(e))))?;
+        }
+        file.sync_all()
+            .await
+            .map_err(|e| forensic_error(format!("Failed to sync forensic image {}", path.display()), Some(Box::new(e))))?;
+        wait_zfs(send, "send").await?;
+
+        Ok(to_hex(context.finish().as_ref()))
+    }
+
+    /// Unmounts and destroys a clone, removing its mountpoint directory
+    async fn destroy_clone(&self, clone: &ForensicClone) -> Result<(), GuardianError> {
+        let mut cmd = Command::new("zfs");
+        cmd.args(["destroy", "-f", &clone.dataset]);
+        wait_zfs(spawn_zfs(cmd, Stdio::null(), Stdio::null())?, "destroy").await?;
+
+        if let Some(mountpoint) = &clone.mountpoint {
+            if let Err(e) = tokio::fs::remove_dir(mountpoint).await {
+                debug!(error = %e, path = %mountpoint.display(), "Forensic mountpoint not removed");
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Parses one `zfs list` line of a forensic clone
+fn parse_clone(line: &str) -> Option<ForensicClone> {
+    let mut fields = line.split('\t');
+    let dataset = fields.next()?.to_string();
+    let mountpoint = fields.next()?;
+    let snapshot = fields.next()?.to_string();
+    let requested_by = fields.next()?.to_string();
+    let expires_at = fields.next()?.trim().parse().ok()?;
+
+    let (_, id) = dataset.rsplit_once('/')?;
+    let mountpoint = match mountpoint {
+        "none" | "-" | "legacy" => None,
+        path => Some(PathBuf::from(path)),
+    };
+    Some(ForensicClone {
+        id: id.to_string(),
+        snapshot,
+        dataset,
+        mountpoint,
+        image: None,
+        image_sha256: None,
+        requested_by,
+        expires_at,
+    })
+}
+
+fn forensic_error(context: String, source: Option<Box<dyn std::error::Error + Send + Sync>>) -> GuardianError {
+    GuardianError::StorageError {
+        context,
+        source,
+        severity: crate::utils::error::ErrorSeverity::High,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: ErrorCategory::Storage,
+        retry_count: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_clone_listing() {
+        let clone = parse_clone(
+            "guardian/guardian/forensics/op-42\t/var/lib/guardian/forensics/op-42\tguardian/guardian/events@backup-1\talice\t1700000000",
+        )
+        .unwrap();
+        assert_eq!(clone.id, "op-42");
+        assert_eq!(clone.snapshot, "guardian/guardian/events@backup-1");
+        assert_eq!(clone.mountpoint, Some(PathBuf::from("/var/lib/guardian/forensics/op-42")));
+        assert_eq!(clone.expires_at, 1_700_000_000);
+
+        // Image-only clones are never mounted, and clones without an expiry are not ours
+        let unmounted = parse_clone("pool/guardian/forensics/op-7\tnone\tpool/guardian@s\tbob\t1").unwrap();
+        assert!(unmounted.mountpoint.is_none());
+        assert!(parse_clone("pool/guardian/forensics/op-8\tnone\tpool/guardian@s\tbob\t-").is_none());
+    }
+}
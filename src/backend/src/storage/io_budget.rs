@@ -0,0 +1,176 @@
+//! Write budget protecting model reads
+//!
+//! Metrics and event flushes share the pool with the models dataset, and a burst of them
+//! pushes model loads past their latency target. Model reads report how long they took per
+//! MiB; while that latency is above the threshold and the models dataset's ZFS statistics show
+//! it is being read, the budget has the storage `IoThrottler` hold metrics and event writes to
+//! a fraction of their limits that shrinks as latency grows. Otherwise writes pass untouched.
+
+use std::{sync::Arc, time::Duration};
+
+use metrics::{gauge, histogram};
+use parking_lot::Mutex;
+use tracing::{debug, info, warn};
+
+use crate::config::storage_config::IoBudgetConfig;
+use crate::storage::io_throttle::IoThrottler;
+use crate::storage::zfs_manager::{DatasetIoStats, ZfsManager};
+
+// Constants for the I/O budget
+const MIB: f64 = 1024.0 * 1024.0;
+/// Weight of the newest model read in the latency average
+const LATENCY_SMOOTHING: f64 = 0.3;
+
+#[derive(Debug, Default)]
+struct ReadLatency {
+    /// Smoothed read time per MiB, in seconds
+    average: Option<f64>,
+    /// Reads reported since the last sample
+    reads: u64,
+}
+
+/// Throttles metrics and event writes while model reads are slow
+#[derive(Debug)]
+pub struct IoBudget {
+    config: IoBudgetConfig,
+    /// Throttler enforcing the write scale, shared with every store on the pool
+    throttler: Arc<IoThrottler>,
+    latency: Mutex<ReadLatency>,
+    /// Models dataset counters at the last sample
+    last_stats: Mutex<Option<DatasetIoStats>>,
+}
+
+impl IoBudget {
+    /// Creates a budget setting the write scale of `throttler`, whose write limits come from the same config
+    pub fn new(config: IoBudgetConfig, throttler: Arc<IoThrottler>) -> Self {
+        Self {
+            config,
+            throttler,
+            latency: Mutex::new(ReadLatency::default()),
+            last_stats: Mutex::new(None),
+        }
+    }
+
+    /// Records a model read of `bytes` bytes that took `elapsed`
+    ///
+    /// Reads under a MiB count as a MiB, so small reads are not mistaken for slow ones.
+    pub fn record_model_read(&self, bytes: u64, elapsed: Duration) {
+        let per_mib = elapsed.as_secs_f64() / (bytes as f64 / MIB).max(1.0);
+        let mut latency = self.latency.lock();
+        latency.average = Some(match latency.average {
+            Some(average) => average + LATENCY_SMOOTHING * (per_mib - average),
+            None => per_mib,
+        });
+        latency.reads += 1;
+        histogram!("guardian.storage.io_budget.model_read_seconds_per_mib", per_mib);
+    }
+
+    /// Fraction of the write limits in effect, `None` while writes are not throttled
+    pub fn scale(&self) -> Option<f64> {
+        self.throttler.write_scale()
+    }
+
+    /// Samples the models dataset and adjusts the write limits until the task is aborted
+    pub fn start(self: Arc<Self>, zfs: Arc<ZfsManager>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let dataset = zfs.dataset_path(&self.config.models_dataset);
+            let mut interval = tokio::time::interval(self.config.sample_interval);
+            loop {
+                interval.tick().await;
+                let stats = match zfs.dataset_io_stats(&dataset).await {
+                    Ok(stats) => Some(stats),
+                    Err(e) => {
+                        debug!(error = %e, %dataset, "Dataset statistics unavailable, relying on reported reads");
+                        None
+                    }
+                };
+                self.update(stats);
+            }
+        })
+    }
+
+    /// Derives the write scale from the latest read latency and models dataset counters
+    fn update(&self, stats: Option<DatasetIoStats>) -> Option<f64> {
+        if !self.config.enabled {
+            return None;
+        }
+
+        let dataset_reads = stats.map(|stats| {
+            let previous = self.last_stats.lock().replace(stats);
+            let delta = previous.map_or(0, |previous| stats.reads.saturating_sub(previous.reads));
+            gauge!("guardian.storage.io_budget.model_reads", delta as f64);
+            delta
+        });
+
+        let mut latency = self.latency.lock();
+        let reported_reads = std::mem::take(&mut latency.reads);
+        // Reads of mapped models fault pages in without being reported, so the dataset
+        // counters keep the last latency relevant while they show reads
+        let reading = reported_reads > 0 || dataset_reads.map_or(false, |reads| reads > 0);
+        if !reading {
+            latency.average = None;
+        }
+        let threshold = self.config.read_latency_threshold.as_secs_f64();
+        let scale = latency
+            .average
+            .filter(|&average| average > threshold)
+            .map(|average| (threshold / average).clamp(self.config.min_scale, 1.0));
+        let average = latency.average;
+        drop(latency);
+
+        let previous = self.throttler.write_scale();
+        self.throttler.set_write_scale(scale);
+        gauge!("guardian.storage.io_budget.scale", scale.unwrap_or(1.0));
+        match (previous, scale) {
+            (None, Some(scale)) => warn!(scale, read_seconds_per_mib = ?average, "Model reads slow, throttling metrics and event writes"),
+            (Some(_), None) => info!("Model reads recovered, metrics and event writes unthrottled"),
+            _ => {}
+        }
+        scale
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    use crate::config::storage_config::{BackgroundIoConfig, IoClassLimit, WriteClass};
+
+    #[tokio::test(start_paused = true)]
+    async fn test_writes_throttled_only_while_model_reads_are_slow() {
+        let config = IoBudgetConfig {
+            read_latency_threshold: Duration::from_millis(20),
+            write_limits: HashMap::from([(
+                WriteClass::Metrics,
+                IoClassLimit { bytes_per_sec: 1000, ops_per_sec: 0, burst_seconds: 1.0 },
+            )]),
+            ..Default::default()
+        };
+        let throttler = Arc::new(IoThrottler::new(&BackgroundIoConfig::default(), &config.write_limits));
+        let budget = IoBudget::new(config, Arc::clone(&throttler));
+        let reads = |reads| Some(DatasetIoStats { reads, ..Default::default() });
+        let started = tokio::time::Instant::now();
+
+        // Fast reads leave writes alone
+        budget.record_model_read(4 * 1024 * 1024, Duration::from_millis(40));
+        assert_eq!(budget.update(reads(10)), None);
+        throttler.acquire_write(WriteClass::Metrics, u64::MAX).await;
+        assert_eq!(started.elapsed(), Duration::ZERO);
+
+        // A slow read lifts the average to about twice the threshold: about half the limit
+        budget.record_model_read(1024 * 1024, Duration::from_millis(110));
+        let scale = budget.update(reads(20)).unwrap();
+        assert!((scale - 0.5).abs() < 1e-6);
+        assert_eq!(throttler.write_scale(), Some(scale));
+        throttler.acquire_write(WriteClass::Metrics, 500).await;
+        throttler.acquire_write(WriteClass::Metrics, 250).await;
+        assert!(started.elapsed() >= Duration::from_millis(490));
+
+        // Once the models dataset goes quiet the latency is stale and writes resume
+        assert_eq!(budget.update(reads(20)), None);
+        let resumed = tokio::time::Instant::now();
+        throttler.acquire_write(WriteClass::Metrics, u64::MAX).await;
+        assert_eq!(resumed.elapsed(), Duration::ZERO);
+    }
+}
@@ -0,0 +1,303 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use metrics::{counter, gauge, histogram};
+use parking_lot::Mutex;
+use tracing::{debug, instrument};
+
+use crate::config::storage_config::{BackgroundIoConfig, BackgroundJobClass, IoBudgetConfig, IoClassLimit, WriteClass};
+use crate::core::resource_governor::{governor, Subsystem};
+
+// Constants for I/O throttling
+const MIN_GOVERNOR_SCALE: f64 = 0.05;
+const MAX_THROTTLE_WAIT: Duration = Duration::from_secs(30);
+const MAX_WRITE_WAIT: Duration = Duration::from_secs(10);
+
+/// Token bucket refilled continuously at a fixed rate
+#[derive(Debug)]
+struct TokenBucket {
+    rate: f64,
+    burst_seconds: f64,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(rate: f64, burst_seconds: f64) -> Self {
+        Self {
+            rate,
+            burst_seconds,
+            tokens: rate * burst_seconds,
+            last_refill: Instant::now(),
+        }
+    }
+
+    /// Reserves `amount` tokens at the scaled rate and returns how long the caller must wait.
+    /// Reservations may drive the bucket negative so oversized requests still make progress.
+    fn reserve(&mut self, amount: f64, scale: f64, now: Instant) -> Duration {
+        let rate = self.rate * scale;
+        if rate <= 0.0 {
+            return Duration::ZERO;
+        }
+
+        let capacity = rate * self.burst_seconds;
+        let elapsed = now.saturating_duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * rate).min(capacity);
+        self.last_refill = now;
+
+        self.tokens -= amount;
+        if self.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.tokens / rate)
+        }
+    }
+}
+
+#[derive(Debug)]
+struct ClassBuckets {
+    bytes: TokenBucket,
+    ops: TokenBucket,
+}
+
+impl ClassBuckets {
+    fn new(limit: &IoClassLimit) -> Self {
+        Self {
+            bytes: TokenBucket::new(limit.bytes_per_sec as f64, limit.burst_seconds),
+            ops: TokenBucket::new(limit.ops_per_sec as f64, limit.burst_seconds),
+        }
+    }
+
+    fn reserve(&mut self, bytes: u64, scale: f64, now: Instant) -> Duration {
+        let byte_wait = self.bytes.reserve(bytes as f64, scale, now);
+        let ops_wait = self.ops.reserve(1.0, scale, now);
+        byte_wait.max(ops_wait)
+    }
+}
+
+/// Rate limits disk I/O of background storage jobs per job class, and of metrics and event
+/// writes while the I/O budget finds model reads slow
+#[derive(Debug)]
+pub struct IoThrottler {
+    enabled: bool,
+    buckets: Mutex<HashMap<BackgroundJobClass, ClassBuckets>>,
+    write_buckets: Mutex<HashMap<WriteClass, ClassBuckets>>,
+    governor_scale: AtomicU64,
+    /// Fraction of the write limits in effect, `None` while writes are not throttled
+    write_scale: Mutex<Option<f64>>,
+}
+
+impl IoThrottler {
+    /// Creates a throttler from the storage background I/O limits and the I/O budget's write limits
+    pub fn new(config: &BackgroundIoConfig, write_limits: &HashMap<WriteClass, IoClassLimit>) -> Self {
+        let buckets = config
+            .class_limits
+            .iter()
+            .map(|(class, limit)| (*class, ClassBuckets::new(limit)))
+            .collect();
+        let write_buckets = write_limits
+            .iter()
+            .map(|(class, limit)| (*class, ClassBuckets::new(limit)))
+            .collect();
+
+        Self {
+            enabled: config.enabled,
+            buckets: Mutex::new(buckets),
+            write_buckets: Mutex::new(write_buckets),
+            governor_scale: AtomicU64::new(1.0f64.to_bits()),
+            write_scale: Mutex::new(None),
+        }
+    }
+
+    /// Scales every class limit, called by the resource governor under pressure
+    pub fn set_governor_scale(&self, scale: f64) {
+        let scale = if scale.is_finite() { scale.clamp(MIN_GOVERNOR_SCALE, 1.0) } else { 1.0 };
+        self.governor_scale.store(scale.to_bits(), Ordering::Relaxed);
+        gauge!("guardian.storage.io_throttle.governor_scale", scale);
+        debug!(scale, "Background I/O governor scale updated");
+    }
+
+    /// Follows the resource governor's storage scale for as long as the throttler lives
+    pub fn follow_governor(self: &Arc<Self>) {
+        let throttler = Arc::downgrade(self);
+        governor().on_scale_change(Subsystem::Storage, move |scale| {
+            if let Some(throttler) = throttler.upgrade() {
+                throttler.set_governor_scale(scale);
+            }
+        });
+    }
+
+    /// Returns the current governor scale factor
+    pub fn governor_scale(&self) -> f64 {
+        f64::from_bits(self.governor_scale.load(Ordering::Relaxed))
+    }
+
+    /// Throttles metrics and event writes to a fraction of their limits, or lifts the throttle
+    /// with `None`; set by the I/O budget as model read latency changes
+    pub fn set_write_scale(&self, scale: Option<f64>) {
+        *self.write_scale.lock() = scale.filter(|scale| scale.is_finite()).map(|scale| scale.clamp(MIN_GOVERNOR_SCALE, 1.0));
+    }
+
+    /// Fraction of the write limits in effect, `None` while writes are not throttled
+    pub fn write_scale(&self) -> Option<f64> {
+        *self.write_scale.lock()
+    }
+
+    /// Waits until the job class may perform one operation of `bytes` bytes
+    #[instrument(skip(self))]
+    pub async fn acquire(&self, class: BackgroundJobClass, bytes: u64) {
+        let wait = self.reserve(class, bytes, Instant::now());
+        let class_label = class_label(class);
+
+        counter!("guardian.storage.io_throttle.bytes", bytes, "class" => class_label);
+        if wait.is_zero() {
+            return;
+        }
+
+        counter!("guardian.storage.io_throttle.throttled", 1, "class" => class_label);
+        histogram!("guardian.storage.io_throttle.wait_seconds", wait.as_secs_f64(), "class" => class_label);
+        tokio::time::sleep(wait).await;
+    }
+
+    /// Waits until a metrics or event store may write `bytes` bytes
+    #[instrument(skip(self))]
+    pub async fn acquire_write(&self, class: WriteClass, bytes: u64) {
+        let wait = self.reserve_write(class, bytes, Instant::now());
+        if wait.is_zero() {
+            return;
+        }
+
+        let class_label = write_class_label(class);
+        counter!("guardian.storage.io_budget.throttled", 1, "class" => class_label);
+        histogram!("guardian.storage.io_budget.wait_seconds", wait.as_secs_f64(), "class" => class_label);
+        tokio::time::sleep(wait).await;
+    }
+
+    fn reserve(&self, class: BackgroundJobClass, bytes: u64, now: Instant) -> Duration {
+        if !self.enabled {
+            return Duration::ZERO;
+        }
+
+        let scale = self.governor_scale();
+        let mut buckets = self.buckets.lock();
+        let Some(class_buckets) = buckets.get_mut(&class) else {
+            return Duration::ZERO;
+        };
+        class_buckets.reserve(bytes, scale, now).min(MAX_THROTTLE_WAIT)
+    }
+
+    /// Writes are foreground work, so they wait on their buckets only while the budget asks for it,
+    /// and never longer than a flush can afford
+    fn reserve_write(&self, class: WriteClass, bytes: u64, now: Instant) -> Duration {
+        let Some(write_scale) = self.write_scale() else {
+            return Duration::ZERO;
+        };
+
+        let scale = write_scale * self.governor_scale();
+        let mut buckets = self.write_buckets.lock();
+        let Some(class_buckets) = buckets.get_mut(&class) else {
+            return Duration::ZERO;
+        };
+        class_buckets.reserve(bytes, scale, now).min(MAX_WRITE_WAIT)
+    }
+}
+
+impl Default for IoThrottler {
+    fn default() -> Self {
+        Self::new(&BackgroundIoConfig::default(), &IoBudgetConfig::default().write_limits)
+    }
+}
+
+fn write_class_label(class: WriteClass) -> &'static str {
+    match class {
+        WriteClass::Metrics => "metrics",
+        WriteClass::Events => "events",
+    }
+}
+
+fn class_label(class: BackgroundJobClass) -> &'static str {
+    match class {
+        BackgroundJobClass::Scrub => "scrub",
+        BackgroundJobClass::Rollup => "rollup",
+        BackgroundJobClass::Backup => "backup",
+        BackgroundJobClass::Retention => "retention",
+        BackgroundJobClass::Snapshot => "snapshot",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(bytes_per_sec: u64, ops_per_sec: u32) -> BackgroundIoConfig {
+        BackgroundIoConfig {
+            enabled: true,
+            class_limits: HashMap::from([(
+                BackgroundJobClass::Backup,
+                IoClassLimit { bytes_per_sec, ops_per_sec, burst_seconds: 1.0 },
+            )]),
+        }
+    }
+
+    #[test]
+    fn test_token_bucket_throttles_after_burst() {
+        let throttler = IoThrottler::new(&config(1000, 0), &HashMap::new());
+        let now = Instant::now();
+
+        assert_eq!(throttler.reserve(BackgroundJobClass::Backup, 1000, now), Duration::ZERO);
+        let wait = throttler.reserve(BackgroundJobClass::Backup, 500, now);
+        assert!((wait.as_secs_f64() - 0.5).abs() < 1e-6);
+
+        // Unconfigured classes are never throttled
+        assert_eq!(throttler.reserve(BackgroundJobClass::Scrub, u64::MAX, now), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_governor_scale_slows_jobs() {
+        let throttler = IoThrottler::new(&config(0, 10), &HashMap::new());
+        throttler.set_governor_scale(0.5);
+        let now = Instant::now();
+
+        // Burst shrinks to 5 ops at half rate
+        for _ in 0..5 {
+            assert_eq!(throttler.reserve(BackgroundJobClass::Backup, 0, now), Duration::ZERO);
+        }
+        let wait = throttler.reserve(BackgroundJobClass::Backup, 0, now);
+        assert!((wait.as_secs_f64() - 0.2).abs() < 1e-6);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_acquire_waits_out_the_limit() {
+        let throttler = IoThrottler::new(&config(1000, 0), &HashMap::new());
+        let started = tokio::time::Instant::now();
+
+        // The burst covers the first second's worth; the next 500 bytes take half a second
+        throttler.acquire(BackgroundJobClass::Backup, 1000).await;
+        assert_eq!(started.elapsed(), Duration::ZERO);
+        throttler.acquire(BackgroundJobClass::Backup, 500).await;
+        assert!(started.elapsed() >= Duration::from_millis(490));
+    }
+
+    #[test]
+    fn test_writes_throttled_only_under_write_scale() {
+        let limits = HashMap::from([(WriteClass::Metrics, IoClassLimit { bytes_per_sec: 1000, ops_per_sec: 0, burst_seconds: 1.0 })]);
+        let throttler = IoThrottler::new(&config(0, 0), &limits);
+        let now = Instant::now();
+        assert_eq!(throttler.reserve_write(WriteClass::Metrics, u64::MAX, now), Duration::ZERO);
+
+        // At half scale the burst holds 500 bytes
+        throttler.set_write_scale(Some(0.5));
+        assert_eq!(throttler.reserve_write(WriteClass::Metrics, 500, now), Duration::ZERO);
+        assert!(throttler.reserve_write(WriteClass::Metrics, 100, now) > Duration::ZERO);
+        assert_eq!(throttler.reserve_write(WriteClass::Events, u64::MAX, now), Duration::ZERO);
+
+        throttler.set_write_scale(None);
+        assert_eq!(throttler.reserve_write(WriteClass::Metrics, u64::MAX, now), Duration::ZERO);
+    }
+}
@@ -0,0 +1,163 @@
+use std::{
+    collections::BTreeMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use metrics::{counter, gauge};
+use once_cell::sync::Lazy;
+use parking_lot::{Mutex, RwLock};
+use tracing::{error, info, instrument, warn};
+
+use crate::config::storage_config::ScrubConfig;
+use crate::core::event_bus::{Event, EventBus, EventPriority};
+use crate::storage::zfs_manager::{PoolStatus, ScanStatus, VdevStatus, ZfsManager};
+use crate::utils::error::GuardianError;
+
+// Constants for storage maintenance
+/// Scrubs are deferred while the resource governor has storage I/O scaled below this
+const MIN_SCRUB_GOVERNOR_SCALE: f64 = 0.5;
+
+static POOL_REPORTS: Lazy<RwLock<BTreeMap<String, PoolStatus>>> = Lazy::new(|| RwLock::new(BTreeMap::new()));
+
+/// Returns the latest status of every monitored pool
+pub fn pool_reports() -> Vec<PoolStatus> {
+    POOL_REPORTS.read().values().cloned().collect()
+}
+
+/// Schedules periodic scrubs and watches pool health through the ZFS manager
+#[derive(Debug)]
+pub struct StorageMaintenance {
+    zfs: Arc<ZfsManager>,
+    event_bus: Arc<EventBus>,
+    config: ScrubConfig,
+    /// When this process last started a scrub, or when monitoring started
+    last_scrub: Mutex<Instant>,
+}
+
+impl StorageMaintenance {
+    /// Creates maintenance for the manager's pool; the first scrub is due one interval from now
+    pub fn new(zfs: Arc<ZfsManager>, event_bus: Arc<EventBus>, config: ScrubConfig) -> Self {
+        Self {
+            zfs,
+            event_bus,
+            config,
+            last_scrub: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Polls pool health and starts scrubs in the background until the task is aborted
+    pub fn start(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.config.poll_interval);
+            loop {
+                interval.tick().await;
+                if let Err(e) = self.run_once().await {
+                    warn!(error = %e, pool = self.zfs.pool_name(), "Storage maintenance pass failed");
+                }
+            }
+        })
+    }
+
+    /// Reads pool status, raises events for newly impaired vdevs and starts a scrub when one is due
+    #[instrument(skip(self), fields(pool = self.zfs.pool_name()))]
+    pub async fn run_once(&self) -> Result<PoolStatus, GuardianError> {
+        let status = self.zfs.pool_status().await?;
+        let previous = POOL_REPORTS.write().insert(status.pool.clone(), status.clone());
+        self.record_metrics(&status);
+
+        for vdev in newly_impaired(previous.as_ref(), &status) {
+            error!(vdev = %vdev.name, state = ?vdev.state, "ZFS vdev impaired");
+            counter!("guardian.storage.vdev.impaired", 1, "pool" => status.pool.clone());
+            self.event_bus.publish(Event::new(
+                self.zfs.tenant().topic("storage_vdev_impaired"),
+                serde_json::json!({
+                    "pool": status.pool,
+                    "pool_state": status.state,
+                    "vdev": vdev,
+                }),
+                EventPriority::Critical,
+            )?).await?;
+        }
+
+        let new_checksum_errors = status
+            .checksum_errors()
+            .saturating_sub(previous.as_ref().map(PoolStatus::checksum_errors).unwrap_or_default());
+        if new_checksum_errors > 0 {
+            warn!(pool = %status.pool, new_checksum_errors, "ZFS checksum errors increased");
+        }
+
+        if self.scrub_due(&status) {
+            self.zfs.start_scrub().await?;
+            *self.last_scrub.lock() = Instant::now();
+        }
+        Ok(status)
+    }
+
+    fn scrub_due(&self, status: &PoolStatus) -> bool {
+        if !self.config.enabled || matches!(status.scan, ScanStatus::InProgress { .. }) {
+            return false;
+        }
+        // A pool never scrubbed is due right away
+        let due = status.scan == ScanStatus::None || self.last_scrub.lock().elapsed() >= self.config.interval;
+        if due && self.zfs.io_throttler().governor_scale() < MIN_SCRUB_GOVERNOR_SCALE {
+            info!(pool = %status.pool, "Scrub due but deferred while storage I/O is throttled");
+            counter!("guardian.storage.scrub.deferred", 1);
+            return false;
+        }
+        due
+    }
+
+    fn record_metrics(&self, status: &PoolStatus) {
+        let pool = status.pool.clone();
+        gauge!("guardian.storage.pool.checksum_errors", status.checksum_errors() as f64, "pool" => pool.clone());
+        gauge!("guardian.storage.pool.impaired_vdevs", status.impaired_vdevs().count() as f64, "pool" => pool.clone());
+        let progress = match status.scan {
+            ScanStatus::InProgress { percent_done } => percent_done,
+            ScanStatus::Completed { .. } => 100.0,
+            ScanStatus::None | ScanStatus::Canceled => 0.0,
+        };
+        gauge!("guardian.storage.scrub.progress_percent", progress, "pool" => pool);
+        if let ScanStatus::Completed { errors } = status.scan {
+            gauge!("guardian.storage.scrub.errors", errors as f64, "pool" => status.pool.clone());
+        }
+    }
+}
+
+/// Vdevs impaired now that were not impaired, or in a different state, at the previous poll
+fn newly_impaired<'a>(previous: Option<&PoolStatus>, current: &'a PoolStatus) -> Vec<&'a VdevStatus> {
+    current
+        .impaired_vdevs()
+        .filter(|vdev| {
+            previous
+                .and_then(|p| p.vdevs.iter().find(|v| v.name == vdev.name))
+                .map_or(true, |before| before.state != vdev.state)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::zfs_manager::VdevState;
+
+    #[test]
+    fn test_only_new_impairments_are_raised() {
+        let vdev = |name: &str, state| VdevStatus {
+            name: name.into(),
+            state,
+            read_errors: 0,
+            write_errors: 0,
+            checksum_errors: 0,
+        };
+        let pool = |vdevs| PoolStatus { pool: "tank".into(), state: VdevState::Online, scan: ScanStatus::None, vdevs };
+
+        let before = pool(vec![vdev("ada0", VdevState::Online), vdev("ada1", VdevState::Degraded)]);
+        let after = pool(vec![vdev("ada0", VdevState::Faulted), vdev("ada1", VdevState::Degraded)]);
+
+        let raised: Vec<_> = newly_impaired(Some(&before), &after).iter().map(|v| v.name.clone()).collect();
+        assert_eq!(raised, ["ada0"]);
+        assert_eq!(newly_impaired(None, &after).len(), 2);
+        assert!(newly_impaired(Some(&after), &after).is_empty());
+    }
+}
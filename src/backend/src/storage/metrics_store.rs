@@ -2,24 +2,39 @@ use async_trait::async_trait;
 use chrono::{DateTime, Duration, Utc};
 use lru::LruCache;
 use serde::{Deserialize, Serialize};
+use metrics::{counter, gauge, histogram};
 use std::{
     collections::HashMap,
     sync::Arc,
+    time::Instant,
 };
-use tokio::sync::RwLock;
-use tracing::{debug, error, info, instrument};
+use tokio::sync::{mpsc, oneshot, RwLock};
+use tracing::{debug, error, info, instrument, warn};
 
+use crate::core::resource_governor::{governor, Subsystem};
 use crate::utils::error::{GuardianError, ErrorCategory};
 use crate::utils::metrics::{MetricsCollector, MetricType, MetricPriority};
+use crate::utils::metrics_stream::{metrics_hub, MetricSample, MetricsFrame};
+use crate::utils::queue_metrics::{queue_registry, QueueMonitor};
+use crate::config::storage_config::{BackgroundJobClass, WriteClass};
+use crate::storage::retention::{self, ArchiveStore, RetentionReport, RetentionTier, TierPolicy};
 use crate::storage::zfs_manager::ZfsManager;
 
 // Constants for metrics storage configuration
 const DEFAULT_RETENTION_DAYS: u32 = 90;
 const METRICS_PARTITION_PREFIX: &str = "metrics";
 const CLEANUP_INTERVAL: Duration = Duration::days(1);
+/// Days past a tier boundary a partition is still moved, covering passes missed while stopped
+const TIER_CATCH_UP_DAYS: i64 = 7;
 const DEFAULT_BATCH_SIZE: usize = 1000;
 const DEFAULT_COMPRESSION_LEVEL: u8 = 6;
 const MAX_CACHE_SIZE: usize = 10000;
+const DEFAULT_FLUSH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+const DEFAULT_FLUSH_SIZE: usize = 5000;
+const DEFAULT_MAX_BUFFERED: usize = 100_000;
+const DEFAULT_INGEST_CHANNEL_CAPACITY: usize = 1024;
+/// How long the snapshot recorder waits on the hub before checking it again
+const SNAPSHOT_WAIT: std::time::Duration = std::time::Duration::from_secs(30);
 
 /// Represents a single metric data point
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -31,6 +46,208 @@ pub struct Metric {
     tags: HashMap<String, String>,
 }
 
+impl Metric {
+    /// Creates a data point stamped with the current time
+    pub fn new(name: impl Into<String>, value: f64, metric_type: MetricType) -> Self {
+        Self {
+            name: name.into(),
+            value,
+            timestamp: Utc::now(),
+            metric_type,
+            tags: HashMap::new(),
+        }
+    }
+
+    /// Adds a tag to the data point
+    pub fn with_tag(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.tags.insert(key.into(), value.into());
+        self
+    }
+
+    /// Returns the metric name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the recorded value
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    /// Returns when the data point was recorded
+    pub fn timestamp(&self) -> DateTime<Utc> {
+        self.timestamp
+    }
+
+    /// Returns the value of a tag, if set
+    pub fn tag(&self, key: &str) -> Option<&str> {
+        self.tags.get(key).map(String::as_str)
+    }
+
+    /// Converts a collector's aggregated sample into a data point stamped with its flush time
+    fn from_sample(sample: MetricSample, collected_at: DateTime<Utc>) -> Self {
+        Self {
+            name: sample.name,
+            value: sample.value,
+            timestamp: collected_at,
+            metric_type: sample.metric_type,
+            tags: sample.tags.into_iter().collect(),
+        }
+    }
+
+    fn partition_key(&self) -> String {
+        partition_key(self.timestamp.date_naive())
+    }
+}
+
+fn partition_key(date: chrono::NaiveDate) -> String {
+    format!("{}/{}", METRICS_PARTITION_PREFIX, date.format("%Y-%m-%d"))
+}
+
+/// Age in days of a partition, from the date in its key
+fn partition_age(partition: &str, today: chrono::NaiveDate) -> Option<i64> {
+    let date = partition.strip_prefix(METRICS_PARTITION_PREFIX)?.strip_prefix('/')?;
+    let date = chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?;
+    Some((today - date).num_days())
+}
+
+/// Retention tiers in effect and the archive partitions move to
+#[derive(Debug, Clone)]
+struct Tiering {
+    policy: TierPolicy,
+    archive: Option<Arc<dyn ArchiveStore>>,
+}
+
+/// Tuning for buffered metric ingestion
+#[derive(Debug, Clone)]
+pub struct IngestConfig {
+    /// Longest time a metric waits in the buffer
+    pub flush_interval: std::time::Duration,
+    /// Buffered metric count that triggers an early flush
+    pub flush_size: usize,
+    /// Metrics retained across failed flushes before the oldest are dropped
+    pub max_buffered: usize,
+    pub channel_capacity: usize,
+}
+
+impl Default for IngestConfig {
+    fn default() -> Self {
+        Self {
+            flush_interval: DEFAULT_FLUSH_INTERVAL,
+            flush_size: DEFAULT_FLUSH_SIZE,
+            max_buffered: DEFAULT_MAX_BUFFERED,
+            channel_capacity: DEFAULT_INGEST_CHANNEL_CAPACITY,
+        }
+    }
+}
+
+#[derive(Debug)]
+enum IngestCommand {
+    Metrics(Vec<Metric>),
+    Flush(oneshot::Sender<Result<usize, GuardianError>>),
+}
+
+/// Handle for submitting metrics to a background flusher
+#[derive(Debug, Clone)]
+pub struct MetricsIngester {
+    tx: mpsc::Sender<IngestCommand>,
+    queue: Arc<QueueMonitor>,
+}
+
+impl MetricsIngester {
+    /// Buffers a batch of metrics, waiting only when the flusher is saturated
+    pub async fn ingest_batch(&self, metrics: Vec<Metric>) -> Result<(), GuardianError> {
+        if metrics.is_empty() {
+            return Ok(());
+        }
+        self.tx
+            .send(IngestCommand::Metrics(metrics))
+            .await
+            .map_err(|_| storage_error("Metrics flusher has stopped".into(), None))?;
+        self.queue.record_enqueue();
+        Ok(())
+    }
+
+    /// Writes all buffered metrics and returns how many were written
+    pub async fn flush(&self) -> Result<usize, GuardianError> {
+        let (done, result) = oneshot::channel();
+        self.tx
+            .send(IngestCommand::Flush(done))
+            .await
+            .map_err(|_| storage_error("Metrics flusher has stopped".into(), None))?;
+        self.queue.record_enqueue();
+        result
+            .await
+            .map_err(|_| storage_error("Metrics flusher has stopped".into(), None))?
+    }
+
+    /// Spawns a task buffering every snapshot the metrics collectors flush to the hub
+    ///
+    /// The task takes one snapshot at a time, granting the next only once the current one is
+    /// buffered, so a saturated flusher makes the hub skip snapshots instead of queueing them.
+    pub fn record_snapshots(self) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let hub = metrics_hub();
+            let mut subscription = hub.subscribe(Vec::new(), 1, None);
+            loop {
+                let MetricsFrame::Snapshot { snapshot, skipped, .. } = subscription.next(SNAPSHOT_WAIT).await else {
+                    continue;
+                };
+                if skipped > 0 {
+                    counter!("guardian.storage.metrics.snapshots_skipped", skipped);
+                    warn!(skipped, "Metrics snapshots skipped before they were stored");
+                }
+                let collected_at = snapshot.collected_at;
+                let metrics = snapshot.samples.into_iter().map(|s| Metric::from_sample(s, collected_at)).collect();
+                if let Err(e) = self.ingest_batch(metrics).await {
+                    error!(error = %e, "Stopped recording metrics snapshots");
+                    return;
+                }
+                hub.grant(subscription.id(), 1);
+            }
+        })
+    }
+}
+
+/// Metrics awaiting a flush, coalesced per partition
+#[derive(Debug, Default)]
+struct PendingMetrics {
+    partitions: HashMap<String, Vec<Metric>>,
+    len: usize,
+}
+
+impl PendingMetrics {
+    fn push(&mut self, metrics: Vec<Metric>) {
+        self.len += metrics.len();
+        for metric in metrics {
+            self.partitions.entry(metric.partition_key()).or_default().push(metric);
+        }
+    }
+
+    fn take(&mut self) -> HashMap<String, Vec<Metric>> {
+        self.len = 0;
+        std::mem::take(&mut self.partitions)
+    }
+
+    /// Re-queues a partition whose write failed, dropping the oldest metrics beyond the limit
+    fn restore(&mut self, partition: String, mut metrics: Vec<Metric>, max_buffered: usize) {
+        let room = max_buffered.saturating_sub(self.len);
+        if metrics.len() > room {
+            let dropped = metrics.len() - room;
+            metrics.drain(..dropped);
+            counter!("guardian.storage.metrics.dropped", dropped as u64);
+            warn!(partition = %partition, dropped, "Dropping metrics after failed flush");
+        }
+        if metrics.is_empty() {
+            return;
+        }
+        self.len += metrics.len();
+        let entry = self.partitions.entry(partition).or_default();
+        metrics.append(entry);
+        *entry = metrics;
+    }
+}
+
 /// Query parameters for retrieving metrics
 #[derive(Debug, Clone)]
 pub struct MetricsQuery {
@@ -48,33 +265,43 @@ pub struct MetricsStore {
     batch_size: usize,
     compression_level: u8,
     metrics_cache: Arc<RwLock<LruCache<String, Vec<Metric>>>>,
+    tiering: Arc<RwLock<Tiering>>,
 }
 
 impl MetricsStore {
     /// Creates a new MetricsStore instance with optimized configuration
+    ///
+    /// Partitions are compressed at `compression_level` and deleted after `retention_days` until
+    /// retention tiers are set with `set_tiers`.
     pub async fn new(
         zfs_manager: Arc<ZfsManager>,
         retention_days: u32,
         batch_size: usize,
         compression_level: u8,
     ) -> Result<Self, GuardianError> {
+        let retention_days = retention_days.max(1).min(365);
+        let compression_level = compression_level.max(1).min(9);
         let store = Self {
             zfs_manager,
             metrics_collector: Arc::new(RwLock::new(MetricsCollector::new(Default::default())?)),
-            retention_days: retention_days.max(1).min(365),
+            retention_days,
             batch_size: batch_size.max(100).min(10000),
-            compression_level: compression_level.max(1).min(9),
+            compression_level,
             metrics_cache: Arc::new(RwLock::new(LruCache::new(MAX_CACHE_SIZE))),
+            tiering: Arc::new(RwLock::new(Tiering {
+                policy: TierPolicy::delete_after(retention_days, i32::from(compression_level)),
+                archive: None,
+            })),
         };
 
-        // Start background cleanup task
+        // Start background retention task
         let store_clone = store.clone();
         tokio::spawn(async move {
             let mut interval = tokio::time::interval(CLEANUP_INTERVAL.to_std().unwrap());
             loop {
                 interval.tick().await;
-                if let Err(e) = cleanup_old_metrics(store_clone.retention_days).await {
-                    error!("Failed to cleanup old metrics: {:?}", e);
+                if let Err(e) = store_clone.apply_retention(Utc::now()).await {
+                    error!("Failed to apply metrics retention: {:?}", e);
                 }
             }
         });
@@ -82,82 +309,213 @@ impl MetricsStore {
         Ok(store)
     }
 
+    /// Moves partitions through hot, warm and archive tiers; without an archive, partitions
+    /// leaving the warm tier are deleted
+    pub async fn set_tiers(&self, policy: TierPolicy, archive: Option<Arc<dyn ArchiveStore>>) {
+        info!(ages = ?policy.ages, archived = archive.is_some(), "Metrics retention tiers set");
+        *self.tiering.write().await = Tiering { policy, archive };
+    }
+
+    /// Moves partitions that crossed a tier boundary in the last `TIER_CATCH_UP_DAYS`
+    ///
+    /// Warm partitions are recompressed in place, archived ones moved off the pool, and expired
+    /// ones deleted from wherever they are.
+    #[instrument(skip(self))]
+    pub async fn apply_retention(&self, now: DateTime<Utc>) -> Result<RetentionReport, GuardianError> {
+        let Tiering { policy, archive } = self.tiering.read().await.clone();
+        let today = now.date_naive();
+        let io_throttler = self.zfs_manager.io_throttler();
+        let mut report = RetentionReport::default();
+
+        let mut ages: Vec<i64> = [policy.ages.hot_days, policy.ages.warm_days, policy.horizon_days()]
+            .into_iter()
+            .flat_map(|boundary| i64::from(boundary)..i64::from(boundary) + TIER_CATCH_UP_DAYS)
+            .collect();
+        ages.sort_unstable();
+        ages.dedup();
+
+        for age in ages {
+            let partition = partition_key(today - Duration::days(age));
+            match (policy.tier_for(age), &archive) {
+                (Some(RetentionTier::Hot), _) => {}
+                (Some(RetentionTier::Warm), _) => {
+                    let Ok(data) = self.zfs_manager.read_data(&partition).await else {
+                        continue;
+                    };
+                    if retention::is_compressed(&data) {
+                        continue;
+                    }
+                    io_throttler.acquire(BackgroundJobClass::Retention, data.len() as u64).await;
+                    let data = policy.encode(data, RetentionTier::Warm)?;
+                    self.zfs_manager
+                        .write_data(&partition, &data)
+                        .await
+                        .map_err(|e| storage_error(format!("Failed to recompress partition {}", partition), Some(Box::new(e))))?;
+                    report.compressed += 1;
+                }
+                (Some(RetentionTier::Archive), Some(archive)) => {
+                    let Ok(data) = self.zfs_manager.read_data(&partition).await else {
+                        continue;
+                    };
+                    io_throttler.acquire(BackgroundJobClass::Retention, data.len() as u64).await;
+                    archive.put(&partition, policy.encode(data, RetentionTier::Archive)?).await?;
+                    self.zfs_manager
+                        .delete_data(&partition)
+                        .await
+                        .map_err(|e| storage_error(format!("Failed to remove archived partition {}", partition), Some(Box::new(e))))?;
+                    self.metrics_cache.write().await.pop(&partition);
+                    report.archived += 1;
+                }
+                (Some(RetentionTier::Archive), None) | (None, _) => {
+                    if let Ok(data) = self.zfs_manager.read_data(&partition).await {
+                        io_throttler.acquire(BackgroundJobClass::Retention, data.len() as u64).await;
+                        self.zfs_manager
+                            .delete_data(&partition)
+                            .await
+                            .map_err(|e| storage_error(format!("Failed to delete partition {}", partition), Some(Box::new(e))))?;
+                        report.deleted += 1;
+                    }
+                    if let Some(archive) = &archive {
+                        archive.delete(&partition).await?;
+                    }
+                    self.metrics_cache.write().await.pop(&partition);
+                }
+            }
+        }
+
+        counter!("guardian.storage.retention.moved", report.compressed as u64, "store" => "metrics", "tier" => "warm");
+        counter!("guardian.storage.retention.moved", report.archived as u64, "store" => "metrics", "tier" => "archive");
+        counter!("guardian.storage.retention.deleted", report.deleted as u64, "store" => "metrics");
+        info!(?report, "Metrics retention applied");
+        Ok(report)
+    }
+
     /// Stores metrics batch with compression and deduplication
     #[instrument(skip(self, metrics))]
     pub async fn store_metrics(&self, metrics: Vec<Metric>) -> Result<(), GuardianError> {
         if metrics.is_empty() {
             return Ok(());
         }
+        let count = metrics.len();
 
         // Group metrics by partition key (day)
-        let mut partitioned_metrics: HashMap<String, Vec<Metric>> = HashMap::new();
-        for metric in metrics {
-            let partition_key = format!(
-                "{}/{}",
-                METRICS_PARTITION_PREFIX,
-                metric.timestamp.format("%Y-%m-%d")
-            );
-            partitioned_metrics
-                .entry(partition_key)
-                .or_default()
-                .push(metric);
-        }
+        let mut pending = PendingMetrics::default();
+        pending.push(metrics);
 
         // Store metrics in batches
-        for (partition, metrics) in partitioned_metrics {
-            let compressed_data = {
-                let mut compressor = zstd::Encoder::new(Vec::new(), self.compression_level as i32)
-                    .map_err(|e| GuardianError::StorageError {
-                        context: "Failed to create compression encoder".into(),
-                        source: Some(Box::new(e)),
-                        severity: crate::utils::error::ErrorSeverity::High,
-                        timestamp: time::OffsetDateTime::now_utc(),
-                        correlation_id: uuid::Uuid::new_v4(),
-                        category: ErrorCategory::Storage,
-                        retry_count: 0,
-                    })?;
-                serde_json::to_writer(&mut compressor, &metrics).map_err(|e| {
-                    GuardianError::StorageError {
-                        context: "Failed to serialize metrics".into(),
-                        source: Some(Box::new(e)),
-                        severity: crate::utils::error::ErrorSeverity::High,
-                        timestamp: time::OffsetDateTime::now_utc(),
-                        correlation_id: uuid::Uuid::new_v4(),
-                        category: ErrorCategory::Storage,
-                        retry_count: 0,
+        for (partition, metrics) in pending.take() {
+            self.write_partition(partition, metrics).await?;
+        }
+
+        debug!("Successfully stored {} metrics", count);
+        Ok(())
+    }
+
+    /// Starts a background flusher that coalesces ingested metrics into one write per partition
+    pub fn start_ingestion(&self, config: IngestConfig) -> MetricsIngester {
+        let (tx, rx) = mpsc::channel(config.channel_capacity.max(1));
+        let weak = tx.downgrade();
+        let queue = queue_registry().register(
+            QueueMonitor::new("metrics_ingest", config.channel_capacity.max(1))
+                .fifo(move || weak.upgrade().map(|tx| tx.max_capacity() - tx.capacity())),
+        );
+        tokio::spawn(self.clone().run_flusher(rx, config));
+        MetricsIngester { tx, queue }
+    }
+
+    async fn run_flusher(self, mut rx: mpsc::Receiver<IngestCommand>, config: IngestConfig) {
+        let mut pending = PendingMetrics::default();
+        let mut interval = tokio::time::interval(config.flush_interval);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            tokio::select! {
+                command = rx.recv() => match command {
+                    Some(IngestCommand::Metrics(metrics)) => {
+                        pending.push(metrics);
+                        if pending.len >= config.flush_size {
+                            let _ = self.flush_pending(&mut pending, &config).await;
+                        }
+                    }
+                    Some(IngestCommand::Flush(done)) => {
+                        let _ = done.send(self.flush_pending(&mut pending, &config).await);
                     }
-                })?;
-                compressor.finish().map_err(|e| GuardianError::StorageError {
-                    context: "Failed to finish compression".into(),
-                    source: Some(Box::new(e)),
-                    severity: crate::utils::error::ErrorSeverity::High,
-                    timestamp: time::OffsetDateTime::now_utc(),
-                    correlation_id: uuid::Uuid::new_v4(),
-                    category: ErrorCategory::Storage,
-                    retry_count: 0,
-                })?
-            };
-
-            // Write compressed batch to ZFS
-            self.zfs_manager
-                .write_data(&partition, &compressed_data)
-                .await
-                .map_err(|e| GuardianError::StorageError {
-                    context: format!("Failed to write metrics to partition {}", partition),
-                    source: Some(Box::new(e)),
-                    severity: crate::utils::error::ErrorSeverity::High,
-                    timestamp: time::OffsetDateTime::now_utc(),
-                    correlation_id: uuid::Uuid::new_v4(),
-                    category: ErrorCategory::Storage,
-                    retry_count: 0,
-                })?;
-
-            // Update cache
-            let mut cache = self.metrics_cache.write().await;
-            cache.put(partition, metrics);
+                    None => {
+                        // All ingesters dropped; write what is left and stop
+                        let _ = self.flush_pending(&mut pending, &config).await;
+                        info!("Metrics flusher stopped");
+                        return;
+                    }
+                },
+                _ = interval.tick() => {
+                    let _ = self.flush_pending(&mut pending, &config).await;
+                }
+            }
+            gauge!("guardian.storage.metrics.buffered", pending.len as f64);
+        }
+    }
+
+    /// Writes every buffered partition, keeping failed partitions for the next flush
+    async fn flush_pending(&self, pending: &mut PendingMetrics, config: &IngestConfig) -> Result<usize, GuardianError> {
+        if pending.len == 0 {
+            return Ok(0);
+        }
+        let start = Instant::now();
+        let mut written = 0;
+        let mut first_error = None;
+
+        for (partition, metrics) in pending.take() {
+            let count = metrics.len();
+            match self.write_partition(partition.clone(), metrics.clone()).await {
+                Ok(()) => written += count,
+                Err(e) => {
+                    error!(partition = %partition, error = %e, "Failed to flush metrics");
+                    pending.restore(partition, metrics, config.max_buffered);
+                    first_error.get_or_insert(e);
+                }
+            }
         }
 
-        debug!("Successfully stored {} metrics", metrics.len());
+        counter!("guardian.storage.metrics.flushed", written as u64);
+        histogram!("guardian.storage.metrics.flush_duration", start.elapsed().as_secs_f64());
+        match first_error {
+            Some(e) => Err(e),
+            None => Ok(written),
+        }
+    }
+
+    /// Encodes one partition's metrics for its tier and writes them
+    async fn write_partition(&self, partition: String, metrics: Vec<Metric>) -> Result<(), GuardianError> {
+        let governor = governor();
+        let _permit = governor.acquire(Subsystem::Storage).await;
+        let data = {
+            let policy = self.tiering.read().await.policy.clone();
+            // Late metrics for a partition already past the warm tier are written warm
+            let tier = partition_age(&partition, Utc::now().date_naive())
+                .and_then(|age| policy.tier_for(age))
+                .unwrap_or(RetentionTier::Warm)
+                .min(RetentionTier::Warm);
+            let json = serde_json::to_vec(&metrics)
+                .map_err(|e| storage_error("Failed to serialize metrics".into(), Some(Box::new(e))))?;
+            policy.encode(json, tier)?
+        };
+
+        // Write the batch to ZFS, yielding to model reads while they are slow
+        self.zfs_manager.io_throttler().acquire_write(WriteClass::Metrics, data.len() as u64).await;
+        self.zfs_manager
+            .write_data(&partition, &data)
+            .await
+            .map_err(|e| storage_error(format!("Failed to write metrics to partition {}", partition), Some(Box::new(e))))?;
+
+        // Update cache, kept within the governor's capacity
+        let mut cache = self.metrics_cache.write().await;
+        let capacity = governor.cache_capacity(Subsystem::Storage, MAX_CACHE_SIZE);
+        if cache.cap() != capacity {
+            cache.resize(capacity);
+        }
+        cache.put(partition, metrics);
+        let cached: usize = cache.iter().map(|(_, metrics)| metrics.len()).sum();
+        governor.report_memory(Subsystem::Storage, (cached * std::mem::size_of::<Metric>()) as u64);
         Ok(())
     }
 
@@ -180,11 +538,13 @@ impl MetricsStore {
             current_date = current_date + chrono::Duration::days(1);
         }
 
-        // Read metrics from each partition in parallel
+        // Read metrics from each partition in parallel, from the pool or the archive
+        let archive = self.tiering.read().await.archive.clone();
         let mut tasks = Vec::new();
         for partition_key in partition_keys {
             let zfs_manager = Arc::clone(&self.zfs_manager);
             let cache = Arc::clone(&self.metrics_cache);
+            let archive = archive.clone();
             let task = tokio::spawn(async move {
                 // Check cache first
                 let cache_read = cache.read().await;
@@ -193,26 +553,22 @@ impl MetricsStore {
                 }
                 drop(cache_read);
 
-                // Read from ZFS if not in cache
-                let compressed_data = zfs_manager.read_data(&partition_key).await?;
+                // Read from ZFS if not in cache, falling back to the archive for old partitions
+                let stored = match zfs_manager.read_data(&partition_key).await {
+                    Ok(data) => data,
+                    Err(e) => match &archive {
+                        Some(archive) => archive.get(&partition_key).await?.ok_or(e)?,
+                        None => return Err(e),
+                    },
+                };
                 let metrics: Vec<Metric> = {
-                    let decoder = zstd::Decoder::new(&compressed_data[..]).map_err(|e| {
-                        GuardianError::StorageError {
-                            context: "Failed to create decompression decoder".into(),
-                            source: Some(Box::new(e)),
-                            severity: crate::utils::error::ErrorSeverity::High,
-                            timestamp: time::OffsetDateTime::now_utc(),
-                            correlation_id: uuid::Uuid::new_v4(),
-                            category: ErrorCategory::Storage,
-                            retry_count: 0,
-                        }
-                    })?;
-                    serde_json::from_reader(decoder).map_err(|e| GuardianError::StorageError {
+                    let data = retention::decode(stored)?;
+                    serde_json::from_slice(&data).map_err(|e| GuardianError::StorageError {
                         context: "Failed to deserialize metrics".into(),
                         source: Some(Box::new(e)),
                         severity: crate::utils::error::ErrorSeverity::High,
                         timestamp: time::OffsetDateTime::now_utc(),
-                        correlation_id: uuid::Uuid::new_v4(),
+                        correlation_id: crate::utils::correlation::current_or_new(),
                         category: ErrorCategory::Storage,
                         retry_count: 0,
                     })?
@@ -263,20 +619,21 @@ impl Clone for MetricsStore {
             batch_size: self.batch_size,
             compression_level: self.compression_level,
             metrics_cache: Arc::clone(&self.metrics_cache),
+            tiering: Arc::clone(&self.tiering),
         }
     }
 }
 
-/// Removes expired metrics with integrity verification
-#[instrument]
-async fn cleanup_old_metrics(retention_days: u32) -> Result<(), GuardianError> {
-    let cutoff_date = Utc::now() - chrono::Duration::days(retention_days as i64);
-    info!("Cleaning up metrics older than {}", cutoff_date);
-
-    // Implementation would delete old partitions using ZFS manager
-    // Omitted for brevity as it depends on ZFS manager implementation
-
-    Ok(())
+fn storage_error(context: String, source: Option<Box<dyn std::error::Error + Send + Sync>>) -> GuardianError {
+    GuardianError::StorageError {
+        context,
+        source,
+        severity: crate::utils::error::ErrorSeverity::High,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: ErrorCategory::Storage,
+        retry_count: 0,
+    }
 }
 
 #[cfg(test)]
@@ -313,4 +670,24 @@ mod tests {
         // Test storing metrics
         assert!(store.store_metrics(metrics).await.is_ok());
     }
+
+    #[test]
+    fn test_pending_metrics_coalesce_per_partition() {
+        let mut yesterday = Metric::new("cpu", 0.5, MetricType::Gauge);
+        yesterday.timestamp = yesterday.timestamp - chrono::Duration::days(1);
+
+        let mut pending = PendingMetrics::default();
+        pending.push(vec![Metric::new("cpu", 0.4, MetricType::Gauge), yesterday.clone()]);
+        pending.push(vec![Metric::new("mem", 0.7, MetricType::Gauge)]);
+        assert_eq!(pending.len, 3);
+
+        let partitions = pending.take();
+        assert_eq!(partitions.len(), 2);
+        assert_eq!(partitions[&Metric::new("cpu", 0.0, MetricType::Gauge).partition_key()].len(), 2);
+        assert_eq!(pending.len, 0);
+
+        // Failed partitions are re-queued up to the buffer limit, keeping the newest
+        pending.restore(yesterday.partition_key(), vec![yesterday.clone(), yesterday], 1);
+        assert_eq!(pending.len, 1);
+    }
 }
\ No newline at end of file
@@ -5,24 +5,61 @@ use tracing::{debug, error, info, instrument, warn}; // v0.1
 use std::{sync::Arc, time::Duration};
 
 use crate::utils::error::{GuardianError, Result};
+use crate::utils::logging::LogManager;
 use crate::config::storage_config::StorageConfig;
+use crate::core::event_bus::EventBus;
+use crate::core::guardian::TenantContext;
+use crate::security::key_provider::KeyProvider;
 
 // Constants for storage configuration
 const STORAGE_VERSION: &str = "1.0";
 const DEFAULT_ZFS_POOL: &str = "guardian_pool";
 const ENCRYPTION_ALGORITHM: &str = "AES-256-GCM";
-const KEY_ROTATION_INTERVAL: Duration = Duration::from_secs(86400); // 24 hours
+const METRICS_BATCH_SIZE: usize = 1000;
 
 // Re-export storage components
 mod metrics_store;
 mod event_store;
 mod model_store;
+mod model_mmap;
+mod model_patch;
+mod object_store;
 mod zfs_manager;
-
-pub use metrics_store::MetricsStore;
-pub use event_store::EventStore;
-pub use model_store::ModelStore;
-pub use zfs_manager::ZFSManager;
+mod forensics;
+mod forensic_bundle;
+mod io_budget;
+mod io_throttle;
+mod maintenance;
+mod quota;
+mod retention;
+mod response_wal;
+mod write_coalescer;
+
+pub use metrics_store::{IngestConfig, Metric, MetricsIngester, MetricsQuery, MetricsStore};
+pub use event_store::{EventArchive, EventStore};
+pub use event_store::{Event, PartitionSpan, EVENT_SCHEMA_VERSION};
+pub use model_store::{ModelStore, SyncDirection, SyncReport, VersionIndexEntry, VersionLabel, VersionPage, VersionQuery};
+pub use model_mmap::{ModelBytes, PageInStats};
+pub use object_store::ObjectStoreBackend;
+pub use model_patch::{ModelPatch, PatchFormat};
+pub use zfs_manager::{
+    BackupManifest, BackupOptions, BackupTarget, ChildDataset, DatasetIoStats, DatasetUsage, PoolStatus, ScanStatus, VdevState,
+    VdevStatus, ZFSManager, BACKUP_MANIFEST_VERSION,
+};
+pub use forensic_bundle::{BundleSummary, BundleWriter};
+pub use forensics::{
+    ForensicClone, ForensicExport, ForensicManager, ForensicRequest, DEFAULT_FORENSIC_TTL,
+    FORENSIC_REAP_INTERVAL,
+};
+pub use io_budget::IoBudget;
+pub use io_throttle::IoThrottler;
+pub use maintenance::{pool_reports, StorageMaintenance};
+pub use quota::{OldestPartitionCleanup, QuotaCleanup, QuotaMonitor, UsageLevel};
+pub use retention::{
+    archive_store, ArchiveStore, DatasetArchive, ObjectStoreArchive, RetentionReport, RetentionTier, TierPolicy,
+};
+pub use response_wal::{ResponseWal, WalIntent, DEFAULT_RESPONSE_WAL_PATH};
+pub use write_coalescer::{WriteCoalescer, WritePriority};
 
 /// Storage trait defining common operations for all storage types
 #[async_trait]
@@ -155,35 +192,83 @@ impl ModelStore {
     }
 }
 
-/// Initialize storage subsystems with HSM integration and retention policies
-#[instrument(skip(config))]
-pub async fn init_storage(config: StorageConfig) -> Result<()> {
-    info!("Initializing storage subsystems v{}", STORAGE_VERSION);
+/// Event store opened by `init_storage`, shared with components built outside the daemon's startup
+static EVENT_STORE: once_cell::sync::OnceCell<Arc<event_store::EventStore>> = once_cell::sync::OnceCell::new();
 
-    // Create ZFS manager
-    let zfs_manager = Arc::new(ZFSManager::new(config.clone())?);
+/// Returns the daemon's event store, once storage has been initialized
+pub fn event_store() -> Option<Arc<event_store::EventStore>> {
+    EVENT_STORE.get().cloned()
+}
 
-    // Initialize encryption with HSM if configured
-    if config.get_encryption_settings().hsm_integration {
-        debug!("Initializing HSM integration");
-        zfs_manager.manage_encryption("init").await?;
-    }
+/// Storage components shared across the daemon, and the background tasks keeping them in check
+pub struct StorageRuntime {
+    pub zfs: Arc<zfs_manager::ZfsManager>,
+    pub events: Arc<event_store::EventStore>,
+    tasks: Vec<tokio::task::JoinHandle<()>>,
+}
 
-    // Create storage datasets
-    let pool = config.get_zfs_pool_name();
-    for dataset in ["metrics", "events", "models"] {
-        let path = format!("{}/{}", pool, dataset);
-        zfs_manager.create_dataset(&path).await?;
+impl StorageRuntime {
+    /// Stops the storage background tasks
+    pub fn shutdown(&self) {
+        for task in &self.tasks {
+            task.abort();
+        }
     }
+}
 
-    // Verify encryption status
-    if let Err(e) = zfs_manager.manage_encryption("verify").await {
-        error!("Encryption verification failed: {}", e);
-        return Err(GuardianError::StorageError("Encryption verification failed".into()));
+/// Opens the pool with the configured background I/O limits and write budget, inside the
+/// tenant's dataset subtree, opens the event store, and starts the storage background tasks,
+/// quota monitoring, metrics persistence and forensic clone expiry included. Every store built
+/// on the returned manager shares one throttler.
+#[instrument(skip(config, provider, logger, event_bus), fields(tenant = %tenant.tenant_id()))]
+pub async fn init_storage(
+    config: &StorageConfig,
+    provider: &dyn KeyProvider,
+    logger: Arc<LogManager>,
+    event_bus: Arc<EventBus>,
+    tenant: TenantContext,
+) -> Result<StorageRuntime> {
+    info!("Initializing storage subsystems v{}", STORAGE_VERSION);
+    config.validate()?;
+    // Staging and production refuse unencrypted datasets
+    crate::config::active_profile().require_encryption("storage datasets", config.encryption_enabled)?;
+
+    let zfs = Arc::new(
+        zfs_manager::ZfsManager::from_key_provider(config.zfs_pool_name.clone(), provider, logger, None)
+            .await?
+            .with_tenant(tenant)
+            .await?
+            .with_io_config(config),
+    );
+    let hsm = hsm_client::HSMClient::new()
+        .map_err(|e| GuardianError::StorageError(format!("Failed to open HSM session for event keys: {}", e)))?;
+    let events = Arc::new(event_store::EventStore::new(Arc::clone(&zfs), Arc::new(hsm)).await?);
+    let events = Arc::clone(EVENT_STORE.get_or_init(|| events));
+
+    let mut tasks = Vec::new();
+    // Slow model reads hold back metrics and event writes on the shared pool
+    if config.io_budget.enabled {
+        tasks.push(zfs.io_budget().start(Arc::clone(&zfs)));
+    }
+    // Quotas are applied up front, then usage is watched so cleanup runs before the hard limit
+    if config.dataset_quotas.enabled {
+        let quotas = QuotaMonitor::new(Arc::clone(&zfs), event_bus, config.dataset_quotas.clone());
+        tasks.push(Arc::new(quotas).start());
     }
+    // Collector snapshots are persisted through the buffered ingester, one write per partition
+    let metrics = metrics_store::MetricsStore::new(
+        Arc::clone(&zfs),
+        config.retention_tiers.metrics.warm_days,
+        METRICS_BATCH_SIZE,
+        config.compression_level.min(u32::from(u8::MAX)) as u8,
+    )
+    .await?;
+    tasks.push(metrics.start_ingestion(metrics_store::IngestConfig::default()).record_snapshots());
+    // Expired forensic clones are destroyed so they stop pinning their origin snapshots
+    tasks.push(Arc::new(ForensicManager::new(Arc::clone(&zfs))).spawn_reaper(FORENSIC_REAP_INTERVAL));
 
     info!("Storage subsystems initialized successfully");
-    Ok(())
+    Ok(StorageRuntime { zfs, events, tasks })
 }
 
 #[cfg(test)]
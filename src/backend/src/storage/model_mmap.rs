@@ -0,0 +1,198 @@
+//! Memory-mapped model binaries
+//!
+//! A model version is read through a private, read-only mapping of its `model.bin` instead of
+//! into a heap buffer, so a loaded model costs page cache rather than RSS and a hot-swap never
+//! holds two private copies. Pages fault in on first use; latency-sensitive deployments
+//! prefetch a version before it takes traffic. Version files are replaced by rename and never
+//! rewritten in place, so a mapping keeps the binary it was opened on.
+
+use std::{
+    fmt,
+    fs::File,
+    ops::Deref,
+    path::Path,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Instant,
+};
+
+use memmap2::{Advice, Mmap, MmapOptions};
+use metrics::{counter, gauge, histogram};
+use serde::Serialize;
+use tracing::debug;
+
+/// Stride of the prefetch walk; larger pages are touched more than once, which is harmless
+const PAGE_SIZE: usize = 4096;
+
+static MAPPED_BYTES: AtomicU64 = AtomicU64::new(0);
+
+/// What prefetching a model paged in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct PageInStats {
+    pub bytes: u64,
+    pub pages: u64,
+    /// Major faults of the whole process during the walk, so an upper bound for this model
+    pub major_faults: u64,
+    pub duration_ms: f64,
+}
+
+enum Backing {
+    Owned(Vec<u8>),
+    Mapped(Mmap),
+}
+
+impl Drop for Backing {
+    fn drop(&mut self) {
+        if let Backing::Mapped(mmap) = self {
+            let mapped = MAPPED_BYTES.fetch_sub(mmap.len() as u64, Ordering::Relaxed) - mmap.len() as u64;
+            gauge!("guardian.storage.model_mmap.mapped_bytes", mapped as f64);
+        }
+    }
+}
+
+/// Bytes of a model binary, mapped from disk or held in memory; clones share them
+#[derive(Clone)]
+pub struct ModelBytes(Arc<Backing>);
+
+impl ModelBytes {
+    /// Maps a model file copy-on-write: the process sees the file as it was opened and can
+    /// never write through to it
+    pub fn map(path: &Path) -> std::io::Result<Self> {
+        let file = File::open(path)?;
+        // Empty files cannot be mapped on every platform and gain nothing from it
+        if file.metadata()?.len() == 0 {
+            return Ok(Self::from(Vec::new()));
+        }
+        // SAFETY: version files are only ever replaced by rename, never truncated or rewritten,
+        // and the mapping is private, so the bytes cannot change underneath the slice
+        let mmap = unsafe { MmapOptions::new().map_copy_read_only(&file)? };
+        let mapped = MAPPED_BYTES.fetch_add(mmap.len() as u64, Ordering::Relaxed) + mmap.len() as u64;
+        gauge!("guardian.storage.model_mmap.mapped_bytes", mapped as f64);
+        Ok(Self(Arc::new(Backing::Mapped(mmap))))
+    }
+
+    pub fn is_mapped(&self) -> bool {
+        matches!(*self.0, Backing::Mapped(_))
+    }
+
+    /// An owned buffer, taken without copying when this is the only holder of an in-memory
+    /// model and copied otherwise
+    pub fn into_owned(self) -> Vec<u8> {
+        match Arc::try_unwrap(self.0) {
+            Ok(mut backing) => match &mut backing {
+                Backing::Owned(data) => std::mem::take(data),
+                Backing::Mapped(mmap) => mmap.to_vec(),
+            },
+            Err(shared) => backing_slice(&shared).to_vec(),
+        }
+    }
+
+    /// Faults every page of a mapped model in now; blocks, so run it off the async workers
+    pub fn prefetch(&self) -> PageInStats {
+        let Backing::Mapped(mmap) = &*self.0 else {
+            return PageInStats::default();
+        };
+        let start = Instant::now();
+        let faults_before = major_faults();
+        if let Err(e) = mmap.advise(Advice::WillNeed) {
+            debug!(error = %e, "madvise(WILLNEED) failed, touching pages only");
+        }
+        let mut checksum = 0u8;
+        for offset in (0..mmap.len()).step_by(PAGE_SIZE) {
+            checksum ^= std::hint::black_box(mmap[offset]);
+        }
+        std::hint::black_box(checksum);
+
+        let stats = PageInStats {
+            bytes: mmap.len() as u64,
+            pages: mmap.len().div_ceil(PAGE_SIZE) as u64,
+            major_faults: major_faults().saturating_sub(faults_before),
+            duration_ms: start.elapsed().as_secs_f64() * 1000.0,
+        };
+        counter!("guardian.storage.model_mmap.prefetched_bytes", stats.bytes);
+        counter!("guardian.storage.model_mmap.major_faults", stats.major_faults);
+        histogram!("guardian.storage.model_mmap.prefetch_duration", start.elapsed().as_secs_f64());
+        stats
+    }
+}
+
+impl From<Vec<u8>> for ModelBytes {
+    fn from(data: Vec<u8>) -> Self {
+        Self(Arc::new(Backing::Owned(data)))
+    }
+}
+
+impl Deref for ModelBytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        backing_slice(&self.0)
+    }
+}
+
+impl AsRef<[u8]> for ModelBytes {
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+impl fmt::Debug for ModelBytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ModelBytes")
+            .field("len", &self.len())
+            .field("mapped", &self.is_mapped())
+            .finish()
+    }
+}
+
+fn backing_slice(backing: &Backing) -> &[u8] {
+    match backing {
+        Backing::Owned(data) => data,
+        Backing::Mapped(mmap) => mmap,
+    }
+}
+
+/// Major page faults of this process so far, from `/proc/self/stat`; zero where unavailable
+fn major_faults() -> u64 {
+    std::fs::read_to_string("/proc/self/stat")
+        .ok()
+        .and_then(|stat| {
+            // Fields after the parenthesised command name start at the state, field 3; majflt is field 12
+            let rest = &stat[stat.rfind(')')? + 1..];
+            rest.split_whitespace().nth(9)?.parse().ok()
+        })
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mapped_model_reads_prefetches_and_copies_on_write() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("model.bin");
+        let data: Vec<u8> = (0..3 * PAGE_SIZE + 10).map(|i| (i % 251) as u8).collect();
+        std::fs::write(&path, &data).unwrap();
+
+        let model = ModelBytes::map(&path).unwrap();
+        assert!(model.is_mapped());
+        assert_eq!(&model[..], &data[..]);
+
+        let stats = model.prefetch();
+        assert_eq!((stats.bytes, stats.pages), (data.len() as u64, 4));
+
+        // Replacing the file by rename leaves the mapping on the old binary
+        let replacement = dir.path().join("model.bin.tmp");
+        std::fs::write(&replacement, b"new").unwrap();
+        std::fs::rename(&replacement, &path).unwrap();
+        let mut owned = model.clone().into_owned();
+        owned[0] ^= 0xff;
+        assert_eq!(model[0], data[0]);
+
+        assert!(ModelBytes::map(&dir.path().join("missing")).is_err());
+        assert_eq!(ModelBytes::from(vec![1, 2]).into_owned(), vec![1, 2]);
+    }
+}
@@ -0,0 +1,184 @@
+use std::io::Cursor;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use zstd::stream::raw::{CParameter, DParameter};
+
+use crate::utils::error::{ErrorCategory, GuardianError};
+
+// Constants for model patches
+const ZSTD_PATCH_LEVEL: i32 = 19;
+const MIN_WINDOW_LOG: u32 = 10;
+/// Largest window zstd decoders accept by default on 64-bit hosts, enough for 1GB models
+const MAX_WINDOW_LOG: u32 = 30;
+
+/// Binary diff algorithm a model patch was produced with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PatchFormat {
+    /// bsdiff 4 control, diff and extra blocks
+    Bsdiff,
+    /// zstd frame compressed with the base version as a raw content dictionary
+    Zstd,
+}
+
+impl std::str::FromStr for PatchFormat {
+    type Err = GuardianError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bsdiff" => Ok(PatchFormat::Bsdiff),
+            "zstd" => Ok(PatchFormat::Zstd),
+            other => Err(patch_error(format!("Unknown patch format: {}", other), None)),
+        }
+    }
+}
+
+/// Difference between two model versions, shipped instead of the full model binary
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelPatch {
+    pub format: PatchFormat,
+    pub base_version: String,
+    /// SHA-256 of the base version the patch applies to
+    pub base_hash: String,
+    /// SHA-256 the reconstructed model must have
+    pub target_hash: String,
+    pub target_size: u64,
+    pub data: Vec<u8>,
+}
+
+impl ModelPatch {
+    /// Diffs `target` against the `base_version` model `base`
+    pub fn create(format: PatchFormat, base_version: &str, base: &[u8], target: &[u8]) -> Result<Self, GuardianError> {
+        let data = match format {
+            PatchFormat::Bsdiff => {
+                let mut patch = Vec::new();
+                qbsdiff::Bsdiff::new(base, target)
+                    .compare(Cursor::new(&mut patch))
+                    .map_err(|e| patch_error("Failed to compute bsdiff patch".into(), Some(Box::new(e))))?;
+                patch
+            }
+            PatchFormat::Zstd => {
+                let mut compressor = zstd::bulk::Compressor::with_dictionary(ZSTD_PATCH_LEVEL, base)
+                    .map_err(|e| patch_error("Failed to load zstd patch base".into(), Some(Box::new(e))))?;
+                compressor
+                    .set_parameter(CParameter::WindowLog(window_log(base.len().max(target.len()))))
+                    .and_then(|_| compressor.set_parameter(CParameter::EnableLongDistanceMatching(true)))
+                    .map_err(|e| patch_error("Failed to configure zstd patch".into(), Some(Box::new(e))))?;
+                compressor
+                    .compress(target)
+                    .map_err(|e| patch_error("Failed to compute zstd patch".into(), Some(Box::new(e))))?
+            }
+        };
+
+        Ok(Self {
+            format,
+            base_version: base_version.to_string(),
+            base_hash: sha256_hex(base),
+            target_hash: sha256_hex(target),
+            target_size: target.len() as u64,
+            data,
+        })
+    }
+
+    /// Rebuilds the target model from `base`, verifying both ends against the patch's hashes
+    pub fn apply(&self, base: &[u8], max_size: u64) -> Result<Vec<u8>, GuardianError> {
+        if sha256_hex(base) != self.base_hash {
+            return Err(patch_error(
+                format!("Patch does not apply: base version {} has a different hash", self.base_version),
+                None,
+            ));
+        }
+        if self.target_size > max_size {
+            return Err(patch_error(
+                format!("Patched model of {} bytes exceeds maximum of {} bytes", self.target_size, max_size),
+                None,
+            ));
+        }
+
+        let target = match self.format {
+            PatchFormat::Bsdiff => {
+                let mut target = Vec::with_capacity(self.target_size as usize);
+                qbsdiff::Bspatch::new(&self.data)
+                    .and_then(|patcher| patcher.apply(base, Cursor::new(&mut target)))
+                    .map_err(|e| patch_error("Failed to apply bsdiff patch".into(), Some(Box::new(e))))?;
+                target
+            }
+            PatchFormat::Zstd => {
+                let mut decompressor = zstd::bulk::Decompressor::with_dictionary(base)
+                    .map_err(|e| patch_error("Failed to load zstd patch base".into(), Some(Box::new(e))))?;
+                decompressor
+                    .set_parameter(DParameter::WindowLogMax(MAX_WINDOW_LOG))
+                    .map_err(|e| patch_error("Failed to configure zstd patch".into(), Some(Box::new(e))))?;
+                decompressor
+                    .decompress(&self.data, self.target_size as usize)
+                    .map_err(|e| patch_error("Failed to apply zstd patch".into(), Some(Box::new(e))))?
+            }
+        };
+
+        if target.len() as u64 != self.target_size || sha256_hex(&target) != self.target_hash {
+            return Err(patch_error(
+                "Patched model does not match the expected hash".into(),
+                None,
+            ));
+        }
+        Ok(target)
+    }
+}
+
+/// Hex-encoded SHA-256, the form model versions record their hash in
+pub(super) fn sha256_hex(data: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(data))
+}
+
+/// Smallest zstd window covering `len` bytes, so matches can reach anywhere in the base
+fn window_log(len: usize) -> u32 {
+    let bits = usize::BITS - len.saturating_sub(1).leading_zeros();
+    bits.clamp(MIN_WINDOW_LOG, MAX_WINDOW_LOG)
+}
+
+fn patch_error(context: String, source: Option<Box<dyn std::error::Error + Send + Sync>>) -> GuardianError {
+    GuardianError::StorageError {
+        context,
+        source,
+        severity: crate::utils::error::ErrorSeverity::High,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: ErrorCategory::Storage,
+        retry_count: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn versions() -> (Vec<u8>, Vec<u8>) {
+        let base: Vec<u8> = (0..64 * 1024u32).map(|i| (i % 251) as u8).collect();
+        let mut target = base.clone();
+        target[1000..1016].copy_from_slice(&[0xAB; 16]);
+        target.extend_from_slice(b"new layer");
+        (base, target)
+    }
+
+    #[test]
+    fn test_patch_round_trip() {
+        let (base, target) = versions();
+        for format in [PatchFormat::Bsdiff, PatchFormat::Zstd] {
+            let patch = ModelPatch::create(format, "v1.0.0", &base, &target).unwrap();
+            assert!(patch.data.len() < target.len() / 4, "{:?} patch should be small", format);
+            assert_eq!(patch.apply(&base, u64::MAX).unwrap(), target);
+        }
+    }
+
+    #[test]
+    fn test_patch_rejects_wrong_base_and_corruption() {
+        let (base, target) = versions();
+        let mut patch = ModelPatch::create(PatchFormat::Bsdiff, "v1.0.0", &base, &target).unwrap();
+        assert!(patch.apply(&target, u64::MAX).is_err());
+        assert!(patch.apply(&base, 1024).is_err());
+
+        patch.target_hash = sha256_hex(b"something else");
+        assert!(patch.apply(&base, u64::MAX).is_err());
+    }
+}
@@ -1,7 +1,9 @@
 use std::{
+    collections::BTreeMap,
     path::PathBuf,
     sync::Arc,
 };
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
 use tokio::sync::RwLock;
 use async_trait::async_trait;
 use serde::{Serialize, Deserialize};
@@ -10,7 +12,11 @@ use sha2::{Sha256, Digest};
 use lru::LruCache;
 use tracing::{info, warn, error, instrument};
 
+use crate::config::ModelLoadingConfig;
 use crate::utils::error::{GuardianError, ErrorCategory};
+use crate::storage::model_mmap::{ModelBytes, PageInStats};
+use crate::storage::model_patch::ModelPatch;
+use crate::storage::object_store::{file_sha256, ObjectStoreBackend};
 use crate::storage::zfs_manager::ZfsManager;
 
 // Constants for model storage configuration
@@ -19,6 +25,13 @@ const VERSION_INDEX_FILE: &str = "version_index.json";
 const MAX_MODEL_SIZE: u64 = 1024 * 1024 * 1024; // 1GB
 const VERSION_REGEX: &str = r"^v\d+\.\d+\.\d+$";
 const DEFAULT_CACHE_SIZE: usize = 5;
+const SIGNATURE_FILE: &str = "model.sig";
+const PROVENANCE_FILE: &str = "provenance.json";
+const METADATA_FILE: &str = "metadata.json";
+const DEFAULT_PAGE_SIZE: usize = 50;
+const MAX_PAGE_SIZE: usize = 500;
+/// Status of versions the registry has not labelled yet
+const UNLABELED_STATUS: &str = "inactive";
 
 /// Metadata for stored ML model versions
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -30,13 +43,114 @@ pub struct ModelVersion {
     pub compression_ratio: f64,
 }
 
+/// Version index entry, so listings never read every version's metadata file
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct VersionIndexEntry {
+    pub version: String,
+    /// Model name, as labelled by the registry
+    pub name: String,
+    /// Lifecycle status, as labelled by the registry
+    pub status: String,
+    pub created_at: DateTime<Utc>,
+    pub hash: String,
+    pub size: u64,
+    #[serde(default)]
+    pub compression_ratio: f64,
+}
+
+impl VersionIndexEntry {
+    fn from_version(version: &ModelVersion) -> Self {
+        Self {
+            version: version.version.clone(),
+            name: version.version.clone(),
+            status: UNLABELED_STATUS.into(),
+            created_at: version.created_at,
+            hash: version.hash.clone(),
+            size: version.size,
+            compression_ratio: version.compression_ratio,
+        }
+    }
+
+    fn to_version(&self) -> ModelVersion {
+        ModelVersion {
+            version: self.version.clone(),
+            created_at: self.created_at,
+            hash: self.hash.clone(),
+            size: self.size,
+            compression_ratio: self.compression_ratio,
+        }
+    }
+}
+
+/// Filters and page position of a version listing
+#[derive(Debug, Clone, Default)]
+pub struct VersionQuery {
+    pub status: Option<String>,
+    pub name_prefix: Option<String>,
+    /// Inclusive lower bound on creation time
+    pub created_after: Option<DateTime<Utc>>,
+    /// Exclusive upper bound on creation time
+    pub created_before: Option<DateTime<Utc>>,
+    /// Zero selects the default page size
+    pub page_size: usize,
+    /// `next_page_token` of the previous page
+    pub page_token: Option<String>,
+}
+
+impl VersionQuery {
+    fn matches(&self, entry: &VersionIndexEntry) -> bool {
+        self.status.as_deref().map_or(true, |s| entry.status.eq_ignore_ascii_case(s))
+            && self.name_prefix.as_deref().map_or(true, |p| entry.name.starts_with(p))
+            && self.created_after.map_or(true, |t| entry.created_at >= t)
+            && self.created_before.map_or(true, |t| entry.created_at < t)
+    }
+}
+
+/// One page of a version listing, newest first
+#[derive(Debug, Clone, Default)]
+pub struct VersionPage {
+    pub entries: Vec<VersionIndexEntry>,
+    /// Set when more versions match; pass back as `page_token`
+    pub next_page_token: Option<String>,
+}
+
+/// Registry labels applied to an indexed version
+#[derive(Debug, Clone)]
+pub struct VersionLabel {
+    pub version: String,
+    pub name: String,
+    pub status: String,
+}
+
+/// Which way `ModelStore::sync` copies versions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncDirection {
+    /// Publish local versions the remote lacks
+    Push,
+    /// Fetch remote versions the local registry lacks
+    Pull,
+    Both,
+}
+
+/// Versions one sync copied, and those it left alone because both sides differ
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct SyncReport {
+    pub pushed: Vec<String>,
+    pub pulled: Vec<String>,
+    /// Versions present on both sides with different content; never overwritten
+    pub conflicts: Vec<String>,
+}
+
 /// Manages secure storage and versioning of ML models
 #[derive(Debug)]
 #[async_trait]
 pub struct ModelStore {
     zfs_manager: Arc<ZfsManager>,
     base_path: PathBuf,
-    model_cache: Arc<RwLock<LruCache<String, Vec<u8>>>>,
+    model_cache: Arc<RwLock<LruCache<String, ModelBytes>>>,
+    version_index: RwLock<BTreeMap<String, VersionIndexEntry>>,
+    remote: Option<Arc<ObjectStoreBackend>>,
+    loading: ModelLoadingConfig,
 }
 
 impl ModelStore {
@@ -61,16 +175,41 @@ impl ModelStore {
             source: Some(Box::new(e)),
             severity: crate::utils::error::ErrorSeverity::Critical,
             timestamp: time::OffsetDateTime::now_utc(),
-            correlation_id: uuid::Uuid::new_v4(),
+            correlation_id: crate::utils::correlation::current_or_new(),
             category: ErrorCategory::Storage,
             retry_count: 0,
         })?;
 
-        Ok(Self {
+        let store = Self {
             zfs_manager,
             base_path,
             model_cache: Arc::new(RwLock::new(LruCache::new(cache_size))),
-        })
+            version_index: RwLock::new(BTreeMap::new()),
+            remote: None,
+            loading: ModelLoadingConfig::default(),
+        };
+        store.load_index().await?;
+        Ok(store)
+    }
+
+    /// Mirrors versions to and from a remote registry; missing versions are fetched on load
+    pub fn with_remote(mut self, remote: Arc<ObjectStoreBackend>) -> Self {
+        self.remote = Some(remote);
+        self
+    }
+
+    pub fn remote(&self) -> Option<&Arc<ObjectStoreBackend>> {
+        self.remote.as_ref()
+    }
+
+    /// Chooses between mapping model files and reading them into memory
+    pub fn with_loading(mut self, loading: ModelLoadingConfig) -> Self {
+        self.loading = loading;
+        self
+    }
+
+    pub fn loading(&self) -> &ModelLoadingConfig {
+        &self.loading
     }
 
     /// Stores a new ML model version with verification
@@ -87,7 +226,7 @@ impl ModelStore {
                 source: None,
                 severity: crate::utils::error::ErrorSeverity::High,
                 timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id: uuid::Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: ErrorCategory::Storage,
                 retry_count: 0,
             });
@@ -110,14 +249,19 @@ impl ModelStore {
             None,
         ).await?;
 
-        // Store model data
+        // Store model data, replacing any earlier file by rename so live mappings keep their bytes
         let model_file = format!("{}/model.bin", version_path);
-        tokio::fs::write(&model_file, &model_data).await.map_err(|e| GuardianError::StorageError {
+        let tmp_file = format!("{}.tmp", model_file);
+        let written = match tokio::fs::write(&tmp_file, &model_data).await {
+            Ok(()) => tokio::fs::rename(&tmp_file, &model_file).await,
+            Err(e) => Err(e),
+        };
+        written.map_err(|e| GuardianError::StorageError {
             context: format!("Failed to write model data for version {}", version),
             source: Some(Box::new(e)),
             severity: crate::utils::error::ErrorSeverity::High,
             timestamp: time::OffsetDateTime::now_utc(),
-            correlation_id: uuid::Uuid::new_v4(),
+            correlation_id: crate::utils::correlation::current_or_new(),
             category: ErrorCategory::Storage,
             retry_count: 0,
         })?;
@@ -130,17 +274,74 @@ impl ModelStore {
             size: model_data.len() as u64,
             compression_ratio: 0.0, // Will be updated with actual ZFS compression ratio
         };
+        let metadata_file = format!("{}/{}", version_path, METADATA_FILE);
+        let metadata = serde_json::to_vec_pretty(&version_info).map_err(|e| index_error("Failed to encode version metadata", e))?;
+        tokio::fs::write(&metadata_file, metadata).await.map_err(|e| GuardianError::StorageError {
+            context: format!("Failed to write metadata for version {}", version),
+            source: Some(Box::new(e)),
+            severity: crate::utils::error::ErrorSeverity::High,
+            timestamp: time::OffsetDateTime::now_utc(),
+            correlation_id: crate::utils::correlation::current_or_new(),
+            category: ErrorCategory::Storage,
+            retry_count: 0,
+        })?;
+
+        {
+            let mut index = self.version_index.write().await;
+            index.insert(version.clone(), VersionIndexEntry::from_version(&version_info));
+            self.persist_index(&index).await?;
+        }
 
         // Update cache
-        self.model_cache.write().await.put(version.clone(), model_data);
+        let cached = if self.loading.mmap {
+            self.read_model_file(&model_file).await.unwrap_or_else(|_| model_data.into())
+        } else {
+            model_data.into()
+        };
+        self.model_cache.write().await.put(version.clone(), cached);
 
         info!("Stored model version {} successfully", version);
         Ok(version_info)
     }
 
+    /// Rebuilds a model version from a patch against an installed base version
+    #[instrument(skip(self, patch), fields(base_version = %patch.base_version, format = ?patch.format))]
+    pub async fn reconstruct_model(&self, patch: ModelPatch) -> Result<Vec<u8>, GuardianError> {
+        validate_version(&patch.base_version)?;
+        let base = self.load_model(patch.base_version.clone()).await?;
+
+        // Diffing a model-sized buffer is CPU bound
+        let model_data = tokio::task::spawn_blocking(move || patch.apply(&base, MAX_MODEL_SIZE))
+            .await
+            .map_err(|e| GuardianError::StorageError {
+                context: "Model patch task failed".into(),
+                source: Some(Box::new(e)),
+                severity: crate::utils::error::ErrorSeverity::High,
+                timestamp: time::OffsetDateTime::now_utc(),
+                correlation_id: crate::utils::correlation::current_or_new(),
+                category: ErrorCategory::Storage,
+                retry_count: 0,
+            })??;
+
+        metrics::counter!("guardian.storage.model_patches.applied", 1);
+        Ok(model_data)
+    }
+
+    /// Stores a new model version shipped as a patch against an installed base version
+    #[instrument(skip(self, patch))]
+    pub async fn store_patch(&self, patch: ModelPatch, version: String) -> Result<ModelVersion, GuardianError> {
+        validate_version(&version)?;
+        let base_version = patch.base_version.clone();
+        let patch_size = patch.data.len();
+        let model_data = self.reconstruct_model(patch).await?;
+
+        info!(%base_version, patch_size, model_size = model_data.len(), "Reconstructed model version {} from patch", version);
+        self.store_model(model_data, version).await
+    }
+
     /// Loads a specific model version with caching
     #[instrument(skip(self))]
-    pub async fn load_model(&self, version: String) -> Result<Vec<u8>, GuardianError> {
+    pub async fn load_model(&self, version: String) -> Result<ModelBytes, GuardianError> {
         // Check cache first
         if let Some(cached_data) = self.model_cache.read().await.get(&version) {
             // Verify cached data integrity
@@ -149,7 +350,7 @@ impl ModelStore {
             let cached_hash = format!("{:x}", hasher.finalize());
 
             let version_path = format!("{}/{}/{}", self.base_path.display(), MODEL_DATASET_PREFIX, version);
-            let metadata_file = format!("{}/metadata.json", version_path);
+            let metadata_file = format!("{}/{}", version_path, METADATA_FILE);
             let metadata: ModelVersion = tokio::fs::read_to_string(&metadata_file)
                 .await
                 .map_err(|e| GuardianError::StorageError {
@@ -157,7 +358,7 @@ impl ModelStore {
                     source: Some(Box::new(e)),
                     severity: crate::utils::error::ErrorSeverity::Medium,
                     timestamp: time::OffsetDateTime::now_utc(),
-                    correlation_id: uuid::Uuid::new_v4(),
+                    correlation_id: crate::utils::correlation::current_or_new(),
                     category: ErrorCategory::Storage,
                     retry_count: 0,
                 })
@@ -166,7 +367,7 @@ impl ModelStore {
                     source: Some(Box::new(e)),
                     severity: crate::utils::error::ErrorSeverity::Medium,
                     timestamp: time::OffsetDateTime::now_utc(),
-                    correlation_id: uuid::Uuid::new_v4(),
+                    correlation_id: crate::utils::correlation::current_or_new(),
                     category: ErrorCategory::Storage,
                     retry_count: 0,
                 }))?;
@@ -180,12 +381,20 @@ impl ModelStore {
         let version_path = format!("{}/{}/{}", self.base_path.display(), MODEL_DATASET_PREFIX, version);
         let model_file = format!("{}/model.bin", version_path);
         
-        let model_data = tokio::fs::read(&model_file).await.map_err(|e| GuardianError::StorageError {
+        let model_data = match self.read_model_file(&model_file).await {
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound && self.remote.is_some() => {
+                info!(%version, "Model version not installed, fetching it from the remote registry");
+                self.fetch_version(version.clone()).await?;
+                self.read_model_file(&model_file).await
+            }
+            read => read,
+        }
+        .map_err(|e| GuardianError::StorageError {
             context: format!("Failed to read model data for version {}", version),
             source: Some(Box::new(e)),
             severity: crate::utils::error::ErrorSeverity::High,
             timestamp: time::OffsetDateTime::now_utc(),
-            correlation_id: uuid::Uuid::new_v4(),
+            correlation_id: crate::utils::correlation::current_or_new(),
             category: ErrorCategory::Storage,
             retry_count: 0,
         })?;
@@ -196,60 +405,240 @@ impl ModelStore {
         Ok(model_data)
     }
 
-    /// Lists all available model versions
+    /// Pages a version's binary in ahead of use; in-memory models report nothing to do
     #[instrument(skip(self))]
-    pub async fn list_versions(&self) -> Result<Vec<ModelVersion>, GuardianError> {
-        let versions_path = format!("{}/{}", self.base_path.display(), MODEL_DATASET_PREFIX);
-        let mut versions = Vec::new();
+    pub async fn prefetch(&self, version: String) -> Result<PageInStats, GuardianError> {
+        let model = self.load_model(version.clone()).await?;
+        let stats = tokio::task::spawn_blocking(move || model.prefetch())
+            .await
+            .map_err(|e| GuardianError::StorageError {
+                context: format!("Prefetch of model version {} failed", version),
+                source: Some(Box::new(e)),
+                severity: crate::utils::error::ErrorSeverity::Medium,
+                timestamp: time::OffsetDateTime::now_utc(),
+                correlation_id: crate::utils::correlation::current_or_new(),
+                category: ErrorCategory::Storage,
+                retry_count: 0,
+            })?;
+        if stats.major_faults > 0 {
+            self.zfs_manager
+                .io_budget()
+                .record_model_read(stats.bytes, std::time::Duration::from_secs_f64(stats.duration_ms / 1000.0));
+        }
+        info!(%version, bytes = stats.bytes, major_faults = stats.major_faults, duration_ms = stats.duration_ms, "Prefetched model");
+        Ok(stats)
+    }
+
+    /// Maps or reads a model file according to the loading settings
+    ///
+    /// Read times feed the I/O budget; mapped files are only timed when prefetched.
+    async fn read_model_file(&self, model_file: &str) -> std::io::Result<ModelBytes> {
+        if self.loading.mmap {
+            return ModelBytes::map(std::path::Path::new(model_file));
+        }
+        let start = std::time::Instant::now();
+        let data = tokio::fs::read(model_file).await?;
+        self.zfs_manager.io_budget().record_model_read(data.len() as u64, start.elapsed());
+        Ok(ModelBytes::from(data))
+    }
 
-        let mut entries = tokio::fs::read_dir(&versions_path).await.map_err(|e| GuardianError::StorageError {
-            context: "Failed to read versions directory".into(),
+    /// Stores the base64 detached signature shipped with a model version
+    #[instrument(skip(self, signature))]
+    pub async fn store_signature(&self, version: &str, signature: &str) -> Result<(), GuardianError> {
+        validate_version(version)?;
+        let signature_file = format!("{}/{}/{}/{}", self.base_path.display(), MODEL_DATASET_PREFIX, version, SIGNATURE_FILE);
+        tokio::fs::write(&signature_file, signature).await.map_err(|e| GuardianError::StorageError {
+            context: format!("Failed to write signature for version {}", version),
             source: Some(Box::new(e)),
-            severity: crate::utils::error::ErrorSeverity::Medium,
+            severity: crate::utils::error::ErrorSeverity::High,
             timestamp: time::OffsetDateTime::now_utc(),
-            correlation_id: uuid::Uuid::new_v4(),
+            correlation_id: crate::utils::correlation::current_or_new(),
             category: ErrorCategory::Storage,
             retry_count: 0,
-        })?;
+        })
+    }
 
-        while let Some(entry) = entries.next_entry().await.map_err(|e| GuardianError::StorageError {
-            context: "Failed to read version entry".into(),
+    /// Stores the provenance record (JSON) of a trained model version
+    #[instrument(skip(self, provenance))]
+    pub async fn store_provenance(&self, version: &str, provenance: &str) -> Result<(), GuardianError> {
+        validate_version(version)?;
+        let provenance_file = format!("{}/{}/{}/{}", self.base_path.display(), MODEL_DATASET_PREFIX, version, PROVENANCE_FILE);
+        tokio::fs::write(&provenance_file, provenance).await.map_err(|e| GuardianError::StorageError {
+            context: format!("Failed to write provenance for version {}", version),
             source: Some(Box::new(e)),
             severity: crate::utils::error::ErrorSeverity::Medium,
             timestamp: time::OffsetDateTime::now_utc(),
-            correlation_id: uuid::Uuid::new_v4(),
+            correlation_id: crate::utils::correlation::current_or_new(),
             category: ErrorCategory::Storage,
             retry_count: 0,
-        })? {
-            let metadata_file = entry.path().join("metadata.json");
-            if metadata_file.exists() {
-                let metadata: ModelVersion = tokio::fs::read_to_string(&metadata_file)
-                    .await
-                    .map_err(|e| GuardianError::StorageError {
-                        context: format!("Failed to read metadata file: {:?}", metadata_file),
-                        source: Some(Box::new(e)),
-                        severity: crate::utils::error::ErrorSeverity::Medium,
-                        timestamp: time::OffsetDateTime::now_utc(),
-                        correlation_id: uuid::Uuid::new_v4(),
-                        category: ErrorCategory::Storage,
-                        retry_count: 0,
-                    })
-                    .and_then(|data| serde_json::from_str(&data).map_err(|e| GuardianError::StorageError {
-                        context: format!("Failed to parse metadata file: {:?}", metadata_file),
-                        source: Some(Box::new(e)),
-                        severity: crate::utils::error::ErrorSeverity::Medium,
-                        timestamp: time::OffsetDateTime::now_utc(),
-                        correlation_id: uuid::Uuid::new_v4(),
-                        category: ErrorCategory::Storage,
-                        retry_count: 0,
-                    }))?;
-                versions.push(metadata);
+        })
+    }
+
+    /// Loads the provenance record of a model version, if it was trained here
+    #[instrument(skip(self))]
+    pub async fn load_provenance(&self, version: &str) -> Result<Option<String>, GuardianError> {
+        validate_version(version)?;
+        let provenance_file = format!("{}/{}/{}/{}", self.base_path.display(), MODEL_DATASET_PREFIX, version, PROVENANCE_FILE);
+        match tokio::fs::read_to_string(&provenance_file).await {
+            Ok(provenance) => Ok(Some(provenance)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(GuardianError::StorageError {
+                context: format!("Failed to read provenance for version {}", version),
+                source: Some(Box::new(e)),
+                severity: crate::utils::error::ErrorSeverity::Medium,
+                timestamp: time::OffsetDateTime::now_utc(),
+                correlation_id: crate::utils::correlation::current_or_new(),
+                category: ErrorCategory::Storage,
+                retry_count: 0,
+            }),
+        }
+    }
+
+    /// Loads the detached signature of a model version, if one was stored
+    #[instrument(skip(self))]
+    pub async fn load_signature(&self, version: &str) -> Result<Option<String>, GuardianError> {
+        validate_version(version)?;
+        let signature_file = format!("{}/{}/{}/{}", self.base_path.display(), MODEL_DATASET_PREFIX, version, SIGNATURE_FILE);
+        match tokio::fs::read_to_string(&signature_file).await {
+            Ok(signature) => Ok(Some(signature)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(GuardianError::StorageError {
+                context: format!("Failed to read signature for version {}", version),
+                source: Some(Box::new(e)),
+                severity: crate::utils::error::ErrorSeverity::High,
+                timestamp: time::OffsetDateTime::now_utc(),
+                correlation_id: crate::utils::correlation::current_or_new(),
+                category: ErrorCategory::Storage,
+                retry_count: 0,
+            }),
+        }
+    }
+
+    /// Lists all available model versions
+    #[instrument(skip(self))]
+    pub async fn list_versions(&self) -> Result<Vec<ModelVersion>, GuardianError> {
+        Ok(self.version_index.read().await.values().map(VersionIndexEntry::to_version).collect())
+    }
+
+    /// Lists one page of indexed versions matching `query`, newest first
+    #[instrument(skip(self))]
+    pub async fn query_versions(&self, query: &VersionQuery) -> Result<VersionPage, GuardianError> {
+        let after = query.page_token.as_deref().map(decode_page_token).transpose()?;
+        let page_size = match query.page_size {
+            0 => DEFAULT_PAGE_SIZE,
+            n => n.min(MAX_PAGE_SIZE),
+        };
+
+        let index = self.version_index.read().await;
+        let mut matches: Vec<&VersionIndexEntry> = index
+            .values()
+            .filter(|entry| query.matches(entry))
+            .filter(|entry| after.as_ref().map_or(true, |cursor| page_key(entry) < (cursor.0, cursor.1.as_str())))
+            .collect();
+        matches.sort_by(|a, b| page_key(b).cmp(&page_key(a)));
+
+        let next_page_token = (matches.len() > page_size).then(|| encode_page_token(matches[page_size - 1]));
+        Ok(VersionPage {
+            entries: matches.into_iter().take(page_size).cloned().collect(),
+            next_page_token,
+        })
+    }
+
+    /// Applies registry names and statuses to indexed versions in one index write
+    #[instrument(skip(self, labels))]
+    pub async fn label_versions(&self, labels: &[VersionLabel]) -> Result<(), GuardianError> {
+        let mut index = self.version_index.write().await;
+        let mut changed = false;
+        for label in labels {
+            if let Some(entry) = index.get_mut(&label.version) {
+                if entry.name != label.name || entry.status != label.status {
+                    entry.name = label.name.clone();
+                    entry.status = label.status.clone();
+                    changed = true;
+                }
+            }
+        }
+        if changed {
+            self.persist_index(&index).await?;
+        }
+        Ok(())
+    }
+
+    /// Loads the version index, rebuilding it from version metadata when missing or unreadable
+    async fn load_index(&self) -> Result<(), GuardianError> {
+        let index_file = self.index_path();
+        let loaded = match tokio::fs::read(&index_file).await {
+            Ok(data) => match serde_json::from_slice::<Vec<VersionIndexEntry>>(&data) {
+                Ok(entries) => Some(entries),
+                Err(e) => {
+                    warn!(error = %e, "Model version index is corrupt, rebuilding");
+                    None
+                }
+            },
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => return Err(index_error("Failed to read model version index", e)),
+        };
+
+        let mut index = self.version_index.write().await;
+        match loaded {
+            Some(entries) => {
+                *index = entries.into_iter().map(|e| (e.version.clone(), e)).collect();
+            }
+            None => {
+                *index = self
+                    .scan_versions()
+                    .await?
+                    .iter()
+                    .map(|v| (v.version.clone(), VersionIndexEntry::from_version(v)))
+                    .collect();
+                self.persist_index(&index).await?;
+                info!(versions = index.len(), "Rebuilt model version index");
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads every version's metadata file; only used to rebuild the index
+    async fn scan_versions(&self) -> Result<Vec<ModelVersion>, GuardianError> {
+        let versions_path = format!("{}/{}", self.base_path.display(), MODEL_DATASET_PREFIX);
+        let mut versions = Vec::new();
+
+        let mut entries = match tokio::fs::read_dir(&versions_path).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(versions),
+            Err(e) => return Err(index_error("Failed to read versions directory", e)),
+        };
+
+        while let Some(entry) = entries.next_entry().await.map_err(|e| index_error("Failed to read version entry", e))? {
+            let metadata_file = entry.path().join(METADATA_FILE);
+            let data = match tokio::fs::read_to_string(&metadata_file).await {
+                Ok(data) => data,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(index_error(&format!("Failed to read metadata file: {:?}", metadata_file), e)),
+            };
+            match serde_json::from_str::<ModelVersion>(&data) {
+                Ok(metadata) => versions.push(metadata),
+                Err(e) => warn!(error = %e, file = ?metadata_file, "Skipping unparseable model metadata"),
             }
         }
 
         Ok(versions)
     }
 
+    /// Writes the index through a temporary file so readers never see a partial index
+    async fn persist_index(&self, index: &BTreeMap<String, VersionIndexEntry>) -> Result<(), GuardianError> {
+        let entries: Vec<&VersionIndexEntry> = index.values().collect();
+        let data = serde_json::to_vec(&entries).map_err(|e| index_error("Failed to encode model version index", e))?;
+        let index_file = self.index_path();
+        let tmp_file = index_file.with_extension("json.tmp");
+        tokio::fs::write(&tmp_file, data).await.map_err(|e| index_error("Failed to write model version index", e))?;
+        tokio::fs::rename(&tmp_file, &index_file).await.map_err(|e| index_error("Failed to replace model version index", e))
+    }
+
+    fn index_path(&self) -> PathBuf {
+        self.base_path.join(MODEL_DATASET_PREFIX).join(VERSION_INDEX_FILE)
+    }
+
     /// Deletes a specific model version
     #[instrument(skip(self))]
     pub async fn delete_version(&self, version: String) -> Result<(), GuardianError> {
@@ -258,12 +647,194 @@ impl ModelStore {
         let version_path = format!("{}/{}/{}", self.base_path.display(), MODEL_DATASET_PREFIX, version);
         self.zfs_manager.destroy_dataset(&version_path).await?;
 
-        // Remove from cache
+        // Remove from cache and index
         self.model_cache.write().await.pop(&version);
+        {
+            let mut index = self.version_index.write().await;
+            if index.remove(&version).is_some() {
+                self.persist_index(&index).await?;
+            }
+        }
 
         info!("Deleted model version {} successfully", version);
         Ok(())
     }
+
+    /// Uploads a local version to the remote registry
+    ///
+    /// The model goes up first and the metadata last, so a version is only listed remotely
+    /// once all of it has arrived; an interrupted publish resumes its upload on the next call.
+    #[instrument(skip(self))]
+    pub async fn publish_version(&self, version: String) -> Result<ModelVersion, GuardianError> {
+        validate_version(&version)?;
+        let remote = self.require_remote()?;
+        let metadata = self.read_metadata(&version).await?;
+
+        let version_path = self.version_path(&version);
+        let model_file = version_path.join("model.bin");
+        let (hash, _) = file_sha256(&model_file).await?;
+        if hash != metadata.hash {
+            return Err(remote_error(format!("Local model version {} does not match its recorded hash", version)));
+        }
+        remote.upload_file(&remote.key(&format!("{}/model.bin", version)), &model_file, &metadata.hash).await?;
+
+        for file in [SIGNATURE_FILE, PROVENANCE_FILE] {
+            match tokio::fs::read(version_path.join(file)).await {
+                Ok(data) => remote.put_object(&remote.key(&format!("{}/{}", version, file)), data).await?,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => return Err(index_error("Failed to read version file", e)),
+            }
+        }
+        let encoded = serde_json::to_vec_pretty(&metadata).map_err(|e| index_error("Failed to encode version metadata", e))?;
+        remote.put_object(&remote.key(&format!("{}/{}", version, METADATA_FILE)), encoded).await?;
+
+        metrics::counter!("guardian.storage.model_remote.published", 1);
+        info!(%version, size = metadata.size, "Published model version to remote registry");
+        Ok(metadata)
+    }
+
+    /// Installs a version from the remote registry, checking the model against its hash
+    #[instrument(skip(self))]
+    pub async fn fetch_version(&self, version: String) -> Result<ModelVersion, GuardianError> {
+        validate_version(&version)?;
+        let remote = self.require_remote()?;
+        let metadata: ModelVersion = remote
+            .get_object(&remote.key(&format!("{}/{}", version, METADATA_FILE)))
+            .await?
+            .ok_or_else(|| remote_error(format!("Model version {} is not in the remote registry", version)))
+            .and_then(|data| serde_json::from_slice(&data).map_err(|e| index_error("Failed to parse remote version metadata", e)))?;
+        if metadata.version != version || metadata.size > MAX_MODEL_SIZE {
+            return Err(remote_error(format!("Remote metadata of model version {} is not valid", version)));
+        }
+
+        let version_path = self.version_path(&version);
+        self.zfs_manager.create_dataset(
+            &version_path.display().to_string(),
+            Some(std::collections::HashMap::from([
+                ("compression".to_string(), "lz4".to_string()),
+            ])),
+            None,
+        ).await?;
+        remote
+            .download_file(&remote.key(&format!("{}/model.bin", version)), &version_path.join("model.bin"), &metadata.hash)
+            .await?;
+        for file in [SIGNATURE_FILE, PROVENANCE_FILE] {
+            if let Some(data) = remote.get_object(&remote.key(&format!("{}/{}", version, file))).await? {
+                tokio::fs::write(version_path.join(file), data)
+                    .await
+                    .map_err(|e| index_error("Failed to write version file", e))?;
+            }
+        }
+        let encoded = serde_json::to_vec_pretty(&metadata).map_err(|e| index_error("Failed to encode version metadata", e))?;
+        tokio::fs::write(version_path.join(METADATA_FILE), encoded)
+            .await
+            .map_err(|e| index_error("Failed to write version metadata", e))?;
+
+        {
+            let mut index = self.version_index.write().await;
+            index.insert(version.clone(), VersionIndexEntry::from_version(&metadata));
+            self.persist_index(&index).await?;
+        }
+
+        metrics::counter!("guardian.storage.model_remote.fetched", 1);
+        info!(%version, size = metadata.size, "Fetched model version from remote registry");
+        Ok(metadata)
+    }
+
+    /// Copies versions between the local and remote registries
+    ///
+    /// A version on both sides is left alone, and reported as a conflict when the hashes
+    /// differ. One failed version does not stop the others; the first error is returned after
+    /// the rest were tried.
+    #[instrument(skip(self))]
+    pub async fn sync(&self, direction: SyncDirection) -> Result<SyncReport, GuardianError> {
+        let remote = self.require_remote()?;
+        let local: BTreeMap<String, String> = self
+            .version_index
+            .read()
+            .await
+            .values()
+            .map(|entry| (entry.version.clone(), entry.hash.clone()))
+            .collect();
+
+        let mut remote_versions = BTreeMap::new();
+        let listing_prefix = remote.key("");
+        for key in remote.list(&listing_prefix).await? {
+            let Some(version) = key
+                .strip_prefix(listing_prefix.as_str())
+                .and_then(|rest| rest.strip_suffix(&format!("/{}", METADATA_FILE)))
+            else {
+                continue;
+            };
+            if validate_version(version).is_err() || !local.contains_key(version) {
+                remote_versions.insert(version.to_string(), None);
+                continue;
+            }
+            // Only versions on both sides need their remote hash
+            let hash = remote
+                .get_object(&key)
+                .await?
+                .and_then(|data| serde_json::from_slice::<ModelVersion>(&data).ok())
+                .map(|metadata| metadata.hash);
+            remote_versions.insert(version.to_string(), hash);
+        }
+
+        let mut report = SyncReport::default();
+        let mut first_error = None;
+        for (version, remote_hash) in &remote_versions {
+            match local.get(version) {
+                Some(hash) if remote_hash.as_ref().map_or(false, |remote_hash| remote_hash != hash) => {
+                    warn!(%version, "Model version differs between local and remote registries");
+                    report.conflicts.push(version.clone());
+                }
+                Some(_) => {}
+                None if direction != SyncDirection::Push && validate_version(version).is_ok() => {
+                    match self.fetch_version(version.clone()).await {
+                        Ok(_) => report.pulled.push(version.clone()),
+                        Err(e) => {
+                            error!(%version, error = %e, "Failed to fetch model version");
+                            first_error.get_or_insert(e);
+                        }
+                    }
+                }
+                None => {}
+            }
+        }
+        if direction != SyncDirection::Pull {
+            for version in local.keys().filter(|v| !remote_versions.contains_key(*v)) {
+                match self.publish_version(version.clone()).await {
+                    Ok(_) => report.pushed.push(version.clone()),
+                    Err(e) => {
+                        error!(%version, error = %e, "Failed to publish model version");
+                        first_error.get_or_insert(e);
+                    }
+                }
+            }
+        }
+
+        metrics::gauge!("guardian.storage.model_remote.conflicts", report.conflicts.len() as f64);
+        match first_error {
+            Some(e) => Err(e),
+            None => Ok(report),
+        }
+    }
+
+    fn require_remote(&self) -> Result<&Arc<ObjectStoreBackend>, GuardianError> {
+        self.remote
+            .as_ref()
+            .ok_or_else(|| remote_error("No remote model registry is configured".into()))
+    }
+
+    fn version_path(&self, version: &str) -> PathBuf {
+        self.base_path.join(MODEL_DATASET_PREFIX).join(version)
+    }
+
+    async fn read_metadata(&self, version: &str) -> Result<ModelVersion, GuardianError> {
+        let data = tokio::fs::read(self.version_path(version).join(METADATA_FILE))
+            .await
+            .map_err(|e| index_error("Failed to read version metadata", e))?;
+        serde_json::from_slice(&data).map_err(|e| index_error("Failed to parse version metadata", e))
+    }
 }
 
 /// Validates model version string format and uniqueness
@@ -276,7 +847,7 @@ fn validate_version(version: &str) -> Result<(), GuardianError> {
             source: None,
             severity: crate::utils::error::ErrorSeverity::Medium,
             timestamp: time::OffsetDateTime::now_utc(),
-            correlation_id: uuid::Uuid::new_v4(),
+            correlation_id: crate::utils::correlation::current_or_new(),
             category: ErrorCategory::Storage,
             retry_count: 0,
         });
@@ -284,6 +855,65 @@ fn validate_version(version: &str) -> Result<(), GuardianError> {
     Ok(())
 }
 
+/// Sort key of listings: creation time, then version to break ties
+fn page_key(entry: &VersionIndexEntry) -> (DateTime<Utc>, &str) {
+    (entry.created_at, entry.version.as_str())
+}
+
+/// Encodes the position after `entry` as an opaque page token
+fn encode_page_token(entry: &VersionIndexEntry) -> String {
+    let created_at = entry.created_at;
+    URL_SAFE_NO_PAD.encode(format!("{}.{}|{}", created_at.timestamp(), created_at.timestamp_subsec_nanos(), entry.version))
+}
+
+fn decode_page_token(token: &str) -> Result<(DateTime<Utc>, String), GuardianError> {
+    URL_SAFE_NO_PAD
+        .decode(token)
+        .ok()
+        .and_then(|raw| String::from_utf8(raw).ok())
+        .and_then(|raw| {
+            let (created_at, version) = raw.split_once('|')?;
+            let (secs, nanos) = created_at.split_once('.')?;
+            Some((DateTime::<Utc>::from_timestamp(secs.parse().ok()?, nanos.parse().ok()?)?, version.to_string()))
+        })
+        .ok_or_else(|| GuardianError::ValidationError {
+            context: "Invalid page token".into(),
+            source: None,
+            severity: crate::utils::error::ErrorSeverity::Low,
+            timestamp: time::OffsetDateTime::now_utc(),
+            correlation_id: crate::utils::correlation::current_or_new(),
+            category: ErrorCategory::Validation,
+            retry_count: 0,
+        })
+}
+
+fn index_error<E>(context: &str, e: E) -> GuardianError
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    GuardianError::StorageError {
+        context: context.into(),
+        source: Some(Box::new(e)),
+        severity: crate::utils::error::ErrorSeverity::Medium,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: ErrorCategory::Storage,
+        retry_count: 0,
+    }
+}
+
+fn remote_error(context: String) -> GuardianError {
+    GuardianError::StorageError {
+        context,
+        source: None,
+        severity: crate::utils::error::ErrorSeverity::Medium,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: ErrorCategory::Storage,
+        retry_count: 0,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -313,7 +943,7 @@ mod tests {
 
         // Test loading model
         let loaded_data = store.load_model(version.clone()).await.unwrap();
-        assert_eq!(loaded_data, test_data);
+        assert_eq!(&loaded_data[..], &test_data[..]);
 
         // Test version listing
         let versions = store.list_versions().await.unwrap();
@@ -324,6 +954,44 @@ mod tests {
         assert!(store.delete_version(version).await.is_ok());
     }
 
+    #[test]
+    fn test_version_query_paging() {
+        let entry = |version: &str, name: &str, status: &str, minutes: i64| VersionIndexEntry {
+            version: version.into(),
+            name: name.into(),
+            status: status.into(),
+            created_at: DateTime::<Utc>::from_timestamp(1_700_000_000 + minutes * 60, 0).unwrap(),
+            hash: String::new(),
+            size: 0,
+            compression_ratio: 0.0,
+        };
+        let entries = vec![
+            entry("v1.0.0", "threat", "inactive", 0),
+            entry("v1.1.0", "threat", "active", 1),
+            entry("v1.2.0", "threat", "failed", 2),
+            entry("v2.0.0", "anomaly", "inactive", 3),
+        ];
+
+        let query = VersionQuery { name_prefix: Some("thr".into()), ..Default::default() };
+        let mut matches: Vec<&VersionIndexEntry> = entries.iter().filter(|e| query.matches(e)).collect();
+        assert_eq!(matches.len(), 3);
+        matches.sort_by(|a, b| page_key(b).cmp(&page_key(a)));
+        assert_eq!(matches[0].version, "v1.2.0");
+
+        let status = VersionQuery { status: Some("ACTIVE".into()), ..Default::default() };
+        assert_eq!(entries.iter().filter(|e| status.matches(e)).count(), 1);
+        let window = VersionQuery {
+            created_after: Some(entries[1].created_at),
+            created_before: Some(entries[3].created_at),
+            ..Default::default()
+        };
+        assert_eq!(entries.iter().filter(|e| window.matches(e)).count(), 2);
+
+        let (created_at, version) = decode_page_token(&encode_page_token(&entries[2])).unwrap();
+        assert_eq!((created_at, version.as_str()), page_key(&entries[2]));
+        assert!(decode_page_token("not a token").is_err());
+    }
+
     #[tokio::test]
     async fn test_version_validation() {
         assert!(validate_version("v1.0.0").is_ok());
@@ -0,0 +1,235 @@
+use std::{collections::HashMap, sync::Arc};
+
+use async_trait::async_trait;
+use metrics::{counter, gauge};
+use parking_lot::Mutex;
+use serde::Serialize;
+use tracing::{error, info, instrument, warn};
+
+use crate::config::storage_config::{BackgroundJobClass, DatasetQuota, DatasetQuotaConfig};
+use crate::core::event_bus::{Event, EventBus, EventPriority};
+use crate::storage::zfs_manager::{DatasetUsage, ZfsManager};
+use crate::utils::error::GuardianError;
+
+const BYTES_PER_GB: u64 = 1024 * 1024 * 1024;
+
+/// Where a dataset's usage sits relative to its thresholds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UsageLevel {
+    Normal,
+    Warning,
+    Critical,
+}
+
+impl UsageLevel {
+    pub fn classify(percent_used: f64, quota: &DatasetQuota) -> Self {
+        if percent_used >= f64::from(quota.cleanup_percent) {
+            Self::Critical
+        } else if percent_used >= f64::from(quota.warn_percent) {
+            Self::Warning
+        } else {
+            Self::Normal
+        }
+    }
+
+    fn priority(self) -> EventPriority {
+        match self {
+            Self::Critical => EventPriority::Critical,
+            Self::Warning => EventPriority::High,
+            Self::Normal => EventPriority::Low,
+        }
+    }
+}
+
+/// Frees space in a dataset that is close to its limit
+#[async_trait]
+pub trait QuotaCleanup: Send + Sync + std::fmt::Debug {
+    /// Tries to free at least `bytes` from `dataset`; returns the bytes actually freed
+    async fn reclaim(&self, dataset: &str, bytes: u64) -> Result<u64, GuardianError>;
+}
+
+/// Destroys the oldest child datasets (time partitions) first, always keeping the newest one
+#[derive(Debug)]
+pub struct OldestPartitionCleanup {
+    zfs: Arc<ZfsManager>,
+}
+
+impl OldestPartitionCleanup {
+    pub fn new(zfs: Arc<ZfsManager>) -> Self {
+        Self { zfs }
+    }
+}
+
+#[async_trait]
+impl QuotaCleanup for OldestPartitionCleanup {
+    async fn reclaim(&self, dataset: &str, bytes: u64) -> Result<u64, GuardianError> {
+        let mut partitions = self.zfs.child_datasets(dataset).await?;
+        // The newest partition is the one taking writes
+        partitions.pop();
+
+        let mut freed = 0;
+        for partition in partitions {
+            if freed >= bytes {
+                break;
+            }
+            self.zfs.io_throttler().acquire(BackgroundJobClass::Retention, partition.used).await;
+            self.zfs.destroy_dataset(&partition.name).await?;
+            freed += partition.used;
+        }
+        Ok(freed)
+    }
+}
+
+/// Applies dataset quotas and watches usage, raising events and cleaning up before hard limits are hit
+#[derive(Debug)]
+pub struct QuotaMonitor {
+    zfs: Arc<ZfsManager>,
+    event_bus: Arc<EventBus>,
+    config: DatasetQuotaConfig,
+    /// Last level seen per dataset, so events fire on transitions only
+    levels: Mutex<HashMap<String, UsageLevel>>,
+    cleanups: HashMap<String, Arc<dyn QuotaCleanup>>,
+}
+
+impl QuotaMonitor {
+    /// Creates a monitor; datasets with `auto_cleanup` get partition cleanup unless replaced
+    pub fn new(zfs: Arc<ZfsManager>, event_bus: Arc<EventBus>, config: DatasetQuotaConfig) -> Self {
+        let cleanups = config
+            .datasets
+            .iter()
+            .filter(|(_, quota)| quota.auto_cleanup)
+            .map(|(name, _)| {
+                let cleanup: Arc<dyn QuotaCleanup> = Arc::new(OldestPartitionCleanup::new(Arc::clone(&zfs)));
+                (name.clone(), cleanup)
+            })
+            .collect();
+        Self {
+            zfs,
+            event_bus,
+            config,
+            levels: Mutex::new(HashMap::new()),
+            cleanups,
+        }
+    }
+
+    /// Replaces the cleanup strategy of a dataset
+    pub fn with_cleanup(mut self, dataset: impl Into<String>, cleanup: Arc<dyn QuotaCleanup>) -> Self {
+        self.cleanups.insert(dataset.into(), cleanup);
+        self
+    }
+
+    /// Sets the configured quota and reservation on every dataset
+    #[instrument(skip(self))]
+    pub async fn apply_quotas(&self) -> Result<(), GuardianError> {
+        for (name, quota) in &self.config.datasets {
+            self.zfs
+                .set_quota(
+                    &self.zfs.dataset_path(name),
+                    quota.quota_gb.map(|gb| gb * BYTES_PER_GB),
+                    quota.reservation_gb.map(|gb| gb * BYTES_PER_GB),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Applies quotas, then polls usage in the background until the task is aborted
+    pub fn start(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            if !self.config.enabled {
+                return;
+            }
+            if let Err(e) = self.apply_quotas().await {
+                error!(error = %e, "Failed to apply dataset quotas");
+            }
+            let mut interval = tokio::time::interval(self.config.poll_interval);
+            loop {
+                interval.tick().await;
+                self.run_once().await;
+            }
+        })
+    }
+
+    /// Checks every dataset once; a failure on one dataset does not stop the others
+    pub async fn run_once(&self) -> Vec<DatasetUsage> {
+        let mut reports = Vec::with_capacity(self.config.datasets.len());
+        for (name, quota) in &self.config.datasets {
+            match self.check_dataset(name, quota).await {
+                Ok(usage) => reports.push(usage),
+                Err(e) => warn!(error = %e, dataset = %name, "Dataset quota check failed"),
+            }
+        }
+        reports
+    }
+
+    #[instrument(skip(self, quota))]
+    async fn check_dataset(&self, name: &str, quota: &DatasetQuota) -> Result<DatasetUsage, GuardianError> {
+        let path = self.zfs.dataset_path(name);
+        let mut usage = self.zfs.dataset_usage(&path).await?;
+        let percent = usage.percent_used();
+        gauge!("guardian.storage.dataset.usage_percent", percent, "dataset" => name.to_string());
+        gauge!("guardian.storage.dataset.used_bytes", usage.used as f64, "dataset" => name.to_string());
+
+        let level = UsageLevel::classify(percent, quota);
+        let previous = self.levels.lock().insert(name.to_string(), level).unwrap_or(UsageLevel::Normal);
+        if level != previous {
+            warn!(dataset = name, ?previous, ?level, percent, "Dataset usage crossed a threshold");
+            self.event_bus.publish(Event::new(
+                self.zfs.tenant().topic("storage_quota_threshold"),
+                serde_json::json!({
+                    "dataset": name,
+                    "level": level,
+                    "previous_level": previous,
+                    "percent_used": percent,
+                    "used_bytes": usage.used,
+                    "limit_bytes": usage.limit(),
+                }),
+                level.priority(),
+            )?).await?;
+        }
+
+        if level == UsageLevel::Critical && quota.auto_cleanup {
+            if let Some(cleanup) = self.cleanups.get(name) {
+                let needed = bytes_to_free(&usage, quota.cleanup_target_percent);
+                let freed = cleanup.reclaim(&path, needed).await?;
+                counter!("guardian.storage.dataset.cleanup_bytes", freed, "dataset" => name.to_string());
+                info!(dataset = name, needed, freed, "Dataset cleanup finished");
+                if freed < needed {
+                    warn!(dataset = name, needed, freed, "Dataset cleanup could not reach its target");
+                }
+                usage = self.zfs.dataset_usage(&path).await?;
+            }
+        }
+        Ok(usage)
+    }
+}
+
+/// Bytes to remove to bring usage down to `target_percent` of the dataset's limit
+fn bytes_to_free(usage: &DatasetUsage, target_percent: u8) -> u64 {
+    let target = usage.limit() / 100 * u64::from(target_percent);
+    usage.used.saturating_sub(target)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_usage_levels_and_cleanup_target() {
+        let quota = DatasetQuota::default();
+        assert_eq!(UsageLevel::classify(50.0, &quota), UsageLevel::Normal);
+        assert_eq!(UsageLevel::classify(80.0, &quota), UsageLevel::Warning);
+        assert_eq!(UsageLevel::classify(93.5, &quota), UsageLevel::Critical);
+
+        let usage = DatasetUsage {
+            dataset: "guardian/events".into(),
+            used: 950,
+            available: 50,
+            quota: Some(1000),
+            reservation: None,
+        };
+        assert_eq!(bytes_to_free(&usage, 75), 200);
+        assert_eq!(bytes_to_free(&usage, 100), 0);
+    }
+}
@@ -0,0 +1,237 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use metrics::{counter, gauge};
+use serde::{Deserialize, Serialize};
+use tokio::{io::AsyncWriteExt, sync::Mutex};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::utils::error::{ErrorCategory, GuardianError};
+use crate::utils::ids::{next_id, IdKind};
+
+// Constants for the response write-ahead log
+/// Lives on the Guardian dataset so it shares its snapshots and integrity checks
+pub const DEFAULT_RESPONSE_WAL_PATH: &str = "/var/lib/guardian/wal/responses.wal";
+
+/// An action recorded before it was executed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WalIntent {
+    pub id: String,
+    pub action_name: String,
+    /// The serialized action, opaque to storage
+    pub action: serde_json::Value,
+    pub correlation_id: Uuid,
+    pub recorded_at: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "record", rename_all = "snake_case")]
+enum WalRecord {
+    Intent(WalIntent),
+    Completed { id: String, success: bool, error: Option<String>, at: u64 },
+    /// An interrupted intent settled by recovery
+    Reconciled { id: String, resolution: String, at: u64 },
+}
+
+/// Durable log of response actions: intent before execution, completion after
+///
+/// Records are JSON lines synced on every append. Intents with no completion or
+/// reconciliation were interrupted and are returned by `incomplete` for recovery.
+/// Opening the log compacts it down to those intents.
+#[derive(Debug)]
+pub struct ResponseWal {
+    path: PathBuf,
+    file: Mutex<tokio::fs::File>,
+    incomplete: Mutex<BTreeMap<String, WalIntent>>,
+}
+
+impl ResponseWal {
+    /// Opens the log at `path`, creating it if needed and dropping settled records
+    pub async fn open(path: impl Into<PathBuf>) -> Result<Self, GuardianError> {
+        let path = path.into();
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await.map_err(|e| io_error(&path, e))?;
+        }
+
+        let incomplete = match tokio::fs::read_to_string(&path).await {
+            Ok(raw) => replay(&raw),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(e) => return Err(io_error(&path, e)),
+        };
+
+        // Rewrite through a temporary file so a crash mid-compaction keeps the old log
+        let compacted: String = incomplete
+            .values()
+            .filter_map(|intent| record_line(&WalRecord::Intent(intent.clone())).ok())
+            .collect();
+        let temporary = path.with_extension("wal.tmp");
+        write_synced(&temporary, compacted.as_bytes()).await?;
+        tokio::fs::rename(&temporary, &path).await.map_err(|e| io_error(&path, e))?;
+
+        let file = tokio::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .await
+            .map_err(|e| io_error(&path, e))?;
+
+        if !incomplete.is_empty() {
+            warn!(path = %path.display(), incomplete = incomplete.len(), "Response WAL has interrupted actions");
+        }
+        gauge!("guardian.response.wal.incomplete", incomplete.len() as f64);
+        Ok(Self { path, file: Mutex::new(file), incomplete: Mutex::new(incomplete) })
+    }
+
+    /// Durably records that an action is about to run, returning the intent ID
+    pub async fn record_intent(
+        &self,
+        action_name: &str,
+        action: serde_json::Value,
+        correlation_id: Uuid,
+    ) -> Result<String, GuardianError> {
+        let intent = WalIntent {
+            id: next_id(IdKind::Operation).to_string(),
+            action_name: action_name.to_string(),
+            action,
+            correlation_id,
+            recorded_at: unix_now(),
+        };
+        self.append(&WalRecord::Intent(intent.clone())).await?;
+
+        let id = intent.id.clone();
+        let mut incomplete = self.incomplete.lock().await;
+        incomplete.insert(id.clone(), intent);
+        gauge!("guardian.response.wal.incomplete", incomplete.len() as f64);
+        Ok(id)
+    }
+
+    /// Records how an action ended
+    pub async fn record_completion(&self, id: &str, success: bool, error: Option<String>) -> Result<(), GuardianError> {
+        self.append(&WalRecord::Completed { id: id.to_string(), success, error, at: unix_now() }).await?;
+        self.settle(id).await;
+        Ok(())
+    }
+
+    /// Records how recovery settled an interrupted action
+    pub async fn record_reconciled(&self, id: &str, resolution: &str) -> Result<(), GuardianError> {
+        self.append(&WalRecord::Reconciled { id: id.to_string(), resolution: resolution.to_string(), at: unix_now() })
+            .await?;
+        self.settle(id).await;
+        info!(target: "SECURITY-AUDIT", intent_id = id, resolution, "Interrupted response action reconciled");
+        Ok(())
+    }
+
+    /// Returns intents without a completion, oldest first
+    pub async fn incomplete(&self) -> Vec<WalIntent> {
+        let mut intents: Vec<WalIntent> = self.incomplete.lock().await.values().cloned().collect();
+        intents.sort_by_key(|intent| intent.recorded_at);
+        intents
+    }
+
+    async fn settle(&self, id: &str) {
+        let mut incomplete = self.incomplete.lock().await;
+        incomplete.remove(id);
+        gauge!("guardian.response.wal.incomplete", incomplete.len() as f64);
+    }
+
+    async fn append(&self, record: &WalRecord) -> Result<(), GuardianError> {
+        let line = record_line(record)?;
+        let mut file = self.file.lock().await;
+        file.write_all(line.as_bytes()).await.map_err(|e| io_error(&self.path, e))?;
+        file.sync_data().await.map_err(|e| io_error(&self.path, e))?;
+        counter!("guardian.response.wal.records", 1);
+        Ok(())
+    }
+}
+
+/// Rebuilds the incomplete intents from log contents, stopping at a torn final record
+fn replay(raw: &str) -> BTreeMap<String, WalIntent> {
+    let mut incomplete = BTreeMap::new();
+    for line in raw.lines().filter(|line| !line.trim().is_empty()) {
+        match serde_json::from_str::<WalRecord>(line) {
+            Ok(WalRecord::Intent(intent)) => {
+                incomplete.insert(intent.id.clone(), intent);
+            }
+            Ok(WalRecord::Completed { id, .. }) | Ok(WalRecord::Reconciled { id, .. }) => {
+                incomplete.remove(&id);
+            }
+            Err(e) => {
+                warn!(error = %e, "Response WAL ends in a torn record, ignoring the rest");
+                break;
+            }
+        }
+    }
+    incomplete
+}
+
+fn record_line(record: &WalRecord) -> Result<String, GuardianError> {
+    serde_json::to_string(record)
+        .map(|line| line + "\n")
+        .map_err(|e| wal_error("Failed to serialize response WAL record".into(), Some(Box::new(e))))
+}
+
+async fn write_synced(path: &Path, data: &[u8]) -> Result<(), GuardianError> {
+    let mut file = tokio::fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)
+        .await
+        .map_err(|e| io_error(path, e))?;
+    file.write_all(data).await.map_err(|e| io_error(path, e))?;
+    file.sync_all().await.map_err(|e| io_error(path, e))
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or_default()
+}
+
+fn io_error(path: &Path, e: std::io::Error) -> GuardianError {
+    wal_error(format!("Response WAL I/O failed on {}", path.display()), Some(Box::new(e)))
+}
+
+fn wal_error(context: String, source: Option<Box<dyn std::error::Error + Send + Sync>>) -> GuardianError {
+    GuardianError::StorageError {
+        context,
+        source,
+        severity: crate::utils::error::ErrorSeverity::Critical,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: ErrorCategory::Storage,
+        retry_count: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_reopened_log_returns_only_interrupted_intents() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("responses.wal");
+
+        let wal = ResponseWal::open(&path).await.unwrap();
+        let done = wal.record_intent("block_network", serde_json::json!({ "address": "10.0.0.1" }), Uuid::new_v4()).await.unwrap();
+        wal.record_completion(&done, true, None).await.unwrap();
+        let interrupted = wal.record_intent("terminate_process", serde_json::json!({ "pid": 42 }), Uuid::new_v4()).await.unwrap();
+        drop(wal);
+
+        // A crash mid-append leaves a partial line behind
+        let mut file = std::fs::OpenOptions::new().append(true).open(&path).unwrap();
+        std::io::Write::write_all(&mut file, b"{\"record\":\"completed\",\"id\":").unwrap();
+
+        let wal = ResponseWal::open(&path).await.unwrap();
+        let incomplete = wal.incomplete().await;
+        assert_eq!(incomplete.len(), 1);
+        assert_eq!(incomplete[0].id, interrupted);
+
+        wal.record_reconciled(&interrupted, "verified").await.unwrap();
+        drop(wal);
+        assert!(ResponseWal::open(&path).await.unwrap().incomplete().await.is_empty());
+    }
+}
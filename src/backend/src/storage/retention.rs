@@ -0,0 +1,323 @@
+//! Tiered retention for the time-partitioned stores
+//!
+//! Partitions age through three tiers: hot partitions are left uncompressed so appends and
+//! recent queries stay cheap, warm partitions are recompressed with a high zstd level on the
+//! pool, and archived partitions are moved off the pool to a separate dataset or an object
+//! store. Stores read every tier through the same query path, so callers never need to know
+//! where a partition currently lives.
+
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use serde::Serialize;
+use tracing::debug;
+
+use crate::config::storage_config::{ArchiveTarget, TierAges};
+use crate::utils::error::{ErrorCategory, GuardianError};
+
+use super::zfs_manager::ZfsManager;
+
+/// First bytes of every zstd frame, used to tell compressed partitions from hot ones
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xB5, 0x2F, 0xFD];
+const OBJECT_STORE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Where a partition is kept, by age
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RetentionTier {
+    Hot,
+    Warm,
+    Archive,
+}
+
+impl RetentionTier {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RetentionTier::Hot => "hot",
+            RetentionTier::Warm => "warm",
+            RetentionTier::Archive => "archive",
+        }
+    }
+}
+
+/// Tier ages of one store, with whether it has an archive to move data to
+#[derive(Debug, Clone, PartialEq)]
+pub struct TierPolicy {
+    pub ages: TierAges,
+    pub warm_compression_level: i32,
+    pub archived: bool,
+}
+
+impl TierPolicy {
+    pub fn new(ages: TierAges, warm_compression_level: i32, archived: bool) -> Self {
+        Self {
+            ages,
+            warm_compression_level,
+            archived,
+        }
+    }
+
+    /// Keeps data compressed at `level` for `days` and then deletes it, as before tiers existed
+    pub fn delete_after(days: u32, level: i32) -> Self {
+        Self::new(
+            TierAges {
+                hot_days: 0,
+                warm_days: days,
+                archive_days: 0,
+            },
+            level,
+            false,
+        )
+    }
+
+    /// Tier of data `age_days` old; `None` once it should be deleted
+    pub fn tier_for(&self, age_days: i64) -> Option<RetentionTier> {
+        let age = age_days.max(0);
+        if age < i64::from(self.ages.hot_days) {
+            Some(RetentionTier::Hot)
+        } else if age < i64::from(self.ages.warm_days) {
+            Some(RetentionTier::Warm)
+        } else if self.archived && (self.ages.archive_days == 0 || age < i64::from(self.ages.archive_days)) {
+            Some(RetentionTier::Archive)
+        } else {
+            None
+        }
+    }
+
+    /// Oldest age worth looking at for tier changes; older partitions are already gone
+    pub fn horizon_days(&self) -> u32 {
+        match (self.archived, self.ages.archive_days) {
+            (true, 0) => self.ages.warm_days,
+            (true, days) => days,
+            (false, _) => self.ages.warm_days,
+        }
+    }
+
+    /// Encodes partition data for `tier`: hot data is stored as is, older data compressed
+    pub fn encode(&self, data: Vec<u8>, tier: RetentionTier) -> Result<Vec<u8>, GuardianError> {
+        if tier == RetentionTier::Hot || is_compressed(&data) {
+            return Ok(data);
+        }
+        zstd::encode_all(&data[..], self.warm_compression_level)
+            .map_err(|e| retention_error("Failed to compress partition".into(), Some(Box::new(e))))
+    }
+}
+
+/// Counts of partitions a retention pass moved
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct RetentionReport {
+    pub compressed: usize,
+    pub archived: usize,
+    pub deleted: usize,
+}
+
+/// Whether partition data was written by a warm or archive tier
+pub fn is_compressed(data: &[u8]) -> bool {
+    data.starts_with(&ZSTD_MAGIC)
+}
+
+/// Decodes partition data from any tier
+pub fn decode(data: Vec<u8>) -> Result<Vec<u8>, GuardianError> {
+    if !is_compressed(&data) {
+        return Ok(data);
+    }
+    zstd::decode_all(&data[..]).map_err(|e| retention_error("Failed to decompress partition".into(), Some(Box::new(e))))
+}
+
+/// Storage for partitions that left the pool
+#[async_trait]
+pub trait ArchiveStore: Send + Sync + std::fmt::Debug {
+    async fn put(&self, key: &str, data: Vec<u8>) -> Result<(), GuardianError>;
+
+    /// Returns `None` when nothing is archived under `key`
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, GuardianError>;
+
+    /// Deleting a missing key succeeds
+    async fn delete(&self, key: &str) -> Result<(), GuardianError>;
+}
+
+/// Builds the archive store a target names
+pub fn archive_store(target: &ArchiveTarget, zfs: &ZfsManager) -> Result<Arc<dyn ArchiveStore>, GuardianError> {
+    Ok(match target {
+        // Datasets mount at their default mountpoint, the dataset path under /
+        ArchiveTarget::Dataset { dataset } => {
+            Arc::new(DatasetArchive::new(Path::new("/").join(zfs.dataset_path(dataset))))
+        }
+        ArchiveTarget::ObjectStore { endpoint, token } => Arc::new(ObjectStoreArchive::new(endpoint, token.clone())?),
+    })
+}
+
+/// Archive kept as files on a dataset outside the stores' own
+#[derive(Debug)]
+pub struct DatasetArchive {
+    root: PathBuf,
+}
+
+impl DatasetArchive {
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    fn path(&self, key: &str) -> Result<PathBuf, GuardianError> {
+        if key.split('/').any(|part| part.is_empty() || part == "." || part == "..") {
+            return Err(retention_error(format!("Invalid archive key {}", key), None));
+        }
+        Ok(self.root.join(key))
+    }
+}
+
+#[async_trait]
+impl ArchiveStore for DatasetArchive {
+    async fn put(&self, key: &str, data: Vec<u8>) -> Result<(), GuardianError> {
+        let path = self.path(key)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(|e| retention_error(format!("Failed to create {}", parent.display()), Some(Box::new(e))))?;
+        }
+        // Written aside and renamed so a crash never leaves a truncated archive
+        let staging = path.with_extension("partial");
+        tokio::fs::write(&staging, data)
+            .await
+            .map_err(|e| retention_error(format!("Failed to archive {}", key), Some(Box::new(e))))?;
+        tokio::fs::rename(&staging, &path)
+            .await
+            .map_err(|e| retention_error(format!("Failed to archive {}", key), Some(Box::new(e))))
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, GuardianError> {
+        match tokio::fs::read(self.path(key)?).await {
+            Ok(data) => Ok(Some(data)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(retention_error(format!("Failed to read archived {}", key), Some(Box::new(e)))),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), GuardianError> {
+        match tokio::fs::remove_file(self.path(key)?).await {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(retention_error(format!("Failed to delete archived {}", key), Some(Box::new(e))))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Archive in an HTTP object store
+#[derive(Debug)]
+pub struct ObjectStoreArchive {
+    client: reqwest::Client,
+    endpoint: String,
+    token: Option<String>,
+}
+
+impl ObjectStoreArchive {
+    pub fn new(endpoint: &str, token: Option<String>) -> Result<Self, GuardianError> {
+        let client = reqwest::Client::builder()
+            .timeout(OBJECT_STORE_TIMEOUT)
+            .build()
+            .map_err(|e| retention_error("Failed to create object store client".into(), Some(Box::new(e))))?;
+        Ok(Self {
+            client,
+            endpoint: endpoint.trim_end_matches('/').to_string(),
+            token,
+        })
+    }
+
+    fn request(&self, method: reqwest::Method, key: &str) -> reqwest::RequestBuilder {
+        let request = self.client.request(method, format!("{}/{}", self.endpoint, key));
+        match &self.token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        }
+    }
+
+    async fn send(&self, request: reqwest::RequestBuilder, key: &str) -> Result<reqwest::Response, GuardianError> {
+        request
+            .send()
+            .await
+            .map_err(|e| retention_error(format!("Object store request for {} failed", key), Some(Box::new(e))))
+    }
+}
+
+#[async_trait]
+impl ArchiveStore for ObjectStoreArchive {
+    async fn put(&self, key: &str, data: Vec<u8>) -> Result<(), GuardianError> {
+        self.send(self.request(reqwest::Method::PUT, key).body(data), key)
+            .await?
+            .error_for_status()
+            .map_err(|e| retention_error(format!("Failed to archive {}", key), Some(Box::new(e))))?;
+        debug!(key, "Partition archived to object store");
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, GuardianError> {
+        let response = self.send(self.request(reqwest::Method::GET, key), key).await?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        let body = response
+            .error_for_status()
+            .map_err(|e| retention_error(format!("Failed to read archived {}", key), Some(Box::new(e))))?
+            .bytes()
+            .await
+            .map_err(|e| retention_error(format!("Failed to read archived {}", key), Some(Box::new(e))))?;
+        Ok(Some(body.to_vec()))
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), GuardianError> {
+        let response = self.send(self.request(reqwest::Method::DELETE, key), key).await?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(());
+        }
+        response
+            .error_for_status()
+            .map_err(|e| retention_error(format!("Failed to delete archived {}", key), Some(Box::new(e))))?;
+        Ok(())
+    }
+}
+
+fn retention_error(context: String, source: Option<Box<dyn std::error::Error + Send + Sync>>) -> GuardianError {
+    GuardianError::StorageError {
+        context,
+        source,
+        severity: crate::utils::error::ErrorSeverity::Medium,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: ErrorCategory::Storage,
+        retry_count: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_tiers_and_dataset_archive() {
+        let policy = TierPolicy::new(TierAges { hot_days: 7, warm_days: 30, archive_days: 365 }, 19, true);
+        assert_eq!(policy.tier_for(0), Some(RetentionTier::Hot));
+        assert_eq!(policy.tier_for(7), Some(RetentionTier::Warm));
+        assert_eq!(policy.tier_for(30), Some(RetentionTier::Archive));
+        assert_eq!(policy.tier_for(365), None);
+        assert_eq!(TierPolicy::delete_after(90, 6).tier_for(90), None);
+
+        let data = br#"[{"name":"cpu"}]"#.to_vec();
+        assert_eq!(policy.encode(data.clone(), RetentionTier::Hot).unwrap(), data);
+        let warm = policy.encode(data.clone(), RetentionTier::Warm).unwrap();
+        assert!(is_compressed(&warm));
+        assert_eq!(decode(warm.clone()).unwrap(), data);
+
+        let dir = tempfile::tempdir().unwrap();
+        let archive = DatasetArchive::new(dir.path().to_path_buf());
+        archive.put("metrics/2024-01-01", warm).await.unwrap();
+        assert_eq!(decode(archive.get("metrics/2024-01-01").await.unwrap().unwrap()).unwrap(), data);
+        archive.delete("metrics/2024-01-01").await.unwrap();
+        assert_eq!(archive.get("metrics/2024-01-01").await.unwrap(), None);
+        assert!(archive.put("../escape", Vec::new()).await.is_err());
+    }
+}
@@ -0,0 +1,359 @@
+use std::{
+    collections::VecDeque,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use metrics::{counter, gauge, histogram};
+use parking_lot::Mutex;
+use tokio::sync::{oneshot, Notify};
+use tracing::{debug, instrument, warn};
+
+use crate::core::event_bus::EventPriority;
+use crate::security::threat_detection::ThreatLevel;
+use crate::utils::correlation;
+use crate::utils::error::GuardianError;
+use crate::utils::queue_metrics::{queue_registry, QueueMonitor};
+
+// Constants for write coalescing
+const MAX_COALESCED_WRITES: usize = 256;
+const MAX_QUEUED_WRITES: usize = 16_384;
+const STARVATION_THRESHOLD: Duration = Duration::from_secs(2);
+const FAIR_SHARE_INTERVAL: u32 = 8;
+
+/// Priority of a storage write, highest first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum WritePriority {
+    /// Forensic data of critical detections
+    Critical = 0,
+    High = 1,
+    Normal = 2,
+    /// Background batch flushes
+    Batch = 3,
+}
+
+impl WritePriority {
+    const ALL: [WritePriority; 4] = [
+        WritePriority::Critical,
+        WritePriority::High,
+        WritePriority::Normal,
+        WritePriority::Batch,
+    ];
+
+    fn label(&self) -> &'static str {
+        match self {
+            WritePriority::Critical => "critical",
+            WritePriority::High => "high",
+            WritePriority::Normal => "normal",
+            WritePriority::Batch => "batch",
+        }
+    }
+}
+
+impl From<ThreatLevel> for WritePriority {
+    fn from(level: ThreatLevel) -> Self {
+        match level {
+            ThreatLevel::Critical => WritePriority::Critical,
+            ThreatLevel::High => WritePriority::High,
+            ThreatLevel::Medium | ThreatLevel::Low => WritePriority::Normal,
+        }
+    }
+}
+
+impl From<EventPriority> for WritePriority {
+    fn from(priority: EventPriority) -> Self {
+        match priority {
+            EventPriority::Critical => WritePriority::Critical,
+            EventPriority::High => WritePriority::High,
+            EventPriority::Medium => WritePriority::Normal,
+            EventPriority::Low => WritePriority::Batch,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct PendingWrite {
+    partition: String,
+    data: Vec<u8>,
+    enqueued_at: Instant,
+    correlation_id: uuid::Uuid,
+    done: oneshot::Sender<Result<(), GuardianError>>,
+}
+
+/// Group of queued writes to the same partition, flushed together
+#[derive(Debug)]
+pub struct WriteBatch {
+    pub partition: String,
+    pub priority: WritePriority,
+    pub records: Vec<Vec<u8>>,
+    /// Writer correlation IDs, so a failed flush is reported under each writer's request
+    waiters: Vec<(uuid::Uuid, oneshot::Sender<Result<(), GuardianError>>)>,
+}
+
+impl WriteBatch {
+    /// Reports the flush outcome to every writer in the batch
+    pub fn complete(self, result: &Result<(), GuardianError>) {
+        for (correlation_id, waiter) in self.waiters {
+            let outcome = match result {
+                Ok(()) => Ok(()),
+                Err(e) => Err(GuardianError::StorageError {
+                    context: format!("Coalesced write to {} failed: {}", self.partition, e),
+                    source: None,
+                    severity: crate::utils::error::ErrorSeverity::High,
+                    timestamp: time::OffsetDateTime::now_utc(),
+                    correlation_id,
+                    category: crate::utils::error::ErrorCategory::Storage,
+                    retry_count: 0,
+                }),
+            };
+            let _ = waiter.send(outcome);
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct PriorityQueues {
+    queues: [VecDeque<PendingWrite>; 4],
+    /// Consecutive batches served ahead of a waiting lower priority
+    preemptions: u32,
+}
+
+impl PriorityQueues {
+    fn len(&self) -> usize {
+        self.queues.iter().map(VecDeque::len).sum()
+    }
+
+    fn oldest(&self) -> Option<Instant> {
+        self.queues.iter().filter_map(|q| q.front()).map(|w| w.enqueued_at).min()
+    }
+
+    /// Picks the queue to serve next, letting starved lower priorities through
+    fn select(&mut self, now: Instant) -> Option<WritePriority> {
+        let highest = WritePriority::ALL
+            .into_iter()
+            .find(|p| !self.queues[*p as usize].is_empty())?;
+
+        let starved = WritePriority::ALL
+            .into_iter()
+            .rev()
+            .filter(|p| *p > highest)
+            .find(|p| {
+                self.queues[*p as usize]
+                    .front()
+                    .map_or(false, |w| now.saturating_duration_since(w.enqueued_at) >= STARVATION_THRESHOLD)
+            });
+
+        let waiting_lower = WritePriority::ALL
+            .into_iter()
+            .filter(|p| *p > highest)
+            .find(|p| !self.queues[*p as usize].is_empty());
+
+        let selected = match (starved, waiting_lower) {
+            (Some(starved), _) => {
+                counter!("guardian.storage.write_coalescer.starvation_promotions", 1, "priority" => starved.label());
+                starved
+            }
+            (None, Some(lower)) if self.preemptions >= FAIR_SHARE_INTERVAL => lower,
+            _ => highest,
+        };
+
+        if selected == highest && waiting_lower.is_some() {
+            self.preemptions += 1;
+        } else {
+            self.preemptions = 0;
+        }
+        Some(selected)
+    }
+
+    /// Drains up to `MAX_COALESCED_WRITES` writes for the head partition of a queue
+    fn take_batch(&mut self, priority: WritePriority) -> Option<WriteBatch> {
+        let queue = &mut self.queues[priority as usize];
+        let partition = queue.front()?.partition.clone();
+
+        let mut records = Vec::new();
+        let mut waiters = Vec::new();
+        let mut remaining = VecDeque::with_capacity(queue.len());
+
+        while let Some(write) = queue.pop_front() {
+            if write.partition == partition && records.len() < MAX_COALESCED_WRITES {
+                records.push(write.data);
+                waiters.push((write.correlation_id, write.done));
+            } else {
+                remaining.push_back(write);
+            }
+        }
+        *queue = remaining;
+
+        Some(WriteBatch { partition, priority, records, waiters })
+    }
+}
+
+/// Orders and coalesces storage writes so critical forensic data jumps ahead of batch flushes
+#[derive(Debug)]
+pub struct WriteCoalescer {
+    queues: Mutex<PriorityQueues>,
+    notify: Notify,
+    monitor: Arc<QueueMonitor>,
+}
+
+impl Default for WriteCoalescer {
+    fn default() -> Self {
+        Self {
+            queues: Mutex::new(PriorityQueues::default()),
+            notify: Notify::new(),
+            monitor: queue_registry().register(QueueMonitor::new("storage_writes", MAX_QUEUED_WRITES)),
+        }
+    }
+}
+
+impl WriteCoalescer {
+    /// Creates an empty write coalescer
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queues a write and waits until the batch containing it has been flushed
+    #[instrument(skip(self, data), fields(bytes = data.len()))]
+    pub async fn submit(
+        &self,
+        partition: &str,
+        data: Vec<u8>,
+        priority: WritePriority,
+    ) -> Result<(), GuardianError> {
+        let (done, rx) = oneshot::channel();
+        let enqueued_at = Instant::now();
+        {
+            let mut queues = self.queues.lock();
+            if queues.len() >= MAX_QUEUED_WRITES && priority > WritePriority::High {
+                warn!(priority = priority.label(), "Write queue full, rejecting write");
+                return Err(GuardianError::StorageError {
+                    context: format!("Write queue limit of {} reached", MAX_QUEUED_WRITES),
+                    source: None,
+                    severity: crate::utils::error::ErrorSeverity::Medium,
+                    timestamp: time::OffsetDateTime::now_utc(),
+                    correlation_id: crate::utils::correlation::current_or_new(),
+                    category: crate::utils::error::ErrorCategory::Storage,
+                    retry_count: 0,
+                });
+            }
+            queues.queues[priority as usize].push_back(PendingWrite {
+                partition: partition.to_string(),
+                data,
+                enqueued_at,
+                correlation_id: correlation::current_or_new(),
+                done,
+            });
+            gauge!("guardian.storage.write_coalescer.queued", queues.len() as f64);
+            self.monitor.record_enqueue();
+            self.monitor.observe(queues.len(), queues.oldest());
+        }
+        counter!("guardian.storage.write_coalescer.submitted", 1, "priority" => priority.label());
+        self.notify.notify_one();
+
+        let result = rx.await.map_err(|_| GuardianError::StorageError {
+            context: "Write coalescer dropped pending write".into(),
+            source: None,
+            severity: crate::utils::error::ErrorSeverity::High,
+            timestamp: time::OffsetDateTime::now_utc(),
+            correlation_id: crate::utils::correlation::current_or_new(),
+            category: crate::utils::error::ErrorCategory::Storage,
+            retry_count: 0,
+        })?;
+
+        histogram!(
+            "guardian.storage.write_coalescer.latency",
+            enqueued_at.elapsed().as_secs_f64(),
+            "priority" => priority.label()
+        );
+        result
+    }
+
+    /// Waits for the next batch to flush, chosen by priority with starvation protection
+    pub async fn next_batch(&self) -> WriteBatch {
+        loop {
+            let notified = self.notify.notified();
+            if let Some(batch) = self.try_next_batch(Instant::now()) {
+                return batch;
+            }
+            notified.await;
+        }
+    }
+
+    fn try_next_batch(&self, now: Instant) -> Option<WriteBatch> {
+        let mut queues = self.queues.lock();
+        let priority = queues.select(now)?;
+        let waited = queues.queues[priority as usize]
+            .front()
+            .map(|w| now.saturating_duration_since(w.enqueued_at));
+        let batch = queues.take_batch(priority)?;
+
+        gauge!("guardian.storage.write_coalescer.queued", queues.len() as f64);
+        if let Some(waited) = waited {
+            self.monitor.record_wait(waited);
+        }
+        self.monitor.observe(queues.len(), queues.oldest());
+        histogram!("guardian.storage.write_coalescer.batch_size", batch.records.len() as f64);
+        debug!(
+            partition = %batch.partition,
+            priority = priority.label(),
+            records = batch.records.len(),
+            "Flushing coalesced writes"
+        );
+        Some(batch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn enqueue(coalescer: &WriteCoalescer, partition: &str, priority: WritePriority, enqueued_at: Instant) {
+        let (done, _rx) = oneshot::channel();
+        coalescer.queues.lock().queues[priority as usize].push_back(PendingWrite {
+            partition: partition.to_string(),
+            data: vec![0u8; 8],
+            enqueued_at,
+            correlation_id: uuid::Uuid::nil(),
+            done,
+        });
+    }
+
+    #[test]
+    fn test_critical_writes_jump_ahead_and_coalesce() {
+        let coalescer = WriteCoalescer::new();
+        let now = Instant::now();
+        enqueue(&coalescer, "events_1", WritePriority::Batch, now);
+        enqueue(&coalescer, "forensics_1", WritePriority::Critical, now);
+        enqueue(&coalescer, "forensics_1", WritePriority::Critical, now);
+
+        let batch = coalescer.try_next_batch(now).unwrap();
+        assert_eq!(batch.priority, WritePriority::Critical);
+        assert_eq!(batch.records.len(), 2);
+
+        let batch = coalescer.try_next_batch(now).unwrap();
+        assert_eq!(batch.priority, WritePriority::Batch);
+        assert!(coalescer.try_next_batch(now).is_none());
+    }
+
+    #[test]
+    fn test_starvation_protection() {
+        let coalescer = WriteCoalescer::new();
+        let now = Instant::now();
+        enqueue(&coalescer, "events_1", WritePriority::Batch, now - STARVATION_THRESHOLD);
+        enqueue(&coalescer, "forensics_1", WritePriority::Critical, now);
+
+        // Aged batch write is served despite pending critical writes
+        assert_eq!(coalescer.try_next_batch(now).unwrap().priority, WritePriority::Batch);
+
+        // Fresh low priority writes still get a fair share under sustained critical load
+        enqueue(&coalescer, "events_2", WritePriority::Batch, now);
+        for i in 0..FAIR_SHARE_INTERVAL {
+            enqueue(&coalescer, &format!("forensics_{}", i + 2), WritePriority::Critical, now);
+        }
+        let served: Vec<WritePriority> = (0..=FAIR_SHARE_INTERVAL + 1)
+            .filter_map(|_| coalescer.try_next_batch(now).map(|b| b.priority))
+            .collect();
+        let batch_position = served.iter().position(|p| *p == WritePriority::Batch).unwrap();
+        assert!(batch_position <= FAIR_SHARE_INTERVAL as usize);
+    }
+}
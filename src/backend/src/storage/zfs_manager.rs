@@ -1,17 +1,30 @@
 use async_trait::async_trait;
 use libc::{c_int, c_void};
+use metrics::{counter, histogram};
+use ring::digest;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::{
     collections::HashMap,
-    path::PathBuf,
+    path::{Path, PathBuf},
+    process::Stdio,
     sync::Arc,
-    time::Duration,
+    time::{Duration, Instant},
+};
+use tokio::{
+    io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
+    process::Command,
+    sync::Mutex,
 };
-use tokio::sync::Mutex;
 use tracing::{debug, error, info, instrument, warn};
 
+use crate::core::guardian::TenantContext;
 use crate::utils::error::{GuardianError, ErrorCategory};
 use crate::utils::logging::LogManager;
+use crate::config::storage_config::{BackgroundJobClass, IoBudgetConfig, StorageConfig};
+use crate::security::key_provider::{KeyMaterial, KeyProvider, KeyRotationParticipant};
+use crate::storage::io_budget::IoBudget;
+use crate::storage::io_throttle::IoThrottler;
 
 // Constants for ZFS configuration and security
 const DEFAULT_COMPRESSION: &str = "lz4";
@@ -19,6 +32,17 @@ const MAX_POOL_NAME_LENGTH: usize = 255;
 const ENCRYPTION_TYPE: &str = "aes-256-gcm";
 const DEFAULT_RETENTION_DAYS: u32 = 90;
 const SECURE_DATASET_PROPS: &[&str] = &["encryption", "compression", "readonly"];
+const BACKUP_SNAPSHOT_PREFIX: &str = "backup-";
+const BACKUP_STREAM_EXTENSION: &str = "zstream";
+const BACKUP_MANIFEST_EXTENSION: &str = "json";
+const BACKUP_CHUNK_SIZE: usize = 1024 * 1024;
+const KSTAT_ROOT: &str = "/proc/spl/kstat/zfs";
+
+/// Version of the backup manifest format, bumped on incompatible changes
+pub const BACKUP_MANIFEST_VERSION: u32 = 1;
+
+/// Key protecting the root dataset, as named in the key provider
+pub const STORAGE_KEY_ID: &str = "zfs-root";
 
 /// Encryption configuration for ZFS datasets
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -46,16 +70,185 @@ impl Default for RetentionPolicy {
     }
 }
 
+/// Destination of a `zfs send` stream
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BackupTarget {
+    /// Directory holding stream files and their manifests
+    Directory(PathBuf),
+    /// Dataset the stream is received into, e.g. on a replication pool
+    Dataset(String),
+}
+
+/// Options controlling a backup or restore
+#[derive(Debug, Clone)]
+pub struct BackupOptions {
+    /// Send only the changes since the last snapshot both sides have
+    pub incremental: bool,
+    /// Upper bound on stream throughput, on top of the background I/O throttle
+    pub bandwidth_limit_bytes_per_sec: Option<u64>,
+    /// Verify stream checksums after writing and before receiving
+    pub verify: bool,
+}
+
+impl Default for BackupOptions {
+    fn default() -> Self {
+        Self {
+            incremental: true,
+            bandwidth_limit_bytes_per_sec: None,
+            verify: true,
+        }
+    }
+}
+
+/// Record of one backup stream, stored next to the stream file
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct BackupManifest {
+    pub dataset: String,
+    pub snapshot: String,
+    /// Snapshot the stream is incremental from, `None` for full streams
+    pub base_snapshot: Option<String>,
+    pub bytes: u64,
+    pub sha256: String,
+    pub created_at: i64,
+}
+
+/// Snapshot of a dataset as reported by `zfs list`
+#[derive(Debug, Clone)]
+struct SnapshotInfo {
+    name: String,
+    creation_time: i64,
+    /// Space only this snapshot holds, freed when it is destroyed
+    used: u64,
+}
+
+/// State of a pool or vdev as reported by `zpool status`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VdevState {
+    Online,
+    Degraded,
+    Faulted,
+    Offline,
+    Unavail,
+    Removed,
+    Unknown(String),
+}
+
+impl VdevState {
+    fn parse(state: &str) -> Self {
+        match state {
+            "ONLINE" => VdevState::Online,
+            "DEGRADED" => VdevState::Degraded,
+            "FAULTED" => VdevState::Faulted,
+            "OFFLINE" => VdevState::Offline,
+            "UNAVAIL" => VdevState::Unavail,
+            "REMOVED" => VdevState::Removed,
+            other => VdevState::Unknown(other.to_string()),
+        }
+    }
+
+    /// Whether the vdev has lost redundancy or is out of service
+    pub fn is_impaired(&self) -> bool {
+        matches!(self, VdevState::Degraded | VdevState::Faulted | VdevState::Unavail | VdevState::Removed)
+    }
+}
+
+/// Progress or outcome of the most recent scrub or resilver
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "scan", rename_all = "snake_case")]
+pub enum ScanStatus {
+    None,
+    InProgress { percent_done: f64 },
+    Completed { errors: u64 },
+    Canceled,
+}
+
+/// Error counters and state of one vdev
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct VdevStatus {
+    pub name: String,
+    pub state: VdevState,
+    pub read_errors: u64,
+    pub write_errors: u64,
+    pub checksum_errors: u64,
+}
+
+/// Parsed `zpool status` of a pool
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PoolStatus {
+    pub pool: String,
+    pub state: VdevState,
+    pub scan: ScanStatus,
+    /// The pool itself first, then its vdevs and devices in tree order
+    pub vdevs: Vec<VdevStatus>,
+}
+
+impl PoolStatus {
+    /// Total checksum errors across all devices, excluding the pool's own roll-up row
+    pub fn checksum_errors(&self) -> u64 {
+        self.vdevs.iter().skip(1).map(|v| v.checksum_errors).sum()
+    }
+
+    /// Vdevs that have lost redundancy or are out of service
+    pub fn impaired_vdevs(&self) -> impl Iterator<Item = &VdevStatus> {
+        self.vdevs.iter().filter(|v| v.state.is_impaired())
+    }
+}
+
+/// Space accounting of one dataset, in bytes
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DatasetUsage {
+    pub dataset: String,
+    pub used: u64,
+    pub available: u64,
+    pub quota: Option<u64>,
+    pub reservation: Option<u64>,
+}
+
+impl DatasetUsage {
+    /// Capacity usage counts against: the quota if set, otherwise what the pool can still give
+    pub fn limit(&self) -> u64 {
+        self.quota.unwrap_or(self.used + self.available)
+    }
+
+    pub fn percent_used(&self) -> f64 {
+        match self.limit() {
+            0 => 0.0,
+            limit => self.used as f64 * 100.0 / limit as f64,
+        }
+    }
+}
+
+/// Cumulative I/O counters of one dataset since it was mounted
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct DatasetIoStats {
+    pub reads: u64,
+    pub read_bytes: u64,
+    pub writes: u64,
+    pub written_bytes: u64,
+}
+
+/// A direct child of a dataset, such as one time partition of the events dataset
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ChildDataset {
+    pub name: String,
+    pub used: u64,
+    pub creation_time: i64,
+}
+
 /// Core ZFS management structure
 #[derive(Debug)]
 pub struct ZfsManager {
     pool_name: String,
     root_dataset: String,
-    encryption_key: Arc<[u8]>,
+    encryption_key: parking_lot::RwLock<Arc<[u8]>>,
     compression_enabled: bool,
     logger: Arc<LogManager>,
     retention_policy: RetentionPolicy,
     dataset_cache: Arc<Mutex<HashMap<String, DatasetInfo>>>,
+    io_throttler: Arc<IoThrottler>,
+    io_budget: Arc<IoBudget>,
+    tenant: TenantContext,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -79,20 +272,92 @@ impl ZfsManager {
     ) -> Result<Self, GuardianError> {
         validate_pool_name(&pool_name)?;
 
+        let io_throttler = Arc::new(IoThrottler::default());
+        io_throttler.follow_governor();
+        let io_budget = Arc::new(IoBudget::new(IoBudgetConfig::default(), Arc::clone(&io_throttler)));
         let manager = Self {
             pool_name: pool_name.clone(),
             root_dataset: format!("{}/guardian", pool_name),
-            encryption_key: Arc::from(encryption_key),
+            encryption_key: parking_lot::RwLock::new(Arc::from(encryption_key)),
             compression_enabled: true,
             logger,
             retention_policy: retention_policy.unwrap_or_default(),
             dataset_cache: Arc::new(Mutex::new(HashMap::new())),
+            io_throttler,
+            io_budget,
+            tenant: TenantContext::default(),
         };
 
         manager.init_pool().await?;
         Ok(manager)
     }
 
+    /// Creates a ZFS manager whose root dataset key comes from a key provider
+    pub async fn from_key_provider(
+        pool_name: String,
+        provider: &dyn KeyProvider,
+        logger: Arc<LogManager>,
+        retention_policy: Option<RetentionPolicy>,
+    ) -> Result<Self, GuardianError> {
+        let key = provider.current_key(STORAGE_KEY_ID).await?;
+        info!(provider = provider.kind(), version = key.version, "Loaded storage encryption key");
+        Self::new(pool_name, key.bytes().to_vec(), logger, retention_policy).await
+    }
+
+    /// Applies the background I/O limits and the write budget of the storage configuration
+    ///
+    /// The budget drives the write scale of the same throttler, so every store sharing this
+    /// manager is held to one set of limits.
+    pub fn with_io_config(mut self, config: &StorageConfig) -> Self {
+        let io_throttler = Arc::new(IoThrottler::new(&config.background_io, &config.io_budget.write_limits));
+        io_throttler.follow_governor();
+        self.io_budget = Arc::new(IoBudget::new(config.io_budget.clone(), Arc::clone(&io_throttler)));
+        self.io_throttler = io_throttler;
+        self
+    }
+
+    /// Returns the throttler background storage jobs must go through
+    pub fn io_throttler(&self) -> Arc<IoThrottler> {
+        Arc::clone(&self.io_throttler)
+    }
+
+    /// Returns the budget model reads report their latency to
+    pub fn io_budget(&self) -> Arc<IoBudget> {
+        Arc::clone(&self.io_budget)
+    }
+
+    /// Moves this manager's datasets into the tenant's subtree, creating it if needed
+    pub async fn with_tenant(mut self, tenant: TenantContext) -> Result<Self, GuardianError> {
+        if !tenant.is_default() {
+            let base = format!("{}/guardian", self.pool_name);
+            let tenants_root = format!("{}/tenants", base);
+            if self.get_dataset_info(&tenants_root).await.is_err() {
+                self.create_dataset(&tenants_root, None, None).await?;
+            }
+
+            self.root_dataset = tenant.dataset(&base);
+            info!(tenant = %tenant.tenant_id(), dataset = %self.root_dataset, "Using tenant dataset subtree");
+            self.init_pool().await?;
+        }
+        self.tenant = tenant;
+        Ok(self)
+    }
+
+    /// Returns the tenant whose dataset subtree this manager writes to
+    pub fn tenant(&self) -> &TenantContext {
+        &self.tenant
+    }
+
+    /// Returns the pool the managed datasets live in
+    pub fn pool_name(&self) -> &str {
+        &self.pool_name
+    }
+
+    /// Returns the root dataset all managed datasets live under
+    pub fn root_dataset(&self) -> &str {
+        &self.root_dataset
+    }
+
     /// Initializes the ZFS storage pool with security features
     #[instrument(skip(self))]
     async fn init_pool(&self) -> Result<(), GuardianError> {
@@ -105,7 +370,7 @@ impl ZfsManager {
                 source: None,
                 severity: crate::utils::error::ErrorSeverity::Critical,
                 timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id: uuid::Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: ErrorCategory::Storage,
                 retry_count: 0,
             });
@@ -178,7 +443,7 @@ impl ZfsManager {
             source: Some(Box::new(e)),
             severity: crate::utils::error::ErrorSeverity::High,
             timestamp: time::OffsetDateTime::now_utc(),
-            correlation_id: uuid::Uuid::new_v4(),
+            correlation_id: crate::utils::correlation::current_or_new(),
             category: ErrorCategory::Storage,
             retry_count: 0,
         })?;
@@ -190,7 +455,7 @@ impl ZfsManager {
                 source: None,
                 severity: crate::utils::error::ErrorSeverity::High,
                 timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id: uuid::Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: ErrorCategory::Storage,
                 retry_count: 0,
             });
@@ -215,7 +480,15 @@ impl ZfsManager {
         let retention = retention.unwrap_or_else(|| self.retention_policy.clone());
         let full_snapshot_name = format!("{}@{}", dataset, snapshot_name);
 
-        // Create snapshot
+        // Create snapshot, charged with the data it pins since the previous one
+        let written = zfs_query(
+            &["get", "-H", "-p", "-o", "value", "written", dataset],
+            &format!("read written bytes of {}", dataset),
+        )
+        .ok()
+        .and_then(|value| value.trim().parse().ok())
+        .unwrap_or_default();
+        self.io_throttler.acquire(BackgroundJobClass::Snapshot, written).await;
         let output = std::process::Command::new("zfs")
             .args(["snapshot", &full_snapshot_name])
             .output()
@@ -224,7 +497,7 @@ impl ZfsManager {
                 source: Some(Box::new(e)),
                 severity: crate::utils::error::ErrorSeverity::Medium,
                 timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id: uuid::Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: ErrorCategory::Storage,
                 retry_count: 0,
             })?;
@@ -236,7 +509,7 @@ impl ZfsManager {
                 source: None,
                 severity: crate::utils::error::ErrorSeverity::Medium,
                 timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id: uuid::Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: ErrorCategory::Storage,
                 retry_count: 0,
             });
@@ -267,6 +540,7 @@ impl ZfsManager {
         // Remove excess snapshots
         while snapshots.len() > policy.max_snapshots as usize {
             let snapshot = snapshots.remove(0);
+            self.io_throttler.acquire(BackgroundJobClass::Retention, snapshot.used).await;
             if let Err(e) = self.destroy_snapshot(&snapshot.name).await {
                 warn!("Failed to remove snapshot {}: {:?}", snapshot.name, e);
             }
@@ -275,6 +549,312 @@ impl ZfsManager {
         Ok(())
     }
 
+    /// Backs up a dataset by snapshotting it and sending the snapshot to the target.
+    /// Incremental backups send from the newest snapshot the target already holds.
+    #[instrument(skip(self, options))]
+    pub async fn backup_dataset(
+        &self,
+        dataset: &str,
+        target: &BackupTarget,
+        options: &BackupOptions,
+    ) -> Result<BackupManifest, GuardianError> {
+        let start = Instant::now();
+        let snapshot = format!(
+            "{}{}",
+            BACKUP_SNAPSHOT_PREFIX,
+            time::OffsetDateTime::now_utc().unix_timestamp_nanos() / 1_000_000
+        );
+
+        // Look up the base before snapshotting so the new snapshot is never its own base
+        let base_snapshot = if options.incremental {
+            let source = snapshot_names(dataset, &self.list_snapshots(dataset).await?);
+            let existing = match target {
+                BackupTarget::Directory(dir) => read_manifests(dir, dataset)
+                    .await?
+                    .into_iter()
+                    .map(|m| m.snapshot)
+                    .collect(),
+                BackupTarget::Dataset(target_dataset) => match self.list_snapshots(target_dataset).await {
+                    Ok(snapshots) => snapshot_names(target_dataset, &snapshots),
+                    Err(_) => Vec::new(),
+                },
+            };
+            last_common_snapshot(&source, &existing)
+        } else {
+            None
+        };
+
+        self.snapshot_dataset(dataset, &snapshot, None).await?;
+
+        let mut cmd = Command::new("zfs");
+        cmd.args(["send", "-w"]);
+        if let Some(base) = &base_snapshot {
+            cmd.args(["-i", &format!("{}@{}", dataset, base)]);
+        }
+        cmd.arg(format!("{}@{}", dataset, snapshot));
+        let mut send = spawn_zfs(cmd, Stdio::null(), Stdio::piped())?;
+        let mut stream = send.stdout.take().ok_or_else(|| backup_error("zfs send produced no output stream".into(), None))?;
+
+        let (bytes, sha256) = match target {
+            BackupTarget::Directory(dir) => {
+                tokio::fs::create_dir_all(dir)
+                    .await
+                    .map_err(|e| backup_error(format!("Failed to create backup directory {}", dir.display()), Some(Box::new(e))))?;
+                let path = stream_path(dir, dataset, &snapshot);
+                let mut file = tokio::fs::File::create(&path)
+                    .await
+                    .map_err(|e| backup_error(format!("Failed to create backup file {}", path.display()), Some(Box::new(e))))?;
+                let copied = self.copy_stream(&mut stream, &mut file, options).await?;
+                file.sync_all()
+                    .await
+                    .map_err(|e| backup_error(format!("Failed to sync backup file {}", path.display()), Some(Box::new(e))))?;
+                copied
+            }
+            BackupTarget::Dataset(target_dataset) => {
+                let mut cmd = Command::new("zfs");
+                cmd.args(["receive", "-F", "-u", target_dataset]);
+                let mut receive = spawn_zfs(cmd, Stdio::piped(), Stdio::null())?;
+                let mut stdin = receive.stdin.take().ok_or_else(|| backup_error("zfs receive has no input stream".into(), None))?;
+                let copied = self.copy_stream(&mut stream, &mut stdin, options).await?;
+                drop(stdin);
+                wait_zfs(receive, "receive").await?;
+                copied
+            }
+        };
+        wait_zfs(send, "send").await?;
+
+        let manifest = BackupManifest {
+            dataset: dataset.to_string(),
+            snapshot,
+            base_snapshot,
+            bytes,
+            sha256,
+            created_at: time::OffsetDateTime::now_utc().unix_timestamp(),
+        };
+
+        if options.verify {
+            self.verify_backup(target, &manifest).await?;
+        }
+
+        if let BackupTarget::Directory(dir) = target {
+            let path = manifest_path(dir, dataset, &manifest.snapshot);
+            let json = serde_json::to_vec_pretty(&manifest)
+                .map_err(|e| backup_error("Failed to serialize backup manifest".into(), Some(Box::new(e))))?;
+            tokio::fs::write(&path, json)
+                .await
+                .map_err(|e| backup_error(format!("Failed to write backup manifest {}", path.display()), Some(Box::new(e))))?;
+        }
+
+        let kind = if manifest.base_snapshot.is_some() { "incremental" } else { "full" };
+        counter!("guardian.storage.backup.completed", 1, "kind" => kind);
+        counter!("guardian.storage.backup.bytes", manifest.bytes, "kind" => kind);
+        histogram!("guardian.storage.backup.duration", start.elapsed().as_secs_f64());
+        info!(
+            dataset,
+            snapshot = %manifest.snapshot,
+            base = ?manifest.base_snapshot,
+            bytes = manifest.bytes,
+            "Backup completed"
+        );
+        Ok(manifest)
+    }
+
+    /// Restores a dataset from the directory backups of `dataset`, replaying the
+    /// incremental chain up to `snapshot`, or the newest backup if none is given
+    #[instrument(skip(self, options))]
+    pub async fn restore_dataset(
+        &self,
+        backup_dir: &Path,
+        dataset: &str,
+        snapshot: Option<&str>,
+        target_dataset: &str,
+        options: &BackupOptions,
+    ) -> Result<BackupManifest, GuardianError> {
+        let manifests = read_manifests(backup_dir, dataset).await?;
+        let chain = restore_chain(&manifests, snapshot).ok_or_else(|| {
+            backup_error(
+                format!(
+                    "No complete backup chain for {}@{} in {}",
+                    dataset,
+                    snapshot.unwrap_or("latest"),
+                    backup_dir.display()
+                ),
+                None,
+            )
+        })?;
+
+        for (index, manifest) in chain.iter().enumerate() {
+            let path = stream_path(backup_dir, dataset, &manifest.snapshot);
+            if options.verify {
+                verify_stream_file(&path, manifest).await?;
+            }
+
+            let mut file = tokio::fs::File::open(&path)
+                .await
+                .map_err(|e| backup_error(format!("Failed to open backup file {}", path.display()), Some(Box::new(e))))?;
+            let mut cmd = Command::new("zfs");
+            // Only the full stream may replace an existing target dataset
+            if index == 0 {
+                cmd.args(["receive", "-F", "-u", target_dataset]);
+            } else {
+                cmd.args(["receive", "-u", target_dataset]);
+            }
+            let mut receive = spawn_zfs(cmd, Stdio::piped(), Stdio::null())?;
+            let mut stdin = receive.stdin.take().ok_or_else(|| backup_error("zfs receive has no input stream".into(), None))?;
+            self.copy_stream(&mut file, &mut stdin, options).await?;
+            drop(stdin);
+            wait_zfs(receive, "receive").await?;
+
+            debug!(snapshot = %manifest.snapshot, target_dataset, "Backup stream received");
+        }
+
+        let restored = chain.last().cloned().expect("restore chain is never empty");
+        counter!("guardian.storage.restore.completed", 1);
+        info!(dataset, target_dataset, snapshot = %restored.snapshot, streams = chain.len(), "Restore completed");
+        Ok(restored)
+    }
+
+    /// Checks that a finished backup is intact on its target
+    async fn verify_backup(&self, target: &BackupTarget, manifest: &BackupManifest) -> Result<(), GuardianError> {
+        match target {
+            BackupTarget::Directory(dir) => {
+                verify_stream_file(&stream_path(dir, &manifest.dataset, &manifest.snapshot), manifest).await
+            }
+            BackupTarget::Dataset(target_dataset) => {
+                let snapshots = snapshot_names(target_dataset, &self.list_snapshots(target_dataset).await?);
+                if snapshots.contains(&manifest.snapshot) {
+                    Ok(())
+                } else {
+                    counter!("guardian.storage.backup.verify_failed", 1);
+                    Err(backup_error(
+                        format!("Snapshot {} missing on {} after receive", manifest.snapshot, target_dataset),
+                        None,
+                    ))
+                }
+            }
+        }
+    }
+
+    /// Copies a send stream through the backup throttle while hashing it
+    async fn copy_stream<R, W>(
+        &self,
+        reader: &mut R,
+        writer: &mut W,
+        options: &BackupOptions,
+    ) -> Result<(u64, String), GuardianError>
+    where
+        R: AsyncRead + Unpin,
+        W: AsyncWrite + Unpin,
+    {
+        let start = Instant::now();
+        let mut context = digest::Context::new(&digest::SHA256);
+        let mut buffer = vec![0u8; BACKUP_CHUNK_SIZE];
+        let mut total = 0u64;
+
+        loop {
+            let read = reader
+                .read(&mut buffer)
+                .await
+                .map_err(|e| backup_error("Failed to read backup stream".into(), Some(Box::new(e))))?;
+            if read == 0 {
+                break;
+            }
+
+            self.io_throttler.acquire(BackgroundJobClass::Backup, read as u64).await;
+            context.update(&buffer[..read]);
+            writer
+                .write_all(&buffer[..read])
+                .await
+                .map_err(|e| backup_error("Failed to write backup stream".into(), Some(Box::new(e))))?;
+            total += read as u64;
+
+            if let Some(limit) = options.bandwidth_limit_bytes_per_sec {
+                let wait = bandwidth_delay(total, limit, start.elapsed());
+                if !wait.is_zero() {
+                    tokio::time::sleep(wait).await;
+                }
+            }
+        }
+
+        writer
+            .flush()
+            .await
+            .map_err(|e| backup_error("Failed to flush backup stream".into(), Some(Box::new(e))))?;
+        Ok((total, to_hex(context.finish().as_ref())))
+    }
+
+    /// Lists the snapshots of a dataset, oldest first
+    async fn list_snapshots(&self, dataset: &str) -> Result<Vec<SnapshotInfo>, GuardianError> {
+        let output = std::process::Command::new("zfs")
+            .args(["list", "-H", "-p", "-t", "snapshot", "-o", "name,creation,used", "-s", "creation", "-d", "1", dataset])
+            .output()
+            .map_err(|e| GuardianError::StorageError {
+                context: format!("Failed to list snapshots of {}", dataset),
+                source: Some(Box::new(e)),
+                severity: crate::utils::error::ErrorSeverity::Medium,
+                timestamp: time::OffsetDateTime::now_utc(),
+                correlation_id: crate::utils::correlation::current_or_new(),
+                category: ErrorCategory::Storage,
+                retry_count: 0,
+            })?;
+
+        if !output.status.success() {
+            return Err(GuardianError::StorageError {
+                context: format!("Snapshot listing failed: {}",
+                    String::from_utf8_lossy(&output.stderr)),
+                source: None,
+                severity: crate::utils::error::ErrorSeverity::Medium,
+                timestamp: time::OffsetDateTime::now_utc(),
+                correlation_id: crate::utils::correlation::current_or_new(),
+                category: ErrorCategory::Storage,
+                retry_count: 0,
+            });
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.split('\t');
+                Some(SnapshotInfo {
+                    name: fields.next()?.to_string(),
+                    creation_time: fields.next()?.trim().parse().unwrap_or_default(),
+                    used: fields.next().and_then(|used| used.trim().parse().ok()).unwrap_or_default(),
+                })
+            })
+            .collect())
+    }
+
+    /// Destroys a single snapshot
+    async fn destroy_snapshot(&self, name: &str) -> Result<(), GuardianError> {
+        let output = std::process::Command::new("zfs")
+            .args(["destroy", name])
+            .output()
+            .map_err(|e| GuardianError::StorageError {
+                context: format!("Failed to destroy snapshot {}", name),
+                source: Some(Box::new(e)),
+                severity: crate::utils::error::ErrorSeverity::Medium,
+                timestamp: time::OffsetDateTime::now_utc(),
+                correlation_id: crate::utils::correlation::current_or_new(),
+                category: ErrorCategory::Storage,
+                retry_count: 0,
+            })?;
+
+        if !output.status.success() {
+            return Err(GuardianError::StorageError {
+                context: format!("Snapshot destruction failed: {}",
+                    String::from_utf8_lossy(&output.stderr)),
+                source: None,
+                severity: crate::utils::error::ErrorSeverity::Medium,
+                timestamp: time::OffsetDateTime::now_utc(),
+                correlation_id: crate::utils::correlation::current_or_new(),
+                category: ErrorCategory::Storage,
+                retry_count: 0,
+            });
+        }
+
+        Ok(())
+    }
+
     /// Retrieves dataset information
     async fn get_dataset_info(&self, name: &str) -> Result<DatasetInfo, GuardianError> {
         let output = std::process::Command::new("zfs")
@@ -289,7 +869,7 @@ impl ZfsManager {
                 source: Some(Box::new(e)),
                 severity: crate::utils::error::ErrorSeverity::Medium,
                 timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id: uuid::Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: ErrorCategory::Storage,
                 retry_count: 0,
             })?;
@@ -307,6 +887,187 @@ impl ZfsManager {
         })
     }
 
+    /// Starts a scrub of the pool; ZFS verifies every block's checksum in the background
+    #[instrument(skip(self))]
+    pub async fn start_scrub(&self) -> Result<(), GuardianError> {
+        self.io_throttler.acquire(BackgroundJobClass::Scrub, 0).await;
+        let output = std::process::Command::new("zpool")
+            .args(["scrub", &self.pool_name])
+            .output()
+            .map_err(|e| GuardianError::StorageError {
+                context: format!("Failed to start scrub of {}", self.pool_name),
+                source: Some(Box::new(e)),
+                severity: crate::utils::error::ErrorSeverity::Medium,
+                timestamp: time::OffsetDateTime::now_utc(),
+                correlation_id: crate::utils::correlation::current_or_new(),
+                category: ErrorCategory::Storage,
+                retry_count: 0,
+            })?;
+
+        if !output.status.success() {
+            return Err(GuardianError::StorageError {
+                context: format!("Scrub start failed: {}",
+                    String::from_utf8_lossy(&output.stderr)),
+                source: None,
+                severity: crate::utils::error::ErrorSeverity::Medium,
+                timestamp: time::OffsetDateTime::now_utc(),
+                correlation_id: crate::utils::correlation::current_or_new(),
+                category: ErrorCategory::Storage,
+                retry_count: 0,
+            });
+        }
+
+        counter!("guardian.storage.scrub.started", 1);
+        info!(pool = %self.pool_name, "Scrub started");
+        Ok(())
+    }
+
+    /// Reads pool and vdev states, scan progress and error counters
+    pub async fn pool_status(&self) -> Result<PoolStatus, GuardianError> {
+        let output = std::process::Command::new("zpool")
+            .args(["status", "-p", &self.pool_name])
+            .output()
+            .map_err(|e| GuardianError::StorageError {
+                context: format!("Failed to read status of {}", self.pool_name),
+                source: Some(Box::new(e)),
+                severity: crate::utils::error::ErrorSeverity::Medium,
+                timestamp: time::OffsetDateTime::now_utc(),
+                correlation_id: crate::utils::correlation::current_or_new(),
+                category: ErrorCategory::Storage,
+                retry_count: 0,
+            })?;
+
+        if !output.status.success() {
+            return Err(GuardianError::StorageError {
+                context: format!("Pool status failed: {}",
+                    String::from_utf8_lossy(&output.stderr)),
+                source: None,
+                severity: crate::utils::error::ErrorSeverity::Medium,
+                timestamp: time::OffsetDateTime::now_utc(),
+                correlation_id: crate::utils::correlation::current_or_new(),
+                category: ErrorCategory::Storage,
+                retry_count: 0,
+            });
+        }
+
+        Ok(parse_pool_status(&self.pool_name, &String::from_utf8_lossy(&output.stdout)))
+    }
+
+    /// Full name of a dataset under the root dataset
+    pub fn dataset_path(&self, name: &str) -> String {
+        format!("{}/{}", self.root_dataset, name)
+    }
+
+    /// Sets or clears (None) the quota and reservation of a dataset, in bytes
+    #[instrument(skip(self))]
+    pub async fn set_quota(
+        &self,
+        dataset: &str,
+        quota: Option<u64>,
+        reservation: Option<u64>,
+    ) -> Result<(), GuardianError> {
+        let property = |name: &str, value: Option<u64>| {
+            format!("{}={}", name, value.map_or_else(|| "none".to_string(), |v| v.to_string()))
+        };
+        let quota_arg = property("quota", quota);
+        let reservation_arg = property("reservation", reservation);
+        zfs_query(&["set", &quota_arg, &reservation_arg, dataset], &format!("set quota on {}", dataset))?;
+
+        info!(dataset, ?quota, ?reservation, "Dataset quota applied");
+        Ok(())
+    }
+
+    /// Rewrites every block of a dataset with `compression`, e.g. `zstd-19`, returning the bytes copied
+    ///
+    /// Setting the property only affects new writes, so the dataset is sent without `-w` and
+    /// received beside itself with the new compression, then swapped in. The copy is received under
+    /// the same encrypted parent, so it is re-encrypted with the parent's key.
+    #[instrument(skip(self))]
+    pub async fn recompress_dataset(&self, dataset: &str, compression: &str) -> Result<u64, GuardianError> {
+        let snapshot = format!("recompress-{}", time::OffsetDateTime::now_utc().unix_timestamp());
+        self.snapshot_dataset(dataset, &snapshot, None).await?;
+        let staging = format!("{}-recompress", dataset);
+
+        let mut cmd = Command::new("zfs");
+        cmd.args(["send", &format!("{}@{}", dataset, snapshot)]);
+        let mut send = spawn_zfs(cmd, Stdio::null(), Stdio::piped())?;
+        let mut stream = send.stdout.take().ok_or_else(|| backup_error("zfs send produced no output stream".into(), None))?;
+
+        let property = format!("compression={}", compression);
+        let mut cmd = Command::new("zfs");
+        cmd.args(["receive", "-u", "-o", &property, &staging]);
+        let mut receive = spawn_zfs(cmd, Stdio::piped(), Stdio::null())?;
+        let mut stdin = receive.stdin.take().ok_or_else(|| backup_error("zfs receive has no input stream".into(), None))?;
+        let (bytes, _) = self.copy_stream(&mut stream, &mut stdin, &BackupOptions::default()).await?;
+        drop(stdin);
+        wait_zfs(receive, "receive").await?;
+        wait_zfs(send, "send").await?;
+
+        zfs_query(&["destroy", "-r", dataset], &format!("destroy {}", dataset))?;
+        zfs_query(&["rename", &staging, dataset], &format!("rename {} to {}", staging, dataset))?;
+        self.dataset_cache.lock().await.remove(dataset);
+
+        counter!("guardian.storage.dataset.recompressed", 1);
+        info!(dataset, compression, bytes, "Dataset recompressed");
+        Ok(bytes)
+    }
+
+    /// Reads used and available space with the quota and reservation in effect
+    pub async fn dataset_usage(&self, dataset: &str) -> Result<DatasetUsage, GuardianError> {
+        let output = zfs_query(
+            &["get", "-H", "-p", "-o", "value", "used,available,quota,reservation", dataset],
+            &format!("read usage of {}", dataset),
+        )?;
+        parse_dataset_usage(dataset, &output).ok_or_else(|| GuardianError::StorageError {
+            context: format!("Unexpected usage output for {}", dataset),
+            source: None,
+            severity: crate::utils::error::ErrorSeverity::Medium,
+            timestamp: time::OffsetDateTime::now_utc(),
+            correlation_id: crate::utils::correlation::current_or_new(),
+            category: ErrorCategory::Storage,
+            retry_count: 0,
+        })
+    }
+
+    /// Reads the I/O counters OpenZFS keeps per dataset in its objset kstat
+    pub async fn dataset_io_stats(&self, dataset: &str) -> Result<DatasetIoStats, GuardianError> {
+        let objset_id = zfs_query(
+            &["get", "-H", "-p", "-o", "value", "objsetid", dataset],
+            &format!("read object set of {}", dataset),
+        )?;
+        let pool = dataset.split('/').next().unwrap_or(&self.pool_name);
+        let path = format!("{}/{}/objset-0x{:x}", KSTAT_ROOT, pool, objset_id.trim().parse::<u64>().unwrap_or_default());
+        let kstat = tokio::fs::read_to_string(&path).await.map_err(|e| GuardianError::StorageError {
+            context: format!("Failed to read I/O statistics of {} from {}", dataset, path),
+            source: Some(Box::new(e)),
+            severity: crate::utils::error::ErrorSeverity::Low,
+            timestamp: time::OffsetDateTime::now_utc(),
+            correlation_id: crate::utils::correlation::current_or_new(),
+            category: ErrorCategory::Storage,
+            retry_count: 0,
+        })?;
+        Ok(parse_objset_kstat(&kstat))
+    }
+
+    /// Lists the direct children of a dataset, oldest first
+    pub async fn child_datasets(&self, dataset: &str) -> Result<Vec<ChildDataset>, GuardianError> {
+        let output = zfs_query(
+            &["list", "-H", "-p", "-r", "-d", "1", "-t", "filesystem", "-o", "name,used,creation", "-s", "creation", dataset],
+            &format!("list children of {}", dataset),
+        )?;
+        Ok(parse_child_datasets(dataset, &output))
+    }
+
+    /// Destroys a dataset with its snapshots and children
+    #[instrument(skip(self))]
+    pub async fn destroy_dataset(&self, name: &str) -> Result<(), GuardianError> {
+        zfs_query(&["destroy", "-r", name], &format!("destroy {}", name))?;
+        self.dataset_cache.lock().await.remove(name);
+        counter!("guardian.storage.dataset.destroyed", 1);
+        info!(dataset = name, "Dataset destroyed");
+        Ok(())
+    }
+
     /// Verifies if pool exists
     async fn pool_exists(&self) -> Result<bool, GuardianError> {
         let output = std::process::Command::new("zpool")
@@ -317,7 +1078,7 @@ impl ZfsManager {
                 source: Some(Box::new(e)),
                 severity: crate::utils::error::ErrorSeverity::High,
                 timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id: uuid::Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: ErrorCategory::Storage,
                 retry_count: 0,
             })?;
@@ -328,6 +1089,173 @@ impl ZfsManager {
 
 /// Validates ZFS pool name
 #[inline]
+#[async_trait]
+impl KeyRotationParticipant for ZfsManager {
+    fn name(&self) -> &str {
+        "zfs"
+    }
+
+    async fn key_ids(&self) -> Vec<String> {
+        vec![STORAGE_KEY_ID.to_string()]
+    }
+
+    /// Rewraps the root dataset's master key with the new wrapping key; data is not rewritten
+    #[instrument(skip(self, key), fields(version = key.version))]
+    async fn apply_key(&self, _key_id: &str, key: &KeyMaterial) -> Result<(), GuardianError> {
+        let mut cmd = Command::new("zfs");
+        cmd.args(["change-key", "-o", "keylocation=prompt", "-o", "keyformat=raw", &self.root_dataset]);
+
+        let mut change_key = spawn_zfs(cmd, Stdio::piped(), Stdio::null())?;
+        let mut stdin = change_key.stdin.take().ok_or_else(|| backup_error("zfs change-key has no input stream".into(), None))?;
+        stdin
+            .write_all(key.bytes())
+            .await
+            .map_err(|e| backup_error("Failed to pass key to zfs change-key".into(), Some(Box::new(e))))?;
+        drop(stdin);
+        wait_zfs(change_key, "change-key").await?;
+
+        *self.encryption_key.write() = Arc::from(key.bytes());
+        info!(dataset = %self.root_dataset, "Storage encryption key changed");
+        Ok(())
+    }
+}
+
+/// Parses `zpool status -p` output; unrecognised sections are ignored
+fn parse_pool_status(pool: &str, output: &str) -> PoolStatus {
+    let mut state = VdevState::Unknown(String::new());
+    let mut scan = ScanStatus::None;
+    let mut vdevs = Vec::new();
+    let mut in_config = false;
+
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if let Some(value) = trimmed.strip_prefix("state:") {
+            state = VdevState::parse(value.trim());
+        } else if let Some(value) = trimmed.strip_prefix("scan:") {
+            let value = value.trim();
+            scan = if value.contains("in progress") {
+                ScanStatus::InProgress { percent_done: 0.0 }
+            } else if value.contains("canceled") {
+                ScanStatus::Canceled
+            } else if let Some((_, rest)) = value.split_once(" with ") {
+                let errors = rest.split_whitespace().next().and_then(|n| n.parse().ok()).unwrap_or_default();
+                ScanStatus::Completed { errors }
+            } else {
+                ScanStatus::None
+            };
+        } else if let (ScanStatus::InProgress { percent_done }, Some((before, _))) = (&mut scan, trimmed.split_once("% done")) {
+            // Progress is reported on a continuation line of the scan section
+            *percent_done = before.rsplit(|c: char| c == ' ' || c == ',').next().and_then(|p| p.parse().ok()).unwrap_or(0.0);
+        } else if trimmed.starts_with("config:") {
+            in_config = true;
+        } else if trimmed.starts_with("errors:") {
+            in_config = false;
+        } else if in_config {
+            let fields: Vec<&str> = trimmed.split_whitespace().collect();
+            if fields.len() < 5 || fields[0] == "NAME" {
+                continue;
+            }
+            let count = |field: &str| field.parse().unwrap_or_default();
+            vdevs.push(VdevStatus {
+                name: fields[0].to_string(),
+                state: VdevState::parse(fields[1]),
+                read_errors: count(fields[2]),
+                write_errors: count(fields[3]),
+                checksum_errors: count(fields[4]),
+            });
+        }
+    }
+
+    PoolStatus { pool: pool.to_string(), state, scan, vdevs }
+}
+
+/// Parses `zfs get -H -p -o value used,available,quota,reservation`; ZFS reports an unset limit as 0
+fn parse_dataset_usage(dataset: &str, output: &str) -> Option<DatasetUsage> {
+    let values: Vec<u64> = output.lines().map(|line| line.trim().parse().ok()).collect::<Option<_>>()?;
+    let [used, available, quota, reservation] = values[..] else {
+        return None;
+    };
+    let limit = |value: u64| (value > 0).then_some(value);
+    Some(DatasetUsage {
+        dataset: dataset.to_string(),
+        used,
+        available,
+        quota: limit(quota),
+        reservation: limit(reservation),
+    })
+}
+
+/// Parses an objset kstat, rows of `name type data` after two header lines
+fn parse_objset_kstat(kstat: &str) -> DatasetIoStats {
+    let mut stats = DatasetIoStats::default();
+    for line in kstat.lines().skip(2) {
+        let mut fields = line.split_whitespace();
+        let (Some(name), Some(_), Some(value)) = (fields.next(), fields.next(), fields.next()) else {
+            continue;
+        };
+        let Ok(value) = value.parse() else {
+            continue;
+        };
+        match name {
+            "reads" => stats.reads = value,
+            "nread" => stats.read_bytes = value,
+            "writes" => stats.writes = value,
+            "nwritten" => stats.written_bytes = value,
+            _ => {}
+        }
+    }
+    stats
+}
+
+/// Parses `zfs list -H -p -r -d 1 -o name,used,creation`, skipping the parent itself
+fn parse_child_datasets(parent: &str, output: &str) -> Vec<ChildDataset> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\t');
+            let name = fields.next()?;
+            if name == parent {
+                return None;
+            }
+            Some(ChildDataset {
+                name: name.to_string(),
+                used: fields.next()?.trim().parse().ok()?,
+                creation_time: fields.next()?.trim().parse().ok()?,
+            })
+        })
+        .collect()
+}
+
+/// Runs a short zfs command and returns its stdout
+fn zfs_query(args: &[&str], action: &str) -> Result<String, GuardianError> {
+    let output = std::process::Command::new("zfs")
+        .args(args)
+        .output()
+        .map_err(|e| GuardianError::StorageError {
+            context: format!("Failed to {}", action),
+            source: Some(Box::new(e)),
+            severity: crate::utils::error::ErrorSeverity::Medium,
+            timestamp: time::OffsetDateTime::now_utc(),
+            correlation_id: crate::utils::correlation::current_or_new(),
+            category: ErrorCategory::Storage,
+            retry_count: 0,
+        })?;
+
+    if !output.status.success() {
+        return Err(GuardianError::StorageError {
+            context: format!("Failed to {}: {}", action, String::from_utf8_lossy(&output.stderr)),
+            source: None,
+            severity: crate::utils::error::ErrorSeverity::Medium,
+            timestamp: time::OffsetDateTime::now_utc(),
+            correlation_id: crate::utils::correlation::current_or_new(),
+            category: ErrorCategory::Storage,
+            retry_count: 0,
+        });
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
 fn validate_pool_name(name: &str) -> Result<(), GuardianError> {
     if name.is_empty() || name.len() > MAX_POOL_NAME_LENGTH {
         return Err(GuardianError::StorageError {
@@ -335,7 +1263,7 @@ fn validate_pool_name(name: &str) -> Result<(), GuardianError> {
             source: None,
             severity: crate::utils::error::ErrorSeverity::High,
             timestamp: time::OffsetDateTime::now_utc(),
-            correlation_id: uuid::Uuid::new_v4(),
+            correlation_id: crate::utils::correlation::current_or_new(),
             category: ErrorCategory::Storage,
             retry_count: 0,
         });
@@ -347,7 +1275,7 @@ fn validate_pool_name(name: &str) -> Result<(), GuardianError> {
             source: None,
             severity: crate::utils::error::ErrorSeverity::High,
             timestamp: time::OffsetDateTime::now_utc(),
-            correlation_id: uuid::Uuid::new_v4(),
+            correlation_id: crate::utils::correlation::current_or_new(),
             category: ErrorCategory::Storage,
             retry_count: 0,
         });
@@ -356,6 +1284,171 @@ fn validate_pool_name(name: &str) -> Result<(), GuardianError> {
     Ok(())
 }
 
+/// Returns the short names of a dataset's snapshots, oldest first
+fn snapshot_names(dataset: &str, snapshots: &[SnapshotInfo]) -> Vec<String> {
+    let prefix = format!("{}@", dataset);
+    snapshots
+        .iter()
+        .filter_map(|s| s.name.strip_prefix(&prefix).map(str::to_string))
+        .collect()
+}
+
+/// Returns the newest source snapshot that the target also has
+fn last_common_snapshot(source: &[String], target: &[String]) -> Option<String> {
+    source.iter().rev().find(|s| target.contains(s)).cloned()
+}
+
+/// Resolves the streams needed to restore `snapshot`, full stream first
+fn restore_chain(manifests: &[BackupManifest], snapshot: Option<&str>) -> Option<Vec<BackupManifest>> {
+    let mut current = match snapshot {
+        Some(name) => manifests.iter().find(|m| m.snapshot == name)?,
+        None => manifests.iter().max_by_key(|m| m.created_at)?,
+    };
+
+    let mut chain = vec![current.clone()];
+    while let Some(base) = &current.base_snapshot {
+        current = manifests.iter().find(|m| &m.snapshot == base)?;
+        // A cycle means the manifests are corrupt
+        if chain.len() > manifests.len() {
+            return None;
+        }
+        chain.push(current.clone());
+    }
+
+    chain.reverse();
+    Some(chain)
+}
+
+/// Returns how long to pause so `bytes` sent in `elapsed` stays under `limit` bytes/sec
+fn bandwidth_delay(bytes: u64, limit: u64, elapsed: Duration) -> Duration {
+    if limit == 0 {
+        return Duration::ZERO;
+    }
+    Duration::from_secs_f64(bytes as f64 / limit as f64).saturating_sub(elapsed)
+}
+
+fn backup_file_stem(dataset: &str, snapshot: &str) -> String {
+    format!("{}@{}", dataset.replace('/', "_"), snapshot)
+}
+
+fn stream_path(dir: &Path, dataset: &str, snapshot: &str) -> PathBuf {
+    dir.join(format!("{}.{}", backup_file_stem(dataset, snapshot), BACKUP_STREAM_EXTENSION))
+}
+
+fn manifest_path(dir: &Path, dataset: &str, snapshot: &str) -> PathBuf {
+    dir.join(format!("{}.{}", backup_file_stem(dataset, snapshot), BACKUP_MANIFEST_EXTENSION))
+}
+
+/// Reads the manifests of every backup of `dataset` in a directory
+async fn read_manifests(dir: &Path, dataset: &str) -> Result<Vec<BackupManifest>, GuardianError> {
+    let mut manifests = Vec::new();
+    let mut entries = match tokio::fs::read_dir(dir).await {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(manifests),
+        Err(e) => return Err(backup_error(format!("Failed to read backup directory {}", dir.display()), Some(Box::new(e)))),
+    };
+
+    while let Some(entry) = entries
+        .next_entry()
+        .await
+        .map_err(|e| backup_error(format!("Failed to read backup directory {}", dir.display()), Some(Box::new(e))))?
+    {
+        let path = entry.path();
+        if path.extension().and_then(|e| e.to_str()) != Some(BACKUP_MANIFEST_EXTENSION) {
+            continue;
+        }
+        let Ok(raw) = tokio::fs::read(&path).await else {
+            continue;
+        };
+        match serde_json::from_slice::<BackupManifest>(&raw) {
+            Ok(manifest) if manifest.dataset == dataset => manifests.push(manifest),
+            Ok(_) => {}
+            Err(e) => warn!(path = %path.display(), error = %e, "Skipping unreadable backup manifest"),
+        }
+    }
+
+    manifests.sort_by_key(|m| m.created_at);
+    Ok(manifests)
+}
+
+/// Recomputes the checksum of a stream file and compares it with its manifest
+async fn verify_stream_file(path: &Path, manifest: &BackupManifest) -> Result<(), GuardianError> {
+    let mut file = tokio::fs::File::open(path)
+        .await
+        .map_err(|e| backup_error(format!("Failed to open backup file {}", path.display()), Some(Box::new(e))))?;
+    let mut context = digest::Context::new(&digest::SHA256);
+    let mut buffer = vec![0u8; BACKUP_CHUNK_SIZE];
+    let mut total = 0u64;
+
+    loop {
+        let read = file
+            .read(&mut buffer)
+            .await
+            .map_err(|e| backup_error(format!("Failed to read backup file {}", path.display()), Some(Box::new(e))))?;
+        if read == 0 {
+            break;
+        }
+        context.update(&buffer[..read]);
+        total += read as u64;
+    }
+
+    let sha256 = to_hex(context.finish().as_ref());
+    if total != manifest.bytes || sha256 != manifest.sha256 {
+        counter!("guardian.storage.backup.verify_failed", 1);
+        return Err(GuardianError::StorageError {
+            context: format!("Backup {} failed integrity check", path.display()),
+            source: None,
+            severity: crate::utils::error::ErrorSeverity::Critical,
+            timestamp: time::OffsetDateTime::now_utc(),
+            correlation_id: crate::utils::correlation::current_or_new(),
+            category: ErrorCategory::Storage,
+            retry_count: 0,
+        });
+    }
+
+    Ok(())
+}
+
+pub(super) fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+pub(super) fn spawn_zfs(mut cmd: Command, stdin: Stdio, stdout: Stdio) -> Result<tokio::process::Child, GuardianError> {
+    cmd.stdin(stdin)
+        .stdout(stdout)
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| backup_error("Failed to spawn zfs".into(), Some(Box::new(e))))
+}
+
+pub(super) async fn wait_zfs(child: tokio::process::Child, action: &str) -> Result<(), GuardianError> {
+    let output = child
+        .wait_with_output()
+        .await
+        .map_err(|e| backup_error(format!("Failed to wait for zfs {}", action), Some(Box::new(e))))?;
+
+    if !output.status.success() {
+        return Err(backup_error(
+            format!("zfs {} failed: {}", action, String::from_utf8_lossy(&output.stderr)),
+            None,
+        ));
+    }
+    Ok(())
+}
+
+fn backup_error(context: String, source: Option<Box<dyn std::error::Error + Send + Sync>>) -> GuardianError {
+    GuardianError::StorageError {
+        context,
+        source,
+        severity: crate::utils::error::ErrorSeverity::High,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: ErrorCategory::Storage,
+        retry_count: 0,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -388,4 +1481,96 @@ mod tests {
 
         assert!(result.is_ok());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_last_common_snapshot_and_restore_chain() {
+        let names = |v: &[&str]| v.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        let source = names(&["backup-1", "backup-2", "backup-3"]);
+        assert_eq!(last_common_snapshot(&source, &names(&["backup-1", "backup-2"])), Some("backup-2".into()));
+        assert_eq!(last_common_snapshot(&source, &names(&["other"])), None);
+
+        let manifest = |snapshot: &str, base: Option<&str>, created_at| BackupManifest {
+            dataset: "pool/guardian/events".into(),
+            snapshot: snapshot.into(),
+            base_snapshot: base.map(str::to_string),
+            bytes: 0,
+            sha256: String::new(),
+            created_at,
+        };
+        let manifests = vec![
+            manifest("backup-1", None, 1),
+            manifest("backup-2", Some("backup-1"), 2),
+            manifest("backup-3", Some("backup-2"), 3),
+        ];
+
+        let chain: Vec<_> = restore_chain(&manifests, None).unwrap().into_iter().map(|m| m.snapshot).collect();
+        assert_eq!(chain, names(&["backup-1", "backup-2", "backup-3"]));
+        assert_eq!(restore_chain(&manifests, Some("backup-1")).unwrap().len(), 1);
+        assert!(restore_chain(&manifests[1..], None).is_none());
+    }
+
+    #[test]
+    fn test_parse_pool_status() {
+        let output = "  pool: tank
+ state: DEGRADED
+status: One or more devices could not be used because the label is missing or invalid.
+  scan: scrub in progress since Sun Jul 25 16:07:49 2021
+\t1.21G scanned at 413M/s, 620M issued at 207M/s, 2.42G total
+\t0B repaired, 25.03% done, 00:00:08 to go
+config:
+
+\tNAME        STATE     READ WRITE CKSUM
+\ttank        DEGRADED     0     0     0
+\t  mirror-0  DEGRADED     0     0     0
+\t    ada0    ONLINE       0     0     3
+\t    ada1    FAULTED      0     0     0  too many errors
+
+errors: No known data errors
+";
+        let status = parse_pool_status("tank", output);
+        assert_eq!(status.state, VdevState::Degraded);
+        assert_eq!(status.scan, ScanStatus::InProgress { percent_done: 25.03 });
+        assert_eq!(status.vdevs.len(), 4);
+        assert_eq!(status.checksum_errors(), 3);
+        let impaired: Vec<_> = status.impaired_vdevs().map(|v| v.name.as_str()).collect();
+        assert_eq!(impaired, ["tank", "mirror-0", "ada1"]);
+
+        let done = parse_pool_status("tank", "  scan: scrub repaired 0B in 00:00:01 with 2 errors on Sun Jul 25 16:07:49 2021\n");
+        assert_eq!(done.scan, ScanStatus::Completed { errors: 2 });
+    }
+
+    #[test]
+    fn test_bandwidth_delay() {
+        assert_eq!(bandwidth_delay(1000, 1000, Duration::from_millis(250)), Duration::from_millis(750));
+        assert_eq!(bandwidth_delay(1000, 1000, Duration::from_secs(2)), Duration::ZERO);
+        assert_eq!(bandwidth_delay(1000, 0, Duration::ZERO), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_parse_dataset_usage_and_children() {
+        let usage = parse_dataset_usage("guardian/events", "850\n150\n1000\n0\n").unwrap();
+        assert_eq!(usage.quota, Some(1000));
+        assert_eq!(usage.reservation, None);
+        assert_eq!(usage.percent_used(), 85.0);
+        assert!(parse_dataset_usage("guardian/events", "850\n150\n").is_none());
+
+        let output = "guardian/events\t900\t100\nguardian/events/2024-01\t400\t200\nguardian/events/2024-02\t500\t300\n";
+        let children = parse_child_datasets("guardian/events", output);
+        assert_eq!(children.len(), 2);
+        assert_eq!(children[0].name, "guardian/events/2024-01");
+        assert_eq!(children[1].used, 500);
+    }
+
+    #[test]
+    fn test_parse_objset_kstat() {
+        let kstat = "49 1 0x01 7 2160 5214446682 1081264503208\n\
+            name                            type data\n\
+            dataset_name                    7    guardian_pool/guardian/models\n\
+            writes                          4    12\n\
+            nwritten                        4    49152\n\
+            reads                           4    310\n\
+            nread                           4    8126464\n";
+        let stats = parse_objset_kstat(kstat);
+        assert_eq!(stats, DatasetIoStats { reads: 310, read_bytes: 8126464, writes: 12, written_bytes: 49152 });
+    }
+}
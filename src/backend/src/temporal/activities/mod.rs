@@ -10,10 +10,16 @@ use crate::utils::error::{GuardianError, ErrorCategory};
 mod security_activities;
 mod monitoring_activities;
 mod maintenance_activities;
+mod model_activities;
+mod training_activities;
 
 pub use security_activities::SecurityActivities;
 pub use monitoring_activities::MonitoringActivities;
 pub use maintenance_activities::MaintenanceActivities;
+pub use model_activities::{CanaryMetrics, ModelActivities};
+pub use training_activities::{
+    CandidateValidation, ExportedDataset, TrainedCandidate, TrainingActivities, TrainingRunRequest,
+};
 
 // Constants for activity configuration
 const ACTIVITY_NAMESPACE: &str = "guardian.activities";
@@ -88,7 +94,7 @@ pub async fn register_activities(
         source: Some(Box::new(e)),
         severity: crate::utils::error::ErrorSeverity::Critical,
         timestamp: time::OffsetDateTime::now_utc(),
-        correlation_id: uuid::Uuid::new_v4(),
+        correlation_id: crate::utils::correlation::current_or_new(),
         category: ErrorCategory::System,
         retry_count: 0,
     })?;
@@ -103,7 +109,7 @@ pub async fn register_activities(
         source: Some(Box::new(e)),
         severity: crate::utils::error::ErrorSeverity::Critical,
         timestamp: time::OffsetDateTime::now_utc(),
-        correlation_id: uuid::Uuid::new_v4(),
+        correlation_id: crate::utils::correlation::current_or_new(),
         category: ErrorCategory::System,
         retry_count: 0,
     })?;
@@ -118,7 +124,115 @@ pub async fn register_activities(
         source: Some(Box::new(e)),
         severity: crate::utils::error::ErrorSeverity::Critical,
         timestamp: time::OffsetDateTime::now_utc(),
-        correlation_id: uuid::Uuid::new_v4(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: ErrorCategory::System,
+        retry_count: 0,
+    })?;
+
+    // Register model rollout activities
+    worker.register_activity(
+        "start_canary",
+        options.clone(),
+        ModelActivities::start_canary,
+    ).map_err(|e| GuardianError::SystemError {
+        context: "Failed to register model rollout activities".into(),
+        source: Some(Box::new(e)),
+        severity: crate::utils::error::ErrorSeverity::Critical,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: ErrorCategory::System,
+        retry_count: 0,
+    })?;
+    worker.register_activity(
+        "canary_metrics",
+        options.clone(),
+        ModelActivities::canary_metrics,
+    ).map_err(|e| GuardianError::SystemError {
+        context: "Failed to register model rollout activities".into(),
+        source: Some(Box::new(e)),
+        severity: crate::utils::error::ErrorSeverity::Critical,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: ErrorCategory::System,
+        retry_count: 0,
+    })?;
+    worker.register_activity(
+        "promote_canary",
+        options.clone(),
+        ModelActivities::promote_canary,
+    ).map_err(|e| GuardianError::SystemError {
+        context: "Failed to register model rollout activities".into(),
+        source: Some(Box::new(e)),
+        severity: crate::utils::error::ErrorSeverity::Critical,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: ErrorCategory::System,
+        retry_count: 0,
+    })?;
+    worker.register_activity(
+        "abort_canary",
+        options.clone(),
+        ModelActivities::abort_canary,
+    ).map_err(|e| GuardianError::SystemError {
+        context: "Failed to register model rollout activities".into(),
+        source: Some(Box::new(e)),
+        severity: crate::utils::error::ErrorSeverity::Critical,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: ErrorCategory::System,
+        retry_count: 0,
+    })?;
+
+    // Register model training activities
+    worker.register_activity(
+        "export_training_set",
+        options.clone(),
+        TrainingActivities::export_training_set,
+    ).map_err(|e| GuardianError::SystemError {
+        context: "Failed to register model training activities".into(),
+        source: Some(Box::new(e)),
+        severity: crate::utils::error::ErrorSeverity::Critical,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: ErrorCategory::System,
+        retry_count: 0,
+    })?;
+    worker.register_activity(
+        "train_candidate",
+        options.clone(),
+        TrainingActivities::train_candidate,
+    ).map_err(|e| GuardianError::SystemError {
+        context: "Failed to register model training activities".into(),
+        source: Some(Box::new(e)),
+        severity: crate::utils::error::ErrorSeverity::Critical,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: ErrorCategory::System,
+        retry_count: 0,
+    })?;
+    worker.register_activity(
+        "validate_candidate",
+        options.clone(),
+        TrainingActivities::validate_candidate,
+    ).map_err(|e| GuardianError::SystemError {
+        context: "Failed to register model training activities".into(),
+        source: Some(Box::new(e)),
+        severity: crate::utils::error::ErrorSeverity::Critical,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: ErrorCategory::System,
+        retry_count: 0,
+    })?;
+    worker.register_activity(
+        "register_candidate",
+        options.clone(),
+        TrainingActivities::register_candidate,
+    ).map_err(|e| GuardianError::SystemError {
+        context: "Failed to register model training activities".into(),
+        source: Some(Box::new(e)),
+        severity: crate::utils::error::ErrorSeverity::Critical,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
         category: ErrorCategory::System,
         retry_count: 0,
     })?;
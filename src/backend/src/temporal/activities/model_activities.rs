@@ -0,0 +1,85 @@
+use std::sync::Arc;
+use std::time::Duration;
+use async_trait::async_trait;
+use temporal_sdk::RetryPolicy;
+use tracing::{info, warn, instrument};
+use serde::{Serialize, Deserialize};
+
+use crate::ml::inference_engine::InferenceEngine;
+use crate::ml::model_registry::{ModelMetrics, ModelRegistry};
+use crate::utils::error::GuardianError;
+
+// Constants for model rollout activities
+const MAX_RETRY_ATTEMPTS: u32 = 3;
+
+/// Live metrics of a canary and the incumbent it is measured against
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CanaryMetrics {
+    pub candidate: Option<ModelMetrics>,
+    pub incumbent: Option<ModelMetrics>,
+}
+
+/// Activities driving staged model rollouts on the local inference engine
+#[derive(Debug, Clone)]
+pub struct ModelActivities {
+    registry: Arc<ModelRegistry>,
+    engine: Arc<InferenceEngine>,
+}
+
+impl ModelActivities {
+    pub fn new(registry: Arc<ModelRegistry>, engine: Arc<InferenceEngine>) -> Self {
+        Self { registry, engine }
+    }
+
+    fn rollout_retry_policy() -> RetryPolicy {
+        RetryPolicy {
+            initial_interval: Duration::from_secs(1),
+            backoff: 2.0,
+            max_interval: Duration::from_secs(10),
+            max_attempts: MAX_RETRY_ATTEMPTS,
+            non_retryable_error_types: vec!["ValidationError".to_string(), "MLError".to_string()],
+        }
+    }
+}
+
+#[async_trait]
+impl ModelActivities {
+    /// Routes a share of traffic to the candidate and returns the incumbent version
+    #[instrument(level = "info", skip(self), err)]
+    #[temporal_sdk::activity(retry_policy = "rollout_retry_policy()")]
+    pub async fn start_canary(&self, version: String, traffic_percent: u8) -> Result<String, GuardianError> {
+        let incumbent = self.engine.current_model_version().await?;
+        self.engine.set_canary(version, traffic_percent).await?;
+        Ok(incumbent)
+    }
+
+    /// Fetches the latest metrics of both sides of the split
+    #[instrument(level = "info", skip(self), err)]
+    #[temporal_sdk::activity(retry_policy = "rollout_retry_policy()")]
+    pub async fn canary_metrics(&self, candidate: String, incumbent: String) -> Result<CanaryMetrics, GuardianError> {
+        Ok(CanaryMetrics {
+            candidate: self.registry.get_model_metrics(candidate).await.ok(),
+            incumbent: self.registry.get_model_metrics(incumbent).await.ok(),
+        })
+    }
+
+    /// Activates the candidate for all traffic
+    #[instrument(level = "info", skip(self), err)]
+    #[temporal_sdk::activity(retry_policy = "rollout_retry_policy()")]
+    pub async fn promote_canary(&self, version: String) -> Result<(), GuardianError> {
+        self.registry.activate_model(version.clone()).await?;
+        self.engine.clear_canary().await;
+        info!(version = %version, "Canary model promoted");
+        Ok(())
+    }
+
+    /// Sends all traffic back to the incumbent
+    #[instrument(level = "info", skip(self), err)]
+    #[temporal_sdk::activity(retry_policy = "rollout_retry_policy()")]
+    pub async fn abort_canary(&self, reason: String) -> Result<(), GuardianError> {
+        if let Some(route) = self.engine.clear_canary().await {
+            warn!(version = %route.version, reason = %reason, "Canary model rolled back");
+        }
+        Ok(())
+    }
+}
@@ -104,7 +104,7 @@ impl MonitoringActivities {
                 source: None,
                 severity: ErrorSeverity::High,
                 timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id: uuid::Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: ErrorCategory::System,
                 retry_count: 0,
             }.into());
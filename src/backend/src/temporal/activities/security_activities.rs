@@ -6,9 +6,10 @@ use serde::{Serialize, Deserialize};
 use metrics::{counter, histogram};
 
 use crate::security::threat_detection::{ThreatDetector, ThreatLevel};
-use crate::security::response_engine::{ResponseEngine, ResponseAction, ResponseStatus};
+use crate::security::response_engine::{ResponseEngine, ResponseAction, ResponseStatus, ResponseStep, ThreatAnalysis};
 use crate::security::audit::{AuditLogger, AuditEvent, SecurityLevel};
 use crate::utils::error::{GuardianError, SecurityError};
+use crate::utils::{correlation, telemetry};
 
 // Constants for activity configuration
 const ACTIVITY_VERSION: &str = "1.0.0";
@@ -65,6 +66,15 @@ pub trait SecurityActivities {
     
     async fn record_audit(&self, ctx: ActivityContext, event: AuditEvent) 
         -> Result<(), ActivityError>;
+
+    async fn plan_response(&self, ctx: ActivityContext, threat_analysis: ThreatAnalysis)
+        -> Result<Vec<ResponseStep>, ActivityError>;
+
+    async fn execute_response_step(&self, ctx: ActivityContext, action: ResponseAction)
+        -> Result<ResponseStatus, ActivityError>;
+
+    async fn compensate_response(&self, ctx: ActivityContext, action: ResponseAction)
+        -> Result<(), ActivityError>;
     
     async fn batch_detect_threats(&self, ctx: ActivityContext, system_data: Vec<SystemData>) 
         -> Result<Vec<ThreatAnalysis>, ActivityError>;
@@ -135,33 +145,40 @@ impl SecurityActivities for SecurityActivitiesImpl {
     ) -> Result<ThreatAnalysis, ActivityError> {
         // Validate activity context
         validate_activity_context(&ctx)?;
+        telemetry::follow_workflow_headers(ctx.headers());
 
-        // Check circuit breaker
-        if self.circuit_breaker.is_open.load(Ordering::SeqCst) {
-            return Err(ActivityError::CircuitBreakerOpen);
-        }
+        correlation::scope(activity_correlation_id(&ctx), async {
+            // Check circuit breaker
+            if self.circuit_breaker.is_open.load(Ordering::SeqCst) {
+                return Err(ActivityError::CircuitBreakerOpen);
+            }
 
-        let start_time = Instant::now();
+            let start_time = Instant::now();
 
-        // Record activity start
-        counter!("guardian.activity.detect_threats.start", 1);
+            // Record activity start
+            counter!("guardian.activity.detect_threats.start", 1);
 
-        // Execute threat detection
-        let result = tokio::time::timeout(
-            self.batch_config.timeout,
-            self.threat_detector.analyze_threat(system_data)
-        ).await.map_err(|_| ActivityError::Timeout)??;
+            // Execute threat detection
+            let result = tokio::time::timeout(
+                self.batch_config.timeout,
+                self.threat_detector.analyze_threat(system_data)
+            ).await.map_err(|_| ActivityError::Timeout)??;
 
-        // Update metrics
-        self.metrics.success_count.fetch_add(1, Ordering::SeqCst);
-        *self.metrics.last_execution.write().await = Instant::now();
-        
-        histogram!(
-            "guardian.activity.detect_threats.duration",
-            start_time.elapsed().as_secs_f64()
-        );
+            // Ancestry is captured now, while the process and its parents are still in the tree
+            let result = result.with_lineage();
 
-        Ok(result)
+            // Update metrics
+            self.metrics.success_count.fetch_add(1, Ordering::SeqCst);
+            *self.metrics.last_execution.write().await = Instant::now();
+        
+            histogram!(
+                "guardian.activity.detect_threats.duration",
+                start_time.elapsed().as_secs_f64()
+            );
+
+            Ok(result)
+        })
+        .await
     }
 
     #[tracing::instrument(skip(self, ctx))]
@@ -172,53 +189,125 @@ impl SecurityActivities for SecurityActivitiesImpl {
         threat_analysis: ThreatAnalysis,
     ) -> Result<ResponseStatus, ActivityError> {
         validate_activity_context(&ctx)?;
+        telemetry::follow_workflow_headers(ctx.headers());
+
+        correlation::scope(activity_correlation_id(&ctx), async {
+            if self.circuit_breaker.is_open.load(Ordering::SeqCst) {
+                return Err(ActivityError::CircuitBreakerOpen);
+            }
+
+            let start_time = Instant::now();
+            counter!("guardian.activity.execute_response.start", 1);
+
+            // Execute response with heartbeat
+            let threat_analysis = threat_analysis.with_lineage();
+            let audit_data = threat_analysis.audit_data();
+            let result = self.response_engine.execute_response(threat_analysis).await?;
+
+            // Record audit event
+            let event = AuditEvent::new(
+                "security.response.executed",
+                SecurityLevel::High,
+                "response_engine",
+                Some(result.correlation_id.to_string()),
+            );
+            self.audit_logger.record_event(event.clone().with_data(audit_data).unwrap_or(event)).await?;
+
+            histogram!(
+                "guardian.activity.execute_response.duration",
+                start_time.elapsed().as_secs_f64()
+            );
+
+            Ok(result)
+        })
+        .await
+    }
 
-        if self.circuit_breaker.is_open.load(Ordering::SeqCst) {
-            return Err(ActivityError::CircuitBreakerOpen);
-        }
+    #[tracing::instrument(skip(self, ctx))]
+    #[activity(retention_period = "90 days")]
+    async fn record_audit(
+        &self,
+        ctx: ActivityContext,
+        event: AuditEvent,
+    ) -> Result<(), ActivityError> {
+        validate_activity_context(&ctx)?;
+
+        correlation::scope(activity_correlation_id(&ctx), async {
+            let start_time = Instant::now();
+            counter!("guardian.activity.record_audit.start", 1);
 
-        let start_time = Instant::now();
-        counter!("guardian.activity.execute_response.start", 1);
+            self.audit_logger.record_event(event).await?;
 
-        // Execute response with heartbeat
-        let result = self.response_engine.execute_response(threat_analysis).await?;
+            histogram!(
+                "guardian.activity.record_audit.duration",
+                start_time.elapsed().as_secs_f64()
+            );
 
-        // Record audit event
-        self.audit_logger.record_event(AuditEvent::new(
-            "security.response.executed",
-            SecurityLevel::High,
-            "response_engine",
-            Some(result.correlation_id.to_string()),
-        )).await?;
+            Ok(())
+        })
+        .await
+    }
 
-        histogram!(
-            "guardian.activity.execute_response.duration",
-            start_time.elapsed().as_secs_f64()
-        );
+    #[tracing::instrument(skip(self, ctx))]
+    #[activity(retention_period = "24 hours")]
+    async fn plan_response(
+        &self,
+        ctx: ActivityContext,
+        threat_analysis: ThreatAnalysis,
+    ) -> Result<Vec<ResponseStep>, ActivityError> {
+        validate_activity_context(&ctx)?;
 
-        Ok(result)
+        Ok(correlation::sync_scope(activity_correlation_id(&ctx), || {
+            self.response_engine.plan_response(&threat_analysis)
+        }))
     }
 
     #[tracing::instrument(skip(self, ctx))]
-    #[activity(retention_period = "90 days")]
-    async fn record_audit(
+    #[activity(heartbeat_timeout = "5 seconds")]
+    async fn execute_response_step(
         &self,
         ctx: ActivityContext,
-        event: AuditEvent,
-    ) -> Result<(), ActivityError> {
+        action: ResponseAction,
+    ) -> Result<ResponseStatus, ActivityError> {
         validate_activity_context(&ctx)?;
-        
-        let start_time = Instant::now();
-        counter!("guardian.activity.record_audit.start", 1);
+        telemetry::follow_workflow_headers(ctx.headers());
+
+        correlation::scope(activity_correlation_id(&ctx), async {
+            if self.circuit_breaker.is_open.load(Ordering::SeqCst) {
+                return Err(ActivityError::CircuitBreakerOpen);
+            }
+
+            let start_time = Instant::now();
+            counter!("guardian.activity.execute_response_step.start", 1, "action" => action.name().to_string());
 
-        self.audit_logger.record_event(event).await?;
+            let result = self.response_engine.execute_step(action).await?;
 
-        histogram!(
-            "guardian.activity.record_audit.duration",
-            start_time.elapsed().as_secs_f64()
-        );
+            histogram!(
+                "guardian.activity.execute_response_step.duration",
+                start_time.elapsed().as_secs_f64()
+            );
 
-        Ok(())
+            Ok(result)
+        })
+        .await
+    }
+
+    #[tracing::instrument(skip(self, ctx))]
+    #[activity(heartbeat_timeout = "5 seconds")]
+    async fn compensate_response(
+        &self,
+        ctx: ActivityContext,
+        action: ResponseAction,
+    ) -> Result<(), ActivityError> {
+        validate_activity_context(&ctx)?;
+        telemetry::follow_workflow_headers(ctx.headers());
+
+        correlation::scope(activity_correlation_id(&ctx), async {
+            counter!("guardian.activity.compensate_response.start", 1, "action" => action.name().to_string());
+            self.response_engine.revert_response(&action).await?;
+            Ok(())
+        })
+        .await
     }
 
     #[tracing::instrument(skip(self, ctx))]
@@ -230,24 +319,43 @@ impl SecurityActivities for SecurityActivitiesImpl {
     ) -> Result<Vec<ThreatAnalysis>, ActivityError> {
         validate_activity_context(&ctx)?;
 
-        if system_data.len() > self.batch_config.max_size {
-            return Err(ActivityError::BatchSizeExceeded);
-        }
-
-        let start_time = Instant::now();
-        counter!("guardian.activity.batch_detect_threats.start", 1);
-
-        let results = self.threat_detector.batch_analyze(system_data).await?;
-
-        histogram!(
-            "guardian.activity.batch_detect_threats.duration",
-            start_time.elapsed().as_secs_f64()
-        );
-
-        Ok(results)
+        correlation::scope(activity_correlation_id(&ctx), async {
+            if system_data.len() > self.batch_config.max_size {
+                return Err(ActivityError::BatchSizeExceeded);
+            }
+
+            let start_time = Instant::now();
+            counter!("guardian.activity.batch_detect_threats.start", 1);
+
+            let results: Vec<ThreatAnalysis> = self
+                .threat_detector
+                .batch_analyze(system_data)
+                .await?
+                .into_iter()
+                .map(ThreatAnalysis::with_lineage)
+                .collect();
+
+            histogram!(
+                "guardian.activity.batch_detect_threats.duration",
+                start_time.elapsed().as_secs_f64()
+            );
+
+            Ok(results)
+        })
+        .await
     }
 }
 
+/// Correlation ID of the workflow that scheduled the activity, so its logs and errors join the
+/// request that started the workflow
+fn activity_correlation_id(ctx: &ActivityContext) -> uuid::Uuid {
+    ctx.info()
+        .workflow_execution
+        .as_ref()
+        .and_then(|execution| correlation::from_workflow_id(&execution.workflow_id))
+        .unwrap_or_else(correlation::current_or_new)
+}
+
 /// Validates the Temporal activity context
 #[tracing::instrument]
 fn validate_activity_context(ctx: &ActivityContext) -> Result<(), ActivityError> {
@@ -0,0 +1,373 @@
+use std::{path::PathBuf, sync::Arc, time::Duration};
+use async_trait::async_trait;
+use chrono::Utc;
+use metrics::{counter, histogram};
+use temporal_sdk::RetryPolicy;
+use tracing::{info, warn, instrument};
+use serde::{Serialize, Deserialize};
+
+use crate::config::ml_config::RetrainingConfig;
+use crate::core::guardian::TenantId;
+use crate::ml::model_registry::{
+    ModelListQuery, ModelMetadata, ModelMetrics, ModelProvenance, ModelRegistry, ModelStatus, ValidationStatus,
+};
+use crate::ml::trainer::{self, Evaluation, LogisticModel, TrainingSet, LOGISTIC_MODEL_FORMAT};
+use crate::security::anomaly_feedback::{AnomalyFeedback, LabeledSample};
+use crate::security::model_signing::ModelSigner;
+use crate::utils::error::{ErrorCategory, ErrorSeverity, GuardianError};
+
+// Constants for training activities
+const MAX_RETRY_ATTEMPTS: u32 = 3;
+const DATASET_FILE: &str = "dataset.json";
+const MODEL_FILE: &str = "model.json";
+/// Version chosen for a run, kept so a retried registration does not mint a second version
+const VERSION_FILE: &str = "version";
+const TRAINED_MODEL_NAME: &str = "anomaly_classifier";
+const VERSION_LIST_PAGE_SIZE: usize = 500;
+
+type TrainingBackend = burn::backend::NdArrayBackend<f32>;
+
+/// One training run, as started by the maintenance workflow
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrainingRunRequest {
+    pub run_id: String,
+    pub tenant_id: String,
+    /// Labeled events exported, as Unix seconds
+    pub since: u64,
+    pub until: u64,
+}
+
+/// Labeled events staged for training
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportedDataset {
+    pub request: TrainingRunRequest,
+    pub samples: usize,
+    /// Samples whose features could not be used
+    pub skipped: usize,
+    pub dataset_hash: String,
+}
+
+/// A trained model staged for validation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrainedCandidate {
+    pub dataset: ExportedDataset,
+    pub training_samples: usize,
+    pub holdout_samples: usize,
+    pub training_accuracy: f64,
+    pub base_version: Option<String>,
+}
+
+/// Result of checking a candidate against the held-out samples
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CandidateValidation {
+    pub holdout: Evaluation,
+    /// Accuracy the active model has earned from analyst feedback, for comparison
+    pub incumbent_accuracy: Option<f64>,
+    pub passed: bool,
+    pub reason: String,
+}
+
+/// Activities that export labeled events, train a candidate with burn, validate it and register it.
+/// Datasets and models are staged on disk between activities rather than passed through Temporal.
+#[derive(Debug, Clone)]
+pub struct TrainingActivities {
+    feedback: Arc<AnomalyFeedback>,
+    registry: Arc<ModelRegistry>,
+    signer: Option<Arc<ModelSigner>>,
+    config: RetrainingConfig,
+}
+
+impl TrainingActivities {
+    pub fn new(feedback: Arc<AnomalyFeedback>, registry: Arc<ModelRegistry>, config: RetrainingConfig) -> Result<Self, GuardianError> {
+        let signer = match &config.signing_key_path {
+            Some(path) => Some(Arc::new(ModelSigner::from_pkcs8_file(path)?)),
+            None => {
+                warn!("No model signing key configured; trained models are rejected while signatures are enforced");
+                None
+            }
+        };
+        Ok(Self { feedback, registry, signer, config })
+    }
+
+    fn training_retry_policy() -> RetryPolicy {
+        RetryPolicy {
+            initial_interval: Duration::from_secs(5),
+            backoff: 2.0,
+            max_interval: Duration::from_secs(60),
+            max_attempts: MAX_RETRY_ATTEMPTS,
+            non_retryable_error_types: vec!["ValidationError".to_string(), "SecurityError".to_string()],
+        }
+    }
+
+    fn run_dir(&self, run_id: &str) -> PathBuf {
+        PathBuf::from(&self.config.staging_dir).join(run_id)
+    }
+
+    async fn load_samples(&self, dataset: &ExportedDataset) -> Result<Vec<LabeledSample>, GuardianError> {
+        let bytes = read_staged(&self.run_dir(&dataset.request.run_id).join(DATASET_FILE)).await?;
+        if sha256_hex(&bytes) != dataset.dataset_hash {
+            return Err(training_error(
+                format!("Staged dataset of run {} does not match its export", dataset.request.run_id),
+                ErrorCategory::Validation,
+                None,
+            ));
+        }
+        serde_json::from_slice(&bytes).map_err(|e| training_error("Failed to parse staged dataset".into(), ErrorCategory::ML, Some(Box::new(e))))
+    }
+
+    async fn load_split(&self, dataset: &ExportedDataset) -> Result<(TrainingSet, TrainingSet), GuardianError> {
+        let (set, _) = TrainingSet::from_samples(&self.load_samples(dataset).await?);
+        Ok(set.split_holdout(self.config.holdout_fraction))
+    }
+
+    /// Next patch version after every registered version
+    async fn next_version(&self) -> Result<String, GuardianError> {
+        let mut latest = (0, 0, 0);
+        let mut page_token = None;
+        loop {
+            let page = self
+                .registry
+                .list_models(ModelListQuery {
+                    page_size: VERSION_LIST_PAGE_SIZE,
+                    page_token: page_token.take(),
+                    ..Default::default()
+                })
+                .await?;
+            latest = page.models.iter().filter_map(|m| parse_version(&m.version)).fold(latest, std::cmp::max);
+            match page.next_page_token {
+                Some(token) => page_token = Some(token),
+                None => break,
+            }
+        }
+        Ok(match latest {
+            (0, 0, 0) => "v1.0.0".to_string(),
+            (major, minor, patch) => format!("v{}.{}.{}", major, minor, patch + 1),
+        })
+    }
+}
+
+#[async_trait]
+impl TrainingActivities {
+    /// Exports the tenant's labeled events in the run's window and stages them
+    #[instrument(level = "info", skip(self), fields(run_id = %request.run_id), err)]
+    #[temporal_sdk::activity(retry_policy = "training_retry_policy()")]
+    pub async fn export_training_set(&self, request: TrainingRunRequest) -> Result<ExportedDataset, GuardianError> {
+        let tenant_id = TenantId::new(request.tenant_id.clone())?;
+        let samples: Vec<LabeledSample> = self
+            .feedback
+            .export_dataset(&tenant_id, Some(request.since))
+            .await?
+            .into_iter()
+            .filter(|s| s.labeled_at < request.until)
+            .collect();
+
+        let (set, skipped) = TrainingSet::from_samples(&samples);
+        if set.len() < self.config.min_samples {
+            return Err(training_error(
+                format!("Run {} has {} usable labeled samples, {} required", request.run_id, set.len(), self.config.min_samples),
+                ErrorCategory::Validation,
+                None,
+            ));
+        }
+
+        let bytes = serde_json::to_vec(&samples)
+            .map_err(|e| training_error("Failed to serialize dataset".into(), ErrorCategory::ML, Some(Box::new(e))))?;
+        let dataset_hash = sha256_hex(&bytes);
+        write_staged(&self.run_dir(&request.run_id), DATASET_FILE, &bytes).await?;
+
+        counter!("guardian.training.samples_exported", set.len() as u64);
+        info!(samples = set.len(), skipped, "Training dataset exported");
+        Ok(ExportedDataset { request, samples: set.len(), skipped, dataset_hash })
+    }
+
+    /// Trains a candidate on all but the held-out samples
+    #[instrument(level = "info", skip(self, dataset), fields(run_id = %dataset.request.run_id), err)]
+    #[temporal_sdk::activity(retry_policy = "training_retry_policy()")]
+    pub async fn train_candidate(&self, dataset: ExportedDataset) -> Result<TrainedCandidate, GuardianError> {
+        let (training, holdout) = self.load_split(&dataset).await?;
+        let (epochs, learning_rate) = (self.config.epochs, self.config.learning_rate);
+
+        let started = std::time::Instant::now();
+        let fitted = training.clone();
+        let model = tokio::task::spawn_blocking(move || {
+            trainer::train::<TrainingBackend>(&fitted, epochs, learning_rate, &Default::default())
+        })
+        .await
+        .map_err(|e| training_error("Training task panicked".into(), ErrorCategory::ML, Some(Box::new(e))))??;
+        histogram!("guardian.training.duration_seconds", started.elapsed().as_secs_f64());
+
+        write_staged(&self.run_dir(&dataset.request.run_id), MODEL_FILE, &model.to_bytes()?).await?;
+        let training_accuracy = trainer::evaluate(&model, &training).accuracy;
+        info!(samples = training.len(), training_accuracy, "Candidate model trained");
+
+        Ok(TrainedCandidate {
+            training_samples: training.len(),
+            holdout_samples: holdout.len(),
+            training_accuracy,
+            base_version: self.registry.active_version().await,
+            dataset,
+        })
+    }
+
+    /// Scores the candidate on the held-out samples
+    #[instrument(level = "info", skip(self, candidate), fields(run_id = %candidate.dataset.request.run_id), err)]
+    #[temporal_sdk::activity(retry_policy = "training_retry_policy()")]
+    pub async fn validate_candidate(&self, candidate: TrainedCandidate) -> Result<CandidateValidation, GuardianError> {
+        let (_, holdout) = self.load_split(&candidate.dataset).await?;
+        let model = LogisticModel::from_bytes(&read_staged(&self.run_dir(&candidate.dataset.request.run_id).join(MODEL_FILE)).await?)?;
+        let evaluation = trainer::evaluate(&model, &holdout);
+
+        let incumbent_accuracy = match &candidate.base_version {
+            Some(version) => self.registry.get_model_metrics(version.clone()).await.ok().map(|m| m.accuracy),
+            None => None,
+        };
+        let passed = evaluation.accuracy >= self.config.min_holdout_accuracy;
+        let reason = format!(
+            "held-out accuracy {:.4} {} the {:.4} threshold",
+            evaluation.accuracy,
+            if passed { "meets" } else { "is below" },
+            self.config.min_holdout_accuracy
+        );
+        histogram!("guardian.training.holdout_accuracy", evaluation.accuracy);
+        info!(accuracy = evaluation.accuracy, ?incumbent_accuracy, passed, "Candidate model validated");
+
+        Ok(CandidateValidation { holdout: evaluation, incumbent_accuracy, passed, reason })
+    }
+
+    /// Signs the validated candidate and registers it, inactive, with its provenance
+    #[instrument(level = "info", skip(self, candidate, validation), fields(run_id = %candidate.dataset.request.run_id), err)]
+    #[temporal_sdk::activity(retry_policy = "training_retry_policy()")]
+    pub async fn register_candidate(
+        &self,
+        candidate: TrainedCandidate,
+        validation: CandidateValidation,
+    ) -> Result<String, GuardianError> {
+        let request = &candidate.dataset.request;
+        if !validation.passed {
+            return Err(training_error(
+                format!("Run {} candidate failed validation: {}", request.run_id, validation.reason),
+                ErrorCategory::Validation,
+                None,
+            ));
+        }
+
+        let run_dir = self.run_dir(&request.run_id);
+        let version = match read_staged(&run_dir.join(VERSION_FILE)).await {
+            Ok(version) => String::from_utf8_lossy(&version).trim().to_string(),
+            Err(_) => {
+                let version = self.next_version().await?;
+                write_staged(&run_dir, VERSION_FILE, version.as_bytes()).await?;
+                version
+            }
+        };
+        if self.registry.model_metadata(&version).await.is_some() {
+            info!(version = %version, "Candidate already registered");
+            return Ok(version);
+        }
+
+        let model_data = read_staged(&run_dir.join(MODEL_FILE)).await?;
+        let now = Utc::now();
+        let metadata = ModelMetadata {
+            name: TRAINED_MODEL_NAME.to_string(),
+            version: version.clone(),
+            created_at: now,
+            updated_at: now,
+            status: ModelStatus::Inactive,
+            metrics: None,
+            validation_status: ValidationStatus::Success,
+            hash: String::new(),
+            size_bytes: model_data.len() as u64,
+            signature: self.signer.as_ref().map(|signer| signer.sign(&model_data)),
+            provenance: Some(ModelProvenance {
+                training_run_id: request.run_id.clone(),
+                trainer: LOGISTIC_MODEL_FORMAT.to_string(),
+                tenant_id: request.tenant_id.clone(),
+                dataset_since: request.since,
+                dataset_until: request.until,
+                dataset_hash: candidate.dataset.dataset_hash.clone(),
+                training_samples: candidate.training_samples,
+                holdout_samples: candidate.holdout_samples,
+                holdout_accuracy: validation.holdout.accuracy,
+                epochs: self.config.epochs,
+                learning_rate: self.config.learning_rate,
+                base_version: candidate.base_version.clone(),
+                trained_at: now,
+            }),
+        };
+
+        self.registry.register_model(model_data, version.clone(), metadata).await?;
+        self.registry
+            .update_metrics(version.clone(), ModelMetrics {
+                inference_time_ms: 0.0,
+                memory_usage_mb: 0.0,
+                accuracy: validation.holdout.accuracy,
+                true_positives: 0,
+                false_positives: validation.holdout.false_positives,
+                false_negatives: validation.holdout.false_negatives,
+                total_inferences: 0,
+                last_updated: now,
+            })
+            .await?;
+
+        counter!("guardian.training.models_registered", 1);
+        info!(version = %version, accuracy = validation.holdout.accuracy, "Trained model registered");
+        Ok(version)
+    }
+}
+
+/// Parses `vMAJOR.MINOR.PATCH`
+fn parse_version(version: &str) -> Option<(u64, u64, u64)> {
+    let mut parts = version.strip_prefix('v')?.splitn(3, '.').map(|p| p.parse().ok());
+    Some((parts.next()??, parts.next()??, parts.next()??))
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    ring::digest::digest(&ring::digest::SHA256, bytes)
+        .as_ref()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+async fn write_staged(dir: &std::path::Path, file: &str, bytes: &[u8]) -> Result<(), GuardianError> {
+    tokio::fs::create_dir_all(dir)
+        .await
+        .map_err(|e| training_error(format!("Failed to create staging directory {}", dir.display()), ErrorCategory::Storage, Some(Box::new(e))))?;
+    tokio::fs::write(dir.join(file), bytes)
+        .await
+        .map_err(|e| training_error(format!("Failed to stage {}", file), ErrorCategory::Storage, Some(Box::new(e))))
+}
+
+async fn read_staged(path: &std::path::Path) -> Result<Vec<u8>, GuardianError> {
+    tokio::fs::read(path)
+        .await
+        .map_err(|e| training_error(format!("Failed to read staged {}", path.display()), ErrorCategory::Storage, Some(Box::new(e))))
+}
+
+fn training_error(
+    context: String,
+    category: ErrorCategory,
+    source: Option<Box<dyn std::error::Error + Send + Sync>>,
+) -> GuardianError {
+    let severity = ErrorSeverity::High;
+    let timestamp = time::OffsetDateTime::now_utc();
+    let correlation_id = crate::utils::correlation::current_or_new();
+    match category {
+        ErrorCategory::Validation => GuardianError::ValidationError { context, source, severity, timestamp, correlation_id, category, retry_count: 0 },
+        ErrorCategory::Storage => GuardianError::StorageError { context, source, severity, timestamp, correlation_id, category, retry_count: 0 },
+        _ => GuardianError::MLError { context, source, severity, timestamp, correlation_id, category, retry_count: 0 },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(parse_version("v1.2.3"), Some((1, 2, 3)));
+        assert_eq!(parse_version("1.2.3"), None);
+        assert_eq!(parse_version("v1.2"), None);
+        assert_eq!(parse_version("v1.2.x"), None);
+    }
+}
@@ -0,0 +1,186 @@
+//! Endpoint selection and failover bookkeeping for multi-region Temporal deployments
+
+use std::time::Duration;
+
+/// A Temporal frontend, typically one per region
+#[derive(Debug, Clone, PartialEq)]
+pub struct TemporalEndpoint {
+    pub url: String,
+    pub region: String,
+}
+
+impl TemporalEndpoint {
+    pub fn new(url: impl Into<String>, region: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            region: region.into(),
+        }
+    }
+}
+
+/// Health checking, failover and reconnection tuning
+#[derive(Debug, Clone)]
+pub struct FailoverConfig {
+    pub health_check_interval: Duration,
+    /// Consecutive failed checks before leaving the current endpoint
+    pub failure_threshold: u32,
+    /// Consecutive healthy probes of a preferred endpoint before failing back to it
+    pub failback_threshold: u32,
+    pub backoff_base: Duration,
+    pub backoff_max: Duration,
+    /// Rounds over every endpoint before initialization gives up
+    pub initial_connect_rounds: u32,
+    /// Open workflows remembered for the continuity check after a failover
+    pub max_tracked_workflows: usize,
+}
+
+impl Default for FailoverConfig {
+    fn default() -> Self {
+        Self {
+            health_check_interval: Duration::from_secs(10),
+            failure_threshold: 3,
+            failback_threshold: 6,
+            backoff_base: Duration::from_millis(500),
+            backoff_max: Duration::from_secs(30),
+            initial_connect_rounds: 3,
+            max_tracked_workflows: 1000,
+        }
+    }
+}
+
+/// What the health monitor should do after a check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailoverDecision {
+    Stay,
+    /// The current endpoint crossed the failure threshold
+    Failover,
+    /// The preferred endpoint at this index has been healthy long enough to move back
+    Failback(usize),
+}
+
+/// Tracks which endpoint is in use and the streaks that trigger a switch
+#[derive(Debug)]
+pub struct FailoverState {
+    current: usize,
+    failures: u32,
+    /// Preferred endpoint being probed and its healthy streak
+    failback: Option<(usize, u32)>,
+    failure_threshold: u32,
+    failback_threshold: u32,
+}
+
+impl FailoverState {
+    pub fn new(current: usize, config: &FailoverConfig) -> Self {
+        Self {
+            current,
+            failures: 0,
+            failback: None,
+            failure_threshold: config.failure_threshold.max(1),
+            failback_threshold: config.failback_threshold.max(1),
+        }
+    }
+
+    pub fn current(&self) -> usize {
+        self.current
+    }
+
+    /// Records a health check of the current endpoint
+    pub fn record_check(&mut self, healthy: bool) -> FailoverDecision {
+        if healthy {
+            self.failures = 0;
+            return FailoverDecision::Stay;
+        }
+        self.failures += 1;
+        self.failback = None;
+        if self.failures >= self.failure_threshold {
+            FailoverDecision::Failover
+        } else {
+            FailoverDecision::Stay
+        }
+    }
+
+    /// Records a probe of an endpoint preferred over the current one
+    pub fn record_probe(&mut self, index: usize, healthy: bool) -> FailoverDecision {
+        if index >= self.current || !healthy {
+            self.failback = None;
+            return FailoverDecision::Stay;
+        }
+        let streak = match self.failback {
+            Some((probed, streak)) if probed == index => streak + 1,
+            _ => 1,
+        };
+        self.failback = Some((index, streak));
+        if streak >= self.failback_threshold {
+            FailoverDecision::Failback(index)
+        } else {
+            FailoverDecision::Stay
+        }
+    }
+
+    /// Records a completed switch to the endpoint at `index`
+    pub fn switched_to(&mut self, index: usize) {
+        self.current = index;
+        self.failures = 0;
+        self.failback = None;
+    }
+}
+
+/// Endpoint indexes in the order a failover tries them: by priority, the failed endpoint last
+pub fn failover_order(endpoints: usize, failed: usize) -> Vec<usize> {
+    (0..endpoints).filter(|i| *i != failed).chain(std::iter::once(failed).filter(|i| *i < endpoints)).collect()
+}
+
+/// Exponential backoff with full jitter, so regions recovering together are not hit in lockstep
+pub fn jittered_backoff(base: Duration, max: Duration, attempt: u32) -> Duration {
+    let ceiling = base.saturating_mul(1u32 << attempt.min(16)).min(max);
+    ceiling.mul_f64(fastrand::f64())
+}
+
+/// Outcome of a failover, including the workflow continuity check
+#[derive(Debug, Clone, Default)]
+pub struct FailoverReport {
+    pub from: String,
+    pub to: String,
+    /// Workflows open before the failover that the new endpoint knows
+    pub verified: usize,
+    /// Workflows open before the failover that the new endpoint does not know
+    pub missing: Vec<String>,
+}
+
+impl FailoverReport {
+    pub fn is_continuous(&self) -> bool {
+        self.missing.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_failover_and_failback() {
+        let config = FailoverConfig {
+            failure_threshold: 2,
+            failback_threshold: 2,
+            ..Default::default()
+        };
+        let mut state = FailoverState::new(0, &config);
+
+        assert_eq!(state.record_check(false), FailoverDecision::Stay);
+        assert_eq!(state.record_check(false), FailoverDecision::Failover);
+        assert_eq!(failover_order(3, 0), vec![1, 2, 0]);
+        state.switched_to(1);
+
+        // A failed probe resets the failback streak
+        assert_eq!(state.record_probe(0, true), FailoverDecision::Stay);
+        assert_eq!(state.record_probe(0, false), FailoverDecision::Stay);
+        assert_eq!(state.record_probe(0, true), FailoverDecision::Stay);
+        assert_eq!(state.record_probe(0, true), FailoverDecision::Failback(0));
+        assert_eq!(state.record_probe(2, true), FailoverDecision::Stay);
+
+        for attempt in 0..20 {
+            let delay = jittered_backoff(Duration::from_millis(100), Duration::from_secs(1), attempt);
+            assert!(delay <= Duration::from_secs(1));
+        }
+    }
+}
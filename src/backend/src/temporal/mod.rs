@@ -1,23 +1,34 @@
-use std::{sync::Arc, time::Duration};
+use std::{collections::HashSet, sync::Arc, time::Duration};
+use parking_lot::{Mutex, RwLock};
 use temporal_sdk::{Client, Runtime, Worker, WorkerOptions};
 use tracing::{debug, error, info, instrument, warn};
 use metrics::{counter, gauge, histogram};
 
 use crate::utils::error::{GuardianError, ErrorCategory, ErrorSeverity};
+use crate::utils::retry::{RetryExecutor, RetryPolicy};
 
 // Re-export activity and workflow implementations
 pub mod activities;
+pub mod failover;
+pub mod visibility;
 pub mod workflows;
 
 pub use activities::{SecurityActivities, MonitoringActivities, MaintenanceActivities};
+pub use failover::{FailoverConfig, FailoverReport, TemporalEndpoint};
+pub use visibility::{workflow_admin, WorkflowAdmin, WorkflowDetail, WorkflowListFilter, WorkflowState, WorkflowSummary};
 pub use workflows::{SecurityWorkflow, MonitoringWorkflow, MaintenanceWorkflow};
 
+use failover::{failover_order, jittered_backoff, FailoverDecision, FailoverState};
+
 // Core constants for Temporal configuration
 const TEMPORAL_NAMESPACE: &str = "guardian";
 const DEFAULT_TASK_QUEUE: &str = "guardian.default";
 const DEFAULT_TIMEOUT: Duration = Duration::from_secs(3600);
 const CIRCUIT_BREAKER_THRESHOLD: u32 = 5;
 const MAX_CONCURRENT_WORKFLOWS: usize = 1000;
+const DEFAULT_ENDPOINT: &str = "localhost:7233";
+const DEFAULT_REGION: &str = "local";
+const WORKER_DRAIN_TIMEOUT: Duration = Duration::from_secs(30);
 
 /// Configuration for Temporal runtime initialization
 #[derive(Debug, Clone)]
@@ -27,6 +38,9 @@ pub struct TemporalConfig {
     pub worker_options: WorkerOptions,
     pub timeout: Duration,
     pub metrics_enabled: bool,
+    /// Frontends in priority order; the first reachable one is used
+    pub endpoints: Vec<TemporalEndpoint>,
+    pub failover: FailoverConfig,
 }
 
 impl Default for TemporalConfig {
@@ -41,45 +55,135 @@ impl Default for TemporalConfig {
             },
             timeout: DEFAULT_TIMEOUT,
             metrics_enabled: true,
+            endpoints: vec![TemporalEndpoint::new(DEFAULT_ENDPOINT, DEFAULT_REGION)],
+            failover: FailoverConfig::default(),
         }
     }
 }
 
+/// Client and worker bound to one endpoint
+#[derive(Debug, Clone)]
+struct Connection {
+    endpoint: usize,
+    client: Arc<Client>,
+    worker: Arc<Worker>,
+}
+
 /// Core Temporal runtime management
 #[derive(Debug)]
 pub struct TemporalRuntime {
-    client: Arc<Client>,
-    worker: Arc<Worker>,
+    connection: RwLock<Connection>,
     config: TemporalConfig,
+    metrics: Arc<crate::core::metrics::CoreMetricsManager>,
     circuit_breaker_failures: std::sync::atomic::AtomicU32,
+    failover: Mutex<FailoverState>,
+    /// Workflows seen open on the current endpoint, checked on the next one after a failover
+    open_workflows: Mutex<HashSet<String>>,
+    /// Serializes failover and failback so only one switch runs at a time
+    switching: tokio::sync::Mutex<()>,
 }
 
 impl TemporalRuntime {
     /// Initializes the Temporal runtime with enhanced error handling and telemetry
+    ///
+    /// Endpoints are tried in priority order, with jittered backoff between rounds.
     #[instrument(skip(config, metrics), fields(namespace = %config.namespace))]
     pub async fn initialize(
         config: TemporalConfig,
         metrics: Arc<crate::core::metrics::CoreMetricsManager>,
     ) -> Result<Self, GuardianError> {
         info!("Initializing Temporal runtime");
+        if config.endpoints.is_empty() {
+            return Err(GuardianError::SystemError {
+                context: "No Temporal endpoints configured".into(),
+                source: None,
+                severity: ErrorSeverity::Critical,
+                timestamp: time::OffsetDateTime::now_utc(),
+                correlation_id: crate::utils::correlation::current_or_new(),
+                category: ErrorCategory::System,
+                retry_count: 0,
+            });
+        }
 
-        // Initialize Temporal client with retry policy
-        let client = Client::new(
-            temporal_sdk::ConnectionOptions::default()
-                .set_identity("guardian_system")
-                .set_namespace(&config.namespace)
-                .set_target_url("localhost:7233"),
-        )
-        .await
-        .map_err(|e| GuardianError::SystemError {
-            context: "Failed to initialize Temporal client".into(),
-            source: Some(Box::new(e)),
-            severity: ErrorSeverity::Critical,
+        // Each attempt walks every endpoint in priority order
+        let policy = RetryPolicy {
+            max_attempts: config.failover.initial_connect_rounds.max(1),
+            initial_backoff: config.failover.backoff_base,
+            max_backoff: config.failover.backoff_max,
+            jitter: 1.0,
+            ..Default::default()
+        };
+        let connection = RetryExecutor::default()
+            .with_policy(ErrorCategory::System, policy)
+            .run("temporal_connect", || Self::connect_any(&config, &metrics))
+            .await?;
+
+        let endpoint = &config.endpoints[connection.endpoint];
+        info!(endpoint = %endpoint.url, region = %endpoint.region, "Temporal runtime initialized successfully");
+        gauge!("guardian.temporal.endpoint.index", connection.endpoint as f64);
+
+        Ok(Self {
+            failover: Mutex::new(FailoverState::new(connection.endpoint, &config.failover)),
+            connection: RwLock::new(connection),
+            config,
+            metrics,
+            circuit_breaker_failures: std::sync::atomic::AtomicU32::new(0),
+            open_workflows: Mutex::new(HashSet::new()),
+            switching: tokio::sync::Mutex::new(()),
+        })
+    }
+
+    /// Connects to the first reachable endpoint in priority order
+    async fn connect_any(
+        config: &TemporalConfig,
+        metrics: &Arc<crate::core::metrics::CoreMetricsManager>,
+    ) -> Result<Connection, GuardianError> {
+        let mut last_error = None;
+        for index in 0..config.endpoints.len() {
+            match Self::connect(config, index, metrics).await {
+                Ok(connection) => return Ok(connection),
+                Err(e) => {
+                    warn!(endpoint = %config.endpoints[index].url, error = ?e, "Temporal endpoint unavailable");
+                    last_error = Some(e);
+                }
+            }
+        }
+        Err(last_error.expect("endpoints are checked to be non-empty"))
+    }
+
+    /// Connects a client, without a worker, to the first reachable endpoint and returns its URL
+    pub async fn probe(config: &TemporalConfig) -> Result<String, GuardianError> {
+        Self::connect_client_any(config).await.map(|(_, endpoint)| endpoint.url)
+    }
+
+    /// Connects a client, without a worker, to the first reachable endpoint in priority order
+    pub async fn connect_client_any(config: &TemporalConfig) -> Result<(Client, TemporalEndpoint), GuardianError> {
+        let mut last_error = None;
+        for endpoint in &config.endpoints {
+            match Self::connect_client(config, endpoint).await {
+                Ok(client) => return Ok((client, endpoint.clone())),
+                Err(e) => last_error = Some(e),
+            }
+        }
+        Err(last_error.unwrap_or_else(|| GuardianError::SystemError {
+            context: "No Temporal endpoints configured".into(),
+            source: None,
+            severity: ErrorSeverity::High,
             timestamp: time::OffsetDateTime::now_utc(),
-            correlation_id: uuid::Uuid::new_v4(),
+            correlation_id: crate::utils::correlation::current_or_new(),
             category: ErrorCategory::System,
             retry_count: 0,
-        })?;
+        }))
+    }
+
+    /// Connects to one endpoint and starts a worker with every activity and workflow registered
+    async fn connect(
+        config: &TemporalConfig,
+        index: usize,
+        metrics: &Arc<crate::core::metrics::CoreMetricsManager>,
+    ) -> Result<Connection, GuardianError> {
+        let endpoint = &config.endpoints[index];
+        let client = Self::connect_client(config, endpoint).await?;
 
         // Create worker with configured options
         let worker = Worker::new(
@@ -96,7 +200,7 @@ impl TemporalRuntime {
                 source: Some(Box::new(e)),
                 severity: ErrorSeverity::Critical,
                 timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id: uuid::Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: ErrorCategory::System,
                 retry_count: 0,
             })?;
@@ -105,9 +209,9 @@ impl TemporalRuntime {
         workflows::register_workflows(
             client.clone(),
             workflows::WorkflowConfig {
-                temporal_url: "localhost:7233".to_string(),
+                temporal_url: endpoint.url.clone(),
                 security_config: Default::default(),
-                metrics_manager: metrics,
+                metrics_manager: metrics.clone(),
                 system_state: Arc::new(parking_lot::RwLock::new(
                     crate::core::system_state::SystemState::default(),
                 )),
@@ -117,26 +221,218 @@ impl TemporalRuntime {
         )
         .await?;
 
-        let runtime = Self {
-            client: Arc::new(client),
-            worker: Arc::new(worker),
-            config,
-            circuit_breaker_failures: std::sync::atomic::AtomicU32::new(0),
-        };
-
         // Start worker
-        runtime.worker.start().await.map_err(|e| GuardianError::SystemError {
-            context: "Failed to start Temporal worker".into(),
+        worker.start().await.map_err(|e| GuardianError::SystemError {
+            context: format!("Failed to start Temporal worker against {}", endpoint.url),
             source: Some(Box::new(e)),
             severity: ErrorSeverity::Critical,
             timestamp: time::OffsetDateTime::now_utc(),
-            correlation_id: uuid::Uuid::new_v4(),
+            correlation_id: crate::utils::correlation::current_or_new(),
             category: ErrorCategory::System,
             retry_count: 0,
         })?;
 
-        info!("Temporal runtime initialized successfully");
-        Ok(runtime)
+        Ok(Connection {
+            endpoint: index,
+            client: Arc::new(client),
+            worker: Arc::new(worker),
+        })
+    }
+
+    async fn connect_client(config: &TemporalConfig, endpoint: &TemporalEndpoint) -> Result<Client, GuardianError> {
+        Client::new(
+            temporal_sdk::ConnectionOptions::default()
+                .set_identity("guardian_system")
+                .set_namespace(&config.namespace)
+                .set_target_url(&endpoint.url),
+        )
+        .await
+        .map_err(|e| GuardianError::SystemError {
+            context: format!("Failed to connect to Temporal endpoint {} ({})", endpoint.url, endpoint.region),
+            source: Some(Box::new(e)),
+            severity: ErrorSeverity::High,
+            timestamp: time::OffsetDateTime::now_utc(),
+            correlation_id: crate::utils::correlation::current_or_new(),
+            category: ErrorCategory::System,
+            retry_count: 0,
+        })
+    }
+
+    /// Client of the endpoint currently in use
+    pub fn client(&self) -> Arc<Client> {
+        self.connection.read().client.clone()
+    }
+
+    fn worker(&self) -> Arc<Worker> {
+        self.connection.read().worker.clone()
+    }
+
+    /// Endpoint currently in use
+    pub fn current_endpoint(&self) -> TemporalEndpoint {
+        self.config.endpoints[self.connection.read().endpoint].clone()
+    }
+
+    /// Starts the background health monitor that fails over, reconnects and fails back
+    pub fn start_failover_monitor(self: &Arc<Self>) -> tokio::task::JoinHandle<()> {
+        let runtime = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(runtime.config.failover.health_check_interval);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                runtime.monitor_tick().await;
+            }
+        })
+    }
+
+    async fn monitor_tick(&self) {
+        let client = self.client();
+        let healthy = client.get_system_info().await.is_ok();
+        let decision = self.failover.lock().record_check(healthy);
+        if decision == FailoverDecision::Failover {
+            self.fail_over().await;
+            return;
+        }
+        if !healthy {
+            counter!("guardian.temporal.health_check.failures", 1);
+            return;
+        }
+
+        self.refresh_open_workflows(&client).await;
+
+        // While on a fallback endpoint, probe the preferred ones to move back once they recover
+        let current = self.failover.lock().current();
+        if current == 0 {
+            return;
+        }
+        let mut preferred = None;
+        for index in 0..current {
+            if self.probe(index).await {
+                preferred = Some(index);
+                break;
+            }
+        }
+        let decision = match preferred {
+            Some(index) => self.failover.lock().record_probe(index, true),
+            None => self.failover.lock().record_probe(0, false),
+        };
+        if let FailoverDecision::Failback(index) = decision {
+            self.switch_to(index, "failback").await;
+        }
+    }
+
+    async fn probe(&self, index: usize) -> bool {
+        match Self::connect_client(&self.config, &self.config.endpoints[index]).await {
+            Ok(candidate) => candidate.get_system_info().await.is_ok(),
+            Err(_) => false,
+        }
+    }
+
+    /// Remembers workflows open on the current endpoint for the continuity check
+    async fn refresh_open_workflows(&self, client: &Client) {
+        let limit = self.config.failover.max_tracked_workflows;
+        match client.list_open_workflow_executions(limit as i32, Vec::new(), None).await {
+            Ok(response) => {
+                let open: HashSet<String> = response
+                    .executions
+                    .into_iter()
+                    .filter_map(|execution| execution.execution.map(|e| e.workflow_id))
+                    .take(limit)
+                    .collect();
+                gauge!("guardian.temporal.workflows.tracked", open.len() as f64);
+                *self.open_workflows.lock() = open;
+            }
+            Err(e) => debug!(error = ?e, "Failed to list open workflows"),
+        }
+    }
+
+    /// Moves to the next reachable endpoint, retrying every endpoint with jittered backoff until one answers
+    #[instrument(skip(self))]
+    async fn fail_over(&self) {
+        let _switching = self.switching.lock().await;
+        let failed = self.failover.lock().current();
+        warn!(endpoint = %self.config.endpoints[failed].url, "Temporal endpoint unhealthy, failing over");
+        counter!("guardian.temporal.failovers", 1);
+
+        let mut attempt = 0;
+        loop {
+            for index in failover_order(self.config.endpoints.len(), failed) {
+                match Self::connect(&self.config, index, &self.metrics).await {
+                    Ok(connection) => {
+                        let report = self.install(connection).await;
+                        self.log_report("failover", &report);
+                        return;
+                    }
+                    Err(e) => {
+                        warn!(endpoint = %self.config.endpoints[index].url, error = ?e, "Temporal endpoint unavailable");
+                    }
+                }
+            }
+            let delay = jittered_backoff(self.config.failover.backoff_base, self.config.failover.backoff_max, attempt);
+            counter!("guardian.temporal.reconnect.attempts", 1);
+            warn!(attempt, delay_ms = delay.as_millis() as u64, "No Temporal endpoint reachable, retrying");
+            tokio::time::sleep(delay).await;
+            attempt = attempt.saturating_add(1);
+        }
+    }
+
+    /// Moves to a specific endpoint, staying put if it cannot be reached
+    async fn switch_to(&self, index: usize, reason: &'static str) {
+        let _switching = self.switching.lock().await;
+        match Self::connect(&self.config, index, &self.metrics).await {
+            Ok(connection) => {
+                let report = self.install(connection).await;
+                self.log_report(reason, &report);
+            }
+            Err(e) => warn!(endpoint = %self.config.endpoints[index].url, error = ?e, "Temporal {} aborted", reason),
+        }
+    }
+
+    /// Swaps in a new connection, drains the old worker and checks workflow continuity
+    async fn install(&self, connection: Connection) -> FailoverReport {
+        let new_client = connection.client.clone();
+        let new_index = connection.endpoint;
+        let previous = std::mem::replace(&mut *self.connection.write(), connection);
+        self.failover.lock().switched_to(new_index);
+        self.circuit_breaker_failures.store(0, std::sync::atomic::Ordering::Relaxed);
+        gauge!("guardian.temporal.endpoint.index", new_index as f64);
+
+        // The old worker may hold activities; give them the drain timeout before dropping it
+        previous.worker.stop().await;
+        if tokio::time::timeout(WORKER_DRAIN_TIMEOUT, previous.worker.wait_until_stopped()).await.is_err() {
+            warn!(endpoint = %self.config.endpoints[previous.endpoint].url, "Previous Temporal worker did not drain in time");
+        }
+
+        let tracked: Vec<String> = self.open_workflows.lock().iter().cloned().collect();
+        let mut report = FailoverReport {
+            from: self.config.endpoints[previous.endpoint].url.clone(),
+            to: self.config.endpoints[new_index].url.clone(),
+            ..Default::default()
+        };
+        for workflow_id in tracked {
+            match new_client.describe_workflow_execution(workflow_id.clone(), None).await {
+                Ok(_) => report.verified += 1,
+                Err(_) => report.missing.push(workflow_id),
+            }
+        }
+        report
+    }
+
+    fn log_report(&self, reason: &str, report: &FailoverReport) {
+        counter!("guardian.temporal.failover.missing_workflows", report.missing.len() as u64);
+        if report.is_continuous() {
+            info!(reason, from = %report.from, to = %report.to, verified = report.verified, "Temporal endpoint switched");
+        } else {
+            // Missing workflows were not replicated to the new region and need operator attention
+            error!(
+                reason,
+                from = %report.from,
+                to = %report.to,
+                verified = report.verified,
+                missing = ?report.missing,
+                "Temporal endpoint switched, workflows missing on the new endpoint"
+            );
+        }
     }
 
     /// Gracefully shuts down the Temporal runtime with resource cleanup
@@ -145,18 +441,18 @@ impl TemporalRuntime {
         info!("Shutting down Temporal runtime");
 
         // Stop accepting new workflows
-        self.worker.stop().await;
+        let worker = self.worker();
+        worker.stop().await;
 
         // Wait for active workflows to complete
-        let timeout = Duration::from_secs(30);
-        tokio::time::timeout(timeout, self.worker.wait_until_stopped())
+        tokio::time::timeout(WORKER_DRAIN_TIMEOUT, worker.wait_until_stopped())
             .await
             .map_err(|_| GuardianError::SystemError {
                 context: "Timeout waiting for worker shutdown".into(),
                 source: None,
                 severity: ErrorSeverity::High,
                 timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id: uuid::Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: ErrorCategory::System,
                 retry_count: 0,
             })?;
@@ -173,7 +469,7 @@ impl TemporalRuntime {
         }
 
         // Check client connectivity
-        if let Err(e) = self.client.get_system_info().await {
+        if let Err(e) = self.client().get_system_info().await {
             error!(?e, "Temporal client health check failed");
             self.circuit_breaker_failures
                 .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
@@ -188,13 +484,18 @@ impl TemporalRuntime {
         let mut metrics = Vec::new();
 
         // Collect worker metrics
+        let worker = self.worker();
         metrics.push((
             "guardian.temporal.workflows.active".into(),
-            self.worker.get_running_workflows() as f64,
+            worker.get_running_workflows() as f64,
         ));
         metrics.push((
             "guardian.temporal.activities.active".into(),
-            self.worker.get_running_activities() as f64,
+            worker.get_running_activities() as f64,
+        ));
+        metrics.push((
+            "guardian.temporal.endpoint.index".into(),
+            self.connection.read().endpoint as f64,
         ));
 
         // Collect circuit breaker metrics
@@ -0,0 +1,408 @@
+//! Workflow visibility and administration
+//!
+//! Lists Guardian's security and maintenance workflows as Temporal records them, describes
+//! the step a running workflow is on and how often it has been retried, and cancels or
+//! signals a workflow. Only workflow types Guardian owns can be cancelled or signalled, and
+//! only while they are running; everything else is refused before reaching Temporal.
+
+use std::{collections::HashMap, sync::Arc};
+
+use chrono::{DateTime, TimeZone, Utc};
+use metrics::counter;
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use serde::Serialize;
+use temporal_sdk::protos::temporal::api::common::v1::{Payload, Payloads};
+use temporal_sdk::Client;
+use tracing::{info, instrument};
+
+use super::{TemporalConfig, TemporalRuntime};
+use crate::utils::error::{ErrorCategory, ErrorSeverity, GuardianError};
+
+/// Workflow types exposed to operators, as registered with the worker
+pub const VISIBLE_WORKFLOW_TYPES: &[&str] = &["security_workflow", "maintenance_workflow"];
+
+/// Most executions fetched per listing
+pub const DEFAULT_LIST_LIMIT: usize = 100;
+
+static WORKFLOW_ADMIN: Lazy<Arc<WorkflowAdmin>> = Lazy::new(|| Arc::new(WorkflowAdmin::new(TemporalConfig::default())));
+
+/// Returns the process-wide workflow administration handle
+pub fn workflow_admin() -> Arc<WorkflowAdmin> {
+    Arc::clone(&WORKFLOW_ADMIN)
+}
+
+/// Execution status as reported by Temporal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WorkflowState {
+    Running,
+    Completed,
+    Failed,
+    Canceled,
+    Terminated,
+    ContinuedAsNew,
+    TimedOut,
+    Unknown,
+}
+
+impl WorkflowState {
+    /// Maps Temporal's `WorkflowExecutionStatus` enum value
+    pub fn from_status(status: i32) -> Self {
+        match status {
+            1 => Self::Running,
+            2 => Self::Completed,
+            3 => Self::Failed,
+            4 => Self::Canceled,
+            5 => Self::Terminated,
+            6 => Self::ContinuedAsNew,
+            7 => Self::TimedOut,
+            _ => Self::Unknown,
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        *self == Self::Running
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Running => "running",
+            Self::Completed => "completed",
+            Self::Failed => "failed",
+            Self::Canceled => "canceled",
+            Self::Terminated => "terminated",
+            Self::ContinuedAsNew => "continued_as_new",
+            Self::TimedOut => "timed_out",
+            Self::Unknown => "unknown",
+        }
+    }
+}
+
+/// Which executions a listing returns
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WorkflowListFilter {
+    #[default]
+    Running,
+    Closed,
+    All,
+}
+
+/// One workflow execution
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkflowSummary {
+    pub workflow_id: String,
+    pub run_id: String,
+    pub workflow_type: String,
+    pub state: WorkflowState,
+    pub started_at: Option<DateTime<Utc>>,
+    pub closed_at: Option<DateTime<Utc>>,
+    pub history_length: i64,
+}
+
+/// Activity a running workflow is waiting on
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PendingStep {
+    pub activity: String,
+    /// Current attempt, starting at 1
+    pub attempt: u32,
+    /// Zero when retries are unlimited
+    pub maximum_attempts: u32,
+    pub last_failure: Option<String>,
+}
+
+/// A workflow execution with its progress
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkflowDetail {
+    #[serde(flatten)]
+    pub summary: WorkflowSummary,
+    /// Activity the workflow is on, the first pending one when several run in parallel
+    pub current_step: Option<String>,
+    /// Retries of the pending activities so far
+    pub retries: u32,
+    pub pending_steps: Vec<PendingStep>,
+}
+
+impl WorkflowDetail {
+    fn new(summary: WorkflowSummary, pending_steps: Vec<PendingStep>) -> Self {
+        Self {
+            summary,
+            current_step: pending_steps.first().map(|step| step.activity.clone()),
+            retries: pending_steps.iter().map(|step| step.attempt.saturating_sub(1)).sum(),
+            pending_steps,
+        }
+    }
+}
+
+/// Lists, describes, cancels and signals Guardian workflows
+///
+/// Uses the client of an attached runtime, so it follows failovers; without one it connects
+/// its own client to the first reachable endpoint on first use.
+#[derive(Debug)]
+pub struct WorkflowAdmin {
+    config: TemporalConfig,
+    runtime: RwLock<Option<Arc<TemporalRuntime>>>,
+    client: tokio::sync::OnceCell<Arc<Client>>,
+}
+
+impl WorkflowAdmin {
+    pub fn new(config: TemporalConfig) -> Self {
+        Self {
+            config,
+            runtime: RwLock::new(None),
+            client: tokio::sync::OnceCell::new(),
+        }
+    }
+
+    /// Uses the runtime's client from now on
+    pub fn attach_runtime(&self, runtime: Arc<TemporalRuntime>) {
+        *self.runtime.write() = Some(runtime);
+    }
+
+    async fn client(&self) -> Result<Arc<Client>, GuardianError> {
+        if let Some(runtime) = self.runtime.read().as_ref() {
+            return Ok(runtime.client());
+        }
+        self.client
+            .get_or_try_init(|| async {
+                TemporalRuntime::connect_client_any(&self.config)
+                    .await
+                    .map(|(client, _)| Arc::new(client))
+            })
+            .await
+            .cloned()
+    }
+
+    /// Lists Guardian workflows, newest first
+    #[instrument(skip(self))]
+    pub async fn list(
+        &self,
+        filter: WorkflowListFilter,
+        workflow_type: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<WorkflowSummary>, GuardianError> {
+        if let Some(workflow_type) = workflow_type {
+            check_visible(workflow_type)?;
+        }
+        let client = self.client().await?;
+        let mut executions = Vec::new();
+        if filter != WorkflowListFilter::Closed {
+            let response = client
+                .list_open_workflow_executions(limit as i32, Vec::new(), None)
+                .await
+                .map_err(|e| temporal_error("Failed to list running workflows", e))?;
+            executions.extend(response.executions);
+        }
+        if filter != WorkflowListFilter::Running {
+            let response = client
+                .list_closed_workflow_executions(limit as i32, Vec::new(), None)
+                .await
+                .map_err(|e| temporal_error("Failed to list closed workflows", e))?;
+            executions.extend(response.executions);
+        }
+
+        let mut workflows: Vec<WorkflowSummary> = executions
+            .into_iter()
+            .map(summary_from_info)
+            .filter(|summary| match workflow_type {
+                Some(workflow_type) => summary.workflow_type == workflow_type,
+                None => VISIBLE_WORKFLOW_TYPES.contains(&summary.workflow_type.as_str()),
+            })
+            .collect();
+        workflows.sort_by(|a, b| b.started_at.cmp(&a.started_at));
+        workflows.truncate(limit);
+        Ok(workflows)
+    }
+
+    /// Describes a workflow with the step it is on and its retries
+    #[instrument(skip(self))]
+    pub async fn describe(&self, workflow_id: &str, run_id: Option<&str>) -> Result<WorkflowDetail, GuardianError> {
+        let response = self
+            .client()
+            .await?
+            .describe_workflow_execution(workflow_id.to_string(), run_id.map(str::to_string))
+            .await
+            .map_err(|e| temporal_error(&format!("Failed to describe workflow {}", workflow_id), e))?;
+        let summary = response
+            .workflow_execution_info
+            .map(summary_from_info)
+            .ok_or_else(|| workflow_error(format!("Workflow {} has no execution info", workflow_id)))?;
+        check_visible(&summary.workflow_type)?;
+
+        let pending = response
+            .pending_activities
+            .into_iter()
+            .map(|activity| PendingStep {
+                activity: activity.activity_type.map(|t| t.name).unwrap_or_default(),
+                attempt: activity.attempt.max(0) as u32,
+                maximum_attempts: activity.maximum_attempts.max(0) as u32,
+                last_failure: activity.last_failure.map(|failure| failure.message),
+            })
+            .collect();
+        Ok(WorkflowDetail::new(summary, pending))
+    }
+
+    /// Requests cancellation of a running workflow; it runs its compensations before closing
+    #[instrument(skip(self))]
+    pub async fn cancel(&self, workflow_id: &str, run_id: Option<&str>, reason: &str) -> Result<WorkflowSummary, GuardianError> {
+        let summary = self.running(workflow_id, run_id).await?;
+        self.client()
+            .await?
+            .cancel_workflow_execution(workflow_id.to_string(), Some(summary.run_id.clone()), reason.to_string(), None)
+            .await
+            .map_err(|e| temporal_error(&format!("Failed to cancel workflow {}", workflow_id), e))?;
+
+        info!(workflow_id, run_id = %summary.run_id, reason, "Workflow cancellation requested");
+        counter!("guardian.temporal.workflows.cancelled", 1, "type" => summary.workflow_type.clone());
+        Ok(summary)
+    }
+
+    /// Sends a signal with an optional JSON payload to a running workflow
+    #[instrument(skip(self, payload))]
+    pub async fn signal(
+        &self,
+        workflow_id: &str,
+        run_id: Option<&str>,
+        signal: &str,
+        payload: Option<serde_json::Value>,
+    ) -> Result<WorkflowSummary, GuardianError> {
+        if signal.trim().is_empty() {
+            return Err(validation_error("Signal name must not be empty".into()));
+        }
+        let summary = self.running(workflow_id, run_id).await?;
+        let payloads = payload.map(|value| json_payloads(&value)).transpose()?;
+        self.client()
+            .await?
+            .signal_workflow_execution(workflow_id.to_string(), summary.run_id.clone(), signal.to_string(), payloads, None)
+            .await
+            .map_err(|e| temporal_error(&format!("Failed to signal workflow {}", workflow_id), e))?;
+
+        info!(workflow_id, run_id = %summary.run_id, signal, "Workflow signalled");
+        counter!("guardian.temporal.workflows.signalled", 1, "type" => summary.workflow_type.clone(), "signal" => signal.to_string());
+        Ok(summary)
+    }
+
+    /// Describes a workflow and refuses unless it is running
+    async fn running(&self, workflow_id: &str, run_id: Option<&str>) -> Result<WorkflowSummary, GuardianError> {
+        let summary = self.describe(workflow_id, run_id).await?.summary;
+        if !summary.state.is_running() {
+            return Err(validation_error(format!(
+                "Workflow {} is {}, only running workflows can be changed",
+                workflow_id,
+                summary.state.as_str()
+            )));
+        }
+        Ok(summary)
+    }
+}
+
+fn check_visible(workflow_type: &str) -> Result<(), GuardianError> {
+    if VISIBLE_WORKFLOW_TYPES.contains(&workflow_type) {
+        Ok(())
+    } else {
+        Err(validation_error(format!(
+            "Workflow type {} is not administered by Guardian, expected one of {}",
+            workflow_type,
+            VISIBLE_WORKFLOW_TYPES.join(", ")
+        )))
+    }
+}
+
+fn summary_from_info(info: temporal_sdk::protos::temporal::api::workflow::v1::WorkflowExecutionInfo) -> WorkflowSummary {
+    let (workflow_id, run_id) = info.execution.map(|e| (e.workflow_id, e.run_id)).unwrap_or_default();
+    WorkflowSummary {
+        workflow_id,
+        run_id,
+        workflow_type: info.r#type.map(|t| t.name).unwrap_or_default(),
+        state: WorkflowState::from_status(info.status),
+        started_at: info.start_time.and_then(from_timestamp),
+        closed_at: info.close_time.and_then(from_timestamp),
+        history_length: info.history_length,
+    }
+}
+
+fn from_timestamp(timestamp: prost_types::Timestamp) -> Option<DateTime<Utc>> {
+    Utc.timestamp_opt(timestamp.seconds, timestamp.nanos.max(0) as u32).single()
+}
+
+/// Encodes a signal argument the way the SDK's JSON converter does
+fn json_payloads(value: &serde_json::Value) -> Result<Payloads, GuardianError> {
+    Ok(Payloads {
+        payloads: vec![Payload {
+            metadata: HashMap::from([("encoding".to_string(), b"json/plain".to_vec())]),
+            data: serde_json::to_vec(value)?,
+        }],
+    })
+}
+
+fn temporal_error<E>(context: &str, source: E) -> GuardianError
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    GuardianError::SystemError {
+        context: context.to_string(),
+        source: Some(Box::new(source)),
+        severity: ErrorSeverity::Medium,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: ErrorCategory::System,
+        retry_count: 0,
+    }
+}
+
+fn workflow_error(context: String) -> GuardianError {
+    GuardianError::SystemError {
+        context,
+        source: None,
+        severity: ErrorSeverity::Medium,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: ErrorCategory::System,
+        retry_count: 0,
+    }
+}
+
+fn validation_error(context: String) -> GuardianError {
+    GuardianError::ValidationError {
+        context,
+        source: None,
+        severity: ErrorSeverity::Low,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: ErrorCategory::Validation,
+        retry_count: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detail_reports_current_step_and_retries() {
+        let summary = WorkflowSummary {
+            workflow_id: "security-1".into(),
+            run_id: "run-1".into(),
+            workflow_type: "security_workflow".into(),
+            state: WorkflowState::from_status(1),
+            started_at: None,
+            closed_at: None,
+            history_length: 12,
+        };
+        let step = |activity: &str, attempt| PendingStep {
+            activity: activity.into(),
+            attempt,
+            maximum_attempts: 3,
+            last_failure: None,
+        };
+        let detail = WorkflowDetail::new(summary, vec![step("execute_response_step", 3), step("record_audit", 1)]);
+        assert_eq!(detail.current_step.as_deref(), Some("execute_response_step"));
+        assert_eq!(detail.retries, 2);
+        assert!(detail.summary.state.is_running());
+
+        assert_eq!(WorkflowState::from_status(4), WorkflowState::Canceled);
+        assert_eq!(WorkflowState::from_status(0), WorkflowState::Unknown);
+        assert!(check_visible("maintenance_workflow").is_ok());
+        assert!(check_visible("canary_workflow").is_err());
+    }
+}
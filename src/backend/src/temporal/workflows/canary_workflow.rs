@@ -0,0 +1,286 @@
+use std::time::Duration;
+use async_trait::async_trait;
+use temporal_sdk::{
+    workflow,
+    workflow::{Context, WorkflowResult},
+    ActivityOptions, RetryPolicy,
+};
+use tracing::{info, warn, error, instrument};
+use serde::{Serialize, Deserialize};
+
+use crate::temporal::activities::{CanaryMetrics, ModelActivities};
+use crate::ml::model_registry::ModelMetrics;
+use crate::utils::error::GuardianError;
+
+// Constants for canary rollout configuration
+const DEFAULT_TRAFFIC_PERCENT: u8 = 10;
+const DEFAULT_OBSERVATION_INTERVAL: Duration = Duration::from_secs(300);
+const DEFAULT_MAX_OBSERVATIONS: u32 = 24;
+const DEFAULT_MIN_INFERENCES: u64 = 1000;
+const DEFAULT_MAX_ACCURACY_DROP: f64 = 0.01;
+const DEFAULT_MAX_LATENCY_FACTOR: f64 = 1.2;
+const MAX_RETRY_ATTEMPTS: u32 = 3;
+
+/// Rollout parameters and promotion thresholds for a candidate model
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CanaryConfig {
+    pub candidate_version: String,
+    /// Percentage of inference traffic served by the candidate
+    pub traffic_percent: u8,
+    pub observation_interval: Duration,
+    /// Observations before an under-sampled canary is rolled back
+    pub max_observations: u32,
+    /// Candidate inferences required before a decision is made
+    pub min_inferences: u64,
+    /// Largest tolerated accuracy loss against the incumbent
+    pub max_accuracy_drop: f64,
+    /// Largest tolerated candidate/incumbent mean inference time ratio
+    pub max_latency_factor: f64,
+}
+
+impl CanaryConfig {
+    pub fn new(candidate_version: String) -> Self {
+        Self {
+            candidate_version,
+            traffic_percent: DEFAULT_TRAFFIC_PERCENT,
+            observation_interval: DEFAULT_OBSERVATION_INTERVAL,
+            max_observations: DEFAULT_MAX_OBSERVATIONS,
+            min_inferences: DEFAULT_MIN_INFERENCES,
+            max_accuracy_drop: DEFAULT_MAX_ACCURACY_DROP,
+            max_latency_factor: DEFAULT_MAX_LATENCY_FACTOR,
+        }
+    }
+}
+
+/// Outcome of one canary observation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum CanaryDecision {
+    Continue,
+    Promote,
+    Rollback(String),
+}
+
+/// Final result of a canary rollout
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CanaryOutcome {
+    pub candidate_version: String,
+    pub incumbent_version: String,
+    pub promoted: bool,
+    pub reason: String,
+    pub observations: u32,
+    pub candidate_metrics: Option<ModelMetrics>,
+}
+
+/// Workflow state for persistence
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CanaryState {
+    incumbent_version: Option<String>,
+    observations: u32,
+    last_metrics: Option<CanaryMetrics>,
+}
+
+/// Staged model rollout that promotes or rolls back a candidate on live metrics
+#[derive(Debug)]
+#[workflow_version("1.0.0")]
+pub struct CanaryWorkflow {
+    activities: ModelActivities,
+    state: CanaryState,
+}
+
+impl CanaryWorkflow {
+    pub fn new(activities: ModelActivities) -> Self {
+        Self {
+            activities,
+            state: CanaryState {
+                incumbent_version: None,
+                observations: 0,
+                last_metrics: None,
+            },
+        }
+    }
+
+    fn rollout_retry_policy() -> RetryPolicy {
+        RetryPolicy {
+            initial_interval: Duration::from_secs(1),
+            backoff: 2.0,
+            max_interval: Duration::from_secs(30),
+            max_attempts: MAX_RETRY_ATTEMPTS,
+            non_retryable_error_types: vec!["ValidationError".to_string(), "MLError".to_string()],
+        }
+    }
+}
+
+#[async_trait]
+impl CanaryWorkflow {
+    /// Runs the canary until it is promoted or rolled back
+    #[instrument(skip(self))]
+    #[workflow::workflow]
+    pub async fn execute_canary(&mut self, config: CanaryConfig) -> WorkflowResult<CanaryOutcome> {
+        info!(version = %config.candidate_version, traffic = config.traffic_percent, "Starting canary rollout");
+
+        let ctx = workflow::Context::current();
+        let options = ActivityOptions {
+            retry_policy: Some(Self::rollout_retry_policy()),
+            ..Default::default()
+        };
+
+        let incumbent = ctx.with_activity_options(options.clone())
+            .activity()
+            .start_canary(config.candidate_version.clone(), config.traffic_percent)
+            .await
+            .map_err(|e| rollout_error("Failed to start canary", e))?;
+        self.state.incumbent_version = Some(incumbent.clone());
+        ctx.persist_workflow_state(&self.state)?;
+
+        let decision = loop {
+            ctx.timer(config.observation_interval).await?;
+            self.state.observations += 1;
+
+            let decision = match ctx.with_activity_options(options.clone())
+                .activity()
+                .canary_metrics(config.candidate_version.clone(), incumbent.clone())
+                .await
+            {
+                Ok(metrics) => {
+                    let decision = evaluate_canary(&config, &metrics, self.state.observations);
+                    self.state.last_metrics = Some(metrics);
+                    decision
+                }
+                Err(e) => {
+                    error!(?e, "Failed to fetch canary metrics");
+                    CanaryDecision::Rollback(format!("metrics unavailable: {}", e))
+                }
+            };
+
+            ctx.persist_workflow_state(&self.state)?;
+            if decision != CanaryDecision::Continue {
+                break decision;
+            }
+        };
+
+        let (promoted, reason) = match decision {
+            CanaryDecision::Promote => {
+                ctx.with_activity_options(options)
+                    .activity()
+                    .promote_canary(config.candidate_version.clone())
+                    .await
+                    .map_err(|e| rollout_error("Failed to promote canary", e))?;
+                (true, "candidate within accuracy and latency thresholds".to_string())
+            }
+            CanaryDecision::Rollback(reason) => {
+                warn!(version = %config.candidate_version, reason = %reason, "Rolling back canary");
+                ctx.with_activity_options(options)
+                    .activity()
+                    .abort_canary(reason.clone())
+                    .await
+                    .map_err(|e| rollout_error("Failed to roll back canary", e))?;
+                (false, reason)
+            }
+            CanaryDecision::Continue => unreachable!("loop exits on a final decision"),
+        };
+
+        Ok(CanaryOutcome {
+            candidate_version: config.candidate_version,
+            incumbent_version: incumbent,
+            promoted,
+            reason,
+            observations: self.state.observations,
+            candidate_metrics: self.state.last_metrics.as_ref().and_then(|m| m.candidate.clone()),
+        })
+    }
+}
+
+/// Compares the candidate against the incumbent once enough traffic has been served
+fn evaluate_canary(config: &CanaryConfig, metrics: &CanaryMetrics, observations: u32) -> CanaryDecision {
+    let served = metrics.candidate.as_ref().map_or(0, |m| m.total_inferences);
+    if served < config.min_inferences {
+        if observations >= config.max_observations {
+            return CanaryDecision::Rollback(format!(
+                "only {} of {} required inferences after {} observations",
+                served, config.min_inferences, observations
+            ));
+        }
+        return CanaryDecision::Continue;
+    }
+
+    // Nothing to regress against without incumbent metrics
+    let (Some(candidate), Some(incumbent)) = (&metrics.candidate, &metrics.incumbent) else {
+        return CanaryDecision::Promote;
+    };
+
+    let accuracy_drop = incumbent.accuracy - candidate.accuracy;
+    if accuracy_drop > config.max_accuracy_drop {
+        return CanaryDecision::Rollback(format!(
+            "accuracy {:.4} is {:.4} below incumbent",
+            candidate.accuracy, accuracy_drop
+        ));
+    }
+
+    if incumbent.inference_time_ms > 0.0
+        && candidate.inference_time_ms > incumbent.inference_time_ms * config.max_latency_factor
+    {
+        return CanaryDecision::Rollback(format!(
+            "inference time {:.2}ms exceeds {:.1}x incumbent {:.2}ms",
+            candidate.inference_time_ms, config.max_latency_factor, incumbent.inference_time_ms
+        ));
+    }
+
+    CanaryDecision::Promote
+}
+
+fn rollout_error(context: &str, e: impl std::error::Error + Send + Sync + 'static) -> GuardianError {
+    GuardianError::MLError {
+        context: context.into(),
+        source: Some(Box::new(e)),
+        severity: crate::utils::error::ErrorSeverity::High,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: crate::utils::error::ErrorCategory::ML,
+        retry_count: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metrics(accuracy: f64, inference_time_ms: f64, total_inferences: u64) -> ModelMetrics {
+        ModelMetrics {
+            inference_time_ms,
+            memory_usage_mb: 0.0,
+            accuracy,
+            true_positives: 0,
+            false_positives: 0,
+            false_negatives: 0,
+            total_inferences,
+            last_updated: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_canary_decision_thresholds() {
+        let config = CanaryConfig::new("v2".into());
+        let observe = |candidate, incumbent| CanaryMetrics { candidate: Some(candidate), incumbent: Some(incumbent) };
+
+        let healthy = observe(metrics(0.995, 10.0, 5000), metrics(0.99, 9.0, 50000));
+        assert_eq!(evaluate_canary(&config, &healthy, 1), CanaryDecision::Promote);
+
+        let inaccurate = observe(metrics(0.95, 10.0, 5000), metrics(0.99, 9.0, 50000));
+        assert!(matches!(evaluate_canary(&config, &inaccurate, 1), CanaryDecision::Rollback(_)));
+
+        let slow = observe(metrics(0.99, 20.0, 5000), metrics(0.99, 9.0, 50000));
+        assert!(matches!(evaluate_canary(&config, &slow, 1), CanaryDecision::Rollback(_)));
+    }
+
+    #[test]
+    fn test_undersampled_canary_times_out() {
+        let config = CanaryConfig::new("v2".into());
+        let sparse = CanaryMetrics { candidate: Some(metrics(0.99, 9.0, 10)), incumbent: None };
+
+        assert_eq!(evaluate_canary(&config, &sparse, 1), CanaryDecision::Continue);
+        assert!(matches!(
+            evaluate_canary(&config, &sparse, config.max_observations),
+            CanaryDecision::Rollback(_)
+        ));
+    }
+}
@@ -0,0 +1,117 @@
+use std::future::Future;
+
+use serde::{Deserialize, Serialize};
+use tracing::{error, warn};
+
+use crate::security::response_engine::ResponseAction;
+use crate::utils::error::GuardianError;
+
+/// Undo action registered by a completed workflow step
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Compensation {
+    pub step: String,
+    pub action: ResponseAction,
+}
+
+/// How undoing one step went
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompensationOutcome {
+    pub step: String,
+    pub action: String,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+/// Saga of a multi-step workflow: each completed step registers how to undo it
+#[derive(Debug, Default)]
+pub struct Saga {
+    compensations: Vec<Compensation>,
+}
+
+impl Saga {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the undo action of a step that completed
+    pub fn register(&mut self, step: impl Into<String>, action: ResponseAction) {
+        self.compensations.push(Compensation {
+            step: step.into(),
+            action,
+        });
+    }
+
+    /// Number of steps that can still be undone
+    pub fn len(&self) -> usize {
+        self.compensations.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.compensations.is_empty()
+    }
+
+    /// Undoes the registered steps newest first
+    ///
+    /// A failed compensation does not stop the rollback: every other step still gets undone,
+    /// and the failure is reported in its outcome for the audit trail.
+    pub async fn compensate<F, Fut>(&mut self, mut undo: F) -> Vec<CompensationOutcome>
+    where
+        F: FnMut(Compensation) -> Fut,
+        Fut: Future<Output = Result<(), GuardianError>>,
+    {
+        let mut outcomes = Vec::with_capacity(self.compensations.len());
+        while let Some(compensation) = self.compensations.pop() {
+            let step = compensation.step.clone();
+            let action = compensation.action.name().to_string();
+            let outcome = match undo(compensation).await {
+                Ok(()) => {
+                    warn!(step = %step, action = %action, "Workflow step compensated");
+                    CompensationOutcome { step, action, success: true, error: None }
+                }
+                Err(e) => {
+                    error!(step = %step, action = %action, error = %e, "Workflow step compensation failed");
+                    CompensationOutcome { step, action, success: false, error: Some(e.to_string()) }
+                }
+            };
+            outcomes.push(outcome);
+        }
+        outcomes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_compensates_in_reverse_past_failures() {
+        let mut saga = Saga::new();
+        saga.register("1:block_network", ResponseAction::BlockNetwork {
+            address: "203.0.113.7".into(),
+            duration: std::time::Duration::from_secs(60),
+        });
+        saga.register("2:isolate_process", ResponseAction::IsolateProcess { pid: 42, reason: "test".into() });
+
+        let outcomes = saga
+            .compensate(|compensation| async move {
+                match compensation.action {
+                    ResponseAction::IsolateProcess { .. } => Err(GuardianError::SystemError {
+                        context: "jail busy".into(),
+                        source: None,
+                        severity: crate::utils::error::ErrorSeverity::High,
+                        timestamp: time::OffsetDateTime::now_utc(),
+                        correlation_id: crate::utils::correlation::current_or_new(),
+                        category: crate::utils::error::ErrorCategory::System,
+                        retry_count: 0,
+                    }),
+                    _ => Ok(()),
+                }
+            })
+            .await;
+
+        assert!(saga.is_empty());
+        assert_eq!(outcomes.iter().map(|o| o.step.as_str()).collect::<Vec<_>>(), ["2:isolate_process", "1:block_network"]);
+        assert!(!outcomes[0].success);
+        assert!(outcomes[1].success);
+    }
+}
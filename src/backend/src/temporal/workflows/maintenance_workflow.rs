@@ -9,19 +9,24 @@ use tracing::{info, warn, error, instrument};
 use thiserror::Error;
 use serde::{Serialize, Deserialize};
 
+use crate::config::ml_config::RetrainingConfig;
 use crate::temporal::activities::maintenance_activities::{
     MaintenanceActivities,
     SystemHealthResult,
     OptimizationResult,
 };
+use crate::temporal::activities::{CandidateValidation, TrainingRunRequest};
 use crate::core::system_state::{SystemState, SystemHealth};
 use crate::utils::error::GuardianError;
+use crate::utils::ids::{next_id, IdKind};
 
 // Constants for workflow configuration
 const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(300);
 const RESOURCE_OPTIMIZATION_INTERVAL: Duration = Duration::from_secs(3600);
 const MAX_RETRY_ATTEMPTS: u32 = 3;
 const CIRCUIT_BREAKER_THRESHOLD: u32 = 5;
+/// Training runs far longer than the default activity timeout
+const TRAINING_ACTIVITY_TIMEOUT: Duration = Duration::from_secs(4 * 3600);
 
 /// Circuit breaker for maintenance workflow
 #[derive(Debug)]
@@ -62,6 +67,20 @@ struct MaintenanceState {
     circuit_breaker_state: bool,
     consecutive_failures: u32,
     last_failure_timestamp: time::OffsetDateTime,
+    #[serde(default)]
+    last_training_at: Option<time::OffsetDateTime>,
+    #[serde(default)]
+    last_training: Option<TrainingOutcome>,
+}
+
+/// Result of one scheduled training run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrainingOutcome {
+    pub run_id: String,
+    /// Registered version, inactive until promoted through a canary rollout
+    pub version: Option<String>,
+    pub validation: Option<CandidateValidation>,
+    pub reason: String,
 }
 
 /// Main maintenance workflow implementation
@@ -71,6 +90,7 @@ pub struct MaintenanceWorkflow {
     activities: MaintenanceActivities,
     circuit_breaker: CircuitBreaker,
     state: MaintenanceState,
+    training: Option<RetrainingConfig>,
 }
 
 impl MaintenanceWorkflow {
@@ -84,7 +104,34 @@ impl MaintenanceWorkflow {
                 circuit_breaker_state: false,
                 consecutive_failures: 0,
                 last_failure_timestamp: time::OffsetDateTime::now_utc(),
+                last_training_at: None,
+                last_training: None,
             },
+            training: None,
+        }
+    }
+
+    /// Retrains the anomaly model from labeled events on the configured interval
+    pub fn with_training(mut self, config: RetrainingConfig) -> Self {
+        self.training = Some(config);
+        self
+    }
+
+    fn training_due(&self, now: time::OffsetDateTime) -> Option<&RetrainingConfig> {
+        let config = self.training.as_ref()?;
+        match self.state.last_training_at {
+            Some(last) if now - last < config.interval => None,
+            _ => Some(config),
+        }
+    }
+
+    fn training_retry_policy() -> RetryPolicy {
+        RetryPolicy {
+            initial_interval: Duration::from_secs(5),
+            backoff: 2.0,
+            max_interval: Duration::from_secs(60),
+            max_attempts: MAX_RETRY_ATTEMPTS,
+            non_retryable_error_types: vec!["ValidationError".to_string(), "SecurityError".to_string()],
         }
     }
 
@@ -150,6 +197,19 @@ impl MaintenanceWorkflow {
                 }
             }
 
+            // Retrain on a healthy system once the interval has passed; a failed run waits for the next one
+            let healthy = matches!(&self.state.last_health_check, Some(h) if h.status == SystemHealth::Healthy);
+            let now = time::OffsetDateTime::now_utc();
+            if let (true, Some(config)) = (healthy, self.training_due(now).cloned()) {
+                let outcome = self.schedule_model_training(&config, now).await;
+                match &outcome.version {
+                    Some(version) => info!(run_id = %outcome.run_id, version = %version, "Model retrained"),
+                    None => warn!(run_id = %outcome.run_id, reason = %outcome.reason, "Model training produced no model"),
+                }
+                self.state.last_training_at = Some(now);
+                self.state.last_training = Some(outcome);
+            }
+
             // Persist workflow state
             ctx.persist_workflow_state(&self.state)?;
 
@@ -176,12 +236,72 @@ impl MaintenanceWorkflow {
                 source: Some(Box::new(e)),
                 severity: crate::utils::error::ErrorSeverity::High,
                 timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id: uuid::Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: crate::utils::error::ErrorCategory::System,
                 retry_count: 0,
             })
     }
 
+    /// Runs export, training, validation and registration of one candidate model
+    #[instrument(skip(self, config))]
+    async fn schedule_model_training(&self, config: &RetrainingConfig, now: time::OffsetDateTime) -> TrainingOutcome {
+        let ctx = workflow::Context::current();
+        let options = ActivityOptions {
+            retry_policy: Some(Self::training_retry_policy()),
+            start_to_close_timeout: Some(TRAINING_ACTIVITY_TIMEOUT),
+            ..Default::default()
+        };
+        let until = now.unix_timestamp().max(0) as u64;
+        let request = TrainingRunRequest {
+            run_id: next_id(IdKind::Operation).to_string(),
+            tenant_id: config.tenant_id.clone(),
+            since: until.saturating_sub(config.lookback.as_secs()),
+            until,
+        };
+        let mut outcome = TrainingOutcome {
+            run_id: request.run_id.clone(),
+            version: None,
+            validation: None,
+            reason: String::new(),
+        };
+
+        let dataset = match ctx.with_activity_options(options.clone()).activity().export_training_set(request).await {
+            Ok(dataset) => dataset,
+            Err(e) => {
+                outcome.reason = format!("dataset export failed: {}", e);
+                return outcome;
+            }
+        };
+        let candidate = match ctx.with_activity_options(options.clone()).activity().train_candidate(dataset).await {
+            Ok(candidate) => candidate,
+            Err(e) => {
+                outcome.reason = format!("training failed: {}", e);
+                return outcome;
+            }
+        };
+        let validation = match ctx.with_activity_options(options.clone()).activity().validate_candidate(candidate.clone()).await {
+            Ok(validation) => validation,
+            Err(e) => {
+                outcome.reason = format!("validation failed: {}", e);
+                return outcome;
+            }
+        };
+        outcome.validation = Some(validation.clone());
+        if !validation.passed {
+            outcome.reason = validation.reason;
+            return outcome;
+        }
+
+        match ctx.with_activity_options(options).activity().register_candidate(candidate, validation).await {
+            Ok(version) => {
+                outcome.reason = "registered".to_string();
+                outcome.version = Some(version);
+            }
+            Err(e) => outcome.reason = format!("registration failed: {}", e),
+        }
+        outcome
+    }
+
     /// Schedules and executes resource optimization with ML guidance
     #[instrument(skip(self))]
     async fn schedule_resource_optimization(&self) -> Result<OptimizationResult, GuardianError> {
@@ -200,7 +320,7 @@ impl MaintenanceWorkflow {
                 source: Some(Box::new(e)),
                 severity: crate::utils::error::ErrorSeverity::Medium,
                 timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id: uuid::Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: crate::utils::error::ErrorCategory::System,
                 retry_count: 0,
             })
@@ -9,7 +9,9 @@ use tracing::{debug, error, info, instrument, warn};
 // Re-export workflow implementations
 pub use self::security_workflow::{SecurityWorkflow, SecurityWorkflowImpl};
 pub use self::monitoring_workflow::MonitoringWorkflow;
-pub use self::maintenance_workflow::MaintenanceWorkflow;
+pub use self::maintenance_workflow::{MaintenanceWorkflow, TrainingOutcome};
+pub use self::canary_workflow::{CanaryConfig, CanaryDecision, CanaryOutcome, CanaryWorkflow};
+pub use self::offline_response_workflow::OfflineResponseWorkflow;
 
 // Core workflow module constants
 const WORKFLOW_NAMESPACE: &str = "guardian.workflows";
@@ -57,7 +59,7 @@ pub async fn register_workflows(
             source: Some(Box::new(e)),
             severity: crate::utils::error::ErrorSeverity::Critical,
             timestamp: time::OffsetDateTime::now_utc(),
-            correlation_id: uuid::Uuid::new_v4(),
+            correlation_id: crate::utils::correlation::current_or_new(),
             category: crate::utils::error::ErrorCategory::System,
             retry_count: 0,
         })?;
@@ -79,15 +81,19 @@ pub async fn register_workflows(
             source: Some(Box::new(e)),
             severity: crate::utils::error::ErrorSeverity::Critical,
             timestamp: time::OffsetDateTime::now_utc(),
-            correlation_id: uuid::Uuid::new_v4(),
+            correlation_id: crate::utils::correlation::current_or_new(),
             category: crate::utils::error::ErrorCategory::System,
             retry_count: 0,
         })?;
 
-    // Register maintenance workflow
+    // Register maintenance workflow, which also drives scheduled retraining when configured
+    let mut maintenance = MaintenanceWorkflow::new(config.maintenance_activities.clone());
+    if let Some(retraining) = config.retraining.clone() {
+        maintenance = maintenance.with_training(retraining);
+    }
     client
         .register_workflow(
-            MaintenanceWorkflow::new(config.maintenance_activities.clone()),
+            maintenance,
             "maintenance_workflow",
             &default_options,
         )
@@ -97,7 +103,46 @@ pub async fn register_workflows(
             source: Some(Box::new(e)),
             severity: crate::utils::error::ErrorSeverity::Critical,
             timestamp: time::OffsetDateTime::now_utc(),
-            correlation_id: uuid::Uuid::new_v4(),
+            correlation_id: crate::utils::correlation::current_or_new(),
+            category: crate::utils::error::ErrorCategory::System,
+            retry_count: 0,
+        })?;
+
+    // Register canary model rollout workflow
+    client
+        .register_workflow(
+            CanaryWorkflow::new(config.model_activities.clone()),
+            "canary_workflow",
+            &default_options,
+        )
+        .await
+        .map_err(|e| GuardianError::SystemError {
+            context: "Failed to register canary workflow".into(),
+            source: Some(Box::new(e)),
+            severity: crate::utils::error::ErrorSeverity::Critical,
+            timestamp: time::OffsetDateTime::now_utc(),
+            correlation_id: crate::utils::correlation::current_or_new(),
+            category: crate::utils::error::ErrorCategory::System,
+            retry_count: 0,
+        })?;
+
+    // Register the record of responses executed offline, on the queue the response engine syncs to
+    client
+        .register_workflow(
+            OfflineResponseWorkflow::new(),
+            crate::security::offline_executor::OFFLINE_SYNC_WORKFLOW,
+            &WorkflowOptions {
+                task_queue: crate::security::response_engine::RESPONSE_TASK_QUEUE.to_string(),
+                ..default_options.clone()
+            },
+        )
+        .await
+        .map_err(|e| GuardianError::SystemError {
+            context: "Failed to register offline response workflow".into(),
+            source: Some(Box::new(e)),
+            severity: crate::utils::error::ErrorSeverity::Critical,
+            timestamp: time::OffsetDateTime::now_utc(),
+            correlation_id: crate::utils::correlation::current_or_new(),
             category: crate::utils::error::ErrorCategory::System,
             retry_count: 0,
         })?;
@@ -131,7 +176,7 @@ pub async fn initialize_workflow_environment(
         source: Some(Box::new(e)),
         severity: crate::utils::error::ErrorSeverity::Critical,
         timestamp: time::OffsetDateTime::now_utc(),
-        correlation_id: uuid::Uuid::new_v4(),
+        correlation_id: crate::utils::correlation::current_or_new(),
         category: crate::utils::error::ErrorCategory::System,
         retry_count: 0,
     })?;
@@ -146,9 +191,12 @@ pub async fn initialize_workflow_environment(
 }
 
 // Internal modules
+pub mod compensation;
 mod security_workflow;
 mod monitoring_workflow;
 mod maintenance_workflow;
+mod canary_workflow;
+mod offline_response_workflow;
 
 #[cfg(test)]
 mod tests {
@@ -164,6 +212,8 @@ mod tests {
             system_state: Arc::new(create_test_system_state()),
             retry_policy: Default::default(),
             maintenance_activities: create_test_maintenance_activities(),
+            model_activities: create_test_model_activities(),
+            retraining: None,
         };
 
         let result = initialize_workflow_environment(config).await;
@@ -185,4 +235,9 @@ mod tests {
         // Implementation omitted for brevity
         unimplemented!()
     }
+
+    fn create_test_model_activities() -> ModelActivities {
+        // Implementation omitted for brevity
+        unimplemented!()
+    }
 }
\ No newline at end of file
@@ -10,11 +10,12 @@ use tokio::time;
 use tracing::{debug, error, info, instrument, warn};
 
 use crate::core::metrics::CoreMetricsManager;
+use crate::core::state_journal::TransitionCause;
 use crate::core::system_state::{SystemHealth, SystemState};
 use crate::temporal::activities::monitoring_activities::{
     MetricsSnapshot, MonitoringActivities, ResourceUsage,
 };
-use crate::utils::error::{GuardianError, ErrorSeverity, ErrorCategory};
+use crate::utils::error::{GuardianError, ErrorSeverity, ErrorCategory, RETRY_LIMIT};
 
 // Core monitoring workflow constants
 const MONITORING_CYCLE_INTERVAL: Duration = Duration::from_secs(60);
@@ -169,7 +170,7 @@ impl MonitoringWorkflow {
 
         // Update system state with comprehensive metrics
         if let Ok(mut state) = self.system_state.write() {
-            state.update_state(SystemState {
+            state.update_state_with_cause(SystemState {
                 health: result.health.clone(),
                 cpu_usage: result.metrics.cpu_usage,
                 memory_usage: result.metrics.memory_usage,
@@ -178,7 +179,7 @@ impl MonitoringWorkflow {
                 state_history: Default::default(),
                 circuit_breaker: Default::default(),
                 validation_rules: Vec::new(),
-            }).await?;
+            }, TransitionCause::HealthCheck { source: "monitoring_workflow".into() }).await?;
         }
 
         result.timestamp = time::OffsetDateTime::now_utc();
@@ -217,18 +218,25 @@ impl MonitoringWorkflow {
             source: Some(Box::new(e)),
             severity: ErrorSeverity::High,
             timestamp: time::OffsetDateTime::now_utc(),
-            correlation_id: uuid::Uuid::new_v4(),
+            correlation_id: crate::utils::correlation::current_or_new(),
             category: ErrorCategory::System,
             retry_count: 0,
         })?;
 
         // Check if retry is possible
-        if let Some(retryable_error) = error.increment_retry() {
-            let backoff = Duration::from_secs(2u64.pow(retryable_error.retry_count()));
-            ctx.timer(backoff).await;
-            Ok(())
-        } else {
-            Err(error)
+        match error.retry() {
+            Ok(retryable_error) => {
+                let backoff = Duration::from_secs(2u64.pow(retryable_error.retry_count()));
+                counter!("guardian.retry.attempts", 1, "operation" => "monitoring_cycle");
+                warn!(
+                    retry_count = retryable_error.retry_count(),
+                    backoff_secs = backoff.as_secs(),
+                    "Retrying monitoring cycle"
+                );
+                ctx.timer(backoff).await;
+                Ok(())
+            }
+            Err(exhausted) => Err(exhausted.retried("monitoring_cycle", RETRY_LIMIT)),
         }
     }
 }
@@ -0,0 +1,32 @@
+use temporal_sdk::workflow::{self, WorkflowResult};
+use tracing::{info, instrument};
+
+use crate::security::offline_executor::{JournalEntry, JournalOutcome};
+
+/// Records in Temporal a response action that ran in-process while Temporal was unreachable
+///
+/// The action has already taken effect, so the workflow runs no activities: its history is the
+/// record, keyed by the journal entry's stable workflow ID.
+#[derive(Debug, Default)]
+#[workflow_version("1.0.0")]
+pub struct OfflineResponseWorkflow;
+
+impl OfflineResponseWorkflow {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Completes with the outcome the offline executor journaled
+    #[instrument(skip(self, entry), fields(entry_id = %entry.id, action = entry.action.name()))]
+    #[workflow::workflow]
+    pub async fn record_offline_response(&mut self, entry: JournalEntry) -> WorkflowResult<JournalOutcome> {
+        info!(
+            workflow_type = %entry.workflow_type,
+            correlation_id = %entry.correlation_id,
+            executed_at = entry.executed_at,
+            outcome = ?entry.outcome,
+            "Recording response executed offline"
+        );
+        Ok(entry.outcome)
+    }
+}
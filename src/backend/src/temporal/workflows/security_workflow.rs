@@ -5,12 +5,17 @@ use temporal_sdk::{
 };
 use async_trait::async_trait;
 use tracing::{debug, error, info, instrument, warn};
+use metrics::counter;
 use serde::{Serialize, Deserialize};
 use circuit_breaker::CircuitBreaker;
 
 use crate::temporal::activities::security_activities::SecurityActivities;
+use crate::temporal::workflows::compensation::Saga;
+use crate::security::audit::{AuditEvent, SecurityLevel};
+use crate::security::response_engine::{ResponseStatus, ThreatAnalysis};
 use crate::security::threat_detection::ThreatLevel;
 use crate::utils::error::GuardianError;
+use crate::utils::telemetry;
 
 // Workflow version and configuration constants
 const WORKFLOW_VERSION: &str = "1.0.0";
@@ -106,6 +111,9 @@ impl SecurityWorkflowImpl {
             "Starting security workflow execution"
         );
 
+        // Join the trace of the detection that started this workflow
+        telemetry::follow_workflow_headers(ctx.headers());
+
         let start_time = ctx.current_time();
 
         // Check circuit breaker
@@ -116,7 +124,7 @@ impl SecurityWorkflowImpl {
                 source: None,
                 severity: crate::utils::error::ErrorSeverity::High,
                 timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id: uuid::Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: crate::utils::error::ErrorCategory::Security,
                 retry_count: 0,
             });
@@ -142,6 +150,10 @@ impl SecurityWorkflowImpl {
             },
             _ => ActivityOptions::default(),
         };
+        let activity_options = ActivityOptions {
+            headers: telemetry::workflow_headers(),
+            ..activity_options
+        };
 
         // Execute threat detection activity
         let detection_start = ctx.current_time();
@@ -153,26 +165,25 @@ impl SecurityWorkflowImpl {
 
         self.metrics.threat_detection_time = ctx.current_time() - detection_start;
 
-        // Execute response if threats detected
+        // Execute response if threats detected, rolling back completed steps if a later one fails
         if threat_analysis.severity >= ThreatLevel::High {
             let response_start = ctx.current_time();
-            let response_status = ctx
-                .activity(SecurityActivities::execute_response)
-                .activity_options(activity_options.clone())
-                .arg(threat_analysis.clone())
+            let response_status = self
+                .execute_response_saga(&ctx, &activity_options, threat_analysis.clone())
                 .await?;
 
             self.metrics.response_time = ctx.current_time() - response_start;
 
-            // Record audit event
+            // Record audit event, with the lineage detection attached
+            let event = AuditEvent::new(
+                "security.response.executed",
+                SecurityLevel::High,
+                "security_workflow",
+                Some(response_status.correlation_id.to_string()),
+            );
             ctx.activity(SecurityActivities::record_audit)
                 .activity_options(activity_options)
-                .arg(AuditEvent::new(
-                    "security.response.executed",
-                    SecurityLevel::High,
-                    "security_workflow",
-                    Some(response_status.correlation_id.to_string()),
-                ))
+                .arg(event.clone().with_data(threat_analysis.audit_data()).unwrap_or(event))
                 .await?;
         }
 
@@ -188,10 +199,118 @@ impl SecurityWorkflowImpl {
             success: true,
             execution_time: self.metrics.execution_time,
             threat_detected: threat_analysis.severity >= ThreatLevel::High,
-            correlation_id: uuid::Uuid::new_v4(),
+            correlation_id: crate::utils::correlation::current_or_new(),
         })
     }
 
+    /// Runs the planned response steps as a saga
+    ///
+    /// Every completed reversible step registers its undo action. When a step fails, the
+    /// completed steps are compensated newest first and each compensation is audited, so a
+    /// partial response never leaves, say, the network blocked but the process still running.
+    async fn execute_response_saga(
+        &self,
+        ctx: &WfContext,
+        activity_options: &ActivityOptions,
+        threat_analysis: ThreatAnalysis,
+    ) -> WfResult<ResponseStatus> {
+        let steps = ctx
+            .activity(SecurityActivities::plan_response)
+            .activity_options(activity_options.clone())
+            .arg(threat_analysis)
+            .await?;
+
+        let mut saga = Saga::new();
+        let mut last_status = None;
+        for (index, step) in steps.into_iter().enumerate() {
+            let name = format!("{}:{}", index + 1, step.action.name());
+            let result = ctx
+                .activity(SecurityActivities::execute_response_step)
+                .activity_options(activity_options.clone())
+                .arg(step.action.clone())
+                .await;
+
+            let reason = match result {
+                Ok(status) if status.success() => {
+                    if step.reversible {
+                        saga.register(name, step.action);
+                    } else {
+                        debug!(step = %name, "Response step cannot be undone, no compensation registered");
+                    }
+                    last_status = Some(status);
+                    continue;
+                }
+                Ok(status) => status.error_context().unwrap_or("action did not take effect").to_string(),
+                Err(e) => e.to_string(),
+            };
+            return Err(self.roll_back(ctx, activity_options, &mut saga, &name, reason).await);
+        }
+
+        last_status.ok_or_else(|| workflow_error("Response plan has no steps".into()))
+    }
+
+    /// Compensates the completed steps of a failed response and audits every compensation
+    async fn roll_back(
+        &self,
+        ctx: &WfContext,
+        activity_options: &ActivityOptions,
+        saga: &mut Saga,
+        failed_step: &str,
+        reason: String,
+    ) -> GuardianError {
+        warn!(step = failed_step, %reason, compensations = saga.len(), "Response step failed, rolling back completed steps");
+
+        let outcomes = saga
+            .compensate(|compensation| {
+                let options = activity_options.clone();
+                async move {
+                    ctx.activity(SecurityActivities::compensate_response)
+                        .activity_options(options)
+                        .arg(compensation.action)
+                        .await
+                        .map(|_| ())
+                }
+            })
+            .await;
+
+        let correlation_id = crate::utils::correlation::current_or_new().to_string();
+        for outcome in &outcomes {
+            let event = AuditEvent::new(
+                "security.response.compensated".to_string(),
+                if outcome.success { SecurityLevel::High } else { SecurityLevel::Critical },
+                "security_workflow".to_string(),
+                Some(correlation_id.clone()),
+            );
+            let data = serde_json::json!({
+                "failed_step": failed_step,
+                "failure": reason,
+                "compensated_step": outcome.step,
+                "action": outcome.action,
+                "success": outcome.success,
+                "error": outcome.error,
+            });
+            let event = event.clone().with_data(data).unwrap_or(event);
+            if let Err(e) = ctx
+                .activity(SecurityActivities::record_audit)
+                .activity_options(activity_options.clone())
+                .arg(event)
+                .await
+            {
+                error!(error = %e, step = %outcome.step, "Failed to audit response compensation");
+            }
+        }
+
+        let failed = outcomes.iter().filter(|outcome| !outcome.success).count();
+        counter!("guardian.workflow.response.rollbacks", 1, "complete" => (failed == 0).to_string());
+        workflow_error(format!(
+            "Response step {} failed: {}; rolled back {} completed steps, {} compensations failed",
+            failed_step,
+            reason,
+            outcomes.len(),
+            failed
+        ))
+    }
+
     /// Records workflow execution metrics
     async fn record_workflow_metrics(&self, ctx: &WfContext) -> WfResult<()> {
         ctx.record_metric(
@@ -214,6 +333,18 @@ impl SecurityWorkflowImpl {
     }
 }
 
+fn workflow_error(context: String) -> GuardianError {
+    GuardianError::WorkflowError {
+        context,
+        source: None,
+        severity: crate::utils::error::ErrorSeverity::High,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: crate::utils::error::ErrorCategory::Security,
+        retry_count: 0,
+    }
+}
+
 /// Validates workflow configuration
 #[tracing::instrument]
 fn validate_workflow_config(config: &SecurityWorkflowConfig) -> Result<(), GuardianError> {
@@ -223,7 +354,7 @@ fn validate_workflow_config(config: &SecurityWorkflowConfig) -> Result<(), Guard
             source: None,
             severity: crate::utils::error::ErrorSeverity::High,
             timestamp: time::OffsetDateTime::now_utc(),
-            correlation_id: uuid::Uuid::new_v4(),
+            correlation_id: crate::utils::correlation::current_or_new(),
             category: crate::utils::error::ErrorCategory::Validation,
             retry_count: 0,
         });
@@ -235,7 +366,7 @@ fn validate_workflow_config(config: &SecurityWorkflowConfig) -> Result<(), Guard
             source: None,
             severity: crate::utils::error::ErrorSeverity::High,
             timestamp: time::OffsetDateTime::now_utc(),
-            correlation_id: uuid::Uuid::new_v4(),
+            correlation_id: crate::utils::correlation::current_or_new(),
             category: crate::utils::error::ErrorCategory::Validation,
             retry_count: 0,
         });
@@ -0,0 +1,265 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicU64, AtomicU8, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use metrics::{counter, gauge};
+use once_cell::sync::{Lazy, OnceCell};
+use serde::Serialize;
+use tracing::{info, warn};
+
+use crate::config::app_config::AdmissionConfig;
+use crate::utils::queue_metrics::{queue_registry, QueueStats};
+
+// Constants for admission control
+const SEVERE_CPU_FACTOR: f64 = 1.25;
+const SOURCES: usize = 2;
+const PRIORITIES: usize = 4;
+
+static ADMISSION: Lazy<Arc<AdmissionController>> = Lazy::new(|| Arc::new(AdmissionController::default()));
+static SAMPLER: OnceCell<()> = OnceCell::new();
+
+/// Priority of work offered for admission, highest first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AdmissionPriority {
+    Critical = 0,
+    High = 1,
+    Medium = 2,
+    Low = 3,
+}
+
+impl AdmissionPriority {
+    const ALL: [AdmissionPriority; PRIORITIES] = [
+        AdmissionPriority::Critical,
+        AdmissionPriority::High,
+        AdmissionPriority::Medium,
+        AdmissionPriority::Low,
+    ];
+
+    fn label(&self) -> &'static str {
+        match self {
+            AdmissionPriority::Critical => "critical",
+            AdmissionPriority::High => "high",
+            AdmissionPriority::Medium => "medium",
+            AdmissionPriority::Low => "low",
+        }
+    }
+}
+
+/// Kind of work offered for admission
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WorkSource {
+    Event = 0,
+    Metric = 1,
+}
+
+impl WorkSource {
+    const ALL: [WorkSource; SOURCES] = [WorkSource::Event, WorkSource::Metric];
+
+    fn label(&self) -> &'static str {
+        match self {
+            WorkSource::Event => "event",
+            WorkSource::Metric => "metric",
+        }
+    }
+}
+
+/// Overload level derived from queue backlog and CPU load
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PressureLevel {
+    Normal = 0,
+    /// Low priority work is shed
+    Elevated = 1,
+    /// Medium and low priority work is shed
+    Severe = 2,
+}
+
+impl PressureLevel {
+    fn from_u8(value: u8) -> Self {
+        match value {
+            0 => PressureLevel::Normal,
+            1 => PressureLevel::Elevated,
+            _ => PressureLevel::Severe,
+        }
+    }
+
+    /// Critical and High work is never shed
+    pub fn sheds(&self, priority: AdmissionPriority) -> bool {
+        match self {
+            PressureLevel::Normal => false,
+            PressureLevel::Elevated => priority == AdmissionPriority::Low,
+            PressureLevel::Severe => priority >= AdmissionPriority::Medium,
+        }
+    }
+}
+
+/// Work shed for one source and priority since startup
+#[derive(Debug, Clone, Serialize)]
+pub struct ShedCount {
+    pub source: WorkSource,
+    pub priority: AdmissionPriority,
+    pub shed: u64,
+}
+
+/// Current pressure and shed totals
+#[derive(Debug, Clone, Serialize)]
+pub struct AdmissionSnapshot {
+    pub enabled: bool,
+    pub pressure: PressureLevel,
+    pub shed: Vec<ShedCount>,
+}
+
+/// Sheds low priority work while Guardian is overloaded
+#[derive(Debug)]
+pub struct AdmissionController {
+    enabled: AtomicBool,
+    level: AtomicU8,
+    shed: [[AtomicU64; PRIORITIES]; SOURCES],
+}
+
+impl Default for AdmissionController {
+    fn default() -> Self {
+        Self {
+            enabled: AtomicBool::new(true),
+            level: AtomicU8::new(PressureLevel::Normal as u8),
+            shed: Default::default(),
+        }
+    }
+}
+
+/// Returns the process-wide admission controller
+pub fn admission() -> Arc<AdmissionController> {
+    Arc::clone(&ADMISSION)
+}
+
+/// Applies the admission configuration and starts sampling pressure
+pub fn init_admission(config: &AdmissionConfig) {
+    let controller = admission();
+    controller.enabled.store(config.enabled, Ordering::Relaxed);
+    info!(enabled = config.enabled, cpu_budget = config.cpu_budget, "Admission control configured");
+
+    if !config.enabled || SAMPLER.set(()).is_err() {
+        return;
+    }
+    let config = config.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(config.sample_interval);
+        loop {
+            interval.tick().await;
+            let level = evaluate(&config, &queue_registry().snapshot(), load_per_core());
+            controller.set_pressure(level);
+        }
+    });
+}
+
+impl AdmissionController {
+    /// Returns whether work should be accepted, counting it as shed otherwise
+    pub fn admit(&self, source: WorkSource, priority: AdmissionPriority) -> bool {
+        if !self.enabled.load(Ordering::Relaxed) || !self.pressure().sheds(priority) {
+            return true;
+        }
+        self.shed[source as usize][priority as usize].fetch_add(1, Ordering::Relaxed);
+        counter!("guardian.admission.shed", 1, "source" => source.label(), "priority" => priority.label());
+        false
+    }
+
+    /// Returns the current pressure level
+    pub fn pressure(&self) -> PressureLevel {
+        PressureLevel::from_u8(self.level.load(Ordering::Relaxed))
+    }
+
+    /// Returns the current pressure and shed totals
+    pub fn snapshot(&self) -> AdmissionSnapshot {
+        let shed = WorkSource::ALL
+            .iter()
+            .flat_map(|source| {
+                AdmissionPriority::ALL.iter().map(move |priority| ShedCount {
+                    source: *source,
+                    priority: *priority,
+                    shed: self.shed[*source as usize][*priority as usize].load(Ordering::Relaxed),
+                })
+            })
+            .collect();
+        AdmissionSnapshot {
+            enabled: self.enabled.load(Ordering::Relaxed),
+            pressure: self.pressure(),
+            shed,
+        }
+    }
+
+    fn set_pressure(&self, level: PressureLevel) {
+        let previous = PressureLevel::from_u8(self.level.swap(level as u8, Ordering::Relaxed));
+        gauge!("guardian.admission.pressure", level as u8 as f64);
+        if previous != level {
+            warn!(?previous, current = ?level, "Admission pressure changed");
+        }
+    }
+}
+
+/// Derives the pressure level from the worst queue backlog and CPU load per core
+fn evaluate(config: &AdmissionConfig, queues: &[QueueStats], load_per_core: f64) -> PressureLevel {
+    let backlog = queues
+        .iter()
+        .map(|q| q.consumer_lag_ms.max(q.backlog_age_ms()))
+        .fold(0.0, f64::max);
+    let backlog = Duration::from_secs_f64(backlog / 1000.0);
+
+    if backlog >= config.severe_lag || load_per_core >= config.cpu_budget * SEVERE_CPU_FACTOR {
+        PressureLevel::Severe
+    } else if backlog >= config.elevated_lag || load_per_core >= config.cpu_budget {
+        PressureLevel::Elevated
+    } else {
+        PressureLevel::Normal
+    }
+}
+
+fn load_per_core() -> f64 {
+    match (sys_info::loadavg(), sys_info::cpu_num()) {
+        (Ok(load), Ok(cpus)) if cpus > 0 => load.one / cpus as f64,
+        _ => 0.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pressure_from_backlog_and_cpu() {
+        let config = AdmissionConfig::default();
+        let queue = |consumer_lag_ms| QueueStats {
+            name: "event_bus".into(),
+            consumer_lag_ms,
+            ..Default::default()
+        };
+
+        assert_eq!(evaluate(&config, &[queue(100.0)], 0.2), PressureLevel::Normal);
+        assert_eq!(evaluate(&config, &[queue(100.0), queue(3_000.0)], 0.2), PressureLevel::Elevated);
+        assert_eq!(evaluate(&config, &[queue(100.0)], 0.95), PressureLevel::Elevated);
+        assert_eq!(evaluate(&config, &[queue(15_000.0)], 0.2), PressureLevel::Severe);
+    }
+
+    #[test]
+    fn test_critical_and_high_work_is_never_shed() {
+        let controller = AdmissionController::default();
+        controller.set_pressure(PressureLevel::Severe);
+
+        assert!(controller.admit(WorkSource::Event, AdmissionPriority::Critical));
+        assert!(controller.admit(WorkSource::Event, AdmissionPriority::High));
+        assert!(!controller.admit(WorkSource::Event, AdmissionPriority::Medium));
+        assert!(!controller.admit(WorkSource::Metric, AdmissionPriority::Low));
+
+        let snapshot = controller.snapshot();
+        assert_eq!(snapshot.shed.iter().map(|c| c.shed).sum::<u64>(), 2);
+
+        controller.set_pressure(PressureLevel::Elevated);
+        assert!(controller.admit(WorkSource::Metric, AdmissionPriority::Medium));
+        assert!(!controller.admit(WorkSource::Metric, AdmissionPriority::Low));
+    }
+}
@@ -0,0 +1,294 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use metrics::counter;
+use once_cell::sync::{Lazy, OnceCell};
+use parking_lot::RwLock;
+use serde::Serialize;
+use tracing::{debug, info, warn};
+
+use crate::config::app_config::CpuAffinityConfig;
+use crate::utils::error::GuardianError;
+
+// Constants for CPU affinity management
+const MAX_CPU_ID: usize = 1023;
+
+static AFFINITY_MANAGER: OnceCell<Arc<AffinityManager>> = OnceCell::new();
+static THREAD_PLACEMENTS: Lazy<RwLock<BTreeMap<String, ThreadPlacement>>> =
+    Lazy::new(|| RwLock::new(BTreeMap::new()));
+
+/// Class of Guardian worker thread, each pinned to its own core set
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ThreadClass {
+    Inference,
+    Io,
+}
+
+/// Requested and actual CPU placement of a Guardian worker thread
+#[derive(Debug, Clone, Serialize)]
+pub struct ThreadPlacement {
+    pub thread_name: String,
+    pub class: ThreadClass,
+    pub requested_cpus: String,
+    pub actual_cpus: String,
+    pub pinned: bool,
+}
+
+/// Pins runtime worker threads onto configured cpusets
+#[derive(Debug, Clone, Default)]
+pub struct AffinityManager {
+    enabled: bool,
+    inference_cpus: Vec<usize>,
+    io_cpus: Vec<usize>,
+}
+
+impl AffinityManager {
+    /// Creates an affinity manager from the application affinity configuration
+    pub fn new(config: &CpuAffinityConfig) -> Result<Self, GuardianError> {
+        let parse = |spec: &Option<String>| spec.as_deref().map(parse_cpuset).transpose();
+
+        Ok(Self {
+            enabled: config.enabled,
+            inference_cpus: parse(&config.inference_cpuset)?.unwrap_or_default(),
+            io_cpus: parse(&config.io_cpuset)?.unwrap_or_default(),
+        })
+    }
+
+    /// Returns the configured cpus for a thread class, empty when unrestricted
+    pub fn cpus_for(&self, class: ThreadClass) -> &[usize] {
+        match class {
+            ThreadClass::Inference => &self.inference_cpus,
+            ThreadClass::Io => &self.io_cpus,
+        }
+    }
+
+    /// Pins the calling thread to the cpuset of its class and records the placement
+    pub fn pin_current_thread(&self, class: ThreadClass) -> ThreadPlacement {
+        let cpus = self.cpus_for(class);
+        let pinned = if self.enabled && !cpus.is_empty() {
+            match set_thread_affinity(cpus) {
+                Ok(()) => true,
+                Err(e) => {
+                    // Placement is best effort, the thread keeps running unpinned
+                    warn!(error = %e, ?class, "Failed to pin worker thread");
+                    counter!("guardian.affinity.pin_failures", 1);
+                    false
+                }
+            }
+        } else {
+            false
+        };
+
+        let current = std::thread::current();
+        let placement = ThreadPlacement {
+            thread_name: format!("{}-{:?}", current.name().unwrap_or("unnamed"), current.id()),
+            class,
+            requested_cpus: format_cpuset(cpus),
+            actual_cpus: thread_affinity().map(|c| format_cpuset(&c)).unwrap_or_default(),
+            pinned,
+        };
+
+        debug!(thread = %placement.thread_name, cpus = %placement.actual_cpus, "Worker thread placed");
+        THREAD_PLACEMENTS.write().insert(placement.thread_name.clone(), placement.clone());
+        placement
+    }
+
+    /// Returns a multi-threaded runtime builder whose workers are pinned to the class cpuset
+    pub fn runtime_builder(self: &Arc<Self>, class: ThreadClass) -> tokio::runtime::Builder {
+        let mut builder = tokio::runtime::Builder::new_multi_thread();
+        let start_manager = Arc::clone(self);
+        builder.on_thread_start(move || {
+            start_manager.pin_current_thread(class);
+        });
+        builder.on_thread_stop(|| {
+            let current = std::thread::current();
+            let name = format!("{}-{:?}", current.name().unwrap_or("unnamed"), current.id());
+            THREAD_PLACEMENTS.write().remove(&name);
+        });
+
+        // Size the pool to the cpuset so workers do not oversubscribe their cores
+        let cpus = self.cpus_for(class).len();
+        if self.enabled && cpus > 0 {
+            builder.worker_threads(cpus);
+        }
+        builder
+    }
+}
+
+/// Installs the process-wide affinity manager, first call wins
+pub fn init_affinity(config: &CpuAffinityConfig) -> Result<(), GuardianError> {
+    let manager = AffinityManager::new(config)?;
+    info!(
+        enabled = manager.enabled,
+        inference = %format_cpuset(&manager.inference_cpus),
+        io = %format_cpuset(&manager.io_cpus),
+        "CPU affinity configured"
+    );
+    if AFFINITY_MANAGER.set(Arc::new(manager)).is_err() {
+        debug!("CPU affinity already initialized");
+    }
+    Ok(())
+}
+
+/// Returns the active affinity manager, unrestricted when none was installed
+pub fn affinity_manager() -> Arc<AffinityManager> {
+    AFFINITY_MANAGER.get().cloned().unwrap_or_default()
+}
+
+/// Returns the placement of every live pinned worker thread for diagnostics
+pub fn thread_placements() -> Vec<ThreadPlacement> {
+    THREAD_PLACEMENTS.read().values().cloned().collect()
+}
+
+/// Parses a cpuset list such as `0-3,6` into sorted cpu ids
+pub fn parse_cpuset(spec: &str) -> Result<Vec<usize>, GuardianError> {
+    let invalid = |reason: &str| GuardianError::ValidationError {
+        context: format!("Invalid cpuset '{}': {}", spec, reason),
+        source: None,
+        severity: crate::utils::error::ErrorSeverity::High,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: crate::utils::error::ErrorCategory::Validation,
+        retry_count: 0,
+    };
+
+    let mut cpus = Vec::new();
+    for part in spec.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let (start, end) = match part.split_once('-') {
+            Some((start, end)) => (start.trim(), end.trim()),
+            None => (part, part),
+        };
+        let start: usize = start.parse().map_err(|_| invalid("not a cpu id"))?;
+        let end: usize = end.parse().map_err(|_| invalid("not a cpu id"))?;
+        if start > end || end > MAX_CPU_ID {
+            return Err(invalid("range out of bounds"));
+        }
+        cpus.extend(start..=end);
+    }
+
+    if cpus.is_empty() {
+        return Err(invalid("empty cpuset"));
+    }
+    cpus.sort_unstable();
+    cpus.dedup();
+    Ok(cpus)
+}
+
+/// Formats cpu ids back into compact cpuset notation
+pub fn format_cpuset(cpus: &[usize]) -> String {
+    let mut ranges: Vec<String> = Vec::new();
+    let mut iter = cpus.iter().copied().peekable();
+
+    while let Some(start) = iter.next() {
+        let mut end = start;
+        while iter.peek() == Some(&(end + 1)) {
+            end = iter.next().unwrap_or(end);
+        }
+        ranges.push(if start == end { start.to_string() } else { format!("{}-{}", start, end) });
+    }
+    ranges.join(",")
+}
+
+#[cfg(target_os = "linux")]
+fn set_thread_affinity(cpus: &[usize]) -> Result<(), nix::Error> {
+    let mut set = nix::sched::CpuSet::new();
+    for cpu in cpus {
+        set.set(*cpu)?;
+    }
+    nix::sched::sched_setaffinity(nix::unistd::Pid::from_raw(0), &set)
+}
+
+#[cfg(target_os = "freebsd")]
+fn set_thread_affinity(cpus: &[usize]) -> Result<(), nix::Error> {
+    // SAFETY: cpuset_t is a plain bitmask, for which all zeroes is the empty set
+    let mut set: libc::cpuset_t = unsafe { std::mem::zeroed() };
+    for cpu in cpus {
+        if *cpu >= libc::CPU_SETSIZE as usize {
+            return Err(nix::Error::EINVAL);
+        }
+        libc::CPU_SET(*cpu, &mut set);
+    }
+    // An ID of -1 at the thread level names the calling thread
+    // SAFETY: the mask is initialized and its size is passed alongside it
+    let result = unsafe {
+        libc::cpuset_setaffinity(
+            libc::CPU_LEVEL_WHICH,
+            libc::CPU_WHICH_TID,
+            -1,
+            std::mem::size_of::<libc::cpuset_t>(),
+            &set,
+        )
+    };
+    nix::errno::Errno::result(result).map(drop)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "freebsd")))]
+fn set_thread_affinity(_cpus: &[usize]) -> Result<(), nix::Error> {
+    Err(nix::Error::ENOTSUP)
+}
+
+#[cfg(target_os = "linux")]
+fn thread_affinity() -> Option<Vec<usize>> {
+    let set = nix::sched::sched_getaffinity(nix::unistd::Pid::from_raw(0)).ok()?;
+    Some(
+        (0..nix::sched::CpuSet::count())
+            .filter(|cpu| set.is_set(*cpu).unwrap_or(false))
+            .collect(),
+    )
+}
+
+#[cfg(target_os = "freebsd")]
+fn thread_affinity() -> Option<Vec<usize>> {
+    // SAFETY: cpuset_t is a plain bitmask, for which all zeroes is the empty set
+    let mut set: libc::cpuset_t = unsafe { std::mem::zeroed() };
+    // SAFETY: the kernel writes at most the passed size into the mask
+    let result = unsafe {
+        libc::cpuset_getaffinity(
+            libc::CPU_LEVEL_WHICH,
+            libc::CPU_WHICH_TID,
+            -1,
+            std::mem::size_of::<libc::cpuset_t>(),
+            &mut set,
+        )
+    };
+    nix::errno::Errno::result(result).ok()?;
+    Some(
+        (0..libc::CPU_SETSIZE as usize)
+            .filter(|cpu| libc::CPU_ISSET(*cpu, &set))
+            .collect(),
+    )
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "freebsd")))]
+fn thread_affinity() -> Option<Vec<usize>> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cpuset_round_trip() {
+        assert_eq!(parse_cpuset("4-7, 2,3").unwrap(), vec![2, 3, 4, 5, 6, 7]);
+        assert_eq!(format_cpuset(&[0, 1, 2, 3, 6, 8, 9]), "0-3,6,8-9");
+        assert!(parse_cpuset("3-1").is_err());
+        assert!(parse_cpuset("").is_err());
+        assert!(parse_cpuset("a").is_err());
+    }
+
+    #[test]
+    fn test_disabled_affinity_leaves_threads_unpinned() {
+        let manager = AffinityManager::new(&CpuAffinityConfig {
+            enabled: false,
+            inference_cpuset: Some("0".into()),
+            io_cpuset: None,
+        })
+        .unwrap();
+
+        let placement = manager.pin_current_thread(ThreadClass::Inference);
+        assert!(!placement.pinned);
+        assert_eq!(placement.requested_cpus, "0");
+        assert!(manager.cpus_for(ThreadClass::Io).is_empty());
+    }
+}
@@ -0,0 +1,502 @@
+//! Golden-file compatibility checks for wire formats.
+//!
+//! Each format is reduced to a flat map of field paths to shapes and compared with the
+//! snapshot under `compat/golden/`. Breaking changes fail unless the format's version was
+//! bumped; run with `GUARDIAN_UPDATE_GOLDEN=1` to rewrite the snapshots after a change.
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use schemars::{
+    gen::SchemaSettings,
+    schema::{InstanceType, Schema, SchemaObject, SingleOrVec},
+    JsonSchema,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::warn;
+
+use crate::utils::error::{ErrorCategory, ErrorSeverity, GuardianError};
+
+// Constants for golden snapshots
+const GOLDEN_DIR: &str = "compat/golden";
+const UPDATE_ENV: &str = "GUARDIAN_UPDATE_GOLDEN";
+const DEFINITIONS_PREFIX: &str = "#/definitions/";
+const MAX_SCHEMA_DEPTH: usize = 16;
+const ANY_KIND: &str = "any";
+
+/// Normalized shape of one field of a wire format
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct FieldShape {
+    kind: String,
+    required: bool,
+    /// Field or enum number for protobuf definitions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tag: Option<u32>,
+}
+
+impl FieldShape {
+    fn new(kind: impl Into<String>, required: bool, tag: Option<u32>) -> Self {
+        Self {
+            kind: kind.into(),
+            required,
+            tag,
+        }
+    }
+}
+
+/// Versioned snapshot of a wire format as stored in a golden file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct WireFormat {
+    name: String,
+    version: String,
+    fields: BTreeMap<String, FieldShape>,
+}
+
+impl WireFormat {
+    /// Describes a serde type through its JSON schema
+    pub(crate) fn from_schema<T: JsonSchema>(name: &str, version: impl ToString) -> Self {
+        let root = SchemaSettings::draft07().into_generator().into_root_schema_for::<T>();
+        let mut fields = BTreeMap::new();
+        let walker = SchemaWalker {
+            definitions: &root.definitions,
+        };
+        walker.walk(&root.schema, "", true, &mut fields, 0);
+
+        Self {
+            name: name.to_string(),
+            version: version.to_string(),
+            fields,
+        }
+    }
+
+    /// Describes a format from a representative value, for types without a schema.
+    /// Null values match any kind since they carry no type.
+    pub(crate) fn from_value(name: &str, version: impl ToString, value: &Value) -> Self {
+        let mut fields = BTreeMap::new();
+        walk_value(value, "", &mut fields);
+
+        Self {
+            name: name.to_string(),
+            version: version.to_string(),
+            fields,
+        }
+    }
+
+    /// Describes a protobuf file; the version is the `vN` suffix of its package.
+    /// Proto3 fields are never required, so only removals, renumbering and retyping break.
+    pub(crate) fn from_proto(name: &str, source: &str) -> Self {
+        let (version, fields) = parse_proto(source);
+        Self {
+            name: name.to_string(),
+            version,
+            fields,
+        }
+    }
+}
+
+/// Returns the changes from `golden` to `current` that break existing readers or writers
+pub(crate) fn incompatibilities(golden: &WireFormat, current: &WireFormat) -> Vec<String> {
+    let mut problems = Vec::new();
+
+    for (path, old) in &golden.fields {
+        let Some(new) = current.fields.get(path) else {
+            problems.push(format!("{} removed", path));
+            continue;
+        };
+        if old.kind != new.kind && old.kind != ANY_KIND && new.kind != ANY_KIND {
+            problems.push(format!("{} changed from {} to {}", path, old.kind, new.kind));
+        }
+        if old.tag != new.tag {
+            problems.push(format!("{} renumbered from {:?} to {:?}", path, old.tag, new.tag));
+        }
+        if !old.required && new.required {
+            problems.push(format!("{} became required", path));
+        }
+    }
+
+    for (path, new) in &current.fields {
+        if new.required && !golden.fields.contains_key(path) {
+            problems.push(format!("{} added as required", path));
+        }
+    }
+    problems
+}
+
+/// Compares a format with its golden file, writing the file when missing or when updating
+pub(crate) fn check_golden(dir: &Path, current: &WireFormat) -> Result<(), GuardianError> {
+    let path = dir.join(format!("{}.json", current.name));
+    let updating = std::env::var_os(UPDATE_ENV).is_some();
+
+    if updating || !path.exists() {
+        std::fs::create_dir_all(dir)
+            .map_err(|e| compat_error(format!("Failed to create {}", dir.display()), Some(Box::new(e))))?;
+        let json = serde_json::to_string_pretty(current)
+            .map_err(|e| compat_error(format!("Failed to serialize {}", current.name), Some(Box::new(e))))?;
+        return std::fs::write(&path, json + "\n")
+            .map_err(|e| compat_error(format!("Failed to write {}", path.display()), Some(Box::new(e))));
+    }
+
+    let json = std::fs::read_to_string(&path)
+        .map_err(|e| compat_error(format!("Failed to read {}", path.display()), Some(Box::new(e))))?;
+    let golden: WireFormat = serde_json::from_str(&json)
+        .map_err(|e| compat_error(format!("Failed to parse {}", path.display()), Some(Box::new(e))))?;
+    if golden == *current {
+        return Ok(());
+    }
+
+    let problems = incompatibilities(&golden, current);
+    if !problems.is_empty() && golden.version == current.version {
+        return Err(compat_error(
+            format!(
+                "{} changed incompatibly without a version bump (still {}):\n  {}",
+                current.name,
+                current.version,
+                problems.join("\n  ")
+            ),
+            None,
+        ));
+    }
+
+    warn!(
+        format = %current.name,
+        golden = %path.display(),
+        "Wire format differs compatibly from its golden file; rerun with {}=1 to refresh it",
+        UPDATE_ENV
+    );
+    Ok(())
+}
+
+/// Directory holding the golden files of this crate
+pub(crate) fn golden_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join(GOLDEN_DIR)
+}
+
+/// Every wire format whose compatibility is guarded by a golden file
+pub(crate) fn wire_formats() -> Result<Vec<WireFormat>, GuardianError> {
+    let config = serde_json::to_value(crate::config::app_config::AppConfig::new(None))
+        .map_err(|e| compat_error("Failed to serialize the default configuration".into(), Some(Box::new(e))))?;
+
+    Ok(vec![
+        WireFormat::from_proto("proto.guardian", include_str!("../api/proto/guardian.proto")),
+        WireFormat::from_proto("proto.security", include_str!("../api/proto/security.proto")),
+        WireFormat::from_proto("proto.ml", include_str!("../api/proto/ml.proto")),
+        WireFormat::from_schema::<crate::storage::Event>("event.stored", crate::storage::EVENT_SCHEMA_VERSION),
+        WireFormat::from_schema::<crate::security::audit::AuditEvent>(
+            "event.audit",
+            crate::security::audit::AUDIT_EVENT_SCHEMA_VERSION,
+        ),
+        WireFormat::from_schema::<crate::storage::BackupManifest>(
+            "storage.backup_manifest",
+            crate::storage::BACKUP_MANIFEST_VERSION,
+        ),
+        WireFormat::from_value("config.app", crate::config::app_config::CONFIG_VERSION, &config),
+    ])
+}
+
+fn compat_error(context: String, source: Option<Box<dyn std::error::Error + Send + Sync>>) -> GuardianError {
+    GuardianError::ValidationError {
+        context,
+        source,
+        severity: ErrorSeverity::Medium,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: ErrorCategory::Validation,
+        retry_count: 0,
+    }
+}
+
+struct SchemaWalker<'a> {
+    definitions: &'a schemars::Map<String, Schema>,
+}
+
+impl SchemaWalker<'_> {
+    fn walk(&self, schema: &Schema, path: &str, required: bool, out: &mut BTreeMap<String, FieldShape>, depth: usize) {
+        let Schema::Object(object) = schema else {
+            if !path.is_empty() {
+                out.insert(path.to_string(), FieldShape::new(ANY_KIND, required, None));
+            }
+            return;
+        };
+        if depth > MAX_SCHEMA_DEPTH {
+            return;
+        }
+
+        if let Some(definition) = object
+            .reference
+            .as_deref()
+            .and_then(|reference| reference.strip_prefix(DEFINITIONS_PREFIX))
+            .and_then(|name| self.definitions.get(name))
+        {
+            return self.walk(definition, path, required, out, depth + 1);
+        }
+
+        let (kind, nullable) = schema_kind(object);
+        if !path.is_empty() {
+            out.insert(path.to_string(), FieldShape::new(kind, required && !nullable, None));
+        }
+
+        for value in object.enum_values.iter().flatten() {
+            if let Some(variant) = value.as_str() {
+                out.insert(format!("{}#{}", path, variant), FieldShape::new("variant", false, None));
+            }
+        }
+
+        if let Some(subschemas) = &object.subschemas {
+            let alternatives = subschemas.one_of.iter().chain(subschemas.any_of.iter()).chain(subschemas.all_of.iter());
+            for schema in alternatives.flatten().filter(|schema| !is_null(schema)) {
+                self.walk(schema, path, false, out, depth + 1);
+            }
+        }
+
+        if let Some(validation) = &object.object {
+            for (name, property) in &validation.properties {
+                let child = if path.is_empty() { name.clone() } else { format!("{}.{}", path, name) };
+                self.walk(property, &child, validation.required.contains(name), out, depth + 1);
+            }
+            if let Some(values) = &validation.additional_properties {
+                self.walk(values, &format!("{}.*", path), false, out, depth + 1);
+            }
+        }
+
+        if let Some(SingleOrVec::Single(items)) = object.array.as_ref().and_then(|array| array.items.as_ref()) {
+            self.walk(items, &format!("{}[]", path), true, out, depth + 1);
+        }
+    }
+}
+
+fn is_null(schema: &Schema) -> bool {
+    matches!(
+        schema,
+        Schema::Object(SchemaObject { instance_type: Some(SingleOrVec::Single(single)), .. }) if **single == InstanceType::Null
+    )
+}
+
+/// Returns the non-null JSON types a schema allows and whether it also allows null
+fn schema_kind(object: &SchemaObject) -> (String, bool) {
+    let types: Vec<InstanceType> = match &object.instance_type {
+        Some(SingleOrVec::Single(single)) => vec![**single],
+        Some(SingleOrVec::Vec(types)) => types.clone(),
+        None if object.enum_values.is_some() => return ("enum".to_string(), false),
+        None => return (ANY_KIND.to_string(), false),
+    };
+    let nullable = types.contains(&InstanceType::Null);
+    let kinds: Vec<String> = types
+        .iter()
+        .filter(|t| **t != InstanceType::Null)
+        .map(|t| format!("{:?}", t).to_lowercase())
+        .collect();
+
+    match kinds.is_empty() {
+        true => (ANY_KIND.to_string(), nullable),
+        false => (kinds.join("|"), nullable),
+    }
+}
+
+fn walk_value(value: &Value, path: &str, out: &mut BTreeMap<String, FieldShape>) {
+    let kind = match value {
+        Value::Null => ANY_KIND,
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    };
+    if !path.is_empty() {
+        out.insert(path.to_string(), FieldShape::new(kind, false, None));
+    }
+
+    match value {
+        Value::Object(map) => {
+            for (name, child) in map {
+                let child_path = if path.is_empty() { name.clone() } else { format!("{}.{}", path, name) };
+                walk_value(child, &child_path, out);
+            }
+        }
+        Value::Array(items) => {
+            if let Some(first) = items.first() {
+                walk_value(first, &format!("{}[]", path), out);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Block a protobuf statement belongs to
+enum ProtoScope {
+    Message(String),
+    Enum(String),
+    Service(String),
+    /// Oneofs and rpc option bodies, whose contents belong to the enclosing scope
+    Nested,
+}
+
+/// Extracts the package version and the fields, enum values and rpcs of a proto file
+fn parse_proto(source: &str) -> (String, BTreeMap<String, FieldShape>) {
+    let stripped: String = source
+        .lines()
+        .map(|line| line.split("//").next().unwrap_or_default())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let mut version = String::new();
+    let mut fields = BTreeMap::new();
+    let mut scopes: Vec<ProtoScope> = Vec::new();
+    let mut statement = String::new();
+
+    for ch in stripped.chars() {
+        match ch {
+            '{' => {
+                let header: Vec<&str> = statement.split_whitespace().collect();
+                let qualified = |name: &str| match enclosing_message(&scopes) {
+                    Some(parent) => format!("{}.{}", parent, name),
+                    None => name.to_string(),
+                };
+                let scope = match header.as_slice() {
+                    ["message", name, ..] => ProtoScope::Message(qualified(name)),
+                    ["enum", name, ..] => ProtoScope::Enum(qualified(name)),
+                    ["service", name, ..] => ProtoScope::Service(name.to_string()),
+                    _ => {
+                        record_proto_statement(&statement, &scopes, &mut fields);
+                        ProtoScope::Nested
+                    }
+                };
+                scopes.push(scope);
+                statement.clear();
+            }
+            '}' => {
+                scopes.pop();
+                statement.clear();
+            }
+            ';' => {
+                if let Some(package) = statement.trim().strip_prefix("package ") {
+                    version = package
+                        .trim()
+                        .rsplit('.')
+                        .find(|part| part.starts_with('v'))
+                        .unwrap_or_default()
+                        .to_string();
+                } else {
+                    record_proto_statement(&statement, &scopes, &mut fields);
+                }
+                statement.clear();
+            }
+            _ => statement.push(ch),
+        }
+    }
+    (version, fields)
+}
+
+fn enclosing_message(scopes: &[ProtoScope]) -> Option<&str> {
+    scopes.iter().rev().find_map(|scope| match scope {
+        ProtoScope::Message(name) => Some(name.as_str()),
+        _ => None,
+    })
+}
+
+fn record_proto_statement(statement: &str, scopes: &[ProtoScope], fields: &mut BTreeMap<String, FieldShape>) {
+    let statement = statement.split('[').next().unwrap_or_default().trim();
+    let owner = scopes.iter().rev().find(|scope| !matches!(scope, ProtoScope::Nested));
+
+    match owner {
+        Some(ProtoScope::Message(message)) => {
+            let Some((declaration, number)) = statement.rsplit_once('=') else { return };
+            let Ok(number) = number.trim().parse::<u32>() else { return };
+            let Some((kind, name)) = declaration.trim().rsplit_once(char::is_whitespace) else { return };
+            if kind.starts_with("option") || kind.starts_with("reserved") {
+                return;
+            }
+            let kind = kind.split_whitespace().collect::<Vec<_>>().join(" ");
+            fields.insert(format!("{}.{}", message, name), FieldShape::new(kind, false, Some(number)));
+        }
+        Some(ProtoScope::Enum(name)) => {
+            let Some((value, number)) = statement.split_once('=') else { return };
+            let Ok(number) = number.trim().parse::<u32>() else { return };
+            fields.insert(format!("{}#{}", name, value.trim()), FieldShape::new("variant", false, Some(number)));
+        }
+        Some(ProtoScope::Service(service)) => {
+            let Some(rpc) = statement.strip_prefix("rpc") else { return };
+            let Some((method, signature)) = rpc.split_once('(') else { return };
+            let signature = signature.split_whitespace().collect::<Vec<_>>().join(" ");
+            fields.insert(
+                format!("{}/{}", service, method.trim()),
+                FieldShape::new(format!("({}", signature), false, None),
+            );
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROTO: &str = r#"
+        syntax = "proto3";
+        package guardian.test.v1;
+
+        enum Level {
+            LEVEL_UNKNOWN = 0;
+            LEVEL_HIGH = 1;  // most severe
+        }
+
+        message Alert {
+            string id = 1;
+            repeated string tags = 2 [packed = true];
+            oneof target {
+                string host = 3;
+            }
+            map<string, string> labels = 4;
+        }
+
+        service Alerts {
+            rpc Stream(Alert) returns (stream Alert);
+        }
+    "#;
+
+    #[test]
+    fn test_proto_shapes_and_renumbering() {
+        let golden = WireFormat::from_proto("proto.test", PROTO);
+        assert_eq!(golden.version, "v1");
+        assert_eq!(golden.fields["Alert.host"].tag, Some(3));
+        assert_eq!(golden.fields["Alert.labels"].kind, "map<string, string>");
+        assert_eq!(golden.fields["Level#LEVEL_HIGH"].tag, Some(1));
+        assert!(golden.fields.contains_key("Alerts/Stream"));
+
+        let renumbered = WireFormat::from_proto("proto.test", &PROTO.replace("string id = 1", "string id = 5"));
+        assert_eq!(incompatibilities(&golden, &renumbered), vec!["Alert.id renumbered from Some(1) to Some(5)"]);
+    }
+
+    #[test]
+    fn test_golden_check_requires_version_bump() {
+        let dir = tempfile::tempdir().unwrap();
+        let golden = WireFormat::from_value("config.test", "1", &serde_json::json!({ "port": 80, "host": "a" }));
+        check_golden(dir.path(), &golden).unwrap();
+
+        let added = WireFormat::from_value("config.test", "1", &serde_json::json!({ "port": 80, "host": "a", "tls": true }));
+        assert!(check_golden(dir.path(), &added).is_ok());
+
+        let retyped = WireFormat::from_value("config.test", "1", &serde_json::json!({ "port": "80", "host": "a" }));
+        assert!(check_golden(dir.path(), &retyped)
+            .unwrap_err()
+            .to_string()
+            .contains("port changed from number to string"));
+
+        let bumped = WireFormat::from_value("config.test", "2", &serde_json::json!({ "port": "80", "host": "a" }));
+        assert!(check_golden(dir.path(), &bumped).is_ok());
+    }
+
+    #[test]
+    fn test_wire_formats_match_golden() {
+        let dir = golden_dir();
+        let failures: Vec<String> = wire_formats()
+            .unwrap()
+            .iter()
+            .filter_map(|format| check_golden(&dir, format).err())
+            .map(|e| e.to_string())
+            .collect();
+        assert!(failures.is_empty(), "{}", failures.join("\n\n"));
+    }
+}
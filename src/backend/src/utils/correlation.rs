@@ -0,0 +1,156 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use http::{HeaderValue, Request, Response};
+use tracing::{info_span, Instrument};
+use uuid::Uuid;
+
+use crate::utils::ids::{next_uuid, IdKind};
+
+// Constants for correlation propagation
+pub const CORRELATION_HEADER: &str = "x-correlation-id";
+pub const CORRELATION_METADATA_KEY: &str = "correlation_id";
+
+tokio::task_local! {
+    static CORRELATION_ID: Uuid;
+}
+
+/// Returns the correlation ID of the current task, if one is in scope
+pub fn current() -> Option<Uuid> {
+    CORRELATION_ID.try_with(|id| *id).ok()
+}
+
+/// Returns the correlation ID of the current task, or a fresh one outside any scope
+pub fn current_or_new() -> Uuid {
+    current().unwrap_or_else(|| next_uuid(IdKind::Correlation))
+}
+
+/// Runs a future with the correlation ID in task-local storage and on its tracing span
+pub async fn scope<F: Future>(correlation_id: Uuid, future: F) -> F::Output {
+    let span = info_span!("correlation", correlation_id = %correlation_id);
+    CORRELATION_ID.scope(correlation_id, future.instrument(span)).await
+}
+
+/// Runs a closure with the correlation ID in scope, for synchronous code paths
+pub fn sync_scope<R>(correlation_id: Uuid, f: impl FnOnce() -> R) -> R {
+    let span = info_span!("correlation", correlation_id = %correlation_id);
+    CORRELATION_ID.sync_scope(correlation_id, || span.in_scope(f))
+}
+
+/// Spawns a task that inherits the caller's correlation ID
+pub fn spawn<F>(future: F) -> tokio::task::JoinHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    let correlation_id = current_or_new();
+    tokio::spawn(scope(correlation_id, future))
+}
+
+/// Parses a correlation ID received from a header, event or workflow
+pub fn parse(value: &str) -> Option<Uuid> {
+    Uuid::parse_str(value.trim()).ok()
+}
+
+/// Returns a Temporal workflow ID carrying the current correlation ID
+pub fn workflow_id(prefix: &str) -> String {
+    format!("{}-{}", prefix, current_or_new())
+}
+
+/// Recovers the correlation ID from a workflow ID created by `workflow_id`
+pub fn from_workflow_id(workflow_id: &str) -> Option<Uuid> {
+    // Hyphenated UUIDs are 36 characters long
+    let split = workflow_id.len().checked_sub(36)?;
+    workflow_id.get(split..).and_then(parse)
+}
+
+/// Tower layer scoping every request to the correlation ID of its `x-correlation-id` header
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CorrelationLayer;
+
+impl<S> tower::Layer<S> for CorrelationLayer {
+    type Service = CorrelationService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        CorrelationService { inner }
+    }
+}
+
+/// Service created by `CorrelationLayer`
+#[derive(Debug, Clone)]
+pub struct CorrelationService<S> {
+    inner: S,
+}
+
+impl<S, ReqBody, ResBody> tower::Service<Request<ReqBody>> for CorrelationService<S>
+where
+    S: tower::Service<Request<ReqBody>, Response = Response<ResBody>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: Request<ReqBody>) -> Self::Future {
+        let correlation_id = request
+            .headers()
+            .get(CORRELATION_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .and_then(parse)
+            .unwrap_or_else(|| next_uuid(IdKind::Correlation));
+        let header = HeaderValue::from_str(&correlation_id.to_string()).ok();
+
+        // Handlers forwarding the header see the ID assigned to requests that had none
+        if let Some(header) = &header {
+            request.headers_mut().insert(CORRELATION_HEADER, header.clone());
+        }
+
+        let future = self.inner.call(request);
+        Box::pin(scope(correlation_id, async move {
+            let mut response = future.await?;
+            if let Some(header) = header {
+                response.headers_mut().insert(CORRELATION_HEADER, header);
+            }
+            Ok(response)
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_scope_propagates_to_nested_and_spawned_tasks() {
+        assert!(current().is_none());
+
+        let correlation_id = next_uuid(IdKind::Correlation);
+        let (nested, spawned) = scope(correlation_id, async {
+            let nested = current_or_new();
+            let spawned = spawn(async { current() }).await.unwrap();
+            (nested, spawned)
+        })
+        .await;
+
+        assert_eq!(nested, correlation_id);
+        assert_eq!(spawned, Some(correlation_id));
+        assert_eq!(sync_scope(correlation_id, current), Some(correlation_id));
+    }
+
+    #[tokio::test]
+    async fn test_workflow_id_round_trip() {
+        let correlation_id = next_uuid(IdKind::Correlation);
+        let workflow_id = scope(correlation_id, async { workflow_id("train-model") }).await;
+
+        assert!(workflow_id.starts_with("train-model-"));
+        assert_eq!(from_workflow_id(&workflow_id), Some(correlation_id));
+        assert_eq!(from_workflow_id("short"), None);
+    }
+}
@@ -1,17 +1,23 @@
 use metrics::{counter, histogram};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
 use serde::Serialize;
+use std::collections::HashMap;
 use thiserror::Error;
 use time::OffsetDateTime;
 use tracing::{error, info, warn};
 use uuid::Uuid;
 
 // Constants for error handling configuration
-const RETRY_LIMIT: u32 = 3;
+pub(crate) const RETRY_LIMIT: u32 = 3;
 const ERROR_CONTEXT_MAX_LENGTH: usize = 1024;
 const ERROR_SAMPLING_RATE: f64 = 0.1;
 const MAX_ERROR_CHAIN_LENGTH: usize = 10;
 const ERROR_CACHE_SIZE: usize = 1000;
 
+static ERROR_AGGREGATES: Lazy<Mutex<HashMap<(String, String), ErrorAggregate>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
 /// Error severity levels for prioritization and handling
 #[derive(Debug, Clone, Copy, Serialize, PartialEq)]
 pub enum ErrorSeverity {
@@ -22,7 +28,7 @@ pub enum ErrorSeverity {
 }
 
 /// Categories of errors for classification and metrics
-#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, Hash)]
 pub enum ErrorCategory {
     System,
     Security,
@@ -31,6 +37,62 @@ pub enum ErrorCategory {
     Validation,
 }
 
+impl ErrorCategory {
+    /// Stable lowercase name, as exposed to API clients
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorCategory::System => "system",
+            ErrorCategory::Security => "security",
+            ErrorCategory::ML => "ml",
+            ErrorCategory::Storage => "storage",
+            ErrorCategory::Validation => "validation",
+        }
+    }
+
+    /// Hundreds digit of error codes in this category
+    fn code_digit(&self) -> u32 {
+        match self {
+            ErrorCategory::System => 1,
+            ErrorCategory::Security => 2,
+            ErrorCategory::ML => 3,
+            ErrorCategory::Storage => 4,
+            ErrorCategory::Validation => 5,
+        }
+    }
+}
+
+impl ErrorSeverity {
+    /// Stable lowercase name, as exposed to API clients
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorSeverity::Critical => "critical",
+            ErrorSeverity::High => "high",
+            ErrorSeverity::Medium => "medium",
+            ErrorSeverity::Low => "low",
+        }
+    }
+}
+
+/// Stable code identifying the kind of an error to API clients
+///
+/// The thousands digit is the variant and the hundreds digit the category, both numbered
+/// System 1, Security 2, ML 3, Storage 4 and Validation 5: a storage failure reported as a
+/// system error is 1400, a rejected request 5500. Codes and reasons are part of the API
+/// and are never renumbered or reused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ErrorCode {
+    pub numeric: u32,
+    /// `google.rpc.ErrorInfo` reason naming the variant, e.g. `STORAGE_ERROR`
+    pub reason: &'static str,
+    pub category: ErrorCategory,
+}
+
+impl std::fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "GRD-{}", self.numeric)
+    }
+}
+
 /// Primary error type for the Guardian system
 #[derive(Debug, Error, Serialize)]
 pub enum GuardianError {
@@ -107,7 +169,7 @@ impl GuardianError {
                     source,
                     severity,
                     timestamp: OffsetDateTime::now_utc(),
-                    correlation_id: Uuid::new_v4(),
+                    correlation_id: crate::utils::correlation::current_or_new(),
                     category,
                     retry_count,
                 }
@@ -127,7 +189,7 @@ impl GuardianError {
                     source,
                     severity,
                     timestamp: OffsetDateTime::now_utc(),
-                    correlation_id: Uuid::new_v4(),
+                    correlation_id: crate::utils::correlation::current_or_new(),
                     category,
                     retry_count,
                 }
@@ -138,14 +200,43 @@ impl GuardianError {
     }
 
     /// Increments the retry count for retryable errors
-    pub fn increment_retry(&self) -> Option<Self> {
+    pub fn increment_retry(self) -> Option<Self> {
+        self.retry().ok()
+    }
+
+    /// Increments the retry count, or returns the error unchanged once the retry limit is reached
+    pub fn retry(self) -> std::result::Result<Self, Self> {
         if self.retry_count() >= RETRY_LIMIT {
-            None
+            Err(self)
         } else {
-            Some(self.clone().with_retry_count(self.retry_count() + 1))
+            let count = self.retry_count() + 1;
+            Ok(self.with_retry_count(count))
         }
     }
 
+    /// Records that an operation failing with this error was retried, annotating the context
+    pub fn retried(mut self, operation: &str, retries: u32) -> Self {
+        if retries == 0 {
+            return self;
+        }
+
+        let annotation = format!(" (after {} retries of {})", retries, operation);
+        let context = self.context_mut();
+        if context.len() + annotation.len() <= ERROR_CONTEXT_MAX_LENGTH {
+            context.push_str(&annotation);
+        }
+        counter!("guardian.errors.retried", 1, "operation" => operation.to_string());
+        self.with_retry_count(retries)
+    }
+
+    /// Returns true if retrying the failed operation may succeed
+    pub fn is_retryable(&self) -> bool {
+        !matches!(
+            self,
+            GuardianError::ValidationError { .. } | GuardianError::SecurityError { .. }
+        ) && self.severity() != ErrorSeverity::Critical
+    }
+
     /// Gets the current retry count
     pub fn retry_count(&self) -> u32 {
         match self {
@@ -157,22 +248,75 @@ impl GuardianError {
         }
     }
 
-    /// Sets a specific retry count
-    fn with_retry_count(self, count: u32) -> Self {
+    /// Gets the error category
+    pub fn category(&self) -> ErrorCategory {
         match self {
-            GuardianError::SystemError { context, source, severity, category, .. } => {
-                GuardianError::SystemError {
-                    context,
-                    source,
-                    severity,
-                    timestamp: OffsetDateTime::now_utc(),
-                    correlation_id: Uuid::new_v4(),
-                    category,
-                    retry_count: count,
-                }
-            }
-            // Similar pattern for other variants...
-            _ => self
+            GuardianError::SystemError { category, .. }
+            | GuardianError::SecurityError { category, .. }
+            | GuardianError::MLError { category, .. }
+            | GuardianError::StorageError { category, .. }
+            | GuardianError::ValidationError { category, .. } => *category,
+        }
+    }
+
+    /// Gets the severity level
+    pub fn severity(&self) -> ErrorSeverity {
+        match self {
+            GuardianError::SystemError { severity, .. }
+            | GuardianError::SecurityError { severity, .. }
+            | GuardianError::MLError { severity, .. }
+            | GuardianError::StorageError { severity, .. }
+            | GuardianError::ValidationError { severity, .. } => *severity,
+        }
+    }
+
+    /// Stable code of this error for API clients
+    pub fn code(&self) -> ErrorCode {
+        let (variant, reason) = match self {
+            GuardianError::SystemError { .. } => (1, "SYSTEM_ERROR"),
+            GuardianError::SecurityError { .. } => (2, "SECURITY_ERROR"),
+            GuardianError::MLError { .. } => (3, "ML_ERROR"),
+            GuardianError::StorageError { .. } => (4, "STORAGE_ERROR"),
+            GuardianError::ValidationError { .. } => (5, "VALIDATION_ERROR"),
+        };
+        let category = self.category();
+        ErrorCode {
+            numeric: variant * 1000 + category.code_digit() * 100,
+            reason,
+            category,
+        }
+    }
+
+    /// Correlation ID of the request or operation that failed
+    pub fn correlation_id(&self) -> Uuid {
+        match self {
+            GuardianError::SystemError { correlation_id, .. }
+            | GuardianError::SecurityError { correlation_id, .. }
+            | GuardianError::MLError { correlation_id, .. }
+            | GuardianError::StorageError { correlation_id, .. }
+            | GuardianError::ValidationError { correlation_id, .. } => *correlation_id,
+        }
+    }
+
+    /// Sets a specific retry count, keeping the original correlation ID
+    fn with_retry_count(mut self, count: u32) -> Self {
+        match &mut self {
+            GuardianError::SystemError { retry_count, .. }
+            | GuardianError::SecurityError { retry_count, .. }
+            | GuardianError::MLError { retry_count, .. }
+            | GuardianError::StorageError { retry_count, .. }
+            | GuardianError::ValidationError { retry_count, .. } => *retry_count = count,
+        }
+        self
+    }
+
+    fn context_mut(&mut self) -> &mut String {
+        match self {
+            GuardianError::SystemError { context, .. }
+            | GuardianError::SecurityError { context, .. }
+            | GuardianError::MLError { context, .. }
+            | GuardianError::StorageError { context, .. }
+            | GuardianError::ValidationError { context, .. } => context,
         }
     }
 }
@@ -188,6 +332,24 @@ pub trait ErrorContext<T, E> {
     fn error_source(self) -> Result<T, GuardianError>;
 }
 
+/// Count of errors recorded in this process for one category and severity
+#[derive(Debug, Clone, Serialize)]
+pub struct ErrorAggregate {
+    pub category: String,
+    pub severity: String,
+    pub count: u64,
+    pub first_seen: OffsetDateTime,
+    pub last_seen: OffsetDateTime,
+    pub last_context: String,
+}
+
+/// Returns the errors recorded so far, most frequent first
+pub fn error_aggregates() -> Vec<ErrorAggregate> {
+    let mut aggregates: Vec<ErrorAggregate> = ERROR_AGGREGATES.lock().values().cloned().collect();
+    aggregates.sort_by(|a, b| b.count.cmp(&a.count));
+    aggregates
+}
+
 /// Type alias for Guardian results
 pub type Result<T> = std::result::Result<T, GuardianError>;
 
@@ -226,6 +388,24 @@ pub fn record_error_metrics(error: &GuardianError) {
     if error.retry_count() >= RETRY_LIMIT {
         counter!("guardian.errors.retry_exceeded", 1, "category" => category.to_string());
     }
+
+    let key = (format!("{:?}", category), format!("{:?}", error.severity()));
+    let now = OffsetDateTime::now_utc();
+    let mut aggregates = ERROR_AGGREGATES.lock();
+    if aggregates.len() >= ERROR_CACHE_SIZE && !aggregates.contains_key(&key) {
+        return;
+    }
+    let aggregate = aggregates.entry(key.clone()).or_insert_with(|| ErrorAggregate {
+        category: key.0,
+        severity: key.1,
+        count: 0,
+        first_seen: now,
+        last_seen: now,
+        last_context: String::new(),
+    });
+    aggregate.count += 1;
+    aggregate.last_seen = now;
+    aggregate.last_context = error.to_string().chars().take(ERROR_CONTEXT_MAX_LENGTH).collect();
 }
 
 #[cfg(test)]
@@ -239,7 +419,7 @@ mod tests {
             source: None,
             severity: ErrorSeverity::High,
             timestamp: OffsetDateTime::now_utc(),
-            correlation_id: Uuid::new_v4(),
+            correlation_id: crate::utils::correlation::current_or_new(),
             category: ErrorCategory::System,
             retry_count: 0,
         };
@@ -255,11 +435,50 @@ mod tests {
             source: None,
             severity: ErrorSeverity::High,
             timestamp: OffsetDateTime::now_utc(),
-            correlation_id: Uuid::new_v4(),
+            correlation_id: crate::utils::correlation::current_or_new(),
             category: ErrorCategory::System,
             retry_count: RETRY_LIMIT,
         };
 
         assert!(error.increment_retry().is_none());
     }
+
+    #[test]
+    fn test_retried_annotates_context() {
+        let error = GuardianError::StorageError {
+            context: "write failed".to_string(),
+            source: None,
+            severity: ErrorSeverity::High,
+            timestamp: OffsetDateTime::now_utc(),
+            correlation_id: crate::utils::correlation::current_or_new(),
+            category: ErrorCategory::Storage,
+            retry_count: 0,
+        };
+        let correlation_id = match &error {
+            GuardianError::StorageError { correlation_id, .. } => *correlation_id,
+            _ => unreachable!(),
+        };
+
+        let error = error.retried("flush_partition", 2);
+        assert_eq!(error.retry_count(), 2);
+        assert!(error.to_string().contains("after 2 retries of flush_partition"));
+        assert!(error.is_retryable());
+        assert!(matches!(error, GuardianError::StorageError { correlation_id: id, .. } if id == correlation_id));
+    }
+
+    #[test]
+    fn test_error_codes_combine_variant_and_category() {
+        let error = GuardianError::SystemError {
+            context: "pool offline".to_string(),
+            source: None,
+            severity: ErrorSeverity::High,
+            timestamp: OffsetDateTime::now_utc(),
+            correlation_id: crate::utils::correlation::current_or_new(),
+            category: ErrorCategory::Storage,
+            retry_count: 0,
+        };
+        let code = error.code();
+        assert_eq!((code.numeric, code.reason, code.category), (1400, "SYSTEM_ERROR", ErrorCategory::Storage));
+        assert_eq!(code.to_string(), "GRD-1400");
+    }
 }
\ No newline at end of file
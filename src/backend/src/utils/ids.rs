@@ -0,0 +1,200 @@
+use std::{fmt, str::FromStr};
+
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use ulid::{Generator, Ulid};
+use uuid::Uuid;
+
+use crate::utils::error::GuardianError;
+
+// Constants for ID generation
+const ID_SEPARATOR: char = '_';
+
+static ID_GENERATOR: Lazy<IdGenerator> = Lazy::new(IdGenerator::new);
+
+/// Kind of entity an ID identifies, encoded as the ID prefix
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum IdKind {
+    Event,
+    Detection,
+    Incident,
+    Operation,
+    Correlation,
+}
+
+impl IdKind {
+    const ALL: [IdKind; 5] = [
+        IdKind::Event,
+        IdKind::Detection,
+        IdKind::Incident,
+        IdKind::Operation,
+        IdKind::Correlation,
+    ];
+
+    /// Returns the short prefix used in the string form of IDs of this kind
+    pub fn prefix(&self) -> &'static str {
+        match self {
+            IdKind::Event => "evt",
+            IdKind::Detection => "det",
+            IdKind::Incident => "inc",
+            IdKind::Operation => "op",
+            IdKind::Correlation => "cor",
+        }
+    }
+
+    fn from_prefix(prefix: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|k| k.prefix() == prefix)
+    }
+}
+
+/// Time-ordered identifier backed by a ULID, rendered as `<prefix>_<ulid>`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct GuardianId {
+    kind: IdKind,
+    ulid: Ulid,
+}
+
+impl GuardianId {
+    /// Generates a new ID, monotonically increasing within this process
+    pub fn new(kind: IdKind) -> Self {
+        ID_GENERATOR.generate(kind)
+    }
+
+    /// Wraps an existing UUID, keeping its bits so both forms refer to the same entity
+    pub fn from_uuid(kind: IdKind, uuid: Uuid) -> Self {
+        Self {
+            kind,
+            ulid: Ulid::from(uuid),
+        }
+    }
+
+    /// Returns the kind of entity this ID identifies
+    pub fn kind(&self) -> IdKind {
+        self.kind
+    }
+
+    /// Returns the creation time in milliseconds since the Unix epoch
+    pub fn timestamp_ms(&self) -> u64 {
+        self.ulid.timestamp_ms()
+    }
+
+    /// Returns the UUID form for fields and APIs that still carry UUIDs
+    pub fn to_uuid(&self) -> Uuid {
+        Uuid::from(self.ulid)
+    }
+}
+
+impl fmt::Display for GuardianId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}{}", self.kind.prefix(), ID_SEPARATOR, self.ulid)
+    }
+}
+
+impl FromStr for GuardianId {
+    type Err = GuardianError;
+
+    /// Parses the prefixed form, falling back to a bare UUID for legacy IDs
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: &str| GuardianError::ValidationError {
+            context: format!("Invalid ID '{}': {}", s, reason),
+            source: None,
+            severity: crate::utils::error::ErrorSeverity::Medium,
+            timestamp: time::OffsetDateTime::now_utc(),
+            correlation_id: crate::utils::correlation::current_or_new(),
+            category: crate::utils::error::ErrorCategory::Validation,
+            retry_count: 0,
+        };
+
+        let (prefix, ulid) = s.split_once(ID_SEPARATOR).ok_or_else(|| invalid("missing kind prefix"))?;
+        let kind = IdKind::from_prefix(prefix).ok_or_else(|| invalid("unknown kind prefix"))?;
+
+        match Ulid::from_string(ulid) {
+            Ok(ulid) => Ok(Self { kind, ulid }),
+            Err(_) => Uuid::parse_str(ulid)
+                .map(|uuid| Self::from_uuid(kind, uuid))
+                .map_err(|_| invalid("not a ULID or UUID")),
+        }
+    }
+}
+
+impl From<GuardianId> for Uuid {
+    fn from(id: GuardianId) -> Self {
+        id.to_uuid()
+    }
+}
+
+impl Serialize for GuardianId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for GuardianId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Process-wide generator keeping IDs monotonic when several are created in the same millisecond
+#[derive(Debug)]
+pub struct IdGenerator {
+    generator: Mutex<Generator>,
+}
+
+impl IdGenerator {
+    fn new() -> Self {
+        Self {
+            generator: Mutex::new(Generator::new()),
+        }
+    }
+
+    fn generate(&self, kind: IdKind) -> GuardianId {
+        let mut generator = self.generator.lock();
+        // Overflow of the random part within one millisecond falls back to a fresh ULID
+        let ulid = generator.generate().unwrap_or_else(|_| Ulid::new());
+        GuardianId { kind, ulid }
+    }
+}
+
+/// Generates a new time-ordered ID of the given kind
+pub fn next_id(kind: IdKind) -> GuardianId {
+    GuardianId::new(kind)
+}
+
+/// Generates a time-ordered UUID for fields that are still typed as `Uuid`
+pub fn next_uuid(kind: IdKind) -> Uuid {
+    GuardianId::new(kind).to_uuid()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ids_sort_by_creation_time() {
+        let ids: Vec<GuardianId> = (0..1000).map(|_| next_id(IdKind::Event)).collect();
+        assert!(ids.windows(2).all(|w| w[0] < w[1]));
+
+        let uuids: Vec<Uuid> = ids.iter().map(GuardianId::to_uuid).collect();
+        assert!(uuids.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn test_string_and_uuid_round_trip() {
+        let id = next_id(IdKind::Detection);
+        let rendered = id.to_string();
+        assert!(rendered.starts_with("det_"));
+        assert_eq!(rendered.parse::<GuardianId>().unwrap(), id);
+        assert_eq!(GuardianId::from_uuid(IdKind::Detection, id.to_uuid()), id);
+
+        // Legacy UUIDs remain addressable through the prefixed form
+        let legacy = Uuid::new_v4();
+        let parsed: GuardianId = format!("op_{}", legacy).parse().unwrap();
+        assert_eq!(parsed.to_uuid(), legacy);
+
+        assert!("xyz_01ARZ3NDEKTSV4RRFFQ69G5FAV".parse::<GuardianId>().is_err());
+        assert!("evt".parse::<GuardianId>().is_err());
+    }
+}
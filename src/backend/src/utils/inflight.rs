@@ -0,0 +1,253 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use metrics::counter;
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use serde::Serialize;
+use tokio::sync::Notify;
+use tracing::{info, warn};
+
+static INFLIGHT_REGISTRY: Lazy<Arc<InflightRegistry>> = Lazy::new(|| Arc::new(InflightRegistry::default()));
+
+/// Order in which request classes are drained, callers before the work they depend on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DrainStage {
+    Api,
+    Inference,
+}
+
+/// Outcome of draining one class of requests
+#[derive(Debug, Clone, Serialize)]
+pub struct DrainReport {
+    pub name: String,
+    pub stage: DrainStage,
+    /// Requests outstanding when draining began
+    pub in_flight: usize,
+    /// Requests that completed while draining
+    pub drained: u64,
+    /// Requests still outstanding at the deadline
+    pub abandoned: usize,
+    /// New requests turned away while draining
+    pub rejected: u64,
+    pub elapsed: Duration,
+}
+
+/// Counts outstanding requests of one class and turns new ones away once draining
+#[derive(Debug)]
+pub struct InflightTracker {
+    name: String,
+    stage: DrainStage,
+    active: AtomicUsize,
+    draining: AtomicBool,
+    drained: AtomicU64,
+    rejected: AtomicU64,
+    idle: Notify,
+}
+
+/// Marks a request as outstanding until dropped
+#[derive(Debug)]
+pub struct InflightGuard {
+    tracker: Arc<InflightTracker>,
+}
+
+impl Drop for InflightGuard {
+    fn drop(&mut self) {
+        self.tracker.leave(true);
+    }
+}
+
+impl InflightTracker {
+    fn new(name: &str, stage: DrainStage) -> Self {
+        Self {
+            name: name.to_string(),
+            stage,
+            active: AtomicUsize::new(0),
+            draining: AtomicBool::new(false),
+            drained: AtomicU64::new(0),
+            rejected: AtomicU64::new(0),
+            idle: Notify::new(),
+        }
+    }
+
+    /// Admits a request, or returns `None` once draining so callers can ask clients to retry elsewhere
+    pub fn enter(self: &Arc<Self>) -> Option<InflightGuard> {
+        // Count first so a concurrent drain never misses an admitted request
+        self.active.fetch_add(1, Ordering::SeqCst);
+        if self.draining.load(Ordering::SeqCst) {
+            self.leave(false);
+            self.rejected.fetch_add(1, Ordering::Relaxed);
+            counter!("guardian.inflight.rejected", 1, "requests" => self.name.clone());
+            return None;
+        }
+        Some(InflightGuard {
+            tracker: Arc::clone(self),
+        })
+    }
+
+    /// Returns the number of outstanding requests
+    pub fn active(&self) -> usize {
+        self.active.load(Ordering::SeqCst)
+    }
+
+    /// Returns whether new requests are being turned away
+    pub fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::SeqCst)
+    }
+
+    fn leave(&self, completed: bool) {
+        let remaining = self.active.fetch_sub(1, Ordering::SeqCst) - 1;
+        if completed && self.is_draining() {
+            self.drained.fetch_add(1, Ordering::Relaxed);
+            counter!("guardian.inflight.drained", 1, "requests" => self.name.clone());
+        }
+        if remaining == 0 {
+            self.idle.notify_waiters();
+        }
+    }
+
+    /// Stops admitting requests and waits for outstanding ones until the deadline
+    async fn drain(&self, deadline: Instant) -> DrainReport {
+        let start = Instant::now();
+        self.draining.store(true, Ordering::SeqCst);
+        let in_flight = self.active();
+
+        loop {
+            // Register before checking so a release in between is not missed
+            let idle = self.idle.notified();
+            if self.active() == 0 {
+                break;
+            }
+            if tokio::time::timeout_at(deadline.into(), idle).await.is_err() {
+                break;
+            }
+        }
+
+        DrainReport {
+            name: self.name.clone(),
+            stage: self.stage,
+            in_flight,
+            drained: self.drained.load(Ordering::Relaxed),
+            abandoned: self.active(),
+            rejected: self.rejected.load(Ordering::Relaxed),
+            elapsed: start.elapsed(),
+        }
+    }
+}
+
+/// Registry of request classes drained on shutdown
+#[derive(Debug, Default)]
+pub struct InflightRegistry {
+    trackers: RwLock<Vec<Arc<InflightTracker>>>,
+}
+
+/// Returns the process-wide in-flight request registry
+pub fn inflight_registry() -> Arc<InflightRegistry> {
+    Arc::clone(&INFLIGHT_REGISTRY)
+}
+
+impl InflightRegistry {
+    /// Returns the tracker of a request class, creating it on first use
+    pub fn tracker(&self, name: &str, stage: DrainStage) -> Arc<InflightTracker> {
+        if let Some(tracker) = self.trackers.read().iter().find(|t| t.name == name) {
+            return Arc::clone(tracker);
+        }
+        let mut trackers = self.trackers.write();
+        if let Some(tracker) = trackers.iter().find(|t| t.name == name) {
+            return Arc::clone(tracker);
+        }
+        let tracker = Arc::new(InflightTracker::new(name, stage));
+        trackers.push(Arc::clone(&tracker));
+        tracker
+    }
+
+    /// Drains every stage in order, sharing one deadline
+    pub async fn drain_all(&self, timeout: Duration) -> Vec<DrainReport> {
+        let deadline = Instant::now() + timeout;
+        let mut stages: Vec<DrainStage> = self.trackers.read().iter().map(|t| t.stage).collect();
+        stages.sort();
+        stages.dedup();
+
+        let mut reports = Vec::new();
+        for stage in stages {
+            reports.extend(self.drain_until(stage, deadline).await);
+        }
+        reports
+    }
+
+    /// Drains the request classes of one stage
+    pub async fn drain_stage(&self, stage: DrainStage, timeout: Duration) -> Vec<DrainReport> {
+        self.drain_until(stage, Instant::now() + timeout).await
+    }
+
+    async fn drain_until(&self, stage: DrainStage, deadline: Instant) -> Vec<DrainReport> {
+        let trackers: Vec<Arc<InflightTracker>> =
+            self.trackers.read().iter().filter(|t| t.stage == stage).cloned().collect();
+
+        let mut reports = Vec::with_capacity(trackers.len());
+        for tracker in trackers {
+            let report = tracker.drain(deadline).await;
+            if report.abandoned > 0 {
+                warn!(
+                    requests = %report.name,
+                    abandoned = report.abandoned,
+                    drained = report.drained,
+                    "Drain deadline reached with requests outstanding"
+                );
+            } else {
+                info!(
+                    requests = %report.name,
+                    drained = report.drained,
+                    rejected = report.rejected,
+                    elapsed_ms = report.elapsed.as_millis() as u64,
+                    "Requests drained"
+                );
+            }
+            reports.push(report);
+        }
+        reports
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_drain_waits_for_outstanding_and_rejects_new() {
+        let registry = InflightRegistry::default();
+        let tracker = registry.tracker("grpc.security", DrainStage::Api);
+        let guard = tracker.enter().unwrap();
+
+        let release = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            drop(guard);
+        });
+        let reports = registry.drain_all(Duration::from_secs(5)).await;
+        release.await.unwrap();
+
+        assert_eq!(reports.len(), 1);
+        assert_eq!((reports[0].in_flight, reports[0].drained, reports[0].abandoned), (1, 1, 0));
+        assert!(tracker.enter().is_none());
+        assert_eq!(tracker.active(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_drain_abandons_requests_past_deadline() {
+        let registry = InflightRegistry::default();
+        let api = registry.tracker("grpc.ml", DrainStage::Api);
+        let inference = registry.tracker("inference", DrainStage::Inference);
+        let _stuck = inference.enter().unwrap();
+
+        let reports = registry.drain_all(Duration::from_millis(10)).await;
+        assert_eq!(reports.iter().map(|r| r.stage).collect::<Vec<_>>(), vec![DrainStage::Api, DrainStage::Inference]);
+        assert_eq!(reports[1].abandoned, 1);
+        assert!(api.is_draining());
+    }
+}
@@ -1,13 +1,16 @@
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Write as _;
 use std::path::PathBuf;
 use std::sync::Arc;
 use time::OffsetDateTime;
-use tracing::{info, warn, error, Level, Metadata, Subscriber};
+use tracing::{field::{Field, Visit}, info, warn, error, Event, Level, Metadata, Subscriber};
 use tracing_appender::rolling::{RollingFileAppender, Rotation};
 use tracing_subscriber::{
     fmt::{self, time::UtcTime},
-    layer::SubscriberExt,
+    layer::{Context, SubscriberExt},
     util::SubscriberInitExt,
     Layer,
 };
@@ -23,6 +26,10 @@ const LOG_ROTATION_DAYS: u32 = 30;
 const DEFAULT_BUFFER_SIZE: usize = 8192;
 const SECURITY_LOG_PREFIX: &str = "SECURITY-";
 const MAX_CORRELATION_ID_LENGTH: usize = 64;
+const FLIGHT_RECORDER_CAPACITY: usize = 2048;
+const MAX_FLIGHT_RECORD_LENGTH: usize = 2048;
+
+static FLIGHT_RECORDER: Lazy<FlightRecorder> = Lazy::new(|| FlightRecorder::new(FLIGHT_RECORDER_CAPACITY));
 
 /// Log levels supported by the system
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -93,6 +100,11 @@ impl LogConfig {
         self
     }
 
+    /// Returns the file security audit events are written to
+    pub fn security_audit_path(&self) -> &str {
+        &self.security_audit_path
+    }
+
     /// Configures performance-related logging settings
     pub fn with_performance_settings(mut self, buffer_size: usize, enable_metrics: bool) -> Self {
         self.buffer_size = buffer_size;
@@ -113,6 +125,89 @@ struct SecurityContext {
     additional_context: HashMap<String, String>,
 }
 
+/// Log event kept in memory by the flight recorder
+#[derive(Debug, Clone, Serialize)]
+pub struct FlightRecord {
+    pub timestamp: OffsetDateTime,
+    pub level: String,
+    pub target: String,
+    pub message: String,
+    pub correlation_id: Option<Uuid>,
+}
+
+/// Bounded in-memory ring of the most recent log events, kept regardless of file log level
+#[derive(Debug)]
+pub struct FlightRecorder {
+    records: Mutex<VecDeque<FlightRecord>>,
+    capacity: usize,
+}
+
+impl FlightRecorder {
+    fn new(capacity: usize) -> Self {
+        Self {
+            records: Mutex::new(VecDeque::with_capacity(capacity)),
+            capacity,
+        }
+    }
+
+    fn record(&self, record: FlightRecord) {
+        let mut records = self.records.lock();
+        if records.len() >= self.capacity {
+            records.pop_front();
+        }
+        records.push_back(record);
+    }
+
+    /// Returns the recorded events, oldest first
+    pub fn snapshot(&self) -> Vec<FlightRecord> {
+        self.records.lock().iter().cloned().collect()
+    }
+}
+
+/// Returns the process-wide flight recorder
+pub fn flight_recorder() -> &'static FlightRecorder {
+    &FLIGHT_RECORDER
+}
+
+/// Tracing layer feeding every event into the flight recorder
+struct FlightRecorderLayer;
+
+impl<S: Subscriber> Layer<S> for FlightRecorderLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = FlightRecordVisitor::default();
+        event.record(&mut visitor);
+
+        let metadata = event.metadata();
+        let mut message = visitor.message;
+        message.push_str(&visitor.fields);
+        message.truncate(MAX_FLIGHT_RECORD_LENGTH);
+
+        FLIGHT_RECORDER.record(FlightRecord {
+            timestamp: OffsetDateTime::now_utc(),
+            level: metadata.level().to_string(),
+            target: metadata.target().to_string(),
+            message: sanitize_log_data(&message, &HashMap::new()),
+            correlation_id: crate::utils::correlation::current(),
+        });
+    }
+}
+
+#[derive(Default)]
+struct FlightRecordVisitor {
+    message: String,
+    fields: String,
+}
+
+impl Visit for FlightRecordVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{:?}", value);
+        } else {
+            let _ = write!(self.fields, " {}={:?}", field.name(), value);
+        }
+    }
+}
+
 /// Initializes the logging subsystem with enhanced security and performance features
 pub fn init_logging(config: LogConfig) -> Result<(), GuardianError> {
     // Validate paths and create directories if needed
@@ -123,7 +218,7 @@ pub fn init_logging(config: LogConfig) -> Result<(), GuardianError> {
             source: Some(Box::new(e)),
             severity: crate::utils::error::ErrorSeverity::High,
             timestamp: OffsetDateTime::now_utc(),
-            correlation_id: Uuid::new_v4(),
+            correlation_id: crate::utils::correlation::current_or_new(),
             category: ErrorCategory::System,
             retry_count: 0,
         })?;
@@ -166,7 +261,8 @@ pub fn init_logging(config: LogConfig) -> Result<(), GuardianError> {
                 .with_filter(|metadata: &Metadata| {
                     metadata.target().starts_with(SECURITY_LOG_PREFIX)
                 })
-        );
+        )
+        .with(FlightRecorderLayer);
 
     // Initialize the subscriber
     subscriber.init();
@@ -265,7 +361,7 @@ mod tests {
         setup();
 
         let context = SecurityContext {
-            correlation_id: Uuid::new_v4(),
+            correlation_id: crate::utils::correlation::current_or_new(),
             timestamp: OffsetDateTime::now_utc(),
             severity: "HIGH".to_string(),
             source: "test".to_string(),
@@ -287,4 +383,21 @@ mod tests {
         let sanitized = sanitize_log_data(input, &rules);
         assert!(!sanitized.contains("secret123"));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_flight_recorder_keeps_most_recent() {
+        let recorder = FlightRecorder::new(2);
+        for message in ["first", "second", "third"] {
+            recorder.record(FlightRecord {
+                timestamp: OffsetDateTime::now_utc(),
+                level: "INFO".into(),
+                target: "test".into(),
+                message: message.into(),
+                correlation_id: None,
+            });
+        }
+
+        let messages: Vec<_> = recorder.snapshot().into_iter().map(|r| r.message).collect();
+        assert_eq!(messages, vec!["second", "third"]);
+    }
+}
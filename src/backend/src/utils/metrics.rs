@@ -2,14 +2,24 @@ use metrics::{counter, gauge, histogram, Key, KeyName, Unit};
 use metrics_exporter_statsd::{StatsdClient, StatsdError};
 use ring_buffer::{RingBuffer, RingBufferWrite};
 use serde::{Deserialize, Serialize};
+use crossbeam_queue::ArrayQueue;
 use std::{
+    cell::RefCell,
     collections::HashMap,
-    sync::{Arc, Mutex},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, Weak,
+    },
     time::{Duration, Instant},
 };
 use tokio::time;
+use tracing::warn;
 
 use crate::error::GuardianError;
+use crate::core::capabilities::{capabilities, Capability};
+use super::admission::{admission, AdmissionPriority, WorkSource};
+use super::metrics_stream::{metrics_hub, MetricSample};
+use super::queue_metrics::{queue_registry, QueueMonitor};
 
 // Core constants for metrics configuration
 const METRICS_BUFFER_SIZE: usize = 1000;
@@ -17,6 +27,15 @@ const FLUSH_INTERVAL: Duration = Duration::from_secs(60);
 const STATSD_PREFIX: &str = "guardian";
 const MAX_RETRY_ATTEMPTS: u32 = 3;
 const CIRCUIT_BREAKER_THRESHOLD: u32 = 5;
+const PRIORITY_LEVELS: usize = 4;
+
+static NEXT_COLLECTOR_ID: AtomicU64 = AtomicU64::new(1);
+
+thread_local! {
+    /// Buffers of the current thread, one per collector it has recorded into; the collector
+    /// owns them, so a dropped collector leaves only a dead entry, pruned on the next record
+    static THREAD_BUFFERS: RefCell<Vec<(u64, Weak<ThreadBuffer>)>> = RefCell::new(Vec::new());
+}
 
 /// Supported metric types
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
@@ -35,6 +54,17 @@ pub enum MetricPriority {
     Low,
 }
 
+impl From<MetricPriority> for AdmissionPriority {
+    fn from(priority: MetricPriority) -> Self {
+        match priority {
+            MetricPriority::Critical => AdmissionPriority::Critical,
+            MetricPriority::High => AdmissionPriority::High,
+            MetricPriority::Medium => AdmissionPriority::Medium,
+            MetricPriority::Low => AdmissionPriority::Low,
+        }
+    }
+}
+
 /// Configuration for metrics collection
 #[derive(Debug, Clone, Deserialize)]
 pub struct MetricsConfig {
@@ -56,6 +86,186 @@ struct Metric {
     tags: HashMap<String, String>,
 }
 
+/// Lock-free metric buffer written only by its owning thread
+#[derive(Debug)]
+struct ThreadBuffer {
+    queues: [ArrayQueue<Metric>; PRIORITY_LEVELS],
+    dropped: AtomicU64,
+}
+
+impl ThreadBuffer {
+    fn new(capacity: usize) -> Self {
+        Self {
+            queues: std::array::from_fn(|_| ArrayQueue::new(capacity.max(1))),
+            dropped: AtomicU64::new(0),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.queues.iter().all(ArrayQueue::is_empty)
+    }
+}
+
+/// Thread buffers of a collector, aggregated by the background drainer
+#[derive(Debug)]
+struct MetricsBuffers {
+    collector_id: u64,
+    capacity: usize,
+    threads: parking_lot::RwLock<Vec<Arc<ThreadBuffer>>>,
+    /// Metrics recorded while the recording thread's buffers were already torn down
+    orphaned: AtomicU64,
+    queue: Arc<QueueMonitor>,
+    /// No pending metric is older than the last drain of every priority
+    last_full_drain: parking_lot::Mutex<Instant>,
+}
+
+impl MetricsBuffers {
+    fn new(capacity: usize, flush_interval: Duration) -> Self {
+        Self {
+            collector_id: NEXT_COLLECTOR_ID.fetch_add(1, Ordering::Relaxed),
+            capacity,
+            threads: parking_lot::RwLock::new(Vec::new()),
+            orphaned: AtomicU64::new(0),
+            // Thread buffers are added on demand, so the total capacity is unbounded
+            queue: queue_registry().register(QueueMonitor::new("metrics_buffer", 0).batched(flush_interval)),
+            last_full_drain: parking_lot::Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Pushes a metric into the calling thread's buffer without taking a lock
+    fn push(&self, queue_idx: usize, metric: Metric) -> bool {
+        let pushed = THREAD_BUFFERS.try_with(|buffers| {
+            let buffer = self.local_buffer(&mut buffers.borrow_mut());
+            match buffer.queues[queue_idx].push(metric) {
+                Ok(()) => {
+                    self.queue.record_enqueue();
+                    true
+                }
+                Err(_) => {
+                    buffer.dropped.fetch_add(1, Ordering::Relaxed);
+                    false
+                }
+            }
+        });
+        // Thread-local storage is gone while the thread is exiting
+        pushed.unwrap_or_else(|_| {
+            self.orphaned.fetch_add(1, Ordering::Relaxed);
+            false
+        })
+    }
+
+    fn local_buffer(&self, buffers: &mut Vec<(u64, Weak<ThreadBuffer>)>) -> Arc<ThreadBuffer> {
+        if let Some(buffer) = buffers
+            .iter()
+            .find(|(id, _)| *id == self.collector_id)
+            .and_then(|(_, buffer)| buffer.upgrade())
+        {
+            return buffer;
+        }
+
+        // First record from this thread, the only time the recording path takes a lock
+        let buffer = Arc::new(ThreadBuffer::new(self.capacity));
+        self.threads.write().push(Arc::clone(&buffer));
+        // Entries of dropped collectors go with it
+        buffers.retain(|(_, buffer)| buffer.strong_count() > 0);
+        buffers.push((self.collector_id, Arc::downgrade(&buffer)));
+        buffer
+    }
+
+    /// Drains the given priority queues of every thread buffer
+    fn drain(&self, queue_indices: &[usize]) -> Vec<Metric> {
+        let drain_start = Instant::now();
+        let mut drained = Vec::new();
+        let mut dropped = 0;
+        let mut remaining = 0;
+        let mut has_exited_threads = false;
+
+        for buffer in self.threads.read().iter() {
+            for &idx in queue_indices {
+                while let Some(metric) = buffer.queues[idx].pop() {
+                    drained.push(metric);
+                }
+            }
+            remaining += buffer.queues.iter().map(ArrayQueue::len).sum::<usize>();
+            dropped += buffer.dropped.swap(0, Ordering::Relaxed);
+            has_exited_threads |= Arc::weak_count(buffer) == 0;
+        }
+        dropped += self.orphaned.swap(0, Ordering::Relaxed);
+
+        if let Some(oldest) = drained.iter().map(|m| m.timestamp).min() {
+            self.queue.record_wait(drain_start.saturating_duration_since(oldest));
+        }
+        let oldest_pending = {
+            let mut last_full_drain = self.last_full_drain.lock();
+            if queue_indices.len() == PRIORITY_LEVELS {
+                *last_full_drain = drain_start;
+            }
+            *last_full_drain
+        };
+        self.queue.observe(remaining, (remaining > 0).then_some(oldest_pending));
+
+        if dropped > 0 {
+            counter!("guardian.metrics.buffer.dropped", dropped);
+        }
+        if has_exited_threads {
+            // Buffers of exited threads are only referenced here once drained
+            self.threads.write().retain(|b| Arc::weak_count(b) > 0 || !b.is_empty());
+        }
+        drained
+    }
+}
+
+impl Drop for MetricsBuffers {
+    /// Releases every thread's buffer, reporting the metrics that were never drained
+    fn drop(&mut self) {
+        let threads = std::mem::take(self.threads.get_mut());
+        let lost: u64 = threads
+            .iter()
+            .map(|b| {
+                let pending: usize = b.queues.iter().map(ArrayQueue::len).sum();
+                pending as u64 + b.dropped.load(Ordering::Relaxed)
+            })
+            .sum::<u64>()
+            + self.orphaned.load(Ordering::Relaxed);
+        if lost > 0 {
+            counter!("guardian.metrics.buffer.dropped", lost);
+            warn!(collector = self.collector_id, dropped = lost, "Metrics collector dropped with undrained metrics");
+        }
+    }
+}
+
+/// Merges counters sharing a name and tag set and keeps the latest gauge value
+fn aggregate_metrics(metrics: Vec<Metric>) -> Vec<Metric> {
+    let mut aggregated: Vec<Metric> = Vec::with_capacity(metrics.len());
+    let mut index: HashMap<(String, Vec<(String, String)>, bool), usize> = HashMap::new();
+
+    for metric in metrics {
+        let is_counter = match metric.metric_type {
+            MetricType::Counter => true,
+            MetricType::Gauge => false,
+            MetricType::Histogram => {
+                aggregated.push(metric);
+                continue;
+            }
+        };
+
+        let mut tags: Vec<(String, String)> = metric.tags.clone().into_iter().collect();
+        tags.sort();
+        let key = (metric.name.clone(), tags, is_counter);
+
+        match index.get(&key) {
+            Some(&i) if is_counter => aggregated[i].value += metric.value,
+            Some(&i) if metric.timestamp >= aggregated[i].timestamp => aggregated[i] = metric,
+            Some(_) => {}
+            None => {
+                index.insert(key, aggregated.len());
+                aggregated.push(metric);
+            }
+        }
+    }
+    aggregated
+}
+
 /// Circuit breaker for StatsD connection
 #[derive(Debug)]
 struct CircuitBreaker {
@@ -78,7 +288,7 @@ pub struct MetricsCollector {
     statsd_client: StatsdClient,
     last_flush: Arc<Mutex<Instant>>,
     config: MetricsConfig,
-    priority_queues: Vec<Arc<Mutex<Vec<Metric>>>>,
+    buffers: Arc<MetricsBuffers>,
     circuit_breaker: Arc<Mutex<CircuitBreaker>>,
 }
 
@@ -95,17 +305,13 @@ impl MetricsCollector {
             source: Some(Box::new(e)),
         })?;
 
+        let flush_interval = config.flush_interval.unwrap_or(FLUSH_INTERVAL);
         let collector = Self {
             ring_buffer: Arc::new(Mutex::new(RingBuffer::new(buffer_size))),
             statsd_client,
             last_flush: Arc::new(Mutex::new(Instant::now())),
             config,
-            priority_queues: vec![
-                Arc::new(Mutex::new(Vec::new())), // Critical
-                Arc::new(Mutex::new(Vec::new())), // High
-                Arc::new(Mutex::new(Vec::new())), // Medium
-                Arc::new(Mutex::new(Vec::new())), // Low
-            ],
+            buffers: Arc::new(MetricsBuffers::new(buffer_size, flush_interval)),
             circuit_breaker: Arc::new(Mutex::new(CircuitBreaker {
                 failures: 0,
                 last_failure: Instant::now(),
@@ -113,24 +319,28 @@ impl MetricsCollector {
             })),
         };
 
-        // Start background flush task
+        // Start background flush task, which stops once it holds the last handle to the collector
         let collector_clone = collector.clone();
         tokio::spawn(async move {
             let interval = collector_clone.config.flush_interval.unwrap_or(FLUSH_INTERVAL);
             let mut interval_timer = time::interval(interval);
             loop {
                 interval_timer.tick().await;
+                let last_handle = Arc::strong_count(&collector_clone.buffers) == 1;
                 if let Err(e) = collector_clone.flush_metrics().await {
                     counter!("guardian.metrics.flush.errors", 1);
                     eprintln!("Error flushing metrics: {:?}", e);
                 }
+                if last_handle {
+                    return;
+                }
             }
         });
 
         Ok(collector)
     }
 
-    /// Records a single metric with priority and sampling
+    /// Records a single metric with priority and sampling, without locking on the hot path
     pub fn record_metric(
         &self,
         name: String,
@@ -139,6 +349,11 @@ impl MetricsCollector {
         priority: MetricPriority,
         tags: Option<HashMap<String, String>>,
     ) -> Result<(), GuardianError> {
+        // Metrics export is unavailable while performance metrics are disabled
+        if !capabilities().is_enabled(Capability::Metrics) {
+            return Ok(());
+        }
+
         // Apply sampling based on priority
         let sampling_rates = self.config.sampling_rates.as_ref()
             .unwrap_or(&HashMap::new());
@@ -148,6 +363,10 @@ impl MetricsCollector {
             return Ok(());
         }
 
+        if !admission().admit(WorkSource::Metric, priority.into()) {
+            return Ok(());
+        }
+
         let metric = Metric {
             name,
             value,
@@ -165,18 +384,9 @@ impl MetricsCollector {
             MetricPriority::Low => 3,
         };
 
-        let mut queue = self.priority_queues[queue_idx].lock().map_err(|e| GuardianError::MetricsError {
-            context: "Failed to lock priority queue".into(),
-            source: Some(Box::new(e)),
-        })?;
-
-        queue.push(metric);
-
-        // Check buffer pressure
-        if queue.len() >= self.config.buffer_size.unwrap_or(METRICS_BUFFER_SIZE) {
-            counter!("guardian.metrics.buffer.pressure", 1);
-            self.flush_metrics().await?;
-        }
+        // A full buffer drops the metric rather than blocking the caller;
+        // drops are reported by the drainer
+        self.buffers.push(queue_idx, metric);
 
         Ok(())
     }
@@ -193,14 +403,7 @@ impl MetricsCollector {
             Some(MetricPriority::Low) | None => vec![0, 1, 2, 3],
         };
 
-        for idx in queue_indices {
-            let mut queue = self.priority_queues[idx].lock().map_err(|e| GuardianError::MetricsError {
-                context: "Failed to lock priority queue".into(),
-                source: Some(Box::new(e)),
-            })?;
-
-            collected.extend(queue.drain(..));
-        }
+        collected.extend(self.buffers.drain(&queue_indices));
 
         Ok(collected)
     }
@@ -219,12 +422,23 @@ impl MetricsCollector {
             });
         }
 
-        let metrics = self.collect_metrics(None).await?;
+        let metrics = aggregate_metrics(self.collect_metrics(None).await?);
         if metrics.is_empty() {
             return Ok(());
         }
 
+        // Streamed collectors get the snapshot even when StatsD is unreachable
+        metrics_hub().publish(metrics.iter().map(|m| MetricSample {
+            name: m.name.clone(),
+            metric_type: m.metric_type,
+            value: m.value,
+            tags: m.tags.iter().map(|(k, v)| (k.clone(), v.clone())).collect(),
+        }).collect());
+
         for metric in metrics {
+            #[cfg(feature = "prometheus")]
+            super::prometheus::mirror(&metric.name, metric.metric_type, metric.value, &metric.tags);
+
             let key = Key::from_parts(metric.name, metric.tags);
             match metric.metric_type {
                 MetricType::Counter => self.statsd_client.increment(&key),
@@ -250,7 +464,7 @@ impl Clone for MetricsCollector {
             statsd_client: self.statsd_client.clone(),
             last_flush: Arc::clone(&self.last_flush),
             config: self.config.clone(),
-            priority_queues: self.priority_queues.clone(),
+            buffers: Arc::clone(&self.buffers),
             circuit_breaker: Arc::clone(&self.circuit_breaker),
         }
     }
@@ -284,4 +498,69 @@ mod tests {
         let metrics = collector.collect_metrics(None).await.unwrap();
         assert_eq!(metrics.len(), 1);
     }
+
+    #[tokio::test]
+    async fn test_thread_local_buffers_are_drained_and_aggregated() {
+        let config = MetricsConfig {
+            statsd_host: "localhost".into(),
+            statsd_port: 8125,
+            buffer_size: Some(1000),
+            flush_interval: Some(Duration::from_secs(60)),
+            sampling_rates: None,
+        };
+        let collector = MetricsCollector::new(config).unwrap();
+
+        std::thread::scope(|scope| {
+            for _ in 0..4 {
+                scope.spawn(|| {
+                    for _ in 0..500 {
+                        collector.record_metric(
+                            "test.events".into(),
+                            1.0,
+                            MetricType::Counter,
+                            MetricPriority::Medium,
+                            None,
+                        ).unwrap();
+                    }
+                });
+            }
+        });
+
+        let metrics = collector.collect_metrics(None).await.unwrap();
+        assert_eq!(metrics.len(), 2000);
+
+        let aggregated = aggregate_metrics(metrics);
+        assert_eq!(aggregated.len(), 1);
+        assert_eq!(aggregated[0].value, 2000.0);
+
+        // Buffers of exited threads are released once drained
+        assert!(collector.collect_metrics(None).await.unwrap().is_empty());
+        assert!(collector.buffers.threads.read().is_empty());
+    }
+
+    #[test]
+    fn test_dropped_collector_releases_thread_buffers() {
+        let metric = || Metric {
+            name: "test.events".into(),
+            value: 1.0,
+            metric_type: MetricType::Counter,
+            priority: MetricPriority::Low,
+            timestamp: Instant::now(),
+            tags: HashMap::new(),
+        };
+        let first = MetricsBuffers::new(10, Duration::from_secs(60));
+        assert!(first.push(3, metric()));
+        let buffer = Arc::downgrade(&first.threads.read()[0]);
+        drop(first);
+        assert!(buffer.upgrade().is_none());
+
+        // The next collector recording on this thread prunes the dead entry
+        let second = MetricsBuffers::new(10, Duration::from_secs(60));
+        assert!(second.push(3, metric()));
+        THREAD_BUFFERS.with(|buffers| {
+            let buffers = buffers.borrow();
+            assert!(buffers.iter().all(|(_, buffer)| buffer.strong_count() > 0));
+            assert!(buffers.iter().any(|(id, _)| *id == second.collector_id));
+        });
+    }
 }
\ No newline at end of file
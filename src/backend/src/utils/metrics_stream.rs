@@ -0,0 +1,336 @@
+//! Aggregated metric snapshots streamed to external collectors
+//!
+//! Every metrics flush publishes its aggregated values as a numbered snapshot. Subscribers pull
+//! snapshots under a credit window the client replenishes, so a slow collector is never sent
+//! more than it asked for; the hub keeps only a bounded history, and snapshots a subscriber
+//! falls behind on are skipped and counted instead of buffered. Resumption tokens name the last
+//! snapshot delivered, letting a reconnecting collector continue where it left off while that
+//! snapshot is still retained.
+
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use metrics::{counter, gauge};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::Serialize;
+use tokio::sync::{watch, Notify};
+
+use super::ids::{next_id, IdKind};
+use super::metrics::MetricType;
+
+/// Snapshots kept for resuming subscribers
+const SNAPSHOT_HISTORY: usize = 64;
+/// Snapshots a subscriber may receive before its first window update
+pub const DEFAULT_WINDOW: u32 = 4;
+/// Largest window a subscriber may hold, bounding what a single grant can release
+pub const MAX_WINDOW: u32 = 1024;
+pub const DEFAULT_HEARTBEAT: Duration = Duration::from_secs(15);
+pub const MIN_HEARTBEAT: Duration = Duration::from_secs(1);
+
+static HUB: Lazy<Arc<MetricsHub>> = Lazy::new(|| Arc::new(MetricsHub::new(SNAPSHOT_HISTORY)));
+
+/// Returns the process-wide snapshot hub
+pub fn metrics_hub() -> Arc<MetricsHub> {
+    Arc::clone(&HUB)
+}
+
+/// One aggregated metric value
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MetricSample {
+    pub name: String,
+    pub metric_type: MetricType,
+    pub value: f64,
+    pub tags: BTreeMap<String, String>,
+}
+
+/// Metrics aggregated by one flush
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MetricsSnapshot {
+    pub sequence: u64,
+    pub collected_at: DateTime<Utc>,
+    pub samples: Vec<MetricSample>,
+}
+
+/// What a subscriber sends next
+#[derive(Debug, Clone, PartialEq)]
+pub enum MetricsFrame {
+    Snapshot {
+        snapshot: MetricsSnapshot,
+        /// Snapshots dropped since the previous one because the subscriber fell behind
+        skipped: u64,
+        resume_token: String,
+    },
+    /// Sent when nothing else was for a heartbeat interval; does not use window credit
+    Heartbeat { window: u32, resume_token: String },
+}
+
+/// Credits a subscriber's client has granted but the server has not used yet
+#[derive(Debug)]
+struct FlowWindow {
+    credits: AtomicU32,
+    granted: Notify,
+}
+
+impl FlowWindow {
+    fn take(&self) -> bool {
+        self.credits
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |c| c.checked_sub(1))
+            .is_ok()
+    }
+}
+
+/// Publishes snapshots and tracks the flow window of each subscriber
+#[derive(Debug)]
+pub struct MetricsHub {
+    /// Distinguishes this process's sequence numbers from those of an earlier run
+    epoch: u64,
+    capacity: usize,
+    history: Mutex<VecDeque<Arc<MetricsSnapshot>>>,
+    latest: watch::Sender<u64>,
+    windows: Mutex<HashMap<String, Arc<FlowWindow>>>,
+}
+
+impl MetricsHub {
+    pub fn new(capacity: usize) -> Self {
+        let (latest, _) = watch::channel(0);
+        Self {
+            epoch: Utc::now().timestamp_millis() as u64,
+            capacity: capacity.max(1),
+            history: Mutex::new(VecDeque::new()),
+            latest,
+            windows: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Numbers and retains an aggregated flush, waking subscribers with credit
+    pub fn publish(&self, samples: Vec<MetricSample>) -> u64 {
+        let sequence = {
+            let mut history = self.history.lock();
+            let sequence = history.back().map_or(1, |s| s.sequence + 1);
+            history.push_back(Arc::new(MetricsSnapshot { sequence, collected_at: Utc::now(), samples }));
+            while history.len() > self.capacity {
+                history.pop_front();
+            }
+            sequence
+        };
+        self.latest.send_replace(sequence);
+        sequence
+    }
+
+    /// Opens a subscription with `window` credits, resuming after the snapshot a token names
+    ///
+    /// A token from another process run, or naming a snapshot no longer retained, starts the
+    /// subscription at the oldest retained snapshot; `MetricsSubscription::resumed_with_gap`
+    /// tells the client that snapshots were lost.
+    pub fn subscribe(
+        self: &Arc<Self>,
+        prefixes: Vec<String>,
+        window: u32,
+        resume_token: Option<&str>,
+    ) -> MetricsSubscription {
+        let latest = *self.latest.borrow();
+        let oldest = self.history.lock().front().map_or(latest + 1, |s| s.sequence);
+        let (cursor, gap) = match resume_token.map(|t| self.parse_token(t)) {
+            None => (latest + 1, false),
+            Some(Some(delivered)) if delivered + 1 >= oldest => (delivered + 1, false),
+            Some(_) => (oldest, true),
+        };
+
+        let id = next_id(IdKind::Event).to_string();
+        let flow = Arc::new(FlowWindow {
+            credits: AtomicU32::new(window.min(MAX_WINDOW)),
+            granted: Notify::new(),
+        });
+        let subscribers = {
+            let mut windows = self.windows.lock();
+            windows.insert(id.clone(), Arc::clone(&flow));
+            windows.len()
+        };
+        gauge!("guardian.metrics_stream.subscribers", subscribers as f64);
+        if gap {
+            counter!("guardian.metrics_stream.resume_gaps", 1);
+        }
+
+        MetricsSubscription {
+            id,
+            hub: Arc::clone(self),
+            flow,
+            prefixes,
+            cursor,
+            gap,
+            latest: self.latest.subscribe(),
+        }
+    }
+
+    /// Adds credits to a subscriber's window, returning the new window
+    pub fn grant(&self, subscription_id: &str, credits: u32) -> Option<u32> {
+        let flow = self.windows.lock().get(subscription_id).cloned()?;
+        let window = flow
+            .credits
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |c| Some(c.saturating_add(credits).min(MAX_WINDOW)))
+            .map_or(0, |previous| previous.saturating_add(credits).min(MAX_WINDOW));
+        flow.granted.notify_one();
+        Some(window)
+    }
+
+    fn token(&self, delivered: u64) -> String {
+        format!("{:x}-{}", self.epoch, delivered)
+    }
+
+    fn parse_token(&self, token: &str) -> Option<u64> {
+        let (epoch, delivered) = token.split_once('-')?;
+        (u64::from_str_radix(epoch, 16).ok()? == self.epoch).then_some(())?;
+        delivered.parse().ok()
+    }
+
+    /// First retained snapshot numbered `cursor` or later, with the count of skipped ones
+    fn next_after(&self, cursor: u64) -> Option<(Arc<MetricsSnapshot>, u64)> {
+        let history = self.history.lock();
+        let snapshot = history.iter().find(|s| s.sequence >= cursor)?;
+        Some((Arc::clone(snapshot), snapshot.sequence - cursor))
+    }
+}
+
+/// A collector's position in the snapshot stream
+#[derive(Debug)]
+pub struct MetricsSubscription {
+    id: String,
+    hub: Arc<MetricsHub>,
+    flow: Arc<FlowWindow>,
+    /// Metric name prefixes to keep; empty keeps every metric
+    prefixes: Vec<String>,
+    /// Sequence of the next snapshot wanted
+    cursor: u64,
+    gap: bool,
+    latest: watch::Receiver<u64>,
+}
+
+impl MetricsSubscription {
+    /// Identifies the subscription in window updates
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Whether a resumption token named a snapshot that was no longer retained
+    pub fn resumed_with_gap(&self) -> bool {
+        self.gap
+    }
+
+    pub fn window(&self) -> u32 {
+        self.flow.credits.load(Ordering::Acquire)
+    }
+
+    /// Waits for the next snapshot the window allows, or a heartbeat after `heartbeat` of silence
+    pub async fn next(&mut self, heartbeat: Duration) -> MetricsFrame {
+        let deadline = tokio::time::Instant::now() + heartbeat;
+        let mut skipped = 0;
+        loop {
+            if let Some((snapshot, behind)) = self.hub.next_after(self.cursor) {
+                let samples = self.filter(&snapshot.samples);
+                if samples.is_empty() {
+                    // Nothing this collector asked for; move on without using credit
+                    self.cursor = snapshot.sequence + 1;
+                    skipped += behind;
+                    continue;
+                }
+                if self.flow.take() {
+                    self.cursor = snapshot.sequence + 1;
+                    skipped += behind;
+                    if skipped > 0 {
+                        counter!("guardian.metrics_stream.skipped", skipped);
+                    }
+                    return MetricsFrame::Snapshot {
+                        snapshot: MetricsSnapshot { samples, ..(*snapshot).clone() },
+                        skipped,
+                        resume_token: self.hub.token(snapshot.sequence),
+                    };
+                }
+            }
+
+            tokio::select! {
+                _ = self.latest.changed() => {}
+                _ = self.flow.granted.notified() => {}
+                _ = tokio::time::sleep_until(deadline) => {
+                    return MetricsFrame::Heartbeat {
+                        window: self.window(),
+                        resume_token: self.hub.token(self.cursor.saturating_sub(1)),
+                    };
+                }
+            }
+        }
+    }
+
+    fn filter(&self, samples: &[MetricSample]) -> Vec<MetricSample> {
+        samples
+            .iter()
+            .filter(|s| self.prefixes.is_empty() || self.prefixes.iter().any(|p| s.name.starts_with(p.as_str())))
+            .cloned()
+            .collect()
+    }
+}
+
+impl Drop for MetricsSubscription {
+    fn drop(&mut self) {
+        let subscribers = {
+            let mut windows = self.hub.windows.lock();
+            windows.remove(&self.id);
+            windows.len()
+        };
+        gauge!("guardian.metrics_stream.subscribers", subscribers as f64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(name: &str, value: f64) -> Vec<MetricSample> {
+        vec![MetricSample {
+            name: name.to_string(),
+            metric_type: MetricType::Gauge,
+            value,
+            tags: BTreeMap::new(),
+        }]
+    }
+
+    #[tokio::test]
+    async fn test_window_and_resumption() {
+        let hub = Arc::new(MetricsHub::new(2));
+        let heartbeat = Duration::from_millis(20);
+        let mut subscription = hub.subscribe(vec!["guardian.".into()], 1, None);
+
+        hub.publish(sample("guardian.cpu", 0.5));
+        let token = match subscription.next(heartbeat).await {
+            MetricsFrame::Snapshot { snapshot, skipped, resume_token } => {
+                assert_eq!((snapshot.sequence, skipped), (1, 0));
+                resume_token
+            }
+            frame => panic!("unexpected frame {:?}", frame),
+        };
+
+        // Without credit the collector only gets heartbeats while snapshots pile up
+        for value in [0.6, 0.7, 0.8] {
+            hub.publish(sample("guardian.cpu", value));
+        }
+        assert!(matches!(subscription.next(heartbeat).await, MetricsFrame::Heartbeat { window: 0, .. }));
+        assert_eq!(hub.grant(subscription.id(), 5), Some(5));
+        match subscription.next(heartbeat).await {
+            MetricsFrame::Snapshot { snapshot, skipped, .. } => assert_eq!((snapshot.sequence, skipped), (3, 1)),
+            frame => panic!("unexpected frame {:?}", frame),
+        }
+
+        // The resumed snapshot has left the history, so the gap is reported
+        let resumed = hub.subscribe(Vec::new(), 1, Some(&token));
+        assert!(resumed.resumed_with_gap());
+        let current = hub.subscribe(Vec::new(), 1, Some(&hub.token(3)));
+        assert!(!current.resumed_with_gap());
+    }
+}
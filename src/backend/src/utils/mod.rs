@@ -6,15 +6,36 @@ use std::sync::Once;
 use std::time::Duration;
 
 // Re-export core types and functionality from submodules
-pub use error::{ErrorContext, GuardianError, Result};
-pub use logging::{init_logging, LogConfig};
-pub use metrics::{MetricPriority, MetricType, MetricsCollector};
+pub use admission::{admission, init_admission, AdmissionController, AdmissionPriority, PressureLevel, WorkSource};
+pub use affinity::{affinity_manager, init_affinity, thread_placements, AffinityManager, ThreadClass, ThreadPlacement};
+pub use error::{error_aggregates, ErrorAggregate, ErrorCode, ErrorContext, GuardianError, Result};
+pub use ids::{next_id, next_uuid, GuardianId, IdKind};
+pub use inflight::{inflight_registry, DrainReport, DrainStage, InflightGuard, InflightRegistry, InflightTracker};
+pub use logging::{flight_recorder, init_logging, FlightRecord, FlightRecorder, LogConfig};
+pub use retry::{init_retry_executor, retry, retry_executor, RetryExecutor, RetryPolicy};
+pub use telemetry::{TraceContextLayer, TEMPORAL_TRACE_HEADER};
+pub use metrics::{MetricPriority, MetricType, MetricsCollector, MetricsConfig};
+pub use metrics_stream::{metrics_hub, MetricSample, MetricsFrame, MetricsHub, MetricsSnapshot, MetricsSubscription};
+pub use queue_metrics::{queue_registry, QueueMonitor, QueueRegistry, QueueStats};
 pub use validation::{ValidationContext, ValidationError, ValidationResult};
 
 // Internal module declarations
+pub mod admission;
+pub mod affinity;
+#[cfg(test)]
+mod compat;
+pub mod correlation;
 mod error;
+pub mod ids;
+pub mod inflight;
 mod logging;
 mod metrics;
+pub mod metrics_stream;
+#[cfg(feature = "prometheus")]
+pub mod prometheus;
+pub mod queue_metrics;
+pub mod retry;
+pub mod telemetry;
 mod validation;
 
 // Create a prelude module for commonly used types
@@ -113,7 +134,7 @@ fn verify_resource_limits(limits: &ResourceLimits) -> Result<()> {
         source: Some(Box::new(e)),
         severity: error::ErrorSeverity::High,
         timestamp: time::OffsetDateTime::now_utc(),
-        correlation_id: uuid::Uuid::new_v4(),
+        correlation_id: crate::utils::correlation::current_or_new(),
         category: error::ErrorCategory::System,
         retry_count: 0,
     })?;
@@ -124,7 +145,7 @@ fn verify_resource_limits(limits: &ResourceLimits) -> Result<()> {
             source: None,
             severity: error::ErrorSeverity::Critical,
             timestamp: time::OffsetDateTime::now_utc(),
-            correlation_id: uuid::Uuid::new_v4(),
+            correlation_id: crate::utils::correlation::current_or_new(),
             category: error::ErrorCategory::System,
             retry_count: 0,
         });
@@ -136,7 +157,7 @@ fn verify_resource_limits(limits: &ResourceLimits) -> Result<()> {
         source: Some(Box::new(e)),
         severity: error::ErrorSeverity::High,
         timestamp: time::OffsetDateTime::now_utc(),
-        correlation_id: uuid::Uuid::new_v4(),
+        correlation_id: crate::utils::correlation::current_or_new(),
         category: error::ErrorCategory::System,
         retry_count: 0,
     })?;
@@ -147,7 +168,7 @@ fn verify_resource_limits(limits: &ResourceLimits) -> Result<()> {
             source: None,
             severity: error::ErrorSeverity::High,
             timestamp: time::OffsetDateTime::now_utc(),
-            correlation_id: uuid::Uuid::new_v4(),
+            correlation_id: crate::utils::correlation::current_or_new(),
             category: error::ErrorCategory::System,
             retry_count: 0,
         });
@@ -0,0 +1,105 @@
+use std::{collections::HashMap, net::SocketAddr};
+
+use axum::{extract::State, http::header, response::IntoResponse, routing::get, Router};
+use metrics::{counter, gauge, histogram, Label};
+use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle};
+use once_cell::sync::OnceCell;
+use tracing::{error, info};
+
+use super::metrics::MetricType;
+use crate::utils::error::GuardianError;
+
+// Constants for the Prometheus exporter
+pub const DEFAULT_PROMETHEUS_ADDR: &str = "127.0.0.1:9464";
+const METRICS_PATH: &str = "/metrics";
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+const SUBSYSTEM_LABEL: &str = "subsystem";
+const LATENCY_BUCKETS: &[f64] = &[0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+
+static HANDLE: OnceCell<PrometheusHandle> = OnceCell::new();
+
+/// Installs the Prometheus recorder and serves the scrape endpoint on `addr`
+pub async fn start_exporter(addr: SocketAddr) -> Result<(), GuardianError> {
+    let handle = install()?.clone();
+    let app = Router::new()
+        .route(METRICS_PATH, get(render))
+        .with_state(handle);
+
+    let server = axum::Server::try_bind(&addr)
+        .map_err(|e| exporter_error(format!("Failed to bind Prometheus exporter to {}", addr), Some(Box::new(e))))?
+        .serve(app.into_make_service());
+    info!(%addr, path = METRICS_PATH, "Prometheus exporter listening");
+
+    tokio::spawn(async move {
+        if let Err(e) = server.await {
+            error!(error = %e, "Prometheus exporter stopped");
+        }
+    });
+    Ok(())
+}
+
+/// Installs the global recorder once, so every `metrics` macro is exported
+pub fn install() -> Result<&'static PrometheusHandle, GuardianError> {
+    HANDLE.get_or_try_init(|| {
+        PrometheusBuilder::new()
+            .set_buckets(LATENCY_BUCKETS)
+            .and_then(|builder| builder.install_recorder())
+            .map_err(|e| exporter_error("Failed to install Prometheus recorder".into(), Some(Box::new(e))))
+    })
+}
+
+/// Re-records a collector metric through the installed recorder
+pub(crate) fn mirror(name: &str, metric_type: MetricType, value: f64, tags: &HashMap<String, String>) {
+    if HANDLE.get().is_none() {
+        return;
+    }
+    let labels = labels_for(name, tags);
+    let name = name.to_string();
+    match metric_type {
+        MetricType::Counter => counter!(name, value.max(0.0) as u64, &labels),
+        MetricType::Gauge => gauge!(name, value, &labels),
+        MetricType::Histogram => histogram!(name, value, &labels),
+    }
+}
+
+/// Converts tags to labels, deriving the subsystem from `guardian.<subsystem>.*` names
+fn labels_for(name: &str, tags: &HashMap<String, String>) -> Vec<Label> {
+    let mut labels: Vec<Label> = tags.iter().map(|(k, v)| Label::new(k.clone(), v.clone())).collect();
+    if !tags.contains_key(SUBSYSTEM_LABEL) {
+        if let Some(subsystem) = name.split('.').nth(1) {
+            labels.push(Label::new(SUBSYSTEM_LABEL, subsystem.to_string()));
+        }
+    }
+    labels.sort_by(|a, b| a.key().cmp(b.key()));
+    labels
+}
+
+async fn render(State(handle): State<PrometheusHandle>) -> impl IntoResponse {
+    ([(header::CONTENT_TYPE, CONTENT_TYPE)], handle.render())
+}
+
+fn exporter_error(context: String, source: Option<Box<dyn std::error::Error + Send + Sync>>) -> GuardianError {
+    GuardianError::SystemError {
+        context,
+        source,
+        severity: crate::utils::error::ErrorSeverity::Medium,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: crate::utils::error::ErrorCategory::System,
+        retry_count: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subsystem_label_derived_from_name() {
+        let labels = labels_for("guardian.storage.metrics.flushed", &HashMap::new());
+        assert_eq!(labels, vec![Label::new("subsystem", "storage")]);
+
+        let tags = HashMap::from([("subsystem".to_string(), "security".to_string())]);
+        assert_eq!(labels_for("guardian.security.threats", &tags), vec![Label::new("subsystem", "security")]);
+    }
+}
@@ -0,0 +1,307 @@
+use std::{
+    collections::{BTreeMap, VecDeque},
+    fmt,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Weak,
+    },
+    time::{Duration, Instant},
+};
+
+use metrics::gauge;
+use once_cell::sync::Lazy;
+use parking_lot::{Mutex, RwLock};
+use serde::Serialize;
+
+// Constants for queue instrumentation
+const MAX_TRACKED_ITEMS: usize = 65_536;
+
+type DepthProbe = Box<dyn Fn() -> Option<usize> + Send + Sync>;
+
+/// Depth and lag indicators of an internal queue
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct QueueStats {
+    pub name: String,
+    pub depth: usize,
+    /// 0 when the queue is unbounded
+    pub capacity: usize,
+    /// Items enqueued per second since the previous sample
+    pub enqueue_rate: f64,
+    /// Time the most recently consumed item spent queued
+    pub consumer_lag_ms: f64,
+    pub oldest_age_ms: f64,
+    /// Expected wait of a queue drained in periodic batches
+    pub drain_interval_ms: f64,
+}
+
+impl QueueStats {
+    /// Fraction of capacity in use
+    pub fn saturation(&self) -> f64 {
+        if self.capacity == 0 {
+            0.0
+        } else {
+            self.depth as f64 / self.capacity as f64
+        }
+    }
+
+    /// Age of the oldest item beyond what the consumer's drain interval explains
+    pub fn backlog_age_ms(&self) -> f64 {
+        (self.oldest_age_ms - self.drain_interval_ms).max(0.0)
+    }
+}
+
+#[derive(Debug)]
+struct QueueState {
+    /// Enqueue times of pending items, oldest first, for FIFO queues
+    pending: VecDeque<Instant>,
+    depth: usize,
+    oldest: Option<Instant>,
+    lag: Duration,
+    sampled_enqueued: u64,
+    sampled_at: Instant,
+}
+
+/// Instrumentation shared by a queue's producers and consumers
+pub struct QueueMonitor {
+    name: String,
+    capacity: usize,
+    fifo: bool,
+    drain_interval: Duration,
+    enqueued: AtomicU64,
+    state: Mutex<QueueState>,
+    depth_probe: Option<DepthProbe>,
+}
+
+impl fmt::Debug for QueueMonitor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("QueueMonitor")
+            .field("name", &self.name)
+            .field("capacity", &self.capacity)
+            .field("fifo", &self.fifo)
+            .finish()
+    }
+}
+
+impl QueueMonitor {
+    /// Creates a monitor for a queue that reports its own depth through `observe`
+    pub fn new(name: &str, capacity: usize) -> Self {
+        Self {
+            name: name.to_string(),
+            capacity,
+            fifo: false,
+            drain_interval: Duration::ZERO,
+            enqueued: AtomicU64::new(0),
+            state: Mutex::new(QueueState {
+                pending: VecDeque::new(),
+                depth: 0,
+                oldest: None,
+                lag: Duration::ZERO,
+                sampled_enqueued: 0,
+                sampled_at: Instant::now(),
+            }),
+            depth_probe: None,
+        }
+    }
+
+    /// Tracks enqueue times of a FIFO queue whose consumer cannot be instrumented,
+    /// treating the oldest items as consumed whenever the depth shrinks
+    pub fn fifo(mut self, depth_probe: impl Fn() -> Option<usize> + Send + Sync + 'static) -> Self {
+        self.fifo = true;
+        self.depth_probe = Some(Box::new(depth_probe));
+        self
+    }
+
+    /// Marks a queue whose consumer drains it every `interval`, so items normally wait that long
+    pub fn batched(mut self, interval: Duration) -> Self {
+        self.drain_interval = interval;
+        self
+    }
+
+    /// Counts one enqueued item
+    pub fn record_enqueue(&self) {
+        self.record_enqueue_many(1);
+    }
+
+    /// Counts a batch of enqueued items
+    pub fn record_enqueue_many(&self, count: usize) {
+        self.enqueued.fetch_add(count as u64, Ordering::Relaxed);
+        if !self.fifo {
+            return;
+        }
+
+        let now = Instant::now();
+        {
+            let mut state = self.state.lock();
+            let room = MAX_TRACKED_ITEMS.saturating_sub(state.pending.len());
+            state.pending.extend(std::iter::repeat(now).take(count.min(room)));
+        }
+        self.probe();
+    }
+
+    /// Records how long a consumed item spent queued
+    pub fn record_wait(&self, wait: Duration) {
+        self.state.lock().lag = wait;
+    }
+
+    /// Records the depth and oldest item of a queue that tracks its own enqueue times
+    pub fn observe(&self, depth: usize, oldest: Option<Instant>) {
+        let mut state = self.state.lock();
+        state.depth = depth;
+        state.oldest = oldest;
+    }
+
+    /// Samples the queue, resetting the enqueue rate window
+    pub fn stats(&self) -> QueueStats {
+        self.probe();
+        let now = Instant::now();
+        let enqueued = self.enqueued.load(Ordering::Relaxed);
+
+        let mut state = self.state.lock();
+        let elapsed = now.saturating_duration_since(state.sampled_at).as_secs_f64();
+        let enqueue_rate = if elapsed > 0.0 {
+            enqueued.saturating_sub(state.sampled_enqueued) as f64 / elapsed
+        } else {
+            0.0
+        };
+        state.sampled_enqueued = enqueued;
+        state.sampled_at = now;
+
+        QueueStats {
+            name: self.name.clone(),
+            depth: state.depth,
+            capacity: self.capacity,
+            enqueue_rate,
+            consumer_lag_ms: state.lag.as_secs_f64() * 1000.0,
+            oldest_age_ms: state
+                .oldest
+                .map_or(0.0, |oldest| now.saturating_duration_since(oldest).as_secs_f64() * 1000.0),
+            drain_interval_ms: self.drain_interval.as_secs_f64() * 1000.0,
+        }
+    }
+
+    /// Reconciles tracked enqueue times of a FIFO queue with its current depth
+    fn probe(&self) {
+        let Some(depth) = self.depth_probe.as_ref().and_then(|probe| probe()) else {
+            return;
+        };
+        let now = Instant::now();
+        let mut state = self.state.lock();
+        let mut consumed = None;
+        while state.pending.len() > depth {
+            consumed = state.pending.pop_front();
+        }
+        if let Some(enqueued_at) = consumed {
+            state.lag = now.saturating_duration_since(enqueued_at);
+        }
+        state.depth = depth;
+        state.oldest = state.pending.front().copied();
+    }
+}
+
+/// Registry of every instrumented internal queue
+#[derive(Debug, Default)]
+pub struct QueueRegistry {
+    monitors: RwLock<Vec<Weak<QueueMonitor>>>,
+}
+
+static QUEUE_REGISTRY: Lazy<Arc<QueueRegistry>> = Lazy::new(|| Arc::new(QueueRegistry::default()));
+
+/// Returns the process-wide queue registry
+pub fn queue_registry() -> Arc<QueueRegistry> {
+    Arc::clone(&QUEUE_REGISTRY)
+}
+
+impl QueueRegistry {
+    /// Registers a monitor until the returned handle is dropped
+    pub fn register(&self, monitor: QueueMonitor) -> Arc<QueueMonitor> {
+        let monitor = Arc::new(monitor);
+        self.monitors.write().push(Arc::downgrade(&monitor));
+        monitor
+    }
+
+    /// Samples every live queue, merging monitors that share a name
+    pub fn snapshot(&self) -> Vec<QueueStats> {
+        let live: Vec<Arc<QueueMonitor>> = {
+            let mut monitors = self.monitors.write();
+            monitors.retain(|m| m.strong_count() > 0);
+            monitors.iter().filter_map(Weak::upgrade).collect()
+        };
+        merge(live.iter().map(|m| m.stats()))
+    }
+
+    /// Samples every queue and exports the indicators as gauges
+    pub fn export(&self) -> Vec<QueueStats> {
+        let stats = self.snapshot();
+        for queue in &stats {
+            gauge!("guardian.queue.depth", queue.depth as f64, "queue" => queue.name.clone());
+            gauge!("guardian.queue.enqueue_rate", queue.enqueue_rate, "queue" => queue.name.clone());
+            gauge!("guardian.queue.consumer_lag_ms", queue.consumer_lag_ms, "queue" => queue.name.clone());
+            gauge!("guardian.queue.oldest_age_ms", queue.oldest_age_ms, "queue" => queue.name.clone());
+        }
+        stats
+    }
+}
+
+/// Sums depth, capacity and rate of same-named queues and keeps their worst lag and age
+fn merge(stats: impl Iterator<Item = QueueStats>) -> Vec<QueueStats> {
+    let mut merged: BTreeMap<String, QueueStats> = BTreeMap::new();
+    for queue in stats {
+        let entry = merged.entry(queue.name.clone()).or_insert_with(|| QueueStats {
+            name: queue.name.clone(),
+            ..Default::default()
+        });
+        entry.depth += queue.depth;
+        entry.capacity += queue.capacity;
+        entry.enqueue_rate += queue.enqueue_rate;
+        entry.consumer_lag_ms = entry.consumer_lag_ms.max(queue.consumer_lag_ms);
+        entry.oldest_age_ms = entry.oldest_age_ms.max(queue.oldest_age_ms);
+        entry.drain_interval_ms = entry.drain_interval_ms.max(queue.drain_interval_ms);
+    }
+    merged.into_values().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+
+    #[test]
+    fn test_fifo_monitor_infers_consumption_from_depth() {
+        let depth = Arc::new(AtomicUsize::new(0));
+        let probe_depth = Arc::clone(&depth);
+        let monitor = QueueMonitor::new("event_bus", 8).fifo(move || Some(probe_depth.load(Ordering::Relaxed)));
+
+        depth.store(3, Ordering::Relaxed);
+        monitor.record_enqueue_many(3);
+        assert_eq!(monitor.stats().depth, 3);
+        assert_eq!(monitor.state.lock().pending.len(), 3);
+
+        // Consumer drained two items
+        depth.store(1, Ordering::Relaxed);
+        let stats = monitor.stats();
+        assert_eq!(stats.depth, 1);
+        assert_eq!(monitor.state.lock().pending.len(), 1);
+        assert!(stats.oldest_age_ms >= 0.0);
+        assert!((stats.saturation() - 0.125).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_registry_merges_and_drops_released_queues() {
+        let registry = QueueRegistry::default();
+        let first = registry.register(QueueMonitor::new("event_bus", 4));
+        let second = registry.register(QueueMonitor::new("event_bus", 4));
+        let other = registry.register(QueueMonitor::new("response_queue", 10));
+
+        first.observe(1, None);
+        second.observe(3, Some(Instant::now() - Duration::from_secs(2)));
+        other.observe(5, None);
+
+        let stats = registry.snapshot();
+        assert_eq!(stats.len(), 2);
+        assert_eq!((stats[0].name.as_str(), stats[0].depth, stats[0].capacity), ("event_bus", 4, 8));
+        assert!(stats[0].oldest_age_ms >= 2000.0);
+
+        drop(other);
+        assert_eq!(registry.snapshot().len(), 1);
+    }
+}
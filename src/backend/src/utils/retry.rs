@@ -0,0 +1,289 @@
+use std::{collections::HashMap, future::Future, sync::Arc, time::Duration};
+
+use metrics::{counter, histogram};
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use tracing::{info, warn};
+
+use crate::utils::error::{ErrorCategory, GuardianError, RETRY_LIMIT};
+
+// Constants for retry backoff
+const DEFAULT_INITIAL_BACKOFF: Duration = Duration::from_millis(100);
+const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(10);
+const DEFAULT_BACKOFF_MULTIPLIER: f64 = 2.0;
+const DEFAULT_JITTER: f64 = 0.2;
+
+static RETRY_EXECUTOR: Lazy<RwLock<Arc<RetryExecutor>>> = Lazy::new(|| RwLock::new(Arc::new(RetryExecutor::default())));
+
+/// Exponential backoff policy for retrying fallible operations
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Total attempts including the first one
+    pub max_attempts: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    pub multiplier: f64,
+    /// Fraction of each delay that is randomized away; 1.0 is full jitter
+    pub jitter: f64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: RETRY_LIMIT + 1,
+            initial_backoff: DEFAULT_INITIAL_BACKOFF,
+            max_backoff: DEFAULT_MAX_BACKOFF,
+            multiplier: DEFAULT_BACKOFF_MULTIPLIER,
+            jitter: DEFAULT_JITTER,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Policy for failures of the given category
+    pub fn for_category(category: ErrorCategory) -> Self {
+        match category {
+            // Local I/O contention clears quickly
+            ErrorCategory::Storage => Self {
+                max_attempts: 5,
+                ..Default::default()
+            },
+            // Remote services (Temporal, peers) need longer, more spread out retries
+            ErrorCategory::System => Self {
+                max_attempts: 4,
+                initial_backoff: Duration::from_millis(250),
+                max_backoff: Duration::from_secs(15),
+                jitter: 0.5,
+                ..Default::default()
+            },
+            // Inference and model loading are expensive; retry sparingly
+            ErrorCategory::ML => Self {
+                max_attempts: 3,
+                initial_backoff: Duration::from_millis(500),
+                max_backoff: Duration::from_secs(30),
+                ..Default::default()
+            },
+            // Retrying a rejected credential or invalid input gives the same answer
+            ErrorCategory::Security | ErrorCategory::Validation => Self {
+                max_attempts: 1,
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Returns the delay before the given retry, starting at 1
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = self.multiplier.powi(retry.saturating_sub(1) as i32);
+        self.initial_backoff.mul_f64(factor).min(self.max_backoff)
+    }
+
+    /// Returns the backoff before the given retry with jitter applied
+    pub fn delay(&self, retry: u32) -> Duration {
+        self.backoff(retry).mul_f64(1.0 - self.jitter.clamp(0.0, 1.0) * fastrand::f64())
+    }
+}
+
+/// Retries async operations with the policy of the category each failure belongs to
+#[derive(Debug, Clone)]
+pub struct RetryExecutor {
+    policies: HashMap<ErrorCategory, RetryPolicy>,
+}
+
+impl Default for RetryExecutor {
+    fn default() -> Self {
+        let categories = [
+            ErrorCategory::System,
+            ErrorCategory::Security,
+            ErrorCategory::ML,
+            ErrorCategory::Storage,
+            ErrorCategory::Validation,
+        ];
+        Self {
+            policies: categories.into_iter().map(|c| (c, RetryPolicy::for_category(c))).collect(),
+        }
+    }
+}
+
+impl RetryExecutor {
+    /// Executor applying the same policy to every category
+    pub fn uniform(policy: RetryPolicy) -> Self {
+        let mut executor = Self::default();
+        executor.policies.values_mut().for_each(|p| *p = policy.clone());
+        executor
+    }
+
+    /// Replaces the policy of one category
+    pub fn with_policy(mut self, category: ErrorCategory, policy: RetryPolicy) -> Self {
+        self.policies.insert(category, policy);
+        self
+    }
+
+    pub fn policy(&self, category: ErrorCategory) -> RetryPolicy {
+        self.policies.get(&category).cloned().unwrap_or_else(|| RetryPolicy::for_category(category))
+    }
+
+    /// Runs an operation until it succeeds, fails with a non-retryable error or exhausts the policy of
+    /// the failure's category. Retries already recorded on an error by a nested executor count against
+    /// the budget, so layered retries do not multiply. The returned error carries the total retries
+    /// in its `retry_count`.
+    pub async fn run<T, F, Fut>(&self, operation: &str, mut f: F) -> Result<T, GuardianError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, GuardianError>>,
+    {
+        let mut retries = 0;
+
+        loop {
+            let error = match f().await {
+                Ok(value) => {
+                    if retries > 0 {
+                        info!(operation, retries, "Operation succeeded after retries");
+                        counter!("guardian.retry.recovered", 1, "operation" => operation.to_string());
+                    }
+                    histogram!("guardian.retry.retries", retries as f64, "operation" => operation.to_string());
+                    return Ok(value);
+                }
+                Err(e) => {
+                    let spent = retries + e.retry_count();
+                    e.retried(operation, spent)
+                }
+            };
+
+            let policy = self.policy(error.category());
+            if !error.is_retryable() || error.retry_count() + 1 >= policy.max_attempts {
+                warn!(operation, retries = error.retry_count(), error = %error, "Operation failed, not retrying");
+                counter!(
+                    "guardian.retry.exhausted", 1,
+                    "operation" => operation.to_string(),
+                    "category" => format!("{:?}", error.category())
+                );
+                histogram!("guardian.retry.retries", retries as f64, "operation" => operation.to_string());
+                return Err(error);
+            }
+
+            retries += 1;
+            let delay = policy.delay(error.retry_count() + 1);
+            warn!(
+                operation,
+                attempt = error.retry_count() + 2,
+                max_attempts = policy.max_attempts,
+                delay_ms = delay.as_millis() as u64,
+                error = %error,
+                "Retrying failed operation"
+            );
+            counter!(
+                "guardian.retry.attempts", 1,
+                "operation" => operation.to_string(),
+                "category" => format!("{:?}", error.category())
+            );
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
+
+/// Returns the process-wide executor
+pub fn retry_executor() -> Arc<RetryExecutor> {
+    RETRY_EXECUTOR.read().clone()
+}
+
+/// Replaces the process-wide executor, e.g. to tune one category's policy
+pub fn init_retry_executor(executor: RetryExecutor) {
+    *RETRY_EXECUTOR.write() = Arc::new(executor);
+}
+
+/// Runs an operation with one policy regardless of the failure's category.
+/// The returned error carries the number of retries performed in its `retry_count`.
+pub async fn retry<T, F, Fut>(operation: &str, policy: &RetryPolicy, f: F) -> Result<T, GuardianError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, GuardianError>>,
+{
+    RetryExecutor::uniform(policy.clone()).run(operation, f).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn storage_error() -> GuardianError {
+        GuardianError::StorageError {
+            context: "transient failure".into(),
+            source: None,
+            severity: crate::utils::error::ErrorSeverity::High,
+            timestamp: time::OffsetDateTime::now_utc(),
+            correlation_id: crate::utils::correlation::current_or_new(),
+            category: crate::utils::error::ErrorCategory::Storage,
+            retry_count: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retry_records_attempts_on_exhaustion() {
+        let policy = RetryPolicy {
+            initial_backoff: Duration::from_millis(1),
+            ..Default::default()
+        };
+        let calls = AtomicU32::new(0);
+
+        let result: Result<(), _> = retry("test_op", &policy, || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err(storage_error())
+        })
+        .await;
+
+        assert_eq!(calls.load(Ordering::SeqCst), policy.max_attempts);
+        assert_eq!(result.unwrap_err().retry_count(), policy.max_attempts - 1);
+
+        let calls = AtomicU32::new(0);
+        let result = retry("test_op", &policy, || async {
+            match calls.fetch_add(1, Ordering::SeqCst) {
+                0 => Err(storage_error()),
+                n => Ok(n),
+            }
+        })
+        .await;
+        assert_eq!(result.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_executor_uses_category_policy_and_nested_retries() {
+        let fast = |max_attempts| RetryPolicy {
+            max_attempts,
+            initial_backoff: Duration::from_millis(1),
+            ..Default::default()
+        };
+        let executor = RetryExecutor::default()
+            .with_policy(ErrorCategory::Storage, fast(3))
+            .with_policy(ErrorCategory::System, fast(10));
+        let calls = AtomicU32::new(0);
+
+        let result: Result<(), _> = executor
+            .run("test_op", || async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err(storage_error())
+            })
+            .await;
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+        assert_eq!(result.unwrap_err().retry_count(), 2);
+
+        // An error already retried twice by an inner layer leaves one attempt of a three-attempt budget
+        let calls = AtomicU32::new(0);
+        let result: Result<(), _> = executor
+            .run("outer_op", || async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err(storage_error().retried("inner_op", 2))
+            })
+            .await;
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(result.unwrap_err().retry_count(), 2);
+    }
+
+    #[test]
+    fn test_backoff_is_capped() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.backoff(1), DEFAULT_INITIAL_BACKOFF);
+        assert_eq!(policy.backoff(2), DEFAULT_INITIAL_BACKOFF * 2);
+        assert_eq!(policy.backoff(30), DEFAULT_MAX_BACKOFF);
+    }
+}
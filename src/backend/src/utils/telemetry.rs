@@ -0,0 +1,226 @@
+use std::{
+    collections::HashMap,
+    future::Future,
+    pin::Pin,
+    task::{Context as TaskContext, Poll},
+    time::Duration,
+};
+
+use http::{HeaderMap, Request, Response};
+use opentelemetry::{
+    global,
+    propagation::{Extractor, Injector},
+    Context, KeyValue,
+};
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::{
+    propagation::TraceContextPropagator,
+    runtime,
+    trace::{self as sdktrace, Sampler},
+    Resource,
+};
+use tonic::metadata::{MetadataKey, MetadataMap, MetadataValue};
+use tracing::{info, info_span, Instrument, Span};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+use crate::config::{Environment, MonitoringConfig};
+use crate::utils::error::{ErrorCategory, GuardianError};
+
+// Constants for trace export
+const SERVICE_NAME: &str = "guardian";
+const EXPORT_TIMEOUT: Duration = Duration::from_secs(5);
+/// Temporal header carrying the W3C trace context, the key Temporal's own tracing interceptors use
+pub const TEMPORAL_TRACE_HEADER: &str = "_tracer-data";
+
+/// Installs the W3C propagator and, when an OTLP endpoint is configured, a batching span exporter.
+/// Returns the tracer to attach to the subscriber with `tracing_opentelemetry::layer()`.
+pub fn init_tracer(
+    config: &MonitoringConfig,
+    environment: &Environment,
+) -> Result<Option<sdktrace::Tracer>, GuardianError> {
+    // Context is propagated even when this host exports nothing, so downstream traces stay connected
+    global::set_text_map_propagator(TraceContextPropagator::new());
+
+    let endpoint = match (&config.otlp_endpoint, config.enable_tracing) {
+        (Some(endpoint), true) => endpoint,
+        _ => return Ok(None),
+    };
+
+    let sampler = Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(
+        config.trace_sample_ratio.clamp(0.0, 1.0),
+    )));
+    let resource = Resource::new(vec![
+        KeyValue::new("service.name", SERVICE_NAME),
+        KeyValue::new("service.version", env!("CARGO_PKG_VERSION")),
+        KeyValue::new("deployment.environment", format!("{:?}", environment).to_lowercase()),
+    ]);
+
+    let tracer = opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(
+            opentelemetry_otlp::new_exporter()
+                .tonic()
+                .with_endpoint(endpoint.clone())
+                .with_timeout(EXPORT_TIMEOUT),
+        )
+        .with_trace_config(sdktrace::config().with_sampler(sampler).with_resource(resource))
+        .install_batch(runtime::Tokio)
+        .map_err(|e| telemetry_error(format!("Failed to install OTLP exporter for {}", endpoint), Some(Box::new(e))))?;
+
+    info!(%endpoint, sample_ratio = config.trace_sample_ratio, "OpenTelemetry trace export enabled");
+    Ok(Some(tracer))
+}
+
+/// Flushes spans still queued for export
+pub fn shutdown() {
+    global::shutdown_tracer_provider();
+}
+
+/// Writes the current span's trace context into outgoing gRPC metadata
+pub fn inject_metadata(metadata: &mut MetadataMap) {
+    let context = Span::current().context();
+    global::get_text_map_propagator(|propagator| propagator.inject_context(&context, &mut MetadataInjector(metadata)));
+}
+
+/// Reads the caller's trace context from request headers
+pub fn extract_headers(headers: &HeaderMap) -> Context {
+    global::get_text_map_propagator(|propagator| propagator.extract(&HeaderExtractor(headers)))
+}
+
+/// Returns Temporal headers carrying the current span's trace context to a workflow or activity
+pub fn workflow_headers() -> HashMap<String, serde_json::Value> {
+    let context = Span::current().context();
+    let mut carrier: HashMap<String, String> = HashMap::new();
+    global::get_text_map_propagator(|propagator| propagator.inject_context(&context, &mut carrier));
+
+    let mut headers = HashMap::new();
+    if !carrier.is_empty() {
+        headers.insert(TEMPORAL_TRACE_HEADER.to_string(), serde_json::json!(carrier));
+    }
+    headers
+}
+
+/// Parents the current span on the trace context of the workflow or activity that scheduled it
+pub fn follow_workflow_headers(headers: &HashMap<String, serde_json::Value>) {
+    let carrier: HashMap<String, String> = headers
+        .get(TEMPORAL_TRACE_HEADER)
+        .and_then(|value| serde_json::from_value(value.clone()).ok())
+        .unwrap_or_default();
+    if carrier.is_empty() {
+        return;
+    }
+    let parent = global::get_text_map_propagator(|propagator| propagator.extract(&carrier));
+    Span::current().set_parent(parent);
+}
+
+struct MetadataInjector<'a>(&'a mut MetadataMap);
+
+impl Injector for MetadataInjector<'_> {
+    fn set(&mut self, key: &str, value: String) {
+        if let (Ok(key), Ok(value)) = (MetadataKey::from_bytes(key.as_bytes()), MetadataValue::try_from(value)) {
+            self.0.insert(key, value);
+        }
+    }
+}
+
+struct HeaderExtractor<'a>(&'a HeaderMap);
+
+impl Extractor for HeaderExtractor<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|v| v.to_str().ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(|k| k.as_str()).collect()
+    }
+}
+
+/// Tower layer opening a server span for every request, parented on its `traceparent` header
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TraceContextLayer;
+
+impl<S> tower::Layer<S> for TraceContextLayer {
+    type Service = TraceContextService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        TraceContextService { inner }
+    }
+}
+
+/// Service created by `TraceContextLayer`
+#[derive(Debug, Clone)]
+pub struct TraceContextService<S> {
+    inner: S,
+}
+
+impl<S, ReqBody, ResBody> tower::Service<Request<ReqBody>> for TraceContextService<S>
+where
+    S: tower::Service<Request<ReqBody>, Response = Response<ResBody>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut TaskContext<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<ReqBody>) -> Self::Future {
+        let span = info_span!(
+            "request",
+            otel.name = %request.uri().path(),
+            otel.kind = "server",
+            http.method = %request.method(),
+        );
+        span.set_parent(extract_headers(request.headers()));
+
+        let future = span.in_scope(|| self.inner.call(request));
+        Box::pin(future.instrument(span))
+    }
+}
+
+fn telemetry_error(context: String, source: Option<Box<dyn std::error::Error + Send + Sync>>) -> GuardianError {
+    GuardianError::SystemError {
+        context,
+        source,
+        severity: crate::utils::error::ErrorSeverity::Medium,
+        timestamp: time::OffsetDateTime::now_utc(),
+        correlation_id: crate::utils::correlation::current_or_new(),
+        category: ErrorCategory::System,
+        retry_count: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::trace::{SpanContext, SpanId, TraceContextExt, TraceFlags, TraceId, TraceState};
+
+    #[test]
+    fn test_trace_context_round_trips_through_metadata_and_workflow_headers() {
+        global::set_text_map_propagator(TraceContextPropagator::new());
+        let span_context = SpanContext::new(
+            TraceId::from_hex("4bf92f3577b34da6a3ce929d0e0e4736").unwrap(),
+            SpanId::from_hex("00f067aa0ba902b7").unwrap(),
+            TraceFlags::SAMPLED,
+            true,
+            TraceState::default(),
+        );
+        let context = Context::new().with_remote_span_context(span_context.clone());
+
+        let mut metadata = MetadataMap::new();
+        global::get_text_map_propagator(|p| p.inject_context(&context, &mut MetadataInjector(&mut metadata)));
+        assert_eq!(
+            metadata.get("traceparent").unwrap(),
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"
+        );
+
+        let extracted = extract_headers(&metadata.into_headers());
+        assert_eq!(extracted.span().span_context().trace_id(), span_context.trace_id());
+        assert_eq!(extracted.span().span_context().span_id(), span_context.span_id());
+
+        // Without an active span there is nothing to carry into a workflow
+        assert!(workflow_headers().is_empty());
+    }
+}
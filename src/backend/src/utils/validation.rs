@@ -114,7 +114,7 @@ impl ValidationContext {
                 source: None,
                 severity: ErrorSeverity::High,
                 timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id: uuid::Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: ErrorCategory::Validation,
                 retry_count: 0,
             });
@@ -145,7 +145,7 @@ impl ValidationContext {
                 source: None,
                 severity: ErrorSeverity::Medium,
                 timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id: uuid::Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: ErrorCategory::Validation,
                 retry_count: 0,
             });
@@ -170,7 +170,7 @@ impl ValidationContext {
                 source: None,
                 severity: ErrorSeverity::Medium,
                 timestamp: time::OffsetDateTime::now_utc(),
-                correlation_id: uuid::Uuid::new_v4(),
+                correlation_id: crate::utils::correlation::current_or_new(),
                 category: ErrorCategory::Validation,
                 retry_count: 0,
             });
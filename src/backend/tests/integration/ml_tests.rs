@@ -130,6 +130,8 @@ async fn test_model_registration() -> Result<(), GuardianError> {
         validation_status: ValidationStatus::Pending,
         hash: "".to_string(),
         size_bytes: test_model_data.len() as u64,
+        signature: None,
+        provenance: None,
     };
 
     // Test model registration